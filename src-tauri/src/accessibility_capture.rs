@@ -4,10 +4,13 @@
 // Similar to audio transcription, this creates a running "visual transcript"
 // of what the user is doing by extracting text from focused windows.
 
-use crate::accessibility_extractor::AccessibilityExtractor;
+use crate::accessibility_extractor::{AccessibilityExtractor, AccessibilityResult};
+use crate::capture_metrics::MetricsCollector;
 use crate::database::DatabaseManager;
+use crate::dork_mode::{self, SharedDorkSession};
 use crate::pinecone_client::PineconeClient;
 use crate::settings::SettingsManager;
+use crate::snapshot_extractor::{ExtractionResult, ExtractionSource, SnapshotExtractor};
 use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
@@ -42,6 +45,136 @@ impl Default for AccessibilityCaptureConfig {
     }
 }
 
+/// Text from one capture attempt, from whichever path produced it
+#[derive(Debug, Clone)]
+pub struct CaptureCandidate {
+    pub text: String,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+    pub quality_score: f32,
+    pub source: ExtractionSource,
+    /// Screen state the OCR'd keyframe belongs to
+    pub state_id: Option<String>,
+}
+
+impl CaptureCandidate {
+    /// Wrap an accessibility read (None when it produced no text)
+    pub fn from_accessibility(result: AccessibilityResult) -> Option<Self> {
+        if result.text.trim().is_empty() {
+            return None;
+        }
+
+        // Quality score based on word count and accessibility status
+        let word_count = result.text.split_whitespace().count();
+        let quality_score = if result.is_accessible && word_count > 20 {
+            0.9
+        } else if result.is_accessible {
+            0.7
+        } else {
+            0.3
+        };
+
+        Some(Self {
+            text: result.text,
+            app_name: result.app_name,
+            window_title: result.window_title,
+            quality_score,
+            source: ExtractionSource::Accessibility,
+            state_id: None,
+        })
+    }
+}
+
+/// Why an accessibility read is unusable, or None if it can be saved as-is
+pub fn accessibility_verdict(
+    attempt: &Result<AccessibilityResult, String>,
+) -> Option<ExtractionResult> {
+    match attempt {
+        Err(e) => Some(ExtractionResult::Failed(e.clone())),
+        Ok(result) if result.text.trim().is_empty() => Some(ExtractionResult::Failed(
+            "No text found via accessibility".to_string(),
+        )),
+        // App doesn't expose its text tree - whatever came back is window chrome
+        Ok(result) if !result.is_accessible => Some(ExtractionResult::LowQuality(0.3)),
+        Ok(_) => None,
+    }
+}
+
+/// OCR the keyframe of the meeting's most recent screen state.
+/// Ok(None) when there's no keyframe yet or OCR found nothing usable.
+pub async fn ocr_latest_keyframe(
+    database: &DatabaseManager,
+    meeting_id: &str,
+) -> Result<Option<CaptureCandidate>, String> {
+    let state = database
+        .get_latest_screen_state(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get latest screen state: {}", e))?;
+    let Some(state) = state else {
+        return Ok(None);
+    };
+    let Some(path) = state.keyframe_path.clone().filter(|p| !p.is_empty()) else {
+        return Ok(None);
+    };
+
+    let state_id = state.state_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        SnapshotExtractor::new().extract_from_keyframe(
+            std::path::Path::new(&path),
+            None,
+            Some(&state_id),
+        )
+    })
+    .await
+    .map_err(|e| format!("OCR task failed: {}", e))?;
+
+    match result {
+        ExtractionResult::Success(snapshot) => Ok(Some(CaptureCandidate {
+            text: snapshot.text,
+            app_name: state.app_name,
+            window_title: state.window_title,
+            quality_score: snapshot.quality_score,
+            source: ExtractionSource::Ocr,
+            state_id: Some(state.state_id),
+        })),
+        ExtractionResult::Failed(e) => Err(e),
+        _ => Ok(None),
+    }
+}
+
+/// Pipe a capture that passed the snapshot gates into the active dork session
+async fn feed_dork_session(
+    session: &SharedDorkSession,
+    database: &DatabaseManager,
+    allowlist: &[String],
+    candidate: &CaptureCandidate,
+) {
+    if candidate.quality_score < dork_mode::MIN_CAPTURE_QUALITY
+        || !dork_mode::app_allowed(allowlist, candidate.app_name.as_deref())
+    {
+        return;
+    }
+    let added = session.read().as_ref().and_then(|s| {
+        s.accumulate_capture(
+            candidate.app_name.as_deref(),
+            candidate.window_title.as_deref(),
+            &candidate.text,
+        )
+        .map(|chunk| (s.session_id.clone(), chunk))
+    });
+    let Some((session_id, chunk)) = added else {
+        return;
+    };
+    match database.add_dork_content(&session_id, &chunk).await {
+        Ok(()) => log::debug!(
+            "📚 Fed {} chars from {} into dork session",
+            chunk.len(),
+            candidate.app_name.as_deref().unwrap_or("unknown")
+        ),
+        Err(e) => log::warn!("📚 Failed to save dork content: {}", e),
+    }
+}
+
 /// Statistics for accessibility capture
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccessibilityCaptureStats {
@@ -71,6 +204,8 @@ pub struct AccessibilityCaptureService {
     last_app: Arc<RwLock<Option<String>>>,
     config: Arc<RwLock<AccessibilityCaptureConfig>>,
     current_meeting_id: Arc<RwLock<Option<String>>>,
+    metrics: Arc<RwLock<Option<Arc<MetricsCollector>>>>,
+    dork_session: Arc<RwLock<Option<SharedDorkSession>>>,
 }
 
 impl AccessibilityCaptureService {
@@ -85,9 +220,21 @@ impl AccessibilityCaptureService {
             last_app: Arc::new(RwLock::new(None)),
             config: Arc::new(RwLock::new(AccessibilityCaptureConfig::default())),
             current_meeting_id: Arc::new(RwLock::new(None)),
+            metrics: Arc::new(RwLock::new(None)),
+            dork_session: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Attach the metrics collector so saves are counted per source
+    pub fn set_metrics_collector(&self, metrics: Arc<MetricsCollector>) {
+        *self.metrics.write() = Some(metrics);
+    }
+
+    /// Attach the dork session slot; captures feed it while study mode is on
+    pub fn set_dork_session(&self, session: SharedDorkSession) {
+        *self.dork_session.write() = Some(session);
+    }
+
     /// Set the current meeting ID for linking captures
     pub fn set_meeting_id(&self, meeting_id: Option<String>) {
         *self.current_meeting_id.write() = meeting_id;
@@ -153,10 +300,14 @@ impl AccessibilityCaptureService {
         let config = self.config.clone();
         let pinecone = pinecone.clone();
         let current_meeting_id = self.current_meeting_id.clone();
+        let metrics = self.metrics.clone();
+        let dork_session = self.dork_session.clone();
 
         // Spawn background task
         tokio::spawn(async move {
             let extractor = AccessibilityExtractor::new();
+            // Keyframe OCR'd last time, so an unchanged screen isn't re-read
+            let mut last_ocr_state_id: Option<String> = None;
 
             while running.load(Ordering::SeqCst) {
                 // Get current config
@@ -168,167 +319,136 @@ impl AccessibilityCaptureService {
                     continue;
                 }
 
-                // Also check settings
-                let settings_enabled = match settings.get_all().await {
-                    Ok(s) => s.accessibility_capture_enabled,
-                    Err(_) => false,
-                };
-
-                if !settings_enabled {
+                // Also check the live feature flag
+                if !settings
+                    .feature_flags()
+                    .read()
+                    .accessibility_capture_enabled
+                {
                     tokio::time::sleep(Duration::from_secs(1)).await;
                     continue;
                 }
+                let saved = settings.get_all().await.ok();
+                let ocr_fallback_enabled = saved
+                    .as_ref()
+                    .map(|s| s.ocr_fallback_enabled)
+                    .unwrap_or(false);
+
+                // Get current meeting ID for linking
+                let meeting_id_opt = current_meeting_id.read().clone();
 
                 // Perform capture
-                match extractor.extract_focused_window() {
-                    Ok(result) => {
-                        capture_count.fetch_add(1, Ordering::SeqCst);
-                        *last_capture.write() = Some(Utc::now());
-                        *last_app.write() = result.app_name.clone();
-
-                        // Calculate word count from text
-                        let word_count = result.text.split_whitespace().count();
-
-                        // Check word count threshold
-                        if word_count < cfg.min_word_count as usize {
-                            log::debug!(
-                                "📝 Skipping capture: {} words < {} minimum",
-                                word_count,
-                                cfg.min_word_count
-                            );
-                            skipped_count.fetch_add(1, Ordering::SeqCst);
-                        } else if cfg.deduplicate {
-                            // Hash the text for dedup
-                            let mut hasher = DefaultHasher::new();
-                            result.text.hash(&mut hasher);
-                            let new_hash = hasher.finish();
-
-                            // Format text with context for vector DB and FTS
-                            let context_text = format!(
-                                "[App: {}] {}\n{}",
-                                result.app_name.as_deref().unwrap_or("Unknown"),
-                                result.window_title.as_deref().unwrap_or(""),
-                                result.text
-                            );
-
-                            let prev_hash = *last_text_hash.read();
-                            if new_hash == prev_hash {
-                                log::debug!("📝 Skipping duplicate content");
-                                skipped_count.fetch_add(1, Ordering::SeqCst);
-                            } else {
-                                // Save to database
-                                *last_text_hash.write() = new_hash;
-                                // Calculate quality score based on word count and accessibility status
-                                let quality_score = if result.is_accessible && word_count > 20 {
-                                    0.9
-                                } else if result.is_accessible {
-                                    0.7
-                                } else {
-                                    0.3
-                                };
-
-                                // Get current meeting ID for linking
-                                let meeting_id_opt = current_meeting_id.read().clone();
-
-                                if let Err(e) = database
-                                    .add_text_snapshot_full(
-                                        &Uuid::new_v4().to_string(),
-                                        None, // episode_id
-                                        None, // state_id
-                                        meeting_id_opt.as_deref(),
-                                        Utc::now(),
-                                        &result.text,
-                                        &format!("{:x}", new_hash),
-                                        quality_score,
-                                        "accessibility",
-                                        result.app_name.as_deref(),
-                                        result.window_title.as_deref(),
-                                    )
-                                    .await
+                let attempt = extractor.extract_focused_window();
+                if let Ok(result) = &attempt {
+                    capture_count.fetch_add(1, Ordering::SeqCst);
+                    *last_capture.write() = Some(Utc::now());
+                    *last_app.write() = result.app_name.clone();
+                }
+
+                let verdict = accessibility_verdict(&attempt);
+                let mut candidate = attempt.ok().and_then(CaptureCandidate::from_accessibility);
+
+                if let Some(verdict) = verdict.filter(ExtractionResult::should_fall_back) {
+                    log::debug!("📝 Accessibility unusable ({:?})", verdict);
+
+                    if ocr_fallback_enabled {
+                        if let Some(mid) = meeting_id_opt.as_deref() {
+                            match ocr_latest_keyframe(&database, mid).await {
+                                Ok(Some(ocr))
+                                    if last_ocr_state_id.as_deref() == ocr.state_id.as_deref() =>
                                 {
-                                    log::warn!("📝 Failed to save text snapshot: {}", e);
-                                } else {
-                                    saved_count.fetch_add(1, Ordering::SeqCst);
-                                    log::debug!(
-                                        "📝 Saved snapshot: {} words from {} (meeting: {:?})",
-                                        word_count,
-                                        result.app_name.as_deref().unwrap_or("unknown"),
-                                        meeting_id_opt
-                                    );
-
-                                    // Trigger Pinecone embedding
-                                    let pinecone_config_opt = { pinecone.read().get_config() };
-
-                                    if let Some(pinecone_config) = pinecone_config_opt {
-                                        let id = format!("acc_{}", Uuid::new_v4());
-                                        let metadata = serde_json::json!({
-                                            "type": "accessibility",
-                                            "source": "accessibility",
-                                            "meeting_id": meeting_id_opt,
-                                            "app_name": result.app_name,
-                                            "window_title": result.window_title,
-                                            "timestamp": Utc::now().to_rfc3339(),
-                                            "text": result.text.chars().take(1000).collect::<String>(), // Truncate for metadata
-                                        });
-
-                                        let _ = crate::pinecone_client::pinecone_upsert_generic(
-                                            &pinecone_config,
-                                            &id,
-                                            &context_text,
-                                            &metadata,
-                                        )
-                                        .await
-                                        .map_err(|e| {
-                                            log::warn!("📝 Pinecone upsert failed: {}", e)
-                                        });
-                                    }
+                                    log::debug!("📝 Keyframe unchanged since last OCR");
+                                    candidate = None;
+                                }
+                                Ok(Some(ocr)) => {
+                                    last_ocr_state_id = ocr.state_id.clone();
+                                    candidate = Some(ocr);
                                 }
+                                Ok(None) => {}
+                                Err(e) => log::debug!("📝 OCR fallback failed: {}", e),
                             }
-                        } else {
-                            // No dedup, always save
-                            let mut hasher = DefaultHasher::new();
-                            result.text.hash(&mut hasher);
-                            let hash = hasher.finish();
-
-                            // Format text with context for vector DB and FTS
-                            let context_text = format!(
-                                "[App: {}] {}\n{}",
-                                result.app_name.as_deref().unwrap_or("Unknown"),
-                                result.window_title.as_deref().unwrap_or(""),
-                                result.text
-                            );
-
-                            // Calculate quality score
-                            let quality_score = if result.is_accessible && word_count > 20 {
-                                0.9
-                            } else if result.is_accessible {
-                                0.7
-                            } else {
-                                0.3
-                            };
-
-                            // Get current meeting ID for linking
-                            let meeting_id_opt = current_meeting_id.read().clone();
-
-                            if let Err(e) = database
-                                .add_text_snapshot_full(
-                                    &Uuid::new_v4().to_string(),
-                                    None,
-                                    None,
-                                    meeting_id_opt.as_deref(),
-                                    Utc::now(),
-                                    &result.text,
-                                    &format!("{:x}", hash),
-                                    quality_score,
-                                    "accessibility",
-                                    result.app_name.as_deref(),
-                                    result.window_title.as_deref(),
-                                )
-                                .await
-                            {
-                                log::warn!("📝 Failed to save text snapshot: {}", e);
-                            } else {
+                        }
+                    }
+                }
+
+                if let Some(candidate) = candidate {
+                    // Calculate word count from text
+                    let word_count = candidate.text.split_whitespace().count();
+
+                    // Hash the text for dedup
+                    let mut hasher = DefaultHasher::new();
+                    candidate.text.hash(&mut hasher);
+                    let new_hash = hasher.finish();
+
+                    // Check word count threshold
+                    if word_count < cfg.min_word_count as usize {
+                        log::debug!(
+                            "📝 Skipping capture: {} words < {} minimum",
+                            word_count,
+                            cfg.min_word_count
+                        );
+                        skipped_count.fetch_add(1, Ordering::SeqCst);
+                    } else if cfg.deduplicate && new_hash == *last_text_hash.read() {
+                        log::debug!("📝 Skipping duplicate content");
+                        skipped_count.fetch_add(1, Ordering::SeqCst);
+                    } else {
+                        *last_text_hash.write() = new_hash;
+
+                        let dork = saved
+                            .as_ref()
+                            .filter(|s| s.session_mode == "dork")
+                            .and_then(|s| {
+                                dork_session
+                                    .read()
+                                    .clone()
+                                    .map(|session| (session, s.dork_app_allowlist.clone()))
+                            });
+                        if let Some((session, allowlist)) = dork {
+                            feed_dork_session(&session, &database, &allowlist, &candidate).await;
+                        }
+
+                        let source = candidate.source.as_str();
+                        match database
+                            .add_text_snapshot_full(
+                                &Uuid::new_v4().to_string(),
+                                None, // episode_id
+                                candidate.state_id.as_deref(),
+                                meeting_id_opt.as_deref(),
+                                Utc::now(),
+                                &candidate.text,
+                                &format!("{:x}", new_hash),
+                                candidate.quality_score,
+                                source,
+                                candidate.app_name.as_deref(),
+                                candidate.window_title.as_deref(),
+                            )
+                            .await
+                        {
+                            Err(e) => log::warn!("📝 Failed to save text snapshot: {}", e),
+                            Ok(write) => {
                                 saved_count.fetch_add(1, Ordering::SeqCst);
+                                if let Some(metrics) = metrics.read().as_ref() {
+                                    metrics.record_snapshot_write(&write);
+                                    match candidate.source {
+                                        ExtractionSource::Ocr => metrics.record_ocr_fallback(),
+                                        _ => metrics.record_accessibility_hit(),
+                                    }
+                                }
+                                log::debug!(
+                                    "📝 Saved {} snapshot: {} words from {} (meeting: {:?})",
+                                    source,
+                                    word_count,
+                                    candidate.app_name.as_deref().unwrap_or("unknown"),
+                                    meeting_id_opt
+                                );
+
+                                // Format text with context for vector DB and FTS
+                                let context_text = format!(
+                                    "[App: {}] {}\n{}",
+                                    candidate.app_name.as_deref().unwrap_or("Unknown"),
+                                    candidate.window_title.as_deref().unwrap_or(""),
+                                    candidate.text
+                                );
 
                                 // Trigger Pinecone embedding
                                 let pinecone_config_opt = { pinecone.read().get_config() };
@@ -337,16 +457,17 @@ impl AccessibilityCaptureService {
                                     let id = format!("acc_{}", Uuid::new_v4());
                                     let metadata = serde_json::json!({
                                         "type": "accessibility",
-                                        "source": "accessibility",
+                                        "source": source,
                                         "meeting_id": meeting_id_opt,
-                                        "app_name": result.app_name,
-                                        "window_title": result.window_title,
+                                        "app_name": candidate.app_name,
+                                        "window_title": candidate.window_title,
                                         "timestamp": Utc::now().to_rfc3339(),
-                                        "text": result.text.chars().take(1000).collect::<String>(),
+                                        "text": candidate.text.chars().take(1000).collect::<String>(), // Truncate for metadata
                                     });
 
                                     let _ = crate::pinecone_client::pinecone_upsert_generic(
                                         &pinecone_config,
+                                        crate::pinecone_client::VectorKind::Snapshot,
                                         &id,
                                         &context_text,
                                         &metadata,
@@ -357,9 +478,6 @@ impl AccessibilityCaptureService {
                             }
                         }
                     }
-                    Err(e) => {
-                        log::debug!("📝 Capture failed: {}", e);
-                    }
                 }
 
                 // Sleep for the configured interval
@@ -400,6 +518,30 @@ mod tests {
         assert!(config.deduplicate);
     }
 
+    #[test]
+    fn test_accessibility_verdict() {
+        let readable = AccessibilityResult {
+            text: "Quarterly planning notes for the roadmap review".to_string(),
+            is_accessible: true,
+            ..Default::default()
+        };
+        assert!(accessibility_verdict(&Ok(readable.clone())).is_none());
+        let candidate = CaptureCandidate::from_accessibility(readable).unwrap();
+        assert_eq!(candidate.source, ExtractionSource::Accessibility);
+        assert_eq!(candidate.quality_score, 0.7);
+
+        let opaque = AccessibilityResult {
+            text: "Close Minimize Zoom".to_string(),
+            is_accessible: false,
+            ..Default::default()
+        };
+        assert!(accessibility_verdict(&Ok(opaque)).is_some_and(|v| v.should_fall_back()));
+
+        let failed: Result<AccessibilityResult, String> = Err("no focused window".to_string());
+        assert!(accessibility_verdict(&failed).is_some_and(|v| v.should_fall_back()));
+        assert!(CaptureCandidate::from_accessibility(AccessibilityResult::default()).is_none());
+    }
+
     #[test]
     fn test_service_creation() {
         let service = AccessibilityCaptureService::new();
@@ -40,6 +40,16 @@ impl Default for AccessibilityResult {
     }
 }
 
+/// Identity of the frontmost window (no text extraction - cheap enough per frame)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FrontmostWindow {
+    pub app_name: Option<String>,
+    /// e.g. "com.agilebits.onepassword7"
+    pub bundle_id: Option<String>,
+    /// Only available with accessibility permission
+    pub window_title: Option<String>,
+}
+
 /// Configuration for accessibility extraction
 #[derive(Debug, Clone)]
 pub struct AccessibilityConfig {
@@ -271,6 +281,77 @@ impl AccessibilityExtractor {
         }
     }
 
+    /// App name, bundle id and (if permitted) focused window title of the frontmost app
+    #[cfg(target_os = "macos")]
+    pub fn frontmost_window() -> Option<FrontmostWindow> {
+        unsafe {
+            use objc::runtime::{Class, Object};
+            use objc::{msg_send, sel, sel_impl};
+
+            let workspace_class = Class::get("NSWorkspace")?;
+            let workspace: *mut Object = msg_send![workspace_class, sharedWorkspace];
+            let front_app: *mut Object = msg_send![workspace, frontmostApplication];
+            if front_app.is_null() {
+                return None;
+            }
+
+            let app_name_obj: *mut Object = msg_send![front_app, localizedName];
+            let bundle_id_obj: *mut Object = msg_send![front_app, bundleIdentifier];
+            let mut window = FrontmostWindow {
+                app_name: Some(nsstring_to_rust(app_name_obj)).filter(|s| !s.is_empty()),
+                bundle_id: Some(nsstring_to_rust(bundle_id_obj)).filter(|s| !s.is_empty()),
+                window_title: None,
+            };
+
+            if !Self::is_trusted() {
+                return Some(window);
+            }
+
+            #[link(name = "ApplicationServices", kind = "framework")]
+            extern "C" {
+                fn AXUIElementCreateApplication(pid: i32) -> *mut c_void;
+                fn AXUIElementCopyAttributeValue(
+                    element: *mut c_void,
+                    attribute: *const c_void,
+                    value: *mut *mut c_void,
+                ) -> i32;
+            }
+
+            let pid: i32 = msg_send![front_app, processIdentifier];
+            let ax_app = AXUIElementCreateApplication(pid);
+            if ax_app.is_null() {
+                return Some(window);
+            }
+
+            let nsstring_class = Class::get("NSString")?;
+            let focused_window_key: *mut Object =
+                msg_send![nsstring_class, stringWithUTF8String: "AXFocusedWindow\0".as_ptr()];
+            let mut focused_window: *mut c_void = std::ptr::null_mut();
+            let result = AXUIElementCopyAttributeValue(
+                ax_app,
+                focused_window_key as *const c_void,
+                &mut focused_window,
+            );
+            if result != 0 || focused_window.is_null() {
+                return Some(window);
+            }
+
+            let title_key: *mut Object =
+                msg_send![nsstring_class, stringWithUTF8String: "AXTitle\0".as_ptr()];
+            let mut title_value: *mut c_void = std::ptr::null_mut();
+            let _ = AXUIElementCopyAttributeValue(
+                focused_window,
+                title_key as *const c_void,
+                &mut title_value,
+            );
+            if !title_value.is_null() {
+                window.window_title = Some(nsstring_to_rust(title_value as *mut Object));
+            }
+
+            Some(window)
+        }
+    }
+
     /// Recursively extract text from an accessibility element
     #[cfg(target_os = "macos")]
     fn extract_text_from_element(
@@ -389,6 +470,11 @@ impl AccessibilityExtractor {
     pub fn extract_focused_window(&self) -> Result<AccessibilityResult, String> {
         Err("Accessibility extraction only available on macOS".to_string())
     }
+
+    #[cfg(not(target_os = "macos"))]
+    pub fn frontmost_window() -> Option<FrontmostWindow> {
+        None
+    }
 }
 
 impl Default for AccessibilityExtractor {
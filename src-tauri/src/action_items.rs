@@ -0,0 +1,292 @@
+// noFriction Meetings - Action Items
+// Parses action items into tracked records shared by live and post-hoc extraction
+//
+// Live detection (LiveIntelAgent cues on final transcripts), AI extraction
+// and manual entry all write to the `action_items` table. Rows are deduped
+// per meeting on normalized text, so the same task said live and later
+// extracted by the model collapses into one record.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, OnceLock};
+
+use crate::database::{ActionItemRecord, DatabaseManager};
+
+/// Lifecycle of a tracked action item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionItemStatus {
+    Open,
+    InProgress,
+    Done,
+    Cancelled,
+}
+
+impl ActionItemStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActionItemStatus::Open => "open",
+            ActionItemStatus::InProgress => "in_progress",
+            ActionItemStatus::Done => "done",
+            ActionItemStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "open" => Some(ActionItemStatus::Open),
+            "in_progress" => Some(ActionItemStatus::InProgress),
+            "done" => Some(ActionItemStatus::Done),
+            "cancelled" => Some(ActionItemStatus::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+/// Where a record came from
+pub const SOURCE_LIVE: &str = "live";
+pub const SOURCE_EXTRACTED: &str = "extracted";
+pub const SOURCE_MANUAL: &str = "manual";
+
+/// One action item parsed out of model output
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedActionItem {
+    pub text: String,
+    pub assignee: Option<String>,
+    pub due_hint: Option<String>,
+}
+
+/// Dedup key: lowercase alphanumerics, single spaces
+pub fn normalize_text(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Deserialize)]
+struct JsonActionItem {
+    #[serde(alias = "text")]
+    task: String,
+    #[serde(default)]
+    assignee: Option<String>,
+    #[serde(default, alias = "due_hint")]
+    due_date: Option<String>,
+}
+
+struct MetadataPatterns {
+    assignee: Regex,
+    due: Regex,
+    bullet: Regex,
+}
+
+fn metadata_patterns() -> &'static MetadataPatterns {
+    static PATTERNS: OnceLock<MetadataPatterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| MetadataPatterns {
+        assignee: Regex::new(
+            r"(?i)\b(?:owner|assignee|responsible|assigned to|who)\s*:\s*([^,;()\]\n]+)",
+        )
+        .unwrap(),
+        due: Regex::new(r"(?i)\b(?:due(?: date)?|deadline|timeline|when)\s*:\s*([^,;()\]\n]+)")
+            .unwrap(),
+        bullet: Regex::new(r"^\s*(?:[-*•]|\d+[.)])\s+(?:\[[ xX]\]\s*)?").unwrap(),
+    })
+}
+
+fn clean_field(value: &str) -> Option<String> {
+    let value = value.trim().trim_matches('*').trim();
+    let lower = value.to_lowercase();
+    if value.is_empty() || matches!(lower.as_str(), "none" | "n/a" | "unknown" | "tbd") {
+        return None;
+    }
+    Some(value.to_string())
+}
+
+/// Parse model output: a JSON array if there is one, otherwise a markdown
+/// checklist with optional "Owner:" / "Due:" metadata on the line or on
+/// sub-bullets underneath it.
+pub fn parse_action_items(response: &str) -> Vec<ParsedActionItem> {
+    if let (Some(start), Some(end)) = (response.find('['), response.rfind(']')) {
+        if start < end {
+            if let Ok(items) = serde_json::from_str::<Vec<JsonActionItem>>(&response[start..=end]) {
+                return items
+                    .into_iter()
+                    .filter_map(|i| {
+                        Some(ParsedActionItem {
+                            text: clean_field(&i.task)?,
+                            assignee: i.assignee.as_deref().and_then(clean_field),
+                            due_hint: i.due_date.as_deref().and_then(clean_field),
+                        })
+                    })
+                    .collect();
+            }
+        }
+    }
+
+    let patterns = metadata_patterns();
+    let mut items: Vec<ParsedActionItem> = Vec::new();
+
+    for line in response.lines() {
+        let bullet = match patterns.bullet.find(line) {
+            Some(m) => m,
+            None => continue,
+        };
+        let body = line[bullet.end()..].replace("**", "");
+        let assignee = patterns
+            .assignee
+            .captures(&body)
+            .and_then(|c| clean_field(&c[1]));
+        let due_hint = patterns
+            .due
+            .captures(&body)
+            .and_then(|c| clean_field(&c[1]));
+
+        // Task text is whatever precedes the first metadata field
+        let cut = [patterns.assignee.find(&body), patterns.due.find(&body)]
+            .into_iter()
+            .flatten()
+            .map(|m| m.start())
+            .min()
+            .unwrap_or(body.len());
+        let task = body[..cut]
+            .trim()
+            .trim_start_matches("Task:")
+            .trim_end_matches(|c: char| c == '(' || c == '-' || c == '–' || c == ',' || c == ':')
+            .trim();
+
+        // Metadata-only sub-bullet: belongs to the previous task
+        if task.is_empty() {
+            if let Some(last) = items.last_mut() {
+                last.assignee = last.assignee.take().or(assignee);
+                last.due_hint = last.due_hint.take().or(due_hint);
+            }
+            continue;
+        }
+
+        items.push(ParsedActionItem {
+            text: task.to_string(),
+            assignee,
+            due_hint,
+        });
+    }
+
+    items
+}
+
+/// Record a live-detected action item for a freshly saved final transcript
+pub async fn record_live_action_item(
+    database: &Arc<DatabaseManager>,
+    transcript_id: i64,
+    meeting_id: &str,
+    text: &str,
+    speaker: Option<&str>,
+) {
+    let cue = match crate::live_intel_agent::detect_action_item(text, speaker) {
+        Some(cue) => cue,
+        None => return,
+    };
+
+    match database
+        .upsert_action_item(
+            meeting_id,
+            text,
+            &normalize_text(text),
+            cue.assignee.as_deref(),
+            None,
+            SOURCE_LIVE,
+            Some(transcript_id),
+        )
+        .await
+    {
+        Ok(true) => {
+            log::debug!("Action item captured from transcript {}", transcript_id);
+            crate::integrations::emit_action_item_created(
+                database,
+                meeting_id,
+                &normalize_text(text),
+            )
+            .await;
+        }
+        Ok(false) => {}
+        Err(e) => log::warn!("Failed to save live action item: {}", e),
+    }
+}
+
+/// Markdown checklist for the vault; checked state mirrors status
+pub fn to_markdown(items: &[ActionItemRecord]) -> String {
+    items
+        .iter()
+        .map(|item| {
+            let status = ActionItemStatus::parse(&item.status).unwrap_or(ActionItemStatus::Open);
+            let text = match status {
+                ActionItemStatus::Cancelled => format!("~~{}~~", item.text),
+                _ => item.text.clone(),
+            };
+            let checkbox = match status {
+                ActionItemStatus::Done | ActionItemStatus::Cancelled => "[x]",
+                _ => "[ ]",
+            };
+            let mut meta = Vec::new();
+            if let Some(assignee) = &item.assignee {
+                meta.push(format!("assigned: {}", assignee));
+            }
+            if let Some(due) = &item.due_hint {
+                meta.push(format!("due: {}", due));
+            }
+            if meta.is_empty() {
+                format!("- {} {}", checkbox, text)
+            } else {
+                format!("- {} {} *({})*", checkbox, text, meta.join(", "))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_text() {
+        assert_eq!(
+            normalize_text("  Send the Q3 deck -- by Friday!"),
+            "send the q3 deck by friday"
+        );
+    }
+
+    #[test]
+    fn test_parse_markdown_checklist() {
+        let response = "Here are the action items:\n\n\
+            - [ ] **Send the Q3 deck to finance** (Owner: Alice, Due: Friday)\n\
+            - [ ] Book the offsite venue\n  - Responsible: Bob\n  - Deadline: next week\n\
+            1. Review hiring plan - Owner: None";
+
+        let items = parse_action_items(response);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].text, "Send the Q3 deck to finance");
+        assert_eq!(items[0].assignee.as_deref(), Some("Alice"));
+        assert_eq!(items[0].due_hint.as_deref(), Some("Friday"));
+        assert_eq!(items[1].text, "Book the offsite venue");
+        assert_eq!(items[1].assignee.as_deref(), Some("Bob"));
+        assert_eq!(items[1].due_hint.as_deref(), Some("next week"));
+        assert_eq!(items[2].text, "Review hiring plan");
+        assert_eq!(items[2].assignee, None);
+    }
+
+    #[test]
+    fn test_parse_json_array() {
+        let response =
+            r#"Sure: [{"task": "Email the client", "assignee": "Dana", "priority": "high"}]"#;
+        let items = parse_action_items(response);
+        assert_eq!(
+            items,
+            vec![ParsedActionItem {
+                text: "Email the client".to_string(),
+                assignee: Some("Dana".to_string()),
+                due_hint: None,
+            }]
+        );
+    }
+}
@@ -0,0 +1,291 @@
+// noFriction Meetings - Activity Clusters
+// Groups activity_log summaries into projects, so a week reads as "where did
+// my time go" without tagging themes by hand.
+//
+// Each run embeds the activities that aren't clustered yet (with the
+// configured vector backend) and lets each join the nearest existing cluster.
+// Leftovers that resemble each other become new clusters, labeled by the AI.
+// Cluster ids are created once and an assigned activity never moves, so ids
+// stay stable between runs; labels change only when the user renames them.
+
+use crate::ai_client::AIClient;
+use crate::clustering::{clean_topic_label, cosine_similarity, leader_clusters};
+use crate::database::{ActivityClusterRecord, ActivityLogEntry, DatabaseManager};
+use crate::embeddings::{decode_vector, encode_vector, VectorStore};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Minimum similarity to a cluster's centroid for an activity to join it
+pub const ASSIGN_THRESHOLD: f64 = 0.78;
+
+/// Leftover activities needed before they form a cluster of their own
+pub const MIN_CLUSTER_SIZE: usize = 3;
+
+/// Unclustered activities older than this are left alone
+const LOOKBACK_DAYS: i64 = 14;
+
+/// Most unclustered activities looked at per run
+const RUN_LIMIT: i64 = 500;
+
+/// Summaries shown to the AI when labeling a new cluster
+const LABEL_SAMPLES: usize = 6;
+
+/// A cluster's running centroid
+#[derive(Debug, Clone)]
+pub struct ClusterCentroid {
+    pub id: String,
+    pub centroid: Vec<f32>,
+    pub member_count: i64,
+}
+
+impl ClusterCentroid {
+    /// Fold a new member into the mean
+    fn absorb(&mut self, vector: &[f32]) {
+        let n = self.member_count as f32;
+        for (c, v) in self.centroid.iter_mut().zip(vector) {
+            *c = (*c * n + v) / (n + 1.0);
+        }
+        self.member_count += 1;
+    }
+}
+
+/// Put a vector in the most similar cluster at or above the threshold,
+/// updating its centroid; returns the cluster's index
+pub fn assign_to_cluster(clusters: &mut [ClusterCentroid], vector: &[f32]) -> Option<usize> {
+    let (best, _) = clusters
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.centroid.len() == vector.len())
+        .map(|(i, c)| (i, cosine_similarity(vector, &c.centroid)))
+        .filter(|(_, sim)| *sim >= ASSIGN_THRESHOLD)
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+    clusters[best].absorb(vector);
+    Some(best)
+}
+
+/// What a clustering run did
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClusterRunResult {
+    pub embedded: usize,
+    /// Activities that joined an existing cluster
+    pub assigned: usize,
+    pub new_clusters: usize,
+    /// Activities still waiting for enough similar ones
+    pub unclustered: usize,
+}
+
+/// "Where did my week go": time per project over a period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityClusterReport {
+    pub period: String,
+    pub since: Option<DateTime<Utc>>,
+    pub clusters: Vec<ActivityClusterRecord>,
+    /// Time in activities no cluster covers yet
+    pub unclustered_seconds: i64,
+    pub total_seconds: i64,
+}
+
+/// Text embedded for an activity: the app gives short summaries context
+fn activity_text(activity: &ActivityLogEntry) -> String {
+    match activity.app_name.as_deref() {
+        Some(app) if !app.is_empty() => format!("{}: {}", app, activity.summary.trim()),
+        _ => activity.summary.trim().to_string(),
+    }
+}
+
+fn label_prompt(summaries: &[&str]) -> String {
+    format!(
+        "These activity summaries were captured from one person's screen and belong to the same project:\n\n{}\n\n\
+         Reply with a short project name (2-5 words). No quotes, no punctuation at the end, nothing else.",
+        summaries
+            .iter()
+            .map(|s| format!("- {}", s))
+            .collect::<Vec<_>>()
+            .join("\n")
+    )
+}
+
+/// Cluster the activities that aren't in a cluster yet
+pub async fn update_clusters(
+    db: &DatabaseManager,
+    store: &VectorStore,
+    ai_client: &AIClient,
+) -> Result<ClusterRunResult, String> {
+    let backend = store.name();
+    let mut activities = db
+        .get_unclustered_activities(Utc::now() - Duration::days(LOOKBACK_DAYS), RUN_LIMIT)
+        .await
+        .map_err(|e| format!("Failed to get activities: {}", e))?;
+    activities.retain(|a| a.id.is_some());
+    // Oldest first, so clusters grow in the order the work happened
+    activities.reverse();
+    if activities.is_empty() {
+        return Ok(ClusterRunResult::default());
+    }
+
+    // Reuse vectors from earlier runs; embed the rest in one call
+    let ids: Vec<i64> = activities.iter().filter_map(|a| a.id).collect();
+    let mut vectors: HashMap<i64, Vec<f32>> = db
+        .get_activity_embeddings(&ids, backend)
+        .await
+        .map_err(|e| format!("Failed to get activity embeddings: {}", e))?
+        .into_iter()
+        .map(|(id, bytes)| (id, decode_vector(&bytes)))
+        .collect();
+    let missing: Vec<&ActivityLogEntry> = activities
+        .iter()
+        .filter(|a| a.id.is_some_and(|id| !vectors.contains_key(&id)))
+        .collect();
+    let mut result = ClusterRunResult::default();
+    if !missing.is_empty() {
+        let texts: Vec<String> = missing.iter().map(|a| activity_text(a)).collect();
+        let embedded = store.embed(&texts).await?;
+        for (activity, vector) in missing.iter().zip(embedded) {
+            let id = activity.id.unwrap_or_default();
+            db.save_activity_embedding(id, backend, &encode_vector(&vector))
+                .await
+                .map_err(|e| format!("Failed to save activity embedding: {}", e))?;
+            vectors.insert(id, vector);
+        }
+        result.embedded = missing.len();
+    }
+
+    let mut clusters: Vec<ClusterCentroid> = db
+        .get_activity_cluster_centroids(backend)
+        .await
+        .map_err(|e| format!("Failed to get activity clusters: {}", e))?
+        .into_iter()
+        .map(|(id, centroid, member_count)| ClusterCentroid {
+            id,
+            centroid: decode_vector(&centroid),
+            member_count,
+        })
+        .collect();
+
+    // Join existing clusters first; only what's left can start new ones
+    let mut joined: HashMap<usize, Vec<i64>> = HashMap::new();
+    let mut leftovers: Vec<&ActivityLogEntry> = Vec::new();
+    for activity in &activities {
+        let Some(vector) = activity.id.and_then(|id| vectors.get(&id)) else {
+            continue;
+        };
+        match assign_to_cluster(&mut clusters, vector) {
+            Some(index) => joined.entry(index).or_default().extend(activity.id),
+            None => leftovers.push(activity),
+        }
+    }
+    for (index, activity_ids) in &joined {
+        let cluster = &clusters[*index];
+        db.save_activity_cluster(
+            &cluster.id,
+            "",
+            &encode_vector(&cluster.centroid),
+            cluster.member_count,
+            backend,
+            activity_ids,
+        )
+        .await
+        .map_err(|e| format!("Failed to update activity cluster: {}", e))?;
+        result.assigned += activity_ids.len();
+    }
+
+    let leftover_vectors: Vec<Vec<f32>> = leftovers
+        .iter()
+        .filter_map(|a| a.id.and_then(|id| vectors.get(&id)).cloned())
+        .collect();
+    for group in leader_clusters(&leftover_vectors, ASSIGN_THRESHOLD) {
+        if group.len() < MIN_CLUSTER_SIZE {
+            result.unclustered += group.len();
+            continue;
+        }
+        let members: Vec<&ActivityLogEntry> = group.iter().map(|&i| leftovers[i]).collect();
+        let samples: Vec<&str> = members
+            .iter()
+            .take(LABEL_SAMPLES)
+            .map(|a| a.summary.trim())
+            .collect();
+        let label = match ai_client.complete(&label_prompt(&samples)).await {
+            Ok(response) => clean_topic_label(&response),
+            Err(e) => {
+                log::warn!("Activity cluster labeling failed: {}", e);
+                None
+            }
+        }
+        .unwrap_or_else(|| "Untitled project".to_string());
+
+        let centroid = crate::clustering::centroid(&leftover_vectors, &group);
+        let activity_ids: Vec<i64> = members.iter().filter_map(|a| a.id).collect();
+        db.save_activity_cluster(
+            &uuid::Uuid::new_v4().to_string(),
+            &label,
+            &encode_vector(&centroid),
+            activity_ids.len() as i64,
+            backend,
+            &activity_ids,
+        )
+        .await
+        .map_err(|e| format!("Failed to save activity cluster: {}", e))?;
+        log::info!(
+            "🗂️ New activity cluster '{}' ({} activities)",
+            label,
+            activity_ids.len()
+        );
+        result.new_clusters += 1;
+    }
+
+    Ok(result)
+}
+
+/// Per-cluster time totals for a period ("today", "week", "month", "30d", "all")
+pub async fn report(db: &DatabaseManager, period: &str) -> Result<ActivityClusterReport, String> {
+    let since = crate::usage_ledger::period_start(period, Utc::now())?;
+    let clusters = db
+        .get_activity_cluster_totals(since)
+        .await
+        .map_err(|e| format!("Failed to get activity clusters: {}", e))?;
+    let unclustered_seconds = db
+        .get_unclustered_activity_seconds(since)
+        .await
+        .map_err(|e| format!("Failed to get unclustered time: {}", e))?;
+    let total_seconds = clusters.iter().map(|c| c.total_seconds).sum::<i64>() + unclustered_seconds;
+    Ok(ActivityClusterReport {
+        period: period.to_string(),
+        since,
+        clusters,
+        unclustered_seconds,
+        total_seconds,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assignment_keeps_existing_clusters() {
+        let mut clusters = vec![
+            ClusterCentroid {
+                id: "billing".to_string(),
+                centroid: vec![1.0, 0.0, 0.0],
+                member_count: 4,
+            },
+            ClusterCentroid {
+                id: "hiring".to_string(),
+                centroid: vec![0.0, 1.0, 0.0],
+                member_count: 2,
+            },
+        ];
+
+        assert_eq!(assign_to_cluster(&mut clusters, &[0.9, 0.1, 0.0]), Some(0));
+        assert_eq!(assign_to_cluster(&mut clusters, &[0.0, 0.0, 1.0]), None);
+        // Mismatched dimensions (another backend's vector) never match
+        assert_eq!(assign_to_cluster(&mut clusters, &[1.0, 0.0]), None);
+
+        assert_eq!(clusters[0].id, "billing");
+        assert_eq!(clusters[0].member_count, 5);
+        assert!((clusters[0].centroid[0] - 0.98).abs() < 1e-6);
+        assert!((clusters[0].centroid[1] - 0.02).abs() < 1e-6);
+        assert_eq!(clusters[1].member_count, 2);
+    }
+}
@@ -4,15 +4,32 @@
 // Features:
 // - Recordings library with storage enumeration
 // - Safe deletion with preview and audit
+// - Keyframe retention cleanup (manual and scheduled)
 // - Learned data editing with versioning
 // - System health and tools management
-
-use crate::audit_log::{AuditEntry, AuditLog};
-use crate::data_editor::{DataEditor, DataVersion, EditResult, LearnedDataItem};
-use crate::storage_manager::{DeletePreview, DeleteResult, StorageManager};
+// - AI/STT usage and cost estimates
+// - VLM backfill of already-analyzed keyframes
+// - Database integrity check and repair
+// - Settings export/import between machines
+
+use crate::audit_log::{AuditEntry, AuditFilter, AuditLog};
+use crate::data_editor::{
+    BulkEditResult, BulkFilter, DataEditor, DataVersion, EditResult, LearnedDataItem,
+};
+use crate::db_maintenance::{run_maintenance, MaintenanceOptions, MaintenanceReport};
+use crate::frame_backfill::{BackfillScope, RequeueSummary};
+use crate::health_probe::{
+    health_cache, overall_status, run_probes, DependencyHealth, Probe, ProbeOutcome, ProbeStatus,
+    PROBE_TIMEOUT,
+};
+use crate::settings_transfer::{SettingsExportSummary, SettingsImportReport};
+use crate::storage_manager::{
+    CleanupPreview, CleanupResult, DeletePreview, DeleteResult, RetentionPolicy, StorageManager,
+};
+use crate::usage_ledger::{usage_ledger, UsagePrice, UsageSummary};
 use crate::AppState;
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 // ═══════════════════════════════════════════════════════════════════════════
 // Storage/Recordings Commands
@@ -184,6 +201,20 @@ pub async fn delete_recordings(
         return Err("Cannot delete while recording is active".to_string());
     }
 
+    // Soft-delete-first policy: only archived meetings can be removed
+    let mut rejected = Vec::new();
+    let mut meeting_ids = meeting_ids;
+    if delete_db_records {
+        let mut allowed = Vec::with_capacity(meeting_ids.len());
+        for meeting_id in meeting_ids {
+            match crate::commands::ensure_deletable(&state, &meeting_id).await {
+                Ok(()) => allowed.push(meeting_id),
+                Err(e) => rejected.push(e),
+            }
+        }
+        meeting_ids = allowed;
+    }
+
     let app_data_dir = app
         .path()
         .app_data_dir()
@@ -196,6 +227,10 @@ pub async fn delete_recordings(
 
     // Delete filesystem artifacts
     let mut result = storage_manager.delete_meetings(&meeting_ids).await;
+    if !rejected.is_empty() {
+        result.success = false;
+        result.errors.extend(rejected);
+    }
 
     // Log to audit
     let audit = AuditLog::new(state.database.get_pool().as_ref().clone());
@@ -226,13 +261,171 @@ pub async fn delete_recordings(
                 result
                     .errors
                     .push(format!("DB delete {}: {}", meeting_id, e));
+                continue;
             }
+            crate::commands::delete_meeting_vectors(&state, meeting_id).await;
         }
     }
 
     Ok(result)
 }
 
+// ═══════════════════════════════════════════════════════════════════════════
+// Keyframe Retention Commands
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Delay before the first scheduled cleanup after launch
+const CLEANUP_STARTUP_DELAY_SECS: u64 = 10 * 60;
+/// Interval between scheduled cleanups
+const CLEANUP_INTERVAL_SECS: u64 = 6 * 60 * 60;
+
+async fn retention_policy(state: &AppState) -> Result<RetentionPolicy, String> {
+    let settings = state
+        .settings
+        .get_all()
+        .await
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(RetentionPolicy::from_settings(
+        settings.frame_retention_days,
+        settings.frame_retention_max_gb,
+    ))
+}
+
+async fn cleanup_preview(
+    app: &AppHandle,
+    state: &AppState,
+) -> Result<(StorageManager, CleanupPreview), String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let storage_manager = StorageManager::new(app_data_dir);
+
+    let policy = retention_policy(state).await?;
+    let active_meeting = state.timeline_builder.current_meeting_id();
+    let preview = storage_manager
+        .preview_cleanup(&state.database, policy, active_meeting.as_deref())
+        .await?;
+    Ok((storage_manager, preview))
+}
+
+/// Apply the retention policy now and write a summary to the audit log
+pub async fn run_storage_cleanup(
+    app: &AppHandle,
+    state: &AppState,
+    trigger: &str,
+) -> Result<CleanupResult, String> {
+    let (storage_manager, preview) = cleanup_preview(app, state).await?;
+    if !preview.policy.is_enabled() {
+        return Err("No frame retention policy configured".to_string());
+    }
+
+    let result = storage_manager.run_cleanup(&state.database, &preview).await;
+
+    let audit = AuditLog::new(state.database.get_pool().as_ref().clone());
+    let _ = audit
+        .log_action(crate::audit_log::AuditAction {
+            action: "storage_cleanup".to_string(),
+            target_type: "keyframes".to_string(),
+            target_id: trigger.to_string(),
+            details: Some(
+                serde_json::json!({
+                    "policy": preview.policy,
+                    "files_deleted": result.files_deleted,
+                    "rows_deleted": result.rows_deleted,
+                    "meetings": preview.by_meeting.len(),
+                    "errors": result.errors.len(),
+                })
+                .to_string(),
+            ),
+            bytes_affected: result.bytes_freed,
+        })
+        .await;
+
+    Ok(result)
+}
+
+/// Run the retention policy periodically in the background (no-op while disabled)
+pub fn spawn_storage_cleanup_task(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(CLEANUP_STARTUP_DELAY_SECS)).await;
+        loop {
+            if let Some(state) = app.try_state::<AppState>() {
+                let enabled = retention_policy(&state)
+                    .await
+                    .map(|p| p.is_enabled())
+                    .unwrap_or(false);
+                if enabled {
+                    if let Err(e) = run_storage_cleanup(&app, &state, "scheduled").await {
+                        log::warn!("Scheduled storage cleanup failed: {}", e);
+                    }
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(CLEANUP_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+/// Interval between text snapshot chain compactions
+const SNAPSHOT_COMPACTION_INTERVAL_SECS: u64 = 15 * 60;
+
+/// Periodically re-baseline text snapshots sitting behind long patch chains
+pub fn spawn_snapshot_compaction_task(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(
+                SNAPSHOT_COMPACTION_INTERVAL_SECS,
+            ))
+            .await;
+            if let Some(state) = app.try_state::<AppState>() {
+                match state
+                    .database
+                    .compact_snapshot_chains(crate::diff_builder::MAX_SNAPSHOT_PATCH_CHAIN)
+                    .await
+                {
+                    Ok(0) => {}
+                    Ok(n) => log::info!("Re-baselined {} text snapshot patch chains", n),
+                    Err(e) => log::warn!("Text snapshot compaction failed: {}", e),
+                }
+            }
+        }
+    });
+}
+
+/// Show which keyframes the retention policy would delete
+#[tauri::command]
+pub async fn get_storage_cleanup_preview(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<CleanupPreview, String> {
+    cleanup_preview(&app, &state)
+        .await
+        .map(|(_, preview)| preview)
+}
+
+/// Apply the retention policy immediately
+#[tauri::command]
+pub async fn run_storage_cleanup_now(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<CleanupResult, String> {
+    run_storage_cleanup(&app, &state, "manual").await
+}
+
+/// Configure keyframe retention (0 disables a limit)
+#[tauri::command]
+pub async fn set_frame_retention(
+    state: State<'_, AppState>,
+    max_days: u32,
+    max_total_gb: f32,
+) -> Result<(), String> {
+    state
+        .settings
+        .set_frame_retention(max_days, max_total_gb)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // Audit Log Commands
 // ═══════════════════════════════════════════════════════════════════════════
@@ -244,21 +437,107 @@ pub async fn get_audit_log(
     limit: u32,
     offset: u32,
     action_filter: Option<String>,
+    actor_filter: Option<String>,
 ) -> Result<Vec<AuditEntry>, String> {
     let audit = AuditLog::new(state.database.get_pool().as_ref().clone());
-    audit
-        .get_entries(limit, offset, action_filter.as_deref())
-        .await
+    let filter = AuditFilter {
+        action: action_filter,
+        actor: actor_filter,
+        ..Default::default()
+    };
+    audit.get_entries(limit, offset, &filter).await
 }
 
-/// Get audit log count
+/// Get audit log count (with the same filters as get_audit_log)
 #[tauri::command]
 pub async fn get_audit_log_count(
     state: State<'_, AppState>,
     action_filter: Option<String>,
+    actor_filter: Option<String>,
 ) -> Result<u32, String> {
     let audit = AuditLog::new(state.database.get_pool().as_ref().clone());
-    audit.count_entries(action_filter.as_deref()).await
+    let filter = AuditFilter {
+        action: action_filter,
+        actor: actor_filter,
+        ..Default::default()
+    };
+    audit.count_entries(&filter).await
+}
+
+/// Export audit entries in a date range to `path` as "csv" or "jsonl".
+/// Returns the number of entries written.
+#[tauri::command]
+pub async fn export_audit_log(
+    state: State<'_, AppState>,
+    date_from: Option<String>,
+    date_to: Option<String>,
+    format: String,
+    path: String,
+) -> Result<u64, String> {
+    let format: crate::audit_log::ExportFormat = format.parse()?;
+    let audit = AuditLog::new(state.database.get_pool().as_ref().clone());
+    let filter = AuditFilter {
+        date_from,
+        date_to,
+        ..Default::default()
+    };
+
+    let written = audit
+        .export(&filter, format, std::path::Path::new(&path))
+        .await?;
+
+    let _ = audit
+        .log_action(crate::audit_log::AuditAction {
+            action: "export_audit_log".to_string(),
+            target_type: "audit_log".to_string(),
+            target_id: path,
+            details: Some(
+                serde_json::json!({
+                    "date_from": filter.date_from,
+                    "date_to": filter.date_to,
+                    "rows": written
+                })
+                .to_string(),
+            ),
+            bytes_affected: 0,
+        })
+        .await;
+
+    Ok(written)
+}
+
+/// Keep audit entries for `days` before compacting them into daily summaries (0 = keep)
+#[tauri::command]
+pub async fn set_audit_retention(state: State<'_, AppState>, days: u32) -> Result<(), String> {
+    state
+        .settings
+        .set_audit_retention_days(days)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))
+}
+
+/// Periodically fold audit entries past the retention window into daily summaries
+pub fn spawn_audit_compaction_task(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(CLEANUP_STARTUP_DELAY_SECS)).await;
+        loop {
+            if let Some(state) = app.try_state::<AppState>() {
+                let days = state
+                    .settings
+                    .get_all()
+                    .await
+                    .map(|s| s.audit_retention_days)
+                    .unwrap_or(0);
+                if days > 0 {
+                    let audit = AuditLog::new(state.database.get_pool().as_ref().clone());
+                    if let Err(e) = audit.compact_older_than(days).await {
+                        log::warn!("Audit log compaction failed: {}", e);
+                    }
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(CLEANUP_INTERVAL_SECS)).await;
+        }
+    });
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -274,9 +553,163 @@ pub struct ServiceHealth {
     pub last_check: String,
 }
 
-/// Get system health status for all services
+/// Local services plus actively probed external dependencies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemHealth {
+    pub overall: ProbeStatus,
+    pub services: Vec<ServiceHealth>,
+    pub dependencies: Vec<DependencyHealth>,
+    /// True when dependency results came from the 30s cache
+    pub cached: bool,
+}
+
+/// Payload of the `health-changed` event
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthChanged {
+    pub overall: ProbeStatus,
+    pub changed: Vec<DependencyHealth>,
+}
+
+/// Build one probe per configured dependency; unconfigured ones are skipped
+async fn dependency_probes(app: &AppHandle, state: &AppState) -> Vec<Probe> {
+    let http = reqwest::Client::builder()
+        .timeout(PROBE_TIMEOUT)
+        .build()
+        .unwrap_or_default();
+    let mut probes = Vec::new();
+
+    // VLM server
+    let (vlm_url, vlm_auth) = {
+        let vlm = state.vlm_client.read();
+        (vlm.get_base_url(), vlm.get_auth_header())
+    };
+    if !vlm_url.is_empty() {
+        let client = http.clone();
+        probes.push(Probe::new("VLM", async move {
+            let mut request = client.get(format!("{}/api/tags", vlm_url.trim_end_matches('/')));
+            if let Some(auth) = vlm_auth {
+                request = request.header("Authorization", auth);
+            }
+            let resp = request.send().await.map_err(|e| e.to_string())?;
+            if resp.status().is_success() {
+                Ok(ProbeOutcome::Ok(None))
+            } else {
+                Err(format!("HTTP {}", resp.status()))
+            }
+        }));
+    }
+
+    // AI backend (local Ollama or the configured remote provider)
+    let ai_client = state.ai_client.read().clone();
+    let ai_name = if crate::ai_client::remote_provider().is_some() {
+        "AI Provider"
+    } else {
+        "Ollama"
+    };
+    probes.push(Probe::new(ai_name, async move {
+        let status = ai_client.check_provider().await;
+        match (status.reachable, status.error) {
+            (true, _) if status.models.is_empty() => {
+                Ok(ProbeOutcome::Degraded("No models available".to_string()))
+            }
+            (true, _) => Ok(ProbeOutcome::Ok(Some(format!(
+                "{} models",
+                status.models.len()
+            )))),
+            (false, error) => Err(error.unwrap_or_else(|| "Unreachable".to_string())),
+        }
+    }));
+
+    // Pinecone
+    if let Some(config) = state.pinecone_client.read().get_config() {
+        probes.push(Probe::new("Pinecone", async move {
+            let stats = crate::pinecone_client::pinecone_stats(&config).await?;
+            Ok(ProbeOutcome::Ok(
+                stats
+                    .get("totalVectorCount")
+                    .and_then(|v| v.as_u64())
+                    .map(|count| format!("{} vectors", count)),
+            ))
+        }));
+    }
+
+    // Supabase
+    if let Some(pool) = state.supabase_client.read().get_pool() {
+        probes.push(Probe::new("Supabase", async move {
+            sqlx::query("SELECT 1")
+                .execute(&pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(ProbeOutcome::Ok(None))
+        }));
+    }
+
+    // Ingest server
+    crate::subsystems::ensure_ingest(&state).await;
+    if let Some(ingest) = state.ingest_client() {
+        probes.push(Probe::new("Ingest", async move {
+            match ingest.health_check().await.map_err(|e| e.to_string())? {
+                true => Ok(ProbeOutcome::Ok(None)),
+                false => Err("Health endpoint returned an error".to_string()),
+            }
+        }));
+    }
+
+    // Deepgram: listing projects only needs a valid key
+    if let Ok(Some(key)) = state.settings.get_deepgram_api_key().await {
+        let client = http.clone();
+        probes.push(Probe::new("Deepgram", async move {
+            let resp = client
+                .get("https://api.deepgram.com/v1/projects")
+                .header("Authorization", format!("Token {}", key))
+                .send()
+                .await
+                .map_err(|e| e.to_string())?;
+            match resp.status() {
+                s if s.is_success() => Ok(ProbeOutcome::Ok(None)),
+                s if s.as_u16() == 401 || s.as_u16() == 403 => Err("API key rejected".to_string()),
+                s => Err(format!("HTTP {}", s)),
+            }
+        }));
+    }
+
+    // Free space under the app data dir
+    if let Ok(data_dir) = app.path().app_data_dir() {
+        probes.push(Probe::new("Disk", async move {
+            crate::health_probe::probe_disk(&data_dir).await
+        }));
+    }
+
+    probes
+}
+
+/// Get system health: local service status plus dependency probes, which
+/// are cached for 30 seconds. Emits `health-changed` when a dependency
+/// changes state.
 #[tauri::command]
-pub async fn get_system_health(state: State<'_, AppState>) -> Result<Vec<ServiceHealth>, String> {
+pub async fn get_system_health(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SystemHealth, String> {
+    let cache = health_cache();
+    let (dependencies, cached) = match cache.fresh() {
+        Some(dependencies) => (dependencies, true),
+        None => {
+            let mut dependencies = run_probes(dependency_probes(&app, &state).await).await;
+            let changed = cache.store(&mut dependencies);
+            if !changed.is_empty() {
+                let _ = app.emit(
+                    "health-changed",
+                    &HealthChanged {
+                        overall: overall_status(&dependencies),
+                        changed,
+                    },
+                );
+            }
+            (dependencies, false)
+        }
+    };
+
     let now = chrono::Utc::now().to_rfc3339();
     let mut services = Vec::new();
 
@@ -321,14 +754,51 @@ pub async fn get_system_health(state: State<'_, AppState>) -> Result<Vec<Service
         });
     }
 
+    // PII redaction on cloud sync
+    let redaction_mode = crate::privacy_filter::redaction_mode();
+    match state.database.get_total_pii_redactions().await {
+        Ok(total) => {
+            services.push(ServiceHealth {
+                name: "PII Redaction".to_string(),
+                status: if redaction_mode == crate::privacy_filter::RedactionMode::Off {
+                    "degraded".to_string()
+                } else {
+                    "healthy".to_string()
+                },
+                message: Some(format!(
+                    "Mode: {}, {} redactions",
+                    redaction_mode.as_str(),
+                    total
+                )),
+                last_check: now.clone(),
+            });
+        }
+        Err(e) => {
+            services.push(ServiceHealth {
+                name: "PII Redaction".to_string(),
+                status: "error".to_string(),
+                message: Some(format!("Failed to count redactions: {}", e)),
+                last_check: now.clone(),
+            });
+        }
+    }
+
     // Ingest queue
-    let queue_result = state.ingest_queue.lock().get_stats();
+    let queue_result = state.ingest_queue.lock().get_state_counts();
     match queue_result {
-        Ok((pending, processing)) => {
+        Ok(counts) => {
             services.push(ServiceHealth {
                 name: "Ingest Queue".to_string(),
-                status: "healthy".to_string(),
-                message: Some(format!("{} pending, {} processing", pending, processing)),
+                status: if counts.failed > 0 {
+                    "degraded"
+                } else {
+                    "healthy"
+                }
+                .to_string(),
+                message: Some(format!(
+                    "{} pending, {} uploading, {} failed",
+                    counts.pending, counts.uploading, counts.failed
+                )),
                 last_check: now.clone(),
             });
         }
@@ -342,7 +812,100 @@ pub async fn get_system_health(state: State<'_, AppState>) -> Result<Vec<Service
         }
     }
 
-    Ok(services)
+    // Month-to-date AI/STT usage
+    match usage_ledger().summary("month").await {
+        Ok(summary) => {
+            let total = &summary.total;
+            let cost = match total.estimated_cost_usd {
+                Some(cost) if summary.unpriced_providers.is_empty() => format!("~${:.2}", cost),
+                Some(cost) => format!("~${:.2} + unpriced", cost),
+                None => "unpriced".to_string(),
+            };
+            services.push(ServiceHealth {
+                name: "AI Usage (month to date)".to_string(),
+                status: if total.calls > 0 && total.failures * 10 > total.calls {
+                    "degraded"
+                } else {
+                    "healthy"
+                }
+                .to_string(),
+                message: Some(format!(
+                    "{} calls ({} failed), {} tokens, {:.0} min audio, {}",
+                    total.calls,
+                    total.failures,
+                    total.input_tokens + total.output_tokens,
+                    total.audio_seconds / 60.0,
+                    cost
+                )),
+                last_check: now.clone(),
+            });
+        }
+        Err(e) => {
+            services.push(ServiceHealth {
+                name: "AI Usage (month to date)".to_string(),
+                status: "error".to_string(),
+                message: Some(format!("Failed to summarize usage: {}", e)),
+                last_check: now.clone(),
+            });
+        }
+    }
+
+    let mut overall = overall_status(&dependencies);
+    if overall == ProbeStatus::Ok && services.iter().any(|s| s.status == "error") {
+        overall = ProbeStatus::Degraded;
+    }
+
+    Ok(SystemHealth {
+        overall,
+        services,
+        dependencies,
+        cached,
+    })
+}
+
+/// Usage totals by provider and day for "today", "week", "month", "30d" or "all"
+#[tauri::command]
+pub async fn get_usage_summary(period: Option<String>) -> Result<UsageSummary, String> {
+    usage_ledger()
+        .summary(period.as_deref().unwrap_or("month"))
+        .await
+}
+
+/// Per-provider prices used for usage cost estimates
+#[tauri::command]
+pub async fn get_usage_prices() -> Result<Vec<UsagePrice>, String> {
+    Ok(usage_ledger().prices())
+}
+
+/// Replace the per-provider prices
+#[tauri::command]
+pub async fn set_usage_prices(
+    state: State<'_, AppState>,
+    prices: Vec<UsagePrice>,
+) -> Result<Vec<UsagePrice>, String> {
+    let prices: Vec<UsagePrice> = prices
+        .into_iter()
+        .map(|mut p| {
+            p.provider = p.provider.trim().to_lowercase();
+            p
+        })
+        .filter(|p| !p.provider.is_empty())
+        .collect();
+
+    if let Some(p) = prices.iter().find(|p| {
+        p.input_per_1k_tokens < 0.0 || p.output_per_1k_tokens < 0.0 || p.per_audio_minute < 0.0
+    }) {
+        return Err(format!("Negative price for {}", p.provider));
+    }
+
+    state
+        .settings
+        .set_usage_prices(&prices)
+        .await
+        .map_err(|e| format!("Failed to save usage prices: {}", e))?;
+
+    usage_ledger().set_prices(prices.clone());
+    Ok(prices)
 }
 
 /// Get ingest queue statistics
@@ -350,19 +913,23 @@ pub async fn get_system_health(state: State<'_, AppState>) -> Result<Vec<Service
 pub async fn get_admin_queue_stats(
     state: State<'_, AppState>,
 ) -> Result<serde_json::Value, String> {
-    let (pending, processing) = state
+    let counts = state
         .ingest_queue
         .lock()
-        .get_stats()
+        .get_state_counts()
         .map_err(|e| format!("Failed to get queue stats: {}", e))?;
+    let total_bytes = match state.ingest_uploader() {
+        Some(uploader) => uploader.get_stats().await?.bytes_uploaded,
+        None => 0,
+    };
 
     Ok(serde_json::json!({
-        "pending": pending,
-        "processing": processing,
-        "completed": 0,  // Not tracked by current API
-        "failed": 0,     // Not tracked by current API
-        "total_bytes": 0,
-        "total_bytes_formatted": "0 B",
+        "pending": counts.pending,
+        "processing": counts.uploading,
+        "completed": counts.done,
+        "failed": counts.failed,
+        "total_bytes": total_bytes,
+        "total_bytes_formatted": StorageManager::format_bytes(total_bytes),
     }))
 }
 
@@ -370,62 +937,49 @@ pub async fn get_admin_queue_stats(
 // Feature Flags Commands
 // ═══════════════════════════════════════════════════════════════════════════
 
-/// Get all feature flags
+/// Get all feature flags (live values)
 #[tauri::command]
 pub async fn get_feature_flags(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
-    let settings = state
-        .settings
-        .get_all()
-        .await
-        .map_err(|e| format!("Failed to get settings: {}", e))?;
-
-    Ok(serde_json::json!({
-        "admin_console_enabled": true,  // Always true if we got here
-        "dedup_enabled": settings.dedup_enabled.unwrap_or(true),
-        "vlm_auto_process": settings.vlm_auto_process,
-        "enable_ingest": settings.enable_ingest.unwrap_or(false),
-        "queue_frames_for_vlm": settings.queue_frames_for_vlm,
-    }))
+    let mut flags = serde_json::to_value(*state.feature_flags.read())
+        .map_err(|e| format!("Failed to serialize feature flags: {}", e))?;
+    flags["admin_console_enabled"] = serde_json::Value::Bool(true); // Always true if we got here
+    Ok(flags)
 }
 
-/// Set a feature flag
+/// Set a feature flag. The live flags are updated immediately so running
+/// loops pick the change up on their next iteration.
 #[tauri::command]
 pub async fn set_feature_flag(
     state: State<'_, AppState>,
     flag: String,
     value: bool,
 ) -> Result<bool, String> {
-    // Update the appropriate flag using dedicated setters
+    // Rejects unknown flag names
+    let old_value = state.feature_flags.read().get(&flag)?;
+
+    // Persists and updates the live flags
+    state
+        .settings
+        .set(&flag, &value.to_string())
+        .await
+        .map_err(|e| format!("Failed to set {}: {}", flag, e))?;
+
     match flag.as_str() {
-        "dedup_enabled" => {
-            state
-                .settings
-                .set("dedup_enabled", &value.to_string())
-                .await
-                .map_err(|e| format!("Failed to set dedup_enabled: {}", e))?;
-        }
+        // The scheduler loop only exists once started
         "vlm_auto_process" => {
-            state
-                .settings
-                .set("vlm_auto_process", &value.to_string())
-                .await
-                .map_err(|e| format!("Failed to set vlm_auto_process: {}", e))?;
-        }
-        "enable_ingest" => {
-            state
-                .settings
-                .set("enable_ingest", &value.to_string())
-                .await
-                .map_err(|e| format!("Failed to set enable_ingest: {}", e))?;
-        }
-        "queue_frames_for_vlm" => {
-            state
-                .settings
-                .set_queue_frames_for_vlm(value)
-                .await
-                .map_err(|e| format!("Failed to set queue_frames_for_vlm: {}", e))?;
+            state.vlm_scheduler.set_enabled(value);
+            if value {
+                state.vlm_scheduler.start();
+            }
         }
-        _ => return Err(format!("Unknown feature flag: {}", flag)),
+        "enable_ingest" => match state.ingest_uploader() {
+            Some(uploader) if value => uploader.wake(),
+            None if value => {
+                crate::subsystems::reload_ingest(&state).await;
+            }
+            _ => {}
+        },
+        _ => {}
     }
 
     // Audit log
@@ -435,15 +989,266 @@ pub async fn set_feature_flag(
             action: "toggle_flag".to_string(),
             target_type: "feature_flag".to_string(),
             target_id: flag.clone(),
-            details: Some(serde_json::json!({ "value": value }).to_string()),
+            details: Some(serde_json::json!({ "old": old_value, "new": value }).to_string()),
             bytes_affected: 0,
         })
         .await;
 
-    log::info!("Feature flag {} set to {}", flag, value);
+    log::info!("Feature flag {} set to {} (was {})", flag, value, old_value);
     Ok(value)
 }
 
+// ═══════════════════════════════════════════════════════════════════════════
+// Settings Export/Import Commands
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Export the settings table to a JSON file for another machine. Secrets are
+/// only included when requested, encrypted with `passphrase`.
+#[tauri::command]
+pub async fn export_settings(
+    state: State<'_, AppState>,
+    path: String,
+    include_secrets: bool,
+    passphrase: Option<String>,
+) -> Result<SettingsExportSummary, String> {
+    let summary = crate::settings_transfer::export_settings(
+        &state.settings,
+        std::path::Path::new(&path),
+        include_secrets,
+        passphrase.as_deref(),
+    )
+    .await?;
+
+    let audit = AuditLog::new(state.database.get_pool().as_ref().clone());
+    let _ = audit
+        .log_action(crate::audit_log::AuditAction {
+            action: "export_settings".to_string(),
+            target_type: "settings".to_string(),
+            target_id: path.clone(),
+            details: Some(
+                serde_json::json!({ "settings": summary.settings, "secrets": summary.secrets })
+                    .to_string(),
+            ),
+            bytes_affected: 0,
+        })
+        .await;
+
+    log::info!(
+        "Exported {} settings ({} secrets) to {}",
+        summary.settings,
+        summary.secrets,
+        path
+    );
+    Ok(summary)
+}
+
+/// Merge a settings export into this machine's settings and re-apply them to
+/// the running services
+#[tauri::command]
+pub async fn import_settings(
+    state: State<'_, AppState>,
+    path: String,
+    passphrase: Option<String>,
+) -> Result<SettingsImportReport, String> {
+    let report = crate::settings_transfer::import_settings(
+        &state.settings,
+        std::path::Path::new(&path),
+        passphrase.as_deref(),
+    )
+    .await?;
+
+    apply_imported_settings(&state, &report.imported).await;
+
+    let audit = AuditLog::new(state.database.get_pool().as_ref().clone());
+    let _ = audit
+        .log_action(crate::audit_log::AuditAction {
+            action: "import_settings".to_string(),
+            target_type: "settings".to_string(),
+            target_id: path.clone(),
+            details: Some(
+                serde_json::json!({
+                    "imported": report.imported,
+                    "skipped": report.skipped.len(),
+                    "failed": report.failed.iter().map(|i| &i.key).collect::<Vec<_>>(),
+                })
+                .to_string(),
+            ),
+            bytes_affected: 0,
+        })
+        .await;
+
+    log::info!(
+        "Imported {} settings from {} ({} skipped, {} failed)",
+        report.imported.len(),
+        path,
+        report.skipped.len(),
+        report.failed.len()
+    );
+    Ok(report)
+}
+
+/// Push imported settings into the running services the same way AppState
+/// startup applies saved settings. Only groups with an imported key are touched.
+async fn apply_imported_settings(state: &AppState, imported: &[String]) {
+    use crate::transcription::ProviderType;
+
+    if imported.is_empty() {
+        return;
+    }
+    let saved = match state.settings.get_all().await {
+        Ok(saved) => saved,
+        Err(e) => {
+            log::warn!("Imported settings saved but not applied: {}", e);
+            return;
+        }
+    };
+    let touched = |prefix: &str| imported.iter().any(|key| key.starts_with(prefix));
+
+    // Transcription keys, provider, glossary and review threshold
+    for (provider, key) in [
+        (ProviderType::Deepgram, &saved.deepgram_api_key),
+        (ProviderType::Gemini, &saved.gemini_api_key),
+        (ProviderType::Gladia, &saved.gladia_api_key),
+        (ProviderType::GoogleSTT, &saved.google_stt_key_json),
+    ] {
+        if let Some(key) = key {
+            state
+                .transcription_manager
+                .set_api_key_for_provider(provider, key.clone());
+        }
+    }
+    if touched("transcription_provider") {
+        let provider = match saved.transcription_provider.as_str() {
+            "gemini" => ProviderType::Gemini,
+            "gladia" => ProviderType::Gladia,
+            "google_stt" => ProviderType::GoogleSTT,
+            "whisper_local" => ProviderType::WhisperLocal,
+            _ => ProviderType::Deepgram,
+        };
+        state.transcription_manager.switch_provider(provider);
+    }
+    if touched("transcription_glossary") {
+        state
+            .transcription_manager
+            .set_glossary(saved.transcription_glossary.clone());
+    }
+    if touched("transcription_failover") {
+        state
+            .transcription_manager
+            .set_failover_policy(saved.transcription_failover);
+    }
+    if touched("low_confidence_threshold") {
+        crate::transcription::review::low_confidence_review()
+            .set_threshold(saved.low_confidence_threshold);
+    }
+
+    // VLM client (per-app and global) and scheduler
+    if touched("vlm_") {
+        {
+            let vlm = state.vlm_client.read();
+            if let Some(ref base_url) = saved.vlm_base_url {
+                vlm.set_base_url(base_url.clone());
+            }
+            if let Some(ref token) = saved.vlm_bearer_token {
+                vlm.set_bearer_token(token.clone());
+            }
+            if let Some(ref model) = saved.vlm_model_primary {
+                vlm.set_model(model.clone());
+            }
+            if let Some(ref model) = saved.vlm_model_fallback {
+                vlm.set_fallback_model(model.clone());
+            }
+        }
+        if let Some(ref base_url) = saved.vlm_base_url {
+            crate::vlm_client::vlm_configure(base_url, saved.vlm_bearer_token.as_deref());
+        }
+
+        state.vlm_scheduler.set_concurrency(saved.vlm_concurrency);
+        state.vlm_scheduler.set_batch_size(saved.vlm_batch_size);
+        state
+            .vlm_scheduler
+            .set_policy(saved.vlm_scheduler_policy.clone());
+        state
+            .vlm_scheduler
+            .set_interval(saved.vlm_process_interval_secs);
+        state.vlm_scheduler.set_enabled(saved.vlm_auto_process);
+        if saved.vlm_auto_process {
+            state.vlm_scheduler.start();
+        }
+    }
+
+    // AI provider
+    if touched("ai_") {
+        crate::ai_client::set_remote_provider(crate::ai_client::RemoteProvider::from_settings(
+            &saved,
+        ));
+        crate::model_router::model_router().set_default_chat_model(saved.ai_chat_model.clone());
+    }
+
+    // Knowledge base
+    if touched("pinecone_") {
+        match (&saved.pinecone_api_key, &saved.pinecone_index_host) {
+            (Some(key), Some(host)) if !key.is_empty() && !host.is_empty() => {
+                state.pinecone_client.read().configure(
+                    key.clone(),
+                    host.clone(),
+                    saved.pinecone_namespace.clone(),
+                    crate::pinecone_client::PineconeNamespaces::from_settings(&saved),
+                );
+            }
+            _ => {}
+        }
+    }
+    if touched("supabase_connection_string") {
+        if let Some(ref conn) = saved.supabase_connection_string {
+            state
+                .supabase_client
+                .read()
+                .set_connection_string(conn.clone());
+        }
+    }
+
+    // Vault, webhooks, privacy, pricing and shortcuts
+    if touched("obsidian_vault_path") {
+        if let Some(vault_path) = saved.obsidian_vault_path.clone() {
+            state.vault_manager.set_vault_path(vault_path);
+        }
+    }
+    if touched("webhook_") {
+        crate::integrations::webhooks()
+            .configure(crate::integrations::WebhookConfig::from_settings(&saved));
+    }
+    if touched("privacy_rules") {
+        if let Err(e) = crate::privacy_filter::set_rules(saved.privacy_rules.clone()) {
+            log::warn!("Ignoring imported privacy rules: {}", e);
+        }
+    }
+    if touched("redaction_mode") {
+        crate::privacy_filter::set_redaction_mode(
+            crate::privacy_filter::RedactionMode::parse(&saved.redaction_mode).unwrap_or_default(),
+        );
+    }
+    if touched("usage_prices") {
+        usage_ledger().set_prices(saved.usage_prices.clone());
+    }
+    if touched("global_shortcuts") {
+        crate::shortcuts::shortcuts().apply(&saved.global_shortcuts);
+    }
+    if touched("check_in_policy") {
+        state.interaction_loop.set_checkin_policy(saved.check_in_policy);
+    }
+
+    // Feature flags were updated by SettingsManager::set
+    if touched("enable_ingest") && saved.enable_ingest.unwrap_or(false) {
+        match state.ingest_uploader() {
+            Some(uploader) => uploader.wake(),
+            None => {
+                crate::subsystems::reload_ingest(state).await;
+            }
+        }
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // Learned Data Commands (M2)
 // ═══════════════════════════════════════════════════════════════════════════
@@ -565,6 +1370,50 @@ pub async fn restore_data_version(
     Ok(result)
 }
 
+/// Bulk edit activity_log rows matching `filter`. Dry run unless `apply` is
+/// true: call without it first to preview affected counts and sample rows.
+#[tauri::command]
+pub async fn bulk_edit_learned_data(
+    state: State<'_, AppState>,
+    filter: BulkFilter,
+    changes: std::collections::BTreeMap<String, String>,
+    apply: Option<bool>,
+) -> Result<BulkEditResult, String> {
+    filter.validate()?;
+    crate::data_editor::validate_bulk_changes(&changes)?;
+    let apply = apply.unwrap_or(false);
+
+    let editor = DataEditor::new(state.database.get_pool().clone(), 10);
+    let result = editor
+        .bulk_edit_learned_data(&filter, &changes, apply)
+        .await
+        .map_err(|e| format!("Failed to bulk edit learned data: {}", e))?;
+
+    // One audit entry per applied bulk edit
+    if result.applied {
+        let audit = AuditLog::new(state.database.get_pool().as_ref().clone());
+        let _ = audit
+            .log_action(crate::audit_log::AuditAction {
+                action: "bulk_edit_learned_data".to_string(),
+                target_type: "activity_log".to_string(),
+                target_id: "bulk".to_string(),
+                details: Some(
+                    serde_json::json!({
+                        "filter": filter,
+                        "changes": changes,
+                        "rows": result.changed,
+                        "versions_written": result.versions_written
+                    })
+                    .to_string(),
+                ),
+                bytes_affected: 0,
+            })
+            .await;
+    }
+
+    Ok(result)
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // Tools Console Commands (M4)
 // ═══════════════════════════════════════════════════════════════════════════
@@ -719,5 +1568,121 @@ pub async fn get_database_stats(state: State<'_, AppState>) -> Result<serde_json
         "entities": entities_count,
         "frame_queue": frame_queue_count,
         "audit_log": audit_log_count,
+        "vlm_cache": state.database.count_vlm_cache().await.unwrap_or(0),
+        "write_queue": state.database.write_queue_stats(),
     }))
 }
+
+/// Clear cached VLM results (e.g. after changing analysis prompts)
+#[tauri::command]
+pub async fn clear_vlm_cache(state: State<'_, AppState>) -> Result<u64, String> {
+    let removed = state
+        .database
+        .clear_vlm_cache()
+        .await
+        .map_err(|e| format!("Failed to clear VLM cache: {}", e))?;
+
+    let audit = AuditLog::new(state.database.get_pool().as_ref().clone());
+    let _ = audit
+        .log_action(crate::audit_log::AuditAction {
+            action: "clear_vlm_cache".to_string(),
+            target_type: "vlm_cache".to_string(),
+            target_id: "all".to_string(),
+            details: Some(serde_json::json!({ "entries_removed": removed }).to_string()),
+            bytes_affected: 0,
+        })
+        .await;
+
+    log::info!("Cleared {} VLM cache entries", removed);
+    Ok(removed)
+}
+
+/// Integrity check, FTS rebuild, VACUUM/REINDEX, dangling keyframe paths and
+/// orphaned rows. Omitted options run every check without deleting anything.
+#[tauri::command]
+pub async fn run_database_maintenance(
+    state: State<'_, AppState>,
+    options: Option<MaintenanceOptions>,
+) -> Result<MaintenanceReport, String> {
+    let options = options.unwrap_or_default();
+    let report = run_maintenance(&state.database, &options).await;
+
+    let audit = AuditLog::new(state.database.get_pool().as_ref().clone());
+    let _ = audit
+        .log_action(crate::audit_log::AuditAction {
+            action: "database_maintenance".to_string(),
+            target_type: "database".to_string(),
+            target_id: "main".to_string(),
+            details: Some(report.summary()),
+            bytes_affected: 0,
+        })
+        .await;
+
+    log::info!("Database maintenance: {}", report.summary());
+    Ok(report)
+}
+
+/// Requeue keyframes of a meeting or date range for VLM analysis (e.g. after
+/// improving a prompt). With `overwrite`, their previous activities and
+/// Pinecone vectors are deleted. The scheduler reports progress through
+/// `vlm-backfill-progress`.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn requeue_frames_for_analysis(
+    meeting_id: Option<String>,
+    date_from: Option<String>,
+    date_to: Option<String>,
+    overwrite: bool,
+    state: State<'_, AppState>,
+) -> Result<RequeueSummary, String> {
+    let scope = BackfillScope::from_params(meeting_id, date_from.as_deref(), date_to.as_deref())?;
+    let pool = state.database.get_pool();
+    let summary = crate::frame_backfill::requeue_frames(&pool, &scope, overwrite).await?;
+
+    if !summary.pinecone_ids.is_empty() {
+        let pinecone_config = state.pinecone_client.read().get_config();
+        if let Some(config) = pinecone_config {
+            if let Err(e) = crate::pinecone_client::pinecone_delete_by_ids(
+                &config,
+                config.namespace_for(crate::pinecone_client::VectorKind::Activity),
+                &summary.pinecone_ids,
+            )
+            .await
+            {
+                log::warn!("Backfill: failed to delete old activity vectors: {}", e);
+            }
+        }
+    }
+
+    state.vlm_scheduler.add_backfill(summary.frames_requeued);
+
+    let audit = AuditLog::new(pool.as_ref().clone());
+    let _ = audit
+        .log_action(crate::audit_log::AuditAction {
+            action: "requeue_frames".to_string(),
+            target_type: "frame_queue".to_string(),
+            target_id: match &scope {
+                BackfillScope::Meeting(id) => id.clone(),
+                BackfillScope::Range { .. } => "range".to_string(),
+            },
+            details: Some(
+                serde_json::json!({
+                    "frames_requeued": summary.frames_requeued,
+                    "newly_queued": summary.newly_queued,
+                    "activities_deleted": summary.activities_deleted,
+                    "date_from": date_from,
+                    "date_to": date_to,
+                    "overwrite": overwrite,
+                })
+                .to_string(),
+            ),
+            bytes_affected: 0,
+        })
+        .await;
+
+    log::info!(
+        "Requeued {} frames for VLM analysis ({} activities deleted)",
+        summary.frames_requeued,
+        summary.activities_deleted
+    );
+    Ok(summary)
+}
@@ -1,10 +1,163 @@
 // noFriction Meetings - AI Client (Centralized API Integration)
 // Provides AI capabilities for meeting analysis using centralized qwen2.5vl API
-
-use parking_lot::RwLock;
+//
+// Chat and completions go to the backend picked by the `ai_provider` setting:
+// the Ollama-style API, or any OpenAI-compatible chat-completions endpoint
+// (OpenAI, Groq, LM Studio, ...). Embeddings always stay on the local backend.
+//
+// Chat and completion requests from every `AIClient` share one per-minute
+// budget (`ai_requests_per_minute`), so bursts like attendee intel can't get
+// the remote key throttled; callers over budget wait for a slot.
+
+use crate::model_router::RoutedPrompt;
+use crate::settings::AppSettings;
+use crate::usage_ledger::{estimate_tokens, UsageCall};
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use std::time::Duration;
+use std::collections::VecDeque;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Provider names in the usage ledger
+const USAGE_PROVIDER: &str = "ollama";
+const USAGE_PROVIDER_REMOTE: &str = "remote";
+
+/// Model used by `AIClient::complete`
+pub const COMPLETION_MODEL: &str = "qwen2.5vl:7b";
+/// Model used by `AIClient::embed` (384-dim all-MiniLM sentence embeddings)
+pub const EMBEDDING_MODEL: &str = "all-minilm";
+
+/// Default shared budget for chat and completion requests
+pub const DEFAULT_REQUESTS_PER_MINUTE: u32 = 60;
+
+/// Backend that serves chat and completions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AIBackend {
+    Local,
+    Remote,
+}
+
+/// OpenAI-compatible endpoint from the `ai_remote_*` settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteProvider {
+    pub url: String, // e.g. "https://api.openai.com/v1"
+    pub api_key: Option<String>,
+    pub model: Option<String>, // None = same model names as the local backend
+}
+
+impl RemoteProvider {
+    /// Remote provider if `ai_provider` is "remote" and a URL is set; otherwise
+    /// requests stay on the local backend
+    pub fn from_settings(settings: &AppSettings) -> Option<Self> {
+        if settings.ai_provider != "remote" {
+            return None;
+        }
+        let url = settings.ai_remote_url.as_deref()?.trim();
+        if url.is_empty() {
+            return None;
+        }
+        Some(Self {
+            url: url.to_string(),
+            api_key: settings.ai_remote_key.clone().filter(|k| !k.is_empty()),
+            model: settings
+                .ai_remote_model
+                .clone()
+                .filter(|m| !m.trim().is_empty()),
+        })
+    }
+
+    fn base_url(&self) -> &str {
+        self.url.trim_end_matches('/')
+    }
+}
+
+fn remote_slot() -> &'static RwLock<Option<RemoteProvider>> {
+    static REMOTE: OnceLock<RwLock<Option<RemoteProvider>>> = OnceLock::new();
+    REMOTE.get_or_init(|| RwLock::new(None))
+}
+
+/// Switch every `AIClient` to a remote provider (None = local backend)
+pub fn set_remote_provider(remote: Option<RemoteProvider>) {
+    *remote_slot().write() = remote;
+}
+
+pub fn remote_provider() -> Option<RemoteProvider> {
+    remote_slot().read().clone()
+}
+
+/// Sliding one-minute window of request start times
+#[derive(Debug)]
+struct RateLimiter {
+    /// 0 = unlimited
+    requests_per_minute: u32,
+    recent: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_minute: u32) -> Self {
+        Self {
+            requests_per_minute,
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Take a slot at `now`, or say how long until one frees up
+    fn try_acquire(&mut self, now: Instant) -> Option<Duration> {
+        if self.requests_per_minute == 0 {
+            return None;
+        }
+        let window = Duration::from_secs(60);
+        while self
+            .recent
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= window)
+        {
+            self.recent.pop_front();
+        }
+        if self.recent.len() < self.requests_per_minute as usize {
+            self.recent.push_back(now);
+            return None;
+        }
+        self.recent
+            .front()
+            .map(|oldest| window.saturating_sub(now.duration_since(*oldest)))
+    }
+}
+
+fn rate_limiter() -> &'static Mutex<RateLimiter> {
+    static LIMITER: OnceLock<Mutex<RateLimiter>> = OnceLock::new();
+    LIMITER.get_or_init(|| Mutex::new(RateLimiter::new(DEFAULT_REQUESTS_PER_MINUTE)))
+}
+
+/// Set the shared chat/completion budget for every `AIClient` (0 = unlimited)
+pub fn set_requests_per_minute(requests_per_minute: u32) {
+    rate_limiter().lock().requests_per_minute = requests_per_minute;
+}
+
+/// Wait until the shared budget has room for one more request
+async fn acquire_request_slot() {
+    loop {
+        let wait = rate_limiter().lock().try_acquire(Instant::now());
+        match wait {
+            None => return,
+            Some(wait) => {
+                log::debug!("AI rate limit reached, waiting {:?}", wait);
+                tokio::time::sleep(wait.max(Duration::from_millis(10))).await;
+            }
+        }
+    }
+}
+
+/// Result of `check_ai_provider`
+#[derive(Debug, Clone, Serialize)]
+pub struct AIProviderStatus {
+    pub backend: AIBackend,
+    pub url: String,
+    pub reachable: bool,
+    pub models: Vec<String>,
+    pub error: Option<String>,
+}
 
 /// AI Model preset for different use cases
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -105,6 +258,9 @@ struct OllamaModelInfo {
 #[derive(Debug, Deserialize)]
 struct OllamaChatResponse {
     message: OllamaMessage,
+    /// Token counts reported by Ollama (absent on some proxies)
+    prompt_eval_count: Option<u64>,
+    eval_count: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -112,6 +268,36 @@ struct OllamaMessage {
     content: String,
 }
 
+/// OpenAI-style chat completion response
+#[derive(Debug, Deserialize)]
+struct RemoteChatResponse {
+    choices: Vec<RemoteChoice>,
+    usage: Option<RemoteUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteChoice {
+    message: RemoteMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteMessage {
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+}
+
+/// Text of a finished request plus any token counts the server reported
+struct Completion {
+    content: String,
+    input_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+}
+
 /// AI Client for Centralized API (via SSH tunnel)
 pub struct AIClient {
     base_url: Arc<RwLock<String>>,
@@ -164,8 +350,12 @@ impl AIClient {
             .map(|t| format!("Bearer {}", t))
     }
 
-    /// Check if API is available
+    /// Check if the active backend is available
     pub async fn is_available(&self) -> bool {
+        if let Some(remote) = remote_provider() {
+            return self.list_remote_models(&remote).await.is_ok();
+        }
+
         let url = format!("{}/api/tags", self.base_url.read());
 
         let mut request = self.client.get(&url);
@@ -217,16 +407,22 @@ impl AIClient {
             .collect())
     }
 
-    /// Chat with a model using a preset
-    pub async fn chat(
-        &self,
+    /// Estimated prompt tokens of a chat request
+    fn prompt_tokens(preset: &AIPreset, messages: &[ChatMessage], context: Option<&str>) -> u64 {
+        estimate_tokens(&preset.system_prompt)
+            + context.map(estimate_tokens).unwrap_or(0)
+            + messages
+                .iter()
+                .map(|m| estimate_tokens(&m.content))
+                .sum::<u64>()
+    }
+
+    /// Messages for a preset: system prompt, optional context, then the turns
+    fn preset_messages(
         preset: &AIPreset,
         messages: Vec<ChatMessage>,
         context: Option<&str>,
-    ) -> Result<String, String> {
-        let url = format!("{}/api/chat", self.base_url.read());
-
-        // Build messages with system prompt and context
+    ) -> Vec<serde_json::Value> {
         let mut chat_messages = vec![serde_json::json!({
             "role": "system",
             "content": &preset.system_prompt
@@ -247,13 +443,25 @@ impl AIClient {
                 "content": msg.content
             }));
         }
+        chat_messages
+    }
+
+    /// Ollama /api/chat request
+    fn chat_request(
+        &self,
+        model: &str,
+        messages: Vec<serde_json::Value>,
+        temperature: f32,
+        stream: bool,
+    ) -> reqwest::RequestBuilder {
+        let url = format!("{}/api/chat", self.base_url.read());
 
         let body = serde_json::json!({
-            "model": &preset.model,
-            "messages": chat_messages,
-            "stream": false,
+            "model": model,
+            "messages": messages,
+            "stream": stream,
             "options": {
-                "temperature": preset.temperature
+                "temperature": temperature
             }
         });
 
@@ -261,28 +469,146 @@ impl AIClient {
         if let Some(auth) = self.get_auth_header() {
             request = request.header("Authorization", auth);
         }
+        request
+    }
 
-        let response = request
+    /// OpenAI-compatible /chat/completions request
+    fn remote_request(
+        &self,
+        remote: &RemoteProvider,
+        model: &str,
+        messages: Vec<serde_json::Value>,
+        temperature: Option<f32>,
+        stream: bool,
+    ) -> reqwest::RequestBuilder {
+        let mut body = serde_json::json!({
+            "model": model,
+            "messages": messages,
+            "stream": stream,
+        });
+        if let Some(temperature) = temperature {
+            body["temperature"] = serde_json::json!(temperature);
+        }
+
+        let mut request = self
+            .client
+            .post(format!("{}/chat/completions", remote.base_url()))
+            .json(&body);
+        if let Some(key) = remote.api_key.as_deref().filter(|k| !k.is_empty()) {
+            request = request.bearer_auth(key);
+        }
+        request
+    }
+
+    /// Chat with a model using a preset
+    pub async fn chat(
+        &self,
+        preset: &AIPreset,
+        messages: Vec<ChatMessage>,
+        context: Option<&str>,
+    ) -> Result<String, String> {
+        let remote = remote_provider();
+        let model = resolve_model(remote.as_ref(), &preset.model);
+        let call = UsageCall::start(usage_provider(remote.as_ref()), "chat", &model);
+        let input_tokens = Self::prompt_tokens(preset, &messages, context);
+        let messages = Self::preset_messages(preset, messages, context);
+
+        acquire_request_slot().await;
+        let result = match &remote {
+            Some(remote) => {
+                self.send_remote(remote, &model, messages, Some(preset.temperature))
+                    .await
+            }
+            None => self.send_chat(&model, messages, preset.temperature).await,
+        };
+        record_completion(call, input_tokens, &result);
+        result.map(|c| c.content)
+    }
+
+    async fn send_chat(
+        &self,
+        model: &str,
+        messages: Vec<serde_json::Value>,
+        temperature: f32,
+    ) -> Result<Completion, String> {
+        let response = self
+            .chat_request(model, messages, temperature, false)
             .send()
             .await
             .map_err(|e| format!("Failed to send request: {}", e))?;
+        let response = check_response(response).await?;
 
-        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-            return Err("Unauthorized: Invalid or missing bearer token".to_string());
-        }
+        let data: OllamaChatResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(format!("API error ({}): {}", status, text));
-        }
+        Ok(Completion {
+            content: data.message.content,
+            input_tokens: data.prompt_eval_count,
+            output_tokens: data.eval_count,
+        })
+    }
 
-        let data: OllamaChatResponse = response
+    async fn send_remote(
+        &self,
+        remote: &RemoteProvider,
+        model: &str,
+        messages: Vec<serde_json::Value>,
+        temperature: Option<f32>,
+    ) -> Result<Completion, String> {
+        let response = self
+            .remote_request(remote, model, messages, temperature, false)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request: {}", e))?;
+        let response = check_response(response).await?;
+
+        let data: RemoteChatResponse = response
             .json()
             .await
             .map_err(|e| format!("Failed to parse response: {}", e))?;
 
-        Ok(data.message.content)
+        let content = data
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|c| c.message.content)
+            .ok_or_else(|| "No response from API".to_string())?;
+
+        Ok(Completion {
+            content,
+            input_tokens: data.usage.as_ref().map(|u| u.prompt_tokens),
+            output_tokens: data.usage.as_ref().map(|u| u.completion_tokens),
+        })
+    }
+
+    /// Chat with a preset, passing each text fragment to `on_delta` as it
+    /// arrives. Returns the full response.
+    pub async fn chat_stream(
+        &self,
+        preset: &AIPreset,
+        messages: Vec<ChatMessage>,
+        context: Option<&str>,
+        on_delta: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<String, String> {
+        let remote = remote_provider();
+        let model = resolve_model(remote.as_ref(), &preset.model);
+        let call = UsageCall::start(usage_provider(remote.as_ref()), "chat", &model);
+        let input_tokens = Self::prompt_tokens(preset, &messages, context);
+        let messages = Self::preset_messages(preset, messages, context);
+
+        let request = match &remote {
+            Some(remote) => {
+                self.remote_request(remote, &model, messages, Some(preset.temperature), true)
+            }
+            None => self.chat_request(&model, messages, preset.temperature, true),
+        };
+        acquire_request_slot().await;
+        let result = send_stream(request, on_delta).await;
+        let output_tokens = result.as_deref().map(estimate_tokens).unwrap_or(0);
+        call.finish(input_tokens, output_tokens, result.is_ok());
+        result
     }
 
     /// Quick summarize helper
@@ -295,6 +621,21 @@ impl AIClient {
         self.chat(&preset, messages, Some(content)).await
     }
 
+    /// Streaming variant of `summarize`
+    pub async fn summarize_stream(
+        &self,
+        content: &str,
+        on_delta: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<String, String> {
+        let preset = AIPreset::summarize();
+        let messages = vec![ChatMessage {
+            role: "user".to_string(),
+            content: "Please summarize this meeting.".to_string(),
+        }];
+        self.chat_stream(&preset, messages, Some(content), on_delta)
+            .await
+    }
+
     /// Quick action items helper
     pub async fn extract_action_items(&self, content: &str) -> Result<String, String> {
         let preset = AIPreset::action_items();
@@ -307,10 +648,65 @@ impl AIClient {
 
     /// Generic prompt completion
     pub async fn complete(&self, prompt: &str) -> Result<String, String> {
+        let remote = remote_provider();
+        let model = resolve_model(remote.as_ref(), COMPLETION_MODEL);
+        let call = UsageCall::start(usage_provider(remote.as_ref()), "completion", &model);
+
+        acquire_request_slot().await;
+        let result = match &remote {
+            Some(remote) => {
+                let messages = vec![serde_json::json!({ "role": "user", "content": prompt })];
+                self.send_remote(remote, &model, messages, None).await
+            }
+            None => self.send_completion(&model, prompt).await,
+        };
+        record_completion(call, estimate_tokens(prompt), &result);
+        result.map(|c| c.content)
+    }
+
+    /// Completion for a use case, with the prompt and model the router picks
+    pub async fn complete_for(&self, use_case: &str, prompt: &str) -> Result<String, String> {
+        let route = crate::model_router::resolve(use_case).await;
+        self.complete_routed(&route, prompt).await
+    }
+
+    /// Completion with a resolved route: its system prompt, model and temperature
+    pub async fn complete_routed(
+        &self,
+        route: &RoutedPrompt,
+        prompt: &str,
+    ) -> Result<String, String> {
+        let remote = remote_provider();
+        let call = UsageCall::start(usage_provider(remote.as_ref()), "completion", &route.model);
+
+        let mut messages = Vec::new();
+        let mut input_tokens = estimate_tokens(prompt);
+        if let Some(ref system_prompt) = route.system_prompt {
+            messages.push(serde_json::json!({ "role": "system", "content": system_prompt }));
+            input_tokens += estimate_tokens(system_prompt);
+        }
+        messages.push(serde_json::json!({ "role": "user", "content": prompt }));
+
+        acquire_request_slot().await;
+        let result = match &remote {
+            Some(remote) => {
+                self.send_remote(remote, &route.model, messages, Some(route.temperature))
+                    .await
+            }
+            None => {
+                self.send_chat(&route.model, messages, route.temperature)
+                    .await
+            }
+        };
+        record_completion(call, input_tokens, &result);
+        result.map(|c| c.content)
+    }
+
+    async fn send_completion(&self, model: &str, prompt: &str) -> Result<Completion, String> {
         let url = format!("{}/api/generate", self.base_url.read());
 
         let body = serde_json::json!({
-            "model": "qwen2.5vl:7b",
+            "model": model,
             "prompt": prompt,
             "stream": false
         });
@@ -338,14 +734,188 @@ impl AIClient {
             .await
             .map_err(|e| format!("Failed to parse response: {}", e))?;
 
-        result
+        let content = result
             .get("response")
             .and_then(|r| r.as_str())
             .map(|s| s.to_string())
-            .ok_or_else(|| "No response from API".to_string())
+            .ok_or_else(|| "No response from API".to_string())?;
+
+        Ok(Completion {
+            content,
+            input_tokens: result.get("prompt_eval_count").and_then(|v| v.as_u64()),
+            output_tokens: result.get("eval_count").and_then(|v| v.as_u64()),
+        })
+    }
+
+    /// Which backend is active, whether it answers, and the models it offers
+    pub async fn check_provider(&self) -> AIProviderStatus {
+        match remote_provider() {
+            Some(remote) => {
+                let models = self.list_remote_models(&remote).await;
+                AIProviderStatus {
+                    backend: AIBackend::Remote,
+                    url: remote.base_url().to_string(),
+                    reachable: models.is_ok(),
+                    models: models.as_ref().cloned().unwrap_or_default(),
+                    error: models.err(),
+                }
+            }
+            None => {
+                let models = self.list_models().await;
+                AIProviderStatus {
+                    backend: AIBackend::Local,
+                    url: self.base_url.read().clone(),
+                    reachable: models.is_ok(),
+                    models: models
+                        .as_ref()
+                        .map(|m| m.iter().map(|m| m.name.clone()).collect())
+                        .unwrap_or_default(),
+                    error: models.err(),
+                }
+            }
+        }
+    }
+
+    /// Model ids from an OpenAI-compatible /models endpoint
+    async fn list_remote_models(&self, remote: &RemoteProvider) -> Result<Vec<String>, String> {
+        let mut request = self.client.get(format!("{}/models", remote.base_url()));
+        if let Some(key) = remote.api_key.as_deref().filter(|k| !k.is_empty()) {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to connect to API: {}", e))?;
+        let response = check_response(response).await?;
+
+        #[derive(Deserialize)]
+        struct ModelList {
+            data: Vec<ModelEntry>,
+        }
+
+        #[derive(Deserialize)]
+        struct ModelEntry {
+            id: String,
+        }
+
+        let list: ModelList = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+        Ok(list.data.into_iter().map(|m| m.id).collect())
+    }
+
+    /// Embed texts with the local embedding model (one vector per input)
+    pub async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let call = UsageCall::start(USAGE_PROVIDER, "embedding", EMBEDDING_MODEL);
+        let result = self.send_embed(texts).await;
+        let input_tokens = texts.iter().map(|t| estimate_tokens(t)).sum();
+        call.finish(input_tokens, 0, result.is_ok());
+        result
+    }
+
+    async fn send_embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let url = format!("{}/api/embed", self.base_url.read());
+
+        let body = serde_json::json!({
+            "model": EMBEDDING_MODEL,
+            "input": texts,
+        });
+
+        let mut request = self.client.post(&url).json(&body);
+        if let Some(auth) = self.get_auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to call embedding API: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "Embedding API returned error: {}",
+                response.status()
+            ));
+        }
+
+        #[derive(Deserialize)]
+        struct EmbedResponse {
+            embeddings: Vec<Vec<f32>>,
+        }
+
+        let result: EmbedResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse embedding response: {}", e))?;
+
+        if result.embeddings.len() != texts.len() {
+            return Err(format!(
+                "Embedding API returned {} vectors for {} inputs",
+                result.embeddings.len(),
+                texts.len()
+            ));
+        }
+        Ok(result.embeddings)
     }
 }
 
+/// Model to request: the remote override if one is set, else the local name
+fn resolve_model(remote: Option<&RemoteProvider>, local_model: &str) -> String {
+    remote
+        .and_then(|r| r.model.clone())
+        .unwrap_or_else(|| local_model.to_string())
+}
+
+fn usage_provider(remote: Option<&RemoteProvider>) -> &'static str {
+    if remote.is_some() {
+        USAGE_PROVIDER_REMOTE
+    } else {
+        USAGE_PROVIDER
+    }
+}
+
+/// Record a completion, preferring the server's token counts over estimates
+fn record_completion(call: UsageCall, input_tokens: u64, result: &Result<Completion, String>) {
+    match result {
+        Ok(c) => call.finish(
+            c.input_tokens.unwrap_or(input_tokens),
+            c.output_tokens
+                .unwrap_or_else(|| estimate_tokens(&c.content)),
+            true,
+        ),
+        Err(_) => call.finish(input_tokens, 0, false),
+    }
+}
+
+/// Map 401 and other failures to errors
+async fn check_response(response: reqwest::Response) -> Result<reqwest::Response, String> {
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Err("Unauthorized: Invalid or missing bearer token".to_string());
+    }
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("API error ({}): {}", status, text));
+    }
+    Ok(response)
+}
+
+/// Send a streaming request and read it with `ai_stream::read_stream`
+async fn send_stream(
+    request: reqwest::RequestBuilder,
+    on_delta: &mut (dyn FnMut(&str) + Send),
+) -> Result<String, String> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request: {}", e))?;
+    let response = check_response(response).await?;
+    crate::ai_stream::read_stream(response, on_delta).await
+}
+
 fn format_size(bytes: i64) -> String {
     const KB: i64 = 1024;
     const MB: i64 = KB * 1024;
@@ -377,3 +947,76 @@ impl Clone for AIClient {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote_settings(url: Option<&str>, model: Option<&str>) -> AppSettings {
+        AppSettings {
+            ai_provider: "remote".to_string(),
+            ai_remote_url: url.map(str::to_string),
+            ai_remote_key: Some("sk-test".to_string()),
+            ai_remote_model: model.map(str::to_string),
+            ..AppSettings::default()
+        }
+    }
+
+    #[test]
+    fn remote_provider_requires_remote_mode_and_url() {
+        assert!(RemoteProvider::from_settings(&remote_settings(None, None)).is_none());
+        assert!(RemoteProvider::from_settings(&remote_settings(Some("  "), None)).is_none());
+
+        let mut local = remote_settings(Some("https://api.openai.com/v1"), None);
+        local.ai_provider = "local".to_string();
+        assert!(RemoteProvider::from_settings(&local).is_none());
+
+        let remote = RemoteProvider::from_settings(&remote_settings(
+            Some("https://api.openai.com/v1/"),
+            None,
+        ))
+        .unwrap();
+        assert_eq!(remote.base_url(), "https://api.openai.com/v1");
+        assert_eq!(remote.api_key.as_deref(), Some("sk-test"));
+    }
+
+    #[test]
+    fn rate_limiter_waits_for_the_oldest_request() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new(2);
+        assert_eq!(limiter.try_acquire(start), None);
+        assert_eq!(limiter.try_acquire(start + Duration::from_secs(10)), None);
+        assert_eq!(
+            limiter.try_acquire(start + Duration::from_secs(20)),
+            Some(Duration::from_secs(40))
+        );
+        // The first request has left the window
+        assert_eq!(limiter.try_acquire(start + Duration::from_secs(60)), None);
+
+        let mut unlimited = RateLimiter::new(0);
+        assert!((0..100).all(|_| unlimited.try_acquire(start).is_none()));
+    }
+
+    #[test]
+    fn remote_model_overrides_preset_model() {
+        let remote = RemoteProvider::from_settings(&remote_settings(
+            Some("https://api.groq.com/openai/v1"),
+            Some("llama-3.1-8b-instant"),
+        ))
+        .unwrap();
+        assert_eq!(
+            resolve_model(Some(&remote), COMPLETION_MODEL),
+            "llama-3.1-8b-instant"
+        );
+        assert_eq!(resolve_model(None, COMPLETION_MODEL), COMPLETION_MODEL);
+
+        let no_override = RemoteProvider {
+            model: None,
+            ..remote
+        };
+        assert_eq!(
+            resolve_model(Some(&no_override), COMPLETION_MODEL),
+            COMPLETION_MODEL
+        );
+    }
+}
@@ -0,0 +1,315 @@
+// noFriction Meetings - AI Streaming
+// Incremental AI responses delivered to the frontend as `ai-stream` events.
+//
+// Each stream runs in its own task keyed by a request id. Cancelling aborts the
+// task, which drops the reqwest body and closes the connection mid-response.
+
+use futures_util::StreamExt;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Emitter};
+use tokio::task::AbortHandle;
+
+pub const AI_STREAM_EVENT: &str = "ai-stream";
+
+/// Payload of one `ai-stream` event
+#[derive(Debug, Clone, Serialize)]
+pub struct AiStreamChunk {
+    pub request_id: String,
+    /// Text appended since the previous chunk (empty on the final chunk)
+    pub delta: String,
+    pub done: bool,
+    /// Set on the final chunk when the stream failed or was cancelled
+    pub error: Option<String>,
+}
+
+/// Emits the chunks of one stream
+#[derive(Clone)]
+pub struct StreamSink {
+    app: AppHandle,
+    request_id: String,
+}
+
+impl StreamSink {
+    pub fn delta(&self, text: &str) {
+        if !text.is_empty() {
+            emit_chunk(&self.app, &self.request_id, text.to_string(), false, None);
+        }
+    }
+}
+
+fn emit_chunk(app: &AppHandle, request_id: &str, delta: String, done: bool, error: Option<String>) {
+    let chunk = AiStreamChunk {
+        request_id: request_id.to_string(),
+        delta,
+        done,
+        error,
+    };
+    if let Err(e) = app.emit(AI_STREAM_EVENT, chunk) {
+        log::warn!("Failed to emit {} event: {}", AI_STREAM_EVENT, e);
+    }
+}
+
+/// In-flight streams by request id
+fn active_streams() -> &'static Mutex<HashMap<String, AbortHandle>> {
+    static STREAMS: OnceLock<Mutex<HashMap<String, AbortHandle>>> = OnceLock::new();
+    STREAMS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Run `run` in the background and return its request id immediately. The
+/// future pushes text through the sink; its result becomes the final chunk.
+pub fn spawn_stream<F, Fut>(app: AppHandle, run: F) -> String
+where
+    F: FnOnce(StreamSink) -> Fut,
+    Fut: Future<Output = Result<(), String>> + Send + 'static,
+{
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let sink = StreamSink {
+        app: app.clone(),
+        request_id: request_id.clone(),
+    };
+    let future = run(sink);
+
+    // Hold the registry while spawning so a stream that finishes instantly
+    // cannot deregister before it is registered
+    let mut streams = active_streams().lock().unwrap_or_else(|e| e.into_inner());
+    let id = request_id.clone();
+    let task = tokio::spawn(async move {
+        let result = future.await;
+        // Whoever removes the entry (this task or `cancel`) sends the final chunk
+        let still_active = active_streams()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&id)
+            .is_some();
+        if still_active {
+            if let Err(e) = &result {
+                log::warn!("AI stream {} failed: {}", id, e);
+            }
+            emit_chunk(&app, &id, String::new(), true, result.err());
+        }
+    });
+    streams.insert(request_id.clone(), task.abort_handle());
+
+    request_id
+}
+
+/// Abort a running stream. Returns false if it already finished.
+pub fn cancel(app: &AppHandle, request_id: &str) -> bool {
+    let handle = active_streams()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(request_id);
+    match handle {
+        Some(handle) => {
+            handle.abort();
+            emit_chunk(
+                app,
+                request_id,
+                String::new(),
+                true,
+                Some("cancelled".to_string()),
+            );
+            true
+        }
+        None => false,
+    }
+}
+
+/// Read a streamed completion, passing each text fragment to `on_delta`, and
+/// return the full text. Understands Ollama NDJSON and `data:` SSE lines. A
+/// server that ignores `stream: true` and answers with one JSON document is
+/// delivered as a single fragment.
+pub async fn read_stream(
+    response: reqwest::Response,
+    on_delta: &mut (dyn FnMut(&str) + Send),
+) -> Result<String, String> {
+    let mut decoder = LineDecoder::default();
+    let mut full = String::new();
+    let mut unparsed = String::new();
+    let mut body = response.bytes_stream();
+
+    while let Some(bytes) = body.next().await {
+        let bytes = bytes.map_err(|e| format!("Failed to read stream: {}", e))?;
+        for line in decoder.push(&bytes) {
+            handle_line(&line, on_delta, &mut full, &mut unparsed)?;
+        }
+    }
+    if let Some(line) = decoder.finish() {
+        handle_line(&line, on_delta, &mut full, &mut unparsed)?;
+    }
+
+    if full.is_empty() && !unparsed.trim().is_empty() {
+        // Not line-delimited: treat the whole body as one response
+        match serde_json::from_str::<serde_json::Value>(&unparsed) {
+            Ok(value) => {
+                if let Some(text) = extract_text(&value) {
+                    on_delta(text);
+                    full.push_str(text);
+                }
+            }
+            Err(e) => return Err(format!("Failed to parse response: {}", e)),
+        }
+    }
+
+    Ok(full)
+}
+
+fn handle_line(
+    line: &str,
+    on_delta: &mut (dyn FnMut(&str) + Send),
+    full: &mut String,
+    unparsed: &mut String,
+) -> Result<(), String> {
+    match parse_line(line) {
+        StreamLine::Text(text) => {
+            on_delta(&text);
+            full.push_str(&text);
+        }
+        StreamLine::Error(e) => return Err(format!("Stream error: {}", e)),
+        StreamLine::Ignore => {}
+        StreamLine::Unparsed => {
+            unparsed.push_str(line);
+            unparsed.push('\n');
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+enum StreamLine {
+    Text(String),
+    Error(String),
+    /// Blank lines, SSE fields, end markers and metadata-only objects
+    Ignore,
+    /// Not JSON on its own (part of a pretty-printed document)
+    Unparsed,
+}
+
+fn parse_line(line: &str) -> StreamLine {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with(':') {
+        return StreamLine::Ignore;
+    }
+    let payload = match line.strip_prefix("data:") {
+        Some(data) => data.trim_start(),
+        None if line.starts_with("event:") || line.starts_with("id:") => return StreamLine::Ignore,
+        None => line,
+    };
+    if payload == "[DONE]" {
+        return StreamLine::Ignore;
+    }
+
+    match serde_json::from_str::<serde_json::Value>(payload) {
+        Ok(value) if value.is_object() => {
+            if let Some(e) = value.get("error") {
+                let message = e
+                    .as_str()
+                    .map(String::from)
+                    .or_else(|| e.get("message").and_then(|m| m.as_str()).map(String::from))
+                    .unwrap_or_else(|| e.to_string());
+                return StreamLine::Error(message);
+            }
+            match extract_text(&value) {
+                Some(text) if !text.is_empty() => StreamLine::Text(text.to_string()),
+                _ => StreamLine::Ignore,
+            }
+        }
+        _ => StreamLine::Unparsed,
+    }
+}
+
+/// Text fragment of a chat (`message.content`), generate (`response`),
+/// TheBrain SSE (`content`) or OpenAI-style (`choices[0].delta.content`) chunk
+fn extract_text(value: &serde_json::Value) -> Option<&str> {
+    value
+        .pointer("/message/content")
+        .or_else(|| value.get("response"))
+        .or_else(|| value.get("content"))
+        .or_else(|| value.pointer("/choices/0/delta/content"))
+        .or_else(|| value.pointer("/choices/0/message/content"))
+        .and_then(|v| v.as_str())
+}
+
+/// Splits a byte stream into lines, keeping multi-byte characters that
+/// straddle chunk boundaries intact
+#[derive(Default)]
+struct LineDecoder {
+    buffer: Vec<u8>,
+}
+
+impl LineDecoder {
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+        let mut lines = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            lines.push(decode_line(&line[..line.len() - 1]));
+        }
+        lines
+    }
+
+    fn finish(&mut self) -> Option<String> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        let line = decode_line(&self.buffer);
+        self.buffer.clear();
+        Some(line)
+    }
+}
+
+fn decode_line(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_suffix(b"\r").unwrap_or(bytes);
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_decoder_joins_split_characters() {
+        let mut decoder = LineDecoder::default();
+        let text = "caf\u{e9}\r\nna\u{ef}ve\n";
+        let bytes = text.as_bytes();
+        // Split inside the two-byte 'é'
+        assert!(decoder.push(&bytes[..4]).is_empty());
+        assert_eq!(decoder.push(&bytes[4..9]), vec!["caf\u{e9}"]);
+        assert_eq!(decoder.push(&bytes[9..]), vec!["na\u{ef}ve"]);
+        assert_eq!(decoder.finish(), None);
+
+        decoder.push(b"tail");
+        assert_eq!(decoder.finish(), Some("tail".to_string()));
+    }
+
+    #[test]
+    fn test_parse_line_formats() {
+        assert_eq!(
+            parse_line(r#"{"message":{"role":"assistant","content":"Hel"},"done":false}"#),
+            StreamLine::Text("Hel".to_string())
+        );
+        assert_eq!(
+            parse_line(r#"data: {"content": "lo"}"#),
+            StreamLine::Text("lo".to_string())
+        );
+        assert_eq!(
+            parse_line(r#"data:{"choices":[{"delta":{"content":"!"}}]}"#),
+            StreamLine::Text("!".to_string())
+        );
+        assert_eq!(
+            parse_line(r#"{"message":{"content":""},"done":true,"eval_count":12}"#),
+            StreamLine::Ignore
+        );
+        assert_eq!(parse_line("data: [DONE]"), StreamLine::Ignore);
+        assert_eq!(parse_line("event: message"), StreamLine::Ignore);
+        assert_eq!(parse_line(""), StreamLine::Ignore);
+        assert_eq!(
+            parse_line(r#"{"error":"model not found"}"#),
+            StreamLine::Error("model not found".to_string())
+        );
+        assert_eq!(parse_line(r#"  "message": {"#), StreamLine::Unparsed);
+    }
+}
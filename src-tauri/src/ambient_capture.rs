@@ -5,19 +5,26 @@
 // - Always-on screen capture at configurable interval (default: 5s)
 // - Frame diff detection to skip duplicates
 // - Power-state aware (pauses on sleep/idle, resumes on wake/activity)
+// - Input idle detection: ambient capture pauses after N idle minutes and
+//   resumes on the next input, leaving an idle gap on the timeline
 // - Rolling storage cleanup based on retention period
 // - VLM integration for context tagging
+// - Rolling "last N minutes" buffer (audio in memory, screen states on disk
+//   scratch) that can be promoted to a meeting after the fact
 
-use crate::capture_engine::CapturedFrame;
+use crate::capture_engine::{AudioBuffer, CaptureEngine, CapturedFrame};
+use crate::dedupe_gate::AverageHash;
+use crate::feature_flags::SharedFeatureFlags;
 use crate::power_manager::{PowerManager, PowerState};
+use crate::transcription::audio_mixer::{AudioMixer, MIX_SAMPLE_RATE};
 // use crate::vlm_client::VLMClient;
 use chrono::{DateTime, Duration, Utc};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::VecDeque;
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter};
 
@@ -97,6 +104,402 @@ pub struct AmbientFrameMeta {
     pub storage_path: PathBuf,
 }
 
+/// Default length of the rolling ambient buffer
+pub const DEFAULT_BUFFER_MINUTES: u32 = 10;
+/// Default minutes without keyboard/mouse input before ambient capture pauses
+pub const DEFAULT_IDLE_TIMEOUT_MINS: u32 = 5;
+/// How often input idleness is polled (also bounds how late capture resumes)
+const IDLE_POLL_SECS: u64 = 2;
+/// Consecutive frames this close (pHash bits) extend one buffered screen state
+const BUFFER_DEDUP_DISTANCE: u32 = 3;
+
+/// One screen state held in the ambient buffer (keyframe on disk scratch)
+#[derive(Debug, Clone)]
+pub struct BufferedScreenState {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// None for states redacted by the privacy filter
+    pub path: Option<PathBuf>,
+    phash: u64,
+    bytes: u64,
+}
+
+impl BufferedScreenState {
+    pub fn is_redacted(&self) -> bool {
+        self.path.is_none()
+    }
+}
+
+/// Span during which ambient capture was paused for inactivity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdleGap {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+}
+
+impl IdleGap {
+    pub fn duration_ms(&self) -> i64 {
+        (self.ended_at - self.started_at).num_milliseconds()
+    }
+}
+
+/// Everything taken out of the buffer when it is promoted to a meeting
+pub struct AmbientSnapshot {
+    /// 16kHz mono audio, oldest first
+    pub audio: Vec<f32>,
+    pub audio_started_at: Option<DateTime<Utc>>,
+    pub screen_states: Vec<BufferedScreenState>,
+    pub idle_gaps: Vec<IdleGap>,
+}
+
+impl AmbientSnapshot {
+    pub fn is_empty(&self) -> bool {
+        self.audio.is_empty() && self.screen_states.is_empty()
+    }
+
+    /// Earliest buffered moment (audio or screen)
+    pub fn started_at(&self) -> Option<DateTime<Utc>> {
+        let first_state = self.screen_states.first().map(|s| s.started_at);
+        match (self.audio_started_at, first_state) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+/// Buffer usage reported in capture metrics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmbientBufferStats {
+    pub active: bool,
+    pub minutes: u32,
+    pub audio_seconds: f64,
+    pub screen_states: usize,
+    pub memory_bytes: u64,
+    pub disk_bytes: u64,
+}
+
+/// Rolling buffer of the last N minutes of ambient audio and screen states.
+/// Nothing in it is transcribed or persisted unless it is promoted.
+pub struct AmbientBuffer {
+    minutes: AtomicU32,
+    active: AtomicBool,
+    scratch_dir: RwLock<Option<PathBuf>>,
+    mixer: Mutex<AudioMixer>,
+    /// Mixed audio chunks as 16-bit PCM (half the memory of f32)
+    audio: Mutex<VecDeque<(DateTime<Utc>, Vec<i16>)>>,
+    screen_states: Mutex<VecDeque<BufferedScreenState>>,
+    idle_gaps: Mutex<VecDeque<IdleGap>>,
+}
+
+impl AmbientBuffer {
+    pub fn new(minutes: u32) -> Self {
+        Self {
+            minutes: AtomicU32::new(minutes),
+            active: AtomicBool::new(false),
+            scratch_dir: RwLock::new(None),
+            mixer: Mutex::new(AudioMixer::new()),
+            audio: Mutex::new(VecDeque::new()),
+            screen_states: Mutex::new(VecDeque::new()),
+            idle_gaps: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Where buffered keyframes are written. Leftovers from a previous run are wiped.
+    pub fn set_scratch_dir(&self, dir: PathBuf) {
+        if dir.exists() {
+            let _ = std::fs::remove_dir_all(&dir);
+        }
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!("Failed to create ambient buffer dir: {}", e);
+        }
+        *self.scratch_dir.write() = Some(dir);
+    }
+
+    /// Buffer length in minutes (0 disables the buffer)
+    pub fn set_minutes(&self, minutes: u32) {
+        self.minutes.store(minutes, Ordering::SeqCst);
+        if minutes == 0 {
+            self.set_active(false);
+            self.clear();
+        } else {
+            self.evict(Utc::now());
+        }
+    }
+
+    pub fn minutes(&self) -> u32 {
+        self.minutes.load(Ordering::SeqCst)
+    }
+
+    /// Start/stop filling the buffer (only fills while ambient capture runs)
+    pub fn set_active(&self, active: bool) {
+        let active = active && self.minutes() > 0;
+        if active && !self.active.load(Ordering::SeqCst) {
+            self.mixer.lock().reset();
+        }
+        self.active.store(active, Ordering::SeqCst);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Add captured audio (mic or system) to the buffer
+    pub fn push_audio(&self, buffer: &AudioBuffer) {
+        if !self.is_active() || buffer.samples.is_empty() {
+            return;
+        }
+        let mixed = self.mixer.lock().push(
+            buffer.source,
+            &buffer.samples,
+            buffer.sample_rate,
+            buffer.channels,
+        );
+        if mixed.is_empty() {
+            return;
+        }
+        let pcm = mixed
+            .iter()
+            .map(|s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+            .collect();
+        self.push_audio_at(Utc::now(), pcm);
+    }
+
+    fn push_audio_at(&self, at: DateTime<Utc>, pcm: Vec<i16>) {
+        self.audio.lock().push_back((at, pcm));
+        self.evict(at);
+    }
+
+    /// Add a screen frame: near-duplicates extend the last state, new
+    /// content is written to scratch. Excluded windows become redacted
+    /// states with no keyframe. Returns false if the frame was redacted.
+    pub fn push_frame(&self, frame: &CapturedFrame) -> bool {
+        if !self.is_active() {
+            return true;
+        }
+
+        if let Some(reason) = crate::privacy_filter::frontmost_exclusion() {
+            log::trace!("🔒 Ambient frame redacted ({})", reason);
+            self.push_redacted(frame.timestamp);
+            return false;
+        }
+
+        let phash = AverageHash::compute(&frame.image).as_u64();
+        {
+            let mut states = self.screen_states.lock();
+            if let Some(last) = states.back_mut() {
+                if !last.is_redacted() && (last.phash ^ phash).count_ones() <= BUFFER_DEDUP_DISTANCE
+                {
+                    last.ended_at = frame.timestamp;
+                    return true;
+                }
+            }
+        }
+
+        let dir = match self.scratch_dir.read().clone() {
+            Some(dir) => dir,
+            None => return true,
+        };
+        let path = dir.join(format!("{}.jpg", uuid::Uuid::new_v4()));
+        if let Err(e) = frame.image.to_rgb8().save(&path) {
+            log::warn!("Failed to buffer ambient frame: {}", e);
+            return true;
+        }
+        let bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+        self.screen_states.lock().push_back(BufferedScreenState {
+            started_at: frame.timestamp,
+            ended_at: frame.timestamp,
+            path: Some(path),
+            phash,
+            bytes,
+        });
+        self.evict(frame.timestamp);
+        true
+    }
+
+    /// Extend the open redacted state, or start one
+    fn push_redacted(&self, at: DateTime<Utc>) {
+        {
+            let mut states = self.screen_states.lock();
+            match states.back_mut() {
+                Some(last) if last.is_redacted() => last.ended_at = at,
+                _ => states.push_back(BufferedScreenState {
+                    started_at: at,
+                    ended_at: at,
+                    path: None,
+                    phash: 0,
+                    bytes: 0,
+                }),
+            }
+        }
+        self.evict(at);
+    }
+
+    /// Record an idle pause so a promoted meeting shows the gap
+    pub fn push_idle_gap(&self, gap: IdleGap) {
+        if self.minutes() == 0 {
+            return;
+        }
+        self.idle_gaps.lock().push_back(gap);
+        self.evict(gap.ended_at);
+    }
+
+    /// Drop anything older than the buffer window
+    fn evict(&self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::minutes(self.minutes() as i64);
+
+        let mut audio = self.audio.lock();
+        while audio.front().map(|(at, _)| *at < cutoff).unwrap_or(false) {
+            audio.pop_front();
+        }
+        drop(audio);
+
+        let mut states = self.screen_states.lock();
+        while states.front().map(|s| s.ended_at < cutoff).unwrap_or(false) {
+            if let Some(state) = states.pop_front() {
+                if let Some(path) = &state.path {
+                    let _ = std::fs::remove_file(path);
+                }
+            }
+        }
+        drop(states);
+
+        let mut gaps = self.idle_gaps.lock();
+        while gaps.front().map(|g| g.ended_at < cutoff).unwrap_or(false) {
+            gaps.pop_front();
+        }
+    }
+
+    /// Take everything buffered so far, leaving the buffer empty.
+    /// Keyframe files stay on disk for the caller to move.
+    pub fn take(&self) -> AmbientSnapshot {
+        let chunks: Vec<(DateTime<Utc>, Vec<i16>)> = self.audio.lock().drain(..).collect();
+        let audio_started_at = chunks.first().map(|(at, pcm)| {
+            *at - Duration::milliseconds(pcm.len() as i64 * 1000 / MIX_SAMPLE_RATE as i64)
+        });
+        let audio = chunks
+            .into_iter()
+            .flat_map(|(_, pcm)| pcm)
+            .map(|s| s as f32 / i16::MAX as f32)
+            .collect();
+
+        AmbientSnapshot {
+            audio,
+            audio_started_at,
+            screen_states: self.screen_states.lock().drain(..).collect(),
+            idle_gaps: self.idle_gaps.lock().drain(..).collect(),
+        }
+    }
+
+    /// Wipe all buffered audio and keyframes
+    pub fn clear(&self) {
+        self.audio.lock().clear();
+        for state in self.screen_states.lock().drain(..) {
+            if let Some(path) = &state.path {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+        self.idle_gaps.lock().clear();
+        self.mixer.lock().reset();
+    }
+
+    pub fn stats(&self) -> AmbientBufferStats {
+        let audio_samples: usize = self.audio.lock().iter().map(|(_, pcm)| pcm.len()).sum();
+        let states = self.screen_states.lock();
+        AmbientBufferStats {
+            active: self.is_active(),
+            minutes: self.minutes(),
+            audio_seconds: audio_samples as f64 / MIX_SAMPLE_RATE as f64,
+            screen_states: states.len(),
+            memory_bytes: (audio_samples * std::mem::size_of::<i16>()
+                + states.len() * std::mem::size_of::<BufferedScreenState>())
+                as u64,
+            disk_bytes: states.iter().map(|s| s.bytes).sum(),
+        }
+    }
+}
+
+/// Idle state reported by `get_capture_mode`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdleStatus {
+    pub is_idle: bool,
+    pub idle_since: Option<DateTime<Utc>>,
+    pub last_activity: Option<DateTime<Utc>>,
+    pub idle_timeout_mins: u32,
+}
+
+/// What an idle poll changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleTransition {
+    /// Inactivity passed the timeout: stop capturing
+    Paused,
+    /// Input came back (or idle pausing no longer applies): resume
+    Resumed(IdleGap),
+}
+
+/// Decides when ambient capture pauses for inactivity
+pub struct IdleTracker {
+    timeout_mins: u32,
+    idle_since: Option<DateTime<Utc>>,
+    last_activity: Option<DateTime<Utc>>,
+}
+
+impl IdleTracker {
+    pub fn new(timeout_mins: u32) -> Self {
+        Self {
+            timeout_mins,
+            idle_since: None,
+            last_activity: None,
+        }
+    }
+
+    /// 0 disables idle pausing
+    pub fn set_timeout_mins(&mut self, mins: u32) {
+        self.timeout_mins = mins;
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.idle_since.is_some()
+    }
+
+    /// Feed one poll: seconds since the last input, and whether ambient
+    /// capture is what's running (meeting recordings are never paused)
+    pub fn observe(
+        &mut self,
+        idle_secs: f64,
+        now: DateTime<Utc>,
+        ambient_active: bool,
+    ) -> Option<IdleTransition> {
+        self.last_activity = Some(now - Duration::milliseconds((idle_secs * 1000.0) as i64));
+        let timeout_secs = self.timeout_mins as f64 * 60.0;
+        let past_timeout = self.timeout_mins > 0 && idle_secs >= timeout_secs;
+
+        match self.idle_since {
+            None if ambient_active && past_timeout => {
+                self.idle_since = Some(now);
+                Some(IdleTransition::Paused)
+            }
+            Some(started_at) if !ambient_active || !past_timeout => {
+                self.idle_since = None;
+                Some(IdleTransition::Resumed(IdleGap {
+                    started_at,
+                    ended_at: now,
+                }))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn status(&self) -> IdleStatus {
+        IdleStatus {
+            is_idle: self.is_idle(),
+            idle_since: self.idle_since,
+            last_activity: self.last_activity,
+            idle_timeout_mins: self.timeout_mins,
+        }
+    }
+}
+
 /// Ambient Capture Service
 pub struct AmbientCaptureService {
     config: Arc<RwLock<AmbientCaptureConfig>>,
@@ -109,6 +512,8 @@ pub struct AmbientCaptureService {
     last_frame_hash: Arc<RwLock<Option<String>>>,
     frame_history: Arc<RwLock<VecDeque<AmbientFrameMeta>>>,
     app_handle: Option<AppHandle>,
+    buffer: Arc<AmbientBuffer>,
+    idle: Arc<Mutex<IdleTracker>>,
 }
 
 impl AmbientCaptureService {
@@ -124,9 +529,16 @@ impl AmbientCaptureService {
             last_frame_hash: Arc::new(RwLock::new(None)),
             frame_history: Arc::new(RwLock::new(VecDeque::new())),
             app_handle: None,
+            buffer: Arc::new(AmbientBuffer::new(DEFAULT_BUFFER_MINUTES)),
+            idle: Arc::new(Mutex::new(IdleTracker::new(DEFAULT_IDLE_TIMEOUT_MINS))),
         }
     }
 
+    /// Rolling "last N minutes" buffer
+    pub fn buffer(&self) -> Arc<AmbientBuffer> {
+        self.buffer.clone()
+    }
+
     pub fn with_config(config: AmbientCaptureConfig) -> Self {
         let service = Self::new();
         *service.config.write() = config;
@@ -146,6 +558,7 @@ impl AmbientCaptureService {
     pub fn set_mode(&self, mode: CaptureMode) {
         let old_mode = *self.mode.read();
         *self.mode.write() = mode;
+        crate::tray_builder::update_tray_status(|s| s.ambient = mode == CaptureMode::Ambient);
 
         if old_mode != mode {
             log::info!("📹 Capture mode changed: {:?} -> {:?}", old_mode, mode);
@@ -205,11 +618,13 @@ impl AmbientCaptureService {
         power_manager.set_callback(Arc::new(move |state| match state {
             PowerState::Sleeping | PowerState::Idle => {
                 *mode.write() = CaptureMode::Paused;
+                crate::tray_builder::update_tray_status(|s| s.ambient = false);
                 log::info!("📹 Ambient capture paused (power state: {:?})", state);
             }
             PowerState::Active | PowerState::Waking => {
                 if is_running.load(Ordering::SeqCst) {
                     *mode.write() = CaptureMode::Ambient;
+                    crate::tray_builder::update_tray_status(|s| s.ambient = true);
                     log::info!("📹 Ambient capture resumed (power state: {:?})", state);
                 }
             }
@@ -263,6 +678,90 @@ impl AmbientCaptureService {
         Ok(())
     }
 
+    /// Minutes without input before ambient capture pauses (0 = never)
+    pub fn set_idle_timeout_mins(&self, mins: u32) {
+        self.idle.lock().set_timeout_mins(mins);
+    }
+
+    pub fn idle_status(&self) -> IdleStatus {
+        self.idle.lock().status()
+    }
+
+    /// Poll input idleness and suspend/resume the engine's ambient capture.
+    /// Only Ambient mode is ever paused: people sit still in meetings.
+    /// Also stops ambient capture when its feature flag is switched off.
+    pub fn start_idle_watch(
+        &self,
+        engine: Arc<RwLock<CaptureEngine>>,
+        power_manager: Arc<PowerManager>,
+        flags: SharedFeatureFlags,
+    ) {
+        let idle = self.idle.clone();
+        let buffer = self.buffer.clone();
+        let app_handle = self.app_handle.clone();
+
+        std::thread::spawn(move || loop {
+            std::thread::sleep(std::time::Duration::from_secs(IDLE_POLL_SECS));
+
+            let idle_secs = power_manager.get_idle_seconds();
+            let (ambient, suspended) = {
+                let engine = engine.read();
+                (
+                    engine.get_status().is_recording
+                        && engine.get_mode() == crate::capture_engine::CaptureMode::Ambient,
+                    engine.is_suspended(),
+                )
+            };
+
+            if ambient && !flags.read().ambient_capture_enabled {
+                if let Err(e) = engine.read().stop() {
+                    log::warn!("Failed to stop ambient capture: {}", e);
+                }
+                power_manager.release_assertion();
+                log::info!("📹 Ambient capture stopped (feature flag off)");
+                continue;
+            }
+
+            let mut tracker = idle.lock();
+            // Leave a user's own pause alone
+            let ambient_active = ambient && (!suspended || tracker.is_idle());
+            let transition = tracker.observe(idle_secs, Utc::now(), ambient_active);
+            let status = tracker.status();
+            drop(tracker);
+
+            match transition {
+                Some(IdleTransition::Paused) => {
+                    if let Err(e) = engine.read().suspend_recording() {
+                        log::warn!("Failed to pause ambient capture for idle: {}", e);
+                    }
+                    power_manager.release_assertion();
+                    log::info!("💤 Ambient capture paused after {:.0}s idle", idle_secs);
+                }
+                Some(IdleTransition::Resumed(gap)) => {
+                    let engine = engine.read();
+                    if engine.is_suspended()
+                        && engine.get_mode() == crate::capture_engine::CaptureMode::Ambient
+                    {
+                        let _ = engine.resume_recording();
+                        let _ = power_manager
+                            .prevent_sleep("Ambient Capture Active")
+                            .map_err(|e| log::warn!("Failed to prevent sleep: {}", e));
+                    }
+                    buffer.push_idle_gap(gap);
+                    log::info!(
+                        "☀️ Ambient capture resumed after {}s idle gap",
+                        gap.duration_ms() / 1000
+                    );
+                }
+                None => continue,
+            }
+
+            if let Some(ref app) = app_handle {
+                let _ = app.emit("ambient-idle-changed", &status);
+            }
+        });
+    }
+
     /// Stop ambient capture
     pub fn stop(&self) {
         self.is_running.store(false, Ordering::SeqCst);
@@ -380,4 +879,90 @@ mod tests {
         service.exit_meeting_mode();
         assert_eq!(service.get_mode(), CaptureMode::Ambient);
     }
+
+    #[test]
+    fn test_buffer_keeps_last_minutes_of_audio() {
+        let buffer = AmbientBuffer::new(10);
+        buffer.set_active(true);
+        let t0 = Utc::now();
+        let second = vec![0i16; MIX_SAMPLE_RATE as usize];
+
+        buffer.push_audio_at(t0, second.clone());
+        buffer.push_audio_at(t0 + Duration::minutes(5), second.clone());
+        assert_eq!(buffer.stats().audio_seconds, 2.0);
+
+        // The first chunk falls out of the 10-minute window
+        buffer.push_audio_at(t0 + Duration::minutes(11), second);
+        let stats = buffer.stats();
+        assert_eq!(stats.audio_seconds, 2.0);
+        assert_eq!(stats.memory_bytes, 2 * MIX_SAMPLE_RATE as u64 * 2);
+
+        let snapshot = buffer.take();
+        assert_eq!(snapshot.audio.len(), 2 * MIX_SAMPLE_RATE as usize);
+        assert_eq!(
+            snapshot.started_at(),
+            Some(t0 + Duration::minutes(5) - Duration::seconds(1))
+        );
+        assert_eq!(buffer.stats().audio_seconds, 0.0);
+    }
+
+    #[test]
+    fn test_redacted_frames_extend_one_state() {
+        let buffer = AmbientBuffer::new(10);
+        buffer.set_active(true);
+        let t0 = Utc::now();
+
+        buffer.push_redacted(t0);
+        buffer.push_redacted(t0 + Duration::seconds(5));
+
+        let snapshot = buffer.take();
+        assert_eq!(snapshot.screen_states.len(), 1);
+        assert!(snapshot.screen_states[0].is_redacted());
+        assert_eq!(
+            snapshot.screen_states[0].ended_at,
+            t0 + Duration::seconds(5)
+        );
+    }
+
+    #[test]
+    fn test_idle_tracker_pauses_ambient_only() {
+        let mut tracker = IdleTracker::new(5);
+        let t0 = Utc::now();
+
+        assert_eq!(tracker.observe(60.0, t0, true), None);
+        // Meeting recordings are never paused, however long the user sits still
+        assert_eq!(tracker.observe(600.0, t0, false), None);
+
+        let paused_at = t0 + Duration::seconds(10);
+        assert_eq!(
+            tracker.observe(300.0, paused_at, true),
+            Some(IdleTransition::Paused)
+        );
+        assert!(tracker.status().is_idle);
+        assert_eq!(
+            tracker.observe(900.0, t0 + Duration::minutes(10), true),
+            None
+        );
+
+        // First input afterwards resumes and reports the gap
+        let resumed_at = t0 + Duration::minutes(20);
+        assert_eq!(
+            tracker.observe(0.5, resumed_at, true),
+            Some(IdleTransition::Resumed(IdleGap {
+                started_at: paused_at,
+                ended_at: resumed_at,
+            }))
+        );
+        assert!(!tracker.status().is_idle);
+
+        tracker.set_timeout_mins(0);
+        assert_eq!(tracker.observe(10_000.0, resumed_at, true), None);
+    }
+
+    #[test]
+    fn test_buffer_disabled_at_zero_minutes() {
+        let buffer = AmbientBuffer::new(0);
+        buffer.set_active(true);
+        assert!(!buffer.is_active());
+    }
 }
@@ -1,9 +1,30 @@
 // Attendee Intelligence Module
 // Extracts and enriches attendee information from calendar events
 // Generates AI-powered briefings on people and companies for meeting prep
+//
+// Briefings run concurrently (bounded, on top of AIClient's shared rate
+// limit) and each succeeds or fails on its own, so one bad call doesn't sink
+// a 15-person meeting.
 
 use crate::ai_client::AIClient;
+use crate::model_router::USE_CASE_ATTENDEE_BRIEFING;
 use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Sqlite};
+use std::collections::HashMap;
+use tokio::sync::Semaphore;
+
+/// Briefings generated at the same time
+pub const BRIEFING_CONCURRENCY: usize = 4;
+
+/// How a person or company briefing was obtained
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BriefingStatus {
+    Generated,
+    /// Reused from a fresh vault note
+    Cached,
+    Failed,
+}
 
 /// Profile for an individual meeting attendee
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,7 +33,11 @@ pub struct AttendeeProfile {
     pub name: String,
     pub company: String,
     pub company_domain: String,
+    /// Empty when generation failed
     pub briefing: String,
+    pub status: BriefingStatus,
+    /// Why it failed
+    pub error: Option<String>,
 }
 
 /// Profile for a company extracted from attendee emails
@@ -20,16 +45,85 @@ pub struct AttendeeProfile {
 pub struct CompanyProfile {
     pub domain: String,
     pub name: String,
+    /// Empty when generation failed
     pub briefing: String,
+    pub status: BriefingStatus,
+    /// Why it failed
+    pub error: Option<String>,
+}
+
+/// Briefings reused from fresh vault notes instead of calling the AI
+#[derive(Debug, Clone, Default)]
+pub struct CachedBriefings {
+    /// Lowercased email → person briefing
+    pub people: std::collections::HashMap<String, String>,
+    /// Domain → company overview
+    pub companies: std::collections::HashMap<String, String>,
 }
 
-/// Complete meeting intelligence package
+/// Canonical entity names for attendees, so vault notes and entity analytics
+/// call people and companies the same thing
+#[derive(Debug, Clone, Default)]
+pub struct CanonicalNames {
+    /// Lowercased email → person name
+    pub people: HashMap<String, String>,
+    /// Domain → company name
+    pub companies: HashMap<String, String>,
+}
+
+impl CanonicalNames {
+    /// Display name and (domain, company name) for an attendee
+    pub fn resolve(&self, email: &str) -> (String, String, String) {
+        let name = self
+            .people
+            .get(&email.to_lowercase())
+            .cloned()
+            .unwrap_or_else(|| extract_name_from_email(email));
+        let (domain, company_name) = extract_company_from_email(email);
+        let company_name = self.companies.get(&domain).cloned().unwrap_or(company_name);
+        (name, domain, company_name)
+    }
+}
+
+/// Link attendees (as people) and their companies to canonical entities,
+/// creating them if needed. Failures fall back to names derived from emails.
+pub async fn link_attendees(pool: &Pool<Sqlite>, attendee_emails: &[String]) -> CanonicalNames {
+    use crate::entity_resolution::link_entity;
+
+    let mut names = CanonicalNames::default();
+    for email in attendee_emails {
+        let name = extract_name_from_email(email);
+        match link_entity(pool, "people", &name, None).await {
+            Ok(entity) => {
+                names.people.insert(email.to_lowercase(), entity.name);
+            }
+            Err(e) => log::warn!("Failed to link attendee {}: {}", email, e),
+        }
+
+        let (domain, company_name) = extract_company_from_email(email);
+        if is_personal_domain(&domain) || names.companies.contains_key(&domain) {
+            continue;
+        }
+        match link_entity(pool, "companies", &company_name, Some(&domain)).await {
+            Ok(entity) => {
+                names.companies.insert(domain, entity.name);
+            }
+            Err(e) => log::warn!("Failed to link company {}: {}", domain, e),
+        }
+    }
+    names
+}
+
+/// Complete meeting intelligence package; failed briefings are included
+/// with their reason
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MeetingIntelPackage {
     pub event_title: String,
     pub attendees: Vec<AttendeeProfile>,
     pub companies: Vec<CompanyProfile>,
-    pub meeting_prep: String,
+    /// None when the prep call failed (see `meeting_prep_error`)
+    pub meeting_prep: Option<String>,
+    pub meeting_prep_error: Option<String>,
 }
 
 // Common email domains that are not company-specific
@@ -83,6 +177,11 @@ pub fn extract_name_from_email(email: &str) -> String {
         .join(" ")
 }
 
+/// Free mail providers, which say nothing about someone's company
+pub fn is_personal_domain(domain: &str) -> bool {
+    PERSONAL_DOMAINS.contains(&domain.to_lowercase().as_str())
+}
+
 /// Extract company domain and name from an email address
 /// e.g. "casey@acme-corp.com" → ("acme-corp.com", "Acme Corp")
 pub fn extract_company_from_email(email: &str) -> (String, String) {
@@ -92,7 +191,7 @@ pub fn extract_company_from_email(email: &str) -> (String, String) {
         .unwrap_or("unknown.com")
         .to_lowercase();
 
-    if PERSONAL_DOMAINS.contains(&domain.as_str()) {
+    if is_personal_domain(&domain) {
         return (domain.clone(), "Personal".to_string());
     }
 
@@ -154,16 +253,10 @@ If you're uncertain about specifics, say "likely" or "estimated" rather than gue
         name, email, company_display, company_display
     );
 
-    let briefing = match ai_client.complete(&prompt).await {
-        Ok(text) => text,
-        Err(e) => {
-            log::warn!("AI briefing failed for {}: {}", name, e);
-            format!(
-                "**{}** — {} ({})\n\n*Briefing not available — AI service unavailable*",
-                name, company_display, email
-            )
-        }
-    };
+    let briefing = ai_client
+        .complete_for(USE_CASE_ATTENDEE_BRIEFING, &prompt)
+        .await
+        .map_err(|e| format!("AI briefing failed: {}", e))?;
 
     Ok(AttendeeProfile {
         email: email.to_string(),
@@ -171,6 +264,8 @@ If you're uncertain about specifics, say "likely" or "estimated" rather than gue
         company: company_display,
         company_domain: domain,
         briefing,
+        status: BriefingStatus::Generated,
+        error: None,
     })
 }
 
@@ -185,6 +280,8 @@ pub async fn generate_company_briefing(
             domain: domain.to_string(),
             name: company_name.to_string(),
             briefing: "*Personal email domain — no company profile available.*".to_string(),
+            status: BriefingStatus::Generated,
+            error: None,
         });
     }
 
@@ -205,50 +302,109 @@ Keep it brief. If uncertain, note it. This is a quick reference, not a research
         company_name, domain
     );
 
-    let briefing = match ai_client.complete(&prompt).await {
-        Ok(text) => text,
-        Err(e) => {
-            log::warn!("AI briefing failed for company {}: {}", company_name, e);
-            format!(
-                "**{}** ({})\n\n*Company briefing not available — AI service unavailable*",
-                company_name, domain
-            )
-        }
-    };
+    let briefing = ai_client
+        .complete_for(USE_CASE_ATTENDEE_BRIEFING, &prompt)
+        .await
+        .map_err(|e| format!("AI briefing failed: {}", e))?;
 
     Ok(CompanyProfile {
         domain: domain.to_string(),
         name: company_name.to_string(),
         briefing,
+        status: BriefingStatus::Generated,
+        error: None,
     })
 }
 
-/// Generate a complete meeting intelligence package from attendee emails
+/// Generate a complete meeting intelligence package from attendee emails;
+/// people and companies in `cached` skip the AI briefing. Briefings run
+/// `BRIEFING_CONCURRENCY` at a time and a failed one is reported rather than
+/// failing the package.
 pub async fn generate_meeting_intel(
     ai_client: &AIClient,
     event_title: &str,
     attendee_emails: &[String],
-) -> Result<MeetingIntelPackage, String> {
-    let mut attendees = Vec::new();
-    let mut companies: Vec<CompanyProfile> = Vec::new();
-    let mut seen_domains = std::collections::HashSet::new();
+    cached: &CachedBriefings,
+    names: &CanonicalNames,
+) -> MeetingIntelPackage {
+    let semaphore = Semaphore::new(BRIEFING_CONCURRENCY);
+
+    let people = attendee_emails.iter().map(|email| {
+        let semaphore = &semaphore;
+        async move {
+            let (name, domain, company_name) = names.resolve(email);
+            if let Some(briefing) = cached.people.get(&email.to_lowercase()) {
+                return AttendeeProfile {
+                    email: email.to_string(),
+                    name,
+                    company: company_name,
+                    company_domain: domain,
+                    briefing: briefing.clone(),
+                    status: BriefingStatus::Cached,
+                    error: None,
+                };
+            }
+            let _permit = semaphore.acquire().await.ok();
+            match generate_person_briefing(ai_client, &name, email, &company_name).await {
+                Ok(profile) => profile,
+                Err(e) => {
+                    log::warn!("Briefing failed for {}: {}", name, e);
+                    AttendeeProfile {
+                        email: email.to_string(),
+                        name,
+                        company: company_name,
+                        company_domain: domain,
+                        briefing: String::new(),
+                        status: BriefingStatus::Failed,
+                        error: Some(e),
+                    }
+                }
+            }
+        }
+    });
 
-    // Generate profiles for each attendee
+    // One company briefing per domain, in attendee order
+    let mut domains: Vec<(String, String)> = Vec::new();
     for email in attendee_emails {
-        let name = extract_name_from_email(email);
-        let (domain, company_name) = extract_company_from_email(email);
-
-        // Generate person briefing
-        let profile = generate_person_briefing(ai_client, &name, email, &company_name).await?;
-        attendees.push(profile);
-
-        // Generate company briefing (once per domain)
-        if !seen_domains.contains(&domain) {
-            seen_domains.insert(domain.clone());
-            let company = generate_company_briefing(ai_client, &domain, &company_name).await?;
-            companies.push(company);
+        let (_, domain, company_name) = names.resolve(email);
+        if !domains.iter().any(|(d, _)| *d == domain) {
+            domains.push((domain, company_name));
         }
     }
+    let companies = domains.into_iter().map(|(domain, company_name)| {
+        let semaphore = &semaphore;
+        async move {
+            if let Some(briefing) = cached.companies.get(&domain) {
+                return CompanyProfile {
+                    domain,
+                    name: company_name,
+                    briefing: briefing.clone(),
+                    status: BriefingStatus::Cached,
+                    error: None,
+                };
+            }
+            let _permit = semaphore.acquire().await.ok();
+            match generate_company_briefing(ai_client, &domain, &company_name).await {
+                Ok(profile) => profile,
+                Err(e) => {
+                    log::warn!("Briefing failed for company {}: {}", company_name, e);
+                    CompanyProfile {
+                        domain,
+                        name: company_name,
+                        briefing: String::new(),
+                        status: BriefingStatus::Failed,
+                        error: Some(e),
+                    }
+                }
+            }
+        }
+    });
+
+    let (attendees, companies) = futures_util::future::join(
+        futures_util::future::join_all(people),
+        futures_util::future::join_all(companies),
+    )
+    .await;
 
     // Generate meeting prep summary
     let attendee_summary: Vec<String> = attendees
@@ -289,24 +445,24 @@ Be concise and actionable."#,
         }
     );
 
-    let meeting_prep = match ai_client.complete(&prep_prompt).await {
-        Ok(text) => text,
+    let (meeting_prep, meeting_prep_error) = match ai_client
+        .complete_for(USE_CASE_ATTENDEE_BRIEFING, &prep_prompt)
+        .await
+    {
+        Ok(text) => (Some(text), None),
         Err(e) => {
             log::warn!("Meeting prep generation failed: {}", e);
-            format!(
-                "**Meeting**: {}\n\n**Attendees**: {}\n\n*Meeting prep not available — AI service unavailable*",
-                event_title,
-                attendees.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", ")
-            )
+            (None, Some(format!("Meeting prep failed: {}", e)))
         }
     };
 
-    Ok(MeetingIntelPackage {
+    MeetingIntelPackage {
         event_title: event_title.to_string(),
         attendees,
         companies,
         meeting_prep,
-    })
+        meeting_prep_error,
+    }
 }
 
 #[cfg(test)]
@@ -342,6 +498,26 @@ mod tests {
         assert_eq!(name, "Openai");
     }
 
+    #[test]
+    fn test_canonical_names_override_email_guesses() {
+        let mut names = CanonicalNames::default();
+        names
+            .companies
+            .insert("acme-corp.com".to_string(), "Acme Corporation".to_string());
+        names
+            .people
+            .insert("casey@acme-corp.com".to_string(), "Casey P.".to_string());
+
+        let (name, domain, company) = names.resolve("Casey@acme-corp.com");
+        assert_eq!(name, "Casey P.");
+        assert_eq!(domain, "acme-corp.com");
+        assert_eq!(company, "Acme Corporation");
+
+        let (name, _, company) = names.resolve("jane.doe@initech.com");
+        assert_eq!(name, "Jane Doe");
+        assert_eq!(company, "Initech");
+    }
+
     #[test]
     fn test_personal_domain_detection() {
         for domain in &[
@@ -0,0 +1,350 @@
+// noFriction Meetings - Audio Level Metering
+// Live input level per capture source and dead-mic detection
+//
+// The capture threads feed every AudioBuffer into the meter. A 5 Hz task
+// closes the current window, publishes the RMS/peak per source as
+// `audio-level`, and watches the microphone: if it stays below the silence
+// threshold (or stops delivering buffers at all) for `after_secs` while a
+// meeting is recording, one `silence-warning` is raised per silent stretch.
+
+use chrono::{DateTime, Utc};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+use crate::capture_engine::AudioSource;
+
+/// Metering window / `audio-level` event interval (~5 Hz)
+pub const LEVEL_TICK_MS: u64 = 200;
+/// Mic level below which input counts as silent
+pub const DEFAULT_SILENCE_THRESHOLD_DB: f32 = -50.0;
+/// Seconds of silence before warning
+pub const DEFAULT_SILENCE_AFTER_SECS: u32 = 30;
+/// Reported level for digital silence
+const FLOOR_DB: f32 = -100.0;
+
+/// When to warn about a silent microphone
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SilenceWarningPolicy {
+    pub enabled: bool,
+    /// RMS level (dBFS) below which the mic counts as silent
+    pub threshold_db: f32,
+    /// Silent seconds before the warning fires
+    pub after_secs: u32,
+}
+
+impl Default for SilenceWarningPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold_db: DEFAULT_SILENCE_THRESHOLD_DB,
+            after_secs: DEFAULT_SILENCE_AFTER_SECS,
+        }
+    }
+}
+
+impl SilenceWarningPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(-90.0..=-10.0).contains(&self.threshold_db) {
+            return Err("Silence threshold must be between -90 and -10 dB".to_string());
+        }
+        if !(5..=600).contains(&self.after_secs) {
+            return Err("Silence warning delay must be between 5 and 600 seconds".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Level of one source over a metering window
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct AudioLevel {
+    /// Linear RMS (0.0-1.0)
+    pub rms: f32,
+    /// Linear absolute peak (0.0-1.0)
+    pub peak: f32,
+    pub rms_db: f32,
+    pub peak_db: f32,
+}
+
+impl AudioLevel {
+    /// Measure a block of interleaved samples
+    pub fn measure(samples: &[f32]) -> Self {
+        let mut window = Window::default();
+        window.add(samples);
+        window.close().unwrap_or(Self {
+            rms: 0.0,
+            peak: 0.0,
+            rms_db: FLOOR_DB,
+            peak_db: FLOOR_DB,
+        })
+    }
+}
+
+fn to_db(linear: f32) -> f32 {
+    if linear <= 0.0 {
+        return FLOOR_DB;
+    }
+    (20.0 * linear.log10()).max(FLOOR_DB)
+}
+
+/// Payload for `audio-level` and `get_audio_levels`
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioLevels {
+    /// None when the source delivered no audio in the last window
+    pub microphone: Option<AudioLevel>,
+    pub system: Option<AudioLevel>,
+    /// Seconds the microphone has been silent (0 while audible or not recording)
+    pub mic_silent_secs: u64,
+    pub at: DateTime<Utc>,
+}
+
+/// Payload for the `silence-warning` event
+#[derive(Debug, Clone, Serialize)]
+pub struct SilenceWarning {
+    pub meeting_id: Option<String>,
+    /// When the microphone went quiet
+    pub since: DateTime<Utc>,
+    pub silent_secs: u64,
+    pub threshold_db: f32,
+    /// Last mic level (None = the device stopped delivering audio)
+    pub level_db: Option<f32>,
+}
+
+/// Start or end of a warned-about silent stretch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SilenceTransition {
+    Warn {
+        since: DateTime<Utc>,
+        silent_secs: u64,
+    },
+    Recovered {
+        since: DateTime<Utc>,
+        silent_secs: u64,
+    },
+}
+
+/// Tracks how long the microphone has been silent
+#[derive(Debug, Default)]
+pub struct SilenceTracker {
+    silent_since: Option<DateTime<Utc>>,
+    warned: bool,
+}
+
+impl SilenceTracker {
+    /// Feed one window's mic level (None = no audio arrived)
+    pub fn observe(
+        &mut self,
+        level_db: Option<f32>,
+        now: DateTime<Utc>,
+        policy: &SilenceWarningPolicy,
+    ) -> Option<SilenceTransition> {
+        let silent = !matches!(level_db, Some(db) if db >= policy.threshold_db);
+        if !silent {
+            return self.end(now);
+        }
+
+        let since = *self.silent_since.get_or_insert(now);
+        let silent_secs = (now - since).num_seconds().max(0) as u64;
+        if policy.enabled && !self.warned && silent_secs >= policy.after_secs as u64 {
+            self.warned = true;
+            return Some(SilenceTransition::Warn { since, silent_secs });
+        }
+        None
+    }
+
+    /// Input is back (or recording stopped); reports recovery if a warning was raised
+    pub fn end(&mut self, now: DateTime<Utc>) -> Option<SilenceTransition> {
+        let since = self.silent_since.take()?;
+        let warned = std::mem::take(&mut self.warned);
+        warned.then(|| SilenceTransition::Recovered {
+            since,
+            silent_secs: (now - since).num_seconds().max(0) as u64,
+        })
+    }
+
+    pub fn silent_secs(&self, now: DateTime<Utc>) -> u64 {
+        self.silent_since
+            .map(|since| (now - since).num_seconds().max(0) as u64)
+            .unwrap_or(0)
+    }
+}
+
+/// Running sums for the current metering window
+#[derive(Debug, Default)]
+struct Window {
+    sum_sq: f64,
+    peak: f32,
+    samples: usize,
+}
+
+impl Window {
+    fn add(&mut self, samples: &[f32]) {
+        for &s in samples {
+            self.sum_sq += (s as f64) * (s as f64);
+            self.peak = self.peak.max(s.abs());
+        }
+        self.samples += samples.len();
+    }
+
+    fn close(&mut self) -> Option<AudioLevel> {
+        let window = std::mem::take(self);
+        if window.samples == 0 {
+            return None;
+        }
+        let rms = (window.sum_sq / window.samples as f64).sqrt() as f32;
+        Some(AudioLevel {
+            rms,
+            peak: window.peak,
+            rms_db: to_db(rms),
+            peak_db: to_db(window.peak),
+        })
+    }
+}
+
+/// Input level meter shared by the capture threads and the level task
+pub struct LevelMeter {
+    mic: Mutex<Window>,
+    system: Mutex<Window>,
+    latest: RwLock<Option<AudioLevels>>,
+    policy: RwLock<SilenceWarningPolicy>,
+    tracker: Mutex<SilenceTracker>,
+}
+
+/// Global meter (capture callbacks have no access to AppState)
+pub fn audio_meter() -> &'static LevelMeter {
+    static METER: OnceLock<LevelMeter> = OnceLock::new();
+    METER.get_or_init(|| LevelMeter {
+        mic: Mutex::new(Window::default()),
+        system: Mutex::new(Window::default()),
+        latest: RwLock::new(None),
+        policy: RwLock::new(SilenceWarningPolicy::default()),
+        tracker: Mutex::new(SilenceTracker::default()),
+    })
+}
+
+impl LevelMeter {
+    /// Add a captured buffer to the current window (called from the audio callback)
+    pub fn record(&self, source: AudioSource, samples: &[f32]) {
+        match source {
+            AudioSource::Microphone => self.mic.lock().add(samples),
+            AudioSource::System => self.system.lock().add(samples),
+        }
+    }
+
+    pub fn set_policy(&self, policy: SilenceWarningPolicy) {
+        *self.policy.write() = policy;
+    }
+
+    pub fn policy(&self) -> SilenceWarningPolicy {
+        *self.policy.read()
+    }
+
+    /// Levels from the most recent window
+    pub fn levels(&self) -> AudioLevels {
+        self.latest.read().clone().unwrap_or(AudioLevels {
+            microphone: None,
+            system: None,
+            mic_silent_secs: 0,
+            at: Utc::now(),
+        })
+    }
+
+    /// Close the current window. `mic_expected` is true while a meeting is
+    /// recording with the microphone enabled and not paused.
+    pub fn tick(
+        &self,
+        now: DateTime<Utc>,
+        mic_expected: bool,
+    ) -> (AudioLevels, Option<SilenceTransition>) {
+        let microphone = self.mic.lock().close();
+        let system = self.system.lock().close();
+        let policy = self.policy();
+
+        let (transition, mic_silent_secs) = {
+            let mut tracker = self.tracker.lock();
+            let transition = if mic_expected {
+                tracker.observe(microphone.map(|l| l.rms_db), now, &policy)
+            } else {
+                tracker.end(now)
+            };
+            (transition, tracker.silent_secs(now))
+        };
+
+        let levels = AudioLevels {
+            microphone,
+            system,
+            mic_silent_secs,
+            at: now,
+        };
+        *self.latest.write() = Some(levels.clone());
+        (levels, transition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_measure_levels() {
+        let level = AudioLevel::measure(&[0.5, -0.5, 0.5, -0.5]);
+        assert!((level.rms - 0.5).abs() < 1e-6);
+        assert!((level.peak - 0.5).abs() < 1e-6);
+        assert!((level.rms_db + 6.02).abs() < 0.01);
+
+        let silent = AudioLevel::measure(&[0.0; 64]);
+        assert_eq!(silent.rms_db, FLOOR_DB);
+    }
+
+    #[test]
+    fn test_silence_warns_once_then_recovers() {
+        let policy = SilenceWarningPolicy {
+            enabled: true,
+            threshold_db: -50.0,
+            after_secs: 10,
+        };
+        let mut tracker = SilenceTracker::default();
+        let start = Utc::now();
+
+        assert_eq!(tracker.observe(Some(-70.0), start, &policy), None);
+        // A dead device (no buffers at all) still counts as silence
+        assert_eq!(
+            tracker.observe(None, start + Duration::seconds(5), &policy),
+            None
+        );
+        assert_eq!(
+            tracker.observe(Some(-80.0), start + Duration::seconds(10), &policy),
+            Some(SilenceTransition::Warn {
+                since: start,
+                silent_secs: 10
+            })
+        );
+        assert_eq!(
+            tracker.observe(Some(-80.0), start + Duration::seconds(20), &policy),
+            None
+        );
+        assert_eq!(
+            tracker.observe(Some(-20.0), start + Duration::seconds(25), &policy),
+            Some(SilenceTransition::Recovered {
+                since: start,
+                silent_secs: 25
+            })
+        );
+        assert_eq!(tracker.silent_secs(start + Duration::seconds(26)), 0);
+    }
+
+    #[test]
+    fn test_short_silence_is_not_reported() {
+        let policy = SilenceWarningPolicy::default();
+        let mut tracker = SilenceTracker::default();
+        let start = Utc::now();
+
+        tracker.observe(Some(-70.0), start, &policy);
+        assert_eq!(
+            tracker.observe(Some(-20.0), start + Duration::seconds(3), &policy),
+            None
+        );
+    }
+}
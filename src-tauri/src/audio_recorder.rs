@@ -0,0 +1,617 @@
+// noFriction Meetings - Audio Recorder
+// Archives raw meeting audio to disk alongside the transcripts
+//
+// The capture callback hands every buffer to the recorder, which mixes mic +
+// system audio into the same 16kHz mono stream the providers receive and
+// writes it as 16-bit PCM WAV under `audio/<meeting_id>/`. Chunks rotate every
+// five minutes and after any gap in the audio (a pause, a stalled device), so
+// each file is continuous and a wall-clock time maps to one file and offset.
+// Chunks are registered in `audio_chunks` as they open and finish. With the
+// "aac" format, finished chunks are transcoded with ffmpeg when it's on PATH;
+// otherwise the WAV is kept.
+
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, OnceLock};
+use tokio::sync::mpsc as async_mpsc;
+
+use crate::capture_engine::AudioBuffer;
+use crate::database::{AudioChunk, DatabaseManager};
+use crate::transcription::audio_mixer::{AudioMixer, MIX_SAMPLE_RATE};
+
+/// Length of each audio chunk
+const CHUNK_DURATION_SECS: u64 = 300;
+/// A hole this long in the audio starts a new chunk
+const GAP_ROTATE_SECS: f64 = 2.0;
+/// Rewrite the WAV header about once a second so a crash leaves a playable file
+const HEADER_REFRESH_SAMPLES: u64 = MIX_SAMPLE_RATE as u64;
+/// Archived audio is mono
+const CHANNELS: u16 = 1;
+
+/// On-disk format for archived audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    Wav,
+    /// AAC in .m4a, transcoded from WAV with ffmpeg
+    Aac,
+}
+
+impl AudioFormat {
+    /// Parse the `audio_recording_format` setting (unknown values fall back to WAV)
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "aac" => Self::Aac,
+            _ => Self::Wav,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Aac => "aac",
+        }
+    }
+}
+
+/// Where a timestamp falls in a meeting's archived audio
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioChunkPosition {
+    pub chunk: AudioChunk,
+    /// Seconds from the start of the chunk file
+    pub offset_secs: f64,
+}
+
+/// Seconds into `chunk` for `ts`, or None when `ts` is outside it
+pub fn offset_in_chunk(chunk: &AudioChunk, ts: DateTime<Utc>) -> Option<f64> {
+    if ts < chunk.start_ts || chunk.end_ts.is_some_and(|end| ts > end) {
+        return None;
+    }
+    Some((ts - chunk.start_ts).num_milliseconds() as f64 / 1000.0)
+}
+
+/// Find the chunk and offset holding `ts` for playback
+pub async fn chunk_for_timestamp(
+    database: &DatabaseManager,
+    meeting_id: &str,
+    ts: DateTime<Utc>,
+) -> Result<Option<AudioChunkPosition>, String> {
+    let chunk = database
+        .get_audio_chunk_at(meeting_id, ts)
+        .await
+        .map_err(|e| format!("Failed to look up audio chunk: {}", e))?;
+
+    Ok(chunk.and_then(|chunk| {
+        offset_in_chunk(&chunk, ts).map(|offset_secs| AudioChunkPosition { chunk, offset_secs })
+    }))
+}
+
+/// 44-byte canonical WAV header for 16-bit PCM
+fn wav_header(sample_rate: u32, channels: u16, data_bytes: u32) -> [u8; 44] {
+    let block_align = channels * 2;
+    let byte_rate = sample_rate * block_align as u32;
+
+    let mut header = [0u8; 44];
+    header[0..4].copy_from_slice(b"RIFF");
+    header[4..8].copy_from_slice(&(36 + data_bytes).to_le_bytes());
+    header[8..12].copy_from_slice(b"WAVE");
+    header[12..16].copy_from_slice(b"fmt ");
+    header[16..20].copy_from_slice(&16u32.to_le_bytes());
+    header[20..22].copy_from_slice(&1u16.to_le_bytes()); // PCM
+    header[22..24].copy_from_slice(&channels.to_le_bytes());
+    header[24..28].copy_from_slice(&sample_rate.to_le_bytes());
+    header[28..32].copy_from_slice(&byte_rate.to_le_bytes());
+    header[32..34].copy_from_slice(&block_align.to_le_bytes());
+    header[34..36].copy_from_slice(&16u16.to_le_bytes());
+    header[36..40].copy_from_slice(b"data");
+    header[40..44].copy_from_slice(&data_bytes.to_le_bytes());
+    header
+}
+
+/// Streaming 16-bit PCM WAV writer
+struct WavWriter {
+    file: BufWriter<File>,
+    sample_rate: u32,
+    channels: u16,
+    data_bytes: u32,
+}
+
+impl WavWriter {
+    fn create(path: &Path, sample_rate: u32, channels: u16) -> std::io::Result<Self> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(&wav_header(sample_rate, channels, 0))?;
+        Ok(Self {
+            file,
+            sample_rate,
+            channels,
+            data_bytes: 0,
+        })
+    }
+
+    fn write_samples(&mut self, samples: &[f32]) -> std::io::Result<()> {
+        for &s in samples {
+            let pcm = (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            self.file.write_all(&pcm.to_le_bytes())?;
+        }
+        self.data_bytes = self.data_bytes.saturating_add((samples.len() * 2) as u32);
+        Ok(())
+    }
+
+    /// Patch the header with the current length
+    fn refresh_header(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+        let file = self.file.get_mut();
+        let end = file.stream_position()?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(&wav_header(
+            self.sample_rate,
+            self.channels,
+            self.data_bytes,
+        ))?;
+        file.seek(SeekFrom::Start(end))?;
+        Ok(())
+    }
+
+    fn finish(mut self) -> std::io::Result<()> {
+        self.refresh_header()?;
+        self.file.get_ref().sync_all()
+    }
+}
+
+/// Chunk currently being written
+struct OpenChunk {
+    id: String,
+    path: PathBuf,
+    writer: WavWriter,
+    start_ts: DateTime<Utc>,
+    samples: u64,
+}
+
+impl OpenChunk {
+    fn end_ts(&self) -> DateTime<Utc> {
+        self.start_ts
+            + Duration::milliseconds((self.samples * 1000 / MIX_SAMPLE_RATE as u64) as i64)
+    }
+
+    /// Full, or the new audio doesn't follow on from what was written
+    fn should_rotate(&self, incoming_start: DateTime<Utc>) -> bool {
+        let gap = (incoming_start - self.end_ts()).num_milliseconds() as f64 / 1000.0;
+        self.samples >= CHUNK_DURATION_SECS * MIX_SAMPLE_RATE as u64 || gap > GAP_ROTATE_SECS
+    }
+}
+
+enum RecorderMsg {
+    Audio(AudioBuffer),
+    Stop,
+}
+
+/// Chunk lifecycle, forwarded to the database by the registrar task
+enum ChunkEvent {
+    Opened(AudioChunk),
+    Finished {
+        id: String,
+        path: String,
+        format: AudioFormat,
+        end_ts: DateTime<Utc>,
+        size_bytes: i64,
+    },
+}
+
+/// Writes meeting audio to disk while a recording with audio archiving is active
+pub struct AudioRecorder {
+    active: AtomicBool,
+    tx: Mutex<Option<mpsc::Sender<RecorderMsg>>>,
+    writer: Mutex<Option<std::thread::JoinHandle<()>>>,
+    registrar: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+}
+
+/// Global recorder (fed from the capture callback)
+pub fn audio_recorder() -> &'static AudioRecorder {
+    static RECORDER: OnceLock<AudioRecorder> = OnceLock::new();
+    RECORDER.get_or_init(|| AudioRecorder {
+        active: AtomicBool::new(false),
+        tx: Mutex::new(None),
+        writer: Mutex::new(None),
+        registrar: Mutex::new(None),
+    })
+}
+
+impl AudioRecorder {
+    /// Start archiving audio for a meeting into `dir`
+    pub fn start(
+        &self,
+        meeting_id: &str,
+        dir: PathBuf,
+        format: AudioFormat,
+        database: Arc<DatabaseManager>,
+    ) -> Result<(), String> {
+        if self.active.load(Ordering::SeqCst) {
+            return Err("Audio recording already active".to_string());
+        }
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create audio directory: {}", e))?;
+
+        let (tx, rx) = mpsc::channel();
+        let (events_tx, mut events_rx) = async_mpsc::unbounded_channel();
+        let meeting_id = meeting_id.to_string();
+
+        let writer = std::thread::spawn(move || {
+            Self::run_writer(rx, events_tx, &meeting_id, &dir, format);
+        });
+        let registrar = tauri::async_runtime::spawn(async move {
+            while let Some(event) = events_rx.recv().await {
+                if let Err(e) = Self::register(&database, event).await {
+                    log::warn!("Failed to register audio chunk: {}", e);
+                }
+            }
+        });
+
+        *self.tx.lock() = Some(tx);
+        *self.writer.lock() = Some(writer);
+        *self.registrar.lock() = Some(registrar);
+        self.active.store(true, Ordering::SeqCst);
+        log::info!("🎙️ Audio archiving started ({})", format.as_str());
+        Ok(())
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Queue a captured buffer (no-op unless archiving is active)
+    pub fn push(&self, buffer: &AudioBuffer) {
+        if !self.active.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Some(tx) = self.tx.lock().as_ref() {
+            let _ = tx.send(RecorderMsg::Audio(buffer.clone()));
+        }
+    }
+
+    /// Finalize the last chunk and wait until every chunk is registered
+    pub async fn stop(&self) {
+        if !self.active.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        if let Some(tx) = self.tx.lock().take() {
+            let _ = tx.send(RecorderMsg::Stop);
+        }
+
+        let writer = self.writer.lock().take();
+        if let Some(writer) = writer {
+            let _ = tokio::task::spawn_blocking(move || writer.join()).await;
+        }
+        let registrar = self.registrar.lock().take();
+        if let Some(registrar) = registrar {
+            let _ = registrar.await;
+        }
+        log::info!("🎙️ Audio archiving stopped");
+    }
+
+    async fn register(database: &DatabaseManager, event: ChunkEvent) -> Result<(), sqlx::Error> {
+        match event {
+            ChunkEvent::Opened(chunk) => database.add_audio_chunk(&chunk).await,
+            ChunkEvent::Finished {
+                id,
+                path,
+                format,
+                end_ts,
+                size_bytes,
+            } => {
+                database
+                    .finish_audio_chunk(&id, &path, format.as_str(), end_ts, size_bytes)
+                    .await
+            }
+        }
+    }
+
+    fn run_writer(
+        rx: mpsc::Receiver<RecorderMsg>,
+        events: async_mpsc::UnboundedSender<ChunkEvent>,
+        meeting_id: &str,
+        dir: &Path,
+        format: AudioFormat,
+    ) {
+        let mut mixer = AudioMixer::new();
+        let mut current: Option<OpenChunk> = None;
+        let mut chunk_index = 0i64;
+
+        for msg in rx {
+            let buffer = match msg {
+                RecorderMsg::Audio(buffer) => buffer,
+                RecorderMsg::Stop => break,
+            };
+            let mixed = mixer.push(
+                buffer.source,
+                &buffer.samples,
+                buffer.sample_rate,
+                buffer.channels,
+            );
+            if mixed.is_empty() {
+                continue;
+            }
+
+            let incoming_start = Utc::now()
+                - Duration::milliseconds(
+                    (mixed.len() as u64 * 1000 / MIX_SAMPLE_RATE as u64) as i64,
+                );
+            if current
+                .as_ref()
+                .is_some_and(|open| open.should_rotate(incoming_start))
+            {
+                if let Some(open) = current.take() {
+                    Self::finish_chunk(open, format, &events);
+                }
+            }
+
+            if current.is_none() {
+                chunk_index += 1;
+                let id = uuid::Uuid::new_v4().to_string();
+                let path = dir.join(format!("chunk_{:04}.wav", chunk_index));
+                match WavWriter::create(&path, MIX_SAMPLE_RATE, CHANNELS) {
+                    Ok(writer) => {
+                        let _ = events.send(ChunkEvent::Opened(AudioChunk {
+                            id: id.clone(),
+                            meeting_id: meeting_id.to_string(),
+                            chunk_index,
+                            path: path.to_string_lossy().to_string(),
+                            format: AudioFormat::Wav.as_str().to_string(),
+                            sample_rate: MIX_SAMPLE_RATE,
+                            channels: CHANNELS,
+                            start_ts: incoming_start,
+                            end_ts: None,
+                            size_bytes: 0,
+                        }));
+                        current = Some(OpenChunk {
+                            id,
+                            path,
+                            writer,
+                            start_ts: incoming_start,
+                            samples: 0,
+                        });
+                    }
+                    Err(e) => {
+                        log::error!("Failed to create audio chunk {}: {}", path.display(), e);
+                        continue;
+                    }
+                }
+            }
+
+            if let Some(open) = current.as_mut() {
+                if let Err(e) = open.writer.write_samples(&mixed) {
+                    log::error!("Failed to write audio chunk: {}", e);
+                    continue;
+                }
+                let before = open.samples;
+                open.samples += mixed.len() as u64;
+                if before / HEADER_REFRESH_SAMPLES != open.samples / HEADER_REFRESH_SAMPLES {
+                    let _ = open.writer.refresh_header();
+                }
+            }
+        }
+
+        if let Some(open) = current.take() {
+            Self::finish_chunk(open, format, &events);
+        }
+    }
+
+    fn finish_chunk(
+        open: OpenChunk,
+        format: AudioFormat,
+        events: &async_mpsc::UnboundedSender<ChunkEvent>,
+    ) {
+        let end_ts = open.end_ts();
+        if let Err(e) = open.writer.finish() {
+            log::error!(
+                "Failed to finalize audio chunk {}: {}",
+                open.path.display(),
+                e
+            );
+        }
+
+        let (path, format) = match format {
+            AudioFormat::Aac => match transcode_to_aac(&open.path) {
+                Ok(path) => (path, AudioFormat::Aac),
+                Err(e) => {
+                    log::warn!("Keeping WAV audio chunk: {}", e);
+                    (open.path, AudioFormat::Wav)
+                }
+            },
+            AudioFormat::Wav => (open.path, AudioFormat::Wav),
+        };
+        let size_bytes = std::fs::metadata(&path)
+            .map(|m| m.len() as i64)
+            .unwrap_or(0);
+
+        let _ = events.send(ChunkEvent::Finished {
+            id: open.id,
+            path: path.to_string_lossy().to_string(),
+            format,
+            end_ts,
+            size_bytes,
+        });
+    }
+}
+
+/// Decode an archived chunk back to 16kHz mono samples
+pub fn read_chunk_samples(chunk: &AudioChunk) -> Result<Vec<f32>, String> {
+    let pcm = match AudioFormat::from_setting(&chunk.format) {
+        AudioFormat::Wav => {
+            let bytes = std::fs::read(&chunk.path)
+                .map_err(|e| format!("Failed to read {}: {}", chunk.path, e))?;
+            wav_data(&bytes)?.to_vec()
+        }
+        AudioFormat::Aac => decode_with_ffmpeg(Path::new(&chunk.path))?,
+    };
+    Ok(pcm
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / i16::MAX as f32)
+        .collect())
+}
+
+/// PCM payload of a WAV written by `WavWriter`. Everything after the header
+/// is used, so a chunk cut off before its last header refresh still decodes.
+fn wav_data(bytes: &[u8]) -> Result<&[u8], String> {
+    if bytes.len() < 44 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("Not a WAV file".to_string());
+    }
+    Ok(&bytes[44..])
+}
+
+/// Decode any ffmpeg-readable file to 16kHz mono 16-bit PCM
+fn decode_with_ffmpeg(path: &Path) -> Result<Vec<u8>, String> {
+    if !ffmpeg_available() {
+        return Err("ffmpeg not found for AAC decoding".to_string());
+    }
+
+    let output = Command::new("ffmpeg")
+        .args(["-loglevel", "error", "-i"])
+        .arg(path)
+        .args(["-f", "s16le", "-ac", "1", "-ar"])
+        .arg(MIX_SAMPLE_RATE.to_string())
+        .arg("-")
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("ffmpeg exited with {}", output.status));
+    }
+    Ok(output.stdout)
+}
+
+/// Whether an ffmpeg binary is on PATH (checked once)
+pub(crate) fn ffmpeg_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        Command::new("ffmpeg")
+            .arg("-version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// Transcode a finished WAV chunk to AAC (.m4a), removing the WAV on success
+fn transcode_to_aac(wav: &Path) -> Result<PathBuf, String> {
+    if !ffmpeg_available() {
+        return Err("ffmpeg not found for AAC encoding".to_string());
+    }
+
+    let m4a = wav.with_extension("m4a");
+    let status = Command::new("ffmpeg")
+        .args(["-y", "-loglevel", "error", "-i"])
+        .arg(wav)
+        .args(["-c:a", "aac", "-b:a", "64k"])
+        .arg(&m4a)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+    if !status.success() {
+        let _ = std::fs::remove_file(&m4a);
+        return Err(format!("ffmpeg exited with {}", status));
+    }
+
+    let _ = std::fs::remove_file(wav);
+    Ok(m4a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> AudioChunk {
+        AudioChunk {
+            id: "chunk".to_string(),
+            meeting_id: "meeting".to_string(),
+            chunk_index: 1,
+            path: "audio/meeting/chunk_0001.wav".to_string(),
+            format: "wav".to_string(),
+            sample_rate: MIX_SAMPLE_RATE,
+            channels: CHANNELS,
+            start_ts: start,
+            end_ts: end,
+            size_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn test_wav_writer_header_matches_data() {
+        let path =
+            std::env::temp_dir().join(format!("audio_recorder_{}.wav", uuid::Uuid::new_v4()));
+        let mut writer = WavWriter::create(&path, MIX_SAMPLE_RATE, CHANNELS).unwrap();
+        writer.write_samples(&[0.0, 0.5, -0.5, 1.0]).unwrap();
+        writer.finish().unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(bytes.len(), 44 + 8);
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(bytes[4..8].try_into().unwrap()), 36 + 8);
+        assert_eq!(
+            u32::from_le_bytes(bytes[24..28].try_into().unwrap()),
+            MIX_SAMPLE_RATE
+        );
+        assert_eq!(u32::from_le_bytes(bytes[40..44].try_into().unwrap()), 8);
+        assert_eq!(i16::from_le_bytes([bytes[50], bytes[51]]), i16::MAX);
+    }
+
+    #[test]
+    fn test_read_chunk_samples_round_trips_wav() {
+        let path =
+            std::env::temp_dir().join(format!("audio_recorder_{}.wav", uuid::Uuid::new_v4()));
+        let mut writer = WavWriter::create(&path, MIX_SAMPLE_RATE, CHANNELS).unwrap();
+        writer.write_samples(&[0.0, 0.5, -0.5]).unwrap();
+        writer.finish().unwrap();
+
+        let mut stored = chunk(Utc::now(), None);
+        stored.path = path.to_string_lossy().to_string();
+        let samples = read_chunk_samples(&stored).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(samples.len(), 3);
+        assert!((samples[1] - 0.5).abs() < 1e-3);
+        assert!((samples[2] + 0.5).abs() < 1e-3);
+        assert!(wav_data(b"not a wav").is_err());
+    }
+
+    #[test]
+    fn test_offset_in_chunk() {
+        let start = Utc::now();
+        let finished = chunk(start, Some(start + Duration::seconds(300)));
+        assert_eq!(
+            offset_in_chunk(&finished, start + Duration::milliseconds(12_500)),
+            Some(12.5)
+        );
+        assert_eq!(
+            offset_in_chunk(&finished, start + Duration::seconds(301)),
+            None
+        );
+        assert_eq!(
+            offset_in_chunk(&finished, start - Duration::seconds(1)),
+            None
+        );
+
+        // A chunk still being written covers everything after its start
+        let open = chunk(start, None);
+        assert_eq!(
+            offset_in_chunk(&open, start + Duration::seconds(400)),
+            Some(400.0)
+        );
+    }
+
+    #[test]
+    fn test_format_setting() {
+        assert_eq!(AudioFormat::from_setting("aac"), AudioFormat::Aac);
+        assert_eq!(AudioFormat::from_setting("wav"), AudioFormat::Wav);
+        assert_eq!(AudioFormat::from_setting("flac"), AudioFormat::Wav);
+    }
+}
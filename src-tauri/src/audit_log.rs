@@ -3,12 +3,21 @@
 //
 // Features:
 // - Append-only action logging
-// - Query by action type, target, time range
-// - Export capability
+// - Query by action type, actor, time range
+// - Streaming CSV / JSONL export
+// - Retention: entries past the retention window are folded into one
+//   "compacted" summary record per day instead of being deleted silently
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use futures_util::TryStreamExt;
 use serde::{Deserialize, Serialize};
-use sqlx::{Pool, Row, Sqlite};
+use sqlx::{Pool, QueryBuilder, Row, Sqlite};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+/// Action of the per-day summary records written by compaction
+pub const COMPACTED_ACTION: &str = "compacted";
 
 /// Represents an entry in the audit log
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +29,109 @@ pub struct AuditEntry {
     pub details: Option<String>, // JSON metadata
     pub bytes_affected: u64,
     pub timestamp: DateTime<Utc>,
+    pub actor: String, // "user" or "system" (scheduled tasks)
+}
+
+/// Conditions for querying/exporting entries (unset = any)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditFilter {
+    pub action: Option<String>,
+    pub actor: Option<String>,
+    /// Entries at or after this (RFC 3339 or YYYY-MM-DD)
+    pub date_from: Option<String>,
+    /// Entries before this; a bare date includes that whole day
+    pub date_to: Option<String>,
+}
+
+impl AuditFilter {
+    fn push_conditions(&self, query: &mut QueryBuilder<'_, Sqlite>) {
+        query.push(" WHERE 1 = 1");
+        if let Some(action) = &self.action {
+            query.push(" AND action = ").push_bind(action.clone());
+        }
+        if let Some(actor) = &self.actor {
+            query.push(" AND actor = ").push_bind(actor.clone());
+        }
+        if let Some(from) = &self.date_from {
+            query.push(" AND timestamp >= ").push_bind(from.clone());
+        }
+        if let Some(to) = &self.date_to {
+            // "2024-01-31" sorts before every timestamp on that day
+            let to = if to.len() == 10 {
+                format!("{}~", to)
+            } else {
+                to.clone()
+            };
+            query.push(" AND timestamp < ").push_bind(to);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "jsonl" | "ndjson" => Ok(ExportFormat::Jsonl),
+            other => Err(format!("Unsupported export format: {}", other)),
+        }
+    }
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or newline
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn entry_from_row(row: &sqlx::sqlite::SqliteRow) -> Option<AuditEntry> {
+    let timestamp_str: String = row.get("timestamp");
+    let timestamp = DateTime::parse_from_rfc3339(&timestamp_str)
+        .ok()?
+        .with_timezone(&Utc);
+
+    Some(AuditEntry {
+        id: row.get("id"),
+        action: row.get("action"),
+        target_type: row.get("target_type"),
+        target_id: row.get("target_id"),
+        details: row.get("details"),
+        bytes_affected: row.get::<i64, _>("bytes_affected") as u64,
+        timestamp,
+        actor: row.get("actor"),
+    })
+}
+
+/// What one day's compacted record covers
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct DaySummary {
+    entries: i64,
+    bytes_affected: i64,
+    actions: BTreeMap<String, i64>,
+    actors: BTreeMap<String, i64>,
+}
+
+impl DaySummary {
+    fn merge(&mut self, other: &DaySummary) {
+        self.entries += other.entries;
+        self.bytes_affected += other.bytes_affected;
+        for (action, n) in &other.actions {
+            *self.actions.entry(action.clone()).or_default() += n;
+        }
+        for (actor, n) in &other.actors {
+            *self.actors.entry(actor.clone()).or_default() += n;
+        }
+    }
 }
 
 /// Input for creating a new audit entry
@@ -43,14 +155,19 @@ impl AuditLog {
         Self { pool }
     }
 
-    /// Log an action (append-only)
+    /// Log an action taken by the user (append-only)
     pub async fn log_action(&self, action: AuditAction) -> Result<i64, String> {
+        self.log_action_as(action, "user").await
+    }
+
+    /// Log an action on behalf of `actor` (e.g. "system" for scheduled tasks)
+    pub async fn log_action_as(&self, action: AuditAction, actor: &str) -> Result<i64, String> {
         let now = Utc::now();
 
         let result = sqlx::query(
             r#"
-            INSERT INTO audit_log (action, target_type, target_id, details, bytes_affected, timestamp)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO audit_log (action, target_type, target_id, details, bytes_affected, timestamp, actor)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&action.action)
@@ -59,6 +176,7 @@ impl AuditLog {
         .bind(&action.details)
         .bind(action.bytes_affected as i64)
         .bind(now.to_rfc3339())
+        .bind(actor)
         .execute(&self.pool)
         .await
         .map_err(|e| format!("Failed to insert audit log: {}", e))?;
@@ -117,84 +235,218 @@ impl AuditLog {
         .await
     }
 
-    /// Query audit log entries
+    /// Query audit log entries, newest first
     pub async fn get_entries(
         &self,
         limit: u32,
         offset: u32,
-        action_filter: Option<&str>,
+        filter: &AuditFilter,
     ) -> Result<Vec<AuditEntry>, String> {
-        let query = if let Some(action) = action_filter {
-            sqlx::query(
-                r#"
-                SELECT id, action, target_type, target_id, details, bytes_affected, timestamp
-                FROM audit_log
-                WHERE action = ?
-                ORDER BY timestamp DESC
-                LIMIT ? OFFSET ?
-                "#,
-            )
-            .bind(action)
-            .bind(limit)
-            .bind(offset)
-        } else {
-            sqlx::query(
-                r#"
-                SELECT id, action, target_type, target_id, details, bytes_affected, timestamp
-                FROM audit_log
-                ORDER BY timestamp DESC
-                LIMIT ? OFFSET ?
-                "#,
-            )
-            .bind(limit)
-            .bind(offset)
-        };
+        let mut query = QueryBuilder::new(
+            "SELECT id, action, target_type, target_id, details, bytes_affected, timestamp, actor
+             FROM audit_log",
+        );
+        filter.push_conditions(&mut query);
+        query
+            .push(" ORDER BY timestamp DESC LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
 
         let rows = query
+            .build()
             .fetch_all(&self.pool)
             .await
             .map_err(|e| format!("Failed to query audit log: {}", e))?;
 
-        let entries = rows
-            .iter()
-            .filter_map(|row| {
-                let timestamp_str: String = row.get("timestamp");
-                let timestamp = DateTime::parse_from_rfc3339(&timestamp_str)
-                    .ok()?
-                    .with_timezone(&Utc);
-
-                Some(AuditEntry {
-                    id: row.get("id"),
-                    action: row.get("action"),
-                    target_type: row.get("target_type"),
-                    target_id: row.get("target_id"),
-                    details: row.get("details"),
-                    bytes_affected: row.get::<i64, _>("bytes_affected") as u64,
-                    timestamp,
-                })
-            })
-            .collect();
-
-        Ok(entries)
+        Ok(rows.iter().filter_map(entry_from_row).collect())
     }
 
-    /// Get count of audit entries
-    pub async fn count_entries(&self, action_filter: Option<&str>) -> Result<u32, String> {
-        let count: i64 = if let Some(action) = action_filter {
-            sqlx::query_scalar("SELECT COUNT(*) FROM audit_log WHERE action = ?")
-                .bind(action)
-                .fetch_one(&self.pool)
-                .await
-                .map_err(|e| format!("Failed to count: {}", e))?
-        } else {
-            sqlx::query_scalar("SELECT COUNT(*) FROM audit_log")
-                .fetch_one(&self.pool)
-                .await
-                .map_err(|e| format!("Failed to count: {}", e))?
-        };
+    /// Get count of audit entries matching `filter`
+    pub async fn count_entries(&self, filter: &AuditFilter) -> Result<u32, String> {
+        let mut query = QueryBuilder::new("SELECT COUNT(*) FROM audit_log");
+        filter.push_conditions(&mut query);
+
+        let count: i64 = query
+            .build_query_scalar()
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to count: {}", e))?;
 
         Ok(count as u32)
     }
+
+    /// Write entries matching `filter` to `path`, oldest first, one row at a
+    /// time. Returns the number of entries written.
+    pub async fn export(
+        &self,
+        filter: &AuditFilter,
+        format: ExportFormat,
+        path: &Path,
+    ) -> Result<u64, String> {
+        let file = std::fs::File::create(path)
+            .map_err(|e| format!("Failed to create export file: {}", e))?;
+        let mut out = std::io::BufWriter::new(file);
+        let write_err = |e: std::io::Error| format!("Failed to write export: {}", e);
+
+        if format == ExportFormat::Csv {
+            writeln!(
+                out,
+                "id,timestamp,action,actor,target_type,target_id,bytes_affected,details"
+            )
+            .map_err(write_err)?;
+        }
+
+        let mut query = QueryBuilder::new(
+            "SELECT id, action, target_type, target_id, details, bytes_affected, timestamp, actor
+             FROM audit_log",
+        );
+        filter.push_conditions(&mut query);
+        query.push(" ORDER BY timestamp ASC");
+
+        let mut rows = query.build().fetch(&self.pool);
+        let mut written = 0u64;
+        while let Some(row) = rows
+            .try_next()
+            .await
+            .map_err(|e| format!("Failed to read audit log: {}", e))?
+        {
+            let Some(entry) = entry_from_row(&row) else {
+                continue;
+            };
+            match format {
+                ExportFormat::Csv => writeln!(
+                    out,
+                    "{},{},{},{},{},{},{},{}",
+                    entry.id,
+                    entry.timestamp.to_rfc3339(),
+                    csv_field(&entry.action),
+                    csv_field(&entry.actor),
+                    csv_field(&entry.target_type),
+                    csv_field(&entry.target_id),
+                    entry.bytes_affected,
+                    csv_field(entry.details.as_deref().unwrap_or(""))
+                ),
+                ExportFormat::Jsonl => writeln!(
+                    out,
+                    "{}",
+                    serde_json::to_string(&entry).unwrap_or_else(|_| "{}".to_string())
+                ),
+            }
+            .map_err(write_err)?;
+            written += 1;
+        }
+
+        out.flush().map_err(write_err)?;
+        Ok(written)
+    }
+
+    /// Fold entries older than `retention_days` into one "compacted" record
+    /// per day (per-action and per-actor counts, total bytes) and delete the
+    /// originals. Returns the number of entries folded.
+    pub async fn compact_older_than(&self, retention_days: u32) -> Result<u64, String> {
+        // Whole days only, so a day is summarized once
+        let cutoff = (Utc::now() - Duration::days(retention_days as i64))
+            .format("%Y-%m-%d")
+            .to_string();
+        let db_err = |e: sqlx::Error| format!("Failed to compact audit log: {}", e);
+
+        let rows = sqlx::query(
+            r#"
+            SELECT substr(timestamp, 1, 10) AS day, action, actor, COUNT(*) AS n,
+                   COALESCE(SUM(bytes_affected), 0) AS bytes, MAX(timestamp) AS last_ts
+            FROM audit_log
+            WHERE action != ? AND substr(timestamp, 1, 10) < ?
+            GROUP BY day, action, actor
+            "#,
+        )
+        .bind(COMPACTED_ACTION)
+        .bind(&cutoff)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_err)?;
+
+        let mut days: BTreeMap<String, (DaySummary, String)> = BTreeMap::new();
+        for row in &rows {
+            let (summary, last_ts) = days.entry(row.get("day")).or_default();
+            let n: i64 = row.get("n");
+            summary.entries += n;
+            summary.bytes_affected += row.get::<i64, _>("bytes");
+            *summary.actions.entry(row.get("action")).or_default() += n;
+            *summary.actors.entry(row.get("actor")).or_default() += n;
+            let ts: String = row.get("last_ts");
+            if ts > *last_ts {
+                *last_ts = ts;
+            }
+        }
+
+        let mut folded = 0u64;
+        let mut tx = self.pool.begin().await.map_err(db_err)?;
+        for (day, (mut summary, last_ts)) in days {
+            folded += summary.entries as u64;
+
+            let existing =
+                sqlx::query("SELECT id, details FROM audit_log WHERE action = ? AND target_id = ?")
+                    .bind(COMPACTED_ACTION)
+                    .bind(&day)
+                    .fetch_optional(&mut *tx)
+                    .await
+                    .map_err(db_err)?;
+
+            match existing {
+                Some(row) => {
+                    let previous: Option<DaySummary> = row
+                        .get::<Option<String>, _>("details")
+                        .and_then(|d| serde_json::from_str(&d).ok());
+                    if let Some(previous) = previous {
+                        summary.merge(&previous);
+                    }
+                    sqlx::query(
+                        "UPDATE audit_log SET details = ?, bytes_affected = ? WHERE id = ?",
+                    )
+                    .bind(serde_json::to_string(&summary).ok())
+                    .bind(summary.bytes_affected)
+                    .bind(row.get::<i64, _>("id"))
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(db_err)?;
+                }
+                None => {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO audit_log (action, target_type, target_id, details, bytes_affected, timestamp, actor)
+                        VALUES (?, 'audit_log', ?, ?, ?, ?, 'system')
+                        "#,
+                    )
+                    .bind(COMPACTED_ACTION)
+                    .bind(&day)
+                    .bind(serde_json::to_string(&summary).ok())
+                    .bind(summary.bytes_affected)
+                    .bind(&last_ts)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(db_err)?;
+                }
+            }
+
+            sqlx::query("DELETE FROM audit_log WHERE action != ? AND substr(timestamp, 1, 10) = ?")
+                .bind(COMPACTED_ACTION)
+                .bind(&day)
+                .execute(&mut *tx)
+                .await
+                .map_err(db_err)?;
+        }
+        tx.commit().await.map_err(db_err)?;
+
+        if folded > 0 {
+            log::info!(
+                "Compacted {} audit entries older than {} into daily summaries",
+                folded,
+                cutoff
+            );
+        }
+        Ok(folded)
+    }
 }
 
 #[cfg(test)]
@@ -218,7 +470,8 @@ mod tests {
                 target_id TEXT NOT NULL,
                 details TEXT,
                 bytes_affected INTEGER DEFAULT 0,
-                timestamp TEXT NOT NULL DEFAULT (datetime('now'))
+                timestamp TEXT NOT NULL DEFAULT (datetime('now')),
+                actor TEXT NOT NULL DEFAULT 'user'
             )
             "#,
         )
@@ -229,6 +482,26 @@ mod tests {
         pool
     }
 
+    fn action_filter(action: &str) -> AuditFilter {
+        AuditFilter {
+            action: Some(action.to_string()),
+            ..Default::default()
+        }
+    }
+
+    async fn insert_at(pool: &Pool<Sqlite>, action: &str, timestamp: &str, bytes: i64) {
+        sqlx::query(
+            "INSERT INTO audit_log (action, target_type, target_id, bytes_affected, timestamp)
+             VALUES (?, 'meeting', 'm1', ?, ?)",
+        )
+        .bind(action)
+        .bind(bytes)
+        .bind(timestamp)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
     #[tokio::test]
     async fn test_log_and_query() {
         let pool = setup_test_db().await;
@@ -242,7 +515,10 @@ mod tests {
         assert!(id > 0);
 
         // Query entries
-        let entries = audit.get_entries(10, 0, None).await.unwrap();
+        let entries = audit
+            .get_entries(10, 0, &AuditFilter::default())
+            .await
+            .unwrap();
         assert_eq!(entries.len(), 1);
         assert_eq!(entries[0].action, "delete");
         assert_eq!(entries[0].target_id, "test123");
@@ -264,10 +540,136 @@ mod tests {
             .unwrap();
 
         // Filter by action
-        let deletes = audit.get_entries(10, 0, Some("delete")).await.unwrap();
+        let deletes = audit
+            .get_entries(10, 0, &action_filter("delete"))
+            .await
+            .unwrap();
         assert_eq!(deletes.len(), 1);
 
-        let edits = audit.get_entries(10, 0, Some("edit")).await.unwrap();
+        let edits = audit
+            .get_entries(10, 0, &action_filter("edit"))
+            .await
+            .unwrap();
         assert_eq!(edits.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_filter_by_actor_and_count() {
+        let pool = setup_test_db().await;
+        let audit = AuditLog::new(pool);
+
+        audit
+            .log_deletion("meeting", "m1", 1000, None)
+            .await
+            .unwrap();
+        audit
+            .log_action_as(
+                AuditAction {
+                    action: "delete".to_string(),
+                    target_type: "meeting".to_string(),
+                    target_id: "m2".to_string(),
+                    details: None,
+                    bytes_affected: 0,
+                },
+                "system",
+            )
+            .await
+            .unwrap();
+
+        let system = AuditFilter {
+            actor: Some("system".to_string()),
+            ..action_filter("delete")
+        };
+        let entries = audit.get_entries(10, 0, &system).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].target_id, "m2");
+        assert_eq!(audit.count_entries(&system).await.unwrap(), 1);
+        assert_eq!(
+            audit.count_entries(&action_filter("delete")).await.unwrap(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compaction_keeps_daily_summary() {
+        let pool = setup_test_db().await;
+        let audit = AuditLog::new(pool.clone());
+
+        insert_at(&pool, "delete", "2020-03-01T09:00:00+00:00", 100).await;
+        insert_at(&pool, "delete", "2020-03-01T17:00:00+00:00", 50).await;
+        insert_at(&pool, "edit", "2020-03-01T18:00:00+00:00", 0).await;
+        insert_at(&pool, "edit", "2020-03-02T08:00:00+00:00", 0).await;
+        audit
+            .log_edit("entity", "e1", "name", None, "x")
+            .await
+            .unwrap();
+
+        assert_eq!(audit.compact_older_than(30).await.unwrap(), 4);
+        // Running again folds nothing new
+        assert_eq!(audit.compact_older_than(30).await.unwrap(), 0);
+
+        let compacted = audit
+            .get_entries(10, 0, &action_filter(COMPACTED_ACTION))
+            .await
+            .unwrap();
+        assert_eq!(compacted.len(), 2);
+        let march_first = compacted
+            .iter()
+            .find(|e| e.target_id == "2020-03-01")
+            .unwrap();
+        assert_eq!(march_first.bytes_affected, 150);
+        let summary: DaySummary =
+            serde_json::from_str(march_first.details.as_deref().unwrap()).unwrap();
+        assert_eq!(summary.entries, 3);
+        assert_eq!(summary.actions["delete"], 2);
+
+        // The recent edit is untouched
+        assert_eq!(
+            audit.count_entries(&action_filter("edit")).await.unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_jsonl_and_csv() {
+        let pool = setup_test_db().await;
+        let audit = AuditLog::new(pool.clone());
+
+        insert_at(&pool, "delete", "2024-01-01T09:00:00+00:00", 0).await;
+        insert_at(&pool, "edit", "2024-01-31T23:00:00+00:00", 0).await;
+        insert_at(&pool, "edit", "2024-02-01T00:00:00+00:00", 0).await;
+
+        let january = AuditFilter {
+            date_from: Some("2024-01-01".to_string()),
+            date_to: Some("2024-01-31".to_string()),
+            ..Default::default()
+        };
+        let dir = std::env::temp_dir().join(format!("audit_export_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let jsonl = dir.join("audit.jsonl");
+        let written = audit
+            .export(&january, ExportFormat::Jsonl, &jsonl)
+            .await
+            .unwrap();
+        assert_eq!(written, 2);
+        let first: AuditEntry = serde_json::from_str(
+            std::fs::read_to_string(&jsonl)
+                .unwrap()
+                .lines()
+                .next()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(first.action, "delete");
+
+        let csv = dir.join("audit.csv");
+        audit
+            .export(&january, ExportFormat::Csv, &csv)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&csv).unwrap().lines().count(), 3);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -37,12 +37,27 @@ pub struct CalendarEventNative {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CalendarAccessStatus {
     Authorized,
+    /// Events can be created but not read (macOS 14+)
+    WriteOnly,
     Denied,
     Restricted,
     NotDetermined,
     Unknown,
 }
 
+/// A calendar the user can pick for detection and intel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarInfo {
+    pub id: String,
+    pub name: String,
+    /// "#rrggbb"
+    pub color: Option<String>,
+    /// Account it belongs to (iCloud, Google, ...)
+    pub source: Option<String>,
+    /// Whether events from it are used
+    pub selected: bool,
+}
+
 /// Configuration for calendar client
 #[derive(Debug, Clone)]
 pub struct CalendarConfig {
@@ -54,6 +69,8 @@ pub struct CalendarConfig {
     pub included_calendars: Vec<String>,
     /// Calendar names to exclude
     pub excluded_calendars: Vec<String>,
+    /// Calendar ids picked by the user (empty = all)
+    pub selected_calendars: Vec<String>,
 }
 
 impl Default for CalendarConfig {
@@ -63,6 +80,7 @@ impl Default for CalendarConfig {
             lookbehind_hours: 2,
             included_calendars: Vec::new(),
             excluded_calendars: vec!["Birthdays".to_string(), "Holidays".to_string()],
+            selected_calendars: Vec::new(),
         }
     }
 }
@@ -88,6 +106,8 @@ impl Default for CalendarCache {
 pub struct CalendarClient {
     config: CalendarConfig,
     cache: Arc<RwLock<CalendarCache>>,
+    /// Events from the last .ics import (for users without EventKit access)
+    imported: Arc<RwLock<Vec<CalendarEventNative>>>,
 }
 
 impl CalendarClient {
@@ -99,9 +119,26 @@ impl CalendarClient {
         Self {
             config,
             cache: Arc::new(RwLock::new(CalendarCache::default())),
+            imported: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
+    /// Use only these calendars (by id); empty means all of them
+    pub fn set_selected_calendars(&mut self, calendar_ids: Vec<String>) {
+        self.config.selected_calendars = calendar_ids;
+        self.clear_cache();
+    }
+
+    /// Whether events from this calendar are used
+    pub fn is_selected(&self, calendar_id: &str) -> bool {
+        self.config.selected_calendars.is_empty()
+            || self
+                .config
+                .selected_calendars
+                .iter()
+                .any(|id| id == calendar_id)
+    }
+
     /// Check calendar access status
     #[cfg(target_os = "macos")]
     pub fn check_access() -> CalendarAccessStatus {
@@ -122,6 +159,7 @@ impl CalendarClient {
                 1 => CalendarAccessStatus::Restricted,
                 2 => CalendarAccessStatus::Denied,
                 3 => CalendarAccessStatus::Authorized,
+                4 => CalendarAccessStatus::WriteOnly,
                 _ => CalendarAccessStatus::Unknown,
             }
         }
@@ -174,6 +212,206 @@ impl CalendarClient {
         }
     }
 
+    /// Every event calendar, for picking which ones detection and intel use
+    #[cfg(target_os = "macos")]
+    pub fn list_calendars(&self) -> Result<Vec<CalendarInfo>, String> {
+        use objc::runtime::{Class, Object};
+        use objc::{msg_send, sel, sel_impl};
+
+        unsafe {
+            if Self::check_access() != CalendarAccessStatus::Authorized {
+                return Err("Calendar access not authorized".to_string());
+            }
+
+            let ek_class = Class::get("EKEventStore").ok_or("EKEventStore not found")?;
+            let store: *mut Object = msg_send![ek_class, alloc];
+            let store: *mut Object = msg_send![store, init];
+
+            if store.is_null() {
+                return Err("Failed to create EKEventStore".to_string());
+            }
+
+            let calendars: *mut Object = msg_send![store, calendarsForEntityType: 0i64];
+            if calendars.is_null() {
+                return Ok(Vec::new());
+            }
+
+            let count: usize = msg_send![calendars, count];
+            let mut result = Vec::with_capacity(count);
+            for i in 0..count {
+                let calendar: *mut Object = msg_send![calendars, objectAtIndex: i];
+                if calendar.is_null() {
+                    continue;
+                }
+
+                let identifier: *mut Object = msg_send![calendar, calendarIdentifier];
+                let title: *mut Object = msg_send![calendar, title];
+                let id = nsstring_to_rust(identifier);
+
+                let source: *mut Object = msg_send![calendar, source];
+                let source = if source.is_null() {
+                    None
+                } else {
+                    let source_title: *mut Object = msg_send![source, title];
+                    Some(nsstring_to_rust(source_title)).filter(|s| !s.is_empty())
+                };
+
+                result.push(CalendarInfo {
+                    selected: self.is_selected(&id),
+                    id,
+                    name: nsstring_to_rust(title),
+                    color: nscolor_to_hex(msg_send![calendar, color]),
+                    source,
+                });
+            }
+
+            result.sort_by(|a, b| a.source.cmp(&b.source).then(a.name.cmp(&b.name)));
+            Ok(result)
+        }
+    }
+
+    /// Whether events can be created (full or write-only access)
+    pub fn check_write_access() -> bool {
+        matches!(
+            Self::check_access(),
+            CalendarAccessStatus::Authorized | CalendarAccessStatus::WriteOnly
+        )
+    }
+
+    /// Request write access (will prompt user); asked separately from read
+    /// access, so creating follow-ups doesn't need the full calendar
+    #[cfg(target_os = "macos")]
+    pub async fn request_write_access() -> Result<bool, String> {
+        use objc::runtime::{Class, Object};
+        use objc::{msg_send, sel, sel_impl};
+
+        match Self::check_access() {
+            CalendarAccessStatus::Authorized | CalendarAccessStatus::WriteOnly => return Ok(true),
+            CalendarAccessStatus::Denied | CalendarAccessStatus::Restricted => return Ok(false),
+            _ => {}
+        }
+
+        unsafe {
+            let ek_class = Class::get("EKEventStore").ok_or("EKEventStore not found")?;
+            let store: *mut Object = msg_send![ek_class, alloc];
+            let store: *mut Object = msg_send![store, init];
+
+            if store.is_null() {
+                return Err("Failed to create EKEventStore".to_string());
+            }
+
+            // Same approach as request_access: touching the default calendar
+            // for new events triggers the system prompt
+            let _calendar: *mut Object = msg_send![store, defaultCalendarForNewEvents];
+
+            for _ in 0..100 {
+                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                if Self::check_access() != CalendarAccessStatus::NotDetermined {
+                    return Ok(Self::check_write_access());
+                }
+            }
+
+            Ok(Self::check_write_access())
+        }
+    }
+
+    /// Create an event; `calendar_name` picks a writable calendar by title,
+    /// otherwise the default calendar for new events. Returns the event id.
+    #[cfg(target_os = "macos")]
+    pub fn create_calendar_event(
+        title: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        notes: Option<&str>,
+        calendar_name: Option<&str>,
+    ) -> Result<String, String> {
+        use objc::runtime::{Class, Object, BOOL, NO};
+        use objc::{msg_send, sel, sel_impl};
+
+        if end <= start {
+            return Err("Event must end after it starts".to_string());
+        }
+        if !Self::check_write_access() {
+            return Err("Calendar write access not authorized".to_string());
+        }
+
+        unsafe {
+            let ek_class = Class::get("EKEventStore").ok_or("EKEventStore not found")?;
+            let store: *mut Object = msg_send![ek_class, alloc];
+            let store: *mut Object = msg_send![store, init];
+
+            if store.is_null() {
+                return Err("Failed to create EKEventStore".to_string());
+            }
+
+            let calendar: *mut Object = match calendar_name {
+                Some(name) => {
+                    let calendars: *mut Object = msg_send![store, calendarsForEntityType: 0i64];
+                    let count: usize = if calendars.is_null() {
+                        0
+                    } else {
+                        msg_send![calendars, count]
+                    };
+                    let mut found: *mut Object = std::ptr::null_mut();
+                    for i in 0..count {
+                        let calendar: *mut Object = msg_send![calendars, objectAtIndex: i];
+                        if calendar.is_null() {
+                            continue;
+                        }
+                        let title: *mut Object = msg_send![calendar, title];
+                        let writable: BOOL = msg_send![calendar, allowsContentModifications];
+                        if writable != NO && nsstring_to_rust(title) == name {
+                            found = calendar;
+                            break;
+                        }
+                    }
+                    if found.is_null() {
+                        return Err(format!("Calendar '{}' not found or read-only", name));
+                    }
+                    found
+                }
+                None => msg_send![store, defaultCalendarForNewEvents],
+            };
+            if calendar.is_null() {
+                return Err("No calendar available for new events".to_string());
+            }
+
+            let event_class = Class::get("EKEvent").ok_or("EKEvent not found")?;
+            let event: *mut Object = msg_send![event_class, eventWithEventStore: store];
+            if event.is_null() {
+                return Err("Failed to create EKEvent".to_string());
+            }
+
+            let _: () = msg_send![event, setTitle: rust_to_nsstring(title)];
+            let _: () = msg_send![event, setStartDate: chrono_to_nsdate(start)];
+            let _: () = msg_send![event, setEndDate: chrono_to_nsdate(end)];
+            if let Some(notes) = notes {
+                let _: () = msg_send![event, setNotes: rust_to_nsstring(notes)];
+            }
+            let _: () = msg_send![event, setCalendar: calendar];
+
+            // EKSpanThisEvent = 0
+            let mut error: *mut Object = std::ptr::null_mut();
+            let saved: BOOL = msg_send![store, saveEvent:event span:0i64 error:&mut error];
+            if saved == NO {
+                let reason = if error.is_null() {
+                    "unknown error".to_string()
+                } else {
+                    let description: *mut Object = msg_send![error, localizedDescription];
+                    nsstring_to_rust(description)
+                };
+                return Err(format!("Failed to save calendar event: {}", reason));
+            }
+
+            let event_id_obj: *mut Object = msg_send![event, eventIdentifier];
+            let event_id = nsstring_to_rust(event_id_obj);
+            if event_id.is_empty() {
+                return Err("Calendar event saved without an identifier".to_string());
+            }
+            Ok(event_id)
+        }
+    }
+
     /// Fetch events for today (with caching)
     #[cfg(target_os = "macos")]
     pub fn fetch_events(&self) -> Result<Vec<CalendarEventNative>, String> {
@@ -264,15 +502,21 @@ impl CalendarClient {
                     continue;
                 }
 
-                // Get calendar name
+                // Get calendar name and id
                 let calendar: *mut Object = msg_send![event, calendar];
-                let calendar_name = if !calendar.is_null() {
+                let (calendar_name, calendar_id) = if !calendar.is_null() {
                     let title: *mut Object = msg_send![calendar, title];
-                    nsstring_to_rust(title)
+                    let identifier: *mut Object = msg_send![calendar, calendarIdentifier];
+                    (nsstring_to_rust(title), nsstring_to_rust(identifier))
                 } else {
-                    String::new()
+                    (String::new(), String::new())
                 };
 
+                // Check the user's calendar selection
+                if !self.is_selected(&calendar_id) {
+                    continue;
+                }
+
                 // Check exclusions
                 if self
                     .config
@@ -408,6 +652,30 @@ impl CalendarClient {
         None
     }
 
+    /// Replace the events remembered from an .ics import
+    pub fn set_imported_events(&self, events: Vec<CalendarEventNative>) {
+        *self.imported.write() = events;
+    }
+
+    /// Find an event by id in EventKit, falling back to imported .ics events
+    pub fn find_event(&self, event_id: &str) -> Result<CalendarEventNative, String> {
+        let native = self.fetch_events();
+        if let Some(event) = native
+            .as_ref()
+            .ok()
+            .and_then(|events| events.iter().find(|e| e.event_id == event_id))
+        {
+            return Ok(event.clone());
+        }
+        if let Some(event) = self.imported.read().iter().find(|e| e.event_id == event_id) {
+            return Ok(event.clone());
+        }
+        match native {
+            Err(e) => Err(format!("Calendar error: {}", e)),
+            Ok(_) => Err(format!("Calendar event '{}' not found", event_id)),
+        }
+    }
+
     /// Clear the event cache
     pub fn clear_cache(&self) {
         let mut cache = self.cache.write();
@@ -430,6 +698,27 @@ impl CalendarClient {
     pub fn fetch_events(&self) -> Result<Vec<CalendarEventNative>, String> {
         Err("Calendar access only available on macOS".to_string())
     }
+
+    #[cfg(not(target_os = "macos"))]
+    pub fn list_calendars(&self) -> Result<Vec<CalendarInfo>, String> {
+        Err("Calendar access only available on macOS".to_string())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub async fn request_write_access() -> Result<bool, String> {
+        Err("Calendar access only available on macOS".to_string())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub fn create_calendar_event(
+        _title: &str,
+        _start: DateTime<Utc>,
+        _end: DateTime<Utc>,
+        _notes: Option<&str>,
+        _calendar_name: Option<&str>,
+    ) -> Result<String, String> {
+        Err("Calendar access only available on macOS".to_string())
+    }
 }
 
 impl Default for CalendarClient {
@@ -439,7 +728,10 @@ impl Default for CalendarClient {
 }
 
 /// Extract meeting URL from location or notes
-fn extract_meeting_url(location: &Option<String>, notes: &Option<String>) -> Option<String> {
+pub(crate) fn extract_meeting_url(
+    location: &Option<String>,
+    notes: &Option<String>,
+) -> Option<String> {
     let text = format!(
         "{} {}",
         location.as_deref().unwrap_or(""),
@@ -522,6 +814,54 @@ unsafe fn nsstring_to_rust(nsstring: *mut objc::runtime::Object) -> String {
     String::new()
 }
 
+/// Helper to convert a Rust string to an autoreleased NSString
+#[cfg(target_os = "macos")]
+unsafe fn rust_to_nsstring(value: &str) -> *mut objc::runtime::Object {
+    use objc::{class, msg_send, sel, sel_impl};
+    use std::ffi::CString;
+
+    let c_string = CString::new(value.replace('\0', "")).unwrap_or_default();
+    msg_send![class!(NSString), stringWithUTF8String: c_string.as_ptr()]
+}
+
+/// Helper to convert chrono DateTime to NSDate
+#[cfg(target_os = "macos")]
+unsafe fn chrono_to_nsdate(dt: DateTime<Utc>) -> *mut objc::runtime::Object {
+    use objc::{class, msg_send, sel, sel_impl};
+
+    let interval = dt.timestamp() as f64 - 978307200.0;
+    msg_send![class!(NSDate), dateWithTimeIntervalSinceReferenceDate: interval]
+}
+
+/// Helper to convert an NSColor to "#rrggbb"
+#[cfg(target_os = "macos")]
+unsafe fn nscolor_to_hex(color: *mut objc::runtime::Object) -> Option<String> {
+    use objc::runtime::Object;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    if color.is_null() {
+        return None;
+    }
+
+    // Components are only defined once the color is in an RGB space
+    let srgb: *mut Object = msg_send![class!(NSColorSpace), sRGBColorSpace];
+    let rgb: *mut Object = msg_send![color, colorUsingColorSpace: srgb];
+    if rgb.is_null() {
+        return None;
+    }
+
+    let red: f64 = msg_send![rgb, redComponent];
+    let green: f64 = msg_send![rgb, greenComponent];
+    let blue: f64 = msg_send![rgb, blueComponent];
+    let byte = |c: f64| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Some(format!(
+        "#{:02x}{:02x}{:02x}",
+        byte(red),
+        byte(green),
+        byte(blue)
+    ))
+}
+
 /// Helper to convert NSDate to chrono DateTime
 #[cfg(target_os = "macos")]
 unsafe fn nsdate_to_chrono(nsdate: *mut objc::runtime::Object) -> DateTime<Utc> {
@@ -553,6 +893,18 @@ mod tests {
         assert!(config.excluded_calendars.contains(&"Birthdays".to_string()));
     }
 
+    #[test]
+    fn test_calendar_selection() {
+        let mut client = CalendarClient::new();
+        // Nothing picked means every calendar
+        assert!(client.is_selected("work"));
+        assert!(client.is_selected("shared-family"));
+
+        client.set_selected_calendars(vec!["work".to_string()]);
+        assert!(client.is_selected("work"));
+        assert!(!client.is_selected("shared-family"));
+    }
+
     #[test]
     fn test_extract_meeting_url() {
         let location = Some("https://zoom.us/j/123456789".to_string());
@@ -7,10 +7,11 @@ use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use image::DynamicImage;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
-use std::sync::Arc;
-use tauri::AppHandle;
-use xcap::Monitor;
+use std::sync::{Arc, OnceLock};
+use tauri::{AppHandle, Emitter};
+use xcap::{Monitor, Window};
 
 /// Audio buffer from capture
 #[derive(Debug, Clone)]
@@ -58,9 +59,26 @@ impl Default for CaptureMode {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecordingStatus {
     pub is_recording: bool,
+    pub is_paused: bool,
     pub duration_seconds: u64,
     pub video_frames: usize,
     pub audio_samples: usize,
+    /// Transcript segments waiting on a DB insert retry
+    pub transcripts_pending_retry: usize,
+    /// Transcript segments that exhausted retries (written to failed_transcripts.jsonl)
+    pub transcripts_failed: usize,
+    /// Active transcription provider's connection state
+    pub transcription_health: crate::transcription::ConnectionHealth,
+    /// Reconnecting or given up while recording ("transcription degraded")
+    pub transcription_degraded: bool,
+}
+
+/// Whether system audio can be captured on this machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemAudioStatus {
+    pub available: bool,
+    /// Why system audio is unavailable (OS too old, permission denied, ...)
+    pub reason: Option<String>,
 }
 
 /// Audio device info
@@ -82,6 +100,38 @@ pub struct MonitorInfo {
     pub is_primary: bool,
 }
 
+/// Window that can be targeted for window-scoped capture
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturableWindow {
+    pub id: u32,
+    pub app_name: String,
+    pub title: String,
+    pub width: u32,
+    pub height: u32,
+    pub monitor_id: Option<u32>,
+    pub is_focused: bool,
+}
+
+/// Payload for `capture-window-lost` when the target window disappears mid-recording
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureWindowLost {
+    pub window_id: u32,
+    pub reason: String,
+}
+
+/// Per-display capture health for diagnostics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorHealth {
+    pub monitor_id: u32,
+    pub name: String,
+    pub frames_captured: u64,
+    pub failures: u64,
+    /// Failures since the last successful frame
+    pub consecutive_failures: u32,
+    pub last_frame_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_error: Option<String>,
+}
+
 /// Audio callback type
 pub type AudioCallback = Arc<dyn Fn(AudioBuffer) + Send + Sync>;
 
@@ -92,6 +142,63 @@ pub type FrameCallback = Arc<dyn Fn(CapturedFrame) + Send + Sync>;
 static MIC_RUNNING: AtomicBool = AtomicBool::new(false);
 static SYSTEM_AUDIO_RUNNING: AtomicBool = AtomicBool::new(false);
 static SCREEN_RUNNING: AtomicBool = AtomicBool::new(false);
+/// Set while a recording is paused: capture threads stay alive but drop their output
+static CAPTURE_SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+/// Last system audio failure reported by the capture thread (None = working or untried)
+fn system_audio_error() -> &'static RwLock<Option<String>> {
+    static ERROR: OnceLock<RwLock<Option<String>>> = OnceLock::new();
+    ERROR.get_or_init(|| RwLock::new(None))
+}
+
+/// Capture health per display for the current screen capture run
+fn monitor_health() -> &'static RwLock<HashMap<u32, MonitorHealth>> {
+    static HEALTH: OnceLock<RwLock<HashMap<u32, MonitorHealth>>> = OnceLock::new();
+    HEALTH.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn record_monitor_capture(monitor_id: u32, name: &str, error: Option<String>) {
+    let mut health = monitor_health().write();
+    let entry = health.entry(monitor_id).or_insert_with(|| MonitorHealth {
+        monitor_id,
+        name: name.to_string(),
+        frames_captured: 0,
+        failures: 0,
+        consecutive_failures: 0,
+        last_frame_at: None,
+        last_error: None,
+    });
+    match error {
+        None => {
+            entry.frames_captured += 1;
+            entry.consecutive_failures = 0;
+            entry.last_frame_at = Some(chrono::Utc::now());
+        }
+        Some(error) => {
+            entry.failures += 1;
+            entry.consecutive_failures += 1;
+            entry.last_error = Some(error);
+        }
+    }
+}
+
+/// ScreenCaptureKit audio capture needs macOS 13 (Ventura) or later
+#[cfg(target_os = "macos")]
+const MIN_SYSTEM_AUDIO_MACOS: u32 = 13;
+
+#[cfg(target_os = "macos")]
+fn macos_major_version() -> Option<u32> {
+    let output = std::process::Command::new("sw_vers")
+        .arg("-productVersion")
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .split('.')
+        .next()?
+        .parse()
+        .ok()
+}
 
 /// Main capture engine - dual audio + screen
 pub struct CaptureEngine {
@@ -101,15 +208,31 @@ pub struct CaptureEngine {
     system_audio_count: Arc<AtomicUsize>,
     frame_number: Arc<AtomicU64>,
     start_time: Arc<RwLock<Option<std::time::Instant>>>,
+    /// When the current pause began (None while capturing)
+    paused_since: Arc<RwLock<Option<std::time::Instant>>>,
+    /// Total time spent paused in the current recording
+    paused_total: Arc<RwLock<std::time::Duration>>,
     selected_mic_id: Arc<RwLock<Option<String>>>,
     selected_monitor_id: Arc<RwLock<Option<u32>>>,
+    /// Capture every display during meetings instead of just the selected one
+    capture_all_monitors: Arc<AtomicBool>,
+    /// Capture only this window instead of a monitor (None = monitor capture)
+    capture_window_id: Arc<RwLock<Option<u32>>>,
     frame_interval_ms: Arc<RwLock<u32>>,
+    /// Adaptive stretch applied on top of frame_interval_ms (1.0 = as configured)
+    interval_factor: Arc<RwLock<f32>>,
     audio_callback: Arc<RwLock<Option<AudioCallback>>>,
     frame_callback: Arc<RwLock<Option<FrameCallback>>>,
     /// Current capture mode (Always-On Recording)
     capture_mode: Arc<RwLock<CaptureMode>>,
     /// Whether audio capture is enabled (off in Ambient mode)
     audio_enabled: Arc<AtomicBool>,
+    /// Capture the microphone (capture_microphone setting)
+    mic_enabled: Arc<AtomicBool>,
+    /// Capture system audio output (capture_system_audio setting)
+    system_audio_enabled: Arc<AtomicBool>,
+    /// Keep audio running in Ambient mode (feeds the rolling ambient buffer)
+    ambient_audio_enabled: Arc<AtomicBool>,
 }
 
 impl CaptureEngine {
@@ -121,16 +244,75 @@ impl CaptureEngine {
             system_audio_count: Arc::new(AtomicUsize::new(0)),
             frame_number: Arc::new(AtomicU64::new(0)),
             start_time: Arc::new(RwLock::new(None)),
+            paused_since: Arc::new(RwLock::new(None)),
+            paused_total: Arc::new(RwLock::new(std::time::Duration::ZERO)),
             selected_mic_id: Arc::new(RwLock::new(None)),
             selected_monitor_id: Arc::new(RwLock::new(None)),
+            capture_all_monitors: Arc::new(AtomicBool::new(false)),
+            capture_window_id: Arc::new(RwLock::new(None)),
             frame_interval_ms: Arc::new(RwLock::new(1000)), // Default: 1 screenshot per second
+            interval_factor: Arc::new(RwLock::new(1.0)),
             audio_callback: Arc::new(RwLock::new(None)),
             frame_callback: Arc::new(RwLock::new(None)),
             capture_mode: Arc::new(RwLock::new(CaptureMode::Paused)),
             audio_enabled: Arc::new(AtomicBool::new(true)),
+            mic_enabled: Arc::new(AtomicBool::new(true)),
+            system_audio_enabled: Arc::new(AtomicBool::new(true)),
+            ambient_audio_enabled: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Capture audio in Ambient mode too (for the rolling ambient buffer)
+    pub fn set_ambient_audio(&self, enabled: bool) {
+        self.ambient_audio_enabled.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Choose which audio sources the next recording captures
+    pub fn set_audio_sources(&self, microphone: bool, system_audio: bool) {
+        self.mic_enabled.store(microphone, Ordering::SeqCst);
+        self.system_audio_enabled
+            .store(system_audio, Ordering::SeqCst);
+        log::info!(
+            "Audio sources: microphone={}, system_audio={}",
+            microphone,
+            system_audio
+        );
+    }
+
+    /// Report whether system audio capture can work on this machine
+    pub fn system_audio_status() -> SystemAudioStatus {
+        if let Err(reason) = Self::check_system_audio_support() {
+            return SystemAudioStatus {
+                available: false,
+                reason: Some(reason),
+            };
+        }
+
+        // Permission problems only show up once the capture thread has tried
+        let error = system_audio_error().read().clone();
+        SystemAudioStatus {
+            available: error.is_none(),
+            reason: error,
+        }
+    }
+
+    /// OS-level support for system audio (independent of permissions)
+    #[cfg(target_os = "macos")]
+    fn check_system_audio_support() -> Result<(), String> {
+        match macos_major_version() {
+            Some(major) if major < MIN_SYSTEM_AUDIO_MACOS => Err(format!(
+                "System audio capture requires macOS {} or later (running macOS {})",
+                MIN_SYSTEM_AUDIO_MACOS, major
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn check_system_audio_support() -> Result<(), String> {
+        Err("System audio capture is only supported on macOS".to_string())
+    }
+
     /// Set the audio callback (receives both mic and system audio)
     pub fn set_audio_callback(&self, callback: AudioCallback) {
         *self.audio_callback.write() = Some(callback);
@@ -142,6 +324,23 @@ impl CaptureEngine {
     }
 
     /// Set the frame capture interval in milliseconds
+    pub fn frame_interval_ms(&self) -> u32 {
+        *self.frame_interval_ms.read()
+    }
+
+    /// Interval actually used between frames, after any adaptive stretch
+    pub fn effective_frame_interval_ms(&self) -> u32 {
+        crate::power_manager::stretched_interval(
+            *self.frame_interval_ms.read(),
+            *self.interval_factor.read(),
+        )
+    }
+
+    /// Stretch the configured interval (adaptive mode); picked up on the next frame
+    pub fn set_interval_factor(&self, factor: f32) {
+        *self.interval_factor.write() = factor.max(1.0);
+    }
+
     pub fn set_frame_interval(&self, interval_ms: u32) {
         let clamped = interval_ms.clamp(100, 60000); // 100ms to 60s range
         *self.frame_interval_ms.write() = clamped;
@@ -152,6 +351,36 @@ impl CaptureEngine {
         );
     }
 
+    /// Capture all displays in Meeting mode (ambient capture stays single-display)
+    pub fn set_capture_all_monitors(&self, enabled: bool) {
+        self.capture_all_monitors.store(enabled, Ordering::SeqCst);
+    }
+
+    pub fn captures_all_monitors(&self) -> bool {
+        self.capture_all_monitors.load(Ordering::SeqCst)
+    }
+
+    /// Target a single window (None = back to monitor capture); applies on the next frame
+    pub fn set_capture_window(&self, window_id: Option<u32>) {
+        *self.capture_window_id.write() = window_id;
+    }
+
+    pub fn capture_window(&self) -> Option<u32> {
+        *self.capture_window_id.read()
+    }
+
+    /// Monitor chosen for single-display capture (None = primary)
+    pub fn selected_monitor(&self) -> Option<u32> {
+        *self.selected_monitor_id.read()
+    }
+
+    /// Capture health per display since the screen capture last started
+    pub fn monitor_health() -> Vec<MonitorHealth> {
+        let mut health: Vec<MonitorHealth> = monitor_health().read().values().cloned().collect();
+        health.sort_by_key(|h| h.monitor_id);
+        health
+    }
+
     /// Get current capture mode
     pub fn get_mode(&self) -> CaptureMode {
         *self.capture_mode.read()
@@ -163,24 +392,42 @@ impl CaptureEngine {
         log::info!("Capture mode set to: {:?}", mode);
     }
 
-    /// Start ambient capture (screen only, no audio, 30s intervals)
+    /// Start ambient capture (screen at 30s intervals; audio only when the
+    /// rolling ambient buffer is enabled)
     pub fn start_ambient(&self, app: AppHandle) -> Result<(), String> {
+        let ambient_audio = self.ambient_audio_enabled.load(Ordering::SeqCst);
+
         if self.is_running.load(Ordering::SeqCst) {
             // If already running, just switch mode
             self.set_mode(CaptureMode::Ambient);
-            self.audio_enabled.store(false, Ordering::SeqCst);
+            self.audio_enabled.store(ambient_audio, Ordering::SeqCst);
             *self.frame_interval_ms.write() = 30000; // 30 seconds
-            log::info!("Switched to Ambient mode (30s intervals, no audio)");
+            if ambient_audio {
+                self.spawn_audio_capture();
+            } else {
+                MIC_RUNNING.store(false, Ordering::SeqCst);
+                SYSTEM_AUDIO_RUNNING.store(false, Ordering::SeqCst);
+            }
+            log::info!(
+                "Switched to Ambient mode (30s intervals, buffered audio: {})",
+                ambient_audio
+            );
             return Ok(());
         }
 
         // Start fresh in ambient mode
         self.set_mode(CaptureMode::Ambient);
-        self.audio_enabled.store(false, Ordering::SeqCst);
+        self.audio_enabled.store(ambient_audio, Ordering::SeqCst);
         *self.frame_interval_ms.write() = 30000; // 30 seconds
 
         self.start_screen_only(app)?;
-        log::info!("🌙 Ambient capture started (screen only @ 30s)");
+        if ambient_audio {
+            self.spawn_audio_capture();
+        }
+        log::info!(
+            "🌙 Ambient capture started (screen @ 30s, buffered audio: {})",
+            ambient_audio
+        );
         Ok(())
     }
 
@@ -191,17 +438,11 @@ impl CaptureEngine {
             self.set_mode(CaptureMode::Meeting);
             self.audio_enabled.store(true, Ordering::SeqCst);
             *self.frame_interval_ms.write() = 2000; // 2 seconds
+            // Ambient may be paused for idleness; a meeting starts live
+            self.reset_pause_state();
 
             // Start audio capture if not running
-            if !MIC_RUNNING.load(Ordering::SeqCst) {
-                MIC_RUNNING.store(true, Ordering::SeqCst);
-                let mic_count = self.mic_audio_count.clone();
-                let audio_callback_mic = self.audio_callback.clone();
-                let selected_mic = self.selected_mic_id.read().clone();
-                std::thread::spawn(move || {
-                    Self::run_mic_capture(mic_count, audio_callback_mic, selected_mic);
-                });
-            }
+            self.spawn_audio_capture();
 
             log::info!("Switched to Meeting mode (2s intervals, audio enabled)");
             return Ok(());
@@ -225,8 +466,62 @@ impl CaptureEngine {
         Ok(())
     }
 
+    /// Suspend a running recording without tearing down capture threads.
+    /// Audio and frame callbacks stop firing until `resume_recording` is called.
+    pub fn suspend_recording(&self) -> Result<(), String> {
+        if !self.is_running.load(Ordering::SeqCst) {
+            return Err("Not recording".to_string());
+        }
+        if CAPTURE_SUSPENDED.swap(true, Ordering::SeqCst) {
+            return Err("Recording already paused".to_string());
+        }
+
+        *self.paused_since.write() = Some(std::time::Instant::now());
+        crate::tray_builder::update_tray_status(|s| s.paused = true);
+        log::info!("⏸️ Recording suspended");
+        Ok(())
+    }
+
+    /// Resume a recording suspended with `suspend_recording`
+    pub fn resume_recording(&self) -> Result<(), String> {
+        if !self.is_running.load(Ordering::SeqCst) {
+            return Err("Not recording".to_string());
+        }
+        if !CAPTURE_SUSPENDED.swap(false, Ordering::SeqCst) {
+            return Err("Recording is not paused".to_string());
+        }
+
+        if let Some(since) = self.paused_since.write().take() {
+            *self.paused_total.write() += since.elapsed();
+        }
+        crate::tray_builder::update_tray_status(|s| s.paused = false);
+        log::info!("▶️ Recording resumed");
+        Ok(())
+    }
+
+    /// Whether the current recording is suspended
+    pub fn is_suspended(&self) -> bool {
+        self.is_running.load(Ordering::SeqCst) && CAPTURE_SUSPENDED.load(Ordering::SeqCst)
+    }
+
+    /// Whether the microphone should be delivering meeting audio right now
+    /// (recording in Meeting mode, mic enabled, not paused)
+    pub fn is_capturing_mic(&self) -> bool {
+        self.is_running.load(Ordering::SeqCst)
+            && self.get_mode() == CaptureMode::Meeting
+            && MIC_RUNNING.load(Ordering::SeqCst)
+            && !CAPTURE_SUSPENDED.load(Ordering::SeqCst)
+    }
+
+    /// Clear pause bookkeeping (called on every start/stop)
+    fn reset_pause_state(&self) {
+        CAPTURE_SUSPENDED.store(false, Ordering::SeqCst);
+        *self.paused_since.write() = None;
+        *self.paused_total.write() = std::time::Duration::ZERO;
+    }
+
     /// Start screen capture only (for ambient mode)
-    fn start_screen_only(&self, _app: AppHandle) -> Result<(), String> {
+    fn start_screen_only(&self, app: AppHandle) -> Result<(), String> {
         if self.is_running.load(Ordering::SeqCst) {
             return Err("Already recording".to_string());
         }
@@ -235,6 +530,7 @@ impl CaptureEngine {
         self.video_frame_count.store(0, Ordering::SeqCst);
         self.frame_number.store(0, Ordering::SeqCst);
         *self.start_time.write() = Some(std::time::Instant::now());
+        self.reset_pause_state();
 
         // Start screen capture only
         SCREEN_RUNNING.store(true, Ordering::SeqCst);
@@ -242,6 +538,11 @@ impl CaptureEngine {
         let frame_number = self.frame_number.clone();
         let frame_callback = self.frame_callback.clone();
         let monitor_id = self.selected_monitor_id.read().clone();
+        let capture_all_monitors = self.capture_all_monitors.clone();
+        let capture_window_id = self.capture_window_id.clone();
+        let capture_mode = self.capture_mode.clone();
+        let frame_interval_ms = self.frame_interval_ms.clone();
+        let interval_factor = self.interval_factor.clone();
         let interval_ms = *self.frame_interval_ms.read();
 
         log::info!(
@@ -254,7 +555,12 @@ impl CaptureEngine {
                 frame_number,
                 frame_callback,
                 monitor_id,
-                interval_ms,
+                capture_all_monitors,
+                capture_window_id,
+                capture_mode,
+                frame_interval_ms,
+                interval_factor,
+                app,
             )
             .await;
         });
@@ -262,7 +568,7 @@ impl CaptureEngine {
         Ok(())
     }
 
-    pub fn start(&self, _app: AppHandle) -> Result<(), String> {
+    pub fn start(&self, app: AppHandle) -> Result<(), String> {
         if self.is_running.load(Ordering::SeqCst) {
             return Err("Already recording".to_string());
         }
@@ -273,25 +579,32 @@ impl CaptureEngine {
         self.system_audio_count.store(0, Ordering::SeqCst);
         self.frame_number.store(0, Ordering::SeqCst);
         *self.start_time.write() = Some(std::time::Instant::now());
+        self.reset_pause_state();
+        crate::tray_builder::update_tray_status(|s| {
+            s.recording = true;
+            s.paused = false;
+        });
 
         // Start microphone capture
-        MIC_RUNNING.store(true, Ordering::SeqCst);
-        let mic_count = self.mic_audio_count.clone();
-        let audio_callback_mic = self.audio_callback.clone();
-        let selected_mic = self.selected_mic_id.read().clone();
-
-        std::thread::spawn(move || {
-            Self::run_mic_capture(mic_count, audio_callback_mic, selected_mic);
-        });
+        if self.mic_enabled.load(Ordering::SeqCst) {
+            MIC_RUNNING.store(true, Ordering::SeqCst);
+            let mic_count = self.mic_audio_count.clone();
+            let audio_callback_mic = self.audio_callback.clone();
+            let selected_mic = self.selected_mic_id.read().clone();
+
+            std::thread::spawn(move || {
+                Self::run_mic_capture(mic_count, audio_callback_mic, selected_mic);
+            });
+        } else {
+            log::info!("🎤 Microphone capture disabled in settings");
+        }
 
         // Start system audio capture (ScreenCaptureKit)
-        SYSTEM_AUDIO_RUNNING.store(true, Ordering::SeqCst);
-        let sys_count = self.system_audio_count.clone();
-        let audio_callback_sys = self.audio_callback.clone();
-
-        std::thread::spawn(move || {
-            Self::run_system_audio_capture(sys_count, audio_callback_sys);
-        });
+        if self.system_audio_enabled.load(Ordering::SeqCst) {
+            self.spawn_system_audio_capture();
+        } else {
+            log::info!("🔊 System audio capture disabled in settings");
+        }
 
         // Start screen capture with configurable interval
         SCREEN_RUNNING.store(true, Ordering::SeqCst);
@@ -299,6 +612,11 @@ impl CaptureEngine {
         let frame_number = self.frame_number.clone();
         let frame_callback = self.frame_callback.clone();
         let monitor_id = self.selected_monitor_id.read().clone();
+        let capture_all_monitors = self.capture_all_monitors.clone();
+        let capture_window_id = self.capture_window_id.clone();
+        let capture_mode = self.capture_mode.clone();
+        let frame_interval_ms = self.frame_interval_ms.clone();
+        let interval_factor = self.interval_factor.clone();
         let interval_ms = *self.frame_interval_ms.read();
 
         log::info!(
@@ -315,7 +633,12 @@ impl CaptureEngine {
                 frame_number,
                 frame_callback,
                 monitor_id,
-                interval_ms,
+                capture_all_monitors,
+                capture_window_id,
+                capture_mode,
+                frame_interval_ms,
+                interval_factor,
+                app,
             )
             .await;
         });
@@ -324,6 +647,42 @@ impl CaptureEngine {
         Ok(())
     }
 
+    /// Start whichever enabled audio sources aren't already running
+    fn spawn_audio_capture(&self) {
+        if self.mic_enabled.load(Ordering::SeqCst) && !MIC_RUNNING.load(Ordering::SeqCst) {
+            MIC_RUNNING.store(true, Ordering::SeqCst);
+            let mic_count = self.mic_audio_count.clone();
+            let audio_callback_mic = self.audio_callback.clone();
+            let selected_mic = self.selected_mic_id.read().clone();
+            std::thread::spawn(move || {
+                Self::run_mic_capture(mic_count, audio_callback_mic, selected_mic);
+            });
+        }
+        if self.system_audio_enabled.load(Ordering::SeqCst)
+            && !SYSTEM_AUDIO_RUNNING.load(Ordering::SeqCst)
+        {
+            self.spawn_system_audio_capture();
+        }
+    }
+
+    /// Start the ScreenCaptureKit audio thread unless the OS can't support it
+    fn spawn_system_audio_capture(&self) {
+        // A previous permission failure may have been fixed since, so only
+        // OS-level problems skip the attempt
+        if let Err(reason) = Self::check_system_audio_support() {
+            log::warn!("⚠️ {}", reason);
+            return;
+        }
+
+        SYSTEM_AUDIO_RUNNING.store(true, Ordering::SeqCst);
+        let sys_count = self.system_audio_count.clone();
+        let audio_callback_sys = self.audio_callback.clone();
+
+        std::thread::spawn(move || {
+            Self::run_system_audio_capture(sys_count, audio_callback_sys);
+        });
+    }
+
     /// Stop capture
     pub fn stop(&self) -> Result<(), String> {
         if !self.is_running.load(Ordering::SeqCst) {
@@ -334,6 +693,12 @@ impl CaptureEngine {
         MIC_RUNNING.store(false, Ordering::SeqCst);
         SYSTEM_AUDIO_RUNNING.store(false, Ordering::SeqCst);
         SCREEN_RUNNING.store(false, Ordering::SeqCst);
+        self.reset_pause_state();
+        crate::tray_builder::update_tray_status(|s| {
+            s.recording = false;
+            s.paused = false;
+            s.transcription_degraded = false;
+        });
 
         log::info!("Capture engine stopped");
         Ok(())
@@ -341,18 +706,30 @@ impl CaptureEngine {
 
     /// Get current recording status
     pub fn get_status(&self) -> RecordingStatus {
+        // Exclude paused time so the UI timer freezes while paused
+        let paused = *self.paused_total.read()
+            + self
+                .paused_since
+                .read()
+                .map(|t| t.elapsed())
+                .unwrap_or_default();
         let duration = self
             .start_time
             .read()
-            .map(|t| t.elapsed().as_secs())
+            .map(|t| t.elapsed().saturating_sub(paused).as_secs())
             .unwrap_or(0);
 
         RecordingStatus {
             is_recording: self.is_running.load(Ordering::SeqCst),
+            is_paused: self.is_suspended(),
             duration_seconds: duration,
             video_frames: self.video_frame_count.load(Ordering::SeqCst),
             audio_samples: self.mic_audio_count.load(Ordering::SeqCst)
                 + self.system_audio_count.load(Ordering::SeqCst),
+            transcripts_pending_retry: 0,
+            transcripts_failed: 0,
+            transcription_health: crate::transcription::ConnectionHealth::Idle,
+            transcription_degraded: false,
         }
     }
 
@@ -402,11 +779,12 @@ impl CaptureEngine {
         let stream = device.build_input_stream(
             &config.into(),
             move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                if !MIC_RUNNING.load(Ordering::SeqCst) {
+                if !MIC_RUNNING.load(Ordering::SeqCst) || CAPTURE_SUSPENDED.load(Ordering::SeqCst) {
                     return;
                 }
 
                 let n = mic_count.fetch_add(1, Ordering::Relaxed);
+                crate::audio_level::audio_meter().record(AudioSource::Microphone, data);
 
                 if let Some(cb) = callback.read().as_ref() {
                     let audio = AudioBuffer {
@@ -465,6 +843,7 @@ impl CaptureEngine {
             Err(e) => {
                 log::warn!("⚠️ ScreenCaptureKit not available: {}", e);
                 log::warn!("⚠️ System audio capture disabled. Only microphone will be captured.");
+                Self::system_audio_failed(format!("ScreenCaptureKit not available: {}", e));
                 return;
             }
         };
@@ -474,6 +853,9 @@ impl CaptureEngine {
             Some(d) => d,
             None => {
                 log::warn!("⚠️ No system audio device from ScreenCaptureKit");
+                Self::system_audio_failed(
+                    "No system audio device (check Screen Recording permission)".to_string(),
+                );
                 return;
             }
         };
@@ -485,6 +867,7 @@ impl CaptureEngine {
             Ok(c) => c,
             Err(e) => {
                 log::error!("System audio config error: {}", e);
+                Self::system_audio_failed(format!("System audio config error: {}", e));
                 return;
             }
         };
@@ -496,11 +879,14 @@ impl CaptureEngine {
         let stream = device.build_input_stream(
             &config.into(),
             move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                if !SYSTEM_AUDIO_RUNNING.load(Ordering::SeqCst) {
+                if !SYSTEM_AUDIO_RUNNING.load(Ordering::SeqCst)
+                    || CAPTURE_SUSPENDED.load(Ordering::SeqCst)
+                {
                     return;
                 }
 
                 let n = sys_count.fetch_add(1, Ordering::Relaxed);
+                crate::audio_level::audio_meter().record(AudioSource::System, data);
 
                 if let Some(cb) = callback.read().as_ref() {
                     let audio = AudioBuffer {
@@ -528,8 +914,10 @@ impl CaptureEngine {
             Ok(s) => {
                 if let Err(e) = s.play() {
                     log::error!("Failed to play system audio stream: {}", e);
+                    Self::system_audio_failed(format!("Failed to start system audio: {}", e));
                     return;
                 }
+                *system_audio_error().write() = None;
                 log::info!("✅ System audio capture started");
 
                 while SYSTEM_AUDIO_RUNNING.load(Ordering::SeqCst) {
@@ -540,10 +928,18 @@ impl CaptureEngine {
             }
             Err(e) => {
                 log::error!("Failed to build system audio stream: {}", e);
+                Self::system_audio_failed(format!("Failed to open system audio stream: {}", e));
             }
         }
     }
 
+    /// Record why system audio isn't being captured so the UI can say so
+    #[cfg(target_os = "macos")]
+    fn system_audio_failed(reason: String) {
+        SYSTEM_AUDIO_RUNNING.store(false, Ordering::SeqCst);
+        *system_audio_error().write() = Some(reason);
+    }
+
     /// Fallback for non-macOS (no system audio)
     #[cfg(not(target_os = "macos"))]
     fn run_system_audio_capture(
@@ -598,7 +994,12 @@ impl CaptureEngine {
         frame_number: Arc<AtomicU64>,
         frame_callback: Arc<RwLock<Option<FrameCallback>>>,
         monitor_id: Option<u32>,
-        interval_ms: u32,
+        capture_all_monitors: Arc<AtomicBool>,
+        capture_window_id: Arc<RwLock<Option<u32>>>,
+        capture_mode: Arc<RwLock<CaptureMode>>,
+        frame_interval_ms: Arc<RwLock<u32>>,
+        interval_factor: Arc<RwLock<f32>>,
+        app: AppHandle,
     ) {
         let monitors = match Monitor::all() {
             Ok(m) => m,
@@ -608,59 +1009,134 @@ impl CaptureEngine {
             }
         };
 
-        let monitor = if let Some(id) = monitor_id {
-            monitors.into_iter().find(|m| m.id().unwrap_or(0) == id)
+        let selected = if let Some(id) = monitor_id {
+            monitors.iter().position(|m| m.id().unwrap_or(0) == id)
         } else {
             monitors
-                .into_iter()
-                .find(|m| m.is_primary().unwrap_or(false))
+                .iter()
+                .position(|m| m.is_primary().unwrap_or(false))
         }
-        .or_else(|| Monitor::all().ok().and_then(|mut m| m.pop()));
+        .or_else(|| monitors.len().checked_sub(1));
 
-        let monitor = match monitor {
-            Some(m) => m,
+        let selected = match selected {
+            Some(i) => i,
             None => {
                 log::error!("No monitor found for capture");
                 return;
             }
         };
 
-        let mon_id = monitor.id().unwrap_or(0);
-        let mon_name = monitor.name().unwrap_or_else(|_| "Unknown".to_string());
-        let mon_width = monitor.width().unwrap_or(0);
-        let mon_height = monitor.height().unwrap_or(0);
-        log::info!(
-            "📺 Screen capture: {} ({}x{})",
-            mon_name,
-            mon_width,
-            mon_height
-        );
+        let targets: Vec<(u32, String)> = monitors
+            .iter()
+            .map(|m| {
+                (
+                    m.id().unwrap_or(0),
+                    m.name().unwrap_or_else(|_| "Unknown".to_string()),
+                )
+            })
+            .collect();
+        for (i, monitor) in monitors.iter().enumerate() {
+            log::info!(
+                "📺 Screen capture{}: {} ({}x{})",
+                if i == selected {
+                    ""
+                } else {
+                    " (all-monitors only)"
+                },
+                targets[i].1,
+                monitor.width().unwrap_or(0),
+                monitor.height().unwrap_or(0)
+            );
+        }
+        monitor_health().write().clear();
 
-        let capture_interval = std::time::Duration::from_millis(interval_ms as u64);
+        // Window that closed mid-recording; stay on monitor capture until the target changes
+        let mut lost_window: Option<u32> = None;
 
         while SCREEN_RUNNING.load(Ordering::SeqCst) {
-            match monitor.capture_image() {
-                Ok(image) => {
-                    let num = frame_number.fetch_add(1, Ordering::SeqCst);
-                    frame_count.fetch_add(1, Ordering::SeqCst);
-
-                    let frame = CapturedFrame {
-                        timestamp: chrono::Utc::now(),
-                        image: Arc::new(DynamicImage::ImageRgba8(image)),
-                        monitor_id: mon_id,
-                        frame_number: num,
-                    };
+            // Re-read each frame so theme and adaptive changes apply mid-recording
+            let capture_interval =
+                std::time::Duration::from_millis(crate::power_manager::stretched_interval(
+                    *frame_interval_ms.read(),
+                    *interval_factor.read(),
+                ) as u64);
+            if CAPTURE_SUSPENDED.load(Ordering::SeqCst) {
+                tokio::time::sleep(capture_interval).await;
+                continue;
+            }
 
-                    if let Some(callback) = frame_callback.read().as_ref() {
-                        callback(frame);
+            let window_target = (*capture_window_id.read()).filter(|id| lost_window != Some(*id));
+            if let Some(window_id) = window_target {
+                match Self::capture_window_frame(window_id) {
+                    Ok((image, mon_id, name)) => {
+                        let num = frame_number.fetch_add(1, Ordering::SeqCst);
+                        frame_count.fetch_add(1, Ordering::SeqCst);
+                        record_monitor_capture(mon_id, &name, None);
+
+                        let frame = CapturedFrame {
+                            timestamp: chrono::Utc::now(),
+                            image: Arc::new(DynamicImage::ImageRgba8(image)),
+                            monitor_id: mon_id,
+                            frame_number: num,
+                        };
+
+                        if let Some(callback) = frame_callback.read().as_ref() {
+                            callback(frame);
+                        }
+
+                        tokio::time::sleep(capture_interval).await;
+                        continue;
                     }
-
-                    if num % 10 == 0 {
-                        log::trace!("📺 Frame #{}", num);
+                    Err(reason) => {
+                        log::warn!(
+                            "📺 Capture window {} unavailable ({}), falling back to monitor capture",
+                            window_id,
+                            reason
+                        );
+                        lost_window = Some(window_id);
+                        let _ = app.emit(
+                            "capture-window-lost",
+                            CaptureWindowLost { window_id, reason },
+                        );
                     }
                 }
-                Err(e) => {
-                    log::warn!("Frame capture failed: {}", e);
+            }
+
+            // All displays only while a meeting is recording; ambient stays on one
+            let capture_all = capture_all_monitors.load(Ordering::SeqCst)
+                && *capture_mode.read() == CaptureMode::Meeting;
+
+            for (i, monitor) in monitors.iter().enumerate() {
+                if !capture_all && i != selected {
+                    continue;
+                }
+                let (mon_id, mon_name) = &targets[i];
+
+                match monitor.capture_image() {
+                    Ok(image) => {
+                        let num = frame_number.fetch_add(1, Ordering::SeqCst);
+                        frame_count.fetch_add(1, Ordering::SeqCst);
+                        record_monitor_capture(*mon_id, mon_name, None);
+
+                        let frame = CapturedFrame {
+                            timestamp: chrono::Utc::now(),
+                            image: Arc::new(DynamicImage::ImageRgba8(image)),
+                            monitor_id: *mon_id,
+                            frame_number: num,
+                        };
+
+                        if let Some(callback) = frame_callback.read().as_ref() {
+                            callback(frame);
+                        }
+
+                        if num % 10 == 0 {
+                            log::trace!("📺 Frame #{}", num);
+                        }
+                    }
+                    Err(e) => {
+                        log::warn!("Frame capture failed on {}: {}", mon_name, e);
+                        record_monitor_capture(*mon_id, mon_name, Some(e.to_string()));
+                    }
                 }
             }
 
@@ -705,6 +1181,74 @@ impl CaptureEngine {
         Ok(devices)
     }
 
+    /// Look up a window by id each frame so moves and resizes are followed
+    fn find_window(window_id: u32) -> Result<Window, String> {
+        let windows = Window::all().map_err(|e| format!("Failed to list windows: {}", e))?;
+        let window = windows
+            .into_iter()
+            .find(|w| w.id().ok() == Some(window_id))
+            .ok_or_else(|| "window closed".to_string())?;
+        if window.is_minimized().unwrap_or(false) {
+            return Err("window minimized".to_string());
+        }
+        Ok(window)
+    }
+
+    /// Capture one frame of a window: (image, monitor it's on, label for diagnostics)
+    fn capture_window_frame(window_id: u32) -> Result<(image::RgbaImage, u32, String), String> {
+        let window = Self::find_window(window_id)?;
+        let image = window
+            .capture_image()
+            .map_err(|e| format!("Failed to capture window: {}", e))?;
+        if image.width() == 0 || image.height() == 0 {
+            return Err("window has no visible area".to_string());
+        }
+        let monitor_id = window
+            .current_monitor()
+            .ok()
+            .and_then(|m| m.id().ok())
+            .unwrap_or(0);
+        let name = format!(
+            "{} (window)",
+            window.app_name().unwrap_or_else(|_| "Unknown".to_string())
+        );
+        Ok((image, monitor_id, name))
+    }
+
+    /// List on-screen windows that can be captured on their own
+    pub fn list_windows() -> Result<Vec<CapturableWindow>, String> {
+        let windows = Window::all().map_err(|e| format!("Failed to list windows: {}", e))?;
+
+        let infos: Vec<CapturableWindow> = windows
+            .into_iter()
+            .filter(|w| !w.is_minimized().unwrap_or(false))
+            .filter_map(|w| {
+                let width = w.width().ok()?;
+                let height = w.height().ok()?;
+                if width < 50 || height < 50 {
+                    return None; // Menu bar items, tooltips, etc.
+                }
+                Some(CapturableWindow {
+                    id: w.id().ok()?,
+                    app_name: w.app_name().unwrap_or_default(),
+                    title: w.title().unwrap_or_default(),
+                    width,
+                    height,
+                    monitor_id: w.current_monitor().ok().and_then(|m| m.id().ok()),
+                    is_focused: w.is_focused().unwrap_or(false),
+                })
+            })
+            .collect();
+
+        Ok(infos)
+    }
+
+    /// Capture a single screenshot of a window (for previews)
+    pub fn capture_window_screenshot(window_id: u32) -> Result<DynamicImage, String> {
+        let (image, _, _) = Self::capture_window_frame(window_id)?;
+        Ok(DynamicImage::ImageRgba8(image))
+    }
+
     /// List available monitors
     pub fn list_monitors() -> Result<Vec<MonitorInfo>, String> {
         let monitors = Monitor::all().map_err(|e| format!("Failed to list monitors: {}", e))?;
@@ -8,7 +8,15 @@
 // - ocr_calls: Number of OCR invocations (state boundaries only)
 // - bytes_saved: Estimated disk savings from deduplication
 // - cpu_time: Accumulated processing time
-
+// - redactions: Frames dropped by the privacy filter
+// - vlm_calls / vlm_cache_hits: VLM API calls vs. cached results (lifetime,
+//   not reset per meeting - the VLM scheduler runs independently of meetings)
+// - accessibility_hits / ocr_fallbacks: text snapshots produced by the
+//   accessibility API vs. the OCR fallback (lifetime, like the VLM counters)
+// - delta_snapshots / snapshot_bytes_saved: text snapshots stored as patches
+//   and the bytes that saved over full copies (lifetime)
+
+use crate::database::SnapshotWrite;
 use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
@@ -27,6 +35,8 @@ pub struct MeetingMetrics {
     pub states_out: u64,
     pub images_written: u64,
     pub duplicates_skipped: u64,
+    #[serde(default)]
+    pub redactions: u64,
 
     // Text extraction
     pub ocr_calls: u64,
@@ -53,6 +63,7 @@ impl MeetingMetrics {
             states_out: 0,
             images_written: 0,
             duplicates_skipped: 0,
+            redactions: 0,
             ocr_calls: 0,
             snapshots_created: 0,
             patches_created: 0,
@@ -90,6 +101,7 @@ impl MeetingMetrics {
         log::info!("  States created:     {:>8}", self.states_out);
         log::info!("  Images written:     {:>8}", self.images_written);
         log::info!("  Duplicates skipped: {:>8}", self.duplicates_skipped);
+        log::info!("  Frames redacted:    {:>8}", self.redactions);
         log::info!("  Dedup ratio:        {:>7.1}%", self.dedup_ratio * 100.0);
         log::info!("──────────────────────────────────────────────────────────");
         log::info!("  OCR calls:          {:>8}", self.ocr_calls);
@@ -116,11 +128,18 @@ pub struct MetricsCollector {
     states_out: AtomicU64,
     images_written: AtomicU64,
     duplicates_skipped: AtomicU64,
+    redactions: AtomicU64,
     ocr_calls: AtomicU64,
     snapshots_created: AtomicU64,
     patches_created: AtomicU64,
     bytes_saved: AtomicU64,
     cpu_time_ns: AtomicU64,
+    vlm_calls: AtomicU64,
+    vlm_cache_hits: AtomicU64,
+    accessibility_hits: AtomicU64,
+    ocr_fallbacks: AtomicU64,
+    delta_snapshots: AtomicU64,
+    snapshot_bytes_saved: AtomicU64,
     started_at: Mutex<Option<DateTime<Utc>>>,
 }
 
@@ -133,11 +152,18 @@ impl MetricsCollector {
             states_out: AtomicU64::new(0),
             images_written: AtomicU64::new(0),
             duplicates_skipped: AtomicU64::new(0),
+            redactions: AtomicU64::new(0),
             ocr_calls: AtomicU64::new(0),
             snapshots_created: AtomicU64::new(0),
             patches_created: AtomicU64::new(0),
             bytes_saved: AtomicU64::new(0),
             cpu_time_ns: AtomicU64::new(0),
+            vlm_calls: AtomicU64::new(0),
+            vlm_cache_hits: AtomicU64::new(0),
+            accessibility_hits: AtomicU64::new(0),
+            ocr_fallbacks: AtomicU64::new(0),
+            delta_snapshots: AtomicU64::new(0),
+            snapshot_bytes_saved: AtomicU64::new(0),
             started_at: Mutex::new(None),
         }
     }
@@ -149,6 +175,7 @@ impl MetricsCollector {
         self.states_out.store(0, Ordering::SeqCst);
         self.images_written.store(0, Ordering::SeqCst);
         self.duplicates_skipped.store(0, Ordering::SeqCst);
+        self.redactions.store(0, Ordering::SeqCst);
         self.ocr_calls.store(0, Ordering::SeqCst);
         self.snapshots_created.store(0, Ordering::SeqCst);
         self.patches_created.store(0, Ordering::SeqCst);
@@ -174,6 +201,7 @@ impl MetricsCollector {
             states_out: self.states_out.load(Ordering::SeqCst),
             images_written: self.images_written.load(Ordering::SeqCst),
             duplicates_skipped: self.duplicates_skipped.load(Ordering::SeqCst),
+            redactions: self.redactions.load(Ordering::SeqCst),
             ocr_calls: self.ocr_calls.load(Ordering::SeqCst),
             snapshots_created: self.snapshots_created.load(Ordering::SeqCst),
             patches_created: self.patches_created.load(Ordering::SeqCst),
@@ -209,6 +237,11 @@ impl MetricsCollector {
             .fetch_add(estimated_bytes, Ordering::SeqCst);
     }
 
+    /// Record a frame dropped by the privacy filter
+    pub fn record_redaction(&self) {
+        self.redactions.fetch_add(1, Ordering::SeqCst);
+    }
+
     /// Record an OCR call
     pub fn record_ocr_call(&self) {
         self.ocr_calls.fetch_add(1, Ordering::SeqCst);
@@ -230,6 +263,61 @@ impl MetricsCollector {
             .fetch_add(duration.as_nanos() as u64, Ordering::SeqCst);
     }
 
+    /// Record a VLM API call (cache miss)
+    pub fn record_vlm_call(&self) {
+        self.vlm_calls.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Record a frame answered from the VLM cache
+    pub fn record_vlm_cache_hit(&self) {
+        self.vlm_cache_hits.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// (VLM calls, VLM cache hits) since app start
+    pub fn vlm_counts(&self) -> (u64, u64) {
+        (
+            self.vlm_calls.load(Ordering::SeqCst),
+            self.vlm_cache_hits.load(Ordering::SeqCst),
+        )
+    }
+
+    /// Record a text snapshot produced by the accessibility API
+    pub fn record_accessibility_hit(&self) {
+        self.accessibility_hits.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Record a text snapshot produced by OCR after accessibility failed
+    pub fn record_ocr_fallback(&self) {
+        self.ocr_fallbacks.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// (accessibility hits, OCR fallbacks) since app start
+    pub fn extraction_counts(&self) -> (u64, u64) {
+        (
+            self.accessibility_hits.load(Ordering::SeqCst),
+            self.ocr_fallbacks.load(Ordering::SeqCst),
+        )
+    }
+
+    /// Record how a text snapshot was stored (full copy or patch)
+    pub fn record_snapshot_write(&self, write: &SnapshotWrite) {
+        if write.delta {
+            self.delta_snapshots.fetch_add(1, Ordering::SeqCst);
+            self.snapshot_bytes_saved.fetch_add(
+                write.text_bytes.saturating_sub(write.stored_bytes),
+                Ordering::SeqCst,
+            );
+        }
+    }
+
+    /// (snapshots stored as patches, bytes saved by patching) since app start
+    pub fn snapshot_delta_counts(&self) -> (u64, u64) {
+        (
+            self.delta_snapshots.load(Ordering::SeqCst),
+            self.snapshot_bytes_saved.load(Ordering::SeqCst),
+        )
+    }
+
     /// Create a timer that automatically records CPU time
     pub fn start_timer(&self) -> MetricsTimer<'_> {
         MetricsTimer {
@@ -251,6 +339,7 @@ impl MetricsCollector {
             states_out: self.states_out.load(Ordering::SeqCst),
             images_written: self.images_written.load(Ordering::SeqCst),
             duplicates_skipped: self.duplicates_skipped.load(Ordering::SeqCst),
+            redactions: self.redactions.load(Ordering::SeqCst),
             ocr_calls: self.ocr_calls.load(Ordering::SeqCst),
             snapshots_created: self.snapshots_created.load(Ordering::SeqCst),
             patches_created: self.patches_created.load(Ordering::SeqCst),
@@ -310,4 +399,46 @@ mod tests {
         assert_eq!(metrics.bytes_saved_estimate, 100_000);
         assert!(metrics.dedup_ratio > 0.9); // 95% dedup
     }
+
+    #[test]
+    fn test_vlm_counts_survive_meeting_reset() {
+        let collector = MetricsCollector::new();
+        collector.record_vlm_call();
+        collector.record_vlm_cache_hit();
+        collector.record_vlm_cache_hit();
+
+        collector.start_meeting("next_meeting");
+
+        assert_eq!(collector.vlm_counts(), (1, 2));
+    }
+
+    #[test]
+    fn test_extraction_counts_by_source() {
+        let collector = MetricsCollector::new();
+        collector.record_accessibility_hit();
+        collector.record_accessibility_hit();
+        collector.record_ocr_fallback();
+
+        collector.start_meeting("next_meeting");
+
+        assert_eq!(collector.extraction_counts(), (2, 1));
+    }
+
+    #[test]
+    fn test_snapshot_delta_savings() {
+        let collector = MetricsCollector::new();
+        collector.record_snapshot_write(&SnapshotWrite {
+            delta: true,
+            text_bytes: 4_000,
+            stored_bytes: 150,
+        });
+        // Full copies don't count towards savings
+        collector.record_snapshot_write(&SnapshotWrite {
+            delta: false,
+            text_bytes: 4_000,
+            stored_bytes: 4_000,
+        });
+
+        assert_eq!(collector.snapshot_delta_counts(), (1, 3_850));
+    }
 }
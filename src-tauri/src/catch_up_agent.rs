@@ -2,7 +2,12 @@
 // Generates catch-up capsules for users joining meetings late
 
 use serde::{Deserialize, Serialize};
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
 use crate::ai_client::AIClient;
+use crate::model_router::USE_CASE_CATCH_UP;
+use crate::action_items::normalize_text;
 
 /// A citation pointing to a specific transcript moment
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,6 +69,9 @@ pub struct CatchUpCapsule {
     pub confidence: f32,
     pub citations: Vec<TranscriptCitation>,
     pub generated_at_minute: i32,
+    /// Seconds since the capsule was last updated (0 = just generated)
+    #[serde(default)]
+    pub cache_age_secs: u64,
 }
 
 impl Default for CatchUpCapsule {
@@ -81,6 +89,7 @@ impl Default for CatchUpCapsule {
             confidence: 0.0,
             citations: Vec::new(),
             generated_at_minute: 0,
+            cache_age_secs: 0,
         }
     }
 }
@@ -92,6 +101,9 @@ pub struct MeetingMetadata {
     pub description: Option<String>,
     pub attendees: Vec<String>,
     pub scheduled_duration_min: Option<i32>,
+    /// Decisions explicitly marked during the meeting (most recent last)
+    #[serde(default)]
+    pub recorded_decisions: Vec<Decision>,
 }
 
 /// A transcript segment
@@ -148,7 +160,7 @@ impl CatchUpAgent {
         );
 
         // Call AI
-        let response = self.ai_client.complete(&prompt).await?;
+        let response = self.ai_client.complete_for(USE_CASE_CATCH_UP, &prompt).await?;
 
         // Parse response into capsule
         let capsule = self.parse_catch_up_response(&response, minutes_since_start, transcript_segments)?;
@@ -156,6 +168,33 @@ impl CatchUpAgent {
         Ok(capsule)
     }
 
+    /// Fold transcript segments that arrived after `previous` was generated
+    /// into it, instead of re-summarizing the whole meeting
+    pub async fn generate_incremental(
+        &self,
+        previous: &CatchUpCapsule,
+        new_segments: &[TranscriptSegment],
+        meeting_metadata: &MeetingMetadata,
+        minutes_since_start: i32,
+    ) -> Result<CatchUpCapsule, String> {
+        let transcript_text = self.build_transcript_text(new_segments);
+
+        if transcript_text.is_empty() {
+            return Ok(previous.clone());
+        }
+
+        let prompt = self.build_incremental_prompt(
+            previous,
+            &transcript_text,
+            meeting_metadata,
+            minutes_since_start,
+        )?;
+
+        let response = self.ai_client.complete_for(USE_CASE_CATCH_UP, &prompt).await?;
+
+        self.parse_catch_up_response(&response, minutes_since_start, new_segments)
+    }
+
     fn build_transcript_text(&self, segments: &[TranscriptSegment]) -> String {
         segments
             .iter()
@@ -188,10 +227,22 @@ impl CatchUpAgent {
             metadata.attendees.join(", ")
         };
 
+        let recorded_decisions = if metadata.recorded_decisions.is_empty() {
+            "None".to_string()
+        } else {
+            metadata.recorded_decisions
+                .iter()
+                .map(|d| format!("- {}", d.text))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
         format!(r#"You are analyzing a meeting transcript. The user just joined {minutes_since_start} minutes late and needs to quickly understand what happened.
 
 MEETING: {title}
 ATTENDEES: {attendees}
+DECISIONS ALREADY MARKED BY PARTICIPANTS:
+{recorded_decisions}
 TRANSCRIPT SO FAR:
 {transcript}
 
@@ -215,15 +266,79 @@ HARD RULES:
 - No hallucination. If transcript is unclear, say so.
 - Keep ten_second_version to 3-4 bullet points max.
 - Make next_moves actionable and specific.
+- Do not repeat decisions already marked by participants in "decisions"; only add new ones.
 
 Return ONLY valid JSON, no other text."#,
             title = metadata.title,
             minutes_since_start = minutes_since_start,
             attendees = attendees,
+            recorded_decisions = recorded_decisions,
             transcript = transcript,
         )
     }
 
+    fn build_incremental_prompt(
+        &self,
+        previous: &CatchUpCapsule,
+        new_transcript: &str,
+        metadata: &MeetingMetadata,
+        minutes_since_start: i32,
+    ) -> Result<String, String> {
+        let previous_json = serde_json::to_string_pretty(&serde_json::json!({
+            "what_missed": previous.what_missed.iter().map(|i| &i.text).collect::<Vec<_>>(),
+            "current_topic": previous.current_topic,
+            "decisions": previous.decisions.iter().map(|d| &d.text).collect::<Vec<_>>(),
+            "open_threads": previous.open_threads,
+            "next_moves": previous.next_moves,
+            "risks": previous.risks.iter().map(|r| &r.text).collect::<Vec<_>>(),
+            "questions_to_ask": previous.questions_to_ask,
+            "ten_second_version": previous.ten_second_version,
+            "sixty_second_version": previous.sixty_second_version,
+            "confidence": previous.confidence,
+        }))
+        .map_err(|e| format!("Failed to serialize previous capsule: {}", e))?;
+
+        let recorded_decisions = if metadata.recorded_decisions.is_empty() {
+            "None".to_string()
+        } else {
+            metadata.recorded_decisions
+                .iter()
+                .map(|d| format!("- {}", d.text))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        Ok(format!(r#"You are maintaining a running Catch-Up Capsule for a meeting that is still in progress. It is now {minutes_since_start} minutes in.
+
+MEETING: {title}
+DECISIONS ALREADY MARKED BY PARTICIPANTS:
+{recorded_decisions}
+
+CAPSULE AS OF MINUTE {previous_minute}:
+{previous_json}
+
+NEW TRANSCRIPT SINCE THEN:
+{new_transcript}
+
+Update the capsule to reflect the new transcript and return it in exactly the same JSON format.
+
+HARD RULES:
+- Keep earlier points that are still relevant; drop open threads that were resolved.
+- current_topic, next_moves and questions_to_ask must reflect the latest discussion.
+- Only add facts that are actually in the transcript. No hallucination.
+- Do not repeat decisions already marked by participants in "decisions"; only add new ones.
+- Keep ten_second_version to 3-4 bullet points max.
+
+Return ONLY valid JSON, no other text."#,
+            minutes_since_start = minutes_since_start,
+            title = metadata.title,
+            recorded_decisions = recorded_decisions,
+            previous_minute = previous.generated_at_minute,
+            previous_json = previous_json,
+            new_transcript = new_transcript,
+        ))
+    }
+
     fn parse_catch_up_response(
         &self,
         response: &str,
@@ -279,6 +394,7 @@ Return ONLY valid JSON, no other text."#,
             confidence: raw.confidence.unwrap_or(0.5),
             citations: Vec::new(),
             generated_at_minute: minutes_since_start,
+            cache_age_secs: 0,
         })
     }
 
@@ -309,3 +425,89 @@ Return ONLY valid JSON, no other text."#,
         Err("No JSON found in response".to_string())
     }
 }
+
+/// Put explicitly marked decisions first and drop inferred ones that repeat them
+pub fn merge_recorded_decisions(capsule: &mut CatchUpCapsule, recorded: &[Decision]) {
+    if recorded.is_empty() {
+        return;
+    }
+    let recorded_texts: HashSet<String> = recorded.iter().map(|d| normalize_text(&d.text)).collect();
+    let inferred = std::mem::take(&mut capsule.decisions);
+    capsule.decisions = recorded.to_vec();
+    capsule.decisions.extend(
+        inferred.into_iter().filter(|d| !recorded_texts.contains(&normalize_text(&d.text))),
+    );
+}
+
+/// Last capsule generated for a meeting and how far into the transcript it goes
+struct CachedCapsule {
+    /// Capsule as generated, before marked decisions are merged in
+    capsule: CatchUpCapsule,
+    /// Id of the last transcript segment summarized
+    cursor: i64,
+    updated_at: Instant,
+}
+
+/// Per-meeting capsule cache so repeated catch-ups only summarize new transcript
+#[derive(Default)]
+pub struct CatchUpCache {
+    entries: Mutex<HashMap<String, CachedCapsule>>,
+}
+
+impl CatchUpCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cached capsule, transcript cursor and age in seconds
+    pub fn get(&self, meeting_id: &str) -> Option<(CatchUpCapsule, i64, u64)> {
+        self.entries.lock().get(meeting_id).map(|entry| {
+            (entry.capsule.clone(), entry.cursor, entry.updated_at.elapsed().as_secs())
+        })
+    }
+
+    pub fn store(&self, meeting_id: &str, capsule: CatchUpCapsule, cursor: i64) {
+        self.entries.lock().insert(
+            meeting_id.to_string(),
+            CachedCapsule { capsule, cursor, updated_at: Instant::now() },
+        );
+    }
+
+    /// Force the next catch-up for this meeting to regenerate from scratch
+    pub fn invalidate(&self, meeting_id: &str) {
+        if self.entries.lock().remove(meeting_id).is_some() {
+            log::debug!("Catch-up cache invalidated for meeting {}", meeting_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decision(text: &str) -> Decision {
+        Decision { text: text.to_string(), made_by: None, citation: None }
+    }
+
+    #[test]
+    fn test_merge_recorded_decisions() {
+        let mut capsule = CatchUpCapsule {
+            decisions: vec![decision("Ship v2 on Friday."), decision("Hire a contractor")],
+            ..Default::default()
+        };
+        merge_recorded_decisions(&mut capsule, &[decision("ship v2 on friday")]);
+
+        let texts: Vec<&str> = capsule.decisions.iter().map(|d| d.text.as_str()).collect();
+        assert_eq!(texts, vec!["ship v2 on friday", "Hire a contractor"]);
+    }
+
+    #[test]
+    fn test_cache_invalidate() {
+        let cache = CatchUpCache::new();
+        cache.store("m1", CatchUpCapsule::default(), 42);
+        assert_eq!(cache.get("m1").map(|(_, cursor, _)| cursor), Some(42));
+
+        cache.invalidate("m1");
+        assert!(cache.get("m1").is_none());
+    }
+}
@@ -2,17 +2,25 @@
 // Manages video storage, retention policies, and disk usage
 
 use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+use crate::playback_manifest::ChunkSpan;
 use crate::video_recorder::VideoChunk;
 
+/// Per-meeting list of registered chunks, in video/<meeting_id>/
+const MANIFEST: &str = "chunks.json";
+
 /// Storage statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageStats {
     pub total_bytes: u64,
     pub video_bytes: u64,
     pub frames_bytes: u64,
+    /// Archived meeting audio under audio/
+    pub audio_bytes: u64,
     pub meetings_count: u32,
     pub chunks_count: u32,
     pub oldest_meeting: Option<DateTime<Utc>>,
@@ -56,12 +64,51 @@ pub struct MeetingStorage {
     pub path: PathBuf,
 }
 
+/// The part of one chunk file that goes into a clip
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipSegment {
+    pub path: PathBuf,
+    /// Seconds into the chunk
+    pub start_secs: f64,
+    pub end_secs: f64,
+}
+
+/// Pieces of the chunks covering `start_ms..end_ms` (meeting offsets), in
+/// playback order. The window is clamped to the recorded video.
+pub fn plan_clip(
+    chunks: &[ChunkSpan],
+    start_ms: i64,
+    end_ms: i64,
+) -> Result<Vec<ClipSegment>, String> {
+    let segments: Vec<ClipSegment> = chunks
+        .iter()
+        .filter(|c| c.start_ms < end_ms && c.end_ms > start_ms)
+        .map(|c| ClipSegment {
+            path: PathBuf::from(&c.path),
+            start_secs: (start_ms.max(c.start_ms) - c.start_ms) as f64 / 1000.0,
+            end_secs: (end_ms.min(c.end_ms) - c.start_ms) as f64 / 1000.0,
+        })
+        .filter(|s| s.end_secs > s.start_secs)
+        .collect();
+
+    if segments.is_empty() {
+        return Err("No recorded video covers that moment".to_string());
+    }
+    Ok(segments)
+}
+
 /// Chunk manager for storage and retention
+///
+/// Storage is laid out as `video/<meeting_id>/`, `frames/<meeting_id>/` and
+/// `audio/<meeting_id>/` under the app data directory. Each video directory
+/// keeps a `chunks.json` manifest of the chunks the recorder registered.
 pub struct ChunkManager {
     /// Base storage directory
     storage_dir: PathBuf,
     /// Retention policy
     policy: RetentionPolicy,
+    /// Serializes manifest read-modify-write
+    manifest_lock: Mutex<()>,
 }
 
 impl ChunkManager {
@@ -69,70 +116,45 @@ impl ChunkManager {
         Self {
             storage_dir,
             policy,
+            manifest_lock: Mutex::new(()),
         }
     }
 
+    /// Directory holding a meeting's video chunks
+    pub fn video_dir(&self, meeting_id: &str) -> PathBuf {
+        self.storage_dir.join("video").join(meeting_id)
+    }
+
     /// Get storage statistics
     pub fn get_stats(&self) -> Result<StorageStats, String> {
-        let mut total_bytes = 0u64;
         let mut video_bytes = 0u64;
-        let mut frames_bytes = 0u64;
-        let mut meetings_count = 0u32;
         let mut chunks_count = 0u32;
         let mut oldest: Option<DateTime<Utc>> = None;
+        let mut meeting_ids = HashSet::new();
 
-        // Scan meeting directories
-        if let Ok(entries) = std::fs::read_dir(&self.storage_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() && path.file_name().map_or(false, |n| n.len() == 36) {
-                    // UUID-like directory name
-                    meetings_count += 1;
-
-                    // Get creation time
-                    if let Ok(meta) = entry.metadata() {
-                        if let Ok(created) = meta.created() {
-                            let dt: DateTime<Utc> = created.into();
-                            if oldest.is_none() || dt < oldest.unwrap() {
-                                oldest = Some(dt);
-                            }
-                        }
-                    }
-
-                    // Scan video directory
-                    let video_dir = path.join("video");
-                    if video_dir.exists() {
-                        if let Ok(files) = std::fs::read_dir(&video_dir) {
-                            for file in files.flatten() {
-                                if let Ok(meta) = file.metadata() {
-                                    let size = meta.len();
-                                    video_bytes += size;
-                                    total_bytes += size;
-                                    if file.path().extension().map_or(false, |e| e == "mov") {
-                                        chunks_count += 1;
-                                    }
-                                }
-                            }
-                        }
-                    }
-
-                    // Scan frames directory
-                    let frames_dir = path.join("frames");
-                    if frames_dir.exists() {
-                        if let Ok(files) = std::fs::read_dir(&frames_dir) {
-                            for file in files.flatten() {
-                                if let Ok(meta) = file.metadata() {
-                                    let size = meta.len();
-                                    frames_bytes += size;
-                                    total_bytes += size;
-                                }
-                            }
-                        }
-                    }
+        for meeting_id in Self::meeting_dirs(&self.storage_dir.join("video")) {
+            let video_dir = self.video_dir(&meeting_id);
+            video_bytes += Self::dir_size(&video_dir).unwrap_or(0);
+            chunks_count += Self::chunk_files(&video_dir).len() as u32;
+
+            if let Some(created) = Self::created_at(&video_dir) {
+                if oldest.map_or(true, |o| created < o) {
+                    oldest = Some(created);
                 }
             }
+            meeting_ids.insert(meeting_id);
         }
 
+        // Keyframes and archived audio are pruned by the storage manager, but
+        // they share the disk budget
+        let frames_root = self.storage_dir.join("frames");
+        let audio_root = self.storage_dir.join("audio");
+        let frames_bytes = Self::dir_size(&frames_root).unwrap_or(0);
+        let audio_bytes = Self::dir_size(&audio_root).unwrap_or(0);
+        meeting_ids.extend(Self::meeting_dirs(&frames_root));
+        meeting_ids.extend(Self::meeting_dirs(&audio_root));
+
+        let total_bytes = video_bytes + frames_bytes + audio_bytes;
         let disk_limit_bytes = (self.policy.max_disk_gb * 1024.0 * 1024.0 * 1024.0) as u64;
         let usage_percent = if disk_limit_bytes > 0 {
             (total_bytes as f32 / disk_limit_bytes as f32) * 100.0
@@ -144,7 +166,8 @@ impl ChunkManager {
             total_bytes,
             video_bytes,
             frames_bytes,
-            meetings_count,
+            audio_bytes,
+            meetings_count: meeting_ids.len() as u32,
             chunks_count,
             oldest_meeting: oldest,
             disk_limit_bytes,
@@ -152,74 +175,27 @@ impl ChunkManager {
         })
     }
 
-    /// Get storage info for all meetings
+    /// Get storage info for all meetings with recorded video
     pub fn list_meetings(&self) -> Result<Vec<MeetingStorage>, String> {
         let mut meetings = Vec::new();
 
-        if let Ok(entries) = std::fs::read_dir(&self.storage_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    let meeting_id = path
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("")
-                        .to_string();
-
-                    if meeting_id.len() != 36 {
-                        continue; // Not a meeting directory
-                    }
-
-                    let mut video_bytes = 0u64;
-                    let mut frames_bytes = 0u64;
-                    let mut chunk_count = 0u32;
-                    let mut created_at = Utc::now();
-
-                    // Get directory creation time
-                    if let Ok(meta) = entry.metadata() {
-                        if let Ok(created) = meta.created() {
-                            created_at = created.into();
-                        }
-                    }
-
-                    // Count video bytes and chunks
-                    let video_dir = path.join("video");
-                    if video_dir.exists() {
-                        if let Ok(files) = std::fs::read_dir(&video_dir) {
-                            for file in files.flatten() {
-                                if let Ok(meta) = file.metadata() {
-                                    video_bytes += meta.len();
-                                    if file.path().extension().map_or(false, |e| e == "mov") {
-                                        chunk_count += 1;
-                                    }
-                                }
-                            }
-                        }
-                    }
-
-                    // Count frames bytes
-                    let frames_dir = path.join("frames");
-                    if frames_dir.exists() {
-                        if let Ok(files) = std::fs::read_dir(&frames_dir) {
-                            for file in files.flatten() {
-                                if let Ok(meta) = file.metadata() {
-                                    frames_bytes += meta.len();
-                                }
-                            }
-                        }
-                    }
-
-                    meetings.push(MeetingStorage {
-                        meeting_id,
-                        created_at,
-                        video_bytes,
-                        frames_bytes,
-                        total_bytes: video_bytes + frames_bytes,
-                        chunk_count,
-                        path,
-                    });
-                }
-            }
+        for meeting_id in Self::meeting_dirs(&self.storage_dir.join("video")) {
+            let path = self.video_dir(&meeting_id);
+            let video_bytes = Self::dir_size(&path).unwrap_or(0);
+            let frames_bytes =
+                Self::dir_size(&self.storage_dir.join("frames").join(&meeting_id)).unwrap_or(0);
+            let chunk_count = Self::chunk_files(&path).len() as u32;
+            let created_at = Self::created_at(&path).unwrap_or_else(Utc::now);
+
+            meetings.push(MeetingStorage {
+                meeting_id,
+                created_at,
+                video_bytes,
+                frames_bytes,
+                total_bytes: video_bytes + frames_bytes,
+                chunk_count,
+                path,
+            });
         }
 
         // Sort by creation time, newest first
@@ -232,31 +208,15 @@ impl ChunkManager {
         let mut deleted_meetings = 0u32;
         let mut freed_bytes = 0u64;
 
-        let now = Utc::now();
-        let video_cutoff = now - Duration::days(self.policy.video_retention_days as i64);
-        let frames_cutoff = now - Duration::days(self.policy.frames_retention_days as i64);
-
-        let meetings = self.list_meetings()?;
+        let video_cutoff = Utc::now() - Duration::days(self.policy.video_retention_days as i64);
 
-        for meeting in &meetings {
-            // Delete old videos
+        // Keyframes follow the storage manager's keyframe retention instead
+        for meeting in self.list_meetings()? {
             if meeting.created_at < video_cutoff {
                 if let Ok(bytes) = self.delete_meeting(&meeting.meeting_id) {
                     freed_bytes += bytes;
                     deleted_meetings += 1;
                 }
-                continue;
-            }
-
-            // Delete old frames (keep video)
-            if meeting.created_at < frames_cutoff {
-                let frames_dir = meeting.path.join("frames");
-                if frames_dir.exists() {
-                    if let Ok(size) = Self::dir_size(&frames_dir) {
-                        freed_bytes += size;
-                    }
-                    let _ = std::fs::remove_dir_all(&frames_dir);
-                }
             }
         }
 
@@ -267,7 +227,6 @@ impl ChunkManager {
         );
         Ok((deleted_meetings, freed_bytes))
     }
-
     /// Apply LRU eviction if over disk limit
     pub fn apply_lru_eviction(&self) -> Result<(u32, u64), String> {
         if !self.policy.enable_lru_eviction {
@@ -302,9 +261,9 @@ impl ChunkManager {
         Ok((deleted, freed))
     }
 
-    /// Delete a meeting's storage
+    /// Delete a meeting's video storage
     pub fn delete_meeting(&self, meeting_id: &str) -> Result<u64, String> {
-        let path = self.storage_dir.join(meeting_id);
+        let path = self.video_dir(meeting_id);
         if !path.exists() {
             return Ok(0);
         }
@@ -316,49 +275,111 @@ impl ChunkManager {
         Ok(size)
     }
 
-    /// Get video chunks for a meeting
+    /// Record a chunk (new or updated) in the meeting's manifest
+    pub fn register_chunk(&self, meeting_id: &str, chunk: &VideoChunk) -> Result<(), String> {
+        let _guard = self.manifest_lock.lock();
+        let video_dir = self.video_dir(meeting_id);
+        std::fs::create_dir_all(&video_dir)
+            .map_err(|e| format!("Failed to create video directory: {}", e))?;
+
+        let mut chunks = Self::read_manifest(&video_dir);
+        chunks.retain(|c| c.chunk_number != chunk.chunk_number);
+        chunks.push(chunk.clone());
+        chunks.sort_by_key(|c| c.chunk_number);
+
+        let json = serde_json::to_string_pretty(&chunks)
+            .map_err(|e| format!("Failed to serialize chunk manifest: {}", e))?;
+        std::fs::write(video_dir.join(MANIFEST), json)
+            .map_err(|e| format!("Failed to write chunk manifest: {}", e))
+    }
+
+    /// Get video chunks for a meeting, with sizes read from disk
     pub fn get_chunks(&self, meeting_id: &str) -> Result<Vec<VideoChunk>, String> {
-        let video_dir = self.storage_dir.join(meeting_id).join("video");
+        let video_dir = self.video_dir(meeting_id);
         if !video_dir.exists() {
             return Ok(Vec::new());
         }
 
-        let mut chunks = Vec::new();
-
-        if let Ok(entries) = std::fs::read_dir(&video_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.extension().map_or(false, |e| e == "mov") {
-                    let filename = path.file_stem().and_then(|n| n.to_str()).unwrap_or("");
-
-                    let chunk_number = filename
-                        .strip_prefix("chunk_")
-                        .and_then(|n| n.parse::<u32>().ok())
-                        .unwrap_or(0);
-
-                    let meta = entry.metadata().ok();
-                    let size = meta.as_ref().map(|m| m.len()).unwrap_or(0);
-                    let created = meta
-                        .and_then(|m| m.created().ok())
-                        .map(|t| t.into())
-                        .unwrap_or_else(Utc::now);
-
-                    chunks.push(VideoChunk {
-                        chunk_number,
-                        path,
-                        start_time: created,
-                        end_time: None,
-                        size_bytes: size,
-                        duration_secs: 0.0,
-                    });
-                }
+        let mut chunks: Vec<VideoChunk> = Self::read_manifest(&video_dir)
+            .into_iter()
+            .filter_map(|mut chunk| {
+                chunk.size_bytes = std::fs::metadata(&chunk.path).ok()?.len();
+                Some(chunk)
+            })
+            .collect();
+
+        // Chunk files the manifest doesn't know about (e.g. written before a crash)
+        for path in Self::chunk_files(&video_dir) {
+            if chunks.iter().any(|c| c.path == path) {
+                continue;
             }
+            let chunk_number = path
+                .file_stem()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix("chunk_"))
+                .and_then(|n| n.parse::<u32>().ok())
+                .unwrap_or(0);
+
+            let meta = std::fs::metadata(&path).ok();
+            let size = meta.as_ref().map(|m| m.len()).unwrap_or(0);
+            let created = meta
+                .and_then(|m| m.created().ok())
+                .map(|t| t.into())
+                .unwrap_or_else(Utc::now);
+
+            chunks.push(VideoChunk {
+                chunk_number,
+                path,
+                start_time: created,
+                end_time: None,
+                size_bytes: size,
+                duration_secs: 0.0,
+            });
         }
 
         chunks.sort_by_key(|c| c.chunk_number);
         Ok(chunks)
     }
 
+    fn read_manifest(video_dir: &Path) -> Vec<VideoChunk> {
+        std::fs::read_to_string(video_dir.join(MANIFEST))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Video chunk files (.mp4, or .mov from older recordings) in a directory
+    fn chunk_files(video_dir: &Path) -> Vec<PathBuf> {
+        std::fs::read_dir(video_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.extension().map_or(false, |e| e == "mp4" || e == "mov"))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Meeting directory names (UUIDs) directly under `root`
+    fn meeting_dirs(root: &Path) -> Vec<String> {
+        std::fs::read_dir(root)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|e| e.path().is_dir())
+                    .filter_map(|e| e.file_name().to_str().map(str::to_string))
+                    .filter(|name| name.len() == 36)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn created_at(path: &Path) -> Option<DateTime<Utc>> {
+        let created = std::fs::metadata(path).ok()?.created().ok()?;
+        Some(created.into())
+    }
+
     /// Format bytes as human-readable string
     pub fn format_size(bytes: u64) -> String {
         const KB: u64 = 1024;
@@ -399,7 +420,35 @@ impl Default for ChunkManager {
     fn default() -> Self {
         let storage_dir = dirs::data_dir()
             .unwrap_or_else(|| PathBuf::from("."))
-            .join("com.nofriction.meetings");
+            .join("ai.nofriction.meetings");
         Self::new(storage_dir, RetentionPolicy::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(number: u32, start_ms: i64, end_ms: i64) -> ChunkSpan {
+        ChunkSpan {
+            chunk_number: number,
+            path: format!("/tmp/chunk_{:03}.mp4", number),
+            start_ms,
+            end_ms,
+        }
+    }
+
+    #[test]
+    fn test_plan_clip_spanning_two_chunks() {
+        let chunks = vec![span(1, 0, 300_000), span(2, 300_000, 600_000)];
+        let segments = plan_clip(&chunks, 290_000, 315_000).unwrap();
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(
+            (segments[0].start_secs, segments[0].end_secs),
+            (290.0, 300.0)
+        );
+        assert_eq!((segments[1].start_secs, segments[1].end_secs), (0.0, 15.0));
+        assert!(plan_clip(&chunks, 700_000, 710_000).is_err());
+    }
+}
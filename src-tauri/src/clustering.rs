@@ -1,11 +1,18 @@
 // noFriction Meetings - Transcript Clustering
 // Group transcripts into logical segments based on time gaps and topic similarity
 
-use crate::database::{DatabaseManager, Transcript};
+use crate::ai_client::AIClient;
+use crate::database::{DatabaseManager, TopicClusterRecord, Transcript};
+use crate::pinecone_client::PineconeConfig;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Transcripts on each side of a candidate topic boundary
+const TOPIC_WINDOW: usize = 3;
+/// Sentences closest to a topic's centroid used to label it
+const TOPIC_LABEL_SENTENCES: usize = 3;
+
 /// A cluster of transcripts representing a logical segment of a meeting
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptCluster {
@@ -19,6 +26,15 @@ pub struct TranscriptCluster {
     pub auto_generated: bool,
 }
 
+/// Contiguous topic segments over a sequence of embedded transcripts
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopicSegmentation {
+    /// Transcript indices per topic, in order
+    pub segments: Vec<Vec<usize>>,
+    /// Mean silhouette against topic centroids (-1..1, higher = better separated)
+    pub quality: f64,
+}
+
 /// Clustering configuration
 #[derive(Debug, Clone)]
 pub struct ClusteringConfig {
@@ -330,6 +346,305 @@ impl ClusteringEngine {
     }
 }
 
+/// Cosine similarity of two vectors (0 if either is zero)
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f64, 0.0f64, 0.0f64);
+    for (x, y) in a.iter().zip(b) {
+        dot += (*x as f64) * (*y as f64);
+        norm_a += (*x as f64) * (*x as f64);
+        norm_b += (*y as f64) * (*y as f64);
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a.sqrt() * norm_b.sqrt())
+    }
+}
+
+pub(crate) fn centroid(embeddings: &[Vec<f32>], indices: &[usize]) -> Vec<f32> {
+    let dim = embeddings.first().map(|e| e.len()).unwrap_or(0);
+    let mut sum = vec![0.0f32; dim];
+    for &i in indices {
+        for (acc, v) in sum.iter_mut().zip(&embeddings[i]) {
+            *acc += v;
+        }
+    }
+    let n = indices.len().max(1) as f32;
+    sum.iter().map(|v| v / n).collect()
+}
+
+/// Group vectors in order: each joins the group whose centroid it is most
+/// similar to (at least `threshold`), otherwise it starts a new group.
+/// Unlike topic segmentation, groups needn't be contiguous.
+pub fn leader_clusters(embeddings: &[Vec<f32>], threshold: f64) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut centroids: Vec<Vec<f32>> = Vec::new();
+    for (i, embedding) in embeddings.iter().enumerate() {
+        let best = centroids
+            .iter()
+            .enumerate()
+            .map(|(g, c)| (g, cosine_similarity(embedding, c)))
+            .filter(|(_, sim)| *sim >= threshold)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match best {
+            Some((g, _)) => {
+                groups[g].push(i);
+                centroids[g] = centroid(embeddings, &groups[g]);
+            }
+            None => {
+                groups.push(vec![i]);
+                centroids.push(embedding.clone());
+            }
+        }
+    }
+    groups
+}
+
+/// Mean silhouette of each transcript against the topic centroids.
+/// A single topic scores 0.
+pub fn segmentation_quality(embeddings: &[Vec<f32>], segments: &[Vec<usize>]) -> f64 {
+    if segments.len() < 2 {
+        return 0.0;
+    }
+
+    let centroids: Vec<Vec<f32>> = segments
+        .iter()
+        .map(|seg| centroid(embeddings, seg))
+        .collect();
+
+    let mut total = 0.0;
+    let mut count = 0;
+    for (k, seg) in segments.iter().enumerate() {
+        for &i in seg {
+            let own = 1.0 - cosine_similarity(&embeddings[i], &centroids[k]);
+            let nearest_other = centroids
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != k)
+                .map(|(_, c)| 1.0 - cosine_similarity(&embeddings[i], c))
+                .fold(f64::MAX, f64::min);
+            let scale = own.max(nearest_other);
+            if scale > 0.0 {
+                total += (nearest_other - own) / scale;
+            }
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        0.0
+    } else {
+        total / count as f64
+    }
+}
+
+impl ClusteringEngine {
+    /// Split a transcript sequence into topics where the windows on either side
+    /// of a gap stop resembling each other (TextTiling-style depth scores).
+    /// Topics are never shorter than `min_cluster_size` transcripts.
+    pub fn segment_by_embeddings(&self, embeddings: &[Vec<f32>]) -> TopicSegmentation {
+        let n = embeddings.len();
+        let min_size = self.config.min_cluster_size.max(1);
+        let whole = TopicSegmentation {
+            segments: vec![(0..n).collect()],
+            quality: 0.0,
+        };
+        if n < min_size * 2 {
+            return whole;
+        }
+
+        // Similarity across each gap; gap i sits between transcripts i-1 and i
+        let similarity: Vec<f64> = (1..n)
+            .map(|i| {
+                let left: Vec<usize> = (i.saturating_sub(TOPIC_WINDOW)..i).collect();
+                let right: Vec<usize> = (i..(i + TOPIC_WINDOW).min(n)).collect();
+                cosine_similarity(&centroid(embeddings, &left), &centroid(embeddings, &right))
+            })
+            .collect();
+
+        // Depth of each valley relative to the peaks around it
+        let depths: Vec<f64> = (0..similarity.len())
+            .map(|g| {
+                let mut left_peak = similarity[g];
+                for &s in similarity[..g].iter().rev() {
+                    if s < left_peak {
+                        break;
+                    }
+                    left_peak = s;
+                }
+                let mut right_peak = similarity[g];
+                for &s in &similarity[g + 1..] {
+                    if s < right_peak {
+                        break;
+                    }
+                    right_peak = s;
+                }
+                (left_peak - similarity[g]) + (right_peak - similarity[g])
+            })
+            .collect();
+
+        let mean = depths.iter().sum::<f64>() / depths.len() as f64;
+        let std_dev =
+            (depths.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / depths.len() as f64).sqrt();
+        let threshold = mean + std_dev / 2.0;
+
+        // Deepest valleys first, as long as every topic stays big enough
+        let mut candidates: Vec<(usize, f64)> = depths
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| **d > 0.0 && **d >= threshold)
+            .map(|(g, d)| (g + 1, *d))
+            .collect();
+        candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut boundaries: Vec<usize> = Vec::new();
+        for (at, _) in candidates {
+            let prev = boundaries
+                .iter()
+                .copied()
+                .filter(|b| *b < at)
+                .max()
+                .unwrap_or(0);
+            let next = boundaries
+                .iter()
+                .copied()
+                .filter(|b| *b > at)
+                .min()
+                .unwrap_or(n);
+            if at - prev >= min_size && next - at >= min_size {
+                boundaries.push(at);
+            }
+        }
+        if boundaries.is_empty() {
+            return whole;
+        }
+        boundaries.sort_unstable();
+
+        let mut segments = Vec::with_capacity(boundaries.len() + 1);
+        let mut start = 0;
+        for b in boundaries.into_iter().chain(std::iter::once(n)) {
+            segments.push((start..b).collect::<Vec<_>>());
+            start = b;
+        }
+
+        let quality = segmentation_quality(embeddings, &segments);
+        TopicSegmentation { segments, quality }
+    }
+
+    /// Replace a finished meeting's heuristic topic clusters with semantic
+    /// ones: embed the final transcripts, split on topic boundaries and label
+    /// each topic from its most central sentences. Returns the topic count;
+    /// errors leave the existing clusters untouched.
+    pub async fn semantic_topic_clusters(
+        &self,
+        meeting_id: &str,
+        database: &Arc<DatabaseManager>,
+        pinecone: &PineconeConfig,
+        ai_client: &AIClient,
+    ) -> Result<usize, String> {
+        let transcripts: Vec<Transcript> = database
+            .get_transcripts(meeting_id)
+            .await
+            .map_err(|e| format!("Failed to get transcripts: {}", e))?
+            .into_iter()
+            .filter(|t| t.is_final && !t.text.trim().is_empty())
+            .collect();
+
+        if transcripts.len() < self.config.min_cluster_size * 2 {
+            return Err("Not enough transcript for semantic topics".to_string());
+        }
+
+        let texts: Vec<String> = transcripts.iter().map(|t| t.text.clone()).collect();
+        let embeddings = crate::pinecone_client::pinecone_embed(pinecone, &texts).await?;
+        let segmentation = self.segment_by_embeddings(&embeddings);
+
+        let mut clusters = Vec::with_capacity(segmentation.segments.len());
+        for (n, segment) in segmentation.segments.iter().enumerate() {
+            let center = centroid(&embeddings, segment);
+            let mut central: Vec<usize> = segment.clone();
+            central.sort_by(|a, b| {
+                cosine_similarity(&embeddings[*b], &center)
+                    .partial_cmp(&cosine_similarity(&embeddings[*a], &center))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            central.truncate(TOPIC_LABEL_SENTENCES);
+            central.sort_unstable();
+            let top_sentences: Vec<&str> = central
+                .iter()
+                .map(|&i| transcripts[i].text.as_str())
+                .collect();
+
+            let name = match ai_client
+                .complete(&topic_label_prompt(&top_sentences))
+                .await
+            {
+                Ok(label) => clean_topic_label(&label),
+                Err(e) => {
+                    log::warn!("Topic labeling failed: {}", e);
+                    None
+                }
+            }
+            .unwrap_or_else(|| format!("Topic {}", n + 1));
+
+            let first = &transcripts[segment[0]];
+            let last = &transcripts[segment[segment.len() - 1]];
+            clusters.push(TopicClusterRecord {
+                topic_id: Uuid::new_v4().to_string(),
+                meeting_id: meeting_id.to_string(),
+                name,
+                description: Some(top_sentences.join(" … ")),
+                start_ts: first.timestamp.to_rfc3339(),
+                end_ts: Some(last.timestamp.to_rfc3339()),
+                event_count: segment.len() as i32,
+                total_duration_ms: (last.timestamp - first.timestamp).num_milliseconds(),
+                quality_score: Some(segmentation.quality),
+            });
+        }
+
+        database
+            .replace_topic_clusters(meeting_id, &clusters)
+            .await
+            .map_err(|e| format!("Failed to save topic clusters: {}", e))?;
+
+        log::info!(
+            "Semantic topics for meeting {}: {} topics (quality {:.2})",
+            meeting_id,
+            clusters.len(),
+            segmentation.quality
+        );
+
+        Ok(clusters.len())
+    }
+}
+
+fn topic_label_prompt(sentences: &[&str]) -> String {
+    format!(
+        "These sentences are the most representative of one topic in a meeting:\n\n{}\n\n\
+         Reply with a short topic label (2-6 words). No quotes, no punctuation at the end, nothing else.",
+        sentences
+            .iter()
+            .map(|s| format!("- {}", s))
+            .collect::<Vec<_>>()
+            .join("\n")
+    )
+}
+
+pub(crate) fn clean_topic_label(response: &str) -> Option<String> {
+    let label = response
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())?
+        .trim_start_matches("Topic:")
+        .trim()
+        .trim_matches(|c: char| c == '"' || c == '\'' || c == '*' || c == '.')
+        .trim();
+    if label.is_empty() {
+        None
+    } else {
+        Some(label.chars().take(60).collect())
+    }
+}
+
 impl Default for ClusteringEngine {
     fn default() -> Self {
         Self::new(ClusteringConfig::default())
@@ -351,4 +666,42 @@ mod tests {
         let sim2 = ClusteringEngine::text_similarity("completely different", "nothing alike");
         assert!(sim2 < 0.2);
     }
+
+    #[test]
+    fn test_segment_by_embeddings_finds_topic_shift() {
+        let engine = ClusteringEngine::default();
+        let mut embeddings = vec![vec![1.0, 0.1, 0.0]; 6];
+        embeddings.extend(vec![vec![0.0, 0.1, 1.0]; 6]);
+
+        let result = engine.segment_by_embeddings(&embeddings);
+        assert_eq!(
+            result.segments,
+            vec![(0..6).collect::<Vec<_>>(), (6..12).collect::<Vec<_>>()]
+        );
+        assert!(result.quality > 0.9);
+    }
+
+    #[test]
+    fn test_leader_clusters_groups_non_contiguous() {
+        let embeddings = vec![
+            vec![1.0, 0.0],
+            vec![0.0, 1.0],
+            vec![0.95, 0.05],
+            vec![0.05, 0.95],
+            vec![1.0, 0.1],
+        ];
+        assert_eq!(
+            leader_clusters(&embeddings, 0.9),
+            vec![vec![0, 2, 4], vec![1, 3]]
+        );
+    }
+
+    #[test]
+    fn test_segment_by_embeddings_single_topic() {
+        let engine = ClusteringEngine::default();
+        let embeddings = vec![vec![1.0, 0.0]; 8];
+        let result = engine.segment_by_embeddings(&embeddings);
+        assert_eq!(result.segments.len(), 1);
+        assert_eq!(result.quality, 0.0);
+    }
 }
@@ -4,9 +4,13 @@
 use crate::capture_engine::{
     AudioBuffer, AudioDevice, CapturedFrame, MonitorInfo, RecordingStatus,
 };
-use crate::database::{Frame, Meeting, SearchResult, SyncedTimeline, Transcript};
+use crate::database::{
+    Frame, Meeting, MeetingSpeaker, SearchResult, SyncedTimeline, Transcript, TranscriptRevision,
+};
+use crate::privacy_filter::PrivacyRules;
 use crate::settings::AppSettings;
-use crate::transcription::ProviderType;
+use crate::transcription::key_validation::{self, KeyValidation, KeyValidationStatus};
+use crate::transcription::{GlossaryTerm, ProviderType};
 use crate::{AppState, InitStatus, InitializationState};
 use base64::Engine;
 use std::sync::Arc;
@@ -20,6 +24,12 @@ pub async fn check_init_status(
     Ok(state.0.read().clone())
 }
 
+/// Status of each optional subsystem (safe to call before AppState is ready)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_subsystem_status() -> Result<Vec<crate::subsystems::SubsystemStatus>, String> {
+    Ok(crate::subsystems::subsystems().snapshot())
+}
+
 #[derive(serde::Serialize)]
 pub struct PermissionStatus {
     pub screen_recording: bool,
@@ -368,9 +378,32 @@ pub async fn request_permission(permission_type: String) -> Result<bool, String>
 /// Start recording with frame capture and live transcription
 #[tauri::command(rename_all = "camelCase")]
 pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Result<String, String> {
+    // Recording during a calendar meeting picks up its title and attendees
+    let event = state.calendar_client.read().get_current_event();
+    let title = match &event {
+        Some(event) => event.title.clone(),
+        None => format!("Meeting {}", chrono::Local::now().format("%Y-%m-%d %H:%M")),
+    };
+    start_recording_titled(app, state, title, event).await
+}
+
+/// Flags stored on screen states covering frames the privacy filter excluded
+const REDACTED_STATE_FLAGS: &str = r#"{"redacted":true}"#;
+
+/// Shared start path for manual and auto-started recordings
+pub(crate) async fn start_recording_titled(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    title: String,
+    event: Option<CalendarEventNative>,
+) -> Result<String, String> {
+    // A re-transcription holds the (single-session) transcription provider
+    if crate::transcription::retranscribe::is_running() {
+        return Err("A meeting is being re-transcribed; try again when it finishes".to_string());
+    }
+
     // Generate a new meeting ID
     let meeting_id = uuid::Uuid::new_v4().to_string();
-    let title = format!("Meeting {}", chrono::Local::now().format("%Y-%m-%d %H:%M"));
 
     // Create meeting in database
     state
@@ -379,6 +412,16 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
         .await
         .map_err(|e| format!("Failed to create meeting: {}", e))?;
 
+    if let Some(event) = event {
+        if let Err(e) = state
+            .database
+            .set_meeting_calendar_link(&meeting_id, &calendar_link(event))
+            .await
+        {
+            log::warn!("Failed to link meeting to calendar event: {}", e);
+        }
+    }
+
     // Get app data directory for frame storage
     let frames_dir = app
         .path()
@@ -426,52 +469,41 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
         let mut provider_type = tm.get_provider_type();
 
         // Check if the current provider has a stored key (already loaded at startup)
+        // that its provider has not rejected
         let has_key = tm.has_key_for_provider(provider_type);
+        let key_invalid = has_key && tm.key_known_invalid(provider_type);
 
-        if !has_key {
+        if !has_key || key_invalid {
             log::warn!(
-                "No API key stored for {:?} — checking other providers for fallback",
+                "{} for {:?} — checking other providers for fallback",
+                if has_key {
+                    "Stored API key was rejected"
+                } else {
+                    "No API key stored"
+                },
                 provider_type
             );
 
-            // Try to auto-switch to a provider that has a key
-            let fallback_providers = [
-                ProviderType::Deepgram,
-                ProviderType::Gemini,
-                ProviderType::Gladia,
-                ProviderType::GoogleSTT,
-            ];
-
-            let mut found_fallback = false;
-            for alt in &fallback_providers {
-                if *alt != provider_type && tm.has_key_for_provider(*alt) {
+            // Try to auto-switch to a provider that has a key, preferring
+            // recently validated ones
+            match tm.preferred_fallback(provider_type) {
+                Some(alt) => {
                     log::info!(
                         "Auto-switching transcription from {:?} to {:?} (has key)",
                         provider_type,
                         alt
                     );
-                    tm.switch_provider(*alt);
-                    provider_type = *alt;
+                    tm.switch_provider(alt);
+                    provider_type = alt;
                     // Also persist the switch
-                    let provider_str = match alt {
-                        ProviderType::Deepgram => "deepgram",
-                        ProviderType::Gemini => "gemini",
-                        ProviderType::Gladia => "gladia",
-                        ProviderType::GoogleSTT => "google_stt",
-                    };
                     let _ = state
                         .settings
-                        .set_transcription_provider(provider_str)
+                        .set_transcription_provider(alt.as_str())
                         .await;
-                    found_fallback = true;
-                    break;
                 }
-            }
-
-            if !found_fallback {
-                log::warn!(
-                    "No transcription API key configured for any provider - transcription disabled"
-                );
+                None => log::warn!(
+                    "No usable transcription API key configured for any provider - transcription disabled"
+                ),
             }
         }
 
@@ -479,6 +511,8 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
             "Setting up Transcription connection for {:?}...",
             provider_type
         );
+        // Fresh context window for the new meeting
+        state.live_intel_agent.write().reset();
         tm.set_context(
             app.clone(),
             state.database.clone(),
@@ -496,10 +530,49 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
             return;
         }
 
-        // Queue audio to provider (non-blocking)
-        transcription_manager.process_audio(&buffer.samples, buffer.sample_rate, buffer.channels);
+        // Mix mic + system audio and queue to provider (non-blocking)
+        transcription_manager.process_buffer(&buffer);
+        // Raw audio archive (no-op unless audio_recording_enabled)
+        crate::audio_recorder::audio_recorder().push(&buffer);
     });
 
+    // Archive the raw audio alongside the transcripts when enabled
+    let audio_format = state
+        .settings
+        .get_all()
+        .await
+        .ok()
+        .filter(|s| s.audio_recording_enabled)
+        .map(|s| crate::audio_recorder::AudioFormat::from_setting(&s.audio_recording_format));
+    if let Some(format) = audio_format {
+        let audio_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app data dir: {}", e))?
+            .join("audio")
+            .join(&meeting_id);
+        if let Err(e) = crate::audio_recorder::audio_recorder().start(
+            &meeting_id,
+            audio_dir,
+            format,
+            state.database.clone(),
+        ) {
+            log::warn!("Failed to start audio archiving: {}", e);
+        }
+    }
+
+    // Load capture settings BEFORE acquiring lock
+    let (frame_interval, capture_mic, capture_system_audio, capture_all_monitors) =
+        match state.settings.get_all().await {
+            Ok(settings) => (
+                settings.frame_capture_interval_ms,
+                settings.capture_microphone,
+                settings.capture_system_audio,
+                settings.capture_all_monitors,
+            ),
+            Err(_) => (1000, true, true, false), // Default to 1 second, both audio sources
+        };
+
     // ═══════════════════════════════════════════════════════════════════════════
     // Phase 1: Stateful Frame Callback (DeDupGate + StateBuilder)
     // ═══════════════════════════════════════════════════════════════════════════
@@ -507,8 +580,13 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
     let meeting_id_for_frames = meeting_id.clone();
     let frames_dir_clone = frames_dir.clone();
     let state_builder = state.state_builder.clone();
+    let episode_builder = state.episode_builder.clone();
+    let timeline_builder = state.timeline_builder.clone();
     let metrics_collector = state.metrics_collector.clone();
-    let settings_for_frames = state.settings.clone();
+    let flags_for_frames = state.feature_flags.clone();
+    // Open redacted state per monitor (consecutive excluded frames extend one state)
+    let redacted_state: Arc<parking_lot::Mutex<std::collections::HashMap<u32, String>>> =
+        Arc::new(parking_lot::Mutex::new(std::collections::HashMap::new()));
 
     // Estimated bytes per frame (for savings calculation)
     const ESTIMATED_FRAME_BYTES: u64 = 50_000; // ~50KB per JPEG
@@ -518,8 +596,15 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
         let mid = meeting_id_for_frames.clone();
         let dir = frames_dir_clone.clone();
         let builder = state_builder.clone();
+        let episodes = episode_builder.clone();
+        let timeline = timeline_builder.clone();
         let metrics = metrics_collector.clone();
-        let settings = settings_for_frames.clone();
+        let flags = flags_for_frames.clone();
+        let redacted_state = redacted_state.clone();
+
+        // Checked on the capture thread so it reflects the window this frame shows
+        let window = crate::accessibility_extractor::AccessibilityExtractor::frontmost_window();
+        let exclusion = crate::privacy_filter::exclusion_for(window.as_ref());
 
         // Process frame through StateBuilder (stateful dedup)
         tokio::spawn(async move {
@@ -529,10 +614,54 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
             // Record frame received
             metrics.record_frame();
 
+            // Privacy filter: no keyframe, no VLM - just keep the timeline continuous
+            if let Some(reason) = exclusion {
+                metrics.record_redaction();
+                let open_state = redacted_state.lock().get(&frame.monitor_id).cloned();
+                match open_state {
+                    Some(state_id) => {
+                        if let Err(e) = db.extend_screen_state(&state_id, frame.timestamp).await {
+                            log::warn!("Failed to extend redacted state: {}", e);
+                        }
+                    }
+                    None => {
+                        builder.read().break_state(frame.monitor_id);
+                        let state_id = uuid::Uuid::new_v4().to_string();
+                        if let Err(e) = db
+                            .add_screen_state(
+                                &state_id,
+                                &mid,
+                                frame.timestamp,
+                                Some(frame.timestamp),
+                                "",
+                                0.0,
+                                None,
+                                "other",
+                                REDACTED_STATE_FLAGS,
+                                Some(frame.monitor_id),
+                            )
+                            .await
+                        {
+                            log::warn!("Failed to save redacted state: {}", e);
+                        }
+                        log::debug!("🔒 Redacted state {} ({})", state_id, reason);
+                        redacted_state.lock().insert(frame.monitor_id, state_id);
+                    }
+                }
+                metrics.record_cpu_time(timer_start.elapsed());
+                return;
+            }
+            redacted_state.lock().remove(&frame.monitor_id);
+
+            // Dedup switched off: every frame starts its own state
+            if !flags.read().dedup_enabled {
+                builder.read().break_state(frame.monitor_id);
+            }
+
             // Process through StateBuilder (pHash + delta scoring)
             let result = {
                 let builder = builder.read();
-                builder.process_frame(frame.image.clone(), frame.timestamp)
+                builder.process_frame(frame.monitor_id, frame.image.clone(), frame.timestamp)
             };
 
             use crate::state_builder::FrameProcessResult;
@@ -572,12 +701,17 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
                     // Get pending keyframe to save
                     let pending_keyframe = {
                         let builder = builder.read();
-                        builder.take_pending_keyframe()
+                        builder.take_pending_keyframe(frame.monitor_id)
                     };
 
                     if let Some(keyframe_image) = pending_keyframe {
                         // Generate keyframe path (state-based, not frame-number-based)
-                        let filename = format!("state_{}.jpg", new_state_id);
+                        // Encode the display when capturing several at once
+                        let filename = if capture_all_monitors {
+                            format!("state_m{}_{}.jpg", frame.monitor_id, new_state_id)
+                        } else {
+                            format!("state_{}.jpg", new_state_id)
+                        };
                         let keyframe_path = dir.join(&filename);
 
                         // Save keyframe as JPEG
@@ -586,13 +720,14 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
                         } else {
                             metrics.record_image_write(ESTIMATED_FRAME_BYTES);
 
-                            // Get state info for database insertion
-                            let _state_record = {
-                                let _builder = builder.read();
-                                // Access current state info from accumulator
-                                // For now we insert with minimal info
-                                None::<crate::state_builder::ScreenState>
-                            };
+                            let app_name = window.as_ref().and_then(|w| w.app_name.clone());
+                            let window_title = window.as_ref().and_then(|w| w.window_title.clone());
+                            let classification = crate::semantic_classifier::classify_state(
+                                app_name.as_deref(),
+                                window.as_ref().and_then(|w| w.bundle_id.as_deref()),
+                                window_title.as_deref(),
+                                None,
+                            );
 
                             // Insert new screen state into database
                             let flags_json = "{}";
@@ -605,31 +740,67 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
                                     "",  // phash - would need to pass from StateBuilder
                                     0.0, // delta_score
                                     Some(keyframe_path.to_str().unwrap_or("")),
-                                    "other",
+                                    classification.state_type.as_str(),
                                     flags_json,
+                                    Some(frame.monitor_id),
                                 )
                                 .await
                             {
                                 log::warn!("Failed to save screen state: {}", e);
+                            } else if let Err(e) = db
+                                .set_screen_state_context(
+                                    &new_state_id,
+                                    app_name.as_deref(),
+                                    window_title.as_deref(),
+                                    classification.confidence,
+                                )
+                                .await
+                            {
+                                log::warn!("Failed to save screen state context: {}", e);
+                            }
+
+                            // Feed the episode builder; a closed episode becomes timeline events
+                            let mut screen_state = crate::state_builder::ScreenState::new(
+                                &mid,
+                                frame.monitor_id,
+                                frame.timestamp,
+                                String::new(),
+                            );
+                            screen_state.state_id = new_state_id.clone();
+                            screen_state.keyframe_path = Some(keyframe_path.clone());
+                            screen_state.app_name = app_name;
+                            screen_state.window_title = window_title;
+                            screen_state.state_type = classification.state_type;
+                            screen_state.category_confidence = classification.confidence;
+
+                            let completed = match episodes.read().process_state(&screen_state) {
+                                crate::episode_builder::EpisodeProcessResult::NewEpisode {
+                                    completed_episode,
+                                    ..
+                                } => completed_episode,
+                                _ => None,
+                            };
+                            if let Some(episode) = completed {
+                                save_episode(&db, &episode).await;
+                                timeline.process_episode(&episode);
                             }
 
                             log::debug!("📺 New state: {} → {:?}", new_state_id, keyframe_path);
 
                             // Queue frame for VLM analysis if enabled
-                            if let Ok(app_settings) = settings.get_all().await {
-                                if app_settings.queue_frames_for_vlm {
-                                    if let Err(e) = db
-                                        .queue_frame(
-                                            None, // frame_id - using screen state
-                                            keyframe_path.to_str().unwrap_or(""),
-                                            frame.timestamp,
-                                        )
-                                        .await
-                                    {
-                                        log::warn!("Failed to queue frame for VLM: {}", e);
-                                    } else {
-                                        log::debug!("📸 Queued frame for VLM: {}", new_state_id);
-                                    }
+                            let queue_for_vlm = flags.read().queue_frames_for_vlm;
+                            if queue_for_vlm {
+                                if let Err(e) = db
+                                    .queue_frame(
+                                        None, // frame_id - using screen state
+                                        keyframe_path.to_str().unwrap_or(""),
+                                        frame.timestamp,
+                                    )
+                                    .await
+                                {
+                                    log::warn!("Failed to queue frame for VLM: {}", e);
+                                } else {
+                                    log::debug!("📸 Queued frame for VLM: {}", new_state_id);
                                 }
                             }
                         }
@@ -638,7 +809,11 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
 
                 FrameProcessResult::PassThrough => {
                     // Stateful capture disabled, fall back to legacy behavior
-                    let filename = format!("frame_{}.jpg", frame.frame_number);
+                    let filename = if capture_all_monitors {
+                        format!("frame_m{}_{}.jpg", frame.monitor_id, frame.frame_number)
+                    } else {
+                        format!("frame_{}.jpg", frame.frame_number)
+                    };
                     let thumbnail_path = dir.join(&filename);
 
                     if let Err(e) = frame.image.to_rgb8().save(&thumbnail_path) {
@@ -665,18 +840,14 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
         });
     });
 
-    // Load frame capture interval from settings BEFORE acquiring lock
-    let frame_interval = match state.settings.get_all().await {
-        Ok(settings) => settings.frame_capture_interval_ms,
-        Err(_) => 1000, // Default to 1 second
-    };
-
     // Set callbacks and start capture
     {
         let engine = state.capture_engine.read();
         engine.set_audio_callback(audio_callback);
         engine.set_frame_callback(frame_callback);
         engine.set_frame_interval(frame_interval);
+        engine.set_audio_sources(capture_mic, capture_system_audio);
+        engine.set_capture_all_monitors(capture_all_monitors);
     }
 
     {
@@ -684,6 +855,11 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
         engine.start(app)?;
     }
 
+    // Watch for the meeting ending (apps closed / calendar event over)
+    state.meeting_trigger.set_recording(true);
+    // Live audio goes to transcription from here on, not the ambient buffer
+    state.ambient_capture.buffer().set_active(false);
+
     log::info!(
         "🎬 Recording started: {} (stateful capture enabled, frames → {:?})",
         meeting_id,
@@ -694,7 +870,7 @@ pub async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
 
 /// Stop recording
 #[tauri::command(rename_all = "camelCase")]
-pub async fn stop_recording(state: State<'_, AppState>) -> Result<(), String> {
+pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
     let was_recording = {
         let engine = state.capture_engine.read();
         engine.get_status().is_recording
@@ -711,17 +887,45 @@ pub async fn stop_recording(state: State<'_, AppState>) -> Result<(), String> {
         state.transcription_manager.stop();
     }
 
+    // Finalize the last raw audio chunk and register it
+    crate::audio_recorder::audio_recorder().stop().await;
+
+    // Let meeting detection suggest/auto-start again
+    state.meeting_trigger.clear_session();
+    state.meeting_trigger.set_recording(false);
+
+    // Drain any transcript inserts still waiting on a retry
+    crate::transcription::retry_queue::retry_queue()
+        .flush()
+        .await;
+
     // ═══════════════════════════════════════════════════════════════════════════
     // Phase 1: Finalize Stateful Screen Ingest
     // ═══════════════════════════════════════════════════════════════════════════
     if was_recording {
+        let ended_meeting_id = state.timeline_builder.current_meeting_id();
+
+        // Close the meeting row (duration excludes paused time)
+        if let Some(ref meeting_id) = ended_meeting_id {
+            if let Err(e) = state.database.end_meeting(meeting_id).await {
+                log::warn!("Failed to end meeting: {}", e);
+            }
+            state.catch_up_cache.invalidate(meeting_id);
+            crate::integrations::emit_meeting_event(
+                &state.database,
+                crate::integrations::EVENT_MEETING_ENDED,
+                meeting_id,
+            )
+            .await;
+        }
+
         // End state builder session
-        let final_state = {
+        let final_states = {
             let state_builder = state.state_builder.read();
             state_builder.end_meeting()
         };
 
-        if let Some(completed) = final_state {
+        for completed in final_states {
             log::info!(
                 "📺 Final state completed: {} (duration: {:?}ms)",
                 completed.state_id,
@@ -742,47 +946,32 @@ pub async fn stop_recording(state: State<'_, AppState>) -> Result<(), String> {
             episodes.len()
         );
 
-        // Save episodes to database
+        // Save episodes to database (earlier ones were saved as they closed)
         for episode in &episodes {
-            // Create the episode first
-            if let Err(e) = state
-                .database
-                .create_episode(
-                    &episode.episode_id,
-                    &episode.meeting_id,
-                    episode.start_ts,
-                    episode.app_name.as_deref(),
-                    episode.window_title.as_deref(),
-                )
-                .await
-            {
-                log::warn!("Failed to create episode: {}", e);
-                continue;
-            }
-
-            // Then update with final stats
-            if let Some(end_ts) = episode.end_ts {
-                if let Err(e) = state
-                    .database
-                    .update_episode(
-                        &episode.episode_id,
-                        end_ts,
-                        episode.state_count,
-                        episode.duration_ms(),
-                    )
-                    .await
-                {
-                    log::warn!("Failed to update episode: {}", e);
-                }
-            }
+            save_episode(&state.database, episode).await;
+            state.timeline_builder.process_episode(episode);
         }
 
         // ═══════════════════════════════════════════════════════════════════════
         // Phase 3: Finalize Timeline Generation
         // ═══════════════════════════════════════════════════════════════════════
-        let timeline_events = state.timeline_builder.end_meeting(chrono::Utc::now());
+        let mut timeline_events = state.timeline_builder.end_meeting(chrono::Utc::now());
         let topic_clusters = state.timeline_builder.get_topics();
 
+        // Score against what was said and decided while each event happened
+        let scoring_context = match ended_meeting_id.as_deref() {
+            Some(meeting_id) => {
+                crate::importance::ScoringContext::load(&state.database, meeting_id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        log::warn!("Scoring timeline without transcript context: {}", e);
+                        Default::default()
+                    })
+            }
+            None => Default::default(),
+        };
+        crate::importance::score_events(&mut timeline_events, &scoring_context);
+
         log::info!(
             "📊 Timeline generation completed: {} events, {} topics",
             timeline_events.len(),
@@ -807,6 +996,8 @@ pub async fn stop_recording(state: State<'_, AppState>) -> Result<(), String> {
                     event.state_id.as_deref(),
                     event.topic.as_deref(),
                     event.importance,
+                    event.importance_reason.as_deref(),
+                    event.category.as_deref(),
                 )
                 .await
             {
@@ -839,6 +1030,43 @@ pub async fn stop_recording(state: State<'_, AppState>) -> Result<(), String> {
             }
         }
 
+        // Semantic topics replace the heuristic clusters once embeddings are back
+        if let Some(ref meeting_id) = ended_meeting_id {
+            let semantic_topics = state
+                .settings
+                .get_all()
+                .await
+                .map(|s| s.semantic_topics_enabled)
+                .unwrap_or(false);
+            let pinecone_config = state.pinecone_client.read().get_config();
+
+            match (semantic_topics, pinecone_config) {
+                (true, Some(pinecone_config)) => {
+                    let db_clone = state.database.clone();
+                    let ai_client = state.ai_client.read().clone();
+                    let meeting_id = meeting_id.clone();
+                    tokio::spawn(async move {
+                        let engine = crate::clustering::ClusteringEngine::default();
+                        if let Err(e) = engine
+                            .semantic_topic_clusters(
+                                &meeting_id,
+                                &db_clone,
+                                &pinecone_config,
+                                &ai_client,
+                            )
+                            .await
+                        {
+                            log::warn!("Semantic topics unavailable, keeping heuristic: {}", e);
+                        }
+                    });
+                }
+                (true, None) => {
+                    log::info!("Semantic topics enabled but Pinecone is not configured");
+                }
+                _ => {}
+            }
+        }
+
         // End metrics collection and log summary
         if let Some(metrics) = state.metrics_collector.end_meeting() {
             metrics.log_summary();
@@ -854,9 +1082,62 @@ pub async fn stop_recording(state: State<'_, AppState>) -> Result<(), String> {
 
         log::info!("🎬 Recording stopped successfully (Phase 1-3 finalized)");
 
+        // Queue the meeting for the Intelligence Pipeline
+        crate::subsystems::ensure_ingest(&state).await;
+        if let (Some(uploader), Some(meeting_id)) =
+            (state.ingest_uploader(), ended_meeting_id.as_ref())
+        {
+            if let Err(e) = uploader.enqueue_meeting(&state.database, meeting_id).await {
+                log::warn!("Failed to queue meeting for ingest: {}", e);
+            }
+        }
+
+        // Summarize finalized episodes (background)
+        if let Some(meeting_id) = ended_meeting_id.clone() {
+            let min_secs = state
+                .settings
+                .get_all()
+                .await
+                .map(|s| s.episode_summary_min_secs)
+                .unwrap_or(30);
+            let db_clone = state.database.clone();
+            let ai_client = state.ai_client.read().clone();
+            tokio::spawn(async move {
+                let stored = crate::meeting_notes::summarize_meeting_episodes(
+                    db_clone,
+                    ai_client,
+                    &meeting_id,
+                    min_secs as i64 * 1000,
+                )
+                .await;
+                log::info!("📝 Summarized {} episodes for {}", stored, meeting_id);
+            });
+        }
+
+        // Replace the timestamp title with an AI-generated one (background)
+        if let Some(meeting_id) = ended_meeting_id {
+            let auto_title = state
+                .settings
+                .get_all()
+                .await
+                .map(|s| s.auto_title_meetings)
+                .unwrap_or(true);
+
+            if auto_title {
+                let topics: Vec<String> = topic_clusters.iter().map(|t| t.name.clone()).collect();
+                let db_clone = state.database.clone();
+                let ai_client = state.ai_client.read().clone();
+                let app_clone = app.clone();
+                tokio::spawn(async move {
+                    auto_title_meeting(app_clone, db_clone, ai_client, meeting_id, topics).await;
+                });
+            }
+        }
+
         // v3.0.0: Obsidian Auto-Export
         if let Ok(settings) = state.settings.get_all().await {
-            if settings.obsidian_auto_export && settings.obsidian_vault_path.is_some() {
+            let auto_export = state.feature_flags.read().obsidian_auto_export;
+            if auto_export && settings.obsidian_vault_path.is_some() {
                 // Determine the meeting ID that just ended
                 let meeting_id = {
                     let timeline = state.timeline_builder.get_events();
@@ -873,12 +1154,27 @@ pub async fn stop_recording(state: State<'_, AppState>) -> Result<(), String> {
                     );
                     let db_clone = state.database.clone();
                     let vm_clone = state.vault_manager.clone();
+                    let template =
+                        active_export_template(&state.prompt_manager, &state.settings).await;
+                    let conflict_policy = vault_conflict_policy(&state.settings).await;
+                    let series_folders = settings.vault_series_folders;
                     tokio::spawn(async move {
+                        // Series meetings go to the series' topic instead of the inbox
+                        let mut topic = None;
+                        if series_folders {
+                            let pool = db_clone.get_pool();
+                            if let Err(e) = crate::meeting_series::detect_series(&pool).await {
+                                log::warn!("Meeting series detection failed: {}", e);
+                            }
+                            topic = crate::meeting_series::series_topic(&pool, &meeting_id).await;
+                        }
                         if let Err(e) = internal_export_meeting(
                             db_clone,
                             vm_clone,
-                            "Inbox".to_string(),
+                            topic.unwrap_or_else(|| "Inbox".to_string()),
                             meeting_id,
+                            template,
+                            conflict_policy,
                         )
                         .await
                         {
@@ -895,17 +1191,276 @@ pub async fn stop_recording(state: State<'_, AppState>) -> Result<(), String> {
     Ok(())
 }
 
+/// Persist a closed document episode with its final stats and category
+async fn save_episode(
+    database: &crate::database::DatabaseManager,
+    episode: &crate::episode_builder::DocumentEpisode,
+) {
+    if let Err(e) = database
+        .create_episode(
+            &episode.episode_id,
+            &episode.meeting_id,
+            episode.start_ts,
+            episode.app_name.as_deref(),
+            episode.window_title.as_deref(),
+        )
+        .await
+    {
+        log::warn!("Failed to create episode: {}", e);
+        return;
+    }
+
+    if let Some(end_ts) = episode.end_ts {
+        if let Err(e) = database
+            .update_episode(
+                &episode.episode_id,
+                end_ts,
+                episode.state_count,
+                episode.duration_ms(),
+                episode.category.as_str(),
+            )
+            .await
+        {
+            log::warn!("Failed to update episode: {}", e);
+        }
+    }
+}
+
+/// Minimum final transcripts before a meeting is worth auto-titling
+const AUTO_TITLE_MIN_TRANSCRIPTS: usize = 20;
+/// How much of the meeting opening is sent to the model
+const AUTO_TITLE_EXCERPT_MINUTES: i64 = 5;
+
+/// Generate a title for a finished meeting and emit `meeting-title-updated`.
+/// Any failure leaves the original timestamp-based title in place.
+async fn auto_title_meeting(
+    app: AppHandle,
+    database: Arc<crate::database::DatabaseManager>,
+    ai_client: AIClient,
+    meeting_id: String,
+    topics: Vec<String>,
+) {
+    let meeting = match database.get_meeting(&meeting_id).await {
+        Ok(Some(m)) => m,
+        Ok(None) => return,
+        Err(e) => {
+            log::warn!("Auto-title: failed to load meeting {}: {}", meeting_id, e);
+            return;
+        }
+    };
+
+    let transcripts: Vec<Transcript> = match database.get_transcripts(&meeting_id).await {
+        Ok(t) => t.into_iter().filter(|t| t.is_final).collect(),
+        Err(e) => {
+            log::warn!("Auto-title: failed to load transcripts: {}", e);
+            return;
+        }
+    };
+
+    if transcripts.len() <= AUTO_TITLE_MIN_TRANSCRIPTS {
+        log::debug!(
+            "Auto-title skipped for {}: only {} final transcripts",
+            meeting_id,
+            transcripts.len()
+        );
+        return;
+    }
+
+    let cutoff = meeting.started_at + chrono::Duration::minutes(AUTO_TITLE_EXCERPT_MINUTES);
+    let opening: Vec<Transcript> = transcripts
+        .into_iter()
+        .filter(|t| t.timestamp <= cutoff)
+        .collect();
+    let excerpt = transcript_context(&opening);
+    if excerpt.trim().is_empty() {
+        return;
+    }
+
+    let generator = crate::meeting_notes::MeetingNotesGenerator::new(ai_client);
+    let title = match generator.generate_title(&excerpt, &topics).await {
+        Ok(t) => t,
+        Err(e) => {
+            log::warn!(
+                "Auto-title failed for {}, keeping '{}': {}",
+                meeting_id,
+                meeting.title,
+                e
+            );
+            return;
+        }
+    };
+
+    if let Err(e) = database.update_meeting_title(&meeting_id, &title).await {
+        log::warn!("Failed to save generated meeting title: {}", e);
+        return;
+    }
+
+    log::info!("🏷️ Meeting {} titled: {}", meeting_id, title);
+    let _ = app.emit(
+        "meeting-title-updated",
+        serde_json::json!({ "meeting_id": meeting_id, "title": title }),
+    );
+}
+
+/// Pause recording without finalizing the meeting.
+/// Capture threads and the state/episode/timeline builders stay alive; only the
+/// audio/frame callbacks and the transcription connection are suspended.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn pause_recording(state: State<'_, AppState>) -> Result<(), String> {
+    {
+        let engine = state.capture_engine.read();
+        engine.suspend_recording()?;
+    }
+
+    state.transcription_manager.stop();
+
+    let now = chrono::Utc::now();
+    if let Some(meeting_id) = state.timeline_builder.current_meeting_id() {
+        state
+            .database
+            .pause_meeting(&meeting_id)
+            .await
+            .map_err(|e| format!("Failed to pause meeting: {}", e))?;
+        state.timeline_builder.pause_meeting(now);
+        log::info!("⏸️ Recording paused: {}", meeting_id);
+    }
+
+    Ok(())
+}
+
+/// Resume a paused recording, reconnecting transcription to the same meeting
+#[tauri::command(rename_all = "camelCase")]
+pub async fn resume_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let meeting_id = state
+        .timeline_builder
+        .current_meeting_id()
+        .ok_or_else(|| "No active meeting to resume".to_string())?;
+
+    {
+        let engine = state.capture_engine.read();
+        engine.resume_recording()?;
+    }
+
+    let paused_secs = state
+        .database
+        .resume_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to resume meeting: {}", e))?;
+    state.timeline_builder.resume_meeting(chrono::Utc::now());
+
+    // Re-apply context in case the provider was switched while paused
+    let tm = &state.transcription_manager;
+    tm.set_context(
+        app,
+        state.database.clone(),
+        meeting_id.clone(),
+        state.live_intel_agent.clone(),
+    );
+    tm.start();
+
+    log::info!(
+        "▶️ Recording resumed: {} (paused {}s)",
+        meeting_id,
+        paused_secs
+    );
+    Ok(())
+}
+
+/// Payload of `recording-state-changed`, emitted when recording is started,
+/// stopped or paused outside the webview (tray, global shortcuts)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RecordingStateChanged {
+    pub is_recording: bool,
+    pub is_paused: bool,
+    pub meeting_id: Option<String>,
+}
+
+fn emit_recording_state(app: &AppHandle, state: &AppState) {
+    let status = state.capture_engine.read().get_status();
+    let payload = RecordingStateChanged {
+        is_recording: status.is_recording,
+        is_paused: status.is_paused,
+        meeting_id: state.timeline_builder.current_meeting_id(),
+    };
+    if let Err(e) = app.emit("recording-state-changed", &payload) {
+        log::error!("Failed to emit recording-state-changed: {}", e);
+    }
+}
+
+fn background_state(app: &AppHandle) -> Result<State<'_, AppState>, String> {
+    app.try_state::<AppState>()
+        .ok_or_else(|| "App is still initializing".to_string())
+}
+
+/// Start recording without the webview: also starts video and links
+/// accessibility captures like the UI does
+pub async fn start_recording_in_background(app: AppHandle) -> Result<String, String> {
+    let state = background_state(&app)?;
+    let meeting_id = start_recording(app.clone(), state.clone()).await?;
+    state
+        .accessibility_capture
+        .set_meeting_id(Some(meeting_id.clone()));
+    if let Err(e) = start_video_recording(meeting_id.clone(), state.clone()).await {
+        log::warn!("Video recording failed to start: {}", e);
+    }
+    emit_recording_state(&app, &state);
+    Ok(meeting_id)
+}
+
+/// Stop recording without the webview (counterpart of
+/// `start_recording_in_background`)
+pub async fn stop_recording_in_background(app: AppHandle) -> Result<(), String> {
+    let state = background_state(&app)?;
+    if let Err(e) = stop_video_recording(state.clone()).await {
+        log::warn!("Video recording failed to stop: {}", e);
+    }
+    state.accessibility_capture.set_meeting_id(None);
+    stop_recording(app.clone(), state.clone()).await?;
+    emit_recording_state(&app, &state);
+    Ok(())
+}
+
+/// Pause a running recording, or resume a paused one, without the webview
+pub async fn toggle_pause_in_background(app: AppHandle) -> Result<(), String> {
+    let state = background_state(&app)?;
+    if state.capture_engine.read().get_status().is_paused {
+        resume_recording(app.clone(), state.clone()).await?;
+    } else {
+        pause_recording(state.clone()).await?;
+    }
+    emit_recording_state(&app, &state);
+    Ok(())
+}
+
 /// Get recording status
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_recording_status(state: State<'_, AppState>) -> Result<RecordingStatus, String> {
-    let engine = state.capture_engine.read();
-    Ok(engine.get_status())
+    let mut status = {
+        let engine = state.capture_engine.read();
+        engine.get_status()
+    };
+
+    let retry_stats = crate::transcription::retry_queue::retry_queue().stats();
+    status.transcripts_pending_retry = retry_stats.pending;
+    status.transcripts_failed = retry_stats.failed;
+
+    status.transcription_health = state.transcription_manager.get_connection_health();
+    status.transcription_degraded =
+        status.is_recording && !status.is_paused && status.transcription_health.is_degraded();
+
+    Ok(status)
 }
 
-/// Capture a single screenshot (for preview)
+/// Capture a single screenshot (for preview); `window_id` previews one window
 #[tauri::command(rename_all = "camelCase")]
-pub async fn capture_screenshot(monitor_id: Option<u32>) -> Result<String, String> {
-    let image = crate::capture_engine::CaptureEngine::capture_screenshot(monitor_id)?;
+pub async fn capture_screenshot(
+    monitor_id: Option<u32>,
+    window_id: Option<u32>,
+) -> Result<String, String> {
+    let image = match window_id {
+        Some(id) => crate::capture_engine::CaptureEngine::capture_window_screenshot(id)?,
+        None => crate::capture_engine::CaptureEngine::capture_screenshot(monitor_id)?,
+    };
 
     // Convert to base64 JPEG for frontend display
     let mut buffer = Vec::new();
@@ -932,23 +1487,203 @@ pub async fn get_transcripts(
         .map_err(|e| format!("Failed to get transcripts: {}", e))
 }
 
-/// Search transcripts across all meetings
+/// Record a transcript correction in the audit log
+async fn audit_transcript_edit(
+    state: &State<'_, AppState>,
+    target_type: &str,
+    target_id: &str,
+    details: serde_json::Value,
+) {
+    let audit = crate::audit_log::AuditLog::new(state.database.get_pool().as_ref().clone());
+    if let Err(e) = audit
+        .log_action(crate::audit_log::AuditAction {
+            action: "edit".to_string(),
+            target_type: target_type.to_string(),
+            target_id: target_id.to_string(),
+            details: Some(details.to_string()),
+            bytes_affected: 0,
+        })
+        .await
+    {
+        log::warn!("Failed to audit transcript edit: {}", e);
+    }
+}
+
+/// Correct the text of a single transcript segment
 #[tauri::command(rename_all = "camelCase")]
-pub async fn search_transcripts(
-    query: String,
+pub async fn update_transcript_text(
+    transcript_id: i64,
+    new_text: String,
     state: State<'_, AppState>,
-) -> Result<Vec<SearchResult>, String> {
-    if query.trim().is_empty() {
-        return Ok(vec![]);
+) -> Result<Option<TranscriptRevision>, String> {
+    let new_text = new_text.trim();
+    if new_text.is_empty() {
+        return Err("Transcript text cannot be empty".to_string());
     }
-    state
+
+    let revision = state
         .database
-        .search_transcripts(&query)
+        .update_transcript_text(transcript_id, new_text)
         .await
-        .map_err(|e| format!("Failed to search: {}", e))
-}
+        .map_err(|e| format!("Failed to update transcript: {}", e))?;
 
-/// Get frames for a meeting (rewind timeline)
+    if let Some(rev) = &revision {
+        audit_transcript_edit(
+            &state,
+            "transcript",
+            &transcript_id.to_string(),
+            serde_json::json!({
+                "meeting_id": rev.meeting_id,
+                "revision_id": rev.id,
+                "previous_text": rev.previous_text,
+                "new_text": rev.new_text,
+            }),
+        )
+        .await;
+    }
+
+    Ok(revision)
+}
+
+/// Find/replace across every transcript segment in a meeting
+#[tauri::command(rename_all = "camelCase")]
+pub async fn bulk_replace_in_meeting(
+    meeting_id: String,
+    find: String,
+    replace: String,
+    case_sensitive: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<TranscriptRevision>, String> {
+    if find.is_empty() {
+        return Err("Search text cannot be empty".to_string());
+    }
+
+    let revisions = state
+        .database
+        .bulk_replace_in_meeting(&meeting_id, &find, &replace, case_sensitive)
+        .await
+        .map_err(|e| format!("Failed to replace in transcripts: {}", e))?;
+
+    if !revisions.is_empty() {
+        audit_transcript_edit(
+            &state,
+            "meeting",
+            &meeting_id,
+            serde_json::json!({
+                "find": find,
+                "replace": replace,
+                "case_sensitive": case_sensitive,
+                "transcripts_changed": revisions.len(),
+                "revision_ids": revisions.iter().map(|r| r.id).collect::<Vec<_>>(),
+            }),
+        )
+        .await;
+    }
+
+    log::info!(
+        "✏️ Replaced '{}' in {} transcripts of meeting {}",
+        find,
+        revisions.len(),
+        meeting_id
+    );
+    Ok(revisions)
+}
+
+/// Edit history for a transcript segment (newest first)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_transcript_revisions(
+    transcript_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<TranscriptRevision>, String> {
+    state
+        .database
+        .get_transcript_revisions(transcript_id)
+        .await
+        .map_err(|e| format!("Failed to get transcript revisions: {}", e))
+}
+
+/// Low-confidence transcripts waiting for review in a meeting
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_transcripts_needing_review(
+    meeting_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<Transcript>, String> {
+    state
+        .database
+        .get_transcripts_needing_review(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get transcripts needing review: {}", e))
+}
+
+/// Remove a transcript from the review queue
+#[tauri::command(rename_all = "camelCase")]
+pub async fn mark_transcript_reviewed(
+    transcript_id: i64,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    state
+        .database
+        .mark_transcript_reviewed(transcript_id)
+        .await
+        .map_err(|e| format!("Failed to mark transcript reviewed: {}", e))
+}
+
+/// Set the confidence threshold below which final transcripts are flagged
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_low_confidence_threshold(
+    threshold: f32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err("Threshold must be between 0.0 and 1.0".to_string());
+    }
+
+    state
+        .settings
+        .set_low_confidence_threshold(threshold)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+
+    crate::transcription::review::low_confidence_review().set_threshold(threshold);
+    Ok(())
+}
+
+/// Search transcripts across all meetings
+#[tauri::command(rename_all = "camelCase")]
+pub async fn search_transcripts(
+    query: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<SearchResult>, String> {
+    if query.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    state
+        .database
+        .search_transcripts(&query)
+        .await
+        .map_err(|e| format!("Failed to search: {}", e))
+}
+
+/// Search transcripts, text snapshots and frame OCR text in one ranked list
+#[tauri::command(rename_all = "camelCase")]
+pub async fn search_everything(
+    query: String,
+    sources: Option<Vec<crate::database::SearchSource>>,
+    limit: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::database::UnifiedSearchResult>, String> {
+    if query.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    let sources = sources.unwrap_or_else(|| crate::database::SearchSource::ALL.to_vec());
+    state
+        .database
+        .search_everything(&query, &sources, limit.unwrap_or(50).clamp(1, 500))
+        .await
+        .map_err(|e| format!("Failed to search: {}", e))
+}
+
+/// Get frames for a meeting (rewind timeline)
 
 #[tauri::command(rename_all = "camelCase")]
 pub async fn debug_log(message: String) {
@@ -1098,16 +1833,132 @@ pub async fn set_monitor(monitor_id: u32, state: State<'_, AppState>) -> Result<
     Ok(())
 }
 
+/// Capture every display during meetings (persisted, applies immediately)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_capture_all_monitors(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    {
+        let engine = state.capture_engine.read();
+        engine.set_capture_all_monitors(enabled);
+    }
+
+    state
+        .settings
+        .set_capture_all_monitors(enabled)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+
+    log::info!("Capture all monitors: {}", enabled);
+    Ok(())
+}
+
+/// Get windows that can be captured on their own
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_capturable_windows() -> Result<Vec<crate::capture_engine::CapturableWindow>, String>
+{
+    crate::capture_engine::CaptureEngine::list_windows()
+}
+
+/// Capture only one window instead of the monitor (None = monitor capture)
+/// Window ids don't survive app restarts, so this isn't persisted
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_capture_window(
+    window_id: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(id) = window_id {
+        let known = crate::capture_engine::CaptureEngine::list_windows()?
+            .iter()
+            .any(|w| w.id == id);
+        if !known {
+            return Err(format!("Window {} not found", id));
+        }
+    }
+
+    let engine = state.capture_engine.read();
+    engine.set_capture_window(window_id);
+    log::info!("Capture window set to: {:?}", window_id);
+    Ok(())
+}
+
+/// With `validate`, check a key with its provider before it is saved. A
+/// rejected key is an error; an unreachable provider only logs, so keys can
+/// still be entered offline.
+async fn validate_before_save(
+    provider: ProviderType,
+    key: &str,
+    validate: Option<bool>,
+) -> Result<Option<KeyValidation>, String> {
+    if !validate.unwrap_or(false) {
+        return Ok(None);
+    }
+    let validation = key_validation::validate_provider_key(provider, key).await;
+    let message = validation.message.clone().unwrap_or_default();
+    match validation.status {
+        KeyValidationStatus::Ok => {}
+        KeyValidationStatus::Invalid => {
+            return Err(format!("{} key not saved: {}", provider.as_str(), message))
+        }
+        KeyValidationStatus::NetworkError => log::warn!(
+            "Could not validate {} key, saving anyway: {}",
+            provider.as_str(),
+            message
+        ),
+    }
+    Ok(Some(validation))
+}
+
+/// Check a provider key with one authenticated request. Without `key` (or
+/// with a masked one) the stored key is checked and the result recorded for
+/// the recording fallback.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn validate_provider_key(
+    provider: String,
+    key: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<KeyValidation, String> {
+    let provider_type = ProviderType::parse(&provider)
+        .ok_or_else(|| format!("Unknown transcription provider: {}", provider))?;
+    let tm = &state.transcription_manager;
+    let stored = tm.get_api_key_for_provider(provider_type);
+
+    let candidate = key.filter(|k| !crate::secret_store::is_masked(k));
+    let key = match candidate.as_deref().or(stored.as_deref()) {
+        Some(key) => key_validation::normalize_key(key),
+        None if !provider_type.requires_api_key() => String::new(),
+        None => return Err(format!("No {} key is configured", provider)),
+    };
+
+    let validation = key_validation::validate_provider_key(provider_type, &key).await;
+    if stored.as_deref().map(key_validation::normalize_key) == Some(key)
+        || !provider_type.requires_api_key()
+    {
+        tm.record_key_validation(validation.clone());
+    }
+    Ok(validation)
+}
+
 /// Set the Deepgram API key (persisted)
 #[tauri::command(rename_all = "camelCase")]
 pub async fn set_deepgram_api_key(
     api_key: String,
+    validate: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    let api_key = key_validation::normalize_key(&api_key);
+    let validation = validate_before_save(ProviderType::Deepgram, &api_key, validate).await?;
+
     // Store on the per-provider key map (survives provider switches)
     state
         .transcription_manager
         .set_api_key_for_provider(ProviderType::Deepgram, api_key.clone());
+    if let Some(validation) = validation {
+        state
+            .transcription_manager
+            .record_key_validation(validation);
+    }
 
     state
         .settings
@@ -1130,6 +1981,30 @@ pub async fn set_deepgram_model(model: String, state: State<'_, AppState>) -> Re
     Ok(())
 }
 
+/// Configure the local Whisper model (explicit path and/or model size)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_whisper_model(
+    model_path: Option<String>,
+    model_size: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(path) = model_path {
+        state
+            .settings
+            .set_whisper_model_path(path.trim())
+            .await
+            .map_err(|e| format!("Failed to save settings: {}", e))?;
+    }
+    if let Some(size) = model_size {
+        state
+            .settings
+            .set_whisper_model_size(size.trim())
+            .await
+            .map_err(|e| format!("Failed to save settings: {}", e))?;
+    }
+    Ok(())
+}
+
 /// Get the Deepgram Model
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_deepgram_model(state: State<'_, AppState>) -> Result<Option<String>, String> {
@@ -1142,11 +2017,23 @@ pub async fn get_deepgram_model(state: State<'_, AppState>) -> Result<Option<Str
 
 /// Set the Gemini API key
 #[tauri::command(rename_all = "camelCase")]
-pub async fn set_gemini_api_key(api_key: String, state: State<'_, AppState>) -> Result<(), String> {
+pub async fn set_gemini_api_key(
+    api_key: String,
+    validate: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let api_key = key_validation::normalize_key(&api_key);
+    let validation = validate_before_save(ProviderType::Gemini, &api_key, validate).await?;
+
     // Store on the per-provider key map (survives provider switches)
     state
         .transcription_manager
         .set_api_key_for_provider(ProviderType::Gemini, api_key.clone());
+    if let Some(validation) = validation {
+        state
+            .transcription_manager
+            .record_key_validation(validation);
+    }
 
     state
         .settings
@@ -1163,17 +2050,9 @@ pub async fn set_gemini_api_key(api_key: String, state: State<'_, AppState>) ->
 pub async fn get_gemini_api_key(state: State<'_, AppState>) -> Result<Option<String>, String> {
     state
         .settings
-        .get("gemini_api_key")
+        .get_gemini_api_key()
         .await
-        .map(|opt| {
-            opt.map(|key| {
-                if key.len() > 4 {
-                    format!("****{}", &key[key.len() - 4..])
-                } else {
-                    "****".to_string()
-                }
-            })
-        })
+        .map(|opt| opt.as_deref().map(crate::secret_store::mask))
         .map_err(|e| format!("Failed to get settings: {}", e))
 }
 
@@ -1200,11 +2079,23 @@ pub async fn get_gemini_model(state: State<'_, AppState>) -> Result<Option<Strin
 
 /// Set the Gladia API key
 #[tauri::command(rename_all = "camelCase")]
-pub async fn set_gladia_api_key(api_key: String, state: State<'_, AppState>) -> Result<(), String> {
+pub async fn set_gladia_api_key(
+    api_key: String,
+    validate: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let api_key = key_validation::normalize_key(&api_key);
+    let validation = validate_before_save(ProviderType::Gladia, &api_key, validate).await?;
+
     // Store on the per-provider key map (survives provider switches)
     state
         .transcription_manager
         .set_api_key_for_provider(ProviderType::Gladia, api_key.clone());
+    if let Some(validation) = validation {
+        state
+            .transcription_manager
+            .record_key_validation(validation);
+    }
 
     state
         .settings
@@ -1220,12 +2111,21 @@ pub async fn set_gladia_api_key(api_key: String, state: State<'_, AppState>) ->
 #[tauri::command(rename_all = "camelCase")]
 pub async fn set_google_stt_key(
     key_json: String,
+    validate: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    let key_json = key_validation::normalize_key(&key_json);
+    let validation = validate_before_save(ProviderType::GoogleSTT, &key_json, validate).await?;
+
     // Store on the per-provider key map (survives provider switches)
     state
         .transcription_manager
         .set_api_key_for_provider(ProviderType::GoogleSTT, key_json.clone());
+    if let Some(validation) = validation {
+        state
+            .transcription_manager
+            .record_key_validation(validation);
+    }
 
     state
         .settings
@@ -1250,6 +2150,7 @@ pub async fn set_active_provider(
         "gemini" => ProviderType::Gemini,
         "gladia" => ProviderType::Gladia,
         "google_stt" => ProviderType::GoogleSTT,
+        "whisper_local" => ProviderType::WhisperLocal,
         _ => return Err("Invalid provider".to_string()),
     };
 
@@ -1265,6 +2166,122 @@ pub async fn set_active_provider(
     Ok(())
 }
 
+/// Get the policy for switching provider when the active one dies mid-meeting
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_transcription_failover(
+    state: State<'_, AppState>,
+) -> Result<crate::transcription::failover::FailoverPolicy, String> {
+    Ok(state.transcription_manager.get_failover_policy())
+}
+
+/// Save the failover policy; applies to the current recording immediately
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_transcription_failover(
+    policy: crate::transcription::failover::FailoverPolicy,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    policy.validate()?;
+
+    state
+        .settings
+        .set_transcription_failover(&policy)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+
+    state.transcription_manager.set_failover_policy(policy);
+    Ok(())
+}
+
+/// Connection state and last transcript time per provider used this run
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_transcription_health(
+) -> Result<Vec<crate::transcription::failover::ProviderHealthSnapshot>, String> {
+    Ok(crate::transcription::failover::provider_health().snapshot())
+}
+
+/// Get the transcription glossary (terms boosted in every provider)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_glossary(state: State<'_, AppState>) -> Result<Vec<GlossaryTerm>, String> {
+    Ok(state.transcription_manager.get_glossary())
+}
+
+/// Replace the transcription glossary; takes effect on the next recording
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_glossary(
+    terms: Vec<GlossaryTerm>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GlossaryTerm>, String> {
+    let terms = crate::transcription::glossary::normalize_terms(terms);
+
+    state
+        .settings
+        .set_transcription_glossary(&terms)
+        .await
+        .map_err(|e| format!("Failed to save glossary: {}", e))?;
+
+    state.transcription_manager.set_glossary(terms.clone());
+
+    log::info!("Transcription glossary updated ({} terms)", terms.len());
+    Ok(terms)
+}
+
+/// Get the user's capture exclusions (bundle ids and window-title patterns)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_privacy_rules() -> Result<PrivacyRules, String> {
+    Ok(crate::privacy_filter::get_rules())
+}
+
+/// Replace the capture exclusions; applies to the next captured frame
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_privacy_rules(
+    rules: PrivacyRules,
+    state: State<'_, AppState>,
+) -> Result<PrivacyRules, String> {
+    let clean = |list: Vec<String>| -> Vec<String> {
+        list.into_iter()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    };
+    let rules = PrivacyRules {
+        bundle_ids: clean(rules.bundle_ids),
+        title_patterns: clean(rules.title_patterns),
+    };
+
+    // Rejects invalid regexes before anything is saved
+    crate::privacy_filter::set_rules(rules.clone())?;
+
+    state
+        .settings
+        .set_privacy_rules(&rules)
+        .await
+        .map_err(|e| format!("Failed to save privacy rules: {}", e))?;
+
+    log::info!(
+        "Privacy rules updated ({} apps, {} title patterns)",
+        rules.bundle_ids.len(),
+        rules.title_patterns.len()
+    );
+    Ok(rules)
+}
+
+/// Set how PII is handled in cloud sync: "off", "mask" or "drop_segment"
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_redaction_mode(mode: String, state: State<'_, AppState>) -> Result<(), String> {
+    let parsed = crate::privacy_filter::RedactionMode::parse(&mode)
+        .ok_or_else(|| format!("Unknown redaction mode: {}", mode))?;
+
+    state
+        .settings
+        .set_redaction_mode(parsed.as_str())
+        .await
+        .map_err(|e| format!("Failed to save redaction mode: {}", e))?;
+
+    crate::privacy_filter::set_redaction_mode(parsed);
+    log::info!("PII redaction mode: {}", parsed.as_str());
+    Ok(())
+}
+
 /// Get the Deepgram API key (masked for display)
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_deepgram_api_key(state: State<'_, AppState>) -> Result<Option<String>, String> {
@@ -1274,105 +2291,296 @@ pub async fn get_deepgram_api_key(state: State<'_, AppState>) -> Result<Option<S
         .await
         .map_err(|e| format!("Failed to get API key: {}", e))?;
 
-    Ok(key.map(|k| {
-        if k.len() > 8 {
-            format!("{}...{}", &k[..4], &k[k.len() - 4..])
-        } else {
-            "****".to_string()
-        }
-    }))
+    Ok(key.as_deref().map(crate::secret_store::mask))
 }
 
-/// Get all settings
+/// Get all settings, with secrets cleared (see `get_secret_status`)
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_settings(state: State<'_, AppState>) -> Result<AppSettings, String> {
     state
         .settings
         .get_all()
         .await
+        .map(AppSettings::redacted)
         .map_err(|e| format!("Failed to get settings: {}", e))
 }
 
-/// Get a single setting value
+/// Get a single setting value (secrets come back masked)
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_setting(
     key: String,
     state: State<'_, AppState>,
 ) -> Result<Option<String>, String> {
-    state
+    let value = state
         .settings
         .get(&key)
         .await
-        .map_err(|e| format!("Failed to get setting: {}", e))
+        .map_err(|e| format!("Failed to get setting: {}", e))?;
+    if crate::secret_store::is_secret(&key) {
+        return Ok(value.as_deref().map(crate::secret_store::mask));
+    }
+    Ok(value)
 }
 
-/// Get all meetings
+/// Whether each API key/token is set, its masked tail and when it last changed
 #[tauri::command(rename_all = "camelCase")]
-pub async fn get_meetings(
-    limit: Option<i32>,
+pub async fn get_secret_status(
     state: State<'_, AppState>,
-) -> Result<Vec<Meeting>, String> {
-    let limit = limit.unwrap_or(50);
+) -> Result<Vec<crate::secret_store::SecretStatus>, String> {
     state
-        .database
-        .list_meetings(limit)
+        .settings
+        .secret_status()
         .await
-        .map_err(|e| format!("Failed to list meetings: {}", e))
+        .map_err(|e| format!("Failed to get secret status: {}", e))
 }
 
-/// Get a single meeting
+/// Rename a diarized speaker for a single meeting
 #[tauri::command(rename_all = "camelCase")]
-pub async fn get_meeting(
+pub async fn rename_speaker(
     meeting_id: String,
+    speaker_label: String,
+    display_name: String,
     state: State<'_, AppState>,
-) -> Result<Option<Meeting>, String> {
+) -> Result<(), String> {
+    let display_name = display_name.trim();
+    if display_name.is_empty() {
+        return Err("Display name cannot be empty".to_string());
+    }
+
     state
         .database
-        .get_meeting(&meeting_id)
+        .rename_speaker(&meeting_id, &speaker_label, display_name)
         .await
-        .map_err(|e| format!("Failed to get meeting: {}", e))
+        .map_err(|e| format!("Failed to rename speaker: {}", e))?;
+
+    // Cached catch-up text still uses the old name
+    state.catch_up_cache.invalidate(&meeting_id);
+    Ok(())
+}
+
+/// List the speakers detected in a meeting and their display names
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_meeting_speakers(
+    meeting_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<MeetingSpeaker>, String> {
+    state
+        .database
+        .get_meeting_speakers(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get speakers: {}", e))
+}
+
+/// Get all meetings
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_meetings(
+    limit: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Meeting>, String> {
+    let limit = limit.unwrap_or(50);
+    state
+        .database
+        .list_meetings(limit)
+        .await
+        .map_err(|e| format!("Failed to list meetings: {}", e))
+}
+
+/// Paginated, filtered meetings list with per-meeting transcript/frame counts
+#[tauri::command(rename_all = "camelCase")]
+pub async fn query_meetings(
+    offset: Option<i64>,
+    limit: Option<i64>,
+    filter: Option<crate::database::MeetingFilter>,
+    state: State<'_, AppState>,
+) -> Result<crate::database::MeetingPage, String> {
+    let offset = offset.unwrap_or(0).max(0);
+    let limit = limit.unwrap_or(50).clamp(1, 500);
+    state
+        .database
+        .list_meetings_filtered(offset, limit, &filter.unwrap_or_default())
+        .await
+        .map_err(|e| format!("Failed to query meetings: {}", e))
+}
+
+/// Get a single meeting
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_meeting(
+    meeting_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<Meeting>, String> {
+    state
+        .database
+        .get_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))
 }
 
 /// Delete a meeting
 #[tauri::command(rename_all = "camelCase")]
 pub async fn delete_meeting(meeting_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_deletable(&state, &meeting_id).await?;
+
     state
         .database
         .delete_meeting(&meeting_id)
         .await
         .map_err(|e| format!("Failed to delete meeting: {}", e))?;
+    delete_meeting_vectors(&state, &meeting_id).await;
 
     log::info!("Meeting deleted: {}", meeting_id);
     Ok(())
 }
 
+/// Remove a deleted meeting's vectors from Pinecone and the local store.
+/// Failures are logged, not returned: the meeting itself is already gone.
+pub(crate) async fn delete_meeting_vectors(state: &AppState, meeting_id: &str) {
+    if let Err(e) = state
+        .database
+        .delete_local_vectors_for_meeting(meeting_id)
+        .await
+    {
+        log::warn!("Failed to delete local vectors for {}: {}", meeting_id, e);
+    }
+
+    let pinecone_config = state.pinecone_client.read().get_config();
+    if let Some(config) = pinecone_config {
+        match crate::pinecone_client::pinecone_delete_meeting(&config, meeting_id).await {
+            Ok(()) => log::info!("🗑️ Deleted Pinecone vectors for meeting {}", meeting_id),
+            Err(e) => log::warn!(
+                "Failed to delete Pinecone vectors for {}: {}",
+                meeting_id,
+                e
+            ),
+        }
+    }
+}
+
+/// Enforce the soft-delete-first policy when it's enabled in settings
+pub(crate) async fn ensure_deletable(
+    state: &State<'_, AppState>,
+    meeting_id: &str,
+) -> Result<(), String> {
+    let require_archive = state
+        .settings
+        .get_all()
+        .await
+        .map(|s| s.require_archive_before_delete)
+        .unwrap_or(false);
+
+    if require_archive
+        && !state
+            .database
+            .is_meeting_archived(meeting_id)
+            .await
+            .map_err(|e| format!("Failed to check meeting: {}", e))?
+    {
+        return Err(format!(
+            "Meeting {} must be archived before it can be deleted",
+            meeting_id
+        ));
+    }
+
+    Ok(())
+}
+
+/// Archive a meeting (hidden from lists, search and indexing, but restorable)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn archive_meeting(meeting_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let found = state
+        .database
+        .archive_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to archive meeting: {}", e))?;
+
+    if !found {
+        return Err(format!("Meeting not found: {}", meeting_id));
+    }
+
+    log::info!("Meeting archived: {}", meeting_id);
+    Ok(())
+}
+
+/// Restore an archived meeting
+#[tauri::command(rename_all = "camelCase")]
+pub async fn unarchive_meeting(
+    meeting_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let found = state
+        .database
+        .unarchive_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to restore meeting: {}", e))?;
+
+    if !found {
+        return Err(format!("Meeting not found: {}", meeting_id));
+    }
+
+    log::info!("Meeting restored: {}", meeting_id);
+    Ok(())
+}
+
+/// List archived meetings
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_archived_meetings(
+    limit: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Meeting>, String> {
+    state
+        .database
+        .list_archived_meetings(limit.unwrap_or(50))
+        .await
+        .map_err(|e| format!("Failed to list archived meetings: {}", e))
+}
+
 /// Get synced timeline for rewind (frames + transcripts aligned by timestamp)
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_synced_timeline(
     meeting_id: String,
+    monitor_id: Option<u32>,
     state: State<'_, AppState>,
 ) -> Result<Option<SyncedTimeline>, String> {
     log::info!("📊 get_synced_timeline called for meeting: {}", meeting_id);
     state
         .database
-        .get_synced_timeline(&meeting_id)
+        .get_synced_timeline(&meeting_id, monitor_id)
         .await
         .map_err(|e| format!("Failed to get synced timeline: {}", e))
 }
 
-/// Get timeline events for a meeting (Phase 3)
+/// Get timeline events for a meeting (Phase 3). `event_types` (e.g.
+/// ["decision", "action_item"]) and `min_importance` thin out dense meetings.
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_timeline_events(
     meeting_id: String,
+    category: Option<String>,
+    event_types: Option<Vec<String>>,
+    min_importance: Option<f32>,
     state: State<'_, AppState>,
 ) -> Result<Vec<crate::database::TimelineEventRecord>, String> {
     state
         .database
-        .get_timeline_events(&meeting_id)
+        .get_timeline_events(
+            &meeting_id,
+            category.as_deref(),
+            &event_types.unwrap_or_default(),
+            min_importance,
+        )
         .await
         .map_err(|e| format!("Failed to get timeline events: {}", e))
 }
 
+/// The meeting's top timeline events by importance, in meeting order, each
+/// with the reason it scored high
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_meeting_highlights(
+    meeting_id: String,
+    max_events: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::database::TimelineEventRecord>, String> {
+    crate::importance::meeting_highlights(&state.database, &meeting_id, max_events).await
+}
+
 /// Get topic clusters for a meeting (Phase 3)
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_topic_clusters(
@@ -1395,13 +2603,13 @@ use crate::meeting_intel::{CalendarEvent, MeetingState, MeetingStateResolver};
 // AI Commands (Ollama Integration)
 // ============================================
 
-use crate::ai_client::{AIClient, AIPreset, ChatMessage, OllamaModel};
+use crate::ai_client::{AIClient, AIPreset, AIProviderStatus, ChatMessage, OllamaModel};
 
-/// Check if Ollama is available
+/// Check which AI backend is active, whether it is reachable, and its models
 #[tauri::command(rename_all = "camelCase")]
-pub async fn check_ollama() -> Result<bool, String> {
+pub async fn check_ai_provider() -> Result<AIProviderStatus, String> {
     let client = AIClient::new();
-    Ok(client.is_available().await)
+    Ok(client.check_provider().await)
 }
 
 /// Get available Ollama models
@@ -1426,48 +2634,101 @@ pub async fn ai_chat(
     state: State<'_, AppState>,
 ) -> Result<String, String> {
     let client = AIClient::new();
+    let preset = chat_preset(&preset_id);
+    let context = meeting_chat_context(&state, meeting_id.as_deref()).await?;
 
-    // Get the preset
-    let presets = AIPreset::get_all_presets();
-    let preset = presets
-        .iter()
-        .find(|p| p.id == preset_id)
-        .cloned()
-        .unwrap_or_else(AIPreset::qa);
+    let messages = vec![ChatMessage {
+        role: "user".to_string(),
+        content: message,
+    }];
 
-    // Build context from meeting if provided
-    let context = if let Some(ref id) = meeting_id {
-        let transcripts = state
-            .database
-            .get_transcripts(id)
-            .await
-            .map_err(|e| format!("Failed to get transcripts: {}", e))?;
+    client.chat(&preset, messages, context.as_deref()).await
+}
 
-        let transcript_text: String = transcripts
-            .iter()
-            .filter(|t| t.is_final)
-            .map(|t| {
-                format!(
-                    "[{}] {}: {}",
-                    t.timestamp.format("%H:%M:%S"),
-                    t.speaker.as_deref().unwrap_or("Speaker"),
-                    t.text
-                )
-            })
-            .collect::<Vec<_>>()
-            .join("\n");
+/// Preset by id, falling back to Q&A
+fn chat_preset(preset_id: &str) -> AIPreset {
+    AIPreset::get_all_presets()
+        .into_iter()
+        .find(|p| p.id == preset_id)
+        .unwrap_or_else(AIPreset::qa)
+}
 
-        Some(transcript_text)
-    } else {
-        None
+/// Timestamped final transcript of a meeting for `ai_chat` context
+async fn meeting_chat_context(
+    state: &AppState,
+    meeting_id: Option<&str>,
+) -> Result<Option<String>, String> {
+    let Some(id) = meeting_id else {
+        return Ok(None);
     };
+    let transcripts = state
+        .database
+        .get_transcripts(id)
+        .await
+        .map_err(|e| format!("Failed to get transcripts: {}", e))?;
+
+    let transcript_text: String = transcripts
+        .iter()
+        .filter(|t| t.is_final)
+        .map(|t| {
+            format!(
+                "[{}] {}: {}",
+                t.timestamp.format("%H:%M:%S"),
+                t.speaker.as_deref().unwrap_or("Speaker"),
+                t.text
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok(Some(transcript_text))
+}
 
+/// Streaming `ai_chat`: returns a request id at once and delivers the answer
+/// as `ai-stream` events
+#[tauri::command(rename_all = "camelCase")]
+pub async fn ai_chat_stream(
+    preset_id: String,
+    message: String,
+    meeting_id: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let preset = chat_preset(&preset_id);
+    let context = meeting_chat_context(&state, meeting_id.as_deref()).await?;
     let messages = vec![ChatMessage {
         role: "user".to_string(),
         content: message,
     }];
 
-    client.chat(&preset, messages, context.as_deref()).await
+    Ok(crate::ai_stream::spawn_stream(app, |sink| async move {
+        AIClient::new()
+            .chat_stream(&preset, messages, context.as_deref(), &mut |delta| {
+                sink.delta(delta)
+            })
+            .await
+            .map(|_| ())
+    }))
+}
+
+/// Stop a streaming AI response. Returns false if it already finished.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn cancel_ai_stream(request_id: String, app: AppHandle) -> Result<bool, String> {
+    Ok(crate::ai_stream::cancel(&app, &request_id))
+}
+
+/// Flatten final transcripts into AI context, prefixing each line with the
+/// speaker's display name so renamed speakers show up as real people
+fn transcript_context(transcripts: &[Transcript]) -> String {
+    transcripts
+        .iter()
+        .filter(|t| t.is_final)
+        .map(|t| match t.speaker.as_deref() {
+            Some(speaker) => format!("{}: {}", speaker, t.text),
+            None => t.text.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 /// Summarize a meeting
@@ -1489,22 +2750,44 @@ pub async fn summarize_meeting(
         return Err("No transcripts found for this meeting".to_string());
     }
 
-    let content: String = transcripts
-        .iter()
-        .filter(|t| t.is_final)
-        .map(|t| t.text.clone())
-        .collect::<Vec<_>>()
-        .join(" ");
+    let content = transcript_context(&transcripts);
 
     client.summarize(&content).await
 }
 
-/// Extract action items from a meeting
+/// Streaming `summarize_meeting`, delivered as `ai-stream` events
 #[tauri::command(rename_all = "camelCase")]
-pub async fn extract_action_items(
+pub async fn summarize_meeting_stream(
     meeting_id: String,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
+    let transcripts = state
+        .database
+        .get_transcripts(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get transcripts: {}", e))?;
+
+    if transcripts.is_empty() {
+        return Err("No transcripts found for this meeting".to_string());
+    }
+
+    let content = transcript_context(&transcripts);
+
+    Ok(crate::ai_stream::spawn_stream(app, |sink| async move {
+        AIClient::new()
+            .summarize_stream(&content, &mut |delta| sink.delta(delta))
+            .await
+            .map(|_| ())
+    }))
+}
+
+/// Extract action items from a meeting into tracked records
+#[tauri::command(rename_all = "camelCase")]
+pub async fn extract_action_items(
+    meeting_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::database::ActionItemRecord>, String> {
     let client = AIClient::new();
 
     // Get transcripts
@@ -1518,72 +2801,395 @@ pub async fn extract_action_items(
         return Err("No transcripts found for this meeting".to_string());
     }
 
-    let content: String = transcripts
-        .iter()
-        .filter(|t| t.is_final)
-        .map(|t| t.text.clone())
-        .collect::<Vec<_>>()
-        .join(" ");
-
-    client.extract_action_items(&content).await
-}
+    let content = transcript_context(&transcripts);
 
-// ============================================
-// Knowledge Base Commands (VLM, Supabase, Pinecone)
-// ============================================
+    let response = client.extract_action_items(&content).await?;
+    let parsed = crate::action_items::parse_action_items(&response);
+    if parsed.is_empty() {
+        log::warn!("No action items parsed from AI response for {}", meeting_id);
+    }
 
-use crate::pinecone_client::{ActivityMetadata, VectorMatch};
-use crate::supabase_client::Activity;
-use crate::vlm_client::ActivityContext;
+    for item in &parsed {
+        let normalized = crate::action_items::normalize_text(&item.text);
+        let inserted = state
+            .database
+            .upsert_action_item(
+                &meeting_id,
+                &item.text,
+                &normalized,
+                item.assignee.as_deref(),
+                item.due_hint.as_deref(),
+                crate::action_items::SOURCE_EXTRACTED,
+                None,
+            )
+            .await
+            .map_err(|e| format!("Failed to save action item: {}", e))?;
+        if inserted {
+            crate::integrations::emit_action_item_created(
+                &state.database,
+                &meeting_id,
+                &normalized,
+            )
+            .await;
+        }
+    }
 
-/// Check if VLM API is available
-#[tauri::command(rename_all = "camelCase")]
-pub async fn check_vlm(_state: State<'_, AppState>) -> Result<bool, String> {
-    Ok(crate::vlm_client::vlm_is_available().await)
+    state
+        .database
+        .list_action_items(Some(&meeting_id))
+        .await
+        .map_err(|e| format!("Failed to list action items: {}", e))
 }
 
-/// Check if VLM has vision model
+/// Action items for a meeting, or every open item across meetings
 #[tauri::command(rename_all = "camelCase")]
-pub async fn check_vlm_vision(_state: State<'_, AppState>) -> Result<bool, String> {
-    crate::vlm_client::vlm_has_vision_model().await
+pub async fn list_action_items(
+    meeting_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::database::ActionItemRecord>, String> {
+    state
+        .database
+        .list_action_items(meeting_id.as_deref())
+        .await
+        .map_err(|e| format!("Failed to list action items: {}", e))
 }
 
-/// Analyze a frame with VLM
+/// Move an action item to open / in_progress / done / cancelled
 #[tauri::command(rename_all = "camelCase")]
-pub async fn analyze_frame(
-    frame_path: String,
-    _state: State<'_, AppState>,
-) -> Result<ActivityContext, String> {
-    // Use default prompt for manual analysis
-    let prompt = r#"Analyze this screenshot and describe what the user is doing. 
-Respond in JSON format with these fields:
-{
-  "app_name": "name of the main application visible",
-  "window_title": "title of the window or document",
-  "category": "one of: development, communication, research, writing, design, media, browsing, system, other",
-  "summary": "brief description of what the user is doing",
-  "focus_area": "specific task or project",
-  "visible_files": [],
-  "confidence": 0.8
-}
-Only respond with valid JSON."#;
+pub async fn update_action_item_status(
+    id: String,
+    status: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let status = crate::action_items::ActionItemStatus::parse(&status)
+        .ok_or_else(|| format!("Unknown action item status: {}", status))?;
 
-    crate::vlm_client::vlm_analyze_frame(&frame_path, prompt).await
+    let updated = state
+        .database
+        .update_action_item_status(&id, status.as_str())
+        .await
+        .map_err(|e| format!("Failed to update action item: {}", e))?;
+
+    if !updated {
+        return Err(format!("Action item {} not found", id));
+    }
+    Ok(())
+}
+
+/// Add an action item by hand
+#[tauri::command(rename_all = "camelCase")]
+pub async fn create_action_item_manual(
+    meeting_id: String,
+    text: String,
+    assignee: Option<String>,
+    due_hint: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::database::ActionItemRecord, String> {
+    let text = text.trim().to_string();
+    let normalized = crate::action_items::normalize_text(&text);
+    if normalized.is_empty() {
+        return Err("Action item text is empty".to_string());
+    }
+
+    let inserted = state
+        .database
+        .upsert_action_item(
+            &meeting_id,
+            &text,
+            &normalized,
+            assignee.as_deref().filter(|a| !a.trim().is_empty()),
+            due_hint.as_deref().filter(|d| !d.trim().is_empty()),
+            crate::action_items::SOURCE_MANUAL,
+            None,
+        )
+        .await
+        .map_err(|e| format!("Failed to save action item: {}", e))?;
+    if inserted {
+        crate::integrations::emit_action_item_created(&state.database, &meeting_id, &normalized)
+            .await;
+    }
+
+    state
+        .database
+        .list_action_items(Some(&meeting_id))
+        .await
+        .map_err(|e| format!("Failed to list action items: {}", e))?
+        .into_iter()
+        .find(|item| crate::action_items::normalize_text(&item.text) == normalized)
+        .ok_or_else(|| "Action item was not saved".to_string())
+}
+
+/// Calendar follow-ups proposed for a meeting's action items that have a
+/// time hint; nothing is created until `schedule_followups` confirms them
+#[tauri::command(rename_all = "camelCase")]
+pub async fn propose_followups(
+    meeting_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::followups::FollowupProposal>, String> {
+    crate::followups::proposals(&state.database, &meeting_id).await
+}
+
+/// Create the follow-up events the user confirmed
+#[tauri::command(rename_all = "camelCase")]
+pub async fn schedule_followups(
+    meeting_id: String,
+    confirmed: Vec<crate::followups::FollowupProposal>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::followups::ScheduledFollowup>, String> {
+    if confirmed.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !CalendarClient::request_write_access().await? {
+        return Err("Calendar write access denied".to_string());
+    }
+    crate::followups::schedule(&state.database, &meeting_id, &confirmed).await
+}
+
+// ============================================
+// CRM Webhook Commands
+// ============================================
+
+/// Save the outbound webhook settings and apply them immediately
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_webhook_config(
+    url: Option<String>,
+    bearer_token: Option<String>,
+    secret: Option<String>,
+    event_types: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    crate::integrations::validate_event_types(&event_types)?;
+    let non_empty = |v: &Option<String>| {
+        v.as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    let url = non_empty(&url);
+    if let Some(url) = &url {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err("Webhook URL must start with http:// or https://".to_string());
+        }
+    }
+
+    state
+        .settings
+        .set_webhook_config(
+            url.as_deref(),
+            non_empty(&bearer_token).as_deref(),
+            non_empty(&secret).as_deref(),
+            &event_types,
+        )
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+
+    let settings = state
+        .settings
+        .get_all()
+        .await
+        .map_err(|e| format!("Failed to get settings: {}", e))?;
+    crate::integrations::webhooks()
+        .configure(crate::integrations::WebhookConfig::from_settings(&settings));
+    Ok(())
+}
+
+/// Recent webhook deliveries, newest first
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_webhook_delivery_log(
+    limit: Option<u32>,
+) -> Result<Vec<crate::integrations::WebhookDelivery>, String> {
+    crate::integrations::webhooks().delivery_log(limit.unwrap_or(100))
+}
+
+/// Send a sample payload to the configured webhook and report the response
+#[tauri::command(rename_all = "camelCase")]
+pub async fn test_webhook() -> Result<crate::integrations::WebhookTestResult, String> {
+    crate::integrations::webhooks().test().await
+}
+
+// ============================================
+// Slack Commands
+// ============================================
+
+/// Save or clear the Slack bot token used by "Send to Slack"
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_slack_bot_token(
+    token: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let token = token
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty());
+    state
+        .settings
+        .set_slack_bot_token(token.as_deref())
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))
+}
+
+async fn slack_client(
+    state: &State<'_, AppState>,
+) -> Result<crate::slack_client::SlackClient, String> {
+    let settings = state
+        .settings
+        .get_all()
+        .await
+        .map_err(|e| format!("Failed to get settings: {}", e))?;
+    let token = settings
+        .slack_bot_token
+        .ok_or_else(|| "No Slack bot token configured. Add one in Settings.".to_string())?;
+    Ok(crate::slack_client::SlackClient::new(&token))
+}
+
+/// Post a meeting's notes and action items to Slack, with highlights in a thread
+#[tauri::command(rename_all = "camelCase")]
+pub async fn send_meeting_summary_to_slack(
+    meeting_id: String,
+    channel: String,
+    state: State<'_, AppState>,
+) -> Result<crate::slack_client::SlackPostResult, String> {
+    use crate::slack_client::{summary_messages, SlackHighlight, SlackMeetingSummary};
+
+    let client = slack_client(&state).await?;
+    let meeting = state
+        .database
+        .get_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting {} not found", meeting_id))?;
+    let notes = state
+        .database
+        .get_meeting_notes(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting notes: {}", e))?;
+    let action_items = state
+        .database
+        .list_action_items(Some(&meeting_id))
+        .await
+        .map_err(|e| format!("Failed to list action items: {}", e))?;
+
+    // Marked decisions and pinned insights are the transcript highlights
+    let start_ms = meeting.started_at.timestamp_millis();
+    let mut highlights: Vec<SlackHighlight> = state
+        .database
+        .get_decisions(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get decisions: {}", e))?
+        .into_iter()
+        .map(|d| SlackHighlight {
+            offset_ms: d.timestamp_ms - start_ms,
+            label: "Decision".to_string(),
+            text: d.text,
+        })
+        .collect();
+    highlights.extend(
+        state
+            .database
+            .get_pinned_insights(&meeting_id)
+            .await
+            .map_err(|e| format!("Failed to get pinned insights: {}", e))?
+            .into_iter()
+            .map(|p| SlackHighlight {
+                offset_ms: p.timestamp_ms - start_ms,
+                label: p.insight_type,
+                text: p.text,
+            }),
+    );
+    highlights.sort_by_key(|h| h.offset_ms);
+
+    let fallback = format!("Meeting summary: {}", meeting.title);
+    let messages = summary_messages(&SlackMeetingSummary {
+        meeting_id: meeting.id,
+        title: meeting.title,
+        started_at: meeting.started_at,
+        duration_seconds: meeting.duration_seconds,
+        summary: notes.and_then(|n| n.summary),
+        action_items,
+        highlights,
+    });
+
+    client
+        .post_thread(channel.trim(), &fallback, &messages)
+        .await
+}
+
+/// Post the current catch-up capsule for late joiners
+#[tauri::command(rename_all = "camelCase")]
+pub async fn send_catch_up_to_slack(
+    meeting_id: String,
+    channel: String,
+    state: State<'_, AppState>,
+) -> Result<crate::slack_client::SlackPostResult, String> {
+    let client = slack_client(&state).await?;
+    let title = state
+        .database
+        .get_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))?
+        .map(|m| m.title)
+        .unwrap_or_else(|| "Meeting in progress".to_string());
+
+    let capsule = generate_catch_up(meeting_id.clone(), None, state.clone()).await?;
+    let messages = crate::slack_client::catch_up_messages(&meeting_id, &title, &capsule);
+    client
+        .post_thread(channel.trim(), &capsule.ten_second_version, &messages)
+        .await
+}
+
+// ============================================
+// Knowledge Base Commands (VLM, Supabase, Pinecone)
+// ============================================
+
+use crate::pinecone_client::{ActivityMetadata, VectorMatch};
+use crate::supabase_client::Activity;
+use crate::vlm_client::ActivityContext;
+
+/// Check if VLM API is available
+#[tauri::command(rename_all = "camelCase")]
+pub async fn check_vlm(_state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(crate::vlm_client::vlm_is_available().await)
+}
+
+/// Check if VLM has vision model
+#[tauri::command(rename_all = "camelCase")]
+pub async fn check_vlm_vision(_state: State<'_, AppState>) -> Result<bool, String> {
+    crate::vlm_client::vlm_has_vision_model().await
+}
+
+/// Analyze a frame with VLM
+#[tauri::command(rename_all = "camelCase")]
+pub async fn analyze_frame(
+    frame_path: String,
+    _state: State<'_, AppState>,
+) -> Result<ActivityContext, String> {
+    // Use default prompt for manual analysis
+    let prompt = r#"Analyze this screenshot and describe what the user is doing. 
+Respond in JSON format with these fields:
+{
+  "app_name": "name of the main application visible",
+  "window_title": "title of the window or document",
+  "category": "one of: development, communication, research, writing, design, media, browsing, system, other",
+  "summary": "brief description of what the user is doing",
+  "focus_area": "specific task or project",
+  "visible_files": [],
+  "confidence": 0.8
+}
+Only respond with valid JSON."#;
+
+    crate::vlm_client::vlm_analyze_frame(&frame_path, prompt).await
 }
 
 /// Analyze multiple frames (batch)
 #[tauri::command(rename_all = "camelCase")]
 pub async fn analyze_frames_batch(
     frame_paths: Vec<String>,
-    _state: State<'_, AppState>,
+    state: State<'_, AppState>,
 ) -> Result<Vec<ActivityContext>, String> {
     let prompt = r#"Analyze this screenshot. Respond in JSON with: app_name, category, summary, confidence."#;
-    let frames: Vec<(String, String)> = frame_paths
-        .into_iter()
-        .map(|p| (p, prompt.to_string()))
-        .collect();
+    let batch_size = state.vlm_scheduler.batch_size() as usize;
 
-    let results = crate::vlm_client::vlm_analyze_frames_batch(frames).await;
+    let results =
+        crate::vlm_client::vlm_analyze_frames_batch(frame_paths, prompt, batch_size).await;
 
     // Collect successful results
     Ok(results.into_iter().filter_map(|r| r.ok()).collect())
@@ -1645,7 +3251,9 @@ pub async fn get_thebrain_models(
 }
 
 /// Capture text from the current focused window using accessibility APIs
-/// and store it as a text snapshot in the database
+/// and store it as a text snapshot in the database.
+/// Falls back to OCR of the latest keyframe (or a live screenshot) when
+/// accessibility fails, unless the OCR fallback is disabled in settings.
 #[tauri::command(rename_all = "camelCase")]
 pub async fn capture_accessibility_snapshot(
     state: State<'_, AppState>,
@@ -1656,9 +3264,61 @@ pub async fn capture_accessibility_snapshot(
     log::info!("📸 Capturing accessibility snapshot from focused window...");
 
     let extractor = SnapshotExtractor::new();
+    let meeting_id = state.accessibility_capture.get_meeting_id();
 
     // First try accessibility, fall back to OCR if needed
-    let result = extractor.extract_from_accessibility(None, None, None, None);
+    let mut result = extractor.extract_from_accessibility(None, None, None, None);
+    let mut fallback_reason = None;
+
+    let ocr_fallback_enabled = state
+        .settings
+        .get_all()
+        .await
+        .map(|s| s.ocr_fallback_enabled)
+        .unwrap_or(true);
+
+    if result.should_fall_back() && ocr_fallback_enabled {
+        let reason = match &result {
+            ExtractionResult::Failed(reason) => reason.clone(),
+            ExtractionResult::LowQuality(score) => {
+                format!("Accessibility text quality too low: {:.1}%", score * 100.0)
+            }
+            _ => String::new(),
+        };
+        log::info!("📸 {} - falling back to OCR", reason);
+        fallback_reason = Some(reason);
+
+        let keyframe = match meeting_id.as_deref() {
+            Some(mid) => state
+                .database
+                .get_latest_screen_state(mid)
+                .await
+                .map_err(|e| format!("Failed to get latest screen state: {}", e))?
+                .filter(|s| s.keyframe_path.as_deref().is_some_and(|p| !p.is_empty())),
+            None => None,
+        };
+
+        let monitor_id = state.capture_engine.read().selected_monitor();
+        result = tokio::task::spawn_blocking(move || {
+            let extractor = SnapshotExtractor::new();
+            match keyframe {
+                Some(screen_state) => extractor.extract_from_keyframe(
+                    std::path::Path::new(screen_state.keyframe_path.as_deref().unwrap_or("")),
+                    None,
+                    Some(&screen_state.state_id),
+                ),
+                // No keyframe for the current screen yet - OCR it live
+                None => {
+                    match crate::capture_engine::CaptureEngine::capture_screenshot(monitor_id) {
+                        Ok(image) => extractor.extract_from_image(&image, None, None),
+                        Err(e) => ExtractionResult::Failed(e),
+                    }
+                }
+            }
+        })
+        .await
+        .map_err(|e| format!("OCR task failed: {}", e))?;
+    }
 
     match result {
         ExtractionResult::Success(snapshot) => {
@@ -1668,40 +3328,49 @@ pub async fn capture_accessibility_snapshot(
             } else {
                 snapshot.text.clone()
             };
+            let source = snapshot.source.as_str();
 
             // Store to database
-            if let Err(e) = state
+            match state
                 .database
-                .add_text_snapshot(
+                .add_text_snapshot_full(
                     &snapshot_id,
                     None, // episode_id
-                    None, // state_id
+                    snapshot.state_id.as_deref(),
+                    meeting_id.as_deref(),
                     chrono::Utc::now(),
                     &snapshot.text,
                     &snapshot.text_hash,
                     snapshot.quality_score,
-                    ExtractionSource::Accessibility.as_str(),
+                    source,
+                    None,
+                    None,
                 )
                 .await
             {
-                log::warn!("Failed to save snapshot to database: {}", e);
+                Err(e) => log::warn!("Failed to save snapshot to database: {}", e),
+                Ok(write) => {
+                    state.metrics_collector.record_snapshot_write(&write);
+                    match snapshot.source {
+                        ExtractionSource::Ocr => state.metrics_collector.record_ocr_fallback(),
+                        _ => state.metrics_collector.record_accessibility_hit(),
+                    }
+                }
             }
 
-            log::info!(
-                "✅ Captured {} words from accessibility API",
-                snapshot.word_count
-            );
+            log::info!("✅ Captured {} words via {}", snapshot.word_count, source);
 
             Ok(CapturedSnapshotResult {
                 success: true,
                 text_preview,
                 word_count: snapshot.word_count,
-                source: "accessibility".to_string(),
+                source: source.to_string(),
                 snapshot_id,
+                fallback_reason,
             })
         }
         ExtractionResult::Failed(reason) => {
-            log::warn!("Accessibility capture failed: {}", reason);
+            log::warn!("Snapshot capture failed: {}", reason);
             Err(format!("Capture failed: {}", reason))
         }
         ExtractionResult::TooShort => Err("Captured text too short to be useful".to_string()),
@@ -1719,8 +3388,11 @@ pub struct CapturedSnapshotResult {
     pub success: bool,
     pub text_preview: String,
     pub word_count: i32,
+    /// Path that produced the text: "accessibility" or "ocr"
     pub source: String,
     pub snapshot_id: String,
+    /// Why accessibility was skipped, when OCR produced the text
+    pub fallback_reason: Option<String>,
 }
 
 /// Chat with TheBrain API using specified model
@@ -1744,12 +3416,66 @@ pub async fn thebrain_chat(
     crate::vlm_client::vlm_chat_stream(&message, &model).await
 }
 
+/// Streaming `thebrain_chat`, delivered as `ai-stream` events
+#[tauri::command(rename_all = "camelCase")]
+pub async fn thebrain_chat_stream(
+    message: String,
+    model: String,
+    app: AppHandle,
+) -> Result<String, String> {
+    if !crate::vlm_client::vlm_is_authenticated() {
+        return Err("Not authenticated with TheBrain. Please login in Settings.".to_string());
+    }
+
+    log::info!(
+        "🧠 TheBrain chat stream: model={}, message_len={}",
+        model,
+        message.len()
+    );
+
+    Ok(crate::ai_stream::spawn_stream(app, |sink| async move {
+        crate::vlm_client::vlm_chat_stream_with(&message, &model, &mut |delta| sink.delta(delta))
+            .await
+            .map(|_| ())
+    }))
+}
+
 /// RAG Chat Response with context and citations
 #[derive(serde::Serialize)]
 pub struct RagChatResponse {
     pub response: String,
     pub context_used: Vec<ContextItem>,
     pub model: String,
+    /// Chat thread the exchange was stored under (memory chat only)
+    pub conversation_id: Option<String>,
+    /// Prior turns included in the prompt
+    pub history_turns_used: usize,
+    pub context_chunks_used: usize,
+}
+
+/// Prior turns fetched for a threaded RAG chat
+const RAG_HISTORY_MAX_TURNS: i32 = 10;
+/// Prompt budget for prior turns, in estimated tokens
+const RAG_HISTORY_TOKEN_BUDGET: usize = 1500;
+
+/// Newest turns (oldest first, as given) that fit in `budget` estimated
+/// tokens; the oldest are dropped first
+fn history_within_budget(
+    turns: &[crate::database::StoredConversation],
+    budget: usize,
+) -> &[crate::database::StoredConversation] {
+    let mut used = 0;
+    let mut start = turns.len();
+    for (i, turn) in turns.iter().enumerate().rev() {
+        // Rough token estimate: chars / 4
+        used +=
+            (turn.user_query.chars().count() + turn.assistant_response.chars().count()).div_ceil(4);
+        if used > budget {
+            break;
+        }
+        start = i;
+    }
+    &turns[start..]
 }
 
 #[derive(serde::Serialize)]
@@ -1768,6 +3494,17 @@ pub async fn thebrain_rag_chat(
     model: String,
     top_k: Option<u32>,
     state: State<'_, AppState>,
+) -> Result<RagChatResponse, String> {
+    rag_chat(&state, message, model, top_k, &[]).await
+}
+
+/// RAG chat with optional prior turns (oldest first) prepended to the prompt
+async fn rag_chat(
+    state: &AppState,
+    message: String,
+    model: String,
+    top_k: Option<u32>,
+    history: &[crate::database::StoredConversation],
 ) -> Result<RagChatResponse, String> {
     if !crate::vlm_client::vlm_is_authenticated() {
         return Err("Not authenticated with TheBrain. Please login in Settings.".to_string());
@@ -1780,13 +3517,10 @@ pub async fn thebrain_rag_chat(
         model
     );
 
-    // Get config before async operations (avoid holding RwLock guard across await)
-    let pinecone_config = state.pinecone_client.read().get_config();
-
-    // Step 1: Search Pinecone for relevant context
-    let context_items = match pinecone_config {
-        Some(config) => {
-            match crate::pinecone_client::pinecone_search(&config, &message, search_count).await {
+    // Step 1: Search the vector store for relevant context
+    let context_items = match vector_store(state).await {
+        Some(store) => {
+            match store.search(&message, search_count).await {
                 Ok(matches) => matches
                     .into_iter()
                     .filter(|m| m.score > 0.5) // Only include good matches
@@ -1812,18 +3546,38 @@ pub async fn thebrain_rag_chat(
                     })
                     .collect::<Vec<_>>(),
                 Err(e) => {
-                    log::warn!("Pinecone search failed, proceeding without context: {}", e);
+                    log::warn!(
+                        "{} search failed, proceeding without context: {}",
+                        store.name(),
+                        e
+                    );
                     vec![]
                 }
             }
         }
         None => {
-            log::info!("Pinecone not configured, proceeding without context");
+            log::info!("No vector store configured, proceeding without context");
             vec![]
         }
     };
 
     // Step 2: Build augmented prompt with context
+    let history_text = if history.is_empty() {
+        String::new()
+    } else {
+        let turns = history
+            .iter()
+            .map(|t| {
+                format!(
+                    "User: {}\nAssistant: {}",
+                    t.user_query, t.assistant_response
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        format!("RECENT CONVERSATION (oldest first):\n{}\n\n", turns)
+    };
+
     let augmented_prompt = if !context_items.is_empty() {
         let context_text = context_items
             .iter()
@@ -1846,23 +3600,23 @@ pub async fn thebrain_rag_chat(
 RELEVANT CONTEXT FROM USER'S HISTORY:
 {}
 
-USER QUESTION: {}
+{}USER QUESTION: {}
 
 Instructions:
 - Use the context above to inform your answer when relevant
 - If the context doesn't contain relevant information, say so and answer based on general knowledge
 - Reference specific items from the context when applicable (e.g., "Based on your meeting on [date]...")
 - Be concise and actionable"#,
-            context_text, message
+            context_text, history_text, message
         )
     } else {
         format!(
             r#"You are an intelligent assistant helping with daily operations.
 
-USER QUESTION: {}
+{}USER QUESTION: {}
 
 Note: No relevant context was found in the user's history for this query. Answer based on general knowledge."#,
-            message
+            history_text, message
         )
     };
 
@@ -1876,8 +3630,11 @@ Note: No relevant context was found in the user's history for this query. Answer
 
     Ok(RagChatResponse {
         response,
+        context_chunks_used: context_items.len(),
         context_used: context_items,
         model,
+        conversation_id: None,
+        history_turns_used: history.len(),
     })
 }
 
@@ -1894,6 +3651,8 @@ pub struct ConversationRecord {
     pub assistant_response: String,
     pub model_used: String,
     pub context_refs: Vec<String>, // IDs of context items used
+    #[serde(default)]
+    pub source: String, // "local", "supabase" or "both"
 }
 
 /// Store a conversation to both Supabase and Pinecone
@@ -1903,6 +3662,7 @@ pub async fn store_conversation(
     assistant_response: String,
     model_used: String,
     context_refs: Vec<String>,
+    conversation_id: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
     let id = uuid::Uuid::new_v4().to_string();
@@ -1910,56 +3670,43 @@ pub async fn store_conversation(
 
     log::info!("💾 Storing conversation: {}", id);
 
-    // Get configs before async operations (avoid RwLock guard across await)
-    let pinecone_config = state.pinecone_client.read().get_config();
-    let supabase_pool = state.supabase_client.read().get_pool();
-
-    // Store to Pinecone for semantic search of past conversations
-    if let Some(config) = pinecone_config {
-        // Create searchable text combining Q&A
-        let searchable_text = format!("Q: {}\nA: {}", user_query, assistant_response);
-
-        let metadata = crate::pinecone_client::ActivityMetadata {
+    // Local copy first so history works without any cloud configured
+    state
+        .database
+        .insert_conversation(&crate::database::StoredConversation {
+            id: id.clone(),
+            conversation_id,
             timestamp: timestamp.clone(),
-            category: "conversation".to_string(),
-            app_name: Some("thebrain-chat".to_string()),
-            focus_area: None,
-            summary: format!(
-                "User asked about: {}...",
-                &user_query.chars().take(100).collect::<String>()
-            ),
-        };
-
-        if let Err(e) =
-            crate::pinecone_client::pinecone_upsert(&config, &id, &searchable_text, &metadata).await
-        {
-            log::warn!("Failed to store conversation to Pinecone: {}", e);
-        } else {
-            log::info!("📌 Conversation stored to Pinecone: {}", id);
-        }
-    }
+            user_query: user_query.clone(),
+            assistant_response: assistant_response.clone(),
+            model_used: model_used.clone(),
+            context_refs: context_refs.clone(),
+        })
+        .await
+        .map_err(|e| format!("Failed to store conversation: {}", e))?;
 
-    // Store to Supabase if connected
-    if let Some(pool) = supabase_pool {
-        let query = r#"
-            INSERT INTO conversations (id, timestamp, user_query, assistant_response, model_used, context_refs)
-            VALUES ($1::uuid, $2::timestamptz, $3, $4, $5, $6)
-            ON CONFLICT (id) DO NOTHING
-        "#;
-
-        match sqlx::query(query)
-            .bind(&id)
-            .bind(&timestamp)
-            .bind(&user_query)
-            .bind(&assistant_response)
-            .bind(&model_used)
-            .bind(serde_json::to_value(&context_refs).unwrap_or_default())
-            .execute(&pool)
-            .await
-        {
-            Ok(_) => log::info!("📦 Conversation stored to Supabase: {}", id),
-            Err(e) => log::warn!("Failed to store conversation to Supabase: {}", e),
-        }
+    // Uploaded to Pinecone (semantic recall of past conversations) and
+    // Supabase by the sync worker, which retries while offline
+    let record = crate::sync_worker::ConversationRecord {
+        id: id.clone(),
+        timestamp,
+        user_query,
+        assistant_response,
+        model_used,
+        context_refs,
+    };
+    let payload = serde_json::to_string(&record)
+        .map_err(|e| format!("Failed to serialize conversation: {}", e))?;
+    if let Err(e) = state
+        .sync_worker
+        .enqueue(
+            crate::sync_worker::SyncJobKind::Conversation,
+            &id,
+            Some(&payload),
+        )
+        .await
+    {
+        log::warn!("Conversation {} stored locally only: {}", id, e);
     }
 
     Ok(id)
@@ -1973,65 +3720,209 @@ pub async fn get_conversation_history(
 ) -> Result<Vec<ConversationRecord>, String> {
     let max_count = limit.unwrap_or(20);
 
+    let local: Vec<ConversationRecord> = state
+        .database
+        .get_conversations(max_count)
+        .await
+        .map_err(|e| format!("Failed to get conversations: {}", e))?
+        .into_iter()
+        .map(|c| ConversationRecord {
+            id: c.id,
+            timestamp: c.timestamp,
+            user_query: c.user_query,
+            assistant_response: c.assistant_response,
+            model_used: c.model_used,
+            context_refs: c.context_refs,
+            source: "local".to_string(),
+        })
+        .collect();
+
     // Get pool before async operations (avoid RwLock guard across await)
     let supabase_pool = state.supabase_client.read().get_pool();
+    let remote = match supabase_pool {
+        Some(pool) => fetch_supabase_conversations(&pool, max_count)
+            .await
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to fetch conversations from Supabase: {}", e);
+                Vec::new()
+            }),
+        None => Vec::new(),
+    };
 
-    // Try to get from Supabase first
-    if let Some(pool) = supabase_pool {
-        let query = r#"
-            SELECT id, timestamp, user_query, assistant_response, model_used, context_refs
-            FROM conversations
-            ORDER BY timestamp DESC
-            LIMIT $1
-        "#;
-
-        match sqlx::query_as::<_, (String, String, String, String, String, serde_json::Value)>(
-            query,
+    // Dedupe by id; rows present in both stores are marked "both"
+    let mut merged = local;
+    for record in remote {
+        match merged.iter_mut().find(|r| r.id == record.id) {
+            Some(existing) => existing.source = "both".to_string(),
+            None => merged.push(record),
+        }
+    }
+    merged.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    merged.truncate(max_count.max(0) as usize);
+
+    Ok(merged)
+}
+
+async fn fetch_supabase_conversations(
+    pool: &sqlx::PgPool,
+    limit: i32,
+) -> Result<Vec<ConversationRecord>, sqlx::Error> {
+    let query = r#"
+        SELECT id::text, timestamp, user_query, assistant_response, model_used, context_refs
+        FROM conversations
+        ORDER BY timestamp DESC
+        LIMIT $1
+    "#;
+
+    let rows = sqlx::query_as::<
+        _,
+        (
+            String,
+            chrono::DateTime<chrono::Utc>,
+            String,
+            String,
+            Option<String>,
+            Option<serde_json::Value>,
+        ),
+    >(query)
+    .bind(limit as i64)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(id, timestamp, user_query, assistant_response, model_used, context_refs)| {
+                ConversationRecord {
+                    id,
+                    timestamp: timestamp.to_rfc3339(),
+                    user_query,
+                    assistant_response,
+                    model_used: model_used.unwrap_or_default(),
+                    context_refs: context_refs
+                        .as_ref()
+                        .and_then(|refs| refs.as_array())
+                        .map(|arr| {
+                            arr.iter()
+                                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    source: "supabase".to_string(),
+                }
+            },
         )
-        .bind(max_count)
-        .fetch_all(&pool)
-        .await
-        {
-            Ok(rows) => {
-                let records = rows
-                    .into_iter()
-                    .map(
-                        |(
-                            id,
-                            timestamp,
-                            user_query,
-                            assistant_response,
-                            model_used,
-                            context_refs,
-                        )| {
-                            ConversationRecord {
-                                id,
-                                timestamp,
-                                user_query,
-                                assistant_response,
-                                model_used,
-                                context_refs: context_refs
-                                    .as_array()
-                                    .map(|arr| {
-                                        arr.iter()
-                                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                                            .collect()
-                                    })
-                                    .unwrap_or_default(),
-                            }
-                        },
-                    )
-                    .collect();
-                return Ok(records);
+        .collect())
+}
+
+/// Delete one conversation locally, in Supabase and its Pinecone vector
+#[tauri::command(rename_all = "camelCase")]
+pub async fn delete_conversation(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    delete_conversations(&state, Some(vec![id])).await?;
+    Ok(())
+}
+
+/// Delete all conversation history from every store. Returns how many
+/// conversations were removed.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn clear_conversation_history(state: State<'_, AppState>) -> Result<usize, String> {
+    delete_conversations(&state, None).await
+}
+
+/// Remove the given conversations (all when None) locally, from Supabase and
+/// from Pinecone, and drop their pending uploads
+async fn delete_conversations(state: &AppState, ids: Option<Vec<String>>) -> Result<usize, String> {
+    let kind = crate::sync_worker::SyncJobKind::Conversation.as_str();
+    let supabase_pool = state.supabase_client.read().get_pool();
+
+    // Pending uploads would resurrect what we delete
+    let mut all_ids: std::collections::HashSet<String> = match ids {
+        Some(ref ids) => {
+            for id in ids {
+                state
+                    .database
+                    .delete_sync_jobs(kind, Some(id))
+                    .await
+                    .map_err(|e| format!("Failed to drop queued upload: {}", e))?;
             }
-            Err(e) => {
-                log::warn!("Failed to fetch conversations from Supabase: {}", e);
+            ids.iter().cloned().collect()
+        }
+        None => {
+            state
+                .database
+                .delete_sync_jobs(kind, None)
+                .await
+                .map_err(|e| format!("Failed to drop queued uploads: {}", e))?;
+            state
+                .database
+                .get_conversation_ids()
+                .await
+                .map_err(|e| format!("Failed to get conversations: {}", e))?
+                .into_iter()
+                .collect()
+        }
+    };
+
+    if let Some(ref pool) = supabase_pool {
+        match ids {
+            Some(ref ids) => {
+                for id in ids {
+                    sqlx::query("DELETE FROM conversations WHERE id::text = $1")
+                        .bind(id)
+                        .execute(pool)
+                        .await
+                        .map_err(|e| {
+                            format!("Failed to delete conversation from Supabase: {}", e)
+                        })?;
+                }
+            }
+            None => {
+                let remote_ids: Vec<String> =
+                    sqlx::query_scalar("SELECT id::text FROM conversations")
+                        .fetch_all(pool)
+                        .await
+                        .map_err(|e| format!("Failed to list Supabase conversations: {}", e))?;
+                all_ids.extend(remote_ids);
+                sqlx::query("DELETE FROM conversations")
+                    .execute(pool)
+                    .await
+                    .map_err(|e| format!("Failed to clear Supabase conversations: {}", e))?;
+            }
+        }
+    }
+
+    match ids {
+        Some(ref ids) => {
+            for id in ids {
+                state
+                    .database
+                    .delete_conversation(id)
+                    .await
+                    .map_err(|e| format!("Failed to delete conversation: {}", e))?;
             }
         }
+        None => {
+            state
+                .database
+                .clear_conversations()
+                .await
+                .map_err(|e| format!("Failed to clear conversations: {}", e))?;
+        }
+    }
+
+    let pinecone_config = state.pinecone_client.read().get_config();
+    if let Some(config) = pinecone_config {
+        let vector_ids: Vec<String> = all_ids.iter().cloned().collect();
+        crate::pinecone_client::pinecone_delete_by_ids(
+            &config,
+            config.namespace_for(crate::pinecone_client::VectorKind::Conversation),
+            &vector_ids,
+        )
+        .await?;
     }
 
-    // If Supabase not available, return empty
-    Ok(vec![])
+    log::info!("🗑️ Deleted {} conversations", all_ids.len());
+    Ok(all_ids.len())
 }
 
 /// Combined RAG chat with automatic conversation storage
@@ -2040,10 +3931,24 @@ pub async fn thebrain_rag_chat_with_memory(
     message: String,
     model: String,
     top_k: Option<u32>,
+    conversation_id: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<RagChatResponse, String> {
+    // Prior turns of the thread give follow-ups ("expand on point 2") their referent
+    let conversation_id = conversation_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let turns = state
+        .database
+        .get_conversation_turns(&conversation_id, RAG_HISTORY_MAX_TURNS)
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to load conversation history: {}", e);
+            Vec::new()
+        });
+    let history = history_within_budget(&turns, RAG_HISTORY_TOKEN_BUDGET);
+
     // First do the RAG chat
-    let response = thebrain_rag_chat(message.clone(), model.clone(), top_k, state.clone()).await?;
+    let mut response = rag_chat(&state, message.clone(), model.clone(), top_k, history).await?;
+    response.conversation_id = Some(conversation_id.clone());
 
     // Then store the conversation for future retrieval
     let context_refs: Vec<String> = response.context_used.iter().map(|c| c.id.clone()).collect();
@@ -2053,6 +3958,7 @@ pub async fn thebrain_rag_chat_with_memory(
         response.response.clone(),
         model,
         context_refs,
+        Some(conversation_id),
         state,
     )
     .await
@@ -2067,6 +3973,7 @@ pub async fn thebrain_rag_chat_with_memory(
 #[tauri::command(rename_all = "camelCase")]
 pub async fn configure_supabase(
     connection_string: String,
+    create_schema: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     // Set connection string (sync), drop guard
@@ -2077,23 +3984,56 @@ pub async fn configure_supabase(
     // Connect using standalone function (no guard held across await)
     let pool = crate::supabase_client::supabase_connect_pool(&connection_string).await?;
     // Store pool in client
-    state.supabase_client.read().set_pool(pool);
+    state.supabase_client.read().set_pool(pool.clone());
+    crate::subsystems::subsystems().record(
+        crate::subsystems::SUPABASE,
+        Ok(crate::subsystems::SubsystemOutcome::Ready(None)),
+    );
+
+    // Tables are only created once the user confirms
+    if create_schema.unwrap_or(false) {
+        return crate::supabase_client::supabase_ensure_schema(&pool).await;
+    }
+    let missing = crate::supabase_client::supabase_missing_tables(&pool).await?;
+    if !missing.is_empty() {
+        return Err(format!(
+            "Connected, but these tables are missing: {}. Confirm schema creation, or run this SQL in the Supabase SQL editor:\n\n{}",
+            missing.join(", "),
+            crate::supabase_client::supabase_schema_sql()
+        ));
+    }
     Ok(())
 }
 
 /// Check Supabase connection
 #[tauri::command(rename_all = "camelCase")]
 pub async fn check_supabase(state: State<'_, AppState>) -> Result<bool, String> {
+    crate::subsystems::ensure_supabase(&state).await;
     let client = state.supabase_client.read();
     Ok(client.is_connected())
 }
 
+/// Round-trip Supabase: latency, table existence and row counts
+#[tauri::command(rename_all = "camelCase")]
+pub async fn check_supabase_health(
+    state: State<'_, AppState>,
+) -> Result<crate::supabase_client::SupabaseHealth, String> {
+    crate::subsystems::ensure_supabase(&state).await;
+    let pool = state
+        .supabase_client
+        .read()
+        .get_pool()
+        .ok_or("Supabase not connected")?;
+    Ok(crate::supabase_client::supabase_health(&pool).await)
+}
+
 /// Sync an activity to Supabase
 #[tauri::command(rename_all = "camelCase")]
 pub async fn sync_activity_to_supabase(
     activity: Activity,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
+    crate::subsystems::ensure_supabase(&state).await;
     let pool = state
         .supabase_client
         .read()
@@ -2118,6 +4058,7 @@ pub async fn query_activities(
         .parse()
         .map_err(|e| format!("Invalid end time: {}", e))?;
 
+    crate::subsystems::ensure_supabase(&state).await;
     let pool = state
         .supabase_client
         .read()
@@ -2134,14 +4075,71 @@ pub async fn configure_pinecone(
     namespace: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    let namespaces = pinecone_namespaces(&state, namespace.as_deref()).await;
     let client = state.pinecone_client.read();
-    client.configure(api_key, index_host, namespace);
+    client.configure(api_key, index_host, namespace, namespaces);
+    crate::subsystems::subsystems().record(
+        crate::subsystems::PINECONE,
+        Ok(crate::subsystems::SubsystemOutcome::Ready(None)),
+    );
     Ok(())
 }
 
+/// Per-kind Pinecone namespaces from settings, derived from `base` (or the
+/// saved base namespace) where not overridden
+async fn pinecone_namespaces(
+    state: &AppState,
+    base: Option<&str>,
+) -> crate::pinecone_client::PineconeNamespaces {
+    let mut settings = state.settings.get_all().await.unwrap_or_default();
+    if let Some(base) = base {
+        settings.pinecone_namespace = Some(base.to_string());
+    }
+    crate::pinecone_client::PineconeNamespaces::from_settings(&settings)
+}
+
+/// Override the Pinecone namespace per record kind; empty or missing values
+/// fall back to "<namespace>-<kind>". Applies to the running client immediately.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn configure_pinecone_namespaces(
+    transcripts: Option<String>,
+    activities: Option<String>,
+    conversations: Option<String>,
+    snapshots: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::pinecone_client::PineconeNamespaces, String> {
+    use crate::pinecone_client::VectorKind;
+
+    for (kind, namespace) in [
+        (VectorKind::Transcript, transcripts),
+        (VectorKind::Activity, activities),
+        (VectorKind::Conversation, conversations),
+        (VectorKind::Snapshot, snapshots),
+    ] {
+        state
+            .settings
+            .set_pinecone_kind_namespace(kind.as_str(), namespace.as_deref().unwrap_or("").trim())
+            .await
+            .map_err(|e| format!("Failed to save setting: {}", e))?;
+    }
+
+    let namespaces = pinecone_namespaces(&state, None).await;
+    let current = state.pinecone_client.read().get_config();
+    if let Some(config) = current {
+        state.pinecone_client.read().configure(
+            config.api_key,
+            config.index_host,
+            config.namespace,
+            namespaces.clone(),
+        );
+    }
+    Ok(namespaces)
+}
+
 /// Check if Pinecone is configured
 #[tauri::command(rename_all = "camelCase")]
 pub async fn check_pinecone(state: State<'_, AppState>) -> Result<bool, String> {
+    crate::subsystems::ensure_pinecone(&state).await;
     let client = state.pinecone_client.read();
     Ok(client.is_configured())
 }
@@ -2159,7 +4157,14 @@ pub async fn upsert_to_pinecone(
         .read()
         .get_config()
         .ok_or("Pinecone not configured")?;
-    crate::pinecone_client::pinecone_upsert(&config, &id, &text, &metadata).await
+    crate::pinecone_client::pinecone_upsert(
+        &config,
+        crate::pinecone_client::VectorKind::Activity,
+        &id,
+        &text,
+        &metadata,
+    )
+    .await
 }
 
 /// Semantic search in Pinecone
@@ -2179,7 +4184,8 @@ pub async fn semantic_search(
     crate::pinecone_client::pinecone_search(&config, &query, k).await
 }
 
-/// Get Pinecone index stats
+/// Get Pinecone index stats, with vector counts broken down per record kind
+/// under `by_type`
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_pinecone_stats(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
     let config = state
@@ -2188,7 +4194,12 @@ pub async fn get_pinecone_stats(state: State<'_, AppState>) -> Result<serde_json
         .get_config()
         .ok_or("Pinecone not configured")?;
 
-    crate::pinecone_client::pinecone_stats(&config).await
+    let mut stats = crate::pinecone_client::pinecone_stats(&config).await?;
+    let breakdown = crate::pinecone_client::namespace_breakdown(&config, &stats);
+    if let Some(map) = stats.as_object_mut() {
+        map.insert("by_type".to_string(), breakdown);
+    }
+    Ok(stats)
 }
 
 /// Index result for transcript embedding
@@ -2196,23 +4207,56 @@ pub async fn get_pinecone_stats(state: State<'_, AppState>) -> Result<serde_json
 pub struct TranscriptIndexResult {
     pub meeting_id: String,
     pub transcripts_indexed: usize,
+    /// Already indexed and unchanged
+    pub transcripts_skipped: usize,
     pub errors: Vec<String>,
 }
 
-/// Index all transcripts from a meeting to Pinecone
+/// Index all transcripts from a meeting to the configured vector store
 #[tauri::command(rename_all = "camelCase")]
 pub async fn index_meeting_transcripts(
     meeting_id: String,
+    only_edited: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<TranscriptIndexResult, String> {
-    // Get Pinecone config
-    let config = state.pinecone_client.read().get_config().ok_or(
-        "Pinecone not configured. Please configure Pinecone in Settings → Knowledge Base.",
+    let store = vector_store(&state).await.ok_or(
+        "Pinecone not configured. Please configure Pinecone or switch to local vectors in Settings → Knowledge Base.",
     )?;
 
-    // Get all transcripts for this meeting
-    let transcripts = state
+    // Archived meetings stay out of the knowledge base
+    if state
         .database
+        .is_meeting_archived(&meeting_id)
+        .await
+        .unwrap_or(false)
+    {
+        return Err("Meeting is archived; restore it before indexing".to_string());
+    }
+
+    index_meeting_into(
+        &state.database,
+        &store,
+        &meeting_id,
+        only_edited.unwrap_or(false),
+    )
+    .await
+}
+
+/// Reported in `TranscriptIndexResult::errors` for a meeting with nothing to index
+pub(crate) const NO_TRANSCRIPTS_ERROR: &str = "No transcripts found for this meeting";
+
+/// Index a meeting's final transcripts into `store`, skipping rows already
+/// indexed unless they were edited since
+pub(crate) async fn index_meeting_into(
+    database: &crate::database::DatabaseManager,
+    store: &crate::embeddings::VectorStore,
+    meeting_id: &str,
+    only_edited: bool,
+) -> Result<TranscriptIndexResult, String> {
+    let meeting_id = meeting_id.to_string();
+
+    // Get all transcripts for this meeting
+    let transcripts = database
         .get_transcripts(&meeting_id)
         .await
         .map_err(|e| format!("Failed to get transcripts: {}", e))?;
@@ -2221,18 +4265,42 @@ pub async fn index_meeting_transcripts(
         return Ok(TranscriptIndexResult {
             meeting_id,
             transcripts_indexed: 0,
-            errors: vec!["No transcripts found for this meeting".to_string()],
+            transcripts_skipped: 0,
+            errors: vec![NO_TRANSCRIPTS_ERROR.to_string()],
         });
     }
 
     // Get meeting info for metadata
-    let meeting_title = match state.database.get_meeting(&meeting_id).await {
+    let meeting_title = match database.get_meeting(&meeting_id).await {
         Ok(Some(m)) => m.title,
         _ => "Unknown Meeting".to_string(),
     };
 
+    // Corrected rows - re-upserting them replaces the stale vectors (ids are stable)
+    let edited_ids: std::collections::HashSet<i64> = database
+        .get_transcripts_needing_reindex(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get edited transcripts: {}", e))?
+        .into_iter()
+        .collect();
+
+    // Already-indexed rows are skipped unless edited, so re-runs are idempotent
+    let existing = store
+        .existing_ids(
+            crate::pinecone_client::VectorKind::Transcript,
+            &format!("transcript_{}_", meeting_id),
+        )
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to list indexed transcripts, re-indexing all: {}", e);
+            Default::default()
+        });
+
     let mut indexed = 0;
+    let mut skipped = 0;
     let mut errors = Vec::new();
+    let mut reindexed = Vec::new();
+    let mut pii_redactions = 0;
 
     // Batch transcripts for efficiency (group by 5 for embedding)
     for (i, transcript) in transcripts.iter().enumerate() {
@@ -2240,9 +4308,23 @@ pub async fn index_meeting_transcripts(
         if !transcript.is_final {
             continue;
         }
+        if only_edited && !edited_ids.contains(&transcript.id) {
+            continue;
+        }
 
         let id = format!("transcript_{}_{}", meeting_id, transcript.id);
-        let text = &transcript.text;
+        if existing.contains(&id) && !edited_ids.contains(&transcript.id) {
+            skipped += 1;
+            continue;
+        }
+
+        // PII never leaves the machine (local copy stays verbatim)
+        let redacted = crate::privacy_filter::redact_text(&transcript.text);
+        pii_redactions += redacted.redactions;
+        let text = match redacted.text {
+            Some(text) => text,
+            None => continue,
+        };
 
         // Build metadata for the vector
         let metadata = serde_json::json!({
@@ -2256,11 +4338,22 @@ pub async fn index_meeting_transcripts(
             "index": i,
         });
 
-        match crate::pinecone_client::pinecone_upsert_generic(&config, &id, text, &metadata).await {
+        match store
+            .upsert(
+                crate::pinecone_client::VectorKind::Transcript,
+                &id,
+                &text,
+                &metadata,
+            )
+            .await
+        {
             Ok(_) => {
                 indexed += 1;
+                if edited_ids.contains(&transcript.id) {
+                    reindexed.push(transcript.id);
+                }
                 if indexed % 10 == 0 {
-                    log::info!("📌 Indexed {} transcripts to Pinecone", indexed);
+                    log::info!("📌 Indexed {} transcripts to {}", indexed, store.name());
                 }
             }
             Err(e) => {
@@ -2272,15 +4365,32 @@ pub async fn index_meeting_transcripts(
         }
     }
 
+    if !reindexed.is_empty() {
+        if let Err(e) = database.mark_transcripts_reindexed(&reindexed).await {
+            errors.push(format!("Failed to clear reindex flags: {}", e));
+        }
+    }
+
+    if pii_redactions > 0 {
+        if let Err(e) = database
+            .add_meeting_pii_redactions(&meeting_id, pii_redactions)
+            .await
+        {
+            log::warn!("Failed to record PII redactions: {}", e);
+        }
+    }
+
     log::info!(
-        "✅ Indexed {} transcripts from meeting '{}' to Pinecone",
+        "✅ Indexed {} transcripts from meeting '{}' to {}",
         indexed,
-        meeting_title
+        meeting_title,
+        store.name()
     );
 
     Ok(TranscriptIndexResult {
         meeting_id,
         transcripts_indexed: indexed,
+        transcripts_skipped: skipped,
         errors,
     })
 }
@@ -2291,6 +4401,8 @@ pub async fn index_all_transcripts_to_pinecone(
     limit: Option<i32>,
     state: State<'_, AppState>,
 ) -> Result<Vec<TranscriptIndexResult>, String> {
+    use crate::pinecone_client::VectorKind;
+
     // Get Pinecone config
     let config = state.pinecone_client.read().get_config().ok_or(
         "Pinecone not configured. Please configure Pinecone in Settings → Knowledge Base.",
@@ -2315,6 +4427,7 @@ pub async fn index_all_transcripts_to_pinecone(
                 results.push(TranscriptIndexResult {
                     meeting_id: meeting_id.clone(),
                     transcripts_indexed: 0,
+                    transcripts_skipped: 0,
                     errors: vec![format!("Failed to get transcripts: {}", e)],
                 });
                 continue;
@@ -2327,14 +4440,37 @@ pub async fn index_all_transcripts_to_pinecone(
 
         let meeting_title = meeting.title.clone();
         let mut indexed = 0;
+        let mut skipped = 0;
         let mut errors = Vec::new();
+        let mut pii_redactions = 0;
+
+        let existing: std::collections::HashSet<String> =
+            crate::pinecone_client::pinecone_list_ids(
+                &config,
+                config.namespace_for(VectorKind::Transcript),
+                &format!("transcript_{}_", meeting_id),
+            )
+            .await
+            .map(|ids| ids.into_iter().collect())
+            .unwrap_or_default();
 
         for (i, transcript) in transcripts.iter().enumerate() {
             if !transcript.is_final {
                 continue;
             }
-
             let id = format!("transcript_{}_{}", meeting_id, transcript.id);
+            if existing.contains(&id) {
+                skipped += 1;
+                continue;
+            }
+
+            let redacted = crate::privacy_filter::redact_text(&transcript.text);
+            pii_redactions += redacted.redactions;
+            let text = match redacted.text {
+                Some(text) => text,
+                None => continue,
+            };
+
             let metadata = serde_json::json!({
                 "type": "transcript",
                 "meeting_id": meeting_id,
@@ -2342,14 +4478,15 @@ pub async fn index_all_transcripts_to_pinecone(
                 "transcript_id": transcript.id,
                 "speaker": transcript.speaker.as_deref().unwrap_or("Unknown"),
                 "timestamp": transcript.timestamp.to_rfc3339(),
-                "text": transcript.text,
+                "text": text,
                 "index": i,
             });
 
             match crate::pinecone_client::pinecone_upsert_generic(
                 &config,
+                VectorKind::Transcript,
                 &id,
-                &transcript.text,
+                &text,
                 &metadata,
             )
             .await
@@ -2359,6 +4496,16 @@ pub async fn index_all_transcripts_to_pinecone(
             }
         }
 
+        if pii_redactions > 0 {
+            if let Err(e) = state
+                .database
+                .add_meeting_pii_redactions(&meeting_id, pii_redactions)
+                .await
+            {
+                log::warn!("Failed to record PII redactions: {}", e);
+            }
+        }
+
         if indexed > 0 {
             log::info!(
                 "📌 Indexed {} transcripts from meeting '{}'",
@@ -2370,6 +4517,7 @@ pub async fn index_all_transcripts_to_pinecone(
         results.push(TranscriptIndexResult {
             meeting_id,
             transcripts_indexed: indexed,
+            transcripts_skipped: skipped,
             errors,
         });
     }
@@ -2571,7 +4719,19 @@ pub async fn set_capture_microphone(
         .settings
         .set_capture_microphone(enabled)
         .await
-        .map_err(|e| format!("Failed to save setting: {}", e))
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+
+    // Applies to the next recording; an in-progress one keeps its sources
+    let settings = state
+        .settings
+        .get_all()
+        .await
+        .map_err(|e| format!("Failed to get settings: {}", e))?;
+    state
+        .capture_engine
+        .read()
+        .set_audio_sources(enabled, settings.capture_system_audio);
+    Ok(())
 }
 
 /// Set capture system audio toggle
@@ -2584,9 +4744,150 @@ pub async fn set_capture_system_audio(
         .settings
         .set_capture_system_audio(enabled)
         .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+
+    // Applies to the next recording; an in-progress one keeps its sources
+    let settings = state
+        .settings
+        .get_all()
+        .await
+        .map_err(|e| format!("Failed to get settings: {}", e))?;
+    state
+        .capture_engine
+        .read()
+        .set_audio_sources(settings.capture_microphone, enabled);
+    Ok(())
+}
+
+/// Latest input levels per audio source (RMS/peak over the last ~200ms)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_audio_levels() -> Result<crate::audio_level::AudioLevels, String> {
+    Ok(crate::audio_level::audio_meter().levels())
+}
+
+/// Get the policy for warning when the microphone goes silent mid-recording
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_silence_warning_policy() -> Result<crate::audio_level::SilenceWarningPolicy, String>
+{
+    Ok(crate::audio_level::audio_meter().policy())
+}
+
+/// Save the silence warning policy; applies to the current recording immediately
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_silence_warning_policy(
+    policy: crate::audio_level::SilenceWarningPolicy,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    policy.validate()?;
+
+    state
+        .settings
+        .set_silence_warning(&policy)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+
+    crate::audio_level::audio_meter().set_policy(policy);
+    Ok(())
+}
+
+/// Set raw meeting audio archiving toggle (applies to the next recording)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_audio_recording_enabled(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings
+        .set_audio_recording_enabled(enabled)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))
+}
+
+/// Set archived audio format: "wav", or "aac" when ffmpeg is installed
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_audio_recording_format(
+    format: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if format != "wav" && format != "aac" {
+        return Err(format!("Unsupported audio format: {}", format));
+    }
+    state
+        .settings
+        .set_audio_recording_format(&format)
+        .await
         .map_err(|e| format!("Failed to save setting: {}", e))
 }
 
+/// Archived audio chunks of a meeting in playback order
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_audio_chunks(
+    meeting_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::database::AudioChunk>, String> {
+    state
+        .database
+        .get_audio_chunks(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get audio chunks: {}", e))
+}
+
+/// Locate the audio file and offset for a moment of a meeting (for rewind playback)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_audio_chunk_for_timestamp(
+    meeting_id: String,
+    timestamp_ms: i64,
+    state: State<'_, AppState>,
+) -> Result<Option<crate::audio_recorder::AudioChunkPosition>, String> {
+    let ts = chrono::DateTime::from_timestamp_millis(timestamp_ms)
+        .ok_or_else(|| format!("Invalid timestamp: {}", timestamp_ms))?;
+    crate::audio_recorder::chunk_for_timestamp(&state.database, &meeting_id, ts).await
+}
+
+/// Re-transcribe a meeting from its archived audio with another provider.
+/// Runs in the background and reports `retranscribe-progress` per chunk.
+/// `replace_existing` overwrites the meeting's transcripts when finished;
+/// otherwise the result is kept as a revision set alongside them.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn retranscribe_meeting(
+    app: AppHandle,
+    meeting_id: String,
+    provider: String,
+    replace_existing: bool,
+    state: State<'_, AppState>,
+) -> Result<crate::database::RetranscribeJob, String> {
+    let provider = ProviderType::parse(&provider)
+        .ok_or_else(|| format!("Unknown transcription provider: {}", provider))?;
+    crate::transcription::retranscribe::start(&app, &state, &meeting_id, provider, replace_existing)
+        .await
+}
+
+/// Re-transcriptions of a meeting, newest first
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_retranscribe_jobs(
+    meeting_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::database::RetranscribeJob>, String> {
+    state
+        .database
+        .get_retranscribe_jobs(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get re-transcription jobs: {}", e))
+}
+
+/// Segments produced by a re-transcription job, in spoken order
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_revision_set(
+    job_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::database::RetranscribedSegment>, String> {
+    state
+        .database
+        .get_retranscribed_segments(&job_id)
+        .await
+        .map_err(|e| format!("Failed to get revision set: {}", e))
+}
+
 /// Set capture screen toggle
 #[tauri::command(rename_all = "camelCase")]
 pub async fn set_capture_screen(enabled: bool, state: State<'_, AppState>) -> Result<(), String> {
@@ -2636,6 +4937,348 @@ pub async fn set_frame_capture_interval(
         .map_err(|e| format!("Failed to save setting: {}", e))
 }
 
+/// Configure the adaptive frame interval (stretches on low battery / high CPU)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_adaptive_frame_interval(
+    enabled: bool,
+    battery_threshold: u32,
+    cpu_budget_ms: u32,
+    max_factor: f32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if battery_threshold > 100 {
+        return Err("Battery threshold must be a percentage (0-100)".to_string());
+    }
+    if !(1.0..=10.0).contains(&max_factor) {
+        return Err("Max factor must be between 1 and 10".to_string());
+    }
+
+    state
+        .settings
+        .set_adaptive_frame_interval(enabled, battery_threshold, cpu_budget_ms, max_factor)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+
+    if !enabled {
+        let engine = state.capture_engine.read();
+        engine.set_interval_factor(1.0);
+    }
+    Ok(())
+}
+
+/// How often the adaptive frame interval re-checks battery and CPU
+const ADAPTIVE_INTERVAL_CHECK_SECS: u64 = 30;
+
+/// Background loop that stretches or restores the frame interval while capturing
+pub fn spawn_adaptive_interval_task(app: AppHandle) {
+    use crate::power_manager::{AdaptiveIntervalConfig, AdaptiveIntervalController};
+
+    tauri::async_runtime::spawn(async move {
+        let mut controller = AdaptiveIntervalController::new(AdaptiveIntervalConfig::default());
+        // (meeting_id, frames_in, cpu_time_ms) at the previous check
+        let mut last_sample: Option<(String, u64, u64)> = None;
+
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(ADAPTIVE_INTERVAL_CHECK_SECS)).await;
+
+            let Some(state) = app.try_state::<AppState>() else {
+                continue;
+            };
+            let Ok(settings) = state.settings.get_all().await else {
+                continue;
+            };
+            let (is_recording, base_interval_ms) = {
+                let engine = state.capture_engine.read();
+                (engine.get_status().is_recording, engine.frame_interval_ms())
+            };
+
+            let adjustment = if !settings.adaptive_frame_interval || !is_recording {
+                last_sample = None;
+                controller.reset(base_interval_ms)
+            } else {
+                controller.update_config(AdaptiveIntervalConfig {
+                    battery_threshold_pct: settings.adaptive_battery_threshold.min(100) as u8,
+                    cpu_budget_ms: settings.adaptive_cpu_budget_ms as f64,
+                    max_factor: settings.adaptive_max_factor,
+                });
+
+                // Average processing time per frame since the previous check
+                let cpu_ms_per_frame = state.metrics_collector.snapshot().and_then(|m| {
+                    let previous = last_sample
+                        .replace((m.meeting_id.clone(), m.frames_in, m.cpu_time_ms))
+                        .filter(|(id, _, _)| *id == m.meeting_id)?;
+                    let frames = m.frames_in.saturating_sub(previous.1);
+                    (frames > 0)
+                        .then(|| m.cpu_time_ms.saturating_sub(previous.2) as f64 / frames as f64)
+                });
+                let battery = tokio::task::spawn_blocking(crate::power_manager::battery_status)
+                    .await
+                    .ok()
+                    .flatten();
+
+                controller.evaluate(base_interval_ms, battery, cpu_ms_per_frame)
+            };
+
+            if let Some(adjustment) = adjustment {
+                {
+                    let engine = state.capture_engine.read();
+                    engine.set_interval_factor(adjustment.factor);
+                }
+                log::info!(
+                    "📺 Frame interval {}ms -> {}ms ({})",
+                    adjustment.base_interval_ms,
+                    adjustment.interval_ms,
+                    adjustment.reason
+                );
+                let _ = app.emit("capture-interval-adjusted", &adjustment);
+            }
+        }
+    });
+}
+
+/// Publish input levels at ~5 Hz and warn when the microphone goes dead mid-recording.
+/// Each warned-about silent stretch lands on the timeline and asks the user
+/// whether to keep recording.
+pub fn spawn_audio_level_task(app: AppHandle) {
+    use crate::audio_level::{audio_meter, SilenceTransition, SilenceWarning, LEVEL_TICK_MS};
+
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_millis(LEVEL_TICK_MS));
+
+        loop {
+            ticker.tick().await;
+
+            let Some(state) = app.try_state::<AppState>() else {
+                continue;
+            };
+            let mic_expected = state.capture_engine.read().is_capturing_mic();
+            let now = chrono::Utc::now();
+            let (levels, transition) = audio_meter().tick(now, mic_expected);
+
+            if levels.microphone.is_some() || levels.system.is_some() {
+                let _ = app.emit("audio-level", &levels);
+            }
+
+            match transition {
+                Some(SilenceTransition::Warn { since, silent_secs }) => {
+                    let policy = audio_meter().policy();
+                    let meeting_id = state.timeline_builder.current_meeting_id();
+                    log::warn!(
+                        "🎤 Microphone silent for {}s (below {:.0} dB)",
+                        silent_secs,
+                        policy.threshold_db
+                    );
+
+                    state
+                        .timeline_builder
+                        .record_silence(since, silent_secs, policy.threshold_db);
+                    if let Some(ref id) = meeting_id {
+                        state
+                            .interaction_loop
+                            .show_mic_silent_prompt(id, silent_secs);
+                    }
+
+                    let warning = SilenceWarning {
+                        meeting_id,
+                        since,
+                        silent_secs,
+                        threshold_db: policy.threshold_db,
+                        level_db: levels.microphone.map(|l| l.rms_db),
+                    };
+                    let _ = app.emit("silence-warning", &warning);
+                }
+                Some(SilenceTransition::Recovered { silent_secs, .. }) => {
+                    log::info!("🎤 Microphone input back after {}s", silent_secs);
+                    state.timeline_builder.end_silence(now);
+                }
+                None => {}
+            }
+        }
+    });
+}
+
+/// Seconds between ambient check-in ticks
+const CHECKIN_TICK_SECS: u64 = 15;
+
+/// Ask "still capturing?" after long stretches of ambient capture and apply
+/// the policy's default when the question goes unanswered
+pub fn spawn_checkin_task(app: AppHandle) {
+    use crate::interaction_loop::CheckInTransition;
+
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(CHECKIN_TICK_SECS));
+
+        loop {
+            ticker.tick().await;
+
+            let Some(state) = app.try_state::<AppState>() else {
+                continue;
+            };
+            let capturing = {
+                let engine = state.capture_engine.read();
+                engine.get_status().is_recording
+                    && engine.get_mode() == crate::capture_engine::CaptureMode::Ambient
+                    && !engine.is_suspended()
+            };
+            let now = chrono::Utc::now();
+            if let Some(CheckInTransition::TimedOut(prompt)) =
+                state.interaction_loop.tick_checkin(now, capturing)
+            {
+                let policy = state.interaction_loop.checkin_policy();
+                apply_checkin_decision(&app, &state, &prompt, policy.default_action, true).await;
+            }
+        }
+    });
+}
+
+/// Carry out a check-in decision. Automatic (timed-out) decisions are
+/// audited as "system" so time away from the keyboard can be reviewed.
+async fn apply_checkin_decision(
+    app: &AppHandle,
+    state: &AppState,
+    prompt: &crate::interaction_loop::PendingPrompt,
+    decision: crate::interaction_loop::CheckInDecision,
+    automatic: bool,
+) {
+    use crate::interaction_loop::CheckInDecision;
+
+    let outcome = match decision {
+        CheckInDecision::Continue => Ok(()),
+        CheckInDecision::Pause => {
+            let result = state.capture_engine.read().suspend_recording();
+            if result.is_ok() {
+                state.power_manager.release_assertion();
+                log::info!("⏸️ Ambient capture paused by check-in");
+            }
+            result
+        }
+    };
+    if let Err(ref e) = outcome {
+        log::warn!("Check-in {} failed: {}", decision.as_str(), e);
+    }
+
+    if automatic {
+        let policy = state.interaction_loop.checkin_policy();
+        let audit = crate::audit_log::AuditLog::new(state.database.get_pool().as_ref().clone());
+        let _ = audit
+            .log_action_as(
+                crate::audit_log::AuditAction {
+                    action: "checkin_timeout".to_string(),
+                    target_type: "ambient_capture".to_string(),
+                    target_id: prompt.id.clone(),
+                    details: Some(
+                        serde_json::json!({
+                            "decision": decision.as_str(),
+                            "asked_at": prompt.created_at,
+                            "timeout_secs": policy.timeout_secs,
+                            "error": outcome.as_ref().err(),
+                        })
+                        .to_string(),
+                    ),
+                    bytes_affected: 0,
+                },
+                "system",
+            )
+            .await;
+    }
+
+    let _ = app.emit(
+        "interaction-checkin-resolved",
+        serde_json::json!({
+            "prompt_id": prompt.id,
+            "decision": decision,
+            "automatic": automatic,
+        }),
+    );
+}
+
+/// Answer the open ambient capture check-in ("continue" or "pause")
+#[tauri::command(rename_all = "camelCase")]
+pub async fn respond_to_checkin(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    decision: crate::interaction_loop::CheckInDecision,
+) -> Result<(), String> {
+    let prompt = state.interaction_loop.answer_checkin(decision)?;
+    apply_checkin_decision(&app, &state, &prompt, decision, false).await;
+    Ok(())
+}
+
+/// Get the ambient capture check-in policy
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_checkin_policy(
+    state: State<'_, AppState>,
+) -> Result<crate::interaction_loop::CheckInPolicy, String> {
+    Ok(state.interaction_loop.checkin_policy())
+}
+
+/// Save the check-in policy; applies to the running capture immediately
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_checkin_policy(
+    policy: crate::interaction_loop::CheckInPolicy,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    policy.validate()?;
+
+    state
+        .settings
+        .set_check_in_policy(&policy)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+
+    state.interaction_loop.set_checkin_policy(policy);
+    Ok(())
+}
+
+/// Set automatic meeting title generation toggle
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_auto_title_meetings(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings
+        .set_auto_title_meetings(enabled)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))
+}
+
+/// Set embedding-based topic segmentation toggle
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_semantic_topics_enabled(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings
+        .set_semantic_topics_enabled(enabled)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))
+}
+
+/// Set the minimum episode duration that gets an AI summary on stop
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_episode_summary_min_secs(
+    secs: u32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings
+        .set_episode_summary_min_secs(secs)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))
+}
+
+/// Regenerate the AI summary of one episode (ignores the minimum duration)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn regenerate_episode_summary(
+    episode_id: String,
+    state: State<'_, AppState>,
+) -> Result<crate::meeting_notes::EpisodeSummary, String> {
+    let ai_client = state.ai_client.read().clone();
+    crate::meeting_notes::summarize_episode(&state.database, ai_client, &episode_id).await
+}
+
 // ============================================
 // Knowledge Base Configuration Commands
 // ============================================
@@ -2674,13 +5317,121 @@ pub async fn configure_knowledge_base(
             .await
             .map_err(|e| format!("Failed to save Pinecone host: {}", e))?;
         // Configure Pinecone client
+        let namespaces = pinecone_namespaces(&state, None).await;
         state
             .pinecone_client
             .read()
-            .configure(key.clone(), host.clone(), None);
+            .configure(key.clone(), host.clone(), None, namespaces);
+    }
+
+    Ok(())
+}
+
+/// Vector store for the configured backend (Pinecone when unset or unrecognized)
+pub(crate) async fn vector_store(state: &AppState) -> Option<crate::embeddings::VectorStore> {
+    let pinecone = state.pinecone_client.read().get_config();
+    let ai_client = state.ai_client.read().clone();
+    crate::embeddings::VectorStore::configured(
+        &state.settings,
+        pinecone,
+        state.database.clone(),
+        ai_client,
+    )
+    .await
+}
+
+/// Set where semantic search vectors live ("pinecone" or "local")
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_vector_backend(backend: String, state: State<'_, AppState>) -> Result<(), String> {
+    let backend = crate::embeddings::VectorBackend::parse(&backend).ok_or_else(|| {
+        format!(
+            "Unknown vector backend: {} (use pinecone or local)",
+            backend
+        )
+    })?;
+
+    state
+        .settings
+        .set_vector_backend(backend.as_str())
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))
+}
+
+/// Progress of a local transcript indexing run
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LocalIndexProgress {
+    pub indexed: usize,
+    pub total: usize,
+}
+
+/// Embed final transcripts of all non-archived meetings into the local vector
+/// store, skipping ones already indexed. Emits "local-index-progress" per batch.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn index_transcripts_locally(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    const BATCH_SIZE: usize = 32;
+
+    let existing: std::collections::HashSet<String> = state
+        .database
+        .get_local_vector_ids(crate::ai_client::EMBEDDING_MODEL)
+        .await
+        .map_err(|e| format!("Failed to get local vectors: {}", e))?
+        .into_iter()
+        .collect();
+    let meetings = state
+        .database
+        .list_meetings(i32::MAX)
+        .await
+        .map_err(|e| format!("Failed to list meetings: {}", e))?;
+
+    let mut pending = Vec::new();
+    for meeting in meetings {
+        let transcripts = state
+            .database
+            .get_transcripts(&meeting.id)
+            .await
+            .map_err(|e| format!("Failed to get transcripts: {}", e))?;
+        for (i, transcript) in transcripts.iter().enumerate() {
+            if !transcript.is_final {
+                continue;
+            }
+            let id = format!("transcript_{}_{}", meeting.id, transcript.id);
+            if existing.contains(&id) {
+                continue;
+            }
+            let metadata = serde_json::json!({
+                "type": "transcript",
+                "meeting_id": meeting.id,
+                "meeting_title": meeting.title,
+                "transcript_id": transcript.id,
+                "speaker": transcript.speaker.as_deref().unwrap_or("Unknown"),
+                "timestamp": transcript.timestamp.to_rfc3339(),
+                "text": transcript.text,
+                "index": i,
+            });
+            pending.push((id, transcript.text.clone(), metadata));
+        }
     }
 
-    Ok(())
+    let store = crate::embeddings::LocalVectorStore::new(
+        state.database.clone(),
+        state.ai_client.read().clone(),
+    );
+    let total = pending.len();
+    let mut indexed = 0;
+    for batch in pending.chunks(BATCH_SIZE) {
+        store.upsert_batch(batch).await?;
+        indexed += batch.len();
+        let _ = app.emit(
+            "local-index-progress",
+            &LocalIndexProgress { indexed, total },
+        );
+    }
+
+    log::info!("✅ Indexed {} transcripts into local vectors", indexed);
+    Ok(indexed)
 }
 
 /// Get all capture settings
@@ -2692,9 +5443,13 @@ pub async fn get_capture_settings(state: State<'_, AppState>) -> Result<serde_js
         .await
         .map_err(|e| format!("Failed to get settings: {}", e))?;
 
+    let system_audio = crate::capture_engine::CaptureEngine::system_audio_status();
+
     Ok(serde_json::json!({
         "capture_microphone": settings.capture_microphone,
         "capture_system_audio": settings.capture_system_audio,
+        "system_audio_available": system_audio.available,
+        "system_audio_unavailable_reason": system_audio.reason,
         "capture_screen": settings.capture_screen,
         "always_on_capture": settings.always_on_capture,
         "queue_frames_for_vlm": settings.queue_frames_for_vlm,
@@ -2716,9 +5471,11 @@ pub struct AnalysisResult {
     pub frames_processed: usize,
     pub activities_created: usize,
     pub errors: Vec<String>,
+    #[serde(default)]
+    pub cancelled: bool,
 }
 
-/// Analyze pending frames with VLM
+/// Analyze pending frames with VLM (bounded concurrency, cancellable)
 #[tauri::command(rename_all = "camelCase")]
 pub async fn analyze_pending_frames(
     limit: Option<i32>,
@@ -2726,165 +5483,56 @@ pub async fn analyze_pending_frames(
 ) -> Result<AnalysisResult, String> {
     let limit = limit.unwrap_or(10);
 
-    // Check if VLM is available
-    if !crate::vlm_client::vlm_is_available().await {
-        return Err("VLM API is not available. Please check SSH tunnel and token.".to_string());
-    }
+    let result = state.vlm_scheduler.run_manual_batch(limit).await?;
 
-    // Get pending frames
-    let pending = state
-        .database
-        .get_pending_frames(limit)
-        .await
-        .map_err(|e| format!("Failed to get pending frames: {}", e))?;
+    log::info!(
+        "🔍 VLM Analysis: {} frames processed, {} activities created{}",
+        result.frames_processed,
+        result.activities_created,
+        if result.cancelled { " (cancelled)" } else { "" }
+    );
 
-    if pending.is_empty() {
-        return Ok(AnalysisResult {
-            frames_processed: 0,
-            activities_created: 0,
-            errors: vec![],
-        });
-    }
+    Ok(AnalysisResult {
+        frames_processed: result.frames_processed,
+        activities_created: result.activities_created,
+        errors: result.errors,
+        cancelled: result.cancelled,
+    })
+}
 
-    // Get active theme and prompt (with fallback logic similar to Scheduler)
-    let mut frames_processed = 0;
-    let mut activities_created = 0;
-    let mut errors = Vec::new();
+/// Cancel the running VLM batch; frames already analyzed are kept
+#[tauri::command(rename_all = "camelCase")]
+pub async fn cancel_vlm_analysis(state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(state.vlm_scheduler.cancel_batch())
+}
 
-    let active_theme = state
+/// Set how many frames are sent to the VLM concurrently
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_vlm_concurrency(
+    concurrency: u32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
         .settings
-        .get_active_theme()
+        .set_vlm_concurrency(concurrency)
         .await
-        .unwrap_or_else(|_| "prospecting".to_string());
-    let prompt_key = format!("{}_context_analysis", active_theme);
-
-    let prompt = match state.prompt_manager.get_prompt(&prompt_key).await {
-        Ok(Some(p)) => p.system_prompt,
-        Ok(None) => {
-            // Try fallback to generic frame_analysis
-            match state.prompt_manager.get_prompt("frame_analysis").await {
-                Ok(Some(p)) => p.system_prompt,
-                _ => {
-                    // Hard fallback
-                    r#"Analyze this screenshot and describe what the user is doing. 
-                    Respond in JSON format with these fields:
-                    {
-                      "app_name": "name of the main application visible",
-                      "window_title": "title of the window or document",
-                      "category": "one of: development, communication, research, writing, design, media, browsing, system, other",
-                      "summary": "brief description of what the user is doing",
-                      "focus_area": "specific task or project",
-                      "visible_files": [],
-                      "confidence": 0.8
-                    }
-                    Only respond with valid JSON."#.to_string()
-                }
-            }
-        }
-        Err(e) => {
-            return Err(format!("Failed to retrieve prompt: {}", e));
-        }
-    };
-
-    for frame in pending {
-        // Analyze frame with VLM (standalone function)
-        match crate::vlm_client::vlm_analyze_frame(&frame.frame_path, &prompt).await {
-            Ok(context) => {
-                frames_processed += 1;
-
-                // Create activity log entry
-                let activity = ActivityLogEntry {
-                    id: None,
-                    start_time: frame.captured_at,
-                    end_time: None,
-                    duration_seconds: None,
-                    app_name: context.app_name,
-                    window_title: context.window_title,
-                    category: context.category,
-                    summary: context.summary,
-                    focus_area: context.focus_area,
-                    visible_files: if context.visible_files.is_empty() {
-                        None
-                    } else {
-                        Some(context.visible_files.join(", "))
-                    },
-                    confidence: Some(context.confidence),
-                    frame_ids: Some(frame.id.to_string()),
-                    pinecone_id: None,
-                    supabase_id: None,
-                    synced_at: None,
-                };
-
-                // Store in activity_log
-                match state.database.add_activity(&activity).await {
-                    Ok(activity_id) => {
-                        activities_created += 1;
-
-                        // Phase 3: Extract and store entities (Identical logic to Scheduler)
-                        if let Some(entities_json) = context.entities {
-                            if let Some(obj) = entities_json.as_object() {
-                                for (entity_type, list) in obj {
-                                    if let Some(items) = list.as_array() {
-                                        for item in items {
-                                            if let Some(name) =
-                                                item.get("name").and_then(|s| s.as_str())
-                                            {
-                                                let conf = item
-                                                    .get("confidence")
-                                                    .and_then(|c| c.as_f64())
-                                                    .or_else(|| {
-                                                        item.get("confidence")
-                                                            .and_then(|s| s.as_str().map(|_| 0.8))
-                                                    })
-                                                    .map(|f| f as f32)
-                                                    .unwrap_or(context.confidence);
-
-                                                let _ = state
-                                                    .database
-                                                    .add_entity(
-                                                        activity_id,
-                                                        entity_type,
-                                                        name,
-                                                        Some(item),
-                                                        conf,
-                                                        Some(&active_theme),
-                                                    )
-                                                    .await;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
 
-                        // Mark frame as analyzed
-                        let _ = state.database.mark_frame_analyzed(frame.id).await;
-                    }
-                    Err(e) => {
-                        errors.push(format!("Failed to store activity: {}", e));
-                    }
-                }
-            }
-            Err(e) => {
-                errors.push(format!(
-                    "VLM analysis failed for {}: {}",
-                    frame.frame_path, e
-                ));
-            }
-        }
-    }
+    state.vlm_scheduler.set_concurrency(concurrency);
+    Ok(())
+}
 
-    log::info!(
-        "🔍 VLM Analysis: {} frames processed, {} activities created",
-        frames_processed,
-        activities_created
-    );
+/// Set how many frames are packed into one VLM request (1 disables batching)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_vlm_batch_size(batch_size: u32, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .settings
+        .set_vlm_batch_size(batch_size)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
 
-    Ok(AnalysisResult {
-        frames_processed,
-        activities_created,
-        errors,
-    })
+    state.vlm_scheduler.set_batch_size(batch_size);
+    Ok(())
 }
 
 /// Get pending frame count
@@ -2958,99 +5606,40 @@ pub async fn sync_to_cloud(
         });
     }
 
-    // Check configuration status upfront and get configs (drop guards immediately)
+    // Get configs up front (drop guards immediately)
     let pinecone_config = state.pinecone_client.read().get_config();
-    let _pinecone_configured = pinecone_config.is_some();
     let supabase_pool = state.supabase_client.read().get_pool();
-    let _supabase_connected = supabase_pool.is_some();
 
     let mut activities_synced = 0;
     let mut pinecone_upserts = 0;
     let mut supabase_inserts = 0;
     let mut errors = Vec::new();
+    let mut pii_redactions = 0;
 
     for activity in activities {
-        let activity_id = match &activity.id {
-            Some(id) => *id,
-            None => continue,
-        };
-
-        let mut pinecone_id: Option<String> = None;
-        let mut supabase_id: Option<String> = None;
-
-        // Sync to Pinecone (if configured)
-        if let Some(ref config) = pinecone_config {
-            let id = format!("activity_{}", activity_id);
-            let text = format!(
-                "{} - {} - {}",
-                activity.category,
-                activity.summary,
-                activity.focus_area.as_deref().unwrap_or("")
-            );
-
-            let metadata = ActivityMetadata {
-                timestamp: activity.start_time.to_rfc3339(),
-                category: activity.category.clone(),
-                app_name: activity.app_name.clone(),
-                focus_area: activity.focus_area.clone(),
-                summary: activity.summary.clone(),
-            };
-
-            // Use standalone function (no guard held across await)
-            match crate::pinecone_client::pinecone_upsert(config, &id, &text, &metadata).await {
-                Ok(_) => {
-                    pinecone_id = Some(id);
-                    pinecone_upserts += 1;
-                }
-                Err(e) => {
-                    errors.push(format!("Pinecone sync failed: {}", e));
-                }
-            }
-        }
-
-        // Sync to Supabase (if connected)
-        if let Some(ref pool) = supabase_pool {
-            let supabase_activity = Activity {
-                id: None,
-                start_time: activity.start_time,
-                end_time: activity.end_time,
-                duration_seconds: activity.duration_seconds,
-                app_name: activity.app_name.clone(),
-                window_title: activity.window_title.clone(),
-                category: activity.category.clone(),
-                summary: activity.summary.clone(),
-                focus_area: activity.focus_area.clone(),
-                pinecone_id: pinecone_id.clone(),
-                created_at: None,
-            };
-
-            // Use standalone function (no guard held across await)
-            match crate::supabase_client::supabase_insert_activity(pool, &supabase_activity).await {
-                Ok(id) => {
-                    supabase_id = Some(id);
-                    supabase_inserts += 1;
-                }
-                Err(e) => {
-                    errors.push(format!("Supabase sync failed: {}", e));
-                }
-            }
-        }
+        let outcome = crate::sync_worker::sync_activity(
+            &state.database,
+            pinecone_config.as_ref(),
+            supabase_pool.as_ref(),
+            activity,
+        )
+        .await;
 
-        // Mark as synced if at least one succeeded
-        if pinecone_id.is_some() || supabase_id.is_some() {
-            let _ = state
-                .database
-                .mark_activity_synced(activity_id, pinecone_id.as_deref(), supabase_id.as_deref())
-                .await;
+        pii_redactions += outcome.pii_redactions;
+        pinecone_upserts += outcome.pinecone_upserted as usize;
+        supabase_inserts += outcome.supabase_inserted as usize;
+        if outcome.pinecone_upserted || outcome.supabase_inserted {
             activities_synced += 1;
         }
+        errors.extend(outcome.errors);
     }
 
     log::info!(
-        "☁️ Cloud Sync: {} activities synced ({} Pinecone, {} Supabase)",
+        "☁️ Cloud Sync: {} activities synced ({} Pinecone, {} Supabase, {} PII redactions)",
         activities_synced,
         pinecone_upserts,
-        supabase_inserts
+        supabase_inserts,
+        pii_redactions
     );
 
     Ok(SyncResult {
@@ -3061,6 +5650,69 @@ pub async fn sync_to_cloud(
     })
 }
 
+/// Background sync worker status (queue depth, last error, last success)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_sync_worker_status(
+    state: State<'_, AppState>,
+) -> Result<crate::sync_worker::SyncWorkerStatus, String> {
+    Ok(state.sync_worker.get_status().await)
+}
+
+/// Start the background sync worker (persisted across restarts)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn start_sync_worker(state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .settings
+        .set_sync_worker_enabled(true)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+    state.sync_worker.start();
+    Ok(())
+}
+
+/// Stop the background sync worker; queued jobs wait until it is started again
+#[tauri::command(rename_all = "camelCase")]
+pub async fn stop_sync_worker(state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .settings
+        .set_sync_worker_enabled(false)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+    state.sync_worker.stop();
+    Ok(())
+}
+
+/// Give parked sync jobs a fresh set of attempts
+#[tauri::command(rename_all = "camelCase")]
+pub async fn retry_parked_sync_jobs(state: State<'_, AppState>) -> Result<u64, String> {
+    let revived = state
+        .database
+        .unpark_sync_jobs()
+        .await
+        .map_err(|e| format!("Failed to retry sync jobs: {}", e))?;
+    state.sync_worker.wake();
+    Ok(revived)
+}
+
+/// Queue meetings for transcript indexing by the sync worker
+#[tauri::command(rename_all = "camelCase")]
+pub async fn queue_transcript_index(
+    meeting_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    for meeting_id in &meeting_ids {
+        state
+            .sync_worker
+            .enqueue(
+                crate::sync_worker::SyncJobKind::TranscriptIndex,
+                meeting_id,
+                None,
+            )
+            .await?;
+    }
+    Ok(())
+}
+
 // ============================================
 // Search Commands (Phase 6)
 // ============================================
@@ -3075,6 +5727,9 @@ pub struct KBSearchResult {
     pub category: Option<String>,
     pub summary: String,
     pub score: Option<f32>,
+    /// Every backend that returned this record
+    #[serde(default)]
+    pub match_sources: Vec<String>,
 }
 
 /// Search options
@@ -3086,105 +5741,101 @@ pub struct SearchOptions {
     pub category: Option<String>,     // Filter by category
     pub limit: Option<u32>,           // Max results
     pub sources: Option<Vec<String>>, // ["local", "pinecone", "supabase"]
+    #[serde(default)]
+    pub search_mode: Option<String>, // "local", "semantic" or "hybrid"
 }
 
-/// Combined search across local SQLite, Pinecone, and Supabase
+/// Time spent in each backend, for diagnostics
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct SearchLatency {
+    pub local_ms: Option<u64>,
+    pub semantic_ms: Option<u64>,
+    pub supabase_ms: Option<u64>,
+}
+
+/// Fused knowledge base search results
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct KBSearchResponse {
+    pub results: Vec<KBSearchResult>,
+    pub search_mode: String,
+    pub latency: SearchLatency,
+}
+
+/// Combined search across local SQLite, Pinecone, and Supabase.
+/// Local full-text and Pinecone results are fused by reciprocal rank.
 #[tauri::command(rename_all = "camelCase")]
 pub async fn search_knowledge_base(
     options: SearchOptions,
     state: State<'_, AppState>,
-) -> Result<Vec<KBSearchResult>, String> {
-    let mut results = Vec::new();
-    let limit = options.limit.unwrap_or(20) as i32;
-    let sources = options
-        .sources
-        .clone()
-        .unwrap_or_else(|| vec!["local".to_string()]);
-
-    // Search local SQLite activity_log
-    if sources.contains(&"local".to_string()) {
-        let local_activities = state
-            .database
-            .get_activities_filtered(
-                options.start_date.as_deref(),
-                options.end_date.as_deref(),
-                options.category.as_deref(),
-                limit,
-            )
-            .await
-            .unwrap_or_default();
-
-        for activity in local_activities {
-            // Filter by query if provided (simple text match)
-            if let Some(ref query) = options.query {
-                let query_lower = query.to_lowercase();
-                let matches = activity.summary.to_lowercase().contains(&query_lower)
-                    || activity.category.to_lowercase().contains(&query_lower)
-                    || activity
-                        .focus_area
-                        .as_ref()
-                        .map(|f| f.to_lowercase().contains(&query_lower))
-                        .unwrap_or(false);
-                if !matches {
-                    continue;
-                }
-            }
+) -> Result<KBSearchResponse, String> {
+    use crate::hybrid_search::{fuse, RankedList, SearchMode};
 
-            results.push(KBSearchResult {
-                id: activity.id.map(|i| i.to_string()).unwrap_or_default(),
-                source: "local".to_string(),
-                timestamp: Some(activity.start_time.to_rfc3339()),
-                app_name: activity.app_name,
-                category: Some(activity.category),
-                summary: activity.summary,
-                score: activity.confidence,
-            });
+    let limit = options.limit.unwrap_or(20) as i32;
+    let sources = options.sources.clone();
+    let store = vector_store(&state).await;
+
+    // An explicit mode wins; otherwise infer it from the legacy source list,
+    // and default to hybrid whenever a vector store is configured
+    let listed = |name: &str| sources.as_ref().map(|s| s.iter().any(|x| x == name));
+    let mode = match options.search_mode.as_deref() {
+        Some(mode) => {
+            SearchMode::parse(mode).ok_or_else(|| format!("Unknown search mode: {}", mode))?
         }
-    }
+        None => match (listed("local"), listed("pinecone")) {
+            (Some(true), Some(true)) => SearchMode::Hybrid,
+            (Some(false), Some(true)) => SearchMode::Semantic,
+            (Some(_), _) => SearchMode::Local,
+            (None, _) if store.is_some() => SearchMode::Hybrid,
+            (None, _) => SearchMode::Local,
+        },
+    };
 
-    // Semantic search via Pinecone
-    if sources.contains(&"pinecone".to_string()) {
-        if let Some(ref query) = options.query {
-            let config = state.pinecone_client.read().get_config();
-            if let Some(config) = config {
-                if let Ok(matches) =
-                    crate::pinecone_client::pinecone_search(&config, query, limit as u32).await
-                {
-                    for m in matches {
-                        results.push(KBSearchResult {
-                            id: m.id,
-                            source: "pinecone".to_string(),
-                            timestamp: m.metadata.as_ref().and_then(|m| {
-                                m.get("timestamp")
-                                    .and_then(|v| v.as_str().map(|s| s.to_string()))
-                            }),
-                            app_name: m.metadata.as_ref().and_then(|m| {
-                                m.get("app_name")
-                                    .and_then(|v| v.as_str().map(|s| s.to_string()))
-                            }),
-                            category: m.metadata.as_ref().and_then(|m| {
-                                m.get("category")
-                                    .and_then(|v| v.as_str().map(|s| s.to_string()))
-                            }),
-                            summary: m
-                                .metadata
-                                .as_ref()
-                                .and_then(|m| {
-                                    m.get("summary")
-                                        .or_else(|| m.get("text"))
-                                        .and_then(|v| v.as_str().map(|s| s.to_string()))
-                                })
-                                .unwrap_or_default(),
-                            score: Some(m.score),
-                        });
-                    }
-                }
-            }
+    let local = async {
+        if !mode.uses_local() {
+            return None;
+        }
+        let started = std::time::Instant::now();
+        let lists = local_kb_search(&state, &options, limit).await;
+        Some((lists, started.elapsed().as_millis() as u64))
+    };
+    let semantic = async {
+        let (Some(query), Some(store)) = (options.query.as_deref(), store.as_ref()) else {
+            return None;
+        };
+        if !mode.uses_semantic() {
+            return None;
         }
+        let started = std::time::Instant::now();
+        let hits = match store.search(query, limit as u32).await {
+            Ok(matches) => matches
+                .into_iter()
+                .map(|m| pinecone_kb_hit(m, store.name()))
+                .collect(),
+            Err(e) => {
+                log::warn!("Semantic search failed: {}", e);
+                Vec::new()
+            }
+        };
+        Some((hits, store.name(), started.elapsed().as_millis() as u64))
+    };
+    let (local, semantic) = tokio::join!(local, semantic);
+
+    let mut latency = SearchLatency::default();
+    let mut lists = Vec::new();
+    if let Some((local_lists, ms)) = local {
+        latency.local_ms = Some(ms);
+        lists.extend(local_lists.into_iter().map(|hits| RankedList {
+            backend: "local",
+            hits,
+        }));
+    }
+    if let Some((hits, backend, ms)) = semantic {
+        latency.semantic_ms = Some(ms);
+        lists.push(RankedList { backend, hits });
     }
 
     // Time-based query via Supabase
-    if sources.contains(&"supabase".to_string()) {
+    if listed("supabase") == Some(true) {
         if let (Some(ref start), Some(ref end)) = (&options.start_date, &options.end_date) {
             use chrono::{DateTime, Utc};
             if let (Ok(start), Ok(end)) =
@@ -3192,46 +5843,185 @@ pub async fn search_knowledge_base(
             {
                 let pool = state.supabase_client.read().get_pool();
                 if let Some(pool) = pool {
+                    let started = std::time::Instant::now();
                     if let Ok(activities) =
                         crate::supabase_client::supabase_query_activities(&pool, start, end).await
                     {
-                        for activity in activities {
+                        let hits = activities
+                            .into_iter()
                             // Filter by category if provided
-                            if let Some(ref cat) = options.category {
-                                if activity.category != *cat {
-                                    continue;
-                                }
-                            }
-
-                            results.push(KBSearchResult {
-                                id: activity.id.unwrap_or_default(),
-                                source: "supabase".to_string(),
-                                timestamp: Some(activity.start_time.to_rfc3339()),
-                                app_name: activity.app_name,
-                                category: Some(activity.category),
-                                summary: activity.summary,
-                                score: None,
-                            });
-                        }
+                            .filter(|a| {
+                                options.category.as_ref().map_or(true, |c| a.category == *c)
+                            })
+                            .map(|activity| {
+                                let id = activity.id.unwrap_or_default();
+                                (
+                                    format!("supabase:{}", id),
+                                    KBSearchResult {
+                                        id,
+                                        source: "supabase".to_string(),
+                                        timestamp: Some(activity.start_time.to_rfc3339()),
+                                        app_name: activity.app_name,
+                                        category: Some(activity.category),
+                                        summary: activity.summary,
+                                        score: None,
+                                        match_sources: Vec::new(),
+                                    },
+                                )
+                            })
+                            .collect();
+                        lists.push(RankedList {
+                            backend: "supabase",
+                            hits,
+                        });
                     }
+                    latency.supabase_ms = Some(started.elapsed().as_millis() as u64);
                 }
             }
         }
     }
 
-    // Sort by score (Pinecone results first), then by timestamp
-    results.sort_by(|a, b| match (&b.score, &a.score) {
-        (Some(bs), Some(as_)) => bs.partial_cmp(as_).unwrap_or(std::cmp::Ordering::Equal),
-        (Some(_), None) => std::cmp::Ordering::Less,
-        (None, Some(_)) => std::cmp::Ordering::Greater,
-        (None, None) => b.timestamp.cmp(&a.timestamp),
-    });
+    let results: Vec<KBSearchResult> = fuse(lists)
+        .into_iter()
+        .take(limit as usize)
+        .map(|hit| KBSearchResult {
+            score: Some(hit.score),
+            match_sources: hit.match_sources,
+            ..hit.item
+        })
+        .collect();
+
+    log::info!(
+        "🔍 Knowledge base search ({}): {} results",
+        mode.as_str(),
+        results.len()
+    );
+    Ok(KBSearchResponse {
+        results,
+        search_mode: mode.as_str().to_string(),
+        latency,
+    })
+}
 
-    // Limit results
-    results.truncate(limit as usize);
+/// Local ranked lists for a knowledge base search: full-text hits over
+/// transcripts/snapshots/OCR, and matching activity log entries
+async fn local_kb_search(
+    state: &AppState,
+    options: &SearchOptions,
+    limit: i32,
+) -> Vec<Vec<(String, KBSearchResult)>> {
+    let mut lists = Vec::new();
+
+    if let Some(query) = options.query.as_deref().filter(|q| !q.trim().is_empty()) {
+        let in_range = |ts: &chrono::DateTime<chrono::Utc>| {
+            let ts = ts.to_rfc3339();
+            options.start_date.as_ref().map_or(true, |s| ts >= *s)
+                && options.end_date.as_ref().map_or(true, |e| ts <= *e)
+        };
+        match state
+            .database
+            .search_everything(query, &crate::database::SearchSource::ALL, limit as i64)
+            .await
+        {
+            Ok(hits) => lists.push(
+                hits.into_iter()
+                    .filter(|h| in_range(&h.timestamp))
+                    .map(|h| {
+                        let key = match h.source {
+                            crate::database::SearchSource::Ocr => format!("frame:{}", h.id),
+                            source => format!("{}:{}", source.as_str(), h.id),
+                        };
+                        (
+                            key,
+                            KBSearchResult {
+                                id: h.id,
+                                source: "local".to_string(),
+                                timestamp: Some(h.timestamp.to_rfc3339()),
+                                app_name: None,
+                                category: Some(h.source.as_str().to_string()),
+                                summary: h.snippet,
+                                score: Some(h.score as f32),
+                                match_sources: Vec::new(),
+                            },
+                        )
+                    })
+                    .collect(),
+            ),
+            Err(e) => log::warn!("Local full-text search failed: {}", e),
+        }
+    }
 
-    log::info!("🔍 Knowledge base search: {} results", results.len());
-    Ok(results)
+    // Search local SQLite activity_log
+    let local_activities = state
+        .database
+        .get_activities_filtered(
+            options.start_date.as_deref(),
+            options.end_date.as_deref(),
+            options.category.as_deref(),
+            limit,
+        )
+        .await
+        .unwrap_or_default();
+
+    let query_lower = options.query.as_ref().map(|q| q.to_lowercase());
+    let activities = local_activities
+        .into_iter()
+        // Filter by query if provided (simple text match)
+        .filter(|activity| {
+            query_lower.as_ref().map_or(true, |q| {
+                activity.summary.to_lowercase().contains(q)
+                    || activity.category.to_lowercase().contains(q)
+                    || activity
+                        .focus_area
+                        .as_ref()
+                        .map(|f| f.to_lowercase().contains(q))
+                        .unwrap_or(false)
+            })
+        })
+        .map(|activity| {
+            let id = activity.id.map(|i| i.to_string()).unwrap_or_default();
+            (
+                format!("activity:{}", id),
+                KBSearchResult {
+                    id,
+                    source: "local".to_string(),
+                    timestamp: Some(activity.start_time.to_rfc3339()),
+                    app_name: activity.app_name,
+                    category: Some(activity.category),
+                    summary: activity.summary,
+                    score: activity.confidence,
+                    match_sources: Vec::new(),
+                },
+            )
+        })
+        .collect();
+    lists.push(activities);
+
+    lists
+}
+
+/// Knowledge base hit for a vector store match, keyed to dedupe against local records
+fn pinecone_kb_hit(m: VectorMatch, backend: &str) -> (String, KBSearchResult) {
+    let field = |name: &str| {
+        m.metadata
+            .as_ref()
+            .and_then(|meta| meta.get(name))
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+    };
+    let key = crate::hybrid_search::pinecone_key(&m.id, m.metadata.as_ref());
+    let result = KBSearchResult {
+        timestamp: field("timestamp"),
+        app_name: field("app_name"),
+        category: field("category").or_else(|| field("type")),
+        summary: field("summary")
+            .or_else(|| field("text"))
+            .unwrap_or_default(),
+        score: Some(m.score),
+        id: m.id,
+        source: backend.to_string(),
+        match_sources: Vec::new(),
+    };
+    (key, result)
 }
 
 /// Quick semantic search (just Pinecone)
@@ -3248,8 +6038,11 @@ pub async fn quick_semantic_search(
         category: None,
         limit,
         sources: Some(vec!["pinecone".to_string()]),
+        search_mode: Some("semantic".to_string()),
     };
-    search_knowledge_base(options, state).await
+    search_knowledge_base(options, state)
+        .await
+        .map(|response| response.results)
 }
 
 /// Get local activity history (from activity_log)
@@ -3336,16 +6129,62 @@ pub async fn export_data(state: State<'_, AppState>) -> Result<String, String> {
         }));
     }
 
-    // Get activity log
-    let activities = state
+    // Get activity log
+    let activities = state
+        .database
+        .get_activities_filtered(None, None, None, 1000)
+        .await
+        .unwrap_or_default();
+    export_data["activities"] = serde_json::to_value(activities).unwrap_or(serde_json::json!([]));
+
+    serde_json::to_string_pretty(&export_data)
+        .map_err(|e| format!("Failed to serialize export data: {}", e))
+}
+
+/// Export a single meeting as a self-contained HTML report, returns the written path
+#[tauri::command(rename_all = "camelCase")]
+pub async fn export_meeting_report(
+    meeting_id: String,
+    output_path: String,
+    include_screenshots: bool,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let path = crate::meeting_report::export_meeting_report(
+        &state.database,
+        &meeting_id,
+        std::path::Path::new(&output_path),
+        include_screenshots,
+    )
+    .await?;
+
+    log::info!("📄 Exported meeting report to {:?}", path);
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Export a meeting's final transcript as SRT or WebVTT subtitles
+#[tauri::command(rename_all = "camelCase")]
+pub async fn export_transcript_subtitles(
+    meeting_id: String,
+    format: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let format: crate::subtitle_export::SubtitleFormat = format.parse()?;
+
+    let meeting = state
+        .database
+        .get_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+
+    let transcripts = state
         .database
-        .get_activities_filtered(None, None, None, 1000)
+        .get_transcripts(&meeting_id)
         .await
-        .unwrap_or_default();
-    export_data["activities"] = serde_json::to_value(activities).unwrap_or(serde_json::json!([]));
+        .map_err(|e| format!("Failed to get transcripts: {}", e))?;
 
-    serde_json::to_string_pretty(&export_data)
-        .map_err(|e| format!("Failed to serialize export data: {}", e))
+    let cues = crate::subtitle_export::build_cues(meeting.started_at, &transcripts);
+    Ok(crate::subtitle_export::render(&cues, format))
 }
 
 // ============================================
@@ -3384,6 +6223,8 @@ pub async fn create_prompt(
     input: crate::prompt_manager::PromptCreate,
     state: State<'_, AppState>,
 ) -> Result<crate::prompt_manager::Prompt, String> {
+    validate_prompt_template(&input.category, &input.system_prompt)?;
+
     state
         .prompt_manager
         .create_prompt(input)
@@ -3398,6 +6239,21 @@ pub async fn update_prompt(
     updates: crate::prompt_manager::PromptUpdate,
     state: State<'_, AppState>,
 ) -> Result<Option<crate::prompt_manager::Prompt>, String> {
+    if let Some(ref template) = updates.system_prompt {
+        let category = match updates.category.clone() {
+            Some(category) => Some(category),
+            None => state
+                .prompt_manager
+                .get_prompt(&id)
+                .await
+                .map_err(|e| format!("Failed to get prompt: {}", e))?
+                .map(|p| p.category),
+        };
+        if let Some(category) = category {
+            validate_prompt_template(&category, template)?;
+        }
+    }
+
     state
         .prompt_manager
         .update_prompt(&id, updates)
@@ -3695,6 +6551,47 @@ pub async fn test_prompt(
         .to_string())
 }
 
+/// Run two prompts side by side on real samples ("frames" = pending frames,
+/// "transcripts" = recent meetings) and store the outputs for grading
+#[tauri::command(rename_all = "camelCase")]
+pub async fn evaluate_prompt(
+    prompt_id_a: String,
+    prompt_id_b: String,
+    sample_source: String,
+    sample_count: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<crate::prompt_evaluation::EvaluationRun, String> {
+    crate::prompt_evaluation::run_evaluation(
+        &state.prompt_manager,
+        &state.database,
+        &prompt_id_a,
+        &prompt_id_b,
+        &sample_source,
+        sample_count.unwrap_or(5),
+    )
+    .await
+}
+
+/// Grade one evaluation: winner is "a", "b" or "tie"
+#[tauri::command(rename_all = "camelCase")]
+pub async fn record_evaluation_verdict(
+    evaluation_id: String,
+    winner: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    crate::prompt_evaluation::record_verdict(&state.database.get_pool(), &evaluation_id, &winner)
+        .await
+}
+
+/// Win rates per prompt version from graded evaluations
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_prompt_evaluation_summary(
+    prompt_name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::prompt_evaluation::PromptWinRate>, String> {
+    crate::prompt_evaluation::win_rates(&state.database.get_pool(), prompt_name.as_deref()).await
+}
+
 // ============================================================================
 // Meeting Intelligence Commands
 // ============================================
@@ -3712,6 +6609,7 @@ pub async fn get_calendar_events(
     if !CalendarClient::request_access().await? {
         return Err("Calendar access denied".to_string());
     }
+    crate::subsystems::ensure_calendar(&state).await;
 
     let client = state.calendar_client.read();
     client.fetch_events()
@@ -3721,7 +6619,10 @@ pub async fn get_calendar_events(
 // Intelligence / Meeting State Commands
 // ============================================
 
-use crate::catch_up_agent::{CatchUpAgent, CatchUpCapsule, MeetingMetadata, TranscriptSegment};
+use crate::catch_up_agent::{
+    merge_recorded_decisions, CatchUpAgent, CatchUpCapsule, Decision, MeetingMetadata,
+    TranscriptCitation, TranscriptSegment,
+};
 use crate::live_intel_agent::{LiveInsightEvent, LiveIntelAgent};
 
 /// Get current meeting state (mode, timing, confidence)
@@ -3776,12 +6677,22 @@ pub async fn get_meeting_state(state: State<'_, AppState>) -> Result<MeetingStat
     Ok(meeting_state)
 }
 
-/// Generate a catch-up capsule for late joiners
+/// Marked decisions included in a catch-up capsule
+const MAX_CATCH_UP_DECISIONS: usize = 10;
+
+/// Generate a catch-up capsule for late joiners.
+/// Repeat calls only summarize transcript added since the cached capsule;
+/// `force_refresh` regenerates from the full transcript.
 #[tauri::command(rename_all = "camelCase")]
 pub async fn generate_catch_up(
     meeting_id: String,
+    force_refresh: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<CatchUpCapsule, String> {
+    if force_refresh.unwrap_or(false) {
+        state.catch_up_cache.invalidate(&meeting_id);
+    }
+
     // Get transcripts for the meeting
     let transcripts = state
         .database
@@ -3811,14 +6722,47 @@ pub async fn generate_catch_up(
         .await
         .map_err(|e| format!("Failed to get meeting: {}", e))?;
 
+    let attendees = state
+        .database
+        .get_meeting_calendar_link(&meeting_id)
+        .await
+        .ok()
+        .flatten()
+        .map(|link| link.attendees)
+        .unwrap_or_default();
+
+    // Most recent marked decisions take precedence over inferred ones
+    let decisions = state
+        .database
+        .get_decisions(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get decisions: {}", e))?;
+    let recorded_decisions: Vec<Decision> = decisions
+        .iter()
+        .rev()
+        .take(MAX_CATCH_UP_DECISIONS)
+        .rev()
+        .map(|d| Decision {
+            text: d.text.clone(),
+            made_by: None,
+            citation: d.transcript_id.map(|id| TranscriptCitation {
+                segment_id: id.to_string(),
+                timestamp_ms: d.timestamp_ms,
+                speaker: None,
+                text_excerpt: d.context.clone().unwrap_or_else(|| d.text.clone()),
+            }),
+        })
+        .collect();
+
     let metadata = MeetingMetadata {
         title: meeting
             .as_ref()
             .map(|m| m.title.clone())
             .unwrap_or_default(),
         description: None,
-        attendees: Vec::new(), // TODO: Get from calendar
+        attendees,
         scheduled_duration_min: None,
+        recorded_decisions,
     };
 
     // Calculate minutes since start
@@ -3829,79 +6773,232 @@ pub async fn generate_catch_up(
     let duration = chrono::Utc::now().signed_duration_since(meeting_start);
     let minutes_since_start = duration.num_minutes() as i32;
 
-    // Create agent and generate catch-up
-    let ai_client = crate::ai_client::AIClient::new();
-    let agent = CatchUpAgent::new(ai_client);
+    let cursor = transcripts.last().map(|t| t.id).unwrap_or_default();
+    let cached = state.catch_up_cache.get(&meeting_id);
 
-    agent
-        .generate(&segments, &metadata, minutes_since_start, None)
-        .await
+    let mut capsule = match cached {
+        // Nothing new was said since the last capsule
+        Some((capsule, cached_cursor, age_secs)) if cached_cursor >= cursor => CatchUpCapsule {
+            cache_age_secs: age_secs,
+            ..capsule
+        },
+        Some((previous, cached_cursor, _)) => {
+            let new_segments: Vec<TranscriptSegment> = transcripts
+                .iter()
+                .zip(segments)
+                .filter(|(t, _)| t.id > cached_cursor)
+                .map(|(_, segment)| segment)
+                .collect();
+
+            let agent = CatchUpAgent::new(crate::ai_client::AIClient::new());
+            let capsule = agent
+                .generate_incremental(&previous, &new_segments, &metadata, minutes_since_start)
+                .await?;
+            state
+                .catch_up_cache
+                .store(&meeting_id, capsule.clone(), cursor);
+            capsule
+        }
+        None => {
+            let agent = CatchUpAgent::new(crate::ai_client::AIClient::new());
+            let capsule = agent
+                .generate(&segments, &metadata, minutes_since_start, None)
+                .await?;
+            state
+                .catch_up_cache
+                .store(&meeting_id, capsule.clone(), cursor);
+            capsule
+        }
+    };
+
+    merge_recorded_decisions(&mut capsule, &metadata.recorded_decisions);
+    Ok(capsule)
 }
 
-/// Get live insights stream for current recording
+/// Get live insights recorded for a meeting. Pass the last seen `since_id`
+/// to poll only for new ones; the `live-insight` event pushes them as they happen.
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_live_insights(
     meeting_id: String,
+    since_id: Option<i64>,
     state: State<'_, AppState>,
 ) -> Result<Vec<LiveInsightEvent>, String> {
-    // Get recent transcripts
-    let transcripts = state
+    state
         .database
-        .get_transcripts(&meeting_id)
+        .get_live_insights(&meeting_id, since_id.unwrap_or(0))
         .await
-        .map_err(|e| format!("Failed to get transcripts: {}", e))?;
-
-    // Process through live intel agent
-    let mut agent = LiveIntelAgent::new();
-
-    for transcript in transcripts.iter().rev().take(50).rev() {
-        let segment = TranscriptSegment {
-            id: transcript.id.to_string(),
-            timestamp_ms: transcript.timestamp.timestamp_millis(),
-            speaker: transcript.speaker.clone(),
-            text: transcript.text.clone(),
-        };
-        agent.process_segment(segment);
-    }
-
-    Ok(agent.get_all_events().to_vec())
+        .map_err(|e| format!("Failed to get live insights: {}", e))
 }
 
-/// Pin an insight for later reference
+/// Pin an insight for later reference; returns the pinned insight's id
 #[tauri::command(rename_all = "camelCase")]
 pub async fn pin_insight(
     meeting_id: String,
     insight_type: String,
     insight_text: String,
-    _timestamp_ms: i64,
-    _state: State<'_, AppState>,
-) -> Result<(), String> {
-    // Store pinned insight in database
-    // TODO: Add pinned_insights table
+    timestamp_ms: i64,
+    transcript_id: Option<i64>,
+    created_by: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let id = state
+        .database
+        .add_pinned_insight(
+            &meeting_id,
+            &insight_type,
+            &insight_text,
+            timestamp_ms,
+            transcript_id,
+            created_by.as_deref().unwrap_or("user"),
+        )
+        .await
+        .map_err(|e| format!("Failed to pin insight: {}", e))?;
+
+    // Keep the keyframe on screen at the pinned moment out of storage cleanup
+    if let Some(at) = chrono::DateTime::from_timestamp_millis(timestamp_ms) {
+        if let Err(e) = state
+            .database
+            .retain_keyframe_at(&meeting_id, at, "pinned_insight")
+            .await
+        {
+            log::warn!("Failed to retain keyframe for pinned insight: {}", e);
+        }
+    }
+
     log::info!(
-        "Pinning insight for meeting {}: {} - {}",
+        "Pinned insight for meeting {}: {} - {}",
         meeting_id,
         insight_type,
         insight_text
     );
+    Ok(id)
+}
+
+/// Get pinned insights for a meeting
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_pinned_insights(
+    meeting_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::database::PinnedInsight>, String> {
+    state
+        .database
+        .get_pinned_insights(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get pinned insights: {}", e))
+}
+
+/// Edit a pinned insight's text (and optionally its type)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn update_pinned_insight(
+    id: String,
+    insight_text: String,
+    insight_type: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let updated = state
+        .database
+        .update_pinned_insight(&id, &insight_text, insight_type.as_deref())
+        .await
+        .map_err(|e| format!("Failed to update pinned insight: {}", e))?;
+
+    if !updated {
+        return Err(format!("Pinned insight not found: {}", id));
+    }
+    Ok(())
+}
+
+/// Remove a pinned insight
+#[tauri::command(rename_all = "camelCase")]
+pub async fn delete_pinned_insight(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let deleted = state
+        .database
+        .delete_pinned_insight(&id)
+        .await
+        .map_err(|e| format!("Failed to delete pinned insight: {}", e))?;
+
+    if !deleted {
+        return Err(format!("Pinned insight not found: {}", id));
+    }
     Ok(())
 }
 
-/// Mark a decision point explicitly
+/// Mark a decision point explicitly; returns the decision's id
 #[tauri::command(rename_all = "camelCase")]
 pub async fn mark_decision(
     meeting_id: String,
     decision_text: String,
-    _context: Option<String>,
-    _state: State<'_, AppState>,
-) -> Result<(), String> {
-    // Store decision in database
-    // TODO: Add decisions table
+    context: Option<String>,
+    timestamp_ms: Option<i64>,
+    transcript_id: Option<i64>,
+    created_by: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let id = state
+        .database
+        .add_decision(
+            &meeting_id,
+            &decision_text,
+            context.as_deref(),
+            timestamp_ms.unwrap_or_else(|| chrono::Utc::now().timestamp_millis()),
+            transcript_id,
+            created_by.as_deref().unwrap_or("user"),
+        )
+        .await
+        .map_err(|e| format!("Failed to mark decision: {}", e))?;
+
     log::info!(
-        "Marking decision for meeting {}: {}",
+        "Marked decision for meeting {}: {}",
         meeting_id,
         decision_text
     );
+    Ok(id)
+}
+
+/// Get marked decisions for a meeting
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_decisions(
+    meeting_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::database::DecisionRecord>, String> {
+    state
+        .database
+        .get_decisions(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get decisions: {}", e))
+}
+
+/// Edit a marked decision
+#[tauri::command(rename_all = "camelCase")]
+pub async fn update_decision(
+    id: String,
+    decision_text: String,
+    context: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let updated = state
+        .database
+        .update_decision(&id, &decision_text, context.as_deref())
+        .await
+        .map_err(|e| format!("Failed to update decision: {}", e))?;
+
+    if !updated {
+        return Err(format!("Decision not found: {}", id));
+    }
+    Ok(())
+}
+
+/// Remove a marked decision
+#[tauri::command(rename_all = "camelCase")]
+pub async fn delete_decision(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let deleted = state
+        .database
+        .delete_decision(&id)
+        .await
+        .map_err(|e| format!("Failed to delete decision: {}", e))?;
+
+    if !deleted {
+        return Err(format!("Decision not found: {}", id));
+    }
     Ok(())
 }
 
@@ -3911,7 +7008,7 @@ pub async fn mark_decision(
 
 use crate::chunk_manager::{ChunkManager, StorageStats};
 use crate::frame_extractor::{ExtractedFrame, FrameExtractor};
-use crate::video_recorder::{PinMoment, RecordingSession, VideoRecorder};
+use crate::video_recorder::{PinMoment, RecordingSession, VideoCaptureConfig, VideoRecorder};
 
 // Lazy static for video recorder (global instance)
 use std::sync::OnceLock;
@@ -3931,6 +7028,19 @@ fn get_chunk_manager() -> &'static ChunkManager {
     CHUNK_MANAGER.get_or_init(ChunkManager::default)
 }
 
+/// Point video storage at the app data directory (video/<meeting_id>/ under it)
+/// and apply saved capture settings. Called once at startup.
+pub fn init_video_storage(app_data_dir: &std::path::Path, config: VideoCaptureConfig) {
+    let _ = CHUNK_MANAGER.set(ChunkManager::new(
+        app_data_dir.to_path_buf(),
+        crate::chunk_manager::RetentionPolicy::default(),
+    ));
+    let _ = VIDEO_RECORDER.set(parking_lot::RwLock::new(VideoRecorder::new(
+        app_data_dir.join("video"),
+    )));
+    get_video_recorder().read().set_config(config);
+}
+
 /// Start video recording for a meeting
 #[tauri::command(rename_all = "camelCase")]
 pub async fn start_video_recording(
@@ -3938,7 +7048,7 @@ pub async fn start_video_recording(
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     let recorder = get_video_recorder();
-    recorder.write().start(&meeting_id)?;
+    recorder.read().start(&meeting_id, get_chunk_manager())?;
 
     // Prevent sleep during video recording
     let _ = state
@@ -3952,7 +7062,7 @@ pub async fn start_video_recording(
 #[tauri::command(rename_all = "camelCase")]
 pub async fn stop_video_recording(state: State<'_, AppState>) -> Result<RecordingSession, String> {
     let recorder = get_video_recorder();
-    let result = recorder.write().stop();
+    let result = recorder.read().stop(get_chunk_manager());
 
     // Release sleep assertion
     state.power_manager.release_assertion();
@@ -3967,30 +7077,264 @@ pub async fn get_video_recording_status() -> Result<Option<RecordingSession>, St
     Ok(recorder.read().get_status())
 }
 
-/// Pin the current moment in recording
+/// Get screen video capture settings
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_video_capture_config() -> Result<VideoCaptureConfig, String> {
+    Ok(get_video_recorder().read().config())
+}
+
+/// Save screen video capture settings (applies to the next recording)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_video_capture_config(
+    config: VideoCaptureConfig,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    config.validate()?;
+
+    state
+        .settings
+        .set_video_capture(&config)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+
+    get_video_recorder().read().set_config(config);
+    Ok(())
+}
+
+/// Pin the current moment in recording; the pin carries the chunk number and
+/// offset to pass to `extract_frame_at`
 #[tauri::command(rename_all = "camelCase")]
 pub async fn video_pin_moment(label: Option<String>) -> Result<PinMoment, String> {
     let recorder = get_video_recorder();
     recorder.read().pin_moment(label)
 }
 
-/// Extract a frame at a specific timestamp
-#[tauri::command(rename_all = "camelCase")]
-pub async fn extract_frame_at(
-    meeting_id: String,
-    chunk_number: u32,
-    timestamp_secs: f64,
-) -> Result<ExtractedFrame, String> {
-    let chunk_manager = get_chunk_manager();
-    let chunks = chunk_manager.get_chunks(&meeting_id)?;
+/// Extract a frame at a specific timestamp
+#[tauri::command(rename_all = "camelCase")]
+pub async fn extract_frame_at(
+    meeting_id: String,
+    chunk_number: u32,
+    timestamp_secs: f64,
+) -> Result<ExtractedFrame, String> {
+    let chunk_manager = get_chunk_manager();
+    let chunks = chunk_manager.get_chunks(&meeting_id)?;
+
+    let chunk = chunks
+        .iter()
+        .find(|c| c.chunk_number == chunk_number)
+        .ok_or_else(|| format!("Chunk {} not found", chunk_number))?;
+
+    let extractor = get_frame_extractor();
+    extractor.extract_at(&chunk.path, timestamp_secs, &meeting_id)
+}
+
+/// Everything the rewind player needs for one meeting: video chunk spans,
+/// transcripts, screen states, episodes, timeline events and pins, keyed by
+/// offset from the meeting start
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_playback_manifest(
+    meeting_id: String,
+    state: State<'_, AppState>,
+) -> Result<crate::playback_manifest::PlaybackManifest, String> {
+    let meeting = state
+        .database
+        .get_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+    let transcripts = state
+        .database
+        .get_transcripts(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get transcripts: {}", e))?;
+
+    build_playback_manifest(&meeting, &transcripts, &state).await
+}
+
+async fn build_playback_manifest(
+    meeting: &crate::database::Meeting,
+    transcripts: &[crate::database::Transcript],
+    state: &AppState,
+) -> Result<crate::playback_manifest::PlaybackManifest, String> {
+    let meeting_id = &meeting.id;
+    let chunks = get_chunk_manager().get_chunks(meeting_id)?;
+    let screen_states = state
+        .database
+        .get_screen_states(meeting_id, 10000)
+        .await
+        .map_err(|e| format!("Failed to get screen states: {}", e))?;
+    let episodes = state
+        .database
+        .get_episodes(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get episodes: {}", e))?;
+    let timeline_events = state
+        .database
+        .get_timeline_events(meeting_id, None, &[], None)
+        .await
+        .map_err(|e| format!("Failed to get timeline events: {}", e))?;
+    let pinned_insights = state
+        .database
+        .get_pinned_insights(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get pinned insights: {}", e))?;
+
+    // Video pins only live in the recorder until the recording stops
+    let video_pins = get_video_recorder()
+        .read()
+        .get_status()
+        .filter(|session| &session.meeting_id == meeting_id)
+        .map(|session| session.pin_moments)
+        .unwrap_or_default();
+
+    Ok(crate::playback_manifest::build_manifest(
+        &crate::playback_manifest::PlaybackSources {
+            meeting_id,
+            started_at: meeting.started_at,
+            ended_at: meeting.ended_at,
+            chunks: &chunks,
+            transcripts,
+            screen_states: &screen_states,
+            episodes: &episodes,
+            timeline_events: &timeline_events,
+            pinned_insights: &pinned_insights,
+            video_pins: &video_pins,
+        },
+    ))
+}
+
+/// Progress of a highlight clip export
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HighlightExportProgress {
+    pub meeting_id: String,
+    /// 0.0 - 1.0
+    pub progress: f32,
+}
+
+/// An exported highlight clip
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HighlightExport {
+    pub path: String,
+    pub duration_secs: f64,
+}
+
+/// Export a video clip around a pinned moment (`pin_id`) or an offset from the
+/// meeting start, cutting across chunk boundaries as needed and optionally
+/// burning in the transcript as subtitles. Emits "highlight-export-progress".
+#[tauri::command(rename_all = "camelCase")]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_highlight(
+    meeting_id: String,
+    pin_id: Option<String>,
+    offset_secs: Option<f64>,
+    pre_secs: Option<f64>,
+    post_secs: Option<f64>,
+    output_path: String,
+    burn_subtitles: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<HighlightExport, String> {
+    use crate::playback_manifest::PlaybackEntryKind;
+
+    let pre_ms = (pre_secs.unwrap_or(15.0).clamp(0.0, 600.0) * 1000.0) as i64;
+    let post_ms = (post_secs.unwrap_or(15.0).clamp(0.0, 600.0) * 1000.0) as i64;
+    if pre_ms + post_ms == 0 {
+        return Err("Highlight needs some time before or after the moment".to_string());
+    }
+
+    let meeting = state
+        .database
+        .get_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+    let transcripts = state
+        .database
+        .get_transcripts(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get transcripts: {}", e))?;
+    let manifest = build_playback_manifest(&meeting, &transcripts, &state).await?;
+
+    let moment_ms = match (pin_id, offset_secs) {
+        (Some(pin_id), _) => manifest
+            .entries
+            .iter()
+            .find(|e| e.kind == PlaybackEntryKind::Pin && e.ref_id == pin_id)
+            .map(|e| e.start_ms)
+            .ok_or_else(|| format!("Pin not found: {}", pin_id))?,
+        (None, Some(secs)) => (secs * 1000.0) as i64,
+        (None, None) => return Err("Pass either a pin id or an offset".to_string()),
+    };
+    let start_ms = (moment_ms - pre_ms).max(0);
+    let end_ms = moment_ms + post_ms;
+    let segments = crate::chunk_manager::plan_clip(&manifest.chunks, start_ms, end_ms)?;
+
+    // Subtitle cues overlapping the clip, shifted to clip time
+    let subtitles = if burn_subtitles.unwrap_or(false) {
+        let clip_start = start_ms.max(manifest.chunks.first().map_or(0, |c| c.start_ms));
+        let clip_end = end_ms.min(manifest.chunks.last().map_or(end_ms, |c| c.end_ms));
+        let correction = manifest.offsets.transcripts_ms;
+        let cues: Vec<_> = crate::subtitle_export::build_cues(meeting.started_at, &transcripts)
+            .into_iter()
+            .map(|mut cue| {
+                cue.start_ms = (cue.start_ms + correction - clip_start).max(0);
+                cue.end_ms = (cue.end_ms + correction - clip_start).min(clip_end - clip_start);
+                cue
+            })
+            .filter(|cue| cue.end_ms > cue.start_ms)
+            .collect();
+
+        if cues.is_empty() {
+            None
+        } else {
+            let srt = std::env::temp_dir()
+                .join(format!("nofriction_highlight_{}.srt", uuid::Uuid::new_v4()));
+            let text =
+                crate::subtitle_export::render(&cues, crate::subtitle_export::SubtitleFormat::Srt);
+            std::fs::write(&srt, text).map_err(|e| format!("Failed to write subtitles: {}", e))?;
+            Some(srt)
+        }
+    } else {
+        None
+    };
 
-    let chunk = chunks
-        .iter()
-        .find(|c| c.chunk_number == chunk_number)
-        .ok_or_else(|| format!("Chunk {} not found", chunk_number))?;
+    let output = std::path::PathBuf::from(&output_path);
+    let progress_meeting = meeting_id.clone();
+    let subtitles_path = subtitles.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        get_frame_extractor().export_clip(
+            &segments,
+            subtitles_path.as_deref(),
+            &output,
+            |progress| {
+                let _ = app.emit(
+                    "highlight-export-progress",
+                    &HighlightExportProgress {
+                        meeting_id: progress_meeting.clone(),
+                        progress,
+                    },
+                );
+            },
+        )
+    })
+    .await
+    .map_err(|e| format!("Highlight export task failed: {}", e))?;
 
-    let extractor = get_frame_extractor();
-    extractor.extract_at(&chunk.path, timestamp_secs, &meeting_id)
+    if let Some(srt) = subtitles {
+        let _ = std::fs::remove_file(srt);
+    }
+    let duration_secs = result?;
+
+    log::info!(
+        "Exported {:.1}s highlight of meeting {} to {}",
+        duration_secs,
+        meeting_id,
+        output_path
+    );
+    Ok(HighlightExport {
+        path: output_path,
+        duration_secs,
+    })
 }
 
 /// Extract thumbnail for timeline view
@@ -4091,6 +7435,32 @@ pub async fn get_vlm_scheduler_status(
     Ok(state.vlm_scheduler.get_status().await)
 }
 
+/// Get the VLM scheduler's budgets, quiet hours and battery guard
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_vlm_scheduler_policy(
+    state: State<'_, AppState>,
+) -> Result<crate::vlm_scheduler::VLMSchedulerPolicy, String> {
+    Ok(state.vlm_scheduler.policy())
+}
+
+/// Set the VLM scheduler policy; the running loop applies it on its next tick
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_vlm_scheduler_policy(
+    policy: crate::vlm_scheduler::VLMSchedulerPolicy,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    policy.validate()?;
+
+    state
+        .settings
+        .set_vlm_scheduler_policy(&policy)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+
+    state.vlm_scheduler.set_policy(policy);
+    Ok(())
+}
+
 // ============================================
 // AI Chat Model Commands
 // ============================================
@@ -4104,6 +7474,7 @@ pub async fn set_ai_chat_model(model: String, state: State<'_, AppState>) -> Res
         .await
         .map_err(|e| format!("Failed to save model: {}", e))?;
 
+    crate::model_router::model_router().set_default_chat_model(Some(model.clone()));
     log::info!("AI chat model set to: {}", model);
     Ok(())
 }
@@ -4124,6 +7495,7 @@ pub async fn set_ai_provider_settings(
     provider: String,
     url: Option<String>,
     key: Option<String>,
+    model: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     state
@@ -4140,7 +7512,8 @@ pub async fn set_ai_provider_settings(
             .map_err(|e| e.to_string())?;
     }
 
-    if let Some(k) = key {
+    // The UI echoes back the masked key from get_ai_provider_settings
+    if let Some(k) = key.filter(|k| !crate::secret_store::is_masked(k)) {
         state
             .settings
             .set_ai_remote_key(&k)
@@ -4148,19 +7521,53 @@ pub async fn set_ai_provider_settings(
             .map_err(|e| e.to_string())?;
     }
 
+    if let Some(m) = model {
+        state
+            .settings
+            .set_ai_remote_model(&m)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    // Route every AIClient through the new provider right away
+    let settings = state.settings.get_all().await.map_err(|e| e.to_string())?;
+    crate::ai_client::set_remote_provider(crate::ai_client::RemoteProvider::from_settings(
+        &settings,
+    ));
+    crate::model_router::model_router().invalidate_models();
+
+    Ok(())
+}
+
+/// Cap chat/completion requests per minute across every AI caller (0 = unlimited)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_ai_requests_per_minute(
+    requests_per_minute: u32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings
+        .set_ai_requests_per_minute(requests_per_minute)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+    crate::ai_client::set_requests_per_minute(requests_per_minute);
     Ok(())
 }
 
-/// Get AI provider configuration
+/// Get AI provider configuration (remote key masked)
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_ai_provider_settings(
     state: State<'_, AppState>,
-) -> Result<(String, Option<String>, Option<String>), String> {
+) -> Result<(String, Option<String>, Option<String>, Option<String>), String> {
     let settings = state.settings.get_all().await.map_err(|e| e.to_string())?;
     Ok((
         settings.ai_provider,
         settings.ai_remote_url,
-        settings.ai_remote_key,
+        settings
+            .ai_remote_key
+            .as_deref()
+            .map(crate::secret_store::mask),
+        settings.ai_remote_model,
     ))
 }
 
@@ -4173,6 +7580,7 @@ pub async fn get_ai_provider_settings(
 pub async fn get_accessibility_capture_status(
     state: State<'_, AppState>,
 ) -> Result<crate::accessibility_capture::AccessibilityCaptureStats, String> {
+    crate::subsystems::ensure_accessibility_capture(&state).await;
     Ok(state.accessibility_capture.get_stats())
 }
 
@@ -4204,11 +7612,28 @@ pub async fn start_accessibility_capture(state: State<'_, AppState>) -> Result<(
             state.pinecone_client.clone(),
         )?;
     }
+    crate::subsystems::subsystems().record(
+        crate::subsystems::ACCESSIBILITY_CAPTURE,
+        Ok(crate::subsystems::SubsystemOutcome::Ready(None)),
+    );
 
     log::info!("📝 Accessibility capture started");
     Ok(())
 }
 
+/// Enable/disable OCR when accessibility extraction fails
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_ocr_fallback_enabled(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings
+        .set_ocr_fallback_enabled(enabled)
+        .await
+        .map_err(|e| format!("Failed to save OCR fallback setting: {}", e))
+}
+
 /// Stop accessibility capture
 #[tauri::command(rename_all = "camelCase")]
 pub async fn stop_accessibility_capture(state: State<'_, AppState>) -> Result<(), String> {
@@ -4248,20 +7673,28 @@ pub async fn set_accessibility_meeting_id(
 // Activity Theme Commands
 // ============================================
 
+/// Activity themes, in display order
+pub const ACTIVITY_THEMES: [&str; 5] = [
+    "prospecting",
+    "fundraising",
+    "product_dev",
+    "admin",
+    "personal",
+];
+
 #[tauri::command(rename_all = "camelCase")]
 pub async fn set_active_theme(theme: String, state: State<'_, AppState>) -> Result<(), String> {
+    apply_active_theme(&state, &theme).await
+}
+
+/// Switch the active theme: closes the open theme session, applies the
+/// theme's capture interval and mic default, and starts a new session
+pub(crate) async fn apply_active_theme(state: &AppState, theme: &str) -> Result<(), String> {
     // Validate theme name
-    let valid_themes = [
-        "prospecting",
-        "fundraising",
-        "product_dev",
-        "admin",
-        "personal",
-    ];
-    if !valid_themes.contains(&theme.as_str()) {
+    if !ACTIVITY_THEMES.contains(&theme) {
         return Err(format!(
             "Invalid theme: {}. Must be one of: {:?}",
-            theme, valid_themes
+            theme, ACTIVITY_THEMES
         ));
     }
 
@@ -4273,14 +7706,15 @@ pub async fn set_active_theme(theme: String, state: State<'_, AppState>) -> Resu
     // Save to settings
     state
         .settings
-        .set_active_theme(&theme)
+        .set_active_theme(theme)
         .await
         .map_err(|e| format!("Failed to set theme: {}", e))?;
+    crate::tray_builder::update_tray_status(|s| s.active_theme = Some(theme.to_string()));
 
     // Apply theme-specific settings
     let interval_ms = state
         .settings
-        .get_theme_interval(&theme)
+        .get_theme_interval(theme)
         .await
         .map_err(|e| format!("Failed to get theme interval: {}", e))?;
 
@@ -4291,7 +7725,7 @@ pub async fn set_active_theme(theme: String, state: State<'_, AppState>) -> Resu
     } // Lock dropped here
 
     // Auto-enable mic transcription for prospecting/fundraising (meeting-heavy themes)
-    let enable_mic = matches!(theme.as_str(), "prospecting" | "fundraising");
+    let enable_mic = matches!(theme, "prospecting" | "fundraising");
     state
         .settings
         .set_capture_microphone(enable_mic)
@@ -4301,24 +7735,162 @@ pub async fn set_active_theme(theme: String, state: State<'_, AppState>) -> Resu
     // Start new theme session
     state
         .database
-        .start_theme_session(&theme)
+        .start_theme_session(theme)
         .await
         .map_err(|e| format!("Failed to start theme session: {}", e))?;
 
     Ok(())
 }
 
+/// Watch recent screen activity for a sustained mismatch with the active
+/// theme and emit `theme-suggestion` when switching looks right
+pub fn spawn_theme_detector_task(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(
+            crate::theme_detector::CHECK_INTERVAL_SECS,
+        ));
+
+        loop {
+            ticker.tick().await;
+
+            let Some(state) = app.try_state::<AppState>() else {
+                continue;
+            };
+            if !state.capture_engine.read().get_status().is_recording {
+                continue;
+            }
+            let now = chrono::Utc::now();
+            let since = now - chrono::Duration::minutes(crate::theme_detector::SUSTAIN_MINS);
+            let active_theme = match state.settings.get_active_theme().await {
+                Ok(theme) => theme,
+                Err(e) => {
+                    log::warn!("Theme detection skipped: {}", e);
+                    continue;
+                }
+            };
+            let states = match state.database.get_screen_states_since(since).await {
+                Ok(states) => states,
+                Err(e) => {
+                    log::warn!("Theme detection skipped: {}", e);
+                    continue;
+                }
+            };
+
+            if let Some(suggestion) = state.theme_detector.check(&states, &active_theme, now) {
+                log::info!(
+                    "🎨 Suggesting theme {} over {} ({:.0}% confidence)",
+                    suggestion.proposed_theme,
+                    suggestion.active_theme,
+                    suggestion.confidence * 100.0
+                );
+                if let Err(e) = app.emit("theme-suggestion", &suggestion) {
+                    log::error!("Failed to emit theme-suggestion: {}", e);
+                }
+            }
+        }
+    });
+}
+
+/// The theme switch waiting for an answer, if any
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_theme_suggestion(
+    state: State<'_, AppState>,
+) -> Result<Option<crate::theme_detector::ThemeSuggestion>, String> {
+    Ok(state.theme_detector.pending())
+}
+
+/// Switch to the suggested theme, ending and starting theme sessions like a
+/// manual switch
+#[tauri::command(rename_all = "camelCase")]
+pub async fn accept_theme_suggestion(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let suggestion = state.theme_detector.accept(&id)?;
+    apply_active_theme(&state, &suggestion.proposed_theme).await
+}
+
+/// Keep the current theme; the same switch isn't suggested again for a while
+#[tauri::command(rename_all = "camelCase")]
+pub async fn dismiss_theme_suggestion(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state.theme_detector.dismiss(&id, chrono::Utc::now())
+}
+
+/// Recently mentioned entities, deduplicated into canonical entities with
+/// mention counts, first/last seen and linked activities
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_recent_entities(
     state: State<'_, AppState>,
     limit: Option<i64>,
-) -> Result<Vec<serde_json::Value>, String> {
-    let limit = limit.unwrap_or(50);
-    state
-        .database
-        .get_recent_entities(limit as i32)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<Vec<crate::entity_resolution::CanonicalEntity>, String> {
+    let pool = state.database.get_pool();
+    crate::entity_resolution::resolve_pending(&pool).await?;
+    crate::entity_resolution::recent_canonical_entities(&pool, limit.unwrap_or(50)).await
+}
+
+/// Merge canonical entities into one named `canonical_name`
+#[tauri::command(rename_all = "camelCase")]
+pub async fn merge_entities(
+    ids: Vec<i64>,
+    canonical_name: String,
+    state: State<'_, AppState>,
+) -> Result<crate::entity_resolution::CanonicalEntity, String> {
+    let pool = state.database.get_pool();
+    crate::entity_resolution::merge_entities(&pool, &ids, &canonical_name).await
+}
+
+/// Split a canonical entity back into one entity per spelling
+#[tauri::command(rename_all = "camelCase")]
+pub async fn split_entity(
+    id: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::entity_resolution::CanonicalEntity>, String> {
+    let pool = state.database.get_pool();
+    crate::entity_resolution::split_entity(&pool, id).await
+}
+
+/// Everything known about a person or company (by canonical id or name) as
+/// one timeline, with an optional cached AI rollup
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_entity_dossier(
+    entity_id: Option<i64>,
+    name: Option<String>,
+    include_rollup: Option<bool>,
+    refresh_rollup: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<crate::entity_dossier::EntityDossier, String> {
+    use crate::entity_resolution::{find_canonical_by_name, get_canonical_entity};
+
+    let pool = state.database.get_pool();
+    crate::entity_resolution::resolve_pending(&pool).await?;
+    let entity = match (entity_id, name.as_deref().map(str::trim)) {
+        (Some(id), _) => get_canonical_entity(&pool, id)
+            .await?
+            .ok_or_else(|| format!("Entity not found: {}", id))?,
+        (None, Some(name)) if !name.is_empty() => find_canonical_by_name(&pool, name)
+            .await?
+            .ok_or_else(|| format!("No entity named '{}'", name))?,
+        _ => return Err("Pass an entity id or name".to_string()),
+    };
+
+    // Calendar is optional context (unavailable off macOS or without access)
+    let events = match state.calendar_client.read().fetch_events() {
+        Ok(events) => events,
+        Err(e) => {
+            log::debug!("Dossier without calendar events: {}", e);
+            Vec::new()
+        }
+    };
+
+    crate::entity_dossier::build_dossier(
+        &pool,
+        &state.vault_manager,
+        entity,
+        &events,
+        include_rollup.unwrap_or(false),
+        refresh_rollup.unwrap_or(false),
+    )
+    .await
 }
 
 #[tauri::command(rename_all = "camelCase")]
@@ -4400,6 +7972,59 @@ pub async fn get_theme_time_today(
     // Convert to hours with 1 decimal place
     Ok((seconds as f64) / 3600.0)
 }
+
+fn parse_theme_report_args(
+    date_from: &str,
+    date_to: &str,
+    granularity: &str,
+) -> Result<
+    (
+        chrono::NaiveDate,
+        chrono::NaiveDate,
+        crate::theme_report::Granularity,
+    ),
+    String,
+> {
+    let parse_date = |s: &str| {
+        chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid date '{}': {}", s, e))
+    };
+    let granularity = crate::theme_report::Granularity::parse(granularity).ok_or_else(|| {
+        format!(
+            "Invalid granularity '{}'. Must be \"day\" or \"week\"",
+            granularity
+        )
+    })?;
+    Ok((parse_date(date_from)?, parse_date(date_to)?, granularity))
+}
+
+/// Hours per theme per day or week between two dates (YYYY-MM-DD, inclusive)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_theme_time_report(
+    date_from: String,
+    date_to: String,
+    granularity: String,
+    state: State<'_, AppState>,
+) -> Result<crate::theme_report::ThemeTimeReport, String> {
+    let (from, to, granularity) = parse_theme_report_args(&date_from, &date_to, &granularity)?;
+    crate::theme_report::report(&state.database, from, to, granularity).await
+}
+
+/// Write the theme time report to a CSV file; returns the number of themes
+#[tauri::command(rename_all = "camelCase")]
+pub async fn export_theme_time_report_csv(
+    date_from: String,
+    date_to: String,
+    granularity: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    let (from, to, granularity) = parse_theme_report_args(&date_from, &date_to, &granularity)?;
+    let report = crate::theme_report::report(&state.database, from, to, granularity).await?;
+    crate::theme_report::write_csv(&report, std::path::Path::new(&path))?;
+    log::info!("📊 Exported theme time report to {}", path);
+    Ok(report.themes.len())
+}
 // ============================================
 // Phase 2: Theme-Specific Prompt Management Commands
 // ============================================
@@ -4454,6 +8079,21 @@ pub async fn create_prompt_version(
     updates: PromptUpdate,
     state: State<'_, AppState>,
 ) -> Result<Prompt, String> {
+    if let Some(ref template) = updates.system_prompt {
+        let category = match updates.category.clone() {
+            Some(category) => Some(category),
+            None => state
+                .prompt_manager
+                .get_prompt(&prompt_id)
+                .await
+                .map_err(|e| format!("Failed to get prompt: {}", e))?
+                .map(|p| p.category),
+        };
+        if let Some(category) = category {
+            validate_prompt_template(&category, template)?;
+        }
+    }
+
     state
         .prompt_manager
         .create_prompt_version(&prompt_id, updates)
@@ -4521,122 +8161,115 @@ pub async fn set_ingest_config(
     Ok(())
 }
 
-/// Get ingest queue statistics
+/// Set the ingest upload bandwidth cap in kilobits/sec (0 = unlimited)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_ingest_bandwidth_cap(kbps: u32, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .settings
+        .set_ingest_bandwidth_kbps(kbps)
+        .await
+        .map_err(|e| format!("Failed to set ingest bandwidth cap: {}", e))
+}
+
+/// Get ingest queue statistics (per-state counts and the current session)
 #[tauri::command(rename_all = "camelCase")]
-pub async fn get_ingest_queue_stats(state: State<'_, AppState>) -> Result<(usize, usize), String> {
-    let queue = state.ingest_queue.lock();
-    queue.get_stats().map_err(|e| e.to_string())
+pub async fn get_ingest_queue_stats(
+    state: State<'_, AppState>,
+) -> Result<crate::ingest_uploader::IngestQueueStats, String> {
+    crate::subsystems::ensure_ingest(&state).await;
+    if let Some(uploader) = state.ingest_uploader() {
+        return uploader.get_stats().await;
+    }
+
+    let counts = state
+        .ingest_queue
+        .lock()
+        .get_state_counts()
+        .map_err(|e| format!("Failed to get queue stats: {}", e))?;
+    let bandwidth_kbps = state
+        .settings
+        .get_all()
+        .await
+        .map(|s| s.ingest_bandwidth_kbps)
+        .unwrap_or(0);
+    Ok(crate::ingest_uploader::IngestQueueStats {
+        pending: counts.pending,
+        uploading: counts.uploading,
+        done: counts.done,
+        failed: counts.failed,
+        running: false,
+        current_session: None,
+        bandwidth_kbps,
+        bytes_uploaded: 0,
+        last_error: None,
+    })
 }
 
 /// Test ingest connection
 #[tauri::command(rename_all = "camelCase")]
 pub async fn test_ingest_connection(state: State<'_, AppState>) -> Result<bool, String> {
-    if let Some(ref client) = state.ingest_client {
+    crate::subsystems::ensure_ingest(&state).await;
+    if let Some(client) = state.ingest_client() {
         client.health_check().await.map_err(|e| e.to_string())
     } else {
         Err("Ingest client not initialized".to_string())
     }
 }
 
-/// Trigger manual ingest of a meeting
+/// Trigger manual ingest of a meeting. Its items are queued under the
+/// meeting's session and uploaded in the background; anything already
+/// uploaded is skipped.
 #[tauri::command(rename_all = "camelCase")]
 pub async fn trigger_meeting_ingest(
     meeting_id: String,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
-    let client = state
-        .ingest_client
-        .as_ref()
+    crate::subsystems::ensure_ingest(&state).await;
+    let uploader = state
+        .ingest_uploader()
         .ok_or_else(|| "Ingest client not initialized".to_string())?;
 
-    // Get meeting details
-    let meeting = state
-        .database
-        .get_meeting(&meeting_id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| "Meeting not found".to_string())?;
-
-    // Create session start request
-    let started_at = meeting.started_at.to_rfc3339();
-    let metadata = serde_json::json!({
-        "title": meeting.title,
-        "meeting_id": meeting.id,
-        "source": "nofriction_meetings",
-        "manual_trigger": true
-    });
-
-    // Start session
-    log::info!("Starting ingest session for meeting {}", meeting_id);
-    let session_id = client
-        .start_session(None, started_at, metadata)
-        .await
-        .map_err(|e| format!("Failed to start session: {}", e))?;
-
-    // Get transcripts
-    let transcripts = state
-        .database
-        .get_transcripts(&meeting_id)
-        .await
-        .map_err(|e| format!("Failed to get transcripts: {}", e))?;
-
-    // Convert transcripts
-    let segments: Vec<crate::ingest_client::TranscriptSegment> = transcripts
-        .into_iter()
-        .map(|t| crate::ingest_client::TranscriptSegment {
-            start_at: t.timestamp.to_rfc3339(),
-            end_at: t.timestamp.to_rfc3339(),
-            text: t.text,
-            speaker: t.speaker,
-            confidence: Some(t.confidence as f64),
-        })
-        .collect();
-
-    let segment_count = segments.len();
+    log::info!("Queuing meeting {} for ingest", meeting_id);
+    let queued = uploader
+        .enqueue_meeting(&state.database, &meeting_id)
+        .await?;
 
-    if !segments.is_empty() {
-        log::info!("Uploading {} transcript segments...", segment_count);
-        client
-            .upload_transcript(session_id, segments)
-            .await
-            .map_err(|e| format!("Failed to upload transcripts: {}", e))?;
+    let mut summary = format!(
+        "Queued {} transcript chunks and {} frames for upload ({} already queued).",
+        queued.transcript_chunks, queued.frames, queued.skipped
+    );
+    if !queued.failed_frames.is_empty() {
+        let paths: Vec<String> = queued
+            .failed_frames
+            .iter()
+            .map(|f| f.image_path.display().to_string())
+            .collect();
+        summary.push_str(&format!(
+            " {} frames failed permanently: {}",
+            paths.len(),
+            paths.join(", ")
+        ));
     }
+    Ok(summary)
+}
 
-    // Get frames (limit to avoid overload)
-    let frames = state
-        .database
-        .get_frames(&meeting_id, 200)
-        .await
-        .map_err(|e| e.to_string())?;
-    log::info!("Found {} frames to upload...", frames.len());
-
-    let mut success_frames = 0;
-    for frame in frames {
-        if let Some(path_str) = frame.file_path {
-            let path = std::path::PathBuf::from(path_str);
-            if path.exists() {
-                match client
-                    .upload_frame(session_id, frame.timestamp.to_rfc3339(), &path, None)
-                    .await
-                {
-                    Ok(_) => success_frames += 1,
-                    Err(e) => log::warn!("Failed to upload frame {}: {}", frame.id, e),
-                }
-            }
-        }
-    }
+/// Compare what the pipeline received for a meeting with local records.
+/// With `repush`, only the missing frames (and transcripts, if the server has
+/// fewer) are queued again.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn verify_remote_session(
+    meeting_id: String,
+    repush: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<crate::ingest_uploader::SessionVerification, String> {
+    crate::subsystems::ensure_ingest(&state).await;
+    let uploader = state
+        .ingest_uploader()
+        .ok_or_else(|| "Ingest client not initialized".to_string())?;
 
-    // End session
-    let ended_at = meeting.ended_at.unwrap_or(chrono::Utc::now()).to_rfc3339();
-    client
-        .end_session(session_id, ended_at)
+    uploader
+        .verify_meeting(&state.database, &meeting_id, repush.unwrap_or(false))
         .await
-        .map_err(|e| format!("Failed to end session: {}", e))?;
-
-    Ok(format!(
-        "Ingest complete. Uploaded {} frames and {} transcripts.",
-        success_frames, segment_count
-    ))
 }
 
 // ===== Calendar Integration Commands =====
@@ -4673,49 +8306,42 @@ pub async fn request_calendar_access() -> Result<bool, String> {
     }
 }
 
-/// Get the current/active meeting from calendar (if any)
+/// Get the current/active meeting from calendar (if any), from the
+/// selected calendars
 #[tauri::command(rename_all = "camelCase")]
-pub async fn get_current_meeting() -> Result<Option<serde_json::Value>, String> {
-    #[cfg(target_os = "macos")]
-    {
-        use crate::calendar_client::CalendarClient;
-
-        let client = CalendarClient::new();
-        match client.get_current_event() {
-            Some(event) => Ok(Some(serde_json::json!({
-                "id": event.event_id,
-                "title": event.title,
-                "start_time": event.start_time.to_rfc3339(),
-                "end_time": event.end_time.to_rfc3339(),
-                "location": event.location,
-                "notes": event.notes,
-                "is_all_day": event.is_all_day,
-                "calendar_name": event.calendar_name,
-            }))),
-            None => Ok(None),
-        }
-    }
-
-    #[cfg(not(target_os = "macos"))]
-    {
-        Ok(None)
-    }
+pub async fn get_current_meeting(
+    state: State<'_, AppState>,
+) -> Result<Option<serde_json::Value>, String> {
+    // Off macOS fetching fails, so there's never a current event
+    let event = state.calendar_client.read().get_current_event();
+    Ok(event.map(|event| {
+        serde_json::json!({
+            "id": event.event_id,
+            "title": event.title,
+            "start_time": event.start_time.to_rfc3339(),
+            "end_time": event.end_time.to_rfc3339(),
+            "location": event.location,
+            "notes": event.notes,
+            "is_all_day": event.is_all_day,
+            "calendar_name": event.calendar_name,
+        })
+    }))
 }
 
-/// Get upcoming meetings for today
+/// Get upcoming meetings for today, from the selected calendars
 #[tauri::command(rename_all = "camelCase")]
-pub async fn get_upcoming_meetings(hours: Option<i64>) -> Result<Vec<serde_json::Value>, String> {
+pub async fn get_upcoming_meetings(
+    hours: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<serde_json::Value>, String> {
     #[cfg(target_os = "macos")]
     {
-        use crate::calendar_client::CalendarClient;
         use chrono::{Duration, Utc};
 
-        let _hours = hours.unwrap_or(24);
         let now = Utc::now();
         let lookahead = now + Duration::hours(hours.unwrap_or(24));
 
-        let client = CalendarClient::new();
-        let events = client.fetch_events()?;
+        let events = state.calendar_client.read().fetch_events()?;
 
         // Filter to upcoming events (not all-day, starts in future within lookahead)
         let json_events: Vec<serde_json::Value> = events
@@ -4736,10 +8362,45 @@ pub async fn get_upcoming_meetings(hours: Option<i64>) -> Result<Vec<serde_json:
         Ok(json_events)
     }
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        Ok(vec![])
-    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (hours, state);
+        Ok(vec![])
+    }
+}
+
+/// Calendars EventKit knows about, flagged with whether detection and
+/// intel use them
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_calendars(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::calendar_client::CalendarInfo>, String> {
+    state.calendar_client.read().list_calendars()
+}
+
+/// Limit meeting detection, upcoming events and attendee intel to these
+/// calendar ids (empty = all calendars)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_selected_calendars(
+    calendar_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut ids: Vec<String> = calendar_ids
+        .into_iter()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect();
+    ids.sort();
+    ids.dedup();
+
+    state
+        .settings
+        .set_selected_calendars(&ids)
+        .await
+        .map_err(|e| format!("Failed to save calendar selection: {}", e))?;
+    log::info!("📅 Calendar selection: {} calendar(s)", ids.len());
+    state.calendar_client.write().set_selected_calendars(ids);
+    Ok(())
 }
 
 // ===== Capture Metrics Commands =====
@@ -4747,12 +8408,22 @@ pub async fn get_upcoming_meetings(hours: Option<i64>) -> Result<Vec<serde_json:
 /// Get capture metrics report for the current or last meeting
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_capture_metrics(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+    let (vlm_calls, vlm_cache_hits) = state.metrics_collector.vlm_counts();
+    let (accessibility_hits, ocr_fallbacks) = state.metrics_collector.extraction_counts();
+    let (delta_snapshots, snapshot_bytes_saved) = state.metrics_collector.snapshot_delta_counts();
+    let ambient_buffer = state.ambient_capture.buffer().stats();
     match state.metrics_collector.snapshot() {
         Some(metrics) => Ok(serde_json::json!({
             "meeting_id": metrics.meeting_id,
             "frames_processed": metrics.frames_in,
             "states_created": metrics.states_out,
             "duplicates_skipped": metrics.duplicates_skipped,
+            "redactions": metrics.redactions,
+            "pii_redactions": state
+                .database
+                .get_meeting_pii_redactions(&metrics.meeting_id)
+                .await
+                .unwrap_or(0),
             "images_written": metrics.images_written,
             "bytes_saved": metrics.bytes_saved_estimate,
             "bytes_saved_formatted": format_bytes(metrics.bytes_saved_estimate),
@@ -4765,9 +8436,25 @@ pub async fn get_capture_metrics(state: State<'_, AppState>) -> Result<serde_jso
             "snapshots": metrics.snapshots_created,
             "patches": metrics.patches_created,
             "cpu_time_ms": metrics.cpu_time_ms,
+            "vlm_calls": vlm_calls,
+            "vlm_cache_hits": vlm_cache_hits,
+            "accessibility_hits": accessibility_hits,
+            "ocr_fallbacks": ocr_fallbacks,
+            "delta_snapshots": delta_snapshots,
+            "snapshot_bytes_saved": snapshot_bytes_saved,
+            "snapshot_bytes_saved_formatted": format_bytes(snapshot_bytes_saved),
+            "ambient_buffer": ambient_buffer,
         })),
         None => Ok(serde_json::json!({
-            "message": "No active meeting"
+            "message": "No active meeting",
+            "vlm_calls": vlm_calls,
+            "vlm_cache_hits": vlm_cache_hits,
+            "accessibility_hits": accessibility_hits,
+            "ocr_fallbacks": ocr_fallbacks,
+            "delta_snapshots": delta_snapshots,
+            "snapshot_bytes_saved": snapshot_bytes_saved,
+            "snapshot_bytes_saved_formatted": format_bytes(snapshot_bytes_saved),
+            "ambient_buffer": ambient_buffer,
         })),
     }
 }
@@ -4801,6 +8488,10 @@ pub struct CaptureDiagnostics {
     pub is_recording: bool,
     pub screen_permission: bool,
     pub mic_permission: bool,
+    pub capture_all_monitors: bool,
+    pub capture_window_id: Option<u32>,
+    /// Frames and failures per display since capture last started
+    pub monitor_health: Vec<crate::capture_engine::MonitorHealth>,
 }
 
 /// Get comprehensive capture diagnostics for troubleshooting
@@ -4814,9 +8505,16 @@ pub async fn get_capture_diagnostics(
     let monitors = CaptureEngine::list_monitors()?;
 
     // Get capture engine status
-    let engine = state.capture_engine.read();
-    let status = engine.get_status();
-    let frame_interval_ms = 1000; // Default, would need to expose this from engine
+    let (status, current_monitor_id, frame_interval_ms, capture_all_monitors, capture_window_id) = {
+        let engine = state.capture_engine.read();
+        (
+            engine.get_status(),
+            engine.selected_monitor(),
+            engine.frame_interval_ms(),
+            engine.captures_all_monitors(),
+            engine.capture_window(),
+        )
+    };
 
     // Check permissions
     #[cfg(target_os = "macos")]
@@ -4831,11 +8529,14 @@ pub async fn get_capture_diagnostics(
 
     Ok(CaptureDiagnostics {
         monitors,
-        current_monitor_id: None, // Would need to expose from engine
+        current_monitor_id,
         frame_interval_ms,
         is_recording: status.is_recording,
         screen_permission,
         mic_permission,
+        capture_all_monitors,
+        capture_window_id,
+        monitor_health: CaptureEngine::monitor_health(),
     })
 }
 
@@ -4917,12 +8618,13 @@ pub async fn start_realtime_transcription(
         ProviderType::Gemini => state.settings.get_gemini_api_key().await.ok().flatten(),
         ProviderType::Gladia => state.settings.get_gladia_api_key().await.ok().flatten(),
         ProviderType::GoogleSTT => state.settings.get_google_stt_key().await.ok().flatten(),
+        ProviderType::WhisperLocal => None,
     };
 
     if let Some(key) = api_key {
         tm.set_api_key(key);
         log::info!("Loaded API key for {:?} from settings", provider_type);
-    } else {
+    } else if provider_type.requires_api_key() {
         log::warn!(
             "No API key found in settings for {:?} - transcription will fail",
             provider_type
@@ -4956,12 +8658,22 @@ pub async fn stop_realtime_transcription(state: State<'_, AppState>) -> Result<(
 // Always-On Recording Commands
 // ============================================
 
+/// Capture mode plus ambient idle state
+#[derive(serde::Serialize)]
+pub struct CaptureModeStatus {
+    pub mode: String,
+    #[serde(flatten)]
+    pub idle: crate::ambient_capture::IdleStatus,
+}
+
 /// Get current capture mode
 #[tauri::command(rename_all = "camelCase")]
-pub async fn get_capture_mode(state: State<'_, AppState>) -> Result<String, String> {
-    let engine = state.capture_engine.read();
-    let mode = engine.get_mode();
-    Ok(format!("{:?}", mode))
+pub async fn get_capture_mode(state: State<'_, AppState>) -> Result<CaptureModeStatus, String> {
+    let mode = state.capture_engine.read().get_mode();
+    Ok(CaptureModeStatus {
+        mode: format!("{:?}", mode),
+        idle: state.ambient_capture.idle_status(),
+    })
 }
 
 /// Start ambient capture (screen only, 30s intervals, no audio)
@@ -4970,8 +8682,31 @@ pub async fn start_ambient_capture(
     app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    if !state.feature_flags.read().ambient_capture_enabled {
+        return Err("Ambient capture is disabled by feature flag".to_string());
+    }
     log::info!("🌙 Starting ambient capture mode");
+    let buffer_minutes = state
+        .settings
+        .get_all()
+        .await
+        .map(|s| s.ambient_buffer_minutes)
+        .unwrap_or(crate::ambient_capture::DEFAULT_BUFFER_MINUTES);
+
+    let buffer = state.ambient_capture.buffer();
+    buffer.set_minutes(buffer_minutes);
+    buffer.set_active(buffer_minutes > 0);
+
     let engine = state.capture_engine.read();
+    engine.set_ambient_audio(buffer.is_active());
+    if buffer.is_active() {
+        // Ambient frames fill the rolling buffer instead of a meeting
+        let frame_buffer = buffer.clone();
+        engine.set_frame_callback(Arc::new(move |frame| {
+            let frame_buffer = frame_buffer.clone();
+            tokio::task::spawn_blocking(move || frame_buffer.push_frame(&frame));
+        }));
+    }
     engine.start_ambient(app)?;
 
     // Prevent sleep
@@ -4989,6 +8724,7 @@ pub async fn start_meeting_capture(
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     log::info!("🎙️ Starting meeting capture mode");
+    state.ambient_capture.buffer().set_active(false);
     let engine = state.capture_engine.read();
     engine.start_meeting(app)?;
 
@@ -5000,10 +8736,211 @@ pub async fn start_meeting_capture(
     Ok(())
 }
 
+/// Turn the last N minutes of ambient capture into a meeting: buffered screen
+/// states are persisted with their capture times and buffered audio is
+/// transcribed in the background through the active provider
+#[tauri::command(rename_all = "camelCase")]
+pub async fn promote_ambient_buffer_to_meeting(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    title: String,
+) -> Result<String, String> {
+    if state.capture_engine.read().get_mode() == crate::capture_engine::CaptureMode::Meeting {
+        return Err("Stop the current recording before saving the ambient buffer".to_string());
+    }
+
+    let snapshot = state.ambient_capture.buffer().take();
+    if snapshot.is_empty() {
+        return Err("Ambient buffer is empty".to_string());
+    }
+
+    let meeting_id = uuid::Uuid::new_v4().to_string();
+    let title = if title.trim().is_empty() {
+        format!("Meeting {}", chrono::Local::now().format("%Y-%m-%d %H:%M"))
+    } else {
+        title
+    };
+    state
+        .database
+        .create_meeting(&meeting_id, &title)
+        .await
+        .map_err(|e| format!("Failed to create meeting: {}", e))?;
+    if let Some(started_at) = snapshot.started_at() {
+        state
+            .database
+            .set_meeting_span(&meeting_id, started_at, chrono::Utc::now())
+            .await
+            .map_err(|e| format!("Failed to set meeting times: {}", e))?;
+    }
+
+    let frames_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("frames")
+        .join(&meeting_id);
+    std::fs::create_dir_all(&frames_dir)
+        .map_err(|e| format!("Failed to create frames directory: {}", e))?;
+
+    let mut states_saved = 0;
+    for screen_state in &snapshot.screen_states {
+        let state_id = uuid::Uuid::new_v4().to_string();
+        let keyframe_path = match &screen_state.path {
+            Some(scratch_path) => {
+                let keyframe_path = frames_dir.join(format!("state_{}.jpg", state_id));
+                // Scratch may be on another volume; fall back to copy
+                let moved = std::fs::rename(scratch_path, &keyframe_path).or_else(|_| {
+                    std::fs::copy(scratch_path, &keyframe_path)
+                        .map(|_| ())
+                        .and_then(|_| std::fs::remove_file(scratch_path))
+                });
+                if let Err(e) = moved {
+                    log::warn!("Failed to move buffered keyframe: {}", e);
+                    continue;
+                }
+                Some(keyframe_path)
+            }
+            None => None,
+        };
+        let flags_json = if screen_state.is_redacted() {
+            REDACTED_STATE_FLAGS
+        } else {
+            "{}"
+        };
+
+        if let Err(e) = state
+            .database
+            .add_screen_state(
+                &state_id,
+                &meeting_id,
+                screen_state.started_at,
+                Some(screen_state.ended_at),
+                "",
+                0.0,
+                keyframe_path.as_ref().and_then(|p| p.to_str()),
+                "other",
+                flags_json,
+                None,
+            )
+            .await
+        {
+            log::warn!("Failed to save buffered screen state: {}", e);
+            continue;
+        }
+        states_saved += 1;
+    }
+
+    for gap in &snapshot.idle_gaps {
+        let mut event = crate::timeline_builder::TimelineEvent::new(
+            &meeting_id,
+            gap.started_at,
+            crate::timeline_builder::TimelineEventType::IdleGap,
+            "Away from keyboard".to_string(),
+        )
+        .with_duration(gap.duration_ms());
+        crate::importance::score_events(
+            std::slice::from_mut(&mut event),
+            &crate::importance::ScoringContext::default(),
+        );
+        if let Err(e) = state
+            .database
+            .add_timeline_event(
+                &event.event_id,
+                &event.meeting_id,
+                event.ts,
+                event.event_type.as_str(),
+                &event.title,
+                None,
+                None,
+                None,
+                event.duration_ms,
+                None,
+                None,
+                None,
+                event.importance,
+                event.importance_reason.as_deref(),
+                None,
+            )
+            .await
+        {
+            log::warn!("Failed to save idle gap: {}", e);
+        }
+    }
+
+    log::info!(
+        "🌙 Ambient buffer promoted to meeting {}: {} screen states, {:.0}s audio",
+        meeting_id,
+        states_saved,
+        snapshot.audio.len() as f64 / crate::transcription::audio_mixer::MIX_SAMPLE_RATE as f64
+    );
+
+    if !snapshot.audio.is_empty() {
+        let tm = state.transcription_manager.clone();
+        let database = state.database.clone();
+        let live_intel = state.live_intel_agent.clone();
+        let promoted_id = meeting_id.clone();
+        let app_handle = app.clone();
+        tokio::spawn(async move {
+            if let Err(e) = tm
+                .transcribe_prerecorded(
+                    app_handle,
+                    database,
+                    promoted_id.clone(),
+                    live_intel,
+                    snapshot.audio,
+                )
+                .await
+            {
+                log::error!(
+                    "Failed to transcribe ambient buffer for {}: {}",
+                    promoted_id,
+                    e
+                );
+            }
+        });
+    }
+
+    Ok(meeting_id)
+}
+
+/// Set the rolling ambient buffer length in minutes (0 turns it off and wipes it)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_ambient_buffer_minutes(
+    state: State<'_, AppState>,
+    minutes: u32,
+) -> Result<(), String> {
+    state
+        .settings
+        .set_ambient_buffer_minutes(minutes)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+    state.ambient_capture.buffer().set_minutes(minutes);
+    Ok(())
+}
+
+/// Minutes without input before ambient capture pauses (0 = never); meetings are never paused
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_ambient_idle_timeout(
+    state: State<'_, AppState>,
+    minutes: u32,
+) -> Result<(), String> {
+    state
+        .settings
+        .set_ambient_idle_timeout_mins(minutes)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+    state.ambient_capture.set_idle_timeout_mins(minutes);
+    Ok(())
+}
+
 /// Pause capture (stop all without ending session)
 #[tauri::command(rename_all = "camelCase")]
 pub async fn pause_capture(state: State<'_, AppState>) -> Result<(), String> {
     log::info!("⏸️ Pausing capture");
+    // Pausing means "stop watching": nothing buffered survives it
+    let buffer = state.ambient_capture.buffer();
+    buffer.set_active(false);
+    buffer.clear();
     let engine = state.capture_engine.read();
     let _ = engine.pause();
     state.power_manager.release_assertion();
@@ -5023,11 +8960,20 @@ pub struct AlwaysOnSettings {
 }
 
 #[tauri::command(rename_all = "camelCase")]
-pub async fn get_always_on_settings() -> Result<AlwaysOnSettings, String> {
-    // TODO: Load from persistent settings
+pub async fn get_always_on_settings(
+    state: State<'_, AppState>,
+) -> Result<AlwaysOnSettings, String> {
+    let idle_timeout_mins = state
+        .settings
+        .get_all()
+        .await
+        .map(|s| s.ambient_idle_timeout_mins)
+        .unwrap_or(crate::ambient_capture::DEFAULT_IDLE_TIMEOUT_MINS);
+
+    // TODO: Load the rest from persistent settings
     Ok(AlwaysOnSettings {
         enabled: false,
-        idle_timeout_mins: 5,
+        idle_timeout_mins,
         ambient_interval_secs: 30,
         meeting_interval_secs: 2,
         retention_hours: 24,
@@ -5072,6 +9018,60 @@ pub async fn check_audio_usage() -> Result<bool, String> {
     Ok(MeetingTriggerEngine::check_audio_usage())
 }
 
+fn calendar_link(event: CalendarEventNative) -> crate::database::MeetingCalendarLink {
+    crate::database::MeetingCalendarLink {
+        calendar_event_id: event.event_id,
+        attendees: event.attendees,
+        meeting_url: event.meeting_url,
+    }
+}
+
+/// Manually associate a meeting with one of today's calendar events
+#[tauri::command(rename_all = "camelCase")]
+pub async fn link_meeting_to_event(
+    state: State<'_, AppState>,
+    meeting_id: String,
+    event_id: String,
+) -> Result<crate::database::MeetingCalendarLink, String> {
+    let event = {
+        let client = state.calendar_client.read();
+        let events = client
+            .fetch_events()
+            .map_err(|e| format!("Calendar error: {}", e))?;
+        events
+            .into_iter()
+            .find(|e| e.event_id == event_id)
+            .ok_or_else(|| format!("Calendar event '{}' not found", event_id))?
+    };
+
+    let link = calendar_link(event);
+    state
+        .database
+        .set_meeting_calendar_link(&meeting_id, &link)
+        .await
+        .map_err(|e| format!("Failed to link meeting: {}", e))?;
+
+    log::info!(
+        "📅 Meeting {} linked to calendar event {}",
+        meeting_id,
+        event_id
+    );
+    Ok(link)
+}
+
+/// Calendar event and attendees linked to a meeting, if any
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_meeting_calendar_link(
+    state: State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Option<crate::database::MeetingCalendarLink>, String> {
+    state
+        .database
+        .get_meeting_calendar_link(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get calendar link: {}", e))
+}
+
 /// Dismiss a meeting detection suggestion
 #[tauri::command(rename_all = "camelCase")]
 pub async fn dismiss_meeting_detection(
@@ -5082,6 +9082,182 @@ pub async fn dismiss_meeting_detection(
     Ok(())
 }
 
+/// Start recording for a session the trigger engine auto-started.
+/// Titled from the calendar event when there is one; emits
+/// `meeting-auto-started` so the UI can offer an undo.
+pub async fn start_detected_meeting(
+    app: AppHandle,
+    session: crate::meeting_trigger::MeetingSession,
+) {
+    use crate::meeting_trigger::{AutoStartedMeeting, AUTO_START_UNDO_SECS};
+
+    let state = app.state::<AppState>();
+
+    let already_recording = state.capture_engine.read().get_status().is_recording;
+    if already_recording {
+        // Keep the session so the engine doesn't retry; stop_recording clears it
+        log::info!("🎯 Meeting detected while already recording, not auto-starting");
+        return;
+    }
+
+    let (title, detection_id) = match (&session.calendar_event, &session.detected_app) {
+        (Some(event), _) => (event.title.clone(), format!("cal-{}", event.event_id)),
+        (None, Some(app_name)) => (
+            format!(
+                "{} Meeting {}",
+                app_name,
+                chrono::Local::now().format("%Y-%m-%d %H:%M")
+            ),
+            crate::meeting_trigger::app_detection_id(app_name),
+        ),
+        (None, None) => (
+            format!("Meeting {}", chrono::Local::now().format("%Y-%m-%d %H:%M")),
+            session.id.clone(),
+        ),
+    };
+
+    match start_recording_titled(
+        app.clone(),
+        state.clone(),
+        title.clone(),
+        session.calendar_event.clone(),
+    )
+    .await
+    {
+        Ok(meeting_id) => {
+            let auto_started = AutoStartedMeeting {
+                meeting_id,
+                title,
+                detection_id,
+                undo_until: chrono::Utc::now() + chrono::Duration::seconds(AUTO_START_UNDO_SECS),
+            };
+            log::info!(
+                "🎯 Auto-started recording: {} ({})",
+                auto_started.title,
+                auto_started.meeting_id
+            );
+            state
+                .meeting_trigger
+                .record_auto_start(auto_started.clone());
+            if let Err(e) = app.emit("meeting-auto-started", &auto_started) {
+                log::error!("Failed to emit meeting-auto-started: {}", e);
+            }
+        }
+        Err(e) => {
+            log::error!("Failed to auto-start recording: {}", e);
+            state.meeting_trigger.clear_session();
+        }
+    }
+}
+
+/// Stop a recording the trigger engine decided is over, recording why in the audit log
+pub async fn auto_stop_recording(
+    app: AppHandle,
+    suggestion: crate::meeting_trigger::StopSuggestion,
+) {
+    let state = app.state::<AppState>();
+
+    let is_recording = state.capture_engine.read().get_status().is_recording;
+    if !is_recording {
+        return;
+    }
+    let meeting_id = state.timeline_builder.current_meeting_id();
+
+    if let Err(e) = stop_recording(app.clone(), state.clone()).await {
+        log::error!("Failed to auto-stop recording: {}", e);
+        return;
+    }
+    log::info!("⏹️ Recording auto-stopped: {}", suggestion.detail);
+
+    let audit = crate::audit_log::AuditLog::new(state.database.get_pool().as_ref().clone());
+    let _ = audit
+        .log_action(crate::audit_log::AuditAction {
+            action: "auto_stop_recording".to_string(),
+            target_type: "meeting".to_string(),
+            target_id: meeting_id.unwrap_or_default(),
+            details: Some(
+                serde_json::json!({
+                    "reason": suggestion.reason,
+                    "detail": suggestion.detail,
+                })
+                .to_string(),
+            ),
+            bytes_affected: 0,
+        })
+        .await;
+}
+
+/// Undo an auto-started meeting: stop recording, discard the meeting and its
+/// frames, and stop suggesting the detection that triggered it
+#[tauri::command(rename_all = "camelCase")]
+pub async fn undo_auto_started_meeting(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    meeting_id: String,
+) -> Result<(), String> {
+    let auto_started = state
+        .meeting_trigger
+        .take_undoable_auto_start(&meeting_id)
+        .ok_or_else(|| format!("Meeting {} can no longer be undone", meeting_id))?;
+
+    stop_recording(app.clone(), state.clone()).await?;
+    state
+        .meeting_trigger
+        .dismiss_detection(&auto_started.detection_id);
+
+    state
+        .database
+        .delete_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to delete meeting: {}", e))?;
+
+    if let Ok(data_dir) = app.path().app_data_dir() {
+        let frames_dir = data_dir.join("frames").join(&meeting_id);
+        if frames_dir.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&frames_dir) {
+                log::warn!("Failed to remove frames for {}: {}", meeting_id, e);
+            }
+        }
+    }
+
+    log::info!("↩️ Auto-started meeting undone: {}", meeting_id);
+    Ok(())
+}
+
+/// Configure auto-stop and the grace periods (minutes) before a recording counts as over
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_auto_stop_recording(
+    state: State<'_, AppState>,
+    enabled: bool,
+    idle_grace_minutes: u32,
+    event_grace_minutes: u32,
+) -> Result<(), String> {
+    state
+        .settings
+        .set_auto_stop_recording(enabled, idle_grace_minutes, event_grace_minutes)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+    state
+        .meeting_trigger
+        .set_auto_stop(enabled, idle_grace_minutes, event_grace_minutes);
+    Ok(())
+}
+
+/// Enable/disable starting a recording automatically when a meeting is detected
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_auto_start_on_meeting_detected(
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    state
+        .settings
+        .set_auto_start_on_meeting_detected(enabled)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+    state.meeting_trigger.set_auto_start(enabled);
+    Ok(())
+}
+
 /// Transform window between Insight Deck and Genie mode
 #[tauri::command(rename_all = "camelCase")]
 pub async fn set_genie_mode(window: Window, is_genie: bool) -> Result<(), String> {
@@ -5117,15 +9293,56 @@ pub async fn set_genie_mode(window: Window, is_genie: bool) -> Result<(), String
         window.set_always_on_top(false).map_err(|e| e.to_string())?;
         window.set_resizable(true).map_err(|e| e.to_string())?;
 
-        // Restore to default large size
-        let deck_size = LogicalSize::new(1400.0, 900.0);
-        window.set_size(deck_size).map_err(|e| e.to_string())?;
+        // Restore to default large size
+        let deck_size = LogicalSize::new(1400.0, 900.0);
+        window.set_size(deck_size).map_err(|e| e.to_string())?;
+
+        // Center the window
+        window.center().map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Global Shortcuts
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Current global shortcut bindings
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_shortcuts() -> Result<Vec<crate::shortcuts::ShortcutBinding>, String> {
+    Ok(crate::shortcuts::shortcuts().bindings())
+}
+
+/// Rebind a global shortcut action ("toggle_recording", "pin_moment",
+/// "toggle_genie", "quick_note"); an empty accelerator unbinds it. Takes
+/// effect immediately and is saved to settings.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_shortcut(
+    state: State<'_, AppState>,
+    action: String,
+    accelerator: Option<String>,
+) -> Result<Vec<crate::shortcuts::ShortcutBinding>, String> {
+    let action = crate::shortcuts::ShortcutAction::parse(&action)
+        .ok_or_else(|| format!("Unknown shortcut action: {}", action))?;
+    let registry = crate::shortcuts::shortcuts();
+    let previous = registry
+        .bindings()
+        .into_iter()
+        .find(|b| b.action == action)
+        .and_then(|b| b.accelerator);
 
-        // Center the window
-        window.center().map_err(|e| e.to_string())?;
+    registry.set(action, accelerator.as_deref())?;
+    if let Err(e) = state
+        .settings
+        .set_global_shortcuts(&registry.saved_map())
+        .await
+    {
+        // Keep the live binding in step with what is saved
+        let _ = registry.set(action, previous.as_deref());
+        return Err(format!("Failed to save shortcut: {}", e));
     }
-
-    Ok(())
+    Ok(registry.bindings())
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -5170,6 +9387,12 @@ pub async fn start_dork_session(state: State<'_, AppState>) -> Result<String, St
     let session = crate::dork_mode::DorkModeSession::new(uuid::Uuid::new_v4().to_string());
     let session_id = session.session_id.clone();
 
+    state
+        .database
+        .create_dork_session(&session_id, session.start_time)
+        .await
+        .map_err(|e| format!("Failed to save dork session: {}", e))?;
+
     *state.dork_mode_session.write() = Some(session);
 
     log::info!("📚 Dork Mode session started: {}", session_id);
@@ -5179,13 +9402,67 @@ pub async fn start_dork_session(state: State<'_, AppState>) -> Result<String, St
 /// Add content to the current dork mode session
 #[tauri::command(rename_all = "camelCase")]
 pub async fn add_dork_content(state: State<'_, AppState>, content: String) -> Result<(), String> {
+    let session_id = {
+        let session_guard = state.dork_mode_session.read();
+        let session = session_guard
+            .as_ref()
+            .ok_or("No active dork mode session")?;
+        if !session.accumulate_content(&content) {
+            return Ok(());
+        }
+        session.session_id.clone()
+    };
+
+    // Persisted chunk by chunk so nothing is lost if the app quits mid-session
+    state
+        .database
+        .add_dork_content(&session_id, &content)
+        .await
+        .map_err(|e| format!("Failed to save dork content: {}", e))
+}
+
+/// Current dork session and how much content it has gathered
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_dork_session_status(
+    state: State<'_, AppState>,
+) -> Result<crate::dork_mode::DorkSessionStatus, String> {
+    let session_mode = state
+        .settings
+        .get_session_mode()
+        .await
+        .map_err(|e| format!("Failed to get session mode: {}", e))?;
+
     let session_guard = state.dork_mode_session.read();
-    if let Some(session) = session_guard.as_ref() {
-        session.accumulate_content(&content);
-        Ok(())
-    } else {
-        Err("No active dork mode session".to_string())
-    }
+    let session = session_guard.as_ref();
+    let active = session.is_some_and(|s| s.is_active());
+    let (chunk_count, content_chars) = session.map(|s| s.content_size()).unwrap_or((0, 0));
+    Ok(crate::dork_mode::DorkSessionStatus {
+        auto_capture: active && session_mode == "dork" && state.accessibility_capture.is_running(),
+        session_mode,
+        active,
+        session_id: session.map(|s| s.session_id.clone()),
+        started_at: session.map(|s| s.start_time),
+        chunk_count,
+        content_chars,
+    })
+}
+
+/// Set which apps feed a dork session from accessibility capture (empty = all)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_dork_app_allowlist(
+    state: State<'_, AppState>,
+    apps: Vec<String>,
+) -> Result<(), String> {
+    let apps: Vec<String> = apps
+        .into_iter()
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect();
+    state
+        .settings
+        .set_dork_app_allowlist(&apps)
+        .await
+        .map_err(|e| format!("Failed to set dork app allowlist: {}", e))
 }
 
 /// End dork mode session and generate study materials
@@ -5230,8 +9507,39 @@ pub async fn end_dork_session(
     let materials =
         crate::dork_mode::generate_study_materials(&ai_client, &session_id, &content).await?;
 
-    // Save to database (if we add the table)
-    // TODO: state.database.save_study_materials(...).await?;
+    state
+        .database
+        .save_study_materials(
+            &uuid::Uuid::new_v4().to_string(),
+            &session_id,
+            Some(&materials.summary),
+            serde_json::to_string(&materials.concepts).ok().as_deref(),
+            serde_json::to_string(&materials.quiz).ok().as_deref(),
+            serde_json::to_string(&materials.flashcards).ok().as_deref(),
+            None,
+        )
+        .await
+        .map_err(|e| format!("Failed to save study materials: {}", e))?;
+    match crate::spaced_repetition::create_cards(
+        &state.database,
+        &session_id,
+        &materials.flashcards,
+    )
+    .await
+    {
+        Ok(added) => {
+            log::info!("🃏 {} new flashcards from session {}", added, session_id);
+            crate::spaced_repetition::refresh_due_badge(&state.database).await;
+        }
+        Err(e) => log::warn!("Failed to create flashcards: {}", e),
+    }
+    // Only marked ended once the materials are safe; a failed generation
+    // leaves the session resumable
+    state
+        .database
+        .set_dork_session_ended(&session_id, Some(chrono::Utc::now()))
+        .await
+        .map_err(|e| format!("Failed to end dork session: {}", e))?;
 
     // Emit completion event
     let _ = app.emit("dork:materials_ready", &materials);
@@ -5247,28 +9555,278 @@ pub async fn end_dork_session(
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_study_materials(
     state: State<'_, AppState>,
-    meeting_id: String,
+    session_id: String,
 ) -> Result<Option<crate::dork_mode::StudyMaterials>, String> {
-    // TODO: Implement database retrieval
-    let _ = state;
-    let _ = meeting_id;
-    Ok(None)
+    let record = state
+        .database
+        .get_study_materials(&session_id)
+        .await
+        .map_err(|e| format!("Failed to get study materials: {}", e))?;
+    Ok(record
+        .as_ref()
+        .map(crate::dork_mode::StudyMaterials::from_record))
+}
+
+/// Past dork mode sessions, newest first
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_dork_sessions(
+    state: State<'_, AppState>,
+    limit: Option<i64>,
+) -> Result<Vec<crate::database::DorkSessionRecord>, String> {
+    state
+        .database
+        .list_dork_sessions(limit.unwrap_or(50))
+        .await
+        .map_err(|e| format!("Failed to list dork sessions: {}", e))
+}
+
+/// Continue a stored session: its captured content is reloaded so new
+/// content adds to it and study materials cover the whole session
+#[tauri::command(rename_all = "camelCase")]
+pub async fn resume_dork_session(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<crate::database::DorkSessionRecord, String> {
+    if let Some(current) = state.dork_mode_session.read().as_ref() {
+        if current.is_active() && current.session_id != session_id {
+            return Err("Another dork mode session is active".to_string());
+        }
+    }
+
+    let record = state
+        .database
+        .get_dork_session(&session_id)
+        .await
+        .map_err(|e| format!("Failed to get dork session: {}", e))?
+        .ok_or("Dork session not found")?;
+    let content = state
+        .database
+        .get_dork_content(&session_id)
+        .await
+        .map_err(|e| format!("Failed to load dork content: {}", e))?;
+    state
+        .database
+        .set_dork_session_ended(&session_id, None)
+        .await
+        .map_err(|e| format!("Failed to reopen dork session: {}", e))?;
+
+    *state.dork_mode_session.write() = Some(crate::dork_mode::DorkModeSession::resume(
+        session_id.clone(),
+        record.started_at,
+        content,
+    ));
+
+    log::info!(
+        "📚 Dork Mode session resumed: {} ({} chunks)",
+        session_id,
+        record.chunk_count
+    );
+    Ok(crate::database::DorkSessionRecord {
+        status: "active".to_string(),
+        ended_at: None,
+        ..record
+    })
+}
+
+/// Write a session's study notes and flashcards to the vault's Study/ folder
+#[tauri::command(rename_all = "camelCase")]
+pub async fn export_study_materials_to_vault(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<String>, String> {
+    let record = state
+        .database
+        .get_study_materials(&session_id)
+        .await
+        .map_err(|e| format!("Failed to get study materials: {}", e))?
+        .ok_or("No study materials for this session")?;
+    let materials = crate::dork_mode::StudyMaterials::from_record(&record);
+    crate::dork_mode::export_to_vault(&state.vault_manager, &materials).await
+}
+
+/// How often new activities are sorted into project clusters
+const ACTIVITY_CLUSTER_INTERVAL_SECS: u64 = 30 * 60;
+
+/// Sort new activity_log entries into project clusters in the background.
+/// Runs are skipped while no vector backend is configured.
+pub fn spawn_activity_cluster_task(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(
+            ACTIVITY_CLUSTER_INTERVAL_SECS,
+        ));
+
+        loop {
+            ticker.tick().await;
+
+            let Some(state) = app.try_state::<AppState>() else {
+                continue;
+            };
+            let Some(store) = vector_store(&state).await else {
+                continue;
+            };
+            let ai_client = state.ai_client.read().clone();
+            match crate::activity_clusters::update_clusters(&state.database, &store, &ai_client)
+                .await
+            {
+                Ok(run) if run.embedded > 0 => log::info!(
+                    "🗂️ Activity clustering: {} embedded, {} assigned, {} new clusters, {} waiting",
+                    run.embedded,
+                    run.assigned,
+                    run.new_clusters,
+                    run.unclustered
+                ),
+                Ok(_) => {}
+                Err(e) => log::warn!("Activity clustering failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Project clusters with the time spent in each over `period` ("today",
+/// "week", "month", "30d" or "all")
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_activity_clusters(
+    period: String,
+    state: State<'_, AppState>,
+) -> Result<crate::activity_clusters::ActivityClusterReport, String> {
+    crate::activity_clusters::report(&state.database, &period).await
+}
+
+/// Give a project cluster a name of the user's choosing
+#[tauri::command(rename_all = "camelCase")]
+pub async fn rename_activity_cluster(
+    id: String,
+    label: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("Cluster name cannot be empty".to_string());
+    }
+    let renamed = state
+        .database
+        .rename_activity_cluster(&id, label)
+        .await
+        .map_err(|e| format!("Failed to rename activity cluster: {}", e))?;
+    if !renamed {
+        return Err("Activity cluster not found".to_string());
+    }
+    Ok(())
+}
+
+/// How often the tray's "cards due" badge is recounted
+const FLASHCARD_BADGE_REFRESH_SECS: u64 = 300;
+
+/// Keep the tray's due-card count current as cards come due
+pub fn spawn_flashcard_badge_task(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker =
+            tokio::time::interval(std::time::Duration::from_secs(FLASHCARD_BADGE_REFRESH_SECS));
+
+        loop {
+            ticker.tick().await;
+
+            if let Some(state) = app.try_state::<AppState>() {
+                crate::spaced_repetition::refresh_due_badge(&state.database).await;
+            }
+        }
+    });
+}
+
+/// Flashcards due for review, most overdue first
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_due_flashcards(
+    state: State<'_, AppState>,
+    limit: Option<i64>,
+) -> Result<Vec<crate::database::FlashcardRecord>, String> {
+    state
+        .database
+        .get_due_flashcards(chrono::Utc::now(), limit.unwrap_or(20))
+        .await
+        .map_err(|e| format!("Failed to get due flashcards: {}", e))
+}
+
+/// Grade a review 0-5 (SM-2: below 3 means forgotten) and reschedule the card
+#[tauri::command(rename_all = "camelCase")]
+pub async fn grade_flashcard(
+    state: State<'_, AppState>,
+    card_id: String,
+    grade: u8,
+) -> Result<crate::database::FlashcardRecord, String> {
+    let card = crate::spaced_repetition::grade_card(&state.database, &card_id, grade).await?;
+    crate::spaced_repetition::refresh_due_badge(&state.database).await;
+    Ok(card)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_flashcard_stats(
+    state: State<'_, AppState>,
+) -> Result<crate::spaced_repetition::FlashcardStats, String> {
+    crate::spaced_repetition::stats(&state.database).await
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
 // Meeting Intelligence System Commands
 // ═══════════════════════════════════════════════════════════════════════════
 
-/// Generate AI meeting notes from transcripts
+/// Generate AI meeting notes from transcripts. Each call is stored as a new
+/// version (which becomes active); earlier versions are kept.
 #[tauri::command(rename_all = "camelCase")]
 pub async fn generate_meeting_notes(
     state: State<'_, AppState>,
     meeting_id: String,
+    instructions: Option<String>,
+    style: Option<crate::meeting_notes::NotesStyle>,
+    start_secs: Option<f64>,
+    end_secs: Option<f64>,
 ) -> Result<crate::meeting_notes::GeneratedNotes, String> {
     let ai_client = state.ai_client.read().clone();
     let generator = crate::meeting_notes::MeetingNotesGenerator::new(ai_client);
+    let options = crate::meeting_notes::NotesOptions {
+        instructions,
+        style: style.unwrap_or_default(),
+        start_secs,
+        end_secs,
+    };
+
+    generator
+        .generate_notes(&meeting_id, &state.database, &options)
+        .await
+}
+
+/// List every notes generation for a meeting, newest first
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_meeting_notes_versions(
+    state: State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<crate::database::MeetingNotesVersion>, String> {
+    state
+        .database
+        .list_meeting_notes_versions(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to list notes versions: {}", e))
+}
+
+/// Pick which notes version is shown, analyzed and exported
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_active_notes_version(
+    state: State<'_, AppState>,
+    meeting_id: String,
+    version_id: String,
+) -> Result<(), String> {
+    let updated = state
+        .database
+        .set_active_notes_version(&meeting_id, &version_id)
+        .await
+        .map_err(|e| format!("Failed to set active notes version: {}", e))?;
 
-    generator.generate_notes(&meeting_id, &state.database).await
+    if !updated {
+        return Err(format!(
+            "Notes version {} not found for meeting {}",
+            version_id, meeting_id
+        ));
+    }
+    Ok(())
 }
 
 /// Get existing meeting notes
@@ -5284,6 +9842,57 @@ pub async fn get_meeting_notes(
         .map_err(|e| format!("Failed to get meeting notes: {}", e))
 }
 
+/// Recurring meeting series with aggregate stats, most recent first.
+/// Meetings not in a series yet are grouped first.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_meeting_series(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::meeting_series::MeetingSeriesSummary>, String> {
+    let pool = state.database.get_pool();
+    crate::meeting_series::detect_series(&pool).await?;
+    crate::meeting_series::list_series(&pool).await
+}
+
+/// A series' member meetings, stats and attendance
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_series(
+    state: State<'_, AppState>,
+    series_id: String,
+) -> Result<crate::meeting_series::MeetingSeries, String> {
+    let pool = state.database.get_pool();
+    crate::meeting_series::get_series(&pool, &series_id).await
+}
+
+/// Compare two meetings' decisions, action items and topics (earlier vs later),
+/// with an AI delta summary. Missing notes are generated when asked; otherwise
+/// the comparison is flagged partial.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn compare_meetings(
+    state: State<'_, AppState>,
+    meeting_id_a: String,
+    meeting_id_b: String,
+    generate_missing_notes: Option<bool>,
+) -> Result<crate::meeting_comparison::MeetingComparison, String> {
+    let ai_client = state.ai_client.read().clone();
+    crate::meeting_comparison::compare_meetings(
+        &state.database,
+        &ai_client,
+        &meeting_id_a,
+        &meeting_id_b,
+        generate_missing_notes.unwrap_or(false),
+    )
+    .await
+}
+
+/// Write a comparison to the vault as a "Sync Delta" note; returns its path
+#[tauri::command(rename_all = "camelCase")]
+pub async fn export_meeting_comparison(
+    state: State<'_, AppState>,
+    comparison: crate::meeting_comparison::MeetingComparison,
+) -> Result<String, String> {
+    crate::meeting_comparison::export_to_vault(&state.vault_manager, &comparison).await
+}
+
 /// Add a comment to a meeting
 #[tauri::command(rename_all = "camelCase")]
 pub async fn add_meeting_comment(
@@ -5312,6 +9921,40 @@ pub async fn add_meeting_comment(
     Ok(id)
 }
 
+/// Jot a note against the recording meeting (or park it when none is
+/// recording); used by the tray's mini input and the quick-note shortcut
+#[tauri::command(rename_all = "camelCase")]
+pub async fn quick_note(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    text: String,
+) -> Result<crate::quick_note::QuickNoteSaved, String> {
+    crate::quick_note::save_quick_note(&app, &state, &text).await
+}
+
+/// Quick notes taken while no meeting was recording
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_unattached_notes(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::database::UnattachedNote>, String> {
+    state
+        .database
+        .get_unattached_notes()
+        .await
+        .map_err(|e| format!("Failed to get notes: {}", e))
+}
+
+/// Attach a parked quick note to a meeting; returns the new comment id
+#[tauri::command(rename_all = "camelCase")]
+pub async fn attach_quick_note(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    note_id: i64,
+    meeting_id: String,
+) -> Result<String, String> {
+    crate::quick_note::attach_note(&app, &state, note_id, &meeting_id).await
+}
+
 /// Get comments for a meeting
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_meeting_comments(
@@ -5374,6 +10017,7 @@ pub async fn get_meeting_analysis(
 /// Get vault configuration status
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_vault_status(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+    crate::subsystems::ensure_vault(&state).await;
     let status = state.vault_manager.get_status().await;
     serde_json::to_value(&status).map_err(|e| e.to_string())
 }
@@ -5406,50 +10050,140 @@ pub async fn create_vault_topic(
     serde_json::to_value(&topic).map_err(|e| e.to_string())
 }
 
-/// Export an existing meeting to the vault
-#[tauri::command(rename_all = "camelCase")]
-pub async fn export_meeting_to_vault(
-    state: State<'_, AppState>,
-    topic_name: String,
-    meeting_id: String,
-) -> Result<String, String> {
-    internal_export_meeting(
-        state.database.clone(),
-        state.vault_manager.clone(),
-        topic_name,
-        meeting_id,
-    )
-    .await
+/// Export an existing meeting to the vault
+#[tauri::command(rename_all = "camelCase")]
+pub async fn export_meeting_to_vault(
+    state: State<'_, AppState>,
+    topic_name: String,
+    meeting_id: String,
+) -> Result<String, String> {
+    let template = active_export_template(&state.prompt_manager, &state.settings).await;
+    let conflict_policy = vault_conflict_policy(&state.settings).await;
+    internal_export_meeting(
+        state.database.clone(),
+        state.vault_manager.clone(),
+        topic_name,
+        meeting_id,
+        template,
+        conflict_policy,
+    )
+    .await
+}
+
+/// Render a meeting through an export template without writing to the vault.
+/// `template_id` defaults to the active template.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn preview_export_template(
+    meeting_id: String,
+    template_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let template = match template_id {
+        Some(id) => {
+            state
+                .prompt_manager
+                .get_prompt(&id)
+                .await
+                .map_err(|e| format!("Failed to get template: {}", e))?
+                .filter(|p| p.category == crate::export_template::EXPORT_TEMPLATE_CATEGORY)
+                .ok_or_else(|| format!("Export template not found: {}", id))?
+                .system_prompt
+        }
+        None => active_export_template(&state.prompt_manager, &state.settings).await,
+    };
+
+    let meeting = build_meeting_export(&state.database, &meeting_id).await?;
+    crate::obsidian_vault::render_meeting_note(&meeting, &template)
+}
+
+/// Choose which export template vault exports use (None = builtin default)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_export_template(
+    template_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(ref id) = template_id {
+        let prompt = state
+            .prompt_manager
+            .get_prompt(id)
+            .await
+            .map_err(|e| format!("Failed to get template: {}", e))?
+            .filter(|p| p.category == crate::export_template::EXPORT_TEMPLATE_CATEGORY)
+            .ok_or_else(|| format!("Export template not found: {}", id))?;
+        crate::export_template::validate(&prompt.system_prompt)?;
+    }
+
+    state
+        .settings
+        .set_export_template_id(template_id.as_deref())
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))
+}
+
+/// Template text for vault exports. Falls back to the builtin layout when the
+/// selected template is missing or no longer valid.
+pub async fn active_export_template(
+    prompt_manager: &crate::prompt_manager::PromptManager,
+    settings: &crate::settings::SettingsManager,
+) -> String {
+    let template_id = settings
+        .get_all()
+        .await
+        .ok()
+        .and_then(|s| s.export_template_id);
+
+    if let Some(id) = template_id {
+        match prompt_manager.get_prompt(&id).await {
+            Ok(Some(prompt)) => match crate::export_template::validate(&prompt.system_prompt) {
+                Ok(()) => return prompt.system_prompt,
+                Err(e) => log::warn!("Export template {} is invalid, using default: {}", id, e),
+            },
+            Ok(None) => log::warn!("Export template {} not found, using default", id),
+            Err(e) => log::warn!("Failed to load export template {}: {}", id, e),
+        }
+    }
+
+    crate::export_template::DEFAULT_EXPORT_TEMPLATE.to_string()
+}
+
+/// Reject export templates with unknown placeholders before they are saved
+fn validate_prompt_template(category: &str, template: &str) -> Result<(), String> {
+    if category == crate::export_template::EXPORT_TEMPLATE_CATEGORY {
+        crate::export_template::validate(template)
+            .map_err(|e| format!("Invalid export template: {}", e))?;
+    }
+    Ok(())
 }
 
-/// Internal helper for exporting a meeting to the vault
-pub async fn internal_export_meeting(
-    database: Arc<crate::database::DatabaseManager>,
-    vault_manager: Arc<crate::obsidian_vault::VaultManager>,
-    topic_name: String,
-    meeting_id: String,
-) -> Result<String, String> {
+/// Timeline events listed under a meeting note's "Highlights"
+const EXPORT_HIGHLIGHTS: usize = 5;
+
+/// Gather everything a vault meeting note shows
+async fn build_meeting_export(
+    database: &Arc<crate::database::DatabaseManager>,
+    meeting_id: &str,
+) -> Result<crate::obsidian_vault::MeetingExport, String> {
     // Get meeting data from database
     let meeting = database
-        .get_meeting(&meeting_id)
+        .get_meeting(meeting_id)
         .await
         .map_err(|e| format!("Failed to get meeting: {}", e))?
         .ok_or_else(|| format!("Meeting {} not found", meeting_id))?;
 
     let transcripts = database
-        .get_transcripts(&meeting_id)
+        .get_transcripts(meeting_id)
         .await
         .map_err(|e| format!("Failed to get transcripts: {}", e))?;
 
     // Get meeting notes if available
     let notes = database
-        .get_meeting_notes(&meeting_id)
+        .get_meeting_notes(meeting_id)
         .await
         .map_err(|e| format!("Failed to get notes: {}", e))?;
 
     // Get frames for screenshot paths
     let frames = database
-        .get_frames(&meeting_id, 1000)
+        .get_frames(meeting_id, 1000)
         .await
         .map_err(|e| format!("Failed to get frames: {}", e))?;
 
@@ -5460,9 +10194,9 @@ pub async fn internal_export_meeting(
 
     let screenshot_paths: Vec<String> = frames.iter().filter_map(|f| f.file_path.clone()).collect();
 
-    let summary = notes.as_ref().and_then(|n| n.summary.as_deref());
-    let key_topics = notes.as_ref().and_then(|n| n.key_topics.as_deref());
-    let action_items = notes.as_ref().and_then(|n| n.action_items.as_deref());
+    let summary = notes.as_ref().and_then(|n| n.summary.clone());
+    let key_topics = notes.as_ref().and_then(|n| n.key_topics.clone());
+    let action_items = notes.as_ref().and_then(|n| n.action_items.clone());
 
     // Generate AI Intelligence from transcripts
     let mut intel_agent = LiveIntelAgent::new();
@@ -5477,98 +10211,309 @@ pub async fn internal_export_meeting(
     }
     let insights = intel_agent.get_all_events().to_vec();
 
+    // Explicitly marked decisions win over agent-inferred ones with the same text
+    let marked_decisions = database.get_decisions(meeting_id).await.unwrap_or_default();
+    let marked_texts: std::collections::HashSet<String> = marked_decisions
+        .iter()
+        .map(|d| crate::action_items::normalize_text(&d.text))
+        .collect();
+
     // Categorize insights into markdown sections
-    let mut ai_action_items = Vec::new();
-    let mut ai_decisions = Vec::new();
-    let mut ai_risks = Vec::new();
-    let mut ai_commitments = Vec::new();
-    let mut ai_questions = Vec::new();
-    let mut ai_topic_shifts = Vec::new();
+    let mut intelligence = crate::export_template::IntelligenceSections {
+        decisions: marked_decisions
+            .iter()
+            .map(|d| match &d.context {
+                Some(context) if !context.trim().is_empty() => {
+                    format!("- ✅ {} — {}", d.text, context.trim())
+                }
+                _ => format!("- ✅ {}", d.text),
+            })
+            .collect(),
+        ..Default::default()
+    };
 
     for insight in &insights {
         match insight {
             LiveInsightEvent::ActionItem { text, assignee, .. } => {
                 if let Some(a) = assignee {
-                    ai_action_items.push(format!("- {} *(assigned: {})*", text, a));
+                    intelligence
+                        .action_items
+                        .push(format!("- {} *(assigned: {})*", text, a));
                 } else {
-                    ai_action_items.push(format!("- {}", text));
+                    intelligence.action_items.push(format!("- {}", text));
                 }
             }
             LiveInsightEvent::Decision { text, .. } => {
-                ai_decisions.push(format!("- {}", text));
+                if !marked_texts.contains(&crate::action_items::normalize_text(text)) {
+                    intelligence.decisions.push(format!("- {}", text));
+                }
             }
             LiveInsightEvent::RiskSignal { text, .. } => {
-                ai_risks.push(format!("- ⚠️ {}", text));
+                intelligence.risks.push(format!("- ⚠️ {}", text));
             }
             LiveInsightEvent::Commitment { text, .. } => {
-                ai_commitments.push(format!("- 🤝 {}", text));
+                intelligence.commitments.push(format!("- 🤝 {}", text));
             }
             LiveInsightEvent::QuestionSuggestion { text, .. } => {
-                ai_questions.push(format!("- ❓ {}", text));
+                intelligence.questions.push(format!("- ❓ {}", text));
             }
             LiveInsightEvent::TopicShift {
                 from_topic,
                 to_topic,
                 ..
             } => {
-                ai_topic_shifts.push(format!("- 🎯 {} → {}", from_topic, to_topic));
+                intelligence
+                    .topic_shifts
+                    .push(format!("- 🎯 {} → {}", from_topic, to_topic));
             }
         }
     }
 
-    let mut intelligence_md = String::new();
-    if !ai_action_items.is_empty() {
-        intelligence_md.push_str("### Action Items\n\n");
-        intelligence_md.push_str(&ai_action_items.join("\n"));
-        intelligence_md.push_str("\n\n");
-    }
-    if !ai_decisions.is_empty() {
-        intelligence_md.push_str("### Decisions\n\n");
-        intelligence_md.push_str(&ai_decisions.join("\n"));
-        intelligence_md.push_str("\n\n");
+    let attendees = database
+        .get_meeting_calendar_link(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get calendar link: {}", e))?
+        .map(|link| link.attendees)
+        .unwrap_or_default();
+
+    let highlights =
+        crate::importance::meeting_highlights(database, meeting_id, EXPORT_HIGHLIGHTS).await?;
+
+    // Tracked action items win over the notes' list; checkboxes mirror status
+    let tracked_items = database
+        .list_action_items(Some(meeting_id))
+        .await
+        .map_err(|e| format!("Failed to get action items: {}", e))?;
+    let action_items = if tracked_items.is_empty() {
+        action_items
+    } else {
+        Some(crate::action_items::to_markdown(&tracked_items))
+    };
+
+    Ok(crate::obsidian_vault::MeetingExport {
+        meeting_id: meeting_id.to_string(),
+        title: meeting.title,
+        started_at: meeting.started_at.to_rfc3339(),
+        duration_secs: meeting.duration_seconds,
+        transcripts: transcript_tuples,
+        summary,
+        highlights: crate::importance::highlights_markdown(&highlights),
+        key_topics,
+        action_items,
+        intelligence,
+        screenshot_paths,
+        attendees,
+    })
+}
+
+/// Internal helper for exporting a meeting to the vault
+pub async fn internal_export_meeting(
+    database: Arc<crate::database::DatabaseManager>,
+    vault_manager: Arc<crate::obsidian_vault::VaultManager>,
+    topic_name: String,
+    meeting_id: String,
+    template: String,
+    conflict_policy: crate::obsidian_vault::VaultConflictPolicy,
+) -> Result<String, String> {
+    let meeting = build_meeting_export(&database, &meeting_id).await?;
+
+    let previous_hashes: std::collections::HashMap<String, String> = database
+        .get_vault_exports(Some(&meeting_id))
+        .await
+        .map_err(|e| format!("Failed to get vault exports: {}", e))?
+        .into_iter()
+        .map(|r| (r.path, r.content_hash))
+        .collect();
+
+    let outcome = vault_manager
+        .export_meeting(
+            &topic_name,
+            &meeting,
+            &template,
+            &previous_hashes,
+            conflict_policy,
+        )
+        .await?;
+
+    // Record how each edited note was handled
+    let audit = crate::audit_log::AuditLog::new(database.get_pool().as_ref().clone());
+    for conflict in &outcome.conflicts {
+        log::info!(
+            "Vault note edited since last export ({}): {}",
+            conflict.resolution,
+            conflict.path
+        );
+        if let Err(e) = audit
+            .log_action(crate::audit_log::AuditAction {
+                action: "vault_conflict".to_string(),
+                target_type: "vault_file".to_string(),
+                target_id: conflict.path.clone(),
+                details: Some(
+                    serde_json::json!({
+                        "meeting_id": meeting_id,
+                        "resolution": conflict.resolution,
+                        "policy": conflict_policy.as_str(),
+                    })
+                    .to_string(),
+                ),
+                bytes_affected: 0,
+            })
+            .await
+        {
+            log::warn!("Failed to audit vault conflict: {}", e);
+        }
     }
-    if !ai_risks.is_empty() {
-        intelligence_md.push_str("### Risk Signals\n\n");
-        intelligence_md.push_str(&ai_risks.join("\n"));
-        intelligence_md.push_str("\n\n");
+
+    if outcome.refused() {
+        let paths: Vec<&str> = outcome.conflicts.iter().map(|c| c.path.as_str()).collect();
+        return Err(format!(
+            "Export refused: {} edited in the vault since the last export. Set the vault conflict policy to \"merge\" to keep your edits and append the new content.",
+            paths.join(", ")
+        ));
     }
-    if !ai_commitments.is_empty() {
-        intelligence_md.push_str("### Commitments\n\n");
-        intelligence_md.push_str(&ai_commitments.join("\n"));
-        intelligence_md.push_str("\n\n");
+
+    for file in &outcome.files {
+        if let Err(e) = database
+            .record_vault_export(&file.path, &meeting_id, &file.content_hash)
+            .await
+        {
+            log::warn!("Failed to record vault export {}: {}", file.path, e);
+        }
     }
-    if !ai_questions.is_empty() {
-        intelligence_md.push_str("### Questions & Suggestions\n\n");
-        intelligence_md.push_str(&ai_questions.join("\n"));
-        intelligence_md.push_str("\n\n");
+
+    // Keep exported screenshots out of storage cleanup
+    if let Err(e) = database
+        .retain_keyframes(&meeting.screenshot_paths, "vault_export")
+        .await
+    {
+        log::warn!("Failed to mark exported keyframes as retained: {}", e);
     }
-    if !ai_topic_shifts.is_empty() {
-        intelligence_md.push_str("### Topic Shifts\n\n");
-        intelligence_md.push_str(&ai_topic_shifts.join("\n"));
-        intelligence_md.push_str("\n\n");
+
+    Ok(outcome.meeting_dir)
+}
+
+/// Vault conflict policy from settings (merge when unset or unrecognized)
+async fn vault_conflict_policy(
+    settings: &crate::settings::SettingsManager,
+) -> crate::obsidian_vault::VaultConflictPolicy {
+    settings
+        .get_all()
+        .await
+        .ok()
+        .and_then(|s| crate::obsidian_vault::VaultConflictPolicy::parse(&s.vault_conflict_policy))
+        .unwrap_or(crate::obsidian_vault::VaultConflictPolicy::Merge)
+}
+
+/// Set what re-exports do with notes edited in the vault ("merge" or "refuse")
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_vault_conflict_policy(
+    policy: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let policy = crate::obsidian_vault::VaultConflictPolicy::parse(&policy).ok_or_else(|| {
+        format!(
+            "Unknown vault conflict policy: {} (use merge or refuse)",
+            policy
+        )
+    })?;
+
+    state
+        .settings
+        .set_vault_conflict_policy(policy.as_str())
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))
+}
+
+/// File auto-exported meetings of a recurring series under the series' topic
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_vault_series_folders(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings
+        .set_vault_series_folders(enabled)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))
+}
+
+/// Exported notes whose on-disk content no longer matches the last export
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_vault_export_conflicts(
+    meeting_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::obsidian_vault::VaultExportDrift>, String> {
+    let records = state
+        .database
+        .get_vault_exports(meeting_id.as_deref())
+        .await
+        .map_err(|e| format!("Failed to get vault exports: {}", e))?;
+
+    let mut drifted = Vec::new();
+    for record in records {
+        if let Some(status) =
+            crate::obsidian_vault::check_drift(&record.path, &record.content_hash).await
+        {
+            drifted.push(crate::obsidian_vault::VaultExportDrift {
+                path: record.path,
+                meeting_id: record.meeting_id,
+                exported_at: record.exported_at,
+                status: status.to_string(),
+            });
+        }
     }
+    Ok(drifted)
+}
 
-    let intelligence = if intelligence_md.is_empty() {
-        None
-    } else {
-        Some(intelligence_md.as_str())
+/// Write a daily or weekly digest note to the vault; `date` (YYYY-MM-DD)
+/// defaults to today. Returns the note's path.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn generate_digest(
+    period: String,
+    date: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let period = crate::digest::DigestPeriod::parse(&period)
+        .ok_or_else(|| format!("Unknown digest period: {} (use daily or weekly)", period))?;
+    let date = match date {
+        Some(d) => chrono::NaiveDate::parse_from_str(&d, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid date {}: {}", d, e))?,
+        None => chrono::Local::now().date_naive(),
     };
 
-    vault_manager
-        .export_meeting(
-            &topic_name,
-            &meeting_id,
-            &meeting.title,
-            &meeting.started_at.to_rfc3339(),
-            meeting.duration_seconds,
-            &transcript_tuples,
-            summary,
-            key_topics,
-            action_items,
-            intelligence,
-            &screenshot_paths,
-        )
+    let ai_client = state.ai_client.read().clone();
+    crate::digest::generate_digest(
+        &state.database,
+        &state.vault_manager,
+        &ai_client,
+        period,
+        date,
+    )
+    .await
+}
+
+/// Turn the evening digest on/off and set its local time ("HH:MM")
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_digest_schedule(
+    enabled: bool,
+    time: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let parsed = crate::digest::parse_digest_time(&time)?;
+
+    state
+        .settings
+        .set_digest_schedule(enabled, &parsed.format("%H:%M").to_string())
         .await
+        .map_err(|e| format!("Failed to save setting: {}", e))?;
+
+    state.digest_scheduler.set_time(parsed);
+    if enabled {
+        state.digest_scheduler.start();
+    } else {
+        state.digest_scheduler.stop();
+    }
+    Ok(())
 }
 
 /// Read a file from the vault
@@ -5722,111 +10667,239 @@ pub async fn generate_meeting_intel(
     event_id: String,
     topic_name: String,
 ) -> Result<serde_json::Value, String> {
-    use crate::attendee_intel;
+    // EventKit first, then events from the last .ics import
+    let event = state.calendar_client.read().find_event(&event_id)?;
+    prepare_meeting_intel(&state, &event, &topic_name).await
+}
 
-    // Fetch calendar events to find the target event
-    let event = {
-        let client = state.calendar_client.read();
-        let events = client
-            .fetch_events()
-            .map_err(|e| format!("Calendar error: {}", e))?;
-        events
-            .into_iter()
-            .find(|e| e.event_id == event_id)
-            .ok_or_else(|| format!("Calendar event '{}' not found", event_id))?
-    };
+/// Write person, company and meeting-prep notes for one calendar event
+async fn prepare_meeting_intel(
+    state: &State<'_, AppState>,
+    event: &crate::calendar_client::CalendarEventNative,
+    topic_name: &str,
+) -> Result<serde_json::Value, String> {
+    use crate::attendee_intel;
 
     if event.attendees.is_empty() {
         return Err("No attendees found for this calendar event".to_string());
     }
 
-    // Generate AI intelligence for all attendees
+    // Same names as the entity list, so notes line up with entity analytics
+    let names = attendee_intel::link_attendees(&state.database.get_pool(), &event.attendees).await;
+
+    // Reuse person/company notes that are still fresh instead of calling the AI
+    let staleness_days = state
+        .settings
+        .get_all()
+        .await
+        .map(|s| s.attendee_intel_staleness_days)
+        .unwrap_or(30);
+    let mut cached = attendee_intel::CachedBriefings::default();
+    let mut person_status: std::collections::HashMap<String, &str> =
+        std::collections::HashMap::new();
+    let mut company_status: std::collections::HashMap<String, &str> =
+        std::collections::HashMap::new();
+    for email in &event.attendees {
+        let (name, domain, company_name) = names.resolve(email);
+        let status = match state.vault_manager.read_person_note(&name).await {
+            Some(note) if note.is_fresh(staleness_days) => {
+                let briefing = note.section("Briefing").unwrap_or_default();
+                cached.people.insert(email.to_lowercase(), briefing);
+                "cached"
+            }
+            Some(_) => "regenerated",
+            None => "created",
+        };
+        person_status.insert(email.to_lowercase(), status);
+
+        if !company_status.contains_key(&domain) {
+            let status = match state.vault_manager.read_company_note(&company_name).await {
+                Some(note) if note.is_fresh(staleness_days) => {
+                    let overview = note.section("Overview").unwrap_or_default();
+                    cached.companies.insert(domain.clone(), overview);
+                    "cached"
+                }
+                Some(_) => "regenerated",
+                None => "created",
+            };
+            company_status.insert(domain, status);
+        }
+    }
+
+    // Generate AI intelligence for all attendees; failures come back per item
     let ai_client = state.ai_client.read().clone();
-    let intel_package =
-        attendee_intel::generate_meeting_intel(&ai_client, &event.title, &event.attendees).await?;
+    let mut intel_package = attendee_intel::generate_meeting_intel(
+        &ai_client,
+        &event.title,
+        &event.attendees,
+        &cached,
+        &names,
+    )
+    .await;
 
     // Ensure vault structure
     state.vault_manager.ensure_structure().await?;
 
-    // Write person notes to vault
+    // Write person notes to vault (fresh notes only get the meeting appended).
+    // A failed write marks that attendee failed rather than aborting the rest.
     let meeting_link = event.title.clone();
-    for profile in &intel_package.attendees {
-        state
-            .vault_manager
-            .write_person_note(
-                &profile.name,
-                &profile.email,
-                &profile.company,
-                &profile.briefing,
-                &[meeting_link.clone()],
-            )
-            .await?;
+    for profile in intel_package.attendees.iter_mut() {
+        let written = match profile.status {
+            attendee_intel::BriefingStatus::Failed => continue,
+            attendee_intel::BriefingStatus::Cached => {
+                state
+                    .vault_manager
+                    .add_person_meeting(&profile.name, &meeting_link)
+                    .await
+            }
+            attendee_intel::BriefingStatus::Generated => state
+                .vault_manager
+                .write_person_note(
+                    &profile.name,
+                    &profile.email,
+                    &profile.company,
+                    &profile.briefing,
+                    &[meeting_link.clone()],
+                )
+                .await
+                .map(|_| ()),
+        };
+        if let Err(e) = written {
+            profile.status = attendee_intel::BriefingStatus::Failed;
+            profile.error = Some(format!("Failed to write vault note: {}", e));
+        }
     }
 
     // Write company notes to vault
-    for company in &intel_package.companies {
+    for company in intel_package.companies.iter_mut() {
         let people_in_company: Vec<String> = intel_package
             .attendees
             .iter()
-            .filter(|a| a.company_domain == company.domain)
+            .filter(|a| {
+                a.company_domain == company.domain
+                    && a.status != attendee_intel::BriefingStatus::Failed
+            })
             .map(|a| a.name.clone())
             .collect();
 
-        state
+        let written = match company.status {
+            attendee_intel::BriefingStatus::Failed => continue,
+            attendee_intel::BriefingStatus::Cached => {
+                state
+                    .vault_manager
+                    .add_company_people(&company.name, &people_in_company)
+                    .await
+            }
+            attendee_intel::BriefingStatus::Generated => state
+                .vault_manager
+                .write_company_note(
+                    &company.name,
+                    &company.domain,
+                    &company.briefing,
+                    &people_in_company,
+                )
+                .await
+                .map(|_| ()),
+        };
+        if let Err(e) = written {
+            company.status = attendee_intel::BriefingStatus::Failed;
+            company.error = Some(format!("Failed to write vault note: {}", e));
+        }
+    }
+
+    // Write meeting prep to vault
+    let mut meeting_prep_error = intel_package.meeting_prep_error.clone();
+    let mut meeting_prep_written = false;
+    if let Some(meeting_prep) = &intel_package.meeting_prep {
+        let attendee_names: Vec<String> = intel_package
+            .attendees
+            .iter()
+            .map(|a| a.name.clone())
+            .collect();
+        let event_date = event.start_time.to_rfc3339();
+        match state
             .vault_manager
-            .write_company_note(
-                &company.name,
-                &company.domain,
-                &company.briefing,
-                &people_in_company,
+            .write_meeting_prep(
+                topic_name,
+                &event.title,
+                &event_date,
+                &attendee_names,
+                meeting_prep,
             )
-            .await?;
+            .await
+        {
+            Ok(_) => meeting_prep_written = true,
+            Err(e) => meeting_prep_error = Some(format!("Failed to write meeting prep: {}", e)),
+        }
     }
 
-    // Write meeting prep to vault
-    let attendee_names: Vec<String> = intel_package
+    let failed_count = intel_package
         .attendees
         .iter()
-        .map(|a| a.name.clone())
-        .collect();
-    let event_date = event.start_time.to_rfc3339();
-    state
-        .vault_manager
-        .write_meeting_prep(
-            &topic_name,
-            &event.title,
-            &event_date,
-            &attendee_names,
-            &intel_package.meeting_prep,
-        )
-        .await?;
+        .filter(|a| a.status == attendee_intel::BriefingStatus::Failed)
+        .count()
+        + intel_package
+            .companies
+            .iter()
+            .filter(|c| c.status == attendee_intel::BriefingStatus::Failed)
+            .count();
 
     // Return summary
     let summary = serde_json::json!({
         "event_title": event.title,
         "attendees_count": intel_package.attendees.len(),
         "companies_count": intel_package.companies.len(),
-        "attendees": intel_package.attendees.iter().map(|a| serde_json::json!({
-            "name": a.name,
-            "email": a.email,
-            "company": a.company,
-        })).collect::<Vec<_>>(),
-        "companies": intel_package.companies.iter().map(|c| serde_json::json!({
-            "name": c.name,
-            "domain": c.domain,
-        })).collect::<Vec<_>>(),
+        "failed_count": failed_count,
+        "meeting_prep_written": meeting_prep_written,
+        "meeting_prep_error": meeting_prep_error,
+        "attendees": intel_package.attendees.iter().map(|a| {
+            let status = person_status.get(&a.email.to_lowercase()).copied().unwrap_or("created");
+            serde_json::json!({
+                "name": a.name,
+                "email": a.email,
+                "company": a.company,
+                "status": a.status,
+                "error": a.error,
+                "cached": status == "cached",
+                "regenerated": status == "regenerated",
+                "created": status == "created",
+            })
+        }).collect::<Vec<_>>(),
+        "companies": intel_package.companies.iter().map(|c| {
+            let status = company_status.get(&c.domain).copied().unwrap_or("created");
+            serde_json::json!({
+                "name": c.name,
+                "domain": c.domain,
+                "status": c.status,
+                "error": c.error,
+                "cached": status == "cached",
+                "regenerated": status == "regenerated",
+                "created": status == "created",
+            })
+        }).collect::<Vec<_>>(),
     });
 
     Ok(summary)
 }
 
+/// Set how old (days) a vault person/company note can be before attendee intel regenerates it
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_attendee_intel_staleness_days(
+    days: u32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings
+        .set_attendee_intel_staleness_days(days)
+        .await
+        .map_err(|e| format!("Failed to save setting: {}", e))
+}
+
 /// Get calendar events enriched with parsed attendee names and company info
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_enriched_calendar_events(
     state: State<'_, AppState>,
 ) -> Result<serde_json::Value, String> {
-    use crate::attendee_intel;
-
     let events = {
         let client = state.calendar_client.read();
         client
@@ -5834,6 +10907,15 @@ pub async fn get_enriched_calendar_events(
             .map_err(|e| format!("Calendar error: {}", e))?
     };
 
+    Ok(enrich_calendar_events(&events))
+}
+
+/// Calendar events as JSON with parsed attendee names and companies
+fn enrich_calendar_events(
+    events: &[crate::calendar_client::CalendarEventNative],
+) -> serde_json::Value {
+    use crate::attendee_intel;
+
     let enriched: Vec<serde_json::Value> = events
         .iter()
         .filter(|e| !e.is_all_day) // Skip all-day events
@@ -5867,5 +10949,43 @@ pub async fn get_enriched_calendar_events(
         })
         .collect();
 
-    Ok(serde_json::json!(enriched))
+    serde_json::json!(enriched)
+}
+
+/// Import an .ics file as calendar events (same shape as get_enriched_calendar_events).
+/// With `prep_days`, meeting-prep notes are written for upcoming events with attendees.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn import_ics(
+    path: String,
+    prep_days: Option<u32>,
+    topic_name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, String> {
+    let lookahead_days = prep_days
+        .map(|d| d as i64)
+        .unwrap_or(crate::ics_import::RECURRENCE_HORIZON_DAYS);
+    let events = crate::ics_import::import_file(std::path::Path::new(&path), lookahead_days)?;
+    log::info!("Imported {} calendar events from {}", events.len(), path);
+
+    // Remember them so generate_meeting_intel can find these event ids later
+    state
+        .calendar_client
+        .read()
+        .set_imported_events(events.clone());
+
+    if let Some(days) = prep_days {
+        let topic_name = topic_name.unwrap_or_else(|| "Inbox".to_string());
+        let now = chrono::Utc::now();
+        let horizon = now + chrono::Duration::days(days as i64);
+        for event in events
+            .iter()
+            .filter(|e| e.start_time >= now && e.start_time <= horizon && !e.attendees.is_empty())
+        {
+            if let Err(e) = prepare_meeting_intel(&state, event, &topic_name).await {
+                log::warn!("Failed to prepare notes for '{}': {}", event.title, e);
+            }
+        }
+    }
+
+    Ok(enrich_calendar_events(&events))
 }
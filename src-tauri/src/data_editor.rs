@@ -2,9 +2,15 @@
 // Provides versioned CRUD operations for learned data (text_snapshots, entities, activity_log)
 
 use serde::{Deserialize, Serialize};
-use sqlx::{Row, SqlitePool};
+use sqlx::{QueryBuilder, Row, Sqlite, SqlitePool};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
+/// activity_log columns a bulk edit may change
+const BULK_FIELDS: &[&str] = &["category", "summary", "focus_area", "app_name"];
+/// Before/after rows returned with a bulk edit
+const BULK_SAMPLE_SIZE: usize = 10;
+
 // =============================================================================
 // Types
 // =============================================================================
@@ -39,6 +45,121 @@ pub struct EditResult {
     pub message: String,
 }
 
+/// Which activity_log rows a bulk edit applies to. Every condition that is set
+/// must match; at least one is required.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BulkFilter {
+    pub category: Option<String>,
+    pub app_name: Option<String>,
+    /// Activities starting at or after this (RFC 3339 or YYYY-MM-DD)
+    pub since: Option<String>,
+    /// Activities starting before this (exclusive)
+    pub until: Option<String>,
+    pub min_confidence: Option<f64>,
+    pub max_confidence: Option<f64>,
+}
+
+impl BulkFilter {
+    pub fn validate(&self) -> Result<(), String> {
+        let is_set = self.category.is_some()
+            || self.app_name.is_some()
+            || self.since.is_some()
+            || self.until.is_some()
+            || self.min_confidence.is_some()
+            || self.max_confidence.is_some();
+        if !is_set {
+            return Err("Bulk edits need at least one filter condition".to_string());
+        }
+        if let (Some(min), Some(max)) = (self.min_confidence, self.max_confidence) {
+            if min > max {
+                return Err("min_confidence is greater than max_confidence".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One row of a bulk edit preview
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkRowPreview {
+    pub entity_id: String,
+    pub before: BTreeMap<String, Option<String>>,
+    pub after: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkEditResult {
+    /// False for a dry run
+    pub applied: bool,
+    /// Rows matching the filter
+    pub matched: usize,
+    /// Matching rows where at least one field actually changes
+    pub changed: usize,
+    /// Versions written (one per changed field per row)
+    pub versions_written: usize,
+    pub sample: Vec<BulkRowPreview>,
+}
+
+/// Check a bulk change set: known fields only, and at least one
+pub fn validate_bulk_changes(changes: &BTreeMap<String, String>) -> Result<(), String> {
+    if changes.is_empty() {
+        return Err("No changes given".to_string());
+    }
+    for field in changes.keys() {
+        if !BULK_FIELDS.contains(&field.as_str()) {
+            return Err(format!(
+                "Field '{}' can't be bulk edited (allowed: {})",
+                field,
+                BULK_FIELDS.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Fields that differ between a row and the change set
+fn changed_fields<'a>(
+    before: &BTreeMap<String, Option<String>>,
+    changes: &'a BTreeMap<String, String>,
+) -> Vec<(&'a str, &'a str)> {
+    changes
+        .iter()
+        .filter(|(field, value)| before.get(*field).cloned().flatten().as_deref() != Some(value))
+        .map(|(field, value)| (field.as_str(), value.as_str()))
+        .collect()
+}
+
+/// Fields `edit_learned_data` may touch per entity type
+fn editable_fields(entity_type: &str) -> &'static [&'static str] {
+    match entity_type {
+        "text_snapshot" => &["content"],
+        "entity" => &["entity_value", "entity_type", "metadata"],
+        "activity_log" => &[
+            "action",
+            "metadata",
+            "category",
+            "summary",
+            "focus_area",
+            "app_name",
+        ],
+        "episode" => &["document_path"],
+        _ => &[],
+    }
+}
+
+fn version_diff(previous_value: Option<&str>, new_value: &str) -> String {
+    // Create a simple diff string
+    if let Some(prev) = previous_value {
+        format!(
+            "-{}\n+{}",
+            prev.chars().take(100).collect::<String>(),
+            new_value.chars().take(100).collect::<String>()
+        )
+    } else {
+        format!("+{}", new_value.chars().take(100).collect::<String>())
+    }
+}
+
 // =============================================================================
 // Data Editor
 // =============================================================================
@@ -203,6 +324,109 @@ impl DataEditor {
         }
     }
 
+    /// Change `changes` on every activity_log row matching `filter`. Without
+    /// `apply` nothing is written and the result previews the affected rows.
+    /// Applied edits write a version per changed field so each row can be
+    /// restored individually.
+    pub async fn bulk_edit_learned_data(
+        &self,
+        filter: &BulkFilter,
+        changes: &BTreeMap<String, String>,
+        apply: bool,
+    ) -> Result<BulkEditResult, sqlx::Error> {
+        let mut query: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT id");
+        for field in changes.keys().filter(|f| BULK_FIELDS.contains(&f.as_str())) {
+            query.push(", ").push(field);
+        }
+        query.push(" FROM activity_log WHERE 1 = 1");
+        if let Some(category) = &filter.category {
+            query.push(" AND category = ").push_bind(category.clone());
+        }
+        if let Some(app_name) = &filter.app_name {
+            query.push(" AND app_name = ").push_bind(app_name.clone());
+        }
+        if let Some(since) = &filter.since {
+            query.push(" AND start_time >= ").push_bind(since.clone());
+        }
+        if let Some(until) = &filter.until {
+            query.push(" AND start_time < ").push_bind(until.clone());
+        }
+        if let Some(min) = filter.min_confidence {
+            query.push(" AND confidence >= ").push_bind(min);
+        }
+        if let Some(max) = filter.max_confidence {
+            query.push(" AND confidence <= ").push_bind(max);
+        }
+        query.push(" ORDER BY start_time DESC");
+
+        let rows = query.build().fetch_all(self.pool.as_ref()).await?;
+        let matched = rows.len();
+
+        let mut previews = Vec::new();
+        for row in rows {
+            let id: i64 = row.get("id");
+            let before: BTreeMap<String, Option<String>> = changes
+                .keys()
+                .map(|field| (field.clone(), row.get::<Option<String>, _>(field.as_str())))
+                .collect();
+            if changed_fields(&before, changes).is_empty() {
+                continue;
+            }
+            previews.push(BulkRowPreview {
+                entity_id: id.to_string(),
+                before,
+                after: changes.clone(),
+            });
+        }
+
+        let mut versions_written = 0;
+        if apply && !previews.is_empty() {
+            let mut tx = self.pool.begin().await?;
+            for preview in &previews {
+                for (field, value) in changed_fields(&preview.before, changes) {
+                    let previous = preview.before.get(field).cloned().flatten();
+                    sqlx::query(
+                        "INSERT INTO data_versions (entity_type, entity_id, field_name, previous_value, new_value, diff)
+                         VALUES ('activity_log', ?, ?, ?, ?, ?)",
+                    )
+                    .bind(&preview.entity_id)
+                    .bind(field)
+                    .bind(&previous)
+                    .bind(value)
+                    .bind(version_diff(previous.as_deref(), value))
+                    .execute(&mut *tx)
+                    .await?;
+
+                    sqlx::query(&format!(
+                        "UPDATE activity_log SET {} = ? WHERE id = ?",
+                        field
+                    ))
+                    .bind(value)
+                    .bind(&preview.entity_id)
+                    .execute(&mut *tx)
+                    .await?;
+                    versions_written += 1;
+                }
+            }
+            tx.commit().await?;
+
+            for preview in &previews {
+                self.prune_versions("activity_log", &preview.entity_id)
+                    .await?;
+            }
+        }
+
+        let changed = previews.len();
+        previews.truncate(BULK_SAMPLE_SIZE);
+        Ok(BulkEditResult {
+            applied: apply,
+            matched,
+            changed,
+            versions_written,
+            sample: previews,
+        })
+    }
+
     // =========================================================================
     // Private helpers
     // =========================================================================
@@ -426,15 +650,7 @@ impl DataEditor {
         };
 
         // Validate field name to prevent SQL injection
-        let valid_fields = match entity_type {
-            "text_snapshot" => vec!["content"],
-            "entity" => vec!["entity_value", "entity_type", "metadata"],
-            "activity_log" => vec!["action", "metadata"],
-            "episode" => vec!["document_path"],
-            _ => vec![],
-        };
-
-        if !valid_fields.contains(&field_name) {
+        if !editable_fields(entity_type).contains(&field_name) {
             return Ok(None);
         }
 
@@ -447,7 +663,7 @@ impl DataEditor {
             .fetch_optional(self.pool.as_ref())
             .await?;
 
-        Ok(row.map(|r| r.get::<String, _>(field_name)))
+        Ok(row.and_then(|r| r.get::<Option<String>, _>(field_name)))
     }
 
     async fn update_field_value(
@@ -466,15 +682,7 @@ impl DataEditor {
         };
 
         // Validate field name
-        let valid_fields = match entity_type {
-            "text_snapshot" => vec!["content"],
-            "entity" => vec!["entity_value", "entity_type", "metadata"],
-            "activity_log" => vec!["action", "metadata"],
-            "episode" => vec!["document_path"],
-            _ => vec![],
-        };
-
-        if !valid_fields.contains(&field_name) {
+        if !editable_fields(entity_type).contains(&field_name) {
             return Ok(());
         }
 
@@ -499,16 +707,7 @@ impl DataEditor {
         previous_value: Option<&str>,
         new_value: &str,
     ) -> Result<i64, sqlx::Error> {
-        // Create a simple diff string
-        let diff = if let Some(prev) = previous_value {
-            format!(
-                "-{}\n+{}",
-                prev.chars().take(100).collect::<String>(),
-                new_value.chars().take(100).collect::<String>()
-            )
-        } else {
-            format!("+{}", new_value.chars().take(100).collect::<String>())
-        };
+        let diff = version_diff(previous_value, new_value);
 
         let result = sqlx::query(
             "INSERT INTO data_versions (entity_type, entity_id, field_name, previous_value, new_value, diff)
@@ -549,3 +748,124 @@ impl DataEditor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> Arc<SqlitePool> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        sqlx::query(
+            r#"
+            CREATE TABLE activity_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                start_time TEXT NOT NULL,
+                app_name TEXT,
+                category TEXT NOT NULL DEFAULT 'other',
+                summary TEXT NOT NULL,
+                focus_area TEXT,
+                confidence REAL DEFAULT 0.0
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"
+            CREATE TABLE data_versions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                entity_type TEXT NOT NULL,
+                entity_id TEXT NOT NULL,
+                field_name TEXT NOT NULL,
+                previous_value TEXT,
+                new_value TEXT,
+                diff TEXT,
+                timestamp TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        for (start, app, category, confidence) in [
+            ("2024-01-01T09:00:00+00:00", "Figma", "communication", 0.3),
+            ("2024-01-02T09:00:00+00:00", "Figma", "communication", 0.4),
+            ("2024-01-02T10:00:00+00:00", "Figma", "communication", 0.9),
+            ("2024-01-02T11:00:00+00:00", "Slack", "communication", 0.2),
+        ] {
+            sqlx::query(
+                "INSERT INTO activity_log (start_time, app_name, category, summary, confidence)
+                 VALUES (?, ?, ?, 'work', ?)",
+            )
+            .bind(start)
+            .bind(app)
+            .bind(category)
+            .bind(confidence)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        Arc::new(pool)
+    }
+
+    #[tokio::test]
+    async fn test_bulk_edit_dry_run_then_apply() {
+        let pool = setup_test_db().await;
+        let editor = DataEditor::new(pool.clone(), 10);
+        let filter = BulkFilter {
+            app_name: Some("Figma".to_string()),
+            max_confidence: Some(0.5),
+            ..Default::default()
+        };
+        let changes = BTreeMap::from([("category".to_string(), "design".to_string())]);
+
+        let preview = editor
+            .bulk_edit_learned_data(&filter, &changes, false)
+            .await
+            .unwrap();
+        assert!(!preview.applied);
+        assert_eq!((preview.matched, preview.changed), (2, 2));
+        assert_eq!(
+            preview.sample[0].before["category"].as_deref(),
+            Some("communication")
+        );
+        let versions: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM data_versions")
+            .fetch_one(pool.as_ref())
+            .await
+            .unwrap();
+        assert_eq!(versions, 0);
+
+        let applied = editor
+            .bulk_edit_learned_data(&filter, &changes, true)
+            .await
+            .unwrap();
+        assert_eq!(applied.versions_written, 2);
+
+        // Each row restores on its own
+        let row_id = applied.sample[0].entity_id.clone();
+        let version = &editor.get_versions("activity_log", &row_id).await.unwrap()[0];
+        editor.restore_version(version.id).await.unwrap();
+        let category: String = sqlx::query_scalar("SELECT category FROM activity_log WHERE id = ?")
+            .bind(&row_id)
+            .fetch_one(pool.as_ref())
+            .await
+            .unwrap();
+        assert_eq!(category, "communication");
+    }
+
+    #[test]
+    fn test_bulk_validation() {
+        assert!(BulkFilter::default().validate().is_err());
+        let unknown = BTreeMap::from([("confidence".to_string(), "1".to_string())]);
+        assert!(validate_bulk_changes(&unknown).is_err());
+    }
+}
@@ -1,10 +1,13 @@
 // noFriction Meetings - Database Manager
 // SQLite storage for meetings, transcripts, and full-text search
 
+use crate::db_writer::{DbWriter, WriteQueueStats};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{sqlite::SqlitePoolOptions, Pool, Row, Sqlite};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
+use sqlx::{Pool, Row, Sqlite};
 use std::path::Path;
+use std::sync::Arc;
 
 /// Meeting record
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,16 +19,84 @@ pub struct Meeting {
     pub duration_seconds: Option<i64>,
 }
 
+/// Calendar event a meeting was recorded during
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingCalendarLink {
+    pub calendar_event_id: String,
+    /// Attendee email addresses
+    pub attendees: Vec<String>,
+    pub meeting_url: Option<String>,
+}
+
 /// Transcript record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transcript {
     pub id: i64,
     pub meeting_id: String,
     pub text: String,
+    /// Display name for the speaker (renamed name if set, otherwise the raw label)
     pub speaker: Option<String>,
+    /// Raw diarization label from the provider (e.g. "speaker_0")
+    pub speaker_label: Option<String>,
     pub timestamp: DateTime<Utc>,
     pub is_final: bool,
     pub confidence: f32,
+    /// Transcribed from audio buffered while the provider was reconnecting
+    pub after_reconnect: bool,
+}
+
+/// Original/corrected text for one transcript edit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptRevision {
+    pub id: i64,
+    pub transcript_id: i64,
+    pub meeting_id: String,
+    pub previous_text: String,
+    pub new_text: String,
+    pub edited_at: DateTime<Utc>,
+}
+
+/// Per-meeting speaker label → display name mapping
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingSpeaker {
+    pub meeting_id: String,
+    pub speaker_label: String,
+    pub display_name: Option<String>,
+    pub segment_count: i64,
+}
+
+/// Filters for the paginated meetings list (all optional)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MeetingFilter {
+    /// Inclusive lower bound, RFC3339 or YYYY-MM-DD
+    pub date_from: Option<String>,
+    /// Upper bound, RFC3339 or YYYY-MM-DD (a bare date includes that whole day)
+    pub date_to: Option<String>,
+    /// Matches the meeting title or anything said in the transcript
+    pub title_query: Option<String>,
+    pub min_duration_secs: Option<i64>,
+    pub has_notes: Option<bool>,
+    /// Archived meetings are excluded unless this is true
+    pub include_archived: Option<bool>,
+}
+
+/// Meeting row with counts for list badges
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingSummary {
+    #[serde(flatten)]
+    pub meeting: Meeting,
+    pub transcript_count: i64,
+    pub frame_count: i64,
+    pub has_notes: bool,
+}
+
+/// One page of filtered meetings plus the total match count
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingPage {
+    pub meetings: Vec<MeetingSummary>,
+    pub total: i64,
+    pub offset: i64,
+    pub limit: i64,
 }
 
 /// Search result
@@ -38,6 +109,85 @@ pub struct SearchResult {
     pub relevance: f64,
 }
 
+/// Locally stored embedding (little-endian f32 blob)
+#[derive(Debug, Clone)]
+pub struct LocalVectorRecord {
+    pub id: String,
+    pub text: String,
+    pub metadata_json: Option<String>,
+    pub embedding: Vec<u8>,
+}
+
+/// Queued cloud sync work item
+#[derive(Debug, Clone)]
+pub struct SyncJob {
+    pub id: i64,
+    pub kind: String,
+    /// Activity id, conversation id or meeting id, depending on `kind`
+    pub item_key: String,
+    pub payload_json: Option<String>,
+    pub attempts: i64,
+}
+
+/// RAG chat exchange kept locally (mirrors the Supabase `conversations` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredConversation {
+    pub id: String,
+    /// Chat thread the exchange belongs to (None for one-off questions)
+    #[serde(default)]
+    pub conversation_id: Option<String>,
+    pub timestamp: String,
+    pub user_query: String,
+    pub assistant_response: String,
+    pub model_used: String,
+    pub context_refs: Vec<String>,
+}
+
+/// Which kind of text a unified search hit came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSource {
+    Transcript,
+    Snapshot,
+    Ocr,
+}
+
+impl SearchSource {
+    pub const ALL: [SearchSource; 3] = [Self::Transcript, Self::Snapshot, Self::Ocr];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Transcript => "transcript",
+            Self::Snapshot => "snapshot",
+            Self::Ocr => "ocr",
+        }
+    }
+
+    /// Relevance multiplier: spoken words beat screen text, and accessibility
+    /// text beats noisier OCR
+    pub fn weight(&self) -> f64 {
+        match self {
+            Self::Transcript => 1.0,
+            Self::Snapshot => 0.8,
+            Self::Ocr => 0.6,
+        }
+    }
+}
+
+/// Unified search result across transcripts, snapshots and OCR text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnifiedSearchResult {
+    pub source: SearchSource,
+    /// Transcript id, snapshot id or frame id, depending on `source`
+    pub id: String,
+    pub meeting_id: Option<String>,
+    pub meeting_title: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    /// Matching excerpt with hits wrapped in `<mark>` tags
+    pub snippet: String,
+    pub score: f64,
+}
+
 /// AI-generated meeting notes
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MeetingNotes {
@@ -52,6 +202,83 @@ pub struct MeetingNotes {
     pub model_used: Option<String>,
 }
 
+/// Insight pinned during a meeting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedInsight {
+    pub id: String,
+    pub meeting_id: String,
+    pub insight_type: String,
+    pub text: String,
+    /// Epoch milliseconds of the pinned moment
+    pub timestamp_ms: i64,
+    pub transcript_id: Option<i64>,
+    /// "user" or "agent"
+    pub created_by: String,
+    pub created_at: String,
+}
+
+/// Explicitly marked decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    pub id: String,
+    pub meeting_id: String,
+    pub text: String,
+    pub context: Option<String>,
+    /// Epoch milliseconds of the decision
+    pub timestamp_ms: i64,
+    pub transcript_id: Option<i64>,
+    /// "user" or "agent"
+    pub created_by: String,
+    pub created_at: String,
+}
+
+/// Vault file written by an export, with the hash of what was written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultExportRecord {
+    pub path: String,
+    pub meeting_id: String,
+    pub content_hash: String,
+    pub exported_at: String,
+}
+
+/// Tracked action item (see action_items.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionItemRecord {
+    pub id: String,
+    pub meeting_id: String,
+    pub text: String,
+    pub assignee: Option<String>,
+    pub due_hint: Option<String>,
+    /// open | in_progress | done | cancelled
+    pub status: String,
+    /// live | extracted | manual
+    pub source: String,
+    pub source_transcript_id: Option<i64>,
+    /// EventKit id of the follow-up event created for it
+    pub calendar_event_id: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// One notes generation and how it was produced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingNotesVersion {
+    /// meeting_notes row id
+    pub id: String,
+    pub meeting_id: String,
+    /// 1-based per meeting; None for notes generated before versioning
+    pub version: Option<i64>,
+    pub style: Option<String>,
+    pub instructions: Option<String>,
+    pub prompt: Option<String>,
+    pub model_used: Option<String>,
+    pub range_start_secs: Option<f64>,
+    pub range_end_secs: Option<f64>,
+    pub created_at: String,
+    pub summary: Option<String>,
+    pub is_active: bool,
+}
+
 /// User comment on a meeting
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MeetingComment {
@@ -66,6 +293,15 @@ pub struct MeetingComment {
     pub parent_id: Option<String>,
 }
 
+/// Quick note taken while no meeting was recording, parked in the activity
+/// log until it is attached to a meeting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnattachedNote {
+    pub id: i64,
+    pub text: String,
+    pub ts: DateTime<Utc>,
+}
+
 /// Study materials record (Dork Mode)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StudyMaterialsRecord {
@@ -79,9 +315,43 @@ pub struct StudyMaterialsRecord {
     pub model_used: Option<String>,
 }
 
+/// Dork Mode session as stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DorkSessionRecord {
+    pub id: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub status: String,
+    pub chunk_count: i64,
+    pub content_chars: i64,
+    /// Study materials were generated for this session
+    pub has_materials: bool,
+}
+
+/// Flashcard with its review schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlashcardRecord {
+    pub id: String,
+    pub session_id: Option<String>,
+    pub front: String,
+    pub back: String,
+    pub ease: f64,
+    pub interval_days: i64,
+    pub repetitions: i64,
+    pub lapses: i64,
+    pub due_at: DateTime<Utc>,
+    pub last_reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Wait this long for a lock before returning SQLITE_BUSY
+const BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
 /// Database manager
 pub struct DatabaseManager {
     pool: Pool<Sqlite>,
+    /// Serialized write path for high-volume inserts
+    writer: Arc<DbWriter>,
 }
 
 impl DatabaseManager {
@@ -90,16 +360,28 @@ impl DatabaseManager {
         std::sync::Arc::new(self.pool.clone())
     }
 
-    /// Create a new database manager
+    /// Create a new database manager. WAL lets readers run alongside the
+    /// single writer; the busy timeout covers writes still going through the pool.
     pub async fn new(db_path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
-        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::new()
+            .filename(db_path)
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal)
+            .busy_timeout(BUSY_TIMEOUT);
 
         let pool = SqlitePoolOptions::new()
             .max_connections(5)
-            .connect(&db_url)
+            .connect_with(options.clone())
             .await?;
+        let writer = Arc::new(DbWriter::connect(&options).await?);
+
+        Ok(Self { pool, writer })
+    }
 
-        Ok(Self { pool })
+    /// Depth and wait times of the serialized write queue
+    pub fn write_queue_stats(&self) -> WriteQueueStats {
+        self.writer.stats()
     }
 
     /// Run database migrations
@@ -160,6 +442,37 @@ impl DatabaseManager {
         let _ = sqlx::query("ALTER TABLE frames ADD COLUMN file_path TEXT")
             .execute(&self.pool)
             .await;
+        // Pause tracking: paused_at is set while paused, paused_seconds accumulates
+        let _ = sqlx::query("ALTER TABLE meetings ADD COLUMN paused_at TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query("ALTER TABLE meetings ADD COLUMN paused_seconds INTEGER DEFAULT 0")
+            .execute(&self.pool)
+            .await;
+        // Soft delete: archived meetings are hidden but keep all relations
+        let _ = sqlx::query("ALTER TABLE meetings ADD COLUMN archived_at TEXT")
+            .execute(&self.pool)
+            .await;
+        // Calendar link: event the meeting was recorded during
+        let _ = sqlx::query("ALTER TABLE meetings ADD COLUMN calendar_event_id TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query("ALTER TABLE meetings ADD COLUMN attendees_json TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query("ALTER TABLE meetings ADD COLUMN meeting_url TEXT")
+            .execute(&self.pool)
+            .await;
+        // Recurring series the meeting belongs to (see meeting_series.rs)
+        let _ = sqlx::query("ALTER TABLE meetings ADD COLUMN series_id TEXT")
+            .execute(&self.pool)
+            .await;
+        // PII redacted from this meeting's text on its way to cloud sync
+        let _ = sqlx::query(
+            "ALTER TABLE meetings ADD COLUMN pii_redactions INTEGER NOT NULL DEFAULT 0",
+        )
+        .execute(&self.pool)
+        .await;
 
         // Create unique index on text_hash for deduplication
         let _ = sqlx::query(r#"
@@ -201,6 +514,19 @@ impl DatabaseManager {
         .execute(&self.pool)
         .await?;
 
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS transcripts_au AFTER UPDATE OF text, meeting_id ON transcripts BEGIN
+                INSERT INTO transcripts_fts(transcripts_fts, rowid, text, meeting_id) 
+                VALUES ('delete', old.id, old.text, old.meeting_id);
+                INSERT INTO transcripts_fts(rowid, text, meeting_id) 
+                VALUES (new.id, new.text, new.meeting_id);
+            END
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
         // Create indexes
         sqlx::query(
             r#"
@@ -576,6 +902,11 @@ impl DatabaseManager {
         .execute(&self.pool)
         .await;
 
+        // Segmentation quality for semantic topics (NULL = heuristic)
+        let _ = sqlx::query("ALTER TABLE topic_clusters ADD COLUMN quality_score REAL")
+            .execute(&self.pool)
+            .await;
+
         // ═══════════════════════════════════════════════════════════════════════
         // v2.1.0: Management Suite - Audit Log & Data Versioning
         // ═══════════════════════════════════════════════════════════════════════
@@ -597,6 +928,10 @@ impl DatabaseManager {
         .execute(&self.pool)
         .await?;
 
+        // Who took the action: "user" or "system" (scheduled tasks)
+        let _ = sqlx::query("ALTER TABLE audit_log ADD COLUMN actor TEXT NOT NULL DEFAULT 'user'")
+            .execute(&self.pool)
+            .await;
         let _ = sqlx::query("CREATE INDEX IF NOT EXISTS idx_audit_action ON audit_log(action)")
             .execute(&self.pool)
             .await;
@@ -659,6 +994,166 @@ impl DatabaseManager {
         .execute(&self.pool)
         .await;
 
+        // How each meeting_notes row was generated (one row per generation)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS meeting_notes_versions (
+                id TEXT PRIMARY KEY,   -- same id as the meeting_notes row
+                meeting_id TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                style TEXT,
+                instructions TEXT,
+                prompt TEXT NOT NULL,
+                model_used TEXT,
+                range_start_secs REAL,
+                range_end_secs REAL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        let _ = sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_notes_versions_meeting ON meeting_notes_versions(meeting_id)",
+        )
+        .execute(&self.pool)
+        .await;
+
+        // Tracked action items (live cues, AI extraction and manual entries)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS action_items (
+                id TEXT PRIMARY KEY,
+                meeting_id TEXT NOT NULL,
+                text TEXT NOT NULL,
+                normalized_text TEXT NOT NULL,   -- dedup key
+                assignee TEXT,
+                due_hint TEXT,
+                status TEXT NOT NULL DEFAULT 'open',
+                source TEXT NOT NULL,            -- live | extracted | manual
+                source_transcript_id INTEGER,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        let _ = sqlx::query(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_action_items_dedup ON action_items(meeting_id, normalized_text)",
+        )
+        .execute(&self.pool)
+        .await;
+        let _ = sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_action_items_status ON action_items(status)",
+        )
+        .execute(&self.pool)
+        .await;
+        let _ = sqlx::query("ALTER TABLE action_items ADD COLUMN calendar_event_id TEXT")
+            .execute(&self.pool)
+            .await;
+
+        // Insights the user (or agent) pinned during a meeting
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS pinned_insights (
+                id TEXT PRIMARY KEY,
+                meeting_id TEXT NOT NULL,
+                insight_type TEXT NOT NULL,
+                text TEXT NOT NULL,
+                timestamp_ms INTEGER NOT NULL,
+                transcript_id INTEGER,
+                created_by TEXT NOT NULL DEFAULT 'user',   -- user | agent
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Insights emitted by the live intel agent during recording
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS live_insights (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                meeting_id TEXT NOT NULL,
+                insight_type TEXT NOT NULL,
+                payload TEXT NOT NULL,   -- LiveInsightEvent JSON
+                timestamp_ms INTEGER NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        let _ = sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_live_insights_meeting ON live_insights(meeting_id, id)",
+        )
+        .execute(&self.pool)
+        .await;
+
+        // Explicitly marked decisions
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS decisions (
+                id TEXT PRIMARY KEY,
+                meeting_id TEXT NOT NULL,
+                text TEXT NOT NULL,
+                context TEXT,
+                timestamp_ms INTEGER NOT NULL,
+                transcript_id INTEGER,
+                created_by TEXT NOT NULL DEFAULT 'user',   -- user | agent
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        let _ = sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_pinned_insights_meeting ON pinned_insights(meeting_id)",
+        )
+        .execute(&self.pool)
+        .await;
+        let _ = sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_decisions_meeting ON decisions(meeting_id)",
+        )
+        .execute(&self.pool)
+        .await;
+
+        // Hash of every vault file the app wrote, to detect edits made in Obsidian
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS vault_exports (
+                path TEXT PRIMARY KEY,
+                meeting_id TEXT NOT NULL,
+                content_hash TEXT NOT NULL,  -- SHA-256 of what was written
+                exported_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        let _ = sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_vault_exports_meeting ON vault_exports(meeting_id)",
+        )
+        .execute(&self.pool)
+        .await;
+
+        // Notes version shown in the app and exported (NULL = latest)
+        let _ = sqlx::query("ALTER TABLE meetings ADD COLUMN active_notes_id TEXT")
+            .execute(&self.pool)
+            .await;
+
         // User comments and annotations on meetings
         sqlx::query(
             r#"
@@ -691,7 +1186,7 @@ impl DatabaseManager {
             r#"
             CREATE TABLE IF NOT EXISTS study_materials (
                 id TEXT PRIMARY KEY,
-                meeting_id TEXT NOT NULL,
+                meeting_id TEXT NOT NULL,  -- meeting or dork session the materials came from
                 summary TEXT,
                 key_concepts TEXT,     -- JSON array of {term, definition}
                 quiz_questions TEXT,   -- JSON array of quiz questions
@@ -711,968 +1206,1197 @@ impl DatabaseManager {
         .execute(&self.pool)
         .await;
 
-        // Transcript clusters for grouping segments into logical meetings
+        // Dork Mode sessions; content is appended as it arrives so a crash
+        // loses nothing and a session can be resumed
         sqlx::query(
             r#"
-            CREATE TABLE IF NOT EXISTS transcript_clusters (
+            CREATE TABLE IF NOT EXISTS dork_sessions (
                 id TEXT PRIMARY KEY,
-                meeting_id TEXT NOT NULL,
-                cluster_name TEXT,
-                start_time TEXT,
-                end_time TEXT,
-                transcript_ids TEXT,   -- JSON array of transcript IDs in this cluster
-                auto_generated INTEGER DEFAULT 1,
-                confidence REAL DEFAULT 0.0,
-                created_at TEXT NOT NULL DEFAULT (datetime('now')),
-                FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+                started_at TEXT NOT NULL,
+                ended_at TEXT,
+                status TEXT NOT NULL DEFAULT 'active',  -- 'active', 'ended'
+                chunk_count INTEGER NOT NULL DEFAULT 0,
+                content_chars INTEGER NOT NULL DEFAULT 0
             )
             "#,
         )
         .execute(&self.pool)
         .await?;
 
-        let _ = sqlx::query(
-            "CREATE INDEX IF NOT EXISTS idx_transcript_clusters_meeting ON transcript_clusters(meeting_id)",
-        )
-        .execute(&self.pool)
-        .await;
-
-        log::info!("Database migrations completed (v2.2 - Meeting Intelligence)");
-        Ok(())
-    }
-
-    /// Create a new meeting
-    pub async fn create_meeting(&self, id: &str, title: &str) -> Result<Meeting, sqlx::Error> {
-        let now = Utc::now();
-        let now_str = now.to_rfc3339();
-
-        sqlx::query("INSERT INTO meetings (id, title, started_at) VALUES (?, ?, ?)")
-            .bind(id)
-            .bind(title)
-            .bind(&now_str)
-            .execute(&self.pool)
-            .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS dork_session_content (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL,
+                content TEXT NOT NULL,
+                added_at TEXT NOT NULL,
+                FOREIGN KEY (session_id) REFERENCES dork_sessions(id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
 
-        Ok(Meeting {
-            id: id.to_string(),
-            title: title.to_string(),
-            started_at: now,
-            ended_at: None,
-            duration_seconds: None,
-        })
-    }
+        let _ = sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_dork_session_content_session ON dork_session_content(session_id)",
+        )
+        .execute(&self.pool)
+        .await;
 
-    /// End a meeting
-    pub async fn end_meeting(&self, id: &str) -> Result<(), sqlx::Error> {
-        let now = Utc::now();
-        let now_str = now.to_rfc3339();
+        // Flashcards with SM-2 review scheduling
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS flashcards (
+                id TEXT PRIMARY KEY,
+                session_id TEXT,
+                front TEXT NOT NULL,
+                back TEXT NOT NULL,
+                front_key TEXT NOT NULL UNIQUE,  -- normalized front, for dedup
+                ease REAL NOT NULL DEFAULT 2.5,
+                interval_days INTEGER NOT NULL DEFAULT 0,
+                repetitions INTEGER NOT NULL DEFAULT 0,
+                lapses INTEGER NOT NULL DEFAULT 0,
+                due_at TEXT NOT NULL,
+                last_reviewed_at TEXT,
+                created_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
 
-        // Get the start time to calculate duration
-        let row: (String,) = sqlx::query_as("SELECT started_at FROM meetings WHERE id = ?")
-            .bind(id)
-            .fetch_one(&self.pool)
-            .await?;
+        let _ = sqlx::query("CREATE INDEX IF NOT EXISTS idx_flashcards_due ON flashcards(due_at)")
+            .execute(&self.pool)
+            .await;
 
-        let started_at = DateTime::parse_from_rfc3339(&row.0)
-            .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or(now);
+        // Project clusters over activity_log summaries
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS activity_clusters (
+                id TEXT PRIMARY KEY,
+                label TEXT NOT NULL,
+                centroid BLOB NOT NULL,          -- little-endian f32 mean of member vectors
+                member_count INTEGER NOT NULL DEFAULT 0,
+                backend TEXT NOT NULL,           -- vector backend the centroid came from
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
 
-        let duration = (now - started_at).num_seconds();
+        // Activity summary vectors, kept so leftovers aren't re-embedded every run
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS activity_embeddings (
+                activity_id INTEGER PRIMARY KEY,
+                backend TEXT NOT NULL,
+                embedding BLOB NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
 
-        sqlx::query("UPDATE meetings SET ended_at = ?, duration_seconds = ? WHERE id = ?")
-            .bind(&now_str)
-            .bind(duration)
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+        // Transcript clusters for grouping segments into logical meetings
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS transcript_clusters (
+                id TEXT PRIMARY KEY,
+                meeting_id TEXT NOT NULL,
+                cluster_name TEXT,
+                start_time TEXT,
+                end_time TEXT,
+                transcript_ids TEXT,   -- JSON array of transcript IDs in this cluster
+                auto_generated INTEGER DEFAULT 1,
+                confidence REAL DEFAULT 0.0,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
 
-        Ok(())
-    }
+        let _ = sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_transcript_clusters_meeting ON transcript_clusters(meeting_id)",
+        )
+        .execute(&self.pool)
+        .await;
 
-    /// Get a meeting by ID
-    pub async fn get_meeting(&self, id: &str) -> Result<Option<Meeting>, sqlx::Error> {
-        let row = sqlx::query(
-            "SELECT id, title, started_at, ended_at, duration_seconds FROM meetings WHERE id = ?",
+        // Speaker display names, scoped per meeting because providers
+        // use different diarization schemes
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS meeting_speakers (
+                meeting_id TEXT NOT NULL,
+                speaker_label TEXT NOT NULL,
+                display_name TEXT NOT NULL,
+                updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (meeting_id, speaker_label),
+                FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+            )
+            "#,
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
+        .execute(&self.pool)
         .await?;
 
-        Ok(row.map(|r| Meeting {
-            id: r.get("id"),
-            title: r.get("title"),
-            started_at: DateTime::parse_from_rfc3339(&r.get::<String, _>("started_at"))
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now()),
-            ended_at: r
-                .get::<Option<String>, _>("ended_at")
-                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.with_timezone(&Utc)),
-            duration_seconds: r.get("duration_seconds"),
-        }))
-    }
-
-    /// List all meetings
-    pub async fn list_meetings(&self, limit: i32) -> Result<Vec<Meeting>, sqlx::Error> {
-        let rows = sqlx::query(
-            "SELECT id, title, started_at, ended_at, duration_seconds 
-             FROM meetings ORDER BY started_at DESC LIMIT ?",
+        // Transcript corrections: original text is kept for every edit
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS transcript_revisions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                transcript_id INTEGER NOT NULL,
+                meeting_id TEXT NOT NULL,
+                previous_text TEXT NOT NULL,
+                new_text TEXT NOT NULL,
+                edited_at TEXT NOT NULL,
+                FOREIGN KEY (transcript_id) REFERENCES transcripts(id) ON DELETE CASCADE
+            )
+            "#,
         )
-        .bind(limit)
-        .fetch_all(&self.pool)
+        .execute(&self.pool)
         .await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|r| Meeting {
-                id: r.get("id"),
-                title: r.get("title"),
-                started_at: DateTime::parse_from_rfc3339(&r.get::<String, _>("started_at"))
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-                ended_at: r
-                    .get::<Option<String>, _>("ended_at")
-                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                    .map(|dt| dt.with_timezone(&Utc)),
-                duration_seconds: r.get("duration_seconds"),
-            })
-            .collect())
-    }
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_transcript_revisions_transcript ON transcript_revisions(transcript_id)",
+        )
+        .execute(&self.pool)
+        .await?;
 
-    /// Delete a meeting and its transcripts
-    pub async fn delete_meeting(&self, id: &str) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM transcripts WHERE meeting_id = ?")
-            .bind(id)
+        // Edited transcripts are flagged until they've been re-upserted to Pinecone
+        let _ = sqlx::query("ALTER TABLE transcripts ADD COLUMN needs_reindex INTEGER DEFAULT 0")
             .execute(&self.pool)
-            .await?;
+            .await;
 
-        sqlx::query("DELETE FROM meetings WHERE id = ?")
-            .bind(id)
+        // Low-confidence final segments queued for manual review
+        let _ = sqlx::query("ALTER TABLE transcripts ADD COLUMN needs_review INTEGER DEFAULT 0")
             .execute(&self.pool)
-            .await?;
-
-        Ok(())
-    }
-
-    /// Add a transcript with smart deduplication (skips duplicates within 30 seconds)
-    pub async fn add_transcript(
-        &self,
-        meeting_id: &str,
-        text: &str,
-        speaker: Option<&str>,
-        is_final: bool,
-        confidence: f32,
-    ) -> Result<i64, sqlx::Error> {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let now = Utc::now();
-        let now_str = now.to_rfc3339();
-
-        // Only deduplicate final transcripts
-        if is_final && !text.trim().is_empty() {
-            // Create hash of text content (normalized)
-            let normalized_text = text.trim().to_lowercase();
-            let mut hasher = DefaultHasher::new();
-            normalized_text.hash(&mut hasher);
-            let text_hash = format!("{:016x}", hasher.finish());
+            .await;
 
-            // Check if this exact transcript already exists within last 30 seconds
-            let thirty_secs_ago = (now - chrono::Duration::seconds(30)).to_rfc3339();
+        // Segments transcribed from audio buffered during a provider reconnect
+        let _ = sqlx::query("ALTER TABLE transcripts ADD COLUMN after_reconnect INTEGER DEFAULT 0")
+            .execute(&self.pool)
+            .await;
 
-            let existing: Option<(i64,)> = sqlx::query_as(
-                "SELECT id FROM transcripts 
-                 WHERE meeting_id = ? AND text_hash = ? AND timestamp > ?
-                 LIMIT 1",
+        // VLM results keyed by screen hash so repeated screens skip the API call.
+        // The 64-bit hash is also split into four 16-bit bands: two hashes within
+        // Hamming distance 3 always share at least one band, so lookups can use
+        // the band indexes and then check the exact distance.
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS vlm_cache (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                phash INTEGER NOT NULL,
+                band0 INTEGER NOT NULL,
+                band1 INTEGER NOT NULL,
+                band2 INTEGER NOT NULL,
+                band3 INTEGER NOT NULL,
+                theme TEXT NOT NULL,
+                context_json TEXT NOT NULL,
+                hit_count INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                last_used_at TEXT NOT NULL
             )
-            .bind(meeting_id)
-            .bind(&text_hash)
-            .bind(&thirty_secs_ago)
-            .fetch_optional(&self.pool)
-            .await?;
-
-            if let Some((existing_id,)) = existing {
-                log::debug!("Skipping duplicate transcript: {}", text);
-                return Ok(existing_id);
-            }
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
 
-            // Insert with hash
-            let result = sqlx::query(
-                "INSERT INTO transcripts (meeting_id, text, speaker, timestamp, is_final, confidence, text_hash) 
-                 VALUES (?, ?, ?, ?, ?, ?, ?)"
-            )
-            .bind(meeting_id)
-            .bind(text)
-            .bind(speaker)
-            .bind(&now_str)
-            .bind(is_final as i32)
-            .bind(confidence)
-            .bind(&text_hash)
+        for band in 0..4 {
+            let _ = sqlx::query(&format!(
+                "CREATE INDEX IF NOT EXISTS idx_vlm_cache_band{0} ON vlm_cache(band{0})",
+                band
+            ))
             .execute(&self.pool)
-            .await?;
-
-            return Ok(result.last_insert_rowid());
+            .await;
         }
 
-        // Non-final (interim) transcripts - no deduplication needed
-        let result = sqlx::query(
-            "INSERT INTO transcripts (meeting_id, text, speaker, timestamp, is_final, confidence) 
-             VALUES (?, ?, ?, ?, ?, ?)",
+        // Activities created from a cached VLM result rather than a fresh call
+        let _ = sqlx::query("ALTER TABLE activity_log ADD COLUMN from_cache INTEGER DEFAULT 0")
+            .execute(&self.pool)
+            .await;
+
+        // Project cluster an activity was assigned to (never reassigned)
+        let _ = sqlx::query("ALTER TABLE activity_log ADD COLUMN cluster_id TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_activity_log_cluster ON activity_log(cluster_id, start_time)",
         )
-        .bind(meeting_id)
-        .bind(text)
-        .bind(speaker)
-        .bind(&now_str)
-        .bind(is_final as i32)
-        .bind(confidence)
         .execute(&self.pool)
-        .await?;
+        .await;
 
-        Ok(result.last_insert_rowid())
-    }
+        // Frames requeued by a backfill skip the VLM cache on their next run
+        let _ =
+            sqlx::query("ALTER TABLE frame_queue ADD COLUMN reanalyze INTEGER NOT NULL DEFAULT 0")
+                .execute(&self.pool)
+                .await;
 
-    /// Get transcripts for a meeting
-    pub async fn get_transcripts(&self, meeting_id: &str) -> Result<Vec<Transcript>, sqlx::Error> {
-        let rows = sqlx::query(
-            "SELECT id, meeting_id, text, speaker, timestamp, is_final, confidence 
-             FROM transcripts WHERE meeting_id = ? ORDER BY timestamp ASC",
-        )
-        .bind(meeting_id)
-        .fetch_all(&self.pool)
-        .await?;
+        // Keyframes that storage cleanup must keep (vault export, pinned insight)
+        let _ = sqlx::query("ALTER TABLE frames ADD COLUMN retain_reason TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query("ALTER TABLE screen_states ADD COLUMN retain_reason TEXT")
+            .execute(&self.pool)
+            .await;
 
-        Ok(rows
-            .into_iter()
-            .map(|r| Transcript {
-                id: r.get("id"),
-                meeting_id: r.get("meeting_id"),
-                text: r.get("text"),
-                speaker: r.get("speaker"),
-                timestamp: DateTime::parse_from_rfc3339(&r.get::<String, _>("timestamp"))
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-                is_final: r.get::<i32, _>("is_final") == 1,
-                confidence: r.get("confidence"),
-            })
-            .collect())
-    }
-
-    /// Search transcripts using FTS5
-    pub async fn search_transcripts(&self, query: &str) -> Result<Vec<SearchResult>, sqlx::Error> {
-        let rows = sqlx::query(
-            r#"
-            SELECT 
-                t.meeting_id,
-                m.title as meeting_title,
-                t.text as transcript_text,
-                t.timestamp,
-                bm25(transcripts_fts) as relevance
-            FROM transcripts_fts
-            JOIN transcripts t ON transcripts_fts.rowid = t.id
-            JOIN meetings m ON t.meeting_id = m.id
-            WHERE transcripts_fts MATCH ?
-            ORDER BY relevance
-            LIMIT 50
-            "#,
+        // Display each state was captured from (NULL = single-monitor capture)
+        let _ = sqlx::query("ALTER TABLE screen_states ADD COLUMN monitor_id INTEGER")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_screen_states_monitor ON screen_states(meeting_id, monitor_id)",
         )
-        .bind(query)
-        .fetch_all(&self.pool)
-        .await?;
+        .execute(&self.pool)
+        .await;
 
-        Ok(rows
-            .into_iter()
-            .map(|r| SearchResult {
-                meeting_id: r.get("meeting_id"),
-                meeting_title: r.get("meeting_title"),
-                transcript_text: r.get("transcript_text"),
-                timestamp: DateTime::parse_from_rfc3339(&r.get::<String, _>("timestamp"))
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-                relevance: r.get("relevance"),
-            })
-            .collect())
-    }
+        // Near-duplicate text snapshots stored as a line patch on their predecessor
+        let _ = sqlx::query("ALTER TABLE text_snapshots ADD COLUMN base_snapshot_id TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query("ALTER TABLE text_snapshots ADD COLUMN patch TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query("ALTER TABLE text_snapshots ADD COLUMN chain_length INTEGER DEFAULT 0")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_snapshots_base ON text_snapshots(base_snapshot_id)",
+        )
+        .execute(&self.pool)
+        .await;
 
-    /// Add a frame to the database (for rewind functionality)
-    pub async fn add_frame(
-        &self,
-        meeting_id: &str,
-        timestamp: DateTime<Utc>,
-        file_path: Option<&str>,
-        ocr_text: Option<&str>,
-    ) -> Result<i64, sqlx::Error> {
-        let timestamp_str = timestamp.to_rfc3339();
+        // Activity categories assigned by the semantic classifier at capture time
+        let _ = sqlx::query("ALTER TABLE screen_states ADD COLUMN category_confidence REAL")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query("ALTER TABLE document_episodes ADD COLUMN category TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query("ALTER TABLE meeting_timeline_events ADD COLUMN category TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_timeline_category ON meeting_timeline_events(meeting_id, category)",
+        )
+        .execute(&self.pool)
+        .await;
+        // Why an event scored the importance it did, set at finalization
+        let _ =
+            sqlx::query("ALTER TABLE meeting_timeline_events ADD COLUMN importance_reason TEXT")
+                .execute(&self.pool)
+                .await;
 
-        // Get next frame number for this meeting
-        let frame_count: i64 =
-            sqlx::query_scalar("SELECT COUNT(*) FROM frames WHERE meeting_id = ?")
-                .bind(meeting_id)
-                .fetch_one(&self.pool)
-                .await
-                .unwrap_or(0);
+        // AI summaries generated when episodes are finalized
+        let _ = sqlx::query("ALTER TABLE document_episodes ADD COLUMN summary TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query("ALTER TABLE document_episodes ADD COLUMN keywords_json TEXT")
+            .execute(&self.pool)
+            .await;
 
-        let result = sqlx::query(
-            "INSERT INTO frames (meeting_id, frame_number, timestamp, file_path, ocr_text) VALUES (?, ?, ?, ?, ?)"
+        // Full-text search over screen text. These are regular FTS5 tables keyed
+        // by the source rowid: delta snapshots keep no text in their row, so the
+        // index holds its own copy of the reconstructed text.
+        sqlx::query(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS text_snapshots_fts USING fts5(text, meeting_id UNINDEXED)",
         )
-        .bind(meeting_id)
-        .bind(frame_count)
-        .bind(&timestamp_str)
-        .bind(file_path)
-        .bind(ocr_text)
         .execute(&self.pool)
         .await?;
-
-        Ok(result.last_insert_rowid())
-    }
-
-    /// Get frames for a meeting (for rewind timeline)
-    pub async fn get_frames(
-        &self,
-        meeting_id: &str,
-        limit: i32,
-    ) -> Result<Vec<Frame>, sqlx::Error> {
-        let rows = sqlx::query(
-            "SELECT id, meeting_id, frame_number, timestamp, file_path, ocr_text 
-             FROM frames WHERE meeting_id = ? ORDER BY timestamp ASC LIMIT ?",
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS text_snapshots_fts_ai AFTER INSERT ON text_snapshots
+            WHEN new.patch IS NULL BEGIN
+                INSERT INTO text_snapshots_fts(rowid, text, meeting_id)
+                VALUES (new.rowid, new.text, new.meeting_id);
+            END
+        "#,
         )
-        .bind(meeting_id)
-        .bind(limit)
-        .fetch_all(&self.pool)
+        .execute(&self.pool)
         .await?;
-
-        Ok(rows
-            .into_iter()
-            .map(|r| Frame {
-                id: r.get("id"),
-                meeting_id: r.get("meeting_id"),
-                frame_number: r.try_get("frame_number").unwrap_or(0),
-                timestamp: DateTime::parse_from_rfc3339(&r.get::<String, _>("timestamp"))
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-                file_path: r.try_get("file_path").ok(),
-                ocr_text: r.try_get("ocr_text").ok(),
-            })
-            .collect())
-    }
-
-    /// Get frames in a time range (for rewind scrubbing)
-    pub async fn get_frames_in_range(
-        &self,
-        meeting_id: &str,
-        start: DateTime<Utc>,
-        end: DateTime<Utc>,
-    ) -> Result<Vec<Frame>, sqlx::Error> {
-        let start_str = start.to_rfc3339();
-        let end_str = end.to_rfc3339();
-
-        let rows = sqlx::query(
-            "SELECT id, meeting_id, frame_number, timestamp, file_path, ocr_text 
-             FROM frames WHERE meeting_id = ? AND timestamp >= ? AND timestamp <= ?
-             ORDER BY timestamp ASC",
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS text_snapshots_fts_ad AFTER DELETE ON text_snapshots BEGIN
+                DELETE FROM text_snapshots_fts WHERE rowid = old.rowid;
+            END
+        "#,
         )
-        .bind(meeting_id)
-        .bind(&start_str)
-        .bind(&end_str)
-        .fetch_all(&self.pool)
+        .execute(&self.pool)
         .await?;
-
-        Ok(rows
-            .into_iter()
-            .map(|r| Frame {
-                id: r.get("id"),
-                meeting_id: r.get("meeting_id"),
-                frame_number: r.try_get("frame_number").unwrap_or(0),
-                timestamp: DateTime::parse_from_rfc3339(&r.get::<String, _>("timestamp"))
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-                file_path: r.try_get("file_path").ok(),
-                ocr_text: r.try_get("ocr_text").ok(),
-            })
-            .collect())
-    }
-
-    /// Get the most recent frame for a meeting
-    pub async fn get_latest_frame(&self, meeting_id: &str) -> Result<Option<Frame>, sqlx::Error> {
-        let row = sqlx::query(
-            "SELECT id, meeting_id, frame_number, timestamp, file_path, ocr_text 
-             FROM frames WHERE meeting_id = ? ORDER BY timestamp DESC LIMIT 1",
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS text_snapshots_fts_au AFTER UPDATE OF text ON text_snapshots
+            WHEN new.patch IS NULL BEGIN
+                DELETE FROM text_snapshots_fts WHERE rowid = old.rowid;
+                INSERT INTO text_snapshots_fts(rowid, text, meeting_id)
+                VALUES (new.rowid, new.text, new.meeting_id);
+            END
+        "#,
         )
-        .bind(meeting_id)
-        .fetch_optional(&self.pool)
+        .execute(&self.pool)
         .await?;
 
-        Ok(row.map(|r| Frame {
-            id: r.get("id"),
-            meeting_id: r.get("meeting_id"),
-            frame_number: r.try_get("frame_number").unwrap_or(0),
-            timestamp: DateTime::parse_from_rfc3339(&r.get::<String, _>("timestamp"))
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now()),
-            file_path: r.try_get("file_path").ok(),
-            ocr_text: r.try_get("ocr_text").ok(),
-        }))
-    }
-
-    /// Count frames for a meeting
-    pub async fn count_frames(&self, meeting_id: &str) -> Result<i64, sqlx::Error> {
-        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM frames WHERE meeting_id = ?")
-            .bind(meeting_id)
-            .fetch_one(&self.pool)
-            .await?;
-
-        Ok(row.0)
-    }
-
-    // ═══════════════════════════════════════════════════════════════════════════
-    // Phase 1: Stateful Screen Ingest - ScreenState methods
-    // ═══════════════════════════════════════════════════════════════════════════
-
-    /// Add a new screen state
-    pub async fn add_screen_state(
-        &self,
-        state_id: &str,
-        meeting_id: &str,
-        start_ts: DateTime<Utc>,
-        end_ts: Option<DateTime<Utc>>,
-        phash: &str,
-        delta_score: f32,
-        keyframe_path: Option<&str>,
-        state_type: &str,
-        flags_json: &str,
-    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS frames_fts USING fts5(ocr_text, meeting_id UNINDEXED)",
+        )
+        .execute(&self.pool)
+        .await?;
         sqlx::query(
             r#"
-            INSERT INTO screen_states 
-            (state_id, meeting_id, start_ts, end_ts, phash, delta_score, keyframe_path, state_type, flags)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
+            CREATE TRIGGER IF NOT EXISTS frames_fts_ai AFTER INSERT ON frames
+            WHEN new.ocr_text IS NOT NULL AND new.ocr_text != '' BEGIN
+                INSERT INTO frames_fts(rowid, ocr_text, meeting_id)
+                VALUES (new.id, new.ocr_text, new.meeting_id);
+            END
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS frames_fts_ad AFTER DELETE ON frames BEGIN
+                DELETE FROM frames_fts WHERE rowid = old.id;
+            END
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS frames_fts_au AFTER UPDATE OF ocr_text ON frames BEGIN
+                DELETE FROM frames_fts WHERE rowid = old.id;
+                INSERT INTO frames_fts(rowid, ocr_text, meeting_id)
+                SELECT new.id, new.ocr_text, new.meeting_id
+                WHERE new.ocr_text IS NOT NULL AND new.ocr_text != '';
+            END
+        "#,
         )
-        .bind(state_id)
-        .bind(meeting_id)
-        .bind(start_ts.to_rfc3339())
-        .bind(end_ts.map(|ts| ts.to_rfc3339()))
-        .bind(phash)
-        .bind(delta_score)
-        .bind(keyframe_path)
-        .bind(state_type)
-        .bind(flags_json)
         .execute(&self.pool)
         .await?;
 
-        Ok(())
-    }
+        // Local vector store (semantic search without Pinecone)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS local_vectors (
+                id TEXT PRIMARY KEY,
+                text TEXT NOT NULL,
+                metadata_json TEXT,
+                embedding BLOB NOT NULL,
+                model TEXT NOT NULL,
+                updated_at TEXT DEFAULT (datetime('now'))
+            )
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
 
-    /// Update screen state end timestamp (extend duration)
-    pub async fn extend_screen_state(
-        &self,
-        state_id: &str,
-        end_ts: DateTime<Utc>,
-    ) -> Result<(), sqlx::Error> {
-        sqlx::query("UPDATE screen_states SET end_ts = ? WHERE state_id = ?")
-            .bind(end_ts.to_rfc3339())
-            .bind(state_id)
+        // Cloud sync queue; attempts survive restarts so poison items get parked
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS sync_jobs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                item_key TEXT NOT NULL,
+                payload_json TEXT,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT,
+                next_attempt_at TEXT,
+                parked_at TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                UNIQUE(kind, item_key)
+            )
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // RAG chat history; written before the (best-effort) cloud upload
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS conversations (
+                id TEXT PRIMARY KEY,
+                timestamp TEXT NOT NULL,
+                user_query TEXT NOT NULL,
+                assistant_response TEXT NOT NULL,
+                model_used TEXT,
+                context_refs TEXT,
+                created_at TEXT DEFAULT (datetime('now'))
+            )
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_conversations_timestamp ON conversations(timestamp)",
+        )
+        .execute(&self.pool)
+        .await?;
+        let _ = sqlx::query("ALTER TABLE conversations ADD COLUMN conversation_id TEXT")
             .execute(&self.pool)
-            .await?;
+            .await;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_conversations_thread ON conversations(conversation_id, timestamp)",
+        )
+        .execute(&self.pool)
+        .await?;
 
-        Ok(())
-    }
+        // Outbound AI/VLM/embedding/STT calls for cost accounting
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS usage_ledger (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                provider TEXT NOT NULL,
+                operation TEXT NOT NULL,
+                model TEXT NOT NULL,
+                input_tokens INTEGER NOT NULL DEFAULT 0,
+                output_tokens INTEGER NOT NULL DEFAULT 0,
+                audio_seconds REAL NOT NULL DEFAULT 0,
+                duration_ms INTEGER NOT NULL DEFAULT 0,
+                success INTEGER NOT NULL,
+                timestamp TEXT NOT NULL
+            )
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_usage_ledger_timestamp ON usage_ledger(timestamp)",
+        )
+        .execute(&self.pool)
+        .await?;
 
-    /// Update screen state with keyframe path after saving
-    pub async fn update_screen_state_keyframe(
-        &self,
-        state_id: &str,
-        keyframe_path: &str,
-    ) -> Result<(), sqlx::Error> {
-        sqlx::query("UPDATE screen_states SET keyframe_path = ? WHERE state_id = ?")
-            .bind(keyframe_path)
-            .bind(state_id)
+        // Canonical entities: raw extracted entities folded together with their aliases
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS canonical_entities (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                entity_type TEXT NOT NULL,
+                name TEXT NOT NULL,
+                domain TEXT,
+                aliases TEXT NOT NULL DEFAULT '[]',
+                locked INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        let _ = sqlx::query("ALTER TABLE entities ADD COLUMN canonical_id INTEGER")
+            .execute(&self.pool)
+            .await;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_entities_canonical ON entities(canonical_id)")
             .execute(&self.pool)
             .await?;
 
-        Ok(())
-    }
-
-    /// Get screen states for a meeting
-    pub async fn get_screen_states(
-        &self,
-        meeting_id: &str,
-        limit: i32,
-    ) -> Result<Vec<ScreenStateRecord>, sqlx::Error> {
-        let rows = sqlx::query(
+        // Recurring meeting series, detected from calendar recurrences or titles
+        sqlx::query(
             r#"
-            SELECT state_id, meeting_id, start_ts, end_ts, app_name, window_title,
-                   phash, delta_score, keyframe_path, state_type, flags, created_at
-            FROM screen_states 
-            WHERE meeting_id = ?
-            ORDER BY start_ts ASC
-            LIMIT ?
-            "#,
+            CREATE TABLE IF NOT EXISTS meeting_series (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                source TEXT NOT NULL,   -- calendar | title
+                created_at TEXT NOT NULL
+            )
+        "#,
         )
-        .bind(meeting_id)
-        .bind(limit)
-        .fetch_all(&self.pool)
+        .execute(&self.pool)
         .await?;
-
-        Ok(rows
-            .into_iter()
-            .map(|r| ScreenStateRecord {
-                state_id: r.get("state_id"),
-                meeting_id: r.get("meeting_id"),
-                start_ts: r.get("start_ts"),
-                end_ts: r.try_get("end_ts").ok(),
-                app_name: r.try_get("app_name").ok(),
-                window_title: r.try_get("window_title").ok(),
-                phash: r.get("phash"),
-                delta_score: r.try_get("delta_score").unwrap_or(0.0),
-                keyframe_path: r.try_get("keyframe_path").ok(),
-                state_type: r
-                    .try_get("state_type")
-                    .unwrap_or_else(|_| "other".to_string()),
-                flags: r.try_get("flags").unwrap_or_else(|_| "{}".to_string()),
-            })
-            .collect())
-    }
-
-    /// Count screen states for a meeting
-    pub async fn count_screen_states(&self, meeting_id: &str) -> Result<i64, sqlx::Error> {
-        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM screen_states WHERE meeting_id = ?")
-            .bind(meeting_id)
-            .fetch_one(&self.pool)
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_meetings_series ON meetings(series_id)")
+            .execute(&self.pool)
             .await?;
 
-        Ok(row.0)
-    }
-
-    /// Get the latest screen state for a meeting
-    pub async fn get_latest_screen_state(
-        &self,
-        meeting_id: &str,
-    ) -> Result<Option<ScreenStateRecord>, sqlx::Error> {
-        let row = sqlx::query(
+        // Cached AI rollups of entity dossiers, with the timeline they were written from
+        sqlx::query(
             r#"
-            SELECT state_id, meeting_id, start_ts, end_ts, app_name, window_title,
-                   phash, delta_score, keyframe_path, state_type, flags, created_at
-            FROM screen_states 
-            WHERE meeting_id = ?
-            ORDER BY start_ts DESC
-            LIMIT 1
-            "#,
+            CREATE TABLE IF NOT EXISTS entity_dossier_rollups (
+                canonical_id INTEGER PRIMARY KEY,
+                rollup TEXT NOT NULL,
+                model TEXT NOT NULL,
+                item_count INTEGER NOT NULL,
+                sources_as_of TEXT,
+                generated_at TEXT NOT NULL
+            )
+        "#,
         )
-        .bind(meeting_id)
-        .fetch_optional(&self.pool)
+        .execute(&self.pool)
         .await?;
 
-        Ok(row.map(|r| ScreenStateRecord {
-            state_id: r.get("state_id"),
-            meeting_id: r.get("meeting_id"),
-            start_ts: r.get("start_ts"),
-            end_ts: r.try_get("end_ts").ok(),
-            app_name: r.try_get("app_name").ok(),
-            window_title: r.try_get("window_title").ok(),
-            phash: r.get("phash"),
-            delta_score: r.try_get("delta_score").unwrap_or(0.0),
-            keyframe_path: r.try_get("keyframe_path").ok(),
-            state_type: r
-                .try_get("state_type")
-                .unwrap_or_else(|_| "other".to_string()),
-            flags: r.try_get("flags").unwrap_or_else(|_| "{}".to_string()),
-        }))
-    }
-
-    // ═══════════════════════════════════════════════════════════════════════════
-    // Phase 2: Document Episodes & Text Snapshots CRUD
-    // ═══════════════════════════════════════════════════════════════════════════
-
-    /// Create a new document episode
-    pub async fn create_episode(
-        &self,
-        episode_id: &str,
-        meeting_id: &str,
-        start_ts: DateTime<Utc>,
-        app_name: Option<&str>,
-        window_title: Option<&str>,
-    ) -> Result<(), sqlx::Error> {
+        // Raw meeting audio, written in chunks under audio/<meeting_id>/
         sqlx::query(
             r#"
-            INSERT INTO document_episodes 
-            (episode_id, meeting_id, start_ts, app_name, window_title)
-            VALUES (?, ?, ?, ?, ?)
-            "#,
+            CREATE TABLE IF NOT EXISTS audio_chunks (
+                id TEXT PRIMARY KEY,
+                meeting_id TEXT NOT NULL,
+                chunk_index INTEGER NOT NULL,
+                path TEXT NOT NULL,
+                format TEXT NOT NULL,   -- wav | aac
+                sample_rate INTEGER NOT NULL,
+                channels INTEGER NOT NULL,
+                start_ts TEXT NOT NULL,
+                end_ts TEXT,            -- NULL while the chunk is being written
+                size_bytes INTEGER DEFAULT 0,
+                retain_reason TEXT
+            )
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_audio_chunks_meeting ON audio_chunks(meeting_id, start_ts)",
         )
-        .bind(episode_id)
-        .bind(meeting_id)
-        .bind(start_ts.to_rfc3339())
-        .bind(app_name)
-        .bind(window_title)
         .execute(&self.pool)
         .await?;
 
-        Ok(())
-    }
-
-    /// Update episode end time and stats
-    pub async fn update_episode(
-        &self,
-        episode_id: &str,
-        end_ts: DateTime<Utc>,
-        state_count: i32,
-        total_duration_ms: i64,
-    ) -> Result<(), sqlx::Error> {
+        // Re-transcription of a meeting from its archived audio. next_chunk is
+        // the resume point; the job's segments form a revision set of the meeting.
         sqlx::query(
             r#"
-            UPDATE document_episodes 
-            SET end_ts = ?, state_count = ?, total_duration_ms = ?
-            WHERE episode_id = ?
-            "#,
+            CREATE TABLE IF NOT EXISTS retranscribe_jobs (
+                id TEXT PRIMARY KEY,
+                meeting_id TEXT NOT NULL,
+                provider TEXT NOT NULL,
+                replace_existing INTEGER NOT NULL DEFAULT 0,
+                status TEXT NOT NULL,   -- running | completed | failed
+                next_chunk INTEGER NOT NULL DEFAULT 0,
+                total_chunks INTEGER NOT NULL,
+                error TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+        "#,
         )
-        .bind(end_ts.to_rfc3339())
-        .bind(state_count)
-        .bind(total_duration_ms)
-        .bind(episode_id)
         .execute(&self.pool)
         .await?;
 
-        Ok(())
-    }
-
-    /// Link a state to an episode
-    pub async fn link_state_to_episode(
-        &self,
-        episode_id: &str,
-        state_id: &str,
-        sequence_num: i32,
-    ) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
-            INSERT OR REPLACE INTO episode_states (episode_id, state_id, sequence_num)
-            VALUES (?, ?, ?)
-            "#,
+            CREATE TABLE IF NOT EXISTS retranscribed_segments (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                job_id TEXT NOT NULL,
+                meeting_id TEXT NOT NULL,
+                chunk_position INTEGER NOT NULL,
+                text TEXT NOT NULL,
+                speaker TEXT,
+                timestamp TEXT NOT NULL,
+                confidence REAL NOT NULL DEFAULT 0.0
+            )
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_retranscribed_segments_job ON retranscribed_segments(job_id, timestamp)",
         )
-        .bind(episode_id)
-        .bind(state_id)
-        .bind(sequence_num)
         .execute(&self.pool)
         .await?;
 
+        // Originals overwritten by a re-transcription are tagged with its job id
+        let _ = sqlx::query("ALTER TABLE transcript_revisions ADD COLUMN revision_set TEXT")
+            .execute(&self.pool)
+            .await;
+
+        // Index rows written before the FTS tables existed without holding up startup
+        let backfill = Self {
+            pool: self.pool.clone(),
+            writer: self.writer.clone(),
+        };
+        tokio::spawn(async move {
+            match backfill.backfill_search_index().await {
+                Ok((0, 0)) => {}
+                Ok((snapshots, frames)) => log::info!(
+                    "🔎 Search index backfilled: {} snapshots, {} OCR frames",
+                    snapshots,
+                    frames
+                ),
+                Err(e) => log::warn!("Search index backfill failed: {}", e),
+            }
+        });
+
+        log::info!("Database migrations completed (v2.2 - Meeting Intelligence)");
         Ok(())
     }
 
-    /// Get episodes for a meeting
-    pub async fn get_episodes(
-        &self,
-        meeting_id: &str,
-    ) -> Result<Vec<DocumentEpisodeRecord>, sqlx::Error> {
-        let rows = sqlx::query(
-            r#"
-            SELECT episode_id, meeting_id, start_ts, end_ts, app_name, window_title,
-                   document_fingerprint, state_count, total_duration_ms
-            FROM document_episodes 
-            WHERE meeting_id = ?
-            ORDER BY start_ts ASC
-            "#,
-        )
-        .bind(meeting_id)
-        .fetch_all(&self.pool)
-        .await?;
+    /// Create a new meeting
+    pub async fn create_meeting(&self, id: &str, title: &str) -> Result<Meeting, sqlx::Error> {
+        let now = Utc::now();
+        let now_str = now.to_rfc3339();
 
-        Ok(rows
-            .into_iter()
-            .map(|r| DocumentEpisodeRecord {
-                episode_id: r.get("episode_id"),
-                meeting_id: r.get("meeting_id"),
-                start_ts: r.get("start_ts"),
-                end_ts: r.try_get("end_ts").ok(),
-                app_name: r.try_get("app_name").ok(),
-                window_title: r.try_get("window_title").ok(),
-                document_fingerprint: r.try_get("document_fingerprint").ok(),
-                state_count: r.try_get("state_count").unwrap_or(0),
-                total_duration_ms: r.try_get("total_duration_ms").unwrap_or(0),
-            })
-            .collect())
+        sqlx::query("INSERT INTO meetings (id, title, started_at) VALUES (?, ?, ?)")
+            .bind(id)
+            .bind(title)
+            .bind(&now_str)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(Meeting {
+            id: id.to_string(),
+            title: title.to_string(),
+            started_at: now,
+            ended_at: None,
+            duration_seconds: None,
+        })
     }
 
-    /// Add a text snapshot (legacy signature for backwards compatibility)
-    pub async fn add_text_snapshot(
+    /// End a meeting (duration excludes any time spent paused)
+    /// Rename a meeting (e.g. AI-generated title after recording stops)
+    pub async fn update_meeting_title(&self, id: &str, title: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE meetings SET title = ? WHERE id = ?")
+            .bind(title)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Set a meeting's start/end explicitly (meetings created after the fact)
+    pub async fn set_meeting_span(
         &self,
-        snapshot_id: &str,
-        episode_id: Option<&str>,
-        state_id: Option<&str>,
-        ts: DateTime<Utc>,
-        text: &str,
-        text_hash: &str,
-        quality_score: f32,
-        source: &str,
+        id: &str,
+        started_at: DateTime<Utc>,
+        ended_at: DateTime<Utc>,
     ) -> Result<(), sqlx::Error> {
-        self.add_text_snapshot_full(
-            snapshot_id,
-            episode_id,
-            state_id,
-            None,
-            ts,
-            text,
-            text_hash,
-            quality_score,
-            source,
-            None,
-            None,
+        sqlx::query(
+            "UPDATE meetings SET started_at = ?, ended_at = ?, duration_seconds = ? WHERE id = ?",
         )
-        .await
+        .bind(started_at.to_rfc3339())
+        .bind(ended_at.to_rfc3339())
+        .bind((ended_at - started_at).num_seconds().max(0))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
     }
 
-    /// Add a text snapshot with full metadata including meeting and app context
-    pub async fn add_text_snapshot_full(
+    /// Link a meeting to a calendar event (id, attendees, meeting URL)
+    pub async fn set_meeting_calendar_link(
         &self,
-        snapshot_id: &str,
-        episode_id: Option<&str>,
-        state_id: Option<&str>,
-        meeting_id: Option<&str>,
-        ts: DateTime<Utc>,
-        text: &str,
-        text_hash: &str,
-        quality_score: f32,
-        source: &str,
-        app_name: Option<&str>,
-        window_title: Option<&str>,
+        id: &str,
+        link: &MeetingCalendarLink,
     ) -> Result<(), sqlx::Error> {
-        let word_count = text.split_whitespace().count() as i32;
-
+        let attendees_json =
+            serde_json::to_string(&link.attendees).unwrap_or_else(|_| "[]".to_string());
         sqlx::query(
-            r#"
-            INSERT INTO text_snapshots 
-            (snapshot_id, episode_id, state_id, meeting_id, ts, text, text_hash, quality_score, source, word_count, app_name, window_title)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
+            "UPDATE meetings SET calendar_event_id = ?, attendees_json = ?, meeting_url = ? WHERE id = ?",
         )
-        .bind(snapshot_id)
-        .bind(episode_id)
-        .bind(state_id)
-        .bind(meeting_id)
-        .bind(ts.to_rfc3339())
-        .bind(text)
-        .bind(text_hash)
-        .bind(quality_score)
-        .bind(source)
-        .bind(word_count)
-        .bind(app_name)
-        .bind(window_title)
+        .bind(&link.calendar_event_id)
+        .bind(&attendees_json)
+        .bind(&link.meeting_url)
+        .bind(id)
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
 
-    /// Get latest snapshot for an episode
-    pub async fn get_latest_snapshot(
+    /// Calendar link for a meeting, if it was recorded during (or linked to) an event
+    pub async fn get_meeting_calendar_link(
         &self,
-        episode_id: &str,
-    ) -> Result<Option<TextSnapshotRecord>, sqlx::Error> {
+        id: &str,
+    ) -> Result<Option<MeetingCalendarLink>, sqlx::Error> {
         let row = sqlx::query(
-            r#"
-            SELECT snapshot_id, episode_id, state_id, meeting_id, ts, text, text_hash, 
-                   quality_score, source, word_count, app_name, window_title
-            FROM text_snapshots 
-            WHERE episode_id = ?
-            ORDER BY ts DESC
-            LIMIT 1
-            "#,
+            "SELECT calendar_event_id, attendees_json, meeting_url FROM meetings WHERE id = ?",
         )
-        .bind(episode_id)
+        .bind(id)
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.map(|r| TextSnapshotRecord {
-            snapshot_id: r.get("snapshot_id"),
-            episode_id: r.try_get("episode_id").ok(),
-            state_id: r.try_get("state_id").ok(),
-            meeting_id: r.try_get("meeting_id").ok(),
-            ts: r.get("ts"),
-            text: r.get("text"),
-            text_hash: r.get("text_hash"),
-            quality_score: r.try_get("quality_score").unwrap_or(0.0),
-            source: r.try_get("source").unwrap_or_else(|_| "ocr".to_string()),
-            word_count: r.try_get("word_count").unwrap_or(0),
-            app_name: r.try_get("app_name").ok(),
-            window_title: r.try_get("window_title").ok(),
+        Ok(row.and_then(|r| {
+            let calendar_event_id: Option<String> = r.get("calendar_event_id");
+            calendar_event_id.map(|calendar_event_id| MeetingCalendarLink {
+                calendar_event_id,
+                attendees: r
+                    .get::<Option<String>, _>("attendees_json")
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default(),
+                meeting_url: r.get("meeting_url"),
+            })
         }))
     }
 
-    /// Get all text snapshots for a meeting (ordered by timestamp)
-    pub async fn get_text_snapshots_by_meeting(
+    /// Add to a meeting's PII redaction counter
+    pub async fn add_meeting_pii_redactions(
         &self,
-        meeting_id: &str,
-    ) -> Result<Vec<TextSnapshotRecord>, sqlx::Error> {
+        id: &str,
+        count: u64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE meetings SET pii_redactions = pii_redactions + ? WHERE id = ?")
+            .bind(count as i64)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// PII redactions for one meeting
+    pub async fn get_meeting_pii_redactions(&self, id: &str) -> Result<u64, sqlx::Error> {
+        let count: Option<i64> =
+            sqlx::query_scalar("SELECT pii_redactions FROM meetings WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(count.unwrap_or(0).max(0) as u64)
+    }
+
+    /// PII redactions across all meetings
+    pub async fn get_total_pii_redactions(&self) -> Result<u64, sqlx::Error> {
+        let total: i64 =
+            sqlx::query_scalar("SELECT COALESCE(SUM(pii_redactions), 0) FROM meetings")
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(total.max(0) as u64)
+    }
+
+    pub async fn end_meeting(&self, id: &str) -> Result<(), sqlx::Error> {
+        // Close out a pause that was still open when recording stopped
+        self.resume_meeting(id).await?;
+
+        let now = Utc::now();
+        let now_str = now.to_rfc3339();
+
+        // Get the start time to calculate duration
+        let row: (String, Option<i64>) =
+            sqlx::query_as("SELECT started_at, paused_seconds FROM meetings WHERE id = ?")
+                .bind(id)
+                .fetch_one(&self.pool)
+                .await?;
+
+        let started_at = DateTime::parse_from_rfc3339(&row.0)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or(now);
+
+        let duration = ((now - started_at).num_seconds() - row.1.unwrap_or(0)).max(0);
+
+        sqlx::query("UPDATE meetings SET ended_at = ?, duration_seconds = ? WHERE id = ?")
+            .bind(&now_str)
+            .bind(duration)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Mark a meeting as paused (no-op if already paused)
+    pub async fn pause_meeting(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE meetings SET paused_at = ? WHERE id = ? AND paused_at IS NULL")
+            .bind(Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Resume a paused meeting, adding the pause length to paused_seconds.
+    /// Returns the length of the pause that was closed (0 if not paused).
+    pub async fn resume_meeting(&self, id: &str) -> Result<i64, sqlx::Error> {
+        let row: Option<(Option<String>,)> =
+            sqlx::query_as("SELECT paused_at FROM meetings WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        let paused_at = match row.and_then(|r| r.0) {
+            Some(ts) => ts,
+            None => return Ok(0),
+        };
+
+        let now = Utc::now();
+        let paused_secs = DateTime::parse_from_rfc3339(&paused_at)
+            .map(|dt| (now - dt.with_timezone(&Utc)).num_seconds().max(0))
+            .unwrap_or(0);
+
+        sqlx::query(
+            "UPDATE meetings SET paused_at = NULL, paused_seconds = COALESCE(paused_seconds, 0) + ? WHERE id = ?",
+        )
+        .bind(paused_secs)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(paused_secs)
+    }
+
+    /// Get a meeting by ID
+    pub async fn get_meeting(&self, id: &str) -> Result<Option<Meeting>, sqlx::Error> {
+        let row = sqlx::query(
+            "SELECT id, title, started_at, ended_at, duration_seconds FROM meetings WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| Meeting {
+            id: r.get("id"),
+            title: r.get("title"),
+            started_at: DateTime::parse_from_rfc3339(&r.get::<String, _>("started_at"))
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+            ended_at: r
+                .get::<Option<String>, _>("ended_at")
+                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+            duration_seconds: r.get("duration_seconds"),
+        }))
+    }
+
+    /// List all meetings
+    pub async fn list_meetings(&self, limit: i32) -> Result<Vec<Meeting>, sqlx::Error> {
         let rows = sqlx::query(
-            r#"
-            SELECT snapshot_id, episode_id, state_id, meeting_id, ts, text, text_hash, 
-                   quality_score, source, word_count, app_name, window_title
-            FROM text_snapshots 
-            WHERE meeting_id = ?
-            ORDER BY ts ASC
-            "#,
+            "SELECT id, title, started_at, ended_at, duration_seconds 
+             FROM meetings WHERE archived_at IS NULL ORDER BY started_at DESC LIMIT ?",
         )
-        .bind(meeting_id)
+        .bind(limit)
         .fetch_all(&self.pool)
         .await?;
 
         Ok(rows
             .into_iter()
-            .map(|r| TextSnapshotRecord {
-                snapshot_id: r.get("snapshot_id"),
-                episode_id: r.try_get("episode_id").ok(),
-                state_id: r.try_get("state_id").ok(),
-                meeting_id: r.try_get("meeting_id").ok(),
-                ts: r.get("ts"),
-                text: r.get("text"),
-                text_hash: r.get("text_hash"),
-                quality_score: r.try_get("quality_score").unwrap_or(0.0),
-                source: r.try_get("source").unwrap_or_else(|_| "ocr".to_string()),
-                word_count: r.try_get("word_count").unwrap_or(0),
-                app_name: r.try_get("app_name").ok(),
-                window_title: r.try_get("window_title").ok(),
+            .map(|r| Meeting {
+                id: r.get("id"),
+                title: r.get("title"),
+                started_at: DateTime::parse_from_rfc3339(&r.get::<String, _>("started_at"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                ended_at: r
+                    .get::<Option<String>, _>("ended_at")
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                duration_seconds: r.get("duration_seconds"),
             })
             .collect())
     }
 
-    /// Add a text patch (diff between snapshots)
-    pub async fn add_text_patch(
+    /// Paginated meetings list with date/duration/notes filters and
+    /// title-or-transcript text search. Counts come back in the same query.
+    pub async fn list_meetings_filtered(
         &self,
-        patch_id: &str,
-        episode_id: &str,
-        from_snapshot_id: Option<&str>,
-        to_snapshot_id: Option<&str>,
-        from_text_hash: &str,
-        to_text_hash: &str,
-        ts: DateTime<Utc>,
-        unified_diff: &str,
-        lines_added: i32,
-        lines_removed: i32,
-        change_summary: Option<&str>,
-        change_type: &str,
-    ) -> Result<(), sqlx::Error> {
-        sqlx::query(
+        offset: i64,
+        limit: i64,
+        filter: &MeetingFilter,
+    ) -> Result<MeetingPage, sqlx::Error> {
+        let title_query = filter
+            .title_query
+            .as_deref()
+            .map(str::trim)
+            .filter(|q| !q.is_empty());
+
+        // Build dynamic WHERE clause; every binding is text so the same list
+        // can be applied to both the page query and the count query
+        let mut conditions = Vec::new();
+        let mut bindings: Vec<String> = Vec::new();
+
+        if !filter.include_archived.unwrap_or(false) {
+            conditions.push("m.archived_at IS NULL");
+        }
+        if let Some(ref from) = filter.date_from {
+            conditions.push("m.started_at >= ?");
+            bindings.push(from.clone());
+        }
+        if let Some(ref to) = filter.date_to {
+            conditions.push("m.started_at < ?");
+            bindings.push(meeting_date_upper_bound(to));
+        }
+        if let Some(q) = title_query {
+            conditions.push(
+                "(m.title LIKE ? OR m.id IN (SELECT meeting_id FROM transcripts_fts WHERE transcripts_fts MATCH ?))",
+            );
+            bindings.push(format!("%{}%", q));
+            bindings.push(fts_phrase_query(q));
+        }
+        if let Some(min) = filter.min_duration_secs {
+            conditions.push("COALESCE(m.duration_seconds, 0) >= CAST(? AS INTEGER)");
+            bindings.push(min.to_string());
+        }
+        match filter.has_notes {
+            Some(true) => {
+                conditions.push("EXISTS (SELECT 1 FROM meeting_notes n WHERE n.meeting_id = m.id)")
+            }
+            Some(false) => conditions
+                .push("NOT EXISTS (SELECT 1 FROM meeting_notes n WHERE n.meeting_id = m.id)"),
+            None => {}
+        }
+
+        let mut where_clause = String::from(" WHERE 1=1");
+        for cond in &conditions {
+            where_clause.push_str(" AND ");
+            where_clause.push_str(cond);
+        }
+
+        let count_sql = format!("SELECT COUNT(*) FROM meetings m{}", where_clause);
+        let mut count_query = sqlx::query_as::<_, (i64,)>(&count_sql);
+        for value in &bindings {
+            count_query = count_query.bind(value);
+        }
+        let total = count_query.fetch_one(&self.pool).await?;
+
+        let page_sql = format!(
             r#"
-            INSERT INTO text_patches 
-            (patch_id, episode_id, from_snapshot_id, to_snapshot_id, from_text_hash, 
-             to_text_hash, ts, unified_diff, lines_added, lines_removed, change_summary, change_type)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            SELECT m.id, m.title, m.started_at, m.ended_at, m.duration_seconds,
+                   (SELECT COUNT(*) FROM transcripts t
+                    WHERE t.meeting_id = m.id AND t.is_final = 1) AS transcript_count,
+                   (SELECT COUNT(*) FROM frames f WHERE f.meeting_id = m.id) AS frame_count,
+                   EXISTS (SELECT 1 FROM meeting_notes n WHERE n.meeting_id = m.id) AS has_notes
+            FROM meetings m{}
+            ORDER BY m.started_at DESC
+            LIMIT ? OFFSET ?
             "#,
-        )
-        .bind(patch_id)
-        .bind(episode_id)
-        .bind(from_snapshot_id)
-        .bind(to_snapshot_id)
-        .bind(from_text_hash)
-        .bind(to_text_hash)
-        .bind(ts.to_rfc3339())
-        .bind(unified_diff)
-        .bind(lines_added)
-        .bind(lines_removed)
-        .bind(change_summary)
-        .bind(change_type)
-        .execute(&self.pool)
-        .await?;
+            where_clause
+        );
+        let mut page_query = sqlx::query(&page_sql);
+        for value in &bindings {
+            page_query = page_query.bind(value);
+        }
+        let rows = page_query
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await?;
 
-        Ok(())
+        let meetings = rows
+            .into_iter()
+            .map(|r| MeetingSummary {
+                meeting: Meeting {
+                    id: r.get("id"),
+                    title: r.get("title"),
+                    started_at: DateTime::parse_from_rfc3339(&r.get::<String, _>("started_at"))
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                    ended_at: r
+                        .get::<Option<String>, _>("ended_at")
+                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                        .map(|dt| dt.with_timezone(&Utc)),
+                    duration_seconds: r.get("duration_seconds"),
+                },
+                transcript_count: r.get("transcript_count"),
+                frame_count: r.get("frame_count"),
+                has_notes: r.get::<i64, _>("has_notes") != 0,
+            })
+            .collect();
+
+        Ok(MeetingPage {
+            meetings,
+            total: total.0,
+            offset,
+            limit,
+        })
     }
 
-    /// Get patches for an episode
-    pub async fn get_patches(&self, episode_id: &str) -> Result<Vec<TextPatchRecord>, sqlx::Error> {
+    /// Archive (soft delete) a meeting. Returns false if it doesn't exist.
+    pub async fn archive_meeting(&self, id: &str) -> Result<bool, sqlx::Error> {
+        let result =
+            sqlx::query("UPDATE meetings SET archived_at = COALESCE(archived_at, ?) WHERE id = ?")
+                .bind(Utc::now().to_rfc3339())
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Restore an archived meeting. Returns false if it doesn't exist.
+    pub async fn unarchive_meeting(&self, id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE meetings SET archived_at = NULL WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Whether a meeting is currently archived
+    pub async fn is_meeting_archived(&self, id: &str) -> Result<bool, sqlx::Error> {
+        let row: Option<(Option<String>,)> =
+            sqlx::query_as("SELECT archived_at FROM meetings WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(matches!(row, Some((Some(_),))))
+    }
+
+    /// List archived meetings, most recently archived first
+    pub async fn list_archived_meetings(&self, limit: i32) -> Result<Vec<Meeting>, sqlx::Error> {
         let rows = sqlx::query(
-            r#"
-            SELECT patch_id, episode_id, from_snapshot_id, to_snapshot_id,
-                   from_text_hash, to_text_hash, ts, unified_diff,
-                   lines_added, lines_removed, change_summary, change_type
-            FROM text_patches 
-            WHERE episode_id = ?
-            ORDER BY ts ASC
-            "#,
+            "SELECT id, title, started_at, ended_at, duration_seconds 
+             FROM meetings WHERE archived_at IS NOT NULL ORDER BY archived_at DESC LIMIT ?",
         )
-        .bind(episode_id)
+        .bind(limit)
         .fetch_all(&self.pool)
         .await?;
 
         Ok(rows
             .into_iter()
-            .map(|r| TextPatchRecord {
-                patch_id: r.get("patch_id"),
-                episode_id: r.get("episode_id"),
-                from_snapshot_id: r.try_get("from_snapshot_id").ok(),
-                to_snapshot_id: r.try_get("to_snapshot_id").ok(),
-                from_text_hash: r.get("from_text_hash"),
-                to_text_hash: r.get("to_text_hash"),
-                ts: r.get("ts"),
-                unified_diff: r.get("unified_diff"),
-                lines_added: r.try_get("lines_added").unwrap_or(0),
-                lines_removed: r.try_get("lines_removed").unwrap_or(0),
-                change_summary: r.try_get("change_summary").ok(),
-                change_type: r
-                    .try_get("change_type")
-                    .unwrap_or_else(|_| "content_changed".to_string()),
+            .map(|r| Meeting {
+                id: r.get("id"),
+                title: r.get("title"),
+                started_at: DateTime::parse_from_rfc3339(&r.get::<String, _>("started_at"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                ended_at: r
+                    .get::<Option<String>, _>("ended_at")
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                duration_seconds: r.get("duration_seconds"),
             })
             .collect())
     }
 
-    // ═══════════════════════════════════════════════════════════════════════════
-    // Phase 3: Timeline Events CRUD
-    // ═══════════════════════════════════════════════════════════════════════════
+    /// Delete a meeting and its transcripts
+    pub async fn delete_meeting(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM transcripts WHERE meeting_id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
 
-    /// Add a timeline event
-    pub async fn add_timeline_event(
-        &self,
-        event_id: &str,
-        meeting_id: &str,
-        ts: DateTime<Utc>,
-        event_type: &str,
-        title: &str,
-        description: Option<&str>,
-        app_name: Option<&str>,
-        window_title: Option<&str>,
-        duration_ms: Option<i64>,
-        episode_id: Option<&str>,
-        state_id: Option<&str>,
-        topic: Option<&str>,
-        importance: f32,
-    ) -> Result<(), sqlx::Error> {
-        sqlx::query(
-            r#"
-            INSERT INTO meeting_timeline_events 
-            (event_id, meeting_id, ts, event_type, title, description, 
-             app_name, window_title, duration_ms, episode_id, state_id, topic, importance)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(event_id)
-        .bind(meeting_id)
-        .bind(ts.to_rfc3339())
-        .bind(event_type)
-        .bind(title)
-        .bind(description)
-        .bind(app_name)
-        .bind(window_title)
-        .bind(duration_ms)
-        .bind(episode_id)
-        .bind(state_id)
-        .bind(topic)
-        .bind(importance)
-        .execute(&self.pool)
-        .await?;
+        sqlx::query("DELETE FROM audio_chunks WHERE meeting_id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("DELETE FROM retranscribed_segments WHERE meeting_id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("DELETE FROM retranscribe_jobs WHERE meeting_id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("DELETE FROM meetings WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
 
         Ok(())
     }
 
-    /// Get timeline events for a meeting
-    pub async fn get_timeline_events(
+    /// Add a transcript with smart deduplication (skips duplicates within 30 seconds)
+    pub async fn add_transcript(
         &self,
         meeting_id: &str,
-    ) -> Result<Vec<TimelineEventRecord>, sqlx::Error> {
+        text: &str,
+        speaker: Option<&str>,
+        is_final: bool,
+        confidence: f32,
+    ) -> Result<i64, sqlx::Error> {
+        self.add_transcript_at(meeting_id, text, speaker, is_final, confidence, Utc::now())
+            .await
+    }
+
+    /// Add a transcript spoken at `at` (provider stream timing rather than
+    /// arrival time, so the synced timeline lines up with the audio)
+    pub async fn add_transcript_at(
+        &self,
+        meeting_id: &str,
+        text: &str,
+        speaker: Option<&str>,
+        is_final: bool,
+        confidence: f32,
+        at: DateTime<Utc>,
+    ) -> Result<i64, sqlx::Error> {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let now = at;
+        let now_str = now.to_rfc3339();
+        let meeting_id = meeting_id.to_string();
+        let text = text.to_string();
+        let speaker = speaker.map(str::to_string);
+
+        // Only deduplicate final transcripts
+        if is_final && !text.trim().is_empty() {
+            // Create hash of text content (normalized)
+            let normalized_text = text.trim().to_lowercase();
+            let mut hasher = DefaultHasher::new();
+            normalized_text.hash(&mut hasher);
+            let text_hash = format!("{:016x}", hasher.finish());
+
+            // Check if this exact transcript already exists within last 30 seconds
+            let thirty_secs_ago = (now - chrono::Duration::seconds(30)).to_rfc3339();
+
+            // Check and insert on the writer so concurrent duplicates can't both land
+            return self
+                .writer
+                .write(move |conn| {
+                    Box::pin(async move {
+                        let existing: Option<(i64,)> = sqlx::query_as(
+                            "SELECT id FROM transcripts 
+                             WHERE meeting_id = ? AND text_hash = ? AND timestamp > ?
+                             LIMIT 1",
+                        )
+                        .bind(&meeting_id)
+                        .bind(&text_hash)
+                        .bind(&thirty_secs_ago)
+                        .fetch_optional(&mut *conn)
+                        .await?;
+
+                        if let Some((existing_id,)) = existing {
+                            log::debug!("Skipping duplicate transcript: {}", text);
+                            return Ok(existing_id);
+                        }
+
+                        // Insert with hash
+                        let result = sqlx::query(
+                            "INSERT INTO transcripts (meeting_id, text, speaker, timestamp, is_final, confidence, text_hash) 
+                             VALUES (?, ?, ?, ?, ?, ?, ?)"
+                        )
+                        .bind(&meeting_id)
+                        .bind(&text)
+                        .bind(&speaker)
+                        .bind(&now_str)
+                        .bind(is_final as i32)
+                        .bind(confidence)
+                        .bind(&text_hash)
+                        .execute(&mut *conn)
+                        .await?;
+
+                        Ok(result.last_insert_rowid())
+                    })
+                })
+                .await;
+        }
+
+        // Non-final (interim) transcripts - no deduplication needed
+        self.writer
+            .write(move |conn| {
+                Box::pin(async move {
+                    let result = sqlx::query(
+                        "INSERT INTO transcripts (meeting_id, text, speaker, timestamp, is_final, confidence) 
+                         VALUES (?, ?, ?, ?, ?, ?)",
+                    )
+                    .bind(&meeting_id)
+                    .bind(&text)
+                    .bind(&speaker)
+                    .bind(&now_str)
+                    .bind(is_final as i32)
+                    .bind(confidence)
+                    .execute(&mut *conn)
+                    .await?;
+
+                    Ok(result.last_insert_rowid())
+                })
+            })
+            .await
+    }
+
+    /// Get transcripts for a meeting (speaker names resolved via meeting_speakers)
+    pub async fn get_transcripts(&self, meeting_id: &str) -> Result<Vec<Transcript>, sqlx::Error> {
         let rows = sqlx::query(
-            r#"
-            SELECT event_id, meeting_id, ts, event_type, title, description,
-                   app_name, window_title, duration_ms, episode_id, state_id, topic, importance
-            FROM meeting_timeline_events 
-            WHERE meeting_id = ?
-            ORDER BY ts ASC
-            "#,
+            "SELECT t.id, t.meeting_id, t.text, t.speaker AS speaker_label,
+                    COALESCE(s.display_name, t.speaker) AS speaker,
+                    t.timestamp, t.is_final, t.confidence, t.after_reconnect
+             FROM transcripts t
+             LEFT JOIN meeting_speakers s
+                ON s.meeting_id = t.meeting_id AND s.speaker_label = t.speaker
+             WHERE t.meeting_id = ? ORDER BY t.timestamp ASC",
         )
         .bind(meeting_id)
         .fetch_all(&self.pool)
@@ -1680,69 +2404,288 @@ impl DatabaseManager {
 
         Ok(rows
             .into_iter()
-            .map(|r| TimelineEventRecord {
-                event_id: r.get("event_id"),
+            .map(|r| Transcript {
+                id: r.get("id"),
                 meeting_id: r.get("meeting_id"),
-                ts: r.get("ts"),
-                event_type: r.get("event_type"),
-                title: r.get("title"),
-                description: r.try_get("description").ok(),
-                app_name: r.try_get("app_name").ok(),
-                window_title: r.try_get("window_title").ok(),
-                duration_ms: r.try_get("duration_ms").ok(),
-                episode_id: r.try_get("episode_id").ok(),
-                state_id: r.try_get("state_id").ok(),
-                topic: r.try_get("topic").ok(),
-                importance: r.try_get("importance").unwrap_or(0.5),
+                text: r.get("text"),
+                speaker: r.get("speaker"),
+                speaker_label: r.get("speaker_label"),
+                timestamp: DateTime::parse_from_rfc3339(&r.get::<String, _>("timestamp"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                is_final: r.get::<i32, _>("is_final") == 1,
+                confidence: r.get("confidence"),
+                after_reconnect: r.get::<Option<i32>, _>("after_reconnect") == Some(1),
             })
             .collect())
     }
 
-    /// Add a topic cluster
-    pub async fn add_topic_cluster(
+    /// Correct one transcript's text, keeping the original in transcript_revisions.
+    /// Returns None if the transcript doesn't exist or the text is unchanged.
+    pub async fn update_transcript_text(
         &self,
-        topic_id: &str,
-        meeting_id: &str,
-        name: &str,
-        description: Option<&str>,
-        start_ts: DateTime<Utc>,
-        end_ts: Option<DateTime<Utc>>,
-        event_count: i32,
-        total_duration_ms: i64,
+        transcript_id: i64,
+        new_text: &str,
+    ) -> Result<Option<TranscriptRevision>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let row: Option<(String, String)> =
+            sqlx::query_as("SELECT meeting_id, text FROM transcripts WHERE id = ?")
+                .bind(transcript_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+        let revision = match row {
+            Some((meeting_id, previous_text)) if previous_text != new_text => Some(
+                Self::apply_transcript_edit(
+                    &mut tx,
+                    transcript_id,
+                    &meeting_id,
+                    &previous_text,
+                    new_text,
+                )
+                .await?,
+            ),
+            _ => None,
+        };
+
+        tx.commit().await?;
+        Ok(revision)
+    }
+
+    /// Find/replace across every transcript in a meeting, one revision per changed row
+    pub async fn bulk_replace_in_meeting(
+        &self,
+        meeting_id: &str,
+        find: &str,
+        replace: &str,
+        case_sensitive: bool,
+    ) -> Result<Vec<TranscriptRevision>, sqlx::Error> {
+        if find.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let rows: Vec<(i64, String)> =
+            sqlx::query_as("SELECT id, text FROM transcripts WHERE meeting_id = ? ORDER BY id")
+                .bind(meeting_id)
+                .fetch_all(&mut *tx)
+                .await?;
+
+        let mut revisions = Vec::new();
+        for (id, text) in rows {
+            if let Some(new_text) = replace_in_text(&text, find, replace, case_sensitive) {
+                revisions.push(
+                    Self::apply_transcript_edit(&mut tx, id, meeting_id, &text, &new_text).await?,
+                );
+            }
+        }
+
+        tx.commit().await?;
+        Ok(revisions)
+    }
+
+    async fn apply_transcript_edit(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        transcript_id: i64,
+        meeting_id: &str,
+        previous_text: &str,
+        new_text: &str,
+    ) -> Result<TranscriptRevision, sqlx::Error> {
+        let edited_at = Utc::now();
+
+        // text_hash is cleared rather than recomputed: the unique (meeting_id, text_hash)
+        // index would otherwise reject a correction that matches another segment.
+        // A manual correction also counts as reviewed.
+        sqlx::query(
+            "UPDATE transcripts SET text = ?, text_hash = NULL, needs_reindex = 1, needs_review = 0
+             WHERE id = ?",
+        )
+        .bind(new_text)
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await?;
+
+        let result = sqlx::query(
+            "INSERT INTO transcript_revisions (transcript_id, meeting_id, previous_text, new_text, edited_at)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(transcript_id)
+        .bind(meeting_id)
+        .bind(previous_text)
+        .bind(new_text)
+        .bind(edited_at.to_rfc3339())
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(TranscriptRevision {
+            id: result.last_insert_rowid(),
+            transcript_id,
+            meeting_id: meeting_id.to_string(),
+            previous_text: previous_text.to_string(),
+            new_text: new_text.to_string(),
+            edited_at,
+        })
+    }
+
+    /// Edit history for a transcript, newest first
+    pub async fn get_transcript_revisions(
+        &self,
+        transcript_id: i64,
+    ) -> Result<Vec<TranscriptRevision>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, transcript_id, meeting_id, previous_text, new_text, edited_at
+             FROM transcript_revisions WHERE transcript_id = ? ORDER BY id DESC",
+        )
+        .bind(transcript_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| TranscriptRevision {
+                id: r.get("id"),
+                transcript_id: r.get("transcript_id"),
+                meeting_id: r.get("meeting_id"),
+                previous_text: r.get("previous_text"),
+                new_text: r.get("new_text"),
+                edited_at: DateTime::parse_from_rfc3339(&r.get::<String, _>("edited_at"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            })
+            .collect())
+    }
+
+    /// Transcript ids edited since they were last pushed to Pinecone
+    pub async fn get_transcripts_needing_reindex(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<i64>, sqlx::Error> {
+        let rows: Vec<(i64,)> =
+            sqlx::query_as("SELECT id FROM transcripts WHERE meeting_id = ? AND needs_reindex = 1")
+                .bind(meeting_id)
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    /// Clear the reindex flag after a successful Pinecone upsert
+    pub async fn mark_transcripts_reindexed(&self, ids: &[i64]) -> Result<(), sqlx::Error> {
+        for id in ids {
+            sqlx::query("UPDATE transcripts SET needs_reindex = 0 WHERE id = ?")
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Flag a transcript for the low-confidence review queue
+    pub async fn flag_transcript_for_review(&self, id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE transcripts SET needs_review = 1 WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Final transcripts still waiting for review, oldest first
+    pub async fn get_transcripts_needing_review(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<Transcript>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT t.id, t.meeting_id, t.text, t.speaker AS speaker_label,
+                    COALESCE(s.display_name, t.speaker) AS speaker,
+                    t.timestamp, t.is_final, t.confidence, t.after_reconnect
+             FROM transcripts t
+             LEFT JOIN meeting_speakers s
+                ON s.meeting_id = t.meeting_id AND s.speaker_label = t.speaker
+             WHERE t.meeting_id = ? AND t.needs_review = 1
+             ORDER BY t.timestamp ASC",
+        )
+        .bind(meeting_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| Transcript {
+                id: r.get("id"),
+                meeting_id: r.get("meeting_id"),
+                text: r.get("text"),
+                speaker: r.get("speaker"),
+                speaker_label: r.get("speaker_label"),
+                timestamp: DateTime::parse_from_rfc3339(&r.get::<String, _>("timestamp"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                is_final: r.get::<i32, _>("is_final") == 1,
+                confidence: r.get("confidence"),
+                after_reconnect: r.get::<Option<i32>, _>("after_reconnect") == Some(1),
+            })
+            .collect())
+    }
+
+    /// Flag a segment as transcribed from audio buffered during a reconnect
+    pub async fn mark_transcript_after_reconnect(&self, id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE transcripts SET after_reconnect = 1 WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Clear the review flag. Returns false if the transcript wasn't flagged.
+    pub async fn mark_transcript_reviewed(&self, id: i64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE transcripts SET needs_review = 0 WHERE id = ? AND needs_review = 1",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Assign a display name to a diarization label within one meeting
+    pub async fn rename_speaker(
+        &self,
+        meeting_id: &str,
+        speaker_label: &str,
+        display_name: &str,
     ) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
-            INSERT INTO topic_clusters 
-            (topic_id, meeting_id, name, description, start_ts, end_ts, event_count, total_duration_ms)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO meeting_speakers (meeting_id, speaker_label, display_name, updated_at)
+            VALUES (?, ?, ?, datetime('now'))
+            ON CONFLICT(meeting_id, speaker_label)
+            DO UPDATE SET display_name = excluded.display_name, updated_at = excluded.updated_at
             "#,
         )
-        .bind(topic_id)
         .bind(meeting_id)
-        .bind(name)
-        .bind(description)
-        .bind(start_ts.to_rfc3339())
-        .bind(end_ts.map(|ts| ts.to_rfc3339()))
-        .bind(event_count)
-        .bind(total_duration_ms)
+        .bind(speaker_label)
+        .bind(display_name)
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
 
-    /// Get topic clusters for a meeting
-    pub async fn get_topic_clusters(
+    /// List speakers seen in a meeting along with any assigned display names
+    pub async fn get_meeting_speakers(
         &self,
         meeting_id: &str,
-    ) -> Result<Vec<TopicClusterRecord>, sqlx::Error> {
+    ) -> Result<Vec<MeetingSpeaker>, sqlx::Error> {
         let rows = sqlx::query(
             r#"
-            SELECT topic_id, meeting_id, name, description, start_ts, end_ts,
-                   event_count, total_duration_ms
-            FROM topic_clusters 
-            WHERE meeting_id = ?
-            ORDER BY start_ts ASC
+            SELECT t.speaker AS speaker_label, s.display_name, COUNT(*) AS segment_count
+            FROM transcripts t
+            LEFT JOIN meeting_speakers s
+                ON s.meeting_id = t.meeting_id AND s.speaker_label = t.speaker
+            WHERE t.meeting_id = ? AND t.speaker IS NOT NULL
+            GROUP BY t.speaker
+            ORDER BY t.speaker ASC
             "#,
         )
         .bind(meeting_id)
@@ -1751,1049 +2694,4378 @@ impl DatabaseManager {
 
         Ok(rows
             .into_iter()
-            .map(|r| TopicClusterRecord {
-                topic_id: r.get("topic_id"),
+            .map(|r| MeetingSpeaker {
+                meeting_id: meeting_id.to_string(),
+                speaker_label: r.get("speaker_label"),
+                display_name: r.get("display_name"),
+                segment_count: r.get("segment_count"),
+            })
+            .collect())
+    }
+
+    /// Search transcripts using FTS5
+    pub async fn search_transcripts(&self, query: &str) -> Result<Vec<SearchResult>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT 
+                t.meeting_id,
+                m.title as meeting_title,
+                t.text as transcript_text,
+                t.timestamp,
+                bm25(transcripts_fts) as relevance
+            FROM transcripts_fts
+            JOIN transcripts t ON transcripts_fts.rowid = t.id
+            JOIN meetings m ON t.meeting_id = m.id
+            WHERE transcripts_fts MATCH ? AND m.archived_at IS NULL
+            ORDER BY relevance
+            LIMIT 50
+            "#,
+        )
+        .bind(query)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| SearchResult {
                 meeting_id: r.get("meeting_id"),
-                name: r.get("name"),
-                description: r.try_get("description").ok(),
-                start_ts: r.get("start_ts"),
-                end_ts: r.try_get("end_ts").ok(),
-                event_count: r.try_get("event_count").unwrap_or(0),
-                total_duration_ms: r.try_get("total_duration_ms").unwrap_or(0),
+                meeting_title: r.get("meeting_title"),
+                transcript_text: r.get("transcript_text"),
+                timestamp: DateTime::parse_from_rfc3339(&r.get::<String, _>("timestamp"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                relevance: r.get("relevance"),
             })
             .collect())
     }
-}
 
-/// Screen state database record
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ScreenStateRecord {
-    pub state_id: String,
-    pub meeting_id: String,
-    pub start_ts: String,
-    pub end_ts: Option<String>,
-    pub app_name: Option<String>,
-    pub window_title: Option<String>,
-    pub phash: String,
-    pub delta_score: f32,
-    pub keyframe_path: Option<String>,
-    pub state_type: String,
-    pub flags: String,
-}
+    /// Index text snapshots and OCR frames missing from the FTS tables, in
+    /// rowid batches. Returns (snapshots indexed, frames indexed).
+    pub async fn backfill_search_index(&self) -> Result<(usize, usize), sqlx::Error> {
+        const BATCH: i64 = 500;
 
-/// Document episode database record (Phase 2)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DocumentEpisodeRecord {
-    pub episode_id: String,
-    pub meeting_id: String,
-    pub start_ts: String,
-    pub end_ts: Option<String>,
-    pub app_name: Option<String>,
-    pub window_title: Option<String>,
-    pub document_fingerprint: Option<String>,
-    pub state_count: i32,
-    pub total_duration_ms: i64,
-}
+        let mut snapshots = 0;
+        let max_rowid: Option<i64> = sqlx::query_scalar("SELECT MAX(rowid) FROM text_snapshots")
+            .fetch_one(&self.pool)
+            .await?;
+        let mut cursor = 0;
+        while cursor < max_rowid.unwrap_or(0) {
+            let full = sqlx::query(
+                r#"
+                INSERT INTO text_snapshots_fts(rowid, text, meeting_id)
+                SELECT s.rowid, s.text, s.meeting_id FROM text_snapshots s
+                WHERE s.rowid > ? AND s.rowid <= ? AND s.patch IS NULL
+                  AND NOT EXISTS (SELECT 1 FROM text_snapshots_fts WHERE rowid = s.rowid)
+                "#,
+            )
+            .bind(cursor)
+            .bind(cursor + BATCH)
+            .execute(&self.pool)
+            .await?;
+            snapshots += full.rows_affected() as usize;
+
+            let deltas: Vec<(String,)> = sqlx::query_as(
+                r#"
+                SELECT s.snapshot_id FROM text_snapshots s
+                WHERE s.rowid > ? AND s.rowid <= ? AND s.patch IS NOT NULL
+                  AND NOT EXISTS (SELECT 1 FROM text_snapshots_fts WHERE rowid = s.rowid)
+                "#,
+            )
+            .bind(cursor)
+            .bind(cursor + BATCH)
+            .fetch_all(&self.pool)
+            .await?;
+            for (snapshot_id,) in deltas {
+                match self.reconstruct_snapshot(&snapshot_id).await {
+                    Ok(text) => {
+                        self.index_snapshot_text(&snapshot_id, &text).await?;
+                        snapshots += 1;
+                    }
+                    Err(e) => {
+                        log::warn!("Skipping snapshot {} in search index: {}", snapshot_id, e)
+                    }
+                }
+            }
 
-/// Text snapshot database record (Phase 2)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TextSnapshotRecord {
-    pub snapshot_id: String,
-    pub episode_id: Option<String>,
-    pub state_id: Option<String>,
-    pub meeting_id: Option<String>,
-    pub ts: String,
-    pub text: String,
-    pub text_hash: String,
-    pub quality_score: f32,
-    pub source: String,
-    pub word_count: i32,
-    pub app_name: Option<String>,
-    pub window_title: Option<String>,
-}
+            cursor += BATCH;
+            tokio::task::yield_now().await;
+        }
 
-/// Text patch database record (Phase 2)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TextPatchRecord {
-    pub patch_id: String,
-    pub episode_id: String,
-    pub from_snapshot_id: Option<String>,
-    pub to_snapshot_id: Option<String>,
-    pub from_text_hash: String,
-    pub to_text_hash: String,
-    pub ts: String,
-    pub unified_diff: String,
-    pub lines_added: i32,
-    pub lines_removed: i32,
-    pub change_summary: Option<String>,
-    pub change_type: String,
-}
+        let mut frames = 0;
+        let max_id: Option<i64> = sqlx::query_scalar("SELECT MAX(id) FROM frames")
+            .fetch_one(&self.pool)
+            .await?;
+        let mut cursor = 0;
+        while cursor < max_id.unwrap_or(0) {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO frames_fts(rowid, ocr_text, meeting_id)
+                SELECT f.id, f.ocr_text, f.meeting_id FROM frames f
+                WHERE f.id > ? AND f.id <= ? AND f.ocr_text IS NOT NULL AND f.ocr_text != ''
+                  AND NOT EXISTS (SELECT 1 FROM frames_fts WHERE rowid = f.id)
+                "#,
+            )
+            .bind(cursor)
+            .bind(cursor + BATCH)
+            .execute(&self.pool)
+            .await?;
+            frames += result.rows_affected() as usize;
 
-/// Timeline event database record (Phase 3)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TimelineEventRecord {
-    pub event_id: String,
-    pub meeting_id: String,
-    pub ts: String,
-    pub event_type: String,
-    pub title: String,
-    pub description: Option<String>,
-    pub app_name: Option<String>,
-    pub window_title: Option<String>,
-    pub duration_ms: Option<i64>,
-    pub episode_id: Option<String>,
-    pub state_id: Option<String>,
-    pub topic: Option<String>,
-    pub importance: f32,
-}
+            cursor += BATCH;
+            tokio::task::yield_now().await;
+        }
 
-/// Topic cluster database record (Phase 3)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TopicClusterRecord {
-    pub topic_id: String,
-    pub meeting_id: String,
-    pub name: String,
-    pub description: Option<String>,
-    pub start_ts: String,
-    pub end_ts: Option<String>,
-    pub event_count: i32,
-    pub total_duration_ms: i64,
-}
+        Ok((snapshots, frames))
+    }
 
-/// Frame record (for rewind timeline)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Frame {
-    pub id: i64,
-    pub meeting_id: String,
-    pub frame_number: i64,
-    pub timestamp: DateTime<Utc>,
-    pub file_path: Option<String>,
-    pub ocr_text: Option<String>,
-}
+    /// Search transcripts, text snapshots and frame OCR text at once.
+    /// Results from all sources are ranked together by source-weighted BM25.
+    pub async fn search_everything(
+        &self,
+        query: &str,
+        sources: &[SearchSource],
+        limit: i64,
+    ) -> Result<Vec<UnifiedSearchResult>, sqlx::Error> {
+        let match_query = fts_terms_query(query);
+        if match_query.is_empty() {
+            return Ok(Vec::new());
+        }
 
-/// Synced timeline data (frames + transcripts aligned by timestamp)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SyncedTimeline {
-    pub meeting_id: String,
-    pub meeting_title: String,
-    pub duration_seconds: i64,
-    pub frames: Vec<TimelineFrame>,
-    pub transcripts: Vec<TimelineTranscript>,
-}
+        let mut results = Vec::new();
+        for source in sources {
+            let sql = match source {
+                SearchSource::Transcript => {
+                    r#"
+                    SELECT CAST(t.id AS TEXT) AS id, t.meeting_id, m.title AS meeting_title,
+                           t.timestamp AS ts,
+                           snippet(transcripts_fts, 0, '<mark>', '</mark>', '…', 16) AS snippet,
+                           bm25(transcripts_fts) AS rank
+                    FROM transcripts_fts
+                    JOIN transcripts t ON transcripts_fts.rowid = t.id
+                    JOIN meetings m ON t.meeting_id = m.id
+                    WHERE transcripts_fts MATCH ? AND m.archived_at IS NULL
+                    ORDER BY rank
+                    LIMIT ?
+                    "#
+                }
+                SearchSource::Snapshot => {
+                    r#"
+                    SELECT s.snapshot_id AS id, s.meeting_id, m.title AS meeting_title,
+                           s.ts AS ts,
+                           snippet(text_snapshots_fts, 0, '<mark>', '</mark>', '…', 16) AS snippet,
+                           bm25(text_snapshots_fts) AS rank
+                    FROM text_snapshots_fts
+                    JOIN text_snapshots s ON text_snapshots_fts.rowid = s.rowid
+                    LEFT JOIN meetings m ON s.meeting_id = m.id
+                    WHERE text_snapshots_fts MATCH ? AND m.archived_at IS NULL
+                    ORDER BY rank
+                    LIMIT ?
+                    "#
+                }
+                SearchSource::Ocr => {
+                    r#"
+                    SELECT CAST(f.id AS TEXT) AS id, f.meeting_id, m.title AS meeting_title,
+                           f.timestamp AS ts,
+                           snippet(frames_fts, 0, '<mark>', '</mark>', '…', 16) AS snippet,
+                           bm25(frames_fts) AS rank
+                    FROM frames_fts
+                    JOIN frames f ON frames_fts.rowid = f.id
+                    JOIN meetings m ON f.meeting_id = m.id
+                    WHERE frames_fts MATCH ? AND m.archived_at IS NULL
+                    ORDER BY rank
+                    LIMIT ?
+                    "#
+                }
+            };
 
-/// Frame on the timeline (simplified for UI)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TimelineFrame {
-    pub id: String,
-    pub frame_number: i64,
-    pub timestamp_ms: i64, // Milliseconds from start of meeting
-    pub thumbnail_path: Option<String>,
-}
+            let rows = sqlx::query(sql)
+                .bind(&match_query)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await?;
 
-/// Transcript on the timeline
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TimelineTranscript {
-    pub id: String,
-    pub timestamp_ms: i64, // Milliseconds from start of meeting
-    pub text: String,
-    pub speaker: Option<String>,
-    pub is_final: bool,
-    pub duration_seconds: f64,
-}
+            results.extend(rows.into_iter().map(|r| {
+                UnifiedSearchResult {
+                    source: *source,
+                    id: r.get("id"),
+                    meeting_id: r.try_get::<Option<String>, _>("meeting_id").ok().flatten(),
+                    meeting_title: r
+                        .try_get::<Option<String>, _>("meeting_title")
+                        .ok()
+                        .flatten(),
+                    timestamp: DateTime::parse_from_rfc3339(&r.get::<String, _>("ts"))
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                    snippet: r.get("snippet"),
+                    // bm25() is lower-is-better; flip it so higher scores rank first
+                    score: -r.get::<f64, _>("rank") * source.weight(),
+                }
+            }));
+        }
 
-impl DatabaseManager {
-    /// Get synced timeline for a meeting (frames + transcripts aligned)
-    /// Supports both legacy frames table and new screen_states table
-    pub async fn get_synced_timeline(
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(limit.max(0) as usize);
+        Ok(results)
+    }
+
+    /// Insert or replace a locally embedded vector
+    pub async fn upsert_local_vector(
         &self,
-        meeting_id: &str,
-    ) -> Result<Option<SyncedTimeline>, sqlx::Error> {
-        eprintln!(
-            "🔍 get_synced_timeline CALLED with meeting_id: {}",
-            meeting_id
-        );
+        id: &str,
+        text: &str,
+        metadata_json: &str,
+        embedding: &[u8],
+        model: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO local_vectors (id, text, metadata_json, embedding, model, updated_at)
+            VALUES (?, ?, ?, ?, ?, datetime('now'))
+            ON CONFLICT(id) DO UPDATE SET
+                text = excluded.text,
+                metadata_json = excluded.metadata_json,
+                embedding = excluded.embedding,
+                model = excluded.model,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(text)
+        .bind(metadata_json)
+        .bind(embedding)
+        .bind(model)
+        .execute(&self.pool)
+        .await?;
 
-        // Get meeting info
-        let meeting = match self.get_meeting(meeting_id).await? {
-            Some(m) => m,
-            None => {
-                eprintln!("❌ Meeting not found: {}", meeting_id);
-                return Ok(None);
-            }
-        };
+        Ok(())
+    }
 
-        let start_time = meeting.started_at;
-        let duration = meeting.duration_seconds.unwrap_or(0);
+    /// All local vectors embedded with `model` (searched by brute force)
+    pub async fn get_local_vectors(
+        &self,
+        model: &str,
+    ) -> Result<Vec<LocalVectorRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, text, metadata_json, embedding FROM local_vectors WHERE model = ?",
+        )
+        .bind(model)
+        .fetch_all(&self.pool)
+        .await?;
 
-        // Try to get frames from legacy frames table first
-        let legacy_frames = self.get_frames(meeting_id, 10000).await?;
-        eprintln!(
-            "📊 Legacy frames count: {} for meeting: {}",
-            legacy_frames.len(),
-            meeting_id
-        );
+        Ok(rows
+            .into_iter()
+            .map(|r| LocalVectorRecord {
+                id: r.get("id"),
+                text: r.get("text"),
+                metadata_json: r
+                    .try_get::<Option<String>, _>("metadata_json")
+                    .ok()
+                    .flatten(),
+                embedding: r.get("embedding"),
+            })
+            .collect())
+    }
 
-        let timeline_frames: Vec<TimelineFrame> = if !legacy_frames.is_empty() {
-            // Use legacy frames table
-            eprintln!("✅ Using {} legacy frames", legacy_frames.len());
-            legacy_frames
-                .into_iter()
-                .map(|f| {
-                    let ms = (f.timestamp - start_time).num_milliseconds();
-                    TimelineFrame {
-                        id: f.id.to_string(),
-                        frame_number: f.frame_number,
-                        timestamp_ms: ms.max(0),
-                        thumbnail_path: f.file_path,
-                    }
-                })
-                .collect()
-        } else {
-            // Fallback to screen_states table (stateful capture)
-            log::info!(
-                "No legacy frames for meeting {}, checking screen_states",
-                meeting_id
-            );
-            let screen_states = self.get_screen_states(meeting_id, 10000).await?;
-            log::info!(
-                "Found {} screen_states for meeting {}",
-                screen_states.len(),
-                meeting_id
-            );
-            let frames: Vec<TimelineFrame> = screen_states
-                .into_iter()
-                .enumerate()
-                .filter(|(_, s)| s.keyframe_path.is_some()) // Only use states with keyframes
-                .map(|(idx, s)| {
-                    // Parse start_ts to calculate timestamp_ms
-                    let state_ts = DateTime::parse_from_rfc3339(&s.start_ts)
-                        .map(|dt| dt.with_timezone(&Utc))
-                        .unwrap_or(start_time);
-                    let ms = (state_ts - start_time).num_milliseconds();
-                    TimelineFrame {
-                        id: s.state_id,
-                        frame_number: idx as i64,
-                        timestamp_ms: ms.max(0),
-                        thumbnail_path: s.keyframe_path,
-                    }
+    /// Ids already embedded with `model`
+    pub async fn get_local_vector_ids(&self, model: &str) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT id FROM local_vectors WHERE model = ?")
+            .bind(model)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Delete local vectors whose metadata points at a meeting
+    pub async fn delete_local_vectors_for_meeting(
+        &self,
+        meeting_id: &str,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM local_vectors WHERE json_extract(metadata_json, '$.meeting_id') = ?",
+        )
+        .bind(meeting_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete_local_vectors(&self, ids: &[String]) -> Result<u64, sqlx::Error> {
+        let mut deleted = 0;
+        for id in ids {
+            deleted += sqlx::query("DELETE FROM local_vectors WHERE id = ?")
+                .bind(id)
+                .execute(&self.pool)
+                .await?
+                .rows_affected();
+        }
+        Ok(deleted)
+    }
+
+    /// Add a frame to the database (for rewind functionality)
+    pub async fn add_frame(
+        &self,
+        meeting_id: &str,
+        timestamp: DateTime<Utc>,
+        file_path: Option<&str>,
+        ocr_text: Option<&str>,
+    ) -> Result<i64, sqlx::Error> {
+        let timestamp_str = timestamp.to_rfc3339();
+        let meeting_id = meeting_id.to_string();
+        let file_path = file_path.map(str::to_string);
+        let ocr_text = ocr_text.map(str::to_string);
+
+        self.writer
+            .write(move |conn| {
+                Box::pin(async move {
+                    // Get next frame number for this meeting
+                    let frame_count: i64 =
+                        sqlx::query_scalar("SELECT COUNT(*) FROM frames WHERE meeting_id = ?")
+                            .bind(&meeting_id)
+                            .fetch_one(&mut *conn)
+                            .await
+                            .unwrap_or(0);
+
+                    let result = sqlx::query(
+                        "INSERT INTO frames (meeting_id, frame_number, timestamp, file_path, ocr_text) VALUES (?, ?, ?, ?, ?)"
+                    )
+                    .bind(&meeting_id)
+                    .bind(frame_count)
+                    .bind(&timestamp_str)
+                    .bind(&file_path)
+                    .bind(&ocr_text)
+                    .execute(&mut *conn)
+                    .await?;
+
+                    Ok(result.last_insert_rowid())
                 })
-                .collect();
-            log::info!(
-                "Returning {} timeline frames from screen_states",
-                frames.len()
-            );
-            frames
-        };
+            })
+            .await
+    }
 
-        // Get transcripts
-        let transcripts = self.get_transcripts(meeting_id).await?;
-        let timeline_transcripts: Vec<TimelineTranscript> = transcripts
+    /// Get frames for a meeting (for rewind timeline)
+    pub async fn get_frames(
+        &self,
+        meeting_id: &str,
+        limit: i32,
+    ) -> Result<Vec<Frame>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, meeting_id, frame_number, timestamp, file_path, ocr_text 
+             FROM frames WHERE meeting_id = ? ORDER BY timestamp ASC LIMIT ?",
+        )
+        .bind(meeting_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
             .into_iter()
-            .map(|t| {
-                let ms = (t.timestamp - start_time).num_milliseconds();
-                TimelineTranscript {
-                    id: t.id.to_string(),
-                    timestamp_ms: ms.max(0),
-                    text: t.text,
-                    speaker: t.speaker,
-                    is_final: t.is_final,
-                    duration_seconds: 0.0, // TODO: Store actual duration from Deepgram
-                }
+            .map(|r| Frame {
+                id: r.get("id"),
+                meeting_id: r.get("meeting_id"),
+                frame_number: r.try_get("frame_number").unwrap_or(0),
+                timestamp: DateTime::parse_from_rfc3339(&r.get::<String, _>("timestamp"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                file_path: r.try_get("file_path").ok(),
+                ocr_text: r.try_get("ocr_text").ok(),
             })
-            .collect();
+            .collect())
+    }
+
+    /// Get frames in a time range (for rewind scrubbing)
+    pub async fn get_frames_in_range(
+        &self,
+        meeting_id: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Frame>, sqlx::Error> {
+        let start_str = start.to_rfc3339();
+        let end_str = end.to_rfc3339();
+
+        let rows = sqlx::query(
+            "SELECT id, meeting_id, frame_number, timestamp, file_path, ocr_text 
+             FROM frames WHERE meeting_id = ? AND timestamp >= ? AND timestamp <= ?
+             ORDER BY timestamp ASC",
+        )
+        .bind(meeting_id)
+        .bind(&start_str)
+        .bind(&end_str)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| Frame {
+                id: r.get("id"),
+                meeting_id: r.get("meeting_id"),
+                frame_number: r.try_get("frame_number").unwrap_or(0),
+                timestamp: DateTime::parse_from_rfc3339(&r.get::<String, _>("timestamp"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                file_path: r.try_get("file_path").ok(),
+                ocr_text: r.try_get("ocr_text").ok(),
+            })
+            .collect())
+    }
+
+    /// Get the most recent frame for a meeting
+    pub async fn get_latest_frame(&self, meeting_id: &str) -> Result<Option<Frame>, sqlx::Error> {
+        let row = sqlx::query(
+            "SELECT id, meeting_id, frame_number, timestamp, file_path, ocr_text 
+             FROM frames WHERE meeting_id = ? ORDER BY timestamp DESC LIMIT 1",
+        )
+        .bind(meeting_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| Frame {
+            id: r.get("id"),
+            meeting_id: r.get("meeting_id"),
+            frame_number: r.try_get("frame_number").unwrap_or(0),
+            timestamp: DateTime::parse_from_rfc3339(&r.get::<String, _>("timestamp"))
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+            file_path: r.try_get("file_path").ok(),
+            ocr_text: r.try_get("ocr_text").ok(),
+        }))
+    }
+
+    /// Count frames for a meeting
+    pub async fn count_frames(&self, meeting_id: &str) -> Result<i64, sqlx::Error> {
+        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM frames WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.0)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // Phase 1: Stateful Screen Ingest - ScreenState methods
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Add a new screen state
+    pub async fn add_screen_state(
+        &self,
+        state_id: &str,
+        meeting_id: &str,
+        start_ts: DateTime<Utc>,
+        end_ts: Option<DateTime<Utc>>,
+        phash: &str,
+        delta_score: f32,
+        keyframe_path: Option<&str>,
+        state_type: &str,
+        flags_json: &str,
+        monitor_id: Option<u32>,
+    ) -> Result<(), sqlx::Error> {
+        let state_id = state_id.to_string();
+        let meeting_id = meeting_id.to_string();
+        let phash = phash.to_string();
+        let keyframe_path = keyframe_path.map(str::to_string);
+        let state_type = state_type.to_string();
+        let flags_json = flags_json.to_string();
+
+        self.writer
+            .write(move |conn| {
+                Box::pin(async move {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO screen_states 
+                        (state_id, meeting_id, start_ts, end_ts, phash, delta_score, keyframe_path, state_type, flags, monitor_id)
+                        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                        "#,
+                    )
+                    .bind(&state_id)
+                    .bind(&meeting_id)
+                    .bind(start_ts.to_rfc3339())
+                    .bind(end_ts.map(|ts| ts.to_rfc3339()))
+                    .bind(&phash)
+                    .bind(delta_score)
+                    .bind(&keyframe_path)
+                    .bind(&state_type)
+                    .bind(&flags_json)
+                    .bind(monitor_id.map(|id| id as i64))
+                    .execute(&mut *conn)
+                    .await?;
+
+                    Ok(())
+                })
+            })
+            .await
+    }
+
+    /// Record the window context and classifier confidence for a screen state
+    pub async fn set_screen_state_context(
+        &self,
+        state_id: &str,
+        app_name: Option<&str>,
+        window_title: Option<&str>,
+        category_confidence: f32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE screen_states
+            SET app_name = ?, window_title = ?, category_confidence = ?
+            WHERE state_id = ?
+            "#,
+        )
+        .bind(app_name)
+        .bind(window_title)
+        .bind(category_confidence)
+        .bind(state_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Update screen state end timestamp (extend duration)
+    pub async fn extend_screen_state(
+        &self,
+        state_id: &str,
+        end_ts: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let state_id = state_id.to_string();
+        self.writer
+            .write(move |conn| {
+                Box::pin(async move {
+                    sqlx::query("UPDATE screen_states SET end_ts = ? WHERE state_id = ?")
+                        .bind(end_ts.to_rfc3339())
+                        .bind(&state_id)
+                        .execute(&mut *conn)
+                        .await?;
+                    Ok(())
+                })
+            })
+            .await
+    }
+
+    /// Update screen state with keyframe path after saving
+    pub async fn update_screen_state_keyframe(
+        &self,
+        state_id: &str,
+        keyframe_path: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE screen_states SET keyframe_path = ? WHERE state_id = ?")
+            .bind(keyframe_path)
+            .bind(state_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get screen states for a meeting
+    pub async fn get_screen_states(
+        &self,
+        meeting_id: &str,
+        limit: i32,
+    ) -> Result<Vec<ScreenStateRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT state_id, meeting_id, start_ts, end_ts, app_name, window_title,
+                   phash, delta_score, keyframe_path, state_type, category_confidence,
+                   flags, monitor_id, created_at
+            FROM screen_states 
+            WHERE meeting_id = ?
+            ORDER BY start_ts ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(meeting_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| ScreenStateRecord {
+                state_id: r.get("state_id"),
+                meeting_id: r.get("meeting_id"),
+                start_ts: r.get("start_ts"),
+                end_ts: r.try_get("end_ts").ok(),
+                app_name: r.try_get("app_name").ok(),
+                window_title: r.try_get("window_title").ok(),
+                phash: r.get("phash"),
+                delta_score: r.try_get("delta_score").unwrap_or(0.0),
+                keyframe_path: r.try_get("keyframe_path").ok(),
+                state_type: r
+                    .try_get("state_type")
+                    .unwrap_or_else(|_| "other".to_string()),
+                category_confidence: r
+                    .try_get::<Option<f32>, _>("category_confidence")
+                    .ok()
+                    .flatten(),
+                flags: r.try_get("flags").unwrap_or_else(|_| "{}".to_string()),
+                monitor_id: r
+                    .try_get::<Option<i64>, _>("monitor_id")
+                    .ok()
+                    .flatten()
+                    .map(|id| id as u32),
+            })
+            .collect())
+    }
+
+    /// Screen states from any meeting that were still on screen after `since`
+    /// (open states count when they started after it)
+    pub async fn get_screen_states_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<ScreenStateRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT state_id, meeting_id, start_ts, end_ts, app_name, window_title,
+                   phash, delta_score, keyframe_path, state_type, category_confidence,
+                   flags, monitor_id, created_at
+            FROM screen_states
+            WHERE COALESCE(end_ts, start_ts) >= ?
+            ORDER BY start_ts ASC
+            "#,
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| ScreenStateRecord {
+                state_id: r.get("state_id"),
+                meeting_id: r.get("meeting_id"),
+                start_ts: r.get("start_ts"),
+                end_ts: r.try_get("end_ts").ok(),
+                app_name: r.try_get("app_name").ok(),
+                window_title: r.try_get("window_title").ok(),
+                phash: r.get("phash"),
+                delta_score: r.try_get("delta_score").unwrap_or(0.0),
+                keyframe_path: r.try_get("keyframe_path").ok(),
+                state_type: r
+                    .try_get("state_type")
+                    .unwrap_or_else(|_| "other".to_string()),
+                category_confidence: r
+                    .try_get::<Option<f32>, _>("category_confidence")
+                    .ok()
+                    .flatten(),
+                flags: r.try_get("flags").unwrap_or_else(|_| "{}".to_string()),
+                monitor_id: r
+                    .try_get::<Option<i64>, _>("monitor_id")
+                    .ok()
+                    .flatten()
+                    .map(|id| id as u32),
+            })
+            .collect())
+    }
+
+    /// Count screen states for a meeting
+    pub async fn count_screen_states(&self, meeting_id: &str) -> Result<i64, sqlx::Error> {
+        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM screen_states WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.0)
+    }
+
+    /// Get the latest screen state for a meeting
+    pub async fn get_latest_screen_state(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<ScreenStateRecord>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT state_id, meeting_id, start_ts, end_ts, app_name, window_title,
+                   phash, delta_score, keyframe_path, state_type, category_confidence,
+                   flags, monitor_id, created_at
+            FROM screen_states 
+            WHERE meeting_id = ?
+            ORDER BY start_ts DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(meeting_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| ScreenStateRecord {
+            state_id: r.get("state_id"),
+            meeting_id: r.get("meeting_id"),
+            start_ts: r.get("start_ts"),
+            end_ts: r.try_get("end_ts").ok(),
+            app_name: r.try_get("app_name").ok(),
+            window_title: r.try_get("window_title").ok(),
+            phash: r.get("phash"),
+            delta_score: r.try_get("delta_score").unwrap_or(0.0),
+            keyframe_path: r.try_get("keyframe_path").ok(),
+            state_type: r
+                .try_get("state_type")
+                .unwrap_or_else(|_| "other".to_string()),
+            category_confidence: r
+                .try_get::<Option<f32>, _>("category_confidence")
+                .ok()
+                .flatten(),
+            flags: r.try_get("flags").unwrap_or_else(|_| "{}".to_string()),
+            monitor_id: r
+                .try_get::<Option<i64>, _>("monitor_id")
+                .ok()
+                .flatten()
+                .map(|id| id as u32),
+        }))
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // Phase 2: Document Episodes & Text Snapshots CRUD
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Create a new document episode
+    pub async fn create_episode(
+        &self,
+        episode_id: &str,
+        meeting_id: &str,
+        start_ts: DateTime<Utc>,
+        app_name: Option<&str>,
+        window_title: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO document_episodes 
+            (episode_id, meeting_id, start_ts, app_name, window_title)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(episode_id)
+        .bind(meeting_id)
+        .bind(start_ts.to_rfc3339())
+        .bind(app_name)
+        .bind(window_title)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Update episode end time and stats
+    pub async fn update_episode(
+        &self,
+        episode_id: &str,
+        end_ts: DateTime<Utc>,
+        state_count: i32,
+        total_duration_ms: i64,
+        category: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE document_episodes 
+            SET end_ts = ?, state_count = ?, total_duration_ms = ?, category = ?
+            WHERE episode_id = ?
+            "#,
+        )
+        .bind(end_ts.to_rfc3339())
+        .bind(state_count)
+        .bind(total_duration_ms)
+        .bind(category)
+        .bind(episode_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Link a state to an episode
+    pub async fn link_state_to_episode(
+        &self,
+        episode_id: &str,
+        state_id: &str,
+        sequence_num: i32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO episode_states (episode_id, state_id, sequence_num)
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(episode_id)
+        .bind(state_id)
+        .bind(sequence_num)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get episodes for a meeting
+    pub async fn get_episodes(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<DocumentEpisodeRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT episode_id, meeting_id, start_ts, end_ts, app_name, window_title,
+                   document_fingerprint, state_count, total_duration_ms, category,
+                   summary, keywords_json
+            FROM document_episodes 
+            WHERE meeting_id = ?
+            ORDER BY start_ts ASC
+            "#,
+        )
+        .bind(meeting_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(episode_from_row).collect())
+    }
+
+    /// Get a single episode
+    pub async fn get_episode(
+        &self,
+        episode_id: &str,
+    ) -> Result<Option<DocumentEpisodeRecord>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT episode_id, meeting_id, start_ts, end_ts, app_name, window_title,
+                   document_fingerprint, state_count, total_duration_ms, category,
+                   summary, keywords_json
+            FROM document_episodes
+            WHERE episode_id = ?
+            "#,
+        )
+        .bind(episode_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(episode_from_row))
+    }
+
+    /// Store the AI summary and keywords for an episode
+    pub async fn set_episode_summary(
+        &self,
+        episode_id: &str,
+        summary: &str,
+        keywords: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let keywords_json = serde_json::to_string(keywords).unwrap_or_else(|_| "[]".to_string());
+        sqlx::query(
+            "UPDATE document_episodes SET summary = ?, keywords_json = ? WHERE episode_id = ?",
+        )
+        .bind(summary)
+        .bind(keywords_json)
+        .bind(episode_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Add a text snapshot (legacy signature for backwards compatibility)
+    pub async fn add_text_snapshot(
+        &self,
+        snapshot_id: &str,
+        episode_id: Option<&str>,
+        state_id: Option<&str>,
+        ts: DateTime<Utc>,
+        text: &str,
+        text_hash: &str,
+        quality_score: f32,
+        source: &str,
+    ) -> Result<(), sqlx::Error> {
+        self.add_text_snapshot_full(
+            snapshot_id,
+            episode_id,
+            state_id,
+            None,
+            ts,
+            text,
+            text_hash,
+            quality_score,
+            source,
+            None,
+            None,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Add a text snapshot with full metadata including meeting and app context.
+    /// Snapshots close to the previous one for the same app/window are stored as
+    /// a line patch against it (see `reconstruct_snapshot`).
+    pub async fn add_text_snapshot_full(
+        &self,
+        snapshot_id: &str,
+        episode_id: Option<&str>,
+        state_id: Option<&str>,
+        meeting_id: Option<&str>,
+        ts: DateTime<Utc>,
+        text: &str,
+        text_hash: &str,
+        quality_score: f32,
+        source: &str,
+        app_name: Option<&str>,
+        window_title: Option<&str>,
+    ) -> Result<SnapshotWrite, sqlx::Error> {
+        let word_count = text.split_whitespace().count() as i32;
+
+        // Only standalone captures are delta-stored: snapshots tied to a screen
+        // state or episode can be cascade-deleted with it, which would orphan
+        // any patches built on top of them
+        let delta = if episode_id.is_none() && state_id.is_none() && !text.is_empty() {
+            // A broken chain just means this one is stored in full
+            self.delta_against_previous(meeting_id, app_name, window_title, text)
+                .await
+                .unwrap_or_else(|e| {
+                    log::warn!("Storing text snapshot in full: {}", e);
+                    None
+                })
+        } else {
+            None
+        };
+
+        let mut write = SnapshotWrite {
+            delta: delta.is_some(),
+            text_bytes: text.len() as u64,
+            stored_bytes: text.len() as u64,
+        };
+        let (stored_text, base_snapshot_id, patch, chain_length) = match &delta {
+            Some((base_id, patch, base_chain)) => {
+                write.stored_bytes = patch.len() as u64;
+                (
+                    "",
+                    Some(base_id.as_str()),
+                    Some(patch.as_str()),
+                    base_chain + 1,
+                )
+            }
+            None => (text, None, None, 0),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO text_snapshots 
+            (snapshot_id, episode_id, state_id, meeting_id, ts, text, text_hash, quality_score, source, word_count, app_name, window_title,
+             base_snapshot_id, patch, chain_length)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(snapshot_id)
+        .bind(episode_id)
+        .bind(state_id)
+        .bind(meeting_id)
+        .bind(ts.to_rfc3339())
+        .bind(stored_text)
+        .bind(text_hash)
+        .bind(quality_score)
+        .bind(source)
+        .bind(word_count)
+        .bind(app_name)
+        .bind(window_title)
+        .bind(base_snapshot_id)
+        .bind(patch)
+        .bind(chain_length)
+        .execute(&self.pool)
+        .await?;
+
+        // Full rows are indexed by trigger; deltas have no text column to index
+        if write.delta {
+            self.index_snapshot_text(snapshot_id, text).await?;
+        }
+
+        Ok(write)
+    }
+
+    /// Add a snapshot's full text to the search index
+    async fn index_snapshot_text(&self, snapshot_id: &str, text: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO text_snapshots_fts(rowid, text, meeting_id)
+            SELECT rowid, ?, meeting_id FROM text_snapshots WHERE snapshot_id = ?
+            "#,
+        )
+        .bind(text)
+        .bind(snapshot_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Line patch from the latest standalone snapshot for the same
+    /// meeting/app/window to `text`, if the two are similar enough and the
+    /// patch is actually smaller. Returns (base snapshot id, patch JSON, base chain length).
+    async fn delta_against_previous(
+        &self,
+        meeting_id: Option<&str>,
+        app_name: Option<&str>,
+        window_title: Option<&str>,
+        text: &str,
+    ) -> Result<Option<(String, String, i64)>, sqlx::Error> {
+        use crate::diff_builder::{DiffBuilder, SNAPSHOT_DELTA_MIN_SIMILARITY};
+
+        let previous: Option<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT snapshot_id, COALESCE(chain_length, 0)
+            FROM text_snapshots
+            WHERE meeting_id IS ? AND app_name IS ? AND window_title IS ?
+              AND episode_id IS NULL AND state_id IS NULL
+            ORDER BY ts DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(meeting_id)
+        .bind(app_name)
+        .bind(window_title)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some((base_id, base_chain)) = previous else {
+            return Ok(None);
+        };
+        let base_text = self.reconstruct_snapshot(&base_id).await?;
+
+        let Some(patch) = DiffBuilder::new().line_patch(&base_text, text) else {
+            return Ok(None);
+        };
+        if patch.similarity() < SNAPSHOT_DELTA_MIN_SIMILARITY {
+            return Ok(None);
+        }
+        let patch_json = serde_json::to_string(&patch)
+            .map_err(|e| sqlx::Error::Decode(format!("Failed to encode patch: {}", e).into()))?;
+        if patch_json.len() >= text.len() {
+            return Ok(None);
+        }
+
+        Ok(Some((base_id, patch_json, base_chain)))
+    }
+
+    /// Full text of a snapshot, applying its patch chain back to the nearest
+    /// fully-stored snapshot
+    pub async fn reconstruct_snapshot(&self, snapshot_id: &str) -> Result<String, sqlx::Error> {
+        // Walk base_snapshot_id links in one query, stopping at the first full copy
+        let chain: Vec<(String, Option<String>)> = sqlx::query_as(
+            r#"
+            WITH RECURSIVE chain(snapshot_id, base_snapshot_id, text, patch, depth) AS (
+                SELECT snapshot_id, base_snapshot_id, text, patch, 0
+                FROM text_snapshots WHERE snapshot_id = ?
+                UNION ALL
+                SELECT t.snapshot_id, t.base_snapshot_id, t.text, t.patch, chain.depth + 1
+                FROM text_snapshots t
+                JOIN chain ON t.snapshot_id = chain.base_snapshot_id
+                WHERE chain.patch IS NOT NULL
+            )
+            SELECT text, patch FROM chain ORDER BY depth DESC
+            "#,
+        )
+        .bind(snapshot_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut links = chain.into_iter();
+        let Some((mut text, None)) = links.next() else {
+            return Err(sqlx::Error::Decode(
+                format!("Snapshot {} has no full-text base", snapshot_id).into(),
+            ));
+        };
+        for (_, patch) in links {
+            if let Some(patch) = patch {
+                text = apply_snapshot_patch(&text, &patch)?;
+            }
+        }
+        Ok(text)
+    }
+
+    /// Store long-chained snapshots in full again so reads stay cheap.
+    /// Returns the number of snapshots re-baselined.
+    pub async fn compact_snapshot_chains(&self, max_chain: i64) -> Result<usize, sqlx::Error> {
+        let candidates: Vec<(String,)> = sqlx::query_as(
+            "SELECT snapshot_id FROM text_snapshots WHERE patch IS NOT NULL AND chain_length > ? ORDER BY ts ASC",
+        )
+        .bind(max_chain)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut rebaselined = 0;
+        for (snapshot_id,) in candidates {
+            // An earlier re-baseline in this pass may already have shortened the chain
+            let chain_length: Option<(i64,)> =
+                sqlx::query_as("SELECT chain_length FROM text_snapshots WHERE snapshot_id = ?")
+                    .bind(&snapshot_id)
+                    .fetch_optional(&self.pool)
+                    .await?;
+            if !chain_length.is_some_and(|(len,)| len > max_chain) {
+                continue;
+            }
+
+            let text = self.reconstruct_snapshot(&snapshot_id).await?;
+            sqlx::query(
+                "UPDATE text_snapshots SET text = ?, patch = NULL, base_snapshot_id = NULL, chain_length = 0 WHERE snapshot_id = ?",
+            )
+            .bind(&text)
+            .bind(&snapshot_id)
+            .execute(&self.pool)
+            .await?;
+
+            // Renumber everything patched on top of it (chain_length is only a
+            // hint for compaction - reconstruction follows the links themselves)
+            sqlx::query(
+                r#"
+                WITH RECURSIVE descendants(snapshot_id, depth) AS (
+                    SELECT snapshot_id, 1 FROM text_snapshots WHERE base_snapshot_id = ?
+                    UNION ALL
+                    SELECT t.snapshot_id, descendants.depth + 1
+                    FROM text_snapshots t
+                    JOIN descendants ON t.base_snapshot_id = descendants.snapshot_id
+                )
+                UPDATE text_snapshots
+                SET chain_length = (
+                    SELECT depth FROM descendants
+                    WHERE descendants.snapshot_id = text_snapshots.snapshot_id
+                )
+                WHERE snapshot_id IN (SELECT snapshot_id FROM descendants)
+                "#,
+            )
+            .bind(&snapshot_id)
+            .execute(&self.pool)
+            .await?;
+
+            rebaselined += 1;
+        }
+
+        Ok(rebaselined)
+    }
+
+    /// Get latest snapshot for an episode
+    pub async fn get_latest_snapshot(
+        &self,
+        episode_id: &str,
+    ) -> Result<Option<TextSnapshotRecord>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT snapshot_id, episode_id, state_id, meeting_id, ts, text, text_hash, 
+                   quality_score, source, word_count, app_name, window_title, patch
+            FROM text_snapshots 
+            WHERE episode_id = ?
+            ORDER BY ts DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(episode_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(r) = row else {
+            return Ok(None);
+        };
+        let text = match r.try_get::<Option<String>, _>("patch").ok().flatten() {
+            Some(_) => {
+                self.reconstruct_snapshot(&r.get::<String, _>("snapshot_id"))
+                    .await?
+            }
+            None => r.get("text"),
+        };
+
+        Ok(Some(TextSnapshotRecord {
+            snapshot_id: r.get("snapshot_id"),
+            episode_id: r.try_get("episode_id").ok(),
+            state_id: r.try_get("state_id").ok(),
+            meeting_id: r.try_get("meeting_id").ok(),
+            ts: r.get("ts"),
+            text,
+            text_hash: r.get("text_hash"),
+            quality_score: r.try_get("quality_score").unwrap_or(0.0),
+            source: r.try_get("source").unwrap_or_else(|_| "ocr".to_string()),
+            word_count: r.try_get("word_count").unwrap_or(0),
+            app_name: r.try_get("app_name").ok(),
+            window_title: r.try_get("window_title").ok(),
+        }))
+    }
+
+    /// Get all text snapshots for a meeting (ordered by timestamp).
+    /// Delta-stored snapshots come back with their full reconstructed text.
+    pub async fn get_text_snapshots_by_meeting(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<TextSnapshotRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT snapshot_id, episode_id, state_id, meeting_id, ts, text, text_hash, 
+                   quality_score, source, word_count, app_name, window_title,
+                   base_snapshot_id, patch
+            FROM text_snapshots 
+            WHERE meeting_id = ?
+            ORDER BY ts ASC
+            "#,
+        )
+        .bind(meeting_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        // Bases precede their patches in ts order, so most chains resolve
+        // from texts already rebuilt in this pass
+        let mut resolved: std::collections::HashMap<String, String> =
+            std::collections::HashMap::new();
+        let mut snapshots = Vec::with_capacity(rows.len());
+        for r in rows {
+            let snapshot_id: String = r.get("snapshot_id");
+            let patch: Option<String> = r.try_get("patch").ok().flatten();
+            let base_id: Option<String> = r.try_get("base_snapshot_id").ok().flatten();
+
+            let text = match (patch, base_id) {
+                (Some(patch), Some(base_id)) => match resolved.get(&base_id) {
+                    Some(base_text) => apply_snapshot_patch(base_text, &patch)?,
+                    None => self.reconstruct_snapshot(&snapshot_id).await?,
+                },
+                _ => r.get("text"),
+            };
+            resolved.insert(snapshot_id.clone(), text.clone());
+
+            snapshots.push(TextSnapshotRecord {
+                snapshot_id,
+                episode_id: r.try_get("episode_id").ok(),
+                state_id: r.try_get("state_id").ok(),
+                meeting_id: r.try_get("meeting_id").ok(),
+                ts: r.get("ts"),
+                text,
+                text_hash: r.get("text_hash"),
+                quality_score: r.try_get("quality_score").unwrap_or(0.0),
+                source: r.try_get("source").unwrap_or_else(|_| "ocr".to_string()),
+                word_count: r.try_get("word_count").unwrap_or(0),
+                app_name: r.try_get("app_name").ok(),
+                window_title: r.try_get("window_title").ok(),
+            });
+        }
+
+        Ok(snapshots)
+    }
+
+    /// Add a text patch (diff between snapshots)
+    pub async fn add_text_patch(
+        &self,
+        patch_id: &str,
+        episode_id: &str,
+        from_snapshot_id: Option<&str>,
+        to_snapshot_id: Option<&str>,
+        from_text_hash: &str,
+        to_text_hash: &str,
+        ts: DateTime<Utc>,
+        unified_diff: &str,
+        lines_added: i32,
+        lines_removed: i32,
+        change_summary: Option<&str>,
+        change_type: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO text_patches 
+            (patch_id, episode_id, from_snapshot_id, to_snapshot_id, from_text_hash, 
+             to_text_hash, ts, unified_diff, lines_added, lines_removed, change_summary, change_type)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(patch_id)
+        .bind(episode_id)
+        .bind(from_snapshot_id)
+        .bind(to_snapshot_id)
+        .bind(from_text_hash)
+        .bind(to_text_hash)
+        .bind(ts.to_rfc3339())
+        .bind(unified_diff)
+        .bind(lines_added)
+        .bind(lines_removed)
+        .bind(change_summary)
+        .bind(change_type)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get patches for an episode
+    pub async fn get_patches(&self, episode_id: &str) -> Result<Vec<TextPatchRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT patch_id, episode_id, from_snapshot_id, to_snapshot_id,
+                   from_text_hash, to_text_hash, ts, unified_diff,
+                   lines_added, lines_removed, change_summary, change_type
+            FROM text_patches 
+            WHERE episode_id = ?
+            ORDER BY ts ASC
+            "#,
+        )
+        .bind(episode_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| TextPatchRecord {
+                patch_id: r.get("patch_id"),
+                episode_id: r.get("episode_id"),
+                from_snapshot_id: r.try_get("from_snapshot_id").ok(),
+                to_snapshot_id: r.try_get("to_snapshot_id").ok(),
+                from_text_hash: r.get("from_text_hash"),
+                to_text_hash: r.get("to_text_hash"),
+                ts: r.get("ts"),
+                unified_diff: r.get("unified_diff"),
+                lines_added: r.try_get("lines_added").unwrap_or(0),
+                lines_removed: r.try_get("lines_removed").unwrap_or(0),
+                change_summary: r.try_get("change_summary").ok(),
+                change_type: r
+                    .try_get("change_type")
+                    .unwrap_or_else(|_| "content_changed".to_string()),
+            })
+            .collect())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // Phase 3: Timeline Events CRUD
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Add a timeline event
+    pub async fn add_timeline_event(
+        &self,
+        event_id: &str,
+        meeting_id: &str,
+        ts: DateTime<Utc>,
+        event_type: &str,
+        title: &str,
+        description: Option<&str>,
+        app_name: Option<&str>,
+        window_title: Option<&str>,
+        duration_ms: Option<i64>,
+        episode_id: Option<&str>,
+        state_id: Option<&str>,
+        topic: Option<&str>,
+        importance: f32,
+        importance_reason: Option<&str>,
+        category: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO meeting_timeline_events 
+            (event_id, meeting_id, ts, event_type, title, description, 
+             app_name, window_title, duration_ms, episode_id, state_id, topic, importance,
+             importance_reason, category)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(event_id)
+        .bind(meeting_id)
+        .bind(ts.to_rfc3339())
+        .bind(event_type)
+        .bind(title)
+        .bind(description)
+        .bind(app_name)
+        .bind(window_title)
+        .bind(duration_ms)
+        .bind(episode_id)
+        .bind(state_id)
+        .bind(topic)
+        .bind(importance)
+        .bind(importance_reason)
+        .bind(category)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get timeline events for a meeting, optionally restricted to one activity
+    /// category, a set of event types and a minimum importance. Events sharing
+    /// a timestamp keep the order they were saved in.
+    pub async fn get_timeline_events(
+        &self,
+        meeting_id: &str,
+        category: Option<&str>,
+        event_types: &[String],
+        min_importance: Option<f32>,
+    ) -> Result<Vec<TimelineEventRecord>, sqlx::Error> {
+        let type_filter = if event_types.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " AND event_type IN ({})",
+                vec!["?"; event_types.len()].join(", ")
+            )
+        };
+        let sql = format!(
+            r#"
+            SELECT event_id, meeting_id, ts, event_type, title, description,
+                   app_name, window_title, duration_ms, episode_id, state_id, topic, importance,
+                   importance_reason, category
+            FROM meeting_timeline_events
+            WHERE meeting_id = ? AND (? IS NULL OR category = ?)
+              AND (? IS NULL OR importance >= ?){}
+            ORDER BY ts ASC, rowid ASC
+            "#,
+            type_filter
+        );
+        let mut query = sqlx::query(&sql)
+            .bind(meeting_id)
+            .bind(category)
+            .bind(category)
+            .bind(min_importance)
+            .bind(min_importance);
+        for event_type in event_types {
+            query = query.bind(event_type);
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| TimelineEventRecord {
+                event_id: r.get("event_id"),
+                meeting_id: r.get("meeting_id"),
+                ts: r.get("ts"),
+                event_type: r.get("event_type"),
+                title: r.get("title"),
+                description: r.try_get("description").ok(),
+                app_name: r.try_get("app_name").ok(),
+                window_title: r.try_get("window_title").ok(),
+                duration_ms: r.try_get("duration_ms").ok(),
+                episode_id: r.try_get("episode_id").ok(),
+                state_id: r.try_get("state_id").ok(),
+                topic: r.try_get("topic").ok(),
+                importance: r.try_get("importance").unwrap_or(0.5),
+                importance_reason: r
+                    .try_get::<Option<String>, _>("importance_reason")
+                    .ok()
+                    .flatten(),
+                category: r.try_get::<Option<String>, _>("category").ok().flatten(),
+            })
+            .collect())
+    }
+
+    /// Add a topic cluster
+    pub async fn add_topic_cluster(
+        &self,
+        topic_id: &str,
+        meeting_id: &str,
+        name: &str,
+        description: Option<&str>,
+        start_ts: DateTime<Utc>,
+        end_ts: Option<DateTime<Utc>>,
+        event_count: i32,
+        total_duration_ms: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO topic_clusters 
+            (topic_id, meeting_id, name, description, start_ts, end_ts, event_count, total_duration_ms)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(topic_id)
+        .bind(meeting_id)
+        .bind(name)
+        .bind(description)
+        .bind(start_ts.to_rfc3339())
+        .bind(end_ts.map(|ts| ts.to_rfc3339()))
+        .bind(event_count)
+        .bind(total_duration_ms)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get topic clusters for a meeting
+    pub async fn get_topic_clusters(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<TopicClusterRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT topic_id, meeting_id, name, description, start_ts, end_ts,
+                   event_count, total_duration_ms, quality_score
+            FROM topic_clusters 
+            WHERE meeting_id = ?
+            ORDER BY start_ts ASC
+            "#,
+        )
+        .bind(meeting_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| TopicClusterRecord {
+                topic_id: r.get("topic_id"),
+                meeting_id: r.get("meeting_id"),
+                name: r.get("name"),
+                description: r.try_get("description").ok(),
+                start_ts: r.get("start_ts"),
+                end_ts: r.try_get("end_ts").ok(),
+                event_count: r.try_get("event_count").unwrap_or(0),
+                total_duration_ms: r.try_get("total_duration_ms").unwrap_or(0),
+                quality_score: r.try_get("quality_score").ok().flatten(),
+            })
+            .collect())
+    }
+
+    /// Swap all topic clusters of a meeting for a new set in one transaction
+    pub async fn replace_topic_clusters(
+        &self,
+        meeting_id: &str,
+        clusters: &[TopicClusterRecord],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM topic_clusters WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for cluster in clusters {
+            sqlx::query(
+                r#"
+                INSERT INTO topic_clusters
+                (topic_id, meeting_id, name, description, start_ts, end_ts, event_count,
+                 total_duration_ms, quality_score)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&cluster.topic_id)
+            .bind(meeting_id)
+            .bind(&cluster.name)
+            .bind(&cluster.description)
+            .bind(&cluster.start_ts)
+            .bind(&cluster.end_ts)
+            .bind(cluster.event_count)
+            .bind(cluster.total_duration_ms)
+            .bind(cluster.quality_score)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+}
+
+/// Screen state database record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenStateRecord {
+    pub state_id: String,
+    pub meeting_id: String,
+    pub start_ts: String,
+    pub end_ts: Option<String>,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+    pub phash: String,
+    pub delta_score: f32,
+    pub keyframe_path: Option<String>,
+    pub state_type: String,
+    /// Classifier confidence for `state_type` (None for states captured before classification)
+    pub category_confidence: Option<f32>,
+    pub flags: String,
+    /// Display the state was captured from (None = single-monitor capture)
+    pub monitor_id: Option<u32>,
+}
+
+/// Document episode database record (Phase 2)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentEpisodeRecord {
+    pub episode_id: String,
+    pub meeting_id: String,
+    pub start_ts: String,
+    pub end_ts: Option<String>,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+    pub document_fingerprint: Option<String>,
+    pub state_count: i32,
+    pub total_duration_ms: i64,
+    /// Activity category inherited from the episode's most confident state
+    pub category: Option<String>,
+    /// One-sentence AI summary (None until generated)
+    pub summary: Option<String>,
+    pub keywords: Vec<String>,
+}
+
+/// Text snapshot database record (Phase 2)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextSnapshotRecord {
+    pub snapshot_id: String,
+    pub episode_id: Option<String>,
+    pub state_id: Option<String>,
+    pub meeting_id: Option<String>,
+    pub ts: String,
+    pub text: String,
+    pub text_hash: String,
+    pub quality_score: f32,
+    pub source: String,
+    pub word_count: i32,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+}
+
+fn episode_from_row(r: &sqlx::sqlite::SqliteRow) -> DocumentEpisodeRecord {
+    DocumentEpisodeRecord {
+        episode_id: r.get("episode_id"),
+        meeting_id: r.get("meeting_id"),
+        start_ts: r.get("start_ts"),
+        end_ts: r.try_get("end_ts").ok(),
+        app_name: r.try_get("app_name").ok(),
+        window_title: r.try_get("window_title").ok(),
+        document_fingerprint: r.try_get("document_fingerprint").ok(),
+        state_count: r.try_get("state_count").unwrap_or(0),
+        total_duration_ms: r.try_get("total_duration_ms").unwrap_or(0),
+        category: r.try_get::<Option<String>, _>("category").ok().flatten(),
+        summary: r.try_get::<Option<String>, _>("summary").ok().flatten(),
+        keywords: r
+            .try_get::<Option<String>, _>("keywords_json")
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+    }
+}
+
+fn stored_conversation_from_row(r: &sqlx::sqlite::SqliteRow) -> StoredConversation {
+    StoredConversation {
+        id: r.get("id"),
+        conversation_id: r.get("conversation_id"),
+        timestamp: r.get("timestamp"),
+        user_query: r.get("user_query"),
+        assistant_response: r.get("assistant_response"),
+        model_used: r.get::<Option<String>, _>("model_used").unwrap_or_default(),
+        context_refs: r
+            .get::<Option<String>, _>("context_refs")
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+    }
+}
+
+fn unsynced_activity_from_row(r: &sqlx::sqlite::SqliteRow) -> ActivityLogEntry {
+    ActivityLogEntry {
+        id: Some(r.get("id")),
+        start_time: DateTime::parse_from_rfc3339(&r.get::<String, _>("start_time"))
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+        end_time: r
+            .get::<Option<String>, _>("end_time")
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc)),
+        duration_seconds: r.get("duration_seconds"),
+        app_name: r.get("app_name"),
+        window_title: r.get("window_title"),
+        category: r.get("category"),
+        summary: r.get("summary"),
+        focus_area: r.get("focus_area"),
+        visible_files: r.get("visible_files"),
+        confidence: r.get("confidence"),
+        frame_ids: r.get("frame_ids"),
+        pinecone_id: r.get("pinecone_id"),
+        supabase_id: r.get("supabase_id"),
+        synced_at: None,
+        from_cache: r.try_get::<i32, _>("from_cache").unwrap_or(0) == 1,
+    }
+}
+
+fn unattached_note_from_row(r: &sqlx::sqlite::SqliteRow) -> UnattachedNote {
+    UnattachedNote {
+        id: r.get("id"),
+        text: r.get("summary"),
+        ts: DateTime::parse_from_rfc3339(&r.get::<String, _>("start_time"))
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+    }
+}
+
+fn audio_chunk_from_row(r: &sqlx::sqlite::SqliteRow) -> AudioChunk {
+    AudioChunk {
+        id: r.get("id"),
+        meeting_id: r.get("meeting_id"),
+        chunk_index: r.get("chunk_index"),
+        path: r.get("path"),
+        format: r.get("format"),
+        sample_rate: r.get::<i64, _>("sample_rate") as u32,
+        channels: r.get::<i64, _>("channels") as u16,
+        start_ts: DateTime::parse_from_rfc3339(&r.get::<String, _>("start_ts"))
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+        end_ts: r
+            .get::<Option<String>, _>("end_ts")
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc)),
+        size_bytes: r.get("size_bytes"),
+    }
+}
+
+fn retranscribe_job_from_row(r: &sqlx::sqlite::SqliteRow) -> RetranscribeJob {
+    let parse_ts = |column: &str| {
+        DateTime::parse_from_rfc3339(&r.get::<String, _>(column))
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now())
+    };
+    RetranscribeJob {
+        id: r.get("id"),
+        meeting_id: r.get("meeting_id"),
+        provider: r.get("provider"),
+        replace_existing: r.get::<i32, _>("replace_existing") == 1,
+        status: r.get("status"),
+        next_chunk: r.get("next_chunk"),
+        total_chunks: r.get("total_chunks"),
+        error: r.get("error"),
+        created_at: parse_ts("created_at"),
+        updated_at: parse_ts("updated_at"),
+    }
+}
+
+fn retranscribed_segment_from_row(r: &sqlx::sqlite::SqliteRow) -> RetranscribedSegment {
+    RetranscribedSegment {
+        id: r.get("id"),
+        job_id: r.get("job_id"),
+        chunk_position: r.get("chunk_position"),
+        text: r.get("text"),
+        speaker: r.get("speaker"),
+        timestamp: DateTime::parse_from_rfc3339(&r.get::<String, _>("timestamp"))
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+        confidence: r.get("confidence"),
+    }
+}
+
+/// How `add_text_snapshot_full` stored a snapshot
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct SnapshotWrite {
+    /// Stored as a line patch on the previous snapshot for the same app/window
+    pub delta: bool,
+    /// Size of the snapshot text
+    pub text_bytes: u64,
+    /// Bytes actually written (the patch JSON when `delta`)
+    pub stored_bytes: u64,
+}
+
+/// Apply a stored `LinePatch` (JSON) to its base text
+fn apply_snapshot_patch(base: &str, patch_json: &str) -> Result<String, sqlx::Error> {
+    let patch: crate::diff_builder::LinePatch = serde_json::from_str(patch_json)
+        .map_err(|e| sqlx::Error::Decode(format!("Invalid snapshot patch: {}", e).into()))?;
+    patch.apply(base).map_err(|e| sqlx::Error::Decode(e.into()))
+}
+
+/// Text patch database record (Phase 2)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextPatchRecord {
+    pub patch_id: String,
+    pub episode_id: String,
+    pub from_snapshot_id: Option<String>,
+    pub to_snapshot_id: Option<String>,
+    pub from_text_hash: String,
+    pub to_text_hash: String,
+    pub ts: String,
+    pub unified_diff: String,
+    pub lines_added: i32,
+    pub lines_removed: i32,
+    pub change_summary: Option<String>,
+    pub change_type: String,
+}
+
+/// Timeline event database record (Phase 3)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEventRecord {
+    pub event_id: String,
+    pub meeting_id: String,
+    pub ts: String,
+    pub event_type: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub episode_id: Option<String>,
+    pub state_id: Option<String>,
+    pub topic: Option<String>,
+    pub importance: f32,
+    /// Short explanation of the score ("7-minute document editing with 2 action items")
+    pub importance_reason: Option<String>,
+    pub category: Option<String>,
+}
+
+/// Topic cluster database record (Phase 3)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicClusterRecord {
+    pub topic_id: String,
+    pub meeting_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub start_ts: String,
+    pub end_ts: Option<String>,
+    pub event_count: i32,
+    pub total_duration_ms: i64,
+    /// Mean silhouette of semantic segmentation; None for heuristic topics
+    pub quality_score: Option<f64>,
+}
+
+/// Frame record (for rewind timeline)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Frame {
+    pub id: i64,
+    pub meeting_id: String,
+    pub frame_number: i64,
+    pub timestamp: DateTime<Utc>,
+    pub file_path: Option<String>,
+    pub ocr_text: Option<String>,
+}
+
+/// Chunk of archived meeting audio on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioChunk {
+    pub id: String,
+    pub meeting_id: String,
+    pub chunk_index: i64,
+    pub path: String,
+    /// "wav" or "aac"
+    pub format: String,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub start_ts: DateTime<Utc>,
+    /// None while the chunk is still being written
+    pub end_ts: Option<DateTime<Utc>>,
+    pub size_bytes: i64,
+}
+
+/// Re-transcription of a meeting's archived audio with another provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetranscribeJob {
+    pub id: String,
+    pub meeting_id: String,
+    /// Provider setting value ("deepgram", "google_stt", ...)
+    pub provider: String,
+    /// Overwrite the meeting's transcripts when finished (otherwise the
+    /// result is kept as a revision set alongside them)
+    pub replace_existing: bool,
+    /// "running", "completed" or "failed"
+    pub status: String,
+    /// Position of the next audio chunk to transcribe
+    pub next_chunk: i64,
+    pub total_chunks: i64,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Final segment produced by a re-transcription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetranscribedSegment {
+    pub id: i64,
+    pub job_id: String,
+    pub chunk_position: i64,
+    pub text: String,
+    pub speaker: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    pub confidence: f32,
+}
+
+/// What replacing a meeting's transcripts with a revision set changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RevisionSetApplied {
+    /// Existing rows rewritten in place (original archived in transcript_revisions)
+    pub updated: usize,
+    /// Rows added because the revision set has more segments
+    pub inserted: usize,
+    /// Rows removed because the revision set has fewer segments
+    pub removed_ids: Vec<i64>,
+}
+
+/// File that storage cleanup may delete (frame, screen state or audio chunk)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrunableKeyframe {
+    /// "frame", "screen_state" or "audio_chunk"
+    pub source: String,
+    /// frames.id, screen_states.state_id or audio_chunks.id
+    pub id: String,
+    pub meeting_id: String,
+    pub captured_at: DateTime<Utc>,
+    pub path: String,
+}
+
+/// Synced timeline data (frames + transcripts aligned by timestamp)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncedTimeline {
+    pub meeting_id: String,
+    pub meeting_title: String,
+    pub duration_seconds: i64,
+    pub frames: Vec<TimelineFrame>,
+    pub transcripts: Vec<TimelineTranscript>,
+    /// Displays with captured states, for the timeline's monitor filter
+    pub monitor_ids: Vec<u32>,
+}
+
+/// Frame on the timeline (simplified for UI)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineFrame {
+    pub id: String,
+    pub frame_number: i64,
+    pub timestamp_ms: i64, // Milliseconds from start of meeting
+    pub thumbnail_path: Option<String>,
+    pub monitor_id: Option<u32>,
+}
+
+/// Transcript on the timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineTranscript {
+    pub id: String,
+    pub timestamp_ms: i64, // Milliseconds from start of meeting
+    pub text: String,
+    pub speaker: Option<String>,
+    pub is_final: bool,
+    pub duration_seconds: f64,
+}
+
+impl DatabaseManager {
+    /// Get synced timeline for a meeting (frames + transcripts aligned)
+    /// Supports both legacy frames table and new screen_states table
+    /// `monitor_id` restricts screen states to one display
+    pub async fn get_synced_timeline(
+        &self,
+        meeting_id: &str,
+        monitor_id: Option<u32>,
+    ) -> Result<Option<SyncedTimeline>, sqlx::Error> {
+        eprintln!(
+            "🔍 get_synced_timeline CALLED with meeting_id: {}",
+            meeting_id
+        );
+
+        // Get meeting info
+        let meeting = match self.get_meeting(meeting_id).await? {
+            Some(m) => m,
+            None => {
+                eprintln!("❌ Meeting not found: {}", meeting_id);
+                return Ok(None);
+            }
+        };
+
+        let start_time = meeting.started_at;
+        let duration = meeting.duration_seconds.unwrap_or(0);
+
+        // Try to get frames from legacy frames table first
+        let legacy_frames = self.get_frames(meeting_id, 10000).await?;
+        eprintln!(
+            "📊 Legacy frames count: {} for meeting: {}",
+            legacy_frames.len(),
+            meeting_id
+        );
+
+        let mut monitor_ids: Vec<u32> = Vec::new();
+        let timeline_frames: Vec<TimelineFrame> = if !legacy_frames.is_empty() {
+            // Use legacy frames table
+            eprintln!("✅ Using {} legacy frames", legacy_frames.len());
+            legacy_frames
+                .into_iter()
+                .map(|f| {
+                    let ms = (f.timestamp - start_time).num_milliseconds();
+                    TimelineFrame {
+                        id: f.id.to_string(),
+                        frame_number: f.frame_number,
+                        timestamp_ms: ms.max(0),
+                        thumbnail_path: f.file_path,
+                        monitor_id: None,
+                    }
+                })
+                .collect()
+        } else {
+            // Fallback to screen_states table (stateful capture)
+            log::info!(
+                "No legacy frames for meeting {}, checking screen_states",
+                meeting_id
+            );
+            let screen_states = self.get_screen_states(meeting_id, 10000).await?;
+            log::info!(
+                "Found {} screen_states for meeting {}",
+                screen_states.len(),
+                meeting_id
+            );
+            monitor_ids = screen_states.iter().filter_map(|s| s.monitor_id).collect();
+            monitor_ids.sort_unstable();
+            monitor_ids.dedup();
+            let frames: Vec<TimelineFrame> = screen_states
+                .into_iter()
+                .filter(|s| monitor_id.is_none() || s.monitor_id == monitor_id)
+                .enumerate()
+                .filter(|(_, s)| s.keyframe_path.is_some()) // Only use states with keyframes
+                .map(|(idx, s)| {
+                    // Parse start_ts to calculate timestamp_ms
+                    let state_ts = DateTime::parse_from_rfc3339(&s.start_ts)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or(start_time);
+                    let ms = (state_ts - start_time).num_milliseconds();
+                    TimelineFrame {
+                        id: s.state_id,
+                        frame_number: idx as i64,
+                        timestamp_ms: ms.max(0),
+                        thumbnail_path: s.keyframe_path,
+                        monitor_id: s.monitor_id,
+                    }
+                })
+                .collect();
+            log::info!(
+                "Returning {} timeline frames from screen_states",
+                frames.len()
+            );
+            frames
+        };
+
+        // Get transcripts
+        let transcripts = self.get_transcripts(meeting_id).await?;
+        let timeline_transcripts: Vec<TimelineTranscript> = transcripts
+            .into_iter()
+            .map(|t| {
+                let ms = (t.timestamp - start_time).num_milliseconds();
+                TimelineTranscript {
+                    id: t.id.to_string(),
+                    timestamp_ms: ms.max(0),
+                    text: t.text,
+                    speaker: t.speaker,
+                    is_final: t.is_final,
+                    duration_seconds: 0.0, // TODO: Store actual duration from Deepgram
+                }
+            })
+            .collect();
+
+        Ok(Some(SyncedTimeline {
+            meeting_id: meeting_id.to_string(),
+            meeting_title: meeting.title,
+            duration_seconds: duration,
+            frames: timeline_frames,
+            transcripts: timeline_transcripts,
+            monitor_ids,
+        }))
+    }
+}
+
+// ============================================
+// Knowledge Base Data Structures
+// ============================================
+
+/// Frame queued for VLM analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameQueueItem {
+    pub id: i64,
+    pub frame_id: Option<i64>,
+    pub frame_path: String,
+    pub captured_at: DateTime<Utc>,
+    pub analyzed: bool,
+    pub synced: bool,
+    pub reanalyze: bool, // Requeued by a backfill; bypass the VLM cache
+}
+
+/// Activity log entry (from VLM analysis)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityLogEntry {
+    pub id: Option<i64>,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub duration_seconds: Option<i64>,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+    pub category: String,
+    pub summary: String,
+    pub focus_area: Option<String>,
+    pub visible_files: Option<String>,
+    pub confidence: Option<f32>,
+    pub frame_ids: Option<String>,
+    pub pinecone_id: Option<String>,
+    pub supabase_id: Option<String>,
+    pub synced_at: Option<DateTime<Utc>>,
+    /// Built from a cached VLM result (no API call)
+    #[serde(default)]
+    pub from_cache: bool,
+}
+
+/// Entity extracted from VLM analysis (Phase 3)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entity {
+    pub id: Option<i64>,
+    pub activity_id: i64,
+    pub entity_type: String, // "person", "company", "feature", "task", etc.
+    pub name: String,
+    pub metadata: Option<String>, // JSON
+    pub confidence: f32,
+    pub theme: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DatabaseManager {
+    // ============================================
+    // Entity Methods (Phase 3)
+    // ============================================
+
+    /// Add an extracted entity
+    pub async fn add_entity(
+        &self,
+        activity_id: i64,
+        entity_type: &str,
+        name: &str,
+        metadata: Option<&serde_json::Value>,
+        confidence: f32,
+        theme: Option<&str>,
+    ) -> Result<i64, sqlx::Error> {
+        let metadata_str = metadata.map(|v| v.to_string());
+
+        let result = sqlx::query(
+            "INSERT INTO entities (activity_id, entity_type, name, metadata, confidence, theme) 
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(activity_id)
+        .bind(entity_type)
+        .bind(name)
+        .bind(metadata_str)
+        .bind(confidence)
+        .bind(theme)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get entities for an activity
+    pub async fn get_entities(&self, activity_id: i64) -> Result<Vec<Entity>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, activity_id, entity_type, name, metadata, confidence, theme, created_at 
+             FROM entities WHERE activity_id = ?",
+        )
+        .bind(activity_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| Entity {
+                id: Some(r.get("id")),
+                activity_id: r.get("activity_id"),
+                entity_type: r.get("entity_type"),
+                name: r.get("name"),
+                metadata: r.get("metadata"),
+                confidence: r.get("confidence"),
+                theme: r.get("theme"),
+                created_at: DateTime::parse_from_rfc3339(&r.get::<String, _>("created_at"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            })
+            .collect())
+    }
+
+    /// Get recent raw entity mentions, with the canonical entity each was
+    /// resolved to (see `entity_resolution`)
+    pub async fn get_recent_entities(
+        &self,
+        limit: i32,
+    ) -> Result<Vec<serde_json::Value>, sqlx::Error> {
+        let sql = r#"
+            SELECT 
+                e.id, e.entity_type, e.name, e.confidence, e.activity_id,
+                e.theme, e.created_at, e.metadata,
+                a.app_name, a.window_title, a.start_time,
+                c.id AS canonical_id, c.name AS canonical_name
+            FROM entities e
+            JOIN activity_log a ON e.activity_id = a.id
+            LEFT JOIN canonical_entities c ON c.id = e.canonical_id
+            ORDER BY a.start_time DESC
+            LIMIT ?
+        "#;
+
+        let rows = sqlx::query(sql).bind(limit).fetch_all(&self.pool).await?;
+
+        let result = rows
+            .into_iter()
+            .map(|row| {
+                let id: i64 = row.get("id");
+                let activity_id: i64 = row.get("activity_id");
+                let entity_type: String = row.get("entity_type");
+                let name: String = row.get("name");
+                let confidence: f32 = row.get("confidence");
+                let theme: Option<String> = row.try_get("theme").ok();
+                let created_at: String = row.get("created_at");
+                let metadata_str: Option<String> = row.try_get("metadata").ok();
+                let app_name: Option<String> = row.try_get("app_name").ok();
+                let window_title: Option<String> = row.try_get("window_title").ok();
+                let start_time: String = row.get("start_time");
+                let canonical_id: Option<i64> = row.try_get("canonical_id").ok();
+                let canonical_name: Option<String> = row.try_get("canonical_name").ok();
+
+                // Parse metadata string to JSON object if present
+                let metadata_obj: Option<serde_json::Value> =
+                    metadata_str.and_then(|s| serde_json::from_str(&s).ok());
+
+                serde_json::json!({
+                    "id": id,
+                    "activity_id": activity_id,
+                    "entity_type": entity_type,
+                    "name": name,
+                    "metadata": metadata_obj,
+                    "confidence": confidence,
+                    "theme": theme,
+                    "created_at": created_at,
+                    "canonical_id": canonical_id,
+                    "canonical_name": canonical_name,
+                    "source": {
+                        "app_name": app_name,
+                        "window_title": window_title,
+                        "timestamp": start_time
+                    }
+                })
+            })
+            .collect();
+
+        Ok(result)
+    }
+
+    /// Add a frame to the analysis queue
+    pub async fn queue_frame(
+        &self,
+        frame_id: Option<i64>,
+        frame_path: &str,
+        captured_at: DateTime<Utc>,
+    ) -> Result<i64, sqlx::Error> {
+        let captured_str = captured_at.to_rfc3339();
+        let frame_path = frame_path.to_string();
+
+        self.writer
+            .write(move |conn| {
+                Box::pin(async move {
+                    let result = sqlx::query(
+                        "INSERT INTO frame_queue (frame_id, frame_path, captured_at) VALUES (?, ?, ?)",
+                    )
+                    .bind(frame_id)
+                    .bind(&frame_path)
+                    .bind(&captured_str)
+                    .execute(&mut *conn)
+                    .await?;
+
+                    Ok(result.last_insert_rowid())
+                })
+            })
+            .await
+    }
+
+    /// Get pending frames for analysis
+    pub async fn get_pending_frames(&self, limit: i32) -> Result<Vec<FrameQueueItem>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, frame_id, frame_path, captured_at, analyzed, synced, reanalyze
+             FROM frame_queue WHERE analyzed = 0 ORDER BY captured_at ASC LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| FrameQueueItem {
+                id: r.get("id"),
+                frame_id: r.get("frame_id"),
+                frame_path: r.get("frame_path"),
+                captured_at: DateTime::parse_from_rfc3339(&r.get::<String, _>("captured_at"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                analyzed: r.get::<i32, _>("analyzed") == 1,
+                synced: r.get::<i32, _>("synced") == 1,
+                reanalyze: r.get::<i32, _>("reanalyze") == 1,
+            })
+            .collect())
+    }
+
+    /// Pending frames requeued by a backfill
+    pub async fn count_reanalyze_pending(&self) -> Result<i64, sqlx::Error> {
+        let row: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM frame_queue WHERE analyzed = 0 AND reanalyze = 1")
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(row.0)
+    }
+
+    /// Mark frame as analyzed
+    pub async fn mark_frame_analyzed(&self, queue_id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE frame_queue SET analyzed = 1, reanalyze = 0 WHERE id = ?")
+            .bind(queue_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Mark frame as synced
+    pub async fn mark_frame_synced(&self, queue_id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE frame_queue SET synced = 1 WHERE id = ?")
+            .bind(queue_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    // ============================================
+    // Keyframe Retention Methods
+    // ============================================
+
+    /// Keyframes and finished audio chunks with files on disk and no retain
+    /// reason, oldest first. Files from `exclude_meeting` (the one being
+    /// recorded) are skipped.
+    pub async fn get_prunable_keyframes(
+        &self,
+        exclude_meeting: Option<&str>,
+    ) -> Result<Vec<PrunableKeyframe>, sqlx::Error> {
+        let exclude = exclude_meeting.unwrap_or("");
+        let rows = sqlx::query(
+            r#"SELECT 'frame' AS source, CAST(id AS TEXT) AS id, meeting_id,
+                      timestamp AS captured_at, file_path AS path
+               FROM frames
+               WHERE file_path IS NOT NULL AND file_path != ''
+                 AND retain_reason IS NULL AND meeting_id != ?
+               UNION ALL
+               SELECT 'screen_state' AS source, state_id AS id, meeting_id,
+                      start_ts AS captured_at, keyframe_path AS path
+               FROM screen_states
+               WHERE keyframe_path IS NOT NULL AND keyframe_path != ''
+                 AND retain_reason IS NULL AND meeting_id != ?
+               UNION ALL
+               SELECT 'audio_chunk' AS source, id, meeting_id,
+                      start_ts AS captured_at, path
+               FROM audio_chunks
+               WHERE end_ts IS NOT NULL AND retain_reason IS NULL AND meeting_id != ?
+               ORDER BY captured_at ASC"#,
+        )
+        .bind(exclude)
+        .bind(exclude)
+        .bind(exclude)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| PrunableKeyframe {
+                source: r.get("source"),
+                id: r.get("id"),
+                meeting_id: r.get("meeting_id"),
+                captured_at: DateTime::parse_from_rfc3339(&r.get::<String, _>("captured_at"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                path: r.get("path"),
+            })
+            .collect())
+    }
+
+    /// Protect keyframes (by file path) from storage cleanup
+    pub async fn retain_keyframes(
+        &self,
+        paths: &[String],
+        reason: &str,
+    ) -> Result<u64, sqlx::Error> {
+        let mut retained = 0;
+        let mut tx = self.pool.begin().await?;
+        for path in paths {
+            for sql in [
+                "UPDATE frames SET retain_reason = ? WHERE file_path = ? AND retain_reason IS NULL",
+                "UPDATE screen_states SET retain_reason = ? WHERE keyframe_path = ? AND retain_reason IS NULL",
+            ] {
+                retained += sqlx::query(sql)
+                    .bind(reason)
+                    .bind(path)
+                    .execute(&mut *tx)
+                    .await?
+                    .rows_affected();
+            }
+        }
+        tx.commit().await?;
+        Ok(retained)
+    }
+
+    /// Protect the keyframe shown at `timestamp` in a meeting (latest one at or before it)
+    pub async fn retain_keyframe_at(
+        &self,
+        meeting_id: &str,
+        timestamp: DateTime<Utc>,
+        reason: &str,
+    ) -> Result<u64, sqlx::Error> {
+        let row: Option<(String,)> = sqlx::query_as(
+            r#"SELECT keyframe_path FROM screen_states
+               WHERE meeting_id = ? AND start_ts <= ? AND keyframe_path IS NOT NULL AND keyframe_path != ''
+               ORDER BY start_ts DESC LIMIT 1"#,
+        )
+        .bind(meeting_id)
+        .bind(timestamp.to_rfc3339())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some((path,)) => self.retain_keyframes(&[path], reason).await,
+            None => Ok(0),
+        }
+    }
+
+    /// Delete rows for pruned files (frames, screen states, audio chunks and queued VLM work)
+    pub async fn delete_pruned_keyframes(
+        &self,
+        keyframes: &[PrunableKeyframe],
+    ) -> Result<u64, sqlx::Error> {
+        let mut deleted = 0;
+        let mut tx = self.pool.begin().await?;
+        for keyframe in keyframes {
+            let result = if keyframe.source == "frame" {
+                sqlx::query("DELETE FROM frames WHERE id = ? AND retain_reason IS NULL")
+                    .bind(keyframe.id.parse::<i64>().unwrap_or(-1))
+                    .execute(&mut *tx)
+                    .await?
+            } else if keyframe.source == "audio_chunk" {
+                sqlx::query("DELETE FROM audio_chunks WHERE id = ? AND retain_reason IS NULL")
+                    .bind(&keyframe.id)
+                    .execute(&mut *tx)
+                    .await?
+            } else {
+                sqlx::query(
+                    "DELETE FROM screen_states WHERE state_id = ? AND retain_reason IS NULL",
+                )
+                .bind(&keyframe.id)
+                .execute(&mut *tx)
+                .await?
+            };
+            deleted += result.rows_affected();
+
+            sqlx::query("DELETE FROM frame_queue WHERE frame_path = ?")
+                .bind(&keyframe.path)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(deleted)
+    }
+
+    // ============================================
+    // Audio Chunk Methods
+    // ============================================
+
+    /// Register an audio chunk when it is opened (end_ts stays NULL until finished)
+    pub async fn add_audio_chunk(&self, chunk: &AudioChunk) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"INSERT INTO audio_chunks
+               (id, meeting_id, chunk_index, path, format, sample_rate, channels,
+                start_ts, end_ts, size_bytes)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        )
+        .bind(&chunk.id)
+        .bind(&chunk.meeting_id)
+        .bind(chunk.chunk_index)
+        .bind(&chunk.path)
+        .bind(&chunk.format)
+        .bind(chunk.sample_rate as i64)
+        .bind(chunk.channels as i64)
+        .bind(chunk.start_ts.to_rfc3339())
+        .bind(chunk.end_ts.map(|t| t.to_rfc3339()))
+        .bind(chunk.size_bytes)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record a finished chunk (the path changes when it was transcoded)
+    pub async fn finish_audio_chunk(
+        &self,
+        id: &str,
+        path: &str,
+        format: &str,
+        end_ts: DateTime<Utc>,
+        size_bytes: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE audio_chunks SET path = ?, format = ?, end_ts = ?, size_bytes = ? WHERE id = ?",
+        )
+        .bind(path)
+        .bind(format)
+        .bind(end_ts.to_rfc3339())
+        .bind(size_bytes)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Audio chunks of a meeting in playback order
+    pub async fn get_audio_chunks(&self, meeting_id: &str) -> Result<Vec<AudioChunk>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"SELECT id, meeting_id, chunk_index, path, format, sample_rate, channels,
+                      start_ts, end_ts, size_bytes
+               FROM audio_chunks WHERE meeting_id = ? ORDER BY start_ts ASC"#,
+        )
+        .bind(meeting_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(audio_chunk_from_row).collect())
+    }
+
+    /// Latest audio chunk of a meeting that started at or before `ts`
+    pub async fn get_audio_chunk_at(
+        &self,
+        meeting_id: &str,
+        ts: DateTime<Utc>,
+    ) -> Result<Option<AudioChunk>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"SELECT id, meeting_id, chunk_index, path, format, sample_rate, channels,
+                      start_ts, end_ts, size_bytes
+               FROM audio_chunks WHERE meeting_id = ? AND start_ts <= ?
+               ORDER BY start_ts DESC LIMIT 1"#,
+        )
+        .bind(meeting_id)
+        .bind(ts.to_rfc3339())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(audio_chunk_from_row))
+    }
+
+    // ============================================
+    // Re-transcription Methods
+    // ============================================
+
+    pub async fn create_retranscribe_job(&self, job: &RetranscribeJob) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"INSERT INTO retranscribe_jobs
+               (id, meeting_id, provider, replace_existing, status, next_chunk,
+                total_chunks, error, created_at, updated_at)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        )
+        .bind(&job.id)
+        .bind(&job.meeting_id)
+        .bind(&job.provider)
+        .bind(job.replace_existing as i32)
+        .bind(&job.status)
+        .bind(job.next_chunk)
+        .bind(job.total_chunks)
+        .bind(&job.error)
+        .bind(job.created_at.to_rfc3339())
+        .bind(job.updated_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_retranscribe_job(
+        &self,
+        id: &str,
+    ) -> Result<Option<RetranscribeJob>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM retranscribe_jobs WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.as_ref().map(retranscribe_job_from_row))
+    }
+
+    /// Re-transcriptions of a meeting, newest first
+    pub async fn get_retranscribe_jobs(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<RetranscribeJob>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM retranscribe_jobs WHERE meeting_id = ? ORDER BY created_at DESC",
+        )
+        .bind(meeting_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(retranscribe_job_from_row).collect())
+    }
+
+    /// Jobs left running when the app quit
+    pub async fn get_running_retranscribe_jobs(&self) -> Result<Vec<RetranscribeJob>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM retranscribe_jobs WHERE status = 'running' ORDER BY created_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(retranscribe_job_from_row).collect())
+    }
+
+    /// Store one chunk's segments and advance the job past it. Segments from an
+    /// earlier, interrupted attempt at the same chunk are replaced.
+    pub async fn save_retranscribed_chunk(
+        &self,
+        job_id: &str,
+        meeting_id: &str,
+        chunk_position: i64,
+        segments: &[RetranscribedSegment],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM retranscribed_segments WHERE job_id = ? AND chunk_position = ?")
+            .bind(job_id)
+            .bind(chunk_position)
+            .execute(&mut *tx)
+            .await?;
+
+        for segment in segments {
+            sqlx::query(
+                r#"INSERT INTO retranscribed_segments
+                   (job_id, meeting_id, chunk_position, text, speaker, timestamp, confidence)
+                   VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+            )
+            .bind(job_id)
+            .bind(meeting_id)
+            .bind(chunk_position)
+            .bind(&segment.text)
+            .bind(&segment.speaker)
+            .bind(segment.timestamp.to_rfc3339())
+            .bind(segment.confidence)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query("UPDATE retranscribe_jobs SET next_chunk = ?, updated_at = ? WHERE id = ?")
+            .bind(chunk_position + 1)
+            .bind(Utc::now().to_rfc3339())
+            .bind(job_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn set_retranscribe_job_status(
+        &self,
+        id: &str,
+        status: &str,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE retranscribe_jobs SET status = ?, error = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(status)
+        .bind(error)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// A job's revision set in spoken order
+    pub async fn get_retranscribed_segments(
+        &self,
+        job_id: &str,
+    ) -> Result<Vec<RetranscribedSegment>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM retranscribed_segments WHERE job_id = ? ORDER BY timestamp ASC, id ASC",
+        )
+        .bind(job_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(retranscribed_segment_from_row).collect())
+    }
+
+    /// Replace a meeting's transcripts with a job's revision set. Existing rows
+    /// are rewritten in place, in spoken order, with the original text archived
+    /// in transcript_revisions under the job id. Surplus originals are deleted;
+    /// their text is archived against the last kept row so nothing is lost.
+    /// Returns None when the revision set is empty.
+    pub async fn apply_revision_set(
+        &self,
+        job_id: &str,
+        meeting_id: &str,
+    ) -> Result<Option<RevisionSetApplied>, sqlx::Error> {
+        let segments = self.get_retranscribed_segments(job_id).await?;
+        if segments.is_empty() {
+            return Ok(None);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let originals: Vec<(i64, String)> = sqlx::query_as(
+            "SELECT id, text FROM transcripts WHERE meeting_id = ? ORDER BY timestamp ASC, id ASC",
+        )
+        .bind(meeting_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let edited_at = Utc::now().to_rfc3339();
+        let mut applied = RevisionSetApplied::default();
+        let mut last_id = 0;
+
+        for (i, segment) in segments.iter().enumerate() {
+            // text_hash is cleared as in apply_transcript_edit; the row is re-pushed to Pinecone
+            if let Some((id, previous_text)) = originals.get(i) {
+                sqlx::query(
+                    r#"UPDATE transcripts
+                       SET text = ?, speaker = ?, timestamp = ?, confidence = ?, text_hash = NULL,
+                           needs_reindex = 1, needs_review = 0, after_reconnect = 0
+                       WHERE id = ?"#,
+                )
+                .bind(&segment.text)
+                .bind(&segment.speaker)
+                .bind(segment.timestamp.to_rfc3339())
+                .bind(segment.confidence)
+                .bind(*id)
+                .execute(&mut *tx)
+                .await?;
+
+                Self::archive_revision(
+                    &mut tx,
+                    *id,
+                    meeting_id,
+                    previous_text,
+                    &segment.text,
+                    &edited_at,
+                    job_id,
+                )
+                .await?;
+                applied.updated += 1;
+                last_id = *id;
+            } else {
+                let result = sqlx::query(
+                    r#"INSERT INTO transcripts
+                       (meeting_id, text, speaker, timestamp, is_final, confidence, needs_reindex)
+                       VALUES (?, ?, ?, ?, 1, ?, 1)"#,
+                )
+                .bind(meeting_id)
+                .bind(&segment.text)
+                .bind(&segment.speaker)
+                .bind(segment.timestamp.to_rfc3339())
+                .bind(segment.confidence)
+                .execute(&mut *tx)
+                .await?;
+                applied.inserted += 1;
+                last_id = result.last_insert_rowid();
+            }
+        }
+
+        for (id, previous_text) in originals.iter().skip(segments.len()) {
+            Self::archive_revision(
+                &mut tx,
+                last_id,
+                meeting_id,
+                previous_text,
+                "",
+                &edited_at,
+                job_id,
+            )
+            .await?;
+            sqlx::query("DELETE FROM transcripts WHERE id = ?")
+                .bind(*id)
+                .execute(&mut *tx)
+                .await?;
+            applied.removed_ids.push(*id);
+        }
+
+        tx.commit().await?;
+        Ok(Some(applied))
+    }
+
+    async fn archive_revision(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        transcript_id: i64,
+        meeting_id: &str,
+        previous_text: &str,
+        new_text: &str,
+        edited_at: &str,
+        revision_set: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO transcript_revisions (transcript_id, meeting_id, previous_text, new_text, edited_at, revision_set)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(transcript_id)
+        .bind(meeting_id)
+        .bind(previous_text)
+        .bind(new_text)
+        .bind(edited_at)
+        .bind(revision_set)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    // ============================================
+    // VLM Cache Methods
+    // ============================================
+
+    /// Closest cached VLM result within `max_distance` bits of `phash` (max 3),
+    /// as the stored ActivityContext JSON. Bumps the entry's hit count.
+    pub async fn find_vlm_cache(
+        &self,
+        phash: u64,
+        theme: &str,
+        max_distance: u32,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let bands = phash_bands(phash);
+        let rows: Vec<(i64, i64, String)> = sqlx::query_as(
+            "SELECT id, phash, context_json FROM vlm_cache
+             WHERE theme = ? AND (band0 = ? OR band1 = ? OR band2 = ? OR band3 = ?)",
+        )
+        .bind(theme)
+        .bind(bands[0])
+        .bind(bands[1])
+        .bind(bands[2])
+        .bind(bands[3])
+        .fetch_all(&self.pool)
+        .await?;
+
+        let best = rows
+            .into_iter()
+            .map(|(id, hash, json)| ((hash as u64 ^ phash).count_ones(), id, json))
+            .filter(|(distance, _, _)| *distance <= max_distance)
+            .min_by_key(|(distance, _, _)| *distance);
+
+        match best {
+            Some((_, id, json)) => {
+                sqlx::query(
+                    "UPDATE vlm_cache SET hit_count = hit_count + 1, last_used_at = ? WHERE id = ?",
+                )
+                .bind(Utc::now().to_rfc3339())
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+                Ok(Some(json))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Store a VLM result, evicting least-recently-used entries beyond `max_entries`
+    pub async fn put_vlm_cache(
+        &self,
+        phash: u64,
+        theme: &str,
+        context_json: &str,
+        max_entries: i64,
+    ) -> Result<(), sqlx::Error> {
+        let bands = phash_bands(phash);
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "INSERT INTO vlm_cache (phash, band0, band1, band2, band3, theme, context_json, created_at, last_used_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(phash as i64)
+        .bind(bands[0])
+        .bind(bands[1])
+        .bind(bands[2])
+        .bind(bands[3])
+        .bind(theme)
+        .bind(context_json)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            "DELETE FROM vlm_cache WHERE id NOT IN
+             (SELECT id FROM vlm_cache ORDER BY last_used_at DESC, id DESC LIMIT ?)",
+        )
+        .bind(max_entries)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Drop every cached VLM result, returning how many were removed
+    pub async fn clear_vlm_cache(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM vlm_cache")
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Number of cached VLM results
+    pub async fn count_vlm_cache(&self) -> Result<i64, sqlx::Error> {
+        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM vlm_cache")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.0)
+    }
+
+    /// Get unsynced frames count
+    pub async fn count_unsynced_frames(&self) -> Result<i64, sqlx::Error> {
+        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM frame_queue WHERE synced = 0")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.0)
+    }
+
+    // ============================================
+    // Activity Log Methods
+    // ============================================
+
+    /// Add an activity log entry
+    pub async fn add_activity(&self, activity: &ActivityLogEntry) -> Result<i64, sqlx::Error> {
+        let start_str = activity.start_time.to_rfc3339();
+        let end_str = activity.end_time.map(|dt| dt.to_rfc3339());
+        let activity = activity.clone();
+
+        self.writer
+            .write(move |conn| {
+                Box::pin(async move {
+                    let result = sqlx::query(
+                        r#"INSERT INTO activity_log 
+                           (start_time, end_time, duration_seconds, app_name, window_title, 
+                            category, summary, focus_area, visible_files, confidence, frame_ids, from_cache)
+                           VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+                    )
+                    .bind(&start_str)
+                    .bind(&end_str)
+                    .bind(activity.duration_seconds)
+                    .bind(&activity.app_name)
+                    .bind(&activity.window_title)
+                    .bind(&activity.category)
+                    .bind(&activity.summary)
+                    .bind(&activity.focus_area)
+                    .bind(&activity.visible_files)
+                    .bind(activity.confidence)
+                    .bind(&activity.frame_ids)
+                    .bind(activity.from_cache as i32)
+                    .execute(&mut *conn)
+                    .await?;
+
+                    Ok(result.last_insert_rowid())
+                })
+            })
+            .await
+    }
+
+    /// Get activities by time range
+    pub async fn get_activities(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ActivityLogEntry>, sqlx::Error> {
+        let start_str = start.to_rfc3339();
+        let end_str = end.to_rfc3339();
+
+        let rows = sqlx::query(
+            "SELECT * FROM activity_log WHERE start_time >= ? AND start_time <= ? ORDER BY start_time ASC"
+        )
+        .bind(&start_str)
+        .bind(&end_str)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| ActivityLogEntry {
+                id: Some(r.get("id")),
+                start_time: DateTime::parse_from_rfc3339(&r.get::<String, _>("start_time"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                end_time: r
+                    .get::<Option<String>, _>("end_time")
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                duration_seconds: r.get("duration_seconds"),
+                app_name: r.get("app_name"),
+                window_title: r.get("window_title"),
+                category: r.get("category"),
+                summary: r.get("summary"),
+                focus_area: r.get("focus_area"),
+                visible_files: r.get("visible_files"),
+                confidence: r.get("confidence"),
+                frame_ids: r.get("frame_ids"),
+                pinecone_id: r.get("pinecone_id"),
+                supabase_id: r.get("supabase_id"),
+                synced_at: r
+                    .get::<Option<String>, _>("synced_at")
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                from_cache: r.try_get::<i32, _>("from_cache").unwrap_or(0) == 1,
+            })
+            .collect())
+    }
+
+    /// Get unsynced activities
+    pub async fn get_unsynced_activities(
+        &self,
+        limit: i32,
+    ) -> Result<Vec<ActivityLogEntry>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM activity_log WHERE synced_at IS NULL ORDER BY start_time ASC LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(unsynced_activity_from_row).collect())
+    }
+
+    /// An activity by id, if it still needs syncing
+    pub async fn get_unsynced_activity(
+        &self,
+        activity_id: i64,
+    ) -> Result<Option<ActivityLogEntry>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM activity_log WHERE id = ? AND synced_at IS NULL")
+            .bind(activity_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.as_ref().map(unsynced_activity_from_row))
+    }
+
+    /// Quick notes not yet attached to a meeting, oldest first
+    pub async fn get_unattached_notes(&self) -> Result<Vec<UnattachedNote>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, start_time, summary FROM activity_log WHERE category = 'quick_note' ORDER BY start_time ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(unattached_note_from_row).collect())
+    }
+
+    /// One unattached quick note
+    pub async fn get_unattached_note(
+        &self,
+        note_id: i64,
+    ) -> Result<Option<UnattachedNote>, sqlx::Error> {
+        let row = sqlx::query(
+            "SELECT id, start_time, summary FROM activity_log WHERE id = ? AND category = 'quick_note'",
+        )
+        .bind(note_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.as_ref().map(unattached_note_from_row))
+    }
+
+    /// Drop a quick note from the activity log once it lives on a meeting
+    pub async fn delete_unattached_note(&self, note_id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM activity_log WHERE id = ? AND category = 'quick_note'")
+            .bind(note_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    // ============================================
+    // Cloud sync queue
+    // ============================================
+
+    /// Queue a sync job. Re-queuing an existing (even parked) job resets its
+    /// attempts and replaces the payload.
+    pub async fn enqueue_sync_job(
+        &self,
+        kind: &str,
+        item_key: &str,
+        payload_json: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO sync_jobs (kind, item_key, payload_json)
+            VALUES (?, ?, ?)
+            ON CONFLICT(kind, item_key) DO UPDATE SET
+                payload_json = excluded.payload_json,
+                attempts = 0,
+                last_error = NULL,
+                next_attempt_at = NULL,
+                parked_at = NULL
+        "#,
+        )
+        .bind(kind)
+        .bind(item_key)
+        .bind(payload_json)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Queue every unsynced activity that isn't queued yet. Returns rows added.
+    pub async fn enqueue_unsynced_activities(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO sync_jobs (kind, item_key)
+            SELECT 'activity', CAST(id AS TEXT) FROM activity_log WHERE synced_at IS NULL
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Jobs that are neither parked nor backing off, oldest first
+    pub async fn get_due_sync_jobs(&self, limit: i32) -> Result<Vec<SyncJob>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, kind, item_key, payload_json, attempts FROM sync_jobs
+            WHERE parked_at IS NULL AND (next_attempt_at IS NULL OR next_attempt_at <= ?)
+            ORDER BY id ASC LIMIT ?
+        "#,
+        )
+        .bind(Utc::now().to_rfc3339())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| SyncJob {
+                id: r.get("id"),
+                kind: r.get("kind"),
+                item_key: r.get("item_key"),
+                payload_json: r.get("payload_json"),
+                attempts: r.get("attempts"),
+            })
+            .collect())
+    }
+
+    /// Drop a finished job
+    pub async fn complete_sync_job(&self, id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM sync_jobs WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Record a failed attempt: retry after `next_attempt_at`, or park the job
+    /// when `next_attempt_at` is None
+    pub async fn fail_sync_job(
+        &self,
+        id: i64,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        let parked_at = match next_attempt_at {
+            Some(_) => None,
+            None => Some(Utc::now().to_rfc3339()),
+        };
+        sqlx::query(
+            r#"
+            UPDATE sync_jobs
+            SET attempts = attempts + 1, last_error = ?, next_attempt_at = ?, parked_at = ?
+            WHERE id = ?
+        "#,
+        )
+        .bind(error)
+        .bind(next_attempt_at.map(|t| t.to_rfc3339()))
+        .bind(parked_at)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// (queued, parked) job counts
+    pub async fn count_sync_jobs(&self) -> Result<(i64, i64), sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                COALESCE(SUM(CASE WHEN parked_at IS NULL THEN 1 ELSE 0 END), 0) AS queued,
+                COALESCE(SUM(CASE WHEN parked_at IS NOT NULL THEN 1 ELSE 0 END), 0) AS parked
+            FROM sync_jobs
+        "#,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok((row.get("queued"), row.get("parked")))
+    }
+
+    /// Give parked jobs a fresh set of attempts. Returns how many were revived.
+    pub async fn unpark_sync_jobs(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE sync_jobs
+            SET attempts = 0, last_error = NULL, next_attempt_at = NULL, parked_at = NULL
+            WHERE parked_at IS NOT NULL
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Drop queued jobs of a kind, or just the one for `item_key`
+    pub async fn delete_sync_jobs(
+        &self,
+        kind: &str,
+        item_key: Option<&str>,
+    ) -> Result<u64, sqlx::Error> {
+        let result =
+            sqlx::query("DELETE FROM sync_jobs WHERE kind = ? AND (? IS NULL OR item_key = ?)")
+                .bind(kind)
+                .bind(item_key)
+                .bind(item_key)
+                .execute(&self.pool)
+                .await?;
+        Ok(result.rows_affected())
+    }
+
+    // ============================================
+    // Conversation History
+    // ============================================
+
+    /// Store (or replace) a chat exchange
+    pub async fn insert_conversation(
+        &self,
+        conversation: &StoredConversation,
+    ) -> Result<(), sqlx::Error> {
+        let context_refs =
+            serde_json::to_string(&conversation.context_refs).unwrap_or_else(|_| "[]".to_string());
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO conversations
+                (id, conversation_id, timestamp, user_query, assistant_response, model_used, context_refs)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+        )
+        .bind(&conversation.id)
+        .bind(&conversation.conversation_id)
+        .bind(&conversation.timestamp)
+        .bind(&conversation.user_query)
+        .bind(&conversation.assistant_response)
+        .bind(&conversation.model_used)
+        .bind(context_refs)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Most recent exchanges, newest first
+    pub async fn get_conversations(
+        &self,
+        limit: i32,
+    ) -> Result<Vec<StoredConversation>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, conversation_id, timestamp, user_query, assistant_response, model_used, context_refs
+            FROM conversations
+            ORDER BY timestamp DESC
+            LIMIT ?
+        "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(stored_conversation_from_row).collect())
+    }
+
+    /// Last `limit` exchanges of a chat thread, oldest first
+    pub async fn get_conversation_turns(
+        &self,
+        conversation_id: &str,
+        limit: i32,
+    ) -> Result<Vec<StoredConversation>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM (
+                SELECT id, conversation_id, timestamp, user_query, assistant_response, model_used, context_refs
+                FROM conversations
+                WHERE conversation_id = ?
+                ORDER BY timestamp DESC
+                LIMIT ?
+            ) ORDER BY timestamp ASC
+        "#,
+        )
+        .bind(conversation_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(stored_conversation_from_row).collect())
+    }
+
+    /// IDs of every stored exchange
+    pub async fn get_conversation_ids(&self) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT id FROM conversations")
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Delete one exchange. Returns rows removed.
+    pub async fn delete_conversation(&self, id: &str) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM conversations WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Delete every exchange. Returns rows removed.
+    pub async fn clear_conversations(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM conversations")
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Update activity with sync info
+    pub async fn mark_activity_synced(
+        &self,
+        activity_id: i64,
+        pinecone_id: Option<&str>,
+        supabase_id: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let synced_at = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "UPDATE activity_log SET pinecone_id = ?, supabase_id = ?, synced_at = ? WHERE id = ?",
+        )
+        .bind(pinecone_id)
+        .bind(supabase_id)
+        .bind(&synced_at)
+        .bind(activity_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get activity stats by category for a date
+    pub async fn get_activity_stats(&self, date: &str) -> Result<serde_json::Value, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"SELECT category, 
+                      COUNT(*) as count, 
+                      SUM(duration_seconds) as total_seconds
+               FROM activity_log 
+               WHERE DATE(start_time) = ?
+               GROUP BY category
+               ORDER BY total_seconds DESC"#,
+        )
+        .bind(date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut stats = serde_json::Map::new();
+        for row in rows {
+            let category: String = row.get("category");
+            let count: i64 = row.get("count");
+            let seconds: Option<i64> = row.get("total_seconds");
+            stats.insert(
+                category,
+                serde_json::json!({
+                    "count": count,
+                    "total_seconds": seconds.unwrap_or(0)
+                }),
+            );
+        }
+
+        Ok(serde_json::Value::Object(stats))
+    }
+
+    /// Get activities with flexible filtering (for search commands)
+    pub async fn get_activities_filtered(
+        &self,
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+        category: Option<&str>,
+        limit: i32,
+    ) -> Result<Vec<ActivityLogEntry>, sqlx::Error> {
+        // Build dynamic query based on provided filters
+        let mut conditions = Vec::new();
+        let mut query_str = String::from("SELECT * FROM activity_log WHERE 1=1");
+
+        if start_date.is_some() {
+            conditions.push("start_time >= ?");
+        }
+        if end_date.is_some() {
+            conditions.push("start_time <= ?");
+        }
+        if category.is_some() {
+            conditions.push("category = ?");
+        }
+
+        for cond in &conditions {
+            query_str.push_str(" AND ");
+            query_str.push_str(cond);
+        }
+        query_str.push_str(" ORDER BY start_time DESC LIMIT ?");
+
+        // Build query with bindings
+        let mut query = sqlx::query(&query_str);
+
+        if let Some(start) = start_date {
+            query = query.bind(start);
+        }
+        if let Some(end) = end_date {
+            query = query.bind(end);
+        }
+        if let Some(cat) = category {
+            query = query.bind(cat);
+        }
+        query = query.bind(limit);
+
+        let rows = query.fetch_all(&self.pool).await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| ActivityLogEntry {
+                id: Some(r.get("id")),
+                start_time: DateTime::parse_from_rfc3339(&r.get::<String, _>("start_time"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                end_time: r
+                    .get::<Option<String>, _>("end_time")
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                duration_seconds: r.get("duration_seconds"),
+                app_name: r.get("app_name"),
+                window_title: r.get("window_title"),
+                category: r.get("category"),
+                summary: r.get("summary"),
+                focus_area: r.get("focus_area"),
+                visible_files: r.get("visible_files"),
+                confidence: r.get("confidence"),
+                frame_ids: r.get("frame_ids"),
+                pinecone_id: r.get("pinecone_id"),
+                supabase_id: r.get("supabase_id"),
+                synced_at: r
+                    .get::<Option<String>, _>("synced_at")
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                from_cache: r.try_get::<i32, _>("from_cache").unwrap_or(0) == 1,
+            })
+            .collect())
+    }
+
+    /// Clear the frame queue (pending VLM analysis)
+    pub async fn clear_frame_queue(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM frame_queue")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Clear the activity log
+    pub async fn clear_activity_log(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM activity_log")
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("DELETE FROM activity_embeddings")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    // ============================================
+    // Theme Session Tracking
+    // ============================================
 
-        Ok(Some(SyncedTimeline {
-            meeting_id: meeting_id.to_string(),
-            meeting_title: meeting.title,
-            duration_seconds: duration,
-            frames: timeline_frames,
-            transcripts: timeline_transcripts,
-        }))
+    /// Start a new theme session
+    pub async fn start_theme_session(&self, theme: &str) -> Result<i64, sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query("INSERT INTO theme_sessions (theme, started_at) VALUES (?, ?)")
+            .bind(theme)
+            .bind(&now)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.last_insert_rowid())
     }
-}
 
-// ============================================
-// Knowledge Base Data Structures
-// ============================================
+    /// End the current theme session
+    pub async fn end_theme_session(&self, session_id: i64) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
 
-/// Frame queued for VLM analysis
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FrameQueueItem {
-    pub id: i64,
-    pub frame_id: Option<i64>,
-    pub frame_path: String,
-    pub captured_at: DateTime<Utc>,
-    pub analyzed: bool,
-    pub synced: bool,
-}
+        // Get start time to calculate duration
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT started_at FROM theme_sessions WHERE id = ? AND ended_at IS NULL",
+        )
+        .bind(session_id)
+        .fetch_optional(&self.pool)
+        .await?;
 
-/// Activity log entry (from VLM analysis)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ActivityLogEntry {
-    pub id: Option<i64>,
-    pub start_time: DateTime<Utc>,
-    pub end_time: Option<DateTime<Utc>>,
-    pub duration_seconds: Option<i64>,
-    pub app_name: Option<String>,
-    pub window_title: Option<String>,
-    pub category: String,
-    pub summary: String,
-    pub focus_area: Option<String>,
-    pub visible_files: Option<String>,
-    pub confidence: Option<f32>,
-    pub frame_ids: Option<String>,
-    pub pinecone_id: Option<String>,
-    pub supabase_id: Option<String>,
-    pub synced_at: Option<DateTime<Utc>>,
-}
+        if let Some((started_at_str,)) = row {
+            if let Ok(started_at) = DateTime::parse_from_rfc3339(&started_at_str) {
+                let duration = (now.timestamp() - started_at.timestamp()) as i32;
 
-/// Entity extracted from VLM analysis (Phase 3)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Entity {
-    pub id: Option<i64>,
-    pub activity_id: i64,
-    pub entity_type: String, // "person", "company", "feature", "task", etc.
-    pub name: String,
-    pub metadata: Option<String>, // JSON
-    pub confidence: f32,
-    pub theme: Option<String>,
-    pub created_at: DateTime<Utc>,
-}
+                sqlx::query(
+                    "UPDATE theme_sessions SET ended_at = ?, duration_seconds = ? WHERE id = ?",
+                )
+                .bind(now.to_rfc3339())
+                .bind(duration)
+                .bind(session_id)
+                .execute(&self.pool)
+                .await?;
+            }
+        }
 
-impl DatabaseManager {
-    // ============================================
-    // Entity Methods (Phase 3)
-    // ============================================
+        Ok(())
+    }
 
-    /// Add an extracted entity
-    pub async fn add_entity(
-        &self,
-        activity_id: i64,
-        entity_type: &str,
-        name: &str,
-        metadata: Option<&serde_json::Value>,
-        confidence: f32,
-        theme: Option<&str>,
-    ) -> Result<i64, sqlx::Error> {
-        let metadata_str = metadata.map(|v| v.to_string());
+    /// Get total time in a theme for today (in seconds)
+    pub async fn get_theme_time_today(&self, theme: &str) -> Result<i64, sqlx::Error> {
+        let today_start = Utc::now().date_naive().and_hms_opt(0, 0, 0).unwrap();
+        let today_start_str =
+            DateTime::<Utc>::from_naive_utc_and_offset(today_start, Utc).to_rfc3339();
 
-        let result = sqlx::query(
-            "INSERT INTO entities (activity_id, entity_type, name, metadata, confidence, theme) 
-             VALUES (?, ?, ?, ?, ?, ?)",
+        let row: (Option<i64>,) = sqlx::query_as(
+            "SELECT SUM(duration_seconds) FROM theme_sessions WHERE theme = ? AND started_at >= ? AND ended_at IS NOT NULL"
         )
-        .bind(activity_id)
-        .bind(entity_type)
-        .bind(name)
-        .bind(metadata_str)
-        .bind(confidence)
         .bind(theme)
-        .execute(&self.pool)
+        .bind(&today_start_str)
+        .fetch_one(&self.pool)
         .await?;
 
-        Ok(result.last_insert_rowid())
+        Ok(row.0.unwrap_or(0))
     }
 
-    /// Get entities for an activity
-    pub async fn get_entities(&self, activity_id: i64) -> Result<Vec<Entity>, sqlx::Error> {
-        let rows = sqlx::query(
-            "SELECT id, activity_id, entity_type, name, metadata, confidence, theme, created_at 
-             FROM entities WHERE activity_id = ?",
+    /// Get the last open session ID for cleanup
+    pub async fn get_last_open_session(&self) -> Result<Option<i64>, sqlx::Error> {
+        let row: Option<(i64,)> = sqlx::query_as(
+            "SELECT id FROM theme_sessions WHERE ended_at IS NULL ORDER BY started_at DESC LIMIT 1",
         )
-        .bind(activity_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| r.0))
+    }
+
+    /// Theme sessions overlapping [from, to), open ones included
+    pub async fn get_theme_sessions_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ThemeSessionRecord>, sqlx::Error> {
+        let rows: Vec<(i64, String, String, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT id, theme, started_at, ended_at FROM theme_sessions
+            WHERE started_at < ? AND (ended_at IS NULL OR ended_at > ?)
+            ORDER BY started_at ASC
+            "#,
+        )
+        .bind(to.to_rfc3339())
+        .bind(from.to_rfc3339())
         .fetch_all(&self.pool)
         .await?;
 
+        let parse = |s: &str| {
+            DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        };
         Ok(rows
             .into_iter()
-            .map(|r| Entity {
-                id: Some(r.get("id")),
-                activity_id: r.get("activity_id"),
-                entity_type: r.get("entity_type"),
-                name: r.get("name"),
-                metadata: r.get("metadata"),
-                confidence: r.get("confidence"),
-                theme: r.get("theme"),
-                created_at: DateTime::parse_from_rfc3339(&r.get::<String, _>("created_at"))
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
+            .filter_map(|(id, theme, started_at, ended_at)| {
+                Some(ThemeSessionRecord {
+                    id,
+                    theme,
+                    started_at: parse(&started_at)?,
+                    ended_at: ended_at.as_deref().and_then(parse),
+                })
             })
             .collect())
     }
 
-    /// Get recent extracted entities (filtered by theme/type)
-    pub async fn get_recent_entities(
+    /// Open sessions that a later session followed (left open by a crash),
+    /// as (id, started_at, next session's started_at)
+    pub async fn get_orphaned_theme_sessions(
         &self,
-        limit: i32,
-    ) -> Result<Vec<serde_json::Value>, sqlx::Error> {
-        let sql = r#"
-            SELECT 
-                e.id, e.entity_type, e.name, e.confidence, e.activity_id,
-                e.theme, e.created_at, e.metadata,
-                a.app_name, a.window_title, a.start_time
-            FROM entities e
-            JOIN activity_log a ON e.activity_id = a.id
-            ORDER BY a.start_time DESC
-            LIMIT ?
-        "#;
-
-        let rows = sqlx::query(sql).bind(limit).fetch_all(&self.pool).await?;
+    ) -> Result<Vec<(i64, DateTime<Utc>, DateTime<Utc>)>, sqlx::Error> {
+        let rows: Vec<(i64, String, String)> = sqlx::query_as(
+            r#"
+            SELECT s.id, s.started_at, MIN(n.started_at)
+            FROM theme_sessions s
+            JOIN theme_sessions n ON n.started_at > s.started_at
+            WHERE s.ended_at IS NULL
+            GROUP BY s.id, s.started_at
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-        let result = rows
+        let parse = |s: &str| {
+            DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        };
+        Ok(rows
             .into_iter()
-            .map(|row| {
-                let id: i64 = row.get("id");
-                let activity_id: i64 = row.get("activity_id");
-                let entity_type: String = row.get("entity_type");
-                let name: String = row.get("name");
-                let confidence: f32 = row.get("confidence");
-                let theme: Option<String> = row.try_get("theme").ok();
-                let created_at: String = row.get("created_at");
-                let metadata_str: Option<String> = row.try_get("metadata").ok();
-                let app_name: Option<String> = row.try_get("app_name").ok();
-                let window_title: Option<String> = row.try_get("window_title").ok();
-                let start_time: String = row.get("start_time");
+            .filter_map(|(id, started_at, next)| Some((id, parse(&started_at)?, parse(&next)?)))
+            .collect())
+    }
 
-                // Parse metadata string to JSON object if present
-                let metadata_obj: Option<serde_json::Value> =
-                    metadata_str.and_then(|s| serde_json::from_str(&s).ok());
+    /// Latest activity log entry or screen state seen in [after, before)
+    pub async fn get_last_activity_between(
+        &self,
+        after: DateTime<Utc>,
+        before: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        let row: (Option<String>,) = sqlx::query_as(
+            r#"
+            SELECT MAX(ts) FROM (
+                SELECT COALESCE(end_time, start_time) AS ts FROM activity_log
+                UNION ALL
+                SELECT COALESCE(end_ts, start_ts) AS ts FROM screen_states
+            )
+            WHERE ts >= ? AND ts < ?
+            "#,
+        )
+        .bind(after.to_rfc3339())
+        .bind(before.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await?;
 
-                serde_json::json!({
-                    "id": id,
-                    "activity_id": activity_id,
-                    "entity_type": entity_type,
-                    "name": name,
-                    "metadata": metadata_obj,
-                    "confidence": confidence,
-                    "theme": theme,
-                    "created_at": created_at,
-                    "source": {
-                        "app_name": app_name,
-                        "window_title": window_title,
-                        "timestamp": start_time
-                    }
-                })
-            })
-            .collect();
+        Ok(row
+            .0
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc)))
+    }
 
-        Ok(result)
+    /// Close an open theme session at a given time rather than now
+    pub async fn close_theme_session_at(
+        &self,
+        session_id: i64,
+        ended_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT started_at FROM theme_sessions WHERE id = ? AND ended_at IS NULL",
+        )
+        .bind(session_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        if let Some((started_at_str,)) = row {
+            if let Ok(started_at) = DateTime::parse_from_rfc3339(&started_at_str) {
+                let duration = (ended_at.timestamp() - started_at.timestamp()).max(0) as i32;
+
+                sqlx::query(
+                    "UPDATE theme_sessions SET ended_at = ?, duration_seconds = ? WHERE id = ?",
+                )
+                .bind(ended_at.to_rfc3339())
+                .bind(duration)
+                .bind(session_id)
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+
+        Ok(())
     }
 
-    /// Add a frame to the analysis queue
-    pub async fn queue_frame(
+    // ============================================
+    // Phase 3: Entity Methods
+    // ============================================
+
+    /// Insert an entity extracted from VLM analysis
+    pub async fn insert_entity(
         &self,
-        frame_id: Option<i64>,
-        frame_path: &str,
-        captured_at: DateTime<Utc>,
+        activity_id: i64,
+        entity_type: &str,
+        name: &str,
+        metadata: Option<&str>,
+        confidence: f32,
+        theme: Option<&str>,
     ) -> Result<i64, sqlx::Error> {
-        let captured_str = captured_at.to_rfc3339();
+        let now = Utc::now().to_rfc3339();
 
         let result = sqlx::query(
-            "INSERT INTO frame_queue (frame_id, frame_path, captured_at) VALUES (?, ?, ?)",
+            "INSERT INTO entities (activity_id, entity_type, name, metadata, confidence, theme, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
         )
-        .bind(frame_id)
-        .bind(frame_path)
-        .bind(&captured_str)
+        .bind(activity_id)
+        .bind(entity_type)
+        .bind(name)
+        .bind(metadata)
+        .bind(confidence)
+        .bind(theme)
+        .bind(&now)
         .execute(&self.pool)
         .await?;
 
         Ok(result.last_insert_rowid())
     }
 
-    /// Get pending frames for analysis
-    pub async fn get_pending_frames(&self, limit: i32) -> Result<Vec<FrameQueueItem>, sqlx::Error> {
-        let rows = sqlx::query(
-            "SELECT id, frame_id, frame_path, captured_at, analyzed, synced
-             FROM frame_queue WHERE analyzed = 0 ORDER BY captured_at ASC LIMIT ?",
-        )
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
+    /// List all entities with optional filters
+    pub async fn list_entities(
+        &self,
+        limit: Option<i32>,
+        entity_type: Option<&str>,
+        theme: Option<&str>,
+    ) -> Result<Vec<Entity>, sqlx::Error> {
+        let limit = limit.unwrap_or(100);
 
-        Ok(rows
-            .into_iter()
-            .map(|r| FrameQueueItem {
-                id: r.get("id"),
-                frame_id: r.get("frame_id"),
-                frame_path: r.get("frame_path"),
-                captured_at: DateTime::parse_from_rfc3339(&r.get::<String, _>("captured_at"))
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-                analyzed: r.get::<i32, _>("analyzed") == 1,
-                synced: r.get::<i32, _>("synced") == 1,
-            })
-            .collect())
-    }
+        let mut query = "SELECT id, activity_id, entity_type, name, metadata, confidence, theme, created_at FROM entities WHERE 1=1".to_string();
 
-    /// Mark frame as analyzed
-    pub async fn mark_frame_analyzed(&self, queue_id: i64) -> Result<(), sqlx::Error> {
-        sqlx::query("UPDATE frame_queue SET analyzed = 1 WHERE id = ?")
-            .bind(queue_id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
-    }
+        if entity_type.is_some() {
+            query.push_str(" AND entity_type = ?");
+        }
+        if theme.is_some() {
+            query.push_str(" AND theme = ?");
+        }
 
-    /// Mark frame as synced
-    pub async fn mark_frame_synced(&self, queue_id: i64) -> Result<(), sqlx::Error> {
-        sqlx::query("UPDATE frame_queue SET synced = 1 WHERE id = ?")
-            .bind(queue_id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
-    }
+        query.push_str(" ORDER BY created_at DESC LIMIT ?");
 
-    /// Get unsynced frames count
-    pub async fn count_unsynced_frames(&self) -> Result<i64, sqlx::Error> {
-        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM frame_queue WHERE synced = 0")
-            .fetch_one(&self.pool)
-            .await?;
-        Ok(row.0)
-    }
+        let mut query_builder = sqlx::query_as::<
+            _,
+            (
+                Option<i64>,
+                i64,
+                String,
+                String,
+                Option<String>,
+                f32,
+                Option<String>,
+                String,
+            ),
+        >(&query);
 
-    // ============================================
-    // Activity Log Methods
-    // ============================================
+        if let Some(et) = entity_type {
+            query_builder = query_builder.bind(et);
+        }
+        if let Some(t) = theme {
+            query_builder = query_builder.bind(t);
+        }
 
-    /// Add an activity log entry
-    pub async fn add_activity(&self, activity: &ActivityLogEntry) -> Result<i64, sqlx::Error> {
-        let start_str = activity.start_time.to_rfc3339();
-        let end_str = activity.end_time.map(|dt| dt.to_rfc3339());
+        query_builder = query_builder.bind(limit);
 
-        let result = sqlx::query(
-            r#"INSERT INTO activity_log 
-               (start_time, end_time, duration_seconds, app_name, window_title, 
-                category, summary, focus_area, visible_files, confidence, frame_ids)
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
-        )
-        .bind(&start_str)
-        .bind(&end_str)
-        .bind(&activity.duration_seconds)
-        .bind(&activity.app_name)
-        .bind(&activity.window_title)
-        .bind(&activity.category)
-        .bind(&activity.summary)
-        .bind(&activity.focus_area)
-        .bind(&activity.visible_files)
-        .bind(&activity.confidence)
-        .bind(&activity.frame_ids)
-        .execute(&self.pool)
-        .await?;
+        let rows = query_builder.fetch_all(&self.pool).await?;
 
-        Ok(result.last_insert_rowid())
+        let entities: Vec<Entity> = rows
+            .into_iter()
+            .map(
+                |(
+                    id,
+                    activity_id,
+                    entity_type,
+                    name,
+                    metadata,
+                    confidence,
+                    theme,
+                    created_at_str,
+                )| {
+                    Entity {
+                        id,
+                        activity_id,
+                        entity_type,
+                        name,
+                        metadata,
+                        confidence,
+                        theme,
+                        created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                            .map(|dt| dt.with_timezone(&Utc))
+                            .unwrap_or_else(|_| Utc::now()),
+                    }
+                },
+            )
+            .collect();
+
+        Ok(entities)
     }
 
-    /// Get activities by time range
-    pub async fn get_activities(
+    /// Get entities by type
+    pub async fn get_entities_by_type(
         &self,
-        start: DateTime<Utc>,
-        end: DateTime<Utc>,
-    ) -> Result<Vec<ActivityLogEntry>, sqlx::Error> {
-        let start_str = start.to_rfc3339();
-        let end_str = end.to_rfc3339();
+        entity_type: &str,
+        limit: i32,
+    ) -> Result<Vec<Entity>, sqlx::Error> {
+        self.list_entities(Some(limit), Some(entity_type), None)
+            .await
+    }
 
-        let rows = sqlx::query(
-            "SELECT * FROM activity_log WHERE start_time >= ? AND start_time <= ? ORDER BY start_time ASC"
+    /// Get entities for a specific activity
+    pub async fn get_entities_for_activity(
+        &self,
+        activity_id: i64,
+    ) -> Result<Vec<Entity>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, (Option<i64>, i64, String, String, Option<String>, f32, Option<String>, String)>(
+            "SELECT id, activity_id, entity_type, name, metadata, confidence, theme, created_at FROM entities WHERE activity_id = ? ORDER BY created_at DESC"
         )
-        .bind(&start_str)
-        .bind(&end_str)
+        .bind(activity_id)
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows
+        let entities: Vec<Entity> = rows
             .into_iter()
-            .map(|r| ActivityLogEntry {
-                id: Some(r.get("id")),
-                start_time: DateTime::parse_from_rfc3339(&r.get::<String, _>("start_time"))
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-                end_time: r
-                    .get::<Option<String>, _>("end_time")
-                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                    .map(|dt| dt.with_timezone(&Utc)),
-                duration_seconds: r.get("duration_seconds"),
-                app_name: r.get("app_name"),
-                window_title: r.get("window_title"),
-                category: r.get("category"),
-                summary: r.get("summary"),
-                focus_area: r.get("focus_area"),
-                visible_files: r.get("visible_files"),
-                confidence: r.get("confidence"),
-                frame_ids: r.get("frame_ids"),
-                pinecone_id: r.get("pinecone_id"),
-                supabase_id: r.get("supabase_id"),
-                synced_at: r
-                    .get::<Option<String>, _>("synced_at")
-                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                    .map(|dt| dt.with_timezone(&Utc)),
-            })
-            .collect())
+            .map(
+                |(
+                    id,
+                    activity_id,
+                    entity_type,
+                    name,
+                    metadata,
+                    confidence,
+                    theme,
+                    created_at_str,
+                )| {
+                    Entity {
+                        id,
+                        activity_id,
+                        entity_type,
+                        name,
+                        metadata,
+                        confidence,
+                        theme,
+                        created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                            .map(|dt| dt.with_timezone(&Utc))
+                            .unwrap_or_else(|_| Utc::now()),
+                    }
+                },
+            )
+            .collect();
+
+        Ok(entities)
     }
 
-    /// Get unsynced activities
-    pub async fn get_unsynced_activities(
+    // ============================================
+    // Meeting Intelligence System Methods
+    // ============================================
+
+    /// Save AI-generated meeting notes
+    pub async fn save_meeting_notes(
         &self,
-        limit: i32,
-    ) -> Result<Vec<ActivityLogEntry>, sqlx::Error> {
-        let rows = sqlx::query(
-            "SELECT * FROM activity_log WHERE synced_at IS NULL ORDER BY start_time ASC LIMIT ?",
+        id: &str,
+        meeting_id: &str,
+        summary: Option<&str>,
+        key_topics: Option<&str>,
+        decisions: Option<&str>,
+        action_items: Option<&str>,
+        participants: Option<&str>,
+        model_used: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO meeting_notes 
+            (id, meeting_id, summary, key_topics, decisions, action_items, participants, model_used, generated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, datetime('now'))
+            "#,
         )
-        .bind(limit)
-        .fetch_all(&self.pool)
+        .bind(id)
+        .bind(meeting_id)
+        .bind(summary)
+        .bind(key_topics)
+        .bind(decisions)
+        .bind(action_items)
+        .bind(participants)
+        .bind(model_used)
+        .execute(&self.pool)
         .await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|r| ActivityLogEntry {
-                id: Some(r.get("id")),
-                start_time: DateTime::parse_from_rfc3339(&r.get::<String, _>("start_time"))
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-                end_time: r
-                    .get::<Option<String>, _>("end_time")
-                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                    .map(|dt| dt.with_timezone(&Utc)),
-                duration_seconds: r.get("duration_seconds"),
-                app_name: r.get("app_name"),
-                window_title: r.get("window_title"),
-                category: r.get("category"),
-                summary: r.get("summary"),
-                focus_area: r.get("focus_area"),
-                visible_files: r.get("visible_files"),
-                confidence: r.get("confidence"),
-                frame_ids: r.get("frame_ids"),
-                pinecone_id: r.get("pinecone_id"),
-                supabase_id: r.get("supabase_id"),
-                synced_at: None,
-            })
-            .collect())
+        Ok(())
     }
 
-    /// Update activity with sync info
-    pub async fn mark_activity_synced(
+    /// Record how a notes row was generated and make it the active version
+    pub async fn save_meeting_notes_version(
         &self,
-        activity_id: i64,
-        pinecone_id: Option<&str>,
-        supabase_id: Option<&str>,
+        id: &str,
+        meeting_id: &str,
+        style: &str,
+        instructions: Option<&str>,
+        prompt: &str,
+        model_used: &str,
+        range_start_secs: Option<f64>,
+        range_end_secs: Option<f64>,
     ) -> Result<(), sqlx::Error> {
-        let synced_at = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
 
         sqlx::query(
-            "UPDATE activity_log SET pinecone_id = ?, supabase_id = ?, synced_at = ? WHERE id = ?",
+            r#"
+            INSERT INTO meeting_notes_versions
+            (id, meeting_id, version, style, instructions, prompt, model_used, range_start_secs, range_end_secs, created_at)
+            VALUES (?, ?, (SELECT COALESCE(MAX(version), 0) + 1 FROM meeting_notes_versions WHERE meeting_id = ?), ?, ?, ?, ?, ?, ?, ?)
+            "#,
         )
-        .bind(pinecone_id)
-        .bind(supabase_id)
-        .bind(&synced_at)
-        .bind(activity_id)
-        .execute(&self.pool)
+        .bind(id)
+        .bind(meeting_id)
+        .bind(meeting_id)
+        .bind(style)
+        .bind(instructions)
+        .bind(prompt)
+        .bind(model_used)
+        .bind(range_start_secs)
+        .bind(range_end_secs)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *tx)
         .await?;
 
+        sqlx::query("UPDATE meetings SET active_notes_id = ? WHERE id = ?")
+            .bind(id)
+            .bind(meeting_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
         Ok(())
     }
 
-    /// Get activity stats by category for a date
-    pub async fn get_activity_stats(&self, date: &str) -> Result<serde_json::Value, sqlx::Error> {
+    /// All notes generations for a meeting, newest first. Notes generated
+    /// before versioning show up with no version metadata.
+    pub async fn list_meeting_notes_versions(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<MeetingNotesVersion>, sqlx::Error> {
         let rows = sqlx::query(
-            r#"SELECT category, 
-                      COUNT(*) as count, 
-                      SUM(duration_seconds) as total_seconds
-               FROM activity_log 
-               WHERE DATE(start_time) = ?
-               GROUP BY category
-               ORDER BY total_seconds DESC"#,
+            r#"
+            SELECT n.id, n.meeting_id, n.summary, n.generated_at, n.model_used AS notes_model,
+                   v.version, v.style, v.instructions, v.prompt, v.model_used,
+                   v.range_start_secs, v.range_end_secs, v.created_at,
+                   n.id = m.active_notes_id AS is_active
+            FROM meeting_notes n
+            LEFT JOIN meeting_notes_versions v ON v.id = n.id
+            LEFT JOIN meetings m ON m.id = n.meeting_id
+            WHERE n.meeting_id = ?
+            ORDER BY COALESCE(v.created_at, n.generated_at) DESC
+            "#,
         )
-        .bind(date)
+        .bind(meeting_id)
         .fetch_all(&self.pool)
         .await?;
 
-        let mut stats = serde_json::Map::new();
-        for row in rows {
-            let category: String = row.get("category");
-            let count: i64 = row.get("count");
-            let seconds: Option<i64> = row.get("total_seconds");
-            stats.insert(
-                category,
-                serde_json::json!({
-                    "count": count,
-                    "total_seconds": seconds.unwrap_or(0)
-                }),
-            );
+        let mut versions: Vec<MeetingNotesVersion> = rows
+            .iter()
+            .map(|r| MeetingNotesVersion {
+                id: r.get("id"),
+                meeting_id: r.get("meeting_id"),
+                version: r.get("version"),
+                style: r.get("style"),
+                instructions: r.get("instructions"),
+                prompt: r.get("prompt"),
+                model_used: r
+                    .get::<Option<String>, _>("model_used")
+                    .or_else(|| r.get("notes_model")),
+                range_start_secs: r.get("range_start_secs"),
+                range_end_secs: r.get("range_end_secs"),
+                created_at: r
+                    .get::<Option<String>, _>("created_at")
+                    .unwrap_or_else(|| r.get("generated_at")),
+                summary: r.get("summary"),
+                is_active: r.get::<Option<bool>, _>("is_active").unwrap_or(false),
+            })
+            .collect();
+
+        // No explicit choice yet: the newest generation is what's shown
+        if !versions.iter().any(|v| v.is_active) {
+            if let Some(latest) = versions.first_mut() {
+                latest.is_active = true;
+            }
         }
 
-        Ok(serde_json::Value::Object(stats))
+        Ok(versions)
     }
 
-    /// Get activities with flexible filtering (for search commands)
-    pub async fn get_activities_filtered(
+    /// Make a notes version the one shown and exported. False if the version
+    /// doesn't belong to the meeting.
+    pub async fn set_active_notes_version(
         &self,
-        start_date: Option<&str>,
-        end_date: Option<&str>,
-        category: Option<&str>,
-        limit: i32,
-    ) -> Result<Vec<ActivityLogEntry>, sqlx::Error> {
-        // Build dynamic query based on provided filters
-        let mut conditions = Vec::new();
-        let mut query_str = String::from("SELECT * FROM activity_log WHERE 1=1");
+        meeting_id: &str,
+        version_id: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE meetings SET active_notes_id = ?
+            WHERE id = ? AND EXISTS (SELECT 1 FROM meeting_notes WHERE id = ? AND meeting_id = ?)
+            "#,
+        )
+        .bind(version_id)
+        .bind(meeting_id)
+        .bind(version_id)
+        .bind(meeting_id)
+        .execute(&self.pool)
+        .await?;
 
-        if start_date.is_some() {
-            conditions.push("start_time >= ?");
-        }
-        if end_date.is_some() {
-            conditions.push("start_time <= ?");
-        }
-        if category.is_some() {
-            conditions.push("category = ?");
-        }
+        Ok(result.rows_affected() > 0)
+    }
 
-        for cond in &conditions {
-            query_str.push_str(" AND ");
-            query_str.push_str(cond);
-        }
-        query_str.push_str(" ORDER BY start_time DESC LIMIT ?");
+    /// Insert an action item, or enrich the existing row with the same
+    /// normalized text. Returns true if a new row was created.
+    pub async fn upsert_action_item(
+        &self,
+        meeting_id: &str,
+        text: &str,
+        normalized_text: &str,
+        assignee: Option<&str>,
+        due_hint: Option<&str>,
+        source: &str,
+        source_transcript_id: Option<i64>,
+    ) -> Result<bool, sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+        let id = uuid::Uuid::new_v4().to_string();
 
-        // Build query with bindings
-        let mut query = sqlx::query(&query_str);
+        sqlx::query(
+            r#"
+            INSERT INTO action_items
+            (id, meeting_id, text, normalized_text, assignee, due_hint, status, source, source_transcript_id, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, 'open', ?, ?, ?, ?)
+            ON CONFLICT(meeting_id, normalized_text) DO UPDATE SET
+                assignee = COALESCE(action_items.assignee, excluded.assignee),
+                due_hint = COALESCE(action_items.due_hint, excluded.due_hint),
+                source_transcript_id = COALESCE(action_items.source_transcript_id, excluded.source_transcript_id),
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(&id)
+        .bind(meeting_id)
+        .bind(text)
+        .bind(normalized_text)
+        .bind(assignee)
+        .bind(due_hint)
+        .bind(source)
+        .bind(source_transcript_id)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await?;
 
-        if let Some(start) = start_date {
-            query = query.bind(start);
-        }
-        if let Some(end) = end_date {
-            query = query.bind(end);
-        }
-        if let Some(cat) = category {
-            query = query.bind(cat);
-        }
-        query = query.bind(limit);
+        let inserted: Option<String> =
+            sqlx::query_scalar("SELECT id FROM action_items WHERE id = ?")
+                .bind(&id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(inserted.is_some())
+    }
 
-        let rows = query.fetch_all(&self.pool).await?;
+    /// Action items for one meeting, or every open/in-progress item if None
+    pub async fn list_action_items(
+        &self,
+        meeting_id: Option<&str>,
+    ) -> Result<Vec<ActionItemRecord>, sqlx::Error> {
+        let rows = match meeting_id {
+            Some(meeting_id) => {
+                sqlx::query(
+                    "SELECT * FROM action_items WHERE meeting_id = ? ORDER BY created_at ASC",
+                )
+                .bind(meeting_id)
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query(
+                    "SELECT * FROM action_items WHERE status IN ('open', 'in_progress') ORDER BY created_at DESC",
+                )
+                .fetch_all(&self.pool)
+                .await?
+            }
+        };
 
         Ok(rows
-            .into_iter()
-            .map(|r| ActivityLogEntry {
-                id: Some(r.get("id")),
-                start_time: DateTime::parse_from_rfc3339(&r.get::<String, _>("start_time"))
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-                end_time: r
-                    .get::<Option<String>, _>("end_time")
-                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                    .map(|dt| dt.with_timezone(&Utc)),
-                duration_seconds: r.get("duration_seconds"),
-                app_name: r.get("app_name"),
-                window_title: r.get("window_title"),
-                category: r.get("category"),
-                summary: r.get("summary"),
-                focus_area: r.get("focus_area"),
-                visible_files: r.get("visible_files"),
-                confidence: r.get("confidence"),
-                frame_ids: r.get("frame_ids"),
-                pinecone_id: r.get("pinecone_id"),
-                supabase_id: r.get("supabase_id"),
-                synced_at: r
-                    .get::<Option<String>, _>("synced_at")
-                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                    .map(|dt| dt.with_timezone(&Utc)),
+            .iter()
+            .map(|r| ActionItemRecord {
+                id: r.get("id"),
+                meeting_id: r.get("meeting_id"),
+                text: r.get("text"),
+                assignee: r.get("assignee"),
+                due_hint: r.get("due_hint"),
+                status: r.get("status"),
+                source: r.get("source"),
+                source_transcript_id: r.get("source_transcript_id"),
+                calendar_event_id: r.try_get("calendar_event_id").unwrap_or(None),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
             })
             .collect())
     }
 
-    /// Clear the frame queue (pending VLM analysis)
-    pub async fn clear_frame_queue(&self) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM frame_queue")
-            .execute(&self.pool)
-            .await?;
-        Ok(())
-    }
-
-    /// Clear the activity log
-    pub async fn clear_activity_log(&self) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM activity_log")
-            .execute(&self.pool)
-            .await?;
-        Ok(())
-    }
-
-    // ============================================
-    // Theme Session Tracking
-    // ============================================
-
-    /// Start a new theme session
-    pub async fn start_theme_session(&self, theme: &str) -> Result<i64, sqlx::Error> {
-        let now = Utc::now().to_rfc3339();
-
-        let result = sqlx::query("INSERT INTO theme_sessions (theme, started_at) VALUES (?, ?)")
-            .bind(theme)
-            .bind(&now)
+    /// Set an action item's status. False if the id doesn't exist.
+    pub async fn update_action_item_status(
+        &self,
+        id: &str,
+        status: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE action_items SET status = ?, updated_at = ? WHERE id = ?")
+            .bind(status)
+            .bind(Utc::now().to_rfc3339())
+            .bind(id)
             .execute(&self.pool)
             .await?;
 
-        Ok(result.last_insert_rowid())
+        Ok(result.rows_affected() > 0)
     }
 
-    /// End the current theme session
-    pub async fn end_theme_session(&self, session_id: i64) -> Result<(), sqlx::Error> {
-        let now = Utc::now();
-
-        // Get start time to calculate duration
-        let row: Option<(String,)> = sqlx::query_as(
-            "SELECT started_at FROM theme_sessions WHERE id = ? AND ended_at IS NULL",
-        )
-        .bind(session_id)
-        .fetch_optional(&self.pool)
-        .await?;
-
-        if let Some((started_at_str,)) = row {
-            if let Ok(started_at) = DateTime::parse_from_rfc3339(&started_at_str) {
-                let duration = (now.timestamp() - started_at.timestamp()) as i32;
-
-                sqlx::query(
-                    "UPDATE theme_sessions SET ended_at = ?, duration_seconds = ? WHERE id = ?",
-                )
-                .bind(now.to_rfc3339())
-                .bind(duration)
-                .bind(session_id)
-                .execute(&self.pool)
-                .await?;
-            }
-        }
+    /// Remember the follow-up event created for an action item. False if
+    /// the item already has one (or doesn't exist).
+    pub async fn set_action_item_calendar_event(
+        &self,
+        id: &str,
+        calendar_event_id: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE action_items SET calendar_event_id = ?, updated_at = ? WHERE id = ? AND calendar_event_id IS NULL",
+        )
+        .bind(calendar_event_id)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
 
-        Ok(())
+        Ok(result.rows_affected() > 0)
     }
 
-    /// Get total time in a theme for today (in seconds)
-    pub async fn get_theme_time_today(&self, theme: &str) -> Result<i64, sqlx::Error> {
-        let today_start = Utc::now().date_naive().and_hms_opt(0, 0, 0).unwrap();
-        let today_start_str =
-            DateTime::<Utc>::from_naive_utc_and_offset(today_start, Utc).to_rfc3339();
+    /// Pin an insight; returns its id
+    pub async fn add_pinned_insight(
+        &self,
+        meeting_id: &str,
+        insight_type: &str,
+        text: &str,
+        timestamp_ms: i64,
+        transcript_id: Option<i64>,
+        created_by: &str,
+    ) -> Result<String, sqlx::Error> {
+        let id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            r#"
+            INSERT INTO pinned_insights
+            (id, meeting_id, insight_type, text, timestamp_ms, transcript_id, created_by, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&id)
+        .bind(meeting_id)
+        .bind(insight_type)
+        .bind(text)
+        .bind(timestamp_ms)
+        .bind(transcript_id)
+        .bind(created_by)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
 
-        let row: (Option<i64>,) = sqlx::query_as(
-            "SELECT SUM(duration_seconds) FROM theme_sessions WHERE theme = ? AND started_at >= ? AND ended_at IS NOT NULL"
+        Ok(id)
+    }
+
+    /// Pinned insights for a meeting in meeting order
+    pub async fn get_pinned_insights(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<PinnedInsight>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM pinned_insights WHERE meeting_id = ? ORDER BY timestamp_ms ASC",
         )
-        .bind(theme)
-        .bind(&today_start_str)
-        .fetch_one(&self.pool)
+        .bind(meeting_id)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(row.0.unwrap_or(0))
+        Ok(rows
+            .iter()
+            .map(|r| PinnedInsight {
+                id: r.get("id"),
+                meeting_id: r.get("meeting_id"),
+                insight_type: r.get("insight_type"),
+                text: r.get("text"),
+                timestamp_ms: r.get("timestamp_ms"),
+                transcript_id: r.get("transcript_id"),
+                created_by: r.get("created_by"),
+                created_at: r.get("created_at"),
+            })
+            .collect())
     }
 
-    /// Get the last open session ID for cleanup
-    pub async fn get_last_open_session(&self) -> Result<Option<i64>, sqlx::Error> {
-        let row: Option<(i64,)> = sqlx::query_as(
-            "SELECT id FROM theme_sessions WHERE ended_at IS NULL ORDER BY started_at DESC LIMIT 1",
+    /// Edit a pinned insight's text/type. False if it doesn't exist.
+    pub async fn update_pinned_insight(
+        &self,
+        id: &str,
+        text: &str,
+        insight_type: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE pinned_insights SET text = ?, insight_type = COALESCE(?, insight_type) WHERE id = ?",
         )
-        .fetch_optional(&self.pool)
+        .bind(text)
+        .bind(insight_type)
+        .bind(id)
+        .execute(&self.pool)
         .await?;
 
-        Ok(row.map(|r| r.0))
+        Ok(result.rows_affected() > 0)
     }
 
-    // ============================================
-    // Phase 3: Entity Methods
-    // ============================================
+    pub async fn delete_pinned_insight(&self, id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM pinned_insights WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
 
-    /// Insert an entity extracted from VLM analysis
-    pub async fn insert_entity(
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Store a live insight; returns its row id
+    pub async fn add_live_insight(
         &self,
-        activity_id: i64,
-        entity_type: &str,
-        name: &str,
-        metadata: Option<&str>,
-        confidence: f32,
-        theme: Option<&str>,
+        meeting_id: &str,
+        event: &crate::live_intel_agent::LiveInsightEvent,
     ) -> Result<i64, sqlx::Error> {
-        let now = Utc::now().to_rfc3339();
-
+        let payload = serde_json::to_string(event).unwrap_or_else(|_| "{}".to_string());
         let result = sqlx::query(
-            "INSERT INTO entities (activity_id, entity_type, name, metadata, confidence, theme, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+            r#"
+            INSERT INTO live_insights (meeting_id, insight_type, payload, timestamp_ms, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
         )
-        .bind(activity_id)
-        .bind(entity_type)
-        .bind(name)
-        .bind(metadata)
-        .bind(confidence)
-        .bind(theme)
-        .bind(&now)
+        .bind(meeting_id)
+        .bind(event.kind())
+        .bind(payload)
+        .bind(event.timestamp())
+        .bind(Utc::now().to_rfc3339())
         .execute(&self.pool)
         .await?;
 
         Ok(result.last_insert_rowid())
     }
 
-    /// List all entities with optional filters
-    pub async fn list_entities(
+    /// Live insights for a meeting with row id > `since_id`, oldest first.
+    /// Each event's `id` is its row id.
+    pub async fn get_live_insights(
         &self,
-        limit: Option<i32>,
-        entity_type: Option<&str>,
-        theme: Option<&str>,
-    ) -> Result<Vec<Entity>, sqlx::Error> {
-        let limit = limit.unwrap_or(100);
-
-        let mut query = "SELECT id, activity_id, entity_type, name, metadata, confidence, theme, created_at FROM entities WHERE 1=1".to_string();
-
-        if entity_type.is_some() {
-            query.push_str(" AND entity_type = ?");
-        }
-        if theme.is_some() {
-            query.push_str(" AND theme = ?");
-        }
-
-        query.push_str(" ORDER BY created_at DESC LIMIT ?");
-
-        let mut query_builder = sqlx::query_as::<
-            _,
-            (
-                Option<i64>,
-                i64,
-                String,
-                String,
-                Option<String>,
-                f32,
-                Option<String>,
-                String,
-            ),
-        >(&query);
-
-        if let Some(et) = entity_type {
-            query_builder = query_builder.bind(et);
-        }
-        if let Some(t) = theme {
-            query_builder = query_builder.bind(t);
-        }
-
-        query_builder = query_builder.bind(limit);
-
-        let rows = query_builder.fetch_all(&self.pool).await?;
+        meeting_id: &str,
+        since_id: i64,
+    ) -> Result<Vec<crate::live_intel_agent::LiveInsightEvent>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, payload FROM live_insights WHERE meeting_id = ? AND id > ? ORDER BY id ASC",
+        )
+        .bind(meeting_id)
+        .bind(since_id)
+        .fetch_all(&self.pool)
+        .await?;
 
-        let entities: Vec<Entity> = rows
-            .into_iter()
-            .map(
-                |(
-                    id,
-                    activity_id,
-                    entity_type,
-                    name,
-                    metadata,
-                    confidence,
-                    theme,
-                    created_at_str,
-                )| {
-                    Entity {
-                        id,
-                        activity_id,
-                        entity_type,
-                        name,
-                        metadata,
-                        confidence,
-                        theme,
-                        created_at: DateTime::parse_from_rfc3339(&created_at_str)
-                            .map(|dt| dt.with_timezone(&Utc))
-                            .unwrap_or_else(|_| Utc::now()),
+        Ok(rows
+            .iter()
+            .filter_map(|r| {
+                let id: i64 = r.get("id");
+                let payload: String = r.get("payload");
+                match serde_json::from_str::<crate::live_intel_agent::LiveInsightEvent>(&payload) {
+                    Ok(mut event) => {
+                        event.set_id(id.to_string());
+                        Some(event)
                     }
-                },
-            )
-            .collect();
-
-        Ok(entities)
+                    Err(e) => {
+                        log::warn!("Skipping unreadable live insight {}: {}", id, e);
+                        None
+                    }
+                }
+            })
+            .collect())
     }
 
-    /// Get entities by type
-    pub async fn get_entities_by_type(
+    /// Record a decision; returns its id
+    pub async fn add_decision(
         &self,
-        entity_type: &str,
-        limit: i32,
-    ) -> Result<Vec<Entity>, sqlx::Error> {
-        self.list_entities(Some(limit), Some(entity_type), None)
-            .await
+        meeting_id: &str,
+        text: &str,
+        context: Option<&str>,
+        timestamp_ms: i64,
+        transcript_id: Option<i64>,
+        created_by: &str,
+    ) -> Result<String, sqlx::Error> {
+        let id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            r#"
+            INSERT INTO decisions
+            (id, meeting_id, text, context, timestamp_ms, transcript_id, created_by, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&id)
+        .bind(meeting_id)
+        .bind(text)
+        .bind(context)
+        .bind(timestamp_ms)
+        .bind(transcript_id)
+        .bind(created_by)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(id)
     }
 
-    /// Get entities for a specific activity
-    pub async fn get_entities_for_activity(
+    /// Decisions for a meeting in meeting order
+    pub async fn get_decisions(
         &self,
-        activity_id: i64,
-    ) -> Result<Vec<Entity>, sqlx::Error> {
-        let rows = sqlx::query_as::<_, (Option<i64>, i64, String, String, Option<String>, f32, Option<String>, String)>(
-            "SELECT id, activity_id, entity_type, name, metadata, confidence, theme, created_at FROM entities WHERE activity_id = ? ORDER BY created_at DESC"
-        )
-        .bind(activity_id)
-        .fetch_all(&self.pool)
-        .await?;
+        meeting_id: &str,
+    ) -> Result<Vec<DecisionRecord>, sqlx::Error> {
+        let rows =
+            sqlx::query("SELECT * FROM decisions WHERE meeting_id = ? ORDER BY timestamp_ms ASC")
+                .bind(meeting_id)
+                .fetch_all(&self.pool)
+                .await?;
 
-        let entities: Vec<Entity> = rows
-            .into_iter()
-            .map(
-                |(
-                    id,
-                    activity_id,
-                    entity_type,
-                    name,
-                    metadata,
-                    confidence,
-                    theme,
-                    created_at_str,
-                )| {
-                    Entity {
-                        id,
-                        activity_id,
-                        entity_type,
-                        name,
-                        metadata,
-                        confidence,
-                        theme,
-                        created_at: DateTime::parse_from_rfc3339(&created_at_str)
-                            .map(|dt| dt.with_timezone(&Utc))
-                            .unwrap_or_else(|_| Utc::now()),
-                    }
-                },
-            )
-            .collect();
+        Ok(rows
+            .iter()
+            .map(|r| DecisionRecord {
+                id: r.get("id"),
+                meeting_id: r.get("meeting_id"),
+                text: r.get("text"),
+                context: r.get("context"),
+                timestamp_ms: r.get("timestamp_ms"),
+                transcript_id: r.get("transcript_id"),
+                created_by: r.get("created_by"),
+                created_at: r.get("created_at"),
+            })
+            .collect())
+    }
 
-        Ok(entities)
+    /// Edit a decision. False if it doesn't exist.
+    pub async fn update_decision(
+        &self,
+        id: &str,
+        text: &str,
+        context: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE decisions SET text = ?, context = ? WHERE id = ?")
+            .bind(text)
+            .bind(context)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
     }
 
-    // ============================================
-    // Meeting Intelligence System Methods
-    // ============================================
+    pub async fn delete_decision(&self, id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM decisions WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
 
-    /// Save AI-generated meeting notes
-    pub async fn save_meeting_notes(
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Remember the hash of a vault file the app just wrote
+    pub async fn record_vault_export(
         &self,
-        id: &str,
+        path: &str,
         meeting_id: &str,
-        summary: Option<&str>,
-        key_topics: Option<&str>,
-        decisions: Option<&str>,
-        action_items: Option<&str>,
-        participants: Option<&str>,
-        model_used: Option<&str>,
+        content_hash: &str,
     ) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
-            INSERT OR REPLACE INTO meeting_notes 
-            (id, meeting_id, summary, key_topics, decisions, action_items, participants, model_used, generated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, datetime('now'))
+            INSERT INTO vault_exports (path, meeting_id, content_hash, exported_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(path) DO UPDATE SET
+                meeting_id = excluded.meeting_id,
+                content_hash = excluded.content_hash,
+                exported_at = excluded.exported_at
             "#,
         )
-        .bind(id)
+        .bind(path)
         .bind(meeting_id)
-        .bind(summary)
-        .bind(key_topics)
-        .bind(decisions)
-        .bind(action_items)
-        .bind(participants)
-        .bind(model_used)
+        .bind(content_hash)
+        .bind(Utc::now().to_rfc3339())
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
 
-    /// Get meeting notes by meeting ID
+    /// Vault files previously written for a meeting
+    pub async fn get_vault_exports(
+        &self,
+        meeting_id: Option<&str>,
+    ) -> Result<Vec<VaultExportRecord>, sqlx::Error> {
+        let rows = match meeting_id {
+            Some(id) => {
+                sqlx::query(
+                    "SELECT path, meeting_id, content_hash, exported_at FROM vault_exports WHERE meeting_id = ? ORDER BY path",
+                )
+                .bind(id)
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query(
+                    "SELECT path, meeting_id, content_hash, exported_at FROM vault_exports ORDER BY exported_at DESC",
+                )
+                .fetch_all(&self.pool)
+                .await?
+            }
+        };
+
+        Ok(rows
+            .into_iter()
+            .map(|r| VaultExportRecord {
+                path: r.get("path"),
+                meeting_id: r.get("meeting_id"),
+                content_hash: r.get("content_hash"),
+                exported_at: r.get("exported_at"),
+            })
+            .collect())
+    }
+
+    /// Get the active meeting notes (latest generation unless one was picked)
     pub async fn get_meeting_notes(
         &self,
         meeting_id: &str,
     ) -> Result<Option<MeetingNotes>, sqlx::Error> {
         let row = sqlx::query_as::<_, (String, String, Option<String>, Option<String>, Option<String>, Option<String>, Option<String>, String, Option<String>)>(
-            "SELECT id, meeting_id, summary, key_topics, decisions, action_items, participants, generated_at, model_used FROM meeting_notes WHERE meeting_id = ? ORDER BY generated_at DESC LIMIT 1"
+            r#"
+            SELECT n.id, n.meeting_id, n.summary, n.key_topics, n.decisions, n.action_items, n.participants, n.generated_at, n.model_used
+            FROM meeting_notes n
+            LEFT JOIN meetings m ON m.id = n.meeting_id
+            WHERE n.meeting_id = ?
+            ORDER BY COALESCE(n.id = m.active_notes_id, 0) DESC, n.generated_at DESC
+            LIMIT 1
+            "#
         )
         .bind(meeting_id)
         .fetch_optional(&self.pool)
@@ -2973,4 +7245,540 @@ impl DatabaseManager {
             },
         ))
     }
+
+    /// Record a new Dork Mode session
+    pub async fn create_dork_session(
+        &self,
+        id: &str,
+        started_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT INTO dork_sessions (id, started_at) VALUES (?, ?)")
+            .bind(id)
+            .bind(started_at.to_rfc3339())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Append one chunk of captured content to a Dork Mode session
+    pub async fn add_dork_content(
+        &self,
+        session_id: &str,
+        content: &str,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "INSERT INTO dork_session_content (session_id, content, added_at) VALUES (?, ?, ?)",
+        )
+        .bind(session_id)
+        .bind(content)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "UPDATE dork_sessions SET chunk_count = chunk_count + 1, content_chars = content_chars + ? WHERE id = ?",
+        )
+        .bind(content.chars().count() as i64)
+        .bind(session_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+
+    /// Content chunks of a Dork Mode session in capture order
+    pub async fn get_dork_content(&self, session_id: &str) -> Result<Vec<String>, sqlx::Error> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT content FROM dork_session_content WHERE session_id = ? ORDER BY id ASC",
+        )
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|(content,)| content).collect())
+    }
+
+    /// Mark a Dork Mode session ended, or active again (ended_at = None)
+    pub async fn set_dork_session_ended(
+        &self,
+        session_id: &str,
+        ended_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE dork_sessions SET status = ?, ended_at = ? WHERE id = ?")
+            .bind(if ended_at.is_some() {
+                "ended"
+            } else {
+                "active"
+            })
+            .bind(ended_at.map(|t| t.to_rfc3339()))
+            .bind(session_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// One Dork Mode session
+    pub async fn get_dork_session(
+        &self,
+        session_id: &str,
+    ) -> Result<Option<DorkSessionRecord>, sqlx::Error> {
+        let row = sqlx::query(&format!("{} WHERE s.id = ?", DORK_SESSION_SELECT))
+            .bind(session_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.as_ref().map(dork_session_from_row))
+    }
+
+    /// Dork Mode sessions, newest first
+    pub async fn list_dork_sessions(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<DorkSessionRecord>, sqlx::Error> {
+        let rows = sqlx::query(&format!(
+            "{} ORDER BY s.started_at DESC LIMIT ?",
+            DORK_SESSION_SELECT
+        ))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(dork_session_from_row).collect())
+    }
+
+    /// Add a flashcard unless one with the same normalized front exists;
+    /// true if it was added
+    pub async fn add_flashcard(
+        &self,
+        id: &str,
+        session_id: &str,
+        front: &str,
+        back: &str,
+        front_key: &str,
+        review: &crate::spaced_repetition::ReviewState,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO flashcards
+            (id, session_id, front, back, front_key, ease, interval_days, repetitions, lapses, due_at, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id)
+        .bind(session_id)
+        .bind(front)
+        .bind(back)
+        .bind(front_key)
+        .bind(review.ease)
+        .bind(review.interval_days)
+        .bind(review.repetitions)
+        .bind(review.lapses)
+        .bind(review.due_at.to_rfc3339())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_flashcard(&self, id: &str) -> Result<Option<FlashcardRecord>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM flashcards WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.as_ref().map(flashcard_from_row))
+    }
+
+    /// Cards due by `now`, most overdue first
+    pub async fn get_due_flashcards(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<FlashcardRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM flashcards WHERE due_at <= ? ORDER BY due_at ASC, created_at ASC LIMIT ?",
+        )
+        .bind(now.to_rfc3339())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(flashcard_from_row).collect())
+    }
+
+    pub async fn count_due_flashcards(&self, now: DateTime<Utc>) -> Result<i64, sqlx::Error> {
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM flashcards WHERE due_at <= ?")
+            .bind(now.to_rfc3339())
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count)
+    }
+
+    /// Save a card's schedule after a review
+    pub async fn update_flashcard_review(
+        &self,
+        id: &str,
+        review: &crate::spaced_repetition::ReviewState,
+        reviewed_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE flashcards
+            SET ease = ?, interval_days = ?, repetitions = ?, lapses = ?, due_at = ?, last_reviewed_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(review.ease)
+        .bind(review.interval_days)
+        .bind(review.repetitions)
+        .bind(review.lapses)
+        .bind(review.due_at.to_rfc3339())
+        .bind(reviewed_at.to_rfc3339())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Card counts; `day_start` bounds "reviewed today"
+    pub async fn get_flashcard_stats(
+        &self,
+        now: DateTime<Utc>,
+        day_start: DateTime<Utc>,
+        mature_days: i64,
+    ) -> Result<crate::spaced_repetition::FlashcardStats, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                COUNT(*) AS total,
+                COALESCE(SUM(CASE WHEN due_at <= ? THEN 1 ELSE 0 END), 0) AS due,
+                COALESCE(SUM(CASE WHEN last_reviewed_at IS NULL THEN 1 ELSE 0 END), 0) AS new,
+                COALESCE(SUM(CASE WHEN interval_days >= ? THEN 1 ELSE 0 END), 0) AS mature,
+                COALESCE(SUM(CASE WHEN last_reviewed_at >= ? THEN 1 ELSE 0 END), 0) AS reviewed_today
+            FROM flashcards
+            "#,
+        )
+        .bind(now.to_rfc3339())
+        .bind(mature_days)
+        .bind(day_start.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(crate::spaced_repetition::FlashcardStats {
+            total: row.get("total"),
+            due: row.get("due"),
+            new: row.get("new"),
+            mature: row.get("mature"),
+            reviewed_today: row.get("reviewed_today"),
+        })
+    }
+
+    // ============================================
+    // Activity Clusters
+    // ============================================
+
+    /// Activities since `since` that aren't in a cluster yet, newest first
+    /// (quick notes and empty summaries are never clustered)
+    pub async fn get_unclustered_activities(
+        &self,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<ActivityLogEntry>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"SELECT * FROM activity_log
+               WHERE cluster_id IS NULL AND start_time >= ?
+                 AND category != 'quick_note' AND TRIM(summary) != ''
+               ORDER BY start_time DESC LIMIT ?"#,
+        )
+        .bind(since.to_rfc3339())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(unsynced_activity_from_row).collect())
+    }
+
+    /// Stored vectors for these activities from one backend
+    pub async fn get_activity_embeddings(
+        &self,
+        activity_ids: &[i64],
+        backend: &str,
+    ) -> Result<std::collections::HashMap<i64, Vec<u8>>, sqlx::Error> {
+        if activity_ids.is_empty() {
+            return Ok(std::collections::HashMap::new());
+        }
+        let sql = format!(
+            "SELECT activity_id, embedding FROM activity_embeddings WHERE backend = ? AND activity_id IN ({})",
+            vec!["?"; activity_ids.len()].join(", ")
+        );
+        let mut query = sqlx::query(&sql).bind(backend);
+        for id in activity_ids {
+            query = query.bind(id);
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+        Ok(rows
+            .iter()
+            .map(|r| (r.get("activity_id"), r.get("embedding")))
+            .collect())
+    }
+
+    pub async fn save_activity_embedding(
+        &self,
+        activity_id: i64,
+        backend: &str,
+        embedding: &[u8],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO activity_embeddings (activity_id, backend, embedding) VALUES (?, ?, ?)",
+        )
+        .bind(activity_id)
+        .bind(backend)
+        .bind(embedding)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// (id, centroid, member_count) of the clusters built with one backend
+    pub async fn get_activity_cluster_centroids(
+        &self,
+        backend: &str,
+    ) -> Result<Vec<(String, Vec<u8>, i64)>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, centroid, member_count FROM activity_clusters WHERE backend = ? ORDER BY created_at ASC",
+        )
+        .bind(backend)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .iter()
+            .map(|r| (r.get("id"), r.get("centroid"), r.get("member_count")))
+            .collect())
+    }
+
+    /// Create or update a cluster's centroid and put activities in it, in one
+    /// transaction; a new cluster gets `label`, an existing one keeps its own
+    pub async fn save_activity_cluster(
+        &self,
+        id: &str,
+        label: &str,
+        centroid: &[u8],
+        member_count: i64,
+        backend: &str,
+        activity_ids: &[i64],
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO activity_clusters (id, label, centroid, member_count, backend, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                centroid = excluded.centroid,
+                member_count = excluded.member_count,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(label)
+        .bind(centroid)
+        .bind(member_count)
+        .bind(backend)
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await?;
+
+        for activity_id in activity_ids {
+            sqlx::query(
+                "UPDATE activity_log SET cluster_id = ? WHERE id = ? AND cluster_id IS NULL",
+            )
+            .bind(id)
+            .bind(activity_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Rename a cluster; false if there is no such cluster
+    pub async fn rename_activity_cluster(
+        &self,
+        id: &str,
+        label: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result =
+            sqlx::query("UPDATE activity_clusters SET label = ?, updated_at = ? WHERE id = ?")
+                .bind(label)
+                .bind(Utc::now().to_rfc3339())
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Clusters with activity since `since` (all time when None) and the
+    /// time spent in each, most time first
+    pub async fn get_activity_cluster_totals(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<ActivityClusterRecord>, sqlx::Error> {
+        let since = since.map(|s| s.to_rfc3339());
+        let rows = sqlx::query(
+            r#"
+            SELECT c.id, c.label, c.created_at,
+                   COUNT(a.id) AS activity_count,
+                   COALESCE(SUM(a.duration_seconds), 0) AS total_seconds,
+                   MIN(a.start_time) AS first_seen,
+                   MAX(a.start_time) AS last_seen
+            FROM activity_clusters c
+            JOIN activity_log a ON a.cluster_id = c.id
+            WHERE (? IS NULL OR a.start_time >= ?)
+            GROUP BY c.id
+            ORDER BY total_seconds DESC, activity_count DESC
+            "#,
+        )
+        .bind(&since)
+        .bind(&since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|r| ActivityClusterRecord {
+                id: r.get("id"),
+                label: r.get("label"),
+                activity_count: r.get("activity_count"),
+                total_seconds: r.get("total_seconds"),
+                first_seen: r.get("first_seen"),
+                last_seen: r.get("last_seen"),
+                created_at: r.get("created_at"),
+            })
+            .collect())
+    }
+
+    /// Seconds of activity since `since` that no cluster covers
+    pub async fn get_unclustered_activity_seconds(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<i64, sqlx::Error> {
+        let since = since.map(|s| s.to_rfc3339());
+        let row = sqlx::query(
+            r#"SELECT COALESCE(SUM(duration_seconds), 0) AS total FROM activity_log
+               WHERE cluster_id IS NULL AND category != 'quick_note'
+                 AND (? IS NULL OR start_time >= ?)"#,
+        )
+        .bind(&since)
+        .bind(&since)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.get("total"))
+    }
+}
+
+/// A project cluster with its time totals over a report period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityClusterRecord {
+    pub id: String,
+    pub label: String,
+    pub activity_count: i64,
+    pub total_seconds: i64,
+    /// RFC3339 start of the first/last activity in the period
+    pub first_seen: String,
+    pub last_seen: String,
+    pub created_at: String,
+}
+
+/// A theme session; `ended_at` is None while it's open
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeSessionRecord {
+    pub id: i64,
+    pub theme: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+fn flashcard_from_row(r: &sqlx::sqlite::SqliteRow) -> FlashcardRecord {
+    let parse = |s: String| {
+        DateTime::parse_from_rfc3339(&s)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    };
+    FlashcardRecord {
+        id: r.get("id"),
+        session_id: r.get("session_id"),
+        front: r.get("front"),
+        back: r.get("back"),
+        ease: r.get("ease"),
+        interval_days: r.get("interval_days"),
+        repetitions: r.get("repetitions"),
+        lapses: r.get("lapses"),
+        due_at: parse(r.get("due_at")).unwrap_or_else(Utc::now),
+        last_reviewed_at: r
+            .get::<Option<String>, _>("last_reviewed_at")
+            .and_then(parse),
+        created_at: parse(r.get("created_at")).unwrap_or_else(Utc::now),
+    }
+}
+
+const DORK_SESSION_SELECT: &str = r#"
+    SELECT s.id, s.started_at, s.ended_at, s.status, s.chunk_count, s.content_chars,
+           EXISTS (SELECT 1 FROM study_materials m WHERE m.meeting_id = s.id) AS has_materials
+    FROM dork_sessions s"#;
+
+fn dork_session_from_row(r: &sqlx::sqlite::SqliteRow) -> DorkSessionRecord {
+    let parse = |s: String| {
+        DateTime::parse_from_rfc3339(&s)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    };
+    DorkSessionRecord {
+        id: r.get("id"),
+        started_at: parse(r.get("started_at")).unwrap_or_else(Utc::now),
+        ended_at: r.get::<Option<String>, _>("ended_at").and_then(parse),
+        status: r.get("status"),
+        chunk_count: r.get("chunk_count"),
+        content_chars: r.get("content_chars"),
+        has_materials: r.get::<i64, _>("has_materials") != 0,
+    }
+}
+
+/// Exclusive upper bound for a started_at filter. A bare date ("2024-03-31")
+/// becomes the next day so the whole day is included.
+fn meeting_date_upper_bound(date_to: &str) -> String {
+    match chrono::NaiveDate::parse_from_str(date_to, "%Y-%m-%d") {
+        Ok(date) => date
+            .succ_opt()
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| date_to.to_string()),
+        Err(_) => date_to.to_string(),
+    }
+}
+
+/// Replace every occurrence of `find`; None if nothing matched
+fn replace_in_text(text: &str, find: &str, replace: &str, case_sensitive: bool) -> Option<String> {
+    if case_sensitive {
+        return text.contains(find).then(|| text.replace(find, replace));
+    }
+
+    let pattern = regex::RegexBuilder::new(&regex::escape(find))
+        .case_insensitive(true)
+        .build()
+        .ok()?;
+    pattern.is_match(text).then(|| {
+        pattern
+            .replace_all(text, regex::NoExpand(replace))
+            .into_owned()
+    })
+}
+
+/// Quote free text as an FTS5 phrase so punctuation can't break MATCH syntax
+pub(crate) fn fts_phrase_query(text: &str) -> String {
+    format!("\"{}\"", text.trim().replace('"', "\"\""))
+}
+
+/// Quote each word of free text so it is matched literally; words are ANDed
+fn fts_terms_query(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Four 16-bit bands of a 64-bit screen hash, for vlm_cache band lookups
+fn phash_bands(phash: u64) -> [i64; 4] {
+    [0, 16, 32, 48].map(|shift| ((phash >> shift) & 0xFFFF) as i64)
 }
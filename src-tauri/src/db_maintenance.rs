@@ -0,0 +1,428 @@
+// noFriction Meetings - Database Maintenance
+// Integrity check, search index rebuild, VACUUM/REINDEX, dangling keyframe
+// paths and orphaned rows.
+//
+// A marker file in the app data dir records that the app is running; it is
+// removed on a clean exit, so finding it at startup means the last session
+// crashed and a bounded automatic maintenance pass should run.
+
+use crate::database::DatabaseManager;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Present while the app runs; left behind by a crash
+const RUNNING_MARKER: &str = ".running";
+/// Time budget for the automatic pass after an unclean shutdown
+pub const AUTO_MAINTENANCE_BUDGET_SECS: u64 = 20;
+/// Dangling keyframes listed individually in the report
+const DANGLING_SAMPLE_LIMIT: usize = 50;
+
+/// Which maintenance steps to run
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MaintenanceOptions {
+    pub integrity_check: bool,
+    /// PRAGMA quick_check instead of the full integrity_check
+    pub quick_integrity: bool,
+    pub rebuild_fts: bool,
+    pub vacuum: bool,
+    pub reindex: bool,
+    pub check_keyframes: bool,
+    /// Null out keyframe_path on screen states whose file is gone
+    pub clear_dangling_keyframes: bool,
+    pub check_orphans: bool,
+    /// Delete rows whose parent meeting (or other FK parent) is gone
+    pub delete_orphans: bool,
+    /// Remaining steps are skipped once this many seconds have passed
+    pub time_budget_secs: Option<u64>,
+}
+
+impl Default for MaintenanceOptions {
+    fn default() -> Self {
+        Self {
+            integrity_check: true,
+            quick_integrity: false,
+            rebuild_fts: true,
+            vacuum: true,
+            reindex: true,
+            check_keyframes: true,
+            clear_dangling_keyframes: false,
+            check_orphans: true,
+            delete_orphans: false,
+            time_budget_secs: None,
+        }
+    }
+}
+
+impl MaintenanceOptions {
+    /// Startup pass after a crash: repair the search index and report the
+    /// rest, skipping the slow VACUUM/REINDEX
+    pub fn after_crash() -> Self {
+        Self {
+            quick_integrity: true,
+            vacuum: false,
+            reindex: false,
+            time_budget_secs: Some(AUTO_MAINTENANCE_BUDGET_SECS),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DanglingKeyframe {
+    pub state_id: String,
+    pub meeting_id: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanCount {
+    pub table: String,
+    pub parent: String,
+    pub count: u64,
+}
+
+/// What maintenance found and fixed
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MaintenanceReport {
+    pub started_at: String,
+    pub duration_ms: u64,
+    /// None when the check was not run
+    pub integrity_ok: Option<bool>,
+    pub integrity_errors: Vec<String>,
+    pub fts_rebuilt: Vec<String>,
+    pub reindexed: bool,
+    pub vacuumed: bool,
+    pub dangling_keyframes: u64,
+    pub dangling_keyframe_samples: Vec<DanglingKeyframe>,
+    pub keyframe_paths_cleared: u64,
+    pub orphans: Vec<OrphanCount>,
+    pub orphans_deleted: u64,
+    /// Steps not run because the time budget ran out
+    pub skipped: Vec<String>,
+    /// Steps that failed (the remaining steps still run)
+    pub errors: Vec<String>,
+}
+
+impl MaintenanceReport {
+    /// One-line summary for logs and the audit trail
+    pub fn summary(&self) -> String {
+        format!(
+            "integrity {}, {} FTS tables rebuilt, {} dangling keyframes ({} cleared), {} orphans ({} deleted), {} skipped, {} errors",
+            match self.integrity_ok {
+                Some(true) => "ok",
+                Some(false) => "FAILED",
+                None => "not checked",
+            },
+            self.fts_rebuilt.len(),
+            self.dangling_keyframes,
+            self.keyframe_paths_cleared,
+            self.orphans.iter().map(|o| o.count).sum::<u64>(),
+            self.orphans_deleted,
+            self.skipped.len(),
+            self.errors.len()
+        )
+    }
+}
+
+/// Run the selected maintenance steps. Individual step failures are
+/// recorded in the report rather than aborting the run.
+pub async fn run_maintenance(
+    db: &DatabaseManager,
+    options: &MaintenanceOptions,
+) -> MaintenanceReport {
+    let started = Instant::now();
+    let deadline = options
+        .time_budget_secs
+        .map(|secs| started + Duration::from_secs(secs));
+    let mut report = MaintenanceReport {
+        started_at: chrono::Utc::now().to_rfc3339(),
+        ..Default::default()
+    };
+
+    let steps = [
+        ("integrity_check", options.integrity_check),
+        ("orphans", options.check_orphans),
+        ("keyframes", options.check_keyframes),
+        ("rebuild_fts", options.rebuild_fts),
+        ("reindex", options.reindex),
+        ("vacuum", options.vacuum),
+    ];
+    for (step, enabled) in steps {
+        if !enabled {
+            continue;
+        }
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            report.skipped.push(step.to_string());
+            continue;
+        }
+        let result = match step {
+            "integrity_check" => check_integrity(db, options.quick_integrity, &mut report).await,
+            "orphans" => check_orphans(db, options.delete_orphans, &mut report).await,
+            "keyframes" => check_keyframes(db, options.clear_dangling_keyframes, &mut report).await,
+            "rebuild_fts" => rebuild_fts(db, &mut report).await,
+            "reindex" => sqlx::query("REINDEX")
+                .execute(db.get_pool().as_ref())
+                .await
+                .map(|_| report.reindexed = true),
+            _ => sqlx::query("VACUUM")
+                .execute(db.get_pool().as_ref())
+                .await
+                .map(|_| report.vacuumed = true),
+        };
+        if let Err(e) = result {
+            log::warn!("Database maintenance step {} failed: {}", step, e);
+            report.errors.push(format!("{}: {}", step, e));
+        }
+    }
+
+    report.duration_ms = started.elapsed().as_millis() as u64;
+    report
+}
+
+async fn check_integrity(
+    db: &DatabaseManager,
+    quick: bool,
+    report: &mut MaintenanceReport,
+) -> Result<(), sqlx::Error> {
+    let pragma = if quick {
+        "PRAGMA quick_check(100)"
+    } else {
+        "PRAGMA integrity_check(100)"
+    };
+    let rows: Vec<(String,)> = sqlx::query_as(pragma)
+        .fetch_all(db.get_pool().as_ref())
+        .await?;
+    report.integrity_errors = rows
+        .into_iter()
+        .map(|(line,)| line)
+        .filter(|line| line != "ok")
+        .collect();
+    report.integrity_ok = Some(report.integrity_errors.is_empty());
+    Ok(())
+}
+
+/// Rows whose foreign-key parent (usually the meeting) no longer exists
+async fn check_orphans(
+    db: &DatabaseManager,
+    delete: bool,
+    report: &mut MaintenanceReport,
+) -> Result<(), sqlx::Error> {
+    let pool = db.get_pool();
+    let rows = sqlx::query("PRAGMA foreign_key_check")
+        .fetch_all(pool.as_ref())
+        .await?;
+
+    let mut orphans: Vec<(String, String, Option<i64>)> = Vec::with_capacity(rows.len());
+    for row in rows {
+        orphans.push((row.try_get(0)?, row.try_get(2)?, row.try_get(1)?));
+    }
+
+    for (table, parent, _) in &orphans {
+        match report
+            .orphans
+            .iter_mut()
+            .find(|o| &o.table == table && &o.parent == parent)
+        {
+            Some(count) => count.count += 1,
+            None => report.orphans.push(OrphanCount {
+                table: table.clone(),
+                parent: parent.clone(),
+                count: 1,
+            }),
+        }
+    }
+
+    if delete {
+        let mut tx = pool.begin().await?;
+        for (table, _, rowid) in &orphans {
+            let Some(rowid) = rowid else { continue };
+            // Table names come from SQLite itself
+            let sql = format!(
+                "DELETE FROM \"{}\" WHERE rowid = ?",
+                table.replace('"', "\"\"")
+            );
+            report.orphans_deleted += sqlx::query(&sql)
+                .bind(rowid)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        }
+        tx.commit().await?;
+    }
+    Ok(())
+}
+
+/// Screen states pointing at keyframe files that no longer exist
+async fn check_keyframes(
+    db: &DatabaseManager,
+    clear: bool,
+    report: &mut MaintenanceReport,
+) -> Result<(), sqlx::Error> {
+    let pool = db.get_pool();
+    let rows: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT state_id, meeting_id, keyframe_path FROM screen_states
+         WHERE keyframe_path IS NOT NULL AND keyframe_path != ''",
+    )
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    let dangling = tokio::task::spawn_blocking(move || {
+        rows.into_iter()
+            .filter(|(_, _, path)| !Path::new(path).exists())
+            .collect::<Vec<_>>()
+    })
+    .await
+    .unwrap_or_default();
+
+    report.dangling_keyframes = dangling.len() as u64;
+    report.dangling_keyframe_samples = dangling
+        .iter()
+        .take(DANGLING_SAMPLE_LIMIT)
+        .map(|(state_id, meeting_id, path)| DanglingKeyframe {
+            state_id: state_id.clone(),
+            meeting_id: meeting_id.clone(),
+            path: path.clone(),
+        })
+        .collect();
+
+    if clear && !dangling.is_empty() {
+        let mut tx = pool.begin().await?;
+        for (state_id, _, _) in &dangling {
+            report.keyframe_paths_cleared +=
+                sqlx::query("UPDATE screen_states SET keyframe_path = NULL WHERE state_id = ?")
+                    .bind(state_id)
+                    .execute(&mut *tx)
+                    .await?
+                    .rows_affected();
+        }
+        tx.commit().await?;
+    }
+    Ok(())
+}
+
+/// Rebuild every full-text index from its content table
+async fn rebuild_fts(
+    db: &DatabaseManager,
+    report: &mut MaintenanceReport,
+) -> Result<(), sqlx::Error> {
+    let pool = db.get_pool();
+
+    // External-content table: FTS5 rebuilds it from `transcripts`
+    sqlx::query("INSERT INTO transcripts_fts(transcripts_fts) VALUES('rebuild')")
+        .execute(pool.as_ref())
+        .await?;
+    report.fts_rebuilt.push("transcripts_fts".to_string());
+
+    // Standalone tables: empty them and re-index from the source rows
+    sqlx::query("DELETE FROM text_snapshots_fts")
+        .execute(pool.as_ref())
+        .await?;
+    sqlx::query("DELETE FROM frames_fts")
+        .execute(pool.as_ref())
+        .await?;
+    db.backfill_search_index().await?;
+    report.fts_rebuilt.push("text_snapshots_fts".to_string());
+    report.fts_rebuilt.push("frames_fts".to_string());
+    Ok(())
+}
+
+/// Record that the app is running. Returns true when a marker from a
+/// previous session was still there, i.e. that session did not exit cleanly.
+pub fn mark_running(app_data_dir: &Path) -> bool {
+    let marker = app_data_dir.join(RUNNING_MARKER);
+    let unclean = marker.exists();
+    if let Err(e) = std::fs::write(&marker, chrono::Utc::now().to_rfc3339()) {
+        log::warn!("Failed to write running marker: {}", e);
+    }
+    unclean
+}
+
+/// Remove the running marker on a clean exit
+pub fn mark_clean_shutdown(app_data_dir: &Path) {
+    let _ = std::fs::remove_file(app_data_dir.join(RUNNING_MARKER));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_running_marker_detects_unclean_exit() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!mark_running(dir.path()));
+        // Never cleaned up: the next start sees a crash
+        assert!(mark_running(dir.path()));
+        mark_clean_shutdown(dir.path());
+        assert!(!mark_running(dir.path()));
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_repairs_fts_keyframes_and_orphans() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DatabaseManager::new(&dir.path().join("test.db"))
+            .await
+            .unwrap();
+        db.run_migrations().await.unwrap();
+        let pool = db.get_pool();
+
+        db.create_meeting("m1", "Standup").await.unwrap();
+        db.add_transcript("m1", "quarterly roadmap review", None, true, 0.9)
+            .await
+            .unwrap();
+        db.add_screen_state(
+            "s1",
+            "m1",
+            chrono::Utc::now(),
+            None,
+            "",
+            0.0,
+            Some("/nonexistent/state_s1.jpg"),
+            "other",
+            "{}",
+            None,
+        )
+        .await
+        .unwrap();
+
+        // Simulate a crash: index out of sync and a row left behind by a deleted meeting
+        sqlx::query("INSERT INTO transcripts_fts(transcripts_fts) VALUES('delete-all')")
+            .execute(pool.as_ref())
+            .await
+            .unwrap();
+        let mut conn = pool.acquire().await.unwrap();
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO transcripts (meeting_id, text, timestamp) VALUES ('gone', 'x', '2026-01-01T00:00:00Z')")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        drop(conn);
+        assert!(db.search_transcripts("roadmap").await.unwrap().is_empty());
+
+        let options = MaintenanceOptions {
+            vacuum: false,
+            clear_dangling_keyframes: true,
+            delete_orphans: true,
+            ..Default::default()
+        };
+        let report = run_maintenance(&db, &options).await;
+
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.integrity_ok, Some(true));
+        assert_eq!(db.search_transcripts("roadmap").await.unwrap().len(), 1);
+        assert_eq!(report.dangling_keyframes, 1);
+        assert_eq!(report.keyframe_paths_cleared, 1);
+        assert_eq!(report.orphans.len(), 1);
+        assert_eq!(report.orphans[0].table, "transcripts");
+        assert_eq!(report.orphans_deleted, 1);
+    }
+}
@@ -0,0 +1,189 @@
+// noFriction Meetings - Serialized SQLite Writer
+// One task owns a dedicated write connection. Hot write paths (transcripts,
+// frames, screen states, activities, the VLM queue) send their statements
+// here over an mpsc channel, so concurrent writers queue up instead of
+// colliding with SQLITE_BUSY. Reads keep using the shared pool.
+
+use futures_util::future::BoxFuture;
+use serde::Serialize;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::ConnectOptions;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{mpsc, oneshot};
+
+type WriteJob = Box<dyn for<'c> FnOnce(&'c mut SqliteConnection) -> BoxFuture<'c, ()> + Send>;
+
+struct QueuedWrite {
+    job: WriteJob,
+    enqueued_at: Instant,
+}
+
+#[derive(Default)]
+struct Counters {
+    depth: AtomicU64,
+    max_depth: AtomicU64,
+    longest_wait_us: AtomicU64,
+    writes: AtomicU64,
+}
+
+/// Write queue metrics (surfaced in get_database_stats)
+#[derive(Debug, Clone, Serialize)]
+pub struct WriteQueueStats {
+    /// Writes waiting or in progress right now
+    pub depth: u64,
+    pub max_depth: u64,
+    /// Longest time a write waited in the queue before running
+    pub longest_wait_ms: f64,
+    pub writes: u64,
+}
+
+/// Handle to the writer task
+pub struct DbWriter {
+    tx: mpsc::UnboundedSender<QueuedWrite>,
+    counters: Arc<Counters>,
+}
+
+impl DbWriter {
+    /// Open the dedicated write connection and start the writer task
+    pub async fn connect(options: &SqliteConnectOptions) -> Result<Self, sqlx::Error> {
+        let mut conn = options.connect().await?;
+        let (tx, mut rx) = mpsc::unbounded_channel::<QueuedWrite>();
+        let counters = Arc::new(Counters::default());
+
+        let task_counters = counters.clone();
+        tokio::spawn(async move {
+            while let Some(write) = rx.recv().await {
+                let waited = write.enqueued_at.elapsed().as_micros() as u64;
+                task_counters
+                    .longest_wait_us
+                    .fetch_max(waited, Ordering::Relaxed);
+                (write.job)(&mut conn).await;
+                task_counters.writes.fetch_add(1, Ordering::Relaxed);
+                task_counters.depth.fetch_sub(1, Ordering::Relaxed);
+            }
+            log::info!("Database writer stopped");
+        });
+
+        Ok(Self { tx, counters })
+    }
+
+    /// Run `f` on the write connection after every write queued before it
+    pub async fn write<T, F>(&self, f: F) -> Result<T, sqlx::Error>
+    where
+        T: Send + 'static,
+        F: for<'c> FnOnce(&'c mut SqliteConnection) -> BoxFuture<'c, Result<T, sqlx::Error>>
+            + Send
+            + 'static,
+    {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        let job: WriteJob = Box::new(move |conn| {
+            Box::pin(async move {
+                let _ = reply_tx.send(f(conn).await);
+            })
+        });
+
+        let depth = self.counters.depth.fetch_add(1, Ordering::Relaxed) + 1;
+        self.counters.max_depth.fetch_max(depth, Ordering::Relaxed);
+        if self
+            .tx
+            .send(QueuedWrite {
+                job,
+                enqueued_at: Instant::now(),
+            })
+            .is_err()
+        {
+            self.counters.depth.fetch_sub(1, Ordering::Relaxed);
+            return Err(sqlx::Error::PoolClosed);
+        }
+
+        reply_rx.await.map_err(|_| sqlx::Error::PoolClosed)?
+    }
+
+    pub fn stats(&self) -> WriteQueueStats {
+        WriteQueueStats {
+            depth: self.counters.depth.load(Ordering::Relaxed),
+            max_depth: self.counters.max_depth.load(Ordering::Relaxed),
+            longest_wait_ms: self.counters.longest_wait_us.load(Ordering::Relaxed) as f64 / 1000.0,
+            writes: self.counters.writes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::{ActivityLogEntry, DatabaseManager};
+    use std::sync::Arc;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_writers_never_hit_locks() {
+        const PER_WRITER: usize = 100;
+
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(
+            DatabaseManager::new(&dir.path().join("stress.db"))
+                .await
+                .unwrap(),
+        );
+        db.run_migrations().await.unwrap();
+        db.create_meeting("m1", "Stress").await.unwrap();
+
+        let mut tasks = Vec::new();
+        for writer in 0..3 {
+            let db = db.clone();
+            tasks.push(tokio::spawn(async move {
+                for i in 0..PER_WRITER {
+                    let result = match writer {
+                        0 => db
+                            .add_transcript("m1", &format!("line {}", i), None, true, 0.9)
+                            .await
+                            .map(|_| ()),
+                        1 => db
+                            .add_frame("m1", chrono::Utc::now(), None, Some("ocr"))
+                            .await
+                            .map(|_| ()),
+                        _ => db
+                            .add_activity(&ActivityLogEntry {
+                                id: None,
+                                start_time: chrono::Utc::now(),
+                                end_time: None,
+                                duration_seconds: Some(5),
+                                app_name: Some("Editor".to_string()),
+                                window_title: None,
+                                category: "development".to_string(),
+                                summary: format!("activity {}", i),
+                                focus_area: None,
+                                visible_files: None,
+                                confidence: Some(0.8),
+                                frame_ids: None,
+                                pinecone_id: None,
+                                supabase_id: None,
+                                synced_at: None,
+                                from_cache: false,
+                            })
+                            .await
+                            .map(|_| ()),
+                    };
+                    result.expect("write failed under contention");
+                }
+            }));
+        }
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let pool = db.get_pool();
+        for table in ["transcripts", "frames", "activity_log"] {
+            let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+                .fetch_one(pool.as_ref())
+                .await
+                .unwrap();
+            assert_eq!(count, PER_WRITER as i64, "{}", table);
+        }
+
+        let stats = db.write_queue_stats();
+        assert_eq!(stats.writes, 3 * PER_WRITER as u64);
+        assert_eq!(stats.depth, 0);
+    }
+}
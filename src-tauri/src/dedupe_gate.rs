@@ -43,6 +43,11 @@ impl AverageHash {
         (self.hash ^ other.hash).count_ones()
     }
 
+    /// Raw 64-bit hash (VLM cache key)
+    pub fn as_u64(&self) -> u64 {
+        self.hash
+    }
+
     /// Convert to base64 string for storage
     pub fn to_base64(&self) -> String {
         use base64::Engine;
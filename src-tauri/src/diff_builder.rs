@@ -6,6 +6,7 @@
 // 2. Unified diff format generation
 // 3. Change type classification
 // 4. Change summary generation
+// 5. Reversible line patches for delta-stored text snapshots
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -96,6 +97,100 @@ impl Default for DiffConfig {
     }
 }
 
+/// Minimum line similarity for a snapshot to be stored as a patch on its predecessor
+pub const SNAPSHOT_DELTA_MIN_SIMILARITY: f32 = 0.6;
+
+/// Patches a snapshot may sit behind before compaction stores it in full again
+pub const MAX_SNAPSHOT_PATCH_CHAIN: i64 = 20;
+
+/// Largest LCS table (base lines x target lines, after trimming the common
+/// prefix/suffix) we'll build for a line patch
+const MAX_PATCH_CELLS: usize = 4_000_000;
+
+/// One step of a line patch, applied to the base text in order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchOp {
+    /// Copy the next n base lines
+    Keep(usize),
+    /// Skip the next n base lines
+    Delete(usize),
+    /// Emit new lines
+    Insert(Vec<String>),
+}
+
+/// Line-level edit script that rebuilds a snapshot from its base.
+/// Unlike `TextDiff::unified_diff` (a display format) this round-trips exactly.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinePatch {
+    /// Line count of the base text, checked before applying
+    pub base_lines: usize,
+    pub ops: Vec<PatchOp>,
+}
+
+impl LinePatch {
+    /// Rebuild the target text from the base the patch was computed against
+    pub fn apply(&self, base: &str) -> Result<String, String> {
+        let base_lines: Vec<&str> = base.split('\n').collect();
+        if base_lines.len() != self.base_lines {
+            return Err(format!(
+                "Patch expects {} base lines, got {}",
+                self.base_lines,
+                base_lines.len()
+            ));
+        }
+
+        let mut out: Vec<&str> = Vec::with_capacity(base_lines.len());
+        let mut pos = 0;
+        for op in &self.ops {
+            match op {
+                PatchOp::Keep(n) => {
+                    let end = pos + n;
+                    if end > base_lines.len() {
+                        return Err("Patch keeps past the end of the base".to_string());
+                    }
+                    out.extend_from_slice(&base_lines[pos..end]);
+                    pos = end;
+                }
+                PatchOp::Delete(n) => pos += n,
+                PatchOp::Insert(lines) => out.extend(lines.iter().map(String::as_str)),
+            }
+        }
+
+        if pos != base_lines.len() {
+            return Err("Patch does not cover the whole base".to_string());
+        }
+        Ok(out.join("\n"))
+    }
+
+    /// Fraction of lines shared between base and target (0.0 - 1.0)
+    pub fn similarity(&self) -> f32 {
+        let mut kept = 0;
+        let mut inserted = 0;
+        for op in &self.ops {
+            match op {
+                PatchOp::Keep(n) => kept += n,
+                PatchOp::Insert(lines) => inserted += lines.len(),
+                PatchOp::Delete(_) => {}
+            }
+        }
+        let total = self.base_lines.max(kept + inserted);
+        if total == 0 {
+            return 1.0;
+        }
+        kept as f32 / total as f32
+    }
+
+    fn push(&mut self, op: PatchOp) {
+        match (self.ops.last_mut(), op) {
+            (Some(PatchOp::Keep(a)), PatchOp::Keep(b)) => *a += b,
+            (Some(PatchOp::Delete(a)), PatchOp::Delete(b)) => *a += b,
+            (Some(PatchOp::Insert(a)), PatchOp::Insert(b)) => a.extend(b),
+            (_, op) => self.ops.push(op),
+        }
+    }
+}
+
 /// Diff builder for comparing text snapshots
 pub struct DiffBuilder {
     config: DiffConfig,
@@ -168,6 +263,74 @@ impl DiffBuilder {
         }
     }
 
+    /// Compute an exact line patch from `from_text` to `to_text`.
+    /// Returns None when the texts are too large to diff cheaply.
+    pub fn line_patch(&self, from_text: &str, to_text: &str) -> Option<LinePatch> {
+        let from_lines: Vec<&str> = from_text.split('\n').collect();
+        let to_lines: Vec<&str> = to_text.split('\n').collect();
+
+        // Trim the common prefix/suffix so the LCS only covers the changed middle
+        let prefix = from_lines
+            .iter()
+            .zip(&to_lines)
+            .take_while(|(a, b)| a == b)
+            .count();
+        let suffix = from_lines[prefix..]
+            .iter()
+            .rev()
+            .zip(to_lines[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let a = &from_lines[prefix..from_lines.len() - suffix];
+        let b = &to_lines[prefix..to_lines.len() - suffix];
+
+        if (a.len() + 1).saturating_mul(b.len() + 1) > MAX_PATCH_CELLS {
+            return None;
+        }
+
+        // lcs[i][j] = LCS length of a[i..] and b[j..]
+        let width = b.len() + 1;
+        let mut lcs = vec![0u32; (a.len() + 1) * width];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i * width + j] = if a[i] == b[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+
+        let mut patch = LinePatch {
+            base_lines: from_lines.len(),
+            ops: Vec::new(),
+        };
+        if prefix > 0 {
+            patch.push(PatchOp::Keep(prefix));
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                patch.push(PatchOp::Keep(1));
+                i += 1;
+                j += 1;
+            } else if j == b.len()
+                || (i < a.len() && lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+            {
+                patch.push(PatchOp::Delete(1));
+                i += 1;
+            } else {
+                patch.push(PatchOp::Insert(vec![b[j].to_string()]));
+                j += 1;
+            }
+        }
+        if suffix > 0 {
+            patch.push(PatchOp::Keep(suffix));
+        }
+
+        Some(patch)
+    }
+
     /// Compute SHA256 hash of text
     pub fn compute_text_hash(text: &str) -> String {
         let mut hasher = Sha256::new();
@@ -359,6 +522,30 @@ mod tests {
         assert_eq!(diff.change_type, ChangeType::ContentAdded);
     }
 
+    #[test]
+    fn test_line_patch_round_trip() {
+        let builder = DiffBuilder::new();
+        let from = "Inbox\nMeeting notes\nDraft reply\nSent\n";
+        let to = "Inbox (3)\nMeeting notes\nSent\nArchive\n";
+
+        let patch = builder.line_patch(from, to).unwrap();
+        assert_eq!(patch.apply(from).unwrap(), to);
+        assert!(patch.similarity() > 0.4 && patch.similarity() < 1.0);
+
+        // Survives storage as JSON
+        let json = serde_json::to_string(&patch).unwrap();
+        let restored: LinePatch = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.apply(from).unwrap(), to);
+
+        // Identical text is a single keep
+        let same = builder.line_patch(from, from).unwrap();
+        assert_eq!(same.ops, vec![PatchOp::Keep(5)]);
+        assert_eq!(same.similarity(), 1.0);
+
+        // Applying to the wrong base is an error, not garbage
+        assert!(patch.apply("something else").is_err());
+    }
+
     #[test]
     fn test_text_hash() {
         let hash1 = DiffBuilder::compute_text_hash("Hello");
@@ -0,0 +1,519 @@
+// noFriction Meetings - Digest Notes
+// Daily/weekly rollups written to the vault's Digests/ folder
+//
+// A digest lists the period's meetings (duration, action items, decisions),
+// time per activity category and the most frequent entities. When the AI
+// API is reachable a short narrative paragraph is added on top; otherwise
+// the note is just the data tables. DigestScheduler checks once a minute and
+// writes the daily digest after the configured local time (plus the weekly
+// one on Sundays).
+
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveTime};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::ai_client::AIClient;
+use crate::database::{DatabaseManager, MeetingFilter};
+use crate::obsidian_vault::VaultManager;
+
+/// How often the scheduler checks the clock
+const DIGEST_CHECK_INTERVAL_SECS: u64 = 60;
+/// Meetings listed per digest
+const MAX_DIGEST_MEETINGS: i64 = 200;
+/// Entities scanned for the "top entities" table
+const ENTITY_SCAN_LIMIT: i32 = 2000;
+const TOP_ENTITIES: usize = 10;
+/// Default local time for the evening digest
+pub const DEFAULT_DIGEST_TIME: &str = "18:00";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestPeriod {
+    Daily,
+    Weekly,
+}
+
+impl DigestPeriod {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "daily" => Some(DigestPeriod::Daily),
+            "weekly" => Some(DigestPeriod::Weekly),
+            _ => None,
+        }
+    }
+
+    /// Inclusive date range covered by a digest for `date` (weeks run Monday-Sunday)
+    pub fn range(&self, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            DigestPeriod::Daily => (date, date),
+            DigestPeriod::Weekly => {
+                let start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+                (start, start + Duration::days(6))
+            }
+        }
+    }
+
+    /// Note name inside Digests/
+    pub fn file_name(&self, date: NaiveDate) -> String {
+        match self {
+            DigestPeriod::Daily => format!("{} Daily Digest.md", date.format("%Y-%m-%d")),
+            DigestPeriod::Weekly => {
+                let week = date.iso_week();
+                format!("{}-W{:02} Weekly Digest.md", week.year(), week.week())
+            }
+        }
+    }
+}
+
+/// Parse a "HH:MM" local time
+pub fn parse_digest_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map_err(|_| format!("Invalid digest time '{}' (expected HH:MM)", value))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DigestMeeting {
+    pub title: String,
+    pub started_at: String,
+    pub duration_secs: Option<i64>,
+    pub action_items: Vec<String>,
+    pub decisions: Vec<String>,
+}
+
+/// Everything a digest note shows
+#[derive(Debug, Clone)]
+pub struct DigestData {
+    pub period: DigestPeriod,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub meetings: Vec<DigestMeeting>,
+    /// (category, activity count, total seconds), longest first
+    pub categories: Vec<(String, i64, i64)>,
+    /// (name, entity type, mentions), most mentioned first
+    pub entities: Vec<(String, String, usize)>,
+}
+
+fn format_duration(secs: i64) -> String {
+    if secs >= 3600 {
+        format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
+    } else {
+        format!("{}m", secs / 60)
+    }
+}
+
+/// Collect the period's meetings, activity totals and top entities
+pub async fn gather(
+    database: &Arc<DatabaseManager>,
+    period: DigestPeriod,
+    date: NaiveDate,
+) -> Result<DigestData, String> {
+    let (start, end) = period.range(date);
+
+    let filter = MeetingFilter {
+        date_from: Some(start.format("%Y-%m-%d").to_string()),
+        date_to: Some(end.format("%Y-%m-%d").to_string()),
+        ..Default::default()
+    };
+    let page = database
+        .list_meetings_filtered(0, MAX_DIGEST_MEETINGS, &filter)
+        .await
+        .map_err(|e| format!("Failed to list meetings: {}", e))?;
+
+    let mut meetings = Vec::new();
+    for summary in page.meetings.into_iter().rev() {
+        let meeting = summary.meeting;
+        let action_items = database
+            .list_action_items(Some(&meeting.id))
+            .await
+            .map_err(|e| format!("Failed to get action items: {}", e))?;
+        let decisions = database
+            .get_decisions(&meeting.id)
+            .await
+            .map_err(|e| format!("Failed to get decisions: {}", e))?;
+
+        meetings.push(DigestMeeting {
+            title: meeting.title,
+            started_at: meeting
+                .started_at
+                .with_timezone(&Local)
+                .format("%a %H:%M")
+                .to_string(),
+            duration_secs: meeting.duration_seconds,
+            action_items: crate::action_items::to_markdown(&action_items)
+                .lines()
+                .map(str::to_string)
+                .collect(),
+            decisions: decisions.into_iter().map(|d| d.text).collect(),
+        });
+    }
+
+    // Activity categories, summed across the period's days
+    let mut totals: HashMap<String, (i64, i64)> = HashMap::new();
+    let mut day = start;
+    while day <= end {
+        let stats = database
+            .get_activity_stats(&day.format("%Y-%m-%d").to_string())
+            .await
+            .map_err(|e| format!("Failed to get activity stats: {}", e))?;
+        if let Some(stats) = stats.as_object() {
+            for (category, value) in stats {
+                let entry = totals.entry(category.clone()).or_default();
+                entry.0 += value["count"].as_i64().unwrap_or(0);
+                entry.1 += value["total_seconds"].as_i64().unwrap_or(0);
+            }
+        }
+        day += Duration::days(1);
+    }
+    let mut categories: Vec<(String, i64, i64)> = totals
+        .into_iter()
+        .map(|(category, (count, secs))| (category, count, secs))
+        .collect();
+    categories.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+
+    // Entities seen during the period
+    let from = start.format("%Y-%m-%d").to_string();
+    let to = end.format("%Y-%m-%d").to_string();
+    // Count canonical entities, so "ACME" and "Acme Corp" are one row
+    if let Err(e) = crate::entity_resolution::resolve_pending(&database.get_pool()).await {
+        log::warn!("Digest: entity resolution failed: {}", e);
+    }
+    let recent = database
+        .get_recent_entities(ENTITY_SCAN_LIMIT)
+        .await
+        .map_err(|e| format!("Failed to get entities: {}", e))?;
+    let mut mentions: HashMap<(String, String), usize> = HashMap::new();
+    for entity in &recent {
+        let day = entity["source"]["timestamp"]
+            .as_str()
+            .map(|t| t.chars().take(10).collect::<String>())
+            .unwrap_or_default();
+        if day < from || day > to {
+            continue;
+        }
+        let name = entity["canonical_name"]
+            .as_str()
+            .or_else(|| entity["name"].as_str());
+        if let (Some(name), Some(kind)) = (name, entity["entity_type"].as_str()) {
+            *mentions
+                .entry((name.to_string(), kind.to_string()))
+                .or_default() += 1;
+        }
+    }
+    let mut entities: Vec<(String, String, usize)> = mentions
+        .into_iter()
+        .map(|((name, kind), count)| (name, kind, count))
+        .collect();
+    entities.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+    entities.truncate(TOP_ENTITIES);
+
+    Ok(DigestData {
+        period,
+        start,
+        end,
+        meetings,
+        categories,
+        entities,
+    })
+}
+
+/// Data tables as markdown, with an optional narrative paragraph on top
+pub fn render_markdown(data: &DigestData, narrative: Option<&str>) -> String {
+    let (kind, title) = match data.period {
+        DigestPeriod::Daily => (
+            "daily-digest",
+            format!("Daily Digest - {}", data.start.format("%A, %B %-d, %Y")),
+        ),
+        DigestPeriod::Weekly => (
+            "weekly-digest",
+            format!(
+                "Weekly Digest - {} to {}",
+                data.start.format("%b %-d"),
+                data.end.format("%b %-d, %Y")
+            ),
+        ),
+    };
+
+    let mut md = format!(
+        "---\ntitle: \"{}\"\ndate: \"{}\"\ntype: {}\ntags: [digest]\n---\n\n# {}\n\n",
+        title,
+        data.start.format("%Y-%m-%d"),
+        kind,
+        title
+    );
+
+    if let Some(narrative) = narrative.map(str::trim).filter(|n| !n.is_empty()) {
+        md.push_str(narrative);
+        md.push_str("\n\n");
+    }
+
+    md.push_str("## Meetings\n\n");
+    if data.meetings.is_empty() {
+        md.push_str("No meetings recorded.\n\n");
+    } else {
+        let total: i64 = data.meetings.iter().filter_map(|m| m.duration_secs).sum();
+        md.push_str(&format!(
+            "{} meeting{}, {} total.\n\n| Meeting | Started | Duration |\n|---|---|---|\n",
+            data.meetings.len(),
+            if data.meetings.len() == 1 { "" } else { "s" },
+            format_duration(total)
+        ));
+        for meeting in &data.meetings {
+            md.push_str(&format!(
+                "| {} | {} | {} |\n",
+                meeting.title.replace('|', "\\|"),
+                meeting.started_at,
+                meeting
+                    .duration_secs
+                    .map(format_duration)
+                    .unwrap_or_else(|| "-".to_string())
+            ));
+        }
+        md.push('\n');
+
+        for meeting in &data.meetings {
+            if meeting.action_items.is_empty() && meeting.decisions.is_empty() {
+                continue;
+            }
+            md.push_str(&format!("### {}\n\n", meeting.title));
+            if !meeting.decisions.is_empty() {
+                md.push_str("**Decisions**\n\n");
+                for decision in &meeting.decisions {
+                    md.push_str(&format!("- ✅ {}\n", decision));
+                }
+                md.push('\n');
+            }
+            if !meeting.action_items.is_empty() {
+                md.push_str("**Action Items**\n\n");
+                md.push_str(&meeting.action_items.join("\n"));
+                md.push_str("\n\n");
+            }
+        }
+    }
+
+    md.push_str("## Time by Category\n\n");
+    if data.categories.is_empty() {
+        md.push_str("No activity captured.\n\n");
+    } else {
+        md.push_str("| Category | Activities | Time |\n|---|---|---|\n");
+        for (category, count, secs) in &data.categories {
+            md.push_str(&format!(
+                "| {} | {} | {} |\n",
+                category,
+                count,
+                format_duration(*secs)
+            ));
+        }
+        md.push('\n');
+    }
+
+    if !data.entities.is_empty() {
+        md.push_str("## Top Entities\n\n| Entity | Type | Mentions |\n|---|---|---|\n");
+        for (name, kind, count) in &data.entities {
+            md.push_str(&format!(
+                "| {} | {} | {} |\n",
+                name.replace('|', "\\|"),
+                kind,
+                count
+            ));
+        }
+        md.push('\n');
+    }
+
+    md
+}
+
+/// Short narrative from the AI API; None when it is unavailable or fails
+pub async fn narrative(ai_client: &AIClient, data: &DigestData) -> Option<String> {
+    if data.meetings.is_empty() && data.categories.is_empty() {
+        return None;
+    }
+    if !ai_client.is_available().await {
+        log::info!("Digest: AI API unavailable, writing data tables only");
+        return None;
+    }
+
+    let prompt = format!(
+        "Write one short paragraph (3-4 sentences, no heading, no bullet points) summarizing this {} for the person it belongs to. Mention the most important meetings, decisions and open action items, and where their time went. Use only the facts below.\n\n{}",
+        match data.period {
+            DigestPeriod::Daily => "day",
+            DigestPeriod::Weekly => "week",
+        },
+        render_markdown(data, None)
+    );
+
+    match ai_client.complete(&prompt).await {
+        Ok(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        Ok(_) => None,
+        Err(e) => {
+            log::warn!("Digest: narrative generation failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Build and write a digest note; returns its path
+pub async fn generate_digest(
+    database: &Arc<DatabaseManager>,
+    vault_manager: &VaultManager,
+    ai_client: &AIClient,
+    period: DigestPeriod,
+    date: NaiveDate,
+) -> Result<String, String> {
+    let data = gather(database, period, date).await?;
+    let narrative = narrative(ai_client, &data).await;
+    let content = render_markdown(&data, narrative.as_deref());
+    vault_manager
+        .write_digest(&period.file_name(date), &content)
+        .await
+}
+
+/// Background loop that writes the evening digest
+pub struct DigestScheduler {
+    running: Arc<AtomicBool>,
+    time: Arc<RwLock<NaiveTime>>,
+    last_run: Arc<RwLock<Option<NaiveDate>>>,
+    database: Arc<DatabaseManager>,
+    vault_manager: Arc<VaultManager>,
+    ai_client: Arc<RwLock<AIClient>>,
+}
+
+impl DigestScheduler {
+    pub fn new(
+        database: Arc<DatabaseManager>,
+        vault_manager: Arc<VaultManager>,
+        ai_client: Arc<RwLock<AIClient>>,
+    ) -> Self {
+        Self {
+            running: Arc::new(AtomicBool::new(false)),
+            time: Arc::new(RwLock::new(
+                parse_digest_time(DEFAULT_DIGEST_TIME).unwrap_or(NaiveTime::MIN),
+            )),
+            last_run: Arc::new(RwLock::new(None)),
+            database,
+            vault_manager,
+            ai_client,
+        }
+    }
+
+    /// Local time after which the day's digest is written
+    pub fn set_time(&self, time: NaiveTime) {
+        *self.time.write() = time;
+        log::info!("Digest scheduler time: {}", time.format("%H:%M"));
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Start the scheduler loop
+    pub fn start(&self) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            log::info!("Digest scheduler already running");
+            return;
+        }
+        log::info!("Digest scheduler starting...");
+
+        let running = self.running.clone();
+        let time = self.time.clone();
+        let last_run = self.last_run.clone();
+        let database = self.database.clone();
+        let vault_manager = self.vault_manager.clone();
+        let ai_client = self.ai_client.clone();
+
+        tokio::spawn(async move {
+            while running.load(Ordering::SeqCst) {
+                tokio::time::sleep(std::time::Duration::from_secs(DIGEST_CHECK_INTERVAL_SECS))
+                    .await;
+                if !running.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let now = Local::now();
+                let today = now.date_naive();
+                if now.time() < *time.read() || *last_run.read() == Some(today) {
+                    continue;
+                }
+                *last_run.write() = Some(today);
+
+                let mut periods = vec![DigestPeriod::Daily];
+                if today.weekday() == chrono::Weekday::Sun {
+                    periods.push(DigestPeriod::Weekly);
+                }
+                let client = ai_client.read().clone();
+                for period in periods {
+                    // Don't redo a digest written earlier today (e.g. before a restart)
+                    if vault_manager.digest_exists(&period.file_name(today)) {
+                        continue;
+                    }
+                    match generate_digest(&database, &vault_manager, &client, period, today).await {
+                        Ok(path) => log::info!("Digest written: {}", path),
+                        Err(e) => log::warn!("Digest scheduler: {}", e),
+                    }
+                }
+            }
+            log::info!("Digest scheduler loop stopped");
+        });
+    }
+
+    /// Stop the scheduler loop
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+        log::info!("Digest scheduler stopped");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_weekly_range_and_file_names() {
+        // 2026-03-12 is a Thursday
+        assert_eq!(
+            DigestPeriod::Weekly.range(date("2026-03-12")),
+            (date("2026-03-09"), date("2026-03-15"))
+        );
+        assert_eq!(
+            DigestPeriod::Weekly.file_name(date("2026-03-12")),
+            "2026-W11 Weekly Digest.md"
+        );
+        assert_eq!(
+            DigestPeriod::Daily.file_name(date("2026-03-12")),
+            "2026-03-12 Daily Digest.md"
+        );
+        assert!(parse_digest_time("18:30").is_ok());
+        assert!(parse_digest_time("6pm").is_err());
+    }
+
+    #[test]
+    fn test_render_without_narrative() {
+        let data = DigestData {
+            period: DigestPeriod::Daily,
+            start: date("2026-03-12"),
+            end: date("2026-03-12"),
+            meetings: vec![DigestMeeting {
+                title: "Pipeline review".to_string(),
+                started_at: "Thu 10:00".to_string(),
+                duration_secs: Some(1800),
+                action_items: vec!["- [ ] Send deck".to_string()],
+                decisions: vec!["Ship on Friday".to_string()],
+            }],
+            categories: vec![("Coding/Development".to_string(), 12, 5400)],
+            entities: Vec::new(),
+        };
+
+        let md = render_markdown(&data, None);
+        assert!(md.contains("# Daily Digest - Thursday, March 12, 2026\n\n## Meetings"));
+        assert!(md.contains("| Pipeline review | Thu 10:00 | 30m |"));
+        assert!(md.contains("- ✅ Ship on Friday\n"));
+        assert!(md.contains("| Coding/Development | 12 | 1h 30m |"));
+        assert!(!md.contains("## Top Entities"));
+    }
+}
@@ -4,8 +4,15 @@
 use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 use crate::ai_client::AIClient;
+use crate::database::StudyMaterialsRecord;
+use crate::model_router::USE_CASE_STUDY_MATERIALS;
+use crate::obsidian_vault::VaultManager;
 
 /// Study materials generated at session end
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -14,9 +21,29 @@ pub struct StudyMaterials {
     pub summary: String,
     pub concepts: Vec<KeyConcept>,
     pub quiz: Vec<QuizQuestion>,
+    #[serde(default)]
+    pub flashcards: Vec<Flashcard>,
     pub created_at: DateTime<Utc>,
 }
 
+impl StudyMaterials {
+    /// Rebuild materials saved in the study_materials table
+    pub fn from_record(record: &StudyMaterialsRecord) -> Self {
+        fn parse<T: serde::de::DeserializeOwned>(json: Option<&str>) -> Vec<T> {
+            json.and_then(|j| serde_json::from_str(j).ok())
+                .unwrap_or_default()
+        }
+        Self {
+            session_id: record.meeting_id.clone(),
+            summary: record.summary.clone().unwrap_or_default(),
+            concepts: parse(record.key_concepts.as_deref()),
+            quiz: parse(record.quiz_questions.as_deref()),
+            flashcards: parse(record.flashcards.as_deref()),
+            created_at: record.generated_at,
+        }
+    }
+}
+
 /// A key concept extracted from the study session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyConcept {
@@ -33,12 +60,120 @@ pub struct QuizQuestion {
     pub explanation: String,
 }
 
+/// A question/answer card for review
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Flashcard {
+    pub front: String,
+    pub back: String,
+}
+
+/// One card per concept (term -> definition) and per quiz question
+/// (question -> correct answer and why)
+pub fn build_flashcards(concepts: &[KeyConcept], quiz: &[QuizQuestion]) -> Vec<Flashcard> {
+    let from_concepts = concepts.iter().map(|c| Flashcard {
+        front: c.term.clone(),
+        back: c.definition.clone(),
+    });
+    let from_quiz = quiz.iter().filter_map(|q| {
+        let answer = q.options.get(q.correct_index)?;
+        let back = if q.explanation.trim().is_empty() {
+            answer.clone()
+        } else {
+            format!("{}\n\n{}", answer, q.explanation)
+        };
+        Some(Flashcard {
+            front: q.question.clone(),
+            back,
+        })
+    });
+    from_concepts.chain(from_quiz).collect()
+}
+
+/// The app-wide slot holding the current session
+pub type SharedDorkSession = Arc<RwLock<Option<DorkModeSession>>>;
+
+/// Apps that feed a session from accessibility capture by default: readers,
+/// browsers and note apps
+pub const DEFAULT_DORK_APPS: &[&str] = &[
+    "Safari",
+    "Google Chrome",
+    "Arc",
+    "Firefox",
+    "Microsoft Edge",
+    "Brave Browser",
+    "Preview",
+    "Books",
+    "Kindle",
+    "Skim",
+    "Notion",
+    "Obsidian",
+];
+
+/// Captures scoring below this are mostly window chrome, not study content
+pub const MIN_CAPTURE_QUALITY: f32 = 0.5;
+
+/// Recently added chunks remembered for dedup
+const RECENT_CHUNKS: usize = 50;
+
+pub fn default_dork_app_allowlist() -> Vec<String> {
+    DEFAULT_DORK_APPS.iter().map(|a| a.to_string()).collect()
+}
+
+/// Whether captures from this app feed the session; an empty allowlist admits
+/// every app
+pub fn app_allowed(allowlist: &[String], app_name: Option<&str>) -> bool {
+    if allowlist.is_empty() {
+        return true;
+    }
+    app_name.is_some_and(|app| allowlist.iter().any(|a| a.eq_ignore_ascii_case(app)))
+}
+
+/// Prefix a captured chunk with where it came from, so study materials can
+/// cite it
+fn tag_chunk(app_name: Option<&str>, window_title: Option<&str>, text: &str) -> String {
+    let app = app_name.unwrap_or("Unknown app");
+    match window_title.filter(|t| !t.trim().is_empty()) {
+        Some(title) => format!("[Source: {} - {}]\n{}", app, title.trim(), text.trim()),
+        None => format!("[Source: {}]\n{}", app, text.trim()),
+    }
+}
+
+/// Hash of a chunk's text without its source tag, ignoring case and spacing
+fn chunk_hash(chunk: &str) -> u64 {
+    let body = match chunk.split_once('\n') {
+        Some((first, rest)) if first.starts_with("[Source: ") => rest,
+        _ => chunk,
+    };
+    let normalized = body
+        .split_whitespace()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut hasher = DefaultHasher::new();
+    normalized.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// What the frontend shows about the running session
+#[derive(Debug, Clone, Serialize)]
+pub struct DorkSessionStatus {
+    pub session_mode: String,
+    pub active: bool,
+    pub session_id: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub chunk_count: usize,
+    pub content_chars: usize,
+    /// Accessibility capture is feeding the session
+    pub auto_capture: bool,
+}
+
 /// Dork Mode session tracker
 pub struct DorkModeSession {
     pub session_id: String,
     pub start_time: DateTime<Utc>,
     pub content_buffer: RwLock<Vec<String>>,
     pub is_active: RwLock<bool>,
+    recent_hashes: RwLock<VecDeque<u64>>,
 }
 
 impl DorkModeSession {
@@ -48,14 +183,67 @@ impl DorkModeSession {
             start_time: Utc::now(),
             content_buffer: RwLock::new(Vec::new()),
             is_active: RwLock::new(true),
+            recent_hashes: RwLock::new(VecDeque::new()),
+        }
+    }
+
+    /// Pick up a stored session with the content captured so far
+    pub fn resume(session_id: String, start_time: DateTime<Utc>, content: Vec<String>) -> Self {
+        let skip = content.len().saturating_sub(RECENT_CHUNKS);
+        let recent = content.iter().skip(skip).map(|c| chunk_hash(c)).collect();
+        Self {
+            session_id,
+            start_time,
+            content_buffer: RwLock::new(content),
+            is_active: RwLock::new(true),
+            recent_hashes: RwLock::new(recent),
         }
     }
 
-    /// Accumulate transcript text during the session
-    pub fn accumulate_content(&self, text: &str) {
+    /// Accumulate transcript text during the session; false if it was ignored
+    pub fn accumulate_content(&self, text: &str) -> bool {
         if !text.trim().is_empty() && *self.is_active.read() {
+            self.remember(chunk_hash(text));
             self.content_buffer.write().push(text.to_string());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Add a captured text snapshot tagged with its source app; returns the
+    /// stored chunk, or None if it repeats recently added content
+    pub fn accumulate_capture(
+        &self,
+        app_name: Option<&str>,
+        window_title: Option<&str>,
+        text: &str,
+    ) -> Option<String> {
+        if text.trim().is_empty() || !*self.is_active.read() {
+            return None;
+        }
+        let chunk = tag_chunk(app_name, window_title, text);
+        let hash = chunk_hash(&chunk);
+        if self.recent_hashes.read().contains(&hash) {
+            return None;
         }
+        self.remember(hash);
+        self.content_buffer.write().push(chunk.clone());
+        Some(chunk)
+    }
+
+    fn remember(&self, hash: u64) {
+        let mut recent = self.recent_hashes.write();
+        if recent.len() >= RECENT_CHUNKS {
+            recent.pop_front();
+        }
+        recent.push_back(hash);
+    }
+
+    /// Number of chunks and characters accumulated so far
+    pub fn content_size(&self) -> (usize, usize) {
+        let buffer = self.content_buffer.read();
+        (buffer.len(), buffer.iter().map(|c| c.chars().count()).sum())
     }
 
     /// Get all accumulated content as a single string
@@ -99,11 +287,13 @@ pub async fn generate_study_materials(
     // Generate quiz questions
     let quiz = generate_quiz(ai_client, content).await?;
 
+    let flashcards = build_flashcards(&concepts, &quiz);
     Ok(StudyMaterials {
         session_id: session_id.to_string(),
         summary,
         concepts,
         quiz,
+        flashcards,
         created_at: Utc::now(),
     })
 }
@@ -112,6 +302,8 @@ async fn generate_summary(ai_client: &AIClient, content: &str) -> Result<String,
     let prompt = format!(
         r#"You are a study assistant. Summarize the following study session content into a clear, structured summary. Use bullet points for key topics and include any important details mentioned.
 
+Content captured from apps starts with a [Source: ...] line; cite the source next to facts taken from it.
+
 STUDY SESSION CONTENT:
 {}
 
@@ -119,7 +311,9 @@ Provide a well-organized summary that would help a student review what they lear
         content
     );
 
-    ai_client.complete(&prompt).await
+    ai_client
+        .complete_for(USE_CASE_STUDY_MATERIALS, &prompt)
+        .await
 }
 
 async fn extract_concepts(ai_client: &AIClient, content: &str) -> Result<Vec<KeyConcept>, String> {
@@ -136,7 +330,9 @@ Return ONLY the JSON array, no other text."#,
         content
     );
 
-    let response = ai_client.complete(&prompt).await?;
+    let response = ai_client
+        .complete_for(USE_CASE_STUDY_MATERIALS, &prompt)
+        .await?;
 
     // Parse JSON response
     let concepts: Vec<KeyConcept> = serde_json::from_str(&response).map_err(|e| {
@@ -172,7 +368,9 @@ Return ONLY the JSON array, no other text."#,
         content
     );
 
-    let response = ai_client.complete(&prompt).await?;
+    let response = ai_client
+        .complete_for(USE_CASE_STUDY_MATERIALS, &prompt)
+        .await?;
 
     // Parse JSON response
     let quiz: Vec<QuizQuestion> = serde_json::from_str(&response).map_err(|e| {
@@ -188,3 +386,190 @@ impl Default for DorkModeSession {
         Self::new(uuid::Uuid::new_v4().to_string())
     }
 }
+
+fn note_title(materials: &StudyMaterials) -> String {
+    let short_id: String = materials.session_id.chars().take(8).collect();
+    format!(
+        "{} Study Session {}",
+        materials.created_at.format("%Y-%m-%d"),
+        short_id
+    )
+}
+
+fn frontmatter(title: &str, materials: &StudyMaterials, note_type: &str) -> String {
+    format!(
+        "---\ntitle: \"{}\"\ndate: \"{}\"\ntype: {}\ntags: [study, {}]\nsession_id: \"{}\"\n---\n\n# {}\n\n",
+        title.replace('"', "'"),
+        materials.created_at.format("%Y-%m-%d"),
+        note_type,
+        note_type,
+        materials.session_id,
+        title
+    )
+}
+
+/// Study note: summary, key concepts and quiz, linking to the flashcards note
+pub fn render_study_note(materials: &StudyMaterials) -> String {
+    let title = note_title(materials);
+    let mut md = frontmatter(&title, materials, "study-notes");
+    md.push_str(&format!("## Summary\n\n{}\n\n", materials.summary.trim()));
+
+    if !materials.concepts.is_empty() {
+        md.push_str("## Key Concepts\n\n");
+        for c in &materials.concepts {
+            md.push_str(&format!("- **{}**: {}\n", c.term, c.definition));
+        }
+        md.push('\n');
+    }
+
+    if !materials.quiz.is_empty() {
+        md.push_str("## Quiz\n\n");
+        for (i, q) in materials.quiz.iter().enumerate() {
+            md.push_str(&format!("{}. {}\n", i + 1, q.question));
+            for (j, option) in q.options.iter().enumerate() {
+                let mark = if j == q.correct_index { "x" } else { " " };
+                md.push_str(&format!("    - [{}] {}\n", mark, option));
+            }
+        }
+        md.push('\n');
+    }
+
+    if !materials.flashcards.is_empty() {
+        md.push_str(&format!("Flashcards: [[{} - Flashcards]]\n", title));
+    }
+    md
+}
+
+/// Flashcards note in the question::answer form spaced-repetition plugins read
+pub fn render_flashcards(materials: &StudyMaterials) -> String {
+    let title = format!("{} - Flashcards", note_title(materials));
+    let mut md = frontmatter(&title, materials, "flashcards");
+    for card in &materials.flashcards {
+        md.push_str(&format!(
+            "{}\n?\n{}\n\n",
+            card.front.trim(),
+            card.back.trim()
+        ));
+    }
+    md
+}
+
+/// Write the study note (and flashcards, if any) to the vault's Study/
+/// folder; returns the written paths
+pub async fn export_to_vault(
+    vault_manager: &VaultManager,
+    materials: &StudyMaterials,
+) -> Result<Vec<String>, String> {
+    let title = note_title(materials);
+    let mut paths = vec![
+        vault_manager
+            .write_study_note(&format!("{}.md", title), &render_study_note(materials))
+            .await?,
+    ];
+    if !materials.flashcards.is_empty() {
+        paths.push(
+            vault_manager
+                .write_study_note(
+                    &format!("{} - Flashcards.md", title),
+                    &render_flashcards(materials),
+                )
+                .await?,
+        );
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn materials() -> StudyMaterials {
+        let concepts = vec![KeyConcept {
+            term: "Osmosis".to_string(),
+            definition: "Diffusion of water across a membrane".to_string(),
+        }];
+        let quiz = vec![QuizQuestion {
+            question: "Where is ATP produced?".to_string(),
+            options: vec!["Nucleus".to_string(), "Mitochondria".to_string()],
+            correct_index: 1,
+            explanation: "Cellular respiration".to_string(),
+        }];
+        StudyMaterials {
+            session_id: "0123456789abcdef".to_string(),
+            summary: "Cell biology basics".to_string(),
+            flashcards: build_flashcards(&concepts, &quiz),
+            concepts,
+            quiz,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_flashcards_from_concepts_and_quiz() {
+        let m = materials();
+        assert_eq!(m.flashcards.len(), 2);
+        assert_eq!(m.flashcards[0].front, "Osmosis");
+        assert_eq!(m.flashcards[1].back, "Mitochondria\n\nCellular respiration");
+
+        // A question whose answer index is out of range yields no card
+        let bad = QuizQuestion {
+            correct_index: 5,
+            ..m.quiz[0].clone()
+        };
+        assert!(build_flashcards(&[], &[bad]).is_empty());
+    }
+
+    #[test]
+    fn test_resumed_session_keeps_content() {
+        let session =
+            DorkModeSession::resume("s1".to_string(), Utc::now(), vec!["first".to_string()]);
+        assert!(session.accumulate_content("second"));
+        assert!(!session.accumulate_content("   "));
+        assert_eq!(session.get_all_content(), "first\nsecond");
+    }
+
+    #[test]
+    fn test_capture_dedup_and_allowlist() {
+        let session = DorkModeSession::new("s2".to_string());
+        let chunk = session
+            .accumulate_capture(
+                Some("Safari"),
+                Some("Cell biology"),
+                "Mitochondria make ATP",
+            )
+            .unwrap();
+        assert_eq!(
+            chunk,
+            "[Source: Safari - Cell biology]\nMitochondria make ATP"
+        );
+        // Same text from another window, or re-spaced, is a repeat
+        assert!(session
+            .accumulate_capture(Some("Preview"), None, "mitochondria  make ATP")
+            .is_none());
+        assert_eq!(session.content_size(), (1, chunk.chars().count()));
+
+        let resumed = DorkModeSession::resume("s2".to_string(), Utc::now(), vec![chunk]);
+        assert!(resumed
+            .accumulate_capture(Some("Safari"), None, "Mitochondria make ATP")
+            .is_none());
+
+        let allowlist = default_dork_app_allowlist();
+        assert!(app_allowed(&allowlist, Some("google chrome")));
+        assert!(!app_allowed(&allowlist, Some("Slack")));
+        assert!(!app_allowed(&allowlist, None));
+        assert!(app_allowed(&[], Some("Slack")));
+    }
+
+    #[test]
+    fn test_render_notes() {
+        let m = materials();
+        let note = render_study_note(&m);
+        assert!(note.contains("type: study-notes"));
+        assert!(note.contains("- **Osmosis**: Diffusion of water across a membrane"));
+        assert!(note.contains("    - [x] Mitochondria"));
+        assert!(note.contains("Study Session 01234567 - Flashcards]]"));
+
+        let cards = render_flashcards(&m);
+        assert!(cards.contains("Osmosis\n?\nDiffusion of water across a membrane\n"));
+    }
+}
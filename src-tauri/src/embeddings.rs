@@ -0,0 +1,293 @@
+// noFriction Meetings - Embeddings
+// Vector store abstraction: Pinecone (cloud) or a local SQLite store embedded
+// with the local all-MiniLM model, behind one upsert/search interface
+
+use crate::ai_client::{AIClient, EMBEDDING_MODEL};
+use crate::database::DatabaseManager;
+use crate::pinecone_client::{PineconeConfig, VectorKind, VectorMatch};
+use std::sync::Arc;
+
+/// Where semantic search vectors live
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorBackend {
+    Pinecone,
+    Local,
+}
+
+impl VectorBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pinecone => "pinecone",
+            Self::Local => "local",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pinecone" => Some(Self::Pinecone),
+            "local" => Some(Self::Local),
+            _ => None,
+        }
+    }
+}
+
+/// A configured vector store
+pub enum VectorStore {
+    Pinecone(PineconeConfig),
+    Local(LocalVectorStore),
+}
+
+impl VectorStore {
+    /// Store for the configured backend, if it is usable
+    pub fn resolve(
+        backend: VectorBackend,
+        pinecone: Option<PineconeConfig>,
+        database: Arc<DatabaseManager>,
+        ai_client: AIClient,
+    ) -> Option<Self> {
+        match backend {
+            VectorBackend::Pinecone => pinecone.map(Self::Pinecone),
+            VectorBackend::Local => Some(Self::Local(LocalVectorStore::new(database, ai_client))),
+        }
+    }
+
+    /// Store for the backend selected in settings (Pinecone when unset or
+    /// unrecognized)
+    pub async fn configured(
+        settings: &crate::settings::SettingsManager,
+        pinecone: Option<PineconeConfig>,
+        database: Arc<DatabaseManager>,
+        ai_client: AIClient,
+    ) -> Option<Self> {
+        let backend = settings
+            .get_all()
+            .await
+            .ok()
+            .and_then(|s| VectorBackend::parse(&s.vector_backend))
+            .unwrap_or(VectorBackend::Pinecone);
+        Self::resolve(backend, pinecone, database, ai_client)
+    }
+
+    /// Label reported with search hits
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pinecone(_) => "pinecone",
+            Self::Local(_) => "local_vectors",
+        }
+    }
+
+    pub async fn upsert(
+        &self,
+        kind: VectorKind,
+        id: &str,
+        text: &str,
+        metadata: &serde_json::Value,
+    ) -> Result<(), String> {
+        match self {
+            Self::Pinecone(config) => {
+                crate::pinecone_client::pinecone_upsert_generic(config, kind, id, text, metadata)
+                    .await
+            }
+            Self::Local(store) => {
+                store
+                    .upsert_batch(&[(id.to_string(), text.to_string(), metadata.clone())])
+                    .await
+            }
+        }
+    }
+
+    /// Remove vectors of one kind by ID
+    pub async fn delete(&self, kind: VectorKind, ids: &[String]) -> Result<(), String> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        match self {
+            Self::Pinecone(config) => {
+                crate::pinecone_client::pinecone_delete_by_ids(
+                    config,
+                    config.namespace_for(kind),
+                    ids,
+                )
+                .await
+            }
+            Self::Local(store) => store
+                .database
+                .delete_local_vectors(ids)
+                .await
+                .map(|_| ())
+                .map_err(|e| format!("Failed to delete local vectors: {}", e)),
+        }
+    }
+
+    /// IDs of stored vectors of one kind that start with `prefix`
+    pub async fn existing_ids(
+        &self,
+        kind: VectorKind,
+        prefix: &str,
+    ) -> Result<std::collections::HashSet<String>, String> {
+        let ids = match self {
+            Self::Pinecone(config) => {
+                crate::pinecone_client::pinecone_list_ids(
+                    config,
+                    config.namespace_for(kind),
+                    prefix,
+                )
+                .await?
+            }
+            Self::Local(store) => store
+                .database
+                .get_local_vector_ids(EMBEDDING_MODEL)
+                .await
+                .map_err(|e| format!("Failed to get local vectors: {}", e))?,
+        };
+        Ok(ids
+            .into_iter()
+            .filter(|id| id.starts_with(prefix))
+            .collect())
+    }
+
+    /// Embed texts with this backend's model without storing them
+    pub async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        match self {
+            Self::Pinecone(config) => crate::pinecone_client::pinecone_embed(config, texts).await,
+            Self::Local(store) => store.ai_client.embed(texts).await,
+        }
+    }
+
+    pub async fn search(&self, query: &str, top_k: u32) -> Result<Vec<VectorMatch>, String> {
+        match self {
+            Self::Pinecone(config) => {
+                crate::pinecone_client::pinecone_search(config, query, top_k).await
+            }
+            Self::Local(store) => store.search(query, top_k as usize).await,
+        }
+    }
+}
+
+/// SQLite-backed vectors searched by brute-force cosine similarity
+pub struct LocalVectorStore {
+    database: Arc<DatabaseManager>,
+    ai_client: AIClient,
+}
+
+impl LocalVectorStore {
+    pub fn new(database: Arc<DatabaseManager>, ai_client: AIClient) -> Self {
+        Self {
+            database,
+            ai_client,
+        }
+    }
+
+    /// Embed and store (id, text, metadata) records in one embedding call
+    pub async fn upsert_batch(
+        &self,
+        records: &[(String, String, serde_json::Value)],
+    ) -> Result<(), String> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let texts: Vec<String> = records.iter().map(|(_, text, _)| text.clone()).collect();
+        let vectors = self.ai_client.embed(&texts).await?;
+
+        for ((id, text, metadata), vector) in records.iter().zip(vectors) {
+            self.database
+                .upsert_local_vector(
+                    id,
+                    text,
+                    &metadata.to_string(),
+                    &encode_vector(&vector),
+                    EMBEDDING_MODEL,
+                )
+                .await
+                .map_err(|e| format!("Failed to store local vector: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Top `top_k` stored vectors by cosine similarity to the query. Matches
+    /// carry their metadata plus `text`, like Pinecone hits.
+    pub async fn search(&self, query: &str, top_k: usize) -> Result<Vec<VectorMatch>, String> {
+        let query_vector = self
+            .ai_client
+            .embed(&[query.to_string()])
+            .await?
+            .pop()
+            .ok_or("Embedding API returned no vector")?;
+
+        let records = self
+            .database
+            .get_local_vectors(EMBEDDING_MODEL)
+            .await
+            .map_err(|e| format!("Failed to load local vectors: {}", e))?;
+
+        let mut scored: Vec<(f32, crate::database::LocalVectorRecord)> = records
+            .into_iter()
+            .map(|r| (cosine(&query_vector, &decode_vector(&r.embedding)), r))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.truncate(top_k);
+
+        Ok(scored
+            .into_iter()
+            .map(|(score, r)| {
+                let mut metadata = r
+                    .metadata_json
+                    .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+                    .filter(|m| m.is_object())
+                    .unwrap_or_else(|| serde_json::json!({}));
+                if metadata.get("text").is_none() {
+                    metadata["text"] = serde_json::Value::String(r.text);
+                }
+                VectorMatch {
+                    id: r.id,
+                    score,
+                    metadata: Some(metadata),
+                }
+            })
+            .collect())
+    }
+}
+
+pub(crate) fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+pub(crate) fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+/// Cosine similarity (0.0 for mismatched or zero-length vectors)
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_blob_round_trip() {
+        let vector = vec![0.5, -1.25, 3.0e-7, 42.0];
+        assert_eq!(decode_vector(&encode_vector(&vector)), vector);
+    }
+
+    #[test]
+    fn test_cosine() {
+        assert!((cosine(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+        assert_eq!(cosine(&[1.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+}
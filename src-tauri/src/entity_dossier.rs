@@ -0,0 +1,634 @@
+// noFriction Meetings - Entity Dossier
+// Everything the app knows about one person or company as a single timeline:
+// recorded meetings they attended, transcript mentions, activities, calendar
+// events and vault notes. The optional AI rollup is cached per canonical
+// entity and goes stale once the timeline gains items or changes size.
+
+use crate::ai_client::AIClient;
+use crate::attendee_intel::{
+    extract_company_from_email, extract_name_from_email, is_personal_domain,
+};
+use crate::calendar_client::CalendarEventNative;
+use crate::entity_resolution::{is_person_type, match_key, normalize_name, CanonicalEntity};
+use crate::model_router::USE_CASE_ENTITY_DOSSIER;
+use crate::obsidian_vault::VaultManager;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Row, Sqlite};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Transcript segments returned per entity
+const MAX_TRANSCRIPT_HITS: i64 = 50;
+/// Recent meetings checked for the entity among their attendees
+const MEETINGS_SCANNED: i64 = 500;
+const SNIPPET_CHARS: usize = 280;
+/// Most recent timeline items given to the rollup prompt
+const ROLLUP_ITEMS: usize = 60;
+
+/// Where a timeline item came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DossierSource {
+    Meeting,
+    Transcript,
+    Activity,
+    CalendarEvent,
+    VaultNote,
+}
+
+/// One entry on an entity's timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DossierItem {
+    pub source: DossierSource,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub title: String,
+    pub snippet: Option<String>,
+    pub meeting_id: Option<String>,
+    pub activity_id: Option<i64>,
+    pub event_id: Option<String>,
+    pub path: Option<String>, // Vault note
+}
+
+impl DossierItem {
+    fn new(source: DossierSource, timestamp: Option<DateTime<Utc>>, title: String) -> Self {
+        Self {
+            source,
+            timestamp,
+            title,
+            snippet: None,
+            meeting_id: None,
+            activity_id: None,
+            event_id: None,
+            path: None,
+        }
+    }
+}
+
+/// AI summary of a dossier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DossierRollup {
+    pub text: String,
+    pub model: String,
+    pub generated_at: String,
+    pub stale: bool, // Timeline changed since it was generated
+}
+
+/// Result of `get_entity_dossier`: items oldest first, undated ones last
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityDossier {
+    pub entity: CanonicalEntity,
+    pub items: Vec<DossierItem>,
+    pub rollup: Option<DossierRollup>,
+}
+
+/// Cached rollup row, with what the timeline looked like when it was written
+struct CachedRollup {
+    rollup: DossierRollup,
+    item_count: i64,
+    sources_as_of: Option<DateTime<Utc>>,
+}
+
+/// Build the dossier for `entity`. The rollup is generated when requested and
+/// missing or stale (or `refresh_rollup`); if that fails the cached one is kept.
+pub async fn build_dossier(
+    pool: &Pool<Sqlite>,
+    vault: &VaultManager,
+    entity: CanonicalEntity,
+    calendar_events: &[CalendarEventNative],
+    include_rollup: bool,
+    refresh_rollup: bool,
+) -> Result<EntityDossier, String> {
+    let (mut items, linked_events) = database_items(pool, &entity).await?;
+    items.extend(calendar_items(&entity, calendar_events, &linked_events));
+    items.extend(vault_items(vault, &entity).await);
+    sort_timeline(&mut items);
+
+    let rollup = if include_rollup {
+        rollup_for(pool, &entity, &items, refresh_rollup).await?
+    } else {
+        None
+    };
+
+    Ok(EntityDossier {
+        entity,
+        items,
+        rollup,
+    })
+}
+
+/// Whether a meeting or calendar attendee is this entity: the same person name
+/// for people; the same domain or company name for organizations
+pub fn attendee_matches(entity: &CanonicalEntity, email: &str) -> bool {
+    let mut names = std::iter::once(&entity.name).chain(entity.aliases.iter());
+    if is_person_type(&entity.entity_type) {
+        let attendee = normalize_name(&extract_name_from_email(email));
+        return names.any(|n| normalize_name(n) == attendee);
+    }
+
+    let (domain, company_name) = extract_company_from_email(email);
+    if is_personal_domain(&domain) {
+        return false;
+    }
+    if entity.domain.as_deref() == Some(domain.as_str()) {
+        return true;
+    }
+    let key = match_key(&company_name);
+    !key.is_empty() && names.any(|n| match_key(n) == key)
+}
+
+/// Recorded meetings, transcript mentions and activities. Also returns the
+/// calendar event ids of matched meetings, so those events aren't listed twice.
+async fn database_items(
+    pool: &Pool<Sqlite>,
+    entity: &CanonicalEntity,
+) -> Result<(Vec<DossierItem>, HashSet<String>), String> {
+    let mut items = Vec::new();
+    let mut linked_events = HashSet::new();
+
+    let meetings = sqlx::query(
+        "SELECT id, title, started_at, attendees_json, calendar_event_id FROM meetings
+         WHERE attendees_json IS NOT NULL AND archived_at IS NULL
+         ORDER BY started_at DESC LIMIT ?",
+    )
+    .bind(MEETINGS_SCANNED)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to load meetings: {}", e))?;
+    for r in &meetings {
+        let attendees: Vec<String> = r
+            .get::<Option<String>, _>("attendees_json")
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        if !attendees.iter().any(|a| attendee_matches(entity, a)) {
+            continue;
+        }
+        if let Some(event_id) = r.get::<Option<String>, _>("calendar_event_id") {
+            linked_events.insert(event_id);
+        }
+        let mut item = DossierItem::new(
+            DossierSource::Meeting,
+            parse_time(&r.get::<String, _>("started_at")),
+            r.get("title"),
+        );
+        item.snippet = Some(format!("Attendees: {}", attendees.join(", ")));
+        item.meeting_id = Some(r.get("id"));
+        items.push(item);
+    }
+
+    let mut seen = HashSet::new();
+    let match_query = std::iter::once(&entity.name)
+        .chain(entity.aliases.iter())
+        .filter(|n| !n.trim().is_empty() && seen.insert(normalize_name(n)))
+        .map(|n| crate::database::fts_phrase_query(n))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    if !match_query.is_empty() {
+        let hits = sqlx::query(
+            r#"
+            SELECT t.meeting_id, m.title, t.text, t.speaker, t.timestamp
+            FROM transcripts_fts
+            JOIN transcripts t ON transcripts_fts.rowid = t.id
+            JOIN meetings m ON t.meeting_id = m.id
+            WHERE transcripts_fts MATCH ? AND m.archived_at IS NULL
+            ORDER BY t.timestamp DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(&match_query)
+        .bind(MAX_TRANSCRIPT_HITS)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Failed to search transcripts: {}", e))?;
+        for r in &hits {
+            let text: String = r.get("text");
+            let mut item = DossierItem::new(
+                DossierSource::Transcript,
+                parse_time(&r.get::<String, _>("timestamp")),
+                r.get("title"),
+            );
+            item.snippet = Some(match r.get::<Option<String>, _>("speaker") {
+                Some(speaker) => format!("{}: {}", speaker, truncate(&text)),
+                None => truncate(&text),
+            });
+            item.meeting_id = Some(r.get("meeting_id"));
+            items.push(item);
+        }
+    }
+
+    if !entity.activity_ids.is_empty() {
+        let placeholders = vec!["?"; entity.activity_ids.len()].join(", ");
+        let sql = format!(
+            "SELECT id, start_time, app_name, window_title, category, summary
+             FROM activity_log WHERE id IN ({})",
+            placeholders
+        );
+        let mut query = sqlx::query(&sql);
+        for id in &entity.activity_ids {
+            query = query.bind(id);
+        }
+        let activities = query
+            .fetch_all(pool)
+            .await
+            .map_err(|e| format!("Failed to load activities: {}", e))?;
+        for r in &activities {
+            let title = r
+                .get::<Option<String>, _>("window_title")
+                .or_else(|| r.get::<Option<String>, _>("app_name"))
+                .unwrap_or_else(|| r.get("category"));
+            let mut item = DossierItem::new(
+                DossierSource::Activity,
+                parse_time(&r.get::<String, _>("start_time")),
+                title,
+            );
+            item.snippet = Some(truncate(&r.get::<String, _>("summary")));
+            item.activity_id = Some(r.get("id"));
+            items.push(item);
+        }
+    }
+
+    Ok((items, linked_events))
+}
+
+/// Calendar events with the entity among the attendees, except events already
+/// represented by a recorded meeting
+fn calendar_items(
+    entity: &CanonicalEntity,
+    events: &[CalendarEventNative],
+    linked_events: &HashSet<String>,
+) -> Vec<DossierItem> {
+    events
+        .iter()
+        .filter(|e| !linked_events.contains(&e.event_id))
+        .filter(|e| e.attendees.iter().any(|a| attendee_matches(entity, a)))
+        .map(|e| {
+            let mut item = DossierItem::new(
+                DossierSource::CalendarEvent,
+                Some(e.start_time),
+                e.title.clone(),
+            );
+            item.snippet = Some(format!("Attendees: {}", e.attendees.join(", ")));
+            item.event_id = Some(e.event_id.clone());
+            item
+        })
+        .collect()
+}
+
+/// The entity's own person/company note and the vault notes linking to it
+async fn vault_items(vault: &VaultManager, entity: &CanonicalEntity) -> Vec<DossierItem> {
+    let mut items = Vec::new();
+    let mut seen = HashSet::new();
+
+    let own = if is_person_type(&entity.entity_type) {
+        vault.read_person_note(&entity.name).await
+    } else {
+        vault.read_company_note(&entity.name).await
+    };
+    if let Some(note) = own {
+        let path = note.path.to_string_lossy().to_string();
+        let mut item = DossierItem::new(
+            DossierSource::VaultNote,
+            note.last_updated,
+            note_title(&path),
+        );
+        item.path = Some(path.clone());
+        seen.insert(path);
+        items.push(item);
+    }
+
+    let backlinks = match vault.get_entity_backlinks(&entity.name).await {
+        Ok(result) => result.backlinks,
+        Err(e) => {
+            log::debug!("No vault backlinks for {}: {}", entity.name, e);
+            Vec::new()
+        }
+    };
+    for link in backlinks {
+        if !seen.insert(link.source_file.clone()) {
+            continue;
+        }
+        let modified = tokio::fs::metadata(&link.source_file)
+            .await
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .map(DateTime::<Utc>::from);
+        let mut item = DossierItem::new(
+            DossierSource::VaultNote,
+            modified,
+            note_title(&link.source_file),
+        );
+        item.snippet = Some(format!("Links to [[{}]]", link.display_text));
+        item.path = Some(link.source_file);
+        items.push(item);
+    }
+    items
+}
+
+fn note_title(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Oldest first; undated items last, each group in source order
+fn sort_timeline(items: &mut [DossierItem]) {
+    items.sort_by_key(|i| (i.timestamp.is_none(), i.timestamp));
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+fn truncate(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().count() <= SNIPPET_CHARS {
+        return text.to_string();
+    }
+    format!("{}…", text.chars().take(SNIPPET_CHARS).collect::<String>())
+}
+
+fn latest_timestamp(items: &[DossierItem]) -> Option<DateTime<Utc>> {
+    items.iter().filter_map(|i| i.timestamp).max()
+}
+
+/// A rollup is stale once the timeline has a different number of items or
+/// anything newer than what it was written from
+fn is_stale(cached: &CachedRollup, items: &[DossierItem]) -> bool {
+    cached.item_count != items.len() as i64 || latest_timestamp(items) > cached.sources_as_of
+}
+
+async fn rollup_for(
+    pool: &Pool<Sqlite>,
+    entity: &CanonicalEntity,
+    items: &[DossierItem],
+    refresh: bool,
+) -> Result<Option<DossierRollup>, String> {
+    let cached = load_rollup(pool, entity.id).await?.map(|mut c| {
+        c.rollup.stale = is_stale(&c, items);
+        c.rollup
+    });
+    if !refresh {
+        if let Some(ref rollup) = cached {
+            if !rollup.stale {
+                return Ok(cached);
+            }
+        }
+    }
+    if items.is_empty() {
+        return Ok(cached);
+    }
+
+    let route = crate::model_router::resolve(USE_CASE_ENTITY_DOSSIER).await;
+    match AIClient::new()
+        .complete_routed(&route, &rollup_prompt(entity, items))
+        .await
+    {
+        Ok(text) => {
+            let rollup = DossierRollup {
+                text: text.trim().to_string(),
+                model: route.model,
+                generated_at: Utc::now().to_rfc3339(),
+                stale: false,
+            };
+            store_rollup(pool, entity.id, &rollup, items).await?;
+            Ok(Some(rollup))
+        }
+        Err(e) => {
+            log::warn!("Dossier rollup for {} failed: {}", entity.name, e);
+            Ok(cached)
+        }
+    }
+}
+
+fn rollup_prompt(entity: &CanonicalEntity, items: &[DossierItem]) -> String {
+    let kind = if is_person_type(&entity.entity_type) {
+        "person"
+    } else {
+        "organization"
+    };
+    let mut prompt = format!(
+        "Write a two-paragraph rollup of everything known about the {} \"{}\"",
+        kind, entity.name
+    );
+    if !entity.aliases.is_empty() {
+        prompt.push_str(&format!(" (also seen as {})", entity.aliases.join(", ")));
+    }
+    prompt.push_str(
+        ". First paragraph: who they are and how they relate to the user. \
+         Second paragraph: the most recent interactions and any open threads. \
+         Use only the timeline below and plain prose, no headings or lists.\n\nTimeline (oldest first):\n",
+    );
+
+    let start = items.len().saturating_sub(ROLLUP_ITEMS);
+    for item in &items[start..] {
+        let when = item
+            .timestamp
+            .map(|t| t.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "undated".to_string());
+        let source = serde_json::to_value(item.source)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        prompt.push_str(&format!("- {} [{}] {}", when, source, item.title));
+        if let Some(ref snippet) = item.snippet {
+            prompt.push_str(&format!(": {}", snippet));
+        }
+        prompt.push('\n');
+    }
+    prompt
+}
+
+async fn load_rollup(
+    pool: &Pool<Sqlite>,
+    canonical_id: i64,
+) -> Result<Option<CachedRollup>, String> {
+    let row = sqlx::query(
+        "SELECT rollup, model, item_count, sources_as_of, generated_at
+         FROM entity_dossier_rollups WHERE canonical_id = ?",
+    )
+    .bind(canonical_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to load dossier rollup: {}", e))?;
+
+    Ok(row.map(|r| CachedRollup {
+        rollup: DossierRollup {
+            text: r.get("rollup"),
+            model: r.get("model"),
+            generated_at: r.get("generated_at"),
+            stale: false,
+        },
+        item_count: r.get("item_count"),
+        sources_as_of: r
+            .get::<Option<String>, _>("sources_as_of")
+            .and_then(|t| parse_time(&t)),
+    }))
+}
+
+async fn store_rollup(
+    pool: &Pool<Sqlite>,
+    canonical_id: i64,
+    rollup: &DossierRollup,
+    items: &[DossierItem],
+) -> Result<(), String> {
+    sqlx::query(
+        r#"
+        INSERT INTO entity_dossier_rollups
+        (canonical_id, rollup, model, item_count, sources_as_of, generated_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(canonical_id) DO UPDATE SET
+            rollup = excluded.rollup,
+            model = excluded.model,
+            item_count = excluded.item_count,
+            sources_as_of = excluded.sources_as_of,
+            generated_at = excluded.generated_at
+        "#,
+    )
+    .bind(canonical_id)
+    .bind(&rollup.text)
+    .bind(&rollup.model)
+    .bind(items.len() as i64)
+    .bind(latest_timestamp(items).map(|t| t.to_rfc3339()))
+    .bind(&rollup.generated_at)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to store dossier rollup: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    fn entity(
+        entity_type: &str,
+        name: &str,
+        aliases: &[&str],
+        domain: Option<&str>,
+    ) -> CanonicalEntity {
+        CanonicalEntity {
+            id: 1,
+            entity_type: entity_type.to_string(),
+            name: name.to_string(),
+            domain: domain.map(str::to_string),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            locked: false,
+            mention_count: 0,
+            confidence: 0.0,
+            first_seen: None,
+            last_seen: None,
+            activity_ids: Vec::new(),
+            metadata: None,
+            theme: None,
+        }
+    }
+
+    #[test]
+    fn attendees_match_people_by_name_and_companies_by_domain() {
+        let casey = entity("people", "Casey Jones", &[], None);
+        assert!(attendee_matches(&casey, "casey.jones@acme.io"));
+        assert!(attendee_matches(&casey, "Casey_Jones@gmail.com"));
+        assert!(!attendee_matches(&casey, "casey@acme.io"));
+
+        let acme = entity("companies", "Acme Corp", &["ACME"], Some("acme.io"));
+        assert!(attendee_matches(&acme, "casey@acme.io"));
+        assert!(attendee_matches(&acme, "sam@acme.com"));
+        assert!(!attendee_matches(&acme, "acme@gmail.com"));
+        assert!(!attendee_matches(&acme, "sam@globex.com"));
+    }
+
+    #[tokio::test]
+    async fn timeline_joins_meetings_transcripts_and_activities() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        for sql in [
+            "CREATE TABLE meetings (id TEXT PRIMARY KEY, title TEXT NOT NULL, started_at TEXT NOT NULL, attendees_json TEXT, calendar_event_id TEXT, archived_at TEXT)",
+            "CREATE TABLE transcripts (id INTEGER PRIMARY KEY AUTOINCREMENT, meeting_id TEXT NOT NULL, text TEXT NOT NULL, speaker TEXT, timestamp TEXT NOT NULL)",
+            "CREATE VIRTUAL TABLE transcripts_fts USING fts5(text, meeting_id, content='transcripts', content_rowid='id')",
+            "CREATE TABLE activity_log (id INTEGER PRIMARY KEY AUTOINCREMENT, start_time TEXT NOT NULL, app_name TEXT, window_title TEXT, category TEXT NOT NULL DEFAULT 'other', summary TEXT NOT NULL)",
+            r#"INSERT INTO meetings VALUES ('m1', 'Acme kickoff', '2026-03-02T15:00:00+00:00', '["casey@acme.io"]', 'evt-1', NULL)"#,
+            r#"INSERT INTO meetings VALUES ('m2', 'Globex sync', '2026-03-05T15:00:00+00:00', '["sam@globex.com"]', NULL, NULL)"#,
+            "INSERT INTO transcripts (meeting_id, text, speaker, timestamp) VALUES ('m2', 'Globex is evaluating ACME as a vendor', 'Sam', '2026-03-05T15:10:00+00:00')",
+            "INSERT INTO transcripts (meeting_id, text, timestamp) VALUES ('m2', 'Nothing relevant here', '2026-03-05T15:11:00+00:00')",
+            "INSERT INTO transcripts_fts(rowid, text, meeting_id) SELECT id, text, meeting_id FROM transcripts",
+            "INSERT INTO activity_log (start_time, app_name, summary) VALUES ('2026-03-01T09:00:00+00:00', 'Safari', 'Read the Acme pricing page')",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+
+        let mut acme = entity("companies", "Acme Corp", &["ACME"], Some("acme.io"));
+        acme.activity_ids = vec![1];
+        let (mut items, linked) = database_items(&pool, &acme).await.unwrap();
+        sort_timeline(&mut items);
+
+        let sources: Vec<DossierSource> = items.iter().map(|i| i.source).collect();
+        assert_eq!(
+            sources,
+            [
+                DossierSource::Activity,
+                DossierSource::Meeting,
+                DossierSource::Transcript
+            ]
+        );
+        assert_eq!(items[1].meeting_id.as_deref(), Some("m1"));
+        assert_eq!(
+            items[2].snippet.as_deref(),
+            Some("Sam: Globex is evaluating ACME as a vendor")
+        );
+        assert!(linked.contains("evt-1"));
+
+        // Events already recorded as meetings aren't listed again
+        let event = |id: &str, attendee: &str| CalendarEventNative {
+            event_id: id.to_string(),
+            title: "Acme renewal".to_string(),
+            start_time: Utc::now(),
+            end_time: Utc::now(),
+            location: None,
+            attendees: vec![attendee.to_string()],
+            calendar_name: "Work".to_string(),
+            is_all_day: false,
+            meeting_url: None,
+            notes: None,
+        };
+        let events = [
+            event("evt-1", "casey@acme.io"),
+            event("evt-2", "casey@acme.io"),
+            event("evt-3", "sam@globex.com"),
+        ];
+        let calendar = calendar_items(&acme, &events, &linked);
+        assert_eq!(calendar.len(), 1);
+        assert_eq!(calendar[0].event_id.as_deref(), Some("evt-2"));
+    }
+
+    #[test]
+    fn rollup_goes_stale_when_timeline_changes() {
+        let at = |s: &str| parse_time(s);
+        let item = |t: &str| DossierItem::new(DossierSource::Activity, at(t), "x".to_string());
+        let items = vec![item("2026-03-01T09:00:00Z"), item("2026-03-02T09:00:00Z")];
+        let cached = CachedRollup {
+            rollup: DossierRollup {
+                text: String::new(),
+                model: String::new(),
+                generated_at: String::new(),
+                stale: false,
+            },
+            item_count: 2,
+            sources_as_of: latest_timestamp(&items),
+        };
+        assert!(!is_stale(&cached, &items));
+
+        let mut newer = items.clone();
+        newer.push(item("2026-03-03T09:00:00Z"));
+        assert!(is_stale(&cached, &newer));
+        // Same size but a newer item (e.g. an edited vault note)
+        assert!(is_stale(&cached, &[items[0].clone(), newer[2].clone()]));
+        assert!(is_stale(&cached, &items[..1]));
+    }
+}
@@ -0,0 +1,875 @@
+// noFriction Meetings - Entity Resolution
+// Folds raw extracted entities ("Acme Corp", "ACME", "acme.com") into
+// canonical entities with aliases, so lists and analytics count one thing once.
+//
+// Matching, per entity type:
+// - Exact normalized name or alias
+// - Same web domain (not for people; coworkers share one)
+// - Same match key: punctuation, corporate suffixes and TLDs stripped
+// - Fuzzy match on the key (not for people)
+//
+// Canonicals split by hand are `locked`: they only take new mentions whose
+// name exactly matches one of their aliases, so a split isn't undone.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Row, Sqlite};
+use std::collections::HashMap;
+
+/// Minimum key similarity (0-1) for a fuzzy merge
+const FUZZY_THRESHOLD: f32 = 0.85;
+/// Keys shorter than this only merge on exact matches
+const MIN_FUZZY_LEN: usize = 5;
+/// Linked activities returned per canonical entity
+const MAX_LINKED_ACTIVITIES: usize = 50;
+
+/// Entity types holding people (matched by name only)
+const PERSON_TYPES: &[&str] = &["people", "person", "contacts"];
+/// Trailing words ignored when comparing organization names
+const CORPORATE_SUFFIXES: &[&str] = &[
+    "inc",
+    "incorporated",
+    "corp",
+    "corporation",
+    "co",
+    "company",
+    "llc",
+    "ltd",
+    "limited",
+    "gmbh",
+    "plc",
+    "ag",
+    "sa",
+    "hq",
+];
+/// Metadata fields that may carry a domain
+const DOMAIN_FIELDS: &[&str] = &["domain", "website", "url", "email", "contact_info"];
+
+/// A resolved entity with its mentions rolled up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanonicalEntity {
+    pub id: i64,
+    pub entity_type: String,
+    pub name: String,
+    pub domain: Option<String>,
+    pub aliases: Vec<String>,
+    pub locked: bool,
+    pub mention_count: i64,
+    pub confidence: f32, // Highest across mentions
+    pub first_seen: Option<String>,
+    pub last_seen: Option<String>,
+    pub activity_ids: Vec<i64>,              // Most recent first
+    pub metadata: Option<serde_json::Value>, // From the latest mention
+    pub theme: Option<String>,
+}
+
+/// Outcome of a resolution pass
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResolveStats {
+    pub resolved: usize,
+    pub created: usize,
+}
+
+/// Canonical entity as held during matching
+#[derive(Debug, Clone)]
+struct Candidate {
+    id: i64,
+    entity_type: String,
+    name: String,
+    domain: Option<String>,
+    aliases: Vec<String>,
+    locked: bool,
+}
+
+impl Candidate {
+    fn has_alias(&self, normalized: &str) -> bool {
+        normalize_name(&self.name) == normalized
+            || self.aliases.iter().any(|a| normalize_name(a) == normalized)
+    }
+
+    /// Remember a new spelling; true if it was new
+    fn add_alias(&mut self, name: &str) -> bool {
+        let name = clean_name(name);
+        if name.is_empty() || self.has_alias(&normalize_name(&name)) {
+            return false;
+        }
+        self.aliases.push(name);
+        true
+    }
+}
+
+/// Lowercase, single-spaced, without surrounding punctuation
+pub fn normalize_name(name: &str) -> String {
+    clean_name(name)
+        .to_lowercase()
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_string()
+}
+
+/// Display form: trimmed and single-spaced
+fn clean_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Comparison key: "Acme Corp", "ACME" and "acme.com" all become "acme"
+pub fn match_key(name: &str) -> String {
+    let normalized = normalize_name(name);
+    let base = match domain_of(&normalized) {
+        Some(domain) => domain.split('.').next().unwrap_or_default().to_string(),
+        None => normalized,
+    };
+    let mut tokens: Vec<&str> = base
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .collect();
+    while tokens.len() > 1
+        && tokens
+            .last()
+            .is_some_and(|t| CORPORATE_SUFFIXES.contains(t))
+    {
+        tokens.pop();
+    }
+    tokens.concat()
+}
+
+/// Web domain in an email address, URL or bare domain ("www.acme.com/x" → "acme.com")
+pub fn domain_of(text: &str) -> Option<String> {
+    let text = text.trim().to_lowercase();
+    let text = text.strip_prefix("mailto:").unwrap_or(&text);
+    let host = match text.rsplit_once('@') {
+        Some((_, host)) => host,
+        None => text
+            .strip_prefix("https://")
+            .or_else(|| text.strip_prefix("http://"))
+            .unwrap_or(text),
+    };
+    let host = host.split(['/', '?', '#', ':']).next().unwrap_or_default();
+    let host = host.strip_prefix("www.").unwrap_or(host);
+
+    let labels: Vec<&str> = host.split('.').collect();
+    let valid = labels.len() >= 2
+        && labels
+            .iter()
+            .all(|l| !l.is_empty() && l.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+        && labels
+            .last()
+            .is_some_and(|tld| tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()));
+    valid.then(|| host.to_string())
+}
+
+/// Organization domain from the name or metadata; personal mail domains don't count
+fn entity_domain(name: &str, metadata: Option<&serde_json::Value>) -> Option<String> {
+    let from_metadata = || {
+        DOMAIN_FIELDS.iter().find_map(|field| {
+            metadata
+                .and_then(|m| m.get(field))
+                .and_then(|v| v.as_str())
+                .and_then(domain_of)
+        })
+    };
+    domain_of(name)
+        .or_else(from_metadata)
+        .filter(|d| !crate::attendee_intel::is_personal_domain(d))
+}
+
+pub fn is_person_type(entity_type: &str) -> bool {
+    PERSON_TYPES.contains(&entity_type.trim().to_lowercase().as_str())
+}
+
+/// Best canonical for a mention, if any
+fn find_match(
+    candidates: &[Candidate],
+    entity_type: &str,
+    name: &str,
+    domain: Option<&str>,
+) -> Option<usize> {
+    let normalized = normalize_name(name);
+    let same_type = |c: &&Candidate| c.entity_type == entity_type;
+    let indexed = || candidates.iter().enumerate().filter(|(_, c)| same_type(c));
+
+    if let Some((i, _)) = indexed().find(|(_, c)| c.has_alias(&normalized)) {
+        return Some(i);
+    }
+
+    let open = || indexed().filter(|(_, c)| !c.locked);
+    let person = is_person_type(entity_type);
+    if let (Some(domain), false) = (domain, person) {
+        if let Some((i, _)) = open().find(|(_, c)| c.domain.as_deref() == Some(domain)) {
+            return Some(i);
+        }
+    }
+
+    let key = match_key(name);
+    if key.is_empty() {
+        return None;
+    }
+    let keys_of = |c: &Candidate| {
+        std::iter::once(match_key(&c.name))
+            .chain(c.aliases.iter().map(|a| match_key(a)))
+            .collect::<Vec<_>>()
+    };
+    if let Some((i, _)) = open().find(|(_, c)| keys_of(c).contains(&key)) {
+        return Some(i);
+    }
+    if person || key.chars().count() < MIN_FUZZY_LEN {
+        return None;
+    }
+    open()
+        .filter_map(|(i, c)| {
+            let best = keys_of(c)
+                .iter()
+                .filter(|k| k.chars().count() >= MIN_FUZZY_LEN)
+                .map(|k| crate::transcription::glossary::similarity(k, &key))
+                .fold(0.0f32, f32::max);
+            (best >= FUZZY_THRESHOLD).then_some((i, best))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
+
+async fn load_candidates(pool: &Pool<Sqlite>) -> Result<Vec<Candidate>, String> {
+    let rows = sqlx::query(
+        "SELECT id, entity_type, name, domain, aliases, locked FROM canonical_entities ORDER BY id",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to load canonical entities: {}", e))?;
+
+    Ok(rows
+        .iter()
+        .map(|r| Candidate {
+            id: r.get("id"),
+            entity_type: r.get("entity_type"),
+            name: r.get("name"),
+            domain: r.get("domain"),
+            aliases: serde_json::from_str(&r.get::<String, _>("aliases")).unwrap_or_default(),
+            locked: r.get::<i64, _>("locked") == 1,
+        })
+        .collect())
+}
+
+async fn insert_canonical<'e, E>(executor: E, candidate: &Candidate) -> Result<i64, String>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    let now = Utc::now().to_rfc3339();
+    sqlx::query(
+        "INSERT INTO canonical_entities (entity_type, name, domain, aliases, locked, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&candidate.entity_type)
+    .bind(&candidate.name)
+    .bind(&candidate.domain)
+    .bind(serde_json::to_string(&candidate.aliases).unwrap_or_else(|_| "[]".to_string()))
+    .bind(candidate.locked as i64)
+    .bind(&now)
+    .bind(&now)
+    .execute(executor)
+    .await
+    .map(|r| r.last_insert_rowid())
+    .map_err(|e| format!("Failed to create canonical entity: {}", e))
+}
+
+async fn update_canonical<'e, E>(executor: E, candidate: &Candidate) -> Result<(), String>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    sqlx::query(
+        "UPDATE canonical_entities SET name = ?, domain = ?, aliases = ?, locked = ?, updated_at = ?
+         WHERE id = ?",
+    )
+    .bind(&candidate.name)
+    .bind(&candidate.domain)
+    .bind(serde_json::to_string(&candidate.aliases).unwrap_or_else(|_| "[]".to_string()))
+    .bind(candidate.locked as i64)
+    .bind(Utc::now().to_rfc3339())
+    .bind(candidate.id)
+    .execute(executor)
+    .await
+    .map_err(|e| format!("Failed to update canonical entity: {}", e))?;
+    Ok(())
+}
+
+/// Assign every entity without a canonical to one, creating canonicals as needed
+pub async fn resolve_pending(pool: &Pool<Sqlite>) -> Result<ResolveStats, String> {
+    let pending = sqlx::query(
+        "SELECT id, entity_type, name, metadata FROM entities WHERE canonical_id IS NULL ORDER BY id",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to load entities: {}", e))?;
+    if pending.is_empty() {
+        return Ok(ResolveStats::default());
+    }
+
+    let mut candidates = load_candidates(pool).await?;
+    let mut dirty = std::collections::HashSet::new();
+    let mut stats = ResolveStats::default();
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    for row in &pending {
+        let entity_id: i64 = row.get("id");
+        let entity_type: String = row.get("entity_type");
+        let name: String = row.get("name");
+        let metadata: Option<serde_json::Value> = row
+            .get::<Option<String>, _>("metadata")
+            .and_then(|m| serde_json::from_str(&m).ok());
+        let domain = entity_domain(&name, metadata.as_ref());
+
+        let canonical_id = match find_match(&candidates, &entity_type, &name, domain.as_deref()) {
+            Some(i) => {
+                let candidate = &mut candidates[i];
+                let mut changed = candidate.add_alias(&name);
+                if candidate.domain.is_none() && domain.is_some() && !is_person_type(&entity_type) {
+                    candidate.domain = domain;
+                    changed = true;
+                }
+                if changed {
+                    dirty.insert(i);
+                }
+                candidate.id
+            }
+            None => {
+                let mut candidate = Candidate {
+                    id: 0,
+                    entity_type: entity_type.clone(),
+                    name: clean_name(&name),
+                    domain: domain.filter(|_| !is_person_type(&entity_type)),
+                    aliases: Vec::new(),
+                    locked: false,
+                };
+                candidate.add_alias(&name);
+                candidate.id = insert_canonical(&mut *tx, &candidate).await?;
+                stats.created += 1;
+                let id = candidate.id;
+                candidates.push(candidate);
+                id
+            }
+        };
+
+        sqlx::query("UPDATE entities SET canonical_id = ? WHERE id = ?")
+            .bind(canonical_id)
+            .bind(entity_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to link entity: {}", e))?;
+        stats.resolved += 1;
+    }
+
+    for i in dirty {
+        update_canonical(&mut *tx, &candidates[i]).await?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit entity resolution: {}", e))?;
+
+    log::info!(
+        "Entity resolution: {} entities linked, {} new canonical entities",
+        stats.resolved,
+        stats.created
+    );
+    Ok(stats)
+}
+
+/// Canonical entity for a name known from elsewhere (e.g. a calendar attendee),
+/// created if missing. Returns it with its canonical name.
+pub async fn link_entity(
+    pool: &Pool<Sqlite>,
+    entity_type: &str,
+    name: &str,
+    domain: Option<&str>,
+) -> Result<CanonicalEntity, String> {
+    let domain = domain
+        .and_then(domain_of)
+        .filter(|d| !crate::attendee_intel::is_personal_domain(d));
+    let mut candidates = load_candidates(pool).await?;
+
+    let id = match find_match(&candidates, entity_type, name, domain.as_deref()) {
+        Some(i) => {
+            let candidate = &mut candidates[i];
+            let mut changed = candidate.add_alias(name);
+            if candidate.domain.is_none() && domain.is_some() && !is_person_type(entity_type) {
+                candidate.domain = domain;
+                changed = true;
+            }
+            if changed {
+                update_canonical(pool, candidate).await?;
+            }
+            candidate.id
+        }
+        None => {
+            let mut candidate = Candidate {
+                id: 0,
+                entity_type: entity_type.to_string(),
+                name: clean_name(name),
+                domain: domain.filter(|_| !is_person_type(entity_type)),
+                aliases: Vec::new(),
+                locked: false,
+            };
+            candidate.add_alias(name);
+            insert_canonical(pool, &candidate).await?
+        }
+    };
+
+    get_canonical_entity(pool, id)
+        .await?
+        .ok_or_else(|| format!("Canonical entity not found: {}", id))
+}
+
+const CANONICAL_SELECT: &str = r#"
+    SELECT c.id, c.entity_type, c.name, c.domain, c.aliases, c.locked,
+           COUNT(e.id) AS mentions,
+           COALESCE(MAX(e.confidence), 0.0) AS confidence,
+           MIN(a.start_time) AS first_seen,
+           MAX(a.start_time) AS last_seen,
+           (SELECT e2.metadata FROM entities e2 WHERE e2.canonical_id = c.id
+            ORDER BY e2.id DESC LIMIT 1) AS metadata,
+           (SELECT e2.theme FROM entities e2 WHERE e2.canonical_id = c.id
+            ORDER BY e2.id DESC LIMIT 1) AS theme,
+           (SELECT GROUP_CONCAT(e2.activity_id) FROM entities e2
+            WHERE e2.canonical_id = c.id) AS activity_ids
+    FROM canonical_entities c
+    LEFT JOIN entities e ON e.canonical_id = c.id
+    LEFT JOIN activity_log a ON a.id = e.activity_id
+"#;
+
+fn canonical_from_row(r: &sqlx::sqlite::SqliteRow) -> CanonicalEntity {
+    let mut activity_ids: Vec<i64> = r
+        .get::<Option<String>, _>("activity_ids")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|id| id.trim().parse().ok())
+        .collect();
+    activity_ids.sort_unstable_by(|a, b| b.cmp(a));
+    activity_ids.dedup();
+    activity_ids.truncate(MAX_LINKED_ACTIVITIES);
+    CanonicalEntity {
+        id: r.get("id"),
+        entity_type: r.get("entity_type"),
+        name: r.get("name"),
+        domain: r.get("domain"),
+        aliases: serde_json::from_str(&r.get::<String, _>("aliases")).unwrap_or_default(),
+        locked: r.get::<i64, _>("locked") == 1,
+        mention_count: r.get("mentions"),
+        confidence: r.get::<f64, _>("confidence") as f32,
+        first_seen: r.get("first_seen"),
+        last_seen: r.get("last_seen"),
+        activity_ids,
+        metadata: r
+            .get::<Option<String>, _>("metadata")
+            .and_then(|m| serde_json::from_str(&m).ok()),
+        theme: r.get("theme"),
+    }
+}
+
+/// Canonical entities mentioned most recently, with their mention roll-ups
+pub async fn recent_canonical_entities(
+    pool: &Pool<Sqlite>,
+    limit: i64,
+) -> Result<Vec<CanonicalEntity>, String> {
+    let sql = format!(
+        "{} GROUP BY c.id HAVING mentions > 0 ORDER BY last_seen DESC LIMIT ?",
+        CANONICAL_SELECT
+    );
+    let rows = sqlx::query(&sql)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Failed to load entities: {}", e))?;
+    Ok(rows.iter().map(canonical_from_row).collect())
+}
+
+pub async fn get_canonical_entity(
+    pool: &Pool<Sqlite>,
+    id: i64,
+) -> Result<Option<CanonicalEntity>, String> {
+    let sql = format!("{} WHERE c.id = ? GROUP BY c.id", CANONICAL_SELECT);
+    let row = sqlx::query(&sql)
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Failed to load entity: {}", e))?;
+    Ok(row.as_ref().map(canonical_from_row))
+}
+
+/// Canonical entity known by `name` (its name or an alias), falling back to
+/// the same match key. Ties go to the oldest canonical.
+pub async fn find_canonical_by_name(
+    pool: &Pool<Sqlite>,
+    name: &str,
+) -> Result<Option<CanonicalEntity>, String> {
+    let normalized = normalize_name(name);
+    if normalized.is_empty() {
+        return Ok(None);
+    }
+    let key = match_key(name);
+    let candidates = load_candidates(pool).await?;
+    let found = candidates
+        .iter()
+        .find(|c| c.has_alias(&normalized))
+        .or_else(|| {
+            candidates.iter().find(|c| {
+                !key.is_empty()
+                    && (match_key(&c.name) == key || c.aliases.iter().any(|a| match_key(a) == key))
+            })
+        });
+    match found {
+        Some(c) => get_canonical_entity(pool, c.id).await,
+        None => Ok(None),
+    }
+}
+
+/// Merge canonical entities into the first one, renamed to `canonical_name`.
+/// The others' names and aliases become aliases of the result.
+pub async fn merge_entities(
+    pool: &Pool<Sqlite>,
+    ids: &[i64],
+    canonical_name: &str,
+) -> Result<CanonicalEntity, String> {
+    let canonical_name = clean_name(canonical_name);
+    if canonical_name.is_empty() {
+        return Err("Canonical name is required".to_string());
+    }
+    let mut unique: Vec<i64> = Vec::new();
+    for id in ids {
+        if !unique.contains(id) {
+            unique.push(*id);
+        }
+    }
+    if unique.len() < 2 {
+        return Err("Pick at least two entities to merge".to_string());
+    }
+
+    let candidates = load_candidates(pool).await?;
+    let by_id: HashMap<i64, &Candidate> = candidates.iter().map(|c| (c.id, c)).collect();
+    let mut merging = Vec::with_capacity(unique.len());
+    for id in &unique {
+        merging.push(
+            *by_id
+                .get(id)
+                .ok_or_else(|| format!("Entity not found: {}", id))?,
+        );
+    }
+    if merging
+        .iter()
+        .any(|c| c.entity_type != merging[0].entity_type)
+    {
+        return Err("Only entities of the same type can be merged".to_string());
+    }
+
+    let mut target = merging[0].clone();
+    for other in &merging {
+        for alias in std::iter::once(&other.name).chain(&other.aliases) {
+            target.add_alias(alias);
+        }
+        if target.domain.is_none() {
+            target.domain = other.domain.clone();
+        }
+    }
+    target.add_alias(&canonical_name);
+    target.name = canonical_name;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    for other in &merging[1..] {
+        sqlx::query("UPDATE entities SET canonical_id = ? WHERE canonical_id = ?")
+            .bind(target.id)
+            .bind(other.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to move mentions: {}", e))?;
+        sqlx::query("DELETE FROM canonical_entities WHERE id = ?")
+            .bind(other.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to delete merged entity: {}", e))?;
+    }
+    update_canonical(&mut *tx, &target).await?;
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit merge: {}", e))?;
+
+    log::info!("Merged {} entities into '{}'", merging.len(), target.name);
+    get_canonical_entity(pool, target.id)
+        .await?
+        .ok_or_else(|| format!("Canonical entity not found: {}", target.id))
+}
+
+/// Undo a bad merge: each distinct spelling among a canonical's mentions
+/// becomes its own (locked) canonical. The spelling matching the current name
+/// keeps the original id.
+pub async fn split_entity(pool: &Pool<Sqlite>, id: i64) -> Result<Vec<CanonicalEntity>, String> {
+    let candidates = load_candidates(pool).await?;
+    let original = candidates
+        .iter()
+        .find(|c| c.id == id)
+        .cloned()
+        .ok_or_else(|| format!("Entity not found: {}", id))?;
+
+    let rows = sqlx::query("SELECT id, name FROM entities WHERE canonical_id = ? ORDER BY id")
+        .bind(id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Failed to load mentions: {}", e))?;
+
+    // Spelling → (display names, entity ids), in first-seen order
+    let mut groups: Vec<(String, Vec<String>, Vec<i64>)> = Vec::new();
+    for row in &rows {
+        let name: String = row.get("name");
+        let normalized = normalize_name(&name);
+        let entity_id: i64 = row.get("id");
+        match groups.iter_mut().find(|g| g.0 == normalized) {
+            Some(group) => {
+                if !group.1.contains(&clean_name(&name)) {
+                    group.1.push(clean_name(&name));
+                }
+                group.2.push(entity_id);
+            }
+            None => groups.push((normalized, vec![clean_name(&name)], vec![entity_id])),
+        }
+    }
+    if groups.len() < 2 {
+        return Err("Nothing to split: all mentions use the same name".to_string());
+    }
+    let keep = groups
+        .iter()
+        .position(|g| g.0 == normalize_name(&original.name))
+        .unwrap_or(0);
+    let kept = groups.remove(keep);
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let mut kept_candidate = original.clone();
+    kept_candidate.aliases = kept.1;
+    kept_candidate.locked = true;
+    update_canonical(&mut *tx, &kept_candidate).await?;
+
+    let mut ids = vec![id];
+    for (_, names, entity_ids) in groups {
+        let candidate = Candidate {
+            id: 0,
+            entity_type: original.entity_type.clone(),
+            name: names[0].clone(),
+            domain: None,
+            aliases: names,
+            locked: true,
+        };
+        let new_id = insert_canonical(&mut *tx, &candidate).await?;
+        for entity_id in entity_ids {
+            sqlx::query("UPDATE entities SET canonical_id = ? WHERE id = ?")
+                .bind(new_id)
+                .bind(entity_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to move mention: {}", e))?;
+        }
+        ids.push(new_id);
+    }
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit split: {}", e))?;
+
+    log::info!("Split entity '{}' into {}", original.name, ids.len());
+    let mut result = Vec::with_capacity(ids.len());
+    for id in ids {
+        if let Some(entity) = get_canonical_entity(pool, id).await? {
+            result.push(entity);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> Pool<Sqlite> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        for sql in [
+            "CREATE TABLE activity_log (id INTEGER PRIMARY KEY AUTOINCREMENT, start_time TEXT NOT NULL)",
+            "CREATE TABLE entities (id INTEGER PRIMARY KEY AUTOINCREMENT, activity_id INTEGER NOT NULL, entity_type TEXT NOT NULL, name TEXT NOT NULL, metadata TEXT, confidence REAL DEFAULT 0.5, theme TEXT, canonical_id INTEGER)",
+            r#"CREATE TABLE canonical_entities (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                entity_type TEXT NOT NULL,
+                name TEXT NOT NULL,
+                domain TEXT,
+                aliases TEXT NOT NULL DEFAULT '[]',
+                locked INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )"#,
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        pool
+    }
+
+    async fn mention(pool: &Pool<Sqlite>, entity_type: &str, name: &str, metadata: Option<&str>) {
+        let activity = sqlx::query("INSERT INTO activity_log (start_time) VALUES (?)")
+            .bind(Utc::now().to_rfc3339())
+            .execute(pool)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        sqlx::query(
+            "INSERT INTO entities (activity_id, entity_type, name, metadata) VALUES (?, ?, ?, ?)",
+        )
+        .bind(activity)
+        .bind(entity_type)
+        .bind(name)
+        .bind(metadata)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn keys_and_domains() {
+        assert_eq!(match_key("Acme Corp"), "acme");
+        assert_eq!(match_key("  ACME  "), "acme");
+        assert_eq!(match_key("acme.com"), "acme");
+        assert_eq!(match_key("Acme-Corp, Inc."), "acme");
+        assert_eq!(match_key("Corp"), "corp");
+        assert_eq!(
+            domain_of("https://www.Acme.com/pricing"),
+            Some("acme.com".to_string())
+        );
+        assert_eq!(domain_of("casey@acme.io"), Some("acme.io".to_string()));
+        assert_eq!(domain_of("Acme Corp"), None);
+        assert_eq!(domain_of("v2.0"), None);
+    }
+
+    #[tokio::test]
+    async fn variants_resolve_to_one_canonical() {
+        let pool = setup_test_db().await;
+        mention(&pool, "companies", "Acme Corp", None).await;
+        mention(&pool, "companies", "ACME", None).await;
+        mention(&pool, "companies", "acme.com", None).await;
+        mention(
+            &pool,
+            "companies",
+            "Acme Robotics",
+            Some(r#"{"website": "acme.com"}"#),
+        )
+        .await;
+        mention(&pool, "companies", "Globex", None).await;
+        // People never merge on shared domains
+        mention(
+            &pool,
+            "people",
+            "Jane Doe",
+            Some(r#"{"contact_info": "jane@acme.com"}"#),
+        )
+        .await;
+        mention(
+            &pool,
+            "people",
+            "John Doe",
+            Some(r#"{"contact_info": "john@acme.com"}"#),
+        )
+        .await;
+
+        let stats = resolve_pending(&pool).await.unwrap();
+        assert_eq!(stats.resolved, 7);
+        assert_eq!(stats.created, 4);
+
+        let entities = recent_canonical_entities(&pool, 10).await.unwrap();
+        let acme = entities.iter().find(|e| e.name == "Acme Corp").unwrap();
+        assert_eq!(acme.mention_count, 4);
+        assert_eq!(acme.domain.as_deref(), Some("acme.com"));
+        assert_eq!(acme.aliases.len(), 4);
+        assert_eq!(acme.activity_ids.len(), 4);
+        assert!(acme.activity_ids[0] > acme.activity_ids[3]);
+
+        // Already resolved entities are left alone
+        assert_eq!(resolve_pending(&pool).await.unwrap().resolved, 0);
+    }
+
+    #[tokio::test]
+    async fn fuzzy_match_needs_long_keys() {
+        let pool = setup_test_db().await;
+        mention(&pool, "companies", "Salesforce", None).await;
+        mention(&pool, "companies", "Salesforse", None).await;
+        mention(&pool, "companies", "Xero", None).await;
+        mention(&pool, "companies", "Zero", None).await;
+        resolve_pending(&pool).await.unwrap();
+
+        let entities = recent_canonical_entities(&pool, 10).await.unwrap();
+        assert_eq!(entities.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn merge_then_split() {
+        let pool = setup_test_db().await;
+        mention(&pool, "companies", "Initech", None).await;
+        mention(&pool, "companies", "Initrode", None).await;
+        mention(&pool, "companies", "Initech", None).await;
+        resolve_pending(&pool).await.unwrap();
+
+        let ids: Vec<i64> = recent_canonical_entities(&pool, 10)
+            .await
+            .unwrap()
+            .iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(ids.len(), 2);
+
+        let merged = merge_entities(&pool, &ids, "Initech Group").await.unwrap();
+        assert_eq!(merged.name, "Initech Group");
+        assert_eq!(merged.mention_count, 3);
+        assert!(merge_entities(&pool, &[merged.id], "x").await.is_err());
+
+        let split = split_entity(&pool, merged.id).await.unwrap();
+        assert_eq!(split.len(), 2);
+        assert!(split.iter().all(|e| e.locked));
+        assert_eq!(split.iter().map(|e| e.mention_count).sum::<i64>(), 3);
+
+        // New mentions go to the locked canonical with the exact spelling
+        mention(&pool, "companies", "initrode", None).await;
+        resolve_pending(&pool).await.unwrap();
+        let initrode = recent_canonical_entities(&pool, 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|e| e.name == "Initrode")
+            .unwrap();
+        assert_eq!(initrode.mention_count, 2);
+    }
+
+    #[tokio::test]
+    async fn link_entity_reuses_canonical_name() {
+        let pool = setup_test_db().await;
+        mention(
+            &pool,
+            "companies",
+            "Acme Corporation",
+            Some(r#"{"url": "https://acme.com"}"#),
+        )
+        .await;
+        resolve_pending(&pool).await.unwrap();
+
+        let linked = link_entity(&pool, "companies", "Acme", Some("acme.com"))
+            .await
+            .unwrap();
+        assert_eq!(linked.name, "Acme Corporation");
+
+        let person = link_entity(&pool, "people", "Casey Potenzone", Some("gmail.com"))
+            .await
+            .unwrap();
+        assert_eq!(person.mention_count, 0);
+        assert!(person.domain.is_none());
+    }
+}
@@ -12,7 +12,7 @@ use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::state_builder::ScreenState;
+use crate::state_builder::{ScreenState, StateType};
 
 /// Configuration for episode building
 #[derive(Debug, Clone)]
@@ -49,6 +49,9 @@ pub struct DocumentEpisode {
     pub document_fingerprint: Option<String>,
     pub state_ids: Vec<String>,
     pub state_count: i32,
+    /// Category of the most confidently classified state in the episode
+    pub category: StateType,
+    pub category_confidence: f32,
 }
 
 impl DocumentEpisode {
@@ -68,6 +71,8 @@ impl DocumentEpisode {
             document_fingerprint: None,
             state_ids: Vec::new(),
             state_count: 0,
+            category: StateType::Other,
+            category_confidence: 0.0,
         }
     }
 
@@ -191,7 +196,7 @@ impl EpisodeBuilder {
             );
 
             // Link state to new episode
-            self.add_state_to_episode(&state.state_id, state.end_ts.unwrap_or(state.start_ts));
+            self.add_state_to_episode(state);
 
             EpisodeProcessResult::NewEpisode {
                 completed_episode: completed,
@@ -199,7 +204,7 @@ impl EpisodeBuilder {
             }
         } else {
             // Extend current episode
-            self.add_state_to_episode(&state.state_id, state.end_ts.unwrap_or(state.start_ts));
+            self.add_state_to_episode(state);
 
             let episode_id = {
                 let acc = self.accumulator.lock();
@@ -301,12 +306,17 @@ impl EpisodeBuilder {
     }
 
     /// Add state to current episode
-    fn add_state_to_episode(&self, state_id: &str, end_ts: DateTime<Utc>) {
+    fn add_state_to_episode(&self, state: &ScreenState) {
         let mut acc = self.accumulator.lock();
         if let Some(ref mut episode) = acc.current_episode {
-            episode.state_ids.push(state_id.to_string());
+            episode.state_ids.push(state.state_id.clone());
             episode.state_count += 1;
-            episode.end_ts = Some(end_ts);
+            episode.end_ts = Some(state.end_ts.unwrap_or(state.start_ts));
+            // The episode takes the category its states are most sure of
+            if state.category_confidence > episode.category_confidence {
+                episode.category = state.state_type;
+                episode.category_confidence = state.category_confidence;
+            }
             acc.sequence_num += 1;
         }
     }
@@ -328,6 +338,7 @@ mod tests {
         ScreenState {
             state_id: Uuid::new_v4().to_string(),
             meeting_id: meeting_id.to_string(),
+            monitor_id: 1,
             start_ts: Utc::now(),
             end_ts: Some(Utc::now()),
             app_name: app.map(String::from),
@@ -336,6 +347,7 @@ mod tests {
             delta_score: 0.0,
             keyframe_path: None,
             state_type: StateType::Other,
+            category_confidence: 0.0,
             flags: StateFlags::default(),
         }
     }
@@ -393,4 +405,24 @@ mod tests {
             _ => panic!("Expected NewEpisode"),
         }
     }
+
+    #[test]
+    fn test_episode_inherits_most_confident_category() {
+        let builder = EpisodeBuilder::new();
+        builder.start_meeting("test_meeting");
+
+        let mut unsure = create_test_state("test_meeting", Some("VSCode"), Some("main.rs"));
+        unsure.state_type = StateType::Document;
+        unsure.category_confidence = 0.4;
+        let mut sure = create_test_state("test_meeting", Some("VSCode"), Some("main.rs"));
+        sure.state_type = StateType::Coding;
+        sure.category_confidence = 0.9;
+
+        builder.process_state(&unsure);
+        builder.process_state(&sure);
+        let episode = builder.finalize_all().pop().unwrap();
+
+        assert_eq!(episode.category, StateType::Coding);
+        assert_eq!(episode.state_count, 2);
+    }
 }
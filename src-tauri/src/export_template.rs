@@ -0,0 +1,328 @@
+// noFriction Meetings - Vault Export Templates
+// Handlebars-style templates for the Obsidian meeting note
+//
+// Templates are stored through PromptManager under the `export_template`
+// category (template text in `system_prompt`). Supported syntax is a small
+// subset of Handlebars: `{{name}}` placeholders and `{{#if name}}...
+// {{else}}...{{/if}}` blocks, where a value counts as present when it is not
+// blank. Unknown placeholders are rejected when a template is saved.
+
+use std::collections::HashMap;
+
+/// PromptManager category holding export templates
+pub const EXPORT_TEMPLATE_CATEGORY: &str = "export_template";
+
+/// Every placeholder a template may reference
+pub const PLACEHOLDERS: &[&str] = &[
+    "title",
+    "date",
+    "meeting_id",
+    "duration",
+    "attendees",
+    "summary",
+    "highlights",
+    "key_topics",
+    "action_items",
+    "intelligence",
+    "intelligence.action_items",
+    "intelligence.decisions",
+    "intelligence.risks",
+    "intelligence.commitments",
+    "intelligence.questions",
+    "intelligence.topic_shifts",
+    "transcript",
+    "screenshots",
+];
+
+/// Builtin layout (the original hardcoded meeting note)
+pub const DEFAULT_EXPORT_TEMPLATE: &str = r#"---
+title: "{{title}}"
+date: "{{date}}"
+type: meeting
+tags: [meeting]
+meeting_id: "{{meeting_id}}"
+duration: "{{duration}}"
+---
+
+# {{title}}
+
+{{#if attendees}}## Attendees
+
+{{attendees}}
+
+{{/if}}{{#if summary}}## Summary
+
+{{summary}}
+
+{{/if}}{{#if highlights}}## Highlights
+
+{{highlights}}
+
+{{/if}}{{#if key_topics}}## Key Topics
+
+{{key_topics}}
+
+{{/if}}{{#if action_items}}## Action Items
+
+{{action_items}}
+
+{{/if}}{{#if intelligence}}## AI Intelligence
+
+{{intelligence}}{{/if}}## Transcript
+
+{{transcript}}"#;
+
+/// Intelligence bullets per section, each a markdown list (may be empty)
+#[derive(Debug, Clone, Default)]
+pub struct IntelligenceSections {
+    pub action_items: Vec<String>,
+    pub decisions: Vec<String>,
+    pub risks: Vec<String>,
+    pub commitments: Vec<String>,
+    pub questions: Vec<String>,
+    pub topic_shifts: Vec<String>,
+}
+
+impl IntelligenceSections {
+    fn sections(&self) -> [(&'static str, &'static str, &Vec<String>); 6] {
+        [
+            (
+                "intelligence.action_items",
+                "Action Items",
+                &self.action_items,
+            ),
+            ("intelligence.decisions", "Decisions", &self.decisions),
+            ("intelligence.risks", "Risk Signals", &self.risks),
+            ("intelligence.commitments", "Commitments", &self.commitments),
+            (
+                "intelligence.questions",
+                "Questions & Suggestions",
+                &self.questions,
+            ),
+            (
+                "intelligence.topic_shifts",
+                "Topic Shifts",
+                &self.topic_shifts,
+            ),
+        ]
+    }
+
+    /// All non-empty sections under `###` headings (`{{intelligence}}`)
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        for (_, heading, items) in self.sections() {
+            if !items.is_empty() {
+                md.push_str(&format!("### {}\n\n{}\n\n", heading, items.join("\n")));
+            }
+        }
+        md
+    }
+
+    /// Values for `{{intelligence}}` and `{{intelligence.*}}`
+    pub fn fill(&self, values: &mut HashMap<&'static str, String>) {
+        values.insert("intelligence", self.to_markdown());
+        for (name, _, items) in self.sections() {
+            values.insert(name, items.join("\n"));
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Var(String),
+    If {
+        name: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+fn line_of(template: &str, offset: usize) -> usize {
+    template[..offset].matches('\n').count() + 1
+}
+
+fn unknown_placeholder(name: &str, line: usize) -> String {
+    format!(
+        "Unknown placeholder {{{{{}}}}} on line {}. Available placeholders: {}",
+        name,
+        line,
+        PLACEHOLDERS.join(", ")
+    )
+}
+
+/// An `{{#if}}` block still being parsed
+struct OpenBlock {
+    name: String,
+    line: usize,
+    then: Vec<Node>,
+    otherwise: Vec<Node>,
+    in_else: bool,
+}
+
+/// Where the next node goes: the innermost open block, or the root
+fn current<'a>(root: &'a mut Vec<Node>, stack: &'a mut [OpenBlock]) -> &'a mut Vec<Node> {
+    match stack.last_mut() {
+        Some(block) if block.in_else => &mut block.otherwise,
+        Some(block) => &mut block.then,
+        None => root,
+    }
+}
+
+fn parse(template: &str) -> Result<Vec<Node>, String> {
+    let mut stack: Vec<OpenBlock> = Vec::new();
+    let mut root: Vec<Node> = Vec::new();
+    let mut pos = 0;
+
+    while let Some(found) = template[pos..].find("{{") {
+        let open = pos + found;
+        if open > pos {
+            current(&mut root, &mut stack).push(Node::Text(template[pos..open].to_string()));
+        }
+        let line = line_of(template, open);
+        let close = template[open..]
+            .find("}}")
+            .map(|i| open + i)
+            .ok_or_else(|| format!("Unclosed '{{{{' on line {}", line))?;
+        let tag = template[open + 2..close].trim();
+        pos = close + 2;
+
+        if let Some(name) = tag.strip_prefix("#if ") {
+            let name = name.trim();
+            if !PLACEHOLDERS.contains(&name) {
+                return Err(unknown_placeholder(name, line));
+            }
+            stack.push(OpenBlock {
+                name: name.to_string(),
+                line,
+                then: Vec::new(),
+                otherwise: Vec::new(),
+                in_else: false,
+            });
+        } else if tag == "else" {
+            match stack.last_mut() {
+                Some(block) if !block.in_else => block.in_else = true,
+                _ => return Err(format!("Unexpected {{{{else}}}} on line {}", line)),
+            }
+        } else if tag == "/if" {
+            let block = stack
+                .pop()
+                .ok_or_else(|| format!("Unexpected {{{{/if}}}} on line {}", line))?;
+            current(&mut root, &mut stack).push(Node::If {
+                name: block.name,
+                then: block.then,
+                otherwise: block.otherwise,
+            });
+        } else if tag.starts_with('#') || tag.starts_with('/') {
+            return Err(format!(
+                "Unsupported block {{{{{}}}}} on line {} (only #if / else / /if)",
+                tag, line
+            ));
+        } else if PLACEHOLDERS.contains(&tag) {
+            current(&mut root, &mut stack).push(Node::Var(tag.to_string()));
+        } else {
+            return Err(unknown_placeholder(tag, line));
+        }
+    }
+
+    if let Some(block) = stack.last() {
+        return Err(format!(
+            "{{{{#if {}}}}} on line {} is never closed with {{{{/if}}}}",
+            block.name, block.line
+        ));
+    }
+    if pos < template.len() {
+        root.push(Node::Text(template[pos..].to_string()));
+    }
+    Ok(root)
+}
+
+/// Check a template before it is saved
+pub fn validate(template: &str) -> Result<(), String> {
+    parse(template).map(|_| ())
+}
+
+fn render_nodes(nodes: &[Node], values: &HashMap<&'static str, String>, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var(name) => {
+                if let Some(value) = values.get(name.as_str()) {
+                    out.push_str(value);
+                }
+            }
+            Node::If {
+                name,
+                then,
+                otherwise,
+            } => {
+                let present = values
+                    .get(name.as_str())
+                    .is_some_and(|v| !v.trim().is_empty());
+                render_nodes(if present { then } else { otherwise }, values, out);
+            }
+        }
+    }
+}
+
+/// Render a template; missing values render as empty
+pub fn render(template: &str, values: &HashMap<&'static str, String>) -> Result<String, String> {
+    let nodes = parse(template)?;
+    let mut out = String::new();
+    render_nodes(&nodes, values, &mut out);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&'static str, &str)]) -> HashMap<&'static str, String> {
+        pairs.iter().map(|(k, v)| (*k, v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_default_template_skips_empty_sections() {
+        let rendered = render(
+            DEFAULT_EXPORT_TEMPLATE,
+            &values(&[
+                ("title", "Standup"),
+                ("summary", "All green."),
+                ("transcript", "**[09:00:00]** hi\n\n"),
+            ]),
+        )
+        .unwrap();
+
+        assert!(rendered.contains("# Standup\n\n## Summary\n\nAll green.\n\n## Transcript\n\n"));
+        assert!(!rendered.contains("## Attendees"));
+        assert!(!rendered.contains("## Highlights"));
+        assert!(!rendered.contains("## AI Intelligence"));
+    }
+
+    #[test]
+    fn test_if_else() {
+        let template = "{{#if summary}}S: {{summary}}{{else}}no summary{{/if}}";
+        assert_eq!(
+            render(template, &values(&[("summary", "ok")])).unwrap(),
+            "S: ok"
+        );
+        assert_eq!(
+            render(template, &values(&[("summary", "  ")])).unwrap(),
+            "no summary"
+        );
+    }
+
+    #[test]
+    fn test_validate_errors() {
+        let err = validate("# {{title}}\n{{atendees}}").unwrap_err();
+        assert!(err.starts_with("Unknown placeholder {{atendees}} on line 2"));
+        assert!(err.contains("attendees"));
+
+        assert!(validate("{{#if summary}}open")
+            .unwrap_err()
+            .contains("never closed"));
+        assert!(validate("{{/if}}").is_err());
+        assert!(validate("{{title").is_err());
+        assert!(validate("{{intelligence.decisions}}").is_ok());
+    }
+}
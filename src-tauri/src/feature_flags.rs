@@ -0,0 +1,114 @@
+// noFriction Meetings - Runtime Feature Flags
+// Typed view of the boolean settings that switch subsystems on and off.
+//
+// The live copy is owned by SettingsManager and updated whenever one of these
+// settings is written, so capture/VLM/ingest loops see a flip on their next
+// iteration instead of after a restart.
+
+use crate::settings::AppSettings;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Every flag name accepted by `get`/`set` (also the settings key it persists to)
+pub const FLAG_NAMES: &[&str] = &[
+    "dedup_enabled",
+    "vlm_auto_process",
+    "enable_ingest",
+    "queue_frames_for_vlm",
+    "accessibility_capture_enabled",
+    "ambient_capture_enabled",
+    "obsidian_auto_export",
+    "require_archive_before_delete",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureFlags {
+    /// Collapse duplicate frames into screen states
+    pub dedup_enabled: bool,
+    /// VLM scheduler analyzes queued frames
+    pub vlm_auto_process: bool,
+    /// Ingest uploader drains the queue
+    pub enable_ingest: bool,
+    /// Capture loop queues new keyframes for VLM
+    pub queue_frames_for_vlm: bool,
+    /// Accessibility text capture loop runs
+    pub accessibility_capture_enabled: bool,
+    /// Ambient (between-meetings) capture may run
+    pub ambient_capture_enabled: bool,
+    /// Export to the Obsidian vault when a recording stops
+    pub obsidian_auto_export: bool,
+    /// Soft-delete-first policy
+    pub require_archive_before_delete: bool,
+}
+
+/// Flags shared between AppState, SettingsManager and background loops
+pub type SharedFeatureFlags = Arc<RwLock<FeatureFlags>>;
+
+impl Default for FeatureFlags {
+    fn default() -> Self {
+        Self::from_settings(&AppSettings::default())
+    }
+}
+
+impl FeatureFlags {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            dedup_enabled: settings.dedup_enabled.unwrap_or(true),
+            vlm_auto_process: settings.vlm_auto_process,
+            enable_ingest: settings.enable_ingest.unwrap_or(false),
+            queue_frames_for_vlm: settings.queue_frames_for_vlm,
+            accessibility_capture_enabled: settings.accessibility_capture_enabled,
+            ambient_capture_enabled: settings.ambient_capture_enabled,
+            obsidian_auto_export: settings.obsidian_auto_export,
+            require_archive_before_delete: settings.require_archive_before_delete,
+        }
+    }
+
+    fn slot_mut(&mut self, name: &str) -> Option<&mut bool> {
+        Some(match name {
+            "dedup_enabled" => &mut self.dedup_enabled,
+            "vlm_auto_process" => &mut self.vlm_auto_process,
+            "enable_ingest" => &mut self.enable_ingest,
+            "queue_frames_for_vlm" => &mut self.queue_frames_for_vlm,
+            "accessibility_capture_enabled" => &mut self.accessibility_capture_enabled,
+            "ambient_capture_enabled" => &mut self.ambient_capture_enabled,
+            "obsidian_auto_export" => &mut self.obsidian_auto_export,
+            "require_archive_before_delete" => &mut self.require_archive_before_delete,
+            _ => return None,
+        })
+    }
+
+    /// Current value of a flag
+    pub fn get(&self, name: &str) -> Result<bool, String> {
+        let mut copy = *self;
+        copy.slot_mut(name)
+            .map(|v| *v)
+            .ok_or_else(|| format!("Unknown feature flag: {}", name))
+    }
+
+    /// Set a flag, returning its previous value
+    pub fn set(&mut self, name: &str, value: bool) -> Result<bool, String> {
+        let slot = self
+            .slot_mut(name)
+            .ok_or_else(|| format!("Unknown feature flag: {}", name))?;
+        Ok(std::mem::replace(slot, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_name_maps_to_a_flag() {
+        let mut flags = FeatureFlags::default();
+        for name in FLAG_NAMES {
+            let old = flags.get(name).unwrap();
+            assert_eq!(flags.set(name, !old), Ok(old));
+            assert_eq!(flags.get(name), Ok(!old));
+        }
+        assert!(flags.set("warp_drive", true).is_err());
+        assert!(flags.get("warp_drive").is_err());
+    }
+}
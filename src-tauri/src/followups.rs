@@ -0,0 +1,426 @@
+// noFriction Meetings - Follow-up Scheduling
+// Turns action items with a time hint ("by Friday", "next Tuesday") into
+// calendar events.
+//
+// Scheduling is two-step: proposals are built from a meeting's open action
+// items, the user confirms (and may edit) them, and only the confirmed list
+// is written to the calendar. The created event id is stored on the action
+// item, so rerunning never proposes or creates the same follow-up twice.
+
+use crate::calendar_client::CalendarClient;
+use crate::database::{ActionItemRecord, DatabaseManager};
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone, Timelike, Utc, Weekday,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Local hour follow-ups are booked at
+const FOLLOWUP_HOUR: u32 = 9;
+
+/// Length of a follow-up event
+const FOLLOWUP_MINS: i64 = 30;
+
+/// Longest event title before the action text is cut
+const MAX_TITLE_CHARS: usize = 80;
+
+const WEEKDAYS: &[(&str, Weekday)] = &[
+    ("monday", Weekday::Mon),
+    ("tuesday", Weekday::Tue),
+    ("wednesday", Weekday::Wed),
+    ("thursday", Weekday::Thu),
+    ("friday", Weekday::Fri),
+    ("saturday", Weekday::Sat),
+    ("sunday", Weekday::Sun),
+    ("mon", Weekday::Mon),
+    ("tue", Weekday::Tue),
+    ("tues", Weekday::Tue),
+    ("wed", Weekday::Wed),
+    ("thu", Weekday::Thu),
+    ("thur", Weekday::Thu),
+    ("thurs", Weekday::Thu),
+    ("fri", Weekday::Fri),
+    ("sat", Weekday::Sat),
+    ("sun", Weekday::Sun),
+];
+
+const MONTHS: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// A follow-up event waiting for confirmation; the confirmed (possibly
+/// edited) list is what gets created
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowupProposal {
+    pub action_item_id: String,
+    pub title: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub notes: Option<String>,
+    /// Calendar to write to; None uses the default for new events
+    pub calendar_name: Option<String>,
+    /// The phrase the date came from
+    pub due_hint: String,
+}
+
+/// A confirmed follow-up and its calendar event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledFollowup {
+    pub action_item_id: String,
+    pub event_id: String,
+    /// False when the item already had an event from an earlier run
+    pub created: bool,
+}
+
+struct DuePatterns {
+    iso: Regex,
+    relative: Regex,
+    weekday: Regex,
+    month_day: Regex,
+    day_month: Regex,
+}
+
+fn due_patterns() -> &'static DuePatterns {
+    static PATTERNS: OnceLock<DuePatterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| DuePatterns {
+        iso: Regex::new(r"\b(\d{4})-(\d{2})-(\d{2})\b").unwrap(),
+        relative: Regex::new(r"\bin (\d+|a|an|one|two|three|four|five|six) (day|week)s?\b")
+            .unwrap(),
+        weekday: Regex::new(
+            r"\b(next |this |on |by |before |until )?(monday|tuesday|wednesday|thursday|friday|saturday|sunday|mon|tues?|wed|thu(?:rs?)?|fri|sat|sun)\b",
+        )
+        .unwrap(),
+        month_day: Regex::new(
+            r"\b(jan|feb|mar|apr|may|jun|jul|aug|sep|sept|oct|nov|dec)[a-z]*\.? (\d{1,2})(?:st|nd|rd|th)?\b",
+        )
+        .unwrap(),
+        day_month: Regex::new(
+            r"\b(\d{1,2})(?:st|nd|rd|th)? (?:of )?(jan|feb|mar|apr|may|jun|jul|aug|sep|sept|oct|nov|dec)[a-z]*\b",
+        )
+        .unwrap(),
+    })
+}
+
+fn count_word(word: &str) -> Option<i64> {
+    match word {
+        "a" | "an" | "one" => Some(1),
+        "two" => Some(2),
+        "three" => Some(3),
+        "four" => Some(4),
+        "five" => Some(5),
+        "six" => Some(6),
+        n => n.parse().ok(),
+    }
+}
+
+/// Next date (today included) with this month and day, rolling into next year
+fn upcoming_month_day(today: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    let this_year = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if this_year >= today {
+        Some(this_year)
+    } else {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    }
+}
+
+fn month_number(name: &str) -> Option<u32> {
+    MONTHS
+        .iter()
+        .position(|m| name.starts_with(m))
+        .map(|i| i as u32 + 1)
+}
+
+/// Date a due hint points at, relative to `today`. Understands "today",
+/// "tomorrow", "EOD", "end of week/month", "next week/month", weekdays
+/// ("by Friday" is the coming one, "next Tuesday" the one in the following
+/// week), "in 3 days", "in two weeks", "March 5", "5th of March" and
+/// "2026-03-05".
+pub fn parse_due_date(hint: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let text = hint.to_lowercase();
+    let patterns = due_patterns();
+    let has = |phrase: &str| {
+        Regex::new(&format!(r"\b{}\b", phrase))
+            .map(|re| re.is_match(&text))
+            .unwrap_or(false)
+    };
+    let days_from_monday = today.weekday().num_days_from_monday() as i64;
+
+    if let Some(caps) = patterns.iso.captures(&text) {
+        let (y, m, d) = (
+            caps[1].parse().ok()?,
+            caps[2].parse().ok()?,
+            caps[3].parse().ok()?,
+        );
+        return NaiveDate::from_ymd_opt(y, m, d);
+    }
+    if has("today") || has("tonight") || has("eod") || has("end of (the )?day") {
+        return Some(today);
+    }
+    if has("tomorrow") {
+        return Some(today + Duration::days(1));
+    }
+    if let Some(caps) = patterns.relative.captures(&text) {
+        let n = count_word(&caps[1])?;
+        let days = if &caps[2] == "week" { n * 7 } else { n };
+        return Some(today + Duration::days(days));
+    }
+    if has("eow") || has("end of (the )?week") {
+        // The coming Friday; on a weekend, next week's
+        let ahead = (4 - days_from_monday).rem_euclid(7);
+        return Some(today + Duration::days(ahead));
+    }
+    if has("eom") || has("end of (the )?month") {
+        let (y, m) = if today.month() == 12 {
+            (today.year() + 1, 1)
+        } else {
+            (today.year(), today.month() + 1)
+        };
+        return NaiveDate::from_ymd_opt(y, m, 1).map(|d| d - Duration::days(1));
+    }
+    if let Some(caps) = patterns.weekday.captures(&text) {
+        let target = WEEKDAYS
+            .iter()
+            .find(|(name, _)| *name == &caps[2])
+            .map(|(_, day)| day.num_days_from_monday() as i64)?;
+        let date = match caps.get(1).map(|m| m.as_str().trim()) {
+            // The weekday in the following week
+            Some("next") => today + Duration::days(7 - days_from_monday + target),
+            _ => today + Duration::days((target - days_from_monday).rem_euclid(7)),
+        };
+        return Some(date);
+    }
+    if has("next week") {
+        return Some(today + Duration::days(7 - days_from_monday));
+    }
+    if has("next month") {
+        let (y, m) = if today.month() == 12 {
+            (today.year() + 1, 1)
+        } else {
+            (today.year(), today.month() + 1)
+        };
+        return NaiveDate::from_ymd_opt(y, m, 1);
+    }
+    if let Some(caps) = patterns.month_day.captures(&text) {
+        return upcoming_month_day(today, month_number(&caps[1])?, caps[2].parse().ok()?);
+    }
+    if let Some(caps) = patterns.day_month.captures(&text) {
+        return upcoming_month_day(today, month_number(&caps[2])?, caps[1].parse().ok()?);
+    }
+    None
+}
+
+fn followup_title(item: &ActionItemRecord) -> String {
+    let text = item.text.trim();
+    if text.chars().count() > MAX_TITLE_CHARS {
+        let cut: String = text.chars().take(MAX_TITLE_CHARS - 1).collect();
+        format!("Follow up: {}…", cut.trim_end())
+    } else {
+        format!("Follow up: {}", text)
+    }
+}
+
+/// When a follow-up on `date` starts: FOLLOWUP_HOUR local, or the next half
+/// hour if that's already past
+fn followup_start<Tz: TimeZone>(date: NaiveDate, now: &DateTime<Tz>) -> Option<DateTime<Utc>> {
+    let at_hour = date.and_time(NaiveTime::from_hms_opt(FOLLOWUP_HOUR, 0, 0)?);
+    let start = now
+        .timezone()
+        .from_local_datetime(&at_hour)
+        .earliest()?
+        .with_timezone(&Utc);
+    let now = now.with_timezone(&Utc);
+    if start >= now {
+        return Some(start);
+    }
+    let rounded = now + Duration::minutes(30 - (now.minute() % 30) as i64);
+    rounded.with_second(0)?.with_nanosecond(0)
+}
+
+/// Proposals for open action items whose due hint (or text) names a date
+/// that hasn't passed, skipping any already on the calendar
+pub fn propose<Tz: TimeZone>(
+    items: &[ActionItemRecord],
+    meeting_title: &str,
+    now: &DateTime<Tz>,
+) -> Vec<FollowupProposal> {
+    let today = now.date_naive();
+    items
+        .iter()
+        .filter(|item| item.calendar_event_id.is_none())
+        .filter(|item| matches!(item.status.as_str(), "open" | "in_progress"))
+        .filter_map(|item| {
+            let (hint, date) = item
+                .due_hint
+                .iter()
+                .chain(std::iter::once(&item.text))
+                .find_map(|hint| parse_due_date(hint, today).map(|date| (hint.clone(), date)))?;
+            if date < today {
+                return None;
+            }
+            let start = followup_start(date, now)?;
+            let mut notes = format!("From \"{}\": {}", meeting_title, item.text.trim());
+            if let Some(assignee) = &item.assignee {
+                notes.push_str(&format!("\nOwner: {}", assignee));
+            }
+            Some(FollowupProposal {
+                action_item_id: item.id.clone(),
+                title: followup_title(item),
+                start,
+                end: start + Duration::minutes(FOLLOWUP_MINS),
+                notes: Some(notes),
+                calendar_name: None,
+                due_hint: hint,
+            })
+        })
+        .collect()
+}
+
+/// Proposed follow-ups for a meeting's action items
+pub async fn proposals(
+    db: &DatabaseManager,
+    meeting_id: &str,
+) -> Result<Vec<FollowupProposal>, String> {
+    let meeting = db
+        .get_meeting(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))?
+        .ok_or("Meeting not found")?;
+    let items = db
+        .list_action_items(Some(meeting_id))
+        .await
+        .map_err(|e| format!("Failed to list action items: {}", e))?;
+    Ok(propose(&items, &meeting.title, &Local::now()))
+}
+
+/// Create the confirmed follow-ups and store their event ids; items that
+/// already have an event are returned as-is instead of duplicated
+pub async fn schedule(
+    db: &DatabaseManager,
+    meeting_id: &str,
+    confirmed: &[FollowupProposal],
+) -> Result<Vec<ScheduledFollowup>, String> {
+    let items = db
+        .list_action_items(Some(meeting_id))
+        .await
+        .map_err(|e| format!("Failed to list action items: {}", e))?;
+
+    let mut scheduled = Vec::new();
+    for followup in confirmed {
+        let item = items
+            .iter()
+            .find(|item| item.id == followup.action_item_id)
+            .ok_or_else(|| {
+                format!(
+                    "Action item '{}' not found in this meeting",
+                    followup.action_item_id
+                )
+            })?;
+        if let Some(event_id) = &item.calendar_event_id {
+            scheduled.push(ScheduledFollowup {
+                action_item_id: item.id.clone(),
+                event_id: event_id.clone(),
+                created: false,
+            });
+            continue;
+        }
+
+        let event_id = CalendarClient::create_calendar_event(
+            &followup.title,
+            followup.start,
+            followup.end,
+            followup.notes.as_deref(),
+            followup.calendar_name.as_deref(),
+        )?;
+        // Stored per item, so a failure later in the list doesn't lose the
+        // events already created
+        db.set_action_item_calendar_event(&item.id, &event_id)
+            .await
+            .map_err(|e| format!("Failed to save calendar event id: {}", e))?;
+        log::info!("📅 Scheduled follow-up '{}'", followup.title);
+        scheduled.push(ScheduledFollowup {
+            action_item_id: item.id.clone(),
+            event_id,
+            created: true,
+        });
+    }
+    Ok(scheduled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_due_date() {
+        // Tuesday
+        let today = date("2026-03-03");
+        let parse = |hint: &str| parse_due_date(hint, today);
+
+        assert_eq!(parse("by Friday"), Some(date("2026-03-06")));
+        assert_eq!(parse("next Tuesday"), Some(date("2026-03-10")));
+        assert_eq!(parse("Tuesday"), Some(today));
+        assert_eq!(parse("send the deck by EOD"), Some(today));
+        assert_eq!(parse("tomorrow morning"), Some(date("2026-03-04")));
+        assert_eq!(parse("in two weeks"), Some(date("2026-03-17")));
+        assert_eq!(parse("end of the month"), Some(date("2026-03-31")));
+        assert_eq!(parse("next week"), Some(date("2026-03-09")));
+        assert_eq!(parse("March 20th"), Some(date("2026-03-20")));
+        assert_eq!(parse("1st of Feb"), Some(date("2027-02-01")));
+        assert_eq!(parse("2026-04-01"), Some(date("2026-04-01")));
+        assert_eq!(parse("soon"), None);
+        // "monitor" isn't Monday
+        assert_eq!(parse("monitor the rollout"), None);
+    }
+
+    #[test]
+    fn test_propose_skips_scheduled_and_closed_items() {
+        let item = |id: &str, text: &str, due: Option<&str>, status: &str| ActionItemRecord {
+            id: id.to_string(),
+            meeting_id: "m1".to_string(),
+            text: text.to_string(),
+            assignee: Some("Dana".to_string()),
+            due_hint: due.map(str::to_string),
+            status: status.to_string(),
+            source: "manual".to_string(),
+            source_transcript_id: None,
+            calendar_event_id: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let mut scheduled = item("a3", "Book venue by Monday", None, "open");
+        scheduled.calendar_event_id = Some("EK-1".to_string());
+        let items = vec![
+            item("a1", "Send pricing to Acme", Some("by Friday"), "open"),
+            item("a2", "Review contract next Tuesday", None, "open"),
+            scheduled,
+            item("a4", "Draft memo by Friday", None, "done"),
+            item("a5", "Think about hiring", None, "open"),
+        ];
+        // Tuesday 10:00 UTC
+        let now = Utc.with_ymd_and_hms(2026, 3, 3, 10, 0, 0).unwrap();
+
+        let proposals = propose(&items, "Pricing sync", &now);
+        let ids: Vec<&str> = proposals
+            .iter()
+            .map(|p| p.action_item_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["a1", "a2"]);
+        assert_eq!(proposals[0].title, "Follow up: Send pricing to Acme");
+        assert_eq!(
+            proposals[0].start,
+            Utc.with_ymd_and_hms(2026, 3, 6, 9, 0, 0).unwrap()
+        );
+        assert_eq!(proposals[0].end - proposals[0].start, Duration::minutes(30));
+        assert_eq!(proposals[1].due_hint, "Review contract next Tuesday");
+        assert!(proposals[0]
+            .notes
+            .as_deref()
+            .unwrap()
+            .contains("Owner: Dana"));
+    }
+}
@@ -0,0 +1,306 @@
+// noFriction Meetings - Frame Backfill
+// Requeues keyframes that were already analyzed (or never queued) so the VLM
+// scheduler re-runs them with the current prompts, e.g. after a prompt change.
+// Requeued frames are flagged `reanalyze` so they skip the VLM cache.
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::query::Query;
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{Pool, Row, Sqlite};
+
+/// Which keyframes to requeue
+#[derive(Debug, Clone)]
+pub enum BackfillScope {
+    Meeting(String),
+    /// Captured between the bounds (inclusive; open-ended when None)
+    Range {
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    },
+}
+
+impl BackfillScope {
+    /// A meeting, or else a date range. Bounds are RFC 3339 or a bare local
+    /// date ("2024-03-31"), which covers the whole day.
+    pub fn from_params(
+        meeting_id: Option<String>,
+        date_from: Option<&str>,
+        date_to: Option<&str>,
+    ) -> Result<Self, String> {
+        if let Some(id) = meeting_id.filter(|id| !id.trim().is_empty()) {
+            return Ok(Self::Meeting(id));
+        }
+        if date_from.is_none() && date_to.is_none() {
+            return Err("Pick a meeting or a date range to requeue".to_string());
+        }
+        let from = date_from.map(|d| parse_bound(d, false)).transpose()?;
+        let to = date_to.map(|d| parse_bound(d, true)).transpose()?;
+        if let (Some(f), Some(t)) = (from, to) {
+            if f > t {
+                return Err("Start date is after end date".to_string());
+            }
+        }
+        Ok(Self::Range { from, to })
+    }
+
+    /// Filter on `frame_queue q` plus its bind values
+    fn queue_filter(&self) -> (&'static str, Vec<String>) {
+        match self {
+            Self::Meeting(id) => (
+                "(q.frame_id IN (SELECT id FROM frames WHERE meeting_id = ?)
+                  OR q.frame_path IN (SELECT keyframe_path FROM screen_states WHERE meeting_id = ?))",
+                vec![id.clone(), id.clone()],
+            ),
+            Self::Range { from, to } => ("q.captured_at >= ? AND q.captured_at <= ?", bounds(from, to)),
+        }
+    }
+
+    /// Filter on `screen_states s` plus its bind values
+    fn state_filter(&self) -> (&'static str, Vec<String>) {
+        match self {
+            Self::Meeting(id) => ("s.meeting_id = ?", vec![id.clone()]),
+            Self::Range { from, to } => ("s.start_ts >= ? AND s.start_ts <= ?", bounds(from, to)),
+        }
+    }
+}
+
+fn parse_bound(value: &str, end_of_day: bool) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(t) = DateTime::parse_from_rfc3339(value) {
+        return Ok(t.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}' (expected YYYY-MM-DD or RFC 3339)", value))?;
+    let time = if end_of_day {
+        date.and_hms_opt(23, 59, 59)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    };
+    time.and_then(|t| t.and_local_timezone(Local).earliest())
+        .map(|t| t.with_timezone(&Utc))
+        .ok_or_else(|| format!("Invalid date '{}'", value))
+}
+
+/// RFC 3339 bounds; "~" sorts after any timestamp
+fn bounds(from: &Option<DateTime<Utc>>, to: &Option<DateTime<Utc>>) -> Vec<String> {
+    vec![
+        from.map(|t| t.to_rfc3339()).unwrap_or_default(),
+        to.map(|t| t.to_rfc3339())
+            .unwrap_or_else(|| "~".to_string()),
+    ]
+}
+
+/// Result of `requeue_frames`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RequeueSummary {
+    /// Frames now waiting for analysis (reset + newly queued)
+    pub frames_requeued: u64,
+    /// Screen-state keyframes that had never been queued
+    pub newly_queued: u64,
+    /// Activities removed because `overwrite` was set
+    pub activities_deleted: u64,
+    /// Pinecone ids of the removed activities (for the caller to delete)
+    pub pinecone_ids: Vec<String>,
+}
+
+/// Reset the analyzed flag on matching queued frames and queue screen-state
+/// keyframes that never were. With `overwrite`, the activities (and their
+/// entities) produced from those frames are deleted first.
+pub async fn requeue_frames(
+    pool: &Pool<Sqlite>,
+    scope: &BackfillScope,
+    overwrite: bool,
+) -> Result<RequeueSummary, String> {
+    let (queue_filter, queue_binds) = scope.queue_filter();
+    let (state_filter, state_binds) = scope.state_filter();
+    let mut summary = RequeueSummary::default();
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    if overwrite {
+        let activities = format!(
+            "SELECT a.id FROM activity_log a
+             JOIN frame_queue q ON a.frame_ids = CAST(q.id AS TEXT)
+             WHERE q.analyzed = 1 AND {}",
+            queue_filter
+        );
+
+        let sql = format!(
+            "SELECT pinecone_id FROM activity_log WHERE pinecone_id IS NOT NULL AND id IN ({})",
+            activities
+        );
+        summary.pinecone_ids = bound(&sql, &queue_binds)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to load activities: {}", e))?
+            .iter()
+            .map(|r| r.get("pinecone_id"))
+            .collect();
+
+        let sql = format!("DELETE FROM entities WHERE activity_id IN ({})", activities);
+        bound(&sql, &queue_binds)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to delete entities: {}", e))?;
+
+        let sql = format!("DELETE FROM activity_log WHERE id IN ({})", activities);
+        summary.activities_deleted = bound(&sql, &queue_binds)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to delete activities: {}", e))?
+            .rows_affected();
+    }
+
+    let sql = format!(
+        "UPDATE frame_queue SET analyzed = 0, reanalyze = 1
+         WHERE id IN (SELECT q.id FROM frame_queue q WHERE q.analyzed = 1 AND {})",
+        queue_filter
+    );
+    let reset = bound(&sql, &queue_binds)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to requeue frames: {}", e))?
+        .rows_affected();
+
+    let sql = format!(
+        "INSERT INTO frame_queue (frame_path, captured_at, reanalyze)
+         SELECT s.keyframe_path, s.start_ts, 1 FROM screen_states s
+         WHERE s.keyframe_path IS NOT NULL AND s.keyframe_path != '' AND {}
+           AND NOT EXISTS (SELECT 1 FROM frame_queue q WHERE q.frame_path = s.keyframe_path)",
+        state_filter
+    );
+    summary.newly_queued = bound(&sql, &state_binds)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to queue keyframes: {}", e))?
+        .rows_affected();
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit requeue: {}", e))?;
+
+    summary.frames_requeued = reset + summary.newly_queued;
+    Ok(summary)
+}
+
+fn bound<'q>(sql: &'q str, binds: &'q [String]) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+    binds
+        .iter()
+        .fold(sqlx::query(sql), |query, b| query.bind(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> Pool<Sqlite> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        for sql in [
+            "CREATE TABLE frames (id INTEGER PRIMARY KEY, meeting_id TEXT NOT NULL, file_path TEXT)",
+            "CREATE TABLE screen_states (state_id TEXT PRIMARY KEY, meeting_id TEXT NOT NULL, start_ts TEXT NOT NULL, keyframe_path TEXT)",
+            "CREATE TABLE frame_queue (id INTEGER PRIMARY KEY AUTOINCREMENT, frame_id INTEGER, frame_path TEXT NOT NULL, captured_at TEXT NOT NULL, analyzed INTEGER NOT NULL DEFAULT 0, synced INTEGER NOT NULL DEFAULT 0, reanalyze INTEGER NOT NULL DEFAULT 0)",
+            "CREATE TABLE activity_log (id INTEGER PRIMARY KEY AUTOINCREMENT, summary TEXT NOT NULL, frame_ids TEXT, pinecone_id TEXT)",
+            "CREATE TABLE entities (id INTEGER PRIMARY KEY AUTOINCREMENT, activity_id INTEGER NOT NULL, name TEXT NOT NULL)",
+            // m1: one analyzed frame with a synced activity, one never-queued keyframe
+            "INSERT INTO frames (id, meeting_id, file_path) VALUES (1, 'm1', '/f/1.jpg')",
+            "INSERT INTO screen_states VALUES ('s1', 'm1', '2024-03-01T10:05:00+00:00', '/f/s1.jpg')",
+            "INSERT INTO frame_queue (id, frame_id, frame_path, captured_at, analyzed) VALUES (1, 1, '/f/1.jpg', '2024-03-01T10:00:00+00:00', 1)",
+            "INSERT INTO activity_log (id, summary, frame_ids, pinecone_id) VALUES (1, 'old', '1', 'activity_1')",
+            "INSERT INTO entities (activity_id, name) VALUES (1, 'Acme')",
+            // m2: analyzed a day later
+            "INSERT INTO frames (id, meeting_id, file_path) VALUES (2, 'm2', '/f/2.jpg')",
+            "INSERT INTO frame_queue (id, frame_id, frame_path, captured_at, analyzed) VALUES (2, 2, '/f/2.jpg', '2024-03-02T10:00:00+00:00', 1)",
+            "INSERT INTO activity_log (id, summary, frame_ids) VALUES (2, 'keep', '2')",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        pool
+    }
+
+    async fn count(pool: &Pool<Sqlite>, sql: &str) -> i64 {
+        sqlx::query_as::<_, (i64,)>(sql)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+            .0
+    }
+
+    #[tokio::test]
+    async fn requeue_meeting_with_overwrite() {
+        let pool = setup_test_db().await;
+        let scope = BackfillScope::Meeting("m1".to_string());
+
+        let summary = requeue_frames(&pool, &scope, true).await.unwrap();
+        assert_eq!(summary.frames_requeued, 2);
+        assert_eq!(summary.newly_queued, 1);
+        assert_eq!(summary.activities_deleted, 1);
+        assert_eq!(summary.pinecone_ids, vec!["activity_1".to_string()]);
+
+        assert_eq!(count(&pool, "SELECT COUNT(*) FROM activity_log").await, 1);
+        assert_eq!(count(&pool, "SELECT COUNT(*) FROM entities").await, 0);
+        assert_eq!(
+            count(
+                &pool,
+                "SELECT COUNT(*) FROM frame_queue WHERE analyzed = 0 AND reanalyze = 1"
+            )
+            .await,
+            2
+        );
+        // The other meeting is untouched
+        assert_eq!(
+            count(&pool, "SELECT analyzed FROM frame_queue WHERE id = 2").await,
+            1
+        );
+
+        // Nothing left to requeue; the keyframe isn't queued twice
+        let again = requeue_frames(&pool, &scope, true).await.unwrap();
+        assert_eq!(again.frames_requeued, 0);
+    }
+
+    #[test]
+    fn scope_from_params() {
+        assert!(matches!(
+            BackfillScope::from_params(Some("m1".to_string()), Some("2024-03-01"), None),
+            Ok(BackfillScope::Meeting(_))
+        ));
+        assert!(BackfillScope::from_params(None, None, None).is_err());
+        assert!(BackfillScope::from_params(None, Some("March"), None).is_err());
+        assert!(BackfillScope::from_params(None, Some("2024-03-02"), Some("2024-03-01")).is_err());
+
+        match BackfillScope::from_params(None, Some("2024-03-01"), Some("2024-03-01")).unwrap() {
+            BackfillScope::Range {
+                from: Some(from),
+                to: Some(to),
+            } => assert_eq!((to - from).num_seconds(), 86_399),
+            other => panic!("unexpected scope {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn requeue_range_keeps_activities() {
+        let pool = setup_test_db().await;
+        let scope = BackfillScope::Range {
+            from: Some("2024-03-02T00:00:00Z".parse().unwrap()),
+            to: None,
+        };
+
+        let summary = requeue_frames(&pool, &scope, false).await.unwrap();
+        assert_eq!(summary.frames_requeued, 1);
+        assert_eq!(summary.newly_queued, 0);
+        assert_eq!(summary.activities_deleted, 0);
+        assert_eq!(count(&pool, "SELECT COUNT(*) FROM activity_log").await, 2);
+        assert_eq!(
+            count(&pool, "SELECT analyzed FROM frame_queue WHERE id = 1").await,
+            1
+        );
+    }
+}
@@ -5,8 +5,11 @@
 use chrono::{DateTime, Utc};
 use image::DynamicImage;
 use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+
+use crate::chunk_manager::ClipSegment;
 
 // VideoChunk type is available in video_recorder but we don't need it here
 
@@ -62,8 +65,8 @@ impl FrameExtractor {
         std::fs::create_dir_all(&frames_dir)
             .map_err(|e| format!("Failed to create frames directory: {}", e))?;
 
-        // Output filename based on timestamp
-        let frame_name = format!("frame_{:.3}.jpg", timestamp_secs);
+        // Output filename based on chunk and timestamp (offsets repeat per chunk)
+        let frame_name = format!("{}_frame_{:.3}.jpg", chunk_name(video_path), timestamp_secs);
         let output_path = frames_dir.join(&frame_name);
 
         // Skip if already extracted
@@ -146,7 +149,7 @@ impl FrameExtractor {
         std::fs::create_dir_all(&thumbs_dir)
             .map_err(|e| format!("Failed to create thumbnails directory: {}", e))?;
 
-        let thumb_name = format!("thumb_{:.3}.jpg", timestamp_secs);
+        let thumb_name = format!("{}_thumb_{:.3}.jpg", chunk_name(video_path), timestamp_secs);
         let output_path = thumbs_dir.join(&thumb_name);
 
         if output_path.exists() {
@@ -305,6 +308,97 @@ impl FrameExtractor {
             .map_err(|_| "Failed to parse duration".to_string())
     }
 
+    /// Cut `segments` (possibly from several chunks) into one MP4 at `output`,
+    /// optionally burning in an SRT file. Calls `on_progress` with 0.0-1.0 as
+    /// ffmpeg encodes; returns the clip duration in seconds.
+    pub fn export_clip(
+        &self,
+        segments: &[ClipSegment],
+        subtitles: Option<&Path>,
+        output: &Path,
+        mut on_progress: impl FnMut(f32),
+    ) -> Result<f64, String> {
+        if !crate::audio_recorder::ffmpeg_available() {
+            return Err(
+                "ffmpeg is required to export highlights. Install it (e.g. `brew install ffmpeg`) and try again."
+                    .to_string(),
+            );
+        }
+        let duration: f64 = segments.iter().map(|s| s.end_secs - s.start_secs).sum();
+        if duration <= 0.0 {
+            return Err("Highlight is empty".to_string());
+        }
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create output directory: {}", e))?;
+        }
+
+        // The concat demuxer joins the pieces, trimming each with inpoint/outpoint
+        let mut list = String::new();
+        for segment in segments {
+            let path = segment.path.to_string_lossy().replace('\'', "'\\''");
+            list.push_str(&format!(
+                "file '{}'\ninpoint {:.3}\noutpoint {:.3}\n",
+                path, segment.start_secs, segment.end_secs
+            ));
+        }
+        let list_path =
+            std::env::temp_dir().join(format!("nofriction_clip_{}.txt", uuid::Uuid::new_v4()));
+        std::fs::write(&list_path, list)
+            .map_err(|e| format!("Failed to write clip list: {}", e))?;
+
+        let mut command = Command::new("ffmpeg");
+        command
+            .args(["-y", "-loglevel", "error", "-nostats"])
+            .args(["-progress", "pipe:1"])
+            .args(["-f", "concat", "-safe", "0", "-i"])
+            .arg(&list_path);
+        if let Some(srt) = subtitles {
+            command.args(["-vf", &format!("subtitles='{}'", srt.to_string_lossy())]);
+        }
+        command
+            .args(["-c:v", "libx264", "-preset", "veryfast"])
+            .args(["-pix_fmt", "yuv420p"])
+            .args(["-movflags", "+faststart", "-an"])
+            .arg(output)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let result = (|| {
+            let mut child = command
+                .spawn()
+                .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+            // -progress reports out_time_us (out_time_ms on older builds, also µs)
+            if let Some(stdout) = child.stdout.take() {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    let Some((key, value)) = line.split_once('=') else {
+                        continue;
+                    };
+                    if key == "out_time_us" || key == "out_time_ms" {
+                        if let Ok(us) = value.trim().parse::<f64>() {
+                            on_progress((us / 1_000_000.0 / duration).clamp(0.0, 1.0) as f32);
+                        }
+                    }
+                }
+            }
+
+            let output = child
+                .wait_with_output()
+                .map_err(|e| format!("Failed to wait for ffmpeg: {}", e))?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("ffmpeg failed: {}", stderr.trim()));
+            }
+            Ok(())
+        })();
+
+        let _ = std::fs::remove_file(&list_path);
+        result?;
+        on_progress(1.0);
+        Ok(duration)
+    }
+
     fn load_frame_metadata(
         &self,
         path: &Path,
@@ -329,6 +423,14 @@ impl FrameExtractor {
     }
 }
 
+/// Cache name prefix for frames taken from `video_path`
+fn chunk_name(video_path: &Path) -> &str {
+    video_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("video")
+}
+
 impl Default for FrameExtractor {
     fn default() -> Self {
         let cache_dir = dirs::cache_dir()
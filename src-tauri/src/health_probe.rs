@@ -0,0 +1,302 @@
+// noFriction Meetings - Dependency Health Probes
+// Actively checks the services the app talks to (VLM, AI backend, Pinecone,
+// Supabase, ingest, Deepgram, local disk) in parallel, each with a timeout
+//
+// Results are cached for 30 seconds so the admin console can poll freely.
+// Every fresh probe run is compared with the previous one so callers can
+// announce dependencies that changed state.
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Per-probe timeout
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long probe results are reused
+const CACHE_TTL: Duration = Duration::from_secs(30);
+/// Probes slower than this report degraded
+const SLOW_PROBE_MS: u64 = 2_000;
+/// Free space under the app data dir below which disk is degraded / down
+#[cfg(unix)]
+const DISK_LOW_BYTES: u64 = 5 * 1024 * 1024 * 1024;
+#[cfg(unix)]
+const DISK_CRITICAL_BYTES: u64 = 500 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProbeStatus {
+    Ok,
+    Degraded,
+    Down,
+}
+
+/// Result of probing one dependency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyHealth {
+    pub name: String,
+    pub status: ProbeStatus,
+    pub latency_ms: Option<u64>,
+    pub message: Option<String>,
+    /// Most recent failure, kept after the dependency recovers
+    pub last_error: Option<String>,
+    pub checked_at: String,
+}
+
+/// A probe either succeeds with an optional detail message, reports itself
+/// degraded, or fails
+pub enum ProbeOutcome {
+    Ok(Option<String>),
+    Degraded(String),
+}
+
+pub type ProbeFuture = Pin<Box<dyn Future<Output = Result<ProbeOutcome, String>> + Send>>;
+
+/// A named dependency check
+pub struct Probe {
+    pub name: String,
+    pub future: ProbeFuture,
+}
+
+impl Probe {
+    pub fn new(
+        name: &str,
+        future: impl Future<Output = Result<ProbeOutcome, String>> + Send + 'static,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            future: Box::pin(future),
+        }
+    }
+}
+
+fn classify(
+    result: &Result<ProbeOutcome, String>,
+    latency_ms: u64,
+) -> (ProbeStatus, Option<String>) {
+    match result {
+        Ok(ProbeOutcome::Ok(message)) if latency_ms > SLOW_PROBE_MS => (
+            ProbeStatus::Degraded,
+            Some(message.clone().map_or_else(
+                || format!("Slow response ({} ms)", latency_ms),
+                |m| format!("{} (slow: {} ms)", m, latency_ms),
+            )),
+        ),
+        Ok(ProbeOutcome::Ok(message)) => (ProbeStatus::Ok, message.clone()),
+        Ok(ProbeOutcome::Degraded(message)) => (ProbeStatus::Degraded, Some(message.clone())),
+        Err(_) => (ProbeStatus::Down, None),
+    }
+}
+
+/// Run all probes concurrently, each bounded by PROBE_TIMEOUT
+pub async fn run_probes(probes: Vec<Probe>) -> Vec<DependencyHealth> {
+    let runs = probes.into_iter().map(|probe| async move {
+        let started = Instant::now();
+        let result = match tokio::time::timeout(PROBE_TIMEOUT, probe.future).await {
+            Ok(result) => result,
+            Err(_) => Err(format!("Timed out after {}s", PROBE_TIMEOUT.as_secs())),
+        };
+        let latency_ms = started.elapsed().as_millis() as u64;
+        let (status, message) = classify(&result, latency_ms);
+
+        DependencyHealth {
+            name: probe.name,
+            status,
+            latency_ms: Some(latency_ms),
+            message,
+            last_error: result.err(),
+            checked_at: chrono::Utc::now().to_rfc3339(),
+        }
+    });
+    futures_util::future::join_all(runs).await
+}
+
+/// Cached probe results plus the last error seen per dependency
+pub struct HealthCache {
+    last: Mutex<Option<(Instant, Vec<DependencyHealth>)>>,
+    last_errors: Mutex<HashMap<String, String>>,
+}
+
+impl HealthCache {
+    fn new() -> Self {
+        Self {
+            last: Mutex::new(None),
+            last_errors: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Results from the last run if still within the TTL
+    pub fn fresh(&self) -> Option<Vec<DependencyHealth>> {
+        self.last
+            .lock()
+            .as_ref()
+            .filter(|(at, _)| at.elapsed() < CACHE_TTL)
+            .map(|(_, results)| results.clone())
+    }
+
+    /// Store a new run (filling in remembered errors) and return the
+    /// dependencies whose status differs from the previous run
+    pub fn store(&self, results: &mut [DependencyHealth]) -> Vec<DependencyHealth> {
+        {
+            let mut errors = self.last_errors.lock();
+            for result in results.iter_mut() {
+                match &result.last_error {
+                    Some(error) => {
+                        errors.insert(result.name.clone(), error.clone());
+                    }
+                    None => result.last_error = errors.get(&result.name).cloned(),
+                }
+            }
+        }
+
+        let mut last = self.last.lock();
+        let changed = match last.as_ref() {
+            Some((_, previous)) => results
+                .iter()
+                .filter(|r| {
+                    previous
+                        .iter()
+                        .find(|p| p.name == r.name)
+                        .map_or(true, |p| p.status != r.status)
+                })
+                .cloned()
+                .collect(),
+            // First run: nothing to compare against
+            None => Vec::new(),
+        };
+        *last = Some((Instant::now(), results.to_vec()));
+        changed
+    }
+}
+
+pub fn health_cache() -> &'static HealthCache {
+    static CACHE: OnceLock<HealthCache> = OnceLock::new();
+    CACHE.get_or_init(HealthCache::new)
+}
+
+/// Worst status across dependencies
+pub fn overall_status(dependencies: &[DependencyHealth]) -> ProbeStatus {
+    if dependencies
+        .iter()
+        .any(|d| d.name == "Disk" && d.status == ProbeStatus::Down)
+    {
+        return ProbeStatus::Down;
+    }
+    if dependencies.iter().any(|d| d.status != ProbeStatus::Ok) {
+        return ProbeStatus::Degraded;
+    }
+    ProbeStatus::Ok
+}
+
+/// Available bytes from `df -k <path>` output
+#[cfg_attr(not(unix), allow(dead_code))]
+fn parse_df_available(output: &str) -> Option<u64> {
+    // Long device names can wrap onto their own line, so read the last line
+    // and take the column right before the first "NN%" (Use% / Capacity)
+    let fields: Vec<&str> = output.lines().last()?.split_whitespace().collect();
+    let capacity = fields.iter().position(|f| f.ends_with('%'))?;
+    fields
+        .get(capacity.checked_sub(1)?)?
+        .parse::<u64>()
+        .ok()
+        .map(|kb| kb * 1024)
+}
+
+/// Probe free space on the volume holding `path`
+#[cfg(not(unix))]
+pub async fn probe_disk(_path: &Path) -> Result<ProbeOutcome, String> {
+    Ok(ProbeOutcome::Ok(Some(
+        "Free space check not supported on this platform".to_string(),
+    )))
+}
+
+/// Probe free space on the volume holding `path`
+#[cfg(unix)]
+pub async fn probe_disk(path: &Path) -> Result<ProbeOutcome, String> {
+    let path = path.to_path_buf();
+    let output = tokio::task::spawn_blocking(move || {
+        std::process::Command::new("df")
+            .arg("-k")
+            .arg(&path)
+            .output()
+    })
+    .await
+    .map_err(|e| format!("Disk check failed: {}", e))?
+    .map_err(|e| format!("Failed to run df: {}", e))?;
+
+    let free = parse_df_available(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| "Could not read free disk space".to_string())?;
+    let message = format!(
+        "{} free",
+        crate::chunk_manager::ChunkManager::format_size(free)
+    );
+    if free < DISK_CRITICAL_BYTES {
+        Err(format!("Only {}", message))
+    } else if free < DISK_LOW_BYTES {
+        Ok(ProbeOutcome::Degraded(format!("Low space: {}", message)))
+    } else {
+        Ok(ProbeOutcome::Ok(Some(message)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(name: &str, status: ProbeStatus, error: Option<&str>) -> DependencyHealth {
+        DependencyHealth {
+            name: name.to_string(),
+            status,
+            latency_ms: Some(10),
+            message: None,
+            last_error: error.map(|e| e.to_string()),
+            checked_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_df_available() {
+        let linux = "Filesystem     1K-blocks     Used Available Use% Mounted on\n\
+                     /dev/nvme0n1p2 490617784 90131688 375465524  20% /\n";
+        assert_eq!(parse_df_available(linux), Some(375465524 * 1024));
+
+        let macos = "Filesystem   1024-blocks      Used Available Capacity iused ifree %iused  Mounted on\n\
+                     /dev/disk3s5   971350180 512345678 421234567    55% 1234567 4212345670    0%   /System/Volumes/Data\n";
+        assert_eq!(parse_df_available(macos), Some(421234567 * 1024));
+    }
+
+    #[tokio::test]
+    async fn test_slow_and_failed_probes() {
+        let results = run_probes(vec![
+            Probe::new("fast", async { Ok(ProbeOutcome::Ok(None)) }),
+            Probe::new("broken", async { Err("connection refused".to_string()) }),
+        ])
+        .await;
+        assert_eq!(results[0].status, ProbeStatus::Ok);
+        assert_eq!(results[1].status, ProbeStatus::Down);
+        assert_eq!(results[1].last_error.as_deref(), Some("connection refused"));
+
+        let (status, _) = classify(&Ok(ProbeOutcome::Ok(None)), SLOW_PROBE_MS + 1);
+        assert_eq!(status, ProbeStatus::Degraded);
+    }
+
+    #[test]
+    fn test_cache_reports_flips_and_keeps_last_error() {
+        let cache = HealthCache::new();
+        let mut first = vec![dependency("Pinecone", ProbeStatus::Down, Some("401"))];
+        assert!(cache.store(&mut first).is_empty());
+
+        let mut second = vec![dependency("Pinecone", ProbeStatus::Ok, None)];
+        let changed = cache.store(&mut second);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(second[0].last_error.as_deref(), Some("401"));
+
+        let mut third = vec![dependency("Pinecone", ProbeStatus::Ok, None)];
+        assert!(cache.store(&mut third).is_empty());
+        assert_eq!(overall_status(&third), ProbeStatus::Ok);
+    }
+}
@@ -0,0 +1,166 @@
+// noFriction Meetings - Hybrid Search
+// Reciprocal-rank fusion of local full-text and Pinecone semantic results
+
+use serde::{Deserialize, Serialize};
+
+/// Rank damping constant from the original RRF paper; larger values flatten
+/// the advantage of the top few hits
+const RRF_K: f32 = 60.0;
+
+/// Which backends a knowledge base search queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMode {
+    /// Local SQLite full-text search only
+    Local,
+    /// Pinecone only
+    Semantic,
+    /// Both, fused by rank
+    Hybrid,
+}
+
+impl SearchMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::Semantic => "semantic",
+            Self::Hybrid => "hybrid",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "local" => Some(Self::Local),
+            "semantic" => Some(Self::Semantic),
+            "hybrid" => Some(Self::Hybrid),
+            _ => None,
+        }
+    }
+
+    pub fn uses_local(&self) -> bool {
+        matches!(self, Self::Local | Self::Hybrid)
+    }
+
+    pub fn uses_semantic(&self) -> bool {
+        matches!(self, Self::Semantic | Self::Hybrid)
+    }
+}
+
+/// One ranked result list from a single backend
+pub struct RankedList<T> {
+    /// Reported in `match_sources` ("local", "pinecone", ...)
+    pub backend: &'static str,
+    /// Best first; the key identifies the underlying record across backends
+    pub hits: Vec<(String, T)>,
+}
+
+/// A deduplicated hit with its fused score
+#[derive(Debug, Clone)]
+pub struct FusedHit<T> {
+    pub key: String,
+    pub item: T,
+    /// Normalized so the best hit scores 1.0
+    pub score: f32,
+    pub match_sources: Vec<String>,
+}
+
+/// Merge ranked lists with reciprocal-rank fusion. A record found by several
+/// backends keeps the item from the first list it appears in and accumulates
+/// `1 / (k + rank)` from each.
+pub fn fuse<T>(lists: Vec<RankedList<T>>) -> Vec<FusedHit<T>> {
+    let mut fused: Vec<FusedHit<T>> = Vec::new();
+
+    for list in lists {
+        for (rank, (key, item)) in list.hits.into_iter().enumerate() {
+            let contribution = 1.0 / (RRF_K + rank as f32 + 1.0);
+            match fused.iter_mut().find(|h| h.key == key) {
+                Some(hit) => {
+                    hit.score += contribution;
+                    if !hit.match_sources.iter().any(|s| s == list.backend) {
+                        hit.match_sources.push(list.backend.to_string());
+                    }
+                }
+                None => fused.push(FusedHit {
+                    key,
+                    item,
+                    score: contribution,
+                    match_sources: vec![list.backend.to_string()],
+                }),
+            }
+        }
+    }
+
+    fused.sort_by(|a, b| b.score.total_cmp(&a.score));
+    if let Some(top) = fused.first().map(|h| h.score) {
+        for hit in &mut fused {
+            hit.score /= top;
+        }
+    }
+    fused
+}
+
+/// Dedup key for a Pinecone match, matching the keys used for local records.
+/// Transcript vectors carry their row id in metadata; activity vectors embed it
+/// in the vector id (`activity_<id>`).
+pub fn pinecone_key(id: &str, metadata: Option<&serde_json::Value>) -> String {
+    if let Some(transcript_id) = metadata
+        .and_then(|m| m.get("transcript_id"))
+        .and_then(|v| v.as_i64())
+    {
+        return format!("transcript:{}", transcript_id);
+    }
+    if let Some(activity_id) = id.strip_prefix("activity_") {
+        return format!("activity:{}", activity_id);
+    }
+    if let Some(transcript_id) = id
+        .strip_prefix("transcript_")
+        .and_then(|rest| rest.rsplit('_').next())
+    {
+        return format!("transcript:{}", transcript_id);
+    }
+    format!("pinecone:{}", id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuse_dedupes_and_ranks_shared_hits_first() {
+        let fused = fuse(vec![
+            RankedList {
+                backend: "local",
+                hits: vec![
+                    ("transcript:1".to_string(), "local one"),
+                    ("transcript:2".to_string(), "local two"),
+                ],
+            },
+            RankedList {
+                backend: "pinecone",
+                hits: vec![
+                    ("activity:9".to_string(), "semantic nine"),
+                    ("transcript:2".to_string(), "semantic two"),
+                ],
+            },
+        ]);
+
+        assert_eq!(fused.len(), 3);
+        assert_eq!(fused[0].key, "transcript:2");
+        assert_eq!(fused[0].item, "local two");
+        assert_eq!(fused[0].match_sources, vec!["local", "pinecone"]);
+        assert_eq!(fused[0].score, 1.0);
+        assert!(fused[1].score < 1.0);
+    }
+
+    #[test]
+    fn test_pinecone_key() {
+        let meta = serde_json::json!({ "type": "transcript", "transcript_id": 42 });
+        assert_eq!(
+            pinecone_key("transcript_m1_42", Some(&meta)),
+            "transcript:42"
+        );
+        assert_eq!(pinecone_key("transcript_m-1_7", None), "transcript:7");
+        assert_eq!(pinecone_key("activity_12", None), "activity:12");
+        assert_eq!(pinecone_key("3f2a", None), "pinecone:3f2a");
+    }
+}
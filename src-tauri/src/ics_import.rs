@@ -0,0 +1,841 @@
+// noFriction Meetings - ICS Import
+// Reads .ics files into calendar events for users without EventKit access
+//
+// Handles folded lines, TZID / UTC / floating times, RRULE expansion,
+// EXDATE and RECURRENCE-ID overrides. All-day events are skipped like the
+// EventKit path does.
+
+use crate::calendar_client::{extract_meeting_url, CalendarEventNative};
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Recurring events are expanded at least this far ahead
+pub const RECURRENCE_HORIZON_DAYS: i64 = 14;
+/// Events that started this long ago are still returned
+const LOOKBEHIND_HOURS: i64 = 2;
+/// Guard against runaway rules (e.g. FREQ=DAILY with no end)
+const MAX_RECURRENCE_PERIODS: usize = 5000;
+
+/// One content line: NAME;PARAM=VALUE:VALUE
+#[derive(Debug, Clone)]
+struct Property {
+    name: String,
+    params: HashMap<String, String>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(|v| v.as_str())
+    }
+}
+
+/// DTSTART-style value with its timezone semantics
+#[derive(Debug, Clone)]
+enum IcsTime {
+    Utc(NaiveDateTime),
+    Zoned(NaiveDateTime, Tz),
+    /// No zone: the user's local time
+    Floating(NaiveDateTime),
+    Date(NaiveDate),
+}
+
+impl IcsTime {
+    /// Wall-clock time in the value's own zone
+    fn naive(&self) -> NaiveDateTime {
+        match self {
+            IcsTime::Utc(n) | IcsTime::Zoned(n, _) | IcsTime::Floating(n) => *n,
+            IcsTime::Date(d) => d.and_time(NaiveTime::MIN),
+        }
+    }
+
+    /// Resolve a wall-clock time in this value's zone
+    fn resolve(&self, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+        // Times skipped by a DST jump resolve to the hour after
+        fn local<Z: TimeZone>(zone: &Z, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+            zone.from_local_datetime(&naive)
+                .earliest()
+                .or_else(|| {
+                    zone.from_local_datetime(&(naive + Duration::hours(1)))
+                        .earliest()
+                })
+                .map(|t| t.with_timezone(&Utc))
+        }
+        match self {
+            IcsTime::Utc(_) => Some(Utc.from_utc_datetime(&naive)),
+            IcsTime::Zoned(_, tz) => local(tz, naive),
+            IcsTime::Floating(_) | IcsTime::Date(_) => local(&Local, naive),
+        }
+    }
+
+    fn to_utc(&self) -> Option<DateTime<Utc>> {
+        self.resolve(self.naive())
+    }
+
+    fn is_date(&self) -> bool {
+        matches!(self, IcsTime::Date(_))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The subset of RRULE we expand
+#[derive(Debug, Clone)]
+struct RecurrenceRule {
+    freq: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<IcsTime>,
+    /// (ordinal within month, weekday); ordinal only applies to MONTHLY
+    by_day: Vec<(Option<i32>, Weekday)>,
+    by_month_day: Vec<i32>,
+}
+
+/// A parsed VEVENT before expansion
+#[derive(Debug, Clone, Default)]
+struct RawEvent {
+    uid: Option<String>,
+    summary: Option<String>,
+    start: Option<IcsTime>,
+    end: Option<IcsTime>,
+    duration: Option<Duration>,
+    location: Option<String>,
+    description: Option<String>,
+    url: Option<String>,
+    attendees: Vec<String>,
+    rrule: Option<String>,
+    exdates: Vec<IcsTime>,
+    recurrence_id: Option<IcsTime>,
+    cancelled: bool,
+}
+
+/// Undo RFC 5545 line folding (continuation lines start with a space or tab)
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.split('\n') {
+        let line = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(rest) = line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        if !line.is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+    lines
+}
+
+fn parse_property(line: &str) -> Option<Property> {
+    // The value starts at the first colon outside a quoted parameter
+    let mut in_quotes = false;
+    let mut split_at = None;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ':' if !in_quotes => {
+                split_at = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let split_at = split_at?;
+    let (head, value) = (&line[..split_at], &line[split_at + 1..]);
+
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_uppercase();
+    let params = parts
+        .filter_map(|p| {
+            let (k, v) = p.split_once('=')?;
+            Some((
+                k.trim().to_uppercase(),
+                v.trim().trim_matches('"').to_string(),
+            ))
+        })
+        .collect();
+
+    Some(Property {
+        name,
+        params,
+        value: value.to_string(),
+    })
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => {}
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Common Outlook/Exchange zone names that are not IANA ids
+fn windows_zone(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "Eastern Standard Time" => "America/New_York",
+        "Central Standard Time" => "America/Chicago",
+        "Mountain Standard Time" => "America/Denver",
+        "US Mountain Standard Time" => "America/Phoenix",
+        "Pacific Standard Time" => "America/Los_Angeles",
+        "Alaskan Standard Time" => "America/Anchorage",
+        "Hawaiian Standard Time" => "Pacific/Honolulu",
+        "E. South America Standard Time" => "America/Sao_Paulo",
+        "GMT Standard Time" => "Europe/London",
+        "W. Europe Standard Time" => "Europe/Berlin",
+        "Romance Standard Time" => "Europe/Paris",
+        "Central Europe Standard Time" => "Europe/Budapest",
+        "Central European Standard Time" => "Europe/Warsaw",
+        "India Standard Time" => "Asia/Kolkata",
+        "China Standard Time" => "Asia/Shanghai",
+        "Singapore Standard Time" => "Asia/Singapore",
+        "Tokyo Standard Time" => "Asia/Tokyo",
+        "AUS Eastern Standard Time" => "Australia/Sydney",
+        "UTC" | "Coordinated Universal Time" => "UTC",
+        _ => return None,
+    })
+}
+
+fn parse_tzid(tzid: &str) -> Option<Tz> {
+    let tzid = tzid.trim();
+    if let Ok(tz) = tzid.parse::<Tz>() {
+        return Some(tz);
+    }
+    if let Some(tz) = windows_zone(tzid).and_then(|name| name.parse::<Tz>().ok()) {
+        return Some(tz);
+    }
+    // Some exporters prefix the IANA id, e.g. "/mozilla.org/20050126_1/America/New_York"
+    let segments: Vec<&str> = tzid.split('/').filter(|s| !s.is_empty()).collect();
+    (1..segments.len())
+        .rev()
+        .find_map(|i| segments[segments.len() - i..].join("/").parse::<Tz>().ok())
+}
+
+/// Parse a single DATE or DATE-TIME value
+fn parse_time(value: &str, tzid: Option<&str>, is_date: bool) -> Option<IcsTime> {
+    let value = value.trim();
+    if is_date || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(IcsTime::Date);
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        return NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(IcsTime::Utc);
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    match tzid {
+        Some(tzid) => match parse_tzid(tzid) {
+            Some(tz) => Some(IcsTime::Zoned(naive, tz)),
+            None => {
+                log::warn!("Unknown TZID '{}' in ICS file; using local time", tzid);
+                Some(IcsTime::Floating(naive))
+            }
+        },
+        None => Some(IcsTime::Floating(naive)),
+    }
+}
+
+fn parse_time_property(prop: &Property) -> Option<IcsTime> {
+    parse_time(
+        &prop.value,
+        prop.param("TZID"),
+        prop.param("VALUE") == Some("DATE"),
+    )
+}
+
+/// ISO 8601 duration as used by DURATION (e.g. PT1H30M, P1D, P1W)
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let value = value.strip_prefix('P')?;
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in value.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                total += match unit {
+                    'W' => Duration::weeks(n),
+                    'D' => Duration::days(n),
+                    'H' => Duration::hours(n),
+                    'M' => Duration::minutes(n),
+                    'S' => Duration::seconds(n),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    Some(if negative { -total } else { total })
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    Some(match code {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+fn parse_rrule(value: &str, start: &IcsTime) -> Option<RecurrenceRule> {
+    let mut rule = RecurrenceRule {
+        freq: Frequency::Daily,
+        interval: 1,
+        count: None,
+        until: None,
+        by_day: Vec::new(),
+        by_month_day: Vec::new(),
+    };
+    let mut freq = None;
+
+    for part in value.split(';') {
+        let Some((key, val)) = part.split_once('=') else {
+            continue;
+        };
+        match key.trim().to_uppercase().as_str() {
+            "FREQ" => {
+                freq = match val.trim().to_uppercase().as_str() {
+                    "DAILY" => Some(Frequency::Daily),
+                    "WEEKLY" => Some(Frequency::Weekly),
+                    "MONTHLY" => Some(Frequency::Monthly),
+                    "YEARLY" => Some(Frequency::Yearly),
+                    _ => None,
+                }
+            }
+            "INTERVAL" => rule.interval = val.trim().parse().unwrap_or(1).max(1),
+            "COUNT" => rule.count = val.trim().parse().ok(),
+            // UNTIL shares DTSTART's zone when it is not UTC
+            "UNTIL" => {
+                let tzid = match start {
+                    IcsTime::Zoned(_, tz) => Some(tz.name()),
+                    _ => None,
+                };
+                rule.until = parse_time(val, tzid, false);
+            }
+            "BYDAY" => {
+                rule.by_day = val
+                    .split(',')
+                    .filter_map(|d| {
+                        let d = d.trim().to_uppercase();
+                        let (ordinal, code) = d.split_at(d.len().saturating_sub(2));
+                        let weekday = parse_weekday(code)?;
+                        let ordinal = if ordinal.is_empty() {
+                            None
+                        } else {
+                            Some(ordinal.trim_start_matches('+').parse().ok()?)
+                        };
+                        Some((ordinal, weekday))
+                    })
+                    .collect()
+            }
+            "BYMONTHDAY" => {
+                rule.by_month_day = val
+                    .split(',')
+                    .filter_map(|d| d.trim().parse().ok())
+                    .collect()
+            }
+            _ => {}
+        }
+    }
+
+    rule.freq = freq?;
+    Some(rule)
+}
+
+fn add_months(year: i32, month: u32, months: u32) -> (i32, u32) {
+    let zero_based = month - 1 + months;
+    (year + (zero_based / 12) as i32, zero_based % 12 + 1)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = add_months(year, month, 1);
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .map(|d| d.day())
+        .unwrap_or(28)
+}
+
+/// Dates in one month matching BYDAY with optional ordinals (1MO, -1FR, TU)
+fn month_weekdays(year: i32, month: u32, by_day: &[(Option<i32>, Weekday)]) -> Vec<NaiveDate> {
+    let last = days_in_month(year, month);
+    let mut dates = Vec::new();
+    for (ordinal, weekday) in by_day {
+        let matching: Vec<NaiveDate> = (1..=last)
+            .filter_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+            .filter(|d| d.weekday() == *weekday)
+            .collect();
+        match ordinal {
+            None => dates.extend(matching),
+            Some(n) if *n > 0 => dates.extend(matching.get(*n as usize - 1)),
+            Some(n) => dates.extend(
+                matching
+                    .len()
+                    .checked_sub(n.unsigned_abs() as usize)
+                    .and_then(|i| matching.get(i)),
+            ),
+        }
+    }
+    dates
+}
+
+/// Candidate dates for the `period`-th recurrence period
+fn period_dates(rule: &RecurrenceRule, first: NaiveDate, period: u32) -> Vec<NaiveDate> {
+    let step = period * rule.interval;
+    let mut dates = match rule.freq {
+        Frequency::Daily => {
+            let date = first + Duration::days(step as i64);
+            if rule.by_day.is_empty() || rule.by_day.iter().any(|(_, w)| *w == date.weekday()) {
+                vec![date]
+            } else {
+                Vec::new()
+            }
+        }
+        Frequency::Weekly => {
+            let week_start = first - Duration::days(first.weekday().num_days_from_monday() as i64)
+                + Duration::weeks(step as i64);
+            if rule.by_day.is_empty() {
+                vec![week_start + Duration::days(first.weekday().num_days_from_monday() as i64)]
+            } else {
+                rule.by_day
+                    .iter()
+                    .map(|(_, w)| week_start + Duration::days(w.num_days_from_monday() as i64))
+                    .collect()
+            }
+        }
+        Frequency::Monthly => {
+            let (year, month) = add_months(first.year(), first.month(), step);
+            if !rule.by_day.is_empty() {
+                month_weekdays(year, month, &rule.by_day)
+            } else {
+                let last = days_in_month(year, month) as i32;
+                let days = if rule.by_month_day.is_empty() {
+                    vec![first.day() as i32]
+                } else {
+                    rule.by_month_day.clone()
+                };
+                days.iter()
+                    .map(|&d| if d < 0 { last + d + 1 } else { d })
+                    .filter(|&d| d >= 1 && d <= last)
+                    .filter_map(|d| NaiveDate::from_ymd_opt(year, month, d as u32))
+                    .collect()
+            }
+        }
+        Frequency::Yearly => {
+            NaiveDate::from_ymd_opt(first.year() + step as i32, first.month(), first.day())
+                .into_iter()
+                .collect()
+        }
+    };
+    dates.sort();
+    dates.dedup();
+    dates
+}
+
+/// Expand a rule into UTC start times that begin before `window_end`
+fn expand(start: &IcsTime, rule: &RecurrenceRule, window_end: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+    let first = start.naive();
+    let until = rule.until.as_ref().and_then(|u| match u {
+        // A date-only UNTIL includes that whole day
+        IcsTime::Date(d) => start.resolve(d.and_hms_opt(23, 59, 59)?),
+        other => other.to_utc(),
+    });
+
+    let mut starts = Vec::new();
+    let mut emitted = 0u32;
+    for period in 0..MAX_RECURRENCE_PERIODS as u32 {
+        let dates = period_dates(rule, first.date(), period);
+        let mut past_end = false;
+        for date in dates {
+            if date < first.date() {
+                continue;
+            }
+            let Some(instance) = start.resolve(date.and_time(first.time())) else {
+                continue;
+            };
+            if until.map(|u| instance > u).unwrap_or(false) || instance >= window_end {
+                past_end = true;
+                break;
+            }
+            if rule.count.map(|c| emitted >= c).unwrap_or(false) {
+                return starts;
+            }
+            emitted += 1;
+            starts.push(instance);
+        }
+        if past_end {
+            break;
+        }
+    }
+    starts
+}
+
+fn parse_events(lines: &[String]) -> (Option<String>, Vec<RawEvent>) {
+    let mut calendar_name = None;
+    let mut events = Vec::new();
+    let mut current: Option<RawEvent> = None;
+    // Nested components (VALARM) inside a VEVENT are ignored
+    let mut nested_depth = 0usize;
+
+    for line in lines {
+        let Some(prop) = parse_property(line) else {
+            continue;
+        };
+        match (
+            prop.name.as_str(),
+            prop.value.trim().to_uppercase().as_str(),
+        ) {
+            ("BEGIN", "VEVENT") => {
+                current = Some(RawEvent::default());
+                nested_depth = 0;
+                continue;
+            }
+            ("END", "VEVENT") => {
+                if let Some(event) = current.take() {
+                    events.push(event);
+                }
+                continue;
+            }
+            ("BEGIN", _) if current.is_some() => {
+                nested_depth += 1;
+                continue;
+            }
+            ("END", _) if current.is_some() => {
+                nested_depth = nested_depth.saturating_sub(1);
+                continue;
+            }
+            _ => {}
+        }
+
+        let Some(event) = current.as_mut() else {
+            if prop.name == "X-WR-CALNAME" {
+                calendar_name = Some(unescape_text(&prop.value));
+            }
+            continue;
+        };
+        if nested_depth > 0 {
+            continue;
+        }
+
+        match prop.name.as_str() {
+            "UID" => event.uid = Some(prop.value.trim().to_string()),
+            "SUMMARY" => event.summary = Some(unescape_text(&prop.value)),
+            "DTSTART" => event.start = parse_time_property(&prop),
+            "DTEND" => event.end = parse_time_property(&prop),
+            "DURATION" => event.duration = parse_duration(&prop.value),
+            "LOCATION" => event.location = Some(unescape_text(&prop.value)),
+            "DESCRIPTION" => event.description = Some(unescape_text(&prop.value)),
+            "URL" | "X-GOOGLE-CONFERENCE" => event.url = Some(prop.value.trim().to_string()),
+            "RRULE" => event.rrule = Some(prop.value.clone()),
+            "RECURRENCE-ID" => event.recurrence_id = parse_time_property(&prop),
+            "STATUS" => event.cancelled = prop.value.trim().eq_ignore_ascii_case("CANCELLED"),
+            "EXDATE" => {
+                let is_date = prop.param("VALUE") == Some("DATE");
+                event.exdates.extend(
+                    prop.value
+                        .split(',')
+                        .filter_map(|v| parse_time(v, prop.param("TZID"), is_date)),
+                );
+            }
+            "ATTENDEE" => {
+                let email = prop
+                    .value
+                    .trim()
+                    .strip_prefix("mailto:")
+                    .or_else(|| prop.value.trim().strip_prefix("MAILTO:"))
+                    .map(|e| e.to_string())
+                    .or_else(|| prop.param("EMAIL").map(|e| e.to_string()));
+                if let Some(email) = email.filter(|e| e.contains('@')) {
+                    let email = email.to_lowercase();
+                    if !event.attendees.contains(&email) {
+                        event.attendees.push(email);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    (calendar_name, events)
+}
+
+fn to_native(
+    event: &RawEvent,
+    event_id: String,
+    start: DateTime<Utc>,
+    duration: Duration,
+    calendar_name: &str,
+) -> CalendarEventNative {
+    let meeting_url =
+        extract_meeting_url(&event.location, &event.description).or_else(|| event.url.clone());
+    CalendarEventNative {
+        event_id,
+        title: event
+            .summary
+            .clone()
+            .unwrap_or_else(|| "Untitled event".to_string()),
+        start_time: start,
+        end_time: start + duration,
+        location: event.location.clone(),
+        attendees: event.attendees.clone(),
+        calendar_name: calendar_name.to_string(),
+        is_all_day: false,
+        meeting_url,
+        notes: event.description.clone(),
+    }
+}
+
+/// Timed events from ICS content overlapping [window_start, window_end), sorted by start
+pub fn parse_ics(
+    content: &str,
+    default_calendar_name: &str,
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+) -> Result<Vec<CalendarEventNative>, String> {
+    let lines = unfold(content);
+    if !lines
+        .iter()
+        .any(|l| l.eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        return Err("Not an iCalendar file (missing BEGIN:VCALENDAR)".to_string());
+    }
+
+    let (calendar_name, raw_events) = parse_events(&lines);
+    let calendar_name = calendar_name.unwrap_or_else(|| default_calendar_name.to_string());
+
+    // Instances replaced by a RECURRENCE-ID override, keyed by UID
+    let mut overridden: HashMap<String, HashSet<DateTime<Utc>>> = HashMap::new();
+    for event in &raw_events {
+        if let (Some(uid), Some(rid)) = (&event.uid, &event.recurrence_id) {
+            if let Some(at) = rid.to_utc() {
+                overridden.entry(uid.clone()).or_default().insert(at);
+            }
+        }
+    }
+
+    let mut results = Vec::new();
+    for (index, event) in raw_events.iter().enumerate() {
+        let Some(start) = &event.start else {
+            continue;
+        };
+        if start.is_date() || event.cancelled {
+            continue;
+        }
+        let Some(first_start) = start.to_utc() else {
+            continue;
+        };
+        let duration = match (&event.end, event.duration) {
+            (Some(end), _) => end
+                .to_utc()
+                .map(|end| end - first_start)
+                .unwrap_or_else(Duration::zero),
+            (None, Some(duration)) => duration,
+            (None, None) => Duration::zero(),
+        };
+        let uid = event
+            .uid
+            .clone()
+            .unwrap_or_else(|| format!("ics-{}", index));
+
+        let rule = if event.recurrence_id.is_none() {
+            event.rrule.as_deref().and_then(|r| parse_rrule(r, start))
+        } else {
+            None
+        };
+
+        let Some(rule) = rule else {
+            if first_start + duration > window_start && first_start < window_end {
+                let event_id = if event.recurrence_id.is_some() {
+                    format!("{}_{}", uid, first_start.format("%Y%m%dT%H%M%SZ"))
+                } else {
+                    uid
+                };
+                results.push(to_native(
+                    event,
+                    event_id,
+                    first_start,
+                    duration,
+                    &calendar_name,
+                ));
+            }
+            continue;
+        };
+
+        let excluded: HashSet<DateTime<Utc>> = event
+            .exdates
+            .iter()
+            .filter_map(|e| e.to_utc())
+            .chain(overridden.get(&uid).into_iter().flatten().copied())
+            .collect();
+
+        for instance in expand(start, &rule, window_end) {
+            if excluded.contains(&instance) || instance + duration <= window_start {
+                continue;
+            }
+            let event_id = format!("{}_{}", uid, instance.format("%Y%m%dT%H%M%SZ"));
+            results.push(to_native(
+                event,
+                event_id,
+                instance,
+                duration,
+                &calendar_name,
+            ));
+        }
+    }
+
+    results.sort_by_key(|e| e.start_time);
+    Ok(results)
+}
+
+/// Read an .ics file, expanding recurrences at least two weeks ahead
+pub fn import_file(path: &Path, lookahead_days: i64) -> Result<Vec<CalendarEventNative>, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read ICS file: {}", e))?;
+    let default_name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Imported".to_string());
+
+    let now = Utc::now();
+    parse_ics(
+        &content,
+        &default_name,
+        now - Duration::hours(LOOKBEHIND_HOURS),
+        now + Duration::days(lookahead_days.max(RECURRENCE_HORIZON_DAYS)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_tzid_and_utc_times() {
+        let ics = "BEGIN:VCALENDAR\r\nX-WR-CALNAME:Work\r\n\
+BEGIN:VEVENT\r\nUID:a\r\nSUMMARY:Winter sync\r\nDTSTART;TZID=America/New_York:20250115T090000\r\nDTEND;TZID=America/New_York:20250115T093000\r\n\
+ATTENDEE;CN=Dana:mailto:Dana@Example.com\r\nDESCRIPTION:Join: https://meet.google.com/abc-defg-hij\r\n  now\r\nEND:VEVENT\r\n\
+BEGIN:VEVENT\r\nUID:b\r\nSUMMARY:Summer sync\r\nDTSTART;TZID=\"Eastern Standard Time\":20250715T090000\r\nDURATION:PT1H\r\nEND:VEVENT\r\n\
+BEGIN:VEVENT\r\nUID:c\r\nSUMMARY:UTC call\r\nDTSTART:20250715T160000Z\r\nDTEND:20250715T163000Z\r\nEND:VEVENT\r\n\
+BEGIN:VEVENT\r\nUID:d\r\nSUMMARY:Holiday\r\nDTSTART;VALUE=DATE:20250704\r\nEND:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let events = parse_ics(
+            ics,
+            "file",
+            utc("2025-01-01T00:00:00Z"),
+            utc("2025-12-31T00:00:00Z"),
+        )
+        .unwrap();
+
+        assert_eq!(events.len(), 3, "all-day event is skipped");
+        assert_eq!(events[0].start_time, utc("2025-01-15T14:00:00Z"));
+        assert_eq!(events[0].end_time, utc("2025-01-15T14:30:00Z"));
+        assert_eq!(events[0].calendar_name, "Work");
+        assert_eq!(events[0].attendees, vec!["dana@example.com"]);
+        assert!(events[0]
+            .meeting_url
+            .as_deref()
+            .unwrap()
+            .contains("meet.google.com"));
+        // DST: 09:00 EDT is 13:00 UTC
+        assert_eq!(events[1].start_time, utc("2025-07-15T13:00:00Z"));
+        assert_eq!(events[1].end_time, utc("2025-07-15T14:00:00Z"));
+        assert_eq!(events[2].start_time, utc("2025-07-15T16:00:00Z"));
+    }
+
+    #[test]
+    fn test_weekly_rrule_across_dst_with_exdate_and_override() {
+        let ics = "BEGIN:VCALENDAR\n\
+BEGIN:VEVENT\nUID:standup\nSUMMARY:Standup\nDTSTART;TZID=America/New_York:20250303T100000\nDTEND;TZID=America/New_York:20250303T101500\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE;COUNT=6\nEXDATE;TZID=America/New_York:20250305T100000\nEND:VEVENT\n\
+BEGIN:VEVENT\nUID:standup\nRECURRENCE-ID;TZID=America/New_York:20250310T100000\nSUMMARY:Standup (moved)\nDTSTART;TZID=America/New_York:20250310T110000\nDTEND;TZID=America/New_York:20250310T111500\nEND:VEVENT\n\
+END:VCALENDAR\n";
+
+        let events = parse_ics(
+            ics,
+            "file",
+            utc("2025-03-01T00:00:00Z"),
+            utc("2025-04-01T00:00:00Z"),
+        )
+        .unwrap();
+        let starts: Vec<String> = events.iter().map(|e| e.start_time.to_rfc3339()).collect();
+
+        assert_eq!(
+            starts,
+            vec![
+                "2025-03-03T15:00:00+00:00", // EST
+                // 03-05 excluded by EXDATE
+                "2025-03-10T15:00:00+00:00", // override at 11:00 EDT
+                "2025-03-12T14:00:00+00:00", // EDT after the DST switch
+                "2025-03-17T14:00:00+00:00",
+                "2025-03-19T14:00:00+00:00",
+            ]
+        );
+        assert_eq!(events[1].title, "Standup (moved)");
+        assert_eq!(events[0].event_id, "standup_20250303T150000Z");
+    }
+
+    #[test]
+    fn test_monthly_rules_and_duration() {
+        let start = IcsTime::Utc(
+            NaiveDate::from_ymd_opt(2025, 1, 31)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap(),
+        );
+        let rule = parse_rrule("FREQ=MONTHLY;BYDAY=-1FR;COUNT=3", &start).unwrap();
+        let starts = expand(&start, &rule, utc("2026-01-01T00:00:00Z"));
+        assert_eq!(
+            starts,
+            vec![
+                utc("2025-01-31T12:00:00Z"),
+                utc("2025-02-28T12:00:00Z"),
+                utc("2025-03-28T12:00:00Z"),
+            ]
+        );
+
+        let rule = parse_rrule("FREQ=MONTHLY;UNTIL=20250501", &start).unwrap();
+        // Months without a 31st are skipped
+        assert_eq!(expand(&start, &rule, utc("2026-01-01T00:00:00Z")).len(), 2);
+
+        assert_eq!(parse_duration("PT1H30M"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("P1W"), Some(Duration::weeks(1)));
+        assert!(parse_tzid("/mozilla.org/20050126_1/America/New_York").is_some());
+    }
+}
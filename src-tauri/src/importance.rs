@@ -0,0 +1,321 @@
+// noFriction Meetings - Timeline Importance
+// One 0-1 importance scale for timeline events, scored when a meeting's
+// timeline is finalized, plus a short reason the UI and vault export show
+// ("7-minute document editing with 2 action items").
+//
+// score = weight of the event type (and activity category)
+//       + duration, up to +0.25 at 10 minutes
+//       + decisions/action items raised during the event, +0.15 each up to +0.3
+//       + named entities in the overlapping transcript, up to +0.1
+
+use crate::database::{DatabaseManager, TimelineEventRecord};
+use crate::live_intel_agent::LiveInsightEvent;
+use crate::timeline_builder::{TimelineEvent, TimelineEventType};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashSet;
+
+/// Point events look this far either side for insights and transcript
+const POINT_WINDOW_SECS: i64 = 30;
+
+/// Events below this never make the highlights
+pub const HIGHLIGHT_MIN_IMPORTANCE: f32 = 0.4;
+
+/// Distinct named entities needed before density counts
+const MIN_ENTITIES: usize = 3;
+
+/// What was said and decided during the meeting, for scoring its events
+#[derive(Debug, Clone, Default)]
+pub struct ScoringContext {
+    /// (when, text) of final transcript segments
+    pub transcripts: Vec<(DateTime<Utc>, String)>,
+    /// When decisions were made
+    pub decisions: Vec<DateTime<Utc>>,
+    /// When action items came up
+    pub action_items: Vec<DateTime<Utc>>,
+}
+
+impl ScoringContext {
+    /// Load a meeting's transcripts and live decisions/action items
+    pub async fn load(db: &DatabaseManager, meeting_id: &str) -> Result<Self, String> {
+        let transcripts = db
+            .get_transcripts(meeting_id)
+            .await
+            .map_err(|e| format!("Failed to get transcripts: {}", e))?
+            .into_iter()
+            .filter(|t| t.is_final)
+            .map(|t| (t.timestamp, t.text))
+            .collect();
+        let insights = db
+            .get_live_insights(meeting_id, 0)
+            .await
+            .map_err(|e| format!("Failed to get live insights: {}", e))?;
+
+        let mut context = Self {
+            transcripts,
+            ..Default::default()
+        };
+        for insight in &insights {
+            match insight {
+                LiveInsightEvent::Decision { timestamp_ms, .. } => context
+                    .decisions
+                    .extend(DateTime::from_timestamp_millis(*timestamp_ms)),
+                LiveInsightEvent::ActionItem { timestamp_ms, .. } => context
+                    .action_items
+                    .extend(DateTime::from_timestamp_millis(*timestamp_ms)),
+                _ => {}
+            }
+        }
+        Ok(context)
+    }
+}
+
+/// How much an event type matters before anything else is known
+fn type_weight(event_type: TimelineEventType) -> f32 {
+    use TimelineEventType::*;
+    match event_type {
+        Decision => 0.8,
+        ActionItem => 0.75,
+        QuickNote => 0.7,
+        QuestionBurst => 0.5,
+        Monologue => 0.4,
+        ContentEdit => 0.35,
+        DocumentOpened | TopicChange | MicSilent => 0.3,
+        AppSwitch => 0.15,
+        MeetingStart | MeetingEnd | Paused | Resumed | DocumentClosed => 0.1,
+        Navigation | ActivityGap | IdleGap => 0.05,
+    }
+}
+
+/// Extra weight for focused work over passive screen time
+fn category_weight(category: Option<&str>) -> f32 {
+    match category {
+        Some("coding") | Some("document") => 0.15,
+        Some("slides") | Some("terminal") => 0.1,
+        Some("browser") | Some("video_call") => 0.05,
+        _ => 0.0,
+    }
+}
+
+/// What the event was, for the reason text
+fn activity(event: &TimelineEvent) -> String {
+    match (event.event_type, event.category.as_deref()) {
+        (TimelineEventType::DocumentOpened, Some("coding")) => "coding".to_string(),
+        (TimelineEventType::DocumentOpened, Some("document")) => "document editing".to_string(),
+        (TimelineEventType::DocumentOpened, Some("browser")) => "browsing".to_string(),
+        (TimelineEventType::DocumentOpened, Some("slides")) => "slide work".to_string(),
+        (TimelineEventType::DocumentOpened, Some("terminal")) => "terminal work".to_string(),
+        (TimelineEventType::DocumentOpened, Some("video_call")) => "video call".to_string(),
+        (TimelineEventType::DocumentOpened, _) => "document work".to_string(),
+        (TimelineEventType::ContentEdit, _) => "editing".to_string(),
+        (event_type, _) => event_type.label().to_lowercase(),
+    }
+}
+
+/// Capitalized words that don't start a sentence, and numbers: a cheap
+/// stand-in for people, companies, products and figures
+fn named_entities(text: &str) -> HashSet<String> {
+    let mut entities = HashSet::new();
+    let mut sentence_start = true;
+    for word in text.split_whitespace() {
+        let clean = word.trim_matches(|c: char| !c.is_alphanumeric());
+        let is_entity = clean.chars().next().is_some_and(|c| {
+            c.is_ascii_digit() || (c.is_uppercase() && !sentence_start && clean.len() > 1)
+        });
+        if is_entity {
+            entities.insert(clean.to_lowercase());
+        }
+        sentence_start = word.ends_with(['.', '?', '!']);
+    }
+    entities
+}
+
+fn plural(n: usize, word: &str) -> String {
+    if n == 1 {
+        format!("1 {}", word)
+    } else {
+        format!("{} {}s", n, word)
+    }
+}
+
+/// Score one event on the 0-1 scale; returns (importance, reason)
+pub fn score_event(event: &TimelineEvent, context: &ScoringContext) -> (f32, String) {
+    let duration_ms = event.duration_ms.unwrap_or(0).max(0);
+    let (from, to) = if duration_ms > 0 {
+        (event.ts, event.ts + Duration::milliseconds(duration_ms))
+    } else {
+        let pad = Duration::seconds(POINT_WINDOW_SECS);
+        (event.ts - pad, event.ts + pad)
+    };
+    let within = |ts: &DateTime<Utc>| *ts >= from && *ts <= to;
+
+    let mut score = type_weight(event.event_type) + category_weight(event.category.as_deref());
+
+    let minutes = duration_ms as f32 / 60_000.0;
+    score += (minutes / 10.0).min(1.0) * 0.25;
+
+    // An insight event is the decision/action item itself
+    let (decisions, action_items) = match event.event_type {
+        TimelineEventType::Decision | TimelineEventType::ActionItem => (0, 0),
+        _ => (
+            context.decisions.iter().filter(|ts| within(ts)).count(),
+            context.action_items.iter().filter(|ts| within(ts)).count(),
+        ),
+    };
+    score += ((decisions + action_items) as f32 * 0.15).min(0.3);
+
+    let spoken: Vec<&str> = context
+        .transcripts
+        .iter()
+        .filter(|(ts, _)| within(ts))
+        .map(|(_, text)| text.as_str())
+        .collect();
+    let entities = named_entities(&spoken.join(" ")).len();
+    if entities >= MIN_ENTITIES {
+        score += (entities as f32 / 20.0).min(1.0) * 0.1;
+    }
+
+    let mut reason = if minutes >= 1.0 {
+        format!("{:.0}-minute {}", minutes, activity(event))
+    } else {
+        let what = activity(event);
+        let mut chars = what.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => what,
+        }
+    };
+    let mut raised = Vec::new();
+    if action_items > 0 {
+        raised.push(plural(action_items, "action item"));
+    }
+    if decisions > 0 {
+        raised.push(plural(decisions, "decision"));
+    }
+    if !raised.is_empty() {
+        reason.push_str(&format!(" with {}", raised.join(" and ")));
+    }
+    if entities >= MIN_ENTITIES {
+        reason.push_str(&format!(", {} named entities", entities));
+    }
+
+    (score.clamp(0.0, 1.0), reason)
+}
+
+/// Rescore every event of a finalized timeline
+pub fn score_events(events: &mut [TimelineEvent], context: &ScoringContext) {
+    for event in events.iter_mut() {
+        let (importance, reason) = score_event(event, context);
+        event.importance = importance;
+        event.importance_reason = Some(reason);
+    }
+}
+
+/// The meeting's most important events, in meeting order
+pub async fn meeting_highlights(
+    db: &DatabaseManager,
+    meeting_id: &str,
+    max_events: usize,
+) -> Result<Vec<TimelineEventRecord>, String> {
+    let mut events = db
+        .get_timeline_events(meeting_id, None, &[], Some(HIGHLIGHT_MIN_IMPORTANCE))
+        .await
+        .map_err(|e| format!("Failed to get timeline events: {}", e))?;
+    events.sort_by(|a, b| b.importance.total_cmp(&a.importance));
+    events.truncate(max_events);
+    events.sort_by(|a, b| a.ts.cmp(&b.ts));
+    Ok(events)
+}
+
+/// "- **[14:05:12]** Working on plan.md — 7-minute document editing" bullets
+pub fn highlights_markdown(events: &[TimelineEventRecord]) -> Vec<String> {
+    events
+        .iter()
+        .map(|e| {
+            let time = e.ts.get(11..19).unwrap_or(&e.ts);
+            match e.importance_reason.as_deref() {
+                Some(reason) => format!("- **[{}]** {} — {}", time, e.title, reason),
+                None => format!("- **[{}]** {}", time, e.title),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scores_and_reasons() {
+        let start = Utc::now();
+        let at = |secs| start + Duration::seconds(secs);
+        let editing = TimelineEvent::new(
+            "m1",
+            start,
+            TimelineEventType::DocumentOpened,
+            "Working on plan.md".to_string(),
+        )
+        .with_category("document")
+        .with_duration(7 * 60_000);
+        let context = ScoringContext {
+            transcripts: vec![(
+                at(60),
+                "We agreed with Acme that Priya sends the Q3 numbers to Berlin by 15 May."
+                    .to_string(),
+            )],
+            decisions: vec![at(600)],
+            action_items: vec![at(120), at(300)],
+        };
+
+        let (score, reason) = score_event(&editing, &context);
+        assert_eq!(
+            reason,
+            "7-minute document editing with 2 action items, 6 named entities"
+        );
+        assert!(score > 0.8);
+
+        // Meeting boundaries are structural, not highlights
+        let start_event = TimelineEvent::new(
+            "m1",
+            start,
+            TimelineEventType::MeetingStart,
+            "Meeting Started".to_string(),
+        );
+        let (score, reason) = score_event(&start_event, &ScoringContext::default());
+        assert!(score < HIGHLIGHT_MIN_IMPORTANCE);
+        assert_eq!(reason, "Meeting started");
+
+        // A decision isn't credited with itself
+        let decision = TimelineEvent::new(
+            "m1",
+            at(600),
+            TimelineEventType::Decision,
+            "Ship Friday".to_string(),
+        );
+        assert_eq!(score_event(&decision, &context).1, "Decision");
+    }
+
+    #[test]
+    fn test_highlights_markdown() {
+        let record = TimelineEventRecord {
+            event_id: "e1".to_string(),
+            meeting_id: "m1".to_string(),
+            ts: "2026-03-02T14:05:12+00:00".to_string(),
+            event_type: "decision".to_string(),
+            title: "Ship Friday".to_string(),
+            description: None,
+            app_name: None,
+            window_title: None,
+            duration_ms: None,
+            episode_id: None,
+            state_id: None,
+            topic: None,
+            importance: 0.8,
+            importance_reason: Some("Decision".to_string()),
+            category: None,
+        };
+        assert_eq!(
+            highlights_markdown(&[record]),
+            vec!["- **[14:05:12]** Ship Friday — Decision".to_string()]
+        );
+    }
+}
@@ -1,13 +1,20 @@
 // noFriction Meetings - Ingest Client
 // Uploads frames and transcripts to Intelligence Pipeline
 
-use reqwest::{multipart, Client};
+use rand::Rng;
+use reqwest::{multipart, Client, StatusCode};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::path::Path;
 use std::time::Duration;
 use tokio::fs;
 use uuid::Uuid;
 
+/// Attempts per frame upload before it counts as failed
+pub const FRAME_UPLOAD_ATTEMPTS: u32 = 3;
+/// First frame retry delay; doubles per attempt, plus up to as much jitter
+const FRAME_RETRY_BASE_MS: u64 = 500;
+
 #[derive(Debug, Clone)]
 pub struct IngestClient {
     client: Client,
@@ -28,7 +35,7 @@ pub struct SessionEndRequest {
     pub ended_at: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TranscriptSegment {
     pub start_at: String,
     pub end_at: String,
@@ -58,6 +65,25 @@ pub struct TranscriptIngestResponse {
     pub count: usize,
 }
 
+/// What the pipeline has received for a session
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteSessionSummary {
+    pub session_id: Uuid,
+    #[serde(default)]
+    pub frame_count: usize,
+    #[serde(default)]
+    pub transcript_count: usize,
+    /// SHA-256 (hex) of every frame received
+    #[serde(default)]
+    pub frame_hashes: Vec<String>,
+}
+
+/// Frame upload failure; only transient ones are retried
+enum FrameUploadError {
+    Transient(String),
+    Permanent(String),
+}
+
 impl IngestClient {
     pub fn new(base_url: String, bearer_token: String) -> Self {
         let client = Client::builder()
@@ -139,7 +165,9 @@ impl IngestClient {
         Ok(())
     }
 
-    /// Upload a frame (image)
+    /// Upload a frame (image). The file's SHA-256 is sent along and checked
+    /// against the server's; transient failures are retried up to
+    /// `FRAME_UPLOAD_ATTEMPTS` times with jittered backoff.
     pub async fn upload_frame(
         &self,
         session_id: Uuid,
@@ -147,8 +175,6 @@ impl IngestClient {
         image_path: &Path,
         sha256: Option<String>,
     ) -> Result<FrameIngestResponse, Box<dyn std::error::Error>> {
-        let url = format!("{}/v1/ingest/frame", self.base_url);
-
         // Read image file
         let file_data = fs::read(image_path).await?;
         let file_name = image_path
@@ -156,36 +182,99 @@ impl IngestClient {
             .and_then(|n| n.to_str())
             .unwrap_or("frame.jpg");
 
-        // Build multipart form
-        let mut form = multipart::Form::new()
-            .text("session_id", session_id.to_string())
-            .text("captured_at", captured_at)
-            .part(
-                "file",
-                multipart::Part::bytes(file_data)
-                    .file_name(file_name.to_string())
-                    .mime_str("image/jpeg")?,
+        let checksum = sha256_hex(&file_data);
+        if let Some(expected) = sha256.filter(|h| !h.eq_ignore_ascii_case(&checksum)) {
+            log::warn!(
+                "Frame {} changed since it was queued (expected {}, now {})",
+                image_path.display(),
+                expected,
+                checksum
             );
+        }
 
-        if let Some(hash) = sha256 {
-            form = form.text("sha256", hash);
+        let mut last_error = String::new();
+        for attempt in 1..=FRAME_UPLOAD_ATTEMPTS {
+            if attempt > 1 {
+                let delay = frame_retry_delay(attempt);
+                log::warn!(
+                    "Frame upload retry {} in {}ms: {}",
+                    attempt - 1,
+                    delay.as_millis(),
+                    last_error
+                );
+                tokio::time::sleep(delay).await;
+            }
+
+            match self
+                .send_frame(session_id, &captured_at, file_name, &file_data, &checksum)
+                .await
+            {
+                Ok(response) => return Ok(response),
+                Err(FrameUploadError::Permanent(e)) => return Err(e.into()),
+                Err(FrameUploadError::Transient(e)) => last_error = e,
+            }
         }
 
+        Err(format!(
+            "Frame upload failed after {} attempts: {}",
+            FRAME_UPLOAD_ATTEMPTS, last_error
+        )
+        .into())
+    }
+
+    async fn send_frame(
+        &self,
+        session_id: Uuid,
+        captured_at: &str,
+        file_name: &str,
+        file_data: &[u8],
+        checksum: &str,
+    ) -> Result<FrameIngestResponse, FrameUploadError> {
+        let url = format!("{}/v1/ingest/frame", self.base_url);
+
+        // Build multipart form
+        let part = multipart::Part::bytes(file_data.to_vec())
+            .file_name(file_name.to_string())
+            .mime_str("image/jpeg")
+            .map_err(|e| FrameUploadError::Permanent(e.to_string()))?;
+        let form = multipart::Form::new()
+            .text("session_id", session_id.to_string())
+            .text("captured_at", captured_at.to_string())
+            .text("sha256", checksum.to_string())
+            .part("file", part);
+
         let response = self
             .client
             .post(&url)
             .header("Authorization", format!("Bearer {}", self.bearer_token))
+            .header("X-Content-SHA256", checksum)
             .multipart(form)
             .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            return Err(format!("Frame upload failed: {} - {}", status, body).into());
+            .await
+            .map_err(|e| FrameUploadError::Transient(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = format!("Frame upload failed: {} - {}", status, body);
+            return Err(if is_transient(status) {
+                FrameUploadError::Transient(message)
+            } else {
+                FrameUploadError::Permanent(message)
+            });
         }
 
-        Ok(response.json::<FrameIngestResponse>().await?)
+        let result = response
+            .json::<FrameIngestResponse>()
+            .await
+            .map_err(|e| FrameUploadError::Transient(e.to_string()))?;
+        if !result.sha256.eq_ignore_ascii_case(checksum) {
+            return Err(FrameUploadError::Transient(format!(
+                "Checksum mismatch: sent {}, server stored {}",
+                checksum, result.sha256
+            )));
+        }
+        Ok(result)
     }
 
     /// Upload transcript segments
@@ -218,6 +307,29 @@ impl IngestClient {
         Ok(response.json::<TranscriptIngestResponse>().await?)
     }
 
+    /// Counts and frame hashes the pipeline has received for a session
+    pub async fn get_session_summary(
+        &self,
+        session_id: Uuid,
+    ) -> Result<RemoteSessionSummary, Box<dyn std::error::Error>> {
+        let url = format!("{}/v1/ingest/session/{}", self.base_url, session_id);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.bearer_token))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await?;
+            return Err(format!("Session lookup failed: {} - {}", status, body).into());
+        }
+
+        Ok(response.json::<RemoteSessionSummary>().await?)
+    }
+
     /// Health check
     pub async fn health_check(&self) -> Result<bool, Box<dyn std::error::Error>> {
         let url = format!("{}/health", self.base_url);
@@ -233,6 +345,25 @@ impl IngestClient {
     }
 }
 
+/// SHA-256 of `data` as lowercase hex
+pub fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Server errors, timeouts and rate limits are worth retrying
+fn is_transient(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Delay before attempt `attempt` (2 = first retry)
+fn frame_retry_delay(attempt: u32) -> Duration {
+    let base = FRAME_RETRY_BASE_MS << attempt.saturating_sub(2);
+    let jitter = rand::rng().random_range(0..base);
+    Duration::from_millis(base + jitter)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -245,4 +376,27 @@ mod tests {
         );
         assert_eq!(client.base_url, "http://localhost:8000");
     }
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_retry_classification_and_delay() {
+        assert!(is_transient(StatusCode::BAD_GATEWAY));
+        assert!(is_transient(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_transient(StatusCode::UNAUTHORIZED));
+        assert!(!is_transient(StatusCode::UNPROCESSABLE_ENTITY));
+
+        for _ in 0..20 {
+            let first = frame_retry_delay(2).as_millis() as u64;
+            let second = frame_retry_delay(3).as_millis() as u64;
+            assert!((500..1000).contains(&first));
+            assert!((1000..2000).contains(&second));
+        }
+    }
 }
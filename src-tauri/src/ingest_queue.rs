@@ -1,5 +1,7 @@
 // noFriction Meetings - Ingest Queue
-// Local durable queue for upload retries
+// Local durable queue for upload retries. Items keep their status after
+// upload so re-queuing a meeting skips what already went up, and each meeting
+// maps to one remote session that survives restarts.
 
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
@@ -19,17 +21,103 @@ pub enum QueuedItem {
         session_id: Uuid,
         segments_json: String,
     },
+    /// Closes the remote session once everything queued before it is uploaded
+    SessionEnd { session_id: Uuid, ended_at: String },
 }
 
-#[derive(Debug)]
+impl QueuedItem {
+    pub fn session_id(&self) -> Uuid {
+        match self {
+            Self::Frame { session_id, .. }
+            | Self::Transcript { session_id, .. }
+            | Self::SessionEnd { session_id, .. } => *session_id,
+        }
+    }
+}
+
+/// Upload state of a queued item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemStatus {
+    Pending,
+    Uploading,
+    Done,
+    Failed,
+}
+
+impl ItemStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Uploading => "uploading",
+            Self::Done => "done",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Item counts per status
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct QueueStateCounts {
+    pub pending: usize,
+    pub uploading: usize,
+    pub done: usize,
+    pub failed: usize,
+}
+
+/// Frame that ran out of upload retries
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FailedFrame {
+    pub image_path: PathBuf,
+    pub last_error: Option<String>,
+}
+
+/// Remote session a meeting uploads into
+#[derive(Debug, Clone, Serialize)]
+pub struct IngestSession {
+    pub meeting_id: String,
+    pub session_id: Uuid,
+    pub started_at: String,
+    pub metadata: serde_json::Value,
+    /// Session start has been acknowledged by the server
+    pub remote_started: bool,
+}
+
+#[derive(Debug, Clone)]
 pub struct IngestQueue {
     conn: Arc<Mutex<Connection>>,
 }
 
 impl IngestQueue {
     pub fn new(db_path: &PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            conn: Arc::new(Mutex::new(Self::open(db_path)?)),
+        })
+    }
+
+    /// Non-persistent queue used while the queue file cannot be opened
+    pub fn in_memory() -> Result<Self, Box<dyn std::error::Error>> {
+        let conn = Connection::open_in_memory()?;
+        Self::prepare(&conn)?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Switch this queue (and every clone of it) over to the file at
+    /// `db_path`. Items held by the previous connection are not carried over.
+    pub fn reopen(&self, db_path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+        let conn = Self::open(db_path)?;
+        *self.conn.lock().unwrap() = conn;
+        Ok(())
+    }
+
+    fn open(db_path: &PathBuf) -> Result<Connection, Box<dyn std::error::Error>> {
         let conn = Connection::open(db_path)?;
+        Self::prepare(&conn)?;
+        Ok(conn)
+    }
 
+    fn prepare(conn: &Connection) -> Result<(), Box<dyn std::error::Error>> {
         // Create table if not exists
         conn.execute(
             "CREATE TABLE IF NOT EXISTS ingest_queue (
@@ -45,9 +133,153 @@ impl IngestQueue {
             [],
         )?;
 
-        Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
-        })
+        // Per-item status and dedupe key (ignored if the columns already exist)
+        let _ = conn.execute(
+            "ALTER TABLE ingest_queue ADD COLUMN status TEXT NOT NULL DEFAULT 'pending'",
+            [],
+        );
+        let _ = conn.execute("ALTER TABLE ingest_queue ADD COLUMN dedupe_key TEXT", []);
+        conn.execute(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_ingest_queue_dedupe
+             ON ingest_queue(dedupe_key) WHERE dedupe_key IS NOT NULL",
+            [],
+        )?;
+        conn.execute(
+            "UPDATE ingest_queue SET status = 'failed'
+             WHERE status = 'pending' AND retries >= max_retries",
+            [],
+        )?;
+        // Uploads interrupted by a crash go back to the front of the line
+        conn.execute(
+            "UPDATE ingest_queue SET status = 'pending' WHERE status = 'uploading'",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS ingest_sessions (
+                meeting_id TEXT PRIMARY KEY,
+                session_id TEXT NOT NULL UNIQUE,
+                started_at TEXT NOT NULL,
+                metadata TEXT NOT NULL,
+                remote_started INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Remote session for a meeting, created (locally) on first use. The id is
+    /// sent with the session start so a resumed upload keeps using it.
+    pub fn session_for_meeting(
+        &self,
+        meeting_id: &str,
+        started_at: &str,
+        metadata: &serde_json::Value,
+    ) -> Result<Uuid, Box<dyn std::error::Error>> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR IGNORE INTO ingest_sessions (meeting_id, session_id, started_at, metadata)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                meeting_id,
+                Uuid::new_v4().to_string(),
+                started_at,
+                metadata.to_string()
+            ],
+        )?;
+        let session_id: String = conn.query_row(
+            "SELECT session_id FROM ingest_sessions WHERE meeting_id = ?1",
+            params![meeting_id],
+            |row| row.get(0),
+        )?;
+        Ok(Uuid::parse_str(&session_id)?)
+    }
+
+    /// Session record by remote id (None for items queued without a meeting)
+    pub fn get_session(
+        &self,
+        session_id: Uuid,
+    ) -> Result<Option<IngestSession>, Box<dyn std::error::Error>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT meeting_id, started_at, metadata, remote_started
+             FROM ingest_sessions WHERE session_id = ?1",
+            params![session_id.to_string()],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(3)?,
+                ))
+            },
+        );
+
+        match result {
+            Ok((meeting_id, started_at, metadata, remote_started)) => Ok(Some(IngestSession {
+                meeting_id,
+                session_id,
+                started_at,
+                metadata: serde_json::from_str(&metadata).unwrap_or_default(),
+                remote_started: remote_started != 0,
+            })),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(Box::new(e)),
+        }
+    }
+
+    /// Session record for a meeting, if it was ever queued
+    pub fn get_meeting_session(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<IngestSession>, Box<dyn std::error::Error>> {
+        let session_id = {
+            let conn = self.conn.lock().unwrap();
+            let result = conn.query_row(
+                "SELECT session_id FROM ingest_sessions WHERE meeting_id = ?1",
+                params![meeting_id],
+                |row| row.get::<_, String>(0),
+            );
+            match result {
+                Ok(id) => id,
+                Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+                Err(e) => return Err(Box::new(e)),
+            }
+        };
+        self.get_session(Uuid::parse_str(&session_id)?)
+    }
+
+    /// Record that the server accepted the session start
+    pub fn mark_session_started(&self, session_id: Uuid) -> Result<(), Box<dyn std::error::Error>> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE ingest_sessions SET remote_started = 1 WHERE session_id = ?1",
+            params![session_id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Enqueue an item unless one with the same key was queued before (in any
+    /// state). Returns false for a duplicate.
+    pub fn enqueue_unique(
+        &self,
+        dedupe_key: &str,
+        item: &QueuedItem,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let item_type = match item {
+            QueuedItem::Frame { .. } => "frame",
+            QueuedItem::Transcript { .. } => "transcript",
+            QueuedItem::SessionEnd { .. } => "session_end",
+        };
+        let payload = serde_json::to_string(item)?;
+
+        let conn = self.conn.lock().unwrap();
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO ingest_queue (item_type, payload, dedupe_key) VALUES (?1, ?2, ?3)",
+            params![item_type, payload, dedupe_key],
+        )?;
+        Ok(inserted > 0)
     }
 
     /// Enqueue a frame for upload
@@ -101,17 +333,25 @@ impl IngestQueue {
     /// Get next pending item
     pub fn get_next_pending(
         &self,
+    ) -> Result<Option<(i64, QueuedItem)>, Box<dyn std::error::Error>> {
+        self.get_next_pending_after(0)
+    }
+
+    /// Get next pending item queued after `after_id`
+    pub fn get_next_pending_after(
+        &self,
+        after_id: i64,
     ) -> Result<Option<(i64, QueuedItem)>, Box<dyn std::error::Error>> {
         let conn = self.conn.lock().unwrap();
 
         let mut stmt = conn.prepare(
             "SELECT id, payload FROM ingest_queue 
-             WHERE retries < max_retries 
-             ORDER BY created_at ASC 
+             WHERE status = 'pending' AND retries < max_retries AND id > ?1
+             ORDER BY id ASC 
              LIMIT 1",
         )?;
 
-        let result = stmt.query_row([], |row| {
+        let result = stmt.query_row(params![after_id], |row| {
             let id: i64 = row.get(0)?;
             let payload: String = row.get(1)?;
             Ok((id, payload))
@@ -127,39 +367,133 @@ impl IngestQueue {
         }
     }
 
-    /// Mark item as successfully processed
+    /// Queue an item again even if it was uploaded or failed before
+    pub fn requeue(
+        &self,
+        dedupe_key: &str,
+        item: &QueuedItem,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let item_type = match item {
+            QueuedItem::Frame { .. } => "frame",
+            QueuedItem::Transcript { .. } => "transcript",
+            QueuedItem::SessionEnd { .. } => "session_end",
+        };
+        let payload = serde_json::to_string(item)?;
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO ingest_queue (item_type, payload, dedupe_key) VALUES (?1, ?2, ?3)
+             ON CONFLICT(dedupe_key) WHERE dedupe_key IS NOT NULL DO UPDATE SET
+                payload = excluded.payload,
+                status = 'pending',
+                retries = 0,
+                last_error = NULL",
+            params![item_type, payload, dedupe_key],
+        )?;
+        Ok(())
+    }
+
+    /// Frames in a session that ran out of retries
+    pub fn failed_frames(
+        &self,
+        session_id: Uuid,
+    ) -> Result<Vec<FailedFrame>, Box<dyn std::error::Error>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT payload, last_error FROM ingest_queue
+             WHERE status = 'failed' AND item_type = 'frame'
+               AND json_extract(payload, '$.Frame.session_id') = ?1
+             ORDER BY id ASC",
+        )?;
+        let rows = stmt.query_map(params![session_id.to_string()], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+        })?;
+
+        let mut frames = Vec::new();
+        for row in rows {
+            let (payload, last_error) = row?;
+            if let QueuedItem::Frame { image_path, .. } = serde_json::from_str(&payload)? {
+                frames.push(FailedFrame {
+                    image_path,
+                    last_error,
+                });
+            }
+        }
+        Ok(frames)
+    }
+
+    /// Mark item as being uploaded
+    pub fn mark_uploading(&self, id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE ingest_queue SET status = 'uploading', last_attempt_at = CURRENT_TIMESTAMP
+             WHERE id = ?1",
+            params![id],
+        )?;
+        Ok(())
+    }
+
+    /// Mark item as successfully processed. The row is kept (as done) so the
+    /// same item isn't queued again.
     pub fn mark_completed(&self, id: i64) -> Result<(), Box<dyn std::error::Error>> {
         let conn = self.conn.lock().unwrap();
-        conn.execute("DELETE FROM ingest_queue WHERE id = ?1", params![id])?;
+        conn.execute(
+            "UPDATE ingest_queue SET status = 'done', last_error = NULL WHERE id = ?1",
+            params![id],
+        )?;
         Ok(())
     }
 
-    /// Mark item as failed and increment retry count
+    /// Mark item as failed and increment retry count; it goes back to pending
+    /// until it runs out of retries
     pub fn mark_failed(&self, id: i64, error: &str) -> Result<(), Box<dyn std::error::Error>> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
             "UPDATE ingest_queue 
              SET retries = retries + 1, 
                  last_attempt_at = CURRENT_TIMESTAMP,
-                 last_error = ?1
+                 last_error = ?1,
+                 status = CASE WHEN retries + 1 >= max_retries THEN 'failed' ELSE 'pending' END
              WHERE id = ?2",
             params![error, id],
         )?;
         Ok(())
     }
 
+    /// Item counts per status
+    pub fn get_state_counts(&self) -> Result<QueueStateCounts, Box<dyn std::error::Error>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT status, COUNT(*) FROM ingest_queue GROUP BY status")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, usize>(1)?))
+        })?;
+
+        let mut counts = QueueStateCounts::default();
+        for row in rows {
+            let (status, count) = row?;
+            match status.as_str() {
+                "pending" => counts.pending = count,
+                "uploading" => counts.uploading = count,
+                "done" => counts.done = count,
+                "failed" => counts.failed = count,
+                _ => {}
+            }
+        }
+        Ok(counts)
+    }
+
     /// Get queue statistics
     pub fn get_stats(&self) -> Result<(usize, usize), Box<dyn std::error::Error>> {
         let conn = self.conn.lock().unwrap();
 
         let pending: usize = conn.query_row(
-            "SELECT COUNT(*) FROM ingest_queue WHERE retries < max_retries",
+            "SELECT COUNT(*) FROM ingest_queue WHERE status IN ('pending', 'uploading')",
             [],
             |row| row.get(0),
         )?;
 
         let failed: usize = conn.query_row(
-            "SELECT COUNT(*) FROM ingest_queue WHERE retries >= max_retries",
+            "SELECT COUNT(*) FROM ingest_queue WHERE status = 'failed'",
             [],
             |row| row.get(0),
         )?;
@@ -170,7 +504,7 @@ impl IngestQueue {
     /// Clear all failed items
     pub fn clear_failed(&self) -> Result<usize, Box<dyn std::error::Error>> {
         let conn = self.conn.lock().unwrap();
-        let count = conn.execute("DELETE FROM ingest_queue WHERE retries >= max_retries", [])?;
+        let count = conn.execute("DELETE FROM ingest_queue WHERE status = 'failed'", [])?;
         Ok(count)
     }
 }
@@ -213,4 +547,98 @@ mod tests {
         let (pending, _) = queue.get_stats().unwrap();
         assert_eq!(pending, 0);
     }
+
+    #[test]
+    fn test_meeting_sessions_and_dedupe() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let queue = IngestQueue::new(&temp_file.path().to_path_buf()).unwrap();
+        let metadata = serde_json::json!({ "title": "Standup" });
+
+        // One session per meeting, stable across calls
+        let session_id = queue
+            .session_for_meeting("m1", "2024-01-01T00:00:00Z", &metadata)
+            .unwrap();
+        assert_eq!(
+            queue
+                .session_for_meeting("m1", "2024-01-01T00:00:00Z", &metadata)
+                .unwrap(),
+            session_id
+        );
+        let session = queue.get_session(session_id).unwrap().unwrap();
+        assert_eq!(session.meeting_id, "m1");
+        assert!(!session.remote_started);
+
+        let frame = QueuedItem::Frame {
+            session_id,
+            captured_at: "2024-01-01T00:00:01Z".to_string(),
+            image_path: PathBuf::from("/tmp/f1.jpg"),
+            sha256: None,
+        };
+        assert!(queue.enqueue_unique("frame:1", &frame).unwrap());
+
+        // Uploaded items are kept as done, so re-queuing them is a no-op
+        let (id, _) = queue.get_next_pending().unwrap().unwrap();
+        queue.mark_uploading(id).unwrap();
+        assert_eq!(queue.get_state_counts().unwrap().uploading, 1);
+        queue.mark_completed(id).unwrap();
+        assert!(!queue.enqueue_unique("frame:1", &frame).unwrap());
+
+        let counts = queue.get_state_counts().unwrap();
+        assert_eq!((counts.pending, counts.done), (0, 1));
+        assert!(queue.get_next_pending().unwrap().is_none());
+
+        // An explicit re-push puts it back in line
+        queue.requeue("frame:1", &frame).unwrap();
+        assert_eq!(queue.get_next_pending().unwrap().unwrap().0, id);
+        assert_eq!(
+            queue.get_meeting_session("m1").unwrap().unwrap().session_id,
+            session_id
+        );
+        assert!(queue.get_meeting_session("m2").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_failed_items_stop_after_max_retries() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let queue = IngestQueue::new(&temp_file.path().to_path_buf()).unwrap();
+        queue
+            .enqueue_transcript(Uuid::new_v4(), "[]".to_string())
+            .unwrap();
+
+        let session_id = Uuid::new_v4();
+        queue
+            .enqueue_frame(
+                session_id,
+                "2024-01-01T00:00:01Z".to_string(),
+                PathBuf::from("/tmp/f1.jpg"),
+                None,
+            )
+            .unwrap();
+
+        // The transcript failing doesn't hold up the frame behind it
+        let (transcript_id, _) = queue.get_next_pending().unwrap().unwrap();
+        queue.mark_failed(transcript_id, "boom").unwrap();
+        let (frame_id, _) = queue
+            .get_next_pending_after(transcript_id)
+            .unwrap()
+            .unwrap();
+        assert!(frame_id > transcript_id);
+
+        for _ in 0..2 {
+            queue.mark_failed(transcript_id, "boom").unwrap();
+        }
+        for _ in 0..3 {
+            queue.mark_failed(frame_id, "500").unwrap();
+        }
+        assert!(queue.get_next_pending().unwrap().is_none());
+        assert_eq!(
+            queue.failed_frames(session_id).unwrap(),
+            vec![FailedFrame {
+                image_path: PathBuf::from("/tmp/f1.jpg"),
+                last_error: Some("500".to_string()),
+            }]
+        );
+        assert_eq!(queue.get_stats().unwrap(), (0, 2));
+        assert_eq!(queue.clear_failed().unwrap(), 2);
+    }
 }
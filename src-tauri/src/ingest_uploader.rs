@@ -0,0 +1,619 @@
+//! Ingest Uploader - Background drain of the Intelligence Pipeline queue
+//!
+//! Meetings are queued item by item (transcript chunks, frames, then a
+//! session end) under a session id that is fixed per meeting and stored in
+//! the queue database. The uploader starts that session on the server with
+//! the same id, so an upload interrupted by a quit or a network drop resumes
+//! where it stopped instead of opening a new session. Items are uploaded in
+//! queue order; uploaded items stay in the queue as `done` so queuing the
+//! same meeting again only adds what is new.
+//!
+//! Uploads are paced to `ingest_bandwidth_kbps` when it is set.
+
+use parking_lot::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+use crate::database::DatabaseManager;
+use crate::database::{Frame, Transcript};
+use crate::ingest_client::{sha256_hex, IngestClient, TranscriptSegment};
+use crate::ingest_queue::{FailedFrame, IngestQueue, IngestSession, QueuedItem};
+use crate::settings::SettingsManager;
+
+/// Pause between queue drains when nothing woke the uploader
+const DRAIN_INTERVAL_SECS: u64 = 30;
+/// Transcript segments per upload request
+const TRANSCRIPT_CHUNK_SIZE: usize = 200;
+/// Frames queued per meeting
+const MAX_FRAMES_PER_MEETING: i32 = 5000;
+/// Frame failures in a row that end a drain pass (likely a network problem)
+const MAX_CONSECUTIVE_FRAME_FAILURES: u32 = 3;
+
+/// Uploader status plus per-state queue counts
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct IngestQueueStats {
+    pub pending: usize,
+    pub uploading: usize,
+    pub done: usize,
+    pub failed: usize,
+    pub running: bool,
+    /// Session of the item being uploaded (or last uploaded)
+    pub current_session: Option<IngestSession>,
+    pub bandwidth_kbps: u32,
+    pub bytes_uploaded: u64,
+    pub last_error: Option<String>,
+}
+
+/// Items added by `enqueue_meeting` (already-queued items are skipped)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MeetingEnqueueResult {
+    pub session_id: Uuid,
+    pub transcript_chunks: usize,
+    pub frames: usize,
+    pub skipped: usize,
+    /// Frames of this meeting that ran out of upload retries
+    pub failed_frames: Vec<FailedFrame>,
+}
+
+/// Local frame the pipeline has no matching hash for
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MissingFrame {
+    pub frame_id: i64,
+    pub file_path: String,
+    pub sha256: String,
+}
+
+/// Local records compared against what the pipeline received for a meeting
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SessionVerification {
+    pub meeting_id: String,
+    pub session_id: Uuid,
+    pub local_frames: usize,
+    pub remote_frames: usize,
+    pub local_transcripts: usize,
+    pub remote_transcripts: usize,
+    pub missing_frames: Vec<MissingFrame>,
+    /// Local frames whose file could not be read for hashing
+    pub unreadable_frames: usize,
+    /// Items put back in the queue (only when re-pushing)
+    pub requeued: usize,
+}
+
+#[derive(Default)]
+struct UploadStats {
+    current_session: Option<Uuid>,
+    bytes_uploaded: u64,
+    last_error: Option<String>,
+}
+
+struct Shared {
+    running: AtomicBool,
+    wake: Notify,
+    stats: RwLock<UploadStats>,
+    client: Arc<IngestClient>,
+    queue: IngestQueue,
+    settings: Arc<SettingsManager>,
+}
+
+/// Ingest Uploader - drains the ingest queue in the background
+pub struct IngestUploader {
+    shared: Arc<Shared>,
+}
+
+impl IngestUploader {
+    pub fn new(
+        client: Arc<IngestClient>,
+        queue: IngestQueue,
+        settings: Arc<SettingsManager>,
+    ) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                running: AtomicBool::new(false),
+                wake: Notify::new(),
+                stats: RwLock::new(UploadStats::default()),
+                client,
+                queue,
+                settings,
+            }),
+        }
+    }
+
+    /// Queue a meeting's transcripts and frames under its session, followed
+    /// by the session end, and wake the uploader
+    pub async fn enqueue_meeting(
+        &self,
+        database: &DatabaseManager,
+        meeting_id: &str,
+    ) -> Result<MeetingEnqueueResult, String> {
+        let meeting = database
+            .get_meeting(meeting_id)
+            .await
+            .map_err(|e| format!("Failed to get meeting: {}", e))?
+            .ok_or_else(|| "Meeting not found".to_string())?;
+
+        let metadata = serde_json::json!({
+            "title": meeting.title,
+            "meeting_id": meeting.id,
+            "source": "nofriction_meetings",
+        });
+        let session_id = self
+            .shared
+            .queue
+            .session_for_meeting(meeting_id, &meeting.started_at.to_rfc3339(), &metadata)
+            .map_err(|e| format!("Failed to create ingest session: {}", e))?;
+
+        let transcripts = database
+            .get_transcripts(meeting_id)
+            .await
+            .map_err(|e| format!("Failed to get transcripts: {}", e))?;
+
+        let mut result = MeetingEnqueueResult {
+            session_id,
+            transcript_chunks: 0,
+            frames: 0,
+            skipped: 0,
+            failed_frames: Vec::new(),
+        };
+
+        for (key, item) in transcript_items(meeting_id, session_id, transcripts)? {
+            if self.enqueue_item(&key, &item)? {
+                result.transcript_chunks += 1;
+            } else {
+                result.skipped += 1;
+            }
+        }
+
+        let frames = database
+            .get_frames(meeting_id, MAX_FRAMES_PER_MEETING)
+            .await
+            .map_err(|e| format!("Failed to get frames: {}", e))?;
+        for frame in frames {
+            let Some((key, item)) = frame_item(session_id, frame, None) else {
+                continue;
+            };
+            if self.enqueue_item(&key, &item)? {
+                result.frames += 1;
+            } else {
+                result.skipped += 1;
+            }
+        }
+
+        let ended_at = meeting
+            .ended_at
+            .unwrap_or_else(chrono::Utc::now)
+            .to_rfc3339();
+        self.enqueue_item(
+            &format!("session_end:{}", session_id),
+            &QueuedItem::SessionEnd {
+                session_id,
+                ended_at,
+            },
+        )?;
+
+        result.failed_frames = self
+            .shared
+            .queue
+            .failed_frames(session_id)
+            .map_err(|e| format!("Failed to get failed frames: {}", e))?;
+
+        self.wake();
+        Ok(result)
+    }
+
+    fn enqueue_item(&self, key: &str, item: &QueuedItem) -> Result<bool, String> {
+        self.shared
+            .queue
+            .enqueue_unique(key, item)
+            .map_err(|e| format!("Failed to queue ingest item: {}", e))
+    }
+
+    /// Compare a meeting's local frames and transcripts with what the
+    /// pipeline received for its session. With `repush`, frames the server
+    /// lacks are queued again, and so are the transcripts if it has fewer
+    /// segments than we do.
+    pub async fn verify_meeting(
+        &self,
+        database: &DatabaseManager,
+        meeting_id: &str,
+        repush: bool,
+    ) -> Result<SessionVerification, String> {
+        let session = self
+            .shared
+            .queue
+            .get_meeting_session(meeting_id)
+            .map_err(|e| format!("Failed to load ingest session: {}", e))?
+            .ok_or_else(|| "Meeting has not been queued for ingest".to_string())?;
+        let session_id = session.session_id;
+
+        let remote = self
+            .shared
+            .client
+            .get_session_summary(session_id)
+            .await
+            .map_err(|e| format!("Failed to get remote session: {}", e))?;
+        let remote_hashes: std::collections::HashSet<String> = remote
+            .frame_hashes
+            .iter()
+            .map(|h| h.to_ascii_lowercase())
+            .collect();
+
+        let frames = database
+            .get_frames(meeting_id, MAX_FRAMES_PER_MEETING)
+            .await
+            .map_err(|e| format!("Failed to get frames: {}", e))?;
+        let transcripts = database
+            .get_transcripts(meeting_id)
+            .await
+            .map_err(|e| format!("Failed to get transcripts: {}", e))?;
+
+        let mut verification = SessionVerification {
+            meeting_id: meeting_id.to_string(),
+            session_id,
+            local_frames: 0,
+            remote_frames: remote.frame_count,
+            local_transcripts: transcripts.len(),
+            remote_transcripts: remote.transcript_count,
+            missing_frames: Vec::new(),
+            unreadable_frames: 0,
+            requeued: 0,
+        };
+
+        for frame in frames {
+            let Some(ref path) = frame.file_path else {
+                continue;
+            };
+            verification.local_frames += 1;
+            let data = match tokio::fs::read(path).await {
+                Ok(data) => data,
+                Err(_) => {
+                    verification.unreadable_frames += 1;
+                    continue;
+                }
+            };
+            let checksum = sha256_hex(&data);
+            if remote_hashes.contains(&checksum) {
+                continue;
+            }
+
+            let missing = MissingFrame {
+                frame_id: frame.id,
+                file_path: path.clone(),
+                sha256: checksum.clone(),
+            };
+            if repush {
+                if let Some((key, item)) = frame_item(session_id, frame, Some(checksum)) {
+                    self.requeue_item(&key, &item)?;
+                    verification.requeued += 1;
+                }
+            }
+            verification.missing_frames.push(missing);
+        }
+
+        if repush && verification.remote_transcripts < verification.local_transcripts {
+            for (key, item) in transcript_items(meeting_id, session_id, transcripts)? {
+                self.requeue_item(&key, &item)?;
+                verification.requeued += 1;
+            }
+        }
+        if verification.requeued > 0 {
+            self.wake();
+        }
+
+        Ok(verification)
+    }
+
+    fn requeue_item(&self, key: &str, item: &QueuedItem) -> Result<(), String> {
+        self.shared
+            .queue
+            .requeue(key, item)
+            .map_err(|e| format!("Failed to re-queue ingest item: {}", e))
+    }
+
+    /// Drain now instead of waiting for the next interval
+    pub fn wake(&self) {
+        self.shared.wake.notify_one();
+    }
+
+    pub async fn get_stats(&self) -> Result<IngestQueueStats, String> {
+        let counts = self
+            .shared
+            .queue
+            .get_state_counts()
+            .map_err(|e| format!("Failed to get queue stats: {}", e))?;
+        let bandwidth_kbps = self
+            .shared
+            .settings
+            .get_all()
+            .await
+            .map(|s| s.ingest_bandwidth_kbps)
+            .unwrap_or(0);
+        let (current_session, bytes_uploaded, last_error) = {
+            let stats = self.shared.stats.read();
+            (
+                stats.current_session,
+                stats.bytes_uploaded,
+                stats.last_error.clone(),
+            )
+        };
+        let current_session = match current_session {
+            Some(id) => self.shared.queue.get_session(id).unwrap_or(None),
+            None => None,
+        };
+
+        Ok(IngestQueueStats {
+            pending: counts.pending,
+            uploading: counts.uploading,
+            done: counts.done,
+            failed: counts.failed,
+            running: self.shared.running.load(Ordering::SeqCst),
+            current_session,
+            bandwidth_kbps,
+            bytes_uploaded,
+            last_error,
+        })
+    }
+
+    /// Start the uploader loop
+    pub fn start(&self) {
+        if self.shared.running.swap(true, Ordering::SeqCst) {
+            log::info!("Ingest uploader already running");
+            return;
+        }
+        log::info!("Ingest uploader starting...");
+
+        let shared = self.shared.clone();
+        tokio::spawn(async move {
+            run_loop(shared).await;
+        });
+    }
+
+    /// Stop the uploader loop; the item in flight finishes
+    pub fn stop(&self) {
+        self.shared.running.store(false, Ordering::SeqCst);
+        self.shared.wake.notify_one();
+        log::info!("Ingest uploader stopped");
+    }
+}
+
+async fn run_loop(shared: Arc<Shared>) {
+    log::info!("Ingest uploader loop started");
+
+    let mut wait = Duration::from_secs(5);
+    while shared.running.load(Ordering::SeqCst) {
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = shared.wake.notified() => {}
+        }
+        wait = Duration::from_secs(DRAIN_INTERVAL_SECS);
+        if !shared.running.load(Ordering::SeqCst) {
+            break;
+        }
+
+        // Pausing the queue (admin) turns off enable_ingest
+        if !shared.settings.feature_flags().read().enable_ingest {
+            continue;
+        }
+        let settings = shared.settings.get_all().await.unwrap_or_default();
+        drain(&shared, settings.ingest_bandwidth_kbps).await;
+    }
+
+    log::info!("Ingest uploader loop stopped");
+}
+
+/// Upload pending items in order until the queue is empty or an upload fails.
+/// A failed frame (already retried by the client) is skipped for this pass so
+/// the rest of the meeting still goes up; any other failure, or several
+/// frames failing in a row, stops the pass and the rest wait for the next
+/// one. Failed items stay pending until they run out of retries.
+async fn drain(shared: &Shared, bandwidth_kbps: u32) {
+    let started = Instant::now();
+    let mut bytes_sent: u64 = 0;
+    let mut cursor = 0;
+    let mut consecutive_frame_failures = 0;
+
+    while shared.running.load(Ordering::SeqCst) {
+        let (id, item) = match shared.queue.get_next_pending_after(cursor) {
+            Ok(Some(next)) => next,
+            Ok(None) => break,
+            Err(e) => {
+                log::warn!("Ingest uploader: failed to read queue: {}", e);
+                break;
+            }
+        };
+
+        let _ = shared.queue.mark_uploading(id);
+        shared.stats.write().current_session = Some(item.session_id());
+
+        match upload_item(shared, &item).await {
+            Ok(bytes) => {
+                let _ = shared.queue.mark_completed(id);
+                consecutive_frame_failures = 0;
+                bytes_sent += bytes;
+                let mut stats = shared.stats.write();
+                stats.bytes_uploaded += bytes;
+                stats.last_error = None;
+            }
+            Err(e) => {
+                log::warn!("Ingest uploader: item {} failed: {}", id, e);
+                let _ = shared.queue.mark_failed(id, &e);
+                shared.stats.write().last_error = Some(e);
+
+                consecutive_frame_failures += 1;
+                if !matches!(item, QueuedItem::Frame { .. })
+                    || consecutive_frame_failures >= MAX_CONSECUTIVE_FRAME_FAILURES
+                {
+                    break;
+                }
+                cursor = id;
+            }
+        }
+
+        let delay = throttle_delay(bytes_sent, started.elapsed(), bandwidth_kbps);
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Upload one item, starting its session first if the server hasn't seen it.
+/// Returns the bytes sent.
+async fn upload_item(shared: &Shared, item: &QueuedItem) -> Result<u64, String> {
+    let session_id = item.session_id();
+    let session = shared
+        .queue
+        .get_session(session_id)
+        .map_err(|e| format!("Failed to load ingest session: {}", e))?;
+    if let Some(session) = session {
+        if !session.remote_started {
+            let remote_id = shared
+                .client
+                .start_session(Some(session_id), session.started_at, session.metadata)
+                .await
+                .map_err(|e| format!("Failed to start session: {}", e))?;
+            if remote_id != session_id {
+                log::warn!(
+                    "Ingest server assigned session {} instead of {}",
+                    remote_id,
+                    session_id
+                );
+            }
+            shared
+                .queue
+                .mark_session_started(session_id)
+                .map_err(|e| format!("Failed to record session start: {}", e))?;
+        }
+    }
+
+    match item {
+        QueuedItem::Frame {
+            captured_at,
+            image_path,
+            sha256,
+            ..
+        } => {
+            let size = tokio::fs::metadata(image_path)
+                .await
+                .map_err(|e| format!("Frame file unavailable: {}", e))?
+                .len();
+            shared
+                .client
+                .upload_frame(session_id, captured_at.clone(), image_path, sha256.clone())
+                .await
+                .map_err(|e| format!("Failed to upload frame: {}", e))?;
+            Ok(size)
+        }
+        QueuedItem::Transcript { segments_json, .. } => {
+            let segments: Vec<TranscriptSegment> = serde_json::from_str(segments_json)
+                .map_err(|e| format!("Invalid queued transcript: {}", e))?;
+            shared
+                .client
+                .upload_transcript(session_id, segments)
+                .await
+                .map_err(|e| format!("Failed to upload transcripts: {}", e))?;
+            Ok(segments_json.len() as u64)
+        }
+        QueuedItem::SessionEnd { ended_at, .. } => {
+            shared
+                .client
+                .end_session(session_id, ended_at.clone())
+                .await
+                .map_err(|e| format!("Failed to end session: {}", e))?;
+            Ok(0)
+        }
+    }
+}
+
+/// Transcript chunks for a meeting, keyed by their id range so a re-queue
+/// only adds the chunks that changed
+fn transcript_items(
+    meeting_id: &str,
+    session_id: Uuid,
+    mut transcripts: Vec<Transcript>,
+) -> Result<Vec<(String, QueuedItem)>, String> {
+    transcripts.sort_by_key(|t| t.id);
+
+    let mut items = Vec::new();
+    for chunk in transcripts.chunks(TRANSCRIPT_CHUNK_SIZE) {
+        let key = format!(
+            "transcript:{}:{}-{}",
+            meeting_id,
+            chunk[0].id,
+            chunk[chunk.len() - 1].id
+        );
+        let segments: Vec<TranscriptSegment> = chunk
+            .iter()
+            .map(|t| TranscriptSegment {
+                start_at: t.timestamp.to_rfc3339(),
+                end_at: t.timestamp.to_rfc3339(),
+                text: t.text.clone(),
+                speaker: t.speaker.clone(),
+                confidence: Some(t.confidence as f64),
+            })
+            .collect();
+        let segments_json = serde_json::to_string(&segments)
+            .map_err(|e| format!("Failed to serialize transcripts: {}", e))?;
+        items.push((
+            key,
+            QueuedItem::Transcript {
+                session_id,
+                segments_json,
+            },
+        ));
+    }
+    Ok(items)
+}
+
+/// Queue item for a frame with a file on disk
+fn frame_item(
+    session_id: Uuid,
+    frame: Frame,
+    sha256: Option<String>,
+) -> Option<(String, QueuedItem)> {
+    let path = frame.file_path?;
+    Some((
+        format!("frame:{}", frame.id),
+        QueuedItem::Frame {
+            session_id,
+            captured_at: frame.timestamp.to_rfc3339(),
+            image_path: std::path::PathBuf::from(path),
+            sha256,
+        },
+    ))
+}
+
+/// How long to wait so that `bytes` sent since the drain started average out
+/// to at most `kbps` kilobits per second (0 = no cap)
+fn throttle_delay(bytes: u64, elapsed: Duration, kbps: u32) -> Duration {
+    if kbps == 0 {
+        return Duration::ZERO;
+    }
+    let target = Duration::from_secs_f64(bytes as f64 * 8.0 / (kbps as f64 * 1000.0));
+    target.saturating_sub(elapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle_delay() {
+        // Unlimited
+        assert_eq!(
+            throttle_delay(10_000_000, Duration::ZERO, 0),
+            Duration::ZERO
+        );
+        // 1 MB at 800 kbps takes 10s; 4s already elapsed
+        assert_eq!(
+            throttle_delay(1_000_000, Duration::from_secs(4), 800),
+            Duration::from_secs(6)
+        );
+        // Already slower than the cap
+        assert_eq!(
+            throttle_delay(1_000, Duration::from_secs(4), 800),
+            Duration::ZERO
+        );
+    }
+}
@@ -0,0 +1,681 @@
+// noFriction Meetings - Integrations
+// Outbound CRM webhooks for meeting summaries and action items
+//
+// Events are queued in a local SQLite file (same durable approach as the
+// ingest queue) and delivered by a background worker with exponential
+// backoff, so a CRM outage never loses a meeting's outputs.
+
+use crate::database::{ActionItemRecord, DatabaseManager, Meeting};
+use parking_lot::RwLock;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+pub const EVENT_NOTES_GENERATED: &str = "meeting-notes-generated";
+pub const EVENT_ACTION_ITEM_CREATED: &str = "action-item-created";
+pub const EVENT_MEETING_ENDED: &str = "meeting-ended";
+pub const WEBHOOK_EVENTS: [&str; 3] = [
+    EVENT_NOTES_GENERATED,
+    EVENT_ACTION_ITEM_CREATED,
+    EVENT_MEETING_ENDED,
+];
+
+pub const SIGNATURE_HEADER: &str = "X-NoFriction-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-NoFriction-Timestamp";
+pub const EVENT_HEADER: &str = "X-NoFriction-Event";
+pub const DELIVERY_HEADER: &str = "X-NoFriction-Delivery";
+
+const MAX_ATTEMPTS: u32 = 8;
+const BASE_BACKOFF_SECS: i64 = 30;
+const MAX_BACKOFF_SECS: i64 = 3600;
+const WORKER_INTERVAL_SECS: u64 = 15;
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+/// Delivered rows kept for the delivery log
+const DELIVERED_RETENTION: i64 = 500;
+
+/// Where and what to send
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    pub url: String,
+    pub bearer_token: Option<String>,
+    /// HMAC-SHA256 signing secret; payloads are unsigned without one
+    pub secret: Option<String>,
+    pub event_types: Vec<String>,
+}
+
+impl WebhookConfig {
+    /// Build from saved settings; None when no URL is configured
+    pub fn from_settings(settings: &crate::settings::AppSettings) -> Option<Self> {
+        let url = settings
+            .webhook_url
+            .clone()
+            .filter(|u| !u.trim().is_empty())?;
+        Some(Self {
+            url,
+            bearer_token: settings.webhook_bearer_token.clone(),
+            secret: settings.webhook_secret.clone(),
+            event_types: parse_event_types(&settings.webhook_events),
+        })
+    }
+
+    pub fn wants(&self, event_type: &str) -> bool {
+        self.event_types.iter().any(|e| e == event_type)
+    }
+}
+
+/// Comma-separated event list from settings, unknown names dropped
+pub fn parse_event_types(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|e| e.trim())
+        .filter(|e| WEBHOOK_EVENTS.contains(e))
+        .map(|e| e.to_string())
+        .collect()
+}
+
+/// Validate event names coming from the UI
+pub fn validate_event_types(event_types: &[String]) -> Result<(), String> {
+    for event_type in event_types {
+        if !WEBHOOK_EVENTS.contains(&event_type.as_str()) {
+            return Err(format!(
+                "Unknown webhook event '{}'; expected one of: {}",
+                event_type,
+                WEBHOOK_EVENTS.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Delay before the next attempt after `attempts` failures
+pub fn backoff_secs(attempts: u32) -> i64 {
+    (BASE_BACKOFF_SECS << attempts.min(10)).min(MAX_BACKOFF_SECS)
+}
+
+/// HMAC-SHA256 as lowercase hex
+pub fn hmac_sha256_hex(key: &[u8], message: &[u8]) -> String {
+    const BLOCK_SIZE: usize = 64;
+
+    let mut block = if key.len() > BLOCK_SIZE {
+        Sha256::digest(key).to_vec()
+    } else {
+        key.to_vec()
+    };
+    block.resize(BLOCK_SIZE, 0);
+
+    let ipad: Vec<u8> = block.iter().map(|b| b ^ 0x36).collect();
+    let opad: Vec<u8> = block.iter().map(|b| b ^ 0x5c).collect();
+
+    let inner = Sha256::new()
+        .chain_update(&ipad)
+        .chain_update(message)
+        .finalize();
+    let outer = Sha256::new()
+        .chain_update(&opad)
+        .chain_update(inner)
+        .finalize();
+    format!("{:x}", outer)
+}
+
+/// Signature header value over "<timestamp>.<body>"
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let message = format!("{}.{}", timestamp, body);
+    format!(
+        "sha256={}",
+        hmac_sha256_hex(secret.as_bytes(), message.as_bytes())
+    )
+}
+
+/// JSON envelope shared by every event
+pub fn envelope(event_type: &str, data: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "id": uuid::Uuid::new_v4().to_string(),
+        "event": event_type,
+        "created_at": chrono::Utc::now().to_rfc3339(),
+        "source": "nofriction-meetings",
+        "data": data,
+    })
+}
+
+/// One row of the delivery log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub event_type: String,
+    /// pending | delivered | failed
+    pub status: String,
+    pub attempts: u32,
+    pub last_status_code: Option<u16>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub next_attempt_at: Option<String>,
+    pub delivered_at: Option<String>,
+}
+
+/// Result of a test_webhook call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookTestResult {
+    pub status_code: u16,
+    pub success: bool,
+    pub response: String,
+}
+
+/// Durable outbound queue
+#[derive(Debug)]
+pub struct WebhookQueue {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl WebhookQueue {
+    pub fn new(db_path: &Path) -> rusqlite::Result<Self> {
+        let conn = Connection::open(db_path)?;
+        Self::with_connection(conn)
+    }
+
+    fn with_connection(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS webhook_deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                event_type TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending',
+                attempts INTEGER NOT NULL DEFAULT 0,
+                max_attempts INTEGER NOT NULL,
+                next_attempt_at TEXT,
+                last_status_code INTEGER,
+                last_error TEXT,
+                created_at TEXT NOT NULL,
+                delivered_at TEXT
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due ON webhook_deliveries(status, next_attempt_at)",
+            [],
+        )?;
+
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Queue a payload for immediate delivery
+    pub fn enqueue(&self, event_type: &str, payload: &str) -> rusqlite::Result<i64> {
+        let now = timestamp(chrono::Utc::now());
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO webhook_deliveries (event_type, payload, max_attempts, next_attempt_at, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)",
+            params![event_type, payload, MAX_ATTEMPTS, now],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Pending deliveries whose backoff has elapsed, oldest first
+    pub fn due(&self, limit: u32) -> rusqlite::Result<Vec<(i64, String, String)>> {
+        let now = timestamp(chrono::Utc::now());
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, event_type, payload FROM webhook_deliveries
+             WHERE status = 'pending' AND next_attempt_at <= ?1
+             ORDER BY id ASC LIMIT ?2",
+        )?;
+        let rows = stmt
+            .query_map(params![now, limit], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    pub fn mark_delivered(&self, id: i64, status_code: u16) -> rusqlite::Result<()> {
+        let now = timestamp(chrono::Utc::now());
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE webhook_deliveries
+             SET status = 'delivered', attempts = attempts + 1, last_status_code = ?1,
+                 last_error = NULL, next_attempt_at = NULL, delivered_at = ?2
+             WHERE id = ?3",
+            params![status_code, now, id],
+        )?;
+        conn.execute(
+            "DELETE FROM webhook_deliveries WHERE status = 'delivered' AND id NOT IN (
+                SELECT id FROM webhook_deliveries WHERE status = 'delivered'
+                ORDER BY id DESC LIMIT ?1
+             )",
+            params![DELIVERED_RETENTION],
+        )?;
+        Ok(())
+    }
+
+    /// Record a failed attempt; reschedules with backoff or gives up
+    pub fn mark_failed(
+        &self,
+        id: i64,
+        status_code: Option<u16>,
+        error: &str,
+    ) -> rusqlite::Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let (attempts, max_attempts): (u32, u32) = match conn
+            .query_row(
+                "SELECT attempts, max_attempts FROM webhook_deliveries WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?
+        {
+            Some(counts) => counts,
+            None => return Ok(()),
+        };
+
+        let attempts = attempts + 1;
+        let (status, next_attempt_at) = if attempts >= max_attempts {
+            ("failed", None)
+        } else {
+            let next = chrono::Utc::now() + chrono::Duration::seconds(backoff_secs(attempts - 1));
+            ("pending", Some(timestamp(next)))
+        };
+
+        conn.execute(
+            "UPDATE webhook_deliveries
+             SET status = ?1, attempts = ?2, last_status_code = ?3, last_error = ?4, next_attempt_at = ?5
+             WHERE id = ?6",
+            params![status, attempts, status_code, error, next_attempt_at, id],
+        )?;
+        Ok(())
+    }
+
+    /// Most recent deliveries first
+    pub fn log(&self, limit: u32) -> rusqlite::Result<Vec<WebhookDelivery>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, event_type, status, attempts, last_status_code, last_error,
+                    created_at, next_attempt_at, delivered_at
+             FROM webhook_deliveries ORDER BY id DESC LIMIT ?1",
+        )?;
+        let rows = stmt
+            .query_map(params![limit], |row| {
+                Ok(WebhookDelivery {
+                    id: row.get(0)?,
+                    event_type: row.get(1)?,
+                    status: row.get(2)?,
+                    attempts: row.get(3)?,
+                    last_status_code: row.get(4)?,
+                    last_error: row.get(5)?,
+                    created_at: row.get(6)?,
+                    next_attempt_at: row.get(7)?,
+                    delivered_at: row.get(8)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+}
+
+/// Process-wide webhook dispatcher
+pub struct WebhookDispatcher {
+    queue: RwLock<Option<Arc<WebhookQueue>>>,
+    config: RwLock<Option<WebhookConfig>>,
+    client: reqwest::Client,
+    draining: AtomicBool,
+    worker_started: AtomicBool,
+}
+
+pub fn webhooks() -> &'static WebhookDispatcher {
+    static DISPATCHER: OnceLock<WebhookDispatcher> = OnceLock::new();
+    DISPATCHER.get_or_init(|| WebhookDispatcher {
+        queue: RwLock::new(None),
+        config: RwLock::new(None),
+        client: reqwest::Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default(),
+        draining: AtomicBool::new(false),
+        worker_started: AtomicBool::new(false),
+    })
+}
+
+impl WebhookDispatcher {
+    /// Open the durable queue
+    pub fn init(&self, db_path: &Path) -> Result<(), String> {
+        let queue = WebhookQueue::new(db_path)
+            .map_err(|e| format!("Failed to open webhook queue: {}", e))?;
+        *self.queue.write() = Some(Arc::new(queue));
+        Ok(())
+    }
+
+    /// Replace the active configuration; None disables new events
+    pub fn configure(&self, config: Option<WebhookConfig>) {
+        *self.config.write() = config;
+    }
+
+    pub fn config(&self) -> Option<WebhookConfig> {
+        self.config.read().clone()
+    }
+
+    /// Spawn the retry loop once
+    pub fn start_worker(&'static self) {
+        if self.worker_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(WORKER_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                self.deliver_due().await;
+            }
+        });
+    }
+
+    /// Queue an event if the webhook wants it, then kick off delivery
+    pub fn emit(&'static self, event_type: &str, data: serde_json::Value) {
+        let wanted = self
+            .config
+            .read()
+            .as_ref()
+            .map(|c| c.wants(event_type))
+            .unwrap_or(false);
+        if !wanted {
+            return;
+        }
+        let Some(queue) = self.queue.read().clone() else {
+            return;
+        };
+
+        let payload = envelope(event_type, data).to_string();
+        if let Err(e) = queue.enqueue(event_type, &payload) {
+            log::warn!("Failed to queue webhook event {}: {}", event_type, e);
+            return;
+        }
+        tokio::spawn(async move {
+            self.deliver_due().await;
+        });
+    }
+
+    /// Send everything that is due; returns how many were delivered
+    pub async fn deliver_due(&self) -> usize {
+        if self.draining.swap(true, Ordering::SeqCst) {
+            return 0;
+        }
+        let delivered = self.drain().await;
+        self.draining.store(false, Ordering::SeqCst);
+        delivered
+    }
+
+    async fn drain(&self) -> usize {
+        let Some(config) = self.config() else {
+            return 0;
+        };
+        let Some(queue) = self.queue.read().clone() else {
+            return 0;
+        };
+        let due = match queue.due(20) {
+            Ok(due) => due,
+            Err(e) => {
+                log::warn!("Failed to read webhook queue: {}", e);
+                return 0;
+            }
+        };
+
+        let mut delivered = 0;
+        for (id, event_type, payload) in due {
+            let result = self.send(&config, &event_type, &payload).await;
+            let recorded = match result {
+                Ok((status, _)) if (200..300).contains(&status) => {
+                    delivered += 1;
+                    queue.mark_delivered(id, status)
+                }
+                Ok((status, body)) => queue.mark_failed(id, Some(status), &truncate(&body, 500)),
+                Err(e) => queue.mark_failed(id, None, &e),
+            };
+            if let Err(e) = recorded {
+                log::warn!("Failed to update webhook delivery {}: {}", id, e);
+            }
+        }
+        delivered
+    }
+
+    /// POST one signed payload; returns status code and response body
+    async fn send(
+        &self,
+        config: &WebhookConfig,
+        event_type: &str,
+        payload: &str,
+    ) -> Result<(u16, String), String> {
+        let timestamp = chrono::Utc::now().timestamp();
+        let delivery_id = serde_json::from_str::<serde_json::Value>(payload)
+            .ok()
+            .and_then(|v| v["id"].as_str().map(|s| s.to_string()))
+            .unwrap_or_default();
+
+        let mut request = self
+            .client
+            .post(&config.url)
+            .header("Content-Type", "application/json")
+            .header(EVENT_HEADER, event_type)
+            .header(DELIVERY_HEADER, delivery_id)
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .body(payload.to_string());
+        if let Some(token) = config.bearer_token.as_deref().filter(|t| !t.is_empty()) {
+            request = request.bearer_auth(token);
+        }
+        if let Some(secret) = config.secret.as_deref().filter(|s| !s.is_empty()) {
+            request = request.header(SIGNATURE_HEADER, sign_payload(secret, timestamp, payload));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Webhook request failed: {}", e))?;
+        let status = response.status().as_u16();
+        let body = response.text().await.unwrap_or_default();
+        Ok((status, body))
+    }
+
+    /// Send a sample payload straight to the configured URL, bypassing the queue
+    pub async fn test(&self) -> Result<WebhookTestResult, String> {
+        let config = self
+            .config()
+            .ok_or_else(|| "No webhook URL configured".to_string())?;
+
+        let sample = envelope(
+            "test",
+            serde_json::json!({
+                "meeting": {
+                    "id": "sample-meeting",
+                    "title": "Sample meeting",
+                    "started_at": chrono::Utc::now().to_rfc3339(),
+                    "ended_at": null,
+                    "duration_seconds": null,
+                },
+                "summary": "This is a test payload from noFriction Meetings.",
+                "action_items": [{
+                    "text": "Confirm the webhook integration works",
+                    "assignee": null,
+                    "due_hint": null,
+                    "status": "open",
+                }],
+            }),
+        )
+        .to_string();
+
+        let (status_code, body) = self.send(&config, "test", &sample).await?;
+        Ok(WebhookTestResult {
+            status_code,
+            success: (200..300).contains(&status_code),
+            response: truncate(&body, 1000),
+        })
+    }
+
+    pub fn delivery_log(&self, limit: u32) -> Result<Vec<WebhookDelivery>, String> {
+        let queue = self
+            .queue
+            .read()
+            .clone()
+            .ok_or_else(|| "Webhook queue not initialized".to_string())?;
+        queue
+            .log(limit)
+            .map_err(|e| format!("Failed to read webhook delivery log: {}", e))
+    }
+}
+
+/// Fixed-width RFC3339 so queue timestamps compare as strings
+fn timestamp(at: chrono::DateTime<chrono::Utc>) -> String {
+    at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    text.chars().take(max_chars).collect()
+}
+
+fn meeting_json(meeting: &Meeting) -> serde_json::Value {
+    serde_json::json!({
+        "id": meeting.id,
+        "title": meeting.title,
+        "started_at": meeting.started_at.to_rfc3339(),
+        "ended_at": meeting.ended_at.map(|t| t.to_rfc3339()),
+        "duration_seconds": meeting.duration_seconds,
+    })
+}
+
+fn action_item_json(item: &ActionItemRecord) -> serde_json::Value {
+    serde_json::json!({
+        "id": item.id,
+        "text": item.text,
+        "assignee": item.assignee,
+        "due_hint": item.due_hint,
+        "status": item.status,
+        "source": item.source,
+    })
+}
+
+/// Emit a meeting-level event with metadata, summary and action items
+pub async fn emit_meeting_event(
+    database: &Arc<DatabaseManager>,
+    event_type: &str,
+    meeting_id: &str,
+) {
+    let wanted = webhooks()
+        .config()
+        .map(|c| c.wants(event_type))
+        .unwrap_or(false);
+    if !wanted {
+        return;
+    }
+
+    let meeting = match database.get_meeting(meeting_id).await {
+        Ok(Some(meeting)) => meeting,
+        Ok(None) => return,
+        Err(e) => {
+            log::warn!("Failed to load meeting for webhook: {}", e);
+            return;
+        }
+    };
+    let notes = database.get_meeting_notes(meeting_id).await.ok().flatten();
+    let action_items = database
+        .list_action_items(Some(meeting_id))
+        .await
+        .unwrap_or_default();
+
+    let parse = |raw: &Option<String>| {
+        raw.as_deref()
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+            .unwrap_or(serde_json::Value::Null)
+    };
+
+    let data = serde_json::json!({
+        "meeting": meeting_json(&meeting),
+        "summary": notes.as_ref().and_then(|n| n.summary.clone()),
+        "key_topics": notes.as_ref().map(|n| parse(&n.key_topics)),
+        "decisions": notes.as_ref().map(|n| parse(&n.decisions)),
+        "action_items": action_items.iter().map(action_item_json).collect::<Vec<_>>(),
+    });
+    webhooks().emit(event_type, data);
+}
+
+/// Emit action-item-created for a freshly inserted item
+pub async fn emit_action_item_created(
+    database: &Arc<DatabaseManager>,
+    meeting_id: &str,
+    normalized_text: &str,
+) {
+    let wanted = webhooks()
+        .config()
+        .map(|c| c.wants(EVENT_ACTION_ITEM_CREATED))
+        .unwrap_or(false);
+    if !wanted {
+        return;
+    }
+
+    let meeting = match database.get_meeting(meeting_id).await {
+        Ok(Some(meeting)) => meeting,
+        _ => return,
+    };
+    let item = database
+        .list_action_items(Some(meeting_id))
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .find(|item| crate::action_items::normalize_text(&item.text) == normalized_text);
+    let Some(item) = item else {
+        return;
+    };
+
+    let data = serde_json::json!({
+        "meeting": meeting_json(&meeting),
+        "action_item": action_item_json(&item),
+    });
+    webhooks().emit(EVENT_ACTION_ITEM_CREATED, data);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hmac_matches_rfc4231() {
+        assert_eq!(
+            hmac_sha256_hex(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert!(sign_payload("secret", 1700000000, "{}").starts_with("sha256="));
+    }
+
+    #[test]
+    fn test_backoff_and_event_parsing() {
+        assert_eq!(backoff_secs(0), 30);
+        assert_eq!(backoff_secs(1), 60);
+        assert_eq!(backoff_secs(20), MAX_BACKOFF_SECS);
+        assert_eq!(
+            parse_event_types("meeting-ended, bogus,action-item-created"),
+            vec!["meeting-ended", "action-item-created"]
+        );
+        assert!(validate_event_types(&["nope".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_queue_retries_then_gives_up() {
+        let queue = WebhookQueue::with_connection(Connection::open_in_memory().unwrap()).unwrap();
+        let id = queue.enqueue(EVENT_MEETING_ENDED, "{}").unwrap();
+        assert_eq!(queue.due(10).unwrap().len(), 1);
+
+        queue.mark_failed(id, Some(500), "boom").unwrap();
+        // Backed off, so not due yet
+        assert!(queue.due(10).unwrap().is_empty());
+        assert_eq!(queue.log(10).unwrap()[0].status, "pending");
+
+        for _ in 1..MAX_ATTEMPTS {
+            queue.mark_failed(id, None, "down").unwrap();
+        }
+        let entry = &queue.log(10).unwrap()[0];
+        assert_eq!(entry.status, "failed");
+        assert_eq!(entry.attempts, MAX_ATTEMPTS);
+
+        let id = queue.enqueue(EVENT_NOTES_GENERATED, "{}").unwrap();
+        queue.mark_delivered(id, 200).unwrap();
+        assert_eq!(queue.log(10).unwrap()[0].status, "delivered");
+    }
+}
@@ -5,10 +5,13 @@
 // - 30-minute check-in for manual recordings
 // - Meeting-end confirmation (extend/snooze/end)
 // - Break detection prompt (silence detection)
+// - Dead-mic prompt when the microphone goes silent mid-recording
+// - Check-ins after long stretches of ambient capture, with a default
+//   decision applied when nobody answers
 // - Frontend notification integration
 
 use chrono::{DateTime, Duration, Utc};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
@@ -27,6 +30,8 @@ pub enum PromptType {
     StorageWarning,
     /// Idle detection prompt
     IdleWarning,
+    /// Microphone input went silent while recording
+    MicSilent,
 }
 
 /// User response to a prompt
@@ -45,7 +50,7 @@ pub enum PromptResponse {
 }
 
 /// A pending prompt waiting for user response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PendingPrompt {
     pub id: String,
     pub prompt_type: PromptType,
@@ -73,6 +78,8 @@ pub struct InteractionConfig {
     pub storage_warning_enabled: bool,
     /// Storage warning threshold (GB remaining)
     pub storage_warning_threshold_gb: f64,
+    /// Ask whether to keep recording when the microphone goes silent
+    pub mic_silence_prompt_enabled: bool,
     /// Prompt expiration time (seconds)
     pub prompt_timeout_secs: u32,
 }
@@ -87,11 +94,154 @@ impl Default for InteractionConfig {
             break_silence_threshold_secs: 180, // 3 minutes
             storage_warning_enabled: true,
             storage_warning_threshold_gb: 5.0,
+            mic_silence_prompt_enabled: true,
             prompt_timeout_secs: 60, // 1 minute to respond
         }
     }
 }
 
+/// What to do with ambient capture when a check-in is answered or times out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckInDecision {
+    /// Keep capturing; ask again after another interval
+    Continue,
+    /// Pause ambient capture until the user resumes it
+    Pause,
+}
+
+impl CheckInDecision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckInDecision::Continue => "continue",
+            CheckInDecision::Pause => "pause",
+        }
+    }
+}
+
+/// When to ask whether ambient capture should keep running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckInPolicy {
+    pub enabled: bool,
+    /// Minutes of continuous capture before asking
+    pub interval_mins: u32,
+    /// Seconds to wait for an answer before applying `default_action`
+    pub timeout_secs: u32,
+    pub default_action: CheckInDecision,
+}
+
+impl Default for CheckInPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_mins: 120,
+            timeout_secs: 300,
+            default_action: CheckInDecision::Continue,
+        }
+    }
+}
+
+impl CheckInPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(5..=1440).contains(&self.interval_mins) {
+            return Err("Check-in interval must be between 5 and 1440 minutes".to_string());
+        }
+        if !(10..=3600).contains(&self.timeout_secs) {
+            return Err("Check-in timeout must be between 10 and 3600 seconds".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Payload of `interaction-checkin`
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckInEvent {
+    #[serde(flatten)]
+    pub prompt: PendingPrompt,
+    /// Minutes capture has been running without a break
+    pub capturing_mins: i64,
+    /// Applied if the prompt expires unanswered
+    pub default_action: CheckInDecision,
+}
+
+/// What a check-in tick asks the caller to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckInTransition {
+    /// A new check-in was raised
+    Ask(PendingPrompt),
+    /// The open check-in expired; apply the policy's default action
+    TimedOut(PendingPrompt),
+}
+
+/// Tracks continuous ambient capture and the open check-in, if any
+#[derive(Debug, Default)]
+pub struct CheckInTracker {
+    capturing_since: Option<DateTime<Utc>>,
+    pending: Option<PendingPrompt>,
+}
+
+impl CheckInTracker {
+    /// Feed the current capture state. Any break in capture (pause, idle,
+    /// stop) restarts the interval and drops an open check-in.
+    pub fn observe(
+        &mut self,
+        now: DateTime<Utc>,
+        capturing: bool,
+        policy: &CheckInPolicy,
+    ) -> Option<CheckInTransition> {
+        if !capturing || !policy.enabled {
+            self.capturing_since = None;
+            self.pending = None;
+            return None;
+        }
+        let since = *self.capturing_since.get_or_insert(now);
+
+        if let Some(prompt) = &self.pending {
+            if prompt.expires_at.is_some_and(|expires| now >= expires) {
+                self.capturing_since = Some(now);
+                return self.pending.take().map(CheckInTransition::TimedOut);
+            }
+            return None;
+        }
+
+        let capturing_mins = (now - since).num_minutes();
+        if capturing_mins < policy.interval_mins as i64 {
+            return None;
+        }
+        let prompt = PendingPrompt {
+            id: uuid::Uuid::new_v4().to_string(),
+            prompt_type: PromptType::CheckIn,
+            title: "Still capturing?".to_string(),
+            message: format!(
+                "Ambient capture has been running for {}h {:02}m. Keep capturing?",
+                capturing_mins / 60,
+                capturing_mins % 60
+            ),
+            created_at: now,
+            expires_at: Some(now + Duration::seconds(policy.timeout_secs as i64)),
+            meeting_id: None,
+        };
+        self.pending = Some(prompt.clone());
+        Some(CheckInTransition::Ask(prompt))
+    }
+
+    /// Close the open check-in after the user answered; the interval
+    /// restarts from `now`
+    pub fn answer(&mut self, now: DateTime<Utc>) -> Option<PendingPrompt> {
+        let prompt = self.pending.take()?;
+        self.capturing_since = Some(now);
+        Some(prompt)
+    }
+
+    /// Minutes of continuous capture so far
+    pub fn capturing_mins(&self, now: DateTime<Utc>) -> i64 {
+        self.capturing_since
+            .map(|since| (now - since).num_minutes())
+            .unwrap_or(0)
+    }
+}
+
 /// Callback for prompt events
 pub type PromptCallback = Arc<dyn Fn(PendingPrompt) + Send + Sync>;
 pub type ResponseCallback = Arc<dyn Fn(PendingPrompt, PromptResponse) + Send + Sync>;
@@ -103,6 +253,7 @@ pub struct InteractionStats {
     pub responses_continue: u64,
     pub responses_end: u64,
     pub responses_snooze: u64,
+    pub responses_pause: u64,
     pub responses_dismissed: u64,
     pub responses_expired: u64,
 }
@@ -118,6 +269,8 @@ pub struct InteractionLoop {
     last_audio_activity: Arc<RwLock<Option<DateTime<Utc>>>>,
     on_prompt: Arc<RwLock<Option<PromptCallback>>>,
     on_response: Arc<RwLock<Option<ResponseCallback>>>,
+    checkin_policy: RwLock<CheckInPolicy>,
+    checkin: Mutex<CheckInTracker>,
     app_handle: Option<AppHandle>,
 }
 
@@ -133,6 +286,8 @@ impl InteractionLoop {
             last_audio_activity: Arc::new(RwLock::new(None)),
             on_prompt: Arc::new(RwLock::new(None)),
             on_response: Arc::new(RwLock::new(None)),
+            checkin_policy: RwLock::new(CheckInPolicy::default()),
+            checkin: Mutex::new(CheckInTracker::default()),
             app_handle: None,
         }
     }
@@ -342,6 +497,50 @@ impl InteractionLoop {
         }
     }
 
+    /// Ask whether to keep recording after the microphone went silent
+    pub fn show_mic_silent_prompt(&self, meeting_id: &str, silent_secs: u64) {
+        let cfg = self.config.read();
+        if !cfg.mic_silence_prompt_enabled {
+            return;
+        }
+
+        // One open dead-mic prompt at a time
+        if self
+            .pending_prompts
+            .read()
+            .iter()
+            .any(|p| p.prompt_type == PromptType::MicSilent)
+        {
+            return;
+        }
+
+        let now = Utc::now();
+        let prompt = PendingPrompt {
+            id: uuid::Uuid::new_v4().to_string(),
+            prompt_type: PromptType::MicSilent,
+            title: "Microphone silent".to_string(),
+            message: format!(
+                "Your mic seems silent ({}s without input) — keep recording?",
+                silent_secs
+            ),
+            created_at: now,
+            expires_at: Some(now + Duration::seconds(cfg.prompt_timeout_secs as i64)),
+            meeting_id: Some(meeting_id.to_string()),
+        };
+
+        log::info!("💬 Showing mic-silent prompt for meeting {}", meeting_id);
+        self.stats.write().prompts_shown += 1;
+        self.pending_prompts.write().push(prompt.clone());
+
+        if let Some(ref app) = self.app_handle {
+            let _ = app.emit("interaction-prompt", prompt.clone());
+        }
+
+        if let Some(ref cb) = *self.on_prompt.read() {
+            cb(prompt);
+        }
+    }
+
     /// Show storage warning
     pub fn show_storage_warning(&self, available_gb: f64) {
         let cfg = self.config.read();
@@ -412,6 +611,68 @@ impl InteractionLoop {
         Ok(())
     }
 
+    /// Current ambient capture check-in policy
+    pub fn checkin_policy(&self) -> CheckInPolicy {
+        *self.checkin_policy.read()
+    }
+
+    pub fn set_checkin_policy(&self, policy: CheckInPolicy) {
+        *self.checkin_policy.write() = policy;
+    }
+
+    /// Advance the ambient check-in; a new check-in is emitted as
+    /// `interaction-checkin`, a timed-out one is returned for the caller to
+    /// apply the default action
+    pub fn tick_checkin(&self, now: DateTime<Utc>, capturing: bool) -> Option<CheckInTransition> {
+        let policy = self.checkin_policy();
+        let mut tracker = self.checkin.lock();
+        let capturing_mins = tracker.capturing_mins(now);
+        let transition = tracker.observe(now, capturing, &policy)?;
+        drop(tracker);
+
+        match &transition {
+            CheckInTransition::Ask(prompt) => {
+                log::info!("💬 Showing ambient check-in after {} min", capturing_mins);
+                self.stats.write().prompts_shown += 1;
+                if let Some(ref app) = self.app_handle {
+                    let event = CheckInEvent {
+                        prompt: prompt.clone(),
+                        capturing_mins,
+                        default_action: policy.default_action,
+                    };
+                    let _ = app.emit("interaction-checkin", event);
+                }
+            }
+            CheckInTransition::TimedOut(prompt) => {
+                log::info!(
+                    "💬 Check-in {} unanswered, applying default: {}",
+                    prompt.id,
+                    policy.default_action.as_str()
+                );
+                self.stats.write().responses_expired += 1;
+            }
+        }
+        Some(transition)
+    }
+
+    /// Answer the open check-in; errors when none is waiting
+    pub fn answer_checkin(&self, decision: CheckInDecision) -> Result<PendingPrompt, String> {
+        let prompt = self
+            .checkin
+            .lock()
+            .answer(Utc::now())
+            .ok_or("No check-in is waiting for an answer")?;
+        {
+            let mut stats = self.stats.write();
+            match decision {
+                CheckInDecision::Continue => stats.responses_continue += 1,
+                CheckInDecision::Pause => stats.responses_pause += 1,
+            }
+        }
+        log::info!("💬 Check-in {} answered: {}", prompt.id, decision.as_str());
+        Ok(prompt)
+    }
+
     /// Get pending prompts
     pub fn get_pending_prompts(&self) -> Vec<PendingPrompt> {
         self.pending_prompts.read().clone()
@@ -466,4 +727,84 @@ mod tests {
         let stats = loop_mgr.get_stats();
         assert_eq!(stats.prompts_shown, 0);
     }
+
+    #[test]
+    fn test_mic_silent_prompt_not_duplicated() {
+        let loop_mgr = InteractionLoop::new();
+        loop_mgr.show_mic_silent_prompt("meeting-1", 30);
+        loop_mgr.show_mic_silent_prompt("meeting-1", 31);
+
+        let prompts = loop_mgr.get_pending_prompts();
+        assert_eq!(prompts.len(), 1);
+        assert_eq!(prompts[0].prompt_type, PromptType::MicSilent);
+        assert_eq!(prompts[0].meeting_id.as_deref(), Some("meeting-1"));
+    }
+
+    #[test]
+    fn test_checkin_asks_after_interval_and_times_out() {
+        let policy = CheckInPolicy {
+            interval_mins: 120,
+            timeout_secs: 300,
+            ..Default::default()
+        };
+        let mut tracker = CheckInTracker::default();
+        let start = Utc::now();
+
+        assert!(tracker.observe(start, true, &policy).is_none());
+        assert!(tracker
+            .observe(start + Duration::minutes(119), true, &policy)
+            .is_none());
+
+        let asked_at = start + Duration::minutes(120);
+        let Some(CheckInTransition::Ask(prompt)) = tracker.observe(asked_at, true, &policy) else {
+            panic!("expected a check-in");
+        };
+        assert_eq!(prompt.prompt_type, PromptType::CheckIn);
+        // Only one open check-in at a time
+        assert!(tracker
+            .observe(asked_at + Duration::seconds(60), true, &policy)
+            .is_none());
+
+        let expired = tracker.observe(asked_at + Duration::seconds(300), true, &policy);
+        assert_eq!(expired, Some(CheckInTransition::TimedOut(prompt)));
+        // The interval restarts after the default is applied
+        assert!(tracker
+            .observe(asked_at + Duration::minutes(60), true, &policy)
+            .is_none());
+    }
+
+    #[test]
+    fn test_checkin_resets_on_break_and_answer() {
+        let policy = CheckInPolicy::default();
+        let mut tracker = CheckInTracker::default();
+        let start = Utc::now();
+
+        tracker.observe(start, true, &policy);
+        assert!(matches!(
+            tracker.observe(start + Duration::minutes(120), true, &policy),
+            Some(CheckInTransition::Ask(_))
+        ));
+        let answered_at = start + Duration::minutes(121);
+        assert!(tracker.answer(answered_at).is_some());
+        assert!(tracker.answer(answered_at).is_none());
+        assert!(tracker
+            .observe(answered_at + Duration::minutes(119), true, &policy)
+            .is_none());
+
+        // A pause in capture starts the count over
+        tracker.observe(answered_at + Duration::minutes(119), false, &policy);
+        let resumed = answered_at + Duration::minutes(130);
+        tracker.observe(resumed, true, &policy);
+        assert!(tracker
+            .observe(resumed + Duration::minutes(60), true, &policy)
+            .is_none());
+
+        assert!(CheckInPolicy::default().validate().is_ok());
+        assert!(CheckInPolicy {
+            interval_mins: 1,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
 }
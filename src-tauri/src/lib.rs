@@ -2,26 +2,52 @@
 // Professional macOS meeting transcription app
 #![allow(unexpected_cfgs)]
 
+pub mod action_items;
+pub mod activity_clusters;
 pub mod ai_client;
+pub mod ai_stream;
 pub mod attendee_intel;
+pub mod audio_level;
+pub mod audio_recorder;
 pub mod capture_engine;
 pub mod catch_up_agent;
 pub mod chunk_manager;
 pub mod clustering;
 pub mod commands;
 pub mod database;
+pub mod digest;
 pub mod dork_mode;
+pub mod embeddings;
+pub mod entity_dossier;
+pub mod entity_resolution;
+pub mod meeting_comparison;
 pub mod meeting_notes;
+pub mod meeting_report;
+pub mod meeting_series;
 
 // pub mod deepgram_client; // Deprecated
+pub mod feature_flags;
+pub mod frame_backfill;
 pub mod frame_extractor;
+pub mod health_probe;
+pub mod hybrid_search;
+pub mod importance;
 pub mod live_intel_agent;
 pub mod meeting_intel;
 pub mod menu_builder;
+pub mod model_router;
 pub mod pinecone_client;
+pub mod playback_manifest;
+pub mod prompt_evaluation;
 pub mod prompt_manager;
+pub mod secret_store;
 pub mod settings;
+pub mod settings_transfer;
+pub mod shortcuts;
+pub mod subsystems;
+pub mod subtitle_export;
 pub mod supabase_client;
+pub mod sync_worker;
 pub mod transcription; // New module
 pub mod video_recorder;
 pub mod vlm_client;
@@ -33,9 +59,14 @@ pub mod env_config;
 // Intelligence Pipeline integration
 pub mod ingest_client;
 pub mod ingest_queue;
+pub mod ingest_uploader;
+pub mod integrations;
+pub mod slack_client;
 
 // Phase 1: Stateful Screen Ingest
 pub mod capture_metrics;
+pub mod db_maintenance;
+pub mod db_writer;
 pub mod dedupe_gate;
 pub mod state_builder;
 
@@ -43,6 +74,9 @@ pub mod state_builder;
 pub mod diff_builder;
 pub mod episode_builder;
 pub mod snapshot_extractor;
+pub mod spaced_repetition;
+pub mod theme_detector;
+pub mod theme_report;
 
 // Phase 3: Timeline & Accessibility
 pub mod timeline_builder;
@@ -51,6 +85,8 @@ pub mod timeline_builder;
 pub mod accessibility_capture;
 pub mod accessibility_extractor;
 pub mod calendar_client;
+pub mod followups;
+pub mod ics_import;
 pub mod semantic_classifier;
 pub mod vision_ocr;
 
@@ -59,6 +95,7 @@ pub mod admin_commands;
 pub mod audit_log;
 pub mod data_editor;
 pub mod storage_manager;
+pub mod usage_ledger;
 
 // v2.5.0: Always-On Recording
 pub mod ambient_capture;
@@ -67,9 +104,11 @@ pub mod interaction_loop;
 pub mod meeting_trigger;
 pub mod power_manager;
 pub mod privacy_filter;
+pub mod quick_note;
 pub mod tray_builder;
 
 // v3.0.0: Obsidian Vault Integration
+pub mod export_template;
 pub mod obsidian_vault;
 
 use parking_lot::RwLock;
@@ -86,7 +125,7 @@ use meeting_trigger::MeetingTriggerEngine;
 use pinecone_client::PineconeClient;
 use power_manager::PowerManager;
 use prompt_manager::PromptManager;
-use settings::SettingsManager;
+use settings::{AppSettings, SettingsManager};
 use supabase_client::SupabaseClient;
 use transcription::TranscriptionManager;
 use vlm_client::VLMClient;
@@ -98,14 +137,17 @@ pub struct AppState {
     pub transcription_manager: Arc<TranscriptionManager>, // New
     pub database: Arc<DatabaseManager>,
     pub settings: Arc<SettingsManager>,
+    pub feature_flags: feature_flags::SharedFeatureFlags,
     pub vlm_client: Arc<RwLock<VLMClient>>,
     pub vlm_scheduler: Arc<vlm_scheduler::VLMScheduler>,
+    pub sync_worker: Arc<sync_worker::SyncWorker>,
     pub supabase_client: Arc<RwLock<SupabaseClient>>,
     pub pinecone_client: Arc<RwLock<PineconeClient>>,
     pub prompt_manager: Arc<PromptManager>,
     // Intelligence Pipeline integration
-    pub ingest_client: Option<Arc<ingest_client::IngestClient>>,
+    pub ingest: subsystems::IngestSlots,
     pub ingest_queue: Arc<parking_lot::Mutex<ingest_queue::IngestQueue>>,
+    pub ingest_queue_path: std::path::PathBuf,
     // Phase 1: Stateful Screen Ingest
     pub state_builder: Arc<RwLock<state_builder::StateBuilder>>,
     pub metrics_collector: Arc<capture_metrics::MetricsCollector>,
@@ -122,14 +164,27 @@ pub struct AppState {
     // v2.7.0: Continuous Accessibility Capture
     pub accessibility_capture: Arc<accessibility_capture::AccessibilityCaptureService>,
     // v2.8.0: Dork Mode (Study Mode)
-    pub dork_mode_session: Arc<RwLock<Option<dork_mode::DorkModeSession>>>,
+    pub dork_mode_session: dork_mode::SharedDorkSession,
     pub ai_client: Arc<RwLock<ai_client::AIClient>>,
     // v3.0.0: Obsidian Vault Integration
     pub vault_manager: Arc<obsidian_vault::VaultManager>,
     pub live_intel_agent: Arc<RwLock<LiveIntelAgent>>,
+    pub catch_up_cache: Arc<catch_up_agent::CatchUpCache>,
+    pub digest_scheduler: Arc<digest::DigestScheduler>,
+    pub theme_detector: Arc<theme_detector::ThemeDetector>,
 }
 
 impl AppState {
+    /// Ingest client, once the ingest subsystem is up and configured
+    pub fn ingest_client(&self) -> Option<Arc<ingest_client::IngestClient>> {
+        self.ingest.client.read().clone()
+    }
+
+    /// Ingest uploader, once the ingest subsystem is up and configured
+    pub fn ingest_uploader(&self) -> Option<Arc<ingest_uploader::IngestUploader>> {
+        self.ingest.uploader.read().clone()
+    }
+
     pub async fn new(
         app: &AppHandle,
         emitter: &AppHandle,
@@ -154,6 +209,29 @@ impl AppState {
         database.run_migrations().await?;
         log::info!("Database initialized.");
 
+        // A marker left by the previous session means it crashed: check the
+        // database and rebuild search indexes, within a bounded time
+        if db_maintenance::mark_running(&app_data_dir) {
+            log::warn!("Previous session did not shut down cleanly, running database maintenance");
+            let _ = emitter.emit("init-step", "Checking Database Integrity...");
+            let budget =
+                std::time::Duration::from_secs(db_maintenance::AUTO_MAINTENANCE_BUDGET_SECS + 10);
+            let options = db_maintenance::MaintenanceOptions::after_crash();
+            match tokio::time::timeout(budget, db_maintenance::run_maintenance(&database, &options))
+                .await
+            {
+                Ok(report) => log::info!("Database maintenance: {}", report.summary()),
+                Err(_) => log::warn!("Database maintenance timed out, continuing startup"),
+            }
+        }
+
+        // Outbound AI/STT calls are recorded from here on
+        usage_ledger::usage_ledger().init((*database.get_pool()).clone());
+
+        // Transcript inserts that exhaust their retries are appended here
+        transcription::retry_queue::retry_queue()
+            .set_failed_log_path(app_data_dir.join("failed_transcripts.jsonl"));
+
         // Load environment configuration
         log::info!("Loading environment configuration from .env...");
         let _ = emitter.emit("init-step", "Loading Environment Configuration...");
@@ -165,10 +243,20 @@ impl AppState {
         let _ = emitter.emit("init-step", "Loading User Settings...");
         let settings = SettingsManager::new(database.get_pool());
         settings.init().await?;
+        match settings.migrate_secrets().await {
+            Ok(0) => {}
+            Ok(moved) => log::info!(
+                "Moved {} API keys from the settings table to the Keychain",
+                moved
+            ),
+            Err(e) => log::warn!("Failed to migrate secrets to the Keychain: {}", e),
+        }
         log::info!("Settings Manager initialized.");
 
         // Load saved settings
         let saved_settings = settings.get_all().await.unwrap_or_default();
+        *settings.feature_flags().write() =
+            feature_flags::FeatureFlags::from_settings(&saved_settings);
         log::info!("Settings loaded.");
 
         // Initialize Transcription Manager (Replaces DeepgramClient)
@@ -206,6 +294,39 @@ impl AppState {
             }
         }
 
+        // Low-confidence final segments are flagged for review as they're saved
+        let review = transcription::review::low_confidence_review();
+        review.set_app_handle(app.clone());
+        review.set_threshold(saved_settings.low_confidence_threshold);
+
+        // User capture exclusions (built-in blocked apps always apply)
+        if let Err(e) = privacy_filter::set_rules(saved_settings.privacy_rules.clone()) {
+            log::warn!("Ignoring saved privacy rules: {}", e);
+        }
+
+        privacy_filter::set_redaction_mode(
+            privacy_filter::RedactionMode::parse(&saved_settings.redaction_mode)
+                .unwrap_or_default(),
+        );
+
+        usage_ledger::usage_ledger().set_prices(saved_settings.usage_prices.clone());
+        ai_client::set_remote_provider(ai_client::RemoteProvider::from_settings(&saved_settings));
+        ai_client::set_requests_per_minute(saved_settings.ai_requests_per_minute);
+        model_router::model_router().set_default_chat_model(saved_settings.ai_chat_model.clone());
+
+        // Glossary is re-applied by the manager on every provider switch
+        if !saved_settings.transcription_glossary.is_empty() {
+            transcription_manager.set_glossary(saved_settings.transcription_glossary.clone());
+            log::info!(
+                "Loaded transcription glossary ({} terms)",
+                saved_settings.transcription_glossary.len()
+            );
+        }
+
+        // Health checks run for the life of the app; failover only acts mid-session
+        transcription_manager.set_failover_policy(saved_settings.transcription_failover);
+        transcription_manager.spawn_health_monitor();
+
         // Restore saved transcription provider choice
         let saved_provider = &saved_settings.transcription_provider;
         match saved_provider.as_str() {
@@ -221,6 +342,10 @@ impl AppState {
                 transcription_manager.switch_provider(transcription::ProviderType::GoogleSTT);
                 log::info!("Restored saved transcription provider: GoogleSTT");
             }
+            "whisper_local" => {
+                transcription_manager.switch_provider(transcription::ProviderType::WhisperLocal);
+                log::info!("Restored saved transcription provider: WhisperLocal");
+            }
             _ => {
                 // Default is Deepgram, already set in TranscriptionManager::new()
                 log::info!("Transcription provider: Deepgram (default)");
@@ -239,6 +364,12 @@ impl AppState {
             capture.set_monitor(monitor);
             log::info!("Loaded saved monitor: {}", monitor);
         }
+        capture.set_audio_sources(
+            saved_settings.capture_microphone,
+            saved_settings.capture_system_audio,
+        );
+        audio_level::audio_meter().set_policy(saved_settings.silence_warning);
+        commands::init_video_storage(&app_data_dir, saved_settings.video_capture);
 
         // Initialize knowledge base clients
         log::info!("Initializing Knowledge Base Clients...");
@@ -332,6 +463,7 @@ impl AppState {
         let _ = emitter.emit("init-step", "Loading Prompt Library...");
         let prompt_manager = Arc::new(PromptManager::new((*database.get_pool()).clone()));
         prompt_manager.run_migrations().await?;
+        model_router::model_router().init(prompt_manager.clone());
         log::info!("Prompt manager initialized with default presets");
 
         log::info!("Database initialized at: {:?}", db_path);
@@ -349,6 +481,11 @@ impl AppState {
             prompt_manager.clone(),
         );
 
+        vlm_scheduler.set_app_handle(app.clone());
+        vlm_scheduler.set_concurrency(saved_settings.vlm_concurrency);
+        vlm_scheduler.set_batch_size(saved_settings.vlm_batch_size);
+        vlm_scheduler.set_policy(saved_settings.vlm_scheduler_policy.clone());
+
         // Load VLM scheduler settings and start if enabled
         if saved_settings.vlm_auto_process {
             vlm_scheduler.set_enabled(true);
@@ -366,36 +503,28 @@ impl AppState {
         log::info!("Initializing Intelligence Pipeline integration...");
         let _ = emitter.emit("init-step", "Setting up Intelligence Pipeline...");
 
-        // Initialize ingest queue (always available for local queueing)
-        let queue_path = app_data_dir.join("ingest_queue.db");
-        let ingest_queue = ingest_queue::IngestQueue::new(&queue_path)
+        // The ingest subsystem moves the queue onto its file below; until
+        // then (or if the file cannot be opened) it is held in memory
+        let ingest_queue_path = app_data_dir.join("ingest_queue.db");
+        let ingest_queue = ingest_queue::IngestQueue::in_memory()
             .map_err(|e| format!("Failed to initialize ingest queue: {}", e))?;
+        let ingest = subsystems::IngestSlots::default();
 
-        // Initialize ingest client if enabled
-        let ingest_client = if saved_settings.enable_ingest.unwrap_or(false) {
-            if let (Some(base_url), Some(bearer_token)) = (
-                saved_settings.ingest_base_url.as_ref(),
-                saved_settings.ingest_bearer_token.as_ref(),
-            ) {
-                log::info!("Ingest enabled, creating client for: {}", base_url);
-                Some(Arc::new(ingest_client::IngestClient::new(
-                    base_url.clone(),
-                    bearer_token.clone(),
-                )))
-            } else {
-                log::warn!("Ingest enabled but missing configuration");
-                None
-            }
-        } else {
-            log::info!("Ingest disabled");
-            None
-        };
+        // Outbound CRM webhooks get their own durable retry queue
+        let webhook_queue_path = app_data_dir.join("webhook_queue.db");
+        if let Err(e) = integrations::webhooks().init(&webhook_queue_path) {
+            log::warn!("Webhooks disabled: {}", e);
+        }
+        integrations::webhooks()
+            .configure(integrations::WebhookConfig::from_settings(&saved_settings));
+        integrations::webhooks().start_worker();
 
         // Initialize Phase 1: Stateful Screen Ingest components
         log::info!("Initializing Stateful Screen Ingest (v2.0)...");
         let _ = emitter.emit("init-step", "Setting up Stateful Capture Pipeline...");
         let state_builder = state_builder::StateBuilder::new();
-        let metrics_collector = capture_metrics::MetricsCollector::new();
+        let metrics_collector = Arc::new(capture_metrics::MetricsCollector::new());
+        vlm_scheduler.set_metrics_collector(metrics_collector.clone());
 
         // Initialize Phase 2: Episode Building
         let episode_builder = episode_builder::EpisodeBuilder::new();
@@ -406,38 +535,57 @@ impl AppState {
 
         // Initialize v2.5.0: Power Manager for Always-On Recording
         log::info!("Initializing Power Manager...");
-        let power_manager = PowerManager::new();
+        let power_manager = Arc::new(PowerManager::new());
 
         // Initialize v2.5.0: Ambient Capture Service
         log::info!("Initializing Ambient Capture Service...");
-        let ambient_capture = AmbientCaptureService::new();
+        let mut ambient_capture = AmbientCaptureService::new();
+        ambient_capture.set_app_handle(emitter.clone());
+        ambient_capture.set_idle_timeout_mins(saved_settings.ambient_idle_timeout_mins);
+        let ambient_buffer = ambient_capture.buffer();
+        ambient_buffer.set_scratch_dir(app_data_dir.join("ambient_buffer"));
+        ambient_buffer.set_minutes(saved_settings.ambient_buffer_minutes);
 
         // Initialize v2.5.0: Meeting Trigger Engine
         log::info!("Initializing Meeting Trigger Engine...");
-        let meeting_trigger = MeetingTriggerEngine::new();
+        let mut meeting_trigger = MeetingTriggerEngine::new();
+        meeting_trigger.set_app_handle(emitter.clone());
+        meeting_trigger.set_auto_start(saved_settings.auto_start_on_meeting_detected);
+        let trigger_app = emitter.clone();
+        meeting_trigger.on_meeting_start(Arc::new(move |session| {
+            let app = trigger_app.clone();
+            tauri::async_runtime::spawn(commands::start_detected_meeting(app, session));
+        }));
+        meeting_trigger.set_auto_stop(
+            saved_settings.auto_stop_recording,
+            saved_settings.auto_stop_idle_minutes,
+            saved_settings.auto_stop_after_event_minutes,
+        );
+        let stop_app = emitter.clone();
+        meeting_trigger.on_auto_stop(Arc::new(move |suggestion| {
+            let app = stop_app.clone();
+            tauri::async_runtime::spawn(commands::auto_stop_recording(app, suggestion));
+        }));
+        let mut calendar_client = calendar_client::CalendarClient::new();
+        calendar_client.set_selected_calendars(saved_settings.selected_calendars.clone());
+        let calendar_client = Arc::new(RwLock::new(calendar_client));
+        if let Err(e) = meeting_trigger.start(calendar_client.clone()) {
+            log::warn!("Failed to start meeting trigger engine: {}", e);
+        }
 
         // Initialize v2.5.0: Interaction Loop for human check-ins
         log::info!("Initializing Interaction Loop...");
-        let interaction_loop = InteractionLoop::new();
+        let mut interaction_loop = InteractionLoop::new();
+        interaction_loop.set_app_handle(emitter.clone());
+        interaction_loop.set_checkin_policy(saved_settings.check_in_policy);
 
         // Initialize v2.7.0: Accessibility Capture Service
         log::info!("Initializing Accessibility Capture Service...");
         let accessibility_capture =
             Arc::new(accessibility_capture::AccessibilityCaptureService::new());
-
-        // Auto-start accessibility capture if enabled
-        if saved_settings.accessibility_capture_enabled {
-            log::info!("Starting Accessibility Capture (enabled in settings)...");
-            let acc_cap = accessibility_capture.clone();
-            let db_clone = database.clone();
-            let settings_clone = settings.clone();
-            let pinecone_clone = pinecone.clone();
-            tokio::spawn(async move {
-                if let Err(e) = acc_cap.start(db_clone, settings_clone, pinecone_clone) {
-                    log::warn!("Failed to start accessibility capture: {}", e);
-                }
-            });
-        }
+        accessibility_capture.set_metrics_collector(metrics_collector.clone());
+        let dork_mode_session: dork_mode::SharedDorkSession = Arc::new(RwLock::new(None));
+        accessibility_capture.set_dork_session(dork_mode_session.clone());
 
         // Initialize Live Intelligence Agent
         log::info!("Initializing Live Intelligence Agent...");
@@ -446,57 +594,184 @@ impl AppState {
         // Wire up audio callback to TranscriptionManager
         let tm_clone = transcription_manager.clone();
         capture.set_audio_callback(Arc::new(move |buffer| {
-            // Always forward audio to the provider — the provider handles
+            // Ambient mode fills the rolling buffer; nothing is transcribed
+            // unless it is promoted to a meeting
+            if ambient_buffer.is_active() {
+                ambient_buffer.push_audio(&buffer);
+                return;
+            }
+            // Otherwise forward audio to the provider — the provider handles
             // buffering/dropping based on its own connection state.
-            tm_clone.process_audio(&buffer.samples, buffer.sample_rate, buffer.channels);
+            tm_clone.process_buffer(&buffer);
         }));
 
+        let ai_client = Arc::new(RwLock::new(ai_client::AIClient::new()));
+
+        // Background cloud sync (activities, conversations, transcript indexes)
+        let supabase = Arc::new(RwLock::new(supabase));
+        let sync_worker = Arc::new(sync_worker::SyncWorker::new(
+            database.clone(),
+            settings.clone(),
+            pinecone.clone(),
+            supabase.clone(),
+            ai_client.clone(),
+        ));
+        sync_worker.set_app_handle(app.clone());
+        if saved_settings.sync_worker_enabled {
+            sync_worker.start();
+        }
+
+        // v3.0.0: Obsidian Vault Integration
+        let vault_manager = Arc::new(obsidian_vault::VaultManager::new());
+
+        // Evening digest notes
+        let digest_scheduler = Arc::new(digest::DigestScheduler::new(
+            database.clone(),
+            vault_manager.clone(),
+            ai_client.clone(),
+        ));
+        if let Ok(time) = digest::parse_digest_time(&saved_settings.digest_time) {
+            digest_scheduler.set_time(time);
+        }
+        if saved_settings.digest_enabled {
+            digest_scheduler.start();
+        }
+
+        // Optional subsystems start concurrently, each under its own timeout.
+        // Failures are recorded and retried on first use instead of failing
+        // startup.
+        log::info!("Initializing optional subsystems...");
+        let registry = subsystems::subsystems();
+        registry.set_app_handle(app.clone());
+        // Re-read so values auto-populated from .env above are included
+        let optional_settings = settings.get_all().await.unwrap_or_default();
+        let report_pending = || {
+            let pending = registry.pending();
+            if !pending.is_empty() {
+                let _ = emitter.emit(
+                    "init-step",
+                    format!("Waiting for {}...", pending.join(", ")),
+                );
+            }
+        };
+        report_pending();
+        tokio::join!(
+            async {
+                registry
+                    .init(subsystems::PINECONE, async {
+                        subsystems::init_pinecone(&pinecone, &optional_settings)
+                    })
+                    .await;
+                report_pending();
+            },
+            async {
+                registry
+                    .init(
+                        subsystems::SUPABASE,
+                        subsystems::init_supabase(&supabase, &optional_settings),
+                    )
+                    .await;
+                report_pending();
+            },
+            async {
+                registry
+                    .init(
+                        subsystems::INGEST,
+                        subsystems::init_ingest(
+                            ingest_queue.clone(),
+                            ingest_queue_path.clone(),
+                            ingest.clone(),
+                            settings.clone(),
+                            &optional_settings,
+                        ),
+                    )
+                    .await;
+                report_pending();
+            },
+            async {
+                registry
+                    .init(
+                        subsystems::CALENDAR,
+                        subsystems::init_calendar(calendar_client.clone()),
+                    )
+                    .await;
+                report_pending();
+            },
+            async {
+                registry
+                    .init(
+                        subsystems::VAULT,
+                        subsystems::init_vault(&vault_manager, &optional_settings),
+                    )
+                    .await;
+                report_pending();
+            },
+            async {
+                registry
+                    .init(subsystems::ACCESSIBILITY_CAPTURE, async {
+                        subsystems::init_accessibility_capture(
+                            &accessibility_capture,
+                            database.clone(),
+                            settings.clone(),
+                            pinecone.clone(),
+                            &optional_settings,
+                        )
+                    })
+                    .await;
+                report_pending();
+            },
+        );
+
+        let capture_engine = Arc::new(RwLock::new(capture));
+        ambient_capture.start_idle_watch(
+            capture_engine.clone(),
+            power_manager.clone(),
+            settings.feature_flags(),
+        );
+
         Ok(Self {
-            capture_engine: Arc::new(RwLock::new(capture)),
+            capture_engine,
             // deepgram_client: Arc::new(RwLock::new(deepgram)),
             transcription_manager,
             database,
             settings: settings.clone(),
+            feature_flags: settings.feature_flags(),
             vlm_client: Arc::new(RwLock::new(vlm)),
             vlm_scheduler: Arc::new(vlm_scheduler),
-            supabase_client: Arc::new(RwLock::new(supabase)),
+            sync_worker,
+            supabase_client: supabase,
             pinecone_client: pinecone,
             prompt_manager,
-            ingest_client,
+            ingest,
             ingest_queue: Arc::new(parking_lot::Mutex::new(ingest_queue)),
+            ingest_queue_path,
             state_builder: Arc::new(RwLock::new(state_builder)),
-            metrics_collector: Arc::new(metrics_collector),
+            metrics_collector,
             episode_builder: Arc::new(RwLock::new(episode_builder)),
             timeline_builder: Arc::new(timeline_builder),
-            calendar_client: Arc::new(RwLock::new(calendar_client::CalendarClient::new())),
-            power_manager: Arc::new(power_manager),
+            calendar_client,
+            power_manager,
             ambient_capture: Arc::new(ambient_capture),
             meeting_trigger: Arc::new(meeting_trigger),
             interaction_loop: Arc::new(interaction_loop),
             accessibility_capture,
             // v2.8.0: Dork Mode (Study Mode)
-            dork_mode_session: Arc::new(RwLock::new(None)),
-            ai_client: Arc::new(RwLock::new(ai_client::AIClient::new())),
+            dork_mode_session,
+            ai_client,
             // v3.0.0: Obsidian Vault Integration
-            vault_manager: {
-                let vm = Arc::new(obsidian_vault::VaultManager::new());
-                // Load vault path from settings if configured
-                let settings_clone = settings.clone();
-                let vm_clone = vm.clone();
-                tokio::spawn(async move {
-                    if let Ok(saved_settings) = settings_clone.get_all().await {
-                        if let Some(vault_path) = saved_settings.obsidian_vault_path {
-                            vm_clone.set_vault_path(vault_path);
-                        }
-                    }
-                });
-                vm
-            },
+            vault_manager,
             live_intel_agent,
+            catch_up_cache: Arc::new(catch_up_agent::CatchUpCache::new()),
+            digest_scheduler,
+            theme_detector: Arc::new(theme_detector::ThemeDetector::new()),
         })
     }
 }
 
+/// Time allowed for the core (database, settings, capture, transcription)
+/// to come up; optional subsystems add their own bounded budget
+const CORE_INIT_TIMEOUT_SECS: u64 = 15;
+
 #[derive(Clone, serde::Serialize, Debug)]
 pub enum InitStatus {
     Initializing,
@@ -514,6 +789,11 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(shortcuts::handle_shortcut)
+                .build(),
+        )
         .setup(|app| {
             let handle = app.handle().clone();
 
@@ -529,14 +809,17 @@ pub fn run() {
             tauri::async_runtime::spawn(async move {
                 log::info!("Starting AppState initialization...");
 
-                // Add a timeout to the initialization (15 seconds)
+                // Optional subsystems have their own timeouts, so this bound
+                // only trips when the core (database, settings) hangs
+                let init_timeout = std::time::Duration::from_secs(CORE_INIT_TIMEOUT_SECS)
+                    + subsystems::OPTIONAL_INIT_TIMEOUT;
                 let init_future = async {
                     let _ = handle_clone.emit("init-step", "Initializing Database Manager...");
                     log::info!("Initializing Database...");
 
                     // Wrap with timeout
                     let result = tokio::time::timeout(
-                        std::time::Duration::from_secs(15),
+                        init_timeout,
                         AppState::new(&handle_clone, &handle_clone), // Pass handle for emitting internal steps
                     )
                     .await;
@@ -548,7 +831,36 @@ pub fn run() {
                                     let _ =
                                         handle_clone.emit("init-step", "Finalizing App State...");
                                     log::info!("AppState created, managing state...");
+                                    let saved = state
+                                        .settings
+                                        .get_all()
+                                        .await
+                                        .unwrap_or_else(|_| AppSettings::with_defaults());
                                     handle_clone.manage(state);
+                                    // Shortcut actions need AppState, so bind them only now
+                                    shortcuts::shortcuts()
+                                        .init(handle_clone.clone(), &saved.global_shortcuts);
+                                    tray_builder::update_tray_status(|s| {
+                                        s.active_theme = Some(saved.active_theme.clone())
+                                    });
+                                    admin_commands::spawn_storage_cleanup_task(
+                                        handle_clone.clone(),
+                                    );
+                                    admin_commands::spawn_snapshot_compaction_task(
+                                        handle_clone.clone(),
+                                    );
+                                    admin_commands::spawn_audit_compaction_task(
+                                        handle_clone.clone(),
+                                    );
+                                    commands::spawn_adaptive_interval_task(handle_clone.clone());
+                                    commands::spawn_audio_level_task(handle_clone.clone());
+                                    commands::spawn_checkin_task(handle_clone.clone());
+                                    commands::spawn_flashcard_badge_task(handle_clone.clone());
+                                    commands::spawn_activity_cluster_task(handle_clone.clone());
+                                    commands::spawn_theme_detector_task(handle_clone.clone());
+                                    transcription::retranscribe::resume_interrupted(
+                                        handle_clone.clone(),
+                                    );
 
                                     // Update status to Ready
                                     *init_state_clone.write() = InitStatus::Ready;
@@ -569,12 +881,14 @@ pub fn run() {
                             }
                         }
                         Err(_) => {
-                            let msg =
-                                "Initialization timed out after 15 seconds. Check database locks.";
+                            let msg = format!(
+                                "Initialization timed out after {} seconds. Check database locks.",
+                                init_timeout.as_secs()
+                            );
                             log::error!("{}", msg);
                             // Update status to Failed
-                            *init_state_clone.write() = InitStatus::Failed(msg.to_string());
-                            let _ = handle_clone.emit("init-error", msg.to_string());
+                            *init_state_clone.write() = InitStatus::Failed(msg.clone());
+                            let _ = handle_clone.emit("init-error", msg);
                         }
                     }
                 };
@@ -603,6 +917,7 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             commands::check_init_status,
+            commands::get_subsystem_status,
             commands::check_permissions,
             commands::test_screen_capture,
             commands::test_microphone,
@@ -610,10 +925,24 @@ pub fn run() {
             commands::request_permission,
             commands::start_recording,
             commands::stop_recording,
+            commands::pause_recording,
+            commands::resume_recording,
             commands::get_recording_status,
             commands::capture_screenshot,
             commands::get_transcripts,
+            commands::update_transcript_text,
+            commands::bulk_replace_in_meeting,
+            commands::get_transcript_revisions,
+            commands::get_transcripts_needing_review,
+            commands::mark_transcript_reviewed,
+            commands::set_low_confidence_threshold,
+            commands::get_transcription_failover,
+            commands::set_transcription_failover,
+            commands::get_transcription_health,
+            commands::rename_speaker,
+            commands::get_meeting_speakers,
             commands::search_transcripts,
+            commands::search_everything,
             commands::get_frames,
             commands::get_frame_count,
             commands::get_frame_thumbnail,
@@ -622,25 +951,40 @@ pub fn run() {
             commands::set_audio_device,
             commands::get_monitors,
             commands::set_monitor,
+            commands::set_capture_all_monitors,
+            commands::get_capturable_windows,
+            commands::set_capture_window,
             commands::set_deepgram_api_key,
             commands::get_deepgram_api_key,
             commands::set_gemini_api_key,
             commands::get_gemini_api_key,
             commands::set_gladia_api_key,
             commands::set_google_stt_key,
+            commands::validate_provider_key,
             commands::set_active_provider,
+            commands::get_glossary,
+            commands::set_glossary,
+            commands::set_whisper_model,
             commands::debug_log,
             commands::get_meetings,
+            commands::query_meetings,
             commands::get_meeting,
             commands::delete_meeting,
+            commands::archive_meeting,
+            commands::unarchive_meeting,
+            commands::list_archived_meetings,
             commands::get_settings,
             commands::get_setting,
+            commands::get_secret_status,
             // AI Commands
-            commands::check_ollama,
+            commands::check_ai_provider,
             commands::get_ollama_models,
             commands::get_ai_presets,
             commands::ai_chat,
+            commands::ai_chat_stream,
+            commands::cancel_ai_stream,
             commands::summarize_meeting,
+            commands::summarize_meeting_stream,
             commands::extract_action_items,
             // Knowledge Base Commands
             commands::check_vlm,
@@ -654,15 +998,20 @@ pub fn run() {
             commands::get_thebrain_models,
             commands::capture_accessibility_snapshot,
             commands::thebrain_chat,
+            commands::thebrain_chat_stream,
             commands::thebrain_rag_chat,
             commands::store_conversation,
             commands::get_conversation_history,
+            commands::delete_conversation,
+            commands::clear_conversation_history,
             commands::thebrain_rag_chat_with_memory,
             commands::configure_supabase,
             commands::check_supabase,
+            commands::check_supabase_health,
             commands::sync_activity_to_supabase,
             commands::query_activities,
             commands::configure_pinecone,
+            commands::configure_pinecone_namespaces,
             commands::check_pinecone,
             commands::upsert_to_pinecone,
             commands::semantic_search,
@@ -674,11 +1023,31 @@ pub fn run() {
             // Capture Mode Commands
             commands::set_capture_microphone,
             commands::set_capture_system_audio,
+            commands::get_audio_levels,
+            commands::get_silence_warning_policy,
+            commands::set_silence_warning_policy,
+            commands::get_checkin_policy,
+            commands::set_checkin_policy,
+            commands::respond_to_checkin,
+            commands::set_audio_recording_enabled,
+            commands::set_audio_recording_format,
+            commands::get_audio_chunks,
+            commands::get_audio_chunk_for_timestamp,
+            commands::retranscribe_meeting,
+            commands::get_retranscribe_jobs,
+            commands::get_revision_set,
             commands::set_capture_screen,
             commands::set_always_on_capture,
             commands::set_queue_frames_for_vlm,
             commands::set_frame_capture_interval,
+            commands::set_adaptive_frame_interval,
+            commands::set_auto_title_meetings,
+            commands::set_semantic_topics_enabled,
+            commands::set_episode_summary_min_secs,
+            commands::regenerate_episode_summary,
             commands::configure_knowledge_base,
+            commands::set_vector_backend,
+            commands::index_transcripts_locally,
             commands::get_capture_settings,
             // AI Provider Settings
             commands::set_ai_provider_settings,
@@ -689,6 +1058,11 @@ pub fn run() {
             commands::get_activity_stats,
             commands::get_unsynced_activities,
             commands::sync_to_cloud,
+            commands::get_sync_worker_status,
+            commands::start_sync_worker,
+            commands::stop_sync_worker,
+            commands::retry_parked_sync_jobs,
+            commands::queue_transcript_index,
             // Search Commands (Phase 6)
             commands::search_knowledge_base,
             commands::quick_semantic_search,
@@ -696,6 +1070,8 @@ pub fn run() {
             // Data Management Commands
             commands::clear_cache,
             commands::export_data,
+            commands::export_meeting_report,
+            commands::export_transcript_subtitles,
             // Prompt Management Commands
             commands::list_prompts,
             commands::get_prompt,
@@ -716,6 +1092,9 @@ pub fn run() {
             commands::get_resolved_use_case,
             commands::update_use_case_mapping,
             commands::test_prompt,
+            commands::evaluate_prompt,
+            commands::record_evaluation_verdict,
+            commands::get_prompt_evaluation_summary,
             // Phase 2: Theme-Specific Prompt Management Commands
             commands::list_prompts_by_theme,
             commands::get_latest_prompt,
@@ -734,8 +1113,12 @@ pub fn run() {
             commands::start_video_recording,
             commands::stop_video_recording,
             commands::get_video_recording_status,
+            commands::get_video_capture_config,
+            commands::set_video_capture_config,
             commands::video_pin_moment,
             commands::extract_frame_at,
+            commands::get_playback_manifest,
+            commands::export_highlight,
             commands::extract_thumbnail,
             commands::get_storage_stats,
             commands::apply_retention,
@@ -744,6 +1127,11 @@ pub fn run() {
             commands::set_vlm_auto_process,
             commands::set_vlm_process_interval,
             commands::get_vlm_scheduler_status,
+            commands::get_vlm_scheduler_policy,
+            commands::set_vlm_scheduler_policy,
+            commands::cancel_vlm_analysis,
+            commands::set_vlm_concurrency,
+            commands::set_vlm_batch_size,
             // AI Chat Model Commands
             commands::set_ai_chat_model,
             commands::get_ai_chat_model,
@@ -751,23 +1139,35 @@ pub fn run() {
             commands::get_accessibility_capture_status,
             commands::start_accessibility_capture,
             commands::stop_accessibility_capture,
+            commands::set_ocr_fallback_enabled,
             commands::set_accessibility_meeting_id,
             // Activity Theme Commands
             commands::set_active_theme,
             commands::get_active_theme,
+            commands::get_theme_suggestion,
+            commands::accept_theme_suggestion,
+            commands::dismiss_theme_suggestion,
             commands::get_theme_settings,
             commands::set_theme_interval,
             commands::get_theme_time_today,
+            commands::get_theme_time_report,
+            commands::export_theme_time_report_csv,
             // Intel Commands
             commands::get_recent_entities,
+            commands::merge_entities,
+            commands::split_entity,
+            commands::get_entity_dossier,
             // Intelligence Pipeline Commands
             commands::set_enable_ingest,
             commands::set_ingest_config,
             commands::get_ingest_queue_stats,
+            commands::set_ingest_bandwidth_cap,
             commands::test_ingest_connection,
             commands::trigger_meeting_ingest,
+            commands::verify_remote_session,
             // Phase 3: Timeline Commands
             commands::get_timeline_events,
+            commands::get_meeting_highlights,
             commands::get_topic_clusters,
             // v2.1.0: Calendar Integration Commands
             commands::check_calendar_access,
@@ -775,6 +1175,8 @@ pub fn run() {
             commands::get_calendar_events,
             commands::get_current_meeting,
             commands::get_upcoming_meetings,
+            commands::list_calendars,
+            commands::set_selected_calendars,
             // v2.1.0: Capture Metrics Command
             commands::get_capture_metrics,
             // v2.1.0: Management Suite Commands
@@ -782,22 +1184,36 @@ pub fn run() {
             admin_commands::get_admin_storage_stats,
             admin_commands::preview_delete_recordings,
             admin_commands::delete_recordings,
+            admin_commands::get_storage_cleanup_preview,
+            admin_commands::run_storage_cleanup_now,
+            admin_commands::set_frame_retention,
             admin_commands::get_audit_log,
             admin_commands::get_audit_log_count,
+            admin_commands::export_audit_log,
+            admin_commands::set_audit_retention,
             admin_commands::get_system_health,
             admin_commands::get_admin_queue_stats,
+            admin_commands::get_usage_summary,
+            admin_commands::get_usage_prices,
+            admin_commands::set_usage_prices,
             admin_commands::get_feature_flags,
             admin_commands::set_feature_flag,
             // v2.1.0: Learned Data Commands
             admin_commands::list_learned_data,
             admin_commands::count_learned_data,
             admin_commands::edit_learned_data,
+            admin_commands::bulk_edit_learned_data,
             admin_commands::get_data_versions,
             admin_commands::restore_data_version,
             // v2.1.0: Tools Console Commands (M4)
             admin_commands::get_job_history,
             admin_commands::pause_ingest_queue,
             admin_commands::get_database_stats,
+            admin_commands::clear_vlm_cache,
+            admin_commands::run_database_maintenance,
+            admin_commands::export_settings,
+            admin_commands::import_settings,
+            admin_commands::requeue_frames_for_analysis,
             // v2.1.0: Video Diagnostics Commands
             commands::get_capture_diagnostics,
             commands::test_live_capture,
@@ -811,7 +1227,48 @@ pub fn run() {
             commands::get_running_meeting_apps,
             commands::check_audio_usage,
             commands::dismiss_meeting_detection,
+            commands::get_privacy_rules,
+            commands::set_privacy_rules,
+            commands::set_redaction_mode,
+            commands::list_meeting_notes_versions,
+            commands::set_active_notes_version,
+            commands::compare_meetings,
+            commands::export_meeting_comparison,
+            commands::list_meeting_series,
+            commands::get_series,
+            commands::list_action_items,
+            commands::update_action_item_status,
+            commands::create_action_item_manual,
+            commands::propose_followups,
+            commands::schedule_followups,
+            commands::set_webhook_config,
+            commands::get_webhook_delivery_log,
+            commands::test_webhook,
+            commands::set_slack_bot_token,
+            commands::send_meeting_summary_to_slack,
+            commands::send_catch_up_to_slack,
+            commands::get_pinned_insights,
+            commands::update_pinned_insight,
+            commands::delete_pinned_insight,
+            commands::get_decisions,
+            commands::update_decision,
+            commands::delete_decision,
+            commands::promote_ambient_buffer_to_meeting,
+            commands::set_ambient_buffer_minutes,
+            commands::set_ambient_idle_timeout,
+            commands::link_meeting_to_event,
+            commands::get_meeting_calendar_link,
+            commands::undo_auto_started_meeting,
+            commands::set_auto_start_on_meeting_detected,
+            commands::set_auto_stop_recording,
             commands::set_genie_mode,
+            commands::get_shortcuts,
+            commands::set_shortcut,
+            commands::add_meeting_comment,
+            commands::get_meeting_comments,
+            commands::quick_note,
+            commands::get_unattached_notes,
+            commands::attach_quick_note,
             // v2.8.0: Dork Mode (Study Mode) Commands
             commands::set_session_mode,
             commands::get_session_mode,
@@ -819,12 +1276,29 @@ pub fn run() {
             commands::add_dork_content,
             commands::end_dork_session,
             commands::get_study_materials,
+            commands::get_dork_session_status,
+            commands::set_dork_app_allowlist,
+            commands::list_dork_sessions,
+            commands::resume_dork_session,
+            commands::export_study_materials_to_vault,
+            commands::get_due_flashcards,
+            commands::grade_flashcard,
+            commands::get_flashcard_stats,
+            commands::get_activity_clusters,
+            commands::rename_activity_cluster,
             // v3.0.0: Obsidian Vault Commands
             commands::get_vault_status,
             commands::list_vault_topics,
             commands::get_vault_topic,
             commands::create_vault_topic,
             commands::export_meeting_to_vault,
+            commands::preview_export_template,
+            commands::set_export_template,
+            commands::set_vault_conflict_policy,
+            commands::set_vault_series_folders,
+            commands::get_vault_export_conflicts,
+            commands::generate_digest,
+            commands::set_digest_schedule,
             commands::read_vault_file,
             commands::write_vault_note,
             commands::upload_to_vault,
@@ -840,7 +1314,10 @@ pub fn run() {
             commands::get_vault_graph,
             // v3.1.0: Calendar Intelligence Commands
             commands::generate_meeting_intel,
+            commands::set_attendee_intel_staleness_days,
+            commands::set_ai_requests_per_minute,
             commands::get_enriched_calendar_events,
+            commands::import_ics,
         ])
         .on_window_event(|window, event| {
             match event {
@@ -873,6 +1350,11 @@ pub fn run() {
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
         .run(|app_handle, event| match event {
+            tauri::RunEvent::Exit => {
+                if let Ok(dir) = app_handle.path().app_data_dir() {
+                    db_maintenance::mark_clean_shutdown(&dir);
+                }
+            }
             tauri::RunEvent::Reopen { .. } => {
                 #[cfg(target_os = "macos")]
                 {
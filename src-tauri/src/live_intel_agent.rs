@@ -59,6 +59,30 @@ impl LiveInsightEvent {
         }
     }
 
+    /// Replace the agent-local id with a persisted one
+    pub fn set_id(&mut self, new_id: String) {
+        match self {
+            LiveInsightEvent::ActionItem { id, .. }
+            | LiveInsightEvent::Decision { id, .. }
+            | LiveInsightEvent::RiskSignal { id, .. }
+            | LiveInsightEvent::QuestionSuggestion { id, .. }
+            | LiveInsightEvent::Commitment { id, .. }
+            | LiveInsightEvent::TopicShift { id, .. } => *id = new_id,
+        }
+    }
+
+    /// Serialized `type` tag
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LiveInsightEvent::ActionItem { .. } => "action_item",
+            LiveInsightEvent::Decision { .. } => "decision",
+            LiveInsightEvent::RiskSignal { .. } => "risk_signal",
+            LiveInsightEvent::QuestionSuggestion { .. } => "question_suggestion",
+            LiveInsightEvent::Commitment { .. } => "commitment",
+            LiveInsightEvent::TopicShift { .. } => "topic_shift",
+        }
+    }
+
     pub fn timestamp(&self) -> i64 {
         match self {
             LiveInsightEvent::ActionItem { timestamp_ms, .. } => *timestamp_ms,
@@ -71,6 +95,43 @@ impl LiveInsightEvent {
     }
 }
 
+/// A segment that reads as an action item
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionItemCue {
+    pub assignee: Option<String>,
+}
+
+/// Action item detection shared by the live agent and transcript persistence
+pub fn detect_action_item(text: &str, speaker: Option<&str>) -> Option<ActionItemCue> {
+    let text_lower = text.to_lowercase();
+
+    // Action item patterns
+    let patterns = [
+        ("can you", None),
+        ("could you", None),
+        ("please", None),
+        ("need to", None),
+        ("should", None),
+        ("will you", None),
+        ("action item", None),
+        ("follow up", None),
+        ("let's make sure", None),
+        ("i'll take care of", Some("speaker")),
+        ("i will", Some("speaker")),
+        ("i can do", Some("speaker")),
+    ];
+
+    patterns
+        .iter()
+        .find(|(pattern, _)| text_lower.contains(pattern))
+        .map(|(_, assignee_hint)| ActionItemCue {
+            assignee: match assignee_hint {
+                Some("speaker") => speaker.map(str::to_string),
+                _ => None,
+            },
+        })
+}
+
 /// Conversation state tracking
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ConversationState {
@@ -160,43 +221,16 @@ impl LiveIntelAgent {
 
     /// Detect action items in text
     fn detect_action_items(&mut self, segment: &TranscriptSegment) -> Vec<LiveInsightEvent> {
-        let mut events = Vec::new();
-        let text_lower = segment.text.to_lowercase();
-
-        // Action item patterns
-        let patterns = [
-            ("can you", None),
-            ("could you", None),
-            ("please", None),
-            ("need to", None),
-            ("should", None),
-            ("will you", None),
-            ("action item", None),
-            ("follow up", None),
-            ("let's make sure", None),
-            ("i'll take care of", Some("speaker")),
-            ("i will", Some("speaker")),
-            ("i can do", Some("speaker")),
-        ];
-
-        for (pattern, assignee_hint) in patterns {
-            if text_lower.contains(pattern) {
-                let assignee = match assignee_hint {
-                    Some("speaker") => segment.speaker.clone(),
-                    _ => None,
-                };
-
-                events.push(LiveInsightEvent::ActionItem {
-                    id: self.generate_id("action"),
-                    text: segment.text.clone(),
-                    assignee,
-                    timestamp_ms: segment.timestamp_ms,
-                });
-                break; // One action per segment
-            }
+        match detect_action_item(&segment.text, segment.speaker.as_deref()) {
+            // One action per segment
+            Some(cue) => vec![LiveInsightEvent::ActionItem {
+                id: self.generate_id("action"),
+                text: segment.text.clone(),
+                assignee: cue.assignee,
+                timestamp_ms: segment.timestamp_ms,
+            }],
+            None => Vec::new(),
         }
-
-        events
     }
 
     /// Detect decisions
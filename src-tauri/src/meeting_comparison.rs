@@ -0,0 +1,497 @@
+// noFriction Meetings - Meeting Comparison
+// "What changed since last time" for recurring meetings: decisions, action
+// items and topics of two meetings aligned into added / removed / carried-over
+//
+// Items come from each meeting's active notes plus tracked action items,
+// marked decisions and topic clusters. Items pair up greedily by normalized
+// text similarity (word overlap or edit distance, whichever is higher), so
+// "Send Q3 budget to finance" carries over as "Send the Q3 budget to Finance".
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::action_items::normalize_text;
+use crate::ai_client::AIClient;
+use crate::clustering::ClusteringEngine;
+use crate::database::DatabaseManager;
+use crate::meeting_notes::{ActionItem, Decision, MeetingNotesGenerator, NotesOptions};
+use crate::model_router::USE_CASE_MEETING_COMPARISON;
+use crate::obsidian_vault::VaultManager;
+
+/// Minimum similarity (0-1) for two items to count as the same one
+const MATCH_THRESHOLD: f64 = 0.6;
+
+/// One decision, action item or topic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeltaItem {
+    pub text: String,
+    pub owner: Option<String>,  // Assignee or decision maker
+    pub status: Option<String>, // Tracked action items only
+}
+
+/// An item present in both meetings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CarriedOverItem {
+    pub before: DeltaItem,
+    pub after: DeltaItem,
+    pub similarity: f64,
+}
+
+/// How one kind of item changed from the earlier meeting to the later one
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ItemDelta {
+    pub added: Vec<DeltaItem>,
+    pub removed: Vec<DeltaItem>,
+    pub carried_over: Vec<CarriedOverItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparedMeeting {
+    pub id: String,
+    pub title: String,
+    pub started_at: DateTime<Utc>,
+    pub has_notes: bool,
+    pub notes_generated: bool, // Generated on demand for this comparison
+}
+
+/// Result of `compare_meetings`. Whichever meeting started first is `earlier`.
+/// `partial` means at least one meeting had no notes, so only its tracked
+/// items and topic clusters were compared.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingComparison {
+    pub earlier: ComparedMeeting,
+    pub later: ComparedMeeting,
+    pub partial: bool,
+    pub decisions: ItemDelta,
+    pub action_items: ItemDelta,
+    pub topics: ItemDelta,
+    pub delta_summary: Option<String>,
+    pub generated_at: String,
+}
+
+#[derive(Debug, Default)]
+struct MeetingItems {
+    decisions: Vec<DeltaItem>,
+    action_items: Vec<DeltaItem>,
+    topics: Vec<DeltaItem>,
+}
+
+/// Compare two meetings. With `generate_missing_notes`, a meeting without
+/// notes gets them generated first; otherwise (or if that fails) the
+/// comparison is returned as partial.
+pub async fn compare_meetings(
+    database: &Arc<DatabaseManager>,
+    ai_client: &AIClient,
+    meeting_id_a: &str,
+    meeting_id_b: &str,
+    generate_missing_notes: bool,
+) -> Result<MeetingComparison, String> {
+    if meeting_id_a == meeting_id_b {
+        return Err("Pick two different meetings to compare".to_string());
+    }
+
+    let mut meetings = Vec::with_capacity(2);
+    for id in [meeting_id_a, meeting_id_b] {
+        let meeting = database
+            .get_meeting(id)
+            .await
+            .map_err(|e| format!("Failed to get meeting: {}", e))?
+            .ok_or_else(|| format!("Meeting {} not found", id))?;
+        meetings.push(meeting);
+    }
+    meetings.sort_by_key(|m| m.started_at);
+
+    let mut compared = Vec::with_capacity(2);
+    let mut items = Vec::with_capacity(2);
+    for meeting in meetings {
+        let mut has_notes = has_notes(database, &meeting.id).await?;
+        let mut notes_generated = false;
+        if !has_notes && generate_missing_notes {
+            let generator = MeetingNotesGenerator::new(ai_client.clone());
+            match generator
+                .generate_notes(&meeting.id, database, &NotesOptions::default())
+                .await
+            {
+                Ok(_) => {
+                    has_notes = true;
+                    notes_generated = true;
+                }
+                Err(e) => log::warn!("Comparison: no notes for {}: {}", meeting.id, e),
+            }
+        }
+        items.push(load_items(database, &meeting.id).await?);
+        compared.push(ComparedMeeting {
+            id: meeting.id,
+            title: meeting.title,
+            started_at: meeting.started_at,
+            has_notes,
+            notes_generated,
+        });
+    }
+
+    let later_items = items.pop().unwrap_or_default();
+    let earlier_items = items.pop().unwrap_or_default();
+    let later = compared.pop().ok_or("Missing meeting")?;
+    let earlier = compared.pop().ok_or("Missing meeting")?;
+
+    let mut comparison = MeetingComparison {
+        partial: !earlier.has_notes || !later.has_notes,
+        earlier,
+        later,
+        decisions: diff_items(&earlier_items.decisions, &later_items.decisions),
+        action_items: diff_items(&earlier_items.action_items, &later_items.action_items),
+        topics: diff_items(&earlier_items.topics, &later_items.topics),
+        delta_summary: None,
+        generated_at: Utc::now().to_rfc3339(),
+    };
+    comparison.delta_summary = delta_summary(ai_client, &comparison).await;
+    Ok(comparison)
+}
+
+async fn has_notes(database: &DatabaseManager, meeting_id: &str) -> Result<bool, String> {
+    database
+        .get_meeting_notes(meeting_id)
+        .await
+        .map(|n| n.is_some())
+        .map_err(|e| format!("Failed to get meeting notes: {}", e))
+}
+
+/// Decisions, action items and topics of one meeting, deduplicated.
+/// Tracked records go first so their status sticks.
+async fn load_items(database: &DatabaseManager, meeting_id: &str) -> Result<MeetingItems, String> {
+    let mut items = MeetingItems::default();
+
+    let tracked = database
+        .list_action_items(Some(meeting_id))
+        .await
+        .map_err(|e| format!("Failed to list action items: {}", e))?;
+    for record in tracked {
+        push_unique(
+            &mut items.action_items,
+            DeltaItem {
+                text: record.text,
+                owner: record.assignee,
+                status: Some(record.status),
+            },
+        );
+    }
+    let marked = database
+        .get_decisions(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get decisions: {}", e))?;
+    for record in marked {
+        push_unique(&mut items.decisions, delta_item(&record.text, None));
+    }
+
+    let notes = database
+        .get_meeting_notes(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting notes: {}", e))?;
+    if let Some(notes) = notes {
+        let decisions: Vec<Decision> = parse_json(notes.decisions.as_deref());
+        for d in decisions {
+            push_unique(&mut items.decisions, delta_item(&d.text, d.made_by));
+        }
+        let action_items: Vec<ActionItem> = parse_json(notes.action_items.as_deref());
+        for a in action_items {
+            push_unique(&mut items.action_items, delta_item(&a.task, a.assignee));
+        }
+        let topics: Vec<String> = parse_json(notes.key_topics.as_deref());
+        for t in topics {
+            push_unique(&mut items.topics, delta_item(&t, None));
+        }
+    }
+
+    let clusters = database
+        .get_topic_clusters(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get topic clusters: {}", e))?;
+    for cluster in clusters {
+        if !is_placeholder_topic(&cluster.name) {
+            push_unique(&mut items.topics, delta_item(&cluster.name, None));
+        }
+    }
+    Ok(items)
+}
+
+fn delta_item(text: &str, owner: Option<String>) -> DeltaItem {
+    DeltaItem {
+        text: text.trim().to_string(),
+        owner: owner.filter(|o| !o.trim().is_empty()),
+        status: None,
+    }
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(json: Option<&str>) -> Vec<T> {
+    json.and_then(|j| serde_json::from_str(j).ok())
+        .unwrap_or_default()
+}
+
+/// Add `item` unless the same text is already listed (filling in a missing owner)
+fn push_unique(items: &mut Vec<DeltaItem>, item: DeltaItem) {
+    let key = normalize_text(&item.text);
+    if key.is_empty() {
+        return;
+    }
+    match items.iter_mut().find(|i| normalize_text(&i.text) == key) {
+        Some(existing) => {
+            if existing.owner.is_none() {
+                existing.owner = item.owner;
+            }
+        }
+        None => items.push(item),
+    }
+}
+
+/// Unlabeled clusters ("Topic 3", "Segment 2") say nothing across meetings
+fn is_placeholder_topic(name: &str) -> bool {
+    let mut words = name.split_whitespace();
+    matches!(
+        (words.next(), words.next(), words.next()),
+        (Some("Topic" | "Segment"), Some(n), None) if n.chars().all(|c| c.is_ascii_digit())
+    )
+}
+
+/// Similarity of two item texts (0-1) after normalization
+pub fn item_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (normalize_text(a), normalize_text(b));
+    if a == b {
+        return 1.0;
+    }
+    let words = ClusteringEngine::text_similarity(&a, &b);
+    let chars = crate::transcription::glossary::similarity(&a, &b) as f64;
+    words.max(chars)
+}
+
+/// Pair items greedily, most similar first; the rest are added or removed
+pub fn diff_items(before: &[DeltaItem], after: &[DeltaItem]) -> ItemDelta {
+    let mut pairs = Vec::new();
+    for (i, b) in before.iter().enumerate() {
+        for (j, a) in after.iter().enumerate() {
+            let similarity = item_similarity(&b.text, &a.text);
+            if similarity >= MATCH_THRESHOLD {
+                pairs.push((similarity, i, j));
+            }
+        }
+    }
+    pairs.sort_by(|x, y| y.0.total_cmp(&x.0));
+
+    let mut before_used = vec![false; before.len()];
+    let mut after_used = vec![false; after.len()];
+    let mut delta = ItemDelta::default();
+    for (similarity, i, j) in pairs {
+        if before_used[i] || after_used[j] {
+            continue;
+        }
+        before_used[i] = true;
+        after_used[j] = true;
+        delta.carried_over.push(CarriedOverItem {
+            before: before[i].clone(),
+            after: after[j].clone(),
+            similarity,
+        });
+    }
+    // Keep the later meeting's order for carried-over items
+    delta
+        .carried_over
+        .sort_by_key(|c| after.iter().position(|a| a == &c.after));
+
+    delta.removed = before
+        .iter()
+        .zip(&before_used)
+        .filter(|(_, used)| !**used)
+        .map(|(item, _)| item.clone())
+        .collect();
+    delta.added = after
+        .iter()
+        .zip(&after_used)
+        .filter(|(_, used)| !**used)
+        .map(|(item, _)| item.clone())
+        .collect();
+    delta
+}
+
+/// One AI-written paragraph on what changed; None when the model is unavailable
+async fn delta_summary(ai_client: &AIClient, comparison: &MeetingComparison) -> Option<String> {
+    let changes = [
+        &comparison.decisions,
+        &comparison.action_items,
+        &comparison.topics,
+    ];
+    if changes
+        .iter()
+        .all(|d| d.added.is_empty() && d.removed.is_empty() && d.carried_over.is_empty())
+    {
+        return None;
+    }
+
+    let prompt = format!(
+        "Write one paragraph (3-5 sentences, no heading, no bullet points) on what changed between these two meetings of a recurring series: new and dropped decisions, action items that are new, finished or still carried over, and shifts in topics. Use only the facts below.\n\n{}",
+        render_sections(comparison)
+    );
+    match ai_client
+        .complete_for(USE_CASE_MEETING_COMPARISON, &prompt)
+        .await
+    {
+        Ok(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        Ok(_) => None,
+        Err(e) => {
+            log::warn!("Comparison: delta summary failed: {}", e);
+            None
+        }
+    }
+}
+
+fn render_item(item: &DeltaItem) -> String {
+    let mut line = item.text.clone();
+    if let Some(ref owner) = item.owner {
+        line.push_str(&format!(" (@{})", owner));
+    }
+    if let Some(ref status) = item.status {
+        line.push_str(&format!(" [{}]", status));
+    }
+    line
+}
+
+/// Markdown sections for the three deltas
+fn render_sections(comparison: &MeetingComparison) -> String {
+    let mut md = String::new();
+    for (heading, delta) in [
+        ("Decisions", &comparison.decisions),
+        ("Action Items", &comparison.action_items),
+        ("Topics", &comparison.topics),
+    ] {
+        md.push_str(&format!("## {}\n\n", heading));
+        if delta.added.is_empty() && delta.removed.is_empty() && delta.carried_over.is_empty() {
+            md.push_str("Nothing recorded.\n\n");
+            continue;
+        }
+        for (label, items) in [("New", &delta.added), ("Dropped", &delta.removed)] {
+            if items.is_empty() {
+                continue;
+            }
+            md.push_str(&format!("**{}**\n\n", label));
+            for item in items {
+                md.push_str(&format!("- {}\n", render_item(item)));
+            }
+            md.push('\n');
+        }
+        if !delta.carried_over.is_empty() {
+            md.push_str("**Carried over**\n\n");
+            for item in &delta.carried_over {
+                md.push_str(&format!("- {}\n", render_item(&item.after)));
+            }
+            md.push('\n');
+        }
+    }
+    md
+}
+
+/// "Sync Delta" note: frontmatter, links to both meetings, summary and deltas
+pub fn render_markdown(comparison: &MeetingComparison) -> String {
+    let (earlier, later) = (&comparison.earlier, &comparison.later);
+    let title = format!("Sync Delta - {}", later.title);
+    let mut md = format!(
+        "---\ntitle: \"{}\"\ndate: \"{}\"\ntype: sync-delta\ntags: [sync-delta]\nmeeting_ids: [\"{}\", \"{}\"]\n---\n\n# {}\n\n",
+        title.replace('"', "'"),
+        later.started_at.format("%Y-%m-%d"),
+        earlier.id,
+        later.id,
+        title
+    );
+    md.push_str(&format!(
+        "Comparing **{}** ({}) with **{}** ({}).\n\n",
+        earlier.title,
+        earlier.started_at.format("%Y-%m-%d"),
+        later.title,
+        later.started_at.format("%Y-%m-%d")
+    ));
+    if comparison.partial {
+        md.push_str("> Partial comparison: notes were missing for at least one meeting.\n\n");
+    }
+    if let Some(summary) = comparison.delta_summary.as_deref() {
+        md.push_str(&format!("## Summary\n\n{}\n\n", summary));
+    }
+    md.push_str(&render_sections(comparison));
+    md
+}
+
+/// Write the comparison to the vault's Sync Deltas/ folder; returns the path
+pub async fn export_to_vault(
+    vault_manager: &VaultManager,
+    comparison: &MeetingComparison,
+) -> Result<String, String> {
+    let safe_title = comparison
+        .later
+        .title
+        .replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "-");
+    let file_name = format!(
+        "{} Sync Delta - {}.md",
+        comparison.later.started_at.format("%Y-%m-%d"),
+        safe_title
+    );
+    vault_manager
+        .write_sync_delta(&file_name, &render_markdown(comparison))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str) -> DeltaItem {
+        delta_item(text, None)
+    }
+
+    #[test]
+    fn similar_items_carry_over() {
+        let before = [
+            item("Send Q3 budget to finance"),
+            item("Book the offsite venue"),
+            item("Hire a designer"),
+        ];
+        let after = [
+            item("Draft the launch announcement"),
+            item("Send the Q3 budget to Finance"),
+            item("Hire a designer"),
+        ];
+        let delta = diff_items(&before, &after);
+
+        assert_eq!(delta.added, [item("Draft the launch announcement")]);
+        assert_eq!(delta.removed, [item("Book the offsite venue")]);
+        let carried: Vec<&str> = delta
+            .carried_over
+            .iter()
+            .map(|c| c.after.text.as_str())
+            .collect();
+        assert_eq!(
+            carried,
+            ["Send the Q3 budget to Finance", "Hire a designer"]
+        );
+        assert_eq!(delta.carried_over[1].similarity, 1.0);
+    }
+
+    #[test]
+    fn each_item_matches_at_most_once() {
+        let before = [item("Update the roadmap")];
+        let after = [item("Update the roadmap"), item("Update roadmap")];
+        let delta = diff_items(&before, &after);
+        assert_eq!(delta.carried_over.len(), 1);
+        assert_eq!(delta.carried_over[0].after.text, "Update the roadmap");
+        assert_eq!(delta.added, [item("Update roadmap")]);
+    }
+
+    #[test]
+    fn duplicates_and_placeholder_topics_are_dropped() {
+        let mut items = vec![item("Ship v2")];
+        push_unique(&mut items, delta_item("ship V2!", Some("Sam".to_string())));
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].owner.as_deref(), Some("Sam"));
+
+        assert!(is_placeholder_topic("Topic 3"));
+        assert!(is_placeholder_topic("Segment 12"));
+        assert!(!is_placeholder_topic("Topic modeling"));
+        assert!(!is_placeholder_topic("Pricing"));
+    }
+}
@@ -2,7 +2,8 @@
 // AI-powered meeting analysis and notes generation
 
 use crate::ai_client::AIClient;
-use crate::database::DatabaseManager;
+use crate::database::{DatabaseManager, Transcript};
+use crate::model_router::{self, RoutedPrompt, USE_CASE_MEETING_NOTES};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
@@ -32,6 +33,72 @@ pub struct ActionItem {
     pub priority: Option<String>,
 }
 
+/// One-sentence summary of a document episode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpisodeSummary {
+    pub summary: String,
+    pub keywords: Vec<String>,
+}
+
+/// Episodes summarized at once when a meeting is finalized
+const EPISODE_SUMMARY_CONCURRENCY: usize = 2;
+/// Screen text sent to the model per episode
+const EPISODE_CONTEXT_TEXT_CHARS: usize = 3000;
+const EPISODE_MAX_KEYWORDS: usize = 5;
+
+/// Length/tone preset for generated notes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotesStyle {
+    #[default]
+    Standard,
+    Brief,
+    Detailed,
+    Executive,
+}
+
+impl NotesStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotesStyle::Standard => "standard",
+            NotesStyle::Brief => "brief",
+            NotesStyle::Detailed => "detailed",
+            NotesStyle::Executive => "executive",
+        }
+    }
+
+    fn guidance(&self) -> Option<&'static str> {
+        match self {
+            NotesStyle::Standard => None,
+            NotesStyle::Brief => {
+                Some("Keep everything short: a one-sentence summary and at most 3 topics.")
+            }
+            NotesStyle::Detailed => Some(
+                "Be thorough: a full-paragraph summary, every topic discussed, and context for each decision.",
+            ),
+            NotesStyle::Executive => Some(
+                "Write for an executive who missed the meeting: lead with outcomes, decisions and risks, skip discussion detail.",
+            ),
+        }
+    }
+}
+
+/// How a notes generation is steered and scoped
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotesOptions {
+    /// Free-form guidance, e.g. "focus on budget decisions, ignore small talk"
+    #[serde(default)]
+    pub instructions: Option<String>,
+    #[serde(default)]
+    pub style: NotesStyle,
+    /// Seconds from meeting start; None means from the beginning
+    #[serde(default)]
+    pub start_secs: Option<f64>,
+    /// Seconds from meeting start; None means to the end
+    #[serde(default)]
+    pub end_secs: Option<f64>,
+}
+
 /// Meeting Notes Generator
 pub struct MeetingNotesGenerator {
     ai_client: AIClient,
@@ -42,11 +109,13 @@ impl MeetingNotesGenerator {
         Self { ai_client }
     }
 
-    /// Generate notes from meeting transcripts
+    /// Generate notes from meeting transcripts. Every generation is kept as
+    /// a new version and becomes the meeting's active notes.
     pub async fn generate_notes(
         &self,
         meeting_id: &str,
         database: &Arc<DatabaseManager>,
+        options: &NotesOptions,
     ) -> Result<GeneratedNotes, String> {
         // Get all transcripts for the meeting
         let transcripts = database
@@ -54,6 +123,17 @@ impl MeetingNotesGenerator {
             .await
             .map_err(|e| format!("Failed to get transcripts: {}", e))?;
 
+        let transcripts = if options.start_secs.is_some() || options.end_secs.is_some() {
+            let meeting = database
+                .get_meeting(meeting_id)
+                .await
+                .map_err(|e| format!("Failed to get meeting: {}", e))?
+                .ok_or_else(|| format!("Meeting {} not found", meeting_id))?;
+            transcripts_in_range(transcripts, meeting.started_at, options)
+        } else {
+            transcripts
+        };
+
         if transcripts.is_empty() {
             return Err("No transcripts found for this meeting".to_string());
         }
@@ -72,7 +152,9 @@ impl MeetingNotesGenerator {
             .join("\n");
 
         // Generate notes using AI
-        let notes = self.analyze_transcript(&full_transcript).await?;
+        let prompt = notes_prompt(&full_transcript, options);
+        let route = model_router::resolve(USE_CASE_MEETING_NOTES).await;
+        let notes = self.analyze_transcript(&route, &prompt).await?;
 
         // Save to database
         let notes_id = Uuid::new_v4().to_string();
@@ -90,43 +172,44 @@ impl MeetingNotesGenerator {
                 Some(&decisions_json),
                 Some(&action_items_json),
                 Some(&participants_json),
-                Some("default"),
+                Some(&route.model),
             )
             .await
             .map_err(|e| format!("Failed to save notes: {}", e))?;
 
-        Ok(notes)
-    }
-
-    /// Analyze transcript and extract structured notes
-    async fn analyze_transcript(&self, transcript: &str) -> Result<GeneratedNotes, String> {
-        let prompt = format!(
-            r#"Analyze this meeting transcript and extract:
-1. A brief summary (2-3 sentences)
-2. Key topics discussed (list of 3-7 topics)
-3. Decisions made (who decided what)
-4. Action items (task, assignee if mentioned, priority)
-5. Participants mentioned
-
-Return as JSON:
-{{
-  "summary": "...",
-  "key_topics": ["topic1", "topic2"],
-  "decisions": [{{"text": "...", "made_by": "...", "context": "..."}}],
-  "action_items": [{{"task": "...", "assignee": "...", "priority": "high/medium/low"}}],
-  "participants": ["name1", "name2"]
-}}
+        database
+            .save_meeting_notes_version(
+                &notes_id,
+                meeting_id,
+                options.style.as_str(),
+                options.instructions.as_deref(),
+                &prompt,
+                &route.model,
+                options.start_secs,
+                options.end_secs,
+            )
+            .await
+            .map_err(|e| format!("Failed to save notes version: {}", e))?;
 
-TRANSCRIPT:
-{}
+        crate::integrations::emit_meeting_event(
+            database,
+            crate::integrations::EVENT_NOTES_GENERATED,
+            meeting_id,
+        )
+        .await;
 
-JSON RESPONSE:"#,
-            transcript.chars().take(8000).collect::<String>()
-        );
+        Ok(notes)
+    }
 
+    /// Run the notes prompt and parse the structured response
+    async fn analyze_transcript(
+        &self,
+        route: &RoutedPrompt,
+        prompt: &str,
+    ) -> Result<GeneratedNotes, String> {
         let response = self
             .ai_client
-            .complete(&prompt)
+            .complete_routed(route, prompt)
             .await
             .map_err(|e| format!("AI analysis failed: {}", e))?;
 
@@ -149,11 +232,67 @@ JSON RESPONSE:"#,
         );
 
         self.ai_client
-            .complete(&prompt)
+            .complete_for(USE_CASE_MEETING_NOTES, &prompt)
             .await
             .map_err(|e| format!("Summary generation failed: {}", e))
     }
 
+    /// Generate a short meeting title from the opening of the transcript
+    pub async fn generate_title(
+        &self,
+        transcript_excerpt: &str,
+        topics: &[String],
+    ) -> Result<String, String> {
+        let topics_line = if topics.is_empty() {
+            String::new()
+        } else {
+            format!("\nTOPICS SEEN ON SCREEN: {}\n", topics.join(", "))
+        };
+
+        let prompt = format!(
+            r#"Write a concise title (3-8 words) for this meeting based on the start of its transcript.
+Return only the title, with no quotes or punctuation at the end.
+{}
+TRANSCRIPT:
+{}
+
+TITLE:"#,
+            topics_line,
+            transcript_excerpt.chars().take(4000).collect::<String>()
+        );
+
+        let response = self
+            .ai_client
+            .complete_for(USE_CASE_MEETING_NOTES, &prompt)
+            .await
+            .map_err(|e| format!("Title generation failed: {}", e))?;
+
+        clean_title(&response).ok_or_else(|| "AI returned an empty title".to_string())
+    }
+
+    /// Summarize one episode in a sentence plus 3-5 keywords
+    pub async fn generate_episode_summary(&self, context: &str) -> Result<EpisodeSummary, String> {
+        let prompt = format!(
+            r#"Summarize what the user did during this stretch of screen activity.
+Return JSON only: {{"summary": "<one sentence>", "keywords": ["3-5 short keywords"]}}
+
+ACTIVITY:
+{}
+
+JSON:"#,
+            context
+        );
+
+        let response = self
+            .ai_client
+            .complete_for(USE_CASE_MEETING_NOTES, &prompt)
+            .await
+            .map_err(|e| format!("Episode summary failed: {}", e))?;
+
+        parse_episode_summary(&response)
+            .ok_or_else(|| format!("Failed to parse episode summary: {}", response))
+    }
+
     /// Extract action items only
     pub async fn extract_action_items(&self, transcript: &str) -> Result<Vec<ActionItem>, String> {
         let prompt = format!(
@@ -169,7 +308,7 @@ JSON ARRAY:"#,
 
         let response = self
             .ai_client
-            .complete(&prompt)
+            .complete_for(USE_CASE_MEETING_NOTES, &prompt)
             .await
             .map_err(|e| format!("Action item extraction failed: {}", e))?;
 
@@ -177,6 +316,269 @@ JSON ARRAY:"#,
     }
 }
 
+/// Notes prompt for a transcript, with style guidance and user instructions
+fn notes_prompt(transcript: &str, options: &NotesOptions) -> String {
+    let mut guidance = String::new();
+    if let Some(style) = options.style.guidance() {
+        guidance.push_str(&format!("\nSTYLE: {}\n", style));
+    }
+    if let Some(instructions) = options
+        .instructions
+        .as_deref()
+        .map(str::trim)
+        .filter(|i| !i.is_empty())
+    {
+        guidance.push_str(&format!("\nADDITIONAL INSTRUCTIONS: {}\n", instructions));
+    }
+
+    format!(
+        r#"Analyze this meeting transcript and extract:
+1. A brief summary (2-3 sentences)
+2. Key topics discussed (list of 3-7 topics)
+3. Decisions made (who decided what)
+4. Action items (task, assignee if mentioned, priority)
+5. Participants mentioned
+{}
+Return as JSON:
+{{
+  "summary": "...",
+  "key_topics": ["topic1", "topic2"],
+  "decisions": [{{"text": "...", "made_by": "...", "context": "..."}}],
+  "action_items": [{{"task": "...", "assignee": "...", "priority": "high/medium/low"}}],
+  "participants": ["name1", "name2"]
+}}
+
+TRANSCRIPT:
+{}
+
+JSON RESPONSE:"#,
+        guidance,
+        transcript.chars().take(8000).collect::<String>()
+    )
+}
+
+/// Keep transcripts inside the options' time range (offsets from meeting start)
+fn transcripts_in_range(
+    transcripts: Vec<Transcript>,
+    meeting_start: chrono::DateTime<chrono::Utc>,
+    options: &NotesOptions,
+) -> Vec<Transcript> {
+    let offset = |secs: f64| meeting_start + chrono::Duration::milliseconds((secs * 1000.0) as i64);
+    let start = options.start_secs.map(offset);
+    let end = options.end_secs.map(offset);
+
+    transcripts
+        .into_iter()
+        .filter(|t| {
+            start.map_or(true, |s| t.timestamp >= s) && end.map_or(true, |e| t.timestamp <= e)
+        })
+        .collect()
+}
+
+/// Normalize a model-generated title: first line, no wrapping quotes, bounded length
+fn clean_title(raw: &str) -> Option<String> {
+    const MAX_TITLE_CHARS: usize = 80;
+
+    let line = raw
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())?
+        .trim_start_matches("Title:")
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'' || c == '*' || c == '#')
+        .trim_end_matches('.')
+        .trim();
+
+    if line.is_empty() {
+        return None;
+    }
+
+    Some(line.chars().take(MAX_TITLE_CHARS).collect())
+}
+
+/// Generate and store the summary for one episode from the states and text
+/// snapshots captured during it
+pub async fn summarize_episode(
+    database: &DatabaseManager,
+    ai_client: AIClient,
+    episode_id: &str,
+) -> Result<EpisodeSummary, String> {
+    let episode = database
+        .get_episode(episode_id)
+        .await
+        .map_err(|e| format!("Failed to load episode: {}", e))?
+        .ok_or_else(|| format!("Episode not found: {}", episode_id))?;
+
+    let parse = |ts: &str| {
+        chrono::DateTime::parse_from_rfc3339(ts)
+            .ok()
+            .map(|t| t.with_timezone(&chrono::Utc))
+    };
+    let start = parse(&episode.start_ts).ok_or("Episode has an invalid start time")?;
+    let end = episode
+        .end_ts
+        .as_deref()
+        .and_then(parse)
+        .unwrap_or_else(chrono::Utc::now);
+    let in_range = |ts: &str| parse(ts).is_some_and(|t| t >= start && t <= end);
+
+    let categories: Vec<String> = database
+        .get_screen_states(&episode.meeting_id, 10_000)
+        .await
+        .map_err(|e| format!("Failed to load screen states: {}", e))?
+        .into_iter()
+        .filter(|s| in_range(&s.start_ts))
+        .map(|s| s.state_type)
+        .collect();
+    let texts: Vec<String> = database
+        .get_text_snapshots_by_meeting(&episode.meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load text snapshots: {}", e))?
+        .into_iter()
+        .filter(|s| s.episode_id.as_deref() == Some(episode_id) || in_range(&s.ts))
+        .map(|s| s.text)
+        .collect();
+
+    let context = episode_context(&episode, &categories, &texts);
+    let summary = MeetingNotesGenerator::new(ai_client)
+        .generate_episode_summary(&context)
+        .await?;
+
+    database
+        .set_episode_summary(episode_id, &summary.summary, &summary.keywords)
+        .await
+        .map_err(|e| format!("Failed to save episode summary: {}", e))?;
+
+    Ok(summary)
+}
+
+/// Summarize every unsummarized episode of a meeting that lasted at least
+/// `min_duration_ms`. Returns how many summaries were stored.
+pub async fn summarize_meeting_episodes(
+    database: Arc<DatabaseManager>,
+    ai_client: AIClient,
+    meeting_id: &str,
+    min_duration_ms: i64,
+) -> usize {
+    let episodes = match database.get_episodes(meeting_id).await {
+        Ok(e) => e,
+        Err(e) => {
+            log::warn!("Episode summaries: failed to load episodes: {}", e);
+            return 0;
+        }
+    };
+
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(EPISODE_SUMMARY_CONCURRENCY));
+    let mut handles = Vec::new();
+    for episode in episodes
+        .into_iter()
+        .filter(|e| e.summary.is_none() && e.total_duration_ms >= min_duration_ms)
+    {
+        let database = database.clone();
+        let ai_client = ai_client.clone();
+        let semaphore = semaphore.clone();
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok()?;
+            match summarize_episode(&database, ai_client, &episode.episode_id).await {
+                Ok(_) => Some(()),
+                Err(e) => {
+                    log::warn!("Episode {} not summarized: {}", episode.episode_id, e);
+                    None
+                }
+            }
+        }));
+    }
+
+    let mut stored = 0;
+    for handle in handles {
+        if let Ok(Some(())) = handle.await {
+            stored += 1;
+        }
+    }
+    stored
+}
+
+/// Prompt context for an episode: where it happened, what kind of activity the
+/// screen states show, and the most recent distinct screen text
+fn episode_context(
+    episode: &crate::database::DocumentEpisodeRecord,
+    categories: &[String],
+    texts: &[String],
+) -> String {
+    let mut context = String::new();
+    if let Some(app) = &episode.app_name {
+        context.push_str(&format!("APP: {}\n", app));
+    }
+    if let Some(title) = &episode.window_title {
+        context.push_str(&format!("WINDOW: {}\n", title));
+    }
+    context.push_str(&format!(
+        "DURATION: {} min\n",
+        (episode.total_duration_ms / 60_000).max(1)
+    ));
+
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for category in categories {
+        match counts.iter_mut().find(|(c, _)| *c == category.as_str()) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((category.as_str(), 1)),
+        }
+    }
+    counts.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+    if !counts.is_empty() {
+        let listed: Vec<String> = counts
+            .iter()
+            .map(|(c, n)| format!("{} ({} screens)", c, n))
+            .collect();
+        context.push_str(&format!("ACTIVITY: {}\n", listed.join(", ")));
+    }
+
+    let mut budget = EPISODE_CONTEXT_TEXT_CHARS;
+    let mut excerpts: Vec<&str> = Vec::new();
+    for text in texts.iter().rev().map(|t| t.trim()) {
+        if budget == 0 {
+            break;
+        }
+        if text.is_empty() || excerpts.contains(&text) {
+            continue;
+        }
+        let end = text
+            .char_indices()
+            .nth(budget)
+            .map_or(text.len(), |(i, _)| i);
+        budget -= text[..end].chars().count();
+        excerpts.push(&text[..end]);
+    }
+    if !excerpts.is_empty() {
+        excerpts.reverse();
+        context.push_str("SCREEN TEXT:\n");
+        context.push_str(&excerpts.join("\n---\n"));
+    }
+
+    context
+}
+
+/// Parse the model's JSON reply, tolerating surrounding prose or code fences
+fn parse_episode_summary(raw: &str) -> Option<EpisodeSummary> {
+    let json = &raw[raw.find('{')?..=raw.rfind('}')?];
+    let parsed: EpisodeSummary = serde_json::from_str(json).ok()?;
+
+    let summary = parsed.summary.trim().to_string();
+    if summary.is_empty() {
+        return None;
+    }
+
+    let mut keywords: Vec<String> = Vec::new();
+    for keyword in parsed.keywords.iter().map(|k| k.trim()) {
+        if !keyword.is_empty() && !keywords.iter().any(|k| k.eq_ignore_ascii_case(keyword)) {
+            keywords.push(keyword.to_string());
+        }
+    }
+    keywords.truncate(EPISODE_MAX_KEYWORDS);
+
+    Some(EpisodeSummary { summary, keywords })
+}
+
 /// Cluster transcripts into logical segments based on time gaps and topic similarity
 pub fn cluster_transcripts_by_time(
     transcripts: &[crate::database::Transcript],
@@ -204,3 +606,55 @@ pub fn cluster_transcripts_by_time(
 
     clusters
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_title() {
+        assert_eq!(
+            clean_title("\n\"Q3 Roadmap Planning.\"\nExtra text").as_deref(),
+            Some("Q3 Roadmap Planning")
+        );
+        assert_eq!(
+            clean_title("Title: Hiring Sync").as_deref(),
+            Some("Hiring Sync")
+        );
+        assert_eq!(clean_title("  \n \"\" "), None);
+    }
+
+    #[test]
+    fn test_parse_episode_summary() {
+        let parsed = parse_episode_summary(
+            "```json\n{\"summary\": \" Reviewed the Q3 budget sheet. \", \"keywords\": [\"budget\", \"Q3\", \"Budget\", \" \", \"forecast\", \"sheets\", \"finance\", \"review\"]}\n```",
+        )
+        .unwrap();
+        assert_eq!(parsed.summary, "Reviewed the Q3 budget sheet.");
+        assert_eq!(
+            parsed.keywords,
+            vec!["budget", "Q3", "forecast", "sheets", "finance"]
+        );
+
+        assert!(parse_episode_summary("no json here").is_none());
+        assert!(parse_episode_summary("{\"summary\": \"\", \"keywords\": []}").is_none());
+    }
+
+    #[test]
+    fn test_notes_prompt_guidance() {
+        let plain = notes_prompt("A: hello", &NotesOptions::default());
+        assert!(!plain.contains("STYLE:"));
+        assert!(!plain.contains("ADDITIONAL INSTRUCTIONS:"));
+
+        let steered = notes_prompt(
+            "A: hello",
+            &NotesOptions {
+                instructions: Some("focus on budget decisions".to_string()),
+                style: NotesStyle::Executive,
+                ..Default::default()
+            },
+        );
+        assert!(steered.contains("STYLE: Write for an executive"));
+        assert!(steered.contains("ADDITIONAL INSTRUCTIONS: focus on budget decisions"));
+    }
+}
@@ -0,0 +1,445 @@
+// noFriction Meetings - Meeting Report Export
+// Renders a single self-contained HTML report for sharing a meeting
+//
+// Everything (styles, keyframes) is inlined so the file can be emailed or
+// dropped into a shared drive. The document is streamed to disk section by
+// section so long meetings never have to fit in memory as one string.
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::database::{
+    DatabaseManager, Meeting, MeetingNotes, ScreenStateRecord, TimelineEventRecord, Transcript,
+};
+use crate::meeting_notes::{ActionItem, Decision};
+
+/// Number of screen states to inline as keyframes
+pub const MAX_REPORT_KEYFRAMES: usize = 12;
+
+const REPORT_STYLE: &str = r#"
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; max-width: 920px; margin: 40px auto; padding: 0 24px; color: #1d1d1f; line-height: 1.5; }
+h1 { margin-bottom: 4px; }
+h2 { margin-top: 36px; border-bottom: 1px solid #e5e5e5; padding-bottom: 6px; }
+.meta { color: #6e6e73; font-size: 14px; }
+.segment { margin: 6px 0; }
+.ts { color: #6e6e73; font-family: ui-monospace, Menlo, monospace; font-size: 12px; margin-right: 8px; }
+.speaker { font-weight: 600; margin-right: 6px; }
+.event { margin: 8px 0; }
+.event-type { display: inline-block; font-size: 11px; text-transform: uppercase; background: #f2f2f7; border-radius: 4px; padding: 1px 6px; margin-right: 6px; }
+.keyframe { margin: 16px 0; }
+.keyframe img { max-width: 100%; border: 1px solid #e5e5e5; border-radius: 6px; }
+.caption { color: #6e6e73; font-size: 13px; }
+"#;
+
+/// Everything needed to render a report, loaded up front from the database
+struct ReportData {
+    meeting: Meeting,
+    transcripts: Vec<Transcript>,
+    notes: Option<MeetingNotes>,
+    timeline: Vec<TimelineEventRecord>,
+    keyframes: Vec<ScreenStateRecord>,
+}
+
+/// Export a meeting as a standalone HTML file at `output_path`
+pub async fn export_meeting_report(
+    database: &DatabaseManager,
+    meeting_id: &str,
+    output_path: &Path,
+    include_screenshots: bool,
+) -> Result<PathBuf, String> {
+    let meeting = database
+        .get_meeting(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+
+    let transcripts: Vec<Transcript> = database
+        .get_transcripts(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get transcripts: {}", e))?
+        .into_iter()
+        .filter(|t| t.is_final)
+        .collect();
+
+    let notes = database
+        .get_meeting_notes(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting notes: {}", e))?;
+
+    let timeline = database
+        .get_timeline_events(meeting_id, None, &[], None)
+        .await
+        .map_err(|e| format!("Failed to get timeline events: {}", e))?;
+
+    let keyframes = if include_screenshots {
+        let states = database
+            .get_screen_states(meeting_id, 10_000)
+            .await
+            .map_err(|e| format!("Failed to get screen states: {}", e))?;
+        select_keyframes(states, MAX_REPORT_KEYFRAMES)
+    } else {
+        Vec::new()
+    };
+
+    let data = ReportData {
+        meeting,
+        transcripts,
+        notes,
+        timeline,
+        keyframes,
+    };
+
+    if let Some(parent) = output_path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create output directory: {}", e))?;
+        }
+    }
+
+    // Keyframe reads and file writes are blocking I/O
+    let path = output_path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let file =
+            File::create(&path).map_err(|e| format!("Failed to create report file: {}", e))?;
+        let mut out = BufWriter::new(file);
+        write_report(&mut out, &data).map_err(|e| format!("Failed to write report: {}", e))?;
+        out.flush()
+            .map_err(|e| format!("Failed to write report: {}", e))?;
+        Ok(path)
+    })
+    .await
+    .map_err(|e| format!("Report export task failed: {}", e))?
+}
+
+/// Pick the longest-lived screen states that have a keyframe, in chronological order
+fn select_keyframes(states: Vec<ScreenStateRecord>, limit: usize) -> Vec<ScreenStateRecord> {
+    let mut with_keyframes: Vec<(i64, ScreenStateRecord)> = states
+        .into_iter()
+        .filter(|s| s.keyframe_path.as_deref().is_some_and(|p| !p.is_empty()))
+        .map(|s| (state_duration_ms(&s), s))
+        .collect();
+
+    with_keyframes.sort_by(|a, b| b.0.cmp(&a.0));
+    with_keyframes.truncate(limit);
+
+    let mut selected: Vec<ScreenStateRecord> = with_keyframes.into_iter().map(|(_, s)| s).collect();
+    selected.sort_by(|a, b| a.start_ts.cmp(&b.start_ts));
+    selected
+}
+
+fn state_duration_ms(state: &ScreenStateRecord) -> i64 {
+    let start = DateTime::parse_from_rfc3339(&state.start_ts).ok();
+    let end = state
+        .end_ts
+        .as_deref()
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok());
+
+    match (start, end) {
+        (Some(start), Some(end)) => (end - start).num_milliseconds().max(0),
+        _ => 0,
+    }
+}
+
+fn write_report<W: Write>(out: &mut W, data: &ReportData) -> std::io::Result<()> {
+    let meeting = &data.meeting;
+    let title = escape_html(&meeting.title);
+
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">")?;
+    writeln!(out, "<title>{}</title>", title)?;
+    writeln!(out, "<style>{}</style>\n</head>\n<body>", REPORT_STYLE)?;
+
+    // Header
+    writeln!(out, "<h1>{}</h1>", title)?;
+    writeln!(
+        out,
+        "<p class=\"meta\">{} &middot; {}</p>",
+        meeting.started_at.format("%A, %B %-d, %Y at %H:%M UTC"),
+        format_duration(meeting.duration_seconds.unwrap_or(0))
+    )?;
+
+    if let Some(notes) = &data.notes {
+        write_notes(out, notes)?;
+    }
+
+    if !data.timeline.is_empty() {
+        writeln!(out, "<h2>Timeline</h2>")?;
+        for event in &data.timeline {
+            let offset = DateTime::parse_from_rfc3339(&event.ts)
+                .map(|dt| offset_label(meeting.started_at, dt.with_timezone(&Utc)))
+                .unwrap_or_default();
+            write!(
+                out,
+                "<div class=\"event\"><span class=\"ts\">{}</span><span class=\"event-type\">{}</span>{}",
+                offset,
+                escape_html(&event.event_type),
+                escape_html(&event.title)
+            )?;
+            if let Some(desc) = event.description.as_deref().filter(|d| !d.is_empty()) {
+                write!(out, " &mdash; {}", escape_html(desc))?;
+            }
+            writeln!(out, "</div>")?;
+        }
+    }
+
+    if !data.keyframes.is_empty() {
+        writeln!(out, "<h2>Screenshots</h2>")?;
+        for state in &data.keyframes {
+            write_keyframe(out, meeting.started_at, state)?;
+        }
+    }
+
+    writeln!(out, "<h2>Transcript</h2>")?;
+    if data.transcripts.is_empty() {
+        writeln!(out, "<p class=\"meta\">No transcript was recorded.</p>")?;
+    }
+    for t in &data.transcripts {
+        write!(
+            out,
+            "<div class=\"segment\"><span class=\"ts\">{}</span>",
+            offset_label(meeting.started_at, t.timestamp)
+        )?;
+        if let Some(speaker) = t.speaker.as_deref() {
+            write!(
+                out,
+                "<span class=\"speaker\">{}:</span>",
+                escape_html(speaker)
+            )?;
+        }
+        writeln!(out, "{}</div>", escape_html(&t.text))?;
+    }
+
+    writeln!(
+        out,
+        "<p class=\"meta\">Exported by noFriction Meetings on {}</p>",
+        Utc::now().format("%Y-%m-%d %H:%M UTC")
+    )?;
+    writeln!(out, "</body>\n</html>")
+}
+
+fn write_notes<W: Write>(out: &mut W, notes: &MeetingNotes) -> std::io::Result<()> {
+    if let Some(summary) = notes.summary.as_deref().filter(|s| !s.is_empty()) {
+        writeln!(out, "<h2>Summary</h2>")?;
+        for para in summary.split("\n\n") {
+            writeln!(out, "<p>{}</p>", escape_html(para.trim()))?;
+        }
+    }
+
+    let topics: Vec<String> = parse_json_list(notes.key_topics.as_deref());
+    if !topics.is_empty() {
+        writeln!(out, "<h2>Key Topics</h2>\n<ul>")?;
+        for topic in &topics {
+            writeln!(out, "<li>{}</li>", escape_html(topic))?;
+        }
+        writeln!(out, "</ul>")?;
+    }
+
+    let decisions: Vec<Decision> = parse_json_list(notes.decisions.as_deref());
+    if !decisions.is_empty() {
+        writeln!(out, "<h2>Decisions</h2>\n<ul>")?;
+        for decision in &decisions {
+            write!(out, "<li>{}", escape_html(&decision.text))?;
+            if let Some(by) = &decision.made_by {
+                write!(out, " <span class=\"meta\">({})</span>", escape_html(by))?;
+            }
+            writeln!(out, "</li>")?;
+        }
+        writeln!(out, "</ul>")?;
+    }
+
+    let action_items: Vec<ActionItem> = parse_json_list(notes.action_items.as_deref());
+    if !action_items.is_empty() {
+        writeln!(out, "<h2>Action Items</h2>\n<ul>")?;
+        for item in &action_items {
+            write!(out, "<li>{}", escape_html(&item.task))?;
+            let details: Vec<String> = [
+                item.assignee.as_deref().map(|a| format!("owner: {}", a)),
+                item.due_date.as_deref().map(|d| format!("due: {}", d)),
+                item.priority.as_deref().map(|p| format!("priority: {}", p)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            if !details.is_empty() {
+                write!(
+                    out,
+                    " <span class=\"meta\">({})</span>",
+                    escape_html(&details.join(", "))
+                )?;
+            }
+            writeln!(out, "</li>")?;
+        }
+        writeln!(out, "</ul>")?;
+    }
+
+    Ok(())
+}
+
+/// Inline one keyframe as a base64 data URI; missing files are skipped
+fn write_keyframe<W: Write>(
+    out: &mut W,
+    meeting_start: DateTime<Utc>,
+    state: &ScreenStateRecord,
+) -> std::io::Result<()> {
+    let Some(path) = state.keyframe_path.as_deref() else {
+        return Ok(());
+    };
+
+    let bytes = match std::fs::read(path) {
+        Ok(b) => b,
+        Err(e) => {
+            log::warn!("Skipping keyframe {} in report: {}", path, e);
+            return Ok(());
+        }
+    };
+
+    let mime = if path.to_lowercase().ends_with(".png") {
+        "image/png"
+    } else {
+        "image/jpeg"
+    };
+
+    let offset = DateTime::parse_from_rfc3339(&state.start_ts)
+        .map(|dt| offset_label(meeting_start, dt.with_timezone(&Utc)))
+        .unwrap_or_default();
+    let caption = match (state.app_name.as_deref(), state.window_title.as_deref()) {
+        (Some(app), Some(window)) => format!("{} — {}", app, window),
+        (Some(app), None) => app.to_string(),
+        (None, Some(window)) => window.to_string(),
+        (None, None) => String::new(),
+    };
+
+    write!(
+        out,
+        "<div class=\"keyframe\"><img alt=\"{}\" src=\"data:{};base64,",
+        escape_html(&caption),
+        mime
+    )?;
+    out.write_all(
+        base64::engine::general_purpose::STANDARD
+            .encode(&bytes)
+            .as_bytes(),
+    )?;
+    writeln!(
+        out,
+        "\"><div class=\"caption\"><span class=\"ts\">{}</span>{}</div></div>",
+        offset,
+        escape_html(&caption)
+    )
+}
+
+/// Parse a JSON array column from meeting_notes, tolerating bad data
+fn parse_json_list<T: serde::de::DeserializeOwned>(raw: Option<&str>) -> Vec<T> {
+    raw.and_then(|s| serde_json::from_str(s).ok())
+        .unwrap_or_default()
+}
+
+/// "[mm:ss]" / "[h:mm:ss]" offset from the meeting start
+fn offset_label(start: DateTime<Utc>, ts: DateTime<Utc>) -> String {
+    let secs = (ts - start).num_seconds().max(0);
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if h > 0 {
+        format!("[{}:{:02}:{:02}]", h, m, s)
+    } else {
+        format!("[{:02}:{:02}]", m, s)
+    }
+}
+
+fn format_duration(seconds: i64) -> String {
+    let (h, m) = (seconds / 3600, (seconds % 3600) / 60);
+    if h > 0 {
+        format!("{}h {}m", h, m)
+    } else if m > 0 {
+        format!("{}m", m)
+    } else {
+        format!("{}s", seconds.max(0))
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(
+        id: &str,
+        start: &str,
+        end: Option<&str>,
+        keyframe: Option<&str>,
+    ) -> ScreenStateRecord {
+        ScreenStateRecord {
+            state_id: id.to_string(),
+            meeting_id: "m1".to_string(),
+            start_ts: start.to_string(),
+            end_ts: end.map(|s| s.to_string()),
+            app_name: None,
+            window_title: None,
+            phash: String::new(),
+            delta_score: 0.0,
+            keyframe_path: keyframe.map(|s| s.to_string()),
+            state_type: "other".to_string(),
+            category_confidence: None,
+            flags: "{}".to_string(),
+            monitor_id: None,
+        }
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("<b>\"Q&A\"</b>"),
+            "&lt;b&gt;&quot;Q&amp;A&quot;&lt;/b&gt;"
+        );
+    }
+
+    #[test]
+    fn test_select_keyframes_longest_first_then_chronological() {
+        let states = vec![
+            state(
+                "short",
+                "2024-01-01T10:00:00Z",
+                Some("2024-01-01T10:00:05Z"),
+                Some("/tmp/a.jpg"),
+            ),
+            state(
+                "long",
+                "2024-01-01T10:01:00Z",
+                Some("2024-01-01T10:05:00Z"),
+                Some("/tmp/b.jpg"),
+            ),
+            state(
+                "medium",
+                "2024-01-01T10:06:00Z",
+                Some("2024-01-01T10:07:00Z"),
+                Some("/tmp/c.jpg"),
+            ),
+            state(
+                "no_keyframe",
+                "2024-01-01T10:08:00Z",
+                Some("2024-01-01T10:30:00Z"),
+                None,
+            ),
+        ];
+
+        let selected = select_keyframes(states, 2);
+        let ids: Vec<&str> = selected.iter().map(|s| s.state_id.as_str()).collect();
+        assert_eq!(ids, vec!["long", "medium"]);
+    }
+}
@@ -0,0 +1,612 @@
+// noFriction Meetings - Meeting Series
+// Groups recurring meetings ("Monday standup") into series stored as
+// `meetings.series_id`
+//
+// Detection, for meetings not in a series yet:
+// - Meetings linked to occurrences of the same recurring calendar event
+// - Otherwise a similar title at the same local time of day (±45 min) on the
+//   same weekday, or on any weekday for identical titles (daily meetings)
+// Default titles ("Meeting 2026-03-02 10:00") never group by title.
+// Existing assignments are kept, so series ids stay stable across runs.
+
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Row, Sqlite};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub const SOURCE_CALENDAR: &str = "calendar";
+pub const SOURCE_TITLE: &str = "title";
+
+/// Minimum title similarity (0-1) for unlinked meetings to group
+const TITLE_THRESHOLD: f32 = 0.8;
+/// Start times of one series drift at most this far (minutes, time of day)
+const SLOT_TOLERANCE_MINUTES: i64 = 45;
+/// Most recent members of a series compared against a new meeting
+const RECENT_MEMBERS: usize = 5;
+/// Titles (after normalization) that say nothing about which meeting it was
+const GENERIC_TITLES: &[&str] = &[
+    "meeting",
+    "new meeting",
+    "untitled",
+    "untitled meeting",
+    "recording",
+    "call",
+];
+
+/// A series with its aggregate stats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingSeriesSummary {
+    pub id: String,
+    pub title: String,
+    pub source: String, // "calendar" or "title"
+    pub meeting_count: i64,
+    pub first_meeting_at: Option<String>,
+    pub last_meeting_at: Option<String>,
+    pub average_duration_secs: Option<f64>,
+    pub action_items_total: i64,
+    pub action_items_open: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeriesMeeting {
+    pub id: String,
+    pub title: String,
+    pub started_at: String,
+    pub duration_seconds: Option<i64>,
+    pub attendees: Vec<String>,
+    pub action_items: i64,
+}
+
+/// How often someone attended, over the meetings with a known attendee list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeriesAttendee {
+    pub email: String,
+    pub meetings_attended: i64,
+    pub attendance_rate: f64,
+}
+
+/// Result of `get_series`: stats, members (oldest first) and attendance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingSeries {
+    #[serde(flatten)]
+    pub summary: MeetingSeriesSummary,
+    pub meetings: Vec<SeriesMeeting>,
+    pub attendance: Vec<SeriesAttendee>, // Most frequent first
+}
+
+/// Meeting as seen by detection
+#[derive(Debug, Clone)]
+struct Candidate {
+    id: String,
+    title: String,
+    local_start: NaiveDateTime,
+    recurrence_key: Option<String>,
+    series_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct NewSeries {
+    id: String,
+    title: String,
+    source: &'static str,
+}
+
+#[derive(Debug, Default)]
+struct SeriesPlan {
+    new_series: Vec<NewSeries>,
+    /// (meeting id, series id)
+    assignments: Vec<(String, String)>,
+}
+
+/// Recurring event id shared by all occurrences: EventKit marks detached
+/// occurrences with "/RID=…", ICS imports append "_YYYYMMDDTHHMMSSZ"
+pub fn recurrence_key(event_id: &str) -> String {
+    let base = event_id.split("/RID=").next().unwrap_or(event_id);
+    match base.rsplit_once('_') {
+        Some((uid, stamp)) if !uid.is_empty() && is_ics_stamp(stamp) => uid.to_string(),
+        _ => base.to_string(),
+    }
+}
+
+fn is_ics_stamp(stamp: &str) -> bool {
+    let b = stamp.as_bytes();
+    b.len() == 16
+        && b[8] == b'T'
+        && b[15] == b'Z'
+        && b[..8].iter().chain(&b[9..15]).all(u8::is_ascii_digit)
+}
+
+/// Lowercase letters only, single spaces ("Weekly Sync #12" → "weekly sync")
+fn title_key(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn is_generic(key: &str) -> bool {
+    key.len() < 3 || GENERIC_TITLES.contains(&key)
+}
+
+fn is_weekday(day: Weekday) -> bool {
+    !matches!(day, Weekday::Sat | Weekday::Sun)
+}
+
+/// Same series by title and schedule
+fn same_slot(a: &Candidate, b: &Candidate) -> bool {
+    let (key_a, key_b) = (title_key(&a.title), title_key(&b.title));
+    let identical = key_a == key_b;
+    if !identical && crate::transcription::glossary::similarity(&key_a, &key_b) < TITLE_THRESHOLD {
+        return false;
+    }
+
+    let minutes = |t: &NaiveDateTime| (t.hour() * 60 + t.minute()) as i64;
+    if (minutes(&a.local_start) - minutes(&b.local_start)).abs() > SLOT_TOLERANCE_MINUTES {
+        return false;
+    }
+    let (day_a, day_b) = (a.local_start.weekday(), b.local_start.weekday());
+    day_a == day_b || (identical && is_weekday(day_a) && is_weekday(day_b))
+}
+
+/// Series for every meeting that isn't in one yet (meetings oldest first)
+fn plan_series(meetings: &[Candidate]) -> SeriesPlan {
+    let mut plan = SeriesPlan::default();
+    let mut series_of: Vec<Option<String>> = meetings.iter().map(|m| m.series_id.clone()).collect();
+
+    // Occurrences of one recurring calendar event
+    let mut by_key: Vec<(&str, Vec<usize>)> = Vec::new();
+    for (i, m) in meetings.iter().enumerate() {
+        let Some(key) = m.recurrence_key.as_deref() else {
+            continue;
+        };
+        match by_key.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, members)) => members.push(i),
+            None => by_key.push((key, vec![i])),
+        }
+    }
+    for (_, members) in by_key.iter().filter(|(_, m)| m.len() >= 2) {
+        let series_id = match members.iter().find_map(|&i| series_of[i].clone()) {
+            Some(id) => id,
+            None => {
+                let id = Uuid::new_v4().to_string();
+                plan.new_series.push(NewSeries {
+                    id: id.clone(),
+                    title: meetings[members[members.len() - 1]].title.clone(),
+                    source: SOURCE_CALENDAR,
+                });
+                id
+            }
+        };
+        for &i in members {
+            if series_of[i].is_none() {
+                series_of[i] = Some(series_id.clone());
+                plan.assignments
+                    .push((meetings[i].id.clone(), series_id.clone()));
+            }
+        }
+    }
+
+    // Everything else by title and schedule, joining existing series first
+    let mut clusters: Vec<(Option<String>, Vec<usize>)> = Vec::new();
+    for (i, series_id) in series_of.iter().enumerate() {
+        if let Some(id) = series_id {
+            match clusters.iter_mut().find(|(c, _)| c.as_ref() == Some(id)) {
+                Some((_, members)) => members.push(i),
+                None => clusters.push((Some(id.clone()), vec![i])),
+            }
+        }
+    }
+    let mut joined: Vec<usize> = Vec::new();
+    for (i, m) in meetings.iter().enumerate() {
+        if series_of[i].is_some() || is_generic(&title_key(&m.title)) {
+            continue;
+        }
+        let found = clusters.iter_mut().find(|(_, members)| {
+            members
+                .iter()
+                .rev()
+                .take(RECENT_MEMBERS)
+                .any(|&j| same_slot(m, &meetings[j]))
+        });
+        match found {
+            Some((_, members)) => members.push(i),
+            None => clusters.push((None, vec![i])),
+        }
+        joined.push(i);
+    }
+
+    for (series_id, members) in clusters {
+        let series_id = match series_id {
+            Some(id) => id,
+            None if members.len() >= 2 => {
+                let id = Uuid::new_v4().to_string();
+                plan.new_series.push(NewSeries {
+                    id: id.clone(),
+                    title: meetings[members[members.len() - 1]].title.clone(),
+                    source: SOURCE_TITLE,
+                });
+                id
+            }
+            None => continue,
+        };
+        for i in members.into_iter().filter(|i| joined.contains(i)) {
+            plan.assignments
+                .push((meetings[i].id.clone(), series_id.clone()));
+        }
+    }
+    plan
+}
+
+/// Assign unassigned meetings to series; returns how many were assigned
+pub async fn detect_series(pool: &Pool<Sqlite>) -> Result<usize, String> {
+    let rows = sqlx::query(
+        "SELECT id, title, started_at, calendar_event_id, series_id FROM meetings
+         WHERE archived_at IS NULL ORDER BY started_at ASC",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to load meetings: {}", e))?;
+
+    let meetings: Vec<Candidate> = rows
+        .iter()
+        .filter_map(|r| {
+            let started_at =
+                DateTime::parse_from_rfc3339(&r.get::<String, _>("started_at")).ok()?;
+            Some(Candidate {
+                id: r.get("id"),
+                title: r.get("title"),
+                local_start: started_at.with_timezone(&Local).naive_local(),
+                recurrence_key: r
+                    .get::<Option<String>, _>("calendar_event_id")
+                    .map(|id| recurrence_key(&id)),
+                series_id: r.get("series_id"),
+            })
+        })
+        .collect();
+
+    let plan = plan_series(&meetings);
+    if plan.assignments.is_empty() {
+        return Ok(0);
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    for series in &plan.new_series {
+        sqlx::query(
+            "INSERT INTO meeting_series (id, title, source, created_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(&series.id)
+        .bind(&series.title)
+        .bind(series.source)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to create series: {}", e))?;
+    }
+    for (meeting_id, series_id) in &plan.assignments {
+        sqlx::query("UPDATE meetings SET series_id = ? WHERE id = ?")
+            .bind(series_id)
+            .bind(meeting_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to assign series: {}", e))?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to save series: {}", e))?;
+
+    log::info!(
+        "Meeting series: {} meetings assigned, {} new series",
+        plan.assignments.len(),
+        plan.new_series.len()
+    );
+    Ok(plan.assignments.len())
+}
+
+const SUMMARY_SELECT: &str = r#"
+    SELECT s.id, s.title, s.source,
+           COUNT(m.id) AS meeting_count,
+           MIN(m.started_at) AS first_meeting_at,
+           MAX(m.started_at) AS last_meeting_at,
+           AVG(m.duration_seconds) AS average_duration_secs,
+           (SELECT COUNT(*) FROM action_items a JOIN meetings m2 ON m2.id = a.meeting_id
+            WHERE m2.series_id = s.id AND m2.archived_at IS NULL) AS action_items_total,
+           (SELECT COUNT(*) FROM action_items a JOIN meetings m2 ON m2.id = a.meeting_id
+            WHERE m2.series_id = s.id AND m2.archived_at IS NULL
+              AND a.status IN ('open', 'in_progress')) AS action_items_open
+    FROM meeting_series s
+    JOIN meetings m ON m.series_id = s.id AND m.archived_at IS NULL
+"#;
+
+fn summary_from_row(r: &sqlx::sqlite::SqliteRow) -> MeetingSeriesSummary {
+    MeetingSeriesSummary {
+        id: r.get("id"),
+        title: r.get("title"),
+        source: r.get("source"),
+        meeting_count: r.get("meeting_count"),
+        first_meeting_at: r.get("first_meeting_at"),
+        last_meeting_at: r.get("last_meeting_at"),
+        average_duration_secs: r.get("average_duration_secs"),
+        action_items_total: r.get("action_items_total"),
+        action_items_open: r.get("action_items_open"),
+    }
+}
+
+/// All series, most recently met first
+pub async fn list_series(pool: &Pool<Sqlite>) -> Result<Vec<MeetingSeriesSummary>, String> {
+    let sql = format!(
+        "{} GROUP BY s.id ORDER BY last_meeting_at DESC",
+        SUMMARY_SELECT
+    );
+    let rows = sqlx::query(&sql)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Failed to list meeting series: {}", e))?;
+    Ok(rows.iter().map(summary_from_row).collect())
+}
+
+pub async fn get_series(pool: &Pool<Sqlite>, series_id: &str) -> Result<MeetingSeries, String> {
+    let sql = format!("{} WHERE s.id = ? GROUP BY s.id", SUMMARY_SELECT);
+    let summary = sqlx::query(&sql)
+        .bind(series_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Failed to load meeting series: {}", e))?
+        .map(|r| summary_from_row(&r))
+        .ok_or_else(|| format!("Meeting series not found: {}", series_id))?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT m.id, m.title, m.started_at, m.duration_seconds, m.attendees_json,
+               (SELECT COUNT(*) FROM action_items a WHERE a.meeting_id = m.id) AS action_items
+        FROM meetings m
+        WHERE m.series_id = ? AND m.archived_at IS NULL
+        ORDER BY m.started_at ASC
+        "#,
+    )
+    .bind(series_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to load series meetings: {}", e))?;
+
+    let meetings: Vec<SeriesMeeting> = rows
+        .iter()
+        .map(|r| SeriesMeeting {
+            id: r.get("id"),
+            title: r.get("title"),
+            started_at: r.get("started_at"),
+            duration_seconds: r.get("duration_seconds"),
+            attendees: r
+                .get::<Option<String>, _>("attendees_json")
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            action_items: r.get("action_items"),
+        })
+        .collect();
+
+    Ok(MeetingSeries {
+        summary,
+        attendance: attendance(&meetings),
+        meetings,
+    })
+}
+
+fn attendance(meetings: &[SeriesMeeting]) -> Vec<SeriesAttendee> {
+    let with_attendees = meetings.iter().filter(|m| !m.attendees.is_empty()).count();
+    let mut counts: HashMap<String, i64> = HashMap::new();
+    for meeting in meetings {
+        let mut seen: Vec<String> = meeting.attendees.iter().map(|a| a.to_lowercase()).collect();
+        seen.sort();
+        seen.dedup();
+        for email in seen {
+            *counts.entry(email).or_default() += 1;
+        }
+    }
+
+    let mut attendees: Vec<SeriesAttendee> = counts
+        .into_iter()
+        .map(|(email, meetings_attended)| SeriesAttendee {
+            email,
+            meetings_attended,
+            attendance_rate: meetings_attended as f64 / with_attendees.max(1) as f64,
+        })
+        .collect();
+    attendees.sort_by(|a, b| {
+        b.meetings_attended
+            .cmp(&a.meetings_attended)
+            .then_with(|| a.email.cmp(&b.email))
+    });
+    attendees
+}
+
+/// Vault topic folder for a meeting's series, if it's in one
+pub async fn series_topic(pool: &Pool<Sqlite>, meeting_id: &str) -> Option<String> {
+    let title: Option<String> = sqlx::query_scalar(
+        "SELECT s.title FROM meetings m JOIN meeting_series s ON s.id = m.series_id WHERE m.id = ?",
+    )
+    .bind(meeting_id)
+    .fetch_optional(pool)
+    .await
+    .ok()
+    .flatten();
+    title
+        .map(|t| t.replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "-"))
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> Pool<Sqlite> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        for sql in [
+            "CREATE TABLE meetings (id TEXT PRIMARY KEY, title TEXT NOT NULL, started_at TEXT NOT NULL, duration_seconds INTEGER, attendees_json TEXT, calendar_event_id TEXT, archived_at TEXT, series_id TEXT)",
+            "CREATE TABLE meeting_series (id TEXT PRIMARY KEY, title TEXT NOT NULL, source TEXT NOT NULL, created_at TEXT NOT NULL)",
+            "CREATE TABLE action_items (id TEXT PRIMARY KEY, meeting_id TEXT NOT NULL, text TEXT NOT NULL, status TEXT NOT NULL DEFAULT 'open')",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        pool
+    }
+
+    fn candidate(id: &str, title: &str, local: &str, event: Option<&str>) -> Candidate {
+        Candidate {
+            id: id.to_string(),
+            title: title.to_string(),
+            local_start: NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M").unwrap(),
+            recurrence_key: event.map(recurrence_key),
+            series_id: None,
+        }
+    }
+
+    fn series_of<'a>(plan: &'a SeriesPlan, meeting_id: &str) -> Option<&'a str> {
+        plan.assignments
+            .iter()
+            .find(|(m, _)| m == meeting_id)
+            .map(|(_, s)| s.as_str())
+    }
+
+    #[test]
+    fn recurrence_keys() {
+        assert_eq!(recurrence_key("abc_20260302T150000Z"), "abc");
+        assert_eq!(recurrence_key("ABC:123/RID=794127600"), "ABC:123");
+        assert_eq!(recurrence_key("ABC:123"), "ABC:123");
+        assert_eq!(recurrence_key("team_sync"), "team_sync");
+    }
+
+    #[test]
+    fn groups_by_calendar_then_title_and_slot() {
+        let meetings = [
+            // 2026-03-02 is a Monday
+            candidate(
+                "c1",
+                "Planning",
+                "2026-03-02 15:00",
+                Some("plan_20260302T150000Z"),
+            ),
+            candidate("w1", "Weekly Sync #1", "2026-03-03 10:00", None),
+            candidate("d1", "Daily Standup", "2026-03-03 09:30", None),
+            candidate("d2", "Daily Standup", "2026-03-04 09:35", None),
+            candidate(
+                "c2",
+                "Planning (moved)",
+                "2026-03-09 16:00",
+                Some("plan_20260309T160000Z"),
+            ),
+            candidate("w2", "Weekly Sync #2", "2026-03-10 10:15", None),
+            // Similar (not identical) title on another weekday
+            candidate("w3", "Weekly Syncs", "2026-03-12 10:00", None),
+            // Same weekday, different time of day
+            candidate("w4", "Weekly Sync", "2026-03-17 15:00", None),
+            candidate("g1", "Meeting 2026-03-02 11:00", "2026-03-02 11:00", None),
+            candidate("g2", "Meeting 2026-03-09 11:00", "2026-03-09 11:00", None),
+        ];
+        let plan = plan_series(&meetings);
+
+        assert_eq!(plan.new_series.len(), 3);
+        let calendar = series_of(&plan, "c1").unwrap();
+        assert_eq!(series_of(&plan, "c2"), Some(calendar));
+        let weekly = series_of(&plan, "w1").unwrap();
+        assert_eq!(series_of(&plan, "w2"), Some(weekly));
+        assert_ne!(weekly, calendar);
+        assert!(series_of(&plan, "d1").is_some());
+        assert_eq!(series_of(&plan, "d1"), series_of(&plan, "d2"));
+        for lone in ["w3", "w4", "g1", "g2"] {
+            assert_eq!(series_of(&plan, lone), None, "{}", lone);
+        }
+        let titles: Vec<(&str, &str)> = plan
+            .new_series
+            .iter()
+            .map(|s| (s.title.as_str(), s.source))
+            .collect();
+        assert!(titles.contains(&("Planning (moved)", SOURCE_CALENDAR)));
+        assert!(titles.contains(&("Weekly Sync #2", SOURCE_TITLE)));
+    }
+
+    #[test]
+    fn new_meetings_join_existing_series() {
+        let mut first = candidate("w1", "Weekly Sync", "2026-03-03 10:00", None);
+        first.series_id = Some("series-1".to_string());
+        let meetings = [
+            first,
+            candidate("w2", "Weekly sync", "2026-03-10 10:05", None),
+        ];
+        let plan = plan_series(&meetings);
+        assert!(plan.new_series.is_empty());
+        assert_eq!(
+            plan.assignments,
+            [("w2".to_string(), "series-1".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn series_stats() {
+        let pool = setup_test_db().await;
+        for (id, started_at, duration, attendees) in [
+            (
+                "m1",
+                "2026-03-02T15:00:00+00:00",
+                1200,
+                r#"["a@x.com", "b@x.com"]"#,
+            ),
+            ("m2", "2026-03-09T15:00:00+00:00", 1800, r#"["a@x.com"]"#),
+        ] {
+            sqlx::query(
+                "INSERT INTO meetings (id, title, started_at, duration_seconds, attendees_json, calendar_event_id) VALUES (?, 'Planning', ?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(started_at)
+            .bind(duration)
+            .bind(attendees)
+            .bind(format!("plan_{}", started_at.replace(['-', ':'], "").replace("+0000", "Z")))
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        for (id, meeting, status) in [("a1", "m1", "done"), ("a2", "m2", "open")] {
+            sqlx::query(
+                "INSERT INTO action_items (id, meeting_id, text, status) VALUES (?, ?, 'x', ?)",
+            )
+            .bind(id)
+            .bind(meeting)
+            .bind(status)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(detect_series(&pool).await.unwrap(), 2);
+        assert_eq!(detect_series(&pool).await.unwrap(), 0);
+
+        let all = list_series(&pool).await.unwrap();
+        assert_eq!(all.len(), 1);
+        let series = get_series(&pool, &all[0].id).await.unwrap();
+        assert_eq!(series.summary.meeting_count, 2);
+        assert_eq!(series.summary.average_duration_secs, Some(1500.0));
+        assert_eq!(
+            (
+                series.summary.action_items_total,
+                series.summary.action_items_open
+            ),
+            (2, 1)
+        );
+        assert_eq!(series.meetings[0].id, "m1");
+        assert_eq!(series.attendance[0].email, "a@x.com");
+        assert_eq!(series.attendance[0].attendance_rate, 1.0);
+        assert_eq!(series.attendance[1].attendance_rate, 0.5);
+        assert_eq!(series_topic(&pool, "m1").await.as_deref(), Some("Planning"));
+    }
+}
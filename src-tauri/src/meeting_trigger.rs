@@ -6,16 +6,30 @@
 // - Meeting app detection (Zoom, Meet, Teams)
 // - Manual button/hotkey triggers
 // - Optional audio pattern detection (VAD)
+// - Optional auto-start: a detection that persists past the debounce window
+//   starts a session via `on_meeting_start` instead of only suggesting one
+// - Stop suggestions while recording: meeting apps gone and audio quiet, or
+//   the linked calendar event over; optional auto-stop via `on_auto_stop`
 
 use crate::calendar_client::{CalendarClient, CalendarEventNative};
 
 use chrono::{DateTime, Duration, Utc};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter};
 
+/// A detection must persist this long before recording auto-starts
+/// (joining and leaving a waiting room shouldn't create a meeting)
+const AUTO_START_DEBOUNCE_SECS: i64 = 45;
+/// After an auto-start, the same detection is ignored until it has been
+/// gone this long (stopping a recording doesn't immediately restart it)
+const AUTO_START_COOLDOWN_MINS: i64 = 10;
+/// How long the user can undo an auto-started meeting
+pub const AUTO_START_UNDO_SECS: i64 = 30;
+
 /// Meeting trigger source
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TriggerSource {
@@ -58,6 +72,165 @@ pub struct MeetingDetection {
 /// Callback for meeting detection suggestions
 pub type DetectionCallback = Arc<dyn Fn(MeetingDetection) + Send + Sync>;
 
+/// Why a recording looks finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    /// No meeting app running and no audio usage for the idle grace period
+    MeetingAppsClosed,
+    /// The linked calendar event ended more than the grace period ago
+    CalendarEventEnded,
+}
+
+impl StopReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StopReason::MeetingAppsClosed => "meeting_apps_closed",
+            StopReason::CalendarEventEnded => "calendar_event_ended",
+        }
+    }
+}
+
+/// Payload of the `suggest-stop-recording` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StopSuggestion {
+    pub reason: StopReason,
+    pub detail: String,
+    pub suggested_at: DateTime<Utc>,
+    /// Whether auto-stop is on and the recording is being stopped
+    pub auto_stop: bool,
+}
+
+/// Callback when auto-stop decides the recording should end
+pub type AutoStopCallback = Arc<dyn Fn(StopSuggestion) + Send + Sync>;
+
+/// Meeting recording started by detection, undoable until `undo_until`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoStartedMeeting {
+    pub meeting_id: String,
+    pub title: String,
+    pub detection_id: String,
+    pub undo_until: DateTime<Utc>,
+}
+
+/// What the debouncer made of the latest detection poll
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Observation {
+    /// Nothing detected
+    Idle,
+    /// First poll with this detection
+    New,
+    /// Still inside the debounce window (or cooling down after a start)
+    Waiting,
+    /// Persisted long enough to auto-start
+    Ready,
+}
+
+/// Tracks how long the current detection has persisted
+#[derive(Debug, Default)]
+struct AutoStartDebounce {
+    /// Detection being observed and when it first appeared
+    pending: Option<(String, DateTime<Utc>)>,
+    /// Detections that already auto-started, with when they were last seen
+    handled: HashMap<String, DateTime<Utc>>,
+}
+
+/// Watches an active recording for signs the meeting is over
+#[derive(Debug, Default)]
+struct RecordingWatch {
+    /// Calendar event the recording is linked to
+    event: Option<CalendarEventNative>,
+    /// Whether we've looked for an event yet (manual recordings)
+    event_checked: bool,
+    /// When meeting apps and audio both went quiet
+    quiet_since: Option<DateTime<Utc>>,
+    /// Suggest once per recording
+    suggested: bool,
+}
+
+impl RecordingWatch {
+    fn observe(
+        &mut self,
+        meeting_signals: bool,
+        now: DateTime<Utc>,
+        idle_grace: Duration,
+        event_grace: Duration,
+    ) -> Option<(StopReason, String)> {
+        if meeting_signals {
+            self.quiet_since = None;
+        } else if self.quiet_since.is_none() {
+            self.quiet_since = Some(now);
+        }
+
+        if self.suggested {
+            return None;
+        }
+
+        let reason = match (self.quiet_since, &self.event) {
+            (Some(quiet_since), _) if now - quiet_since >= idle_grace => Some((
+                StopReason::MeetingAppsClosed,
+                format!(
+                    "No meeting app or audio activity for {} min",
+                    (now - quiet_since).num_minutes()
+                ),
+            )),
+            (_, Some(event)) if now - event.end_time >= event_grace => Some((
+                StopReason::CalendarEventEnded,
+                format!(
+                    "\"{}\" ended {} min ago",
+                    event.title,
+                    (now - event.end_time).num_minutes()
+                ),
+            )),
+            _ => None,
+        };
+
+        if reason.is_some() {
+            self.suggested = true;
+        }
+        reason
+    }
+}
+
+impl AutoStartDebounce {
+    fn observe(&mut self, detection_id: Option<&str>, now: DateTime<Utc>) -> Observation {
+        self.handled
+            .retain(|_, last_seen| now - *last_seen < Duration::minutes(AUTO_START_COOLDOWN_MINS));
+
+        let id = match detection_id {
+            Some(id) => id,
+            None => {
+                self.pending = None;
+                return Observation::Idle;
+            }
+        };
+
+        if let Some(last_seen) = self.handled.get_mut(id) {
+            *last_seen = now;
+            return Observation::Waiting;
+        }
+
+        match &self.pending {
+            Some((pending_id, first_seen)) if pending_id == id => {
+                if now - *first_seen >= Duration::seconds(AUTO_START_DEBOUNCE_SECS) {
+                    Observation::Ready
+                } else {
+                    Observation::Waiting
+                }
+            }
+            _ => {
+                self.pending = Some((id.to_string(), now));
+                Observation::New
+            }
+        }
+    }
+
+    fn mark_started(&mut self, detection_id: &str, now: DateTime<Utc>) {
+        self.pending = None;
+        self.handled.insert(detection_id.to_string(), now);
+    }
+}
+
 /// Configuration for meeting triggers
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MeetingTriggerConfig {
@@ -73,6 +246,10 @@ pub struct MeetingTriggerConfig {
     pub audio_trigger_enabled: bool,
     /// Minimum seconds of conversation to trigger
     pub audio_min_conversation_secs: u32,
+    /// Suggest stopping after this long with no meeting app or audio
+    pub stop_idle_grace_minutes: u32,
+    /// Suggest stopping this long after the linked calendar event ends
+    pub stop_event_grace_minutes: u32,
 }
 
 impl Default for MeetingTriggerConfig {
@@ -93,6 +270,8 @@ impl Default for MeetingTriggerConfig {
             ],
             audio_trigger_enabled: false,
             audio_min_conversation_secs: 30,
+            stop_idle_grace_minutes: 5,
+            stop_event_grace_minutes: 10,
         }
     }
 }
@@ -101,7 +280,8 @@ impl Default for MeetingTriggerConfig {
 pub type TriggerCallback = Arc<dyn Fn(MeetingSession) + Send + Sync>;
 pub type EndCallback = Arc<dyn Fn(MeetingSession) + Send + Sync>;
 
-/// Meeting Trigger Engine
+/// Meeting Trigger Engine (cheap to clone; clones share state)
+#[derive(Clone)]
 pub struct MeetingTriggerEngine {
     config: Arc<RwLock<MeetingTriggerConfig>>,
     is_running: Arc<AtomicBool>,
@@ -112,6 +292,15 @@ pub struct MeetingTriggerEngine {
     app_handle: Option<AppHandle>,
     /// Track which detections user has dismissed (don't re-suggest)
     dismissed_detections: Arc<RwLock<std::collections::HashSet<String>>>,
+    /// Start recording on detection instead of only suggesting
+    auto_start: Arc<AtomicBool>,
+    debounce: Arc<RwLock<AutoStartDebounce>>,
+    last_auto_start: Arc<RwLock<Option<AutoStartedMeeting>>>,
+    /// Stop recording automatically instead of only suggesting
+    auto_stop: Arc<AtomicBool>,
+    on_auto_stop: Arc<RwLock<Option<AutoStopCallback>>>,
+    /// Present while a recording is active
+    recording_watch: Arc<RwLock<Option<RecordingWatch>>>,
 }
 
 impl MeetingTriggerEngine {
@@ -125,9 +314,85 @@ impl MeetingTriggerEngine {
             on_meeting_detected: Arc::new(RwLock::new(None)),
             app_handle: None,
             dismissed_detections: Arc::new(RwLock::new(std::collections::HashSet::new())),
+            auto_start: Arc::new(AtomicBool::new(false)),
+            debounce: Arc::new(RwLock::new(AutoStartDebounce::default())),
+            last_auto_start: Arc::new(RwLock::new(None)),
+            auto_stop: Arc::new(AtomicBool::new(false)),
+            on_auto_stop: Arc::new(RwLock::new(None)),
+            recording_watch: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Configure stop suggestions: auto-stop toggle and grace periods (minutes)
+    pub fn set_auto_stop(&self, enabled: bool, idle_grace_minutes: u32, event_grace_minutes: u32) {
+        self.auto_stop.store(enabled, Ordering::SeqCst);
+        let mut config = self.config.write();
+        config.stop_idle_grace_minutes = idle_grace_minutes.max(1);
+        config.stop_event_grace_minutes = event_grace_minutes;
+        log::info!(
+            "🎯 Auto-stop recording: {} (idle grace {}m, event grace {}m)",
+            enabled,
+            config.stop_idle_grace_minutes,
+            config.stop_event_grace_minutes
+        );
+    }
+
+    /// Set callback for when auto-stop decides the recording is over
+    pub fn on_auto_stop(&self, callback: AutoStopCallback) {
+        *self.on_auto_stop.write() = Some(callback);
+    }
+
+    /// Track recording start/stop so stop suggestions only fire while recording
+    pub fn set_recording(&self, active: bool) {
+        let mut watch = self.recording_watch.write();
+        if !active {
+            *watch = None;
+            return;
+        }
+
+        // Auto-started recordings are already linked to their calendar event
+        let event = self
+            .current_session
+            .read()
+            .as_ref()
+            .and_then(|s| s.calendar_event.clone());
+        *watch = Some(RecordingWatch {
+            event_checked: event.is_some(),
+            event,
+            ..Default::default()
+        });
+    }
+
+    /// Enable/disable starting recordings automatically on detection
+    pub fn set_auto_start(&self, enabled: bool) {
+        self.auto_start.store(enabled, Ordering::SeqCst);
+        log::info!("🎯 Auto-start on meeting detection: {}", enabled);
+    }
+
+    /// Remember an auto-started recording so it can be undone
+    pub fn record_auto_start(&self, meeting: AutoStartedMeeting) {
+        *self.last_auto_start.write() = Some(meeting);
+    }
+
+    /// Take the auto-started meeting if it is still inside its undo window
+    pub fn take_undoable_auto_start(&self, meeting_id: &str) -> Option<AutoStartedMeeting> {
+        let mut last = self.last_auto_start.write();
+        let undoable = last
+            .as_ref()
+            .map(|m| m.meeting_id == meeting_id && Utc::now() <= m.undo_until)
+            .unwrap_or(false);
+        if undoable {
+            last.take()
+        } else {
+            None
+        }
+    }
+
+    /// Forget the current session without firing end callbacks (recording stopped elsewhere)
+    pub fn clear_session(&self) {
+        *self.current_session.write() = None;
+    }
+
     /// Set callback for meeting detection suggestions
     pub fn on_meeting_detected(&self, callback: DetectionCallback) {
         *self.on_meeting_detected.write() = Some(callback);
@@ -190,6 +455,7 @@ impl MeetingTriggerEngine {
         log::info!("🎯 Meeting trigger engine started");
 
         // Start calendar monitoring loop
+        let calendar_app = calendar_client.clone();
         let is_running = self.is_running.clone();
         let config = self.config.clone();
         let current_session = self.current_session.clone();
@@ -254,50 +520,201 @@ impl MeetingTriggerEngine {
         });
 
         // Start app detection loop (separate thread)
-        let is_running_app = self.is_running.clone();
-        let config_app = self.config.clone();
-        let current_session_app = self.current_session.clone();
-        let on_start_app = self.on_meeting_start.clone();
+        let engine = self.clone();
 
         std::thread::spawn(move || {
-            while is_running_app.load(Ordering::SeqCst) {
+            while engine.is_running.load(Ordering::SeqCst) {
                 std::thread::sleep(std::time::Duration::from_secs(5)); // Check every 5s
 
-                let cfg = config_app.read().clone();
+                engine.poll_recording_end(&calendar_app, Utc::now());
 
-                if !cfg.app_trigger_enabled {
+                if !engine.config.read().app_trigger_enabled {
                     continue;
                 }
 
-                // Check if meeting app is frontmost
-                if let Some(app_name) = Self::get_frontmost_meeting_app(&cfg.meeting_apps) {
-                    let session_exists = current_session_app.read().is_some();
-
-                    if !session_exists {
-                        let session = MeetingSession {
-                            id: uuid::Uuid::new_v4().to_string(),
-                            started_at: Utc::now(),
-                            trigger_source: TriggerSource::AppDetection,
-                            calendar_event: None,
-                            detected_app: Some(app_name.clone()),
-                            expected_end: None,
-                            is_active: true,
-                        };
-
-                        log::info!("🎯 Meeting triggered by app detection: {}", app_name);
-                        *current_session_app.write() = Some(session.clone());
-
-                        if let Some(ref cb) = *on_start_app.read() {
-                            cb(session);
-                        }
-                    }
-                }
+                engine.poll_app_detection(&calendar_app, Utc::now());
             }
         });
 
         Ok(())
     }
 
+    /// One app-detection poll: suggest new detections, or auto-start once a
+    /// detection has persisted past the debounce window
+    fn poll_app_detection(
+        &self,
+        calendar_client: &Arc<RwLock<CalendarClient>>,
+        now: DateTime<Utc>,
+    ) {
+        let detection = self.current_app_detection(calendar_client);
+        let observation = self
+            .debounce
+            .write()
+            .observe(detection.as_ref().map(|d| d.id.as_str()), now);
+
+        let detection = match detection {
+            Some(d) => d,
+            None => return,
+        };
+        if self.current_session.read().is_some() || self.is_dismissed(&detection) {
+            return;
+        }
+
+        if !self.auto_start.load(Ordering::SeqCst) {
+            // Suggest once per detection; the banner stays up until dismissed
+            if observation == Observation::New {
+                if let Some(ref app) = self.app_handle {
+                    let _ = app.emit("meeting-detected", detection);
+                }
+            }
+            return;
+        }
+
+        if observation != Observation::Ready {
+            return;
+        }
+
+        let session = MeetingSession {
+            id: uuid::Uuid::new_v4().to_string(),
+            started_at: now,
+            trigger_source: detection.source,
+            expected_end: detection.calendar_event.as_ref().map(|e| e.end_time),
+            calendar_event: detection.calendar_event.clone(),
+            detected_app: detection.app_name.clone(),
+            is_active: true,
+        };
+
+        log::info!(
+            "🎯 Auto-starting meeting from detection {} ({:?})",
+            detection.id,
+            detection.app_name
+        );
+        self.debounce.write().mark_started(&detection.id, now);
+        *self.current_session.write() = Some(session.clone());
+
+        if let Some(ref cb) = *self.on_meeting_start.read() {
+            cb(session);
+        }
+    }
+
+    /// While recording, suggest (or auto-stop) once the meeting looks over
+    fn poll_recording_end(
+        &self,
+        calendar_client: &Arc<RwLock<CalendarClient>>,
+        now: DateTime<Utc>,
+    ) {
+        let cfg = self.config.read().clone();
+
+        let needs_event = match self.recording_watch.read().as_ref() {
+            Some(watch) => !watch.event_checked,
+            None => return,
+        };
+        let linked_event = if needs_event {
+            Self::check_calendar_trigger(calendar_client, cfg.calendar_window_minutes)
+        } else {
+            None
+        };
+
+        let meeting_signals = !Self::get_running_meeting_apps(&cfg.meeting_apps).is_empty()
+            || Self::check_audio_usage();
+
+        let outcome = {
+            let mut guard = self.recording_watch.write();
+            let watch = match guard.as_mut() {
+                Some(watch) => watch,
+                None => return,
+            };
+            if needs_event {
+                watch.event = linked_event;
+                watch.event_checked = true;
+            }
+            watch.observe(
+                meeting_signals,
+                now,
+                Duration::minutes(cfg.stop_idle_grace_minutes as i64),
+                Duration::minutes(cfg.stop_event_grace_minutes as i64),
+            )
+        };
+
+        let (reason, detail) = match outcome {
+            Some(outcome) => outcome,
+            None => return,
+        };
+
+        let suggestion = StopSuggestion {
+            reason,
+            detail,
+            suggested_at: now,
+            auto_stop: self.auto_stop.load(Ordering::SeqCst),
+        };
+        log::info!(
+            "🎯 Recording looks finished: {} ({})",
+            suggestion.detail,
+            reason.as_str()
+        );
+
+        if let Some(ref app) = self.app_handle {
+            let _ = app.emit("suggest-stop-recording", &suggestion);
+        }
+
+        if suggestion.auto_stop {
+            if let Some(ref cb) = *self.on_auto_stop.read() {
+                cb(suggestion);
+            }
+        }
+    }
+
+    /// Meeting app that is frontmost or using audio, tied to the calendar
+    /// event in progress if there is one (so dismissals apply per event)
+    fn current_app_detection(
+        &self,
+        calendar_client: &Arc<RwLock<CalendarClient>>,
+    ) -> Option<MeetingDetection> {
+        let cfg = self.config.read().clone();
+        let frontmost_app = Self::get_frontmost_meeting_app(&cfg.meeting_apps);
+        let audio_active = Self::check_audio_usage();
+
+        let app_name = match frontmost_app {
+            Some(app) => app,
+            None if audio_active => Self::get_running_meeting_apps(&cfg.meeting_apps)
+                .into_iter()
+                .next()?,
+            None => return None,
+        };
+
+        let calendar_event = if cfg.calendar_trigger_enabled {
+            Self::check_calendar_trigger(calendar_client, cfg.calendar_window_minutes)
+        } else {
+            None
+        };
+
+        let (id, source) = match &calendar_event {
+            Some(event) => (format!("cal-{}", event.event_id), TriggerSource::Calendar),
+            None => (app_detection_id(&app_name), TriggerSource::AppDetection),
+        };
+
+        Some(MeetingDetection {
+            id,
+            detected_at: Utc::now(),
+            source,
+            app_name: Some(app_name),
+            calendar_event,
+            is_using_audio: audio_active,
+            is_screen_sharing: false,
+        })
+    }
+
+    /// Dismissed either for this calendar event or for the app itself
+    fn is_dismissed(&self, detection: &MeetingDetection) -> bool {
+        let dismissed = self.dismissed_detections.read();
+        dismissed.contains(&detection.id)
+            || detection
+                .app_name
+                .as_ref()
+                .map(|app| dismissed.contains(&app_detection_id(app)))
+                .unwrap_or(false)
+    }
+
     /// Stop monitoring
     pub fn stop(&self) {
         self.is_running.store(false, Ordering::SeqCst);
@@ -546,7 +963,7 @@ impl MeetingTriggerEngine {
             }
 
             // Create detection ID based on app
-            let detection_id = format!("app-{}", app_name.to_lowercase().replace(" ", "-"));
+            let detection_id = app_detection_id(&app_name);
 
             // Skip if already dismissed
             if self.dismissed_detections.read().contains(&detection_id) {
@@ -617,6 +1034,11 @@ impl MeetingTriggerEngine {
     }
 }
 
+/// Detection ID for a meeting app with no calendar event
+pub fn app_detection_id(app_name: &str) -> String {
+    format!("app-{}", app_name.to_lowercase().replace(" ", "-"))
+}
+
 impl Default for MeetingTriggerEngine {
     fn default() -> Self {
         Self::new()
@@ -648,4 +1070,87 @@ mod tests {
         engine.end_meeting();
         assert!(!engine.is_meeting_active());
     }
+
+    #[test]
+    fn test_recording_watch_reasons() {
+        let t0 = Utc::now();
+        let idle = Duration::minutes(5);
+        let grace = Duration::minutes(10);
+
+        // Apps closed: quiet must last the whole grace period, and any signal resets it
+        let mut watch = RecordingWatch::default();
+        assert_eq!(watch.observe(false, t0, idle, grace), None);
+        assert_eq!(
+            watch.observe(true, t0 + Duration::minutes(3), idle, grace),
+            None
+        );
+        assert_eq!(
+            watch.observe(false, t0 + Duration::minutes(4), idle, grace),
+            None
+        );
+        let (reason, _) = watch
+            .observe(false, t0 + Duration::minutes(9), idle, grace)
+            .unwrap();
+        assert_eq!(reason, StopReason::MeetingAppsClosed);
+        // Only suggested once per recording
+        assert_eq!(
+            watch.observe(false, t0 + Duration::minutes(20), idle, grace),
+            None
+        );
+
+        // Calendar event ended, even though the meeting app is still open
+        let mut watch = RecordingWatch {
+            event: Some(CalendarEventNative {
+                event_id: "evt-1".to_string(),
+                title: "Standup".to_string(),
+                start_time: t0 - Duration::minutes(30),
+                end_time: t0,
+                location: None,
+                notes: None,
+                attendees: vec![],
+                is_all_day: false,
+                calendar_name: "Work".to_string(),
+                meeting_url: None,
+            }),
+            event_checked: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            watch.observe(true, t0 + Duration::minutes(5), idle, grace),
+            None
+        );
+        let (reason, _) = watch
+            .observe(true, t0 + Duration::minutes(10), idle, grace)
+            .unwrap();
+        assert_eq!(reason, StopReason::CalendarEventEnded);
+    }
+
+    #[test]
+    fn test_auto_start_debounce() {
+        let mut debounce = AutoStartDebounce::default();
+        let t0 = Utc::now();
+        let at = |secs: i64| t0 + Duration::seconds(secs);
+
+        // Waiting room: seen briefly, gone, back again - the clock restarts
+        assert_eq!(debounce.observe(Some("app-zoom"), at(0)), Observation::New);
+        assert_eq!(
+            debounce.observe(Some("app-zoom"), at(30)),
+            Observation::Waiting
+        );
+        assert_eq!(debounce.observe(None, at(35)), Observation::Idle);
+        assert_eq!(debounce.observe(Some("app-zoom"), at(40)), Observation::New);
+        assert_eq!(
+            debounce.observe(Some("app-zoom"), at(85)),
+            Observation::Ready
+        );
+
+        // Once started, the same detection is ignored until gone for the cooldown
+        debounce.mark_started("app-zoom", at(85));
+        assert_eq!(
+            debounce.observe(Some("app-zoom"), at(200)),
+            Observation::Waiting
+        );
+        let later = at(200) + Duration::minutes(AUTO_START_COOLDOWN_MINS);
+        assert_eq!(debounce.observe(Some("app-zoom"), later), Observation::New);
+    }
 }
@@ -1,8 +1,9 @@
 // noFriction Meetings - Native macOS Menu Builder
 // Creates native menu bar following Apple Human Interface Guidelines
 
+use crate::shortcuts::{label_with_binding, run_action, ShortcutAction};
 use tauri::{
-    menu::{Menu, MenuBuilder, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder},
+    menu::{Menu, MenuBuilder, MenuItem, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder},
     AppHandle, Emitter, Runtime, Wry,
 };
 
@@ -32,6 +33,13 @@ pub mod menu_ids {
     pub const MODE_AMBIENT: &str = "mode_ambient";
     pub const MODE_MEETING: &str = "mode_meeting";
     pub const MODE_PAUSE: &str = "mode_pause";
+
+    // Global shortcut actions; labels show the current bindings
+    pub const SHORTCUT_TOGGLE_RECORDING: &str = "shortcut_toggle_recording";
+    pub const SHORTCUT_PIN_MOMENT: &str = "shortcut_pin_moment";
+    pub const SHORTCUT_TOGGLE_GENIE: &str = "shortcut_toggle_genie";
+    pub const SHORTCUT_QUICK_NOTE: &str = "shortcut_quick_note";
+    pub const KEYBOARD_SHORTCUTS: &str = "keyboard_shortcuts";
 }
 
 /// Build the application menu bar
@@ -152,6 +160,28 @@ pub fn create_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<Menu<R>> {
         .item(&MenuItemBuilder::with_id(menu_ids::MODE_AMBIENT, "🌙 Ambient Mode").build(app)?)
         .item(&MenuItemBuilder::with_id(menu_ids::MODE_MEETING, "🎙️ Meeting Mode").build(app)?)
         .item(&MenuItemBuilder::with_id(menu_ids::MODE_PAUSE, "⏸️ Pause Recording").build(app)?)
+        .separator()
+        // Global shortcuts (work from any app)
+        .item(&shortcut_item(
+            app,
+            menu_ids::SHORTCUT_TOGGLE_RECORDING,
+            ShortcutAction::ToggleRecording,
+        )?)
+        .item(&shortcut_item(
+            app,
+            menu_ids::SHORTCUT_PIN_MOMENT,
+            ShortcutAction::PinMoment,
+        )?)
+        .item(&shortcut_item(
+            app,
+            menu_ids::SHORTCUT_TOGGLE_GENIE,
+            ShortcutAction::ToggleGenie,
+        )?)
+        .item(&shortcut_item(
+            app,
+            menu_ids::SHORTCUT_QUICK_NOTE,
+            ShortcutAction::QuickNote,
+        )?)
         .build()?;
 
     // Window menu
@@ -166,7 +196,10 @@ pub fn create_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<Menu<R>> {
     let help_menu = SubmenuBuilder::new(app, "Help")
         .item(&MenuItemBuilder::new("noFriction Meetings Help").build(app)?)
         .separator()
-        .item(&MenuItemBuilder::new("Keyboard Shortcuts").build(app)?)
+        .item(
+            &MenuItemBuilder::with_id(menu_ids::KEYBOARD_SHORTCUTS, "Keyboard Shortcuts")
+                .build(app)?,
+        )
         .item(&MenuItemBuilder::new("Report an Issue...").build(app)?)
         .build()?;
 
@@ -182,6 +215,17 @@ pub fn create_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<Menu<R>> {
         .build()
 }
 
+/// Menu item for a global shortcut action, labelled with its binding. The
+/// binding is not set as the item's accelerator: the global hotkey already
+/// fires while the app is focused.
+fn shortcut_item<R: Runtime>(
+    app: &AppHandle<R>,
+    id: &str,
+    action: ShortcutAction,
+) -> tauri::Result<MenuItem<R>> {
+    MenuItemBuilder::with_id(id, label_with_binding(action.label(), action)).build(app)
+}
+
 /// Handle menu item events
 pub fn handle_menu_event(app: &AppHandle<Wry>, event_id: &str) {
     match event_id {
@@ -243,6 +287,22 @@ pub fn handle_menu_event(app: &AppHandle<Wry>, event_id: &str) {
         menu_ids::MODE_PAUSE => {
             emit_to_frontend(app, "menu:mode_pause");
         }
+        // Global shortcut actions
+        menu_ids::SHORTCUT_TOGGLE_RECORDING => {
+            run_action(app, ShortcutAction::ToggleRecording);
+        }
+        menu_ids::SHORTCUT_PIN_MOMENT => {
+            run_action(app, ShortcutAction::PinMoment);
+        }
+        menu_ids::SHORTCUT_TOGGLE_GENIE => {
+            run_action(app, ShortcutAction::ToggleGenie);
+        }
+        menu_ids::SHORTCUT_QUICK_NOTE => {
+            run_action(app, ShortcutAction::QuickNote);
+        }
+        menu_ids::KEYBOARD_SHORTCUTS => {
+            emit_to_frontend(app, "menu:keyboard_shortcuts");
+        }
         _ => {
             log::debug!("Unhandled menu event: {}", event_id);
         }
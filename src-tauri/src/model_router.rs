@@ -0,0 +1,391 @@
+// noFriction Meetings - Model Router
+// Resolves an AI use case to its prompt and model: PromptManager use-case
+// mappings first, builtin prompts when no mapping exists, and a fixed model
+// fallback order (configured -> default chat model -> any available)
+
+use crate::ai_client::{AIClient, COMPLETION_MODEL};
+use crate::prompt_manager::{Prompt, PromptManager};
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+pub const USE_CASE_MEETING_NOTES: &str = "meeting_notes";
+pub const USE_CASE_CATCH_UP: &str = "catch_up";
+pub const USE_CASE_STUDY_MATERIALS: &str = "study_materials";
+pub const USE_CASE_ATTENDEE_BRIEFING: &str = "attendee_briefing";
+pub const USE_CASE_ENTITY_DOSSIER: &str = "entity_dossier";
+pub const USE_CASE_MEETING_COMPARISON: &str = "meeting_comparison";
+
+/// How long the backend's model list is trusted before re-checking
+const AVAILABLE_MODELS_TTL: Duration = Duration::from_secs(60);
+
+/// Prompt seeded for a use case and used when its mapping is missing
+pub struct BuiltinRoute {
+    pub use_case: &'static str,
+    pub display_name: &'static str,
+    pub description: &'static str,
+    pub category: &'static str,
+    pub system_prompt: &'static str,
+    pub temperature: f32,
+}
+
+pub const BUILTIN_ROUTES: &[BuiltinRoute] = &[
+    BuiltinRoute {
+        use_case: USE_CASE_MEETING_NOTES,
+        display_name: "Meeting Notes",
+        description: "Structured notes, titles and summaries of recorded meetings",
+        category: "meeting",
+        system_prompt: "You are a professional meeting assistant. You turn meeting transcripts into accurate, well-structured notes. Only state what the transcript supports, and follow the requested output format exactly.",
+        temperature: 0.3,
+    },
+    BuiltinRoute {
+        use_case: USE_CASE_CATCH_UP,
+        display_name: "Catch-Up",
+        description: "Late-join summaries of a meeting in progress",
+        category: "meeting",
+        system_prompt: "You help someone who joined a meeting late catch up quickly. Be brief, neutral and specific, say who said what when it matters, and follow the requested output format exactly.",
+        temperature: 0.3,
+    },
+    BuiltinRoute {
+        use_case: USE_CASE_STUDY_MATERIALS,
+        display_name: "Study Materials",
+        description: "Summaries, key concepts and quizzes from study (dork mode) sessions",
+        category: "study",
+        system_prompt: "You are a study assistant. You turn study session content into clear summaries, definitions and quiz questions that help a student review. Follow the requested output format exactly.",
+        temperature: 0.4,
+    },
+    BuiltinRoute {
+        use_case: USE_CASE_ATTENDEE_BRIEFING,
+        display_name: "Attendee Briefing",
+        description: "Person, company and meeting-prep briefings for calendar attendees",
+        category: "meeting",
+        system_prompt: "You are a business intelligence analyst preparing meeting briefings. Be concise and actionable, and mark anything you are unsure about as likely or estimated.",
+        temperature: 0.4,
+    },
+    BuiltinRoute {
+        use_case: USE_CASE_ENTITY_DOSSIER,
+        display_name: "Entity Dossier",
+        description: "Two-paragraph rollups of everything known about a person or company",
+        category: "meeting",
+        system_prompt: "You summarize what someone's own meetings, notes and activity say about a person or organization. Be factual and specific, cite dates when they matter, and never invent details the timeline doesn't contain.",
+        temperature: 0.3,
+    },
+    BuiltinRoute {
+        use_case: USE_CASE_MEETING_COMPARISON,
+        display_name: "Meeting Comparison",
+        description: "What changed between two meetings of a recurring series",
+        category: "meeting",
+        system_prompt: "You compare two meetings of a recurring series for someone who wants to know what changed. Be brief and specific, name owners when given, and only state what the provided changes support.",
+        temperature: 0.3,
+    },
+];
+
+pub fn builtin_route(use_case: &str) -> Option<&'static BuiltinRoute> {
+    BUILTIN_ROUTES.iter().find(|r| r.use_case == use_case)
+}
+
+/// Which step of the fallback order picked the model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelResolution {
+    /// Model mapped to the use case (or its prompt) and offered by the backend
+    Configured,
+    /// Configured model missing or not offered; default chat model used
+    DefaultChat,
+    /// Neither is offered; first model the backend lists
+    AnyAvailable,
+    /// Backend model list unknown (unreachable); first candidate used as-is
+    Unchecked,
+}
+
+impl ModelResolution {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModelResolution::Configured => "configured",
+            ModelResolution::DefaultChat => "default_chat",
+            ModelResolution::AnyAvailable => "any_available",
+            ModelResolution::Unchecked => "unchecked",
+        }
+    }
+}
+
+/// Where the system prompt came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptSource {
+    Mapping,
+    Builtin,
+    /// A specific prompt picked by the caller (e.g. an A/B evaluation)
+    Explicit,
+}
+
+impl PromptSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PromptSource::Mapping => "use-case mapping",
+            PromptSource::Builtin => "builtin",
+            PromptSource::Explicit => "explicit prompt",
+        }
+    }
+}
+
+/// Prompt and model to use for one AI call
+#[derive(Debug, Clone, Serialize)]
+pub struct RoutedPrompt {
+    pub use_case: String,
+    pub system_prompt: Option<String>,
+    pub temperature: f32,
+    pub model: String,
+    pub resolution: ModelResolution,
+    pub prompt_source: PromptSource,
+}
+
+pub struct ModelRouter {
+    prompt_manager: RwLock<Option<Arc<PromptManager>>>,
+    default_chat_model: RwLock<Option<String>>,
+    available: Mutex<Option<(Instant, Vec<String>)>>,
+}
+
+pub fn model_router() -> &'static ModelRouter {
+    static ROUTER: OnceLock<ModelRouter> = OnceLock::new();
+    ROUTER.get_or_init(|| ModelRouter {
+        prompt_manager: RwLock::new(None),
+        default_chat_model: RwLock::new(None),
+        available: Mutex::new(None),
+    })
+}
+
+impl ModelRouter {
+    /// Start reading use-case mappings (until then only builtins are used)
+    pub fn init(&self, prompt_manager: Arc<PromptManager>) {
+        *self.prompt_manager.write() = Some(prompt_manager);
+    }
+
+    /// The `ai_chat_model` setting (None = `COMPLETION_MODEL`)
+    pub fn set_default_chat_model(&self, model: Option<String>) {
+        *self.default_chat_model.write() = model.filter(|m| !m.trim().is_empty());
+    }
+
+    /// Forget the cached model list, e.g. after switching AI provider
+    pub fn invalidate_models(&self) {
+        *self.available.lock() = None;
+    }
+
+    /// Resolve a use case to its prompt and model, logging the path taken
+    pub async fn resolve(&self, use_case: &str) -> RoutedPrompt {
+        let builtin = builtin_route(use_case);
+        let mut system_prompt = builtin.map(|b| b.system_prompt.to_string());
+        let mut temperature = builtin.map(|b| b.temperature).unwrap_or(0.3);
+        let mut prompt_source = PromptSource::Builtin;
+        let mut configured: Option<String> = None;
+
+        let prompt_manager = self.prompt_manager.read().clone();
+        if let Some(pm) = prompt_manager {
+            match pm.get_resolved_use_case(use_case).await {
+                Ok(Some(resolved)) if resolved.use_case.is_active => {
+                    let prompt = resolved.prompt.filter(|p| p.is_active);
+                    configured = resolved.model.map(|m| m.name);
+                    if let Some(prompt) = prompt {
+                        if configured.is_none() {
+                            if let Some(ref model_id) = prompt.model_id {
+                                configured = pm
+                                    .get_model_config(model_id)
+                                    .await
+                                    .ok()
+                                    .flatten()
+                                    .map(|m| m.name);
+                            }
+                        }
+                        system_prompt = Some(prompt.system_prompt);
+                        temperature = prompt.temperature;
+                        prompt_source = PromptSource::Mapping;
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("[ModelRouter] Failed to load use case {}: {}", use_case, e),
+            }
+        }
+
+        self.route(
+            use_case,
+            system_prompt,
+            temperature,
+            prompt_source,
+            configured,
+        )
+        .await
+    }
+
+    /// Route one specific prompt (e.g. a version under evaluation): its own
+    /// text and temperature, and its model through the usual fallback order
+    pub async fn resolve_prompt(&self, prompt: &Prompt) -> RoutedPrompt {
+        let prompt_manager = self.prompt_manager.read().clone();
+        let configured = match (prompt_manager, prompt.model_id.as_deref()) {
+            (Some(pm), Some(model_id)) => pm
+                .get_model_config(model_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|m| m.name),
+            _ => None,
+        };
+
+        self.route(
+            &prompt.name,
+            Some(prompt.system_prompt.clone()),
+            prompt.temperature,
+            PromptSource::Explicit,
+            configured,
+        )
+        .await
+    }
+
+    async fn route(
+        &self,
+        use_case: &str,
+        system_prompt: Option<String>,
+        temperature: f32,
+        prompt_source: PromptSource,
+        configured: Option<String>,
+    ) -> RoutedPrompt {
+        let default_chat = self.default_chat_model.read().clone();
+        let available = self.available_models().await;
+        let (model, resolution) =
+            pick_model(configured.as_deref(), default_chat.as_deref(), &available);
+
+        log::info!(
+            "[ModelRouter] {}: model {} via {} (configured: {}), prompt from {}",
+            use_case,
+            model,
+            resolution.as_str(),
+            configured.as_deref().unwrap_or("none"),
+            prompt_source.as_str()
+        );
+
+        RoutedPrompt {
+            use_case: use_case.to_string(),
+            system_prompt,
+            temperature,
+            model,
+            resolution,
+            prompt_source,
+        }
+    }
+
+    /// Models the active backend offers (empty if it could not be reached)
+    async fn available_models(&self) -> Vec<String> {
+        let cached = self
+            .available
+            .lock()
+            .as_ref()
+            .filter(|(checked_at, _)| checked_at.elapsed() < AVAILABLE_MODELS_TTL)
+            .map(|(_, models)| models.clone());
+        if let Some(models) = cached {
+            return models;
+        }
+
+        let status = AIClient::new().check_provider().await;
+        // Don't cache a failed check, so the next call retries
+        if status.reachable {
+            *self.available.lock() = Some((Instant::now(), status.models.clone()));
+        }
+        status.models
+    }
+}
+
+/// Resolve a use case with the process-wide router
+pub async fn resolve(use_case: &str) -> RoutedPrompt {
+    model_router().resolve(use_case).await
+}
+
+/// Apply the fallback order: configured -> default chat -> any available.
+/// With no model list, the first candidate is trusted without checking.
+pub fn pick_model(
+    configured: Option<&str>,
+    default_chat: Option<&str>,
+    available: &[String],
+) -> (String, ModelResolution) {
+    let default_chat = default_chat.unwrap_or(COMPLETION_MODEL);
+
+    if available.is_empty() {
+        let model = configured.unwrap_or(default_chat);
+        return (model.to_string(), ModelResolution::Unchecked);
+    }
+
+    if let Some(model) = configured.filter(|m| is_offered(available, m)) {
+        return (model.to_string(), ModelResolution::Configured);
+    }
+    if is_offered(available, default_chat) {
+        return (default_chat.to_string(), ModelResolution::DefaultChat);
+    }
+    (available[0].clone(), ModelResolution::AnyAvailable)
+}
+
+/// Whether `model` is in the list; a bare Ollama name matches its ":latest" tag
+fn is_offered(available: &[String], model: &str) -> bool {
+    available.iter().any(|a| {
+        a == model
+            || a.strip_suffix(":latest") == Some(model)
+            || model.strip_suffix(":latest") == Some(a.as_str())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn models(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn fallback_order_is_configured_then_default_then_any() {
+        let available = models(&["llama3.2:latest", "qwen2.5vl:7b", "mistral:7b"]);
+
+        assert_eq!(
+            pick_model(Some("llama3.2"), Some("mistral:7b"), &available),
+            ("llama3.2".to_string(), ModelResolution::Configured)
+        );
+        assert_eq!(
+            pick_model(Some("gpt-4o"), Some("mistral:7b"), &available),
+            ("mistral:7b".to_string(), ModelResolution::DefaultChat)
+        );
+        // No default chat model set: the AIClient default is the default
+        assert_eq!(
+            pick_model(None, None, &available),
+            (COMPLETION_MODEL.to_string(), ModelResolution::DefaultChat)
+        );
+        assert_eq!(
+            pick_model(Some("gpt-4o"), Some("phi3"), &models(&["mistral:7b"])),
+            ("mistral:7b".to_string(), ModelResolution::AnyAvailable)
+        );
+    }
+
+    #[test]
+    fn unknown_model_list_trusts_first_candidate() {
+        assert_eq!(
+            pick_model(Some("gpt-4o"), Some("mistral:7b"), &[]),
+            ("gpt-4o".to_string(), ModelResolution::Unchecked)
+        );
+        assert_eq!(
+            pick_model(None, None, &[]),
+            (COMPLETION_MODEL.to_string(), ModelResolution::Unchecked)
+        );
+    }
+
+    #[test]
+    fn every_routed_use_case_has_a_builtin() {
+        for use_case in [
+            USE_CASE_MEETING_NOTES,
+            USE_CASE_CATCH_UP,
+            USE_CASE_STUDY_MATERIALS,
+            USE_CASE_ATTENDEE_BRIEFING,
+            USE_CASE_ENTITY_DOSSIER,
+            USE_CASE_MEETING_COMPARISON,
+        ] {
+            assert!(builtin_route(use_case).is_some(), "{}", use_case);
+        }
+        assert!(builtin_route("unknown").is_none());
+    }
+}
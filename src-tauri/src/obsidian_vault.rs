@@ -3,9 +3,13 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tokio::fs;
 
+use crate::export_template::IntelligenceSections;
+
 /// Represents a Topic — a top-level organizing folder in the vault
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VaultTopic {
@@ -57,6 +61,118 @@ pub struct VaultStatus {
     pub total_files: i32,
 }
 
+/// Everything a meeting note can show, gathered from the database
+#[derive(Debug, Clone, Default)]
+pub struct MeetingExport {
+    pub meeting_id: String,
+    pub title: String,
+    /// RFC3339
+    pub started_at: String,
+    pub duration_secs: Option<i64>,
+    /// (text, speaker, RFC3339 timestamp)
+    pub transcripts: Vec<(String, Option<String>, String)>,
+    pub summary: Option<String>,
+    /// Top timeline events with why they scored high, as markdown bullets
+    pub highlights: Vec<String>,
+    pub key_topics: Option<String>,
+    pub action_items: Option<String>,
+    pub intelligence: IntelligenceSections,
+    pub screenshot_paths: Vec<String>,
+    /// Attendee emails from the linked calendar event
+    pub attendees: Vec<String>,
+}
+
+/// What a re-export does with a meeting note that was edited in Obsidian
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VaultConflictPolicy {
+    /// Keep the edited note and append new sections under a "Re-export" heading
+    Merge,
+    /// Leave the note alone and fail the export
+    Refuse,
+}
+
+impl VaultConflictPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VaultConflictPolicy::Merge => "merge",
+            VaultConflictPolicy::Refuse => "refuse",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "merge" => Some(VaultConflictPolicy::Merge),
+            "refuse" => Some(VaultConflictPolicy::Refuse),
+            _ => None,
+        }
+    }
+}
+
+/// A note written by an export, with the hash to compare against next time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedFile {
+    pub path: String,
+    /// SHA-256 of the rendered content
+    pub content_hash: String,
+}
+
+/// An exported note whose on-disk content no longer matched the last export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultConflict {
+    pub path: String,
+    /// "merged" or "refused"
+    pub resolution: String,
+}
+
+/// Result of exporting a meeting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultExportOutcome {
+    pub meeting_dir: String,
+    pub files: Vec<ExportedFile>,
+    pub conflicts: Vec<VaultConflict>,
+}
+
+impl VaultExportOutcome {
+    /// True when the export stopped instead of touching an edited note
+    pub fn refused(&self) -> bool {
+        self.conflicts.iter().any(|c| c.resolution == "refused")
+    }
+}
+
+/// Exported note that drifted from what the app last wrote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultExportDrift {
+    pub path: String,
+    pub meeting_id: String,
+    pub exported_at: String,
+    /// "modified" or "missing"
+    pub status: String,
+}
+
+/// Person or company note already in the vault
+#[derive(Debug, Clone)]
+pub struct ExistingNote {
+    pub path: PathBuf,
+    pub content: String,
+    /// `last_updated` from the frontmatter, else the file's modified time
+    pub last_updated: Option<DateTime<Utc>>,
+}
+
+impl ExistingNote {
+    /// Whether the note was generated within the last `max_age_days`
+    pub fn is_fresh(&self, max_age_days: u32) -> bool {
+        self.last_updated
+            .map(|t| Utc::now() - t < chrono::Duration::days(max_age_days as i64))
+            .unwrap_or(false)
+    }
+
+    /// Body of the note's `## heading` section
+    pub fn section(&self, heading: &str) -> Option<String> {
+        section_body(&self.content, heading).map(str::to_string)
+    }
+}
+
 /// Search result from vault
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VaultSearchResult {
@@ -371,90 +487,97 @@ impl VaultManager {
         })
     }
 
-    /// Export a meeting to the vault under a topic
+    /// Export a meeting to the vault under a topic; meeting.md is rendered
+    /// through `template` (see export_template.rs). `previous_hashes` maps
+    /// paths from earlier exports to their content hash: notes edited since
+    /// then are merged or left alone according to `policy`.
     pub async fn export_meeting(
         &self,
         topic_name: &str,
-        meeting_id: &str,
-        title: &str,
-        started_at: &str,
-        duration_secs: Option<i64>,
-        transcripts: &[(String, Option<String>, String)], // (text, speaker, timestamp)
-        summary: Option<&str>,
-        key_topics: Option<&str>,
-        action_items: Option<&str>,
-        intelligence: Option<&str>,
-        screenshot_paths: &[String],
-    ) -> Result<String, String> {
+        meeting: &MeetingExport,
+        template: &str,
+        previous_hashes: &HashMap<String, String>,
+        policy: VaultConflictPolicy,
+    ) -> Result<VaultExportOutcome, String> {
         let root = self.nofriction_root().ok_or("Vault path not configured")?;
         let topic_dir = root.join("topics").join(topic_name);
         if !topic_dir.exists() {
             self.create_topic(topic_name, vec![]).await?;
         }
 
+        // Render first so a broken template doesn't leave an empty folder behind
+        let content = render_meeting_note(meeting, template)?;
+        let transcript_md = transcript_markdown(&meeting.transcripts);
+        let title = meeting.title.as_str();
+        let started_at = meeting.started_at.as_str();
+        let meeting_id = meeting.meeting_id.as_str();
+        let screenshot_paths = &meeting.screenshot_paths;
+
         // Create meeting folder named by date + title
         let safe_title = title.replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "-");
         let date_prefix = &started_at[..10]; // YYYY-MM-DD
         let meeting_folder_name = format!("{}-{}", date_prefix, safe_title);
         let meeting_dir = topic_dir.join("meetings").join(&meeting_folder_name);
-        fs::create_dir_all(&meeting_dir)
-            .await
-            .map_err(|e| e.to_string())?;
 
-        // Build transcript markdown
-        let mut transcript_md = String::new();
-        for (text, speaker, timestamp) in transcripts {
-            let time_str = &timestamp[11..19]; // HH:MM:SS
-            match speaker {
-                Some(s) => {
-                    transcript_md.push_str(&format!("**[{}] {}:** {}\n\n", time_str, s, text))
-                }
-                None => transcript_md.push_str(&format!("**[{}]** {}\n\n", time_str, text)),
-            }
+        let mut notes = vec![(meeting_dir.join("meeting.md"), content)];
+        if !transcript_md.is_empty() {
+            let transcript_file = format!(
+                "---\ntitle: \"Transcript - {}\"\ndate: \"{}\"\ntype: transcript\nmeeting_id: \"{}\"\n---\n\n# Transcript\n\n{}",
+                title, started_at, meeting_id, transcript_md
+            );
+            notes.push((meeting_dir.join("transcript.md"), transcript_file));
         }
 
-        // Build meeting markdown
-        let duration_str = duration_secs
-            .map(|d| format!("{}m {}s", d / 60, d % 60))
-            .unwrap_or_else(|| "Unknown".to_string());
-
-        let mut content = format!(
-            "---\ntitle: \"{}\"\ndate: \"{}\"\ntype: meeting\ntags: [meeting]\nmeeting_id: \"{}\"\nduration: \"{}\"\n---\n\n# {}\n\n",
-            title, started_at, meeting_id, duration_str, title
-        );
-
-        if let Some(s) = summary {
-            content.push_str(&format!("## Summary\n\n{}\n\n", s));
-        }
-        if let Some(kt) = key_topics {
-            content.push_str(&format!("## Key Topics\n\n{}\n\n", kt));
-        }
-        if let Some(ai) = action_items {
-            content.push_str(&format!("## Action Items\n\n{}\n\n", ai));
+        // Decide every note before writing any, so a refusal leaves the folder untouched
+        let stamp = Utc::now().format("%Y-%m-%d %H:%M UTC").to_string();
+        let mut writes = Vec::new();
+        let mut outcome = VaultExportOutcome {
+            meeting_dir: meeting_dir.to_string_lossy().to_string(),
+            files: Vec::new(),
+            conflicts: Vec::new(),
+        };
+        for (path, rendered) in notes {
+            let path_str = path.to_string_lossy().to_string();
+            let on_disk = fs::read_to_string(&path).await.ok();
+            let decision = resolve_write(
+                on_disk.as_deref(),
+                previous_hashes.get(&path_str).map(String::as_str),
+                &rendered,
+                policy,
+                &stamp,
+            );
+            let resolution = match &decision {
+                WriteDecision::Write(_) => None,
+                WriteDecision::Merge(_) => Some("merged"),
+                WriteDecision::Refuse => Some("refused"),
+            };
+            if let Some(resolution) = resolution {
+                outcome.conflicts.push(VaultConflict {
+                    path: path_str.clone(),
+                    resolution: resolution.to_string(),
+                });
+            }
+            outcome.files.push(ExportedFile {
+                path: path_str,
+                content_hash: content_hash(&rendered),
+            });
+            writes.push((path, decision));
         }
 
-        if let Some(intel) = intelligence {
-            content.push_str("## AI Intelligence\n\n");
-            content.push_str(intel);
+        if outcome.refused() {
+            outcome.files.clear();
+            return Ok(outcome);
         }
 
-        content.push_str("## Transcript\n\n");
-        content.push_str(&transcript_md);
-
-        // Write main meeting file
-        fs::write(meeting_dir.join("meeting.md"), &content)
+        fs::create_dir_all(&meeting_dir)
             .await
             .map_err(|e| e.to_string())?;
 
-        // Write standalone transcript
-        if !transcript_md.is_empty() {
-            let transcript_file = format!(
-                "---\ntitle: \"Transcript - {}\"\ndate: \"{}\"\ntype: transcript\nmeeting_id: \"{}\"\n---\n\n# Transcript\n\n{}",
-                title, started_at, meeting_id, transcript_md
-            );
-            fs::write(meeting_dir.join("transcript.md"), &transcript_file)
-                .await
-                .map_err(|e| e.to_string())?;
+        // Write meeting note and standalone transcript
+        for (path, decision) in writes {
+            if let WriteDecision::Write(text) | WriteDecision::Merge(text) = decision {
+                fs::write(&path, text).await.map_err(|e| e.to_string())?;
+            }
         }
 
         // Copy screenshots
@@ -466,11 +589,7 @@ impl VaultManager {
             for (i, src_path) in screenshot_paths.iter().enumerate() {
                 let src = Path::new(src_path);
                 if src.exists() {
-                    let ext = src
-                        .extension()
-                        .map(|e| e.to_string_lossy().to_string())
-                        .unwrap_or_else(|| "png".to_string());
-                    let dest = screenshots_dir.join(format!("{:04}.{}", i + 1, ext));
+                    let dest = screenshots_dir.join(screenshot_file_name(i, src_path));
                     if let Err(e) = fs::copy(src, &dest).await {
                         log::warn!("Failed to copy screenshot {}: {}", src_path, e);
                     }
@@ -490,7 +609,7 @@ impl VaultManager {
             }
         }
 
-        Ok(meeting_dir.to_string_lossy().to_string())
+        Ok(outcome)
     }
 
     /// Read a file from the vault
@@ -663,24 +782,30 @@ impl VaultManager {
             .await
             .map_err(|e| e.to_string())?;
 
-        // Sanitize filename
-        let safe_name: String = name
-            .chars()
-            .map(|c| {
-                if c.is_alphanumeric() || c == ' ' || c == '-' {
-                    c
-                } else {
-                    '_'
-                }
-            })
-            .collect();
-        let file_path = people_dir.join(format!("{}.md", safe_name));
+        let file_path = people_dir.join(format!("{}.md", person_note_name(name)));
+
+        // Keep earlier meetings and the user's own notes when regenerating
+        let existing = read_existing_note(&file_path).await;
+        let mut links = existing
+            .as_ref()
+            .and_then(|n| n.section("Meetings"))
+            .map(|s| wikilinks_in(&s))
+            .unwrap_or_default();
+        for link in meeting_links {
+            if !links.contains(link) {
+                links.push(link.clone());
+            }
+        }
+        let my_notes = existing
+            .as_ref()
+            .and_then(|n| n.section(MY_NOTES_HEADING))
+            .unwrap_or_default();
 
         // Build meeting links section
-        let meetings_section = if meeting_links.is_empty() {
-            "*No meetings recorded yet*".to_string()
+        let meetings_section = if links.is_empty() {
+            NO_MEETINGS_PLACEHOLDER.to_string()
         } else {
-            meeting_links
+            links
                 .iter()
                 .map(|link| format!("- [[{}]]", link))
                 .collect::<Vec<_>>()
@@ -689,7 +814,7 @@ impl VaultManager {
 
         // Build the note content
         let content = format!(
-            "---\ntitle: \"{}\"\nemail: \"{}\"\ncompany: \"[[{}]]\"\ntype: person\ntags: [person, contact]\nlast_updated: \"{}\"\n---\n\n# {}\n\n📧 {} | 🏢 [[{}]]\n\n## Briefing\n\n{}\n\n## Meetings\n\n{}\n",
+            "---\ntitle: \"{}\"\nemail: \"{}\"\ncompany: \"[[{}]]\"\ntype: person\ntags: [person, contact]\nlast_updated: \"{}\"\n---\n\n# {}\n\n📧 {} | 🏢 [[{}]]\n\n## Briefing\n\n{}\n\n## Meetings\n\n{}\n\n## {}\n\n{}\n",
             name,
             email,
             company,
@@ -698,7 +823,9 @@ impl VaultManager {
             email,
             company,
             briefing,
-            meetings_section
+            meetings_section,
+            MY_NOTES_HEADING,
+            my_notes
         );
 
         fs::write(&file_path, &content)
@@ -722,24 +849,30 @@ impl VaultManager {
             .await
             .map_err(|e| e.to_string())?;
 
-        // Sanitize filename
-        let safe_name: String = company_name
-            .chars()
-            .map(|c| {
-                if c.is_alphanumeric() || c == ' ' || c == '-' {
-                    c
-                } else {
-                    '_'
-                }
-            })
-            .collect();
-        let file_path = companies_dir.join(format!("{}.md", safe_name));
+        let file_path = companies_dir.join(format!("{}.md", person_note_name(company_name)));
+
+        // Keep known contacts and the user's own notes when regenerating
+        let existing = read_existing_note(&file_path).await;
+        let mut people = existing
+            .as_ref()
+            .and_then(|n| n.section("People"))
+            .map(|s| wikilinks_in(&s))
+            .unwrap_or_default();
+        for person in people_names {
+            if !people.contains(person) {
+                people.push(person.clone());
+            }
+        }
+        let my_notes = existing
+            .as_ref()
+            .and_then(|n| n.section(MY_NOTES_HEADING))
+            .unwrap_or_default();
 
         // Build people section with wikilinks
-        let people_section = if people_names.is_empty() {
-            "*No contacts recorded yet*".to_string()
+        let people_section = if people.is_empty() {
+            NO_CONTACTS_PLACEHOLDER.to_string()
         } else {
-            people_names
+            people
                 .iter()
                 .map(|name| format!("- [[{}]]", name))
                 .collect::<Vec<_>>()
@@ -747,14 +880,16 @@ impl VaultManager {
         };
 
         let content = format!(
-            "---\ntitle: \"{}\"\ndomain: \"{}\"\ntype: company\ntags: [company]\nlast_updated: \"{}\"\n---\n\n# {}\n\n🌐 {}\n\n## Overview\n\n{}\n\n## People\n\n{}\n",
+            "---\ntitle: \"{}\"\ndomain: \"{}\"\ntype: company\ntags: [company]\nlast_updated: \"{}\"\n---\n\n# {}\n\n🌐 {}\n\n## Overview\n\n{}\n\n## People\n\n{}\n\n## {}\n\n{}\n",
             company_name,
             domain,
             Utc::now().format("%Y-%m-%d"),
             company_name,
             domain,
             briefing,
-            people_section
+            people_section,
+            MY_NOTES_HEADING,
+            my_notes
         );
 
         fs::write(&file_path, &content)
@@ -764,6 +899,75 @@ impl VaultManager {
         Ok(file_path.to_string_lossy().to_string())
     }
 
+    /// Existing note for a person, if one was written before
+    pub async fn read_person_note(&self, name: &str) -> Option<ExistingNote> {
+        let root = self.nofriction_root()?;
+        read_existing_note(
+            &root
+                .join("people")
+                .join(format!("{}.md", person_note_name(name))),
+        )
+        .await
+    }
+
+    /// Existing note for a company, if one was written before
+    pub async fn read_company_note(&self, company_name: &str) -> Option<ExistingNote> {
+        let root = self.nofriction_root()?;
+        read_existing_note(
+            &root
+                .join("companies")
+                .join(format!("{}.md", person_note_name(company_name))),
+        )
+        .await
+    }
+
+    /// Add a meeting to a fresh person note without regenerating it
+    pub async fn add_person_meeting(&self, name: &str, meeting_link: &str) -> Result<(), String> {
+        let note = self
+            .read_person_note(name)
+            .await
+            .ok_or_else(|| format!("No person note for {}", name))?;
+        let updated = add_list_item(
+            &note.content,
+            "Meetings",
+            &format!("- [[{}]]", meeting_link),
+            NO_MEETINGS_PLACEHOLDER,
+        );
+        if updated != note.content {
+            fs::write(&note.path, updated)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Add contacts to a fresh company note without regenerating it
+    pub async fn add_company_people(
+        &self,
+        company_name: &str,
+        people_names: &[String],
+    ) -> Result<(), String> {
+        let note = self
+            .read_company_note(company_name)
+            .await
+            .ok_or_else(|| format!("No company note for {}", company_name))?;
+        let mut updated = note.content.clone();
+        for name in people_names {
+            updated = add_list_item(
+                &updated,
+                "People",
+                &format!("- [[{}]]", name),
+                NO_CONTACTS_PLACEHOLDER,
+            );
+        }
+        if updated != note.content {
+            fs::write(&note.path, updated)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
     /// Write a meeting prep document under a topic
     pub async fn write_meeting_prep(
         &self,
@@ -807,6 +1011,61 @@ impl VaultManager {
         Ok(file_path.to_string_lossy().to_string())
     }
 
+    /// Write a digest note into Digests/ (overwrites the same period's note)
+    pub async fn write_digest(&self, file_name: &str, content: &str) -> Result<String, String> {
+        let root = self.nofriction_root().ok_or("Vault path not configured")?;
+        let digests_dir = root.join("Digests");
+        fs::create_dir_all(&digests_dir)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let file_path = digests_dir.join(file_name);
+        fs::write(&file_path, content)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(file_path.to_string_lossy().to_string())
+    }
+
+    /// Write a meeting comparison note into Sync Deltas/ (overwrites the same note)
+    pub async fn write_sync_delta(&self, file_name: &str, content: &str) -> Result<String, String> {
+        let root = self.nofriction_root().ok_or("Vault path not configured")?;
+        let deltas_dir = root.join("Sync Deltas");
+        fs::create_dir_all(&deltas_dir)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let file_path = deltas_dir.join(file_name);
+        fs::write(&file_path, content)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(file_path.to_string_lossy().to_string())
+    }
+
+    /// Write a study note into Study/ (overwrites the same note)
+    pub async fn write_study_note(&self, file_name: &str, content: &str) -> Result<String, String> {
+        let root = self.nofriction_root().ok_or("Vault path not configured")?;
+        let study_dir = root.join("Study");
+        fs::create_dir_all(&study_dir)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let file_path = study_dir.join(file_name);
+        fs::write(&file_path, content)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(file_path.to_string_lossy().to_string())
+    }
+
+    /// Whether a digest note was already written
+    pub fn digest_exists(&self, file_name: &str) -> bool {
+        self.nofriction_root()
+            .map(|root| root.join("Digests").join(file_name).exists())
+            .unwrap_or(false)
+    }
+
     // ═══════════════════════════════════════════════════════════════════
     // Obsidian-style Knowledge Management APIs
     // ═══════════════════════════════════════════════════════════════════
@@ -859,6 +1118,12 @@ impl VaultManager {
         })
     }
 
+    /// Backlinks to the person or company note for `name`
+    pub async fn get_entity_backlinks(&self, name: &str) -> Result<BacklinkResult, String> {
+        self.get_backlinks(&format!("{}.md", person_note_name(name)))
+            .await
+    }
+
     /// List all tags in the vault with file counts
     pub async fn list_tags(&self) -> Result<Vec<VaultTag>, String> {
         let vault = self.get_vault_path().ok_or("Vault path not configured")?;
@@ -956,6 +1221,110 @@ impl VaultManager {
 
 // ─── Helper Functions ──────────────────────────────────────────────
 
+/// File stem of a person note in people/ (also the wiki-link target)
+fn person_note_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == ' ' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// User-owned section of person/company notes, kept across regeneration
+pub const MY_NOTES_HEADING: &str = "My Notes";
+const NO_MEETINGS_PLACEHOLDER: &str = "*No meetings recorded yet*";
+const NO_CONTACTS_PLACEHOLDER: &str = "*No contacts recorded yet*";
+
+async fn read_existing_note(path: &Path) -> Option<ExistingNote> {
+    let content = fs::read_to_string(path).await.ok()?;
+    let (frontmatter, _) = parse_frontmatter(&content);
+    let generated = frontmatter
+        .get("last_updated")
+        .and_then(|v| v.as_str())
+        .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|d| d.and_utc());
+    let last_updated = match generated {
+        Some(t) => Some(t),
+        None => fs::metadata(path)
+            .await
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .map(DateTime::<Utc>::from),
+    };
+
+    Some(ExistingNote {
+        path: path.to_path_buf(),
+        content,
+        last_updated,
+    })
+}
+
+/// Byte range of the body of a `## heading` section (up to the next `## `)
+fn section_range(content: &str, heading: &str) -> Option<(usize, usize)> {
+    let marker = format!("## {}", heading);
+    let mut offset = 0;
+    let mut body_start = None;
+    for line in content.split_inclusive('\n') {
+        match body_start {
+            Some(start) if line.starts_with("## ") => return Some((start, offset)),
+            None if line.trim_end() == marker => body_start = Some(offset + line.len()),
+            _ => {}
+        }
+        offset += line.len();
+    }
+    body_start.map(|start| (start, content.len()))
+}
+
+fn section_body<'a>(content: &'a str, heading: &str) -> Option<&'a str> {
+    section_range(content, heading).map(|(start, end)| content[start..end].trim())
+}
+
+/// Targets of `- [[target]]` list items
+fn wikilinks_in(section: &str) -> Vec<String> {
+    section
+        .lines()
+        .filter_map(|line| {
+            line.trim()
+                .strip_prefix("- [[")
+                .and_then(|rest| rest.strip_suffix("]]"))
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+/// Append `item` to a section's list (creating the section if missing),
+/// dropping the empty-list placeholder; unchanged if already listed
+fn add_list_item(content: &str, heading: &str, item: &str, placeholder: &str) -> String {
+    match section_range(content, heading) {
+        Some((start, end)) => {
+            let mut lines: Vec<&str> = content[start..end]
+                .lines()
+                .filter(|line| !line.trim().is_empty() && line.trim() != placeholder)
+                .collect();
+            if lines.iter().any(|line| line.trim() == item) {
+                return content.to_string();
+            }
+            lines.push(item);
+
+            let mut out = content[..start].to_string();
+            out.push('\n');
+            out.push_str(&lines.join("\n"));
+            out.push('\n');
+            if end < content.len() {
+                out.push('\n');
+                out.push_str(&content[end..]);
+            }
+            out
+        }
+        None => format!("{}\n\n## {}\n\n{}\n", content.trim_end(), heading, item),
+    }
+}
+
 /// Parse YAML frontmatter from markdown content
 fn parse_frontmatter(content: &str) -> (serde_json::Value, String) {
     if !content.starts_with("---") {
@@ -1013,6 +1382,172 @@ async fn count_files_recursive(dir: &Path) -> Result<i32, String> {
     Ok(count)
 }
 
+/// SHA-256 of a note, as stored in `vault_exports`
+pub fn content_hash(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Compare an exported note on disk with the hash recorded when it was written
+pub async fn check_drift(path: &str, expected_hash: &str) -> Option<&'static str> {
+    match fs::read_to_string(path).await {
+        Err(_) => Some("missing"),
+        Ok(content) if content_hash(&content) != expected_hash => Some("modified"),
+        Ok(_) => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum WriteDecision {
+    Write(String),
+    Merge(String),
+    Refuse,
+}
+
+/// What to write over a note. Notes the app never recorded (exported before
+/// hashes were tracked) have no baseline and are overwritten as before.
+fn resolve_write(
+    on_disk: Option<&str>,
+    previous_hash: Option<&str>,
+    rendered: &str,
+    policy: VaultConflictPolicy,
+    stamp: &str,
+) -> WriteDecision {
+    let (on_disk, previous_hash) = match (on_disk, previous_hash) {
+        (Some(on_disk), Some(previous_hash)) => (on_disk, previous_hash),
+        _ => return WriteDecision::Write(rendered.to_string()),
+    };
+    if on_disk == rendered || content_hash(on_disk) == previous_hash {
+        return WriteDecision::Write(rendered.to_string());
+    }
+    match policy {
+        VaultConflictPolicy::Merge => {
+            WriteDecision::Merge(merge_reexport(on_disk, rendered, stamp))
+        }
+        VaultConflictPolicy::Refuse => WriteDecision::Refuse,
+    }
+}
+
+/// `## ` sections of a note body (frontmatter dropped); a body without
+/// sections is returned whole
+fn note_sections(note: &str) -> Vec<&str> {
+    let body = note
+        .strip_prefix("---\n")
+        .and_then(|rest| rest.find("\n---\n").map(|end| &rest[end + 5..]))
+        .unwrap_or(note);
+
+    let mut starts: Vec<usize> = body.match_indices("\n## ").map(|(i, _)| i + 1).collect();
+    if body.starts_with("## ") {
+        starts.insert(0, 0);
+    }
+    if starts.is_empty() {
+        return vec![body.trim()];
+    }
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = starts.get(i + 1).copied().unwrap_or(body.len());
+            body[start..end].trim()
+        })
+        .filter(|section| !section.is_empty())
+        .collect()
+}
+
+/// Keep the edited note and append the sections of the new export it doesn't
+/// already contain, demoted one level under a "Re-export" heading
+fn merge_reexport(existing: &str, rendered: &str, stamp: &str) -> String {
+    let new_sections: Vec<&str> = note_sections(rendered)
+        .into_iter()
+        .filter(|section| !existing.contains(section))
+        .collect();
+    if new_sections.is_empty() {
+        return existing.to_string();
+    }
+
+    let mut merged = format!("{}\n\n## Re-export {}\n\n", existing.trim_end(), stamp);
+    for section in new_sections {
+        for line in section.lines() {
+            if line.starts_with('#') && line.trim_start_matches('#').starts_with(' ') {
+                merged.push('#');
+            }
+            merged.push_str(line);
+            merged.push('\n');
+        }
+        merged.push('\n');
+    }
+    merged
+}
+
+/// Transcript as bold-timestamped paragraphs
+fn transcript_markdown(transcripts: &[(String, Option<String>, String)]) -> String {
+    let mut transcript_md = String::new();
+    for (text, speaker, timestamp) in transcripts {
+        let time_str = &timestamp[11..19]; // HH:MM:SS
+        match speaker {
+            Some(s) => transcript_md.push_str(&format!("**[{}] {}:** {}\n\n", time_str, s, text)),
+            None => transcript_md.push_str(&format!("**[{}]** {}\n\n", time_str, text)),
+        }
+    }
+    transcript_md
+}
+
+/// Name a screenshot gets inside the meeting's `screenshots/` folder
+fn screenshot_file_name(index: usize, src_path: &str) -> String {
+    let ext = Path::new(src_path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_string())
+        .unwrap_or_else(|| "png".to_string());
+    format!("{:04}.{}", index + 1, ext)
+}
+
+/// Render meeting.md without touching the vault (used by export and preview)
+pub fn render_meeting_note(meeting: &MeetingExport, template: &str) -> Result<String, String> {
+    let duration_str = meeting
+        .duration_secs
+        .map(|d| format!("{}m {}s", d / 60, d % 60))
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let attendees = meeting
+        .attendees
+        .iter()
+        .map(|email| {
+            let name = crate::attendee_intel::extract_name_from_email(email);
+            format!("- [[{}|{}]] ({})", person_note_name(&name), name, email)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let screenshots = meeting
+        .screenshot_paths
+        .iter()
+        .enumerate()
+        .map(|(i, path)| format!("![[screenshots/{}]]", screenshot_file_name(i, path)))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut values = std::collections::HashMap::new();
+    values.insert("title", meeting.title.clone());
+    values.insert("date", meeting.started_at.clone());
+    values.insert("meeting_id", meeting.meeting_id.clone());
+    values.insert("duration", duration_str);
+    values.insert("attendees", attendees);
+    values.insert("summary", meeting.summary.clone().unwrap_or_default());
+    values.insert("highlights", meeting.highlights.join("\n"));
+    values.insert("key_topics", meeting.key_topics.clone().unwrap_or_default());
+    values.insert(
+        "action_items",
+        meeting.action_items.clone().unwrap_or_default(),
+    );
+    values.insert("transcript", transcript_markdown(&meeting.transcripts));
+    values.insert("screenshots", screenshots);
+    meeting.intelligence.fill(&mut values);
+
+    crate::export_template::render(template, &values)
+}
+
 /// Collect files recursively into a flat list
 async fn collect_files(
     dir: &Path,
@@ -1380,3 +1915,88 @@ async fn collect_graph_edges(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAMP: &str = "2026-01-05 10:00 UTC";
+
+    #[test]
+    fn test_resolve_write_only_merges_edited_notes() {
+        let exported = "---\ntitle: \"Sync\"\n---\n\n# Sync\n\n## Summary\n\nOld.\n";
+        let rendered = "---\ntitle: \"Sync\"\n---\n\n# Sync\n\n## Summary\n\nNew.\n";
+        let hash = content_hash(exported);
+        let merge = VaultConflictPolicy::Merge;
+
+        // Untouched since the last export: overwrite
+        assert_eq!(
+            resolve_write(Some(exported), Some(&hash), rendered, merge, STAMP),
+            WriteDecision::Write(rendered.to_string())
+        );
+        // Never tracked: overwrite
+        assert_eq!(
+            resolve_write(Some(exported), None, rendered, merge, STAMP),
+            WriteDecision::Write(rendered.to_string())
+        );
+
+        let edited = format!("{}\nMy own notes.\n", exported);
+        assert_eq!(
+            resolve_write(
+                Some(&edited),
+                Some(&hash),
+                rendered,
+                VaultConflictPolicy::Refuse,
+                STAMP
+            ),
+            WriteDecision::Refuse
+        );
+        match resolve_write(Some(&edited), Some(&hash), rendered, merge, STAMP) {
+            WriteDecision::Merge(merged) => {
+                assert!(merged.starts_with(&edited));
+                assert!(merged
+                    .ends_with("## Re-export 2026-01-05 10:00 UTC\n\n### Summary\n\nNew.\n\n"));
+            }
+            other => panic!("expected merge, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_add_list_item_keeps_my_notes() {
+        let note = "# Dana\n\n## Meetings\n\n*No meetings recorded yet*\n\n## My Notes\n\nPrefers async.\n";
+        let updated = add_list_item(
+            note,
+            "Meetings",
+            "- [[Weekly sync]]",
+            NO_MEETINGS_PLACEHOLDER,
+        );
+        assert_eq!(
+            updated,
+            "# Dana\n\n## Meetings\n\n- [[Weekly sync]]\n\n## My Notes\n\nPrefers async.\n"
+        );
+        assert_eq!(
+            add_list_item(
+                &updated,
+                "Meetings",
+                "- [[Weekly sync]]",
+                NO_MEETINGS_PLACEHOLDER
+            ),
+            updated
+        );
+        assert_eq!(
+            section_body(&updated, MY_NOTES_HEADING),
+            Some("Prefers async.")
+        );
+        assert_eq!(
+            wikilinks_in(section_body(&updated, "Meetings").unwrap()),
+            vec!["Weekly sync".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_merge_skips_sections_already_present() {
+        let existing = "# Sync\n\n## Summary\n\nSame.\n\nMy own notes.\n";
+        let rendered = "---\nx: 1\n---\n\n# Sync\n\n## Summary\n\nSame.\n";
+        assert_eq!(merge_reexport(existing, rendered, STAMP), existing);
+    }
+}
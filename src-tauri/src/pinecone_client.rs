@@ -2,16 +2,133 @@
 //!
 //! Uses Pinecone's integrated embedding (llama-text-embed-v2) for auto-embedding.
 
+use crate::usage_ledger::{estimate_tokens, UsageCall};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Max ids per delete request
+const DELETE_BATCH_SIZE: usize = 1000;
+/// Provider name in the usage ledger
+const USAGE_PROVIDER: &str = "pinecone";
+
+/// Kind of record stored in Pinecone; each kind lives in its own namespace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorKind {
+    Transcript,
+    Activity,
+    Conversation,
+    Snapshot,
+}
+
+impl VectorKind {
+    pub const ALL: [VectorKind; 4] = [
+        VectorKind::Transcript,
+        VectorKind::Activity,
+        VectorKind::Conversation,
+        VectorKind::Snapshot,
+    ];
+
+    /// Plural label, also the suffix of the derived namespace
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Transcript => "transcripts",
+            Self::Activity => "activities",
+            Self::Conversation => "conversations",
+            Self::Snapshot => "snapshots",
+        }
+    }
+}
+
+/// Namespace per record kind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PineconeNamespaces {
+    pub transcripts: String,
+    pub activities: String,
+    pub conversations: String,
+    pub snapshots: String,
+}
+
+impl PineconeNamespaces {
+    /// `<base>-transcripts`, `<base>-activities`, ...
+    pub fn derived(base: &str) -> Self {
+        Self {
+            transcripts: format!("{}-{}", base, VectorKind::Transcript.as_str()),
+            activities: format!("{}-{}", base, VectorKind::Activity.as_str()),
+            conversations: format!("{}-{}", base, VectorKind::Conversation.as_str()),
+            snapshots: format!("{}-{}", base, VectorKind::Snapshot.as_str()),
+        }
+    }
+
+    /// Namespaces from settings, derived from the base namespace where unset
+    pub fn from_settings(settings: &crate::settings::AppSettings) -> Self {
+        let base = settings
+            .pinecone_namespace
+            .as_deref()
+            .filter(|ns| !ns.is_empty())
+            .unwrap_or("default");
+        let derived = Self::derived(base);
+        let pick = |value: &Option<String>, fallback: String| {
+            value
+                .clone()
+                .filter(|ns| !ns.is_empty())
+                .unwrap_or(fallback)
+        };
+        Self {
+            transcripts: pick(
+                &settings.pinecone_namespace_transcripts,
+                derived.transcripts,
+            ),
+            activities: pick(&settings.pinecone_namespace_activities, derived.activities),
+            conversations: pick(
+                &settings.pinecone_namespace_conversations,
+                derived.conversations,
+            ),
+            snapshots: pick(&settings.pinecone_namespace_snapshots, derived.snapshots),
+        }
+    }
+
+    pub fn get(&self, kind: VectorKind) -> &str {
+        match kind {
+            VectorKind::Transcript => &self.transcripts,
+            VectorKind::Activity => &self.activities,
+            VectorKind::Conversation => &self.conversations,
+            VectorKind::Snapshot => &self.snapshots,
+        }
+    }
+}
+
 /// Configuration for Pinecone
 #[derive(Debug, Clone)]
 pub struct PineconeConfig {
     pub api_key: String,
     pub index_host: String,
+    /// Shared namespace everything was written to before the per-kind split;
+    /// still searched and cleaned up so older vectors aren't lost
     pub namespace: Option<String>,
+    pub namespaces: PineconeNamespaces,
+}
+
+impl PineconeConfig {
+    pub fn base_namespace(&self) -> &str {
+        self.namespace.as_deref().unwrap_or("default")
+    }
+
+    pub fn namespace_for(&self, kind: VectorKind) -> &str {
+        self.namespaces.get(kind)
+    }
+
+    /// Every namespace records may live in: the per-kind ones plus the shared one
+    pub fn all_namespaces(&self) -> Vec<&str> {
+        let mut namespaces: Vec<&str> = VectorKind::ALL
+            .iter()
+            .map(|kind| self.namespace_for(*kind))
+            .collect();
+        namespaces.push(self.base_namespace());
+        namespaces.sort_unstable();
+        namespaces.dedup();
+        namespaces
+    }
 }
 
 /// Vector match result from Pinecone
@@ -45,11 +162,18 @@ impl PineconeClient {
     }
 
     /// Configure the client
-    pub fn configure(&self, api_key: String, index_host: String, namespace: Option<String>) {
+    pub fn configure(
+        &self,
+        api_key: String,
+        index_host: String,
+        namespace: Option<String>,
+        namespaces: PineconeNamespaces,
+    ) {
         *self.config.write() = Some(PineconeConfig {
             api_key,
             index_host,
             namespace,
+            namespaces,
         });
     }
 
@@ -80,7 +204,7 @@ impl PineconeClient {
         let url = format!(
             "{}/records/namespaces/{}/upsert",
             config.index_host,
-            config.namespace.as_deref().unwrap_or("default")
+            config.namespace_for(VectorKind::Activity)
         );
 
         // Use Pinecone's integrated embedding API
@@ -96,20 +220,7 @@ impl PineconeClient {
             }]
         });
 
-        let client = reqwest::Client::new();
-        let resp = client
-            .post(&url)
-            .header("Api-Key", &config.api_key)
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to upsert to Pinecone: {}", e))?;
-
-        if !resp.status().is_success() {
-            let error_text = resp.text().await.unwrap_or_default();
-            return Err(format!("Pinecone upsert failed: {}", error_text));
-        }
+        post_upsert(&config, &url, &request_body, text).await?;
 
         log::info!("📌 Vector upserted to Pinecone: {}", id);
         Ok(())
@@ -122,106 +233,17 @@ impl PineconeClient {
             .read()
             .clone()
             .ok_or("Pinecone not configured")?;
-
-        let url = format!(
-            "{}/records/namespaces/{}/search",
-            config.index_host,
-            config.namespace.as_deref().unwrap_or("default")
-        );
-
-        let request_body = serde_json::json!({
-            "query": {
-                "top_k": top_k,
-                "inputs": {
-                    "text": query
-                }
-            },
-            "fields": ["category", "app_name", "focus_area", "summary", "timestamp"]
-        });
-
-        let client = reqwest::Client::new();
-        let resp = client
-            .post(&url)
-            .header("Api-Key", &config.api_key)
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to search Pinecone: {}", e))?;
-
-        if !resp.status().is_success() {
-            let error_text = resp.text().await.unwrap_or_default();
-            return Err(format!("Pinecone search failed: {}", error_text));
-        }
-
-        #[derive(Deserialize)]
-        struct SearchResponse {
-            result: Option<SearchResult>,
-        }
-
-        #[derive(Deserialize)]
-        struct SearchResult {
-            hits: Option<Vec<Hit>>,
-        }
-
-        #[derive(Deserialize)]
-        struct Hit {
-            _id: String,
-            _score: Option<f32>,
-            fields: Option<serde_json::Value>,
-        }
-
-        let search_resp: SearchResponse = resp
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse Pinecone response: {}", e))?;
-
-        let matches = search_resp
-            .result
-            .and_then(|r| r.hits)
-            .unwrap_or_default()
-            .into_iter()
-            .map(|hit| VectorMatch {
-                id: hit._id,
-                score: hit._score.unwrap_or(0.0),
-                metadata: hit.fields,
-            })
-            .collect();
-
-        Ok(matches)
+        pinecone_search(&config, query, top_k).await
     }
 
-    /// Delete vectors by ID
-    pub async fn delete(&self, ids: &[String]) -> Result<(), String> {
+    /// Delete vectors of one kind by ID
+    pub async fn delete(&self, kind: VectorKind, ids: &[String]) -> Result<(), String> {
         let config = self
             .config
             .read()
             .clone()
             .ok_or("Pinecone not configured")?;
-
-        let url = format!("{}/vectors/delete", config.index_host);
-
-        let request_body = serde_json::json!({
-            "ids": ids,
-            "namespace": config.namespace.as_deref().unwrap_or("default")
-        });
-
-        let client = reqwest::Client::new();
-        let resp = client
-            .post(&url)
-            .header("Api-Key", &config.api_key)
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to delete from Pinecone: {}", e))?;
-
-        if !resp.status().is_success() {
-            let error_text = resp.text().await.unwrap_or_default();
-            return Err(format!("Pinecone delete failed: {}", error_text));
-        }
-
-        Ok(())
+        pinecone_delete_by_ids(&config, config.namespace_for(kind), ids).await
     }
 
     /// Get index stats
@@ -265,16 +287,53 @@ impl Default for PineconeClient {
 // Standalone async functions (avoid RwLock guard issues)
 // ============================================
 
-/// Search Pinecone with provided config (no guard held)
+/// Search every namespace with provided config (no guard held) and merge the
+/// hits by score. Fails only if no namespace could be searched.
 pub async fn pinecone_search(
     config: &PineconeConfig,
     query: &str,
     top_k: u32,
+) -> Result<Vec<VectorMatch>, String> {
+    let namespaces = config.all_namespaces();
+    let results = futures_util::future::join_all(
+        namespaces
+            .iter()
+            .map(|ns| pinecone_search_namespace(config, ns, query, top_k)),
+    )
+    .await;
+
+    let mut matches = Vec::new();
+    let mut last_error = None;
+    for (ns, result) in namespaces.iter().zip(results) {
+        match result {
+            Ok(hits) => matches.extend(hits),
+            Err(e) => {
+                log::warn!("Pinecone search in namespace '{}' failed: {}", ns, e);
+                last_error = Some(e);
+            }
+        }
+    }
+    if matches.is_empty() {
+        if let Some(e) = last_error {
+            return Err(e);
+        }
+    }
+
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+    matches.truncate(top_k as usize);
+    Ok(matches)
+}
+
+/// Search a single namespace
+pub async fn pinecone_search_namespace(
+    config: &PineconeConfig,
+    namespace: &str,
+    query: &str,
+    top_k: u32,
 ) -> Result<Vec<VectorMatch>, String> {
     let url = format!(
         "{}/records/namespaces/{}/search",
-        config.index_host,
-        config.namespace.as_deref().unwrap_or("default")
+        config.index_host, namespace
     );
 
     let request_body = serde_json::json!({
@@ -284,23 +343,30 @@ pub async fn pinecone_search(
                 "text": query
             }
         },
-        "fields": ["category", "app_name", "focus_area", "summary", "timestamp"]
+        "fields": ["category", "app_name", "focus_area", "summary", "timestamp", "type", "text", "meeting_id", "transcript_id"]
     });
 
-    let client = reqwest::Client::new();
-    let resp = client
-        .post(&url)
-        .header("Api-Key", &config.api_key)
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to search Pinecone: {}", e))?;
+    // The query text is embedded server-side
+    let call = UsageCall::start(USAGE_PROVIDER, "search", EMBED_MODEL);
+    let resp = async {
+        let resp = reqwest::Client::new()
+            .post(&url)
+            .header("Api-Key", &config.api_key)
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to search Pinecone: {}", e))?;
 
-    if !resp.status().is_success() {
-        let error_text = resp.text().await.unwrap_or_default();
-        return Err(format!("Pinecone search failed: {}", error_text));
+        if !resp.status().is_success() {
+            let error_text = resp.text().await.unwrap_or_default();
+            return Err(format!("Pinecone search failed: {}", error_text));
+        }
+        Ok::<_, String>(resp)
     }
+    .await;
+    call.finish(estimate_tokens(query), 0, resp.is_ok());
+    let resp = resp?;
 
     #[derive(serde::Deserialize)]
     struct SearchResponse {
@@ -366,6 +432,7 @@ pub async fn pinecone_stats(config: &PineconeConfig) -> Result<serde_json::Value
 /// Upsert to Pinecone with provided config (no guard held)
 pub async fn pinecone_upsert(
     config: &PineconeConfig,
+    kind: VectorKind,
     id: &str,
     text: &str,
     metadata: &ActivityMetadata,
@@ -373,7 +440,7 @@ pub async fn pinecone_upsert(
     let url = format!(
         "{}/records/namespaces/{}/upsert",
         config.index_host,
-        config.namespace.as_deref().unwrap_or("default")
+        config.namespace_for(kind)
     );
 
     let request_body = serde_json::json!({
@@ -388,29 +455,47 @@ pub async fn pinecone_upsert(
         }]
     });
 
-    let client = reqwest::Client::new();
-    let resp = client
-        .post(&url)
-        .header("Api-Key", &config.api_key)
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to upsert to Pinecone: {}", e))?;
-
-    if !resp.status().is_success() {
-        let error_text = resp.text().await.unwrap_or_default();
-        return Err(format!("Pinecone upsert failed: {}", error_text));
-    }
+    post_upsert(config, &url, &request_body, text).await?;
 
     log::info!("📌 Vector upserted to Pinecone: {}", id);
     Ok(())
 }
 
+/// POST records to an upsert endpoint. Pinecone embeds `text` server-side, so
+/// the call is recorded as embedding usage.
+async fn post_upsert(
+    config: &PineconeConfig,
+    url: &str,
+    request_body: &serde_json::Value,
+    text: &str,
+) -> Result<(), String> {
+    let call = UsageCall::start(USAGE_PROVIDER, "embedding", EMBED_MODEL);
+    let result = async {
+        let resp = reqwest::Client::new()
+            .post(url)
+            .header("Api-Key", &config.api_key)
+            .header("Content-Type", "application/json")
+            .json(request_body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to upsert to Pinecone: {}", e))?;
+
+        if !resp.status().is_success() {
+            let error_text = resp.text().await.unwrap_or_default();
+            return Err(format!("Pinecone upsert failed: {}", error_text));
+        }
+        Ok::<_, String>(())
+    }
+    .await;
+    call.finish(estimate_tokens(text), 0, result.is_ok());
+    result
+}
+
 /// Generic upsert to Pinecone with provided config (no guard held)
 /// Allows passing arbitrary metadata as serde_json::Value
 pub async fn pinecone_upsert_generic(
     config: &PineconeConfig,
+    kind: VectorKind,
     id: &str,
     text: &str,
     metadata: &serde_json::Value,
@@ -418,7 +503,7 @@ pub async fn pinecone_upsert_generic(
     let url = format!(
         "{}/records/namespaces/{}/upsert",
         config.index_host,
-        config.namespace.as_deref().unwrap_or("default")
+        config.namespace_for(kind)
     );
 
     // Construct record with text and ID
@@ -440,21 +525,322 @@ pub async fn pinecone_upsert_generic(
         "records": [serde_json::Value::Object(record)]
     });
 
+    post_upsert(config, &url, &request_body, text).await?;
+
+    log::info!("📌 Generic vector upserted to Pinecone: {}", id);
+    Ok(())
+}
+
+/// Delete vectors by ID from one namespace (no guard held)
+pub async fn pinecone_delete_by_ids(
+    config: &PineconeConfig,
+    namespace: &str,
+    ids: &[String],
+) -> Result<(), String> {
     let client = reqwest::Client::new();
+    for batch in ids.chunks(DELETE_BATCH_SIZE) {
+        let request_body = serde_json::json!({
+            "ids": batch,
+            "namespace": namespace
+        });
+        pinecone_delete_request(&client, config, &request_body).await?;
+    }
+    Ok(())
+}
+
+/// Delete vectors matching a metadata filter from one namespace (no guard held).
+/// Not every index type supports this; callers fall back to deleting by id.
+pub async fn pinecone_delete_by_filter(
+    config: &PineconeConfig,
+    namespace: &str,
+    filter: &serde_json::Value,
+) -> Result<(), String> {
+    let request_body = serde_json::json!({
+        "filter": filter,
+        "namespace": namespace
+    });
+    pinecone_delete_request(&reqwest::Client::new(), config, &request_body).await
+}
+
+async fn pinecone_delete_request(
+    client: &reqwest::Client,
+    config: &PineconeConfig,
+    request_body: &serde_json::Value,
+) -> Result<(), String> {
     let resp = client
-        .post(&url)
+        .post(format!("{}/vectors/delete", config.index_host))
         .header("Api-Key", &config.api_key)
         .header("Content-Type", "application/json")
+        .json(request_body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to delete from Pinecone: {}", e))?;
+
+    if !resp.status().is_success() {
+        let error_text = resp.text().await.unwrap_or_default();
+        return Err(format!("Pinecone delete failed: {}", error_text));
+    }
+    Ok(())
+}
+
+/// IDs in a namespace starting with `prefix`, following pagination (no guard held)
+pub async fn pinecone_list_ids(
+    config: &PineconeConfig,
+    namespace: &str,
+    prefix: &str,
+) -> Result<Vec<String>, String> {
+    #[derive(serde::Deserialize)]
+    struct ListResponse {
+        vectors: Option<Vec<ListedVector>>,
+        pagination: Option<Pagination>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct ListedVector {
+        id: String,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Pagination {
+        next: Option<String>,
+    }
+
+    let client = reqwest::Client::new();
+    let url = format!("{}/vectors/list", config.index_host);
+    let mut ids = Vec::new();
+    let mut token: Option<String> = None;
+
+    loop {
+        let mut query = vec![
+            ("namespace", namespace),
+            ("prefix", prefix),
+            ("limit", "100"),
+        ];
+        if let Some(ref token) = token {
+            query.push(("paginationToken", token.as_str()));
+        }
+
+        let resp = client
+            .get(&url)
+            .header("Api-Key", &config.api_key)
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list Pinecone vectors: {}", e))?;
+
+        if !resp.status().is_success() {
+            let error_text = resp.text().await.unwrap_or_default();
+            return Err(format!("Pinecone list failed: {}", error_text));
+        }
+
+        let page: ListResponse = resp
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Pinecone list: {}", e))?;
+        ids.extend(page.vectors.unwrap_or_default().into_iter().map(|v| v.id));
+
+        match page.pagination.and_then(|p| p.next) {
+            Some(next) => token = Some(next),
+            None => break,
+        }
+    }
+
+    Ok(ids)
+}
+
+/// Delete every vector belonging to a meeting. Tries a `meeting_id` metadata
+/// filter per namespace and falls back to deleting transcript vectors by id
+/// prefix where filtered deletes aren't supported.
+pub async fn pinecone_delete_meeting(
+    config: &PineconeConfig,
+    meeting_id: &str,
+) -> Result<(), String> {
+    let filter = serde_json::json!({ "meeting_id": { "$eq": meeting_id } });
+    let prefix = format!("transcript_{}_", meeting_id);
+    let mut errors = Vec::new();
+
+    for namespace in config.all_namespaces() {
+        if let Err(filter_err) = pinecone_delete_by_filter(config, namespace, &filter).await {
+            log::debug!(
+                "Filtered delete unavailable in '{}', deleting by id: {}",
+                namespace,
+                filter_err
+            );
+            let result = match pinecone_list_ids(config, namespace, &prefix).await {
+                Ok(ids) => pinecone_delete_by_ids(config, namespace, &ids).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                errors.push(format!("{}: {}", namespace, e));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Vector counts per record kind from `describe_index_stats` output. The shared
+/// pre-split namespace is reported as "legacy".
+pub fn namespace_breakdown(
+    config: &PineconeConfig,
+    stats: &serde_json::Value,
+) -> serde_json::Value {
+    let count = |namespace: &str| {
+        stats
+            .get("namespaces")
+            .and_then(|n| n.get(namespace))
+            .and_then(|n| n.get("vectorCount"))
+            .and_then(|c| c.as_u64())
+            .unwrap_or(0)
+    };
+
+    let mut breakdown = serde_json::Map::new();
+    for kind in VectorKind::ALL {
+        let namespace = config.namespace_for(kind);
+        breakdown.insert(
+            kind.as_str().to_string(),
+            serde_json::json!({ "namespace": namespace, "vector_count": count(namespace) }),
+        );
+    }
+    let base = config.base_namespace();
+    breakdown.insert(
+        "legacy".to_string(),
+        serde_json::json!({ "namespace": base, "vector_count": count(base) }),
+    );
+    serde_json::Value::Object(breakdown)
+}
+
+/// Hosted embedding model (same one the index embeds records with)
+pub const EMBED_MODEL: &str = "llama-text-embed-v2";
+/// Max inputs per embed request
+const EMBED_BATCH_SIZE: usize = 96;
+
+/// Embed passages with Pinecone Inference, one vector per input (no guard held)
+pub async fn pinecone_embed(
+    config: &PineconeConfig,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>, String> {
+    let client = reqwest::Client::new();
+    let mut vectors = Vec::with_capacity(texts.len());
+
+    for batch in texts.chunks(EMBED_BATCH_SIZE) {
+        let call = UsageCall::start(USAGE_PROVIDER, "embedding", EMBED_MODEL);
+        let estimated: u64 = batch.iter().map(|t| estimate_tokens(t)).sum();
+        let result = pinecone_embed_batch(&client, config, batch).await;
+        match &result {
+            Ok(resp) => call.finish(
+                resp.usage
+                    .as_ref()
+                    .and_then(|u| u.total_tokens)
+                    .unwrap_or(estimated),
+                0,
+                true,
+            ),
+            Err(_) => call.finish(estimated, 0, false),
+        }
+        let embed_resp = result?;
+
+        if embed_resp.data.len() != batch.len() {
+            return Err(format!(
+                "Pinecone returned {} embeddings for {} inputs",
+                embed_resp.data.len(),
+                batch.len()
+            ));
+        }
+        vectors.extend(embed_resp.data.into_iter().map(|e| e.values));
+    }
+
+    Ok(vectors)
+}
+
+#[derive(serde::Deserialize)]
+struct EmbedResponse {
+    data: Vec<Embedding>,
+    usage: Option<EmbedUsage>,
+}
+
+#[derive(serde::Deserialize)]
+struct Embedding {
+    values: Vec<f32>,
+}
+
+#[derive(serde::Deserialize)]
+struct EmbedUsage {
+    total_tokens: Option<u64>,
+}
+
+async fn pinecone_embed_batch(
+    client: &reqwest::Client,
+    config: &PineconeConfig,
+    batch: &[String],
+) -> Result<EmbedResponse, String> {
+    let request_body = serde_json::json!({
+        "model": EMBED_MODEL,
+        "parameters": { "input_type": "passage", "truncate": "END" },
+        "inputs": batch.iter().map(|t| serde_json::json!({ "text": t })).collect::<Vec<_>>()
+    });
+
+    let resp = client
+        .post("https://api.pinecone.io/embed")
+        .header("Api-Key", &config.api_key)
+        .header("X-Pinecone-API-Version", "2025-01")
+        .header("Content-Type", "application/json")
         .json(&request_body)
         .send()
         .await
-        .map_err(|e| format!("Failed to upsert to Pinecone: {}", e))?;
+        .map_err(|e| format!("Failed to embed with Pinecone: {}", e))?;
 
     if !resp.status().is_success() {
         let error_text = resp.text().await.unwrap_or_default();
-        return Err(format!("Pinecone upsert failed: {}", error_text));
+        return Err(format!("Pinecone embed failed: {}", error_text));
     }
 
-    log::info!("📌 Generic vector upserted to Pinecone: {}", id);
-    Ok(())
+    resp.json()
+        .await
+        .map_err(|e| format!("Failed to parse Pinecone embeddings: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(namespace: Option<&str>) -> PineconeConfig {
+        PineconeConfig {
+            api_key: "key".to_string(),
+            index_host: "https://index".to_string(),
+            namespace: namespace.map(str::to_string),
+            namespaces: PineconeNamespaces::derived(namespace.unwrap_or("default")),
+        }
+    }
+
+    #[test]
+    fn test_namespaces_derive_from_base() {
+        let config = config(Some("work"));
+        assert_eq!(
+            config.namespace_for(VectorKind::Transcript),
+            "work-transcripts"
+        );
+        assert_eq!(config.namespace_for(VectorKind::Snapshot), "work-snapshots");
+        assert_eq!(config.all_namespaces().len(), 5);
+        assert!(config.all_namespaces().contains(&"work"));
+    }
+
+    #[test]
+    fn test_namespace_breakdown() {
+        let config = config(None);
+        let stats = serde_json::json!({
+            "namespaces": {
+                "default": { "vectorCount": 7 },
+                "default-transcripts": { "vectorCount": 3 }
+            }
+        });
+        let breakdown = namespace_breakdown(&config, &stats);
+        assert_eq!(breakdown["transcripts"]["vector_count"], 3);
+        assert_eq!(breakdown["activities"]["vector_count"], 0);
+        assert_eq!(breakdown["legacy"]["vector_count"], 7);
+    }
 }
@@ -0,0 +1,432 @@
+// noFriction Meetings - Playback Manifest
+// Joins video chunks, transcripts, screen states, episodes, timeline events
+// and pins into one structure keyed by offset from the meeting start
+//
+// Every source is anchored to meeting.started_at. Sources stamped by another
+// clock can run slightly ahead of the meeting row (the recorder or provider
+// starts before the row is written, or its clock is skewed); a source whose
+// earliest item lands before the meeting start is shifted by a per-source
+// correction so it begins at offset 0. Video chunk boundaries come from chunk
+// durations rather than wall times, so they stay contiguous.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::database::{
+    DocumentEpisodeRecord, PinnedInsight, ScreenStateRecord, TimelineEventRecord, Transcript,
+};
+use crate::video_recorder::{PinMoment, VideoChunk};
+
+/// Correction (ms) added to each source's raw offsets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceOffsets {
+    pub video_ms: i64,
+    pub transcripts_ms: i64,
+    pub screen_ms: i64,
+    pub pins_ms: i64,
+}
+
+/// One video chunk's span on the meeting timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkSpan {
+    pub chunk_number: u32,
+    pub path: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackEntryKind {
+    Transcript,
+    ScreenState,
+    Episode,
+    TimelineEvent,
+    Pin,
+}
+
+/// Something active on the timeline from `start_ms` until `end_ms`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaybackEntry {
+    pub kind: PlaybackEntryKind,
+    /// Transcript id, state_id, episode_id, event_id or pin id
+    pub ref_id: String,
+    pub start_ms: i64,
+    /// None for instants (pins) and for open-ended items
+    pub end_ms: Option<i64>,
+    pub text: String,
+    /// Speaker, app name or event/insight type
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaybackManifest {
+    pub meeting_id: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: i64,
+    pub offsets: SourceOffsets,
+    pub chunks: Vec<ChunkSpan>,
+    /// `start_ms` of each chunk, ascending (binary-search this)
+    pub chunk_boundaries_ms: Vec<i64>,
+    /// All entries sorted by `start_ms`
+    pub entries: Vec<PlaybackEntry>,
+}
+
+/// What's playing at one offset
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlaybackPosition {
+    pub chunk_number: Option<u32>,
+    pub chunk_offset_secs: f64,
+    pub transcript_id: Option<String>,
+    pub state_id: Option<String>,
+    pub episode_id: Option<String>,
+}
+
+/// Raw rows for one meeting
+pub struct PlaybackSources<'a> {
+    pub meeting_id: &'a str,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub chunks: &'a [VideoChunk],
+    pub transcripts: &'a [Transcript],
+    pub screen_states: &'a [ScreenStateRecord],
+    pub episodes: &'a [DocumentEpisodeRecord],
+    pub timeline_events: &'a [TimelineEventRecord],
+    pub pinned_insights: &'a [PinnedInsight],
+    /// Pins from a video recording still in progress
+    pub video_pins: &'a [PinMoment],
+}
+
+fn parse_ts(ts: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(ts)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Shift that moves a source's earliest raw offset up to 0 (never negative)
+fn correction(raw_offsets: impl Iterator<Item = i64>) -> i64 {
+    raw_offsets.min().map_or(0, |earliest| (-earliest).max(0))
+}
+
+/// Lay chunks end to end from the first chunk's (corrected) start
+fn chunk_spans(
+    chunks: &[VideoChunk],
+    started_at: DateTime<Utc>,
+    correction: i64,
+) -> Vec<ChunkSpan> {
+    let mut sorted: Vec<&VideoChunk> = chunks.iter().collect();
+    sorted.sort_by_key(|c| c.chunk_number);
+
+    let mut spans: Vec<ChunkSpan> = Vec::with_capacity(sorted.len());
+    for (i, chunk) in sorted.iter().enumerate() {
+        let start_ms = match spans.last() {
+            Some(prev) => prev.end_ms,
+            None => (chunk.start_time - started_at).num_milliseconds() + correction,
+        };
+        let wall_ms = |end: DateTime<Utc>| (end - chunk.start_time).num_milliseconds();
+        let length_ms = if chunk.duration_secs > 0.0 {
+            (chunk.duration_secs * 1000.0) as i64
+        } else if let Some(end) = chunk.end_time {
+            wall_ms(end)
+        } else if let Some(next) = sorted.get(i + 1) {
+            wall_ms(next.start_time)
+        } else {
+            0
+        };
+
+        spans.push(ChunkSpan {
+            chunk_number: chunk.chunk_number,
+            path: chunk.path.to_string_lossy().to_string(),
+            start_ms,
+            end_ms: start_ms + length_ms.max(0),
+        });
+    }
+    spans
+}
+
+/// Build the manifest from one meeting's rows
+pub fn build_manifest(src: &PlaybackSources) -> PlaybackManifest {
+    let rel = |ts: DateTime<Utc>| (ts - src.started_at).num_milliseconds();
+    let rel_str = |ts: &str| parse_ts(ts).map(rel);
+
+    let mut transcripts: Vec<&Transcript> = src.transcripts.iter().filter(|t| t.is_final).collect();
+    transcripts.sort_by_key(|t| t.timestamp);
+
+    // Screen states, episodes and timeline events all come from the capture clock
+    let screen_raw = src
+        .screen_states
+        .iter()
+        .filter_map(|s| rel_str(&s.start_ts))
+        .chain(src.episodes.iter().filter_map(|e| rel_str(&e.start_ts)))
+        .chain(src.timeline_events.iter().filter_map(|e| rel_str(&e.ts)));
+
+    let offsets = SourceOffsets {
+        video_ms: correction(src.chunks.iter().map(|c| rel(c.start_time))),
+        transcripts_ms: correction(transcripts.iter().map(|t| rel(t.timestamp))),
+        screen_ms: correction(screen_raw),
+        pins_ms: correction(
+            src.pinned_insights
+                .iter()
+                .map(|p| p.timestamp_ms - src.started_at.timestamp_millis())
+                .chain(src.video_pins.iter().map(|p| rel(p.timestamp))),
+        ),
+    };
+
+    let chunks = chunk_spans(src.chunks, src.started_at, offsets.video_ms);
+    let mut entries = Vec::new();
+
+    // A transcript line stays active until the next one starts
+    for (i, t) in transcripts.iter().enumerate() {
+        let start_ms = rel(t.timestamp) + offsets.transcripts_ms;
+        entries.push(PlaybackEntry {
+            kind: PlaybackEntryKind::Transcript,
+            ref_id: t.id.to_string(),
+            start_ms,
+            end_ms: transcripts
+                .get(i + 1)
+                .map(|next| (rel(next.timestamp) + offsets.transcripts_ms).max(start_ms)),
+            text: t.text.clone(),
+            detail: t.speaker.clone(),
+        });
+    }
+
+    let screen = |ts: &str| rel_str(ts).map(|ms| ms + offsets.screen_ms);
+    for s in src.screen_states {
+        let Some(start_ms) = screen(&s.start_ts) else {
+            continue;
+        };
+        entries.push(PlaybackEntry {
+            kind: PlaybackEntryKind::ScreenState,
+            ref_id: s.state_id.clone(),
+            start_ms,
+            end_ms: s.end_ts.as_deref().and_then(screen),
+            text: s.window_title.clone().unwrap_or_default(),
+            detail: s.app_name.clone(),
+        });
+    }
+    for e in src.episodes {
+        let Some(start_ms) = screen(&e.start_ts) else {
+            continue;
+        };
+        entries.push(PlaybackEntry {
+            kind: PlaybackEntryKind::Episode,
+            ref_id: e.episode_id.clone(),
+            start_ms,
+            end_ms: e.end_ts.as_deref().and_then(screen),
+            text: e
+                .summary
+                .clone()
+                .or_else(|| e.window_title.clone())
+                .unwrap_or_default(),
+            detail: e.app_name.clone(),
+        });
+    }
+    for e in src.timeline_events {
+        let Some(start_ms) = screen(&e.ts) else {
+            continue;
+        };
+        entries.push(PlaybackEntry {
+            kind: PlaybackEntryKind::TimelineEvent,
+            ref_id: e.event_id.clone(),
+            start_ms,
+            end_ms: e.duration_ms.map(|d| start_ms + d),
+            text: e.title.clone(),
+            detail: Some(e.event_type.clone()),
+        });
+    }
+
+    for p in src.pinned_insights {
+        entries.push(PlaybackEntry {
+            kind: PlaybackEntryKind::Pin,
+            ref_id: p.id.clone(),
+            start_ms: p.timestamp_ms - src.started_at.timestamp_millis() + offsets.pins_ms,
+            end_ms: None,
+            text: p.text.clone(),
+            detail: Some(p.insight_type.clone()),
+        });
+    }
+    for (i, p) in src.video_pins.iter().enumerate() {
+        entries.push(PlaybackEntry {
+            kind: PlaybackEntryKind::Pin,
+            ref_id: format!("video-pin-{}", i + 1),
+            start_ms: rel(p.timestamp) + offsets.pins_ms,
+            end_ms: None,
+            text: p.label.clone().unwrap_or_default(),
+            detail: Some("video".to_string()),
+        });
+    }
+
+    entries.sort_by_key(|e| e.start_ms);
+
+    let last_entry_ms = entries
+        .iter()
+        .map(|e| e.end_ms.unwrap_or(e.start_ms))
+        .max()
+        .unwrap_or(0);
+    let duration_ms = src
+        .ended_at
+        .map(rel)
+        .unwrap_or(0)
+        .max(chunks.last().map_or(0, |c| c.end_ms))
+        .max(last_entry_ms);
+
+    PlaybackManifest {
+        meeting_id: src.meeting_id.to_string(),
+        started_at: src.started_at,
+        duration_ms,
+        offsets,
+        chunk_boundaries_ms: chunks.iter().map(|c| c.start_ms).collect(),
+        chunks,
+        entries,
+    }
+}
+
+impl PlaybackManifest {
+    /// Chunk, transcript line, screen state and episode active at `offset_ms`
+    pub fn locate(&self, offset_ms: i64) -> PlaybackPosition {
+        let mut position = PlaybackPosition::default();
+
+        let idx = self
+            .chunk_boundaries_ms
+            .partition_point(|&b| b <= offset_ms);
+        let chunk = idx.checked_sub(1).and_then(|i| self.chunks.get(i));
+        // Past the end of the last chunk there's no video to play
+        if let Some(chunk) = chunk.filter(|c| offset_ms < c.end_ms) {
+            position.chunk_number = Some(chunk.chunk_number);
+            position.chunk_offset_secs = (offset_ms - chunk.start_ms) as f64 / 1000.0;
+        }
+
+        // Latest entry of each kind that started at or before the offset and
+        // hasn't ended yet
+        let started = self.entries.partition_point(|e| e.start_ms <= offset_ms);
+        let active = |kind: PlaybackEntryKind| {
+            self.entries[..started]
+                .iter()
+                .rev()
+                .find(|e| e.kind == kind && e.end_ms.map_or(true, |end| offset_ms < end))
+                .map(|e| e.ref_id.clone())
+        };
+        position.transcript_id = active(PlaybackEntryKind::Transcript);
+        position.state_id = active(PlaybackEntryKind::ScreenState);
+        position.episode_id = active(PlaybackEntryKind::Episode);
+        position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use std::path::PathBuf;
+
+    fn meeting_start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap()
+    }
+
+    fn transcript(id: i64, offset_ms: i64, text: &str) -> Transcript {
+        Transcript {
+            id,
+            meeting_id: "m1".to_string(),
+            text: text.to_string(),
+            speaker: Some("Alice".to_string()),
+            speaker_label: None,
+            timestamp: meeting_start() + Duration::milliseconds(offset_ms),
+            is_final: true,
+            confidence: 0.9,
+            after_reconnect: false,
+        }
+    }
+
+    fn chunk(number: u32, offset_ms: i64, secs: f64) -> VideoChunk {
+        let start_time = meeting_start() + Duration::milliseconds(offset_ms);
+        VideoChunk {
+            chunk_number: number,
+            path: PathBuf::from(format!("/tmp/chunk_{:03}.mp4", number)),
+            start_time,
+            end_time: Some(start_time + Duration::milliseconds((secs * 1000.0) as i64)),
+            size_bytes: 0,
+            duration_secs: secs,
+        }
+    }
+
+    fn state(id: &str, start_ms: i64, end_ms: i64) -> ScreenStateRecord {
+        let ts = |ms: i64| (meeting_start() + Duration::milliseconds(ms)).to_rfc3339();
+        ScreenStateRecord {
+            state_id: id.to_string(),
+            meeting_id: "m1".to_string(),
+            start_ts: ts(start_ms),
+            end_ts: Some(ts(end_ms)),
+            app_name: Some("Figma".to_string()),
+            window_title: None,
+            phash: String::new(),
+            delta_score: 0.0,
+            keyframe_path: None,
+            state_type: "design".to_string(),
+            category_confidence: None,
+            flags: String::new(),
+            monitor_id: None,
+        }
+    }
+
+    fn sources<'a>(
+        chunks: &'a [VideoChunk],
+        transcripts: &'a [Transcript],
+        states: &'a [ScreenStateRecord],
+    ) -> PlaybackSources<'a> {
+        PlaybackSources {
+            meeting_id: "m1",
+            started_at: meeting_start(),
+            ended_at: None,
+            chunks,
+            transcripts,
+            screen_states: states,
+            episodes: &[],
+            timeline_events: &[],
+            pinned_insights: &[],
+            video_pins: &[],
+        }
+    }
+
+    #[test]
+    fn test_locate_joins_sources() {
+        // Chunk 2's wall time drifted; its span follows chunk 1's duration
+        let chunks = vec![chunk(1, 2_000, 300.0), chunk(2, 303_500, 300.0)];
+        let transcripts = vec![
+            transcript(1, 1_000, "hello"),
+            transcript(2, 1_430_000, "second line"),
+        ];
+        let states = vec![state("s1", 1_000_000, 1_500_000)];
+        let manifest = build_manifest(&sources(&chunks, &transcripts, &states));
+
+        assert_eq!(manifest.chunk_boundaries_ms, vec![2_000, 302_000]);
+        assert_eq!(manifest.duration_ms, 1_500_000);
+
+        let at = manifest.locate(1_432_000);
+        assert_eq!(at.transcript_id.as_deref(), Some("2"));
+        assert_eq!(at.state_id.as_deref(), Some("s1"));
+        assert_eq!(at.chunk_number, None);
+
+        let at = manifest.locate(400_000);
+        assert_eq!(at.chunk_number, Some(2));
+        assert_eq!(at.chunk_offset_secs, 98.0);
+        assert_eq!(at.transcript_id.as_deref(), Some("1"));
+
+        let at = manifest.locate(5_000);
+        assert_eq!(at.chunk_number, Some(1));
+        assert_eq!(at.chunk_offset_secs, 3.0);
+        assert_eq!(at.state_id, None);
+    }
+
+    #[test]
+    fn test_skewed_source_is_anchored_to_start() {
+        // Transcript clock runs 1.5s ahead of the meeting row
+        let transcripts = vec![transcript(1, -1_500, "early"), transcript(2, 500, "next")];
+        let manifest = build_manifest(&sources(&[], &transcripts, &[]));
+
+        assert_eq!(manifest.offsets.transcripts_ms, 1_500);
+        assert_eq!(manifest.offsets.video_ms, 0);
+        assert_eq!(manifest.entries[0].start_ms, 0);
+        assert_eq!(manifest.entries[1].start_ms, 2_000);
+        assert_eq!(manifest.entries[0].end_ms, Some(2_000));
+    }
+}
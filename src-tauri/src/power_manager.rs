@@ -5,6 +5,7 @@
 // - NSWorkspace notifications for sleep/wake detection
 // - CGEventSource for user idle detection
 // - IOPMAssertion to prevent sleep during active meetings
+// - pmset battery status for the adaptive frame interval
 
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
@@ -54,6 +55,170 @@ impl Default for PowerConfig {
     }
 }
 
+/// Battery state as reported by the OS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatteryStatus {
+    pub on_battery: bool,
+    pub percent: Option<u8>,
+}
+
+/// Current battery status (None on desktops or when it can't be read)
+#[cfg(target_os = "macos")]
+pub fn battery_status() -> Option<BatteryStatus> {
+    let output = std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .ok()?;
+    parse_pmset_batt(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn battery_status() -> Option<BatteryStatus> {
+    None
+}
+
+/// Parse `pmset -g batt`, e.g.
+/// "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=123)\t42%; discharging; ..."
+fn parse_pmset_batt(output: &str) -> Option<BatteryStatus> {
+    let source = output.lines().next()?;
+    if !source.contains("drawing from") {
+        return None;
+    }
+    let percent = output
+        .lines()
+        .skip(1)
+        .find(|l| l.contains("InternalBattery"))
+        .and_then(|l| {
+            let end = l.find('%')?;
+            let start = l[..end]
+                .rfind(|c: char| !c.is_ascii_digit())
+                .map(|i| i + 1)
+                .unwrap_or(0);
+            l[start..end].parse().ok()
+        });
+    // Desktops report AC power with no internal battery
+    if percent.is_none() && !source.contains("Battery Power") {
+        return None;
+    }
+    Some(BatteryStatus {
+        on_battery: source.contains("Battery Power"),
+        percent,
+    })
+}
+
+/// Limits for the adaptive frame interval
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdaptiveIntervalConfig {
+    /// Stretch to the maximum when on battery at or below this percentage
+    pub battery_threshold_pct: u8,
+    /// Average processing time per frame before the interval is stretched
+    pub cpu_budget_ms: f64,
+    /// Never stretch the configured interval by more than this factor
+    pub max_factor: f32,
+}
+
+impl Default for AdaptiveIntervalConfig {
+    fn default() -> Self {
+        Self {
+            battery_threshold_pct: 30,
+            cpu_budget_ms: 250.0,
+            max_factor: 5.0,
+        }
+    }
+}
+
+/// Emitted as `capture-interval-adjusted` whenever the effective interval changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntervalAdjustment {
+    pub factor: f32,
+    pub interval_ms: u32,
+    pub base_interval_ms: u32,
+    /// "battery_low", "cpu_over_budget", "ac_power", "cpu_recovered", "config_changed" or "disabled"
+    pub reason: String,
+}
+
+/// Decides how far to stretch the configured frame interval
+pub struct AdaptiveIntervalController {
+    config: AdaptiveIntervalConfig,
+    factor: f32,
+}
+
+impl AdaptiveIntervalController {
+    pub fn new(config: AdaptiveIntervalConfig) -> Self {
+        Self {
+            config,
+            factor: 1.0,
+        }
+    }
+
+    pub fn update_config(&mut self, config: AdaptiveIntervalConfig) {
+        self.config = config;
+    }
+
+    pub fn factor(&self) -> f32 {
+        self.factor
+    }
+
+    /// Go back to the configured interval; Some if that changes anything
+    pub fn reset(&mut self, base_interval_ms: u32) -> Option<IntervalAdjustment> {
+        self.apply(1.0, base_interval_ms, "disabled")
+    }
+
+    /// Re-evaluate with fresh readings; Some only when the factor changes
+    pub fn evaluate(
+        &mut self,
+        base_interval_ms: u32,
+        battery: Option<BatteryStatus>,
+        cpu_ms_per_frame: Option<f64>,
+    ) -> Option<IntervalAdjustment> {
+        let max_factor = self.config.max_factor.max(1.0);
+        let battery_low = battery.is_some_and(|b| {
+            b.on_battery
+                && b.percent
+                    .is_some_and(|p| p <= self.config.battery_threshold_pct)
+        });
+        let on_ac = battery.is_some_and(|b| !b.on_battery);
+        let budget = self.config.cpu_budget_ms;
+
+        let (factor, reason) = if battery_low {
+            (max_factor, "battery_low")
+        } else if cpu_ms_per_frame.is_some_and(|ms| ms > budget) {
+            ((self.factor * 2.0).min(max_factor), "cpu_over_budget")
+        } else if on_ac {
+            (1.0, "ac_power")
+        } else if cpu_ms_per_frame.is_some_and(|ms| ms < budget / 2.0) {
+            ((self.factor / 2.0).max(1.0), "cpu_recovered")
+        } else {
+            (self.factor.min(max_factor), "config_changed")
+        };
+
+        self.apply(factor, base_interval_ms, reason)
+    }
+
+    fn apply(
+        &mut self,
+        factor: f32,
+        base_interval_ms: u32,
+        reason: &str,
+    ) -> Option<IntervalAdjustment> {
+        if (factor - self.factor).abs() < f32::EPSILON {
+            return None;
+        }
+        self.factor = factor;
+        Some(IntervalAdjustment {
+            factor,
+            interval_ms: stretched_interval(base_interval_ms, factor),
+            base_interval_ms,
+            reason: reason.to_string(),
+        })
+    }
+}
+
+/// Interval after applying an adaptive factor (capped at the engine's 60s maximum)
+pub fn stretched_interval(base_interval_ms: u32, factor: f32) -> u32 {
+    ((base_interval_ms as f32 * factor).round() as u32).min(60_000)
+}
+
 /// Callback for power state changes
 pub type PowerCallback = Arc<dyn Fn(PowerState) + Send + Sync>;
 
@@ -439,4 +604,50 @@ mod tests {
         assert_eq!(config.idle_timeout_secs, 300);
         assert!(config.respect_low_power_mode);
     }
+
+    #[test]
+    fn test_parse_pmset_batt() {
+        let laptop = "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=4653155)\t27%; discharging; 2:10 remaining present: true\n";
+        assert_eq!(
+            parse_pmset_batt(laptop),
+            Some(BatteryStatus {
+                on_battery: true,
+                percent: Some(27)
+            })
+        );
+        let charging = "Now drawing from 'AC Power'\n -InternalBattery-0 (id=4653155)\t100%; charged; 0:00 remaining present: true\n";
+        assert!(!parse_pmset_batt(charging).unwrap().on_battery);
+        assert_eq!(parse_pmset_batt("Now drawing from 'AC Power'\n"), None);
+    }
+
+    #[test]
+    fn test_adaptive_interval_stretches_and_recovers() {
+        let mut controller = AdaptiveIntervalController::new(AdaptiveIntervalConfig::default());
+        let low = Some(BatteryStatus {
+            on_battery: true,
+            percent: Some(20),
+        });
+        let adj = controller.evaluate(1000, low, None).unwrap();
+        assert_eq!(adj.interval_ms, 5000);
+        assert_eq!(adj.reason, "battery_low");
+        assert!(controller.evaluate(1000, low, Some(10.0)).is_none());
+
+        let ac = Some(BatteryStatus {
+            on_battery: false,
+            percent: Some(25),
+        });
+        let adj = controller.evaluate(1000, ac, Some(10.0)).unwrap();
+        assert_eq!(adj.interval_ms, 1000);
+        assert_eq!(adj.reason, "ac_power");
+
+        // CPU over budget doubles per evaluation, never past max_factor
+        let adj = controller.evaluate(1000, None, Some(400.0)).unwrap();
+        assert_eq!(adj.interval_ms, 2000);
+        controller.evaluate(1000, None, Some(400.0));
+        let adj = controller.evaluate(1000, None, Some(400.0)).unwrap();
+        assert_eq!(adj.factor, 5.0);
+        let adj = controller.evaluate(1000, None, Some(50.0)).unwrap();
+        assert_eq!(adj.reason, "cpu_recovered");
+        assert_eq!(adj.interval_ms, 2500);
+    }
 }
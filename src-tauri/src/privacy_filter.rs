@@ -7,6 +7,21 @@
 // - Firefox Private Browsing
 // - Edge InPrivate
 // - Password managers (1Password, Keychain Access, etc.)
+// - User denylist: app bundle ids and window-title regexes (settings-backed)
+//
+// Excluded frames are never written to disk, queued for VLM or embedded;
+// capture records them as `redacted` screen states so timelines stay intact.
+//
+// Text headed for the cloud (Pinecone, Supabase) goes through `redact_text`
+// first: emails, phone numbers, SSN-like ids and Luhn-valid card numbers are
+// masked or the whole segment is dropped. The local SQLite copy is untouched.
+
+use parking_lot::RwLock;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+use crate::accessibility_extractor::{AccessibilityExtractor, FrontmostWindow};
 
 #[cfg(target_os = "macos")]
 use objc::runtime::Object;
@@ -134,10 +149,246 @@ fn get_frontmost_window_title() -> Option<String> {
     None
 }
 
+/// User-managed exclusions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrivacyRules {
+    /// App bundle ids never captured (case-insensitive exact match)
+    #[serde(default)]
+    pub bundle_ids: Vec<String>,
+    /// Regexes matched against the focused window title
+    #[serde(default)]
+    pub title_patterns: Vec<String>,
+}
+
+struct CompiledRules {
+    rules: PrivacyRules,
+    titles: Vec<Regex>,
+}
+
+impl CompiledRules {
+    fn compile(rules: PrivacyRules) -> Result<Self, String> {
+        let titles = rules
+            .title_patterns
+            .iter()
+            .map(|p| Regex::new(p).map_err(|e| format!("Invalid title pattern '{}': {}", p, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { rules, titles })
+    }
+
+    fn exclusion_reason(&self, window: &FrontmostWindow) -> Option<String> {
+        if let Some(app) = &window.app_name {
+            if is_blocked_app(app) {
+                return Some(format!("blocked app: {}", app));
+            }
+        }
+        if let Some(bundle_id) = &window.bundle_id {
+            if self
+                .rules
+                .bundle_ids
+                .iter()
+                .any(|b| b.eq_ignore_ascii_case(bundle_id))
+            {
+                return Some(format!("denied bundle id: {}", bundle_id));
+            }
+        }
+        if let Some(title) = &window.window_title {
+            if let Some(re) = self.titles.iter().find(|re| re.is_match(title)) {
+                return Some(format!("window title matches /{}/", re.as_str()));
+            }
+        }
+        None
+    }
+}
+
+fn compiled_rules() -> &'static RwLock<CompiledRules> {
+    static RULES: OnceLock<RwLock<CompiledRules>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        RwLock::new(CompiledRules {
+            rules: PrivacyRules::default(),
+            titles: Vec::new(),
+        })
+    })
+}
+
+/// Replace the user denylist (fails without changing anything on a bad regex)
+pub fn set_rules(rules: PrivacyRules) -> Result<(), String> {
+    let compiled = CompiledRules::compile(rules)?;
+    *compiled_rules().write() = compiled;
+    Ok(())
+}
+
+pub fn get_rules() -> PrivacyRules {
+    compiled_rules().read().rules.clone()
+}
+
+/// Why the frontmost window must not be captured, if it mustn't
+pub fn frontmost_exclusion() -> Option<String> {
+    exclusion_for(AccessibilityExtractor::frontmost_window().as_ref())
+}
+
+/// Same as `frontmost_exclusion`, for a window the caller already looked up
+pub fn exclusion_for(window: Option<&FrontmostWindow>) -> Option<String> {
+    if is_private_window() {
+        return Some("private browsing window".to_string());
+    }
+    compiled_rules().read().exclusion_reason(window?)
+}
+
+/// What cloud sync does with text that contains PII
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionMode {
+    /// Upload verbatim
+    #[default]
+    Off,
+    /// Replace each match with a placeholder like `[EMAIL]`
+    Mask,
+    /// Skip any segment that contains PII
+    DropSegment,
+}
+
+impl RedactionMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RedactionMode::Off => "off",
+            RedactionMode::Mask => "mask",
+            RedactionMode::DropSegment => "drop_segment",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "off" => Some(RedactionMode::Off),
+            "mask" => Some(RedactionMode::Mask),
+            "drop_segment" => Some(RedactionMode::DropSegment),
+            _ => None,
+        }
+    }
+}
+
+/// Result of running one piece of text through the redactor
+#[derive(Debug, Clone, PartialEq)]
+pub struct Redacted {
+    /// None when the segment must not be uploaded at all
+    pub text: Option<String>,
+    /// PII matches found
+    pub redactions: u64,
+}
+
+struct PiiDetectors {
+    card: Regex,
+    ssn: Regex,
+    phone: Regex,
+    email: Regex,
+}
+
+fn pii_detectors() -> &'static PiiDetectors {
+    static DETECTORS: OnceLock<PiiDetectors> = OnceLock::new();
+    DETECTORS.get_or_init(|| PiiDetectors {
+        card: Regex::new(r"\b\d(?:[ -]?\d){12,18}\b").unwrap(),
+        ssn: Regex::new(r"\b\d{3}-\d{2}-\d{4}\b").unwrap(),
+        phone: Regex::new(r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{3}\)|\b\d{3})[\s.-]?\d{3}[\s.-]?\d{4}\b")
+            .unwrap(),
+        email: Regex::new(r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b").unwrap(),
+    })
+}
+
+fn luhn_valid(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+/// Mask every PII match; returns the masked text and the number of matches.
+/// Cards go first so their digit runs aren't half-eaten by the phone pattern.
+pub fn mask_pii(text: &str) -> (String, u64) {
+    let detectors = pii_detectors();
+    let mut count = 0u64;
+
+    let masked = detectors.card.replace_all(text, |caps: &regex::Captures| {
+        if luhn_valid(&caps[0]) {
+            count += 1;
+            "[CARD]".to_string()
+        } else {
+            caps[0].to_string()
+        }
+    });
+
+    let mut masked = masked.into_owned();
+    for (re, placeholder) in [
+        (&detectors.ssn, "[SSN]"),
+        (&detectors.phone, "[PHONE]"),
+        (&detectors.email, "[EMAIL]"),
+    ] {
+        let matches = re.find_iter(&masked).count() as u64;
+        if matches > 0 {
+            count += matches;
+            masked = re.replace_all(&masked, placeholder).into_owned();
+        }
+    }
+
+    (masked, count)
+}
+
+/// Apply a redaction mode to text headed for cloud sync
+pub fn redact_with(mode: RedactionMode, text: &str) -> Redacted {
+    if mode == RedactionMode::Off {
+        return Redacted {
+            text: Some(text.to_string()),
+            redactions: 0,
+        };
+    }
+
+    let (masked, redactions) = mask_pii(text);
+    let text = match mode {
+        RedactionMode::DropSegment if redactions > 0 => None,
+        RedactionMode::DropSegment => Some(text.to_string()),
+        _ => Some(masked),
+    };
+    Redacted { text, redactions }
+}
+
+fn redaction_mode_lock() -> &'static RwLock<RedactionMode> {
+    static MODE: OnceLock<RwLock<RedactionMode>> = OnceLock::new();
+    MODE.get_or_init(|| RwLock::new(RedactionMode::default()))
+}
+
+pub fn set_redaction_mode(mode: RedactionMode) {
+    *redaction_mode_lock().write() = mode;
+}
+
+pub fn redaction_mode() -> RedactionMode {
+    *redaction_mode_lock().read()
+}
+
+/// Redact text with the configured mode
+pub fn redact_text(text: &str) -> Redacted {
+    redact_with(redaction_mode(), text)
+}
+
 /// Master check: should we skip capture right now?
 pub fn should_skip_capture() -> bool {
-    if is_private_window() {
-        log::debug!("🔒 Skipping capture - private/sensitive content detected");
+    if let Some(reason) = frontmost_exclusion() {
+        log::debug!("🔒 Skipping capture - {}", reason);
         return true;
     }
     false
@@ -163,4 +414,71 @@ mod tests {
         assert!(is_blocked_app("Keychain Access"));
         assert!(!is_blocked_app("Visual Studio Code"));
     }
+
+    #[test]
+    fn test_user_rules() {
+        let rules = CompiledRules::compile(PrivacyRules {
+            bundle_ids: vec!["org.whispersystems.signal-desktop".to_string()],
+            title_patterns: vec![r"(?i)payroll|salary".to_string()],
+        })
+        .unwrap();
+
+        let window = |bundle: &str, title: &str| FrontmostWindow {
+            app_name: Some("Some App".to_string()),
+            bundle_id: Some(bundle.to_string()),
+            window_title: Some(title.to_string()),
+        };
+
+        assert!(rules
+            .exclusion_reason(&window("org.whispersystems.Signal-Desktop", "Chats"))
+            .is_some());
+        assert!(rules
+            .exclusion_reason(&window("com.google.Chrome", "Q3 Payroll - Sheets"))
+            .is_some());
+        assert!(rules
+            .exclusion_reason(&window("com.google.Chrome", "Roadmap - Docs"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_mask_pii() {
+        let (masked, count) = mask_pii(
+            "Mail jane.doe@acme.io or call (415) 555-0134, SSN 123-45-6789, card 4111 1111 1111 1111",
+        );
+        assert_eq!(
+            masked,
+            "Mail [EMAIL] or call [PHONE], SSN [SSN], card [CARD]"
+        );
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn test_card_requires_luhn() {
+        // 16 digits that fail the checksum (an order number, say) stay put
+        let (masked, count) = mask_pii("order 1234 5678 9012 3456 shipped");
+        assert_eq!(masked, "order 1234 5678 9012 3456 shipped");
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_drop_segment_mode() {
+        let clean = redact_with(RedactionMode::DropSegment, "ship it on friday");
+        assert_eq!(clean.text.as_deref(), Some("ship it on friday"));
+
+        let dirty = redact_with(RedactionMode::DropSegment, "email me at a@b.co");
+        assert_eq!(dirty.text, None);
+        assert_eq!(dirty.redactions, 1);
+
+        let off = redact_with(RedactionMode::Off, "email me at a@b.co");
+        assert_eq!(off.text.as_deref(), Some("email me at a@b.co"));
+    }
+
+    #[test]
+    fn test_invalid_pattern_rejected() {
+        assert!(CompiledRules::compile(PrivacyRules {
+            bundle_ids: vec![],
+            title_patterns: vec!["(unclosed".to_string()],
+        })
+        .is_err());
+    }
 }
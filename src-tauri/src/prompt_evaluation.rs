@@ -0,0 +1,555 @@
+// noFriction Meetings - Prompt Evaluation
+// A/B runs of two prompt versions on the same real samples (pending frames or
+// recent meeting transcripts), stored side by side for human grading
+
+use crate::ai_client::AIClient;
+use crate::database::DatabaseManager;
+use crate::model_router::model_router;
+use crate::prompt_manager::{Prompt, PromptManager};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Row, Sqlite};
+use std::time::Instant;
+use uuid::Uuid;
+
+pub const SAMPLE_SOURCE_FRAMES: &str = "frames";
+pub const SAMPLE_SOURCE_TRANSCRIPTS: &str = "transcripts";
+
+/// Upper bound on samples per run (each sample costs two model calls)
+const MAX_SAMPLES: u32 = 20;
+/// Transcript text sent per sample
+const TRANSCRIPT_SAMPLE_CHARS: usize = 6000;
+const PREVIEW_CHARS: usize = 280;
+/// Where the sample goes in a prompt's user template (appended if absent)
+const CONTENT_PLACEHOLDER: &str = "{{content}}";
+
+/// Prompt version taking part in a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptRef {
+    pub id: String,
+    pub name: String,
+    pub version: i32,
+    pub theme: Option<String>,
+}
+
+impl From<&Prompt> for PromptRef {
+    fn from(p: &Prompt) -> Self {
+        Self {
+            id: p.id.clone(),
+            name: p.name.clone(),
+            version: p.version,
+            theme: p.theme.clone(),
+        }
+    }
+}
+
+/// Both prompts' outputs for one sample
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptEvaluation {
+    pub id: String,
+    pub run_id: String,
+    pub prompt_a_id: String,
+    pub prompt_b_id: String,
+    pub sample_source: String,
+    pub sample_ref: String, // Frame path or meeting id
+    pub sample_preview: Option<String>,
+    pub model_a: String,
+    pub model_b: String,
+    pub output_a: Option<String>,
+    pub output_b: Option<String>,
+    pub error_a: Option<String>,
+    pub error_b: Option<String>,
+    pub duration_a_ms: i64,
+    pub duration_b_ms: i64,
+    pub winner: Option<String>, // "a" | "b" | "tie" once graded
+    pub created_at: String,
+    pub judged_at: Option<String>,
+}
+
+/// Result of `evaluate_prompt`, ready to render side by side
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluationRun {
+    pub run_id: String,
+    pub prompt_a: PromptRef,
+    pub prompt_b: PromptRef,
+    pub sample_source: String,
+    pub items: Vec<PromptEvaluation>,
+}
+
+/// Graded results of one prompt version across all runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptWinRate {
+    pub prompt_id: String,
+    pub name: String,
+    pub version: i32,
+    pub theme: Option<String>,
+    pub judged: i64,
+    pub wins: i64,
+    pub losses: i64,
+    pub ties: i64,
+    pub win_rate: f64, // wins / judged, ties count as half
+}
+
+enum SampleContent {
+    Frame(String),
+    Transcript(String),
+}
+
+struct Sample {
+    reference: String,
+    preview: String,
+    content: SampleContent,
+}
+
+/// Run both prompts on `sample_count` samples and store the outputs
+pub async fn run_evaluation(
+    prompt_manager: &PromptManager,
+    database: &DatabaseManager,
+    prompt_a_id: &str,
+    prompt_b_id: &str,
+    sample_source: &str,
+    sample_count: u32,
+) -> Result<EvaluationRun, String> {
+    if prompt_a_id == prompt_b_id {
+        return Err("Pick two different prompts to compare".to_string());
+    }
+    let load = |id: &str| {
+        let id = id.to_string();
+        async move {
+            prompt_manager
+                .get_prompt(&id)
+                .await
+                .map_err(|e| format!("Failed to get prompt: {}", e))?
+                .ok_or_else(|| format!("Prompt not found: {}", id))
+        }
+    };
+    let prompt_a = load(prompt_a_id).await?;
+    let prompt_b = load(prompt_b_id).await?;
+
+    let count = sample_count.clamp(1, MAX_SAMPLES);
+    let samples = load_samples(database, sample_source, count).await?;
+    if samples.is_empty() {
+        return Err(format!("No {} available to sample", sample_source));
+    }
+
+    let run_id = Uuid::new_v4().to_string();
+    let pool = database.get_pool();
+    log::info!(
+        "Prompt evaluation {}: {} v{} vs {} v{} on {} {}",
+        run_id,
+        prompt_a.name,
+        prompt_a.version,
+        prompt_b.name,
+        prompt_b.version,
+        samples.len(),
+        sample_source
+    );
+
+    let mut items = Vec::with_capacity(samples.len());
+    for sample in samples {
+        let (model_a, result_a, duration_a_ms) =
+            run_prompt(prompt_manager, &prompt_a, &sample).await;
+        let (model_b, result_b, duration_b_ms) =
+            run_prompt(prompt_manager, &prompt_b, &sample).await;
+
+        let evaluation = PromptEvaluation {
+            id: Uuid::new_v4().to_string(),
+            run_id: run_id.clone(),
+            prompt_a_id: prompt_a.id.clone(),
+            prompt_b_id: prompt_b.id.clone(),
+            sample_source: sample_source.to_string(),
+            sample_ref: sample.reference,
+            sample_preview: Some(sample.preview),
+            model_a,
+            model_b,
+            output_a: result_a.as_ref().ok().cloned(),
+            output_b: result_b.as_ref().ok().cloned(),
+            error_a: result_a.err(),
+            error_b: result_b.err(),
+            duration_a_ms,
+            duration_b_ms,
+            winner: None,
+            created_at: Utc::now().to_rfc3339(),
+            judged_at: None,
+        };
+        insert_evaluation(&pool, &evaluation).await?;
+        items.push(evaluation);
+    }
+
+    Ok(EvaluationRun {
+        run_id,
+        prompt_a: PromptRef::from(&prompt_a),
+        prompt_b: PromptRef::from(&prompt_b),
+        sample_source: sample_source.to_string(),
+        items,
+    })
+}
+
+async fn load_samples(
+    database: &DatabaseManager,
+    sample_source: &str,
+    count: u32,
+) -> Result<Vec<Sample>, String> {
+    match sample_source {
+        SAMPLE_SOURCE_FRAMES => {
+            let frames = database
+                .get_pending_frames(count as i32)
+                .await
+                .map_err(|e| format!("Failed to load pending frames: {}", e))?;
+            Ok(frames
+                .into_iter()
+                .filter(|f| std::path::Path::new(&f.frame_path).exists())
+                .map(|f| Sample {
+                    reference: f.frame_path.clone(),
+                    preview: format!("Frame captured {}", f.captured_at.to_rfc3339()),
+                    content: SampleContent::Frame(f.frame_path),
+                })
+                .collect())
+        }
+        SAMPLE_SOURCE_TRANSCRIPTS => {
+            // Look further back than `count`, since some meetings have no transcript
+            let meetings = database
+                .list_meetings((count * 3) as i32)
+                .await
+                .map_err(|e| format!("Failed to list meetings: {}", e))?;
+
+            let mut samples = Vec::new();
+            for meeting in meetings {
+                if samples.len() >= count as usize {
+                    break;
+                }
+                let transcripts = database
+                    .get_transcripts(&meeting.id)
+                    .await
+                    .map_err(|e| format!("Failed to get transcripts: {}", e))?;
+                let text: String = transcripts
+                    .iter()
+                    .map(|t| match t.speaker {
+                        Some(ref speaker) => format!("{}: {}", speaker, t.text),
+                        None => t.text.clone(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                    .chars()
+                    .take(TRANSCRIPT_SAMPLE_CHARS)
+                    .collect();
+                if text.trim().is_empty() {
+                    continue;
+                }
+                samples.push(Sample {
+                    reference: meeting.id.clone(),
+                    preview: format!(
+                        "{}: {}",
+                        meeting.title,
+                        text.chars().take(PREVIEW_CHARS).collect::<String>()
+                    ),
+                    content: SampleContent::Transcript(text),
+                });
+            }
+            Ok(samples)
+        }
+        other => Err(format!(
+            "Unknown sample source '{}' (expected '{}' or '{}')",
+            other, SAMPLE_SOURCE_FRAMES, SAMPLE_SOURCE_TRANSCRIPTS
+        )),
+    }
+}
+
+/// Run one prompt on one sample; returns the model used, the output and its duration
+async fn run_prompt(
+    prompt_manager: &PromptManager,
+    prompt: &Prompt,
+    sample: &Sample,
+) -> (String, Result<String, String>, i64) {
+    let started = Instant::now();
+    let (model, result) = match sample.content {
+        SampleContent::Frame(ref path) => {
+            let configured = match prompt.model_id.as_deref() {
+                Some(id) => prompt_manager
+                    .get_model_config(id)
+                    .await
+                    .ok()
+                    .flatten()
+                    .map(|m| m.name),
+                None => None,
+            };
+            let model = configured.unwrap_or_else(crate::vlm_client::vlm_model);
+            let text = sample_message(
+                Some(&prompt.system_prompt),
+                prompt.user_prompt_template.as_deref(),
+                "",
+            );
+            let result = crate::vlm_client::vlm_analyze_frame_raw(path, &text, &model).await;
+            (model, result)
+        }
+        SampleContent::Transcript(ref transcript) => {
+            let route = model_router().resolve_prompt(prompt).await;
+            let message = sample_message(None, prompt.user_prompt_template.as_deref(), transcript);
+            let result = AIClient::new().complete_routed(&route, &message).await;
+            (route.model, result)
+        }
+    };
+    (model, result, started.elapsed().as_millis() as i64)
+}
+
+/// User message for a sample: the optional preamble, then the prompt's user
+/// template with the sample in place of `{{content}}` (or appended after it)
+fn sample_message(preamble: Option<&str>, template: Option<&str>, content: &str) -> String {
+    let body = match template.map(str::trim).filter(|t| !t.is_empty()) {
+        Some(t) if t.contains(CONTENT_PLACEHOLDER) => t.replace(CONTENT_PLACEHOLDER, content),
+        Some(t) if content.is_empty() => t.to_string(),
+        Some(t) => format!("{}\n\n{}", t, content),
+        None => content.to_string(),
+    };
+    match preamble {
+        Some(p) if body.is_empty() => p.to_string(),
+        Some(p) => format!("{}\n\n{}", p, body),
+        None => body,
+    }
+}
+
+async fn insert_evaluation(pool: &Pool<Sqlite>, e: &PromptEvaluation) -> Result<(), String> {
+    sqlx::query(
+        r#"
+        INSERT INTO prompt_evaluations
+        (id, run_id, prompt_a_id, prompt_b_id, sample_source, sample_ref, sample_preview,
+         model_a, model_b, output_a, output_b, error_a, error_b, duration_a_ms, duration_b_ms,
+         winner, created_at, judged_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, NULL, ?, NULL)
+        "#,
+    )
+    .bind(&e.id)
+    .bind(&e.run_id)
+    .bind(&e.prompt_a_id)
+    .bind(&e.prompt_b_id)
+    .bind(&e.sample_source)
+    .bind(&e.sample_ref)
+    .bind(&e.sample_preview)
+    .bind(&e.model_a)
+    .bind(&e.model_b)
+    .bind(&e.output_a)
+    .bind(&e.output_b)
+    .bind(&e.error_a)
+    .bind(&e.error_b)
+    .bind(e.duration_a_ms)
+    .bind(e.duration_b_ms)
+    .bind(&e.created_at)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to store evaluation: {}", e))?;
+    Ok(())
+}
+
+/// Grade one evaluation: "a", "b" or "tie" (re-grading overwrites)
+pub async fn record_verdict(
+    pool: &Pool<Sqlite>,
+    evaluation_id: &str,
+    winner: &str,
+) -> Result<(), String> {
+    let winner = winner.trim().to_lowercase();
+    if !matches!(winner.as_str(), "a" | "b" | "tie") {
+        return Err(format!(
+            "Invalid winner '{}' (expected 'a', 'b' or 'tie')",
+            winner
+        ));
+    }
+
+    let result =
+        sqlx::query("UPDATE prompt_evaluations SET winner = ?, judged_at = ? WHERE id = ?")
+            .bind(&winner)
+            .bind(Utc::now().to_rfc3339())
+            .bind(evaluation_id)
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to record verdict: {}", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err(format!("Evaluation not found: {}", evaluation_id));
+    }
+    Ok(())
+}
+
+/// Win rates per prompt version over all graded evaluations, optionally for
+/// one prompt name (all its versions)
+pub async fn win_rates(
+    pool: &Pool<Sqlite>,
+    prompt_name: Option<&str>,
+) -> Result<Vec<PromptWinRate>, String> {
+    let rows = sqlx::query(
+        r#"
+        SELECT p.id, p.name, p.version, p.theme,
+               COUNT(*) AS judged,
+               SUM(CASE WHEN (e.prompt_a_id = p.id AND e.winner = 'a')
+                          OR (e.prompt_b_id = p.id AND e.winner = 'b') THEN 1 ELSE 0 END) AS wins,
+               SUM(CASE WHEN e.winner = 'tie' THEN 1 ELSE 0 END) AS ties
+        FROM prompt_evaluations e
+        JOIN prompt_library p ON p.id = e.prompt_a_id OR p.id = e.prompt_b_id
+        WHERE e.winner IS NOT NULL AND (? IS NULL OR p.name = ?)
+        GROUP BY p.id
+        ORDER BY p.name, p.version DESC
+        "#,
+    )
+    .bind(prompt_name)
+    .bind(prompt_name)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to load evaluation summary: {}", e))?;
+
+    Ok(rows
+        .iter()
+        .map(|r| {
+            let judged: i64 = r.get("judged");
+            let wins: i64 = r.get("wins");
+            let ties: i64 = r.get("ties");
+            PromptWinRate {
+                prompt_id: r.get("id"),
+                name: r.get("name"),
+                version: r.get("version"),
+                theme: r.get("theme"),
+                judged,
+                wins,
+                losses: judged - wins - ties,
+                ties,
+                win_rate: if judged > 0 {
+                    (wins as f64 + ties as f64 / 2.0) / judged as f64
+                } else {
+                    0.0
+                },
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> Pool<Sqlite> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        sqlx::query(
+            "CREATE TABLE prompt_library (id TEXT PRIMARY KEY, name TEXT NOT NULL, version INTEGER NOT NULL, theme TEXT)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"
+            CREATE TABLE prompt_evaluations (
+                id TEXT PRIMARY KEY,
+                run_id TEXT NOT NULL,
+                prompt_a_id TEXT NOT NULL,
+                prompt_b_id TEXT NOT NULL,
+                sample_source TEXT NOT NULL,
+                sample_ref TEXT NOT NULL,
+                sample_preview TEXT,
+                model_a TEXT NOT NULL,
+                model_b TEXT NOT NULL,
+                output_a TEXT,
+                output_b TEXT,
+                error_a TEXT,
+                error_b TEXT,
+                duration_a_ms INTEGER NOT NULL DEFAULT 0,
+                duration_b_ms INTEGER NOT NULL DEFAULT 0,
+                winner TEXT,
+                created_at TEXT NOT NULL,
+                judged_at TEXT
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        for (id, version) in [("notes-v1", 1), ("notes-v2", 2)] {
+            sqlx::query(
+                "INSERT INTO prompt_library (id, name, version) VALUES (?, 'Meeting Notes', ?)",
+            )
+            .bind(id)
+            .bind(version)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        pool
+    }
+
+    fn evaluation(id: &str) -> PromptEvaluation {
+        PromptEvaluation {
+            id: id.to_string(),
+            run_id: "run-1".to_string(),
+            prompt_a_id: "notes-v1".to_string(),
+            prompt_b_id: "notes-v2".to_string(),
+            sample_source: SAMPLE_SOURCE_TRANSCRIPTS.to_string(),
+            sample_ref: "meeting-1".to_string(),
+            sample_preview: None,
+            model_a: "qwen2.5vl:7b".to_string(),
+            model_b: "qwen2.5vl:7b".to_string(),
+            output_a: Some("A".to_string()),
+            output_b: Some("B".to_string()),
+            error_a: None,
+            error_b: None,
+            duration_a_ms: 10,
+            duration_b_ms: 12,
+            winner: None,
+            created_at: Utc::now().to_rfc3339(),
+            judged_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn win_rates_count_each_side_and_ties() {
+        let pool = setup_test_db().await;
+        for (id, winner) in [("e1", "b"), ("e2", "B"), ("e3", "a"), ("e4", "tie")] {
+            insert_evaluation(&pool, &evaluation(id)).await.unwrap();
+            record_verdict(&pool, id, winner).await.unwrap();
+        }
+        // Ungraded evaluations don't count
+        insert_evaluation(&pool, &evaluation("e5")).await.unwrap();
+
+        let rates = win_rates(&pool, Some("Meeting Notes")).await.unwrap();
+        assert_eq!(rates.len(), 2);
+
+        let v2 = &rates[0];
+        assert_eq!(
+            (v2.version, v2.judged, v2.wins, v2.losses, v2.ties),
+            (2, 4, 2, 1, 1)
+        );
+        assert!((v2.win_rate - 0.625).abs() < 1e-9);
+
+        let v1 = &rates[1];
+        assert_eq!((v1.version, v1.wins, v1.losses, v1.ties), (1, 1, 2, 1));
+    }
+
+    #[tokio::test]
+    async fn verdict_validation() {
+        let pool = setup_test_db().await;
+        insert_evaluation(&pool, &evaluation("e1")).await.unwrap();
+
+        assert!(record_verdict(&pool, "e1", "c").await.is_err());
+        assert!(record_verdict(&pool, "missing", "a").await.is_err());
+        assert!(record_verdict(&pool, "e1", "tie").await.is_ok());
+    }
+
+    #[test]
+    fn sample_goes_into_template_placeholder() {
+        assert_eq!(
+            sample_message(None, Some("Notes for:\n{{content}}\nEnd"), "hi"),
+            "Notes for:\nhi\nEnd"
+        );
+        assert_eq!(
+            sample_message(None, Some("Summarize"), "hi"),
+            "Summarize\n\nhi"
+        );
+        assert_eq!(sample_message(None, None, "hi"), "hi");
+        assert_eq!(
+            sample_message(Some("Describe the screen"), None, ""),
+            "Describe the screen"
+        );
+    }
+}
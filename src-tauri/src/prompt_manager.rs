@@ -225,8 +225,123 @@ impl PromptManager {
         .execute(&self.pool)
         .await?;
 
+        // A/B evaluation outputs (two prompt versions on the same sample)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS prompt_evaluations (
+                id TEXT PRIMARY KEY,
+                run_id TEXT NOT NULL,
+                prompt_a_id TEXT NOT NULL REFERENCES prompt_library(id),
+                prompt_b_id TEXT NOT NULL REFERENCES prompt_library(id),
+                sample_source TEXT NOT NULL,
+                sample_ref TEXT NOT NULL,
+                sample_preview TEXT,
+                model_a TEXT NOT NULL,
+                model_b TEXT NOT NULL,
+                output_a TEXT,
+                output_b TEXT,
+                error_a TEXT,
+                error_b TEXT,
+                duration_a_ms INTEGER NOT NULL DEFAULT 0,
+                duration_b_ms INTEGER NOT NULL DEFAULT 0,
+                winner TEXT,
+                created_at TEXT NOT NULL,
+                judged_at TEXT
+            )
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        let _ = sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_prompt_evaluations_run ON prompt_evaluations(run_id)",
+        )
+        .execute(&self.pool)
+        .await;
+
         // Seed default data
         self.seed_defaults().await?;
+        self.seed_export_template().await?;
+        self.seed_routed_use_cases().await?;
+
+        Ok(())
+    }
+
+    /// Seed a prompt and use-case mapping for each model-routed use case that
+    /// has none yet. Mappings start without a model, so the default chat
+    /// model applies until one is assigned.
+    async fn seed_routed_use_cases(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let now = Utc::now().to_rfc3339();
+
+        for route in crate::model_router::BUILTIN_ROUTES {
+            if self.get_use_case(route.use_case).await?.is_some() {
+                continue;
+            }
+
+            let prompt_id = Uuid::new_v4().to_string();
+            sqlx::query(r#"
+                INSERT INTO prompt_library 
+                (id, name, description, category, system_prompt, temperature, theme, version, is_builtin, is_active, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?, NULL, 1, 1, 1, ?, ?)
+            "#)
+            .bind(&prompt_id)
+            .bind(route.display_name)
+            .bind(route.description)
+            .bind(route.category)
+            .bind(route.system_prompt)
+            .bind(route.temperature)
+            .bind(&now)
+            .bind(&now)
+            .execute(&self.pool)
+            .await?;
+
+            sqlx::query(r#"
+                INSERT OR IGNORE INTO use_case_mappings 
+                (id, use_case, display_name, description, prompt_id, model_id, priority, is_active, created_at)
+                VALUES (?, ?, ?, ?, ?, NULL, 0, 1, ?)
+            "#)
+            .bind(Uuid::new_v4().to_string())
+            .bind(route.use_case)
+            .bind(route.display_name)
+            .bind(route.description)
+            .bind(&prompt_id)
+            .bind(&now)
+            .execute(&self.pool)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Seed the builtin vault export template (added after the original seed,
+    /// so it is checked on its own)
+    async fn seed_export_template(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM prompt_library WHERE category = ? AND is_builtin = 1",
+        )
+        .bind(crate::export_template::EXPORT_TEMPLATE_CATEGORY)
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or(0);
+
+        if count > 0 {
+            return Ok(());
+        }
+
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(r#"
+            INSERT INTO prompt_library 
+            (id, name, description, category, system_prompt, temperature, theme, version, is_builtin, is_active, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, 0.0, NULL, 1, 1, 1, ?, ?)
+        "#)
+        .bind(Uuid::new_v4().to_string())
+        .bind("Default Meeting Export")
+        .bind("Layout of the meeting note written to the Obsidian vault")
+        .bind(crate::export_template::EXPORT_TEMPLATE_CATEGORY)
+        .bind(crate::export_template::DEFAULT_EXPORT_TEMPLATE)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await?;
 
         Ok(())
     }
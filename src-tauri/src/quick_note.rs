@@ -0,0 +1,236 @@
+// noFriction Meetings - Quick Notes
+// Short notes jotted mid-meeting from the tray or a global shortcut without
+// leaving the meeting window.
+//
+// While a meeting is recording, a note becomes a "quick_note" comment on it
+// (timestamped from the meeting start) and a timeline event, so it shows up
+// in rewind at the moment it was written. With no meeting, the note is parked
+// in the activity log until it is attached to one.
+
+use crate::database::{ActivityLogEntry, DatabaseManager};
+use crate::importance::{score_event, ScoringContext};
+use crate::timeline_builder::{quick_note_event, TimelineEvent};
+use crate::AppState;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+
+/// Comment type and activity category of quick notes
+pub const QUICK_NOTE_TYPE: &str = "quick_note";
+
+/// Label of the mini input window
+const WINDOW_LABEL: &str = "quick-note";
+
+/// Where a quick note ended up
+#[derive(Debug, Clone, Serialize)]
+pub struct QuickNoteSaved {
+    pub ts: DateTime<Utc>,
+    /// Set when the note was attached to the recording meeting
+    pub meeting_id: Option<String>,
+    pub comment_id: Option<String>,
+    /// Activity log id when no meeting was recording
+    pub note_id: Option<i64>,
+}
+
+/// Store a note against the recording meeting, or park it in the activity log
+pub async fn save_quick_note(
+    app: &AppHandle,
+    state: &AppState,
+    text: &str,
+) -> Result<QuickNoteSaved, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Note is empty".to_string());
+    }
+    let ts = Utc::now();
+
+    let Some(meeting_id) = state.timeline_builder.current_meeting_id() else {
+        let note_id = state
+            .database
+            .add_activity(&parked_note(text, ts))
+            .await
+            .map_err(|e| format!("Failed to save note: {}", e))?;
+        log::info!("📝 Quick note saved without a meeting (#{})", note_id);
+        return Ok(QuickNoteSaved {
+            ts,
+            meeting_id: None,
+            comment_id: None,
+            note_id: Some(note_id),
+        });
+    };
+
+    let comment_id = add_note_comment(&state.database, &meeting_id, text, ts).await?;
+    // The recording meeting's timeline is persisted when it stops
+    if let Some(event) = state.timeline_builder.add_quick_note(ts, text) {
+        emit_timeline_event(app, &event);
+    }
+    log::info!("📝 Quick note added to meeting {}", meeting_id);
+
+    Ok(QuickNoteSaved {
+        ts,
+        meeting_id: Some(meeting_id),
+        comment_id: Some(comment_id),
+        note_id: None,
+    })
+}
+
+/// Move a parked note onto a meeting, keeping the time it was written;
+/// returns the new comment id
+pub async fn attach_note(
+    app: &AppHandle,
+    state: &AppState,
+    note_id: i64,
+    meeting_id: &str,
+) -> Result<String, String> {
+    let db = &state.database;
+    let note = db
+        .get_unattached_note(note_id)
+        .await
+        .map_err(|e| format!("Failed to load note: {}", e))?
+        .ok_or("Note not found or already attached")?;
+
+    let comment_id = add_note_comment(db, meeting_id, &note.text, note.ts).await?;
+
+    let recording = state.timeline_builder.current_meeting_id().as_deref() == Some(meeting_id);
+    let event = if recording {
+        state.timeline_builder.add_quick_note(note.ts, &note.text)
+    } else {
+        let mut event = quick_note_event(meeting_id, note.ts, &note.text);
+        // The meeting is already finalized, so score the note on its own
+        let (importance, reason) = score_event(&event, &ScoringContext::default());
+        event.importance = importance;
+        event.importance_reason = Some(reason);
+        db.add_timeline_event(
+            &event.event_id,
+            &event.meeting_id,
+            event.ts,
+            event.event_type.as_str(),
+            &event.title,
+            event.description.as_deref(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            event.importance,
+            event.importance_reason.as_deref(),
+            None,
+        )
+        .await
+        .map_err(|e| format!("Failed to save note to timeline: {}", e))?;
+        Some(event)
+    };
+    if let Some(event) = event {
+        emit_timeline_event(app, &event);
+    }
+
+    db.delete_unattached_note(note_id)
+        .await
+        .map_err(|e| format!("Failed to remove attached note: {}", e))?;
+    Ok(comment_id)
+}
+
+async fn add_note_comment(
+    db: &DatabaseManager,
+    meeting_id: &str,
+    text: &str,
+    ts: DateTime<Utc>,
+) -> Result<String, String> {
+    let meeting = db
+        .get_meeting(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))?
+        .ok_or("Meeting not found")?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    db.add_meeting_comment(
+        &id,
+        meeting_id,
+        text,
+        Some(QUICK_NOTE_TYPE),
+        offset_secs(meeting.started_at, ts),
+        None,
+    )
+    .await
+    .map_err(|e| format!("Failed to add note: {}", e))?;
+    Ok(id)
+}
+
+/// Seconds into the meeting, for the comment's transcript reference; None
+/// for notes written before the meeting started
+fn offset_secs(started_at: DateTime<Utc>, ts: DateTime<Utc>) -> Option<f64> {
+    let ms = (ts - started_at).num_milliseconds();
+    (ms >= 0).then(|| ms as f64 / 1000.0)
+}
+
+fn parked_note(text: &str, ts: DateTime<Utc>) -> ActivityLogEntry {
+    ActivityLogEntry {
+        id: None,
+        start_time: ts,
+        end_time: None,
+        duration_seconds: None,
+        app_name: None,
+        window_title: None,
+        category: QUICK_NOTE_TYPE.to_string(),
+        summary: text.to_string(),
+        focus_area: None,
+        visible_files: None,
+        confidence: None,
+        frame_ids: None,
+        pinecone_id: None,
+        supabase_id: None,
+        synced_at: None,
+        from_cache: false,
+    }
+}
+
+fn emit_timeline_event(app: &AppHandle, event: &TimelineEvent) {
+    if let Err(e) = app.emit("timeline-event", event) {
+        log::error!("Failed to emit timeline-event: {}", e);
+    }
+}
+
+/// Show the mini note input: a small always-on-top window that closes itself
+/// after saving, so the meeting window keeps its place
+pub fn show_quick_note_window(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+        window.show()?;
+        return window.set_focus();
+    }
+    WebviewWindowBuilder::new(
+        app,
+        WINDOW_LABEL,
+        WebviewUrl::App("index.html?window=quick-note".into()),
+    )
+    .title("Quick Note")
+    .inner_size(480.0, 64.0)
+    .resizable(false)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .center()
+    .focused(true)
+    .build()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_offset_from_meeting_start() {
+        let start = Utc::now();
+        assert_eq!(
+            offset_secs(start, start + Duration::milliseconds(90_500)),
+            Some(90.5)
+        );
+        assert_eq!(offset_secs(start, start - Duration::seconds(5)), None);
+
+        let parked = parked_note("follow up on pricing", start);
+        assert_eq!(parked.category, QUICK_NOTE_TYPE);
+        assert_eq!(parked.start_time, start);
+    }
+}
@@ -0,0 +1,158 @@
+// noFriction Meetings - Secret Store
+// API keys and tokens live in the macOS Keychain. The settings table only
+// keeps a sentinel saying the real value is in the Keychain, so the SQLite
+// file (and any copy or backup of it) holds no credentials.
+//
+// Other platforms have no Keychain backend: `store` fails and
+// SettingsManager keeps the plaintext value in SQLite as before.
+//
+// Secret values never go back to the webview; commands return `mask`ed tails
+// or a `SecretStatus` instead.
+
+use serde::Serialize;
+
+/// Settings-table value meaning "look this key up in the Keychain"
+pub const KEYCHAIN_SENTINEL: &str = "__keychain__";
+
+/// Whether secrets are moved out of the settings table on this platform
+pub const KEYCHAIN_AVAILABLE: bool = cfg!(target_os = "macos");
+
+/// Settings holding credentials
+pub const SECRET_KEYS: &[&str] = &[
+    "deepgram_api_key",
+    "gemini_api_key",
+    "gladia_api_key",
+    "google_stt_key_json",
+    "supabase_connection_string",
+    "pinecone_api_key",
+    "ingest_bearer_token",
+    "vlm_bearer_token",
+    "ai_remote_key",
+    "webhook_bearer_token",
+    "webhook_secret",
+    "slack_bot_token",
+    "thebrain_token",
+];
+
+#[cfg(target_os = "macos")]
+const SERVICE: &str = "ai.nofriction.meetings";
+/// errSecItemNotFound
+#[cfg(target_os = "macos")]
+const ITEM_NOT_FOUND: i32 = -25300;
+
+/// What the UI may know about a secret setting
+#[derive(Debug, Clone, Serialize)]
+pub struct SecretStatus {
+    pub key: String,
+    pub is_set: bool,
+    /// "****" plus the last 4 characters (only for values long enough to hide)
+    pub masked: Option<String>,
+    pub in_keychain: bool,
+    pub updated_at: Option<String>,
+}
+
+pub fn is_secret(key: &str) -> bool {
+    SECRET_KEYS.contains(&key)
+}
+
+/// Display form of a secret: "****" plus its last 4 characters when it is
+/// longer than 8, otherwise just "****"
+pub fn mask(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() > 8 {
+        format!(
+            "****{}",
+            chars[chars.len() - 4..].iter().collect::<String>()
+        )
+    } else {
+        "****".to_string()
+    }
+}
+
+/// Whether a value sent back by the UI is a mask rather than a new secret
+pub fn is_masked(value: &str) -> bool {
+    value.starts_with("****")
+}
+
+/// Save `value` under `key` in the Keychain
+#[cfg(target_os = "macos")]
+pub fn store(key: &str, value: &str) -> Result<(), String> {
+    security_framework::passwords::set_generic_password(SERVICE, key, value.as_bytes())
+        .map_err(|e| format!("Keychain write failed for {}: {}", key, e))
+}
+
+/// Read `key` from the Keychain (None when there is no entry)
+#[cfg(target_os = "macos")]
+pub fn load(key: &str) -> Result<Option<String>, String> {
+    match security_framework::passwords::get_generic_password(SERVICE, key) {
+        Ok(bytes) => String::from_utf8(bytes)
+            .map(Some)
+            .map_err(|_| format!("Keychain entry for {} is not UTF-8", key)),
+        Err(e) if e.code() == ITEM_NOT_FOUND => Ok(None),
+        Err(e) => Err(format!("Keychain read failed for {}: {}", key, e)),
+    }
+}
+
+/// Remove `key` from the Keychain (no-op when absent)
+#[cfg(target_os = "macos")]
+pub fn remove(key: &str) -> Result<(), String> {
+    match security_framework::passwords::delete_generic_password(SERVICE, key) {
+        Ok(()) => Ok(()),
+        Err(e) if e.code() == ITEM_NOT_FOUND => Ok(()),
+        Err(e) => Err(format!("Keychain delete failed for {}: {}", key, e)),
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn store(_key: &str, _value: &str) -> Result<(), String> {
+    Err("No keychain on this platform".to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn load(_key: &str) -> Result<Option<String>, String> {
+    Ok(None)
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn remove(_key: &str) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::AppSettings;
+
+    #[test]
+    fn test_responses_never_contain_full_secrets() {
+        // Every secret field configured with a distinctive full value
+        let mut value = serde_json::to_value(AppSettings::with_defaults()).unwrap();
+        let fields = value.as_object_mut().unwrap();
+        let mut configured = Vec::new();
+        for key in SECRET_KEYS {
+            if let Some(field) = fields.get_mut(*key) {
+                let secret = format!("sk-{}-0123456789abcdef", key);
+                *field = serde_json::Value::String(secret.clone());
+                configured.push(secret);
+            }
+        }
+        assert!(
+            configured.len() >= 12,
+            "secret list out of sync with AppSettings"
+        );
+        let settings: AppSettings = serde_json::from_value(value).unwrap();
+
+        // get_settings / get_ai_provider_settings / get_setting / get_secret_status
+        let mut responses = vec![serde_json::to_string(&settings.redacted()).unwrap()];
+        responses.extend(configured.iter().map(|secret| mask(secret)));
+
+        for response in &responses {
+            for secret in &configured {
+                assert!(!response.contains(secret.as_str()), "{} leaked", secret);
+            }
+        }
+        assert_eq!(mask("sk-abcdefgh1234"), "****1234");
+        assert_eq!(mask("short"), "****");
+        assert!(is_masked(&mask("sk-abcdefgh1234")));
+    }
+}
@@ -2,6 +2,9 @@
 // Uses LLM (VLM client) to classify text diffs with semantic understanding
 //
 // Replaces heuristic classification in diff_builder for more accurate results
+//
+// Also provides the capture-time activity classifier for screen states:
+// window/app heuristics only (no network), VLM refinement stays in the scheduler
 
 use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
@@ -9,6 +12,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::diff_builder::ChangeType;
+use crate::state_builder::StateType;
 
 /// Semantic classification result
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -324,6 +328,160 @@ pub struct DiffContext {
     pub previous_summary: Option<String>,
 }
 
+/// Activity category for a screen state
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StateClassification {
+    pub state_type: StateType,
+    /// 0.0 - 1.0: app matches are trusted more than title or text hints
+    pub confidence: f32,
+}
+
+const VIDEO_CALL_APPS: &[&str] = &["zoom.us", "Zoom", "Microsoft Teams", "Webex", "FaceTime"];
+const TERMINAL_APPS: &[&str] = &[
+    "Terminal",
+    "iTerm",
+    "Warp",
+    "Alacritty",
+    "kitty",
+    "WezTerm",
+    "Ghostty",
+    "Hyper",
+];
+const CODING_APPS: &[&str] = &[
+    "Code",
+    "Cursor",
+    "Xcode",
+    "IntelliJ",
+    "PyCharm",
+    "WebStorm",
+    "RustRover",
+    "GoLand",
+    "CLion",
+    "Android Studio",
+    "Sublime Text",
+    "Zed",
+    "Nova",
+];
+const SLIDES_APPS: &[&str] = &["Keynote", "PowerPoint"];
+const DOCUMENT_APPS: &[&str] = &[
+    "Pages", "Word", "TextEdit", "Notes", "Obsidian", "Notion", "Bear", "Preview", "Acrobat",
+];
+const BROWSER_APPS: &[&str] = &[
+    "Safari", "Chrome", "Firefox", "Arc", "Edge", "Brave", "Opera", "Vivaldi",
+];
+const VIDEO_APPS: &[&str] = &["QuickTime Player", "VLC", "IINA"];
+
+/// Window titles of web apps that decide the category inside a browser
+const TITLE_HINTS: &[(&str, StateType)] = &[
+    ("Google Meet", StateType::VideoCall),
+    ("Meet - ", StateType::VideoCall),
+    ("Zoom Meeting", StateType::VideoCall),
+    ("Google Slides", StateType::Slides),
+    ("Google Docs", StateType::Document),
+    ("Google Sheets", StateType::Document),
+    ("GitHub", StateType::Coding),
+    ("GitLab", StateType::Coding),
+    ("YouTube", StateType::Video),
+];
+
+const CODE_EXTENSIONS: &[&str] = &[
+    ".rs", ".ts", ".tsx", ".js", ".py", ".go", ".swift", ".kt", ".java", ".c", ".cpp", ".h", ".rb",
+];
+const DOCUMENT_EXTENSIONS: &[&str] = &[".pdf", ".docx", ".doc", ".md", ".txt", ".pages"];
+
+/// Classify a screen state from its window (and any text already extracted).
+/// Pure string heuristics - cheap enough to run for every new state.
+pub fn classify_state(
+    app_name: Option<&str>,
+    bundle_id: Option<&str>,
+    window_title: Option<&str>,
+    text: Option<&str>,
+) -> StateClassification {
+    let app = app_name.unwrap_or("");
+    let title = window_title.unwrap_or("");
+    let matches_app = |apps: &[&str]| !app.is_empty() && apps.iter().any(|a| app.contains(a));
+    let classified = |state_type, confidence| StateClassification {
+        state_type,
+        confidence,
+    };
+
+    // Dedicated apps are the strongest signal
+    if matches_app(VIDEO_CALL_APPS) {
+        return classified(StateType::VideoCall, 0.9);
+    }
+    if matches_app(TERMINAL_APPS) {
+        return classified(StateType::Terminal, 0.9);
+    }
+    if matches_app(CODING_APPS) || bundle_id.is_some_and(|b| b.starts_with("com.jetbrains.")) {
+        return classified(StateType::Coding, 0.9);
+    }
+    if matches_app(SLIDES_APPS) {
+        return classified(StateType::Slides, 0.9);
+    }
+    if matches_app(DOCUMENT_APPS) {
+        return classified(StateType::Document, 0.85);
+    }
+    if matches_app(VIDEO_APPS) {
+        return classified(StateType::Video, 0.85);
+    }
+
+    // Web apps and file names in the title
+    if let Some((_, state_type)) = TITLE_HINTS.iter().find(|(hint, _)| title.contains(hint)) {
+        return classified(*state_type, 0.75);
+    }
+    let title_lower = title.to_lowercase();
+    let has_extension = |exts: &[&str]| {
+        title_lower
+            .split(|c: char| c.is_whitespace() || c == '—' || c == '|')
+            .any(|word| exts.iter().any(|ext| word.ends_with(ext)))
+    };
+    if has_extension(CODE_EXTENSIONS) {
+        return classified(StateType::Coding, 0.7);
+    }
+    if has_extension(DOCUMENT_EXTENSIONS) {
+        return classified(StateType::Document, 0.7);
+    }
+
+    if matches_app(BROWSER_APPS) {
+        return classified(StateType::Browser, 0.8);
+    }
+
+    // Last resort: what the extracted text looks like
+    if let Some(text) = text.filter(|t| !t.trim().is_empty()) {
+        let lines: Vec<&str> = text.lines().collect();
+        let code_lines = lines
+            .iter()
+            .filter(|l| {
+                let l = l.trim();
+                l.ends_with(';')
+                    || l.ends_with('{')
+                    || l.starts_with("fn ")
+                    || l.starts_with("def ")
+                    || l.starts_with("import ")
+                    || l.contains(" => ")
+            })
+            .count();
+        let prompt_lines = lines
+            .iter()
+            .filter(|l| {
+                let l = l.trim_start();
+                l.starts_with("$ ") || l.starts_with("❯ ")
+            })
+            .count();
+        if code_lines * 3 >= lines.len() {
+            return classified(StateType::Coding, 0.5);
+        }
+        if prompt_lines * 3 >= lines.len() {
+            return classified(StateType::Terminal, 0.5);
+        }
+        if text.split_whitespace().count() > 50 {
+            return classified(StateType::Document, 0.4);
+        }
+    }
+
+    classified(StateType::Other, 0.2)
+}
+
 /// Compute a simple hash for caching
 fn compute_hash(text: &str) -> String {
     use sha2::{Digest, Sha256};
@@ -384,6 +542,37 @@ mod tests {
         assert_eq!(hash1.len(), 16);
     }
 
+    #[test]
+    fn test_classify_state_by_app_and_title() {
+        let by_app = classify_state(Some("iTerm2"), None, Some("~/src"), None);
+        assert_eq!(by_app.state_type, StateType::Terminal);
+        assert!(by_app.confidence >= 0.9);
+
+        // Web apps win over the generic browser category
+        let meet = classify_state(
+            Some("Google Chrome"),
+            None,
+            Some("Meet - abc-defg-hij"),
+            None,
+        );
+        assert_eq!(meet.state_type, StateType::VideoCall);
+        let browsing = classify_state(Some("Safari"), None, Some("Hacker News"), None);
+        assert_eq!(browsing.state_type, StateType::Browser);
+
+        let file = classify_state(None, None, Some("main.rs — nofriction"), None);
+        assert_eq!(file.state_type, StateType::Coding);
+
+        let code_text = "fn main() {\n    let x = 1;\n}";
+        let by_text = classify_state(Some("Unknown"), None, None, Some(code_text));
+        assert_eq!(by_text.state_type, StateType::Coding);
+        assert!(by_text.confidence < by_app.confidence);
+
+        assert_eq!(
+            classify_state(None, None, None, None).state_type,
+            StateType::Other
+        );
+    }
+
     #[test]
     fn test_parse_response() {
         let classifier = SemanticClassifier::new();
@@ -1,10 +1,23 @@
 // noFriction Meetings - Settings Manager
 // Persistent settings storage using SQLite
 
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
+use crate::audio_level::SilenceWarningPolicy;
+use crate::feature_flags::{FeatureFlags, SharedFeatureFlags, FLAG_NAMES};
+use crate::interaction_loop::CheckInPolicy;
+use crate::privacy_filter::PrivacyRules;
+use crate::secret_store::{self, SecretStatus, KEYCHAIN_SENTINEL};
+use crate::transcription::failover::FailoverPolicy;
+use crate::transcription::GlossaryTerm;
+use crate::usage_ledger::UsagePrice;
+use crate::video_recorder::VideoCaptureConfig;
+use crate::vlm_scheduler::VLMSchedulerPolicy;
+
 /// Application settings
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppSettings {
@@ -14,7 +27,13 @@ pub struct AppSettings {
     pub google_stt_key_json: Option<String>,
     pub deepgram_model: Option<String>,
     pub gemini_model: Option<String>,
-    pub transcription_provider: String, // "deepgram", "gemini", "gladia", "google_stt"
+    pub transcription_provider: String, // "deepgram", "gemini", "gladia", "google_stt", "whisper_local"
+    pub transcription_glossary: Vec<GlossaryTerm>, // Jargon/names boosted in every provider
+    pub low_confidence_threshold: f32,  // Final segments below this go to the review queue
+    pub transcription_failover: FailoverPolicy, // Switch provider when the active one dies mid-meeting
+    // Local Whisper settings
+    pub whisper_model_path: Option<String>, // Explicit ggml model file (overrides size)
+    pub whisper_model_size: String,         // "tiny.en", "base.en", "small.en", ...
     pub selected_microphone: Option<String>,
     pub selected_monitor: Option<u32>,
     pub auto_start_recording: bool,
@@ -22,13 +41,25 @@ pub struct AppSettings {
     // Capture mode settings
     pub capture_microphone: bool,
     pub capture_system_audio: bool,
+    pub silence_warning: SilenceWarningPolicy, // Warn when the mic goes dead mid-recording
+    pub audio_recording_enabled: bool, // Archive raw meeting audio under audio/<meeting_id>/
+    pub audio_recording_format: String, // "wav" or "aac" (needs ffmpeg)
     pub capture_screen: bool,
+    pub video_capture: VideoCaptureConfig, // Screen video resolution, fps, bitrate, chunk length
     pub always_on_capture: bool,
+    pub ambient_capture_enabled: bool, // Feature flag: ambient capture may run between meetings
     pub queue_frames_for_vlm: bool,
     pub frame_capture_interval_ms: u32,
+    pub capture_all_monitors: bool, // Every display during meetings, not just the selected one
+    pub adaptive_frame_interval: bool, // Stretch the interval on low battery / high CPU
+    pub adaptive_battery_threshold: u32, // Battery % at or below which capture slows to the max
+    pub adaptive_cpu_budget_ms: u32, // Processing time per frame before stretching
+    pub adaptive_max_factor: f32,   // Never stretch the configured interval beyond this
     // VLM auto-processing settings
     pub vlm_auto_process: bool,
     pub vlm_process_interval_secs: u32,
+    pub vlm_concurrency: u32, // In-flight VLM requests per batch
+    pub vlm_batch_size: u32,  // Frames per VLM request (1 = no batching)
     // AI chat settings
     pub ai_chat_model: Option<String>,
     // Activity theme settings
@@ -43,10 +74,18 @@ pub struct AppSettings {
     pub pinecone_api_key: Option<String>,
     pub pinecone_index_host: Option<String>,
     pub pinecone_namespace: Option<String>,
+    // Per-kind namespaces (unset = "<pinecone_namespace>-<kind>")
+    pub pinecone_namespace_transcripts: Option<String>,
+    pub pinecone_namespace_activities: Option<String>,
+    pub pinecone_namespace_conversations: Option<String>,
+    pub pinecone_namespace_snapshots: Option<String>,
+    pub vector_backend: String, // "pinecone" or "local" (SQLite + local embeddings)
+    pub sync_worker_enabled: bool, // Background upload of activities/conversations/indexes
     // Intelligence Pipeline settings
     pub enable_ingest: Option<bool>,
     pub ingest_base_url: Option<String>,
     pub ingest_bearer_token: Option<String>,
+    pub ingest_bandwidth_kbps: u32, // Ingest upload cap in kilobits/sec (0 = unlimited)
     // VLM API settings (centralized service)
     pub vlm_base_url: Option<String>,
     pub vlm_bearer_token: Option<String>,
@@ -60,16 +99,53 @@ pub struct AppSettings {
     // Accessibility capture settings
     pub accessibility_capture_enabled: bool,
     pub accessibility_capture_interval_secs: u32,
+    pub ocr_fallback_enabled: bool, // OCR the latest keyframe when accessibility fails
     // AI Provider settings
     pub ai_provider: String, // "local" or "remote"
     pub ai_remote_url: Option<String>,
     pub ai_remote_key: Option<String>,
+    pub ai_remote_model: Option<String>, // Model sent to the remote endpoint (None = preset model)
+    pub ai_requests_per_minute: u32, // Shared chat/completion budget across AI callers (0 = unlimited)
     // Session Mode settings
-    pub session_mode: String, // "standard" or "dork" (study mode)
+    pub session_mode: String,            // "standard" or "dork" (study mode)
+    pub dork_app_allowlist: Vec<String>, // Apps whose captured text feeds a dork session (empty = all)
     // Obsidian Vault settings
     pub obsidian_vault_path: Option<String>,
     pub obsidian_auto_export: bool,
     pub obsidian_template: String, // "default" or "zettelkasten"
+    // Meeting intelligence settings
+    pub auto_title_meetings: bool, // AI-generated title when recording stops
+    pub semantic_topics_enabled: bool, // Embedding-based topic segmentation on stop (costs embedding calls)
+    pub episode_summary_min_secs: u32, // Episodes shorter than this are not summarized on stop
+    pub export_template_id: Option<String>, // Prompt used as the vault export template (None = builtin layout)
+    pub vault_conflict_policy: String, // "merge" or "refuse" when an exported note was edited in the vault
+    pub vault_series_folders: bool,    // Auto-export files series meetings under the series' topic
+    pub digest_enabled: bool,          // Write the evening digest note to the vault
+    pub attendee_intel_staleness_days: u32, // Reuse vault person/company notes younger than this
+    pub selected_calendars: Vec<String>, // EventKit calendar ids for detection and intel (empty = all)
+    pub webhook_url: Option<String>,     // Outbound CRM webhook endpoint
+    pub webhook_bearer_token: Option<String>, // Sent as Authorization: Bearer
+    pub webhook_secret: Option<String>,  // HMAC-SHA256 payload signing secret
+    pub webhook_events: String,          // Comma-separated event types to send
+    pub slack_bot_token: Option<String>, // xoxb- token for "Send to Slack"
+    pub digest_time: String,             // Local "HH:MM" after which the daily digest is written
+    // Data safety settings
+    pub require_archive_before_delete: bool, // Soft-delete-first policy
+    pub frame_retention_days: u32,           // Prune keyframes older than this (0 = keep)
+    pub frame_retention_max_gb: f32,         // Cap on keyframe storage (0 = no cap)
+    pub audit_retention_days: u32,           // Compact audit entries older than this (0 = keep)
+    pub auto_start_on_meeting_detected: bool, // Start recording on detection instead of suggesting
+    pub auto_stop_recording: bool,           // Stop recording when the meeting looks over
+    pub auto_stop_idle_minutes: u32,         // Grace period with no meeting app/audio
+    pub auto_stop_after_event_minutes: u32,  // Grace period after the calendar event ends
+    pub ambient_buffer_minutes: u32,         // Rolling ambient buffer length (0 = off)
+    pub ambient_idle_timeout_mins: u32,      // Idle minutes before ambient pauses (0 = never)
+    pub check_in_policy: CheckInPolicy,      // "Still capturing?" check-ins during ambient capture
+    pub privacy_rules: PrivacyRules,         // Apps/window titles never captured
+    pub redaction_mode: String,              // PII in cloud sync: off | mask | drop_segment
+    pub usage_prices: Vec<UsagePrice>,       // Per-provider unit prices for usage cost estimates
+    pub vlm_scheduler_policy: VLMSchedulerPolicy, // Hourly budgets, quiet hours, battery guard
+    pub global_shortcuts: BTreeMap<String, String>, // Action → accelerator ("" = unbound, missing = default)
 }
 
 impl AppSettings {
@@ -83,6 +159,15 @@ impl AppSettings {
             deepgram_model: Some("nova-3".to_string()),
             gemini_model: Some("models/gemini-2.0-flash-exp".to_string()),
             transcription_provider: "deepgram".to_string(),
+            whisper_model_path: None,
+            whisper_model_size: "base.en".to_string(),
+            transcription_glossary: Vec::new(),
+            low_confidence_threshold: 0.6,
+            transcription_failover: FailoverPolicy::default(),
+            silence_warning: SilenceWarningPolicy::default(),
+            audio_recording_enabled: false,
+            audio_recording_format: "wav".to_string(),
+            video_capture: VideoCaptureConfig::default(),
             selected_microphone: None,
             selected_monitor: None,
             auto_start_recording: false,
@@ -91,10 +176,18 @@ impl AppSettings {
             capture_system_audio: true,              // System audio ON for meeting capture
             capture_screen: false,                   // Screen capture OFF by default (reduces CPU)
             always_on_capture: false,                // Not always-on by default
+            ambient_capture_enabled: true,           // Ambient capture allowed unless flagged off
             queue_frames_for_vlm: false,             // VLM OFF by default (saves resources)
             frame_capture_interval_ms: 5000,         // 5 sec instead of 1 (5x less disk I/O)
+            capture_all_monitors: false,             // Selected monitor only
+            adaptive_frame_interval: false,          // Fixed interval unless enabled
+            adaptive_battery_threshold: 30,          // Slow down at 30% on battery
+            adaptive_cpu_budget_ms: 250,             // Per-frame processing budget
+            adaptive_max_factor: 5.0,                // e.g. 1s -> 5s at most
             vlm_auto_process: false,                 // Auto-processing OFF by default
             vlm_process_interval_secs: 120,          // 2 minutes default interval
+            vlm_concurrency: 3,                      // 3 frames in flight
+            vlm_batch_size: 4,                       // 4 frames per request
             ai_chat_model: None,                     // Will use first available model
             active_theme: "prospecting".to_string(), // Default theme
             prospecting_interval_ms: 1500,           // 1.5 seconds
@@ -106,9 +199,16 @@ impl AppSettings {
             pinecone_api_key: None,
             pinecone_index_host: None,
             pinecone_namespace: Some("default".to_string()),
+            pinecone_namespace_transcripts: None,
+            pinecone_namespace_activities: None,
+            pinecone_namespace_conversations: None,
+            pinecone_namespace_snapshots: None,
+            vector_backend: "pinecone".to_string(),
+            sync_worker_enabled: true,
             enable_ingest: Some(false), // Disabled by default
             ingest_base_url: None,
             ingest_bearer_token: None,
+            ingest_bandwidth_kbps: 0,
             vlm_base_url: Some("https://7wk6vrq9achr2djw.caas.targon.com".to_string()), // TheBrain Cloud API
             vlm_bearer_token: None,
             vlm_model_primary: Some("qwen2.5vl:7b".to_string()),
@@ -121,28 +221,107 @@ impl AppSettings {
             // Accessibility capture defaults
             accessibility_capture_enabled: false, // OFF by default
             accessibility_capture_interval_secs: 10, // 10 seconds
+            ocr_fallback_enabled: true,           // OCR keyframes when accessibility fails
             // AI Provider defaults
             ai_provider: "remote".to_string(), // Default to remote for reliability
             ai_remote_url: None,
             ai_remote_key: None,
+            ai_remote_model: None,
+            ai_requests_per_minute: crate::ai_client::DEFAULT_REQUESTS_PER_MINUTE,
             // Session Mode defaults
             session_mode: "standard".to_string(), // Default to standard recording
+            dork_app_allowlist: crate::dork_mode::default_dork_app_allowlist(),
             // Obsidian Vault defaults
             obsidian_vault_path: None,
             obsidian_auto_export: false,
             obsidian_template: "default".to_string(),
+            // Meeting intelligence defaults
+            auto_title_meetings: true,
+            semantic_topics_enabled: false,
+            episode_summary_min_secs: 30,
+            export_template_id: None,
+            vault_conflict_policy: "merge".to_string(),
+            vault_series_folders: false,
+            digest_enabled: false,
+            attendee_intel_staleness_days: 30,
+            selected_calendars: Vec::new(),
+            webhook_url: None,
+            webhook_bearer_token: None,
+            webhook_secret: None,
+            webhook_events: crate::integrations::WEBHOOK_EVENTS.join(","),
+            slack_bot_token: None,
+            digest_time: crate::digest::DEFAULT_DIGEST_TIME.to_string(),
+            // Data safety defaults
+            require_archive_before_delete: false,
+            frame_retention_days: 0,
+            frame_retention_max_gb: 0.0,
+            audit_retention_days: 0,
+            auto_start_on_meeting_detected: false,
+            auto_stop_recording: false,
+            auto_stop_idle_minutes: 5,
+            auto_stop_after_event_minutes: 10,
+            ambient_buffer_minutes: crate::ambient_capture::DEFAULT_BUFFER_MINUTES,
+            ambient_idle_timeout_mins: crate::ambient_capture::DEFAULT_IDLE_TIMEOUT_MINS,
+            check_in_policy: CheckInPolicy::default(),
+            privacy_rules: PrivacyRules::default(),
+            redaction_mode: "off".to_string(),
+            usage_prices: Vec::new(),
+            vlm_scheduler_policy: VLMSchedulerPolicy::default(),
+            global_shortcuts: BTreeMap::new(),
         }
     }
+
+    /// Copy safe to hand to the webview: every secret is cleared (use
+    /// `get_secret_status` for set/unset and masked tails)
+    pub fn redacted(mut self) -> Self {
+        for secret in [
+            &mut self.deepgram_api_key,
+            &mut self.gemini_api_key,
+            &mut self.gladia_api_key,
+            &mut self.google_stt_key_json,
+            &mut self.supabase_connection_string,
+            &mut self.pinecone_api_key,
+            &mut self.ingest_bearer_token,
+            &mut self.vlm_bearer_token,
+            &mut self.ai_remote_key,
+            &mut self.webhook_bearer_token,
+            &mut self.webhook_secret,
+            &mut self.slack_bot_token,
+        ] {
+            *secret = None;
+        }
+        self
+    }
 }
 
-/// Settings manager for persistent storage
+/// Settings manager for persistent storage. Secret keys (see
+/// `secret_store::SECRET_KEYS`) are kept in the Keychain where available.
 pub struct SettingsManager {
     pool: Arc<SqlitePool>,
+    flags: SharedFeatureFlags,
+    /// Secrets already read from the Keychain
+    secrets: Mutex<HashMap<String, String>>,
 }
 
 impl SettingsManager {
     pub fn new(pool: Arc<SqlitePool>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            flags: Arc::new(parking_lot::RwLock::new(FeatureFlags::default())),
+            secrets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Live feature flags, kept in sync with every write through `set`
+    pub fn feature_flags(&self) -> SharedFeatureFlags {
+        self.flags.clone()
+    }
+
+    /// Reload the live feature flags from the stored settings
+    pub async fn load_feature_flags(&self) -> Result<FeatureFlags, sqlx::Error> {
+        let flags = FeatureFlags::from_settings(&self.get_all().await?);
+        *self.flags.write() = flags;
+        Ok(flags)
     }
 
     /// Initialize settings table
@@ -168,11 +347,123 @@ impl SettingsManager {
             .fetch_optional(self.pool.as_ref())
             .await?;
 
-        Ok(result.map(|(v,)| v))
+        Ok(match result {
+            Some((v,)) if v == KEYCHAIN_SENTINEL => self.load_secret(key),
+            other => other.map(|(v,)| v),
+        })
+    }
+
+    fn load_secret(&self, key: &str) -> Option<String> {
+        if let Some(value) = self.secrets.lock().get(key) {
+            return Some(value.clone());
+        }
+        match secret_store::load(key) {
+            Ok(Some(value)) => {
+                self.secrets.lock().insert(key.to_string(), value.clone());
+                Some(value)
+            }
+            Ok(None) => {
+                log::warn!(
+                    "{} is marked as stored in the Keychain but has no entry",
+                    key
+                );
+                None
+            }
+            Err(e) => {
+                log::warn!("{}", e);
+                None
+            }
+        }
+    }
+
+    /// Move a secret into the Keychain, returning what the settings table
+    /// should hold instead (the plaintext value when the Keychain is unavailable)
+    fn store_secret<'a>(&self, key: &str, value: &'a str) -> &'a str {
+        if !secret_store::KEYCHAIN_AVAILABLE || value.is_empty() {
+            self.secrets.lock().remove(key);
+            return value;
+        }
+        match secret_store::store(key, value) {
+            Ok(()) => {
+                self.secrets
+                    .lock()
+                    .insert(key.to_string(), value.to_string());
+                KEYCHAIN_SENTINEL
+            }
+            Err(e) => {
+                log::warn!("{}; keeping {} in the settings table", e, key);
+                self.secrets.lock().remove(key);
+                value
+            }
+        }
+    }
+
+    /// Set/unset state, masked tail and last update of every secret setting
+    pub async fn secret_status(&self) -> Result<Vec<SecretStatus>, sqlx::Error> {
+        let rows: Vec<(String, String, Option<String>)> =
+            sqlx::query_as("SELECT key, value, updated_at FROM settings")
+                .fetch_all(self.pool.as_ref())
+                .await?;
+        let rows: HashMap<String, (String, Option<String>)> = rows
+            .into_iter()
+            .map(|(key, value, updated_at)| (key, (value, updated_at)))
+            .collect();
+
+        Ok(secret_store::SECRET_KEYS
+            .iter()
+            .map(|key| {
+                let (stored, updated_at) = rows.get(*key).cloned().unzip();
+                let in_keychain = stored.as_deref() == Some(KEYCHAIN_SENTINEL);
+                let value = match stored {
+                    Some(_) if in_keychain => self.load_secret(key),
+                    other => other,
+                }
+                .filter(|v| !v.is_empty());
+                SecretStatus {
+                    key: key.to_string(),
+                    is_set: value.is_some(),
+                    masked: value.as_deref().map(secret_store::mask),
+                    in_keychain,
+                    updated_at: updated_at.flatten(),
+                }
+            })
+            .collect())
+    }
+
+    /// Move plaintext secrets left by older versions into the Keychain.
+    /// Returns how many were moved.
+    pub async fn migrate_secrets(&self) -> Result<usize, sqlx::Error> {
+        if !secret_store::KEYCHAIN_AVAILABLE {
+            return Ok(0);
+        }
+        let rows: Vec<(String, String)> = sqlx::query_as("SELECT key, value FROM settings")
+            .fetch_all(self.pool.as_ref())
+            .await?;
+
+        let mut moved = 0;
+        for (key, value) in rows {
+            if !secret_store::is_secret(&key) || value.is_empty() || value == KEYCHAIN_SENTINEL {
+                continue;
+            }
+            if self.store_secret(&key, &value) == KEYCHAIN_SENTINEL {
+                sqlx::query("UPDATE settings SET value = ? WHERE key = ?")
+                    .bind(KEYCHAIN_SENTINEL)
+                    .bind(&key)
+                    .execute(self.pool.as_ref())
+                    .await?;
+                moved += 1;
+            }
+        }
+        Ok(moved)
     }
 
     /// Set a setting value
     pub async fn set(&self, key: &str, value: &str) -> Result<(), sqlx::Error> {
+        let stored = if secret_store::is_secret(key) {
+            self.store_secret(key, value)
+        } else {
+            value
+        };
         sqlx::query(
             r#"
             INSERT INTO settings (key, value, updated_at)
@@ -181,14 +472,42 @@ impl SettingsManager {
             "#,
         )
         .bind(key)
-        .bind(value)
+        .bind(stored)
         .execute(self.pool.as_ref())
         .await?;
+        if FLAG_NAMES.contains(&key) {
+            let _ = self.flags.write().set(key, value == "true");
+        }
         Ok(())
     }
 
+    /// Every stored key/value row, with Keychain secrets resolved
+    pub async fn get_all_values(&self) -> Result<Vec<(String, String)>, sqlx::Error> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT key, value FROM settings ORDER BY key")
+                .fetch_all(self.pool.as_ref())
+                .await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(key, value)| {
+                if value == KEYCHAIN_SENTINEL {
+                    let value = self.load_secret(&key)?;
+                    Some((key, value))
+                } else {
+                    Some((key, value))
+                }
+            })
+            .collect())
+    }
+
     /// Delete a setting
     pub async fn delete(&self, key: &str) -> Result<(), sqlx::Error> {
+        if secret_store::is_secret(key) {
+            self.secrets.lock().remove(key);
+            if let Err(e) = secret_store::remove(key) {
+                log::warn!("{}", e);
+            }
+        }
         sqlx::query("DELETE FROM settings WHERE key = ?")
             .bind(key)
             .execute(self.pool.as_ref())
@@ -221,6 +540,21 @@ impl SettingsManager {
         if let Some(prov) = self.get("transcription_provider").await? {
             settings.transcription_provider = prov;
         }
+        if let Some(v) = self.get("whisper_model_path").await? {
+            settings.whisper_model_path = Some(v);
+        }
+        if let Some(v) = self.get("whisper_model_size").await? {
+            settings.whisper_model_size = v;
+        }
+        if let Some(v) = self.get("transcription_glossary").await? {
+            settings.transcription_glossary = serde_json::from_str(&v).unwrap_or_default();
+        }
+        if let Some(v) = self.get("low_confidence_threshold").await? {
+            settings.low_confidence_threshold = v.parse().unwrap_or(0.6);
+        }
+        if let Some(v) = self.get("transcription_failover").await? {
+            settings.transcription_failover = serde_json::from_str(&v).unwrap_or_default();
+        }
         if let Some(mic) = self.get("selected_microphone").await? {
             settings.selected_microphone = Some(mic);
         }
@@ -240,18 +574,48 @@ impl SettingsManager {
         if let Some(v) = self.get("capture_system_audio").await? {
             settings.capture_system_audio = v == "true";
         }
+        if let Some(v) = self.get("silence_warning").await? {
+            settings.silence_warning = serde_json::from_str(&v).unwrap_or_default();
+        }
+        if let Some(v) = self.get("audio_recording_enabled").await? {
+            settings.audio_recording_enabled = v == "true";
+        }
+        if let Some(v) = self.get("audio_recording_format").await? {
+            settings.audio_recording_format = v;
+        }
         if let Some(v) = self.get("capture_screen").await? {
             settings.capture_screen = v == "true";
         }
+        if let Some(v) = self.get("video_capture").await? {
+            settings.video_capture = serde_json::from_str(&v).unwrap_or_default();
+        }
         if let Some(v) = self.get("always_on_capture").await? {
             settings.always_on_capture = v == "true";
         }
+        if let Some(v) = self.get("ambient_capture_enabled").await? {
+            settings.ambient_capture_enabled = v == "true";
+        }
         if let Some(v) = self.get("queue_frames_for_vlm").await? {
             settings.queue_frames_for_vlm = v == "true";
         }
         if let Some(v) = self.get("frame_capture_interval_ms").await? {
             settings.frame_capture_interval_ms = v.parse().unwrap_or(1000);
         }
+        if let Some(v) = self.get("capture_all_monitors").await? {
+            settings.capture_all_monitors = v == "true";
+        }
+        if let Some(v) = self.get("adaptive_frame_interval").await? {
+            settings.adaptive_frame_interval = v == "true";
+        }
+        if let Some(v) = self.get("adaptive_battery_threshold").await? {
+            settings.adaptive_battery_threshold = v.parse().unwrap_or(30);
+        }
+        if let Some(v) = self.get("adaptive_cpu_budget_ms").await? {
+            settings.adaptive_cpu_budget_ms = v.parse().unwrap_or(250);
+        }
+        if let Some(v) = self.get("adaptive_max_factor").await? {
+            settings.adaptive_max_factor = v.parse().unwrap_or(5.0);
+        }
         // Knowledge base settings
         if let Some(v) = self.get("supabase_connection_string").await? {
             settings.supabase_connection_string = Some(v);
@@ -265,6 +629,24 @@ impl SettingsManager {
         if let Some(v) = self.get("pinecone_namespace").await? {
             settings.pinecone_namespace = Some(v);
         }
+        if let Some(v) = self.get("pinecone_namespace_transcripts").await? {
+            settings.pinecone_namespace_transcripts = Some(v);
+        }
+        if let Some(v) = self.get("pinecone_namespace_activities").await? {
+            settings.pinecone_namespace_activities = Some(v);
+        }
+        if let Some(v) = self.get("pinecone_namespace_conversations").await? {
+            settings.pinecone_namespace_conversations = Some(v);
+        }
+        if let Some(v) = self.get("pinecone_namespace_snapshots").await? {
+            settings.pinecone_namespace_snapshots = Some(v);
+        }
+        if let Some(v) = self.get("vector_backend").await? {
+            settings.vector_backend = v;
+        }
+        if let Some(v) = self.get("sync_worker_enabled").await? {
+            settings.sync_worker_enabled = v == "true";
+        }
         // VLM auto-processing settings
         if let Some(v) = self.get("vlm_auto_process").await? {
             settings.vlm_auto_process = v == "true";
@@ -272,6 +654,12 @@ impl SettingsManager {
         if let Some(v) = self.get("vlm_process_interval_secs").await? {
             settings.vlm_process_interval_secs = v.parse().unwrap_or(120);
         }
+        if let Some(v) = self.get("vlm_concurrency").await? {
+            settings.vlm_concurrency = v.parse().unwrap_or(3);
+        }
+        if let Some(v) = self.get("vlm_batch_size").await? {
+            settings.vlm_batch_size = v.parse().unwrap_or(4);
+        }
         // AI chat model
         if let Some(v) = self.get("ai_chat_model").await? {
             settings.ai_chat_model = Some(v);
@@ -306,6 +694,9 @@ impl SettingsManager {
         if let Some(v) = self.get("ingest_bearer_token").await? {
             settings.ingest_bearer_token = Some(v);
         }
+        if let Some(v) = self.get("ingest_bandwidth_kbps").await? {
+            settings.ingest_bandwidth_kbps = v.parse().unwrap_or(0);
+        }
 
         // VLM API settings (centralized service)
         if let Some(v) = self.get("vlm_base_url").await? {
@@ -321,6 +712,11 @@ impl SettingsManager {
             settings.vlm_model_fallback = Some(v);
         }
 
+        // Stateful Screen Ingest
+        if let Some(v) = self.get("dedup_enabled").await? {
+            settings.dedup_enabled = Some(v == "true");
+        }
+
         // Accessibility capture settings
         if let Some(v) = self.get("accessibility_capture_enabled").await? {
             settings.accessibility_capture_enabled = v == "true";
@@ -328,6 +724,9 @@ impl SettingsManager {
         if let Some(v) = self.get("accessibility_capture_interval_secs").await? {
             settings.accessibility_capture_interval_secs = v.parse().unwrap_or(10);
         }
+        if let Some(v) = self.get("ocr_fallback_enabled").await? {
+            settings.ocr_fallback_enabled = v == "true";
+        }
 
         // AI Provider settings
         if let Some(v) = self.get("ai_provider").await? {
@@ -339,11 +738,23 @@ impl SettingsManager {
         if let Some(v) = self.get("ai_remote_key").await? {
             settings.ai_remote_key = Some(v);
         }
+        if let Some(v) = self.get("ai_remote_model").await? {
+            settings.ai_remote_model = Some(v);
+        }
+        if let Some(v) = self.get("ai_requests_per_minute").await? {
+            settings.ai_requests_per_minute = v
+                .parse()
+                .unwrap_or(crate::ai_client::DEFAULT_REQUESTS_PER_MINUTE);
+        }
 
         // Session Mode settings
         if let Some(v) = self.get("session_mode").await? {
             settings.session_mode = v;
         }
+        if let Some(v) = self.get("dork_app_allowlist").await? {
+            settings.dork_app_allowlist = serde_json::from_str(&v)
+                .unwrap_or_else(|_| crate::dork_mode::default_dork_app_allowlist());
+        }
 
         // Obsidian Vault settings
         if let Some(v) = self.get("obsidian_vault_path").await? {
@@ -356,6 +767,107 @@ impl SettingsManager {
             settings.obsidian_template = v;
         }
 
+        // Meeting intelligence settings
+        if let Some(v) = self.get("auto_title_meetings").await? {
+            settings.auto_title_meetings = v == "true";
+        }
+        if let Some(v) = self.get("semantic_topics_enabled").await? {
+            settings.semantic_topics_enabled = v == "true";
+        }
+        if let Some(v) = self.get("episode_summary_min_secs").await? {
+            settings.episode_summary_min_secs = v.parse().unwrap_or(30);
+        }
+        if let Some(v) = self.get("export_template_id").await? {
+            settings.export_template_id = Some(v);
+        }
+        if let Some(v) = self.get("vault_conflict_policy").await? {
+            settings.vault_conflict_policy = v;
+        }
+        if let Some(v) = self.get("vault_series_folders").await? {
+            settings.vault_series_folders = v == "true";
+        }
+        if let Some(v) = self.get("digest_enabled").await? {
+            settings.digest_enabled = v == "true";
+        }
+        if let Some(v) = self.get("digest_time").await? {
+            settings.digest_time = v;
+        }
+        if let Some(v) = self.get("attendee_intel_staleness_days").await? {
+            settings.attendee_intel_staleness_days = v.parse().unwrap_or(30);
+        }
+        if let Some(v) = self.get("selected_calendars").await? {
+            settings.selected_calendars = serde_json::from_str(&v).unwrap_or_default();
+        }
+        if let Some(v) = self.get("webhook_url").await? {
+            settings.webhook_url = Some(v);
+        }
+        if let Some(v) = self.get("webhook_bearer_token").await? {
+            settings.webhook_bearer_token = Some(v);
+        }
+        if let Some(v) = self.get("webhook_secret").await? {
+            settings.webhook_secret = Some(v);
+        }
+        if let Some(v) = self.get("webhook_events").await? {
+            settings.webhook_events = v;
+        }
+        if let Some(v) = self.get("slack_bot_token").await? {
+            settings.slack_bot_token = Some(v);
+        }
+
+        // Data safety settings
+        if let Some(v) = self.get("require_archive_before_delete").await? {
+            settings.require_archive_before_delete = v == "true";
+        }
+        if let Some(v) = self.get("frame_retention_days").await? {
+            settings.frame_retention_days = v.parse().unwrap_or(0);
+        }
+        if let Some(v) = self.get("frame_retention_max_gb").await? {
+            settings.frame_retention_max_gb = v.parse().unwrap_or(0.0);
+        }
+        if let Some(v) = self.get("audit_retention_days").await? {
+            settings.audit_retention_days = v.parse().unwrap_or(0);
+        }
+        if let Some(v) = self.get("auto_start_on_meeting_detected").await? {
+            settings.auto_start_on_meeting_detected = v == "true";
+        }
+        if let Some(v) = self.get("auto_stop_recording").await? {
+            settings.auto_stop_recording = v == "true";
+        }
+        if let Some(v) = self.get("auto_stop_idle_minutes").await? {
+            settings.auto_stop_idle_minutes = v.parse().unwrap_or(5);
+        }
+        if let Some(v) = self.get("auto_stop_after_event_minutes").await? {
+            settings.auto_stop_after_event_minutes = v.parse().unwrap_or(10);
+        }
+        if let Some(v) = self.get("ambient_buffer_minutes").await? {
+            settings.ambient_buffer_minutes = v
+                .parse()
+                .unwrap_or(crate::ambient_capture::DEFAULT_BUFFER_MINUTES);
+        }
+        if let Some(v) = self.get("ambient_idle_timeout_mins").await? {
+            settings.ambient_idle_timeout_mins = v
+                .parse()
+                .unwrap_or(crate::ambient_capture::DEFAULT_IDLE_TIMEOUT_MINS);
+        }
+        if let Some(v) = self.get("check_in_policy").await? {
+            settings.check_in_policy = serde_json::from_str(&v).unwrap_or_default();
+        }
+        if let Some(v) = self.get("privacy_rules").await? {
+            settings.privacy_rules = serde_json::from_str(&v).unwrap_or_default();
+        }
+        if let Some(v) = self.get("redaction_mode").await? {
+            settings.redaction_mode = v;
+        }
+        if let Some(v) = self.get("usage_prices").await? {
+            settings.usage_prices = serde_json::from_str(&v).unwrap_or_default();
+        }
+        if let Some(v) = self.get("vlm_scheduler_policy").await? {
+            settings.vlm_scheduler_policy = serde_json::from_str(&v).unwrap_or_default();
+        }
+        if let Some(v) = self.get("global_shortcuts").await? {
+            settings.global_shortcuts = serde_json::from_str(&v).unwrap_or_default();
+        }
+
         Ok(settings)
     }
 
@@ -429,6 +941,44 @@ impl SettingsManager {
         self.set("transcription_provider", provider).await
     }
 
+    /// Set local Whisper model path (empty string clears it)
+    pub async fn set_whisper_model_path(&self, path: &str) -> Result<(), sqlx::Error> {
+        if path.is_empty() {
+            self.delete("whisper_model_path").await
+        } else {
+            self.set("whisper_model_path", path).await
+        }
+    }
+
+    /// Set local Whisper model size (used when no explicit path is set)
+    pub async fn set_whisper_model_size(&self, size: &str) -> Result<(), sqlx::Error> {
+        self.set("whisper_model_size", size).await
+    }
+
+    /// Save transcription glossary (stored as JSON)
+    pub async fn set_transcription_glossary(
+        &self,
+        terms: &[GlossaryTerm],
+    ) -> Result<(), sqlx::Error> {
+        let json = serde_json::to_string(terms).unwrap_or_else(|_| "[]".to_string());
+        self.set("transcription_glossary", &json).await
+    }
+
+    /// Set confidence threshold for flagging transcripts for review
+    pub async fn set_low_confidence_threshold(&self, threshold: f32) -> Result<(), sqlx::Error> {
+        self.set("low_confidence_threshold", &threshold.to_string())
+            .await
+    }
+
+    /// Save transcription failover policy (stored as JSON)
+    pub async fn set_transcription_failover(
+        &self,
+        policy: &FailoverPolicy,
+    ) -> Result<(), sqlx::Error> {
+        let json = serde_json::to_string(policy).unwrap_or_else(|_| "{}".to_string());
+        self.set("transcription_failover", &json).await
+    }
+
     /// Save selected microphone
     pub async fn set_selected_microphone(&self, mic_id: &str) -> Result<(), sqlx::Error> {
         self.set("selected_microphone", mic_id).await
@@ -444,6 +994,36 @@ impl SettingsManager {
         self.set("selected_monitor", &monitor_id.to_string()).await
     }
 
+    /// Save multi-monitor capture toggle
+    pub async fn set_capture_all_monitors(&self, enabled: bool) -> Result<(), sqlx::Error> {
+        self.set(
+            "capture_all_monitors",
+            if enabled { "true" } else { "false" },
+        )
+        .await
+    }
+
+    /// Save adaptive frame interval settings
+    pub async fn set_adaptive_frame_interval(
+        &self,
+        enabled: bool,
+        battery_threshold: u32,
+        cpu_budget_ms: u32,
+        max_factor: f32,
+    ) -> Result<(), sqlx::Error> {
+        self.set(
+            "adaptive_frame_interval",
+            if enabled { "true" } else { "false" },
+        )
+        .await?;
+        self.set("adaptive_battery_threshold", &battery_threshold.to_string())
+            .await?;
+        self.set("adaptive_cpu_budget_ms", &cpu_budget_ms.to_string())
+            .await?;
+        self.set("adaptive_max_factor", &max_factor.to_string())
+            .await
+    }
+
     /// Get selected monitor
     pub async fn get_selected_monitor(&self) -> Result<Option<u32>, sqlx::Error> {
         let value = self.get("selected_monitor").await?;
@@ -483,6 +1063,210 @@ impl SettingsManager {
         self.set("obsidian_template", template).await
     }
 
+    /// Set soft-delete-first policy (meetings must be archived before deletion)
+    pub async fn set_require_archive_before_delete(
+        &self,
+        enabled: bool,
+    ) -> Result<(), sqlx::Error> {
+        self.set(
+            "require_archive_before_delete",
+            if enabled { "true" } else { "false" },
+        )
+        .await
+    }
+
+    /// Set keyframe retention limits (0 disables either limit)
+    pub async fn set_frame_retention(&self, days: u32, max_gb: f32) -> Result<(), sqlx::Error> {
+        self.set("frame_retention_days", &days.to_string()).await?;
+        self.set("frame_retention_max_gb", &max_gb.max(0.0).to_string())
+            .await
+    }
+
+    /// Set how long audit entries are kept before daily compaction (0 = keep)
+    pub async fn set_audit_retention_days(&self, days: u32) -> Result<(), sqlx::Error> {
+        self.set("audit_retention_days", &days.to_string()).await
+    }
+
+    /// Set whether detected meetings start recording automatically
+    pub async fn set_auto_start_on_meeting_detected(
+        &self,
+        enabled: bool,
+    ) -> Result<(), sqlx::Error> {
+        self.set(
+            "auto_start_on_meeting_detected",
+            if enabled { "true" } else { "false" },
+        )
+        .await
+    }
+
+    /// Set auto-stop toggle and its grace periods (minutes)
+    pub async fn set_auto_stop_recording(
+        &self,
+        enabled: bool,
+        idle_minutes: u32,
+        after_event_minutes: u32,
+    ) -> Result<(), sqlx::Error> {
+        self.set(
+            "auto_stop_recording",
+            if enabled { "true" } else { "false" },
+        )
+        .await?;
+        self.set("auto_stop_idle_minutes", &idle_minutes.max(1).to_string())
+            .await?;
+        self.set(
+            "auto_stop_after_event_minutes",
+            &after_event_minutes.to_string(),
+        )
+        .await
+    }
+
+    /// Set rolling ambient buffer length in minutes (0 disables it)
+    pub async fn set_ambient_buffer_minutes(&self, minutes: u32) -> Result<(), sqlx::Error> {
+        self.set("ambient_buffer_minutes", &minutes.to_string())
+            .await
+    }
+
+    /// Set minutes of inactivity before ambient capture pauses (0 disables it)
+    pub async fn set_ambient_idle_timeout_mins(&self, minutes: u32) -> Result<(), sqlx::Error> {
+        self.set("ambient_idle_timeout_mins", &minutes.to_string())
+            .await
+    }
+
+    /// Save the ambient capture check-in policy (stored as JSON)
+    pub async fn set_check_in_policy(&self, policy: &CheckInPolicy) -> Result<(), sqlx::Error> {
+        let json = serde_json::to_string(policy).unwrap_or_else(|_| "{}".to_string());
+        self.set("check_in_policy", &json).await
+    }
+
+    /// Save capture exclusions (stored as JSON)
+    pub async fn set_privacy_rules(&self, rules: &PrivacyRules) -> Result<(), sqlx::Error> {
+        let json = serde_json::to_string(rules).unwrap_or_else(|_| "{}".to_string());
+        self.set("privacy_rules", &json).await
+    }
+
+    /// Set how PII is handled in text sent to cloud sync
+    pub async fn set_redaction_mode(&self, mode: &str) -> Result<(), sqlx::Error> {
+        self.set("redaction_mode", mode).await
+    }
+
+    /// Save per-provider usage prices (stored as JSON)
+    pub async fn set_usage_prices(&self, prices: &[UsagePrice]) -> Result<(), sqlx::Error> {
+        let json = serde_json::to_string(prices).unwrap_or_else(|_| "[]".to_string());
+        self.set("usage_prices", &json).await
+    }
+
+    /// Save the VLM scheduler policy (stored as JSON)
+    pub async fn set_vlm_scheduler_policy(
+        &self,
+        policy: &VLMSchedulerPolicy,
+    ) -> Result<(), sqlx::Error> {
+        let json = serde_json::to_string(policy).unwrap_or_else(|_| "{}".to_string());
+        self.set("vlm_scheduler_policy", &json).await
+    }
+
+    /// Save global shortcut bindings (stored as JSON)
+    pub async fn set_global_shortcuts(
+        &self,
+        shortcuts: &BTreeMap<String, String>,
+    ) -> Result<(), sqlx::Error> {
+        let json = serde_json::to_string(shortcuts).unwrap_or_else(|_| "{}".to_string());
+        self.set("global_shortcuts", &json).await
+    }
+
+    /// Set automatic meeting title generation toggle
+    pub async fn set_auto_title_meetings(&self, enabled: bool) -> Result<(), sqlx::Error> {
+        self.set(
+            "auto_title_meetings",
+            if enabled { "true" } else { "false" },
+        )
+        .await
+    }
+
+    /// Set embedding-based topic segmentation toggle
+    pub async fn set_semantic_topics_enabled(&self, enabled: bool) -> Result<(), sqlx::Error> {
+        self.set(
+            "semantic_topics_enabled",
+            if enabled { "true" } else { "false" },
+        )
+        .await
+    }
+
+    /// Set the minimum episode duration worth an AI summary
+    pub async fn set_episode_summary_min_secs(&self, secs: u32) -> Result<(), sqlx::Error> {
+        self.set("episode_summary_min_secs", &secs.to_string())
+            .await
+    }
+
+    /// Set the vault export template (None restores the builtin layout)
+    pub async fn set_export_template_id(&self, id: Option<&str>) -> Result<(), sqlx::Error> {
+        match id {
+            Some(id) => self.set("export_template_id", id).await,
+            None => self.delete("export_template_id").await,
+        }
+    }
+
+    /// Set what re-exports do with notes edited in the vault ("merge" / "refuse")
+    pub async fn set_vault_conflict_policy(&self, policy: &str) -> Result<(), sqlx::Error> {
+        self.set("vault_conflict_policy", policy).await
+    }
+
+    /// File auto-exported series meetings under a topic named after the series
+    pub async fn set_vault_series_folders(&self, enabled: bool) -> Result<(), sqlx::Error> {
+        self.set(
+            "vault_series_folders",
+            if enabled { "true" } else { "false" },
+        )
+        .await
+    }
+
+    /// Set the digest scheduler toggle and its local "HH:MM" time
+    pub async fn set_digest_schedule(&self, enabled: bool, time: &str) -> Result<(), sqlx::Error> {
+        self.set("digest_enabled", if enabled { "true" } else { "false" })
+            .await?;
+        self.set("digest_time", time).await
+    }
+
+    /// Set how old (days) a vault person/company note can be before attendee intel regenerates it
+    pub async fn set_attendee_intel_staleness_days(&self, days: u32) -> Result<(), sqlx::Error> {
+        self.set("attendee_intel_staleness_days", &days.to_string())
+            .await
+    }
+
+    /// Calendar ids meeting detection and intel use (empty = all)
+    pub async fn set_selected_calendars(&self, calendar_ids: &[String]) -> Result<(), sqlx::Error> {
+        let json = serde_json::to_string(calendar_ids).unwrap_or_else(|_| "[]".to_string());
+        self.set("selected_calendars", &json).await
+    }
+
+    /// Set the CRM webhook endpoint, credentials and event types; None clears a value
+    pub async fn set_webhook_config(
+        &self,
+        url: Option<&str>,
+        bearer_token: Option<&str>,
+        secret: Option<&str>,
+        event_types: &[String],
+    ) -> Result<(), sqlx::Error> {
+        for (key, value) in [
+            ("webhook_url", url),
+            ("webhook_bearer_token", bearer_token),
+            ("webhook_secret", secret),
+        ] {
+            match value {
+                Some(value) => self.set(key, value).await?,
+                None => self.delete(key).await?,
+            }
+        }
+        self.set("webhook_events", &event_types.join(",")).await
+    }
+
+    /// Set or clear the Slack bot token
+    pub async fn set_slack_bot_token(&self, token: Option<&str>) -> Result<(), sqlx::Error> {
+        match token {
+            Some(token) => self.set("slack_bot_token", token).await,
+            None => self.delete("slack_bot_token").await,
+        }
+    }
+
     /// Set capture system audio toggle
     pub async fn set_capture_system_audio(&self, enabled: bool) -> Result<(), sqlx::Error> {
         self.set(
@@ -492,6 +1276,35 @@ impl SettingsManager {
         .await
     }
 
+    /// Save mic silence warning policy (stored as JSON)
+    pub async fn set_silence_warning(
+        &self,
+        policy: &SilenceWarningPolicy,
+    ) -> Result<(), sqlx::Error> {
+        let json = serde_json::to_string(policy).unwrap_or_else(|_| "{}".to_string());
+        self.set("silence_warning", &json).await
+    }
+
+    /// Save screen video capture settings (stored as JSON)
+    pub async fn set_video_capture(&self, config: &VideoCaptureConfig) -> Result<(), sqlx::Error> {
+        let json = serde_json::to_string(config).unwrap_or_else(|_| "{}".to_string());
+        self.set("video_capture", &json).await
+    }
+
+    /// Set raw audio archiving toggle
+    pub async fn set_audio_recording_enabled(&self, enabled: bool) -> Result<(), sqlx::Error> {
+        self.set(
+            "audio_recording_enabled",
+            if enabled { "true" } else { "false" },
+        )
+        .await
+    }
+
+    /// Set archived audio format ("wav" or "aac")
+    pub async fn set_audio_recording_format(&self, format: &str) -> Result<(), sqlx::Error> {
+        self.set("audio_recording_format", format).await
+    }
+
     /// Set capture screen toggle
     pub async fn set_capture_screen(&self, enabled: bool) -> Result<(), sqlx::Error> {
         self.set("capture_screen", if enabled { "true" } else { "false" })
@@ -540,6 +1353,15 @@ impl SettingsManager {
             .await
     }
 
+    /// Set whether OCR runs when accessibility extraction fails
+    pub async fn set_ocr_fallback_enabled(&self, enabled: bool) -> Result<(), sqlx::Error> {
+        self.set(
+            "ocr_fallback_enabled",
+            if enabled { "true" } else { "false" },
+        )
+        .await
+    }
+
     // ============================================
     // Knowledge Base Settings
     // ============================================
@@ -564,6 +1386,35 @@ impl SettingsManager {
         self.set("pinecone_namespace", namespace).await
     }
 
+    /// Set the namespace for one kind of record ("transcripts", "activities",
+    /// "conversations" or "snapshots"); empty falls back to the derived default
+    pub async fn set_pinecone_kind_namespace(
+        &self,
+        kind: &str,
+        namespace: &str,
+    ) -> Result<(), sqlx::Error> {
+        self.set(&format!("pinecone_namespace_{}", kind), namespace)
+            .await
+    }
+
+    /// Set where semantic search vectors are stored ("pinecone" / "local")
+    pub async fn set_vector_backend(&self, backend: &str) -> Result<(), sqlx::Error> {
+        self.set("vector_backend", backend).await
+    }
+
+    /// Set whether the background cloud sync worker runs
+    pub async fn set_sync_worker_enabled(&self, enabled: bool) -> Result<(), sqlx::Error> {
+        self.set(
+            "sync_worker_enabled",
+            if enabled { "true" } else { "false" },
+        )
+        .await
+    }
+
+    pub async fn set_ingest_bandwidth_kbps(&self, kbps: u32) -> Result<(), sqlx::Error> {
+        self.set("ingest_bandwidth_kbps", &kbps.to_string()).await
+    }
+
     // ============================================
     // VLM Auto-Processing Settings
     // ============================================
@@ -581,6 +1432,18 @@ impl SettingsManager {
             .await
     }
 
+    /// Set VLM batch concurrency (1-8 in-flight requests)
+    pub async fn set_vlm_concurrency(&self, concurrency: u32) -> Result<(), sqlx::Error> {
+        let clamped = concurrency.clamp(1, 8);
+        self.set("vlm_concurrency", &clamped.to_string()).await
+    }
+
+    /// Set frames per VLM request (1-8, 1 disables batching)
+    pub async fn set_vlm_batch_size(&self, batch_size: u32) -> Result<(), sqlx::Error> {
+        let clamped = batch_size.clamp(1, 8);
+        self.set("vlm_batch_size", &clamped.to_string()).await
+    }
+
     /// Set VLM base URL
     pub async fn set_vlm_base_url(&self, url: &str) -> Result<(), sqlx::Error> {
         self.set("vlm_base_url", url).await
@@ -661,6 +1524,16 @@ impl SettingsManager {
         self.set("ai_remote_key", key).await
     }
 
+    pub async fn set_ai_remote_model(&self, model: &str) -> Result<(), sqlx::Error> {
+        self.set("ai_remote_model", model).await
+    }
+
+    /// Set the shared AI request budget per minute (0 = unlimited)
+    pub async fn set_ai_requests_per_minute(&self, requests: u32) -> Result<(), sqlx::Error> {
+        self.set("ai_requests_per_minute", &requests.to_string())
+            .await
+    }
+
     // ============================================
     // Session Mode Settings
     // ============================================
@@ -677,4 +1550,10 @@ impl SettingsManager {
             .await?
             .unwrap_or_else(|| "standard".to_string()))
     }
+
+    /// Set which apps feed a dork session from accessibility capture
+    pub async fn set_dork_app_allowlist(&self, apps: &[String]) -> Result<(), sqlx::Error> {
+        let json = serde_json::to_string(apps).unwrap_or_else(|_| "[]".to_string());
+        self.set("dork_app_allowlist", &json).await
+    }
 }
@@ -0,0 +1,432 @@
+// noFriction Meetings - Settings Export / Import
+// Moves the settings table to another machine as a single JSON file.
+//
+// API keys and tokens are only exported when asked for, and then always
+// encrypted with a passphrase (Argon2id key derivation + ChaCha20-Poly1305).
+// Import merges key by key: values are type-checked against the current
+// AppSettings schema, machine-specific keys (microphone, monitor, model
+// paths) are never touched, and settings missing from the file stay as they
+// are locally.
+
+use crate::secret_store::SECRET_KEYS;
+use crate::settings::{AppSettings, SettingsManager};
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Marker identifying an export file
+pub const EXPORT_FORMAT: &str = "nofriction-settings";
+/// Bump when the file layout changes incompatibly
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Settings that describe this machine's hardware or files
+const LOCAL_ONLY_KEYS: &[&str] = &[
+    "selected_microphone",
+    "selected_monitor",
+    "whisper_model_path",
+];
+
+/// Passphrase-encrypted secret settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedSecrets {
+    /// Names of the encrypted settings (values stay sealed)
+    pub keys: Vec<String>,
+    pub kdf: String,
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// On-disk export file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsExport {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    pub app_version: String,
+    pub settings: BTreeMap<String, String>,
+    pub secrets: Option<EncryptedSecrets>,
+}
+
+/// Result of writing an export file
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsExportSummary {
+    pub path: String,
+    pub settings: usize,
+    pub secrets: usize,
+}
+
+/// A setting that was not imported, and why
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SettingIssue {
+    pub key: String,
+    pub reason: String,
+}
+
+/// Per-key outcome of an import
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SettingsImportReport {
+    pub imported: Vec<String>,
+    pub skipped: Vec<SettingIssue>,
+    pub failed: Vec<SettingIssue>,
+}
+
+/// Values to write plus the report describing everything else
+pub struct ImportPlan {
+    pub writes: Vec<(String, String)>,
+    pub report: SettingsImportReport,
+}
+
+fn issue(key: &str, reason: impl Into<String>) -> SettingIssue {
+    SettingIssue {
+        key: key.to_string(),
+        reason: reason.into(),
+    }
+}
+
+/// Every setting key with its default value, as serialized by AppSettings
+fn known_settings() -> serde_json::Map<String, Value> {
+    match serde_json::to_value(AppSettings::with_defaults()) {
+        Ok(Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    }
+}
+
+/// Check a stored string value against the type of the setting's default
+fn validate_value(default: &Value, value: &str) -> Result<(), String> {
+    match default {
+        Value::Bool(_) if value != "true" && value != "false" => {
+            Err(format!("expected true or false, got {:?}", value))
+        }
+        Value::Number(_) if value.trim().parse::<f64>().is_err() => {
+            Err(format!("expected a number, got {:?}", value))
+        }
+        Value::Array(_) | Value::Object(_) => {
+            let parsed: Value =
+                serde_json::from_str(value).map_err(|e| format!("invalid JSON: {}", e))?;
+            if parsed.is_array() != default.is_array() {
+                return Err("JSON has the wrong shape".to_string());
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+fn encrypt_secrets(
+    secrets: &BTreeMap<String, String>,
+    passphrase: &str,
+) -> Result<EncryptedSecrets, String> {
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let salt: [u8; 16] = rand::random();
+    let nonce: [u8; 12] = rand::random();
+
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&derive_key(passphrase, &salt)?));
+    let plaintext = serde_json::to_vec(secrets).map_err(|e| e.to_string())?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|_| "Failed to encrypt secrets".to_string())?;
+
+    Ok(EncryptedSecrets {
+        keys: secrets.keys().cloned().collect(),
+        kdf: "argon2id".to_string(),
+        salt: b64.encode(salt),
+        nonce: b64.encode(nonce),
+        ciphertext: b64.encode(ciphertext),
+    })
+}
+
+fn decrypt_secrets(
+    sealed: &EncryptedSecrets,
+    passphrase: &str,
+) -> Result<BTreeMap<String, String>, String> {
+    if sealed.kdf != "argon2id" {
+        return Err(format!("Unsupported key derivation: {}", sealed.kdf));
+    }
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let decode = |field: &str| {
+        b64.decode(field)
+            .map_err(|e| format!("Corrupt secrets block: {}", e))
+    };
+    let salt = decode(&sealed.salt)?;
+    let nonce = decode(&sealed.nonce)?;
+    if nonce.len() != 12 {
+        return Err("Corrupt secrets block: bad nonce".to_string());
+    }
+
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&derive_key(passphrase, &salt)?));
+    let plaintext = cipher
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            decode(&sealed.ciphertext)?.as_slice(),
+        )
+        .map_err(|_| "Wrong passphrase (or the secrets block was modified)".to_string())?;
+    serde_json::from_slice(&plaintext).map_err(|e| format!("Corrupt secrets block: {}", e))
+}
+
+/// Build an export from the stored settings rows. Secrets require a passphrase.
+pub fn build_export(
+    rows: Vec<(String, String)>,
+    include_secrets: bool,
+    passphrase: Option<&str>,
+) -> Result<SettingsExport, String> {
+    let passphrase = match (include_secrets, passphrase) {
+        (true, Some(p)) if !p.is_empty() => Some(p),
+        (true, _) => return Err("A passphrase is required to export secrets".to_string()),
+        (false, _) => None,
+    };
+
+    let known = known_settings();
+    let mut settings = BTreeMap::new();
+    let mut secrets = BTreeMap::new();
+    for (key, value) in rows {
+        if !known.contains_key(&key) || LOCAL_ONLY_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        if SECRET_KEYS.contains(&key.as_str()) {
+            if passphrase.is_some() && !value.is_empty() {
+                secrets.insert(key, value);
+            }
+        } else {
+            settings.insert(key, value);
+        }
+    }
+
+    let secrets = match passphrase {
+        Some(p) if !secrets.is_empty() => Some(encrypt_secrets(&secrets, p)?),
+        _ => None,
+    };
+
+    Ok(SettingsExport {
+        format: EXPORT_FORMAT.to_string(),
+        version: EXPORT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        settings,
+        secrets,
+    })
+}
+
+/// Parse an export file, rejecting other formats and newer schema versions
+pub fn parse_export(contents: &str) -> Result<SettingsExport, String> {
+    let export: SettingsExport =
+        serde_json::from_str(contents).map_err(|e| format!("Not a settings export: {}", e))?;
+    if export.format != EXPORT_FORMAT {
+        return Err(format!(
+            "Not a settings export (format {:?})",
+            export.format
+        ));
+    }
+    if export.version == 0 || export.version > EXPORT_VERSION {
+        return Err(format!(
+            "Settings export uses schema v{}, this app reads up to v{}; update the app first",
+            export.version, EXPORT_VERSION
+        ));
+    }
+    Ok(export)
+}
+
+/// Decide what to write. `current` is the local settings table; keys that
+/// already hold the imported value are reported as unchanged. A wrong
+/// passphrase fails the whole import before anything is written.
+pub fn plan_import(
+    export: &SettingsExport,
+    passphrase: Option<&str>,
+    current: &HashMap<String, String>,
+) -> Result<ImportPlan, String> {
+    let mut report = SettingsImportReport::default();
+    let mut entries = export.settings.clone();
+
+    if let Some(sealed) = &export.secrets {
+        match passphrase.filter(|p| !p.is_empty()) {
+            Some(p) => entries.extend(decrypt_secrets(sealed, p)?),
+            None => report.skipped.extend(
+                sealed
+                    .keys
+                    .iter()
+                    .map(|key| issue(key, "encrypted; no passphrase given")),
+            ),
+        }
+    }
+
+    let known = known_settings();
+    let mut writes = Vec::new();
+    for (key, value) in entries {
+        let Some(default) = known.get(&key) else {
+            report.skipped.push(issue(&key, "unknown setting"));
+            continue;
+        };
+        if LOCAL_ONLY_KEYS.contains(&key.as_str()) {
+            report
+                .skipped
+                .push(issue(&key, "specific to the other machine"));
+            continue;
+        }
+        if let Err(e) = validate_value(default, &value) {
+            report.failed.push(issue(&key, e));
+            continue;
+        }
+        if key == "obsidian_vault_path" && !Path::new(&value).is_dir() {
+            report.failed.push(issue(
+                &key,
+                format!("folder not found on this machine: {}", value),
+            ));
+            continue;
+        }
+        if current.get(&key) == Some(&value) {
+            report.skipped.push(issue(&key, "unchanged"));
+            continue;
+        }
+        writes.push((key, value));
+    }
+
+    Ok(ImportPlan { writes, report })
+}
+
+/// Write the settings table to `path`
+pub async fn export_settings(
+    settings: &SettingsManager,
+    path: &Path,
+    include_secrets: bool,
+    passphrase: Option<&str>,
+) -> Result<SettingsExportSummary, String> {
+    let rows = settings
+        .get_all_values()
+        .await
+        .map_err(|e| format!("Failed to read settings: {}", e))?;
+    let export = build_export(rows, include_secrets, passphrase)?;
+
+    let json = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+    tokio::fs::write(path, json)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    Ok(SettingsExportSummary {
+        path: path.display().to_string(),
+        settings: export.settings.len(),
+        secrets: export.secrets.as_ref().map_or(0, |s| s.keys.len()),
+    })
+}
+
+/// Merge the settings in `path` into the local table. Live side effects are
+/// up to the caller, based on `imported`.
+pub async fn import_settings(
+    settings: &SettingsManager,
+    path: &Path,
+    passphrase: Option<&str>,
+) -> Result<SettingsImportReport, String> {
+    let contents = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let export = parse_export(&contents)?;
+    let current: HashMap<String, String> = settings
+        .get_all_values()
+        .await
+        .map_err(|e| format!("Failed to read settings: {}", e))?
+        .into_iter()
+        .collect();
+
+    let ImportPlan { writes, mut report } = plan_import(&export, passphrase, &current)?;
+    for (key, value) in writes {
+        match settings.set(&key, &value).await {
+            Ok(()) => report.imported.push(key),
+            Err(e) => report
+                .failed
+                .push(issue(&key, format!("write failed: {}", e))),
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_secrets_only_leave_encrypted() {
+        let stored = rows(&[
+            ("deepgram_api_key", "dg-secret"),
+            ("vlm_auto_process", "true"),
+            ("selected_microphone", "USB Mic"),
+        ]);
+        assert!(build_export(stored.clone(), true, None).is_err());
+
+        let plain = build_export(stored.clone(), false, None).unwrap();
+        assert!(plain.secrets.is_none());
+        assert_eq!(plain.settings.len(), 1);
+
+        let sealed = build_export(stored, true, Some("correct horse")).unwrap();
+        let json = serde_json::to_string(&sealed).unwrap();
+        assert!(!json.contains("dg-secret"));
+        assert!(!json.contains("USB Mic"));
+
+        let export = parse_export(&json).unwrap();
+        assert!(plan_import(&export, Some("wrong"), &HashMap::new()).is_err());
+        let plan = plan_import(&export, Some("correct horse"), &HashMap::new()).unwrap();
+        assert!(plan
+            .writes
+            .contains(&("deepgram_api_key".to_string(), "dg-secret".to_string())));
+
+        let plan = plan_import(&export, None, &HashMap::new()).unwrap();
+        assert_eq!(plan.writes.len(), 1);
+        assert_eq!(plan.report.skipped[0].key, "deepgram_api_key");
+    }
+
+    #[test]
+    fn test_plan_validates_and_merges() {
+        let mut export = build_export(Vec::new(), false, None).unwrap();
+        for (key, value) in [
+            ("vlm_auto_process", "yes"),
+            ("vlm_concurrency", "4"),
+            ("frame_retention_days", "thirty"),
+            ("privacy_rules", "[1, 2]"),
+            ("active_theme", "fundraising"),
+            ("selected_monitor", "2"),
+            ("thebrain_token", "abc"),
+            ("obsidian_vault_path", "/definitely/not/here"),
+        ] {
+            export.settings.insert(key.to_string(), value.to_string());
+        }
+        let current = HashMap::from([("active_theme".to_string(), "fundraising".to_string())]);
+
+        let plan = plan_import(&export, None, &current).unwrap();
+        assert_eq!(
+            plan.writes,
+            vec![("vlm_concurrency".to_string(), "4".to_string())]
+        );
+        let failed: Vec<&str> = plan.report.failed.iter().map(|i| i.key.as_str()).collect();
+        assert_eq!(
+            failed,
+            [
+                "frame_retention_days",
+                "obsidian_vault_path",
+                "privacy_rules",
+                "vlm_auto_process"
+            ]
+        );
+        assert_eq!(plan.report.skipped.len(), 3);
+
+        export.version = EXPORT_VERSION + 1;
+        let json = serde_json::to_string(&export).unwrap();
+        assert!(parse_export(&json).is_err());
+    }
+}
@@ -0,0 +1,476 @@
+// noFriction Meetings - Global Keyboard Shortcuts
+// System-wide hotkeys that work while another app (the meeting) has focus.
+//
+// Bindings are stored in the `global_shortcuts` setting as action →
+// accelerator; a missing entry means the default, an empty one means unbound.
+// The tray and menu bar show the current bindings as part of the item label
+// (not as menu accelerators, which would fire the action twice).
+
+use crate::AppState;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter, Manager, Wry};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+    ToggleRecording,
+    PinMoment,
+    ToggleGenie,
+    QuickNote,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 4] = [
+        ShortcutAction::ToggleRecording,
+        ShortcutAction::PinMoment,
+        ShortcutAction::ToggleGenie,
+        ShortcutAction::QuickNote,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShortcutAction::ToggleRecording => "toggle_recording",
+            ShortcutAction::PinMoment => "pin_moment",
+            ShortcutAction::ToggleGenie => "toggle_genie",
+            ShortcutAction::QuickNote => "quick_note",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.as_str() == s)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ShortcutAction::ToggleRecording => "Start/Stop Recording",
+            ShortcutAction::PinMoment => "Pin Moment",
+            ShortcutAction::ToggleGenie => "Toggle Genie Mode",
+            ShortcutAction::QuickNote => "Quick Note",
+        }
+    }
+
+    /// ⌥⌘R, ⌥⌘P, ⌥⌘G and ⌥⌘N on macOS
+    pub fn default_accelerator(&self) -> &'static str {
+        match self {
+            ShortcutAction::ToggleRecording => "Alt+CmdOrCtrl+R",
+            ShortcutAction::PinMoment => "Alt+CmdOrCtrl+P",
+            ShortcutAction::ToggleGenie => "Alt+CmdOrCtrl+G",
+            ShortcutAction::QuickNote => "Alt+CmdOrCtrl+N",
+        }
+    }
+}
+
+/// Current binding of one action, as returned by `get_shortcuts`
+#[derive(Debug, Clone, Serialize)]
+pub struct ShortcutBinding {
+    pub action: ShortcutAction,
+    pub label: String,
+    /// None when the action is unbound
+    pub accelerator: Option<String>,
+    /// Symbol form for display, e.g. "⌥⌘R"
+    pub display: Option<String>,
+    pub default_accelerator: String,
+    /// False when the OS refused the hotkey (e.g. another app owns it)
+    pub registered: bool,
+    pub error: Option<String>,
+}
+
+struct Bound {
+    accelerator: String,
+    shortcut: Shortcut,
+    registered: bool,
+    error: Option<String>,
+}
+
+/// Live bindings plus the handle used to (re)register them
+pub struct ShortcutRegistry {
+    app: Mutex<Option<AppHandle>>,
+    bound: RwLock<BTreeMap<ShortcutAction, Bound>>,
+    /// Serializes rebinding; `bound` is never held across OS registration,
+    /// which waits on the main thread where the shortcut handler reads it
+    changing: Mutex<()>,
+}
+
+static SHORTCUTS: OnceLock<ShortcutRegistry> = OnceLock::new();
+
+/// Global shortcut registry
+pub fn shortcuts() -> &'static ShortcutRegistry {
+    SHORTCUTS.get_or_init(|| ShortcutRegistry {
+        app: Mutex::new(None),
+        bound: RwLock::new(BTreeMap::new()),
+        changing: Mutex::new(()),
+    })
+}
+
+/// Parse an accelerator such as "Alt+CmdOrCtrl+R"; a modifier is required so
+/// a global hotkey never swallows plain typing
+pub fn parse_accelerator(accelerator: &str) -> Result<Shortcut, String> {
+    let shortcut = Shortcut::from_str(accelerator.trim())
+        .map_err(|e| format!("Invalid shortcut \"{}\": {}", accelerator, e))?;
+    if shortcut.mods.is_empty() {
+        return Err(format!(
+            "Shortcut \"{}\" needs at least one modifier (⌘, ⌥, ⌃ or ⇧)",
+            accelerator
+        ));
+    }
+    Ok(shortcut)
+}
+
+/// Symbol form of an accelerator in macOS modifier order, e.g.
+/// "Alt+CmdOrCtrl+R" → "⌥⌘R"
+pub fn display_accelerator(accelerator: &str) -> String {
+    let mut mods = [false; 4]; // ⌃ ⌥ ⇧ ⌘
+    let mut key = String::new();
+    for part in accelerator.split('+').map(str::trim) {
+        match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => mods[0] = true,
+            "alt" | "option" => mods[1] = true,
+            "shift" => mods[2] = true,
+            "cmd" | "command" | "super" | "meta" | "cmdorctrl" | "commandorcontrol"
+            | "cmdorcontrol" | "commandorctrl" => mods[3] = true,
+            _ => {
+                let part = part.strip_prefix("Key").unwrap_or(part);
+                let part = part.strip_prefix("Digit").unwrap_or(part);
+                key = part.to_uppercase();
+            }
+        }
+    }
+    let mut out: String = ["⌃", "⌥", "⇧", "⌘"]
+        .iter()
+        .zip(mods)
+        .filter(|(_, on)| *on)
+        .map(|(symbol, _)| *symbol)
+        .collect();
+    out.push_str(&key);
+    out
+}
+
+/// Effective accelerator for each action: the saved one, else the default;
+/// an empty saved value unbinds
+fn resolve(saved: &BTreeMap<String, String>) -> Vec<(ShortcutAction, Option<String>)> {
+    ShortcutAction::ALL
+        .into_iter()
+        .map(|action| {
+            let accelerator = match saved.get(action.as_str()) {
+                Some(value) if value.trim().is_empty() => None,
+                Some(value) => Some(value.trim().to_string()),
+                None => Some(action.default_accelerator().to_string()),
+            };
+            (action, accelerator)
+        })
+        .collect()
+}
+
+impl ShortcutRegistry {
+    /// Register the saved bindings; called once AppState is ready. Bindings
+    /// the OS refuses are kept (unregistered) so `get_shortcuts` can say why.
+    pub fn init(&self, app: AppHandle, saved: &BTreeMap<String, String>) {
+        *self.app.lock() = Some(app);
+        self.apply(saved);
+    }
+
+    /// Replace all bindings with `saved` (settings import)
+    pub fn apply(&self, saved: &BTreeMap<String, String>) {
+        let Some(app) = self.app.lock().clone() else {
+            return;
+        };
+        let changing = self.changing.lock();
+        let previous = std::mem::take(&mut *self.bound.write());
+        for old in previous.into_values() {
+            if old.registered {
+                let _ = app.global_shortcut().unregister(old.shortcut);
+            }
+        }
+        let mut next: BTreeMap<ShortcutAction, Bound> = BTreeMap::new();
+        for (action, accelerator) in resolve(saved) {
+            let Some(accelerator) = accelerator else {
+                continue;
+            };
+            let shortcut = match parse_accelerator(&accelerator) {
+                Ok(shortcut) => shortcut,
+                Err(e) => {
+                    log::warn!("Skipping {} shortcut: {}", action.as_str(), e);
+                    continue;
+                }
+            };
+            if next.values().any(|b| b.shortcut.id() == shortcut.id()) {
+                log::warn!(
+                    "Skipping {} shortcut: {} is already bound",
+                    action.as_str(),
+                    accelerator
+                );
+                continue;
+            }
+            let error = app
+                .global_shortcut()
+                .register(shortcut)
+                .err()
+                .map(|e| e.to_string());
+            if let Some(ref e) = error {
+                log::warn!(
+                    "Could not register {} ({}): {}",
+                    accelerator,
+                    action.as_str(),
+                    e
+                );
+            }
+            next.insert(
+                action,
+                Bound {
+                    accelerator,
+                    shortcut,
+                    registered: error.is_none(),
+                    error,
+                },
+            );
+        }
+        *self.bound.write() = next;
+        drop(changing);
+        refresh_menus(&app);
+    }
+
+    /// Rebind one action live. `None` (or an empty string) unbinds it.
+    /// Invalid accelerators, ones already bound to another action and ones
+    /// the OS refuses are rejected and the previous binding stays in place.
+    pub fn set(&self, action: ShortcutAction, accelerator: Option<&str>) -> Result<(), String> {
+        let app = self
+            .app
+            .lock()
+            .clone()
+            .ok_or("Shortcuts are not ready yet; try again once startup finishes")?;
+        let accelerator = accelerator.map(str::trim).filter(|a| !a.is_empty());
+        let shortcut = accelerator.map(parse_accelerator).transpose()?;
+
+        let changing = self.changing.lock();
+        if let Some(shortcut) = shortcut {
+            if let Some((other, _)) = self
+                .bound
+                .read()
+                .iter()
+                .find(|(other, b)| **other != action && b.shortcut.id() == shortcut.id())
+            {
+                return Err(format!(
+                    "{} is already used for \"{}\"",
+                    display_accelerator(accelerator.unwrap_or_default()),
+                    other.label()
+                ));
+            }
+        }
+
+        let previous = self.bound.write().remove(&action);
+        if let Some(ref old) = previous {
+            if old.registered {
+                let _ = app.global_shortcut().unregister(old.shortcut);
+            }
+        }
+        if let (Some(accelerator), Some(shortcut)) = (accelerator, shortcut) {
+            if let Err(e) = app.global_shortcut().register(shortcut) {
+                // Put the old binding back so a failed change loses nothing
+                if let Some(mut old) = previous {
+                    old.registered = app.global_shortcut().register(old.shortcut).is_ok();
+                    self.bound.write().insert(action, old);
+                }
+                return Err(format!(
+                    "Could not register {}: {} (it may be in use by another app)",
+                    display_accelerator(accelerator),
+                    e
+                ));
+            }
+            self.bound.write().insert(
+                action,
+                Bound {
+                    accelerator: accelerator.to_string(),
+                    shortcut,
+                    registered: true,
+                    error: None,
+                },
+            );
+        }
+        drop(changing);
+        refresh_menus(&app);
+        Ok(())
+    }
+
+    /// Current bindings in action order
+    pub fn bindings(&self) -> Vec<ShortcutBinding> {
+        let bound = self.bound.read();
+        ShortcutAction::ALL
+            .into_iter()
+            .map(|action| {
+                let b = bound.get(&action);
+                ShortcutBinding {
+                    action,
+                    label: action.label().to_string(),
+                    accelerator: b.map(|b| b.accelerator.clone()),
+                    display: b.map(|b| display_accelerator(&b.accelerator)),
+                    default_accelerator: action.default_accelerator().to_string(),
+                    registered: b.map(|b| b.registered).unwrap_or(false),
+                    error: b.and_then(|b| b.error.clone()),
+                }
+            })
+            .collect()
+    }
+
+    /// Symbol form of an action's binding, for menu labels
+    pub fn display(&self, action: ShortcutAction) -> Option<String> {
+        self.bound
+            .read()
+            .get(&action)
+            .map(|b| display_accelerator(&b.accelerator))
+    }
+
+    /// Settings value for the current bindings
+    pub fn saved_map(&self) -> BTreeMap<String, String> {
+        let bound = self.bound.read();
+        ShortcutAction::ALL
+            .into_iter()
+            .map(|action| {
+                let value = bound
+                    .get(&action)
+                    .map(|b| b.accelerator.clone())
+                    .unwrap_or_default();
+                (action.as_str().to_string(), value)
+            })
+            .collect()
+    }
+
+    fn action_for(&self, shortcut: &Shortcut) -> Option<ShortcutAction> {
+        self.bound
+            .read()
+            .iter()
+            .find(|(_, b)| b.shortcut.id() == shortcut.id())
+            .map(|(action, _)| *action)
+    }
+}
+
+/// Menu label with the action's binding appended, e.g. "Pin Moment   ⌥⌘P"
+pub fn label_with_binding(label: &str, action: ShortcutAction) -> String {
+    match shortcuts().display(action) {
+        Some(display) => format!("{}   {}", label, display),
+        None => label.to_string(),
+    }
+}
+
+fn refresh_menus(app: &AppHandle) {
+    if let Err(e) = crate::tray_builder::refresh_tray_menu(app) {
+        log::warn!("Failed to refresh tray menu: {}", e);
+    }
+    match crate::menu_builder::create_menu(app) {
+        Ok(menu) => {
+            if let Err(e) = app.set_menu(menu) {
+                log::warn!("Failed to refresh menu bar: {}", e);
+            }
+        }
+        Err(e) => log::warn!("Failed to rebuild menu bar: {}", e),
+    }
+}
+
+/// Global shortcut plugin handler
+pub fn handle_shortcut(app: &AppHandle<Wry>, shortcut: &Shortcut, event: ShortcutEvent) {
+    if !matches!(event.state(), ShortcutState::Pressed) {
+        return;
+    }
+    if let Some(action) = shortcuts().action_for(shortcut) {
+        run_action(app, action);
+    }
+}
+
+/// Perform a shortcut action; also used by the tray and menu items. No-op
+/// until AppState is managed.
+pub fn run_action(app: &AppHandle, action: ShortcutAction) {
+    let Some(state) = app.try_state::<AppState>() else {
+        log::info!(
+            "Ignoring {} shortcut: app is still initializing",
+            action.as_str()
+        );
+        return;
+    };
+    log::info!("Shortcut action: {}", action.as_str());
+
+    match action {
+        // Runs in the backend so it works before the window is shown; the
+        // UI follows via `recording-state-changed`
+        ShortcutAction::ToggleRecording => {
+            let is_recording = state.capture_engine.read().get_status().is_recording;
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let result = if is_recording {
+                    crate::commands::stop_recording_in_background(app).await
+                } else {
+                    crate::commands::start_recording_in_background(app)
+                        .await
+                        .map(|_| ())
+                };
+                if let Err(e) = result {
+                    log::warn!("Recording shortcut failed: {}", e);
+                }
+            });
+        }
+        ShortcutAction::PinMoment => {
+            match crate::video_recorder::get_video_recorder()
+                .read()
+                .pin_moment(Some("Shortcut".to_string()))
+            {
+                Ok(pin) => {
+                    let _ = app.emit("shortcut:pin_moment", pin);
+                }
+                Err(e) => log::info!("Pin moment shortcut ignored: {}", e),
+            }
+        }
+        ShortcutAction::ToggleGenie => {
+            let _ = app.emit("shortcut:toggle_genie", ());
+        }
+        ShortcutAction::QuickNote => {
+            if let Err(e) = crate::quick_note::show_quick_note_window(app) {
+                log::warn!("Failed to open quick note: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accelerators_parse_resolve_and_display() {
+        assert!(parse_accelerator("Alt+CmdOrCtrl+R").is_ok());
+        assert!(parse_accelerator("R").is_err());
+        assert!(parse_accelerator("Alt+Cmd+Nope").is_err());
+        assert_eq!(
+            parse_accelerator("Alt+Cmd+R").unwrap().id(),
+            parse_accelerator("Command+Option+KeyR").unwrap().id()
+        );
+
+        assert_eq!(display_accelerator("Alt+CmdOrCtrl+R"), "⌥⌘R");
+        assert_eq!(display_accelerator("CmdOrCtrl+Shift+Ctrl+KeyP"), "⌃⇧⌘P");
+
+        let saved = BTreeMap::from([
+            ("pin_moment".to_string(), "".to_string()),
+            ("toggle_genie".to_string(), "Ctrl+Shift+G".to_string()),
+        ]);
+        assert_eq!(
+            resolve(&saved),
+            vec![
+                (
+                    ShortcutAction::ToggleRecording,
+                    Some("Alt+CmdOrCtrl+R".to_string())
+                ),
+                (ShortcutAction::PinMoment, None),
+                (
+                    ShortcutAction::ToggleGenie,
+                    Some("Ctrl+Shift+G".to_string())
+                ),
+                (
+                    ShortcutAction::QuickNote,
+                    Some("Alt+CmdOrCtrl+N".to_string())
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,480 @@
+//! Slack client for posting meeting summaries and catch-up capsules
+//!
+//! Messages are built as Block Kit. Anything that does not fit in one
+//! message (50 blocks, 3000 chars per section) continues as thread replies.
+
+use crate::catch_up_agent::CatchUpCapsule;
+use crate::database::ActionItemRecord;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const SLACK_API: &str = "https://slack.com/api";
+/// Custom URL scheme the desktop app registers for deep links
+pub const DEEP_LINK_SCHEME: &str = "nofriction";
+
+const MAX_BLOCKS_PER_MESSAGE: usize = 50;
+const MAX_SECTION_CHARS: usize = 3000;
+const MAX_HEADER_CHARS: usize = 150;
+const MAX_FALLBACK_CHARS: usize = 300;
+
+/// A notable moment posted as a thread reply
+#[derive(Debug, Clone)]
+pub struct SlackHighlight {
+    /// Milliseconds since the meeting started
+    pub offset_ms: i64,
+    pub label: String,
+    pub text: String,
+}
+
+/// Everything needed to post one meeting
+#[derive(Debug, Clone)]
+pub struct SlackMeetingSummary {
+    pub meeting_id: String,
+    pub title: String,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub duration_seconds: Option<i64>,
+    pub summary: Option<String>,
+    pub action_items: Vec<ActionItemRecord>,
+    pub highlights: Vec<SlackHighlight>,
+}
+
+/// Where a post landed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlackPostResult {
+    pub channel: String,
+    /// Timestamp of the parent message (Slack's message id)
+    pub ts: String,
+    pub thread_replies: usize,
+}
+
+/// Link that opens the meeting in the app
+pub fn meeting_link(meeting_id: &str) -> String {
+    format!("{}://meetings/{}", DEEP_LINK_SCHEME, meeting_id)
+}
+
+/// Escape the three characters Slack mrkdwn treats as control characters
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Split text into chunks of at most `max_chars`, preferring line breaks
+pub fn split_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for line in text.lines() {
+        let mut line = line.to_string();
+        // A single overlong line is hard-wrapped
+        while line.chars().count() > max_chars {
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+            }
+            let head: String = line.chars().take(max_chars).collect();
+            line = line.chars().skip(max_chars).collect();
+            chunks.push(head);
+        }
+
+        let needed = if current.is_empty() {
+            line.chars().count()
+        } else {
+            current.chars().count() + 1 + line.chars().count()
+        };
+        if needed > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(&line);
+    }
+    if !current.trim().is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+fn header_block(text: &str) -> serde_json::Value {
+    serde_json::json!({
+        "type": "header",
+        "text": { "type": "plain_text", "text": truncate_chars(text, MAX_HEADER_CHARS), "emoji": true }
+    })
+}
+
+fn context_block(mrkdwn: &str) -> serde_json::Value {
+    serde_json::json!({
+        "type": "context",
+        "elements": [{ "type": "mrkdwn", "text": truncate_chars(mrkdwn, MAX_SECTION_CHARS) }]
+    })
+}
+
+/// One or more section blocks holding `mrkdwn`
+fn section_blocks(mrkdwn: &str) -> Vec<serde_json::Value> {
+    split_text(mrkdwn, MAX_SECTION_CHARS)
+        .into_iter()
+        .map(|chunk| {
+            serde_json::json!({
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": chunk }
+            })
+        })
+        .collect()
+}
+
+fn divider_block() -> serde_json::Value {
+    serde_json::json!({ "type": "divider" })
+}
+
+fn link_block(meeting_id: &str) -> serde_json::Value {
+    context_block(&format!(
+        "<{}|Open in noFriction Meetings>",
+        meeting_link(meeting_id)
+    ))
+}
+
+fn format_offset(ms: i64) -> String {
+    let secs = ms.max(0) / 1000;
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Checkbox-style bullet for an action item
+pub fn action_item_line(item: &ActionItemRecord) -> String {
+    let mut line = escape(&item.text);
+    if let Some(assignee) = item.assignee.as_deref().filter(|a| !a.is_empty()) {
+        line.push_str(&format!(" — _{}_", escape(assignee)));
+    }
+    if let Some(due) = item.due_hint.as_deref().filter(|d| !d.is_empty()) {
+        line.push_str(&format!(" (due {})", escape(due)));
+    }
+    match item.status.as_str() {
+        "done" => format!("☑ {}", line),
+        "cancelled" => format!("☐ ~{}~", line),
+        _ => format!("☐ {}", line),
+    }
+}
+
+/// Break a block list into message-sized pieces
+fn into_messages(blocks: Vec<serde_json::Value>) -> Vec<Vec<serde_json::Value>> {
+    blocks
+        .chunks(MAX_BLOCKS_PER_MESSAGE)
+        .map(|chunk| chunk.to_vec())
+        .collect()
+}
+
+/// Messages for a meeting summary: the first is the parent, the rest are thread replies
+pub fn summary_messages(meeting: &SlackMeetingSummary) -> Vec<Vec<serde_json::Value>> {
+    let mut blocks = vec![header_block(&meeting.title)];
+
+    let mut meta = meeting
+        .started_at
+        .format("%b %-d, %Y %H:%M UTC")
+        .to_string();
+    if let Some(secs) = meeting.duration_seconds {
+        meta.push_str(&format!(" · {} min", (secs / 60).max(1)));
+    }
+    blocks.push(context_block(&meta));
+
+    let summary = meeting
+        .summary
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or("_No summary generated yet._");
+    blocks.extend(section_blocks(&format!("*Summary*\n{}", escape(summary))));
+
+    if !meeting.action_items.is_empty() {
+        blocks.push(divider_block());
+        let list = meeting
+            .action_items
+            .iter()
+            .map(action_item_line)
+            .collect::<Vec<_>>()
+            .join("\n");
+        blocks.extend(section_blocks(&format!("*Action items*\n{}", list)));
+    }
+    blocks.push(link_block(&meeting.meeting_id));
+
+    let mut messages = into_messages(blocks);
+
+    if !meeting.highlights.is_empty() {
+        let lines = meeting
+            .highlights
+            .iter()
+            .map(|h| {
+                format!(
+                    "`{}` *{}* {}",
+                    format_offset(h.offset_ms),
+                    escape(&h.label),
+                    escape(&h.text)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        messages.extend(into_messages(section_blocks(&format!(
+            "*Transcript highlights*\n{}",
+            lines
+        ))));
+    }
+    messages
+}
+
+/// Messages for a mid-meeting catch-up capsule
+pub fn catch_up_messages(
+    meeting_id: &str,
+    title: &str,
+    capsule: &CatchUpCapsule,
+) -> Vec<Vec<serde_json::Value>> {
+    let bullets = |items: &[String]| {
+        items
+            .iter()
+            .map(|i| format!("• {}", escape(i)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let mut blocks = vec![header_block(&format!("Catch-up: {}", title))];
+    blocks.push(context_block(&format!(
+        "{} min in · now discussing: {}",
+        capsule.generated_at_minute,
+        escape(&capsule.current_topic)
+    )));
+    blocks.extend(section_blocks(&escape(&capsule.sixty_second_version)));
+
+    let missed: Vec<String> = capsule.what_missed.iter().map(|i| i.text.clone()).collect();
+    let decisions: Vec<String> = capsule.decisions.iter().map(|d| d.text.clone()).collect();
+    for (heading, items) in [
+        ("What you missed", missed),
+        ("Decisions", decisions),
+        ("Open threads", capsule.open_threads.clone()),
+        ("Questions to ask", capsule.questions_to_ask.clone()),
+    ] {
+        if !items.is_empty() {
+            blocks.extend(section_blocks(&format!(
+                "*{}*\n{}",
+                heading,
+                bullets(&items)
+            )));
+        }
+    }
+    blocks.push(link_block(meeting_id));
+    into_messages(blocks)
+}
+
+/// Actionable message for a Slack API error code
+pub fn describe_error(code: &str, channel: &str) -> String {
+    match code {
+        "invalid_auth" | "not_authed" | "token_revoked" | "token_expired" | "account_inactive" => {
+            format!(
+                "Slack rejected the bot token ({}). Update the Slack bot token in Settings.",
+                code
+            )
+        }
+        "channel_not_found" => format!(
+            "Slack channel '{}' not found (channel_not_found). Check the name, or invite the bot if the channel is private.",
+            channel
+        ),
+        "not_in_channel" => format!(
+            "The Slack bot is not a member of '{}' (not_in_channel). Run /invite @<bot> in that channel.",
+            channel
+        ),
+        "is_archived" => format!("Slack channel '{}' is archived (is_archived).", channel),
+        "missing_scope" => {
+            "The Slack bot token lacks the chat:write scope (missing_scope). Add it and reinstall the app.".to_string()
+        }
+        "ratelimited" | "rate_limited" => {
+            "Slack is rate limiting this bot (ratelimited). Try again in a minute.".to_string()
+        }
+        "msg_too_long" | "invalid_blocks" => format!(
+            "Slack refused the message format ({}). Please report this.",
+            code
+        ),
+        other => format!("Slack API error: {}", other),
+    }
+}
+
+/// Slack Web API client authenticated with a bot token
+pub struct SlackClient {
+    bot_token: String,
+    client: reqwest::Client,
+}
+
+impl SlackClient {
+    pub fn new(bot_token: &str) -> Self {
+        Self {
+            bot_token: bot_token.to_string(),
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(20))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// chat.postMessage; returns (channel id, message ts)
+    pub async fn post_message(
+        &self,
+        channel: &str,
+        fallback_text: &str,
+        blocks: &[serde_json::Value],
+        thread_ts: Option<&str>,
+    ) -> Result<(String, String), String> {
+        let mut body = serde_json::json!({
+            "channel": channel,
+            "text": truncate_chars(fallback_text, MAX_FALLBACK_CHARS),
+            "blocks": blocks,
+            "unfurl_links": false,
+        });
+        if let Some(ts) = thread_ts {
+            body["thread_ts"] = serde_json::Value::String(ts.to_string());
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/chat.postMessage", SLACK_API))
+            .bearer_auth(&self.bot_token)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Slack: {}", e))?;
+
+        if response.status().as_u16() == 429 {
+            return Err(describe_error("ratelimited", channel));
+        }
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Slack response: {}", e))?;
+
+        if !json["ok"].as_bool().unwrap_or(false) {
+            let code = json["error"].as_str().unwrap_or("unknown_error");
+            return Err(describe_error(code, channel));
+        }
+
+        let channel_id = json["channel"].as_str().unwrap_or(channel).to_string();
+        let ts = json["ts"]
+            .as_str()
+            .ok_or_else(|| "Slack response missing message ts".to_string())?
+            .to_string();
+        Ok((channel_id, ts))
+    }
+
+    /// Post the first message, then the rest as replies in its thread
+    pub async fn post_thread(
+        &self,
+        channel: &str,
+        fallback_text: &str,
+        messages: &[Vec<serde_json::Value>],
+    ) -> Result<SlackPostResult, String> {
+        let (first, replies) = messages
+            .split_first()
+            .ok_or_else(|| "Nothing to post to Slack".to_string())?;
+
+        let (channel_id, ts) = self
+            .post_message(channel, fallback_text, first, None)
+            .await?;
+
+        for reply in replies {
+            if let Err(e) = self
+                .post_message(&channel_id, fallback_text, reply, Some(&ts))
+                .await
+            {
+                log::warn!("Failed to post Slack thread reply: {}", e);
+                return Err(format!(
+                    "Posted the summary, but a thread reply failed: {}",
+                    e
+                ));
+            }
+        }
+
+        Ok(SlackPostResult {
+            channel: channel_id,
+            ts,
+            thread_replies: replies.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str, status: &str) -> ActionItemRecord {
+        ActionItemRecord {
+            id: "a".to_string(),
+            meeting_id: "m".to_string(),
+            text: text.to_string(),
+            assignee: Some("Dana".to_string()),
+            due_hint: None,
+            status: status.to_string(),
+            source: "manual".to_string(),
+            source_transcript_id: None,
+            calendar_event_id: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_split_text_respects_limit() {
+        let text = "a".repeat(10) + "\n" + &"b".repeat(10) + "\n" + &"c".repeat(25);
+        let chunks = split_text(&text, 12);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 12));
+        assert_eq!(chunks.concat().replace('\n', ""), text.replace('\n', ""));
+    }
+
+    #[test]
+    fn test_summary_overflow_goes_to_thread() {
+        let meeting = SlackMeetingSummary {
+            meeting_id: "m1".to_string(),
+            title: "Weekly <sync>".to_string(),
+            started_at: chrono::Utc::now(),
+            duration_seconds: Some(1800),
+            summary: Some("Shipped & reviewed.".to_string()),
+            action_items: (0..200)
+                .map(|i| {
+                    item(
+                        &format!("Follow up on item number {} with the team", i),
+                        "open",
+                    )
+                })
+                .collect(),
+            highlights: vec![SlackHighlight {
+                offset_ms: 65_000,
+                label: "Decision".to_string(),
+                text: "Go with plan B".to_string(),
+            }],
+        };
+
+        let messages = summary_messages(&meeting);
+        assert!(messages.len() >= 2);
+        assert!(messages.iter().all(|m| m.len() <= MAX_BLOCKS_PER_MESSAGE));
+        assert_eq!(messages[0][0]["type"], "header");
+
+        let all = serde_json::to_string(&messages).unwrap();
+        assert!(all.contains("Shipped &amp; reviewed."));
+        assert!(all.contains("☐ Follow up on item number 0 with the team — _Dana_"));
+        assert!(all.contains("`01:05` *Decision* Go with plan B"));
+        assert!(all.contains("nofriction://meetings/m1"));
+    }
+
+    #[test]
+    fn test_action_item_checkbox_and_errors() {
+        assert!(action_item_line(&item("Ship", "done")).starts_with("☑ "));
+        assert!(action_item_line(&item("Drop", "cancelled")).starts_with("☐ ~"));
+        assert!(describe_error("channel_not_found", "#eng").contains("#eng"));
+        assert!(describe_error("invalid_auth", "#eng").contains("bot token"));
+    }
+}
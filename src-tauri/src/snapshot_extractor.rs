@@ -11,6 +11,7 @@ use image::DynamicImage;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::path::Path;
 use uuid::Uuid;
 
 /// Configuration for snapshot extraction
@@ -134,6 +135,14 @@ pub enum ExtractionResult {
     Disabled,
 }
 
+impl ExtractionResult {
+    /// Whether this result should trigger the OCR fallback
+    /// (accessibility produced nothing usable, as opposed to too little text)
+    pub fn should_fall_back(&self) -> bool {
+        matches!(self, Self::Failed(_) | Self::LowQuality(_))
+    }
+}
+
 /// Tracker for checkpoint timing
 struct CheckpointTracker {
     last_checkpoint_ts: Option<DateTime<Utc>>,
@@ -216,6 +225,27 @@ impl SnapshotExtractor {
         }
     }
 
+    /// Run OCR on a keyframe already written to disk
+    pub fn extract_from_keyframe(
+        &self,
+        keyframe_path: &Path,
+        episode_id: Option<&str>,
+        state_id: Option<&str>,
+    ) -> ExtractionResult {
+        if !self.config.ocr_enabled {
+            return ExtractionResult::Disabled;
+        }
+
+        match image::open(keyframe_path) {
+            Ok(image) => self.extract_from_image(&image, episode_id, state_id),
+            Err(e) => ExtractionResult::Failed(format!(
+                "Failed to load keyframe {}: {}",
+                keyframe_path.display(),
+                e
+            )),
+        }
+    }
+
     /// Extract text using accessibility APIs (macOS)
     /// Faster and more accurate than OCR for apps that support accessibility
     pub fn extract_from_accessibility(
@@ -424,6 +454,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fallback_triggers() {
+        assert!(ExtractionResult::Failed("no text".to_string()).should_fall_back());
+        assert!(ExtractionResult::LowQuality(0.1).should_fall_back());
+        assert!(!ExtractionResult::TooShort.should_fall_back());
+        assert!(!ExtractionResult::Disabled.should_fall_back());
+    }
+
     #[test]
     fn test_checkpoint_logic() {
         let extractor = SnapshotExtractor::new();
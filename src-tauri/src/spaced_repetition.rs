@@ -0,0 +1,229 @@
+// noFriction Meetings - Spaced Repetition
+// SM-2 review scheduling for the flashcards Dork Mode generates.
+//
+// Grades use SM-2's 0-5 scale: below 3 is a lapse, so the card starts over
+// and is due again tomorrow; 3 and up stretches the interval by the card's
+// ease, which itself drifts with how hard each recall was.
+
+use crate::database::{DatabaseManager, FlashcardRecord};
+use crate::dork_mode::Flashcard;
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Ease every new card starts with
+pub const DEFAULT_EASE: f64 = 2.5;
+
+/// Ease never drops below this, or hard cards would come back daily forever
+const MIN_EASE: f64 = 1.3;
+
+/// Cards reviewed at this interval or longer count as mature
+pub const MATURE_INTERVAL_DAYS: i64 = 21;
+
+/// Highest grade (perfect recall)
+pub const MAX_GRADE: u8 = 5;
+
+/// Scheduling state of one card
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReviewState {
+    pub ease: f64,
+    pub interval_days: i64,
+    /// Successful reviews in a row
+    pub repetitions: i64,
+    pub lapses: i64,
+    pub due_at: DateTime<Utc>,
+}
+
+impl ReviewState {
+    /// A card nobody has reviewed yet, due right away
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            ease: DEFAULT_EASE,
+            interval_days: 0,
+            repetitions: 0,
+            lapses: 0,
+            due_at: now,
+        }
+    }
+
+    pub fn of(card: &FlashcardRecord) -> Self {
+        Self {
+            ease: card.ease,
+            interval_days: card.interval_days,
+            repetitions: card.repetitions,
+            lapses: card.lapses,
+            due_at: card.due_at,
+        }
+    }
+
+    /// Next state after a review graded 0-5
+    pub fn grade(&self, grade: u8, now: DateTime<Utc>) -> Self {
+        let q = grade.min(MAX_GRADE) as f64;
+        let ease = (self.ease + 0.1 - (5.0 - q) * (0.08 + (5.0 - q) * 0.02)).max(MIN_EASE);
+
+        let (interval_days, repetitions, lapses) = if grade >= 3 {
+            let interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => ((self.interval_days as f64) * self.ease).round() as i64,
+            };
+            (interval.max(1), self.repetitions + 1, self.lapses)
+        } else {
+            (1, 0, self.lapses + 1)
+        };
+
+        Self {
+            ease,
+            interval_days,
+            repetitions,
+            lapses,
+            due_at: now + Duration::days(interval_days),
+        }
+    }
+}
+
+/// Review counts for the study dashboard and the tray badge
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FlashcardStats {
+    pub total: i64,
+    pub due: i64,
+    /// Never reviewed
+    pub new: i64,
+    pub mature: i64,
+    pub reviewed_today: i64,
+}
+
+/// Dedup key for a card's front: case, punctuation and spacing ignored
+pub fn normalize_front(front: &str) -> String {
+    front
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Store a session's generated cards for review, skipping any whose front
+/// matches an existing card; returns how many were added
+pub async fn create_cards(
+    db: &DatabaseManager,
+    session_id: &str,
+    cards: &[Flashcard],
+) -> Result<usize, String> {
+    let now = Utc::now();
+    let mut added = 0;
+    for card in cards {
+        let key = normalize_front(&card.front);
+        if key.is_empty() || card.back.trim().is_empty() {
+            continue;
+        }
+        let inserted = db
+            .add_flashcard(
+                &uuid::Uuid::new_v4().to_string(),
+                session_id,
+                card.front.trim(),
+                card.back.trim(),
+                &key,
+                &ReviewState::new(now),
+            )
+            .await
+            .map_err(|e| format!("Failed to save flashcard: {}", e))?;
+        if inserted {
+            added += 1;
+        }
+    }
+    Ok(added)
+}
+
+/// Record a review and reschedule the card
+pub async fn grade_card(
+    db: &DatabaseManager,
+    card_id: &str,
+    grade: u8,
+) -> Result<FlashcardRecord, String> {
+    if grade > MAX_GRADE {
+        return Err(format!("Grade must be 0-{}", MAX_GRADE));
+    }
+    let card = db
+        .get_flashcard(card_id)
+        .await
+        .map_err(|e| format!("Failed to get flashcard: {}", e))?
+        .ok_or("Flashcard not found")?;
+
+    let now = Utc::now();
+    let next = ReviewState::of(&card).grade(grade, now);
+    db.update_flashcard_review(card_id, &next, now)
+        .await
+        .map_err(|e| format!("Failed to save review: {}", e))?;
+
+    Ok(FlashcardRecord {
+        ease: next.ease,
+        interval_days: next.interval_days,
+        repetitions: next.repetitions,
+        lapses: next.lapses,
+        due_at: next.due_at,
+        last_reviewed_at: Some(now),
+        ..card
+    })
+}
+
+/// Card counts, with "reviewed today" counted from local midnight
+pub async fn stats(db: &DatabaseManager) -> Result<FlashcardStats, String> {
+    let now = Utc::now();
+    let day_start = Local::now()
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .and_then(|d| d.and_local_timezone(Local).earliest())
+        .map(|d| d.with_timezone(&Utc))
+        .unwrap_or(now);
+    db.get_flashcard_stats(now, day_start, MATURE_INTERVAL_DAYS)
+        .await
+        .map_err(|e| format!("Failed to get flashcard stats: {}", e))
+}
+
+/// Publish the due count to the tray ("12 cards due")
+pub async fn refresh_due_badge(db: &DatabaseManager) {
+    match db.count_due_flashcards(Utc::now()).await {
+        Ok(due) => crate::tray_builder::update_tray_status(|s| s.cards_due = due as u32),
+        Err(e) => log::warn!("Failed to count due flashcards: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sm2_intervals() {
+        let now = Utc::now();
+        let first = ReviewState::new(now).grade(4, now);
+        assert_eq!((first.interval_days, first.repetitions), (1, 1));
+        assert!((first.ease - DEFAULT_EASE).abs() < 1e-9);
+
+        let second = first.grade(5, now);
+        assert_eq!(second.interval_days, 6);
+        assert!(second.ease > DEFAULT_EASE);
+
+        let third = second.grade(3, now);
+        assert_eq!(third.interval_days, (6.0 * second.ease).round() as i64);
+        assert_eq!(third.due_at, now + Duration::days(third.interval_days));
+
+        // A lapse starts the card over without dropping ease below the floor
+        let mut lapsed = third;
+        for _ in 0..10 {
+            lapsed = lapsed.grade(0, now);
+        }
+        assert_eq!((lapsed.interval_days, lapsed.repetitions), (1, 0));
+        assert_eq!(lapsed.lapses, 10);
+        assert_eq!(lapsed.ease, MIN_EASE);
+    }
+
+    #[test]
+    fn test_normalize_front() {
+        assert_eq!(
+            normalize_front("  What is  Osmosis? "),
+            normalize_front("what is osmosis")
+        );
+        assert_eq!(normalize_front("ATP-synthase"), "atp synthase");
+        assert_eq!(normalize_front("?!"), "");
+    }
+}
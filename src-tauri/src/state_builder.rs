@@ -11,6 +11,7 @@ use chrono::{DateTime, Utc};
 use image::DynamicImage;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -44,14 +45,18 @@ impl Default for StateConfig {
     }
 }
 
-/// State type classification
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// State type classification - the activity category of a state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum StateType {
-    TextDoc,
+    Coding,
+    #[serde(alias = "text_doc")]
+    Document,
     Browser,
-    Slide,
+    VideoCall,
     Terminal,
+    #[serde(alias = "slide")]
+    Slides,
     Video,
     Other,
 }
@@ -62,6 +67,35 @@ impl Default for StateType {
     }
 }
 
+impl StateType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Coding => "coding",
+            Self::Document => "document",
+            Self::Browser => "browser",
+            Self::VideoCall => "video_call",
+            Self::Terminal => "terminal",
+            Self::Slides => "slides",
+            Self::Video => "video",
+            Self::Other => "other",
+        }
+    }
+
+    /// Parse a stored category (unknown values map to `Other`)
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "coding" => Self::Coding,
+            "document" | "text_doc" => Self::Document,
+            "browser" => Self::Browser,
+            "video_call" => Self::VideoCall,
+            "terminal" => Self::Terminal,
+            "slides" | "slide" => Self::Slides,
+            "video" => Self::Video,
+            _ => Self::Other,
+        }
+    }
+}
+
 /// Flags for state characteristics
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StateFlags {
@@ -76,6 +110,8 @@ pub struct StateFlags {
 pub struct ScreenState {
     pub state_id: String,
     pub meeting_id: String,
+    /// Display the state was captured from
+    pub monitor_id: u32,
     pub start_ts: DateTime<Utc>,
     pub end_ts: Option<DateTime<Utc>>,
     pub app_name: Option<String>,
@@ -84,15 +120,18 @@ pub struct ScreenState {
     pub delta_score: f32,
     pub keyframe_path: Option<PathBuf>,
     pub state_type: StateType,
+    /// Confidence of `state_type` (0.0 - 1.0)
+    pub category_confidence: f32,
     pub flags: StateFlags,
 }
 
 impl ScreenState {
     /// Create a new state
-    pub fn new(meeting_id: &str, start_ts: DateTime<Utc>, phash: String) -> Self {
+    pub fn new(meeting_id: &str, monitor_id: u32, start_ts: DateTime<Utc>, phash: String) -> Self {
         Self {
             state_id: Uuid::new_v4().to_string(),
             meeting_id: meeting_id.to_string(),
+            monitor_id,
             start_ts,
             end_ts: None,
             app_name: None,
@@ -101,6 +140,7 @@ impl ScreenState {
             delta_score: 0.0,
             keyframe_path: None,
             state_type: StateType::Other,
+            category_confidence: 0.0,
             flags: StateFlags::default(),
         }
     }
@@ -130,16 +170,22 @@ pub enum FrameProcessResult {
 }
 
 /// State accumulator for tracking current state
+#[derive(Default)]
 struct StateAccumulator {
     current_state: Option<ScreenState>,
     pending_keyframe: Option<Arc<DynamicImage>>,
 }
 
+/// Dedup gate and open state for one display; displays never dedup against each other
+struct MonitorTrack {
+    dedup_gate: DedupGate,
+    accumulator: StateAccumulator,
+}
+
 /// State builder for converting frames into states
 pub struct StateBuilder {
     config: StateConfig,
-    dedup_gate: Mutex<DedupGate>,
-    accumulator: Mutex<StateAccumulator>,
+    monitors: Mutex<HashMap<u32, MonitorTrack>>,
     meeting_id: Mutex<Option<String>>,
 }
 
@@ -151,38 +197,44 @@ impl StateBuilder {
 
     /// Create with custom configuration
     pub fn with_config(config: StateConfig) -> Self {
-        let dedup_gate = DedupGate::with_config(config.dedup.clone());
-
         Self {
             config,
-            dedup_gate: Mutex::new(dedup_gate),
-            accumulator: Mutex::new(StateAccumulator {
-                current_state: None,
-                pending_keyframe: None,
-            }),
+            monitors: Mutex::new(HashMap::new()),
             meeting_id: Mutex::new(None),
         }
     }
 
+    /// Run `f` against a display's track, creating it on first use
+    fn with_track<T>(&self, monitor_id: u32, f: impl FnOnce(&mut MonitorTrack) -> T) -> T {
+        let mut monitors = self.monitors.lock();
+        let track = monitors.entry(monitor_id).or_insert_with(|| MonitorTrack {
+            dedup_gate: DedupGate::with_config(self.config.dedup.clone()),
+            accumulator: StateAccumulator::default(),
+        });
+        f(track)
+    }
+
     /// Start tracking a new meeting
     pub fn start_meeting(&self, meeting_id: &str) {
         *self.meeting_id.lock() = Some(meeting_id.to_string());
-        self.dedup_gate.lock().reset();
-        let mut acc = self.accumulator.lock();
-        acc.current_state = None;
-        acc.pending_keyframe = None;
+        self.monitors.lock().clear();
     }
 
-    /// End meeting and finalize current state
-    pub fn end_meeting(&self) -> Option<ScreenState> {
+    /// End meeting and finalize the open state on every display
+    pub fn end_meeting(&self) -> Vec<ScreenState> {
         *self.meeting_id.lock() = None;
-        self.finalize_current_state()
+        let monitor_ids: Vec<u32> = self.monitors.lock().keys().copied().collect();
+        monitor_ids
+            .into_iter()
+            .filter_map(|monitor_id| self.finalize_current_state(monitor_id))
+            .collect()
     }
 
-    /// Process a frame and determine if it's a state boundary
+    /// Process a frame from one display and determine if it's a state boundary
     /// Returns the processing result with state information
     pub fn process_frame(
         &self,
+        monitor_id: u32,
         image: Arc<DynamicImage>,
         timestamp: DateTime<Utc>,
     ) -> FrameProcessResult {
@@ -195,30 +247,26 @@ impl StateBuilder {
             None => return FrameProcessResult::PassThrough,
         };
 
-        // Run deduplication check
-        let dedup_result = self.dedup_gate.lock().check_frame(&image);
+        // Run deduplication check against this display's previous frame
+        let (dedup_result, open_duration_ms) = self.with_track(monitor_id, |track| {
+            let dedup_result = track.dedup_gate.check_frame(&image);
+            let open_duration_ms = track
+                .accumulator
+                .current_state
+                .as_ref()
+                .map(|state| (timestamp - state.start_ts).num_milliseconds() as u64);
+            (dedup_result, open_duration_ms)
+        });
 
         // Check for forced state boundary (max duration)
-        let force_boundary = {
-            let acc = self.accumulator.lock();
-            if let Some(ref state) = acc.current_state {
-                let duration = (timestamp - state.start_ts).num_milliseconds() as u64;
-                duration >= self.config.max_state_duration_ms
-            } else {
-                false
-            }
-        };
+        let force_boundary = open_duration_ms
+            .map(|duration| duration >= self.config.max_state_duration_ms)
+            .unwrap_or(false);
 
         // Check for suppressed boundary (min duration)
-        let suppress_boundary = {
-            let acc = self.accumulator.lock();
-            if let Some(ref state) = acc.current_state {
-                let duration = (timestamp - state.start_ts).num_milliseconds() as u64;
-                duration < self.config.min_state_duration_ms
-            } else {
-                false
-            }
-        };
+        let suppress_boundary = open_duration_ms
+            .map(|duration| duration < self.config.min_state_duration_ms)
+            .unwrap_or(false);
 
         // Decision: extend or new state?
         let is_boundary = if force_boundary {
@@ -231,8 +279,9 @@ impl StateBuilder {
 
         if is_boundary {
             // State boundary - finalize current and start new
-            let completed = self.finalize_current_state();
-            let new_state_id = self.open_new_state(&meeting_id, timestamp, image, &dedup_result);
+            let completed = self.finalize_current_state(monitor_id);
+            let new_state_id =
+                self.open_new_state(&meeting_id, monitor_id, timestamp, image, &dedup_result);
 
             FrameProcessResult::NewState {
                 completed_state: completed,
@@ -240,8 +289,8 @@ impl StateBuilder {
             }
         } else {
             // Extend current state
-            let mut acc = self.accumulator.lock();
-            if let Some(ref mut state) = acc.current_state {
+            let extended = self.with_track(monitor_id, |track| {
+                let state = track.accumulator.current_state.as_mut()?;
                 state.end_ts = Some(timestamp);
 
                 // Update flags based on dedup reason
@@ -255,79 +304,100 @@ impl StateBuilder {
                     _ => {}
                 }
 
-                FrameProcessResult::Extended {
-                    state_id: state.state_id.clone(),
+                Some(state.state_id.clone())
+            });
+
+            match extended {
+                Some(state_id) => FrameProcessResult::Extended {
+                    state_id,
                     new_end_ts: timestamp,
-                }
-            } else {
-                // No current state, start one
-                drop(acc);
-                let new_state_id =
-                    self.open_new_state(&meeting_id, timestamp, image, &dedup_result);
-
-                FrameProcessResult::NewState {
-                    completed_state: None,
-                    new_state_id,
+                },
+                None => {
+                    // No current state, start one
+                    let new_state_id = self.open_new_state(
+                        &meeting_id,
+                        monitor_id,
+                        timestamp,
+                        image,
+                        &dedup_result,
+                    );
+
+                    FrameProcessResult::NewState {
+                        completed_state: None,
+                        new_state_id,
+                    }
                 }
             }
         }
     }
 
-    /// Get the current pending keyframe (for saving)
-    pub fn take_pending_keyframe(&self) -> Option<Arc<DynamicImage>> {
-        self.accumulator.lock().pending_keyframe.take()
+    /// Close the current state so the next frame opens a new one
+    /// (used when frames in between were redacted by the privacy filter)
+    pub fn break_state(&self, monitor_id: u32) -> Option<ScreenState> {
+        self.with_track(monitor_id, |track| {
+            track.dedup_gate.reset();
+            track.accumulator.pending_keyframe = None;
+        });
+        self.finalize_current_state(monitor_id)
     }
 
-    /// Get current state info (for monitoring)
-    pub fn current_state_id(&self) -> Option<String> {
-        self.accumulator
-            .lock()
-            .current_state
-            .as_ref()
-            .map(|s| s.state_id.clone())
+    /// Get a display's pending keyframe (for saving)
+    pub fn take_pending_keyframe(&self, monitor_id: u32) -> Option<Arc<DynamicImage>> {
+        self.with_track(monitor_id, |track| {
+            track.accumulator.pending_keyframe.take()
+        })
     }
 
-    /// Finalize and return the current state
-    fn finalize_current_state(&self) -> Option<ScreenState> {
-        let mut acc = self.accumulator.lock();
+    /// Get a display's current state info (for monitoring)
+    pub fn current_state_id(&self, monitor_id: u32) -> Option<String> {
+        self.with_track(monitor_id, |track| {
+            track
+                .accumulator
+                .current_state
+                .as_ref()
+                .map(|s| s.state_id.clone())
+        })
+    }
 
-        if let Some(mut state) = acc.current_state.take() {
+    /// Finalize and return a display's current state
+    fn finalize_current_state(&self, monitor_id: u32) -> Option<ScreenState> {
+        self.with_track(monitor_id, |track| {
+            let mut state = track.accumulator.current_state.take()?;
             // Ensure end_ts is set
             if state.end_ts.is_none() {
                 state.end_ts = Some(Utc::now());
             }
             Some(state)
-        } else {
-            None
-        }
+        })
     }
 
     /// Open a new state
     fn open_new_state(
         &self,
         meeting_id: &str,
+        monitor_id: u32,
         timestamp: DateTime<Utc>,
         image: Arc<DynamicImage>,
         dedup_result: &DedupResult,
     ) -> String {
-        let mut acc = self.accumulator.lock();
-
         let phash_str = DedupGate::hash_to_string(&dedup_result.ahash);
-        let mut state = ScreenState::new(meeting_id, timestamp, phash_str);
+        let mut state = ScreenState::new(meeting_id, monitor_id, timestamp, phash_str);
         state.delta_score = dedup_result.delta_score;
         state.end_ts = Some(timestamp); // Initially same as start
 
         let state_id = state.state_id.clone();
 
-        acc.current_state = Some(state);
-        acc.pending_keyframe = Some(image);
+        self.with_track(monitor_id, |track| {
+            track.accumulator.current_state = Some(state);
+            track.accumulator.pending_keyframe = Some(image);
+        });
 
         state_id
     }
 
     /// Update config at runtime
     pub fn update_config(&mut self, config: StateConfig) {
-        self.dedup_gate.lock().reset();
+        self.monitors.lock().clear();
         self.config = config;
     }
 }
@@ -357,7 +427,7 @@ mod tests {
         builder.start_meeting("test_meeting");
 
         let img = create_test_image(128);
-        let result = builder.process_frame(img, Utc::now());
+        let result = builder.process_frame(1, img, Utc::now());
 
         match result {
             FrameProcessResult::NewState { new_state_id, .. } => {
@@ -375,14 +445,37 @@ mod tests {
         let img = create_test_image(128);
 
         // First frame -> new state
-        builder.process_frame(img.clone(), Utc::now());
+        builder.process_frame(1, img.clone(), Utc::now());
 
         // Same frame -> extend
-        let result = builder.process_frame(img, Utc::now());
+        let result = builder.process_frame(1, img, Utc::now());
 
         match result {
             FrameProcessResult::Extended { .. } => {}
             _ => panic!("Expected Extended for duplicate frame"),
         }
     }
+
+    #[test]
+    fn test_monitors_dedup_independently() {
+        let builder = StateBuilder::new();
+        builder.start_meeting("test_meeting");
+
+        let img = create_test_image(128);
+        builder.process_frame(1, img.clone(), Utc::now());
+
+        // Same pixels on another display still open that display's first state
+        match builder.process_frame(2, img.clone(), Utc::now()) {
+            FrameProcessResult::NewState { .. } => {}
+            _ => panic!("Expected NewState for a second monitor"),
+        }
+        assert!(builder.take_pending_keyframe(1).is_some());
+        assert!(builder.take_pending_keyframe(2).is_some());
+
+        match builder.process_frame(1, img, Utc::now()) {
+            FrameProcessResult::Extended { .. } => {}
+            _ => panic!("Expected Extended on the first monitor"),
+        }
+        assert_eq!(builder.end_meeting().len(), 2);
+    }
 }
@@ -5,6 +5,8 @@
 // - Enumerate storage usage per meeting (frames, video, audio)
 // - Safe deletion with path allowlist validation
 // - Atomic operations with audit logging hooks
+// - Keyframe retention: prune oldest keyframes (and archived audio chunks)
+//   by age and/or total size
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -12,6 +14,8 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tokio::fs;
 
+use crate::database::{DatabaseManager, PrunableKeyframe};
+
 /// Represents storage usage for a single meeting
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageItem {
@@ -61,6 +65,89 @@ pub struct DeleteResult {
     pub errors: Vec<String>,
 }
 
+/// Keyframe and audio retention policy (either limit may be off)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Delete keyframes and audio chunks older than this many days
+    pub max_age_days: Option<u32>,
+    /// Delete oldest files until frames/ and audio/ together are under this size
+    pub max_total_bytes: Option<u64>,
+}
+
+impl RetentionPolicy {
+    /// Build from settings values, where 0 disables a limit
+    pub fn from_settings(max_days: u32, max_total_gb: f32) -> Self {
+        Self {
+            max_age_days: (max_days > 0).then_some(max_days),
+            max_total_bytes: (max_total_gb > 0.0)
+                .then(|| (max_total_gb as f64 * 1024.0 * 1024.0 * 1024.0) as u64),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_age_days.is_some() || self.max_total_bytes.is_some()
+    }
+}
+
+/// What a cleanup run would delete
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupPreview {
+    pub policy: RetentionPolicy,
+    pub frames_dir_bytes: u64,
+    pub audio_dir_bytes: u64,
+    pub files: u32,
+    pub bytes: u64,
+    pub bytes_formatted: String,
+    /// Files to delete per meeting
+    pub by_meeting: HashMap<String, u32>,
+    pub oldest: Option<DateTime<Utc>>,
+    pub newest: Option<DateTime<Utc>>,
+    /// First few paths, for display
+    pub sample_paths: Vec<String>,
+    #[serde(skip)]
+    selected: Vec<PrunableKeyframe>,
+}
+
+/// Result of a cleanup run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CleanupResult {
+    pub files_deleted: u32,
+    pub bytes_freed: u64,
+    pub rows_deleted: u64,
+    pub errors: Vec<String>,
+}
+
+/// Paths shown in a cleanup preview
+const CLEANUP_SAMPLE_PATHS: usize = 20;
+
+/// Pick keyframes to delete, oldest first: anything past the age limit, then
+/// more until the remaining total fits under the size limit
+pub fn select_for_retention(
+    candidates: Vec<(PrunableKeyframe, u64)>,
+    policy: &RetentionPolicy,
+    total_bytes: u64,
+    now: DateTime<Utc>,
+) -> Vec<(PrunableKeyframe, u64)> {
+    let cutoff = policy
+        .max_age_days
+        .map(|days| now - chrono::Duration::days(days as i64));
+    let mut remaining = total_bytes;
+    let mut selected = Vec::new();
+
+    for (keyframe, bytes) in candidates {
+        let too_old = cutoff.is_some_and(|c| keyframe.captured_at < c);
+        let over_size = policy.max_total_bytes.is_some_and(|max| remaining > max);
+        if !too_old && !over_size {
+            // Candidates are oldest-first, so nothing later qualifies either
+            break;
+        }
+        remaining = remaining.saturating_sub(bytes);
+        selected.push((keyframe, bytes));
+    }
+
+    selected
+}
+
 /// Storage manager for safe filesystem operations
 pub struct StorageManager {
     app_data_dir: PathBuf,
@@ -209,6 +296,18 @@ impl StorageManager {
         Ok((count, size))
     }
 
+    /// Total size of a top-level app data subdirectory (0 if missing)
+    async fn subdir_bytes(&self, subdir: &str) -> u64 {
+        let root = self.app_data_dir.join(subdir);
+        if !root.exists() {
+            return 0;
+        }
+        self.count_directory_size(&root)
+            .await
+            .map(|(_, bytes)| bytes)
+            .unwrap_or(0)
+    }
+
     /// Generate delete preview for multiple meetings
     pub async fn preview_delete(&self, meeting_ids: &[String]) -> DeletePreview {
         let mut breakdown = HashMap::new();
@@ -342,6 +441,119 @@ impl StorageManager {
         result
     }
 
+    /// Work out which keyframes and audio chunks the policy would delete
+    /// (nothing is touched). Files of `skip_meeting` (currently recording)
+    /// are never selected.
+    pub async fn preview_cleanup(
+        &self,
+        database: &DatabaseManager,
+        policy: RetentionPolicy,
+        skip_meeting: Option<&str>,
+    ) -> Result<CleanupPreview, String> {
+        let frames_dir_bytes = self.subdir_bytes("frames").await;
+        let audio_dir_bytes = self.subdir_bytes("audio").await;
+
+        let mut preview = CleanupPreview {
+            policy,
+            frames_dir_bytes,
+            audio_dir_bytes,
+            files: 0,
+            bytes: 0,
+            bytes_formatted: Self::format_bytes(0),
+            by_meeting: HashMap::new(),
+            oldest: None,
+            newest: None,
+            sample_paths: Vec::new(),
+            selected: Vec::new(),
+        };
+        if !policy.is_enabled() {
+            return Ok(preview);
+        }
+
+        let keyframes = database
+            .get_prunable_keyframes(skip_meeting)
+            .await
+            .map_err(|e| format!("Failed to list keyframes: {}", e))?;
+
+        let mut candidates = Vec::with_capacity(keyframes.len());
+        for keyframe in keyframes {
+            let bytes = fs::metadata(&keyframe.path)
+                .await
+                .map(|m| m.len())
+                .unwrap_or(0);
+            candidates.push((keyframe, bytes));
+        }
+
+        let total_bytes = frames_dir_bytes + audio_dir_bytes;
+        for (keyframe, bytes) in select_for_retention(candidates, &policy, total_bytes, Utc::now())
+        {
+            preview.files += 1;
+            preview.bytes += bytes;
+            *preview
+                .by_meeting
+                .entry(keyframe.meeting_id.clone())
+                .or_insert(0) += 1;
+            preview.oldest = preview.oldest.or(Some(keyframe.captured_at));
+            preview.newest = Some(keyframe.captured_at);
+            if preview.sample_paths.len() < CLEANUP_SAMPLE_PATHS {
+                preview.sample_paths.push(keyframe.path.clone());
+            }
+            preview.selected.push(keyframe);
+        }
+        preview.bytes_formatted = Self::format_bytes(preview.bytes);
+
+        Ok(preview)
+    }
+
+    /// Delete the keyframe/audio files and rows selected by `preview_cleanup`
+    pub async fn run_cleanup(
+        &self,
+        database: &DatabaseManager,
+        preview: &CleanupPreview,
+    ) -> CleanupResult {
+        let mut result = CleanupResult::default();
+        let mut removed = Vec::with_capacity(preview.selected.len());
+
+        for keyframe in &preview.selected {
+            let path = Path::new(&keyframe.path);
+            if !path.exists() {
+                // File already gone - just drop the stale rows
+                removed.push(keyframe.clone());
+                continue;
+            }
+            if let Err(e) = self.validate_path(path) {
+                result.errors.push(e);
+                continue;
+            }
+
+            let bytes = fs::metadata(path).await.map(|m| m.len()).unwrap_or(0);
+            match fs::remove_file(path).await {
+                Ok(()) => {
+                    result.files_deleted += 1;
+                    result.bytes_freed += bytes;
+                    removed.push(keyframe.clone());
+                }
+                Err(e) => result
+                    .errors
+                    .push(format!("Failed to delete {}: {}", keyframe.path, e)),
+            }
+        }
+
+        match database.delete_pruned_keyframes(&removed).await {
+            Ok(rows) => result.rows_deleted = rows,
+            Err(e) => result
+                .errors
+                .push(format!("Failed to delete keyframe rows: {}", e)),
+        }
+
+        log::info!(
+            "Storage cleanup: {} files deleted, {} freed",
+            result.files_deleted,
+            Self::format_bytes(result.bytes_freed)
+        );
+        result
+    }
+
     /// Format bytes as human-readable string
     pub fn format_bytes(bytes: u64) -> String {
         const KB: u64 = 1024;
@@ -401,6 +613,46 @@ mod tests {
         assert_eq!(info.total_bytes, 0);
     }
 
+    fn keyframe(days_old: i64, now: DateTime<Utc>) -> PrunableKeyframe {
+        PrunableKeyframe {
+            source: "screen_state".to_string(),
+            id: format!("state_{}", days_old),
+            meeting_id: "m1".to_string(),
+            captured_at: now - chrono::Duration::days(days_old),
+            path: format!("/frames/m1/state_{}.jpg", days_old),
+        }
+    }
+
+    #[test]
+    fn test_select_for_retention_by_age_and_size() {
+        let now = Utc::now();
+        let candidates = || {
+            vec![
+                (keyframe(40, now), 100),
+                (keyframe(20, now), 100),
+                (keyframe(1, now), 100),
+            ]
+        };
+
+        let by_age = RetentionPolicy {
+            max_age_days: Some(30),
+            max_total_bytes: None,
+        };
+        assert_eq!(
+            select_for_retention(candidates(), &by_age, 300, now).len(),
+            1
+        );
+
+        // 300 bytes on disk, 150 allowed: the two oldest go
+        let by_size = RetentionPolicy {
+            max_age_days: None,
+            max_total_bytes: Some(150),
+        };
+        let selected = select_for_retention(candidates(), &by_size, 300, now);
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected[1].0.id, "state_20");
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(StorageManager::format_bytes(500), "500 bytes");
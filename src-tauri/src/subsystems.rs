@@ -0,0 +1,557 @@
+// noFriction Meetings - Optional Subsystems
+// Startup only requires the core (database, settings, capture engine,
+// transcription). Everything that talks to the network, the calendar or
+// other files is optional: each is initialized concurrently under its own
+// timeout and its outcome is recorded here instead of failing the app.
+//
+// A subsystem that failed at startup is retried lazily the next time a
+// command needs it (at most once per RETRY_BACKOFF). Every status change is
+// emitted as `subsystem-status`.
+
+use crate::accessibility_capture::AccessibilityCaptureService;
+use crate::calendar_client::{CalendarAccessStatus, CalendarClient};
+use crate::database::DatabaseManager;
+use crate::ingest_client::IngestClient;
+use crate::ingest_queue::IngestQueue;
+use crate::ingest_uploader::IngestUploader;
+use crate::obsidian_vault::VaultManager;
+use crate::pinecone_client::{PineconeClient, PineconeNamespaces};
+use crate::settings::{AppSettings, SettingsManager};
+use crate::supabase_client::SupabaseClient;
+use crate::AppState;
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+pub const PINECONE: &str = "pinecone";
+pub const SUPABASE: &str = "supabase";
+pub const INGEST: &str = "ingest";
+pub const CALENDAR: &str = "calendar";
+pub const VAULT: &str = "vault";
+pub const ACCESSIBILITY_CAPTURE: &str = "accessibility_capture";
+
+/// Optional subsystems in the order they are reported
+pub const OPTIONAL_SUBSYSTEMS: [&str; 6] = [
+    PINECONE,
+    SUPABASE,
+    INGEST,
+    CALENDAR,
+    VAULT,
+    ACCESSIBILITY_CAPTURE,
+];
+
+/// Per-subsystem initialization timeout
+pub const OPTIONAL_INIT_TIMEOUT: Duration = Duration::from_secs(8);
+/// Minimum time between lazy retries of a failed subsystem
+const RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubsystemState {
+    Pending,
+    Ready,
+    /// Not configured or not permitted; nothing to retry
+    Disabled,
+    /// Errored or timed out; retried on next use
+    Failed,
+}
+
+/// Status of one optional subsystem, as returned to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct SubsystemStatus {
+    pub name: String,
+    pub label: String,
+    pub state: SubsystemState,
+    pub message: Option<String>,
+    pub attempts: u32,
+    pub duration_ms: Option<u64>,
+    pub updated_at: String,
+}
+
+/// Successful initialization: running, or intentionally off
+pub enum SubsystemOutcome {
+    Ready(Option<String>),
+    Disabled(String),
+}
+
+/// Display name used in init-step messages
+pub fn label(name: &str) -> &'static str {
+    match name {
+        PINECONE => "Pinecone",
+        SUPABASE => "Supabase",
+        INGEST => "Ingest pipeline",
+        CALENDAR => "Calendar",
+        VAULT => "Obsidian vault",
+        ACCESSIBILITY_CAPTURE => "Accessibility capture",
+        _ => "Unknown subsystem",
+    }
+}
+
+struct Entry {
+    status: SubsystemStatus,
+    started: Option<Instant>,
+}
+
+pub struct Subsystems {
+    entries: Mutex<BTreeMap<&'static str, Entry>>,
+    app: Mutex<Option<AppHandle>>,
+}
+
+impl Subsystems {
+    fn new() -> Self {
+        let entries = OPTIONAL_SUBSYSTEMS
+            .iter()
+            .map(|name| {
+                let status = SubsystemStatus {
+                    name: name.to_string(),
+                    label: label(name).to_string(),
+                    state: SubsystemState::Pending,
+                    message: None,
+                    attempts: 0,
+                    duration_ms: None,
+                    updated_at: chrono::Utc::now().to_rfc3339(),
+                };
+                (
+                    *name,
+                    Entry {
+                        status,
+                        started: None,
+                    },
+                )
+            })
+            .collect();
+        Self {
+            entries: Mutex::new(entries),
+            app: Mutex::new(None),
+        }
+    }
+
+    pub fn set_app_handle(&self, app: AppHandle) {
+        *self.app.lock() = Some(app);
+    }
+
+    /// All subsystems in reporting order
+    pub fn snapshot(&self) -> Vec<SubsystemStatus> {
+        let entries = self.entries.lock();
+        OPTIONAL_SUBSYSTEMS
+            .iter()
+            .filter_map(|name| entries.get(name).map(|e| e.status.clone()))
+            .collect()
+    }
+
+    pub fn state(&self, name: &str) -> Option<SubsystemState> {
+        self.entries.lock().get(name).map(|e| e.status.state)
+    }
+
+    /// Labels of subsystems still initializing
+    pub fn pending(&self) -> Vec<&'static str> {
+        self.entries
+            .lock()
+            .iter()
+            .filter(|(_, e)| e.status.state == SubsystemState::Pending)
+            .map(|(name, _)| label(name))
+            .collect()
+    }
+
+    /// Mark `name` as initializing. With `retry`, only a failed subsystem
+    /// whose last attempt is older than RETRY_BACKOFF is claimed.
+    fn begin(&self, name: &str, retry: bool) -> bool {
+        let mut entries = self.entries.lock();
+        let Some(entry) = entries.get_mut(name) else {
+            return false;
+        };
+        if retry {
+            let backed_off = entry
+                .started
+                .map_or(true, |at| at.elapsed() >= RETRY_BACKOFF);
+            if entry.status.state != SubsystemState::Failed || !backed_off {
+                return false;
+            }
+        }
+        entry.started = Some(Instant::now());
+        entry.status.state = SubsystemState::Pending;
+        entry.status.attempts += 1;
+        entry.status.updated_at = chrono::Utc::now().to_rfc3339();
+        let status = entry.status.clone();
+        drop(entries);
+        self.emit(&status);
+        true
+    }
+
+    /// Record the outcome of an attempt (or of a configure command)
+    pub fn record(&self, name: &str, result: Result<SubsystemOutcome, String>) {
+        let mut entries = self.entries.lock();
+        let Some(entry) = entries.get_mut(name) else {
+            return;
+        };
+        let (state, message) = match result {
+            Ok(SubsystemOutcome::Ready(message)) => (SubsystemState::Ready, message),
+            Ok(SubsystemOutcome::Disabled(reason)) => (SubsystemState::Disabled, Some(reason)),
+            Err(e) => (SubsystemState::Failed, Some(e)),
+        };
+        match state {
+            SubsystemState::Failed => log::warn!(
+                "{} unavailable: {}",
+                label(name),
+                message.as_deref().unwrap_or("")
+            ),
+            _ => log::info!("{} {:?}", label(name), state),
+        }
+        entry.status.state = state;
+        entry.status.message = message;
+        entry.status.duration_ms = entry.started.map(|at| at.elapsed().as_millis() as u64);
+        entry.status.updated_at = chrono::Utc::now().to_rfc3339();
+        let status = entry.status.clone();
+        drop(entries);
+        self.emit(&status);
+    }
+
+    async fn attempt(
+        &self,
+        name: &str,
+        init: impl Future<Output = Result<SubsystemOutcome, String>>,
+    ) -> SubsystemState {
+        let result = match tokio::time::timeout(OPTIONAL_INIT_TIMEOUT, init).await {
+            Ok(result) => result,
+            Err(_) => Err(format!(
+                "Timed out after {}s",
+                OPTIONAL_INIT_TIMEOUT.as_secs()
+            )),
+        };
+        self.record(name, result);
+        self.state(name).unwrap_or(SubsystemState::Failed)
+    }
+
+    /// Initialize `name` at startup, bounded by OPTIONAL_INIT_TIMEOUT
+    pub async fn init(
+        &self,
+        name: &str,
+        init: impl Future<Output = Result<SubsystemOutcome, String>>,
+    ) -> SubsystemState {
+        self.begin(name, false);
+        self.attempt(name, init).await
+    }
+
+    /// Run `init` again if `name` failed and is due for a retry. Returns
+    /// whether an attempt was made.
+    pub async fn retry(
+        &self,
+        name: &str,
+        init: impl Future<Output = Result<SubsystemOutcome, String>>,
+    ) -> bool {
+        if !self.begin(name, true) {
+            return false;
+        }
+        log::info!("Retrying {}", label(name));
+        self.attempt(name, init).await;
+        true
+    }
+
+    fn emit(&self, status: &SubsystemStatus) {
+        if let Some(app) = self.app.lock().as_ref() {
+            let _ = app.emit("subsystem-status", status);
+        }
+    }
+}
+
+pub fn subsystems() -> &'static Subsystems {
+    static SUBSYSTEMS: OnceLock<Subsystems> = OnceLock::new();
+    SUBSYSTEMS.get_or_init(Subsystems::new)
+}
+
+/// Ingest client and uploader, filled in once the ingest subsystem is up
+#[derive(Clone, Default)]
+pub struct IngestSlots {
+    pub client: Arc<RwLock<Option<Arc<IngestClient>>>>,
+    pub uploader: Arc<RwLock<Option<Arc<IngestUploader>>>>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Initializers (shared by startup and lazy retries)
+// ═══════════════════════════════════════════════════════════════════════════
+
+pub fn init_pinecone(
+    pinecone: &RwLock<PineconeClient>,
+    saved: &AppSettings,
+) -> Result<SubsystemOutcome, String> {
+    let api_key = saved.pinecone_api_key.clone().unwrap_or_default();
+    let index_host = saved.pinecone_index_host.clone().unwrap_or_default();
+    if api_key.is_empty() || index_host.is_empty() {
+        return Ok(SubsystemOutcome::Disabled("Not configured".to_string()));
+    }
+    pinecone.read().configure(
+        api_key,
+        index_host,
+        saved.pinecone_namespace.clone(),
+        PineconeNamespaces::from_settings(saved),
+    );
+    Ok(SubsystemOutcome::Ready(None))
+}
+
+pub async fn init_supabase(
+    supabase: &RwLock<SupabaseClient>,
+    saved: &AppSettings,
+) -> Result<SubsystemOutcome, String> {
+    let conn_str = saved.supabase_connection_string.clone().unwrap_or_default();
+    if conn_str.is_empty() {
+        return Ok(SubsystemOutcome::Disabled("Not configured".to_string()));
+    }
+    supabase.read().set_connection_string(conn_str.clone());
+    let pool = crate::supabase_client::supabase_connect_pool(&conn_str).await?;
+    supabase.read().set_pool(pool);
+    Ok(SubsystemOutcome::Ready(None))
+}
+
+/// Move the queue onto its file and, when ingest is enabled and configured,
+/// start the client and uploader
+pub async fn init_ingest(
+    queue: IngestQueue,
+    queue_path: PathBuf,
+    slots: IngestSlots,
+    settings: Arc<SettingsManager>,
+    saved: &AppSettings,
+) -> Result<SubsystemOutcome, String> {
+    let reopened = queue.clone();
+    tokio::task::spawn_blocking(move || {
+        reopened
+            .reopen(&queue_path)
+            .map_err(|e| format!("Failed to open ingest queue: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    if !saved.enable_ingest.unwrap_or(false) {
+        return Ok(SubsystemOutcome::Disabled("Ingest disabled".to_string()));
+    }
+    let (Some(base_url), Some(bearer_token)) = (
+        saved.ingest_base_url.as_ref(),
+        saved.ingest_bearer_token.as_ref(),
+    ) else {
+        return Ok(SubsystemOutcome::Disabled(
+            "Ingest enabled but missing configuration".to_string(),
+        ));
+    };
+
+    if slots.uploader.read().is_none() {
+        log::info!("Ingest enabled, creating client for: {}", base_url);
+        let client = Arc::new(IngestClient::new(base_url.clone(), bearer_token.clone()));
+        // Drain the ingest queue in the background while a client is configured
+        let uploader = Arc::new(IngestUploader::new(client.clone(), queue, settings));
+        uploader.start();
+        *slots.client.write() = Some(client);
+        *slots.uploader.write() = Some(uploader);
+    }
+    Ok(SubsystemOutcome::Ready(None))
+}
+
+/// Prime the event cache; EventKit can be slow, so it runs off the runtime
+pub async fn init_calendar(
+    calendar: Arc<RwLock<CalendarClient>>,
+) -> Result<SubsystemOutcome, String> {
+    tokio::task::spawn_blocking(move || match CalendarClient::check_access() {
+        CalendarAccessStatus::Authorized => {
+            let events = calendar.read().fetch_events()?;
+            Ok(SubsystemOutcome::Ready(Some(format!(
+                "{} upcoming events",
+                events.len()
+            ))))
+        }
+        status => Ok(SubsystemOutcome::Disabled(format!(
+            "Calendar access {:?}",
+            status
+        ))),
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+pub async fn init_vault(
+    vault: &VaultManager,
+    saved: &AppSettings,
+) -> Result<SubsystemOutcome, String> {
+    let Some(vault_path) = saved.obsidian_vault_path.clone().filter(|p| !p.is_empty()) else {
+        return Ok(SubsystemOutcome::Disabled("Not configured".to_string()));
+    };
+    vault.set_vault_path(vault_path.clone());
+    // Network and removable volumes can take a while to answer
+    match tokio::fs::metadata(&vault_path).await {
+        Ok(meta) if meta.is_dir() => Ok(SubsystemOutcome::Ready(None)),
+        Ok(_) => Err(format!("Vault path is not a folder: {}", vault_path)),
+        Err(e) => Err(format!("Vault folder unavailable ({}): {}", vault_path, e)),
+    }
+}
+
+pub fn init_accessibility_capture(
+    capture: &AccessibilityCaptureService,
+    database: Arc<DatabaseManager>,
+    settings: Arc<SettingsManager>,
+    pinecone: Arc<RwLock<PineconeClient>>,
+    saved: &AppSettings,
+) -> Result<SubsystemOutcome, String> {
+    if !saved.accessibility_capture_enabled {
+        return Ok(SubsystemOutcome::Disabled(
+            "Disabled in settings".to_string(),
+        ));
+    }
+    match capture.start(database, settings, pinecone) {
+        Ok(()) => Ok(SubsystemOutcome::Ready(None)),
+        // Waiting on the user, not worth retrying
+        Err(e) if !crate::accessibility_extractor::AccessibilityExtractor::is_trusted() => {
+            Ok(SubsystemOutcome::Disabled(e))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Lazy retries, called by the commands that need each subsystem
+// ═══════════════════════════════════════════════════════════════════════════
+
+async fn saved_settings(state: &AppState) -> AppSettings {
+    state.settings.get_all().await.unwrap_or_default()
+}
+
+pub async fn ensure_pinecone(state: &AppState) {
+    if subsystems().state(PINECONE) != Some(SubsystemState::Failed) {
+        return;
+    }
+    let saved = saved_settings(state).await;
+    subsystems()
+        .retry(PINECONE, async {
+            init_pinecone(&state.pinecone_client, &saved)
+        })
+        .await;
+}
+
+pub async fn ensure_supabase(state: &AppState) {
+    if subsystems().state(SUPABASE) != Some(SubsystemState::Failed) {
+        return;
+    }
+    let saved = saved_settings(state).await;
+    subsystems()
+        .retry(SUPABASE, init_supabase(&state.supabase_client, &saved))
+        .await;
+}
+
+pub async fn ensure_ingest(state: &AppState) {
+    if subsystems().state(INGEST) != Some(SubsystemState::Failed) {
+        return;
+    }
+    let saved = saved_settings(state).await;
+    let queue = state.ingest_queue.lock().clone();
+    subsystems()
+        .retry(
+            INGEST,
+            init_ingest(
+                queue,
+                state.ingest_queue_path.clone(),
+                state.ingest.clone(),
+                state.settings.clone(),
+                &saved,
+            ),
+        )
+        .await;
+}
+
+/// Initialize ingest again whatever its state, e.g. after it was enabled
+pub async fn reload_ingest(state: &AppState) -> SubsystemState {
+    let saved = saved_settings(state).await;
+    let queue = state.ingest_queue.lock().clone();
+    subsystems()
+        .init(
+            INGEST,
+            init_ingest(
+                queue,
+                state.ingest_queue_path.clone(),
+                state.ingest.clone(),
+                state.settings.clone(),
+                &saved,
+            ),
+        )
+        .await
+}
+
+pub async fn ensure_calendar(state: &AppState) {
+    if subsystems().state(CALENDAR) != Some(SubsystemState::Failed) {
+        return;
+    }
+    subsystems()
+        .retry(CALENDAR, init_calendar(state.calendar_client.clone()))
+        .await;
+}
+
+pub async fn ensure_vault(state: &AppState) {
+    if subsystems().state(VAULT) != Some(SubsystemState::Failed) {
+        return;
+    }
+    let saved = saved_settings(state).await;
+    subsystems()
+        .retry(VAULT, init_vault(&state.vault_manager, &saved))
+        .await;
+}
+
+pub async fn ensure_accessibility_capture(state: &AppState) {
+    if subsystems().state(ACCESSIBILITY_CAPTURE) != Some(SubsystemState::Failed) {
+        return;
+    }
+    let saved = saved_settings(state).await;
+    subsystems()
+        .retry(ACCESSIBILITY_CAPTURE, async {
+            init_accessibility_capture(
+                &state.accessibility_capture,
+                state.database.clone(),
+                state.settings.clone(),
+                state.pinecone_client.clone(),
+                &saved,
+            )
+        })
+        .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_failures_are_recorded_and_retried_after_backoff() {
+        let registry = Subsystems::new();
+        assert_eq!(registry.pending().len(), OPTIONAL_SUBSYSTEMS.len());
+
+        let state = registry
+            .init(SUPABASE, async { Err("dns lookup failed".to_string()) })
+            .await;
+        assert_eq!(state, SubsystemState::Failed);
+        registry
+            .init(VAULT, async {
+                Ok(SubsystemOutcome::Disabled("Not configured".to_string()))
+            })
+            .await;
+        assert!(!registry.pending().contains(&"Supabase"));
+
+        // Within the backoff window nothing is retried, and disabled
+        // subsystems never are
+        let unused = || async { Err("should not run".to_string()) };
+        assert!(!registry.retry(SUPABASE, unused()).await);
+        assert!(!registry.retry(VAULT, unused()).await);
+
+        registry.entries.lock().get_mut(SUPABASE).unwrap().started =
+            Instant::now().checked_sub(RETRY_BACKOFF);
+        assert!(
+            registry
+                .retry(SUPABASE, async { Ok(SubsystemOutcome::Ready(None)) })
+                .await
+        );
+        let supabase = registry
+            .snapshot()
+            .into_iter()
+            .find(|s| s.name == SUPABASE)
+            .unwrap();
+        assert_eq!(supabase.state, SubsystemState::Ready);
+        assert_eq!(supabase.attempts, 2);
+    }
+}
@@ -0,0 +1,222 @@
+// noFriction Meetings - Subtitle Export
+// Converts finalized transcript rows into SRT / WebVTT subtitle files
+//
+// Transcript rows only carry the time the final result was saved, which is
+// roughly the end of the utterance. Cue start times are estimated from the
+// text length and then clamped so cues never overlap or run backwards.
+
+use chrono::{DateTime, Utc};
+use std::fmt::Write;
+
+use crate::database::Transcript;
+
+/// Cues shorter than this are merged into the previous cue (same speaker)
+const MIN_CUE_MS: i64 = 1_000;
+/// Upper bound on an estimated cue length
+const MAX_CUE_MS: i64 = 10_000;
+/// Rough speaking rate used to estimate how long a fragment took to say
+const MS_PER_WORD: i64 = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+}
+
+impl std::str::FromStr for SubtitleFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "srt" => Ok(SubtitleFormat::Srt),
+            "vtt" | "webvtt" => Ok(SubtitleFormat::Vtt),
+            other => Err(format!("Unsupported subtitle format: {}", other)),
+        }
+    }
+}
+
+/// One subtitle cue, times relative to the meeting start
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleCue {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+fn estimate_duration_ms(text: &str) -> i64 {
+    let words = text.split_whitespace().count() as i64;
+    (words * MS_PER_WORD).clamp(MIN_CUE_MS, MAX_CUE_MS)
+}
+
+/// Build monotonically increasing cues from final transcripts
+pub fn build_cues(started_at: DateTime<Utc>, transcripts: &[Transcript]) -> Vec<SubtitleCue> {
+    let mut cues: Vec<SubtitleCue> = Vec::new();
+    let mut last_end = 0i64;
+
+    for t in transcripts.iter().filter(|t| t.is_final) {
+        let text = t.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        // Provider jitter can put a row before the previous one - never go backwards
+        let end = (t.timestamp - started_at).num_milliseconds().max(last_end);
+        let start = (end - estimate_duration_ms(text)).max(last_end);
+
+        if end - start < MIN_CUE_MS {
+            if let Some(prev) = cues.last_mut().filter(|c| c.speaker == t.speaker) {
+                prev.text.push(' ');
+                prev.text.push_str(text);
+                prev.end_ms = prev.end_ms.max(end);
+                last_end = prev.end_ms;
+                continue;
+            }
+        }
+
+        let end = end.max(start + MIN_CUE_MS);
+        cues.push(SubtitleCue {
+            start_ms: start,
+            end_ms: end,
+            speaker: t.speaker.clone(),
+            text: text.to_string(),
+        });
+        last_end = end;
+    }
+
+    cues
+}
+
+/// "HH:MM:SS,mmm" for SRT, "HH:MM:SS.mmm" for VTT
+fn format_timestamp(ms: i64, format: SubtitleFormat) -> String {
+    let ms = ms.max(0);
+    let (h, m, s, millis) = (
+        ms / 3_600_000,
+        (ms % 3_600_000) / 60_000,
+        (ms % 60_000) / 1000,
+        ms % 1000,
+    );
+    let sep = match format {
+        SubtitleFormat::Srt => ',',
+        SubtitleFormat::Vtt => '.',
+    };
+    format!("{:02}:{:02}:{:02}{}{:03}", h, m, s, sep, millis)
+}
+
+/// Render cues as a complete SRT or WebVTT document
+pub fn render(cues: &[SubtitleCue], format: SubtitleFormat) -> String {
+    let mut out = String::new();
+
+    if format == SubtitleFormat::Vtt {
+        out.push_str("WEBVTT\n\n");
+    }
+
+    for (i, cue) in cues.iter().enumerate() {
+        let _ = writeln!(out, "{}", i + 1);
+        let _ = writeln!(
+            out,
+            "{} --> {}",
+            format_timestamp(cue.start_ms, format),
+            format_timestamp(cue.end_ms, format)
+        );
+
+        match (&cue.speaker, format) {
+            (Some(speaker), SubtitleFormat::Srt) => {
+                let _ = writeln!(out, "{}: {}", speaker, cue.text);
+            }
+            (Some(speaker), SubtitleFormat::Vtt) => {
+                let _ = writeln!(out, "<v {}>{}", speaker, cue.text);
+            }
+            (None, _) => {
+                let _ = writeln!(out, "{}", cue.text);
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn transcript(offset_ms: i64, text: &str, speaker: Option<&str>, is_final: bool) -> Transcript {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        Transcript {
+            id: offset_ms,
+            meeting_id: "m1".to_string(),
+            text: text.to_string(),
+            speaker: speaker.map(|s| s.to_string()),
+            speaker_label: None,
+            timestamp: start + chrono::Duration::milliseconds(offset_ms),
+            is_final,
+            confidence: 0.9,
+            after_reconnect: false,
+        }
+    }
+
+    fn meeting_start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_interim_rows_are_skipped() {
+        let rows = vec![
+            transcript(4_000, "hello every", Some("Alice"), false),
+            transcript(5_000, "hello everyone how are you", Some("Alice"), true),
+        ];
+        let cues = build_cues(meeting_start(), &rows);
+        assert_eq!(cues.len(), 1);
+        assert_eq!(cues[0].text, "hello everyone how are you");
+        assert_eq!(cues[0].end_ms, 5_000);
+    }
+
+    #[test]
+    fn test_timestamps_clamped_monotonic() {
+        let rows = vec![
+            transcript(10_000, "first thing we should discuss", Some("Alice"), true),
+            transcript(
+                8_000,
+                "jittered row from another speaker",
+                Some("Bob"),
+                true,
+            ),
+        ];
+        let cues = build_cues(meeting_start(), &rows);
+        assert_eq!(cues.len(), 2);
+        assert!(cues[1].start_ms >= cues[0].end_ms);
+        assert!(cues[1].end_ms > cues[1].start_ms);
+    }
+
+    #[test]
+    fn test_short_fragments_merged_with_same_speaker() {
+        let rows = vec![
+            transcript(3_000, "so the plan is", Some("Alice"), true),
+            transcript(3_400, "yes", Some("Alice"), true),
+        ];
+        let cues = build_cues(meeting_start(), &rows);
+        assert_eq!(cues.len(), 1);
+        assert_eq!(cues[0].text, "so the plan is yes");
+    }
+
+    #[test]
+    fn test_render_srt_and_vtt() {
+        let cues = vec![SubtitleCue {
+            start_ms: 1_500,
+            end_ms: 3_723_004,
+            speaker: Some("Alice".to_string()),
+            text: "Hi".to_string(),
+        }];
+
+        assert_eq!(
+            render(&cues, SubtitleFormat::Srt),
+            "1\n00:00:01,500 --> 01:02:03,004\nAlice: Hi\n\n"
+        );
+        assert_eq!(
+            render(&cues, SubtitleFormat::Vtt),
+            "WEBVTT\n\n1\n00:00:01.500 --> 01:02:03.004\n<v Alice>Hi\n\n"
+        );
+    }
+}
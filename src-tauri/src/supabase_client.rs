@@ -73,47 +73,7 @@ impl SupabaseClient {
 
     /// Run schema migrations
     async fn run_migrations(&self, pool: &PgPool) -> Result<(), String> {
-        sqlx::query(r#"
-            CREATE TABLE IF NOT EXISTS activities (
-                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
-                start_time TIMESTAMPTZ NOT NULL,
-                end_time TIMESTAMPTZ,
-                duration_seconds INTEGER,
-                app_name TEXT,
-                window_title TEXT,
-                category TEXT NOT NULL DEFAULT 'other',
-                summary TEXT NOT NULL,
-                focus_area TEXT,
-                pinecone_id TEXT,
-                created_at TIMESTAMPTZ DEFAULT NOW()
-            )
-        "#)
-        .execute(pool)
-        .await
-        .map_err(|e| format!("Failed to create activities table: {}", e))?;
-
-        sqlx::query(r#"
-            CREATE TABLE IF NOT EXISTS daily_summaries (
-                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
-                date DATE NOT NULL UNIQUE,
-                total_hours DOUBLE PRECISION,
-                categories JSONB,
-                top_activities JSONB,
-                created_at TIMESTAMPTZ DEFAULT NOW()
-            )
-        "#)
-        .execute(pool)
-        .await
-        .map_err(|e| format!("Failed to create daily_summaries table: {}", e))?;
-
-        // Create indexes
-        let _ = sqlx::query("CREATE INDEX IF NOT EXISTS idx_activities_start_time ON activities(start_time)")
-            .execute(pool).await;
-        let _ = sqlx::query("CREATE INDEX IF NOT EXISTS idx_activities_category ON activities(category)")
-            .execute(pool).await;
-
-        log::info!("Supabase schema migrations completed");
-        Ok(())
+        supabase_ensure_schema(pool).await
     }
 
     /// Check if connected
@@ -339,7 +299,8 @@ pub async fn supabase_query_activities(
     }).collect())
 }
 
-/// Connect to Supabase and return pool (standalone - avoids guard across await)
+/// Connect to Supabase and return pool (standalone - avoids guard across await).
+/// Tables are not created here; see `supabase_ensure_schema`.
 pub async fn supabase_connect_pool(conn_str: &str) -> Result<PgPool, String> {
     let pool = PgPoolOptions::new()
         .max_connections(5)
@@ -347,56 +308,181 @@ pub async fn supabase_connect_pool(conn_str: &str) -> Result<PgPool, String> {
         .await
         .map_err(|e| format!("Failed to connect to Supabase: {}", e))?;
 
-    // Run migrations
-    supabase_run_migrations(&pool).await?;
-    
     log::info!("✅ Connected to Supabase Postgres");
     Ok(pool)
 }
 
-/// Run schema migrations (standalone)
-async fn supabase_run_migrations(pool: &PgPool) -> Result<(), String> {
-    sqlx::query(r#"
-        CREATE TABLE IF NOT EXISTS activities (
-            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
-            start_time TIMESTAMPTZ NOT NULL,
-            end_time TIMESTAMPTZ,
-            duration_seconds INTEGER,
-            app_name TEXT,
-            window_title TEXT,
-            category TEXT NOT NULL DEFAULT 'other',
-            summary TEXT NOT NULL,
-            focus_area TEXT,
-            pinecone_id TEXT,
-            created_at TIMESTAMPTZ DEFAULT NOW()
-        )
-    "#)
-    .execute(pool)
-    .await
-    .map_err(|e| format!("Failed to create activities table: {}", e))?;
-
-    sqlx::query(r#"
-        CREATE TABLE IF NOT EXISTS daily_summaries (
-            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
-            date DATE NOT NULL UNIQUE,
-            total_hours DOUBLE PRECISION,
-            categories JSONB,
-            top_activities JSONB,
-            created_at TIMESTAMPTZ DEFAULT NOW()
-        )
-    "#)
-    .execute(pool)
-    .await
-    .map_err(|e| format!("Failed to create daily_summaries table: {}", e))?;
+/// Tables the app writes to
+pub const SUPABASE_TABLES: &[&str] = &["activities", "daily_summaries", "conversations"];
+
+/// Schema statements as (what they create, SQL), in order
+const SCHEMA_STATEMENTS: &[(&str, &str)] = &[
+    ("activities table", r#"CREATE TABLE IF NOT EXISTS activities (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    start_time TIMESTAMPTZ NOT NULL,
+    end_time TIMESTAMPTZ,
+    duration_seconds INTEGER,
+    app_name TEXT,
+    window_title TEXT,
+    category TEXT NOT NULL DEFAULT 'other',
+    summary TEXT NOT NULL,
+    focus_area TEXT,
+    pinecone_id TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+)"#),
+    ("daily_summaries table", r#"CREATE TABLE IF NOT EXISTS daily_summaries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    date DATE NOT NULL UNIQUE,
+    total_hours DOUBLE PRECISION,
+    categories JSONB,
+    top_activities JSONB,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+)"#),
+    ("conversations table", r#"CREATE TABLE IF NOT EXISTS conversations (
+    id UUID PRIMARY KEY,
+    timestamp TIMESTAMPTZ NOT NULL,
+    user_query TEXT NOT NULL,
+    assistant_response TEXT NOT NULL,
+    model_used TEXT,
+    context_refs JSONB,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+)"#),
+    ("activities start_time index", "CREATE INDEX IF NOT EXISTS idx_activities_start_time ON activities(start_time)"),
+    ("activities category index", "CREATE INDEX IF NOT EXISTS idx_activities_category ON activities(category)"),
+    ("conversations timestamp index", "CREATE INDEX IF NOT EXISTS idx_conversations_timestamp ON conversations(timestamp)"),
+];
+
+/// The full schema as a script that can be pasted into the Supabase SQL editor
+pub fn supabase_schema_sql() -> String {
+    SCHEMA_STATEMENTS
+        .iter()
+        .map(|(_, sql)| format!("{};", sql))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
 
-    // Create indexes
-    let _ = sqlx::query("CREATE INDEX IF NOT EXISTS idx_activities_start_time ON activities(start_time)")
-        .execute(pool).await;
-    let _ = sqlx::query("CREATE INDEX IF NOT EXISTS idx_activities_category ON activities(category)")
-        .execute(pool).await;
+/// Create the tables and indexes the app needs if they are missing. On
+/// failure the error includes the SQL to run by hand.
+pub async fn supabase_ensure_schema(pool: &PgPool) -> Result<(), String> {
+    for (what, sql) in SCHEMA_STATEMENTS {
+        if let Err(e) = sqlx::query(sql).execute(pool).await {
+            let insufficient_privilege = e
+                .as_database_error()
+                .and_then(|db| db.code())
+                .map(|code| code == "42501")
+                .unwrap_or(false);
+            let reason = if insufficient_privilege {
+                format!("The Supabase user may not create the {}", what)
+            } else {
+                format!("Failed to create {}: {}", what, e)
+            };
+            return Err(format!(
+                "{}. Run this SQL in the Supabase SQL editor, then reconnect:\n\n{}",
+                reason,
+                supabase_schema_sql()
+            ));
+        }
+    }
 
     log::info!("Supabase schema migrations completed");
     Ok(())
 }
 
+/// Which of `SUPABASE_TABLES` don't exist yet
+pub async fn supabase_missing_tables(pool: &PgPool) -> Result<Vec<String>, String> {
+    let mut missing = Vec::new();
+    for table in SUPABASE_TABLES {
+        let exists: bool = sqlx::query_scalar("SELECT to_regclass($1) IS NOT NULL")
+            .bind(format!("public.{}", table))
+            .fetch_one(pool)
+            .await
+            .map_err(|e| format!("Failed to check table {}: {}", table, e))?;
+        if !exists {
+            missing.push(table.to_string());
+        }
+    }
+    Ok(missing)
+}
+
+/// Existence and size of one table
+#[derive(Debug, Clone, Serialize)]
+pub struct SupabaseTableHealth {
+    pub name: String,
+    pub exists: bool,
+    pub row_count: Option<i64>,
+}
+
+/// Result of a live round-trip against Supabase
+#[derive(Debug, Clone, Serialize)]
+pub struct SupabaseHealth {
+    pub connected: bool,
+    pub latency_ms: Option<u64>,
+    pub tables: Vec<SupabaseTableHealth>,
+    pub error: Option<String>,
+}
+
+/// Run a real query against Supabase and report latency, table existence
+/// and row counts
+pub async fn supabase_health(pool: &PgPool) -> SupabaseHealth {
+    let started = std::time::Instant::now();
+    if let Err(e) = sqlx::query("SELECT 1").execute(pool).await {
+        return SupabaseHealth {
+            connected: false,
+            latency_ms: None,
+            tables: Vec::new(),
+            error: Some(format!("Supabase query failed: {}", e)),
+        };
+    }
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let missing = match supabase_missing_tables(pool).await {
+        Ok(missing) => missing,
+        Err(e) => {
+            return SupabaseHealth {
+                connected: true,
+                latency_ms: Some(latency_ms),
+                tables: Vec::new(),
+                error: Some(e),
+            };
+        }
+    };
+
+    let mut tables = Vec::new();
+    for table in SUPABASE_TABLES {
+        let exists = !missing.iter().any(|m| m == table);
+        let row_count = if exists {
+            sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM {}", table))
+                .fetch_one(pool)
+                .await
+                .ok()
+        } else {
+            None
+        };
+        tables.push(SupabaseTableHealth {
+            name: table.to_string(),
+            exists,
+            row_count,
+        });
+    }
+
+    SupabaseHealth {
+        connected: true,
+        latency_ms: Some(latency_ms),
+        tables,
+        error: None,
+    }
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_sql_covers_every_table() {
+        let sql = supabase_schema_sql();
+        for table in SUPABASE_TABLES {
+            assert!(sql.contains(&format!("CREATE TABLE IF NOT EXISTS {} (", table)));
+        }
+        assert_eq!(sql.matches(';').count(), SCHEMA_STATEMENTS.len());
+    }
+}
@@ -0,0 +1,772 @@
+//! Cloud Sync Worker - Background upload to Pinecone and Supabase
+//!
+//! Drains a persistent job queue (`sync_jobs`): unsynced activities,
+//! transcript index runs and chat conversations. At most
+//! `SYNC_MAX_IN_FLIGHT` jobs run at once. A failed job is retried with
+//! exponential backoff and parked after `SYNC_MAX_ATTEMPTS` failures, so one
+//! poison item can't stall the queue. Attempt counts live in the database
+//! and survive restarts.
+//!
+//! When a job times out (or fails while the connectivity probe fails too) the
+//! worker treats the machine as offline: it stops launching jobs, doesn't
+//! count the failure against the item, and probes every
+//! `OFFLINE_PROBE_SECS` until the network is back.
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use sqlx::PgPool;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{Notify, Semaphore};
+use tokio::task::JoinSet;
+
+use crate::ai_client::AIClient;
+use crate::database::{ActivityLogEntry, DatabaseManager, SyncJob};
+use crate::embeddings::VectorStore;
+use crate::pinecone_client::{ActivityMetadata, PineconeClient, PineconeConfig, VectorKind};
+use crate::settings::SettingsManager;
+use crate::supabase_client::{Activity, SupabaseClient};
+
+/// Jobs running concurrently
+pub const SYNC_MAX_IN_FLIGHT: usize = 4;
+/// Failures before a job is parked
+pub const SYNC_MAX_ATTEMPTS: i64 = 5;
+/// Pause between queue drains while online
+const SYNC_INTERVAL_SECS: u64 = 60;
+/// Pause between connectivity probes while offline
+const OFFLINE_PROBE_SECS: u64 = 30;
+/// A job taking longer than this is treated as a network stall
+const JOB_TIMEOUT_SECS: u64 = 60;
+/// Connectivity probe timeout
+const PROBE_TIMEOUT_SECS: u64 = 5;
+/// Jobs picked up per drain
+const DRAIN_BATCH_SIZE: i32 = 50;
+/// First retry delay; doubles per attempt
+const BACKOFF_BASE_SECS: i64 = 30;
+/// Retry delay cap
+const BACKOFF_MAX_SECS: i64 = 3600;
+
+/// Kind of queued sync work
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncJobKind {
+    /// `item_key` is the activity_log id
+    Activity,
+    /// `item_key` is the conversation id, payload a `ConversationRecord`
+    Conversation,
+    /// `item_key` is the meeting id
+    TranscriptIndex,
+}
+
+impl SyncJobKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Activity => "activity",
+            Self::Conversation => "conversation",
+            Self::TranscriptIndex => "transcript_index",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "activity" => Some(Self::Activity),
+            "conversation" => Some(Self::Conversation),
+            "transcript_index" => Some(Self::TranscriptIndex),
+            _ => None,
+        }
+    }
+}
+
+/// Chat exchange queued for Pinecone + Supabase
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConversationRecord {
+    pub id: String,
+    pub timestamp: String,
+    pub user_query: String,
+    pub assistant_response: String,
+    pub model_used: String,
+    pub context_refs: Vec<String>,
+}
+
+/// Sync worker status
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SyncWorkerStatus {
+    pub running: bool,
+    pub online: bool,
+    /// Jobs waiting or backing off (parked excluded)
+    pub queue_depth: i64,
+    pub parked: i64,
+    pub synced_total: u64,
+    pub failed_total: u64,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<String>,
+    pub last_success_at: Option<String>,
+}
+
+/// Payload for `sync-progress` events, one per finished job
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SyncProgress {
+    pub processed: usize,
+    pub total: usize,
+    pub failed: usize,
+    pub online: bool,
+}
+
+#[derive(Default)]
+struct SyncStats {
+    synced_total: u64,
+    failed_total: u64,
+    last_error: Option<(String, DateTime<Utc>)>,
+    last_success: Option<DateTime<Utc>>,
+}
+
+/// Where jobs can be uploaded right now
+struct SyncTargets {
+    pinecone: Option<PineconeConfig>,
+    supabase: Option<PgPool>,
+    vectors: Option<VectorStore>,
+}
+
+impl SyncTargets {
+    fn has_cloud(&self) -> bool {
+        self.pinecone.is_some() || self.supabase.is_some()
+    }
+}
+
+struct Shared {
+    running: AtomicBool,
+    online: AtomicBool,
+    wake: Notify,
+    stats: RwLock<SyncStats>,
+    app_handle: RwLock<Option<AppHandle>>,
+    database: Arc<DatabaseManager>,
+    settings: Arc<SettingsManager>,
+    pinecone: Arc<RwLock<PineconeClient>>,
+    supabase: Arc<RwLock<SupabaseClient>>,
+    ai_client: Arc<RwLock<AIClient>>,
+}
+
+/// Sync Worker - drains the cloud sync queue in the background
+pub struct SyncWorker {
+    shared: Arc<Shared>,
+}
+
+impl SyncWorker {
+    pub fn new(
+        database: Arc<DatabaseManager>,
+        settings: Arc<SettingsManager>,
+        pinecone: Arc<RwLock<PineconeClient>>,
+        supabase: Arc<RwLock<SupabaseClient>>,
+        ai_client: Arc<RwLock<AIClient>>,
+    ) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                running: AtomicBool::new(false),
+                online: AtomicBool::new(true),
+                wake: Notify::new(),
+                stats: RwLock::new(SyncStats::default()),
+                app_handle: RwLock::new(None),
+                database,
+                settings,
+                pinecone,
+                supabase,
+                ai_client,
+            }),
+        }
+    }
+
+    /// App handle used to emit `sync-progress` events
+    pub fn set_app_handle(&self, app: AppHandle) {
+        *self.shared.app_handle.write() = Some(app);
+    }
+
+    /// Queue a job and wake the worker
+    pub async fn enqueue(
+        &self,
+        kind: SyncJobKind,
+        item_key: &str,
+        payload_json: Option<&str>,
+    ) -> Result<(), String> {
+        self.shared
+            .database
+            .enqueue_sync_job(kind.as_str(), item_key, payload_json)
+            .await
+            .map_err(|e| format!("Failed to queue sync job: {}", e))?;
+        self.wake();
+        Ok(())
+    }
+
+    /// Drain now instead of waiting for the next interval
+    pub fn wake(&self) {
+        self.shared.wake.notify_one();
+    }
+
+    pub async fn get_status(&self) -> SyncWorkerStatus {
+        let (queue_depth, parked) = self
+            .shared
+            .database
+            .count_sync_jobs()
+            .await
+            .unwrap_or((0, 0));
+        let stats = self.shared.stats.read();
+
+        SyncWorkerStatus {
+            running: self.shared.running.load(Ordering::SeqCst),
+            online: self.shared.online.load(Ordering::SeqCst),
+            queue_depth,
+            parked,
+            synced_total: stats.synced_total,
+            failed_total: stats.failed_total,
+            last_error: stats.last_error.as_ref().map(|(e, _)| e.clone()),
+            last_error_at: stats.last_error.as_ref().map(|(_, at)| at.to_rfc3339()),
+            last_success_at: stats.last_success.map(|t| t.to_rfc3339()),
+        }
+    }
+
+    /// Start the worker loop
+    pub fn start(&self) {
+        if self.shared.running.swap(true, Ordering::SeqCst) {
+            log::info!("Sync worker already running");
+            return;
+        }
+        log::info!("Sync worker starting...");
+
+        let shared = self.shared.clone();
+        tokio::spawn(async move {
+            run_loop(shared).await;
+        });
+    }
+
+    /// Stop the worker loop; in-flight jobs finish
+    pub fn stop(&self) {
+        self.shared.running.store(false, Ordering::SeqCst);
+        self.shared.wake.notify_one();
+        log::info!("Sync worker stopped");
+    }
+}
+
+async fn run_loop(shared: Arc<Shared>) {
+    log::info!("Sync worker loop started");
+
+    // First pass shortly after startup rather than a full interval later
+    let mut wait = Duration::from_secs(5);
+    while shared.running.load(Ordering::SeqCst) {
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = shared.wake.notified() => {}
+        }
+        if !shared.running.load(Ordering::SeqCst) {
+            break;
+        }
+
+        let targets = Arc::new(shared.targets().await);
+        if !shared.online.load(Ordering::SeqCst) {
+            if !probe(&targets).await {
+                wait = Duration::from_secs(OFFLINE_PROBE_SECS);
+                continue;
+            }
+            log::info!("Sync worker: back online, resuming");
+            shared.online.store(true, Ordering::SeqCst);
+        }
+
+        if targets.has_cloud() {
+            if let Err(e) = shared.database.enqueue_unsynced_activities().await {
+                log::warn!("Sync worker: failed to queue activities: {}", e);
+            }
+        }
+        drain(&shared, targets).await;
+
+        wait = Duration::from_secs(if shared.online.load(Ordering::SeqCst) {
+            SYNC_INTERVAL_SECS
+        } else {
+            OFFLINE_PROBE_SECS
+        });
+    }
+
+    log::info!("Sync worker loop stopped");
+}
+
+impl Shared {
+    /// Snapshot of configured targets (no guards held afterwards)
+    async fn targets(&self) -> SyncTargets {
+        let pinecone = self.pinecone.read().get_config();
+        let supabase = self.supabase.read().get_pool();
+        let ai_client = self.ai_client.read().clone();
+        let vectors = VectorStore::configured(
+            &self.settings,
+            pinecone.clone(),
+            self.database.clone(),
+            ai_client,
+        )
+        .await;
+        SyncTargets {
+            pinecone,
+            supabase,
+            vectors,
+        }
+    }
+
+    fn emit_progress(&self, progress: SyncProgress) {
+        if let Some(app) = self.app_handle.read().as_ref() {
+            let _ = app.emit("sync-progress", &progress);
+        }
+    }
+
+    fn go_offline(&self, reason: &str) {
+        if self.online.swap(false, Ordering::SeqCst) {
+            log::warn!("Sync worker: offline ({}), pausing", reason);
+        }
+    }
+}
+
+/// Run due jobs with bounded concurrency until the batch is done or the
+/// network drops
+async fn drain(shared: &Arc<Shared>, targets: Arc<SyncTargets>) {
+    let jobs = match shared.database.get_due_sync_jobs(DRAIN_BATCH_SIZE).await {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            log::warn!("Sync worker: failed to load jobs: {}", e);
+            return;
+        }
+    };
+    if jobs.is_empty() {
+        return;
+    }
+
+    let total = jobs.len();
+    let semaphore = Arc::new(Semaphore::new(SYNC_MAX_IN_FLIGHT));
+    let mut tasks = JoinSet::new();
+    let mut progress = SyncProgress {
+        processed: 0,
+        total,
+        failed: 0,
+        online: true,
+    };
+
+    for job in jobs {
+        let permit = match semaphore.clone().acquire_owned().await {
+            Ok(p) => p,
+            Err(_) => break,
+        };
+        // Checked between jobs - jobs already in flight still finish
+        if !shared.running.load(Ordering::SeqCst) || !shared.online.load(Ordering::SeqCst) {
+            break;
+        }
+
+        let database = shared.database.clone();
+        let targets = targets.clone();
+        tasks.spawn(async move {
+            let outcome = tokio::time::timeout(
+                Duration::from_secs(JOB_TIMEOUT_SECS),
+                run_job(&database, &targets, &job),
+            )
+            .await;
+            drop(permit);
+            (job, outcome)
+        });
+
+        while let Some(joined) = tasks.try_join_next() {
+            record_outcome(shared, &targets, joined, &mut progress).await;
+        }
+    }
+
+    while let Some(joined) = tasks.join_next().await {
+        record_outcome(shared, &targets, joined, &mut progress).await;
+    }
+
+    log::info!(
+        "☁️ Sync worker: {}/{} jobs done, {} failed",
+        progress.processed - progress.failed,
+        total,
+        progress.failed
+    );
+}
+
+type JobOutcome = (
+    SyncJob,
+    Result<Result<(), String>, tokio::time::error::Elapsed>,
+);
+
+async fn record_outcome(
+    shared: &Arc<Shared>,
+    targets: &SyncTargets,
+    joined: Result<JobOutcome, tokio::task::JoinError>,
+    progress: &mut SyncProgress,
+) {
+    let (job, outcome) = match joined {
+        Ok(joined) => joined,
+        Err(e) => {
+            log::error!("Sync job task panicked: {}", e);
+            return;
+        }
+    };
+    progress.processed += 1;
+
+    match outcome {
+        Ok(Ok(())) => {
+            if let Err(e) = shared.database.complete_sync_job(job.id).await {
+                log::warn!("Failed to remove finished sync job {}: {}", job.id, e);
+            }
+            let mut stats = shared.stats.write();
+            stats.synced_total += 1;
+            stats.last_success = Some(Utc::now());
+        }
+        Ok(Err(e)) if !probe(targets).await => {
+            // Not the item's fault - leave its attempts alone
+            progress.failed += 1;
+            shared.go_offline(&e);
+            shared.stats.write().last_error = Some((e, Utc::now()));
+        }
+        Err(_) => {
+            progress.failed += 1;
+            shared.go_offline("request timed out");
+            shared.stats.write().last_error =
+                Some((format!("{} job timed out", job.kind), Utc::now()));
+        }
+        Ok(Err(e)) => {
+            progress.failed += 1;
+            let attempts = job.attempts + 1;
+            let next_attempt =
+                (attempts < SYNC_MAX_ATTEMPTS).then(|| Utc::now() + backoff(attempts));
+            if next_attempt.is_none() {
+                log::warn!(
+                    "Sync job {} ({} {}) parked after {} attempts: {}",
+                    job.id,
+                    job.kind,
+                    job.item_key,
+                    attempts,
+                    e
+                );
+            }
+            if let Err(db_err) = shared
+                .database
+                .fail_sync_job(job.id, &e, next_attempt)
+                .await
+            {
+                log::warn!("Failed to record sync job failure: {}", db_err);
+            }
+            let mut stats = shared.stats.write();
+            stats.failed_total += 1;
+            stats.last_error = Some((e, Utc::now()));
+        }
+    }
+
+    progress.online = shared.online.load(Ordering::SeqCst);
+    shared.emit_progress(progress.clone());
+}
+
+/// Retry delay after `attempts` failures: 30s, 60s, 120s, ... capped at an hour
+fn backoff(attempts: i64) -> chrono::Duration {
+    let exponent = (attempts - 1).clamp(0, 16) as u32;
+    chrono::Duration::seconds((BACKOFF_BASE_SECS * 2i64.pow(exponent)).min(BACKOFF_MAX_SECS))
+}
+
+/// Whether the configured cloud services are reachable. Any HTTP response from
+/// the Pinecone host counts; Supabase must answer a trivial query.
+async fn probe(targets: &SyncTargets) -> bool {
+    let timeout = Duration::from_secs(PROBE_TIMEOUT_SECS);
+
+    if let Some(ref config) = targets.pinecone {
+        let client = match reqwest::Client::builder().timeout(timeout).build() {
+            Ok(c) => c,
+            Err(_) => return false,
+        };
+        return client.get(&config.index_host).send().await.is_ok();
+    }
+    if let Some(ref pool) = targets.supabase {
+        return matches!(
+            tokio::time::timeout(timeout, sqlx::query("SELECT 1").execute(pool)).await,
+            Ok(Ok(_))
+        );
+    }
+    // Nothing remote to reach (local vectors only)
+    true
+}
+
+async fn run_job(
+    database: &DatabaseManager,
+    targets: &SyncTargets,
+    job: &SyncJob,
+) -> Result<(), String> {
+    let kind = SyncJobKind::parse(&job.kind)
+        .ok_or_else(|| format!("Unknown sync job kind: {}", job.kind))?;
+
+    match kind {
+        SyncJobKind::Activity => {
+            if !targets.has_cloud() {
+                return Err("No cloud sync target configured".to_string());
+            }
+            let activity_id: i64 = job
+                .item_key
+                .parse()
+                .map_err(|_| format!("Invalid activity id: {}", job.item_key))?;
+            let activity = database
+                .get_unsynced_activity(activity_id)
+                .await
+                .map_err(|e| format!("Failed to load activity: {}", e))?;
+            // Already synced (e.g. by a manual sync) - nothing left to do
+            let Some(activity) = activity else {
+                return Ok(());
+            };
+
+            let outcome = sync_activity(
+                database,
+                targets.pinecone.as_ref(),
+                targets.supabase.as_ref(),
+                activity,
+            )
+            .await;
+            if outcome.is_done() {
+                Ok(())
+            } else {
+                Err(outcome.errors.join("; "))
+            }
+        }
+        SyncJobKind::Conversation => {
+            let record: ConversationRecord = job
+                .payload_json
+                .as_deref()
+                .and_then(|json| serde_json::from_str(json).ok())
+                .ok_or("Conversation job has no valid payload")?;
+            sync_conversation(
+                targets.pinecone.as_ref(),
+                targets.supabase.as_ref(),
+                &record,
+            )
+            .await
+        }
+        SyncJobKind::TranscriptIndex => {
+            let store = targets
+                .vectors
+                .as_ref()
+                .ok_or("No vector backend configured")?;
+            // Archived meetings stay out of the knowledge base
+            if database
+                .is_meeting_archived(&job.item_key)
+                .await
+                .unwrap_or(false)
+            {
+                return Ok(());
+            }
+            let result =
+                crate::commands::index_meeting_into(database, store, &job.item_key, false).await?;
+            let failures: Vec<String> = result
+                .errors
+                .into_iter()
+                .filter(|e| e != crate::commands::NO_TRANSCRIPTS_ERROR)
+                .collect();
+            if failures.is_empty() {
+                Ok(())
+            } else {
+                Err(failures.join("; "))
+            }
+        }
+    }
+}
+
+/// Result of uploading one activity
+#[derive(Debug, Default)]
+pub struct ActivitySyncOutcome {
+    pub pinecone_upserted: bool,
+    pub supabase_inserted: bool,
+    /// Withheld by the privacy filter and marked synced without uploading
+    pub dropped: bool,
+    pub pii_redactions: u64,
+    pub errors: Vec<String>,
+}
+
+impl ActivitySyncOutcome {
+    /// Marked synced - uploaded somewhere or deliberately dropped
+    pub fn is_done(&self) -> bool {
+        self.dropped || self.pinecone_upserted || self.supabase_inserted
+    }
+}
+
+/// Redact and upload one activity to whichever targets are configured, and
+/// mark it synced if at least one upload succeeded
+pub async fn sync_activity(
+    database: &DatabaseManager,
+    pinecone: Option<&PineconeConfig>,
+    supabase: Option<&PgPool>,
+    mut activity: ActivityLogEntry,
+) -> ActivitySyncOutcome {
+    let mut outcome = ActivitySyncOutcome::default();
+    let Some(activity_id) = activity.id else {
+        return outcome;
+    };
+
+    // PII never leaves the machine (local copy stays verbatim)
+    let mode = crate::privacy_filter::redaction_mode();
+    let summary = crate::privacy_filter::redact_with(mode, &activity.summary);
+    let window_title = activity
+        .window_title
+        .as_deref()
+        .map(|t| crate::privacy_filter::redact_with(mode, t));
+    let focus_area = activity
+        .focus_area
+        .as_deref()
+        .map(|t| crate::privacy_filter::redact_with(mode, t));
+    let redacted = [Some(&summary), window_title.as_ref(), focus_area.as_ref()];
+    outcome.pii_redactions = redacted.iter().flatten().map(|r| r.redactions).sum::<u64>();
+    if redacted.iter().flatten().any(|r| r.text.is_none()) {
+        // Dropped, not failed: mark synced so it isn't retried forever
+        let _ = database.mark_activity_synced(activity_id, None, None).await;
+        outcome.dropped = true;
+        return outcome;
+    }
+    activity.summary = summary.text.unwrap_or_default();
+    activity.window_title = window_title.and_then(|r| r.text);
+    activity.focus_area = focus_area.and_then(|r| r.text);
+
+    let mut pinecone_id: Option<String> = None;
+    let mut supabase_id: Option<String> = None;
+
+    if let Some(config) = pinecone {
+        let id = format!("activity_{}", activity_id);
+        let text = format!(
+            "{} - {} - {}",
+            activity.category,
+            activity.summary,
+            activity.focus_area.as_deref().unwrap_or("")
+        );
+
+        let metadata = ActivityMetadata {
+            timestamp: activity.start_time.to_rfc3339(),
+            category: activity.category.clone(),
+            app_name: activity.app_name.clone(),
+            focus_area: activity.focus_area.clone(),
+            summary: activity.summary.clone(),
+        };
+
+        match crate::pinecone_client::pinecone_upsert(
+            config,
+            VectorKind::Activity,
+            &id,
+            &text,
+            &metadata,
+        )
+        .await
+        {
+            Ok(_) => {
+                pinecone_id = Some(id);
+                outcome.pinecone_upserted = true;
+            }
+            Err(e) => outcome.errors.push(format!("Pinecone sync failed: {}", e)),
+        }
+    }
+
+    if let Some(pool) = supabase {
+        let supabase_activity = Activity {
+            id: None,
+            start_time: activity.start_time,
+            end_time: activity.end_time,
+            duration_seconds: activity.duration_seconds,
+            app_name: activity.app_name.clone(),
+            window_title: activity.window_title.clone(),
+            category: activity.category.clone(),
+            summary: activity.summary.clone(),
+            focus_area: activity.focus_area.clone(),
+            pinecone_id: pinecone_id.clone(),
+            created_at: None,
+        };
+
+        match crate::supabase_client::supabase_insert_activity(pool, &supabase_activity).await {
+            Ok(id) => {
+                supabase_id = Some(id);
+                outcome.supabase_inserted = true;
+            }
+            Err(e) => outcome.errors.push(format!("Supabase sync failed: {}", e)),
+        }
+    }
+
+    if pinecone_id.is_some() || supabase_id.is_some() {
+        let _ = database
+            .mark_activity_synced(activity_id, pinecone_id.as_deref(), supabase_id.as_deref())
+            .await;
+    }
+    outcome
+}
+
+/// Store a conversation to Pinecone (for semantic recall) and Supabase. Both
+/// writes are idempotent, so a partly failed job can simply be retried.
+pub async fn sync_conversation(
+    pinecone: Option<&PineconeConfig>,
+    supabase: Option<&PgPool>,
+    record: &ConversationRecord,
+) -> Result<(), String> {
+    if pinecone.is_none() && supabase.is_none() {
+        return Err("No cloud sync target configured".to_string());
+    }
+
+    if let Some(config) = pinecone {
+        let searchable_text = format!("Q: {}\nA: {}", record.user_query, record.assistant_response);
+        let metadata = ActivityMetadata {
+            timestamp: record.timestamp.clone(),
+            category: "conversation".to_string(),
+            app_name: Some("thebrain-chat".to_string()),
+            focus_area: None,
+            summary: format!(
+                "User asked about: {}...",
+                &record.user_query.chars().take(100).collect::<String>()
+            ),
+        };
+
+        crate::pinecone_client::pinecone_upsert(
+            config,
+            VectorKind::Conversation,
+            &record.id,
+            &searchable_text,
+            &metadata,
+        )
+        .await?;
+        log::info!("📌 Conversation stored to Pinecone: {}", record.id);
+    }
+
+    if let Some(pool) = supabase {
+        let query = r#"
+            INSERT INTO conversations (id, timestamp, user_query, assistant_response, model_used, context_refs)
+            VALUES ($1::uuid, $2::timestamptz, $3, $4, $5, $6)
+            ON CONFLICT (id) DO NOTHING
+        "#;
+
+        sqlx::query(query)
+            .bind(&record.id)
+            .bind(&record.timestamp)
+            .bind(&record.user_query)
+            .bind(&record.assistant_response)
+            .bind(&record.model_used)
+            .bind(serde_json::to_value(&record.context_refs).unwrap_or_default())
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to store conversation to Supabase: {}", e))?;
+        log::info!("📦 Conversation stored to Supabase: {}", record.id);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        assert_eq!(backoff(1).num_seconds(), 30);
+        assert_eq!(backoff(2).num_seconds(), 60);
+        assert_eq!(backoff(4).num_seconds(), 240);
+        assert_eq!(backoff(20).num_seconds(), 3600);
+    }
+
+    #[test]
+    fn test_job_kind_round_trip() {
+        for kind in [
+            SyncJobKind::Activity,
+            SyncJobKind::Conversation,
+            SyncJobKind::TranscriptIndex,
+        ] {
+            assert_eq!(SyncJobKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(SyncJobKind::parse("frames"), None);
+    }
+}
@@ -0,0 +1,330 @@
+// noFriction Meetings - Theme Detection
+// Notices when what's on screen stops matching the active theme (half an
+// hour in an IDE while the theme says "prospecting") and suggests switching,
+// so theme-time stats stay honest without remembering to flip the theme.
+//
+// Screen states carry the semantic classifier's category and the app/window
+// they came from; each maps to a theme where it's unambiguous (video calls and
+// plain documents could be any theme, so they only dilute the vote).
+// Suggestions are rate limited: one at a time, none within an hour of the
+// last, and a dismissed switch isn't proposed again for three hours.
+
+use crate::database::ScreenStateRecord;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Mismatch has to hold over this window before anything is suggested
+pub const SUSTAIN_MINS: i64 = 30;
+
+/// How often the detector looks at recent activity
+pub const CHECK_INTERVAL_SECS: u64 = 300;
+
+/// Part of the window that must have observed screen activity
+const MIN_COVERAGE: f32 = 0.5;
+
+/// Share of observed time the proposed theme needs
+const MIN_SHARE: f32 = 0.6;
+
+/// No new suggestion within this long of the last one
+pub const SUGGESTION_COOLDOWN_MINS: i64 = 60;
+
+/// A dismissed switch (from one theme to another) stays quiet this long
+pub const DISMISS_COOLDOWN_MINS: i64 = 180;
+
+/// App or window title fragments that point at one theme, checked before
+/// the activity category (lowercase)
+const THEME_HINTS: &[(&str, &str)] = &[
+    ("salesforce", "prospecting"),
+    ("hubspot", "prospecting"),
+    ("sales navigator", "prospecting"),
+    ("apollo", "prospecting"),
+    ("outreach", "prospecting"),
+    ("pipedrive", "prospecting"),
+    ("docsend", "fundraising"),
+    ("carta", "fundraising"),
+    ("angellist", "fundraising"),
+    ("pitchbook", "fundraising"),
+    ("crunchbase", "fundraising"),
+    ("investor", "fundraising"),
+    ("data room", "fundraising"),
+    ("linear", "product_dev"),
+    ("jira", "product_dev"),
+    ("figma", "product_dev"),
+    ("quickbooks", "admin"),
+    ("expensify", "admin"),
+    ("gusto", "admin"),
+    ("invoice", "admin"),
+    ("calendar", "admin"),
+    ("gmail", "admin"),
+    ("netflix", "personal"),
+    ("spotify", "personal"),
+    ("youtube", "personal"),
+    ("reddit", "personal"),
+    ("instagram", "personal"),
+    ("whatsapp", "personal"),
+];
+
+/// Apps (exact name) that mean admin work
+const ADMIN_APPS: &[&str] = &["Mail", "Outlook", "Calendar", "Numbers", "Excel", "Finder"];
+
+/// The theme a screen state points at, if it's unambiguous
+pub fn theme_for(
+    category: &str,
+    app_name: Option<&str>,
+    window_title: Option<&str>,
+) -> Option<&'static str> {
+    let haystack =
+        format!("{} {}", app_name.unwrap_or(""), window_title.unwrap_or("")).to_lowercase();
+    if let Some((_, theme)) = THEME_HINTS.iter().find(|(hint, _)| haystack.contains(hint)) {
+        return Some(theme);
+    }
+    if app_name.is_some_and(|app| ADMIN_APPS.contains(&app)) {
+        return Some("admin");
+    }
+    match category {
+        "coding" | "terminal" => Some("product_dev"),
+        "video" => Some("personal"),
+        _ => None,
+    }
+}
+
+/// A proposed theme switch waiting for the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeSuggestion {
+    pub id: String,
+    pub active_theme: String,
+    pub proposed_theme: String,
+    /// 0.0 - 1.0: share of the window spent on the proposed theme, weighted
+    /// by how sure the classifier was
+    pub confidence: f32,
+    /// Minutes of the window spent on the proposed theme
+    pub observed_mins: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// (proposed theme, confidence, seconds on it) when recent activity points
+/// away from the active theme
+pub fn evaluate(
+    states: &[ScreenStateRecord],
+    active_theme: &str,
+    now: DateTime<Utc>,
+) -> Option<(&'static str, f32, i64)> {
+    let window_start = now - Duration::minutes(SUSTAIN_MINS);
+    let parse = |ts: &str| {
+        DateTime::parse_from_rfc3339(ts)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    };
+
+    let mut observed = 0i64;
+    let mut by_theme: HashMap<&'static str, (i64, f32)> = HashMap::new();
+    for state in states {
+        let Some(start) = parse(&state.start_ts) else {
+            continue;
+        };
+        // The open state runs until now
+        let end = state.end_ts.as_deref().and_then(parse).unwrap_or(now);
+        let secs = (end.min(now) - start.max(window_start)).num_seconds();
+        if secs <= 0 {
+            continue;
+        }
+        observed += secs;
+        if let Some(theme) = theme_for(
+            &state.state_type,
+            state.app_name.as_deref(),
+            state.window_title.as_deref(),
+        ) {
+            let entry = by_theme.entry(theme).or_default();
+            entry.0 += secs;
+            entry.1 += secs as f32 * state.category_confidence.unwrap_or(0.5);
+        }
+    }
+
+    if (observed as f32) < (SUSTAIN_MINS * 60) as f32 * MIN_COVERAGE {
+        return None;
+    }
+    let (theme, (secs, weighted)) = by_theme.into_iter().max_by_key(|(_, (secs, _))| *secs)?;
+    let share = secs as f32 / observed as f32;
+    if theme == active_theme || share < MIN_SHARE {
+        return None;
+    }
+    let confidence = (share * (weighted / secs as f32)).clamp(0.0, 1.0);
+    Some((theme, confidence, secs))
+}
+
+#[derive(Default)]
+struct DetectorState {
+    pending: Option<ThemeSuggestion>,
+    last_suggested_at: Option<DateTime<Utc>>,
+    /// (active, proposed) -> when it was dismissed
+    dismissed: HashMap<(String, String), DateTime<Utc>>,
+}
+
+/// Pending suggestion and cooldowns, shared by the check task and commands
+#[derive(Default)]
+pub struct ThemeDetector {
+    state: Mutex<DetectorState>,
+}
+
+impl ThemeDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Look at recent screen states; returns a new suggestion to announce
+    pub fn check(
+        &self,
+        states: &[ScreenStateRecord],
+        active_theme: &str,
+        now: DateTime<Utc>,
+    ) -> Option<ThemeSuggestion> {
+        let mut state = self.state.lock();
+        // A manual switch makes the pending suggestion moot
+        if state
+            .pending
+            .as_ref()
+            .is_some_and(|p| p.active_theme != active_theme)
+        {
+            state.pending = None;
+        }
+        if state.pending.is_some() {
+            return None;
+        }
+        if state
+            .last_suggested_at
+            .is_some_and(|at| now - at < Duration::minutes(SUGGESTION_COOLDOWN_MINS))
+        {
+            return None;
+        }
+
+        let (proposed, confidence, secs) = evaluate(states, active_theme, now)?;
+        let key = (active_theme.to_string(), proposed.to_string());
+        if state
+            .dismissed
+            .get(&key)
+            .is_some_and(|at| now - *at < Duration::minutes(DISMISS_COOLDOWN_MINS))
+        {
+            return None;
+        }
+
+        let suggestion = ThemeSuggestion {
+            id: uuid::Uuid::new_v4().to_string(),
+            active_theme: active_theme.to_string(),
+            proposed_theme: proposed.to_string(),
+            confidence,
+            observed_mins: secs / 60,
+            created_at: now,
+        };
+        state.pending = Some(suggestion.clone());
+        state.last_suggested_at = Some(now);
+        Some(suggestion)
+    }
+
+    pub fn pending(&self) -> Option<ThemeSuggestion> {
+        self.state.lock().pending.clone()
+    }
+
+    /// Take the pending suggestion to act on it
+    pub fn accept(&self, id: &str) -> Result<ThemeSuggestion, String> {
+        let mut state = self.state.lock();
+        match state.pending.take() {
+            Some(suggestion) if suggestion.id == id => Ok(suggestion),
+            other => {
+                state.pending = other;
+                Err("Theme suggestion not found or already handled".to_string())
+            }
+        }
+    }
+
+    /// Drop the pending suggestion and hold off on the same switch
+    pub fn dismiss(&self, id: &str, now: DateTime<Utc>) -> Result<(), String> {
+        let mut state = self.state.lock();
+        match state.pending.take() {
+            Some(suggestion) if suggestion.id == id => {
+                state
+                    .dismissed
+                    .insert((suggestion.active_theme, suggestion.proposed_theme), now);
+                Ok(())
+            }
+            other => {
+                state.pending = other;
+                Err("Theme suggestion not found or already handled".to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(app: &str, category: &str, start: DateTime<Utc>, mins: i64) -> ScreenStateRecord {
+        ScreenStateRecord {
+            state_id: uuid::Uuid::new_v4().to_string(),
+            meeting_id: "m1".to_string(),
+            start_ts: start.to_rfc3339(),
+            end_ts: Some((start + Duration::minutes(mins)).to_rfc3339()),
+            app_name: Some(app.to_string()),
+            window_title: None,
+            phash: String::new(),
+            delta_score: 0.0,
+            keyframe_path: None,
+            state_type: category.to_string(),
+            category_confidence: Some(0.9),
+            flags: "{}".to_string(),
+            monitor_id: None,
+        }
+    }
+
+    #[test]
+    fn test_theme_for() {
+        assert_eq!(
+            theme_for("coding", Some("Cursor"), None),
+            Some("product_dev")
+        );
+        assert_eq!(
+            theme_for("browser", Some("Chrome"), Some("Acme | Salesforce")),
+            Some("prospecting")
+        );
+        assert_eq!(theme_for("video_call", Some("zoom.us"), None), None);
+        assert_eq!(
+            theme_for("document", Some("Pages"), Some("Notes.pages")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_suggests_after_sustained_mismatch_with_cooldowns() {
+        let now = Utc::now();
+        let detector = ThemeDetector::new();
+        let coding_until = |end: DateTime<Utc>| {
+            vec![
+                state("Cursor", "coding", end - Duration::minutes(30), 20),
+                state("Terminal", "terminal", end - Duration::minutes(10), 10),
+            ]
+        };
+        let coding = coding_until(now);
+
+        // Ten minutes isn't sustained
+        assert!(detector.check(&coding[1..], "prospecting", now).is_none());
+        // Already on the right theme
+        assert!(detector.check(&coding, "product_dev", now).is_none());
+
+        let suggestion = detector.check(&coding, "prospecting", now).unwrap();
+        assert_eq!(suggestion.proposed_theme, "product_dev");
+        assert_eq!(suggestion.observed_mins, 30);
+        assert!((suggestion.confidence - 0.9).abs() < 1e-3);
+
+        // One at a time, then the cooldown
+        assert!(detector.check(&coding, "prospecting", now).is_none());
+        detector.dismiss(&suggestion.id, now).unwrap();
+        let later = now + Duration::minutes(SUGGESTION_COOLDOWN_MINS + 1);
+        // Past the suggestion cooldown, but this switch was dismissed
+        assert!(detector
+            .check(&coding_until(later), "prospecting", later)
+            .is_none());
+        assert!(detector.accept(&suggestion.id).is_err());
+    }
+}
@@ -0,0 +1,350 @@
+// noFriction Meetings - Theme Time Reports
+// Hours per theme per day or week from theme_sessions, for the stats screen
+// and CSV export.
+//
+// Sessions are split at local midnight so one crossing into the next day
+// counts on both. The current session counts up to now. A session left open
+// by a crash (one with a later session after it) is closed at the last
+// screen activity or activity log entry seen before the next session began,
+// so the time the app wasn't running doesn't inflate the report.
+
+use crate::commands::ACTIVITY_THEMES;
+use crate::database::{DatabaseManager, ThemeSessionRecord};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// Longest range a report covers
+const MAX_REPORT_DAYS: i64 = 366;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    Day,
+    /// Weeks run Monday-Sunday
+    Week,
+}
+
+impl Granularity {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "day" => Some(Granularity::Day),
+            "week" => Some(Granularity::Week),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Granularity::Day => "day",
+            Granularity::Week => "week",
+        }
+    }
+
+    /// First day of the period containing `date`
+    fn period_start(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Granularity::Day => date,
+            Granularity::Week => {
+                date - Duration::days(date.weekday().num_days_from_monday() as i64)
+            }
+        }
+    }
+}
+
+/// One theme's hours per period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeTimeRow {
+    pub theme: String,
+    /// Hours per period, in `ThemeTimeReport::periods` order
+    pub hours: Vec<f64>,
+    pub total_hours: f64,
+}
+
+/// Theme x period matrix of hours
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeTimeReport {
+    pub date_from: NaiveDate,
+    pub date_to: NaiveDate,
+    pub granularity: String,
+    /// First day of each period ("2026-03-02")
+    pub periods: Vec<String>,
+    pub themes: Vec<ThemeTimeRow>,
+    /// Hours per period across themes
+    pub period_totals: Vec<f64>,
+    pub total_hours: f64,
+}
+
+fn round_hours(seconds: f64) -> f64 {
+    (seconds / 36.0).round() / 100.0
+}
+
+/// Midnight at the start of `date` in `tz`
+fn day_start<Tz: TimeZone>(tz: &Tz, date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    tz.from_local_datetime(&midnight)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+/// Spread (theme, start, end) sessions over the days of an inclusive date
+/// range in `tz`, summed per period
+pub fn build_report<Tz: TimeZone>(
+    sessions: &[(String, DateTime<Utc>, DateTime<Utc>)],
+    date_from: NaiveDate,
+    date_to: NaiveDate,
+    granularity: Granularity,
+    tz: &Tz,
+) -> ThemeTimeReport {
+    let mut periods: Vec<NaiveDate> = Vec::new();
+    let mut themes: Vec<String> = ACTIVITY_THEMES.iter().map(|t| t.to_string()).collect();
+    let mut seconds: Vec<Vec<f64>> = vec![Vec::new(); themes.len()];
+
+    let mut date = date_from;
+    while date <= date_to {
+        let period = granularity.period_start(date);
+        if periods.last() != Some(&period) {
+            periods.push(period);
+            for row in seconds.iter_mut() {
+                row.push(0.0);
+            }
+        }
+        let column = periods.len() - 1;
+        let (from, to) = (day_start(tz, date), day_start(tz, date + Duration::days(1)));
+
+        for (theme, start, end) in sessions {
+            let overlap = ((*end).min(to) - (*start).max(from)).num_seconds();
+            if overlap <= 0 {
+                continue;
+            }
+            let row = match themes.iter().position(|t| t == theme) {
+                Some(row) => row,
+                // Themes from older versions still count
+                None => {
+                    themes.push(theme.clone());
+                    seconds.push(vec![0.0; periods.len()]);
+                    themes.len() - 1
+                }
+            };
+            seconds[row][column] += overlap as f64;
+        }
+        date += Duration::days(1);
+    }
+
+    let period_totals: Vec<f64> = (0..periods.len())
+        .map(|c| round_hours(seconds.iter().map(|row| row[c]).sum()))
+        .collect();
+    let total_hours = round_hours(seconds.iter().flatten().sum());
+    let themes = themes
+        .into_iter()
+        .zip(seconds)
+        .map(|(theme, row)| ThemeTimeRow {
+            theme,
+            total_hours: round_hours(row.iter().sum()),
+            hours: row.into_iter().map(round_hours).collect(),
+        })
+        .collect();
+
+    ThemeTimeReport {
+        date_from,
+        date_to,
+        granularity: granularity.as_str().to_string(),
+        periods: periods.iter().map(|d| d.to_string()).collect(),
+        themes,
+        period_totals,
+        total_hours,
+    }
+}
+
+/// Close sessions a crash left open, at the last activity seen before the
+/// next session started; returns how many were closed
+pub async fn close_orphaned_sessions(db: &DatabaseManager) -> Result<usize, String> {
+    let orphans = db
+        .get_orphaned_theme_sessions()
+        .await
+        .map_err(|e| format!("Failed to get open theme sessions: {}", e))?;
+    for (id, started_at, next_started_at) in &orphans {
+        let last_seen = db
+            .get_last_activity_between(*started_at, *next_started_at)
+            .await
+            .map_err(|e| format!("Failed to get last activity: {}", e))?;
+        let ended_at = last_seen.unwrap_or(*started_at);
+        db.close_theme_session_at(*id, ended_at)
+            .await
+            .map_err(|e| format!("Failed to close theme session: {}", e))?;
+        log::info!(
+            "Closed orphaned theme session {} at {}",
+            id,
+            ended_at.to_rfc3339()
+        );
+    }
+    Ok(orphans.len())
+}
+
+/// Hours per theme per day/week between two dates (inclusive, local time)
+pub async fn report(
+    db: &DatabaseManager,
+    date_from: NaiveDate,
+    date_to: NaiveDate,
+    granularity: Granularity,
+) -> Result<ThemeTimeReport, String> {
+    if date_to < date_from {
+        return Err("dateTo is before dateFrom".to_string());
+    }
+    if (date_to - date_from).num_days() >= MAX_REPORT_DAYS {
+        return Err(format!("Reports cover at most {} days", MAX_REPORT_DAYS));
+    }
+    close_orphaned_sessions(db).await?;
+
+    let from = day_start(&Local, date_from);
+    let to = day_start(&Local, date_to + Duration::days(1));
+    let now = Utc::now();
+    let sessions: Vec<(String, DateTime<Utc>, DateTime<Utc>)> = db
+        .get_theme_sessions_between(from, to)
+        .await
+        .map_err(|e| format!("Failed to get theme sessions: {}", e))?
+        .into_iter()
+        .map(|s: ThemeSessionRecord| (s.theme, s.started_at, s.ended_at.unwrap_or(now)))
+        .collect();
+
+    Ok(build_report(
+        &sessions,
+        date_from,
+        date_to,
+        granularity,
+        &Local,
+    ))
+}
+
+/// Write a report as CSV: one row per theme, one column per period, then
+/// totals
+pub fn write_csv(report: &ThemeTimeReport, path: &std::path::Path) -> Result<(), String> {
+    let file =
+        std::fs::File::create(path).map_err(|e| format!("Failed to create export file: {}", e))?;
+    let mut out = std::io::BufWriter::new(file);
+    let write_err = |e: std::io::Error| format!("Failed to write export: {}", e);
+
+    writeln!(out, "theme,{},total", report.periods.join(",")).map_err(write_err)?;
+    let row = |hours: &[f64], total: f64| {
+        hours
+            .iter()
+            .chain(std::iter::once(&total))
+            .map(|h| format!("{:.2}", h))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    for theme in &report.themes {
+        writeln!(
+            out,
+            "{},{}",
+            crate::audit_log::csv_field(&theme.theme),
+            row(&theme.hours, theme.total_hours)
+        )
+        .map_err(write_err)?;
+    }
+    writeln!(
+        out,
+        "total,{}",
+        row(&report.period_totals, report.total_hours)
+    )
+    .map_err(write_err)?;
+    out.flush().map_err(write_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, hour: u32, min: u32) -> DateTime<Utc> {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(hour, min, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    fn test_splits_sessions_at_midnight() {
+        let day = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let sessions = vec![
+            // 22:00 Tuesday to 01:30 Wednesday
+            (
+                "product_dev".to_string(),
+                at("2026-03-03", 22, 0),
+                at("2026-03-04", 1, 30),
+            ),
+            (
+                "admin".to_string(),
+                at("2026-03-04", 9, 0),
+                at("2026-03-04", 9, 45),
+            ),
+            // Starts before the range
+            (
+                "legacy".to_string(),
+                at("2026-03-01", 23, 0),
+                at("2026-03-02", 2, 0),
+            ),
+        ];
+
+        let daily = build_report(
+            &sessions,
+            day("2026-03-02"),
+            day("2026-03-04"),
+            Granularity::Day,
+            &Utc,
+        );
+        assert_eq!(
+            daily.periods,
+            vec!["2026-03-02", "2026-03-03", "2026-03-04"]
+        );
+        let hours = |report: &ThemeTimeReport, theme: &str| {
+            report
+                .themes
+                .iter()
+                .find(|t| t.theme == theme)
+                .unwrap()
+                .hours
+                .clone()
+        };
+        assert_eq!(hours(&daily, "product_dev"), vec![0.0, 2.0, 1.5]);
+        assert_eq!(hours(&daily, "admin"), vec![0.0, 0.0, 0.75]);
+        assert_eq!(hours(&daily, "legacy"), vec![2.0, 0.0, 0.0]);
+        assert_eq!(daily.period_totals, vec![2.0, 2.0, 2.25]);
+        assert_eq!(daily.total_hours, 6.25);
+
+        // Monday 2026-03-02 starts the week
+        let weekly = build_report(
+            &sessions,
+            day("2026-03-02"),
+            day("2026-03-04"),
+            Granularity::Week,
+            &Utc,
+        );
+        assert_eq!(weekly.periods, vec!["2026-03-02"]);
+        assert_eq!(hours(&weekly, "product_dev"), vec![3.5]);
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let report = ThemeTimeReport {
+            date_from: NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            date_to: NaiveDate::from_ymd_opt(2026, 3, 3).unwrap(),
+            granularity: "day".to_string(),
+            periods: vec!["2026-03-02".to_string(), "2026-03-03".to_string()],
+            themes: vec![ThemeTimeRow {
+                theme: "admin".to_string(),
+                hours: vec![1.0, 0.5],
+                total_hours: 1.5,
+            }],
+            period_totals: vec![1.0, 0.5],
+            total_hours: 1.5,
+        };
+        let path = std::env::temp_dir().join(format!("theme-report-{}.csv", uuid::Uuid::new_v4()));
+        write_csv(&report, &path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "theme,2026-03-02,2026-03-03,total\nadmin,1.00,0.50,1.50\ntotal,1.00,0.50,1.50\n"
+        );
+        let _ = std::fs::remove_file(path);
+    }
+}
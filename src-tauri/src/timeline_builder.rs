@@ -6,15 +6,18 @@
 // 2. Timeline event generation
 // 3. Evidence reference linking
 // 4. Summary generation for timeline segments
+// 5. Transcript-derived events (monologues, question bursts, live decisions
+//    and action items), so audio-only meetings get a timeline too
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use uuid::Uuid;
 
 use crate::diff_builder::ChangeType;
 use crate::episode_builder::DocumentEpisode;
+use crate::live_intel_agent::LiveInsightEvent;
 
 /// Configuration for timeline building
 #[derive(Debug, Clone)]
@@ -33,6 +36,15 @@ pub struct TimelineConfig {
 
     /// Whether to include content change events
     pub include_content_changes: bool,
+
+    /// One speaker talking at least this long becomes a monologue event (ms)
+    pub monologue_min_ms: i64,
+
+    /// This many questions within `question_window_ms` make a burst
+    pub question_burst_min: usize,
+
+    /// Window for counting questions (ms)
+    pub question_window_ms: i64,
 }
 
 impl Default for TimelineConfig {
@@ -43,6 +55,9 @@ impl Default for TimelineConfig {
             include_file_events: true,
             include_app_switches: true,
             include_content_changes: true,
+            monologue_min_ms: 120_000, // 2 minutes
+            question_burst_min: 3,
+            question_window_ms: 60_000, // 1 minute
         }
     }
 }
@@ -69,6 +84,24 @@ pub enum TimelineEventType {
     TopicChange,
     /// Activity gap detected
     ActivityGap,
+    /// Recording paused by the user
+    Paused,
+    /// Recording resumed after a pause
+    Resumed,
+    /// Ambient capture paused while the user was away from the keyboard
+    IdleGap,
+    /// Microphone input dropped below the silence threshold mid-recording
+    MicSilent,
+    /// Note jotted by the user from the tray or the quick-note shortcut
+    QuickNote,
+    /// One speaker held the floor for a long stretch
+    Monologue,
+    /// Several questions asked in quick succession
+    QuestionBurst,
+    /// Decision picked up by the live intel agent
+    Decision,
+    /// Action item picked up by the live intel agent
+    ActionItem,
 }
 
 impl TimelineEventType {
@@ -83,6 +116,15 @@ impl TimelineEventType {
             Self::MeetingEnd => "meeting_end",
             Self::TopicChange => "topic_change",
             Self::ActivityGap => "activity_gap",
+            Self::Paused => "paused",
+            Self::Resumed => "resumed",
+            Self::IdleGap => "idle_gap",
+            Self::MicSilent => "mic_silent",
+            Self::QuickNote => "quick_note",
+            Self::Monologue => "monologue",
+            Self::QuestionBurst => "question_burst",
+            Self::Decision => "decision",
+            Self::ActionItem => "action_item",
         }
     }
 
@@ -98,6 +140,15 @@ impl TimelineEventType {
             Self::MeetingEnd => "Meeting Ended",
             Self::TopicChange => "Topic Changed",
             Self::ActivityGap => "Break",
+            Self::Paused => "Paused",
+            Self::Resumed => "Resumed",
+            Self::IdleGap => "Idle",
+            Self::MicSilent => "Mic Silent",
+            Self::QuickNote => "Note",
+            Self::Monologue => "Monologue",
+            Self::QuestionBurst => "Questions",
+            Self::Decision => "Decision",
+            Self::ActionItem => "Action Item",
         }
     }
 }
@@ -120,8 +171,12 @@ pub struct TimelineEvent {
     pub state_id: Option<String>,
     /// Topic/category for grouping
     pub topic: Option<String>,
+    /// Activity category inherited from the source episode's screen states
+    pub category: Option<String>,
     /// Importance score (0.0-1.0)
     pub importance: f32,
+    /// Why the event scored what it did, set when the meeting is finalized
+    pub importance_reason: Option<String>,
 }
 
 impl TimelineEvent {
@@ -144,7 +199,9 @@ impl TimelineEvent {
             episode_id: None,
             state_id: None,
             topic: None,
+            category: None,
             importance: 0.5,
+            importance_reason: None,
         }
     }
 
@@ -183,12 +240,100 @@ impl TimelineEvent {
         self
     }
 
+    pub fn with_category(mut self, category: &str) -> Self {
+        self.category = Some(category.to_string());
+        self
+    }
+
     pub fn with_importance(mut self, importance: f32) -> Self {
         self.importance = importance.clamp(0.0, 1.0);
         self
     }
 }
 
+/// Timeline event for a quick note; the first line becomes the title
+pub fn quick_note_event(meeting_id: &str, ts: DateTime<Utc>, text: &str) -> TimelineEvent {
+    let title: String = text
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take(80)
+        .collect();
+    TimelineEvent::new(meeting_id, ts, TimelineEventType::QuickNote, title)
+        .with_description(text)
+        .with_importance(0.9)
+}
+
+/// Timeline event for a live decision or action item; other insights are
+/// left to the insights panel
+pub fn insight_event(meeting_id: &str, insight: &LiveInsightEvent) -> Option<TimelineEvent> {
+    let (event_type, text, detail, ts_ms, importance) = match insight {
+        LiveInsightEvent::Decision {
+            text,
+            context,
+            timestamp_ms,
+            ..
+        } => (
+            TimelineEventType::Decision,
+            text,
+            Some(context.as_str()),
+            *timestamp_ms,
+            0.9,
+        ),
+        LiveInsightEvent::ActionItem {
+            text,
+            assignee,
+            timestamp_ms,
+            ..
+        } => (
+            TimelineEventType::ActionItem,
+            text,
+            assignee.as_deref(),
+            *timestamp_ms,
+            0.85,
+        ),
+        _ => return None,
+    };
+    let ts = DateTime::<Utc>::from_timestamp_millis(ts_ms).unwrap_or_else(Utc::now);
+    let mut event = TimelineEvent::new(meeting_id, ts, event_type, text.chars().take(80).collect())
+        .with_importance(importance);
+    if let Some(detail) = detail.filter(|d| !d.trim().is_empty()) {
+        event = event.with_description(detail);
+    }
+    Some(event)
+}
+
+/// "speaker_0" → "Speaker 1"; "Me"/"Them" and renamed speakers pass through
+fn speaker_name(label: &str) -> String {
+    match label
+        .strip_prefix("speaker_")
+        .and_then(|n| n.parse::<u32>().ok())
+    {
+        Some(n) => format!("Speaker {}", n + 1),
+        None => label.to_string(),
+    }
+}
+
+/// Uninterrupted talk by one speaker
+#[derive(Debug, Clone)]
+struct SpeakerRun {
+    speaker: String,
+    start_ts: DateTime<Utc>,
+    last_ts: DateTime<Utc>,
+    words: usize,
+}
+
+/// Transcript state between finalized segments
+#[derive(Debug, Default)]
+struct TranscriptTracker {
+    run: Option<SpeakerRun>,
+    /// Recent questions inside the burst window
+    questions: VecDeque<(DateTime<Utc>, String)>,
+    /// A burst was already reported for the current run of questions
+    in_burst: bool,
+}
+
 /// Topic information for clustering
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TopicCluster {
@@ -208,6 +353,7 @@ struct TimelineAccumulator {
     topics: HashMap<String, TopicCluster>,
     last_app: Option<String>,
     last_event_ts: Option<DateTime<Utc>>,
+    transcript: TranscriptTracker,
 }
 
 /// Timeline builder for generating meeting timelines
@@ -231,6 +377,7 @@ impl TimelineBuilder {
                 topics: HashMap::new(),
                 last_app: None,
                 last_event_ts: None,
+                transcript: TranscriptTracker::default(),
             }),
         }
     }
@@ -244,6 +391,7 @@ impl TimelineBuilder {
         acc.topics.clear();
         acc.last_app = None;
         acc.last_event_ts = Some(start_ts);
+        acc.transcript = TranscriptTracker::default();
 
         // Add meeting start event
         let event = TimelineEvent::new(
@@ -266,6 +414,13 @@ impl TimelineBuilder {
 
         let mut acc = self.accumulator.lock();
 
+        // A monologue still running when the meeting stops
+        if let Some(run) = acc.transcript.run.take() {
+            if let Some(event) = self.monologue_event(&meeting_id, &run) {
+                acc.events.push(event);
+            }
+        }
+
         // Add meeting end event
         let event = TimelineEvent::new(
             &meeting_id,
@@ -277,10 +432,224 @@ impl TimelineBuilder {
 
         acc.events.push(event);
 
+        // Transcript events arrive after the screen events around them;
+        // a stable sort keeps arrival order for equal timestamps
+        acc.events.sort_by_key(|e| e.ts);
+
         // Return copy of events
         acc.events.clone()
     }
 
+    /// Get the ID of the meeting currently being built
+    pub fn current_meeting_id(&self) -> Option<String> {
+        self.meeting_id.lock().clone()
+    }
+
+    /// Record a user-initiated pause
+    pub fn pause_meeting(&self, ts: DateTime<Utc>) -> Option<TimelineEvent> {
+        let meeting_id = self.meeting_id.lock().clone()?;
+
+        let event = TimelineEvent::new(
+            &meeting_id,
+            ts,
+            TimelineEventType::Paused,
+            "Recording Paused".to_string(),
+        )
+        .with_importance(0.8);
+
+        self.accumulator.lock().events.push(event.clone());
+        Some(event)
+    }
+
+    /// Record a resume, closing out the most recent pause with its duration
+    pub fn resume_meeting(&self, ts: DateTime<Utc>) -> Option<TimelineEvent> {
+        let meeting_id = self.meeting_id.lock().clone()?;
+        let mut acc = self.accumulator.lock();
+
+        if let Some(paused) = acc
+            .events
+            .iter_mut()
+            .rev()
+            .find(|e| e.event_type == TimelineEventType::Paused)
+        {
+            if paused.duration_ms.is_none() {
+                paused.duration_ms = Some((ts - paused.ts).num_milliseconds());
+            }
+        }
+
+        // Pauses are intentional, so don't let them show up as activity gaps
+        acc.last_event_ts = Some(ts);
+
+        let event = TimelineEvent::new(
+            &meeting_id,
+            ts,
+            TimelineEventType::Resumed,
+            "Recording Resumed".to_string(),
+        )
+        .with_importance(0.8);
+
+        acc.events.push(event.clone());
+        Some(event)
+    }
+
+    /// Record the microphone going silent; `since` is when the input dropped out
+    pub fn record_silence(
+        &self,
+        since: DateTime<Utc>,
+        silent_secs: u64,
+        threshold_db: f32,
+    ) -> Option<TimelineEvent> {
+        let meeting_id = self.meeting_id.lock().clone()?;
+
+        let event = TimelineEvent::new(
+            &meeting_id,
+            since,
+            TimelineEventType::MicSilent,
+            "Microphone Silent".to_string(),
+        )
+        .with_description(&format!(
+            "No microphone input above {:.0} dB for {}s",
+            threshold_db, silent_secs
+        ))
+        .with_importance(0.9);
+
+        self.accumulator.lock().events.push(event.clone());
+        Some(event)
+    }
+
+    /// Close out the open silence event with its duration once input returns
+    pub fn end_silence(&self, ts: DateTime<Utc>) {
+        let mut acc = self.accumulator.lock();
+        if let Some(silent) = acc
+            .events
+            .iter_mut()
+            .rev()
+            .find(|e| e.event_type == TimelineEventType::MicSilent)
+        {
+            if silent.duration_ms.is_none() {
+                silent.duration_ms = Some((ts - silent.ts).num_milliseconds());
+            }
+        }
+    }
+
+    /// Record a quick note against the meeting being built
+    pub fn add_quick_note(&self, ts: DateTime<Utc>, text: &str) -> Option<TimelineEvent> {
+        let meeting_id = self.meeting_id.lock().clone()?;
+        let event = quick_note_event(&meeting_id, ts, text);
+        self.accumulator.lock().events.push(event.clone());
+        Some(event)
+    }
+
+    /// Process a finalized transcript segment: closes monologues when the
+    /// speaker changes and reports bursts of questions
+    pub fn process_transcript(
+        &self,
+        ts: DateTime<Utc>,
+        speaker: Option<&str>,
+        text: &str,
+    ) -> Vec<TimelineEvent> {
+        let Some(meeting_id) = self.meeting_id.lock().clone() else {
+            return Vec::new();
+        };
+        let words = text.split_whitespace().count();
+        if words == 0 {
+            return Vec::new();
+        }
+
+        let mut new_events = Vec::new();
+        let mut acc = self.accumulator.lock();
+        let tracker = &mut acc.transcript;
+
+        // Undiarized segments can't extend a run: we don't know who spoke
+        let same_speaker = matches!(
+            (&tracker.run, speaker),
+            (Some(run), Some(s)) if run.speaker == s
+        );
+        if same_speaker {
+            if let Some(run) = tracker.run.as_mut() {
+                run.last_ts = ts;
+                run.words += words;
+            }
+        } else {
+            if let Some(run) = tracker.run.take() {
+                new_events.extend(self.monologue_event(&meeting_id, &run));
+            }
+            tracker.run = speaker.map(|s| SpeakerRun {
+                speaker: s.to_string(),
+                start_ts: ts,
+                last_ts: ts,
+                words,
+            });
+        }
+
+        let window_start = ts - Duration::milliseconds(self.config.question_window_ms);
+        while tracker
+            .questions
+            .front()
+            .is_some_and(|(q_ts, _)| *q_ts < window_start)
+        {
+            tracker.questions.pop_front();
+        }
+        if tracker.questions.is_empty() {
+            tracker.in_burst = false;
+        }
+        if text.contains('?') {
+            tracker.questions.push_back((ts, text.trim().to_string()));
+            if !tracker.in_burst && tracker.questions.len() >= self.config.question_burst_min {
+                tracker.in_burst = true;
+                let first_ts = tracker.questions.front().map(|(t, _)| *t).unwrap_or(ts);
+                let asked: Vec<&str> = tracker.questions.iter().map(|(_, q)| q.as_str()).collect();
+                new_events.push(
+                    TimelineEvent::new(
+                        &meeting_id,
+                        first_ts,
+                        TimelineEventType::QuestionBurst,
+                        format!("{} questions in quick succession", asked.len()),
+                    )
+                    .with_description(&asked.join("\n"))
+                    .with_duration((ts - first_ts).num_milliseconds())
+                    .with_importance(0.7),
+                );
+            }
+        }
+
+        acc.events.extend(new_events.iter().cloned());
+        new_events
+    }
+
+    /// Record a live decision or action item against the meeting being built
+    pub fn add_insight(&self, insight: &LiveInsightEvent) -> Option<TimelineEvent> {
+        let meeting_id = self.meeting_id.lock().clone()?;
+        let event = insight_event(&meeting_id, insight)?;
+        self.accumulator.lock().events.push(event.clone());
+        Some(event)
+    }
+
+    /// Monologue event for a finished run, if it was long enough
+    fn monologue_event(&self, meeting_id: &str, run: &SpeakerRun) -> Option<TimelineEvent> {
+        let duration_ms = (run.last_ts - run.start_ts).num_milliseconds();
+        if duration_ms < self.config.monologue_min_ms {
+            return None;
+        }
+        let minutes = duration_ms as f32 / 60_000.0;
+        Some(
+            TimelineEvent::new(
+                meeting_id,
+                run.start_ts,
+                TimelineEventType::Monologue,
+                format!(
+                    "{} spoke for {:.0} min",
+                    speaker_name(&run.speaker),
+                    minutes
+                ),
+            )
+            .with_description(&format!("{} words", run.words))
+            .with_duration(duration_ms)
+            // Longer stretches matter more, up to ~10 minutes
+            .with_importance(0.5 + (minutes / 10.0).min(1.0) * 0.3),
+        )
+    }
+
     /// Process an episode and generate timeline events
     pub fn process_episode(&self, episode: &DocumentEpisode) -> Vec<TimelineEvent> {
         let meeting_id = match self.meeting_id.lock().clone() {
@@ -330,6 +699,7 @@ impl TimelineBuilder {
                     )
                     .with_app(app_name)
                     .with_episode(&episode.episode_id)
+                    .with_category(episode.category.as_str())
                     .with_importance(0.6);
 
                     acc.events.push(event.clone());
@@ -352,6 +722,7 @@ impl TimelineBuilder {
             )
             .with_duration(duration_ms)
             .with_episode(&episode.episode_id)
+            .with_category(episode.category.as_str())
             .with_importance(self.calculate_importance(episode));
 
             if let Some(ref app) = episode.app_name {
@@ -590,6 +961,7 @@ impl TimelineBuilder {
         acc.topics.clear();
         acc.last_app = None;
         acc.last_event_ts = None;
+        acc.transcript = TranscriptTracker::default();
     }
 }
 
@@ -616,6 +988,8 @@ mod tests {
             document_fingerprint: None,
             state_ids: vec!["state1".to_string()],
             state_count: 1,
+            category: crate::state_builder::StateType::Coding,
+            category_confidence: 0.9,
         }
     }
 
@@ -631,6 +1005,29 @@ mod tests {
         assert_eq!(events[1].event_type, TimelineEventType::MeetingEnd);
     }
 
+    #[test]
+    fn test_pause_resume_records_duration() {
+        let builder = TimelineBuilder::new();
+        let start = Utc::now();
+        builder.start_meeting("test_meeting", start);
+
+        builder.pause_meeting(start + Duration::seconds(10));
+        builder.resume_meeting(start + Duration::seconds(40));
+        let events = builder.end_meeting(start + Duration::seconds(60));
+
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[1].event_type, TimelineEventType::Paused);
+        assert_eq!(events[1].duration_ms, Some(30_000));
+        assert_eq!(events[2].event_type, TimelineEventType::Resumed);
+    }
+
+    #[test]
+    fn test_pause_without_meeting_is_noop() {
+        let builder = TimelineBuilder::new();
+        assert!(builder.pause_meeting(Utc::now()).is_none());
+        assert!(builder.current_meeting_id().is_none());
+    }
+
     #[test]
     fn test_episode_generates_events() {
         let builder = TimelineBuilder::new();
@@ -641,6 +1038,10 @@ mod tests {
 
         // Should generate at least one event (document opened)
         assert!(!events.is_empty());
+        // ...carrying the episode's activity category
+        assert!(events
+            .iter()
+            .all(|e| e.category.as_deref() == Some("coding")));
     }
 
     #[test]
@@ -674,4 +1075,111 @@ mod tests {
             .iter()
             .any(|e| e.event_type == TimelineEventType::AppSwitch));
     }
+
+    #[test]
+    fn test_silence_event_gets_duration() {
+        let builder = TimelineBuilder::new();
+        let start = Utc::now();
+        builder.start_meeting("test_meeting", start);
+
+        let since = start + Duration::seconds(60);
+        let event = builder.record_silence(since, 30, -50.0).unwrap();
+        assert_eq!(event.event_type, TimelineEventType::MicSilent);
+        assert_eq!(event.ts, since);
+
+        builder.end_silence(since + Duration::seconds(45));
+        let silent = builder
+            .get_events()
+            .into_iter()
+            .find(|e| e.event_type == TimelineEventType::MicSilent)
+            .unwrap();
+        assert_eq!(silent.duration_ms, Some(45_000));
+    }
+
+    #[test]
+    fn test_quick_note_joins_timeline() {
+        let builder = TimelineBuilder::new();
+        assert!(builder.add_quick_note(Utc::now(), "orphan").is_none());
+
+        let start = Utc::now();
+        builder.start_meeting("test_meeting", start);
+        let at = start + Duration::seconds(90);
+        let event = builder
+            .add_quick_note(at, "follow up on pricing\nask about seats")
+            .unwrap();
+        assert_eq!(event.event_type, TimelineEventType::QuickNote);
+        assert_eq!(event.title, "follow up on pricing");
+        assert_eq!(event.ts, at);
+
+        let events = builder.end_meeting(start + Duration::seconds(120));
+        assert_eq!(events[1].event_id, event.event_id);
+    }
+
+    #[test]
+    fn test_transcript_monologue_and_question_burst() {
+        let builder = TimelineBuilder::new();
+        let start = Utc::now();
+        builder.start_meeting("test_meeting", start);
+        let at = |secs| start + Duration::seconds(secs);
+
+        // speaker_0 talks for three minutes, then speaker_1 takes over
+        assert!(builder
+            .process_transcript(at(10), Some("speaker_0"), "Let me walk through the plan")
+            .is_empty());
+        builder.process_transcript(at(100), Some("speaker_0"), "and the budget");
+        builder.process_transcript(at(190), Some("speaker_0"), "and the timeline");
+        let events = builder.process_transcript(at(200), Some("speaker_1"), "Makes sense.");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, TimelineEventType::Monologue);
+        assert_eq!(events[0].title, "Speaker 1 spoke for 3 min");
+        assert_eq!(events[0].ts, at(10));
+        assert_eq!(events[0].duration_ms, Some(180_000));
+
+        // Three questions inside a minute make one burst
+        builder.process_transcript(at(210), Some("speaker_1"), "What about hiring?");
+        builder.process_transcript(at(220), None, "Who owns it?");
+        let burst = builder.process_transcript(at(230), Some("speaker_0"), "When do we ship?");
+        assert_eq!(burst.len(), 1);
+        assert_eq!(burst[0].event_type, TimelineEventType::QuestionBurst);
+        assert_eq!(burst[0].ts, at(210));
+        assert!(builder
+            .process_transcript(at(240), Some("speaker_0"), "Is that final?")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_insights_and_stable_ordering() {
+        let builder = TimelineBuilder::new();
+        // Whole milliseconds, so insight timestamps round-trip exactly
+        let start = DateTime::from_timestamp_millis(Utc::now().timestamp_millis()).unwrap();
+        builder.start_meeting("test_meeting", start);
+        let at = start + Duration::seconds(30);
+
+        let note = builder.add_quick_note(at, "screen side").unwrap();
+        let decision = builder
+            .add_insight(&LiveInsightEvent::Decision {
+                id: "d1".to_string(),
+                text: "Ship on Friday".to_string(),
+                context: "after QA signs off".to_string(),
+                timestamp_ms: at.timestamp_millis(),
+            })
+            .unwrap();
+        assert_eq!(decision.event_type, TimelineEventType::Decision);
+        assert!(builder
+            .add_insight(&LiveInsightEvent::TopicShift {
+                id: "t1".to_string(),
+                from_topic: "a".to_string(),
+                to_topic: "b".to_string(),
+                timestamp_ms: at.timestamp_millis(),
+            })
+            .is_none());
+
+        // A late transcript event with an earlier timestamp sorts before them
+        builder.add_quick_note(start + Duration::seconds(5), "earlier");
+        let events = builder.end_meeting(start + Duration::seconds(60));
+        let ids: Vec<&str> = events.iter().map(|e| e.event_id.as_str()).collect();
+        assert_eq!(ids[2], note.event_id);
+        assert_eq!(ids[3], decision.event_id);
+        assert_eq!(events[1].title, "earlier");
+    }
 }
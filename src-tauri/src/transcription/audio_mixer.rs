@@ -0,0 +1,303 @@
+// noFriction Meetings - Audio Mixer
+// Combines microphone and system audio into one 16kHz mono stream for providers
+//
+// Providers accept a single audio stream, so mic ("me") and system output
+// ("them") are resampled to a common rate and summed. Per-source energy is
+// tracked alongside so final transcripts can be attributed to a side even
+// when the provider doesn't diarize.
+
+use parking_lot::RwLock;
+use std::collections::VecDeque;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::capture_engine::AudioSource;
+
+/// Common output rate handed to every provider
+pub const MIX_SAMPLE_RATE: u32 = 16_000;
+/// Flush a lone source once the other has been silent this long
+const STALE_SOURCE_MS: u64 = 200;
+/// Cap per-source backlog (1s) so clock drift between devices can't grow unbounded
+const MAX_QUEUED_SAMPLES: usize = MIX_SAMPLE_RATE as usize;
+/// How far back energy readings are kept for attribution
+const ACTIVITY_HISTORY: Duration = Duration::from_secs(30);
+/// Window used to attribute a final transcript to a side
+const ATTRIBUTION_WINDOW: Duration = Duration::from_secs(8);
+/// RMS below this is treated as silence
+pub(crate) const SILENCE_RMS: f32 = 0.01;
+
+/// Speaker label for transcripts dominated by the microphone
+pub const SPEAKER_ME: &str = "Me";
+/// Speaker label for transcripts dominated by system audio
+pub const SPEAKER_THEM: &str = "Them";
+
+struct SourceQueue {
+    samples: VecDeque<f32>,
+    last_push: Option<Instant>,
+}
+
+impl SourceQueue {
+    fn new() -> Self {
+        Self {
+            samples: VecDeque::new(),
+            last_push: None,
+        }
+    }
+
+    fn push(&mut self, samples: Vec<f32>) {
+        self.samples.extend(samples);
+        let overflow = self.samples.len().saturating_sub(MAX_QUEUED_SAMPLES);
+        if overflow > 0 {
+            self.samples.drain(..overflow);
+        }
+        self.last_push = Some(Instant::now());
+    }
+
+    fn is_stale(&self, now: Instant) -> bool {
+        self.last_push
+            .map(|t| now.duration_since(t) > Duration::from_millis(STALE_SOURCE_MS))
+            .unwrap_or(true)
+    }
+}
+
+/// Sums mic + system audio into a single 16kHz mono stream
+pub struct AudioMixer {
+    mic: SourceQueue,
+    system: SourceQueue,
+}
+
+impl Default for AudioMixer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioMixer {
+    pub fn new() -> Self {
+        Self {
+            mic: SourceQueue::new(),
+            system: SourceQueue::new(),
+        }
+    }
+
+    /// Drop any buffered audio (called between recordings)
+    pub fn reset(&mut self) {
+        self.mic = SourceQueue::new();
+        self.system = SourceQueue::new();
+    }
+
+    /// Add a captured buffer and return whatever mixed audio is ready
+    pub fn push(
+        &mut self,
+        source: AudioSource,
+        samples: &[f32],
+        sample_rate: u32,
+        channels: u16,
+    ) -> Vec<f32> {
+        let resampled = resample_to_16k_mono(samples, sample_rate, channels.max(1));
+        match source {
+            AudioSource::Microphone => self.mic.push(resampled),
+            AudioSource::System => self.system.push(resampled),
+        }
+        self.drain(Instant::now())
+    }
+
+    fn drain(&mut self, now: Instant) -> Vec<f32> {
+        let both = self.mic.samples.len().min(self.system.samples.len());
+        let mut out: Vec<f32> = self
+            .mic
+            .samples
+            .drain(..both)
+            .zip(self.system.samples.drain(..both))
+            .map(|(a, b)| (a + b).clamp(-1.0, 1.0))
+            .collect();
+
+        // Only one side is producing audio (system audio off, or a device stalled)
+        if self.system.is_stale(now) {
+            out.extend(self.mic.samples.drain(..));
+        }
+        if self.mic.is_stale(now) {
+            out.extend(self.system.samples.drain(..));
+        }
+
+        out
+    }
+}
+
+/// Rolling per-source energy used to tell "me" from "them"
+pub struct SourceActivity {
+    readings: RwLock<VecDeque<(Instant, AudioSource, f32)>>,
+}
+
+/// Global activity tracker shared by the mixer and transcript persistence
+pub fn source_activity() -> &'static SourceActivity {
+    static ACTIVITY: OnceLock<SourceActivity> = OnceLock::new();
+    ACTIVITY.get_or_init(SourceActivity::new)
+}
+
+impl SourceActivity {
+    pub fn new() -> Self {
+        Self {
+            readings: RwLock::new(VecDeque::new()),
+        }
+    }
+
+    pub fn record(&self, source: AudioSource, samples: &[f32]) {
+        if samples.is_empty() {
+            return;
+        }
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+        self.record_at(Instant::now(), source, rms);
+    }
+
+    fn record_at(&self, at: Instant, source: AudioSource, rms: f32) {
+        let mut readings = self.readings.write();
+        readings.push_back((at, source, rms));
+        while let Some((t, _, _)) = readings.front() {
+            if at.duration_since(*t) > ACTIVITY_HISTORY {
+                readings.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn clear(&self) {
+        self.readings.write().clear();
+    }
+
+    /// Which side was louder over `window`. None if only one source is
+    /// being captured (nothing to tell apart) or everything was silent.
+    pub fn dominant_source(&self, window: Duration) -> Option<AudioSource> {
+        self.dominant_source_at(Instant::now(), window)
+    }
+
+    fn dominant_source_at(&self, now: Instant, window: Duration) -> Option<AudioSource> {
+        let readings = self.readings.read();
+        let (mut mic, mut system) = ((0.0f32, 0usize), (0.0f32, 0usize));
+
+        for (t, source, rms) in readings.iter() {
+            if now.saturating_duration_since(*t) > window {
+                continue;
+            }
+            match source {
+                AudioSource::Microphone => {
+                    mic.0 += rms;
+                    mic.1 += 1;
+                }
+                AudioSource::System => {
+                    system.0 += rms;
+                    system.1 += 1;
+                }
+            }
+        }
+
+        if mic.1 == 0 || system.1 == 0 {
+            return None;
+        }
+
+        let mic_avg = mic.0 / mic.1 as f32;
+        let system_avg = system.0 / system.1 as f32;
+        if mic_avg.max(system_avg) < SILENCE_RMS {
+            return None;
+        }
+
+        if mic_avg >= system_avg {
+            Some(AudioSource::Microphone)
+        } else {
+            Some(AudioSource::System)
+        }
+    }
+}
+
+impl Default for SourceActivity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// "Me" / "Them" for a transcript that just finalized, if both sides are captured
+pub fn attribute_speaker() -> Option<String> {
+    source_activity()
+        .dominant_source(ATTRIBUTION_WINDOW)
+        .map(|source| match source {
+            AudioSource::Microphone => SPEAKER_ME.to_string(),
+            AudioSource::System => SPEAKER_THEM.to_string(),
+        })
+}
+
+fn resample_to_16k_mono(samples: &[f32], from_rate: u32, channels: u16) -> Vec<f32> {
+    if samples.is_empty() {
+        return vec![];
+    }
+
+    let mono: Vec<f32> = if channels > 1 {
+        samples
+            .chunks(channels as usize)
+            .map(|chunk| chunk.iter().sum::<f32>() / chunk.len() as f32)
+            .collect()
+    } else {
+        samples.to_vec()
+    };
+
+    if from_rate == MIX_SAMPLE_RATE {
+        return mono;
+    }
+
+    let ratio = MIX_SAMPLE_RATE as f64 / from_rate as f64;
+    let new_len = (mono.len() as f64 * ratio) as usize;
+
+    (0..new_len)
+        .map(|i| {
+            let src_idx = i as f64 / ratio;
+            let idx = src_idx.floor() as usize;
+            let frac = (src_idx - idx as f64) as f32;
+            match (mono.get(idx), mono.get(idx + 1)) {
+                (Some(a), Some(b)) => a * (1.0 - frac) + b * frac,
+                (Some(a), None) => *a,
+                _ => 0.0,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mixes_overlapping_sources() {
+        let mut mixer = AudioMixer::new();
+        let out = mixer.push(AudioSource::Microphone, &[0.25; 160], 16_000, 1);
+        // System hasn't pushed yet, so mic passes straight through
+        assert_eq!(out.len(), 160);
+
+        mixer.mic.push(vec![0.25; 160]);
+        mixer.system.push(vec![0.5; 160]);
+        let out = mixer.drain(Instant::now());
+        assert_eq!(out.len(), 160);
+        assert!((out[0] - 0.75).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_resample_stereo_48k() {
+        let samples = vec![0.5f32; 48_000 * 2];
+        let out = resample_to_16k_mono(&samples, 48_000, 2);
+        assert_eq!(out.len(), 16_000);
+        assert!((out[100] - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dominant_source_requires_both_sides() {
+        let activity = SourceActivity::new();
+        let now = Instant::now();
+        activity.record_at(now, AudioSource::Microphone, 0.2);
+        assert_eq!(activity.dominant_source_at(now, ATTRIBUTION_WINDOW), None);
+
+        activity.record_at(now, AudioSource::System, 0.4);
+        assert_eq!(
+            activity.dominant_source_at(now, ATTRIBUTION_WINDOW),
+            Some(AudioSource::System)
+        );
+    }
+}
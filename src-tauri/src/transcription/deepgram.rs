@@ -5,13 +5,26 @@ use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::mpsc;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
 use crate::database::DatabaseManager;
 use crate::live_intel_agent::LiveIntelAgent;
-use crate::transcription::TranscriptionProvider;
+use crate::transcription::{
+    glossary, ConnectionHealth, FinalSegment, GlossaryTerm, TranscriptionProvider,
+};
+use crate::usage_ledger::AudioUsage;
+
+/// Reconnect attempts after a dropped connection before giving up
+const MAX_RECONNECT_ATTEMPTS: u32 = 8;
+/// First reconnect delay; doubles per attempt
+const RECONNECT_BASE_DELAY_MS: u64 = 500;
+/// Upper bound on reconnect delay
+const RECONNECT_MAX_DELAY_MS: u64 = 30_000;
+/// Audio kept while reconnecting, flushed once the socket is back
+const RECONNECT_BUFFER_SECS: f32 = 30.0;
 
 // Reuse the existing structures from deepgram_client.rs
 // (Normally we would import them if they were public, but simpler to redefine or move here)
@@ -57,6 +70,8 @@ pub struct TranscriptSegment {
     pub start: f64,
     pub duration: f64,
     pub speaker: Option<String>,
+    /// Transcribed from audio buffered while the connection was down
+    pub after_reconnect: bool,
 }
 
 struct AudioBatch {
@@ -65,6 +80,53 @@ struct AudioBatch {
     channels: u16,
 }
 
+impl AudioBatch {
+    fn secs(&self) -> f32 {
+        if self.sample_rate == 0 {
+            return 0.0;
+        }
+        self.samples.len() as f32 / self.channels.max(1) as f32 / self.sample_rate as f32
+    }
+}
+
+/// Bounded audio backlog held while reconnecting; oldest audio is dropped first
+struct ReconnectBuffer {
+    batches: VecDeque<AudioBatch>,
+    secs: f32,
+}
+
+impl ReconnectBuffer {
+    fn new() -> Self {
+        Self {
+            batches: VecDeque::new(),
+            secs: 0.0,
+        }
+    }
+
+    fn push(&mut self, batch: AudioBatch) {
+        self.secs += batch.secs();
+        self.batches.push_back(batch);
+        while self.secs > RECONNECT_BUFFER_SECS {
+            match self.batches.pop_front() {
+                Some(dropped) => self.secs -= dropped.secs(),
+                None => break,
+            }
+        }
+    }
+
+    fn drain(&mut self) -> Vec<AudioBatch> {
+        self.secs = 0.0;
+        self.batches.drain(..).collect()
+    }
+}
+
+/// Delay before reconnect attempt `attempt` (1-based)
+fn reconnect_delay(attempt: u32) -> Duration {
+    let exp = attempt.saturating_sub(1).min(16);
+    let delay = RECONNECT_BASE_DELAY_MS.saturating_mul(1u64 << exp);
+    Duration::from_millis(delay.min(RECONNECT_MAX_DELAY_MS))
+}
+
 pub struct DeepgramProvider {
     api_key: Arc<RwLock<Option<String>>>,
     is_connected: Arc<AtomicBool>,
@@ -74,6 +136,11 @@ pub struct DeepgramProvider {
     database: Arc<RwLock<Option<Arc<DatabaseManager>>>>,
     meeting_id: Arc<RwLock<Option<String>>>,
     live_intel_agent: Arc<RwLock<Option<Arc<RwLock<LiveIntelAgent>>>>>,
+    glossary: Arc<RwLock<Vec<GlossaryTerm>>>,
+    connection: Arc<RwLock<ConnectionHealth>>,
+    reconnect_buffer: Arc<parking_lot::Mutex<ReconnectBuffer>>,
+    /// Bumped by start/stop so a stale reconnect loop knows to exit
+    generation: Arc<AtomicU64>,
 }
 
 impl DeepgramProvider {
@@ -87,14 +154,109 @@ impl DeepgramProvider {
             database: Arc::new(RwLock::new(None)),
             meeting_id: Arc::new(RwLock::new(None)),
             live_intel_agent: Arc::new(RwLock::new(None)),
+            glossary: Arc::new(RwLock::new(Vec::new())),
+            connection: Arc::new(RwLock::new(ConnectionHealth::Idle)),
+            reconnect_buffer: Arc::new(parking_lot::Mutex::new(ReconnectBuffer::new())),
+            generation: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Connect, stream until the socket closes, then reconnect with backoff
+    /// until the session is stopped or the retry budget runs out
+    #[allow(clippy::too_many_arguments)]
+    async fn run_session(
+        generation: u64,
+        current_generation: Arc<AtomicU64>,
+        api_key: String,
+        model: String,
+        glossary_terms: Vec<GlossaryTerm>,
+        app: AppHandle,
+        is_connected: Arc<AtomicBool>,
+        connection: Arc<RwLock<ConnectionHealth>>,
+        reconnect_buffer: Arc<parking_lot::Mutex<ReconnectBuffer>>,
+        audio_tx_holder: Arc<RwLock<Option<mpsc::Sender<AudioBatch>>>>,
+        samples_sent: Arc<AtomicU64>,
+        database: Arc<RwLock<Option<Arc<DatabaseManager>>>>,
+        meeting_id: Arc<RwLock<Option<String>>>,
+        live_intel_agent: Arc<RwLock<Option<Arc<RwLock<LiveIntelAgent>>>>>,
+    ) {
+        let is_current = || current_generation.load(Ordering::SeqCst) == generation;
+        let mut attempt = 0u32;
+
+        loop {
+            let result = Self::connect_internal(
+                api_key.clone(),
+                model.clone(),
+                glossary_terms.clone(),
+                app.clone(),
+                is_connected.clone(),
+                connection.clone(),
+                reconnect_buffer.clone(),
+                audio_tx_holder.clone(),
+                samples_sent.clone(),
+                database.clone(),
+                meeting_id.clone(),
+                live_intel_agent.clone(),
+            )
+            .await;
+
+            if !is_current() {
+                return;
+            }
+
+            let reason = match result {
+                Ok(()) => {
+                    // Was connected; a fresh drop gets the full retry budget
+                    attempt = 0;
+                    "connection closed".to_string()
+                }
+                Err(e) => e,
+            };
+
+            attempt += 1;
+            if attempt > MAX_RECONNECT_ATTEMPTS {
+                log::error!(
+                    "Deepgram gave up after {} reconnect attempts: {}",
+                    MAX_RECONNECT_ATTEMPTS,
+                    reason
+                );
+                reconnect_buffer.lock().drain();
+                *connection.write() = ConnectionHealth::Failed { reason };
+                return;
+            }
+
+            let delay = reconnect_delay(attempt);
+            log::warn!(
+                "Deepgram connection lost ({}), reconnecting in {:?} (attempt {}/{})",
+                reason,
+                delay,
+                attempt,
+                MAX_RECONNECT_ATTEMPTS
+            );
+            *connection.write() = ConnectionHealth::Reconnecting {
+                attempt,
+                max_attempts: MAX_RECONNECT_ATTEMPTS,
+                buffered_secs: 0.0,
+            };
+
+            tokio::time::sleep(delay).await;
+            if !is_current() {
+                return;
+            }
+        }
+    }
+
+    /// One WebSocket connection. Returns once it closes (Ok) or if it could
+    /// not be opened (Err). Audio buffered while reconnecting is sent first.
+    #[allow(clippy::too_many_arguments)]
     async fn connect_internal(
         api_key: String,
         model: String,
+        glossary_terms: Vec<GlossaryTerm>,
         app: AppHandle,
         is_connected: Arc<AtomicBool>,
+        connection: Arc<RwLock<ConnectionHealth>>,
+        reconnect_buffer: Arc<parking_lot::Mutex<ReconnectBuffer>>,
         audio_tx_holder: Arc<RwLock<Option<mpsc::Sender<AudioBatch>>>>,
         samples_sent: Arc<AtomicU64>,
         database: Arc<RwLock<Option<Arc<DatabaseManager>>>>,
@@ -104,8 +266,9 @@ impl DeepgramProvider {
         // Use selected model with advanced features
         // Build a clean URL without escape characters
         let url = format!(
-            "wss://api.deepgram.com/v1/listen?model={}&language=en-US&smart_format=true&punctuate=true&diarize=true&dictation=true&endpointing=10&utterance_end_ms=1000&vad_events=true&interim_results=true&encoding=linear16&sample_rate=16000&channels=1",
-            model
+            "wss://api.deepgram.com/v1/listen?model={}&language=en-US&smart_format=true&punctuate=true&diarize=true&dictation=true&endpointing=10&utterance_end_ms=1000&vad_events=true&interim_results=true&encoding=linear16&sample_rate=16000&channels=1{}",
+            model,
+            glossary::deepgram_query_params(&glossary_terms, &model)
         );
 
         log::info!("🔗 Deepgram URL: {} (Model: {})", url, model);
@@ -129,20 +292,40 @@ impl DeepgramProvider {
             .await
             .map_err(|e| format!("Failed to connect to Deepgram: {}", e))?;
 
-        is_connected.store(true, Ordering::SeqCst);
-        log::info!("✅ Connected to Deepgram WebSocket (nova-3)");
-
         let (mut write, mut read) = ws_stream.split();
 
         // Create channel for audio batches
         let (audio_tx, mut audio_rx) = mpsc::channel::<AudioBatch>(100);
         *audio_tx_holder.write() = Some(audio_tx);
+        is_connected.store(true, Ordering::SeqCst);
+        *connection.write() = ConnectionHealth::Connected;
+
+        // Audio held while reconnecting goes out ahead of live audio. Results
+        // that start within it are flagged as after_reconnect.
+        let backlog = reconnect_buffer.lock().drain();
+        let flushed_secs: f64 = backlog.iter().map(|b| b.secs() as f64).sum();
+        if backlog.is_empty() {
+            log::info!("✅ Connected to Deepgram WebSocket ({})", model);
+        } else {
+            log::info!(
+                "✅ Reconnected to Deepgram WebSocket, flushing {:.1}s of buffered audio",
+                flushed_secs
+            );
+        }
 
         // Spawn task to process and send audio
         let is_connected_send = is_connected.clone();
+        let mut usage = AudioUsage::start("deepgram", &model, 16000);
         tokio::spawn(async move {
             let mut buffer: VecDeque<f32> = VecDeque::with_capacity(8000);
             let batch_size = 320usize; // 20ms @ 16kHz
+            for batch in backlog {
+                buffer.extend(Self::resample_to_16k_mono(
+                    &batch.samples,
+                    batch.sample_rate,
+                    batch.channels,
+                ));
+            }
 
             loop {
                 // Wait for audio with short timeout
@@ -192,114 +375,109 @@ impl DeepgramProvider {
 
                     if let Err(e) = write.send(Message::Binary(bytes.into())).await {
                         log::error!("Failed to send audio: {}", e);
+                        usage.mark_failed();
                         return;
                     }
+                    usage.add_samples(chunk.len());
                 }
             }
 
             let _ = write.close().await;
         });
 
-        // Spawn task to receive transcriptions
-        let is_connected_recv = is_connected.clone();
-        let database_recv = database.clone();
-        let meeting_id_recv = meeting_id.clone();
-        let intel_agent_recv = live_intel_agent.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = read.next().await {
-                if !is_connected_recv.load(Ordering::SeqCst) {
-                    break;
-                }
+        // Receive transcriptions until the socket closes
+        while let Some(msg) = read.next().await {
+            if !is_connected.load(Ordering::SeqCst) {
+                break;
+            }
 
-                match msg {
-                    Ok(Message::Text(text)) => {
-                        // Log raw response for debugging
-                        log::info!("🔍 Deepgram raw: {}", &text[..text.len().min(500)]);
+            match msg {
+                Ok(Message::Text(text)) => {
+                    // Log raw response for debugging
+                    log::info!("🔍 Deepgram raw: {}", &text[..text.len().min(500)]);
+
+                    if let Ok(response) = serde_json::from_str::<DeepgramResponse>(&text) {
+                        // Log parsed response structure
+                        if response.channel.is_some() {
+                            log::info!(
+                                "🎤 Deepgram response: channel present, is_final={:?}",
+                                response.is_final
+                            );
+                        }
 
-                        if let Ok(response) = serde_json::from_str::<DeepgramResponse>(&text) {
-                            // Log parsed response structure
-                            if response.channel.is_some() {
+                        if let Some(channel) = response.channel {
+                            if let Some(alt) = channel.alternatives.first() {
+                                // Log the transcript text for debugging
                                 log::info!(
-                                    "🎤 Deepgram response: channel present, is_final={:?}",
-                                    response.is_final
+                                    "📜 Transcript text: '{}' (len={})",
+                                    alt.transcript,
+                                    alt.transcript.len()
                                 );
-                            }
 
-                            if let Some(channel) = response.channel {
-                                if let Some(alt) = channel.alternatives.first() {
-                                    // Log the transcript text for debugging
-                                    log::info!(
-                                        "📜 Transcript text: '{}' (len={})",
-                                        alt.transcript,
-                                        alt.transcript.len()
-                                    );
-
-                                    if !alt.transcript.is_empty() {
-                                        let is_final = response.is_final.unwrap_or(false);
-                                        let segment = TranscriptSegment {
-                                            text: alt.transcript.clone(),
-                                            is_final,
-                                            confidence: alt.confidence,
-                                            start: response.start.unwrap_or(0.0),
-                                            duration: response.duration.unwrap_or(0.0),
-                                            speaker: alt
-                                                .words
-                                                .as_ref()
-                                                .and_then(|w| w.first())
-                                                .and_then(|w| w.speaker)
-                                                .map(|s| format!("Speaker {}", s)),
-                                        };
-
-                                        // Log transcript reception
-                                        if is_final {
-                                            log::info!("📝 TRANSCRIPT [FINAL]: {}", alt.transcript);
-                                        } else {
-                                            log::debug!(
-                                                "📝 transcript [interim]: {}",
-                                                alt.transcript
-                                            );
-                                        }
+                                if !alt.transcript.is_empty() {
+                                    let is_final = response.is_final.unwrap_or(false);
+                                    let start = response.start.unwrap_or(0.0);
+                                    let segment = TranscriptSegment {
+                                        text: alt.transcript.clone(),
+                                        is_final,
+                                        confidence: alt.confidence,
+                                        start,
+                                        duration: response.duration.unwrap_or(0.0),
+                                        speaker: alt
+                                            .words
+                                            .as_deref()
+                                            .and_then(Self::dominant_speaker)
+                                            .map(crate::transcription::speaker_label),
+                                        after_reconnect: start < flushed_secs,
+                                    };
+
+                                    // Log transcript reception
+                                    if is_final {
+                                        log::info!("📝 TRANSCRIPT [FINAL]: {}", alt.transcript);
+                                    } else {
+                                        log::debug!("📝 transcript [interim]: {}", alt.transcript);
+                                    }
 
-                                        // Emit to frontend
-                                        if let Err(e) = app.emit("live_transcript", &segment) {
-                                            log::error!("Failed to emit transcript: {}", e);
-                                        }
+                                    // Emit to frontend
+                                    if let Err(e) = app.emit("live_transcript", &segment) {
+                                        log::error!("Failed to emit transcript: {}", e);
+                                    }
 
-                                        // Process with LiveIntelAgent
-                                        if let Some(agent) = intel_agent_recv.read().as_ref() {
-                                            let mut agent = agent.write();
-                                            let intel_segment =
-                                                crate::catch_up_agent::TranscriptSegment {
-                                                    id: uuid::Uuid::new_v4().to_string(),
-                                                    timestamp_ms: (segment.start * 1000.0) as i64,
-                                                    speaker: segment.speaker.clone(),
-                                                    text: segment.text.clone(),
-                                                };
-                                            agent.process_segment(intel_segment);
+                                    // Feed finalized segments to LiveIntelAgent
+                                    if is_final {
+                                        if let Some(agent) = live_intel_agent.read().as_ref() {
+                                            crate::transcription::feed_live_intel(
+                                                &app,
+                                                agent,
+                                                database.read().as_ref().cloned(),
+                                                meeting_id.read().as_ref().cloned(),
+                                                &segment.text,
+                                                segment.speaker.clone(),
+                                            );
                                         }
+                                    }
 
-                                        // Save FINAL transcripts
-                                        if is_final {
-                                            if let Some(db) = database_recv.read().as_ref().cloned()
-                                            {
-                                                if let Some(mid) =
-                                                    meeting_id_recv.read().as_ref().cloned()
-                                                {
-                                                    let text_clone = alt.transcript.clone();
-                                                    let speaker_clone = segment.speaker.clone();
-                                                    let confidence = alt.confidence;
-                                                    tokio::spawn(async move {
-                                                        let _ = db
-                                                            .add_transcript(
-                                                                &mid,
-                                                                &text_clone,
-                                                                speaker_clone.as_deref(),
-                                                                true,
-                                                                confidence,
-                                                            )
-                                                            .await;
-                                                    });
-                                                }
+                                    // Save FINAL transcripts
+                                    if is_final {
+                                        if let Some(db) = database.read().as_ref().cloned() {
+                                            if let Some(mid) = meeting_id.read().as_ref().cloned() {
+                                                let text_clone = alt.transcript.clone();
+                                                let speaker_clone = segment.speaker.clone();
+                                                let confidence = alt.confidence;
+                                                tokio::spawn(
+                                                    crate::transcription::save_final_transcript(
+                                                        db,
+                                                        FinalSegment {
+                                                            meeting_id: mid,
+                                                            text: text_clone,
+                                                            speaker: speaker_clone,
+                                                            confidence,
+                                                            spoken_at: None,
+                                                            after_reconnect: segment
+                                                                .after_reconnect,
+                                                        },
+                                                    ),
+                                                );
                                             }
                                         }
                                     }
@@ -307,17 +485,31 @@ impl DeepgramProvider {
                             }
                         }
                     }
-                    Ok(Message::Close(_)) => break,
-                    Err(_) => break,
-                    _ => {}
                 }
+                Ok(Message::Close(_)) => break,
+                Err(_) => break,
+                _ => {}
             }
-            is_connected_recv.store(false, Ordering::SeqCst);
-        });
+        }
+        is_connected.store(false, Ordering::SeqCst);
 
+        // Stops the send task, which closes our half of the socket
+        *audio_tx_holder.write() = None;
         Ok(())
     }
 
+    /// Pick the speaker who said the most words in a segment
+    fn dominant_speaker(words: &[Word]) -> Option<u32> {
+        let mut counts: std::collections::HashMap<u32, usize> = std::collections::HashMap::new();
+        for speaker in words.iter().filter_map(|w| w.speaker) {
+            *counts.entry(speaker).or_insert(0) += 1;
+        }
+        counts
+            .into_iter()
+            .max_by_key(|(speaker, count)| (*count, std::cmp::Reverse(*speaker)))
+            .map(|(speaker, _)| speaker)
+    }
+
     fn f32_to_i16_bytes(samples: &[f32]) -> Vec<u8> {
         samples
             .iter()
@@ -400,16 +592,27 @@ impl TranscriptionProvider for DeepgramProvider {
             }
         };
 
-        if self.is_connected.load(Ordering::SeqCst) {
+        if !matches!(
+            *self.connection.read(),
+            ConnectionHealth::Idle | ConnectionHealth::Failed { .. }
+        ) {
             return;
         }
 
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let current_generation = self.generation.clone();
+        *self.connection.write() = ConnectionHealth::Connecting;
+        self.reconnect_buffer.lock().drain();
+
         let is_connected = self.is_connected.clone();
+        let connection = self.connection.clone();
+        let reconnect_buffer = self.reconnect_buffer.clone();
         let audio_tx_holder = self.audio_tx.clone();
         let samples_sent = self.samples_sent.clone();
         let database = self.database.clone();
         let meeting_id = self.meeting_id.clone();
         let live_intel_agent = self.live_intel_agent.clone();
+        let glossary_terms = self.glossary.read().clone();
 
         let app_handle_clone = app.clone();
 
@@ -423,27 +626,32 @@ impl TranscriptionProvider for DeepgramProvider {
                 }
             };
 
-            if let Err(e) = Self::connect_internal(
+            Self::run_session(
+                generation,
+                current_generation,
                 api_key,
                 model,
+                glossary_terms,
                 app,
                 is_connected,
+                connection,
+                reconnect_buffer,
                 audio_tx_holder,
                 samples_sent,
                 database,
                 meeting_id,
                 live_intel_agent,
             )
-            .await
-            {
-                log::error!("Deepgram connection failed: {}", e);
-            }
+            .await;
         });
     }
 
     fn stop(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
         self.is_connected.store(false, Ordering::SeqCst);
         *self.audio_tx.write() = None;
+        *self.connection.write() = ConnectionHealth::Idle;
+        self.reconnect_buffer.lock().drain();
         log::info!("Deepgram disconnected");
     }
 
@@ -464,7 +672,22 @@ impl TranscriptionProvider for DeepgramProvider {
             );
         }
 
+        let channels = if channels == 0 { 1 } else { channels }; // Guard against 0 channels
+
         if !self.is_connected.load(Ordering::SeqCst) {
+            // Hold audio while reconnecting so the gap can be transcribed
+            if matches!(
+                *self.connection.read(),
+                ConnectionHealth::Reconnecting { .. }
+            ) {
+                self.reconnect_buffer.lock().push(AudioBatch {
+                    samples: samples.to_vec(),
+                    sample_rate,
+                    channels,
+                });
+                return;
+            }
+
             // Log periodically to help debug connection issues
             static DROPPED_COUNT: std::sync::atomic::AtomicU64 =
                 std::sync::atomic::AtomicU64::new(0);
@@ -482,7 +705,7 @@ impl TranscriptionProvider for DeepgramProvider {
             let batch = AudioBatch {
                 samples: samples.to_vec(),
                 sample_rate,
-                channels: if channels == 0 { 1 } else { channels }, // Guard against 0 channels
+                channels,
             };
             if tx.try_send(batch).is_err() {
                 log::trace!("Audio queue full, batch dropped");
@@ -494,10 +717,29 @@ impl TranscriptionProvider for DeepgramProvider {
         self.is_connected.load(Ordering::SeqCst)
     }
 
+    fn get_connection_health(&self) -> ConnectionHealth {
+        match self.connection.read().clone() {
+            ConnectionHealth::Reconnecting {
+                attempt,
+                max_attempts,
+                ..
+            } => ConnectionHealth::Reconnecting {
+                attempt,
+                max_attempts,
+                buffered_secs: self.reconnect_buffer.lock().secs,
+            },
+            health => health,
+        }
+    }
+
     fn set_api_key(&self, key: String) {
         *self.api_key.write() = Some(key);
     }
 
+    fn set_glossary(&self, terms: Vec<GlossaryTerm>) {
+        *self.glossary.write() = terms;
+    }
+
     fn set_context(
         &self,
         app_handle: AppHandle,
@@ -511,3 +753,37 @@ impl TranscriptionProvider for DeepgramProvider {
         *self.live_intel_agent.write() = Some(live_intel_agent);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reconnect_delay_backs_off_to_cap() {
+        assert_eq!(reconnect_delay(1), Duration::from_millis(500));
+        assert_eq!(reconnect_delay(3), Duration::from_millis(2_000));
+        assert_eq!(
+            reconnect_delay(20),
+            Duration::from_millis(RECONNECT_MAX_DELAY_MS)
+        );
+    }
+
+    #[test]
+    fn reconnect_buffer_drops_oldest_audio() {
+        let mut buffer = ReconnectBuffer::new();
+        for i in 0..40 {
+            buffer.push(AudioBatch {
+                samples: vec![i as f32; 48_000 * 2],
+                sample_rate: 48_000,
+                channels: 2,
+            });
+        }
+        assert_eq!(buffer.batches.len(), RECONNECT_BUFFER_SECS as usize);
+        assert_eq!(buffer.batches[0].samples[0], 10.0);
+
+        let drained = buffer.drain();
+        assert_eq!(drained.len(), 30);
+        assert!(buffer.batches.is_empty());
+        assert_eq!(buffer.secs, 0.0);
+    }
+}
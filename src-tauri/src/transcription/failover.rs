@@ -0,0 +1,473 @@
+// noFriction Meetings - Transcription Failover
+// Switches to another provider when the active one dies mid-meeting
+//
+// Health is tracked per provider: connection state and when it last returned
+// a final transcript. During a session the active provider is unhealthy while
+// disconnected, or while connected but silent through a long stretch of
+// audible speech. After `after_secs` of that the manager switches to the next
+// provider with a stored key and replays the audio captured since the last
+// final transcript (at most `buffer_secs`) so the hole stays small.
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use super::audio_mixer::SILENCE_RMS;
+use super::ProviderType;
+
+/// Seconds a provider may stay unhealthy before failing over
+pub const DEFAULT_FAILOVER_AFTER_SECS: u32 = 10;
+/// Seconds of recent audio kept for replay into the next provider
+pub const DEFAULT_FAILOVER_BUFFER_SECS: u32 = 30;
+/// Upper bound on the replay buffer (16kHz mono f32 is ~4MB per minute)
+pub const MAX_FAILOVER_BUFFER_SECS: u32 = 120;
+/// Audible audio without a final transcript before a connected provider counts as stalled
+const STALL_VOICED_SECS: f32 = 20.0;
+
+/// Providers in the order failover tries them (same as the start-of-recording fallback)
+pub const FAILOVER_ORDER: [ProviderType; 5] = [
+    ProviderType::Deepgram,
+    ProviderType::Gemini,
+    ProviderType::Gladia,
+    ProviderType::GoogleSTT,
+    ProviderType::WhisperLocal,
+];
+
+/// When and how to fail over
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FailoverPolicy {
+    pub enabled: bool,
+    /// Unhealthy seconds before switching provider
+    pub after_secs: u32,
+    /// Seconds of gap audio replayed into the new provider
+    pub buffer_secs: u32,
+}
+
+impl Default for FailoverPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            after_secs: DEFAULT_FAILOVER_AFTER_SECS,
+            buffer_secs: DEFAULT_FAILOVER_BUFFER_SECS,
+        }
+    }
+}
+
+impl FailoverPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.after_secs == 0 {
+            return Err("Failover delay must be at least 1 second".to_string());
+        }
+        if self.buffer_secs > MAX_FAILOVER_BUFFER_SECS {
+            return Err(format!(
+                "Failover buffer can be at most {} seconds",
+                MAX_FAILOVER_BUFFER_SECS
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Payload for the `transcription-failover` event
+#[derive(Debug, Clone, Serialize)]
+pub struct FailoverEvent {
+    pub meeting_id: String,
+    pub from: ProviderType,
+    pub to: ProviderType,
+    pub reason: String,
+    pub replayed_secs: f32,
+}
+
+/// Next provider after `current` in failover order that `available` accepts
+pub fn next_provider(
+    current: ProviderType,
+    available: impl Fn(ProviderType) -> bool,
+) -> Option<ProviderType> {
+    let start = FAILOVER_ORDER
+        .iter()
+        .position(|p| *p == current)
+        .unwrap_or(0);
+    (1..FAILOVER_ORDER.len())
+        .map(|offset| FAILOVER_ORDER[(start + offset) % FAILOVER_ORDER.len()])
+        .find(|p| *p != current && available(*p))
+}
+
+/// Health of one provider, as returned to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderHealthSnapshot {
+    pub provider: ProviderType,
+    pub active: bool,
+    pub connected: bool,
+    pub last_transcript_at: Option<DateTime<Utc>>,
+    /// How long the active provider has been unhealthy, if it is
+    pub unhealthy_secs: Option<f32>,
+    /// Times the app failed over away from this provider
+    pub failovers: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ProviderStatus {
+    connected: bool,
+    last_transcript: Option<Instant>,
+    last_transcript_at: Option<DateTime<Utc>>,
+    failovers: u32,
+}
+
+#[derive(Default)]
+struct HealthState {
+    active: Option<ProviderType>,
+    providers: HashMap<ProviderType, ProviderStatus>,
+    /// When the active provider's session (re)started
+    session_started: Option<Instant>,
+    unhealthy_since: Option<Instant>,
+    /// Audible seconds sent since the last final transcript
+    voiced_secs: f32,
+}
+
+/// Per-provider connection state and last transcript times
+pub struct ProviderHealth {
+    state: RwLock<HealthState>,
+}
+
+/// Global health tracker shared by the manager and transcript persistence
+pub fn provider_health() -> &'static ProviderHealth {
+    static HEALTH: OnceLock<ProviderHealth> = OnceLock::new();
+    HEALTH.get_or_init(ProviderHealth::new)
+}
+
+impl ProviderHealth {
+    pub fn new() -> Self {
+        Self {
+            state: RwLock::new(HealthState::default()),
+        }
+    }
+
+    /// A session started (or failed over) on `provider`
+    pub fn begin(&self, provider: ProviderType) {
+        self.begin_at(Instant::now(), provider);
+    }
+
+    fn begin_at(&self, now: Instant, provider: ProviderType) {
+        let mut state = self.state.write();
+        state.active = Some(provider);
+        state.session_started = Some(now);
+        state.unhealthy_since = None;
+        state.voiced_secs = 0.0;
+        state.providers.entry(provider).or_default();
+    }
+
+    /// A final transcript came back from the active provider
+    pub fn record_transcript(&self) {
+        self.record_transcript_at(Instant::now());
+    }
+
+    fn record_transcript_at(&self, now: Instant) {
+        let mut state = self.state.write();
+        state.voiced_secs = 0.0;
+        if let Some(active) = state.active {
+            let status = state.providers.entry(active).or_default();
+            status.last_transcript = Some(now);
+            status.last_transcript_at = Some(Utc::now());
+        }
+    }
+
+    /// Audio sent to the active provider; audible audio counts toward a stall
+    pub fn record_audio(&self, samples: &[f32], sample_rate: u32, channels: u16) {
+        if samples.is_empty() || sample_rate == 0 {
+            return;
+        }
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+        if rms >= SILENCE_RMS {
+            self.state.write().voiced_secs += chunk_secs(samples.len(), sample_rate, channels);
+        }
+    }
+
+    /// Where gap audio should be replayed from: the active provider's last
+    /// final transcript, or the start of its session
+    pub fn replay_from(&self) -> Option<Instant> {
+        let state = self.state.read();
+        state
+            .active
+            .and_then(|p| state.providers.get(&p))
+            .and_then(|s| s.last_transcript)
+            .max(state.session_started)
+    }
+
+    /// Record the active provider's connection state. Returns why it should be
+    /// failed over once it has been unhealthy for `after`.
+    pub fn evaluate(&self, connected: bool, after: Duration) -> Option<String> {
+        self.evaluate_at(Instant::now(), connected, after)
+    }
+
+    fn evaluate_at(&self, now: Instant, connected: bool, after: Duration) -> Option<String> {
+        let mut state = self.state.write();
+        let active = state.active?;
+        state.providers.entry(active).or_default().connected = connected;
+
+        let stalled = connected && state.voiced_secs >= STALL_VOICED_SECS;
+        if connected && !stalled {
+            state.unhealthy_since = None;
+            return None;
+        }
+
+        let since = *state.unhealthy_since.get_or_insert(now);
+        if now.duration_since(since) < after {
+            return None;
+        }
+
+        Some(if connected {
+            format!("no transcripts for {:.0}s of speech", state.voiced_secs)
+        } else {
+            format!("disconnected for {}s", now.duration_since(since).as_secs())
+        })
+    }
+
+    /// The app switched from `from` to `to` mid-session
+    pub fn failed_over(&self, from: ProviderType, to: ProviderType) {
+        {
+            let mut state = self.state.write();
+            let status = state.providers.entry(from).or_default();
+            status.connected = false;
+            status.failovers += 1;
+        }
+        self.begin(to);
+    }
+
+    pub fn snapshot(&self) -> Vec<ProviderHealthSnapshot> {
+        let now = Instant::now();
+        let state = self.state.read();
+        let mut snapshots: Vec<ProviderHealthSnapshot> = state
+            .providers
+            .iter()
+            .map(|(provider, status)| {
+                let active = state.active == Some(*provider);
+                ProviderHealthSnapshot {
+                    provider: *provider,
+                    active,
+                    connected: status.connected,
+                    last_transcript_at: status.last_transcript_at,
+                    unhealthy_secs: state
+                        .unhealthy_since
+                        .filter(|_| active)
+                        .map(|since| now.duration_since(since).as_secs_f32()),
+                    failovers: status.failovers,
+                }
+            })
+            .collect();
+        snapshots.sort_by_key(|s| FAILOVER_ORDER.iter().position(|p| *p == s.provider));
+        snapshots
+    }
+}
+
+impl Default for ProviderHealth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn chunk_secs(samples: usize, sample_rate: u32, channels: u16) -> f32 {
+    samples as f32 / channels.max(1) as f32 / sample_rate as f32
+}
+
+/// Audio chunk kept for replay
+#[derive(Debug, Clone)]
+pub struct GapChunk {
+    at: Instant,
+    seq: u64,
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+/// Rolling buffer of the most recent audio sent to the provider. While a
+/// replay is pending, new audio is held here instead of being sent live so
+/// the new provider receives it in order.
+pub struct GapBuffer {
+    chunks: VecDeque<GapChunk>,
+    buffered_secs: f32,
+    max_secs: f32,
+    next_seq: u64,
+    /// First chunk not yet replayed; Some while a replay is in progress
+    replay_cursor: Option<u64>,
+}
+
+impl GapBuffer {
+    pub fn new(max_secs: u32) -> Self {
+        Self {
+            chunks: VecDeque::new(),
+            buffered_secs: 0.0,
+            max_secs: max_secs as f32,
+            next_seq: 0,
+            replay_cursor: None,
+        }
+    }
+
+    pub fn set_max_secs(&mut self, max_secs: u32) {
+        self.max_secs = max_secs as f32;
+        self.trim();
+    }
+
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+        self.buffered_secs = 0.0;
+        self.replay_cursor = None;
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.replay_cursor.is_some()
+    }
+
+    /// Keep a chunk. Returns whether it should also be sent live (false while
+    /// a replay is pending - the replay delivers it).
+    pub fn push(&mut self, samples: &[f32], sample_rate: u32, channels: u16) -> bool {
+        self.push_at(Instant::now(), samples, sample_rate, channels)
+    }
+
+    fn push_at(&mut self, now: Instant, samples: &[f32], sample_rate: u32, channels: u16) -> bool {
+        if self.max_secs > 0.0 && !samples.is_empty() && sample_rate > 0 {
+            self.buffered_secs += chunk_secs(samples.len(), sample_rate, channels);
+            self.chunks.push_back(GapChunk {
+                at: now,
+                seq: self.next_seq,
+                samples: samples.to_vec(),
+                sample_rate,
+                channels,
+            });
+            self.next_seq += 1;
+            self.trim();
+        }
+        self.replay_cursor.is_none()
+    }
+
+    fn trim(&mut self) {
+        while self.buffered_secs > self.max_secs {
+            match self.chunks.pop_front() {
+                Some(chunk) => {
+                    self.buffered_secs -=
+                        chunk_secs(chunk.samples.len(), chunk.sample_rate, chunk.channels);
+                }
+                None => {
+                    self.buffered_secs = 0.0;
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Start replaying buffered audio captured at or after `from` (everything
+    /// buffered if None). Returns the seconds queued for replay.
+    pub fn begin_replay(&mut self, from: Option<Instant>) -> f32 {
+        let pending: Vec<&GapChunk> = self
+            .chunks
+            .iter()
+            .filter(|c| from.map_or(true, |from| c.at >= from))
+            .collect();
+        let secs = pending
+            .iter()
+            .map(|c| chunk_secs(c.samples.len(), c.sample_rate, c.channels))
+            .sum();
+        self.replay_cursor = Some(pending.first().map_or(self.next_seq, |c| c.seq));
+        secs
+    }
+
+    /// Next chunks to replay. Once caught up this returns nothing and ends the
+    /// replay, so later audio goes live again.
+    pub fn take_replay(&mut self) -> Vec<GapChunk> {
+        let Some(cursor) = self.replay_cursor else {
+            return Vec::new();
+        };
+        let pending: Vec<GapChunk> = self
+            .chunks
+            .iter()
+            .filter(|c| c.seq >= cursor)
+            .cloned()
+            .collect();
+        self.replay_cursor = pending.last().map(|c| c.seq + 1);
+        pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_provider_wraps_and_skips_unavailable() {
+        let available = |p: ProviderType| p != ProviderType::Gladia;
+        assert_eq!(
+            next_provider(ProviderType::Gemini, available),
+            Some(ProviderType::GoogleSTT)
+        );
+        assert_eq!(
+            next_provider(ProviderType::WhisperLocal, available),
+            Some(ProviderType::Deepgram)
+        );
+        assert_eq!(next_provider(ProviderType::Deepgram, |_| false), None);
+    }
+
+    #[test]
+    fn unhealthy_after_delay_and_on_stall() {
+        let health = ProviderHealth::new();
+        let start = Instant::now();
+        let after = Duration::from_secs(10);
+        health.begin_at(start, ProviderType::Deepgram);
+
+        assert!(health.evaluate_at(start, true, after).is_none());
+        assert!(health.evaluate_at(start, false, after).is_none());
+        let reason = health.evaluate_at(start + Duration::from_secs(10), false, after);
+        assert!(reason.unwrap().starts_with("disconnected"));
+
+        // Reconnecting resets the clock
+        assert!(health
+            .evaluate_at(start + Duration::from_secs(11), true, after)
+            .is_none());
+
+        // Connected but 20s of speech without a transcript
+        health.record_audio(&vec![0.5; 16_000 * 20], 16_000, 1);
+        let stalled_at = start + Duration::from_secs(12);
+        assert!(health.evaluate_at(stalled_at, true, after).is_none());
+        assert!(health
+            .evaluate_at(stalled_at + after, true, after)
+            .unwrap()
+            .starts_with("no transcripts"));
+
+        health.record_transcript_at(stalled_at + after);
+        assert!(health
+            .evaluate_at(stalled_at + after, true, after)
+            .is_none());
+    }
+
+    #[test]
+    fn gap_buffer_caps_and_replays_in_order() {
+        let mut buffer = GapBuffer::new(2);
+        let start = Instant::now();
+        for i in 0..3u64 {
+            assert!(buffer.push_at(
+                start + Duration::from_secs(i),
+                &vec![i as f32; 16_000],
+                16_000,
+                1
+            ));
+        }
+        // Oldest second dropped to stay within 2s
+        assert_eq!(buffer.chunks.len(), 2);
+
+        let secs = buffer.begin_replay(Some(start + Duration::from_secs(2)));
+        assert!((secs - 1.0).abs() < 1e-6);
+
+        // Live audio is held back while the replay is pending
+        assert!(!buffer.push_at(start + Duration::from_secs(3), &[3.0; 160], 16_000, 1));
+
+        let replayed = buffer.take_replay();
+        assert_eq!(replayed.len(), 2);
+        assert_eq!(replayed[0].samples[0], 2.0);
+        assert_eq!(replayed[1].samples[0], 3.0);
+        assert!(buffer.take_replay().is_empty());
+        assert!(!buffer.is_replaying());
+        assert!(buffer.push_at(start + Duration::from_secs(4), &[4.0; 160], 16_000, 1));
+    }
+}
@@ -11,7 +11,8 @@ use tokio_tungstenite::{connect_async, tungstenite::Message};
 
 use crate::database::DatabaseManager;
 use crate::live_intel_agent::LiveIntelAgent;
-use crate::transcription::TranscriptionProvider;
+use crate::transcription::{glossary, GlossaryTerm, TranscriptionProvider};
+use crate::usage_ledger::AudioUsage;
 
 #[derive(Debug, Serialize)]
 struct GeminiSetupMessage {
@@ -89,6 +90,7 @@ pub struct GeminiProvider {
     database: Arc<RwLock<Option<Arc<DatabaseManager>>>>,
     meeting_id: Arc<RwLock<Option<String>>>,
     live_intel_agent: Arc<RwLock<Option<Arc<RwLock<LiveIntelAgent>>>>>,
+    glossary: Arc<RwLock<Vec<GlossaryTerm>>>,
 }
 
 impl GeminiProvider {
@@ -101,12 +103,14 @@ impl GeminiProvider {
             database: Arc::new(RwLock::new(None)),
             meeting_id: Arc::new(RwLock::new(None)),
             live_intel_agent: Arc::new(RwLock::new(None)),
+            glossary: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
     async fn connect_internal(
         api_key: String,
         model: String,
+        glossary_terms: Vec<GlossaryTerm>,
         app: AppHandle,
         is_connected: Arc<AtomicBool>,
         audio_tx_holder: Arc<RwLock<Option<mpsc::Sender<AudioBatch>>>>,
@@ -127,6 +131,7 @@ impl GeminiProvider {
         log::info!("✅ Connected to Gemini Live API (Model: {})", model);
 
         let (mut write, mut read) = ws_stream.split();
+        let mut usage = AudioUsage::start("gemini", &model, 16000);
 
         // Send setup message
         let setup = GeminiSetupMessage {
@@ -189,8 +194,10 @@ impl GeminiProvider {
                     if let Ok(json) = serde_json::to_string(&input) {
                         if let Err(e) = write.send(Message::Text(json)).await {
                             log::error!("Failed to send audio to Gemini: {}", e);
+                            usage.mark_failed();
                             return;
                         }
+                        usage.add_samples(chunk.len());
                     }
                 }
             }
@@ -217,6 +224,11 @@ impl GeminiProvider {
                                     for part in turn.parts {
                                         if let Some(transcript_text) = part.text {
                                             if !transcript_text.trim().is_empty() {
+                                                // No native vocabulary support - correct jargon after the fact
+                                                let transcript_text = glossary::apply_fuzzy(
+                                                    &transcript_text,
+                                                    &glossary_terms,
+                                                );
                                                 let is_final =
                                                     content.turn_complete.unwrap_or(false);
                                                 let segment = TranscriptSegment {
@@ -235,20 +247,23 @@ impl GeminiProvider {
                                                     log::error!("Failed to emit transcript: {}", e);
                                                 }
 
-                                                // Process with LiveIntelAgent
-                                                if let Some(agent) =
-                                                    intel_agent_recv.read().as_ref()
-                                                {
-                                                    let mut agent = agent.write();
-                                                    let intel_segment =
-                                                        crate::catch_up_agent::TranscriptSegment {
-                                                            id: uuid::Uuid::new_v4().to_string(),
-                                                            timestamp_ms: chrono::Utc::now()
-                                                                .timestamp_millis(),
-                                                            speaker: segment.speaker.clone(),
-                                                            text: segment.text.clone(),
-                                                        };
-                                                    agent.process_segment(intel_segment);
+                                                // Feed finalized segments to LiveIntelAgent
+                                                if is_final {
+                                                    if let Some(agent) =
+                                                        intel_agent_recv.read().as_ref()
+                                                    {
+                                                        crate::transcription::feed_live_intel(
+                                                            &app,
+                                                            agent,
+                                                            database_recv.read().as_ref().cloned(),
+                                                            meeting_id_recv
+                                                                .read()
+                                                                .as_ref()
+                                                                .cloned(),
+                                                            &segment.text,
+                                                            segment.speaker.clone(),
+                                                        );
+                                                    }
                                                 }
 
                                                 // Save FINAL transcripts
@@ -261,17 +276,7 @@ impl GeminiProvider {
                                                         {
                                                             let text_clone =
                                                                 transcript_text.clone();
-                                                            tokio::spawn(async move {
-                                                                let _ = db
-                                                                    .add_transcript(
-                                                                        &mid,
-                                                                        &text_clone,
-                                                                        None,
-                                                                        true,
-                                                                        0.95,
-                                                                    )
-                                                                    .await;
-                                                            });
+                                                            tokio::spawn(crate::transcription::save_final_transcript(db, crate::transcription::FinalSegment { meeting_id: mid, text: text_clone, confidence: 0.95, ..Default::default() }));
                                                         }
                                                     }
                                                 }
@@ -384,6 +389,7 @@ impl TranscriptionProvider for GeminiProvider {
         let database = self.database.clone();
         let meeting_id = self.meeting_id.clone();
         let live_intel_agent = self.live_intel_agent.clone();
+        let glossary_terms = self.glossary.read().clone();
 
         let app_handle_clone = app.clone();
 
@@ -400,6 +406,7 @@ impl TranscriptionProvider for GeminiProvider {
             if let Err(e) = Self::connect_internal(
                 api_key,
                 model,
+                glossary_terms,
                 app,
                 is_connected,
                 audio_tx_holder,
@@ -472,6 +479,10 @@ impl TranscriptionProvider for GeminiProvider {
         *self.api_key.write() = Some(key);
     }
 
+    fn set_glossary(&self, terms: Vec<GlossaryTerm>) {
+        *self.glossary.write() = terms;
+    }
+
     fn set_context(
         &self,
         app_handle: AppHandle,
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
@@ -11,28 +12,98 @@ use tokio_tungstenite::{connect_async, tungstenite::Message};
 
 use crate::database::DatabaseManager;
 use crate::live_intel_agent::LiveIntelAgent;
-use crate::transcription::TranscriptionProvider;
+use crate::transcription::{glossary, FinalSegment, GlossaryTerm, TranscriptionProvider};
+use crate::usage_ledger::AudioUsage;
 
 #[derive(Debug, Serialize)]
 struct GladiaConfig {
     encoding: String,
     sample_rate: u32,
     language_behaviour: String,
+    /// Per-word time_begin/time_end in every transcript message
+    word_timestamps: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    custom_vocabulary: Vec<String>,
 }
 
+/// Live transcript message. Current sessions send a flat message with
+/// `type` "partial" or "final" and offsets in seconds from the start of the
+/// audio stream; older sessions nested the text under `transcription`.
 #[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
 struct GladiaResponse {
-    _event: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
     transcription: Option<GladiaTranscription>,
+    confidence: Option<f32>,
+    time_begin: Option<f64>,
+    time_end: Option<f64>,
+    #[serde(default)]
+    words: Vec<GladiaWord>,
 }
 
 #[derive(Debug, Deserialize)]
-struct GladiaTranscription {
-    full_transcript: Option<String>,
-    is_final: Option<bool>,
-    confidence: Option<f32>,
-    _language: Option<String>,
+#[serde(untagged)]
+enum GladiaTranscription {
+    Text(String),
+    Nested {
+        full_transcript: Option<String>,
+        is_final: Option<bool>,
+        confidence: Option<f32>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct GladiaWord {
+    time_begin: Option<f64>,
+    time_end: Option<f64>,
+}
+
+/// A transcript message with its offsets in the audio stream
+#[derive(Debug, Clone, PartialEq)]
+struct GladiaUtterance {
+    text: String,
+    is_final: bool,
+    confidence: f32,
+    /// Seconds from stream start; None when the message carried no timing
+    start: Option<f64>,
+    end: Option<f64>,
+}
+
+/// Parse one live message. Utterance offsets fall back to the first and
+/// last word offsets when the message only has word timing.
+fn parse_message(text: &str) -> Option<GladiaUtterance> {
+    let response: GladiaResponse = serde_json::from_str(text).ok()?;
+    let (text, nested_final, nested_confidence) = match response.transcription? {
+        GladiaTranscription::Text(text) => (text, None, None),
+        GladiaTranscription::Nested {
+            full_transcript,
+            is_final,
+            confidence,
+        } => (full_transcript?, is_final, confidence),
+    };
+    if text.trim().is_empty() {
+        return None;
+    }
+
+    let is_final = match response.kind.as_deref() {
+        Some("final") => true,
+        Some("partial") => false,
+        _ => nested_final.unwrap_or(false),
+    };
+    let start = response
+        .time_begin
+        .or_else(|| response.words.first().and_then(|w| w.time_begin));
+    let end = response
+        .time_end
+        .or_else(|| response.words.last().and_then(|w| w.time_end));
+
+    Some(GladiaUtterance {
+        text,
+        is_final,
+        confidence: response.confidence.or(nested_confidence).unwrap_or(0.9),
+        start,
+        end,
+    })
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -59,6 +130,7 @@ pub struct GladiaProvider {
     database: Arc<RwLock<Option<Arc<DatabaseManager>>>>,
     meeting_id: Arc<RwLock<Option<String>>>,
     live_intel_agent: Arc<RwLock<Option<Arc<RwLock<LiveIntelAgent>>>>>,
+    glossary: Arc<RwLock<Vec<GlossaryTerm>>>,
 }
 
 impl GladiaProvider {
@@ -71,11 +143,13 @@ impl GladiaProvider {
             database: Arc::new(RwLock::new(None)),
             meeting_id: Arc::new(RwLock::new(None)),
             live_intel_agent: Arc::new(RwLock::new(None)),
+            glossary: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
     async fn connect_internal(
         api_key: String,
+        glossary_terms: Vec<GlossaryTerm>,
         app: AppHandle,
         is_connected: Arc<AtomicBool>,
         audio_tx_holder: Arc<RwLock<Option<mpsc::Sender<AudioBatch>>>>,
@@ -114,6 +188,8 @@ impl GladiaProvider {
             encoding: "WAV/PCM".to_string(),
             sample_rate: 16000,
             language_behaviour: "automatic single language".to_string(),
+            word_timestamps: true,
+            custom_vocabulary: glossary::term_list(&glossary_terms),
         };
         let config_json = serde_json::to_string(&config)
             .map_err(|e| format!("Failed to serialize config: {}", e))?;
@@ -126,8 +202,13 @@ impl GladiaProvider {
         let (audio_tx, mut audio_rx) = mpsc::channel::<AudioBatch>(100);
         *audio_tx_holder.write() = Some(audio_tx);
 
+        // Offsets in transcript messages count from the first audio sent
+        let stream_started_at: Arc<RwLock<Option<DateTime<Utc>>>> = Arc::new(RwLock::new(None));
+
         // Spawn task to process and send audio
         let is_connected_send = is_connected.clone();
+        let stream_started_send = stream_started_at.clone();
+        let mut usage = AudioUsage::start("gladia", "realtime", 16000);
         tokio::spawn(async move {
             let mut buffer: VecDeque<f32> = VecDeque::with_capacity(8000);
             let batch_size = 320usize; // 20ms @ 16kHz
@@ -166,10 +247,13 @@ impl GladiaProvider {
                     });
 
                     if let Ok(json) = serde_json::to_string(&frames) {
+                        stream_started_send.write().get_or_insert_with(Utc::now);
                         if let Err(e) = write.send(Message::Text(json)).await {
                             log::error!("Failed to send audio to Gladia: {}", e);
+                            usage.mark_failed();
                             return;
                         }
+                        usage.add_samples(chunk.len());
                     }
                 }
             }
@@ -190,66 +274,59 @@ impl GladiaProvider {
 
                 match msg {
                     Ok(Message::Text(text)) => {
-                        if let Ok(response) = serde_json::from_str::<GladiaResponse>(&text) {
-                            if let Some(transcription) = response.transcription {
-                                if let Some(transcript_text) = transcription.full_transcript {
-                                    if !transcript_text.trim().is_empty() {
-                                        let is_final = transcription.is_final.unwrap_or(false);
-                                        let segment = TranscriptSegment {
-                                            text: transcript_text.clone(),
-                                            is_final,
-                                            confidence: transcription.confidence.unwrap_or(0.9),
-                                            start: 0.0,
-                                            duration: 0.0,
-                                            speaker: None,
-                                        };
-
-                                        // Emit to frontend
-                                        if let Err(e) = app.emit("live_transcript", &segment) {
-                                            log::error!("Failed to emit transcript: {}", e);
-                                        }
-
-                                        // Process with LiveIntelAgent
-                                        if let Some(agent) = intel_agent_recv.read().as_ref() {
-                                            let mut agent = agent.write();
-                                            let intel_segment =
-                                                crate::catch_up_agent::TranscriptSegment {
-                                                    id: uuid::Uuid::new_v4().to_string(),
-                                                    timestamp_ms: chrono::Utc::now()
-                                                        .timestamp_millis(),
-                                                    speaker: segment.speaker.clone(),
-                                                    text: segment.text.clone(),
-                                                };
-                                            agent.process_segment(intel_segment);
-                                        }
-
-                                        // Save FINAL transcripts
-                                        if is_final {
-                                            if let Some(db) = database_recv.read().as_ref().cloned()
-                                            {
-                                                if let Some(mid) =
-                                                    meeting_id_recv.read().as_ref().cloned()
-                                                {
-                                                    let text_clone = transcript_text.clone();
-                                                    let confidence =
-                                                        transcription.confidence.unwrap_or(0.9);
-                                                    tokio::spawn(async move {
-                                                        let _ = db
-                                                            .add_transcript(
-                                                                &mid,
-                                                                &text_clone,
-                                                                None,
-                                                                true,
-                                                                confidence,
-                                                            )
-                                                            .await;
-                                                    });
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
+                        let Some(utterance) = parse_message(&text) else {
+                            continue;
+                        };
+                        let segment = TranscriptSegment {
+                            text: utterance.text.clone(),
+                            is_final: utterance.is_final,
+                            confidence: utterance.confidence,
+                            start: utterance.start.unwrap_or(0.0),
+                            duration: match (utterance.start, utterance.end) {
+                                (Some(start), Some(end)) => (end - start).max(0.0),
+                                _ => 0.0,
+                            },
+                            speaker: None,
+                        };
+
+                        // Emit to frontend
+                        if let Err(e) = app.emit("live_transcript", &segment) {
+                            log::error!("Failed to emit transcript: {}", e);
+                        }
+
+                        if !segment.is_final {
+                            continue;
+                        }
+
+                        // Feed finalized segments to LiveIntelAgent
+                        if let Some(agent) = intel_agent_recv.read().as_ref() {
+                            crate::transcription::feed_live_intel(
+                                &app,
+                                agent,
+                                database_recv.read().as_ref().cloned(),
+                                meeting_id_recv.read().as_ref().cloned(),
+                                &segment.text,
+                                segment.speaker.clone(),
+                            );
+                        }
+
+                        // Save FINAL transcripts at the time they were spoken
+                        let spoken_at = (*stream_started_at.read()).zip(utterance.start).map(
+                            |(started, offset)| crate::transcription::stream_time(started, offset),
+                        );
+                        let database = database_recv.read().as_ref().cloned();
+                        let meeting_id = meeting_id_recv.read().as_ref().cloned();
+                        if let (Some(db), Some(mid)) = (database, meeting_id) {
+                            tokio::spawn(crate::transcription::save_final_transcript(
+                                db,
+                                FinalSegment {
+                                    meeting_id: mid,
+                                    text: utterance.text,
+                                    confidence: utterance.confidence,
+                                    spoken_at,
+                                    ..Default::default()
+                                },
+                            ));
                         }
                     }
                     Ok(Message::Close(_)) => break,
@@ -354,10 +431,12 @@ impl TranscriptionProvider for GladiaProvider {
         let database = self.database.clone();
         let meeting_id = self.meeting_id.clone();
         let live_intel_agent = self.live_intel_agent.clone();
+        let glossary_terms = self.glossary.read().clone();
 
         tokio::spawn(async move {
             if let Err(e) = Self::connect_internal(
                 api_key,
+                glossary_terms,
                 app,
                 is_connected,
                 audio_tx_holder,
@@ -401,6 +480,10 @@ impl TranscriptionProvider for GladiaProvider {
         *self.api_key.write() = Some(key);
     }
 
+    fn set_glossary(&self, terms: Vec<GlossaryTerm>) {
+        *self.glossary.write() = terms;
+    }
+
     fn set_context(
         &self,
         app_handle: AppHandle,
@@ -414,3 +497,64 @@ impl TranscriptionProvider for GladiaProvider {
         *self.live_intel_agent.write() = Some(live_intel_agent);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIVE_FIXTURE: &str = include_str!("fixtures/gladia_live.jsonl");
+
+    fn fixture_messages() -> Vec<Option<GladiaUtterance>> {
+        LIVE_FIXTURE.lines().map(parse_message).collect()
+    }
+
+    #[test]
+    fn parses_partials_and_finals_with_offsets() {
+        let messages = fixture_messages();
+
+        // Handshake acknowledgement carries no transcript
+        assert_eq!(messages[0], None);
+
+        let partial = messages[1].as_ref().unwrap();
+        assert!(!partial.is_final);
+        assert_eq!(partial.text, "So the roadmap");
+        assert_eq!(partial.start, Some(1.24));
+
+        let fin = messages[2].as_ref().unwrap();
+        assert!(fin.is_final);
+        assert_eq!(fin.text, "So the roadmap for Q3 is locked.");
+        assert_eq!(fin.start, Some(1.24));
+        assert_eq!(fin.end, Some(3.9));
+        assert!((fin.confidence - 0.94).abs() < 1e-6);
+    }
+
+    #[test]
+    fn falls_back_to_word_offsets_and_legacy_shape() {
+        let messages = fixture_messages();
+
+        // No utterance timing, only words
+        let words_only = messages[3].as_ref().unwrap();
+        assert!(words_only.is_final);
+        assert_eq!(words_only.start, Some(12.5));
+        assert_eq!(words_only.end, Some(13.38));
+
+        // Older nested shape: no timing at all
+        let legacy = messages[4].as_ref().unwrap();
+        assert!(legacy.is_final);
+        assert_eq!(legacy.text, "Thanks everyone.");
+        assert_eq!(legacy.start, None);
+
+        // Empty partials are skipped
+        assert_eq!(messages[5], None);
+    }
+
+    #[test]
+    fn final_offsets_map_to_stream_time() {
+        let started = DateTime::parse_from_rfc3339("2024-05-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let fin = fixture_messages()[2].clone().unwrap();
+        let spoken_at = crate::transcription::stream_time(started, fin.start.unwrap());
+        assert_eq!(spoken_at.to_rfc3339(), "2024-05-01T10:00:01.240+00:00");
+    }
+}
@@ -0,0 +1,210 @@
+// noFriction Meetings - Transcription Glossary
+// Domain terms (product names, clients, jargon) that providers tend to mishear
+//
+// Each provider consumes the glossary in its own way: Deepgram via keyword
+// query params, Gladia via custom vocabulary, Google STT via speech contexts.
+// Providers without native biasing (Gemini) run `apply_fuzzy` over the text.
+
+use serde::{Deserialize, Serialize};
+
+/// Minimum similarity (0-1) for a fuzzy replacement
+const FUZZY_THRESHOLD: f32 = 0.85;
+/// Terms shorter than this are only corrected on exact (case-insensitive) matches
+const MIN_FUZZY_LEN: usize = 5;
+
+/// One glossary entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlossaryTerm {
+    pub term: String,
+    /// Provider-specific boost; None uses the provider default
+    #[serde(default)]
+    pub boost: Option<f32>,
+}
+
+/// Trim, drop empty and duplicate (case-insensitive) terms
+pub fn normalize_terms(terms: Vec<GlossaryTerm>) -> Vec<GlossaryTerm> {
+    let mut seen = std::collections::HashSet::new();
+    terms
+        .into_iter()
+        .filter_map(|t| {
+            let term = t.term.split_whitespace().collect::<Vec<_>>().join(" ");
+            if term.is_empty() || !seen.insert(term.to_lowercase()) {
+                return None;
+            }
+            Some(GlossaryTerm {
+                term,
+                boost: t.boost,
+            })
+        })
+        .collect()
+}
+
+/// Deepgram query string fragment. Nova-3 only accepts `keyterm` (no boosts);
+/// older models take `keywords=term:boost`.
+pub fn deepgram_query_params(terms: &[GlossaryTerm], model: &str) -> String {
+    let nova3 = model.starts_with("nova-3");
+    terms
+        .iter()
+        .map(|t| {
+            let term = urlencoding::encode(&t.term);
+            match (nova3, t.boost) {
+                (true, _) => format!("&keyterm={}", term),
+                (false, Some(boost)) => format!("&keywords={}:{}", term, boost),
+                (false, None) => format!("&keywords={}", term),
+            }
+        })
+        .collect()
+}
+
+/// Plain term list (Gladia custom vocabulary, Whisper prompt)
+pub fn term_list(terms: &[GlossaryTerm]) -> Vec<String> {
+    terms.iter().map(|t| t.term.clone()).collect()
+}
+
+fn strip_punctuation(token: &str) -> (&str, &str, &str) {
+    let start = token
+        .find(|c: char| c.is_alphanumeric())
+        .unwrap_or(token.len());
+    let end = token
+        .rfind(|c: char| c.is_alphanumeric())
+        .map(|i| i + token[i..].chars().next().map_or(1, |c| c.len_utf8()))
+        .unwrap_or(start);
+    (&token[..start], &token[start..end], &token[end..])
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Normalized edit similarity (1.0 = identical)
+pub(crate) fn similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f32 / max_len as f32
+}
+
+/// Does `window` (tokens, punctuation already stripped) sound like `term`?
+fn matches_term(window: &[&str], term: &str) -> bool {
+    let candidate: String = window.join("").to_lowercase();
+    let target: String = term
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase();
+
+    if candidate == target {
+        return true;
+    }
+    target.chars().count() >= MIN_FUZZY_LEN && similarity(&candidate, &target) >= FUZZY_THRESHOLD
+}
+
+/// Post-processing pass for providers with no native vocabulary support.
+/// Rewrites near-misses ("cube flow", "kubeflo") to the canonical term.
+pub fn apply_fuzzy(text: &str, terms: &[GlossaryTerm]) -> String {
+    if terms.is_empty() {
+        return text.to_string();
+    }
+
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let stripped: Vec<(&str, &str, &str)> = tokens.iter().map(|t| strip_punctuation(t)).collect();
+    let mut out: Vec<String> = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+
+    'tokens: while i < tokens.len() {
+        for t in terms {
+            let n = t.term.split_whitespace().count().max(1);
+            // Exact word count first, then one extra token for split words ("cube flow")
+            for size in [n, n + 1] {
+                if i + size > tokens.len() {
+                    continue;
+                }
+                let window: Vec<&str> = stripped[i..i + size].iter().map(|s| s.1).collect();
+                if window.iter().any(|w| w.is_empty()) || !matches_term(&window, &t.term) {
+                    continue;
+                }
+                out.push(format!(
+                    "{}{}{}",
+                    stripped[i].0,
+                    t.term,
+                    stripped[i + size - 1].2
+                ));
+                i += size;
+                continue 'tokens;
+            }
+        }
+        out.push(tokens[i].to_string());
+        i += 1;
+    }
+
+    out.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(list: &[&str]) -> Vec<GlossaryTerm> {
+        list.iter()
+            .map(|t| GlossaryTerm {
+                term: t.to_string(),
+                boost: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fuzzy_replaces_near_misses() {
+        let glossary = terms(&["Kubeflow", "Acme Corp"]);
+        assert_eq!(
+            apply_fuzzy("we deployed cube flow, then kubeflo.", &glossary),
+            "we deployed Kubeflow, then Kubeflow."
+        );
+        assert_eq!(
+            apply_fuzzy("call acme corp today", &glossary),
+            "call Acme Corp today"
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_leaves_unrelated_words() {
+        let glossary = terms(&["Kubeflow", "Jira"]);
+        let text = "the cube is on the floor, just the jar";
+        assert_eq!(apply_fuzzy(text, &glossary), text);
+    }
+
+    #[test]
+    fn test_deepgram_params_by_model() {
+        let glossary = vec![GlossaryTerm {
+            term: "Acme Corp".to_string(),
+            boost: Some(2.0),
+        }];
+        assert_eq!(
+            deepgram_query_params(&glossary, "nova-3"),
+            "&keyterm=Acme%20Corp"
+        );
+        assert_eq!(
+            deepgram_query_params(&glossary, "nova-2"),
+            "&keywords=Acme%20Corp:2"
+        );
+    }
+
+    #[test]
+    fn test_normalize_dedupes() {
+        let glossary = normalize_terms(terms(&["  Kubeflow ", "kubeflow", ""]));
+        assert_eq!(glossary, terms(&["Kubeflow"]));
+    }
+}
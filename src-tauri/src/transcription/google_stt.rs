@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use jsonwebtoken::{Algorithm, EncodingKey, Header};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
@@ -10,7 +11,8 @@ use tokio::sync::mpsc;
 
 use crate::database::DatabaseManager;
 use crate::live_intel_agent::LiveIntelAgent;
-use crate::transcription::TranscriptionProvider;
+use crate::transcription::{FinalSegment, GlossaryTerm, TranscriptionProvider};
+use crate::usage_ledger::UsageCall;
 
 #[derive(Debug, Serialize)]
 struct GoogleSTTRequest {
@@ -25,7 +27,17 @@ struct GoogleSTTConfig {
     sample_rate_hertz: u32,
     language_code: String,
     enable_automatic_punctuation: bool,
+    enable_word_time_offsets: bool,
     model: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    speech_contexts: Vec<GoogleSpeechContext>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GoogleSpeechContext {
+    phrases: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    boost: Option<f32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -43,12 +55,84 @@ struct GoogleSTTResponse {
 struct GoogleSTTResult {
     alternatives: Vec<GoogleSTTAlternative>,
     is_final: Option<bool>,
+    /// Offset of the end of this result within the request audio ("1.500s")
+    result_end_time: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct GoogleSTTAlternative {
     transcript: String,
     confidence: Option<f32>,
+    #[serde(default)]
+    words: Vec<GoogleSTTWord>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoogleSTTWord {
+    start_time: Option<String>,
+    end_time: Option<String>,
+}
+
+/// A recognized result with offsets (seconds) from the start of the stream
+#[derive(Debug, Clone, PartialEq)]
+struct TimedResult {
+    text: String,
+    is_final: bool,
+    confidence: f32,
+    start: f64,
+    end: f64,
+}
+
+/// Parse a protobuf Duration as JSON ("1.500s", "2s")
+fn parse_offset(value: &str) -> Option<f64> {
+    value.strip_suffix('s')?.parse().ok()
+}
+
+/// Results of one recognize call, shifted by where its audio chunk starts in
+/// the stream. Word offsets are used when present; otherwise a result spans
+/// from the previous result's end to its own `resultEndTime`.
+fn timed_results(response: GoogleSTTResponse, chunk_offset: f64) -> Vec<TimedResult> {
+    let mut previous_end = 0.0;
+    let mut timed = Vec::new();
+
+    for result in response.results.unwrap_or_default() {
+        let result_end = result.result_end_time.as_deref().and_then(parse_offset);
+        let Some(alt) = result.alternatives.into_iter().next() else {
+            continue;
+        };
+
+        let start = alt
+            .words
+            .first()
+            .and_then(|w| w.start_time.as_deref())
+            .and_then(parse_offset)
+            .unwrap_or(previous_end);
+        let end = alt
+            .words
+            .last()
+            .and_then(|w| w.end_time.as_deref())
+            .and_then(parse_offset)
+            .or(result_end)
+            .unwrap_or(start)
+            .max(start);
+        previous_end = result_end.unwrap_or(end);
+
+        let text = alt.transcript.trim().to_string();
+        if text.is_empty() {
+            continue;
+        }
+        timed.push(TimedResult {
+            text,
+            // recognize has no partial results; anything returned is final
+            is_final: result.is_final.unwrap_or(true),
+            confidence: alt.confidence.unwrap_or(0.9),
+            start: chunk_offset + start,
+            end: chunk_offset + end,
+        });
+    }
+
+    timed
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -76,6 +160,7 @@ pub struct GoogleSTTProvider {
     database: Arc<RwLock<Option<Arc<DatabaseManager>>>>,
     meeting_id: Arc<RwLock<Option<String>>>,
     live_intel_agent: Arc<RwLock<Option<Arc<RwLock<LiveIntelAgent>>>>>,
+    glossary: Arc<RwLock<Vec<GlossaryTerm>>>,
 }
 
 impl GoogleSTTProvider {
@@ -89,10 +174,26 @@ impl GoogleSTTProvider {
             database: Arc::new(RwLock::new(None)),
             meeting_id: Arc::new(RwLock::new(None)),
             live_intel_agent: Arc::new(RwLock::new(None)),
+            glossary: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
-    async fn get_access_token(service_account_json: &str) -> Result<String, String> {
+    /// One speech context per distinct boost value (Google boosts per context, not per phrase)
+    fn speech_contexts(terms: &[GlossaryTerm]) -> Vec<GoogleSpeechContext> {
+        let mut contexts: Vec<GoogleSpeechContext> = Vec::new();
+        for t in terms {
+            match contexts.iter_mut().find(|c| c.boost == t.boost) {
+                Some(ctx) => ctx.phrases.push(t.term.clone()),
+                None => contexts.push(GoogleSpeechContext {
+                    phrases: vec![t.term.clone()],
+                    boost: t.boost,
+                }),
+            }
+        }
+        contexts
+    }
+
+    pub(crate) async fn get_access_token(service_account_json: &str) -> Result<String, String> {
         // Parse service account JSON
         let sa: serde_json::Value = serde_json::from_str(service_account_json)
             .map_err(|e| format!("Invalid service account JSON: {}", e))?;
@@ -165,6 +266,7 @@ impl GoogleSTTProvider {
 
     async fn process_audio_internal(
         access_token: String,
+        glossary_terms: Vec<GlossaryTerm>,
         app: AppHandle,
         is_connected: Arc<AtomicBool>,
         audio_tx_holder: Arc<RwLock<Option<mpsc::Sender<AudioBatch>>>>,
@@ -179,12 +281,18 @@ impl GoogleSTTProvider {
         *audio_tx_holder.write() = Some(audio_tx);
 
         let client = reqwest::Client::new();
+        let speech_contexts = Self::speech_contexts(&glossary_terms);
 
         // Process audio in batches
         let intel_agent_recv = live_intel_agent.clone();
         tokio::spawn(async move {
             let mut buffer: VecDeque<f32> = VecDeque::with_capacity(16000); // 1 second buffer
 
+            // Result offsets are per request; chunks are placed on the stream
+            // by how much audio was sent before them
+            let mut stream_started_at: Option<DateTime<Utc>> = None;
+            let mut samples_sent: usize = 0;
+
             loop {
                 let result =
                     tokio::time::timeout(std::time::Duration::from_millis(100), audio_rx.recv())
@@ -210,6 +318,10 @@ impl GoogleSTTProvider {
                     }
 
                     let chunk: Vec<f32> = buffer.drain(..16000).collect();
+                    let audio_seconds = chunk.len() as f64 / 16000.0;
+                    let started_at = *stream_started_at.get_or_insert_with(Utc::now);
+                    let chunk_offset = samples_sent as f64 / 16000.0;
+                    samples_sent += chunk.len();
                     let bytes = Self::f32_to_i16_bytes(&chunk);
                     let base64_data =
                         base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes);
@@ -220,7 +332,9 @@ impl GoogleSTTProvider {
                             sample_rate_hertz: 16000,
                             language_code: "en-US".to_string(),
                             enable_automatic_punctuation: true,
+                            enable_word_time_offsets: true,
                             model: "latest_long".to_string(),
+                            speech_contexts: speech_contexts.clone(),
                         },
                         audio: GoogleSTTAudio {
                             content: base64_data,
@@ -238,81 +352,72 @@ impl GoogleSTTProvider {
                     // Spawn task to send request and handle response without blocking audio loop
                     tokio::spawn(async move {
                         // Send to Google Cloud STT API
+                        let usage = UsageCall::start("google_stt", "transcription", "latest_long");
                         let response = client
                             .post("https://speech.googleapis.com/v1/speech:recognize")
                             .bearer_auth(&access_token)
                             .json(&request)
                             .send()
                             .await;
+                        let ok = matches!(&response, Ok(resp) if resp.status().is_success());
+                        usage.finish_audio(audio_seconds, ok);
+
+                        let stt_response = match response {
+                            Ok(resp) => match resp.json::<GoogleSTTResponse>().await {
+                                Ok(parsed) => parsed,
+                                Err(_) => return,
+                            },
+                            Err(_) => return,
+                        };
+
+                        for result in timed_results(stt_response, chunk_offset) {
+                            let segment = TranscriptSegment {
+                                text: result.text.clone(),
+                                is_final: result.is_final,
+                                confidence: result.confidence,
+                                start: result.start,
+                                duration: result.end - result.start,
+                                speaker: None,
+                            };
+
+                            // Emit to frontend
+                            if let Err(e) = app.emit("live_transcript", &segment) {
+                                log::error!("Failed to emit transcript: {}", e);
+                            }
+
+                            if !segment.is_final {
+                                continue;
+                            }
+
+                            // Feed finalized segments to LiveIntelAgent
+                            if let Some(agent) = intel_agent_recv.read().as_ref() {
+                                crate::transcription::feed_live_intel(
+                                    &app,
+                                    agent,
+                                    database.read().as_ref().cloned(),
+                                    meeting_id.read().as_ref().cloned(),
+                                    &segment.text,
+                                    segment.speaker.clone(),
+                                );
+                            }
 
-                        if let Ok(resp) = response {
-                            if let Ok(stt_response) = resp.json::<GoogleSTTResponse>().await {
-                                if let Some(results) = stt_response.results {
-                                    for result in results {
-                                        if let Some(alt) = result.alternatives.first() {
-                                            if !alt.transcript.trim().is_empty() {
-                                                let is_final = result.is_final.unwrap_or(true);
-                                                let segment = TranscriptSegment {
-                                                    text: alt.transcript.clone(),
-                                                    is_final,
-                                                    confidence: alt.confidence.unwrap_or(0.9),
-                                                    start: 0.0,
-                                                    duration: 0.0,
-                                                    speaker: None,
-                                                };
-
-                                                // Emit to frontend
-                                                if let Err(e) =
-                                                    app.emit("live_transcript", &segment)
-                                                {
-                                                    log::error!("Failed to emit transcript: {}", e);
-                                                }
-
-                                                // Process with LiveIntelAgent
-                                                if let Some(agent) =
-                                                    intel_agent_recv.read().as_ref()
-                                                {
-                                                    let mut agent = agent.write();
-                                                    let intel_segment =
-                                                        crate::catch_up_agent::TranscriptSegment {
-                                                            id: uuid::Uuid::new_v4().to_string(),
-                                                            timestamp_ms: chrono::Utc::now()
-                                                                .timestamp_millis(),
-                                                            speaker: segment.speaker.clone(),
-                                                            text: segment.text.clone(),
-                                                        };
-                                                    agent.process_segment(intel_segment);
-                                                }
-
-                                                // Save transcripts
-                                                if is_final {
-                                                    if let Some(db) =
-                                                        database.read().as_ref().cloned()
-                                                    {
-                                                        if let Some(mid) =
-                                                            meeting_id.read().as_ref().cloned()
-                                                        {
-                                                            let text_clone = alt.transcript.clone();
-                                                            let confidence =
-                                                                alt.confidence.unwrap_or(0.9);
-                                                            tokio::spawn(async move {
-                                                                let _ = db
-                                                                    .add_transcript(
-                                                                        &mid,
-                                                                        &text_clone,
-                                                                        None,
-                                                                        true,
-                                                                        confidence,
-                                                                    )
-                                                                    .await;
-                                                            });
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
+                            // Save at the time the words were spoken
+                            let db = database.read().as_ref().cloned();
+                            let mid = meeting_id.read().as_ref().cloned();
+                            if let (Some(db), Some(mid)) = (db, mid) {
+                                tokio::spawn(crate::transcription::save_final_transcript(
+                                    db,
+                                    FinalSegment {
+                                        meeting_id: mid,
+                                        text: result.text,
+                                        confidence: result.confidence,
+                                        spoken_at: Some(crate::transcription::stream_time(
+                                            started_at,
+                                            result.start,
+                                        )),
+                                        ..Default::default()
+                                    },
+                                ));
                             }
                         }
                     });
@@ -415,6 +520,7 @@ impl TranscriptionProvider for GoogleSTTProvider {
         let meeting_id = self.meeting_id.clone();
         let live_intel_agent = self.live_intel_agent.clone();
         let access_token_holder = self.access_token.clone();
+        let glossary_terms = self.glossary.read().clone();
 
         tokio::spawn(async move {
             // Get access token
@@ -423,6 +529,7 @@ impl TranscriptionProvider for GoogleSTTProvider {
                     *access_token_holder.write() = Some(token.clone());
                     if let Err(e) = Self::process_audio_internal(
                         token,
+                        glossary_terms,
                         app,
                         is_connected,
                         audio_tx_holder,
@@ -471,6 +578,10 @@ impl TranscriptionProvider for GoogleSTTProvider {
         *self.service_account_key.write() = Some(key);
     }
 
+    fn set_glossary(&self, terms: Vec<GlossaryTerm>) {
+        *self.glossary.write() = terms;
+    }
+
     fn set_context(
         &self,
         app_handle: AppHandle,
@@ -484,3 +595,39 @@ impl TranscriptionProvider for GoogleSTTProvider {
         *self.live_intel_agent.write() = Some(live_intel_agent);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECOGNIZE_FIXTURE: &str = include_str!("fixtures/google_stt_recognize.json");
+
+    #[test]
+    fn parses_word_offsets_shifted_by_chunk() {
+        let response: GoogleSTTResponse = serde_json::from_str(RECOGNIZE_FIXTURE).unwrap();
+        let results = timed_results(response, 42.0);
+        assert_eq!(results.len(), 2);
+
+        let first = &results[0];
+        assert_eq!(first.text, "let's ship it");
+        assert!(first.is_final);
+        assert!((first.start - 42.1).abs() < 1e-9);
+        assert!((first.end - 42.9).abs() < 1e-9);
+
+        // No word timing: spans from the previous result's end to resultEndTime
+        let second = &results[1];
+        assert_eq!(second.text, "on Friday");
+        assert!((second.start - 42.95).abs() < 1e-9);
+        assert!((second.end - 43.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parses_duration_offsets() {
+        assert_eq!(parse_offset("1.500s"), Some(1.5));
+        assert_eq!(parse_offset("2s"), Some(2.0));
+        assert_eq!(parse_offset("2"), None);
+
+        let empty: GoogleSTTResponse = serde_json::from_str("{}").unwrap();
+        assert!(timed_results(empty, 0.0).is_empty());
+    }
+}
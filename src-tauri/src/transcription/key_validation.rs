@@ -0,0 +1,289 @@
+// Transcription provider key validation
+//
+// One minimal authenticated request per provider tells a good key from a
+// mistyped, revoked or wrong-product one before a meeting depends on it.
+// The manager keeps the latest result per provider so the recording fallback
+// can prefer providers whose keys are known to work.
+
+use super::ProviderType;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Per-request timeout for validation calls
+const VALIDATION_TIMEOUT_SECS: u64 = 10;
+/// A successful validation counts as "recent" for this long
+pub const RECENT_VALIDATION_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyValidationStatus {
+    Ok,
+    /// The provider rejected the key
+    Invalid,
+    /// The provider could not be reached; the key is unverified
+    NetworkError,
+}
+
+/// Result of validating one provider key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyValidation {
+    pub provider: ProviderType,
+    pub status: KeyValidationStatus,
+    /// Provider error message or a hint on what is wrong with the key
+    pub message: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl KeyValidation {
+    fn new(provider: ProviderType, status: KeyValidationStatus, message: Option<String>) -> Self {
+        Self {
+            provider,
+            status,
+            message,
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// Validated successfully within RECENT_VALIDATION_HOURS of `now`
+    pub fn is_recent_ok(&self, now: DateTime<Utc>) -> bool {
+        self.status == KeyValidationStatus::Ok
+            && now - self.checked_at < Duration::hours(RECENT_VALIDATION_HOURS)
+    }
+}
+
+/// Strip what copy/paste tends to add around a key: whitespace, newlines and
+/// surrounding quotes
+pub fn normalize_key(key: &str) -> String {
+    key.trim()
+        .trim_matches(|c| c == '"' || c == '\'')
+        .trim()
+        .to_string()
+}
+
+/// Format problems that need no network round trip, including keys that
+/// clearly belong to another provider
+fn precheck(provider: ProviderType, key: &str) -> Option<String> {
+    if key.is_empty() {
+        return Some("Key is empty".to_string());
+    }
+    let looks_like_google_key = key.starts_with("AIza");
+    let looks_like_json = key.starts_with('{');
+    match provider {
+        ProviderType::GoogleSTT => {
+            let json: serde_json::Value =
+                match serde_json::from_str(key) {
+                    Ok(json) => json,
+                    Err(_) if looks_like_google_key => return Some(
+                        "This is a Google API key; Google STT needs a service account JSON file"
+                            .to_string(),
+                    ),
+                    Err(e) => return Some(format!("Not a service account JSON file: {}", e)),
+                };
+            if json["type"].as_str() != Some("service_account") {
+                return Some(
+                    "JSON is not a service account key (\"type\" must be \"service_account\")"
+                        .to_string(),
+                );
+            }
+            None
+        }
+        _ if looks_like_json => Some(format!(
+            "This looks like a service account JSON file; {} needs an API key",
+            provider.as_str()
+        )),
+        _ if key.chars().any(char::is_whitespace) => {
+            Some("Key contains spaces or line breaks".to_string())
+        }
+        ProviderType::Deepgram | ProviderType::Gladia if looks_like_google_key => Some(format!(
+            "This looks like a Google/Gemini key, not a {} key",
+            provider.as_str()
+        )),
+        _ => None,
+    }
+}
+
+/// Best-effort error message from a provider's JSON error body
+fn provider_message(body: &str) -> String {
+    let json: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+    [
+        &json["err_msg"],
+        &json["error"]["message"],
+        &json["message"],
+        &json["error_description"],
+        &json["error"],
+    ]
+    .into_iter()
+    .find_map(|v| v.as_str().map(str::to_string))
+    .unwrap_or_else(|| body.chars().take(200).collect())
+}
+
+/// Map an HTTP response to a validation status
+fn classify_http(status: u16, body: &str) -> (KeyValidationStatus, Option<String>) {
+    match status {
+        200..=299 => (KeyValidationStatus::Ok, None),
+        400 | 401 | 403 => (
+            KeyValidationStatus::Invalid,
+            Some(format!(
+                "Key rejected (HTTP {}): {}",
+                status,
+                provider_message(body)
+            )),
+        ),
+        _ => (
+            KeyValidationStatus::NetworkError,
+            Some(format!("HTTP {}: {}", status, provider_message(body))),
+        ),
+    }
+}
+
+/// Validate `key` for `provider` with one authenticated request
+pub async fn validate_provider_key(provider: ProviderType, key: &str) -> KeyValidation {
+    if !provider.requires_api_key() {
+        return KeyValidation::new(
+            provider,
+            KeyValidationStatus::Ok,
+            Some("No key required".to_string()),
+        );
+    }
+    let key = normalize_key(key);
+    if let Some(problem) = precheck(provider, &key) {
+        return KeyValidation::new(provider, KeyValidationStatus::Invalid, Some(problem));
+    }
+
+    // Google STT: minting an OAuth token proves the service account works
+    if provider == ProviderType::GoogleSTT {
+        return match super::google_stt::GoogleSTTProvider::get_access_token(&key).await {
+            Ok(_) => KeyValidation::new(provider, KeyValidationStatus::Ok, None),
+            Err(e) if e.starts_with("Failed to get access token") => {
+                KeyValidation::new(provider, KeyValidationStatus::NetworkError, Some(e))
+            }
+            Err(e) => KeyValidation::new(provider, KeyValidationStatus::Invalid, Some(e)),
+        };
+    }
+
+    let client = match reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(VALIDATION_TIMEOUT_SECS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            return KeyValidation::new(
+                provider,
+                KeyValidationStatus::NetworkError,
+                Some(e.to_string()),
+            )
+        }
+    };
+    let request = match provider {
+        ProviderType::Deepgram => client
+            .get("https://api.deepgram.com/v1/projects")
+            .header("Authorization", format!("Token {}", key)),
+        ProviderType::Gemini => client
+            .get("https://generativelanguage.googleapis.com/v1beta/models")
+            .query(&[("pageSize", "1")])
+            .header("x-goog-api-key", &key),
+        ProviderType::Gladia => client
+            .get("https://api.gladia.io/v2/transcription")
+            .query(&[("limit", "1")])
+            .header("x-gladia-key", &key),
+        ProviderType::GoogleSTT | ProviderType::WhisperLocal => unreachable!(),
+    };
+
+    match request.send().await {
+        Ok(resp) => {
+            let status = resp.status().as_u16();
+            let body = resp.text().await.unwrap_or_default();
+            let (status, message) = classify_http(status, &body);
+            KeyValidation::new(provider, status, message)
+        }
+        Err(e) => KeyValidation::new(
+            provider,
+            KeyValidationStatus::NetworkError,
+            Some(format!("Could not reach {}: {}", provider.as_str(), e)),
+        ),
+    }
+}
+
+/// Order fallback candidates: recently validated keys first, then keys never
+/// (or not recently) checked, then local providers; keys the provider
+/// rejected are dropped
+pub fn rank_fallbacks(
+    candidates: &[ProviderType],
+    validations: &HashMap<ProviderType, KeyValidation>,
+    now: DateTime<Utc>,
+) -> Vec<ProviderType> {
+    let mut ranked: Vec<(u8, ProviderType)> = candidates
+        .iter()
+        .filter_map(|provider| match validations.get(provider) {
+            _ if !provider.requires_api_key() => Some((2, *provider)),
+            Some(v) if v.status == KeyValidationStatus::Invalid => None,
+            Some(v) if v.is_recent_ok(now) => Some((0, *provider)),
+            _ => Some((1, *provider)),
+        })
+        .collect();
+    // Stable sort keeps the caller's preference order within each rank
+    ranked.sort_by_key(|(rank, _)| *rank);
+    ranked.into_iter().map(|(_, provider)| provider).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validation(
+        provider: ProviderType,
+        status: KeyValidationStatus,
+        age_hours: i64,
+    ) -> KeyValidation {
+        KeyValidation {
+            provider,
+            status,
+            message: None,
+            checked_at: Utc::now() - Duration::hours(age_hours),
+        }
+    }
+
+    #[test]
+    fn test_precheck_catches_paste_and_product_mistakes() {
+        assert_eq!(normalize_key("  \"abc123\"\n"), "abc123");
+        assert!(precheck(ProviderType::Deepgram, "AIzaSyD-example").is_some());
+        assert!(precheck(ProviderType::Gemini, "{\"type\": \"service_account\"}").is_some());
+        assert!(precheck(ProviderType::GoogleSTT, "AIzaSyD-example").is_some());
+        assert!(precheck(ProviderType::GoogleSTT, "{\"type\": \"service_account\"}").is_none());
+        assert!(precheck(ProviderType::Deepgram, "0123456789abcdef").is_none());
+
+        let (status, message) = classify_http(401, r#"{"err_msg":"Invalid credentials."}"#);
+        assert_eq!(status, KeyValidationStatus::Invalid);
+        assert!(message.unwrap().contains("Invalid credentials."));
+        assert_eq!(classify_http(503, "").0, KeyValidationStatus::NetworkError);
+    }
+
+    #[test]
+    fn test_fallback_prefers_recently_validated_keys() {
+        use ProviderType::*;
+        let validations = HashMap::from([
+            (
+                WhisperLocal,
+                validation(WhisperLocal, KeyValidationStatus::Ok, 0),
+            ),
+            (
+                Deepgram,
+                validation(Deepgram, KeyValidationStatus::Invalid, 0),
+            ),
+            (Gladia, validation(Gladia, KeyValidationStatus::Ok, 1)),
+            (
+                GoogleSTT,
+                validation(GoogleSTT, KeyValidationStatus::Ok, 48),
+            ),
+        ]);
+        assert_eq!(
+            rank_fallbacks(
+                &[Deepgram, Gemini, Gladia, GoogleSTT, WhisperLocal],
+                &validations,
+                Utc::now()
+            ),
+            vec![Gladia, Gemini, GoogleSTT, WhisperLocal]
+        );
+    }
+}
@@ -1,16 +1,29 @@
+use crate::capture_engine::AudioBuffer;
 use crate::database::DatabaseManager;
 use crate::live_intel_agent::LiveIntelAgent;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tauri::AppHandle;
+use tauri::{AppHandle, Emitter, Manager};
 
+pub mod audio_mixer;
 pub mod deepgram;
+pub mod failover;
 pub mod gemini;
 pub mod gladia;
+pub mod glossary;
 pub mod google_stt;
+pub mod key_validation;
+pub mod retranscribe;
+pub mod retry_queue;
+pub mod review;
+pub mod whisper_local;
+
+pub use glossary::GlossaryTerm;
 
 /// Core trait for all transcription providers
 #[async_trait]
@@ -27,9 +40,21 @@ pub trait TranscriptionProvider: Send + Sync {
     /// Check if connected/active
     fn is_active(&self) -> bool;
 
+    /// Connection state, including reconnects for providers that support them
+    fn get_connection_health(&self) -> ConnectionHealth {
+        if self.is_active() {
+            ConnectionHealth::Connected
+        } else {
+            ConnectionHealth::Idle
+        }
+    }
+
     /// Update API Key configuration
     fn set_api_key(&self, key: String);
 
+    /// Domain terms to bias recognition towards (applied on next start)
+    fn set_glossary(&self, terms: Vec<GlossaryTerm>);
+
     /// Set context (meeting ID, database, etc.)
     fn set_context(
         &self,
@@ -40,6 +65,144 @@ pub trait TranscriptionProvider: Send + Sync {
     );
 }
 
+/// Provider connection state, surfaced in `get_recording_status`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ConnectionHealth {
+    /// No session
+    Idle,
+    Connecting,
+    Connected,
+    /// Connection dropped; audio is buffered until it is back
+    Reconnecting {
+        attempt: u32,
+        max_attempts: u32,
+        buffered_secs: f32,
+    },
+    /// Retry budget exhausted
+    Failed {
+        reason: String,
+    },
+}
+
+impl ConnectionHealth {
+    /// Transcription is lagging or stopped while a session should be live
+    pub fn is_degraded(&self) -> bool {
+        matches!(self, Self::Reconnecting { .. } | Self::Failed { .. })
+    }
+}
+
+/// Stable diarization label stored in `transcripts.speaker` (e.g. "speaker_0").
+/// Display names are mapped per meeting via `DatabaseManager::rename_speaker`.
+pub fn speaker_label(index: u32) -> String {
+    format!("speaker_{}", index)
+}
+
+/// A final segment ready to be persisted
+#[derive(Debug, Clone, Default)]
+pub struct FinalSegment {
+    pub meeting_id: String,
+    pub text: String,
+    pub speaker: Option<String>,
+    pub confidence: f32,
+    /// When the words were spoken (stream start + provider offset); None = when saved
+    pub spoken_at: Option<DateTime<Utc>>,
+    /// Transcribed from audio buffered while the provider was reconnecting
+    pub after_reconnect: bool,
+}
+
+/// Wall-clock time of an offset into a provider's audio stream
+pub fn stream_time(stream_started_at: DateTime<Utc>, offset_secs: f64) -> DateTime<Utc> {
+    stream_started_at + chrono::Duration::milliseconds((offset_secs.max(0.0) * 1000.0) as i64)
+}
+
+/// Persist a final transcript segment. Failed inserts (e.g. "database is locked")
+/// go to the retry queue instead of being dropped. Segments without a diarized
+/// speaker are attributed to "Me"/"Them" from mic vs system audio levels.
+/// Finals for a meeting being re-transcribed go to its revision set instead.
+pub async fn save_final_transcript(database: Arc<DatabaseManager>, segment: FinalSegment) {
+    failover::provider_health().record_transcript();
+    let Some(mut segment) = retranscribe::capture(segment) else {
+        return;
+    };
+    segment.speaker = segment.speaker.or_else(audio_mixer::attribute_speaker);
+    retry_queue::retry_queue().insert(database, segment).await;
+}
+
+/// Feed a final segment to the live intel agent. New insights are stored in
+/// `live_insights` and pushed to the frontend as `live-insight` events; the
+/// segment and its decisions/action items also go to the meeting timeline.
+pub fn feed_live_intel(
+    app: &AppHandle,
+    agent: &Arc<RwLock<LiveIntelAgent>>,
+    database: Option<Arc<DatabaseManager>>,
+    meeting_id: Option<String>,
+    text: &str,
+    speaker: Option<String>,
+) {
+    // Insights were already raised when the meeting was live
+    if meeting_id
+        .as_deref()
+        .is_some_and(retranscribe::is_capturing)
+    {
+        return;
+    }
+
+    let events = agent
+        .write()
+        .process_segment(crate::catch_up_agent::TranscriptSegment {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp_ms: chrono::Utc::now().timestamp_millis(),
+            speaker: speaker.clone(),
+            text: text.to_string(),
+        });
+    feed_timeline(app, text, speaker, &events);
+
+    let (database, meeting_id) = match (database, meeting_id) {
+        (Some(database), Some(meeting_id)) if !events.is_empty() => (database, meeting_id),
+        _ => return,
+    };
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        for mut event in events {
+            match database.add_live_insight(&meeting_id, &event).await {
+                Ok(id) => {
+                    // Row id is the stable id the frontend polls with
+                    event.set_id(id.to_string());
+                    if let Err(e) = app.emit("live-insight", &event) {
+                        log::error!("Failed to emit live insight: {}", e);
+                    }
+                }
+                Err(e) => log::warn!("Failed to save live insight: {}", e),
+            }
+        }
+    });
+}
+
+/// Add transcript-derived events (monologues, question bursts, decisions and
+/// action items) to the timeline of the meeting being recorded
+fn feed_timeline(
+    app: &AppHandle,
+    text: &str,
+    speaker: Option<String>,
+    insights: &[crate::live_intel_agent::LiveInsightEvent],
+) {
+    let Some(state) = app.try_state::<crate::AppState>() else {
+        return;
+    };
+    let timeline = &state.timeline_builder;
+    // Same Me/Them fallback the saved transcript gets
+    let speaker = speaker.or_else(audio_mixer::attribute_speaker);
+    let mut events = timeline.process_transcript(Utc::now(), speaker.as_deref(), text);
+    events.extend(insights.iter().filter_map(|i| timeline.add_insight(i)));
+    for event in events {
+        if let Err(e) = app.emit("timeline-event", &event) {
+            log::error!("Failed to emit timeline-event: {}", e);
+        }
+    }
+}
+
 /// Enum for supported providers
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -48,6 +211,38 @@ pub enum ProviderType {
     Gemini,
     Gladia,
     GoogleSTT,
+    /// Offline whisper.cpp model, no API key required
+    WhisperLocal,
+}
+
+impl ProviderType {
+    /// Whether this provider needs a cloud API key to run
+    pub fn requires_api_key(&self) -> bool {
+        !matches!(self, Self::WhisperLocal)
+    }
+
+    /// Parse a `transcription_provider` setting value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "deepgram" => Some(Self::Deepgram),
+            "gemini" => Some(Self::Gemini),
+            "gladia" => Some(Self::Gladia),
+            "google_stt" => Some(Self::GoogleSTT),
+            "whisper_local" => Some(Self::WhisperLocal),
+            _ => None,
+        }
+    }
+
+    /// Setting value for this provider
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Deepgram => "deepgram",
+            Self::Gemini => "gemini",
+            Self::Gladia => "gladia",
+            Self::GoogleSTT => "google_stt",
+            Self::WhisperLocal => "whisper_local",
+        }
+    }
 }
 
 impl Default for ProviderType {
@@ -56,12 +251,48 @@ impl Default for ProviderType {
     }
 }
 
+/// Prerecorded mode: wait up to 5s (50 x 100ms) for the provider to connect
+const PRERECORDED_CONNECT_POLLS: u32 = 50;
+/// Prerecorded mode: how much faster than real time audio is sent
+const PRERECORDED_SPEEDUP: u64 = 10;
+/// Prerecorded mode: wait for trailing finals before closing the session
+const PRERECORDED_TAIL_SECS: u64 = 3;
+/// How often the active provider's health is checked during a session
+const HEALTH_CHECK_SECS: u64 = 1;
+/// Failover: wait up to 10s (100 x 100ms) for the new provider before replaying
+const REPLAY_CONNECT_POLLS: u32 = 100;
+
+/// Context of the current session, re-applied to a provider after failover
+#[derive(Clone)]
+struct SessionContext {
+    app_handle: AppHandle,
+    database: Arc<DatabaseManager>,
+    meeting_id: String,
+    live_intel_agent: Arc<RwLock<LiveIntelAgent>>,
+}
+
 /// Manager to switch between providers safely
 pub struct TranscriptionManager {
     current_provider: Arc<RwLock<Box<dyn TranscriptionProvider>>>,
     provider_type: Arc<RwLock<ProviderType>>,
     /// Per-provider API key store — survives provider switches
     api_keys: Arc<RwLock<HashMap<ProviderType, String>>>,
+    /// Glossary terms — survive provider switches like API keys
+    glossary: Arc<RwLock<Vec<GlossaryTerm>>>,
+    /// Mixes mic + system audio into the single stream providers expect
+    mixer: Arc<parking_lot::Mutex<audio_mixer::AudioMixer>>,
+    /// Last context passed to `set_context`
+    session: Arc<RwLock<Option<SessionContext>>>,
+    /// Between `start` and `stop`
+    session_active: Arc<AtomicBool>,
+    failover_policy: Arc<RwLock<failover::FailoverPolicy>>,
+    /// Recent audio, replayed into the next provider on failover
+    gap_buffer: Arc<parking_lot::Mutex<failover::GapBuffer>>,
+    /// Providers used this session; failover never goes back to one
+    tried_providers: Arc<parking_lot::Mutex<HashSet<ProviderType>>>,
+    failover_exhausted: Arc<AtomicBool>,
+    /// Latest key validation per provider; cleared when the key changes
+    key_validations: Arc<RwLock<HashMap<ProviderType, key_validation::KeyValidation>>>,
 }
 
 impl TranscriptionManager {
@@ -73,6 +304,17 @@ impl TranscriptionManager {
             current_provider: Arc::new(RwLock::new(Box::new(default_provider))),
             provider_type: Arc::new(RwLock::new(ProviderType::Deepgram)),
             api_keys: Arc::new(RwLock::new(HashMap::new())),
+            glossary: Arc::new(RwLock::new(Vec::new())),
+            mixer: Arc::new(parking_lot::Mutex::new(audio_mixer::AudioMixer::new())),
+            session: Arc::new(RwLock::new(None)),
+            session_active: Arc::new(AtomicBool::new(false)),
+            failover_policy: Arc::new(RwLock::new(failover::FailoverPolicy::default())),
+            gap_buffer: Arc::new(parking_lot::Mutex::new(failover::GapBuffer::new(
+                failover::DEFAULT_FAILOVER_BUFFER_SECS,
+            ))),
+            tried_providers: Arc::new(parking_lot::Mutex::new(HashSet::new())),
+            failover_exhausted: Arc::new(AtomicBool::new(false)),
+            key_validations: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -86,6 +328,7 @@ impl TranscriptionManager {
             ProviderType::Gemini => Box::new(gemini::GeminiProvider::new()),
             ProviderType::Gladia => Box::new(gladia::GladiaProvider::new()),
             ProviderType::GoogleSTT => Box::new(google_stt::GoogleSTTProvider::new()),
+            ProviderType::WhisperLocal => Box::new(whisper_local::WhisperLocalProvider::new()),
         };
 
         // Re-apply stored API key for this provider type (if any)
@@ -93,6 +336,7 @@ impl TranscriptionManager {
             new_provider.set_api_key(key.clone());
             log::info!("Re-applied stored API key for {:?}", provider_type);
         }
+        new_provider.set_glossary(self.glossary.read().clone());
 
         *self.current_provider.write() = new_provider;
         *self.provider_type.write() = provider_type;
@@ -105,15 +349,40 @@ impl TranscriptionManager {
 
     /// Store an API key for a specific provider type (persists across switches)
     pub fn set_api_key_for_provider(&self, provider_type: ProviderType, key: String) {
-        self.api_keys.write().insert(provider_type, key.clone());
+        let previous = self.api_keys.write().insert(provider_type, key.clone());
+        if previous.as_deref() != Some(key.as_str()) {
+            self.key_validations.write().remove(&provider_type);
+        }
         // If this is the currently active provider, also set it on the live instance
         if *self.provider_type.read() == provider_type {
             self.current_provider.read().set_api_key(key);
         }
     }
 
-    /// Check if a key exists for a given provider type
+    pub fn get_api_key_for_provider(&self, provider_type: ProviderType) -> Option<String> {
+        self.api_keys
+            .read()
+            .get(&provider_type)
+            .filter(|k| !k.is_empty())
+            .cloned()
+    }
+
+    /// Replace the glossary on the manager and the active provider
+    pub fn set_glossary(&self, terms: Vec<GlossaryTerm>) {
+        *self.glossary.write() = terms.clone();
+        self.current_provider.read().set_glossary(terms);
+    }
+
+    pub fn get_glossary(&self) -> Vec<GlossaryTerm> {
+        self.glossary.read().clone()
+    }
+
+    /// Check if a key exists for a given provider type (local providers always qualify)
     pub fn has_key_for_provider(&self, provider_type: ProviderType) -> bool {
+        if !provider_type.requires_api_key() {
+            return true;
+        }
+
         self.api_keys
             .read()
             .get(&provider_type)
@@ -121,29 +390,118 @@ impl TranscriptionManager {
             .unwrap_or(false)
     }
 
+    pub fn record_key_validation(&self, validation: key_validation::KeyValidation) {
+        self.key_validations
+            .write()
+            .insert(validation.provider, validation);
+    }
+
+    pub fn last_key_validation(
+        &self,
+        provider_type: ProviderType,
+    ) -> Option<key_validation::KeyValidation> {
+        self.key_validations.read().get(&provider_type).cloned()
+    }
+
+    /// Whether the provider rejected the stored key the last time it was checked
+    pub fn key_known_invalid(&self, provider_type: ProviderType) -> bool {
+        self.key_validations
+            .read()
+            .get(&provider_type)
+            .is_some_and(|v| v.status == key_validation::KeyValidationStatus::Invalid)
+    }
+
+    /// Best provider to fall back to from `current` at the start of a
+    /// recording: recently validated keys first, never a rejected one
+    pub fn preferred_fallback(&self, current: ProviderType) -> Option<ProviderType> {
+        let candidates: Vec<ProviderType> = failover::FAILOVER_ORDER
+            .into_iter()
+            .filter(|p| *p != current && self.has_key_for_provider(*p))
+            .collect();
+        key_validation::rank_fallbacks(&candidates, &self.key_validations.read(), Utc::now())
+            .into_iter()
+            .next()
+    }
+
+    pub fn set_failover_policy(&self, policy: failover::FailoverPolicy) {
+        self.gap_buffer.lock().set_max_secs(policy.buffer_secs);
+        *self.failover_policy.write() = policy;
+    }
+
+    pub fn get_failover_policy(&self) -> failover::FailoverPolicy {
+        *self.failover_policy.read()
+    }
+
     // Proxy methods
     pub fn start(&self) {
+        self.mixer.lock().reset();
+        audio_mixer::source_activity().clear();
+        self.gap_buffer.lock().clear();
+
+        let provider_type = self.get_provider_type();
+        {
+            let mut tried = self.tried_providers.lock();
+            tried.clear();
+            tried.insert(provider_type);
+        }
+        self.failover_exhausted.store(false, Ordering::SeqCst);
+        failover::provider_health().begin(provider_type);
+
         self.current_provider.read().start();
+        self.session_active.store(true, Ordering::SeqCst);
     }
 
     pub fn stop(&self) {
+        self.session_active.store(false, Ordering::SeqCst);
         self.current_provider.read().stop();
     }
 
     pub fn process_audio(&self, samples: &[f32], sample_rate: u32, channels: u16) {
+        // Held back while gap audio is replayed into a new provider
+        if !self.gap_buffer.lock().push(samples, sample_rate, channels) {
+            return;
+        }
+
+        failover::provider_health().record_audio(samples, sample_rate, channels);
         self.current_provider
             .read()
             .process_audio(samples, sample_rate, channels);
     }
 
+    /// Route a captured buffer (mic or system audio) through the mixer to the provider
+    pub fn process_buffer(&self, buffer: &AudioBuffer) {
+        if buffer.samples.is_empty() {
+            return;
+        }
+
+        audio_mixer::source_activity().record(buffer.source, &buffer.samples);
+        let mixed = self.mixer.lock().push(
+            buffer.source,
+            &buffer.samples,
+            buffer.sample_rate,
+            buffer.channels,
+        );
+
+        if !mixed.is_empty() {
+            self.process_audio(&mixed, audio_mixer::MIX_SAMPLE_RATE, 1);
+        }
+    }
+
     pub fn is_active(&self) -> bool {
         self.current_provider.read().is_active()
     }
 
+    pub fn get_connection_health(&self) -> ConnectionHealth {
+        self.current_provider.read().get_connection_health()
+    }
+
     /// Set API key on the current active provider AND store it for persistence
     pub fn set_api_key(&self, key: String) {
         let provider_type = *self.provider_type.read();
-        self.api_keys.write().insert(provider_type, key.clone());
+        let previous = self.api_keys.write().insert(provider_type, key.clone());
+        if previous.as_deref() != Some(key.as_str()) {
+            self.key_validations.write().remove(&provider_type);
+        }
         self.current_provider.read().set_api_key(key);
     }
 
@@ -154,6 +512,12 @@ impl TranscriptionManager {
         meeting_id: String,
         live_intel_agent: Arc<RwLock<LiveIntelAgent>>,
     ) {
+        *self.session.write() = Some(SessionContext {
+            app_handle: app_handle.clone(),
+            database: database.clone(),
+            meeting_id: meeting_id.clone(),
+            live_intel_agent: live_intel_agent.clone(),
+        });
         self.current_provider.read().set_context(
             app_handle,
             database,
@@ -161,4 +525,176 @@ impl TranscriptionManager {
             live_intel_agent,
         );
     }
+
+    /// Check the active provider once a second for the life of the app
+    pub fn spawn_health_monitor(self: &Arc<Self>) {
+        let manager = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            let mut interval =
+                tokio::time::interval(std::time::Duration::from_secs(HEALTH_CHECK_SECS));
+            loop {
+                interval.tick().await;
+                manager.check_health();
+            }
+        });
+    }
+
+    /// Fail over if the active provider has been unhealthy for too long
+    fn check_health(&self) {
+        let active = self.session_active.load(Ordering::SeqCst);
+        let degraded = active && self.get_connection_health().is_degraded();
+        crate::tray_builder::update_tray_status(|s| s.transcription_degraded = degraded);
+        if !active {
+            return;
+        }
+
+        let policy = self.get_failover_policy();
+        let after = std::time::Duration::from_secs(policy.after_secs as u64);
+        let Some(reason) = failover::provider_health().evaluate(self.is_active(), after) else {
+            return;
+        };
+        if policy.enabled {
+            self.fail_over(reason);
+        }
+    }
+
+    /// Switch to the next provider with a key, re-apply the session context
+    /// and replay the gap audio into it
+    fn fail_over(&self, reason: String) {
+        let from = self.get_provider_type();
+        let to = {
+            let mut tried = self.tried_providers.lock();
+            let next = failover::next_provider(from, |p| {
+                !tried.contains(&p) && self.has_key_for_provider(p) && !self.key_known_invalid(p)
+            });
+            if let Some(next) = next {
+                tried.insert(next);
+            }
+            next
+        };
+        let Some(to) = to else {
+            // Keep checking in case the provider reconnects by itself, but
+            // only say so once
+            if !self.failover_exhausted.swap(true, Ordering::SeqCst) {
+                log::warn!(
+                    "{:?} is unhealthy ({}) and no provider is left to fail over to",
+                    from,
+                    reason
+                );
+            }
+            return;
+        };
+        let Some(session) = self.session.read().clone() else {
+            return;
+        };
+
+        log::warn!(
+            "Transcription provider {:?} unhealthy ({}), failing over to {:?}",
+            from,
+            reason,
+            to
+        );
+
+        let replayed_secs = self
+            .gap_buffer
+            .lock()
+            .begin_replay(failover::provider_health().replay_from());
+
+        self.switch_provider(to);
+        self.current_provider.read().set_context(
+            session.app_handle.clone(),
+            session.database.clone(),
+            session.meeting_id.clone(),
+            session.live_intel_agent.clone(),
+        );
+        failover::provider_health().failed_over(from, to);
+        self.current_provider.read().start();
+        self.session_active.store(true, Ordering::SeqCst);
+
+        self.spawn_replay();
+
+        let event = failover::FailoverEvent {
+            meeting_id: session.meeting_id,
+            from,
+            to,
+            reason,
+            replayed_secs,
+        };
+        if let Err(e) = session.app_handle.emit("transcription-failover", &event) {
+            log::error!("Failed to emit transcription failover: {}", e);
+        }
+    }
+
+    /// Once the new provider connects, send it the gap audio and anything
+    /// captured meanwhile, then hand back to live streaming
+    fn spawn_replay(&self) {
+        let provider = self.current_provider.clone();
+        let gap_buffer = self.gap_buffer.clone();
+        tauri::async_runtime::spawn(async move {
+            for _ in 0..REPLAY_CONNECT_POLLS {
+                if provider.read().is_active() {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+
+            loop {
+                let chunks = gap_buffer.lock().take_replay();
+                if chunks.is_empty() {
+                    break;
+                }
+                let provider = provider.read();
+                for chunk in chunks {
+                    failover::provider_health().record_audio(
+                        &chunk.samples,
+                        chunk.sample_rate,
+                        chunk.channels,
+                    );
+                    provider.process_audio(&chunk.samples, chunk.sample_rate, chunk.channels);
+                }
+            }
+        });
+    }
+
+    /// Prerecorded mode: stream already-captured 16kHz mono audio through the
+    /// active provider for `meeting_id` faster than real time, then close the
+    /// session. Refuses while a live recording is using the provider.
+    pub async fn transcribe_prerecorded(
+        &self,
+        app_handle: AppHandle,
+        database: Arc<DatabaseManager>,
+        meeting_id: String,
+        live_intel_agent: Arc<RwLock<LiveIntelAgent>>,
+        samples: Vec<f32>,
+    ) -> Result<(), String> {
+        if self.is_active() {
+            return Err("Transcription provider is busy with a live recording".to_string());
+        }
+
+        self.set_context(app_handle, database, meeting_id, live_intel_agent);
+        self.start();
+
+        for _ in 0..PRERECORDED_CONNECT_POLLS {
+            if self.is_active() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+        if !self.is_active() {
+            self.stop();
+            return Err("Transcription provider did not start".to_string());
+        }
+
+        // 100ms chunks, paced at PRERECORDED_SPEEDUP x real time
+        let chunk_len = audio_mixer::MIX_SAMPLE_RATE as usize / 10;
+        for chunk in samples.chunks(chunk_len) {
+            self.process_audio(chunk, audio_mixer::MIX_SAMPLE_RATE, 1);
+            tokio::time::sleep(std::time::Duration::from_millis(100 / PRERECORDED_SPEEDUP)).await;
+        }
+
+        // Give the provider time to return finals for the tail
+        tokio::time::sleep(std::time::Duration::from_secs(PRERECORDED_TAIL_SECS)).await;
+        self.stop();
+        Ok(())
+    }
 }
@@ -0,0 +1,503 @@
+// noFriction Meetings - Re-transcription
+// Runs a meeting's archived audio through a different provider after the fact
+//
+// Audio chunks are decoded in order and streamed through the chosen provider
+// in prerecorded mode. While a job runs, finals for its meeting are diverted
+// here instead of `transcripts`, re-timed onto the chunk's wall clock, and
+// stored per chunk in `retranscribed_segments`. The job's `next_chunk` advances
+// as each chunk is stored, so a job cut short by quitting resumes from the
+// chunk it was on at next launch. When finished the segments either stay as a
+// revision set next to the original transcripts or replace them (originals
+// archived in `transcript_revisions`). Replacing re-indexes the meeting's
+// vectors; `transcripts_fts` follows the table through its triggers.
+
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::{FinalSegment, ProviderType, PRERECORDED_SPEEDUP};
+use crate::audio_recorder::read_chunk_samples;
+use crate::database::{AudioChunk, RetranscribeJob, RetranscribedSegment, RevisionSetApplied};
+use crate::AppState;
+
+pub const STATUS_RUNNING: &str = "running";
+pub const STATUS_COMPLETED: &str = "completed";
+pub const STATUS_FAILED: &str = "failed";
+
+/// Set while a job holds the transcription provider
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Payload for `retranscribe-progress`, emitted after every chunk and at the end
+#[derive(Debug, Clone, Serialize)]
+pub struct RetranscribeProgress {
+    pub job_id: String,
+    pub meeting_id: String,
+    pub completed_chunks: i64,
+    pub total_chunks: i64,
+    /// Segments produced by the chunk just finished
+    pub segments: usize,
+    /// "running", "completed" or "failed"
+    pub status: String,
+    pub error: Option<String>,
+    /// Set when the job replaced the meeting's transcripts
+    pub applied: Option<RevisionSetApplied>,
+}
+
+/// Finals diverted from the meeting being re-transcribed
+struct Capture {
+    meeting_id: String,
+    /// When the current chunk started streaming
+    session_started: DateTime<Utc>,
+    /// Each final with the time it arrived
+    finals: Vec<(FinalSegment, DateTime<Utc>)>,
+}
+
+fn capture_slot() -> &'static Mutex<Option<Capture>> {
+    static SLOT: OnceLock<Mutex<Option<Capture>>> = OnceLock::new();
+    SLOT.get_or_init(|| Mutex::new(None))
+}
+
+/// A re-transcription currently holds the transcription provider
+pub fn is_running() -> bool {
+    RUNNING.load(Ordering::SeqCst)
+}
+
+/// Finals for `meeting_id` are being diverted to a revision set
+pub fn is_capturing(meeting_id: &str) -> bool {
+    capture_slot()
+        .lock()
+        .as_ref()
+        .is_some_and(|c| c.meeting_id == meeting_id)
+}
+
+/// Divert a final for the meeting being re-transcribed. Returns the segment
+/// when it belongs to another meeting and should be saved normally.
+pub fn capture(segment: FinalSegment) -> Option<FinalSegment> {
+    let mut slot = capture_slot().lock();
+    match slot.as_mut() {
+        Some(capture) if capture.meeting_id == segment.meeting_id => {
+            capture.finals.push((segment, Utc::now()));
+            None
+        }
+        _ => Some(segment),
+    }
+}
+
+/// Wall-clock time of a diverted final within `chunk`. Provider offsets
+/// (`spoken_at`) are already in audio time; arrival times are compressed by
+/// the prerecorded speed-up. Clamped to the chunk.
+fn chunk_time(
+    chunk: &AudioChunk,
+    session_started: DateTime<Utc>,
+    spoken_at: Option<DateTime<Utc>>,
+    arrived_at: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let elapsed = match spoken_at {
+        Some(spoken_at) => spoken_at - session_started,
+        None => (arrived_at - session_started) * PRERECORDED_SPEEDUP as i32,
+    };
+    let at = chunk.start_ts + elapsed.max(Duration::zero());
+    match chunk.end_ts {
+        Some(end) => at.min(end),
+        None => at,
+    }
+}
+
+/// Start diverting finals for the next chunk, returning the previous chunk's
+fn restart_capture(meeting_id: &str) -> Option<Capture> {
+    capture_slot().lock().replace(Capture {
+        meeting_id: meeting_id.to_string(),
+        session_started: Utc::now(),
+        finals: Vec::new(),
+    })
+}
+
+fn segments_for_chunk(
+    capture: Capture,
+    job: &RetranscribeJob,
+    chunk: &AudioChunk,
+    position: i64,
+) -> Vec<RetranscribedSegment> {
+    let session_started = capture.session_started;
+    capture
+        .finals
+        .into_iter()
+        .filter(|(segment, _)| !segment.text.trim().is_empty())
+        .map(|(segment, arrived_at)| RetranscribedSegment {
+            id: 0,
+            job_id: job.id.clone(),
+            chunk_position: position,
+            timestamp: chunk_time(chunk, session_started, segment.spoken_at, arrived_at),
+            text: segment.text,
+            speaker: segment.speaker,
+            confidence: segment.confidence,
+        })
+        .collect()
+}
+
+/// Create a job for `meeting_id` and run it in the background
+pub async fn start(
+    app: &AppHandle,
+    state: &AppState,
+    meeting_id: &str,
+    provider: ProviderType,
+    replace_existing: bool,
+) -> Result<RetranscribeJob, String> {
+    if state.transcription_manager.is_active() {
+        return Err("Stop the current recording before re-transcribing".to_string());
+    }
+    if !state.transcription_manager.has_key_for_provider(provider) {
+        return Err(format!("No API key configured for {:?}", provider));
+    }
+
+    let chunks = state
+        .database
+        .get_audio_chunks(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get audio chunks: {}", e))?;
+    if chunks.is_empty() {
+        return Err("No archived audio for this meeting".to_string());
+    }
+
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("Another meeting is being re-transcribed".to_string());
+    }
+
+    let now = Utc::now();
+    let job = RetranscribeJob {
+        id: uuid::Uuid::new_v4().to_string(),
+        meeting_id: meeting_id.to_string(),
+        provider: provider.as_str().to_string(),
+        replace_existing,
+        status: STATUS_RUNNING.to_string(),
+        next_chunk: 0,
+        total_chunks: chunks.len() as i64,
+        error: None,
+        created_at: now,
+        updated_at: now,
+    };
+    if let Err(e) = state.database.create_retranscribe_job(&job).await {
+        RUNNING.store(false, Ordering::SeqCst);
+        return Err(format!("Failed to create re-transcription job: {}", e));
+    }
+
+    log::info!(
+        "🔁 Re-transcribing meeting {} with {:?} ({} chunks)",
+        meeting_id,
+        provider,
+        chunks.len()
+    );
+    let app = app.clone();
+    let spawned = job.clone();
+    tauri::async_runtime::spawn(async move {
+        run(app, spawned).await;
+    });
+    Ok(job)
+}
+
+/// Resume jobs left running when the app quit, one after another
+pub fn resume_interrupted(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let Some(state) = app.try_state::<AppState>() else {
+            return;
+        };
+        let jobs = match state.database.get_running_retranscribe_jobs().await {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                log::warn!("Failed to load interrupted re-transcriptions: {}", e);
+                return;
+            }
+        };
+
+        for job in jobs {
+            // A recording or a newly started job has the provider; the rest
+            // stay queued for the next launch
+            if state.transcription_manager.is_active() || RUNNING.swap(true, Ordering::SeqCst) {
+                break;
+            }
+            log::info!(
+                "🔁 Resuming re-transcription of {} at chunk {}/{}",
+                job.meeting_id,
+                job.next_chunk + 1,
+                job.total_chunks
+            );
+            run(app.clone(), job).await;
+        }
+    });
+}
+
+/// Run a job to completion (RUNNING must already be set) and report the outcome
+async fn run(app: AppHandle, job: RetranscribeJob) {
+    let result = run_job(&app, &job).await;
+    capture_slot().lock().take();
+
+    let (status, error, applied) = match result {
+        Ok(applied) => (STATUS_COMPLETED, None, applied),
+        Err(e) => {
+            log::error!("Re-transcription of {} failed: {}", job.meeting_id, e);
+            (STATUS_FAILED, Some(e), None)
+        }
+    };
+
+    let mut completed_chunks = job.total_chunks;
+    if let Some(state) = app.try_state::<AppState>() {
+        if let Err(e) = state
+            .database
+            .set_retranscribe_job_status(&job.id, status, error.as_deref())
+            .await
+        {
+            log::warn!("Failed to update re-transcription job: {}", e);
+        }
+        if let Ok(Some(saved)) = state.database.get_retranscribe_job(&job.id).await {
+            completed_chunks = saved.next_chunk;
+        }
+    }
+    RUNNING.store(false, Ordering::SeqCst);
+    let _ = app.emit(
+        "retranscribe-progress",
+        RetranscribeProgress {
+            job_id: job.id.clone(),
+            meeting_id: job.meeting_id.clone(),
+            completed_chunks,
+            total_chunks: job.total_chunks,
+            segments: 0,
+            status: status.to_string(),
+            error,
+            applied,
+        },
+    );
+}
+
+async fn run_job(
+    app: &AppHandle,
+    job: &RetranscribeJob,
+) -> Result<Option<RevisionSetApplied>, String> {
+    let state = app
+        .try_state::<AppState>()
+        .ok_or("App state not initialized")?;
+    let provider = ProviderType::parse(&job.provider)
+        .ok_or_else(|| format!("Unknown transcription provider: {}", job.provider))?;
+    let tm = state.transcription_manager.clone();
+
+    let previous_provider = tm.get_provider_type();
+    if previous_provider != provider {
+        tm.switch_provider(provider);
+    }
+    let result = transcribe_chunks(app, &state, job).await;
+    if previous_provider != provider {
+        tm.switch_provider(previous_provider);
+    }
+    result?;
+
+    if !job.replace_existing {
+        return Ok(None);
+    }
+
+    let applied = state
+        .database
+        .apply_revision_set(&job.id, &job.meeting_id)
+        .await
+        .map_err(|e| format!("Failed to replace transcripts: {}", e))?
+        .ok_or("Re-transcription produced no text; the original transcripts were kept")?;
+    log::info!(
+        "🔁 Replaced transcripts of {}: {} updated, {} added, {} removed",
+        job.meeting_id,
+        applied.updated,
+        applied.inserted,
+        applied.removed_ids.len()
+    );
+    reindex(&state, &job.meeting_id, &applied.removed_ids).await;
+    Ok(Some(applied))
+}
+
+async fn transcribe_chunks(
+    app: &AppHandle,
+    state: &AppState,
+    job: &RetranscribeJob,
+) -> Result<(), String> {
+    let chunks = state
+        .database
+        .get_audio_chunks(&job.meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get audio chunks: {}", e))?;
+
+    for (position, chunk) in chunks.iter().enumerate().skip(job.next_chunk as usize) {
+        let position = position as i64;
+        let source = chunk.clone();
+        let samples = tokio::task::spawn_blocking(move || read_chunk_samples(&source))
+            .await
+            .map_err(|e| format!("Audio decode task failed: {}", e))?
+            .unwrap_or_else(|e| {
+                log::warn!("Skipping unreadable audio chunk {}: {}", chunk.path, e);
+                Vec::new()
+            });
+
+        let mut segments = Vec::new();
+        if !samples.is_empty() {
+            restart_capture(&job.meeting_id);
+            state
+                .transcription_manager
+                .transcribe_prerecorded(
+                    app.clone(),
+                    state.database.clone(),
+                    job.meeting_id.clone(),
+                    state.live_intel_agent.clone(),
+                    samples,
+                )
+                .await?;
+            // Late finals from this chunk land in the next capture, clamped to its start
+            if let Some(capture) = restart_capture(&job.meeting_id) {
+                segments = segments_for_chunk(capture, job, chunk, position);
+            }
+        }
+
+        state
+            .database
+            .save_retranscribed_chunk(&job.id, &job.meeting_id, position, &segments)
+            .await
+            .map_err(|e| format!("Failed to save re-transcribed chunk: {}", e))?;
+
+        let _ = app.emit(
+            "retranscribe-progress",
+            RetranscribeProgress {
+                job_id: job.id.clone(),
+                meeting_id: job.meeting_id.clone(),
+                completed_chunks: position + 1,
+                total_chunks: job.total_chunks,
+                segments: segments.len(),
+                status: STATUS_RUNNING.to_string(),
+                error: None,
+                applied: None,
+            },
+        );
+    }
+    Ok(())
+}
+
+/// Push the rewritten transcripts to the vector store and drop vectors of
+/// removed rows. Archived meetings stay out of the knowledge base.
+async fn reindex(state: &AppState, meeting_id: &str, removed_ids: &[i64]) {
+    if state
+        .database
+        .is_meeting_archived(meeting_id)
+        .await
+        .unwrap_or(false)
+    {
+        return;
+    }
+    let Some(store) = crate::commands::vector_store(state).await else {
+        return;
+    };
+
+    let stale: Vec<String> = removed_ids
+        .iter()
+        .map(|id| format!("transcript_{}_{}", meeting_id, id))
+        .collect();
+    if let Err(e) = store
+        .delete(crate::pinecone_client::VectorKind::Transcript, &stale)
+        .await
+    {
+        log::warn!("Failed to delete replaced transcript vectors: {}", e);
+    }
+
+    match crate::commands::index_meeting_into(&state.database, &store, meeting_id, true).await {
+        Ok(result) => log::info!(
+            "🔁 Re-indexed {} re-transcribed segments of {}",
+            result.transcripts_indexed,
+            meeting_id
+        ),
+        Err(e) => log::warn!("Failed to re-index {}: {}", meeting_id, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(start: DateTime<Utc>) -> AudioChunk {
+        AudioChunk {
+            id: "chunk".to_string(),
+            meeting_id: "meeting".to_string(),
+            chunk_index: 1,
+            path: "audio/meeting/chunk_0001.wav".to_string(),
+            format: "wav".to_string(),
+            sample_rate: 16000,
+            channels: 1,
+            start_ts: start,
+            end_ts: Some(start + Duration::seconds(300)),
+            size_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn test_chunk_time_uses_offsets_then_scaled_arrival() {
+        let recorded = Utc::now() - Duration::days(1);
+        let started = Utc::now();
+        let chunk = chunk(recorded);
+
+        // Provider offset: 42s into the audio
+        let spoken = started + Duration::seconds(42);
+        assert_eq!(
+            chunk_time(
+                &chunk,
+                started,
+                Some(spoken),
+                started + Duration::seconds(5)
+            ),
+            recorded + Duration::seconds(42)
+        );
+
+        // No offset: arrival 3s in at 10x is 30s of audio
+        assert_eq!(
+            chunk_time(&chunk, started, None, started + Duration::seconds(3)),
+            recorded + Duration::seconds(3) * PRERECORDED_SPEEDUP as i32
+        );
+
+        // Clamped to the chunk
+        assert_eq!(
+            chunk_time(
+                &chunk,
+                started,
+                Some(started + Duration::seconds(900)),
+                started
+            ),
+            recorded + Duration::seconds(300)
+        );
+        assert_eq!(
+            chunk_time(
+                &chunk,
+                started,
+                Some(started - Duration::seconds(2)),
+                started
+            ),
+            recorded
+        );
+    }
+
+    #[test]
+    fn test_capture_diverts_only_its_meeting() {
+        restart_capture("retranscribe-test");
+        assert!(is_capturing("retranscribe-test"));
+
+        let other = FinalSegment {
+            meeting_id: "live-meeting".to_string(),
+            text: "hello".to_string(),
+            ..Default::default()
+        };
+        assert!(capture(other).is_some());
+
+        let diverted = FinalSegment {
+            meeting_id: "retranscribe-test".to_string(),
+            text: "hello again".to_string(),
+            ..Default::default()
+        };
+        assert!(capture(diverted).is_none());
+
+        let taken = capture_slot().lock().take().unwrap();
+        assert_eq!(taken.finals.len(), 1);
+        assert!(!is_capturing("retranscribe-test"));
+    }
+}
@@ -0,0 +1,399 @@
+// noFriction Meetings - Transcript Retry Queue
+// Bounded in-memory retry buffer for transcript inserts that fail (e.g. "database is locked")
+//
+// Segments are retried with exponential backoff. Anything that still fails after
+// max_attempts (or is evicted because the buffer is full) is appended to
+// failed_transcripts.jsonl so no transcript is ever silently dropped.
+// Successful inserts are passed to the low-confidence reviewer and checked
+// for action items.
+
+use chrono::{DateTime, Utc};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::database::DatabaseManager;
+use crate::transcription::review::low_confidence_review;
+use crate::transcription::FinalSegment;
+
+/// Retry behaviour configuration
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// Maximum segments held in memory before the oldest is spilled to disk
+    pub max_pending: usize,
+    /// Attempts (including the first insert) before giving up on a segment
+    pub max_attempts: u32,
+    /// First retry delay
+    pub base_delay_ms: u64,
+    /// Upper bound on retry delay
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_pending: 500,
+            max_attempts: 6,
+            base_delay_ms: 250,
+            max_delay_ms: 10_000,
+        }
+    }
+}
+
+/// A transcript segment waiting to be written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingTranscript {
+    pub meeting_id: String,
+    pub text: String,
+    pub speaker: Option<String>,
+    pub confidence: f32,
+    /// Transcribed from audio buffered during a provider reconnect
+    #[serde(default)]
+    pub after_reconnect: bool,
+    /// When it was spoken, if the provider reported timing
+    #[serde(default)]
+    pub spoken_at: Option<DateTime<Utc>>,
+    pub attempts: u32,
+    pub first_failed_at: DateTime<Utc>,
+    pub last_error: String,
+}
+
+/// Counters surfaced through get_recording_status
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct RetryStats {
+    pub pending: usize,
+    pub failed: usize,
+}
+
+struct QueueEntry {
+    item: PendingTranscript,
+    database: Arc<DatabaseManager>,
+    next_attempt: Instant,
+}
+
+pub struct TranscriptRetryQueue {
+    config: RetryConfig,
+    pending: Mutex<VecDeque<QueueEntry>>,
+    failed_count: AtomicUsize,
+    failed_log_path: RwLock<Option<PathBuf>>,
+    worker_running: AtomicBool,
+}
+
+/// Global retry queue shared by all transcription providers
+pub fn retry_queue() -> &'static TranscriptRetryQueue {
+    static QUEUE: OnceLock<TranscriptRetryQueue> = OnceLock::new();
+    QUEUE.get_or_init(|| TranscriptRetryQueue::new(RetryConfig::default()))
+}
+
+/// Backoff delay before retry number `attempt` (1-based)
+fn backoff_delay(config: &RetryConfig, attempt: u32) -> Duration {
+    let exp = attempt.saturating_sub(1).min(16);
+    let delay = config.base_delay_ms.saturating_mul(1u64 << exp);
+    Duration::from_millis(delay.min(config.max_delay_ms))
+}
+
+async fn mark_after_reconnect(database: &DatabaseManager, id: i64) {
+    if let Err(e) = database.mark_transcript_after_reconnect(id).await {
+        log::warn!("Failed to flag transcript {} as after_reconnect: {}", id, e);
+    }
+}
+
+impl TranscriptRetryQueue {
+    pub fn new(config: RetryConfig) -> Self {
+        Self {
+            config,
+            pending: Mutex::new(VecDeque::new()),
+            failed_count: AtomicUsize::new(0),
+            failed_log_path: RwLock::new(None),
+            worker_running: AtomicBool::new(false),
+        }
+    }
+
+    /// Set where permanently failed segments are written (app data dir)
+    pub fn set_failed_log_path(&self, path: PathBuf) {
+        *self.failed_log_path.write() = Some(path);
+    }
+
+    pub fn stats(&self) -> RetryStats {
+        RetryStats {
+            pending: self.pending.lock().len(),
+            failed: self.failed_count.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Insert a final transcript, falling back to the retry buffer on failure
+    pub async fn insert(&'static self, database: Arc<DatabaseManager>, segment: FinalSegment) {
+        let FinalSegment {
+            meeting_id,
+            text,
+            speaker,
+            confidence,
+            spoken_at,
+            after_reconnect,
+        } = segment;
+        let result = database
+            .add_transcript_at(
+                &meeting_id,
+                &text,
+                speaker.as_deref(),
+                true,
+                confidence,
+                spoken_at.unwrap_or_else(Utc::now),
+            )
+            .await;
+
+        match result {
+            Ok(id) => {
+                if after_reconnect {
+                    mark_after_reconnect(&database, id).await;
+                }
+                crate::action_items::record_live_action_item(
+                    &database,
+                    id,
+                    &meeting_id,
+                    &text,
+                    speaker.as_deref(),
+                )
+                .await;
+                low_confidence_review()
+                    .check(database, id, &meeting_id, &text, confidence)
+                    .await;
+            }
+            Err(e) => {
+                log::warn!("Transcript insert failed, queued for retry: {}", e);
+                let item = PendingTranscript {
+                    meeting_id,
+                    text,
+                    speaker,
+                    confidence,
+                    after_reconnect,
+                    spoken_at,
+                    attempts: 1,
+                    first_failed_at: Utc::now(),
+                    last_error: e.to_string(),
+                };
+                self.enqueue(item, database);
+                self.ensure_worker();
+            }
+        }
+    }
+
+    fn enqueue(&self, item: PendingTranscript, database: Arc<DatabaseManager>) {
+        let next_attempt = Instant::now() + backoff_delay(&self.config, item.attempts);
+        let evicted = {
+            let mut pending = self.pending.lock();
+            pending.push_back(QueueEntry {
+                item,
+                database,
+                next_attempt,
+            });
+            if pending.len() > self.config.max_pending {
+                pending.pop_front()
+            } else {
+                None
+            }
+        };
+
+        if let Some(entry) = evicted {
+            log::warn!("Transcript retry buffer full, spilling oldest segment to disk");
+            self.write_failed(&entry.item);
+        }
+    }
+
+    /// Spawn the background retry loop if it isn't already running
+    fn ensure_worker(&'static self) {
+        if self.worker_running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                self.retry_due(false).await;
+
+                if self.pending.lock().is_empty() {
+                    self.worker_running.store(false, Ordering::SeqCst);
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Retry entries whose backoff has elapsed (or all of them when forced)
+    async fn retry_due(&self, force: bool) {
+        let now = Instant::now();
+        let due: Vec<QueueEntry> = {
+            let mut pending = self.pending.lock();
+            let (ready, waiting): (Vec<_>, Vec<_>) = pending
+                .drain(..)
+                .partition(|e| force || e.next_attempt <= now);
+            pending.extend(waiting);
+            ready
+        };
+
+        for mut entry in due {
+            let item = &entry.item;
+            let result = entry
+                .database
+                .add_transcript_at(
+                    &item.meeting_id,
+                    &item.text,
+                    item.speaker.as_deref(),
+                    true,
+                    item.confidence,
+                    item.spoken_at.unwrap_or_else(Utc::now),
+                )
+                .await;
+
+            match result {
+                Ok(id) => {
+                    log::info!(
+                        "✅ Transcript insert succeeded after {} attempts",
+                        entry.item.attempts + 1
+                    );
+                    if item.after_reconnect {
+                        mark_after_reconnect(&entry.database, id).await;
+                    }
+                    crate::action_items::record_live_action_item(
+                        &entry.database,
+                        id,
+                        &item.meeting_id,
+                        &item.text,
+                        item.speaker.as_deref(),
+                    )
+                    .await;
+                    low_confidence_review()
+                        .check(
+                            entry.database.clone(),
+                            id,
+                            &item.meeting_id,
+                            &item.text,
+                            item.confidence,
+                        )
+                        .await;
+                }
+                Err(e) => {
+                    entry.item.attempts += 1;
+                    entry.item.last_error = e.to_string();
+
+                    if entry.item.attempts >= self.config.max_attempts {
+                        log::error!(
+                            "❌ Transcript insert failed after {} attempts: {}",
+                            entry.item.attempts,
+                            e
+                        );
+                        self.write_failed(&entry.item);
+                    } else {
+                        entry.next_attempt =
+                            Instant::now() + backoff_delay(&self.config, entry.item.attempts);
+                        self.pending.lock().push_back(entry);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Retry everything immediately; whatever still fails is written to disk.
+    /// Called from stop_recording so nothing is left in memory.
+    pub async fn flush(&self) {
+        for _ in 0..self.config.max_attempts {
+            if self.pending.lock().is_empty() {
+                return;
+            }
+            self.retry_due(true).await;
+            tokio::time::sleep(Duration::from_millis(self.config.base_delay_ms)).await;
+        }
+
+        let remaining: Vec<QueueEntry> = self.pending.lock().drain(..).collect();
+        for entry in remaining {
+            self.write_failed(&entry.item);
+        }
+    }
+
+    /// Append a segment to failed_transcripts.jsonl
+    fn write_failed(&self, item: &PendingTranscript) {
+        self.failed_count.fetch_add(1, Ordering::SeqCst);
+
+        let path = match self.failed_log_path.read().clone() {
+            Some(p) => p,
+            None => {
+                log::error!(
+                    "No failed-transcript log configured, segment lost: {}",
+                    item.text
+                );
+                return;
+            }
+        };
+
+        let line = match serde_json::to_string(item) {
+            Ok(l) => l,
+            Err(e) => {
+                log::error!("Failed to serialize transcript for failure log: {}", e);
+                return;
+            }
+        };
+
+        let result = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut f| writeln!(f, "{}", line));
+
+        if let Err(e) = result {
+            log::error!("Failed to write {:?}: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_item(text: &str) -> PendingTranscript {
+        PendingTranscript {
+            meeting_id: "m1".to_string(),
+            text: text.to_string(),
+            speaker: None,
+            confidence: 0.9,
+            after_reconnect: false,
+            spoken_at: None,
+            attempts: 1,
+            first_failed_at: Utc::now(),
+            last_error: "database is locked".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let config = RetryConfig::default();
+        assert_eq!(backoff_delay(&config, 1), Duration::from_millis(250));
+        assert_eq!(backoff_delay(&config, 2), Duration::from_millis(500));
+        assert_eq!(backoff_delay(&config, 3), Duration::from_millis(1000));
+        assert_eq!(backoff_delay(&config, 20), Duration::from_millis(10_000));
+    }
+
+    #[test]
+    fn test_failed_segments_written_to_jsonl() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("failed_transcripts.jsonl");
+
+        let queue = TranscriptRetryQueue::new(RetryConfig::default());
+        queue.set_failed_log_path(path.clone());
+        queue.write_failed(&test_item("first"));
+        queue.write_failed(&test_item("second"));
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let parsed: PendingTranscript = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(parsed.text, "second");
+        assert_eq!(queue.stats().failed, 2);
+        assert_eq!(queue.stats().pending, 0);
+    }
+}
@@ -0,0 +1,126 @@
+// noFriction Meetings - Low-Confidence Review
+// Flags final transcript segments the provider wasn't sure about
+//
+// Runs after a final segment is persisted. Segments under the configured
+// confidence threshold get `needs_review = 1` and a `transcript-low-confidence`
+// event so the UI can build a review queue. Very short segments ("yeah",
+// "okay so") are exempt - they are often low-confidence and rarely matter.
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::sync::{Arc, OnceLock};
+use tauri::{AppHandle, Emitter};
+
+use crate::database::DatabaseManager;
+
+/// Default confidence below which a segment is flagged
+pub const DEFAULT_REVIEW_THRESHOLD: f32 = 0.6;
+/// Segments with fewer words than this are never flagged
+const MIN_REVIEW_WORDS: usize = 3;
+
+/// Payload for the `transcript-low-confidence` event
+#[derive(Debug, Clone, Serialize)]
+pub struct LowConfidenceEvent {
+    pub transcript_id: i64,
+    pub meeting_id: String,
+    pub text: String,
+    pub confidence: f32,
+}
+
+pub struct LowConfidenceReview {
+    threshold: RwLock<f32>,
+    app_handle: RwLock<Option<AppHandle>>,
+}
+
+/// Global reviewer shared by all transcription providers
+pub fn low_confidence_review() -> &'static LowConfidenceReview {
+    static REVIEW: OnceLock<LowConfidenceReview> = OnceLock::new();
+    REVIEW.get_or_init(|| LowConfidenceReview {
+        threshold: RwLock::new(DEFAULT_REVIEW_THRESHOLD),
+        app_handle: RwLock::new(None),
+    })
+}
+
+/// Whether a final segment should go to the review queue
+pub fn needs_review(text: &str, confidence: f32, threshold: f32) -> bool {
+    text.split_whitespace().count() >= MIN_REVIEW_WORDS && confidence < threshold
+}
+
+impl LowConfidenceReview {
+    /// Where review events are emitted (set once during app init)
+    pub fn set_app_handle(&self, app: AppHandle) {
+        *self.app_handle.write() = Some(app);
+    }
+
+    pub fn set_threshold(&self, threshold: f32) {
+        *self.threshold.write() = threshold.clamp(0.0, 1.0);
+    }
+
+    pub fn threshold(&self) -> f32 {
+        *self.threshold.read()
+    }
+
+    /// Flag a freshly persisted final segment if its confidence is too low
+    pub async fn check(
+        &self,
+        database: Arc<DatabaseManager>,
+        transcript_id: i64,
+        meeting_id: &str,
+        text: &str,
+        confidence: f32,
+    ) {
+        if !needs_review(text, confidence, self.threshold()) {
+            return;
+        }
+
+        if let Err(e) = database.flag_transcript_for_review(transcript_id).await {
+            log::warn!(
+                "Failed to flag transcript {} for review: {}",
+                transcript_id,
+                e
+            );
+            return;
+        }
+
+        log::debug!(
+            "Transcript {} flagged for review (confidence {:.2})",
+            transcript_id,
+            confidence
+        );
+
+        let app = self.app_handle.read().clone();
+        if let Some(app) = app {
+            let event = LowConfidenceEvent {
+                transcript_id,
+                meeting_id: meeting_id.to_string(),
+                text: text.to_string(),
+                confidence,
+            };
+            if let Err(e) = app.emit("transcript-low-confidence", &event) {
+                log::error!("Failed to emit low-confidence event: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_segments_exempt() {
+        assert!(!needs_review("yeah okay", 0.2, DEFAULT_REVIEW_THRESHOLD));
+        assert!(needs_review(
+            "we ship on cube flow",
+            0.2,
+            DEFAULT_REVIEW_THRESHOLD
+        ));
+    }
+
+    #[test]
+    fn test_threshold_is_exclusive() {
+        let text = "the quarterly numbers look good";
+        assert!(!needs_review(text, 0.6, 0.6));
+        assert!(needs_review(text, 0.59, 0.6));
+    }
+}
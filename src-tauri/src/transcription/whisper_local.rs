@@ -0,0 +1,393 @@
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+use crate::database::DatabaseManager;
+use crate::live_intel_agent::LiveIntelAgent;
+use crate::transcription::{glossary, GlossaryTerm, TranscriptionProvider};
+use crate::usage_ledger::AudioUsage;
+
+/// Samples per second fed to whisper.cpp (16kHz mono)
+const WHISPER_SAMPLE_RATE: usize = 16_000;
+/// Length of a final transcription window
+const WINDOW_SECS: usize = 10;
+/// How often to re-run the partial window for interim results
+const INTERIM_SECS: usize = 3;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptSegment {
+    pub text: String,
+    pub is_final: bool,
+    pub confidence: f32,
+    pub start: f64,
+    pub duration: f64,
+    pub speaker: Option<String>,
+}
+
+/// Shared context handed to the background inference thread
+struct WorkerContext {
+    app: AppHandle,
+    is_active: Arc<AtomicBool>,
+    database: Arc<RwLock<Option<Arc<DatabaseManager>>>>,
+    meeting_id: Arc<RwLock<Option<String>>>,
+    live_intel_agent: Arc<RwLock<Option<Arc<RwLock<LiveIntelAgent>>>>>,
+    /// Glossary terms fed to whisper as the initial prompt
+    glossary_terms: Vec<GlossaryTerm>,
+}
+
+/// Offline transcription using whisper.cpp — no API key or network required
+pub struct WhisperLocalProvider {
+    is_active: Arc<AtomicBool>,
+    audio_tx: Arc<RwLock<Option<mpsc::SyncSender<Vec<f32>>>>>,
+    app_handle: Arc<RwLock<Option<AppHandle>>>,
+    database: Arc<RwLock<Option<Arc<DatabaseManager>>>>,
+    meeting_id: Arc<RwLock<Option<String>>>,
+    live_intel_agent: Arc<RwLock<Option<Arc<RwLock<LiveIntelAgent>>>>>,
+    glossary: Arc<RwLock<Vec<GlossaryTerm>>>,
+}
+
+impl WhisperLocalProvider {
+    pub fn new() -> Self {
+        Self {
+            is_active: Arc::new(AtomicBool::new(false)),
+            audio_tx: Arc::new(RwLock::new(None)),
+            app_handle: Arc::new(RwLock::new(None)),
+            database: Arc::new(RwLock::new(None)),
+            meeting_id: Arc::new(RwLock::new(None)),
+            live_intel_agent: Arc::new(RwLock::new(None)),
+            glossary: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    /// Resolve the ggml model file from settings (explicit path wins over size)
+    async fn resolve_model_path(app: &AppHandle) -> Result<PathBuf, String> {
+        let state: tauri::State<crate::AppState> = app.state();
+        let settings = state
+            .settings
+            .get_all()
+            .await
+            .map_err(|e| format!("Failed to load settings: {}", e))?;
+
+        if let Some(path) = settings.whisper_model_path.filter(|p| !p.is_empty()) {
+            return Ok(PathBuf::from(path));
+        }
+
+        let models_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app data dir: {}", e))?
+            .join("models");
+
+        Ok(models_dir.join(format!("ggml-{}.bin", settings.whisper_model_size)))
+    }
+
+    /// Background inference loop: buffers 16kHz mono PCM and transcribes in windows
+    fn run_worker(model_path: PathBuf, audio_rx: mpsc::Receiver<Vec<f32>>, ctx: WorkerContext) {
+        let whisper = match WhisperContext::new_with_params(
+            &model_path.to_string_lossy(),
+            WhisperContextParameters::default(),
+        ) {
+            Ok(w) => w,
+            Err(e) => {
+                log::error!("Failed to load Whisper model {:?}: {}", model_path, e);
+                ctx.is_active.store(false, Ordering::SeqCst);
+                return;
+            }
+        };
+
+        log::info!("✅ Whisper model loaded: {:?}", model_path);
+
+        // Local inference costs nothing per minute, but is still counted
+        let model_name = model_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut usage = AudioUsage::start("whisper_local", &model_name, WHISPER_SAMPLE_RATE as u32);
+
+        let window_len = WINDOW_SECS * WHISPER_SAMPLE_RATE;
+        let interim_len = INTERIM_SECS * WHISPER_SAMPLE_RATE;
+        let mut buffer: Vec<f32> = Vec::with_capacity(window_len);
+        let mut last_interim_len = 0usize;
+        let mut window_start = 0.0f64;
+
+        while ctx.is_active.load(Ordering::SeqCst) {
+            match audio_rx.recv_timeout(std::time::Duration::from_millis(200)) {
+                Ok(samples) => {
+                    usage.add_samples(samples.len());
+                    buffer.extend(samples);
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+
+            if buffer.len() >= window_len {
+                let window: Vec<f32> = buffer.drain(..window_len).collect();
+                Self::transcribe_window(&whisper, &window, window_start, true, &ctx);
+                window_start += WINDOW_SECS as f64;
+                last_interim_len = 0;
+            } else if buffer.len() >= last_interim_len + interim_len {
+                Self::transcribe_window(&whisper, &buffer, window_start, false, &ctx);
+                last_interim_len = buffer.len();
+            }
+        }
+
+        // Flush whatever is left so the tail of the meeting isn't lost
+        if buffer.len() >= WHISPER_SAMPLE_RATE {
+            Self::transcribe_window(&whisper, &buffer, window_start, true, &ctx);
+        }
+
+        log::info!("Whisper worker stopped");
+    }
+
+    /// Run whisper on one window and route the text through the usual transcript path
+    fn transcribe_window(
+        whisper: &WhisperContext,
+        samples: &[f32],
+        window_start: f64,
+        is_final: bool,
+        ctx: &WorkerContext,
+    ) {
+        let mut state = match whisper.create_state() {
+            Ok(s) => s,
+            Err(e) => {
+                log::error!("Failed to create Whisper state: {}", e);
+                return;
+            }
+        };
+
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        params.set_language(Some("en"));
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_special(false);
+        params.set_print_timestamps(false);
+
+        // Whisper biases towards vocabulary that appears in the prompt
+        let prompt = glossary::term_list(&ctx.glossary_terms).join(", ");
+        if !prompt.is_empty() {
+            params.set_initial_prompt(&prompt);
+        }
+
+        if let Err(e) = state.full(params, samples) {
+            log::error!("Whisper inference failed: {}", e);
+            return;
+        }
+
+        let num_segments = state.full_n_segments().unwrap_or(0);
+        let text = (0..num_segments)
+            .filter_map(|i| state.full_get_segment_text(i).ok())
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim()
+            .to_string();
+
+        if text.is_empty() {
+            return;
+        }
+
+        let segment = TranscriptSegment {
+            text: text.clone(),
+            is_final,
+            confidence: 0.9,
+            start: window_start,
+            duration: samples.len() as f64 / WHISPER_SAMPLE_RATE as f64,
+            speaker: None,
+        };
+
+        if is_final {
+            log::info!("📝 TRANSCRIPT [FINAL]: {}", text);
+        } else {
+            log::debug!("📝 transcript [interim]: {}", text);
+        }
+
+        // Emit to frontend
+        if let Err(e) = ctx.app.emit("live_transcript", &segment) {
+            log::error!("Failed to emit transcript: {}", e);
+        }
+
+        // Feed finalized segments to LiveIntelAgent
+        if is_final {
+            if let Some(agent) = ctx.live_intel_agent.read().as_ref() {
+                crate::transcription::feed_live_intel(
+                    &ctx.app,
+                    agent,
+                    ctx.database.read().as_ref().cloned(),
+                    ctx.meeting_id.read().as_ref().cloned(),
+                    &segment.text,
+                    None,
+                );
+            }
+        }
+
+        // Save FINAL transcripts
+        if is_final {
+            if let Some(db) = ctx.database.read().as_ref().cloned() {
+                if let Some(mid) = ctx.meeting_id.read().as_ref().cloned() {
+                    tauri::async_runtime::spawn(crate::transcription::save_final_transcript(
+                        db,
+                        crate::transcription::FinalSegment {
+                            meeting_id: mid,
+                            text,
+                            confidence: 0.9,
+                            ..Default::default()
+                        },
+                    ));
+                }
+            }
+        }
+    }
+
+    fn resample_to_16k_mono(samples: &[f32], from_rate: u32, channels: u16) -> Vec<f32> {
+        if samples.is_empty() {
+            return vec![];
+        }
+
+        let mono: Vec<f32> = if channels > 1 {
+            samples
+                .chunks(channels as usize)
+                .map(|chunk| {
+                    if chunk.len() == channels as usize {
+                        chunk.iter().sum::<f32>() / channels as f32
+                    } else {
+                        chunk[0]
+                    }
+                })
+                .collect()
+        } else {
+            samples.to_vec()
+        };
+
+        if from_rate == 16000 {
+            return mono;
+        }
+
+        let ratio = 16000.0 / from_rate as f64;
+        let new_len = (mono.len() as f64 * ratio) as usize;
+
+        if new_len == 0 {
+            return vec![];
+        }
+
+        let mut resampled = Vec::with_capacity(new_len);
+        for i in 0..new_len {
+            let src_idx = i as f64 / ratio;
+            let idx = src_idx.floor() as usize;
+            let frac = src_idx - idx as f64;
+
+            let sample = if idx + 1 < mono.len() {
+                mono[idx] * (1.0 - frac as f32) + mono[idx + 1] * frac as f32
+            } else if idx < mono.len() {
+                mono[idx]
+            } else {
+                0.0
+            };
+
+            resampled.push(sample);
+        }
+
+        resampled
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for WhisperLocalProvider {
+    fn start(&self) {
+        let app = match self.app_handle.read().clone() {
+            Some(a) => a,
+            None => {
+                log::warn!("Cannot start Whisper: no app handle");
+                return;
+            }
+        };
+
+        if self.is_active.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        // ~30s of buffered audio before we start dropping batches
+        let (audio_tx, audio_rx) = mpsc::sync_channel::<Vec<f32>>(1500);
+        *self.audio_tx.write() = Some(audio_tx);
+
+        let ctx = WorkerContext {
+            app: app.clone(),
+            is_active: self.is_active.clone(),
+            database: self.database.clone(),
+            meeting_id: self.meeting_id.clone(),
+            live_intel_agent: self.live_intel_agent.clone(),
+            glossary_terms: self.glossary.read().clone(),
+        };
+
+        // Model path lives in settings (needs async)
+        tokio::spawn(async move {
+            let model_path = match Self::resolve_model_path(&app).await {
+                Ok(p) => p,
+                Err(e) => {
+                    log::error!("Whisper model path error: {}", e);
+                    ctx.is_active.store(false, Ordering::SeqCst);
+                    return;
+                }
+            };
+
+            if !model_path.exists() {
+                log::error!("Whisper model not found at {:?}", model_path);
+                ctx.is_active.store(false, Ordering::SeqCst);
+                return;
+            }
+
+            std::thread::spawn(move || Self::run_worker(model_path, audio_rx, ctx));
+        });
+
+        log::info!("🎙️ Whisper (local) transcription started");
+    }
+
+    fn stop(&self) {
+        self.is_active.store(false, Ordering::SeqCst);
+        *self.audio_tx.write() = None;
+        log::info!("Whisper (local) transcription stopped");
+    }
+
+    fn process_audio(&self, samples: &[f32], sample_rate: u32, channels: u16) {
+        if samples.is_empty() || !self.is_active.load(Ordering::SeqCst) {
+            return;
+        }
+
+        if let Some(tx) = self.audio_tx.read().as_ref() {
+            let channels = if channels == 0 { 1 } else { channels };
+            let resampled = Self::resample_to_16k_mono(samples, sample_rate, channels);
+            if tx.try_send(resampled).is_err() {
+                log::trace!("Whisper audio queue full, batch dropped");
+            }
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active.load(Ordering::SeqCst)
+    }
+
+    fn set_api_key(&self, _key: String) {
+        // Local model - no API key needed
+    }
+
+    fn set_glossary(&self, terms: Vec<GlossaryTerm>) {
+        *self.glossary.write() = terms;
+    }
+
+    fn set_context(
+        &self,
+        app_handle: AppHandle,
+        database: Arc<DatabaseManager>,
+        meeting_id: String,
+        live_intel_agent: Arc<RwLock<LiveIntelAgent>>,
+    ) {
+        *self.app_handle.write() = Some(app_handle);
+        *self.database.write() = Some(database);
+        *self.meeting_id.write() = Some(meeting_id);
+        *self.live_intel_agent.write() = Some(live_intel_agent);
+    }
+}
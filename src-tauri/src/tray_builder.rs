@@ -1,11 +1,25 @@
 // noFriction Meetings - System Tray Builder
 // Creates system tray icon with right-click context menu
+//
+// The icon, title and menu follow app state: capture engine, ambient capture
+// and the transcription health monitor publish into `tray_status()`, and one
+// task redraws the tray when it changes. Menu actions run in the backend so
+// they work before the main window has been shown.
 
+use crate::shortcuts::{label_with_binding, run_action, ShortcutAction};
+use crate::AppState;
+use std::sync::OnceLock;
+use std::time::Duration;
 use tauri::{
-    menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem},
+    image::Image,
+    menu::{
+        CheckMenuItemBuilder, Menu, MenuBuilder, MenuItemBuilder, PredefinedMenuItem,
+        SubmenuBuilder,
+    },
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    AppHandle, Emitter, Manager, Runtime,
+    AppHandle, Emitter, Manager,
 };
+use tokio::sync::watch;
 
 /// Tray menu item IDs
 pub mod tray_ids {
@@ -13,43 +27,219 @@ pub mod tray_ids {
     pub const START_RECORDING: &str = "tray_start_recording";
     pub const STOP_RECORDING: &str = "tray_stop_recording";
     pub const PAUSE_RECORDING: &str = "tray_pause_recording";
+    pub const PIN_MOMENT: &str = "tray_pin_moment";
+    pub const TOGGLE_GENIE: &str = "tray_toggle_genie";
+    pub const QUICK_NOTE: &str = "tray_quick_note";
 
     // Capture Modes
     pub const MODE_AMBIENT: &str = "tray_mode_ambient";
     pub const MODE_MEETING: &str = "tray_mode_meeting";
     pub const MODE_PAUSED: &str = "tray_mode_paused";
 
+    // Activity theme items are THEME_PREFIX + theme name
+    pub const THEME_PREFIX: &str = "tray_theme:";
+
     // Quick Actions
     pub const SHOW_WINDOW: &str = "tray_show_window";
+    pub const OPEN_LAST_MEETING: &str = "tray_open_last_meeting";
     pub const OPEN_INSIGHTS: &str = "tray_open_insights";
     pub const OPEN_KB: &str = "tray_open_kb";
     pub const OPEN_SETTINGS: &str = "tray_open_settings";
+    pub const REVIEW_FLASHCARDS: &str = "tray_review_flashcards";
 
     // App Controls
     pub const QUIT: &str = "tray_quit";
 }
 
-/// Build the system tray with right-click context menu
-pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
-    const TRAY_ID: &str = "nofriction-main-tray";
+const TRAY_ID: &str = "nofriction-main-tray";
+
+/// Elapsed recording time in the tray title is redrawn at most this often
+const ELAPSED_REFRESH: Duration = Duration::from_secs(10);
+
+/// What the app is doing, as far as the tray cares
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrayStatus {
+    pub recording: bool,
+    pub paused: bool,
+    /// Ambient (background) capture is running
+    pub ambient: bool,
+    /// Transcription is reconnecting or gave up during a recording
+    pub transcription_degraded: bool,
+    pub active_theme: Option<String>,
+    /// Flashcards due for review
+    pub cards_due: u32,
+}
+
+/// Icon state, most urgent first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TrayMode {
+    Degraded,
+    Paused,
+    Recording,
+    Ambient,
+    Idle,
+}
+
+impl TrayStatus {
+    fn mode(&self) -> TrayMode {
+        match self {
+            s if s.recording && s.transcription_degraded => TrayMode::Degraded,
+            s if s.recording && s.paused => TrayMode::Paused,
+            s if s.recording => TrayMode::Recording,
+            s if s.ambient => TrayMode::Ambient,
+            _ => TrayMode::Idle,
+        }
+    }
+}
+
+impl TrayMode {
+    /// Status dot drawn over the app icon; idle shows the plain icon
+    fn badge_color(&self) -> Option<[u8; 3]> {
+        match self {
+            TrayMode::Degraded => Some([245, 158, 11]),
+            TrayMode::Paused => Some([156, 163, 175]),
+            TrayMode::Recording => Some([239, 68, 68]),
+            TrayMode::Ambient => Some([59, 130, 246]),
+            TrayMode::Idle => None,
+        }
+    }
+
+    fn describe(&self) -> &'static str {
+        match self {
+            TrayMode::Degraded => "Recording (transcription degraded)",
+            TrayMode::Paused => "Recording paused",
+            TrayMode::Recording => "Recording",
+            TrayMode::Ambient => "Ambient capture",
+            TrayMode::Idle => "Idle",
+        }
+    }
+}
 
+static TRAY_STATUS: OnceLock<watch::Sender<TrayStatus>> = OnceLock::new();
+
+/// Current tray status; subscribe to be told when it changes
+pub fn tray_status() -> &'static watch::Sender<TrayStatus> {
+    TRAY_STATUS.get_or_init(|| watch::channel(TrayStatus::default()).0)
+}
+
+/// Change the tray status; the tray only redraws if something changed
+pub fn update_tray_status(update: impl FnOnce(&mut TrayStatus)) {
+    tray_status().send_if_modified(|status| {
+        let before = status.clone();
+        update(status);
+        *status != before
+    });
+}
+
+/// Build the system tray with right-click context menu
+pub fn create_tray(app: &AppHandle) -> tauri::Result<()> {
     // Check if tray already exists (singleton pattern)
     if app.tray_by_id(TRAY_ID).is_some() {
         log::warn!("⚠️ System tray already exists, skipping creation");
         return Ok(());
     }
 
-    // Build the context menu
-    let menu = MenuBuilder::new(app)
+    let status = tray_status().borrow().clone();
+    let menu = build_tray_menu(app, &status)?;
+
+    // Create the tray icon with unique ID
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
+        .icon(app.default_window_icon().unwrap().clone())
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| {
+            handle_tray_event(app, event.id().as_ref());
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                // Left click: show/focus main window
+                show_main_window(tray.app_handle());
+            }
+        })
+        .build(app)?;
+
+    spawn_tray_updater(app.clone());
+
+    log::info!("✅ System tray created with context menu (ID: {})", TRAY_ID);
+    Ok(())
+}
+
+/// Tray context menu for `status`; item labels carry the current global
+/// shortcuts
+fn build_tray_menu(app: &AppHandle, status: &TrayStatus) -> tauri::Result<Menu<tauri::Wry>> {
+    let recording_item = if status.recording {
+        MenuItemBuilder::with_id(
+            tray_ids::STOP_RECORDING,
+            label_with_binding("⏹  Stop Recording", ShortcutAction::ToggleRecording),
+        )
+    } else {
+        MenuItemBuilder::with_id(
+            tray_ids::START_RECORDING,
+            label_with_binding("⏺  Start Recording", ShortcutAction::ToggleRecording),
+        )
+    };
+    let pause_label = if status.paused {
+        "▶️ Resume Capture"
+    } else {
+        "⏸  Pause Capture"
+    };
+
+    let mut themes = SubmenuBuilder::new(app, "🎨 Activity Theme");
+    for theme in crate::commands::ACTIVITY_THEMES {
+        themes = themes.item(
+            &CheckMenuItemBuilder::with_id(
+                format!("{}{}", tray_ids::THEME_PREFIX, theme),
+                theme_label(theme),
+            )
+            .checked(status.active_theme.as_deref() == Some(theme))
+            .build(app)?,
+        );
+    }
+
+    let mut menu = MenuBuilder::new(app)
         // Header
         .text("nofriction_header", "🎯 noFriction Meetings")
-        .separator()
+        .text("tray_status", status.mode().describe());
+    if let Some(label) = cards_due_label(status.cards_due) {
+        let review = MenuItemBuilder::with_id(tray_ids::REVIEW_FLASHCARDS, label).build(app)?;
+        menu = menu.item(&review);
+    }
+
+    menu.separator()
         // Recording Controls
+        .item(&recording_item.build(app)?)
         .item(
-            &MenuItemBuilder::with_id(tray_ids::START_RECORDING, "⏺  Start Recording")
+            &MenuItemBuilder::with_id(tray_ids::PAUSE_RECORDING, pause_label)
+                .enabled(status.recording)
                 .build(app)?,
         )
-        .item(&MenuItemBuilder::with_id(tray_ids::STOP_RECORDING, "⏹  Stop Recording").build(app)?)
+        .item(
+            &MenuItemBuilder::with_id(
+                tray_ids::PIN_MOMENT,
+                label_with_binding("📌 Pin Moment", ShortcutAction::PinMoment),
+            )
+            .enabled(status.recording)
+            .build(app)?,
+        )
+        .item(
+            &MenuItemBuilder::with_id(
+                tray_ids::QUICK_NOTE,
+                label_with_binding("📝 Quick Note…", ShortcutAction::QuickNote),
+            )
+            .build(app)?,
+        )
+        .item(
+            &MenuItemBuilder::with_id(
+                tray_ids::TOGGLE_GENIE,
+                label_with_binding("🧞 Genie Mode", ShortcutAction::ToggleGenie),
+            )
+            .build(app)?,
+        )
         .separator()
         // Capture Mode Submenu
         .text("mode_header", "📡 Capture Mode")
@@ -62,6 +252,7 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
                 .build(app)?,
         )
         .item(&MenuItemBuilder::with_id(tray_ids::MODE_PAUSED, "  ⏸️ Paused").build(app)?)
+        .item(&themes.build()?)
         .separator()
         // Quick Access
         .item(
@@ -69,6 +260,10 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
                 .accelerator("CmdOrCtrl+Shift+N")
                 .build(app)?,
         )
+        .item(
+            &MenuItemBuilder::with_id(tray_ids::OPEN_LAST_MEETING, "🕘 Open Last Meeting")
+                .build(app)?,
+        )
         .item(
             &MenuItemBuilder::with_id(tray_ids::OPEN_INSIGHTS, "📊 Activity Insights")
                 .build(app)?,
@@ -77,50 +272,189 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
         .item(&MenuItemBuilder::with_id(tray_ids::OPEN_SETTINGS, "⚙️ Settings").build(app)?)
         .separator()
         .item(&PredefinedMenuItem::quit(app, Some("Quit noFriction"))?)
-        .build()?;
+        .build()
+}
 
-    // Create the tray icon with unique ID
-    let _tray = TrayIconBuilder::with_id(TRAY_ID)
-        .icon(app.default_window_icon().unwrap().clone())
-        .menu(&menu)
-        .show_menu_on_left_click(false)
-        .on_menu_event(|app, event| {
-            handle_tray_event(app, event.id().as_ref());
+/// "🃏 12 cards due", or None when nothing is due
+fn cards_due_label(due: u32) -> Option<String> {
+    match due {
+        0 => None,
+        1 => Some("🃏 1 card due".to_string()),
+        n => Some(format!("🃏 {} cards due", n)),
+    }
+}
+
+/// "product_dev" → "Product Dev"
+fn theme_label(theme: &str) -> String {
+    theme
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
         })
-        .on_tray_icon_event(|tray, event| {
-            if let TrayIconEvent::Click {
-                button: MouseButton::Left,
-                button_state: MouseButtonState::Up,
-                ..
-            } = event
-            {
-                // Left click: show/focus main window
-                if let Some(window) = tray.app_handle().get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Rebuild the tray menu, e.g. after shortcut bindings change
+pub fn refresh_tray_menu(app: &AppHandle) -> tauri::Result<()> {
+    match app.tray_by_id(TRAY_ID) {
+        Some(tray) => {
+            let status = tray_status().borrow().clone();
+            tray.set_menu(Some(build_tray_menu(app, &status)?))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Redraw the tray whenever the status changes, plus the elapsed timer while
+/// recording
+fn spawn_tray_updater(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut changes = tray_status().subscribe();
+        loop {
+            let status = changes.borrow_and_update().clone();
+            if let Err(e) = redraw_tray(&app, &status) {
+                log::warn!("Failed to update tray: {}", e);
+            }
+
+            let ticking = status.recording && !status.paused;
+            loop {
+                if !ticking {
+                    if changes.changed().await.is_err() {
+                        return;
+                    }
+                    break;
+                }
+                tokio::select! {
+                    changed = changes.changed() => {
+                        if changed.is_err() {
+                            return;
+                        }
+                        break;
+                    }
+                    _ = tokio::time::sleep(ELAPSED_REFRESH) => {
+                        if let Some(tray) = app.tray_by_id(TRAY_ID) {
+                            let _ = tray.set_title(tray_title(&status, recording_elapsed(&app)));
+                        }
+                    }
                 }
             }
-        })
-        .build(app)?;
+        }
+    });
+}
 
-    log::info!("✅ System tray created with context menu (ID: {})", TRAY_ID);
-    Ok(())
+/// Icon, title, tooltip and menu for `status`
+fn redraw_tray(app: &AppHandle, status: &TrayStatus) -> tauri::Result<()> {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return Ok(());
+    };
+    let mode = status.mode();
+    if let Some(base) = app.default_window_icon() {
+        let icon = match mode.badge_color() {
+            Some(color) => Image::new_owned(
+                badge_rgba(base.rgba(), base.width(), base.height(), color),
+                base.width(),
+                base.height(),
+            ),
+            None => base.clone(),
+        };
+        tray.set_icon(Some(icon))?;
+    }
+    tray.set_title(tray_title(status, recording_elapsed(app)))?;
+    tray.set_tooltip(Some(format!("noFriction Meetings: {}", mode.describe())))?;
+    tray.set_menu(Some(build_tray_menu(app, status)?))
+}
+
+fn recording_elapsed(app: &AppHandle) -> u64 {
+    app.try_state::<AppState>()
+        .map(|state| state.capture_engine.read().get_status().duration_seconds)
+        .unwrap_or(0)
+}
+
+/// Menu bar text next to the icon: the elapsed time while recording
+fn tray_title(status: &TrayStatus, elapsed_secs: u64) -> Option<String> {
+    let symbol = match status.mode() {
+        TrayMode::Degraded => "⚠",
+        TrayMode::Paused => "⏸",
+        TrayMode::Recording => "⏺",
+        TrayMode::Ambient | TrayMode::Idle => return None,
+    };
+    Some(format!("{} {}", symbol, format_elapsed(elapsed_secs)))
+}
+
+/// "4:05" under an hour, "1:02:05" after
+fn format_elapsed(secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// Copy of an RGBA icon with a filled status dot in the bottom-right corner
+fn badge_rgba(rgba: &[u8], width: u32, height: u32, color: [u8; 3]) -> Vec<u8> {
+    let mut out = rgba.to_vec();
+    let radius = width.min(height) as f32 / 4.0;
+    let (cx, cy) = (width as f32 - radius, height as f32 - radius);
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = (x as f32 + 0.5 - cx, y as f32 + 0.5 - cy);
+            if dx * dx + dy * dy <= radius * radius {
+                let i = ((y * width + x) * 4) as usize;
+                out[i..i + 4].copy_from_slice(&[color[0], color[1], color[2], 255]);
+            }
+        }
+    }
+    out
 }
 
 /// Handle tray menu events
-fn handle_tray_event<R: Runtime>(app: &AppHandle<R>, id: &str) {
+fn handle_tray_event(app: &AppHandle, id: &str) {
     log::info!("Tray menu event: {}", id);
 
+    if let Some(theme) = id.strip_prefix(tray_ids::THEME_PREFIX) {
+        let theme = theme.to_string();
+        run_backend(app, "set theme", move |app| async move {
+            let state = app.state::<AppState>();
+            crate::commands::apply_active_theme(&state, &theme).await
+        });
+        // A check item flips itself on click; redraw from the real state
+        let _ = refresh_tray_menu(app);
+        return;
+    }
+
     match id {
         // Recording Controls
         tray_ids::START_RECORDING => {
-            emit_to_frontend(app, "tray:start_recording");
+            run_backend(app, "start recording", |app| async move {
+                crate::commands::start_recording_in_background(app)
+                    .await
+                    .map(|_| ())
+            });
         }
         tray_ids::STOP_RECORDING => {
-            emit_to_frontend(app, "tray:stop_recording");
+            run_backend(app, "stop recording", |app| async move {
+                crate::commands::stop_recording_in_background(app).await
+            });
         }
         tray_ids::PAUSE_RECORDING => {
-            emit_to_frontend(app, "tray:pause_recording");
+            run_backend(app, "pause recording", |app| async move {
+                crate::commands::toggle_pause_in_background(app).await
+            });
+        }
+        tray_ids::PIN_MOMENT => {
+            run_action(app, ShortcutAction::PinMoment);
+        }
+        tray_ids::TOGGLE_GENIE => {
+            run_action(app, ShortcutAction::ToggleGenie);
+        }
+        tray_ids::QUICK_NOTE => {
+            run_action(app, ShortcutAction::QuickNote);
         }
 
         // Capture Modes
@@ -136,31 +470,39 @@ fn handle_tray_event<R: Runtime>(app: &AppHandle<R>, id: &str) {
 
         // Navigation
         tray_ids::SHOW_WINDOW => {
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.show();
-                let _ = window.set_focus();
-            }
+            show_main_window(app);
+        }
+        tray_ids::OPEN_LAST_MEETING => {
+            run_backend(app, "open last meeting", |app| async move {
+                let state = app.state::<AppState>();
+                let meeting = state
+                    .database
+                    .list_meetings(1)
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .into_iter()
+                    .next()
+                    .ok_or("No meetings yet")?;
+                show_main_window(&app);
+                app.emit("tray:open_meeting", &meeting.id)
+                    .map_err(|e| e.to_string())
+            });
         }
         tray_ids::OPEN_INSIGHTS => {
             emit_to_frontend(app, "menu:insights");
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.show();
-                let _ = window.set_focus();
-            }
+            show_main_window(app);
         }
         tray_ids::OPEN_KB => {
             emit_to_frontend(app, "menu:search");
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.show();
-                let _ = window.set_focus();
-            }
+            show_main_window(app);
         }
         tray_ids::OPEN_SETTINGS => {
             emit_to_frontend(app, "menu:settings");
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.show();
-                let _ = window.set_focus();
-            }
+            show_main_window(app);
+        }
+        tray_ids::REVIEW_FLASHCARDS => {
+            emit_to_frontend(app, "menu:flashcards");
+            show_main_window(app);
         }
 
         _ => {
@@ -169,9 +511,63 @@ fn handle_tray_event<R: Runtime>(app: &AppHandle<R>, id: &str) {
     }
 }
 
+/// Run a tray action against AppState in the background; ignored (with a
+/// log line) while the app is still initializing
+fn run_backend<F, Fut>(app: &AppHandle, what: &'static str, action: F)
+where
+    F: FnOnce(AppHandle) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = Result<(), String>> + Send + 'static,
+{
+    if app.try_state::<AppState>().is_none() {
+        log::info!("Ignoring tray action ({}): app is still initializing", what);
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = action(app).await {
+            log::warn!("Tray action ({}) failed: {}", what, e);
+        }
+    });
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
 /// Helper to emit events to frontend
-fn emit_to_frontend<R: Runtime>(app: &AppHandle<R>, event: &str) {
+fn emit_to_frontend(app: &AppHandle, event: &str) {
     if let Err(e) = app.emit(event, ()) {
         log::error!("Failed to emit {}: {}", event, e);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tray_mode_title_and_badge() {
+        let mut status = TrayStatus::default();
+        assert_eq!(status.mode(), TrayMode::Idle);
+        assert_eq!(tray_title(&status, 90), None);
+
+        status.ambient = true;
+        assert_eq!(status.mode(), TrayMode::Ambient);
+        status.recording = true;
+        assert_eq!(tray_title(&status, 245).as_deref(), Some("⏺ 4:05"));
+        status.transcription_degraded = true;
+        assert_eq!(tray_title(&status, 3725).as_deref(), Some("⚠ 1:02:05"));
+
+        assert_eq!(theme_label("product_dev"), "Product Dev");
+        assert_eq!(cards_due_label(0), None);
+        assert_eq!(cards_due_label(12).as_deref(), Some("🃏 12 cards due"));
+
+        let icon = badge_rgba(&[0u8; 8 * 8 * 4], 8, 8, [239, 68, 68]);
+        let pixel = |x: usize, y: usize| &icon[(y * 8 + x) * 4..(y * 8 + x) * 4 + 4];
+        assert_eq!(pixel(6, 6), &[239, 68, 68, 255]);
+        assert_eq!(pixel(0, 0), &[0, 0, 0, 0]);
+    }
+}
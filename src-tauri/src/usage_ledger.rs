@@ -0,0 +1,463 @@
+// noFriction Meetings - Usage Ledger
+// Records every outbound AI, VLM, embedding and transcription call so the
+// monthly bill can be estimated.
+//
+// Features:
+// - Append-only `usage_ledger` rows (provider, model, tokens or audio seconds)
+// - Fire-and-forget recording from any client, including non-async threads
+// - Summaries grouped by provider and day, priced from per-provider settings
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Row, Sqlite};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+/// One outbound call (or one streamed transcription session)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub provider: String,  // e.g. "ollama", "thebrain", "pinecone", "deepgram"
+    pub operation: String, // "chat", "completion", "embedding", "vision", "transcription"
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub audio_seconds: f64, // STT only
+    pub duration_ms: u64,
+    pub success: bool,
+}
+
+/// Per-provider prices used for the dollar estimate
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsagePrice {
+    pub provider: String,
+    #[serde(default)]
+    pub input_per_1k_tokens: f64,
+    #[serde(default)]
+    pub output_per_1k_tokens: f64,
+    #[serde(default)]
+    pub per_audio_minute: f64,
+}
+
+impl UsagePrice {
+    fn cost(&self, totals: &UsageTotals) -> f64 {
+        totals.input_tokens as f64 / 1000.0 * self.input_per_1k_tokens
+            + totals.output_tokens as f64 / 1000.0 * self.output_per_1k_tokens
+            + totals.audio_seconds / 60.0 * self.per_audio_minute
+    }
+}
+
+/// Aggregated usage; `estimated_cost_usd` is None when the provider has no price
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageTotals {
+    pub calls: u64,
+    pub failures: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub audio_seconds: f64,
+    pub duration_ms: u64,
+    pub estimated_cost_usd: Option<f64>,
+}
+
+impl UsageTotals {
+    fn add(&mut self, other: &UsageTotals) {
+        self.calls += other.calls;
+        self.failures += other.failures;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.audio_seconds += other.audio_seconds;
+        self.duration_ms += other.duration_ms;
+        if let Some(cost) = other.estimated_cost_usd {
+            *self.estimated_cost_usd.get_or_insert(0.0) += cost;
+        }
+    }
+}
+
+/// Usage of one provider on one UTC day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyUsage {
+    pub day: String, // YYYY-MM-DD
+    pub provider: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+/// Usage of one provider over the whole period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderUsage {
+    pub provider: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageSummary {
+    pub period: String,
+    pub since: Option<DateTime<Utc>>,
+    pub by_day: Vec<DailyUsage>,
+    pub by_provider: Vec<ProviderUsage>,
+    pub total: UsageTotals,
+    /// Providers with usage but no configured price
+    pub unpriced_providers: Vec<String>,
+}
+
+/// Start of a summary period: "today", "week" (7 days), "month" (month to
+/// date), "30d" or "all"
+pub fn period_start(period: &str, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, String> {
+    let midnight = |d: DateTime<Utc>| {
+        Utc.with_ymd_and_hms(d.year(), d.month(), d.day(), 0, 0, 0)
+            .single()
+    };
+    match period {
+        "today" => Ok(midnight(now)),
+        "week" => Ok(midnight(now - Duration::days(6))),
+        "month" => Ok(Utc
+            .with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+            .single()),
+        "30d" => Ok(midnight(now - Duration::days(29))),
+        "all" => Ok(None),
+        other => Err(format!("Unknown usage period: {}", other)),
+    }
+}
+
+/// Rough token count for providers that do not report one (~4 chars/token)
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64).div_ceil(4)
+}
+
+/// Process-wide usage ledger
+pub struct UsageLedger {
+    pool: RwLock<Option<Pool<Sqlite>>>,
+    prices: RwLock<Vec<UsagePrice>>,
+}
+
+pub fn usage_ledger() -> &'static UsageLedger {
+    static LEDGER: OnceLock<UsageLedger> = OnceLock::new();
+    LEDGER.get_or_init(|| UsageLedger {
+        pool: RwLock::new(None),
+        prices: RwLock::new(Vec::new()),
+    })
+}
+
+impl UsageLedger {
+    /// Start recording into the app database (calls made earlier are dropped)
+    pub fn init(&self, pool: Pool<Sqlite>) {
+        *self.pool.write() = Some(pool);
+    }
+
+    pub fn set_prices(&self, prices: Vec<UsagePrice>) {
+        *self.prices.write() = prices;
+    }
+
+    pub fn prices(&self) -> Vec<UsagePrice> {
+        self.prices.read().clone()
+    }
+
+    /// Queue a row without blocking the caller
+    pub fn record(&self, record: UsageRecord) {
+        let Some(pool) = self.pool.read().clone() else {
+            return;
+        };
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = insert_record(&pool, &record).await {
+                log::warn!("Failed to record {} usage: {}", record.provider, e);
+            }
+        });
+    }
+
+    pub async fn summary(&self, period: &str) -> Result<UsageSummary, String> {
+        let pool = self
+            .pool
+            .read()
+            .clone()
+            .ok_or_else(|| "Usage ledger not initialized".to_string())?;
+        let since = period_start(period, Utc::now())?;
+        let mut summary = query_summary(&pool, since, &self.prices()).await?;
+        summary.period = period.to_string();
+        Ok(summary)
+    }
+}
+
+/// Times one call and records it when finished
+pub struct UsageCall {
+    provider: &'static str,
+    operation: &'static str,
+    model: String,
+    started: Instant,
+}
+
+impl UsageCall {
+    pub fn start(provider: &'static str, operation: &'static str, model: &str) -> Self {
+        Self {
+            provider,
+            operation,
+            model: model.to_string(),
+            started: Instant::now(),
+        }
+    }
+
+    pub fn finish(self, input_tokens: u64, output_tokens: u64, success: bool) {
+        self.record(input_tokens, output_tokens, 0.0, success);
+    }
+
+    pub fn finish_audio(self, audio_seconds: f64, success: bool) {
+        self.record(0, 0, audio_seconds, success);
+    }
+
+    fn record(self, input_tokens: u64, output_tokens: u64, audio_seconds: f64, success: bool) {
+        usage_ledger().record(UsageRecord {
+            provider: self.provider.to_string(),
+            operation: self.operation.to_string(),
+            model: self.model,
+            input_tokens,
+            output_tokens,
+            audio_seconds,
+            duration_ms: self.started.elapsed().as_millis() as u64,
+            success,
+        });
+    }
+}
+
+/// Counts the audio streamed during one transcription session and records it
+/// when dropped, so every exit path of a provider's send loop is covered
+pub struct AudioUsage {
+    call: Option<UsageCall>,
+    samples: u64,
+    sample_rate: u32,
+    failed: bool,
+}
+
+impl AudioUsage {
+    pub fn start(provider: &'static str, model: &str, sample_rate: u32) -> Self {
+        Self {
+            call: Some(UsageCall::start(provider, "transcription", model)),
+            samples: 0,
+            sample_rate,
+            failed: false,
+        }
+    }
+
+    /// Mono samples sent to the provider at the session sample rate
+    pub fn add_samples(&mut self, count: usize) {
+        self.samples += count as u64;
+    }
+
+    pub fn mark_failed(&mut self) {
+        self.failed = true;
+    }
+}
+
+impl Drop for AudioUsage {
+    fn drop(&mut self) {
+        if self.samples == 0 || self.sample_rate == 0 {
+            return;
+        }
+        if let Some(call) = self.call.take() {
+            call.finish_audio(self.samples as f64 / self.sample_rate as f64, !self.failed);
+        }
+    }
+}
+
+async fn insert_record(pool: &Pool<Sqlite>, record: &UsageRecord) -> Result<(), String> {
+    sqlx::query(
+        r#"
+        INSERT INTO usage_ledger
+            (provider, operation, model, input_tokens, output_tokens, audio_seconds,
+             duration_ms, success, timestamp)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&record.provider)
+    .bind(&record.operation)
+    .bind(&record.model)
+    .bind(record.input_tokens as i64)
+    .bind(record.output_tokens as i64)
+    .bind(record.audio_seconds)
+    .bind(record.duration_ms as i64)
+    .bind(record.success)
+    .bind(Utc::now().to_rfc3339())
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to insert usage: {}", e))?;
+    Ok(())
+}
+
+async fn query_summary(
+    pool: &Pool<Sqlite>,
+    since: Option<DateTime<Utc>>,
+    prices: &[UsagePrice],
+) -> Result<UsageSummary, String> {
+    // RFC 3339 UTC timestamps sort lexically, so the first 10 chars are the day
+    let rows = sqlx::query(
+        r#"
+        SELECT substr(timestamp, 1, 10) AS day, provider,
+               COUNT(*) AS calls,
+               SUM(CASE WHEN success THEN 0 ELSE 1 END) AS failures,
+               SUM(input_tokens) AS input_tokens,
+               SUM(output_tokens) AS output_tokens,
+               SUM(audio_seconds) AS audio_seconds,
+               SUM(duration_ms) AS duration_ms
+        FROM usage_ledger
+        WHERE timestamp >= ?
+        GROUP BY day, provider
+        ORDER BY day, provider
+        "#,
+    )
+    .bind(since.map(|s| s.to_rfc3339()).unwrap_or_default())
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query usage: {}", e))?;
+
+    let mut by_day = Vec::with_capacity(rows.len());
+    let mut by_provider: BTreeMap<String, UsageTotals> = BTreeMap::new();
+    let mut total = UsageTotals::default();
+    let mut unpriced_providers = Vec::new();
+
+    for row in rows {
+        let provider: String = row.get("provider");
+        let mut totals = UsageTotals {
+            calls: row.get::<i64, _>("calls") as u64,
+            failures: row.get::<i64, _>("failures") as u64,
+            input_tokens: row.get::<i64, _>("input_tokens") as u64,
+            output_tokens: row.get::<i64, _>("output_tokens") as u64,
+            audio_seconds: row.get("audio_seconds"),
+            duration_ms: row.get::<i64, _>("duration_ms") as u64,
+            estimated_cost_usd: None,
+        };
+        match prices.iter().find(|p| p.provider == provider) {
+            Some(price) => totals.estimated_cost_usd = Some(price.cost(&totals)),
+            None if !unpriced_providers.contains(&provider) => {
+                unpriced_providers.push(provider.clone())
+            }
+            None => {}
+        }
+
+        by_provider
+            .entry(provider.clone())
+            .or_default()
+            .add(&totals);
+        total.add(&totals);
+        by_day.push(DailyUsage {
+            day: row.get("day"),
+            provider,
+            totals,
+        });
+    }
+
+    Ok(UsageSummary {
+        period: String::new(),
+        since,
+        by_day,
+        by_provider: by_provider
+            .into_iter()
+            .map(|(provider, totals)| ProviderUsage { provider, totals })
+            .collect(),
+        total,
+        unpriced_providers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> Pool<Sqlite> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        sqlx::query(
+            r#"
+            CREATE TABLE usage_ledger (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                provider TEXT NOT NULL,
+                operation TEXT NOT NULL,
+                model TEXT NOT NULL,
+                input_tokens INTEGER NOT NULL DEFAULT 0,
+                output_tokens INTEGER NOT NULL DEFAULT 0,
+                audio_seconds REAL NOT NULL DEFAULT 0,
+                duration_ms INTEGER NOT NULL DEFAULT 0,
+                success INTEGER NOT NULL,
+                timestamp TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    fn record(provider: &str, input: u64, output: u64, audio: f64, success: bool) -> UsageRecord {
+        UsageRecord {
+            provider: provider.to_string(),
+            operation: "test".to_string(),
+            model: "m".to_string(),
+            input_tokens: input,
+            output_tokens: output,
+            audio_seconds: audio,
+            duration_ms: 100,
+            success,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_summary_groups_and_prices() {
+        let pool = setup_test_db().await;
+        insert_record(&pool, &record("ollama", 1000, 500, 0.0, true))
+            .await
+            .unwrap();
+        insert_record(&pool, &record("ollama", 1000, 0, 0.0, false))
+            .await
+            .unwrap();
+        insert_record(&pool, &record("deepgram", 0, 0, 120.0, true))
+            .await
+            .unwrap();
+
+        let prices = vec![UsagePrice {
+            provider: "deepgram".to_string(),
+            per_audio_minute: 0.005,
+            ..Default::default()
+        }];
+        let summary = query_summary(&pool, None, &prices).await.unwrap();
+
+        assert_eq!(summary.by_day.len(), 2);
+        assert_eq!(summary.total.calls, 3);
+        assert_eq!(summary.total.failures, 1);
+        assert_eq!(summary.total.input_tokens, 2000);
+
+        let deepgram = &summary.by_provider[0];
+        assert_eq!(deepgram.provider, "deepgram");
+        assert!((deepgram.totals.estimated_cost_usd.unwrap() - 0.01).abs() < 1e-9);
+        assert_eq!(summary.by_provider[1].totals.estimated_cost_usd, None);
+        assert_eq!(summary.unpriced_providers, vec!["ollama".to_string()]);
+        assert!((summary.total.estimated_cost_usd.unwrap() - 0.01).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_period_start() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 15, 18, 30, 0).unwrap();
+        assert_eq!(
+            period_start("month", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).single()
+        );
+        assert_eq!(
+            period_start("week", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 9, 0, 0, 0).single()
+        );
+        assert_eq!(period_start("all", now).unwrap(), None);
+        assert!(period_start("fortnight", now).is_err());
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+}
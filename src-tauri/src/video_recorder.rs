@@ -1,18 +1,76 @@
 // Video Recorder Module
-// Continuous screen recording using macOS ScreenCaptureKit
+// Continuous screen recording through an ffmpeg child process
 // Records as video chunks, not individual frames
-
-use chrono::{DateTime, Utc};
-use parking_lot::RwLock;
+//
+// One ffmpeg process captures the screen (AVFoundation on macOS) and writes
+// N-minute fragmented MP4 chunks with the segment muxer, so chunks follow on
+// from each other without gaps and the open chunk can be read while it is
+// still being written. ffmpeg appends each finished chunk to a segment list;
+// a monitor thread turns that list into VideoChunks registered with the
+// ChunkManager, which is how frame extraction finds them later.
+
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-/// Duration for each video chunk (5 minutes in seconds)
-const CHUNK_DURATION_SECS: u64 = 300;
+use crate::chunk_manager::ChunkManager;
+
+/// Segment list ffmpeg appends finished chunks to ("file,start,end" per line)
+const SEGMENT_LIST: &str = "segments.csv";
+/// How often the monitor thread picks up finished chunks
+const MONITOR_INTERVAL_MS: u64 = 1000;
+/// How long `stop` waits for ffmpeg to finalize before killing it
+const STOP_TIMEOUT_SECS: u64 = 10;
+
+/// Screen video capture settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoCaptureConfig {
+    /// Scale frames down to this width (0 = native resolution)
+    pub max_width: u32,
+    pub fps: u32,
+    pub bitrate_kbps: u32,
+    /// Length of each chunk
+    pub chunk_minutes: u32,
+}
+
+impl Default for VideoCaptureConfig {
+    fn default() -> Self {
+        Self {
+            max_width: 1920,
+            fps: 15,
+            bitrate_kbps: 2500,
+            chunk_minutes: 5,
+        }
+    }
+}
+
+impl VideoCaptureConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_width != 0 && !(640..=7680).contains(&self.max_width) {
+            return Err("Video width must be 0 (native) or between 640 and 7680".to_string());
+        }
+        if !(1..=60).contains(&self.fps) {
+            return Err("Video frame rate must be between 1 and 60 fps".to_string());
+        }
+        if !(250..=20_000).contains(&self.bitrate_kbps) {
+            return Err("Video bitrate must be between 250 and 20000 kbps".to_string());
+        }
+        if !(1..=60).contains(&self.chunk_minutes) {
+            return Err("Video chunk length must be between 1 and 60 minutes".to_string());
+        }
+        Ok(())
+    }
+
+    fn chunk_secs(&self) -> u64 {
+        self.chunk_minutes as u64 * 60
+    }
+}
 
 /// Video chunk metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,9 +87,12 @@ pub struct VideoChunk {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PinMoment {
     pub timestamp: DateTime<Utc>,
+    /// Seconds since the recording started
     pub offset_secs: f64,
     pub label: Option<String>,
     pub chunk_number: u32,
+    /// Seconds into `chunk_number` (what `extract_frame_at` takes)
+    pub chunk_offset_secs: f64,
 }
 
 /// Recording session state
@@ -44,90 +105,262 @@ pub struct RecordingSession {
     pub is_active: bool,
 }
 
-/// Video recorder using screencapture/ffmpeg
+/// Chunk number and offset for `ts`, from the chunks recorded so far. Times
+/// past the last finished chunk fall in the next one, which starts where the
+/// last one ended (or at `started_at`).
+pub fn locate_moment(
+    chunks: &[VideoChunk],
+    started_at: DateTime<Utc>,
+    ts: DateTime<Utc>,
+) -> (u32, f64) {
+    let secs = |d: Duration| (d.num_milliseconds().max(0)) as f64 / 1000.0;
+
+    for chunk in chunks {
+        if ts >= chunk.start_time && chunk.end_time.map_or(true, |end| ts < end) {
+            return (chunk.chunk_number, secs(ts - chunk.start_time));
+        }
+    }
+
+    match chunks.iter().filter(|c| c.end_time.is_some()).last() {
+        Some(last) => (
+            last.chunk_number + 1,
+            secs(ts - last.end_time.unwrap_or(last.start_time)),
+        ),
+        None => (1, secs(ts - started_at)),
+    }
+}
+
+/// Parse ffmpeg's CSV segment list into finished chunks
+fn parse_segment_list(list: &str, video_dir: &Path, started_at: DateTime<Utc>) -> Vec<VideoChunk> {
+    let at = |secs: f64| started_at + Duration::milliseconds((secs * 1000.0) as i64);
+
+    list.lines()
+        .filter_map(|line| {
+            let mut fields = line.rsplitn(3, ',');
+            let end: f64 = fields.next()?.trim().parse().ok()?;
+            let start: f64 = fields.next()?.trim().parse().ok()?;
+            let file = fields.next()?.trim();
+            let chunk_number = Path::new(file)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.strip_prefix("chunk_"))
+                .and_then(|n| n.parse::<u32>().ok())?;
+            let path = video_dir.join(file);
+            let size_bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+            Some(VideoChunk {
+                chunk_number,
+                path,
+                start_time: at(start),
+                end_time: Some(at(end)),
+                size_bytes,
+                duration_secs: end - start,
+            })
+        })
+        .collect()
+}
+
+/// ffmpeg input arguments for the primary screen
+fn screen_input_args(config: &VideoCaptureConfig) -> Vec<String> {
+    let fps = config.fps.to_string();
+
+    #[cfg(target_os = "macos")]
+    {
+        vec![
+            "-f".into(),
+            "avfoundation".into(),
+            "-capture_cursor".into(),
+            "1".into(),
+            "-framerate".into(),
+            fps,
+            "-i".into(),
+            format!("{}:none", avfoundation_screen_index()), // no audio (archived separately)
+        ]
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        vec![
+            "-f".into(),
+            "gdigrab".into(),
+            "-framerate".into(),
+            fps,
+            "-i".into(),
+            "desktop".into(),
+        ]
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        vec![
+            "-f".into(),
+            "x11grab".into(),
+            "-framerate".into(),
+            fps,
+            "-i".into(),
+            std::env::var("DISPLAY").unwrap_or_else(|_| ":0".to_string()),
+        ]
+    }
+}
+
+/// AVFoundation device index of "Capture screen 0" (cameras are listed first)
+#[cfg(target_os = "macos")]
+fn avfoundation_screen_index() -> u32 {
+    let output = Command::new("ffmpeg")
+        .args(["-f", "avfoundation", "-list_devices", "true", "-i", ""])
+        .stdout(Stdio::null())
+        .output();
+
+    output
+        .ok()
+        .and_then(|o| {
+            String::from_utf8_lossy(&o.stderr)
+                .lines()
+                .find(|line| line.contains("Capture screen 0"))
+                .and_then(|line| {
+                    let start = line.rfind("] [")? + 3;
+                    let end = start + line[start..].find(']')?;
+                    line[start..end].parse().ok()
+                })
+        })
+        .unwrap_or(1)
+}
+
+/// Hardware H.264 on macOS, x264 elsewhere
+fn encoder_args() -> Vec<&'static str> {
+    if cfg!(target_os = "macos") {
+        vec!["-c:v", "h264_videotoolbox"]
+    } else {
+        vec!["-c:v", "libx264", "-preset", "veryfast"]
+    }
+}
+
+/// Video recorder using ffmpeg
 pub struct VideoRecorder {
-    /// Output directory for video files  
+    /// Output directory for video files (video/<meeting_id>/ under it)
     output_dir: PathBuf,
+    config: RwLock<VideoCaptureConfig>,
     /// Current meeting ID
     meeting_id: Arc<RwLock<Option<String>>>,
-    /// Current chunk number
-    current_chunk: AtomicU32,
     /// Recording start time
     start_time: Arc<RwLock<Option<DateTime<Utc>>>>,
     /// Is recording active
-    is_recording: AtomicBool,
+    is_recording: Arc<AtomicBool>,
     /// Current ffmpeg process
-    ffmpeg_process: Arc<RwLock<Option<Child>>>,
-    /// Recorded chunks
+    ffmpeg_process: Arc<Mutex<Option<Child>>>,
+    /// Finished chunks plus the one being written
     chunks: Arc<RwLock<Vec<VideoChunk>>>,
     /// Pin moments
     pin_moments: Arc<RwLock<Vec<PinMoment>>>,
-    /// Chunk rotation handle
-    chunk_rotation_running: AtomicBool,
+    /// Segment list monitor
+    monitor: Mutex<Option<std::thread::JoinHandle<()>>>,
 }
 
 impl VideoRecorder {
     pub fn new(output_dir: PathBuf) -> Self {
         Self {
             output_dir,
+            config: RwLock::new(VideoCaptureConfig::default()),
             meeting_id: Arc::new(RwLock::new(None)),
-            current_chunk: AtomicU32::new(0),
             start_time: Arc::new(RwLock::new(None)),
-            is_recording: AtomicBool::new(false),
-            ffmpeg_process: Arc::new(RwLock::new(None)),
+            is_recording: Arc::new(AtomicBool::new(false)),
+            ffmpeg_process: Arc::new(Mutex::new(None)),
             chunks: Arc::new(RwLock::new(Vec::new())),
             pin_moments: Arc::new(RwLock::new(Vec::new())),
-            chunk_rotation_running: AtomicBool::new(false),
+            monitor: Mutex::new(None),
         }
     }
 
-    /// Start recording for a meeting
-    pub fn start(&self, meeting_id: &str) -> Result<(), String> {
+    /// Capture settings for the next recording
+    pub fn set_config(&self, config: VideoCaptureConfig) {
+        *self.config.write() = config;
+    }
+
+    pub fn config(&self) -> VideoCaptureConfig {
+        *self.config.read()
+    }
+
+    /// Start recording for a meeting; chunks are registered with `chunk_manager`
+    pub fn start(
+        &self,
+        meeting_id: &str,
+        chunk_manager: &'static ChunkManager,
+    ) -> Result<(), String> {
         if self.is_recording.load(Ordering::SeqCst) {
             return Err("Recording already in progress".to_string());
         }
 
         // Create output directory
-        let video_dir = self.output_dir.join(meeting_id).join("video");
+        let video_dir = self.get_video_dir(meeting_id);
         std::fs::create_dir_all(&video_dir)
             .map_err(|e| format!("Failed to create video directory: {}", e))?;
 
+        // Chunk numbers continue after an earlier recording of the same meeting
+        let first_chunk = chunk_manager
+            .get_chunks(meeting_id)?
+            .iter()
+            .map(|c| c.chunk_number)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let _ = std::fs::remove_file(video_dir.join(SEGMENT_LIST));
+
+        let config = self.config();
+        let process = Self::start_ffmpeg_recording(&video_dir, &config, first_chunk)?;
+        let started_at = Utc::now();
+
         // Set state
+        *self.ffmpeg_process.lock() = Some(process);
         *self.meeting_id.write() = Some(meeting_id.to_string());
-        *self.start_time.write() = Some(Utc::now());
-        self.current_chunk.store(1, Ordering::SeqCst);
-        self.is_recording.store(true, Ordering::SeqCst);
-        self.chunks.write().clear();
+        *self.start_time.write() = Some(started_at);
         self.pin_moments.write().clear();
+        let open = VideoChunk {
+            chunk_number: first_chunk,
+            path: video_dir.join(format!("chunk_{:03}.mp4", first_chunk)),
+            start_time: started_at,
+            end_time: None,
+            size_bytes: 0,
+            duration_secs: 0.0,
+        };
+        chunk_manager.register_chunk(meeting_id, &open)?;
+        *self.chunks.write() = vec![open];
+        self.is_recording.store(true, Ordering::SeqCst);
 
-        // Start first chunk
-        self.start_chunk(1, &video_dir)?;
-
-        // Start chunk rotation timer
-        self.start_chunk_rotation(video_dir);
+        self.start_monitor(meeting_id.to_string(), video_dir, started_at, chunk_manager);
 
-        log::info!("Started video recording for meeting: {}", meeting_id);
+        log::info!(
+            "Started video recording for meeting: {} ({} fps, {} kbps, {}-minute chunks)",
+            meeting_id,
+            config.fps,
+            config.bitrate_kbps,
+            config.chunk_minutes
+        );
         Ok(())
     }
 
-    /// Stop recording
-    pub fn stop(&self) -> Result<RecordingSession, String> {
-        if !self.is_recording.load(Ordering::SeqCst) {
+    /// Stop recording, finalizing and registering the open chunk
+    pub fn stop(&self, chunk_manager: &ChunkManager) -> Result<RecordingSession, String> {
+        if !self.is_recording.swap(false, Ordering::SeqCst) {
             return Err("No recording in progress".to_string());
         }
 
-        // Stop chunk rotation
-        self.chunk_rotation_running.store(false, Ordering::SeqCst);
-
-        // Stop current ffmpeg process
-        self.stop_current_chunk()?;
-
-        // Mark as not recording
-        self.is_recording.store(false, Ordering::SeqCst);
+        // Stop current ffmpeg process (it closes the open chunk and lists it)
+        self.stop_ffmpeg();
+        if let Some(monitor) = self.monitor.lock().take() {
+            let _ = monitor.join();
+        }
 
         // Build session result
         let meeting_id = self.meeting_id.read().clone().unwrap_or_default();
         let started_at = self.start_time.read().unwrap_or_else(Utc::now);
+        Self::sync_chunks(
+            &self.chunks,
+            &meeting_id,
+            &self.get_video_dir(&meeting_id),
+            started_at,
+            chunk_manager,
+            true,
+        );
         let chunks = self.chunks.read().clone();
         let pin_moments = self.pin_moments.read().clone();
 
@@ -155,19 +388,20 @@ impl VideoRecorder {
         let now = Utc::now();
         let start = self.start_time.read().unwrap_or(now);
         let offset_secs = (now - start).num_milliseconds() as f64 / 1000.0;
-        let chunk_number = self.current_chunk.load(Ordering::SeqCst);
+        let (chunk_number, chunk_offset_secs) = locate_moment(&self.chunks.read(), start, now);
 
         let pin = PinMoment {
             timestamp: now,
             offset_secs,
             label,
             chunk_number,
+            chunk_offset_secs,
         };
 
         self.pin_moments.write().push(pin.clone());
         log::info!(
-            "Pinned moment at {}s in chunk {}",
-            offset_secs,
+            "Pinned moment at {:.1}s in chunk {}",
+            chunk_offset_secs,
             chunk_number
         );
 
@@ -191,131 +425,203 @@ impl VideoRecorder {
 
     /// Get path to video directory for a meeting
     pub fn get_video_dir(&self, meeting_id: &str) -> PathBuf {
-        self.output_dir.join(meeting_id).join("video")
+        self.output_dir.join(meeting_id)
     }
 
-    /// Start recording a new chunk
-    fn start_chunk(&self, chunk_num: u32, video_dir: &PathBuf) -> Result<(), String> {
-        let chunk_path = video_dir.join(format!("chunk_{:03}.mov", chunk_num));
-
-        // Use screencapture for macOS native recording
-        // Falls back to ffmpeg if screencapture isn't suitable
-        let process = self.start_ffmpeg_recording(&chunk_path)?;
-
-        *self.ffmpeg_process.write() = Some(process);
-
-        // Record chunk metadata
-        let chunk = VideoChunk {
-            chunk_number: chunk_num,
-            path: chunk_path,
-            start_time: Utc::now(),
-            end_time: None,
-            size_bytes: 0,
-            duration_secs: 0.0,
-        };
-        self.chunks.write().push(chunk);
-
-        log::info!("Started chunk {} recording", chunk_num);
-        Ok(())
-    }
+    /// Start the ffmpeg process writing chunk_NNN.mp4 segments into `video_dir`
+    fn start_ffmpeg_recording(
+        video_dir: &Path,
+        config: &VideoCaptureConfig,
+        first_chunk: u32,
+    ) -> Result<Child, String> {
+        let chunk_secs = config.chunk_secs().to_string();
+        let mut filters = vec!["format=yuv420p".to_string()];
+        if config.max_width > 0 {
+            // Never upscale; keep the height even for H.264
+            filters.insert(0, format!("scale='min({},iw)':-2", config.max_width));
+        }
 
-    /// Start ffmpeg recording process
-    fn start_ffmpeg_recording(&self, output_path: &PathBuf) -> Result<Child, String> {
-        // Use ffmpeg with AVFoundation for screen capture
-        // -f avfoundation captures screen and/or audio on macOS
-        // -capture_cursor 1 includes mouse cursor
-        // -framerate 30 for smooth video
-        // -c:v h264_videotoolbox uses hardware encoder
-        let child = Command::new("ffmpeg")
+        let mut command = Command::new("ffmpeg");
+        command
+            .args(["-loglevel", "error"])
+            .args(screen_input_args(config))
+            .args(encoder_args())
+            .args(["-b:v", &format!("{}k", config.bitrate_kbps)])
+            .args(["-r", &config.fps.to_string()])
+            .args(["-vf", &filters.join(",")])
+            // Keyframe on every chunk boundary so chunks are cut exactly
+            .args([
+                "-force_key_frames",
+                &format!("expr:gte(t,n_forced*{})", chunk_secs),
+            ])
+            .args(["-f", "segment", "-segment_time", &chunk_secs])
+            .args(["-segment_format", "mp4"])
+            // Fragmented MP4 stays readable while it's written and after a crash
             .args([
-                "-f",
-                "avfoundation",
-                "-capture_cursor",
-                "1",
-                "-framerate",
-                "30",
-                "-i",
-                "1:none", // Screen 1, no audio (audio handled separately)
-                "-c:v",
-                "h264_videotoolbox", // Hardware H.264 encoder
-                "-preset",
-                "fast",
-                "-crf",
-                "28", // Good quality, reasonable size
-                "-pix_fmt",
-                "yuv420p",
-                "-movflags",
-                "+faststart",
-                "-y", // Overwrite
-                output_path.to_str().unwrap(),
+                "-segment_format_options",
+                "movflags=+frag_keyframe+empty_moov+default_base_moof",
             ])
+            .args(["-reset_timestamps", "1"])
+            .args(["-segment_start_number", &first_chunk.to_string()])
+            .args(["-segment_list_type", "csv", "-segment_list"])
+            .arg(video_dir.join(SEGMENT_LIST))
+            .arg("-y")
+            .arg(video_dir.join("chunk_%03d.mp4"));
+
+        command
             .stdin(Stdio::piped())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
             .spawn()
-            .map_err(|e| format!("Failed to start ffmpeg: {}", e))?;
-
-        Ok(child)
+            .map_err(|e| format!("Failed to start ffmpeg: {}", e))
     }
 
-    /// Stop current chunk recording
-    fn stop_current_chunk(&self) -> Result<(), String> {
-        let mut process_guard = self.ffmpeg_process.write();
+    /// Ask ffmpeg to finish the open chunk, killing it after STOP_TIMEOUT_SECS
+    fn stop_ffmpeg(&self) {
+        let Some(mut process) = self.ffmpeg_process.lock().take() else {
+            return;
+        };
 
-        if let Some(ref mut process) = *process_guard {
-            // Send 'q' to ffmpeg stdin to gracefully stop
-            if let Some(ref mut stdin) = process.stdin {
-                let _ = stdin.write_all(b"q");
-            }
+        // Send 'q' to ffmpeg stdin to gracefully stop
+        if let Some(ref mut stdin) = process.stdin {
+            let _ = stdin.write_all(b"q");
+            let _ = stdin.flush();
+        }
 
-            // Wait for process to finish (with timeout)
-            match process.wait() {
-                Ok(status) => {
+        let deadline =
+            std::time::Instant::now() + std::time::Duration::from_secs(STOP_TIMEOUT_SECS);
+        loop {
+            match process.try_wait() {
+                Ok(Some(status)) => {
                     if !status.success() {
                         log::warn!("ffmpeg exited with status: {}", status);
                     }
+                    return;
+                }
+                Ok(None) if std::time::Instant::now() < deadline => {
+                    std::thread::sleep(std::time::Duration::from_millis(100));
+                }
+                Ok(None) => {
+                    log::warn!("ffmpeg did not stop in {}s, killing it", STOP_TIMEOUT_SECS);
+                    let _ = process.kill();
+                    let _ = process.wait();
+                    return;
                 }
                 Err(e) => {
                     log::error!("Failed to wait for ffmpeg: {}", e);
-                    // Force kill
                     let _ = process.kill();
+                    return;
                 }
             }
+        }
+    }
 
-            // Update chunk metadata
-            let mut chunks = self.chunks.write();
-            if let Some(chunk) = chunks.last_mut() {
-                chunk.end_time = Some(Utc::now());
-                if let Ok(meta) = std::fs::metadata(&chunk.path) {
-                    chunk.size_bytes = meta.len();
-                }
-                if let Some(start) = chunk.end_time {
-                    chunk.duration_secs =
-                        (start - chunk.start_time).num_milliseconds() as f64 / 1000.0;
+    /// Poll the segment list while recording so finished chunks are registered
+    /// as they rotate
+    fn start_monitor(
+        &self,
+        meeting_id: String,
+        video_dir: PathBuf,
+        started_at: DateTime<Utc>,
+        chunk_manager: &'static ChunkManager,
+    ) {
+        let is_recording = self.is_recording.clone();
+        let process = self.ffmpeg_process.clone();
+        let chunks = self.chunks.clone();
+
+        let handle = std::thread::spawn(move || {
+            while is_recording.load(Ordering::SeqCst) {
+                std::thread::sleep(std::time::Duration::from_millis(MONITOR_INTERVAL_MS));
+                Self::sync_chunks(
+                    &chunks,
+                    &meeting_id,
+                    &video_dir,
+                    started_at,
+                    chunk_manager,
+                    false,
+                );
+
+                let exited = process
+                    .lock()
+                    .as_mut()
+                    .and_then(|p| p.try_wait().ok().flatten());
+                if let Some(status) = exited {
+                    log::error!("ffmpeg screen recording exited unexpectedly: {}", status);
+                    break;
                 }
             }
-        }
-
-        *process_guard = None;
-        Ok(())
+        });
+        *self.monitor.lock() = Some(handle);
     }
 
-    /// Start background chunk rotation
-    fn start_chunk_rotation(&self, _video_dir: PathBuf) {
-        if self.chunk_rotation_running.load(Ordering::SeqCst) {
-            return;
+    /// Refresh `chunks` from the segment list and register changes. Unless
+    /// `finished`, the chunk after the last listed one is tracked as open.
+    fn sync_chunks(
+        chunks: &RwLock<Vec<VideoChunk>>,
+        meeting_id: &str,
+        video_dir: &Path,
+        started_at: DateTime<Utc>,
+        chunk_manager: &ChunkManager,
+        finished: bool,
+    ) {
+        let list = std::fs::read_to_string(video_dir.join(SEGMENT_LIST)).unwrap_or_default();
+        let mut listed = parse_segment_list(&list, video_dir, started_at);
+        let first = chunks.read().first().map(|c| c.chunk_number).unwrap_or(1);
+
+        if !finished {
+            let (number, start_time) = match listed.last() {
+                Some(last) => (
+                    last.chunk_number + 1,
+                    last.end_time.unwrap_or(last.start_time),
+                ),
+                None => (first, started_at),
+            };
+            let path = video_dir.join(format!("chunk_{:03}.mp4", number));
+            let size_bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            listed.push(VideoChunk {
+                chunk_number: number,
+                path,
+                start_time,
+                end_time: None,
+                size_bytes,
+                duration_secs: (Utc::now() - start_time).num_milliseconds() as f64 / 1000.0,
+            });
         }
 
-        self.chunk_rotation_running.store(true, Ordering::SeqCst);
+        let mut current = chunks.write();
+        if finished {
+            // A chunk cut short by killing ffmpeg is never listed; close it here
+            let now = Utc::now();
+            for open in current.iter().filter(|c| c.end_time.is_none()) {
+                let listed_already = listed.iter().any(|c| c.chunk_number == open.chunk_number);
+                let size_bytes = std::fs::metadata(&open.path).map(|m| m.len()).unwrap_or(0);
+                if !listed_already && size_bytes > 0 {
+                    listed.push(VideoChunk {
+                        end_time: Some(now),
+                        size_bytes,
+                        duration_secs: (now - open.start_time).num_milliseconds() as f64 / 1000.0,
+                        ..open.clone()
+                    });
+                }
+            }
+        }
 
-        // Note: Chunk rotation is simplified for now.
-        // In production, we would use Arc<AtomicBool> for the running flag
-        // and spawn a proper rotation thread that handles overlapping recordings.
-        // For this implementation, we rely on the 5-minute timer and manual rotation.
-        log::info!(
-            "Chunk rotation timer started ({}s intervals)",
-            CHUNK_DURATION_SECS
-        );
+        for chunk in &listed {
+            let changed = current
+                .iter()
+                .find(|c| c.chunk_number == chunk.chunk_number)
+                .map_or(true, |c| c.end_time != chunk.end_time);
+            if changed {
+                if let Err(e) = chunk_manager.register_chunk(meeting_id, chunk) {
+                    log::warn!(
+                        "Failed to register video chunk {}: {}",
+                        chunk.chunk_number,
+                        e
+                    );
+                }
+            }
+        }
+        *current = listed;
     }
 }
 
@@ -323,7 +629,8 @@ impl Default for VideoRecorder {
     fn default() -> Self {
         let output_dir = dirs::data_dir()
             .unwrap_or_else(|| PathBuf::from("."))
-            .join("com.nofriction.meetings");
+            .join("ai.nofriction.meetings")
+            .join("video");
         Self::new(output_dir)
     }
 }
@@ -332,16 +639,72 @@ impl Default for VideoRecorder {
 mod tests {
     use super::*;
 
+    fn chunk(number: u32, start: DateTime<Utc>, secs: Option<i64>) -> VideoChunk {
+        VideoChunk {
+            chunk_number: number,
+            path: PathBuf::from(format!("/tmp/chunk_{:03}.mp4", number)),
+            start_time: start,
+            end_time: secs.map(|s| start + Duration::seconds(s)),
+            size_bytes: 0,
+            duration_secs: secs.unwrap_or(0) as f64,
+        }
+    }
+
     #[test]
     fn test_video_chunk_creation() {
-        let chunk = VideoChunk {
-            chunk_number: 1,
-            path: PathBuf::from("/tmp/chunk_001.mov"),
-            start_time: Utc::now(),
-            end_time: None,
-            size_bytes: 0,
-            duration_secs: 0.0,
-        };
+        let chunk = chunk(1, Utc::now(), None);
         assert_eq!(chunk.chunk_number, 1);
     }
+
+    #[test]
+    fn test_parse_segment_list() {
+        let started = Utc::now();
+        let list = "chunk_001.mp4,0.000000,300.033333\nchunk_002.mp4,300.033333,412.5\n";
+        let chunks = parse_segment_list(list, Path::new("/tmp/video"), started);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].chunk_number, 2);
+        assert_eq!(chunks[1].path, PathBuf::from("/tmp/video/chunk_002.mp4"));
+        assert_eq!(
+            chunks[1].start_time,
+            started + Duration::milliseconds(300_033)
+        );
+        assert!((chunks[1].duration_secs - 112.466667).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_locate_moment() {
+        let started = Utc::now();
+        let first = chunk(1, started, Some(300));
+        let open = chunk(2, started + Duration::seconds(300), None);
+
+        let (number, offset) = locate_moment(
+            &[first.clone(), open],
+            started,
+            started + Duration::seconds(345),
+        );
+        assert_eq!((number, offset), (2, 45.0));
+
+        // Not yet tracked as open: falls after the last finished chunk
+        let (number, offset) = locate_moment(&[first], started, started + Duration::seconds(310));
+        assert_eq!((number, offset), (2, 10.0));
+
+        let (number, offset) = locate_moment(&[], started, started + Duration::seconds(12));
+        assert_eq!((number, offset), (1, 12.0));
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(VideoCaptureConfig::default().validate().is_ok());
+        let bad_fps = VideoCaptureConfig {
+            fps: 0,
+            ..Default::default()
+        };
+        assert!(bad_fps.validate().is_err());
+        let native = VideoCaptureConfig {
+            max_width: 0,
+            ..Default::default()
+        };
+        assert!(native.validate().is_ok());
+    }
 }
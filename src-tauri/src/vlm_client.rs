@@ -2,7 +2,12 @@
 //!
 //! Uses TheBrain API at https://7wk6vrq9achr2djw.caas.targon.com
 //! Models: qwen3-vl:8b (vision), qwen3:8b (text), qwen2.5-coder:7b (code)
+//!
+//! The vision model accepts multiple images per prompt, so frames can be sent
+//! in batches: one request with an index-tagged prompt, answered with a JSON
+//! array. Callers fall back to single-frame requests when a batch fails.
 
+use crate::usage_ledger::{estimate_tokens, UsageCall};
 use base64::Engine;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
@@ -11,6 +16,14 @@ use std::time::Duration;
 
 /// TheBrain API base URL
 const THEBRAIN_API_URL: &str = "https://7wk6vrq9achr2djw.caas.targon.com";
+/// Response token budget per frame
+const MAX_TOKENS_PER_FRAME: u32 = 800;
+/// Upper bound on frames packed into one request
+pub const MAX_VLM_BATCH_SIZE: u32 = 8;
+/// Provider name in the usage ledger
+const USAGE_PROVIDER: &str = "thebrain";
+/// Prompt tokens charged per attached image when the server reports none
+const IMAGE_TOKENS_ESTIMATE: u64 = 1024;
 
 /// Token response from /api/token
 #[derive(Debug, Deserialize)]
@@ -97,8 +110,26 @@ struct ChatResponse {
     _done: bool,
     #[serde(default, rename = "total_duration")]
     _total_duration: Option<u64>,
-    #[serde(default, rename = "eval_count")]
-    _eval_count: Option<u32>,
+    #[serde(default)]
+    prompt_eval_count: Option<u32>,
+    #[serde(default)]
+    eval_count: Option<u32>,
+}
+
+/// Record a chat call, preferring the server's token counts over estimates
+fn record_usage(call: UsageCall, input_tokens: u64, result: &Result<ChatResponse, String>) {
+    match result {
+        Ok(resp) => call.finish(
+            resp.prompt_eval_count
+                .map(u64::from)
+                .unwrap_or(input_tokens),
+            resp.eval_count
+                .map(u64::from)
+                .unwrap_or_else(|| estimate_tokens(&resp.message.content)),
+            true,
+        ),
+        Err(_) => call.finish(input_tokens, 0, false),
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -282,6 +313,11 @@ impl VLMClient {
         *self.model_fallback.write() = model;
     }
 
+    /// Primary vision model name
+    pub fn get_model(&self) -> String {
+        self.model_primary.read().clone()
+    }
+
     /// Get base URL (for external use)
     pub fn get_base_url(&self) -> String {
         self.base_url.read().clone()
@@ -346,7 +382,12 @@ impl VLMClient {
         // Try primary model first
         let primary_model = self.model_primary.read().clone();
         match self
-            .call_chat_api(&base64_image, prompt, &primary_model)
+            .call_chat_api(
+                vec![base64_image.clone()],
+                prompt,
+                &primary_model,
+                MAX_TOKENS_PER_FRAME,
+            )
             .await
         {
             Ok(response) => return self.parse_response(&response, &primary_model),
@@ -362,18 +403,86 @@ impl VLMClient {
         // Fallback to 3B model
         let fallback_model = self.model_fallback.read().clone();
         let response = self
-            .call_chat_api(&base64_image, prompt, &fallback_model)
+            .call_chat_api(
+                vec![base64_image],
+                prompt,
+                &fallback_model,
+                MAX_TOKENS_PER_FRAME,
+            )
             .await?;
         self.parse_response(&response, &fallback_model)
     }
 
+    /// Run a prompt on one screenshot with a given model and return the raw
+    /// reply (no parsing, no fallback model)
+    pub async fn analyze_frame_raw(
+        &self,
+        image_path: &str,
+        prompt: &str,
+        model: &str,
+    ) -> Result<String, String> {
+        let image_data =
+            std::fs::read(image_path).map_err(|e| format!("Failed to read image: {}", e))?;
+        let base64_image = base64::engine::general_purpose::STANDARD.encode(&image_data);
+
+        self.call_chat_api(vec![base64_image], prompt, model, MAX_TOKENS_PER_FRAME)
+            .await
+    }
+
+    /// Analyze several screenshots in one request (primary model only).
+    /// Errors if the server rejects the request or the reply doesn't contain
+    /// exactly one result per frame - callers fall back to `analyze_frame`.
+    pub async fn analyze_frames_batched(
+        &self,
+        image_paths: &[String],
+        prompt: &str,
+    ) -> Result<Vec<ActivityContext>, String> {
+        let mut images = Vec::with_capacity(image_paths.len());
+        for path in image_paths {
+            let image_data =
+                std::fs::read(path).map_err(|e| format!("Failed to read image: {}", e))?;
+            images.push(base64::engine::general_purpose::STANDARD.encode(&image_data));
+        }
+
+        let count = images.len();
+        let model = self.model_primary.read().clone();
+        let response = self
+            .call_chat_api(
+                images,
+                &batch_prompt(prompt, count),
+                &model,
+                MAX_TOKENS_PER_FRAME * count as u32,
+            )
+            .await?;
+
+        parse_batch_response(&response, count)
+    }
+
     /// Call the /api/chat endpoint with retry
     async fn call_chat_api(
         &self,
-        image_b64: &str,
+        images_b64: Vec<String>,
         prompt: &str,
         model: &str,
+        max_tokens: u32,
     ) -> Result<String, String> {
+        let call = UsageCall::start(USAGE_PROVIDER, "vision", model);
+        let input_tokens =
+            estimate_tokens(prompt) + images_b64.len() as u64 * IMAGE_TOKENS_ESTIMATE;
+        let result = self
+            .send_chat_api(images_b64, prompt, model, max_tokens)
+            .await;
+        record_usage(call, input_tokens, &result);
+        result.map(|resp| resp.message.content)
+    }
+
+    async fn send_chat_api(
+        &self,
+        images_b64: Vec<String>,
+        prompt: &str,
+        model: &str,
+        max_tokens: u32,
+    ) -> Result<ChatResponse, String> {
         let base = self.base_url.read().trim_end_matches('/').to_string();
         let url = format!("{}/api/chat", base);
 
@@ -382,12 +491,12 @@ impl VLMClient {
             messages: vec![ChatMessage {
                 role: "user".to_string(),
                 content: prompt.to_string(),
-                images: Some(vec![image_b64.to_string()]),
+                images: Some(images_b64),
             }],
             stream: false,
             options: Some(ChatOptions {
                 temperature: 0.1,
-                max_tokens: Some(800),
+                max_tokens: Some(max_tokens),
             }),
         };
 
@@ -429,7 +538,7 @@ impl VLMClient {
                         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
                     log::info!("VLM analysis complete using {}", model);
-                    return Ok(chat_resp.message.content);
+                    return Ok(chat_resp);
                 }
                 Err(e) => {
                     last_error = format!("Request failed: {}", e);
@@ -450,44 +559,7 @@ impl VLMClient {
         if let (Some(start), Some(end)) = (json_start, json_end) {
             let json_str = &response[start..=end];
             if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(json_str) {
-                return Ok(ActivityContext {
-                    app_name: parsed
-                        .get("app_name")
-                        .and_then(|v| v.as_str())
-                        .map(String::from),
-                    window_title: parsed
-                        .get("window_title")
-                        .and_then(|v| v.as_str())
-                        .map(String::from),
-                    category: parsed
-                        .get("category")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unknown")
-                        .to_string(),
-                    summary: parsed
-                        .get("summary")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or(&response[..response.len().min(200)])
-                        .to_string(),
-                    focus_area: parsed
-                        .get("focus_area")
-                        .and_then(|v| v.as_str())
-                        .map(String::from),
-                    visible_files: parsed
-                        .get("visible_files")
-                        .and_then(|v| v.as_array())
-                        .map(|arr| {
-                            arr.iter()
-                                .filter_map(|v| v.as_str().map(String::from))
-                                .collect()
-                        })
-                        .unwrap_or_default(),
-                    confidence: parsed
-                        .get("confidence")
-                        .and_then(|v| v.as_f64())
-                        .unwrap_or(0.7) as f32,
-                    entities: parsed.get("entities").cloned(),
-                });
+                return Ok(context_from_json(&parsed, response));
             }
         }
 
@@ -506,9 +578,16 @@ impl VLMClient {
 
     /// Simple text chat (no image)
     pub async fn chat(&self, prompt: &str) -> Result<String, String> {
+        let model = self.model_primary.read().clone();
+        let call = UsageCall::start(USAGE_PROVIDER, "chat", &model);
+        let result = self.send_chat(prompt, model).await;
+        record_usage(call, estimate_tokens(prompt), &result);
+        result.map(|resp| resp.message.content)
+    }
+
+    async fn send_chat(&self, prompt: &str, model: String) -> Result<ChatResponse, String> {
         let base = self.base_url.read().trim_end_matches('/').to_string();
         let url = format!("{}/api/chat", base);
-        let model = self.model_primary.read().clone();
 
         let request_body = ChatRequest {
             model,
@@ -548,16 +627,24 @@ impl VLMClient {
             return Err(format!("Chat API error: {}", body));
         }
 
-        let chat_resp: ChatResponse = resp
-            .json::<ChatResponse>()
+        resp.json::<ChatResponse>()
             .await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-        Ok(chat_resp.message.content)
+            .map_err(|e| format!("Failed to parse response: {}", e))
     }
 
     /// Chat with a specific model (non-streaming)
     pub async fn chat_with_model(&self, prompt: &str, model: &str) -> Result<String, String> {
+        let call = UsageCall::start(USAGE_PROVIDER, "chat", model);
+        let result = self.send_chat_with_model(prompt, model).await;
+        record_usage(call, estimate_tokens(prompt), &result);
+        result.map(|resp| resp.message.content)
+    }
+
+    async fn send_chat_with_model(
+        &self,
+        prompt: &str,
+        model: &str,
+    ) -> Result<ChatResponse, String> {
         let base = self.base_url.read().trim_end_matches('/').to_string();
         let url = format!("{}/api/chat", base);
 
@@ -594,7 +681,7 @@ impl VLMClient {
             // Try reauthentication
             if self.reauthenticate().await.is_ok() {
                 // Retry once
-                return Box::pin(self.chat_with_model(prompt, model)).await;
+                return Box::pin(self.send_chat_with_model(prompt, model)).await;
             }
             return Err("Unauthorized: Please login to TheBrain".to_string());
         }
@@ -604,17 +691,37 @@ impl VLMClient {
             return Err(format!("Chat API error: {}", body));
         }
 
-        let chat_resp: ChatResponse = resp
-            .json::<ChatResponse>()
+        resp.json::<ChatResponse>()
             .await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-        Ok(chat_resp.message.content)
+            .map_err(|e| format!("Failed to parse response: {}", e))
     }
 
     /// Streaming chat with TheBrain API (collects full response)
     /// Uses POST /api/chat/stream with SSE
     pub async fn chat_stream(&self, prompt: &str, model: &str) -> Result<String, String> {
+        self.chat_stream_with(prompt, model, &mut |_| {}).await
+    }
+
+    /// Streaming chat that passes each SSE content fragment to `on_delta`
+    pub async fn chat_stream_with(
+        &self,
+        prompt: &str,
+        model: &str,
+        on_delta: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<String, String> {
+        let call = UsageCall::start(USAGE_PROVIDER, "chat", model);
+        let result = self.send_chat_stream(prompt, model, on_delta).await;
+        let output_tokens = result.as_deref().map(estimate_tokens).unwrap_or(0);
+        call.finish(estimate_tokens(prompt), output_tokens, result.is_ok());
+        result
+    }
+
+    async fn send_chat_stream(
+        &self,
+        prompt: &str,
+        model: &str,
+        on_delta: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<String, String> {
         let base = self.base_url.read().trim_end_matches('/').to_string();
         let url = format!("{}/api/chat/stream", base);
 
@@ -641,7 +748,7 @@ impl VLMClient {
         if resp.status() == 401 {
             // Try reauthentication
             if self.reauthenticate().await.is_ok() {
-                return Box::pin(self.chat_stream(prompt, model)).await;
+                return Box::pin(self.send_chat_stream(prompt, model, on_delta)).await;
             }
             return Err("Unauthorized: Please login to TheBrain".to_string());
         }
@@ -651,22 +758,7 @@ impl VLMClient {
             return Err(format!("Stream API error: {}", body));
         }
 
-        // Read SSE response and collect content
-        let body = resp
-            .text()
-            .await
-            .map_err(|e| format!("Failed to read stream: {}", e))?;
-
-        let mut full_content = String::new();
-        for line in body.lines() {
-            if let Some(data) = line.strip_prefix("data: ") {
-                if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(data) {
-                    if let Some(content) = parsed.get("content").and_then(|v| v.as_str()) {
-                        full_content.push_str(content);
-                    }
-                }
-            }
-        }
+        let full_content = crate::ai_stream::read_stream(resp, on_delta).await?;
 
         if full_content.is_empty() {
             return Err("No content in response".to_string());
@@ -676,6 +768,114 @@ impl VLMClient {
     }
 }
 
+/// Wrap a single-frame prompt for a multi-image request
+fn batch_prompt(prompt: &str, count: usize) -> String {
+    format!(
+        "{}\n\nYou are given {} screenshots, numbered 0 to {} in the order attached. \
+         Analyze each one separately using the instructions above. \
+         Respond with ONLY a JSON array of exactly {} objects, one per screenshot, in order. \
+         Each object must include \"frame_index\" (the screenshot number) plus the fields above.",
+        prompt,
+        count,
+        count.saturating_sub(1),
+        count
+    )
+}
+
+/// Parse a batched reply into one context per frame, ordered by `frame_index`
+/// when every entry has a distinct valid index, otherwise by position
+fn parse_batch_response(response: &str, count: usize) -> Result<Vec<ActivityContext>, String> {
+    let (start, end) = match (response.find('['), response.rfind(']')) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        _ => return Err("Batched VLM response contained no JSON array".to_string()),
+    };
+
+    let items: Vec<serde_json::Value> = serde_json::from_str(&response[start..=end])
+        .map_err(|e| format!("Failed to parse batched VLM response: {}", e))?;
+
+    if items.len() != count {
+        return Err(format!(
+            "Batched VLM response had {} results for {} frames",
+            items.len(),
+            count
+        ));
+    }
+    if items.iter().any(|item| !item.is_object()) {
+        return Err("Batched VLM response contained a non-object entry".to_string());
+    }
+
+    let indices: Vec<Option<usize>> = items
+        .iter()
+        .map(|item| {
+            item.get("frame_index")
+                .and_then(|v| v.as_u64())
+                .map(|i| i as usize)
+                .filter(|i| *i < count)
+        })
+        .collect();
+    let mut slots: Vec<Option<ActivityContext>> = vec![None; count];
+    let indexed = indices.iter().all(|i| i.is_some())
+        && indices
+            .iter()
+            .flatten()
+            .collect::<std::collections::HashSet<_>>()
+            .len()
+            == count;
+
+    for (position, item) in items.iter().enumerate() {
+        let slot = if indexed {
+            indices[position].unwrap_or(position)
+        } else {
+            position
+        };
+        slots[slot] = Some(context_from_json(item, &item.to_string()));
+    }
+
+    Ok(slots.into_iter().flatten().collect())
+}
+
+/// Build an ActivityContext from parsed VLM JSON; `raw` backs a missing summary
+fn context_from_json(parsed: &serde_json::Value, raw: &str) -> ActivityContext {
+    ActivityContext {
+        app_name: parsed
+            .get("app_name")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        window_title: parsed
+            .get("window_title")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        category: parsed
+            .get("category")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string(),
+        summary: parsed
+            .get("summary")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .unwrap_or_else(|| raw.chars().take(200).collect()),
+        focus_area: parsed
+            .get("focus_area")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        visible_files: parsed
+            .get("visible_files")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        confidence: parsed
+            .get("confidence")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.7) as f32,
+        entities: parsed.get("entities").cloned(),
+    }
+}
+
 impl Default for VLMClient {
     fn default() -> Self {
         Self::new()
@@ -717,6 +917,11 @@ pub fn vlm_configure(base_url: &str, token: Option<&str>) {
     }
 }
 
+/// Primary vision model of the global client
+pub fn vlm_model() -> String {
+    get_client().get_model()
+}
+
 /// Check if VLM API is available
 pub async fn vlm_is_available() -> bool {
     get_client().is_available().await
@@ -732,13 +937,48 @@ pub async fn vlm_analyze_frame(image_path: &str, prompt: &str) -> Result<Activit
     get_client().analyze_frame(image_path, prompt).await
 }
 
-/// Analyze multiple frames (batch)
+/// Run a prompt on a single frame and return the model's raw reply
+pub async fn vlm_analyze_frame_raw(
+    image_path: &str,
+    prompt: &str,
+    model: &str,
+) -> Result<String, String> {
+    get_client()
+        .analyze_frame_raw(image_path, prompt, model)
+        .await
+}
+
+/// Analyze several frames in one request (no fallback)
+pub async fn vlm_analyze_frames_batched(
+    image_paths: &[String],
+    prompt: &str,
+) -> Result<Vec<ActivityContext>, String> {
+    get_client()
+        .analyze_frames_batched(image_paths, prompt)
+        .await
+}
+
+/// Analyze multiple frames, `batch_size` per request. Chunks whose batched
+/// request fails are retried one frame at a time.
 pub async fn vlm_analyze_frames_batch(
-    frames: Vec<(String, String)>, // (path, prompt) pairs
+    image_paths: Vec<String>,
+    prompt: &str,
+    batch_size: usize,
 ) -> Vec<Result<ActivityContext, String>> {
     let mut results = Vec::new();
-    for (path, prompt) in frames {
-        results.push(vlm_analyze_frame(&path, &prompt).await);
+    for chunk in image_paths.chunks(batch_size.max(1)) {
+        if chunk.len() > 1 {
+            match vlm_analyze_frames_batched(chunk, prompt).await {
+                Ok(contexts) => {
+                    results.extend(contexts.into_iter().map(Ok));
+                    continue;
+                }
+                Err(e) => log::warn!("Batched VLM request failed, sending singly: {}", e),
+            }
+        }
+        for path in chunk {
+            results.push(vlm_analyze_frame(path, prompt).await);
+        }
     }
     results
 }
@@ -772,3 +1012,34 @@ pub async fn vlm_chat_with_model(prompt: &str, model: &str) -> Result<String, St
 pub async fn vlm_chat_stream(prompt: &str, model: &str) -> Result<String, String> {
     get_client().chat_stream(prompt, model).await
 }
+
+/// Streaming chat reporting each fragment to `on_delta`
+pub async fn vlm_chat_stream_with(
+    prompt: &str,
+    model: &str,
+    on_delta: &mut (dyn FnMut(&str) + Send),
+) -> Result<String, String> {
+    get_client().chat_stream_with(prompt, model, on_delta).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_response_ordered_by_frame_index() {
+        let response = r#"Here you go:
+[{"frame_index": 1, "category": "browsing", "summary": "reading docs"},
+ {"frame_index": 0, "category": "development", "summary": "editing code"}]"#;
+        let contexts = parse_batch_response(response, 2).unwrap();
+        assert_eq!(contexts[0].category, "development");
+        assert_eq!(contexts[1].category, "browsing");
+    }
+
+    #[test]
+    fn test_batch_response_count_mismatch_fails() {
+        let response = r#"[{"frame_index": 0, "category": "development", "summary": "x"}]"#;
+        assert!(parse_batch_response(response, 2).is_err());
+        assert!(parse_batch_response("not json", 1).is_err());
+    }
+}
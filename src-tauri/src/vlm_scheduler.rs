@@ -1,14 +1,212 @@
 //! VLM Scheduler - Background worker for automatic frame analysis
 //!
 //! Runs on a configurable timer and processes pending frames with VLM.
+//! Manual runs (`analyze_pending_frames`) share the same batch pipeline:
+//! frames are analyzed with bounded concurrency, progress is emitted as
+//! `vlm-progress`, and a running batch can be cancelled between frames.
+//! Only one batch (manual or scheduled) runs at a time. When the pending
+//! queue is larger than `vlm_batch_size`, frames are packed several per
+//! request to cut per-request prompt overhead.
+//!
+//! Results are cached by the frame's average hash (per theme). A frame within
+//! `VLM_CACHE_MAX_DISTANCE` bits of a cached screen reuses that result instead
+//! of calling the API; the activity is still logged, marked `from_cache`.
+//!
+//! Scheduled runs obey a `VLMSchedulerPolicy`: a per-theme frames-per-hour
+//! budget, a quiet-hours window and a low-battery guard. The policy is read
+//! on every tick, so changes apply without restarting the loop. Manual runs
+//! ignore the policy but count toward the hourly budget.
 
-use chrono::{DateTime, Utc};
-use parking_lot::RwLock;
+use chrono::{DateTime, Duration, Local, NaiveDateTime, NaiveTime, Timelike, Utc};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
-use crate::database::DatabaseManager;
+use crate::capture_metrics::MetricsCollector;
+use crate::database::{ActivityLogEntry, DatabaseManager, FrameQueueItem};
+use crate::dedupe_gate::AverageHash;
+use crate::power_manager::BatteryStatus;
 use crate::settings::SettingsManager;
+use crate::vlm_client::{ActivityContext, MAX_VLM_BATCH_SIZE};
+
+/// Default number of in-flight VLM requests per batch
+pub const DEFAULT_VLM_CONCURRENCY: u32 = 3;
+/// Upper bound on in-flight VLM requests (the API is a single GPU box)
+pub const MAX_VLM_CONCURRENCY: u32 = 8;
+/// Default frames per VLM request once the queue is larger than one batch
+pub const DEFAULT_VLM_BATCH_SIZE: u32 = 4;
+/// Frames picked up per scheduler run
+const SCHEDULER_BATCH_SIZE: i32 = 10;
+/// Max Hamming distance for a VLM cache hit (band lookup supports up to 3)
+const VLM_CACHE_MAX_DISTANCE: u32 = 3;
+/// Cached VLM results kept before least-recently-used eviction
+const VLM_CACHE_MAX_ENTRIES: i64 = 5000;
+/// Default charge at or below which scheduled runs pause on battery
+pub const DEFAULT_VLM_BATTERY_THRESHOLD: u8 = 20;
+
+/// Limits on scheduled runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VLMSchedulerPolicy {
+    /// Frames analyzed per clock hour, by theme (missing or 0 = unlimited)
+    pub max_frames_per_hour: HashMap<String, u32>,
+    /// Local "HH:MM" window with no processing; may wrap past midnight
+    pub quiet_hours_start: Option<String>,
+    pub quiet_hours_end: Option<String>,
+    /// Pause while on battery at or below this charge (None = no guard)
+    pub battery_threshold_pct: Option<u8>,
+}
+
+impl Default for VLMSchedulerPolicy {
+    fn default() -> Self {
+        Self {
+            max_frames_per_hour: HashMap::new(),
+            quiet_hours_start: None,
+            quiet_hours_end: None,
+            battery_threshold_pct: Some(DEFAULT_VLM_BATTERY_THRESHOLD),
+        }
+    }
+}
+
+impl VLMSchedulerPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        match (&self.quiet_hours_start, &self.quiet_hours_end) {
+            (Some(start), Some(end)) => {
+                parse_local_time(start)?;
+                parse_local_time(end)?;
+            }
+            (None, None) => {}
+            _ => return Err("Quiet hours need both a start and an end time".to_string()),
+        }
+        if self.battery_threshold_pct.is_some_and(|p| p > 100) {
+            return Err("Battery threshold must be between 0 and 100".to_string());
+        }
+        Ok(())
+    }
+
+    /// Quiet window, if set and non-empty
+    fn quiet_window(&self) -> Option<(NaiveTime, NaiveTime)> {
+        let start = parse_local_time(self.quiet_hours_start.as_deref()?).ok()?;
+        let end = parse_local_time(self.quiet_hours_end.as_deref()?).ok()?;
+        (start != end).then_some((start, end))
+    }
+
+    fn theme_budget(&self, theme: &str) -> Option<u32> {
+        self.max_frames_per_hour
+            .get(theme)
+            .copied()
+            .filter(|&max| max > 0)
+    }
+}
+
+fn parse_local_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map_err(|_| format!("Invalid time '{}', expected HH:MM", value))
+}
+
+/// Whether a scheduled run may go ahead now
+#[derive(Debug, Clone, PartialEq)]
+enum PolicyDecision {
+    /// Run, analyzing at most `frame_limit` frames (None = no budget)
+    Run { frame_limit: Option<u32> },
+    /// Skip; `until` is when the block lifts, if known (local wall time)
+    Wait {
+        reason: &'static str,
+        until: Option<NaiveDateTime>,
+    },
+}
+
+/// Apply the policy at local time `now`: quiet hours, then battery, then the
+/// active theme's hourly budget
+fn evaluate_policy(
+    policy: &VLMSchedulerPolicy,
+    now: NaiveDateTime,
+    theme: &str,
+    theme_frames_this_hour: u32,
+    battery: Option<BatteryStatus>,
+) -> PolicyDecision {
+    if let Some((start, end)) = policy.quiet_window() {
+        let t = now.time();
+        let quiet = if start < end {
+            t >= start && t < end
+        } else {
+            t >= start || t < end
+        };
+        if quiet {
+            let mut until = now.date().and_time(end);
+            if until <= now {
+                until += Duration::days(1);
+            }
+            return PolicyDecision::Wait {
+                reason: "quiet_hours",
+                until: Some(until),
+            };
+        }
+    }
+
+    if let Some(threshold) = policy.battery_threshold_pct {
+        let low =
+            battery.is_some_and(|b| b.on_battery && b.percent.is_some_and(|p| p <= threshold));
+        if low {
+            return PolicyDecision::Wait {
+                reason: "battery_low",
+                until: None,
+            };
+        }
+    }
+
+    match policy.theme_budget(theme) {
+        Some(max) if theme_frames_this_hour >= max => PolicyDecision::Wait {
+            reason: "hourly_budget",
+            until: Some(hour_start(now) + Duration::hours(1)),
+        },
+        Some(max) => PolicyDecision::Run {
+            frame_limit: Some(max - theme_frames_this_hour),
+        },
+        None => PolicyDecision::Run { frame_limit: None },
+    }
+}
+
+fn hour_start(t: NaiveDateTime) -> NaiveDateTime {
+    t.date().and_hms_opt(t.hour(), 0, 0).unwrap_or(t)
+}
+
+/// Frames analyzed per theme in the current local clock hour
+#[derive(Debug, Default)]
+struct HourlyUsage {
+    hour: Option<NaiveDateTime>,
+    frames: HashMap<String, u32>,
+}
+
+impl HourlyUsage {
+    fn roll(&mut self, now: NaiveDateTime) {
+        let hour = hour_start(now);
+        if self.hour != Some(hour) {
+            self.hour = Some(hour);
+            self.frames.clear();
+        }
+    }
+
+    fn add(&mut self, now: NaiveDateTime, theme: &str, frames: u32) {
+        self.roll(now);
+        *self.frames.entry(theme.to_string()).or_insert(0) += frames;
+    }
+
+    fn theme_count(&mut self, now: NaiveDateTime, theme: &str) -> u32 {
+        self.roll(now);
+        self.frames.get(theme).copied().unwrap_or(0)
+    }
+
+    fn total(&mut self, now: NaiveDateTime) -> u32 {
+        self.roll(now);
+        self.frames.values().sum()
+    }
+}
 
 /// VLM Scheduler status
 #[derive(Debug, Clone, serde::Serialize)]
@@ -20,6 +218,73 @@ pub struct VLMSchedulerStatus {
     pub last_run: Option<String>,
     pub next_run: Option<String>,
     pub pending_frames: i64,
+    pub batch_running: bool,
+    pub concurrency: u32,
+    pub batch_size: u32,
+    /// Requests avoided by batching frames since app start
+    pub requests_saved: u64,
+    /// Rough prompt tokens avoided by batching (chars / 4)
+    pub tokens_saved_estimate: u64,
+    pub policy: VLMSchedulerPolicy,
+    /// Frames analyzed this clock hour, all themes / active theme
+    pub frames_this_hour: u32,
+    pub theme_frames_this_hour: u32,
+    /// "quiet_hours", "battery_low" or "hourly_budget" while the policy blocks runs
+    pub paused_reason: Option<String>,
+    /// Earliest time the policy allows the next scheduled run
+    pub next_allowed_run: Option<String>,
+}
+
+/// Outcome of one batch, manual or scheduled. Frames finished before a
+/// cancellation are already committed.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct VLMBatchResult {
+    pub frames_processed: usize,
+    pub activities_created: usize,
+    pub errors: Vec<String>,
+    pub cancelled: bool,
+}
+
+/// Payload for `vlm-progress` events
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct VLMProgress {
+    pub processed: usize,
+    pub total: usize,
+    pub failed: usize,
+    pub manual: bool,
+}
+
+/// Payload for `vlm-backfill-progress` events, sent after each batch while
+/// requeued frames are outstanding
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct VLMBackfillProgress {
+    pub total: u64,
+    pub processed: u64,
+    pub remaining: u64,
+}
+
+/// State shared by manual and scheduled batches
+struct BatchState {
+    running: AtomicBool,
+    cancel_requested: AtomicBool,
+    concurrency: RwLock<u32>,
+    batch_size: RwLock<u32>,
+    requests_saved: AtomicU64,
+    tokens_saved: AtomicU64,
+    app_handle: RwLock<Option<AppHandle>>,
+    metrics: RwLock<Option<Arc<MetricsCollector>>>,
+    policy: RwLock<VLMSchedulerPolicy>,
+    hourly: Mutex<HourlyUsage>,
+    /// Frames requeued by backfills still being worked through
+    backfill_total: AtomicU64,
+}
+
+impl BatchState {
+    fn record_frames(&self, theme: &str, frames: usize) {
+        self.hourly
+            .lock()
+            .add(Local::now().naive_local(), theme, frames as u32);
+    }
 }
 
 use crate::prompt_manager::PromptManager;
@@ -34,6 +299,7 @@ pub struct VLMScheduler {
     database: Arc<DatabaseManager>,
     settings: Arc<SettingsManager>,
     prompt_manager: Arc<PromptManager>,
+    batch: Arc<BatchState>,
 }
 
 impl VLMScheduler {
@@ -51,6 +317,135 @@ impl VLMScheduler {
             database,
             settings,
             prompt_manager,
+            batch: Arc::new(BatchState {
+                running: AtomicBool::new(false),
+                cancel_requested: AtomicBool::new(false),
+                concurrency: RwLock::new(DEFAULT_VLM_CONCURRENCY),
+                batch_size: RwLock::new(DEFAULT_VLM_BATCH_SIZE),
+                requests_saved: AtomicU64::new(0),
+                tokens_saved: AtomicU64::new(0),
+                app_handle: RwLock::new(None),
+                metrics: RwLock::new(None),
+                policy: RwLock::new(VLMSchedulerPolicy::default()),
+                hourly: Mutex::new(HourlyUsage::default()),
+                backfill_total: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// App handle used to emit `vlm-progress` events
+    pub fn set_app_handle(&self, app: AppHandle) {
+        *self.batch.app_handle.write() = Some(app);
+    }
+
+    /// Collector that counts VLM calls vs. cache hits
+    pub fn set_metrics_collector(&self, metrics: Arc<MetricsCollector>) {
+        *self.batch.metrics.write() = Some(metrics);
+    }
+
+    /// Set how many VLM requests may be in flight per batch
+    pub fn set_concurrency(&self, concurrency: u32) {
+        let clamped = concurrency.clamp(1, MAX_VLM_CONCURRENCY);
+        *self.batch.concurrency.write() = clamped;
+        log::info!("VLM concurrency: {}", clamped);
+    }
+
+    /// Set how many frames are packed into one VLM request (1 disables batching)
+    pub fn set_batch_size(&self, batch_size: u32) {
+        let clamped = batch_size.clamp(1, MAX_VLM_BATCH_SIZE);
+        *self.batch.batch_size.write() = clamped;
+        log::info!("VLM batch size: {}", clamped);
+    }
+
+    pub fn batch_size(&self) -> u32 {
+        *self.batch.batch_size.read()
+    }
+
+    /// Replace the scheduling policy; the loop picks it up on its next tick
+    pub fn set_policy(&self, policy: VLMSchedulerPolicy) {
+        log::info!("VLM Scheduler policy: {:?}", policy);
+        *self.batch.policy.write() = policy;
+    }
+
+    pub fn policy(&self) -> VLMSchedulerPolicy {
+        self.batch.policy.read().clone()
+    }
+
+    /// Track frames requeued by a backfill so progress can be reported
+    pub fn add_backfill(&self, frames: u64) {
+        self.batch
+            .backfill_total
+            .fetch_add(frames, Ordering::SeqCst);
+    }
+
+    /// Ask the running batch to stop after in-flight frames finish.
+    /// Returns false if no batch is running.
+    pub fn cancel_batch(&self) -> bool {
+        if !self.batch.running.load(Ordering::SeqCst) {
+            return false;
+        }
+        self.batch.cancel_requested.store(true, Ordering::SeqCst);
+        log::info!("VLM batch cancellation requested");
+        true
+    }
+
+    /// Run one batch on demand. Fails if another batch is already running.
+    pub async fn run_manual_batch(&self, limit: i32) -> Result<VLMBatchResult, String> {
+        if self.batch.running.swap(true, Ordering::SeqCst) {
+            return Err("A VLM analysis batch is already running".to_string());
+        }
+
+        let result = self.run_manual_batch_inner(limit).await;
+        self.batch.running.store(false, Ordering::SeqCst);
+
+        if let Ok(r) = &result {
+            self.frames_processed
+                .fetch_add(r.activities_created as u64, Ordering::SeqCst);
+        }
+        result
+    }
+
+    async fn run_manual_batch_inner(&self, limit: i32) -> Result<VLMBatchResult, String> {
+        if !crate::vlm_client::vlm_is_available().await {
+            return Err("VLM API is not available. Please check SSH tunnel and token.".to_string());
+        }
+
+        let pending = self
+            .database
+            .get_pending_frames(limit)
+            .await
+            .map_err(|e| format!("Failed to get pending frames: {}", e))?;
+
+        if pending.is_empty() {
+            return Ok(VLMBatchResult::default());
+        }
+
+        let active_theme = self
+            .settings
+            .get_active_theme()
+            .await
+            .unwrap_or_else(|_| "prospecting".to_string());
+        let prompt = resolve_frame_prompt(&self.prompt_manager, &active_theme).await?;
+
+        self.batch.cancel_requested.store(false, Ordering::SeqCst);
+        let result = process_batch(
+            pending,
+            self.batch_context(prompt, active_theme.clone(), true),
+        )
+        .await;
+        self.batch
+            .record_frames(&active_theme, result.frames_processed);
+        emit_backfill_progress(&self.batch, &self.database).await;
+        Ok(result)
+    }
+
+    fn batch_context(&self, prompt: String, theme: String, manual: bool) -> BatchContext {
+        BatchContext {
+            database: self.database.clone(),
+            prompt: Arc::from(prompt),
+            theme: Arc::from(theme),
+            batch: self.batch.clone(),
+            manual,
         }
     }
 
@@ -79,6 +474,37 @@ impl VLMScheduler {
             None
         };
 
+        let policy = self.policy();
+        let theme = self
+            .settings
+            .get_active_theme()
+            .await
+            .unwrap_or_else(|_| "prospecting".to_string());
+        let now = Local::now().naive_local();
+        let (frames_this_hour, theme_frames_this_hour) = {
+            let mut hourly = self.batch.hourly.lock();
+            (hourly.total(now), hourly.theme_count(now, &theme))
+        };
+        let battery = if policy.battery_threshold_pct.is_some() {
+            tokio::task::spawn_blocking(crate::power_manager::battery_status)
+                .await
+                .ok()
+                .flatten()
+        } else {
+            None
+        };
+        let (paused_reason, next_allowed) =
+            match evaluate_policy(&policy, now, &theme, theme_frames_this_hour, battery) {
+                PolicyDecision::Run { .. } => (None, next),
+                PolicyDecision::Wait { reason, until } => (
+                    Some(reason.to_string()),
+                    until
+                        .and_then(|u| u.and_local_timezone(Local).earliest())
+                        .map(|u| u.with_timezone(&Utc))
+                        .map(|u| next.map_or(u, |n| n.max(u))),
+                ),
+            };
+
         VLMSchedulerStatus {
             running: self.running.load(Ordering::SeqCst),
             enabled: self.enabled.load(Ordering::SeqCst),
@@ -87,6 +513,16 @@ impl VLMScheduler {
             last_run: last.map(|l| l.to_rfc3339()),
             next_run: next.map(|n| n.to_rfc3339()),
             pending_frames: pending,
+            batch_running: self.batch.running.load(Ordering::SeqCst),
+            concurrency: *self.batch.concurrency.read(),
+            batch_size: *self.batch.batch_size.read(),
+            requests_saved: self.batch.requests_saved.load(Ordering::SeqCst),
+            tokens_saved_estimate: self.batch.tokens_saved.load(Ordering::SeqCst),
+            policy,
+            frames_this_hour,
+            theme_frames_this_hour,
+            paused_reason,
+            next_allowed_run: next_allowed.map(|n| n.to_rfc3339()),
         }
     }
 
@@ -109,6 +545,7 @@ impl VLMScheduler {
         let settings = self.settings.clone();
 
         let prompt_manager = self.prompt_manager.clone();
+        let batch = self.batch.clone();
 
         tokio::spawn(async move {
             Self::run_loop(
@@ -120,6 +557,7 @@ impl VLMScheduler {
                 database,
                 settings,
                 prompt_manager,
+                batch,
             )
             .await;
         });
@@ -142,6 +580,7 @@ impl VLMScheduler {
         database: Arc<DatabaseManager>,
         settings: Arc<SettingsManager>,
         prompt_manager: Arc<PromptManager>,
+        batch: Arc<BatchState>,
     ) {
         log::info!("VLM Scheduler loop started");
 
@@ -159,6 +598,13 @@ impl VLMScheduler {
                 continue;
             }
 
+            // Check if VLM processing is enabled (live feature flag)
+            if !settings.feature_flags().read().vlm_auto_process {
+                // Still update last_run so UI shows we checked
+                *last_run.write() = Some(Utc::now());
+                continue;
+            }
+
             // Load settings to get VLM config
             let app_settings = match settings.get_all().await {
                 Ok(s) => s,
@@ -168,15 +614,45 @@ impl VLMScheduler {
                 }
             };
 
-            // Check if VLM processing is enabled in settings
-            if !app_settings.vlm_auto_process {
-                // Still update last_run so UI shows we checked
-                *last_run.write() = Some(Utc::now());
-                continue;
-            }
+            // Quiet hours, battery guard and hourly budget
+            let policy = batch.policy.read().clone();
+            let now = Local::now().naive_local();
+            let theme_frames = batch
+                .hourly
+                .lock()
+                .theme_count(now, &app_settings.active_theme);
+            let battery = if policy.battery_threshold_pct.is_some() {
+                tokio::task::spawn_blocking(crate::power_manager::battery_status)
+                    .await
+                    .ok()
+                    .flatten()
+            } else {
+                None
+            };
+            let frame_limit = match evaluate_policy(
+                &policy,
+                now,
+                &app_settings.active_theme,
+                theme_frames,
+                battery,
+            ) {
+                PolicyDecision::Run { frame_limit } => frame_limit,
+                PolicyDecision::Wait { reason, until } => {
+                    log::debug!(
+                        "VLM Scheduler: Paused by policy ({}) until {:?}",
+                        reason,
+                        until
+                    );
+                    *last_run.write() = Some(Utc::now());
+                    continue;
+                }
+            };
+            let limit = frame_limit
+                .map(|l| (l.min(SCHEDULER_BATCH_SIZE as u32)) as i32)
+                .unwrap_or(SCHEDULER_BATCH_SIZE);
 
             // Get pending frames
-            let pending = match database.get_pending_frames(10).await {
+            let pending = match database.get_pending_frames(limit).await {
                 Ok(p) => p,
                 Err(e) => {
                     log::error!("VLM Scheduler: Failed to get pending frames: {}", e);
@@ -198,136 +674,550 @@ impl VLMScheduler {
                 continue;
             }
 
-            // Determine active theme and load prompt
-            let active_theme = app_settings.active_theme;
-            // The prompt key convention is "{theme}_context_analysis"
-            // Fallback to "frame_analysis" if specific theme prompt not found
-            let prompt_key = format!("{}_context_analysis", active_theme);
-
-            let prompt_text = match prompt_manager.get_prompt(&prompt_key).await {
-                Ok(Some(p)) => {
-                    log::info!(
-                        "VLM Scheduler: Using prompt '{}' for theme '{}'",
-                        p.name,
-                        active_theme
+            // Don't double-process frames a manual batch already picked up
+            if batch.running.swap(true, Ordering::SeqCst) {
+                log::info!("VLM Scheduler: Manual batch in progress, skipping this run");
+                continue;
+            }
+
+            let prompt_text =
+                match resolve_frame_prompt(&prompt_manager, &app_settings.active_theme).await {
+                    Ok(p) => p,
+                    Err(e) => {
+                        log::error!("VLM Scheduler: {}", e);
+                        batch.running.store(false, Ordering::SeqCst);
+                        continue;
+                    }
+                };
+
+            batch.cancel_requested.store(false, Ordering::SeqCst);
+            let ctx = BatchContext {
+                database: database.clone(),
+                prompt: Arc::from(prompt_text),
+                theme: Arc::from(app_settings.active_theme.as_str()),
+                batch: batch.clone(),
+                manual: false,
+            };
+            let result = process_batch(pending, ctx).await;
+            batch.running.store(false, Ordering::SeqCst);
+            batch.record_frames(&app_settings.active_theme, result.frames_processed);
+            emit_backfill_progress(&batch, &database).await;
+
+            frames_processed.fetch_add(result.activities_created as u64, Ordering::SeqCst);
+            *last_run.write() = Some(Utc::now());
+            log::info!(
+                "VLM Scheduler: Processed {} frames{}",
+                result.activities_created,
+                if result.cancelled { " (cancelled)" } else { "" }
+            );
+        }
+
+        log::info!("VLM Scheduler loop stopped");
+    }
+}
+
+/// Everything a batch needs, cloned into each frame task
+#[derive(Clone)]
+struct BatchContext {
+    database: Arc<DatabaseManager>,
+    prompt: Arc<str>,
+    theme: Arc<str>,
+    batch: Arc<BatchState>,
+    manual: bool,
+}
+
+enum FrameOutcome {
+    Stored,
+    StoreFailed(String),
+    AnalysisFailed(String),
+}
+
+/// Theme-specific analysis prompt, falling back to `frame_analysis` and then a built-in default
+pub async fn resolve_frame_prompt(
+    prompt_manager: &PromptManager,
+    active_theme: &str,
+) -> Result<String, String> {
+    // The prompt key convention is "{theme}_context_analysis"
+    let prompt_key = format!("{}_context_analysis", active_theme);
+
+    match prompt_manager.get_prompt(&prompt_key).await {
+        Ok(Some(p)) => {
+            log::info!(
+                "VLM: Using prompt '{}' for theme '{}'",
+                p.name,
+                active_theme
+            );
+            Ok(p.system_prompt)
+        }
+        Ok(None) => {
+            log::warn!(
+                "VLM: Prompt '{}' not found, falling back to 'frame_analysis'",
+                prompt_key
+            );
+            match prompt_manager.get_prompt("frame_analysis").await {
+                Ok(Some(p)) => Ok(p.system_prompt),
+                _ => {
+                    log::error!(
+                        "VLM: Fallback prompt 'frame_analysis' not found. Using hardcoded default."
                     );
-                    p.system_prompt
+                    Ok(r#"Analyze this screenshot and describe what the user is doing. 
+                    Respond in JSON format with these fields:
+                    {
+                      "app_name": "name of the main application visible",
+                      "window_title": "title of the window or document",
+                      "category": "one of: development, communication, research, writing, design, media, browsing, system, other",
+                      "summary": "brief description of what the user is doing",
+                      "focus_area": "specific task or project",
+                      "visible_files": [],
+                      "confidence": 0.8
+                    }
+                    Only respond with valid JSON."#.to_string())
                 }
-                Ok(None) => {
-                    // Try fallback
-                    log::warn!(
-                        "VLM Scheduler: Prompt '{}' not found, falling back to 'frame_analysis'",
-                        prompt_key
-                    );
-                    match prompt_manager.get_prompt("frame_analysis").await {
-                        Ok(Some(p)) => p.system_prompt,
-                        _ => {
-                            log::error!("VLM Scheduler: Fallback prompt 'frame_analysis' not found. Using hardcoded default.");
-                            r#"Analyze this screenshot and describe what the user is doing. 
-                            Respond in JSON format with these fields:
-                            {
-                              "app_name": "name of the main application visible",
-                              "window_title": "title of the window or document",
-                              "category": "one of: development, communication, research, system, other",
-                              "summary": "brief description of what the user is doing",
-                              "focus_area": "specific task or project",
-                              "visible_files": [],
-                              "confidence": 0.5
-                            }
-                            Only respond with valid JSON."#.to_string()
-                        }
+            }
+        }
+        Err(e) => Err(format!("Failed to retrieve prompt: {}", e)),
+    }
+}
+
+/// Analyze frames with at most `concurrency` VLM requests in flight.
+/// When more frames are pending than the batch size, frames are grouped
+/// `batch_size` per request. Each frame is committed as soon as its request
+/// finishes, so a cancelled batch keeps its results.
+async fn process_batch(frames: Vec<FrameQueueItem>, ctx: BatchContext) -> VLMBatchResult {
+    let total = frames.len();
+    let concurrency = *ctx.batch.concurrency.read() as usize;
+    let batch_size = *ctx.batch.batch_size.read() as usize;
+    let group_size = if batch_size > 1 && total > batch_size {
+        batch_size
+    } else {
+        1
+    };
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+    let mut result = VLMBatchResult::default();
+    let mut completed = 0usize;
+
+    for group in frames.chunks(group_size) {
+        let permit = match semaphore.clone().acquire_owned().await {
+            Ok(p) => p,
+            Err(_) => break,
+        };
+
+        // Checked between requests - requests already in flight still finish
+        if ctx.batch.cancel_requested.load(Ordering::SeqCst) {
+            result.cancelled = true;
+            break;
+        }
+
+        let group = group.to_vec();
+        let task_ctx = ctx.clone();
+        tasks.spawn(async move {
+            let outcomes = analyze_group(&task_ctx, group).await;
+            drop(permit);
+            outcomes
+        });
+
+        while let Some(joined) = tasks.try_join_next() {
+            record_outcomes(&mut result, joined, &mut completed, total, &ctx);
+        }
+    }
+
+    while let Some(joined) = tasks.join_next().await {
+        record_outcomes(&mut result, joined, &mut completed, total, &ctx);
+    }
+
+    if result.cancelled {
+        log::info!("VLM batch cancelled after {}/{} frames", completed, total);
+    }
+    result
+}
+
+fn record_outcomes(
+    result: &mut VLMBatchResult,
+    joined: Result<Vec<FrameOutcome>, tokio::task::JoinError>,
+    completed: &mut usize,
+    total: usize,
+    ctx: &BatchContext,
+) {
+    match joined {
+        Ok(outcomes) => {
+            for outcome in outcomes {
+                *completed += 1;
+                match outcome {
+                    FrameOutcome::Stored => {
+                        result.frames_processed += 1;
+                        result.activities_created += 1;
+                    }
+                    FrameOutcome::StoreFailed(e) => {
+                        result.frames_processed += 1;
+                        result.errors.push(e);
                     }
+                    FrameOutcome::AnalysisFailed(e) => result.errors.push(e),
                 }
-                Err(e) => {
-                    log::error!("VLM Scheduler: Failed to get prompt: {}", e);
-                    continue;
+            }
+        }
+        Err(e) => {
+            *completed += 1;
+            result.errors.push(format!("VLM task failed: {}", e));
+        }
+    }
+    if let Some(app) = ctx.batch.app_handle.read().as_ref() {
+        let progress = VLMProgress {
+            processed: *completed,
+            total,
+            failed: result.errors.len(),
+            manual: ctx.manual,
+        };
+        let _ = app.emit("vlm-progress", &progress);
+    }
+}
+
+/// Report how far the scheduler is through requeued frames; the count resets
+/// once none are left
+async fn emit_backfill_progress(batch: &BatchState, database: &DatabaseManager) {
+    let total = batch.backfill_total.load(Ordering::SeqCst);
+    if total == 0 {
+        return;
+    }
+    let remaining = match database.count_reanalyze_pending().await {
+        Ok(n) => (n.max(0) as u64).min(total),
+        Err(e) => {
+            log::warn!("VLM: Failed to count backfill frames: {}", e);
+            return;
+        }
+    };
+    if remaining == 0 {
+        batch.backfill_total.store(0, Ordering::SeqCst);
+        log::info!("VLM: Backfill of {} frames complete", total);
+    }
+    if let Some(app) = batch.app_handle.read().as_ref() {
+        let progress = VLMBackfillProgress {
+            total,
+            processed: total - remaining,
+            remaining,
+        };
+        let _ = app.emit("vlm-backfill-progress", &progress);
+    }
+}
+
+/// Average hash of a frame on disk (None if it can't be decoded)
+async fn frame_hash(frame_path: &str) -> Option<u64> {
+    let path = frame_path.to_string();
+    tokio::task::spawn_blocking(move || {
+        image::open(&path)
+            .ok()
+            .map(|img| AverageHash::compute(&img).as_u64())
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+/// Cached result for a near-identical screen under the same theme
+async fn cached_context(
+    database: &DatabaseManager,
+    phash: u64,
+    active_theme: &str,
+) -> Option<ActivityContext> {
+    match database
+        .find_vlm_cache(phash, active_theme, VLM_CACHE_MAX_DISTANCE)
+        .await
+    {
+        Ok(Some(json)) => serde_json::from_str(&json).ok(),
+        Ok(None) => None,
+        Err(e) => {
+            log::warn!("VLM: Cache lookup failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Analyze a group of frames. Cache hits are stored directly; the remaining
+/// frames go out as one batched request, falling back to single-frame
+/// requests if the batch is rejected or its reply can't be parsed.
+async fn analyze_group(ctx: &BatchContext, frames: Vec<FrameQueueItem>) -> Vec<FrameOutcome> {
+    let metrics = ctx.batch.metrics.read().clone();
+    let mut outcomes = Vec::with_capacity(frames.len());
+    let mut misses = Vec::new();
+
+    for frame in frames {
+        let phash = frame_hash(&frame.frame_path).await;
+        let mut cached = None;
+        // Backfilled frames are being re-run for a new prompt; don't reuse old results
+        if let Some(hash) = phash.filter(|_| !frame.reanalyze) {
+            cached = cached_context(&ctx.database, hash, &ctx.theme).await;
+        }
+
+        match cached {
+            Some(context) => {
+                log::debug!("VLM: Cache hit for frame {}", frame.id);
+                if let Some(m) = &metrics {
+                    m.record_vlm_cache_hit();
                 }
-            };
+                outcomes
+                    .push(store_activity(&ctx.database, &frame, context, true, &ctx.theme).await);
+            }
+            None => misses.push((frame, phash)),
+        }
+    }
 
-            // Process frames
-            let mut processed = 0;
-            for frame in pending {
-                match crate::vlm_client::vlm_analyze_frame(&frame.frame_path, &prompt_text).await {
-                    Ok(context) => {
-                        // Create activity log entry
-                        let activity = crate::database::ActivityLogEntry {
-                            id: None,
-                            start_time: frame.captured_at,
-                            end_time: None,
-                            duration_seconds: None,
-                            app_name: context.app_name,
-                            window_title: context.window_title,
-                            category: context.category,
-                            summary: context.summary,
-                            focus_area: context.focus_area,
-                            visible_files: if context.visible_files.is_empty() {
-                                None
-                            } else {
-                                Some(context.visible_files.join(", "))
-                            },
-                            confidence: Some(context.confidence),
-                            frame_ids: Some(frame.id.to_string()),
-                            pinecone_id: None,
-                            supabase_id: None,
-                            synced_at: None,
-                        };
-
-                        if let Ok(activity_id) = database.add_activity(&activity).await {
-                            // Phase 3: Extract and store entities
-                            if let Some(entities_json) = context.entities {
-                                if let Some(obj) = entities_json.as_object() {
-                                    for (entity_type, list) in obj {
-                                        // Only process arrays as entity lists (skip single fields like app_name)
-                                        if let Some(items) = list.as_array() {
-                                            for item in items {
-                                                // Item must have a 'name' field to be a valid entity
-                                                if let Some(name) =
-                                                    item.get("name").and_then(|s| s.as_str())
-                                                {
-                                                    // Extract confidence if present, else use context confidence
-                                                    let conf = item
-                                                        .get("confidence")
-                                                        .and_then(|c| c.as_f64()) // Handle number
-                                                        .or_else(|| {
-                                                            item.get("confidence").and_then(|s| {
-                                                                s.as_str().map(|_| 0.8)
-                                                            })
-                                                        }) // Handle string (high/med/low) - simplified
-                                                        .map(|f| f as f32)
-                                                        .unwrap_or(context.confidence);
-
-                                                    let _ = database
-                                                        .add_entity(
-                                                            activity_id,
-                                                            entity_type,
-                                                            name,
-                                                            Some(item),
-                                                            conf,
-                                                            Some(&active_theme),
-                                                        )
-                                                        .await;
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-
-                            let _ = database.mark_frame_analyzed(frame.id).await;
-                            processed += 1;
-                        }
-                    }
-                    Err(e) => {
-                        log::warn!("VLM Scheduler: Failed to analyze frame {}: {}", frame.id, e);
+    if misses.len() > 1 {
+        let paths: Vec<String> = misses.iter().map(|(f, _)| f.frame_path.clone()).collect();
+        if let Some(m) = &metrics {
+            m.record_vlm_call();
+        }
+        match crate::vlm_client::vlm_analyze_frames_batched(&paths, &ctx.prompt).await {
+            Ok(contexts) => {
+                record_batch_savings(&ctx.batch, misses.len(), &ctx.prompt);
+                for ((frame, phash), context) in misses.into_iter().zip(contexts) {
+                    cache_context(&ctx.database, phash, &ctx.theme, &context).await;
+                    outcomes.push(
+                        store_activity(&ctx.database, &frame, context, false, &ctx.theme).await,
+                    );
+                }
+                return outcomes;
+            }
+            Err(e) => log::warn!(
+                "VLM: Batched request for {} frames failed, sending singly: {}",
+                misses.len(),
+                e
+            ),
+        }
+    }
+
+    for (frame, phash) in misses {
+        if let Some(m) = &metrics {
+            m.record_vlm_call();
+        }
+        outcomes.push(analyze_uncached(ctx, &frame, phash).await);
+    }
+    outcomes
+}
+
+/// Requests (and, roughly, prompt tokens) a successful batch avoided
+fn record_batch_savings(batch: &BatchState, frames: usize, prompt: &str) {
+    let saved_requests = frames.saturating_sub(1) as u64;
+    batch
+        .requests_saved
+        .fetch_add(saved_requests, Ordering::SeqCst);
+    // ~4 characters per token; the prompt would have been repeated per frame
+    batch
+        .tokens_saved
+        .fetch_add(saved_requests * (prompt.len() as u64 / 4), Ordering::SeqCst);
+}
+
+/// Single-frame VLM request for a cache miss
+async fn analyze_uncached(
+    ctx: &BatchContext,
+    frame: &FrameQueueItem,
+    phash: Option<u64>,
+) -> FrameOutcome {
+    match crate::vlm_client::vlm_analyze_frame(&frame.frame_path, &ctx.prompt).await {
+        Ok(context) => {
+            cache_context(&ctx.database, phash, &ctx.theme, &context).await;
+            store_activity(&ctx.database, frame, context, false, &ctx.theme).await
+        }
+        Err(e) => {
+            log::warn!("VLM: Failed to analyze frame {}: {}", frame.id, e);
+            FrameOutcome::AnalysisFailed(format!(
+                "VLM analysis failed for {}: {}",
+                frame.frame_path, e
+            ))
+        }
+    }
+}
+
+/// Remember a fresh VLM result for near-identical screens
+async fn cache_context(
+    database: &DatabaseManager,
+    phash: Option<u64>,
+    active_theme: &str,
+    context: &ActivityContext,
+) {
+    if let (Some(hash), Ok(json)) = (phash, serde_json::to_string(context)) {
+        if let Err(e) = database
+            .put_vlm_cache(hash, active_theme, &json, VLM_CACHE_MAX_ENTRIES)
+            .await
+        {
+            log::warn!("VLM: Failed to cache result: {}", e);
+        }
+    }
+}
+
+/// Store a frame's activity + entities and mark the frame analyzed
+async fn store_activity(
+    database: &DatabaseManager,
+    frame: &FrameQueueItem,
+    context: ActivityContext,
+    from_cache: bool,
+    active_theme: &str,
+) -> FrameOutcome {
+    let activity = ActivityLogEntry {
+        id: None,
+        start_time: frame.captured_at,
+        end_time: None,
+        duration_seconds: None,
+        app_name: context.app_name,
+        window_title: context.window_title,
+        category: context.category,
+        summary: context.summary,
+        focus_area: context.focus_area,
+        visible_files: if context.visible_files.is_empty() {
+            None
+        } else {
+            Some(context.visible_files.join(", "))
+        },
+        confidence: Some(context.confidence),
+        frame_ids: Some(frame.id.to_string()),
+        pinecone_id: None,
+        supabase_id: None,
+        synced_at: None,
+        from_cache,
+    };
+
+    let activity_id = match database.add_activity(&activity).await {
+        Ok(id) => id,
+        Err(e) => return FrameOutcome::StoreFailed(format!("Failed to store activity: {}", e)),
+    };
+
+    // Phase 3: Extract and store entities
+    if let Some(obj) = context.entities.as_ref().and_then(|e| e.as_object()) {
+        for (entity_type, list) in obj {
+            // Only process arrays as entity lists (skip single fields like app_name)
+            if let Some(items) = list.as_array() {
+                for item in items {
+                    // Item must have a 'name' field to be a valid entity
+                    if let Some(name) = item.get("name").and_then(|s| s.as_str()) {
+                        // Numeric confidence if present; string levels (high/med/low) map to 0.8
+                        let conf = item
+                            .get("confidence")
+                            .and_then(|c| c.as_f64())
+                            .or_else(|| {
+                                item.get("confidence").and_then(|s| s.as_str().map(|_| 0.8))
+                            })
+                            .map(|f| f as f32)
+                            .unwrap_or(context.confidence);
+
+                        let _ = database
+                            .add_entity(
+                                activity_id,
+                                entity_type,
+                                name,
+                                Some(item),
+                                conf,
+                                Some(active_theme),
+                            )
+                            .await;
                     }
                 }
             }
+        }
+    }
 
-            frames_processed.fetch_add(processed, Ordering::SeqCst);
-            *last_run.write() = Some(Utc::now());
-            log::info!("VLM Scheduler: Processed {} frames", processed);
+    let _ = database.mark_frame_analyzed(frame.id).await;
+    FrameOutcome::Stored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(h: u32, m: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_opt(h, m, 0)
+            .unwrap()
+    }
+
+    fn policy(quiet: Option<(&str, &str)>) -> VLMSchedulerPolicy {
+        VLMSchedulerPolicy {
+            max_frames_per_hour: HashMap::from([("prospecting".to_string(), 30)]),
+            quiet_hours_start: quiet.map(|q| q.0.to_string()),
+            quiet_hours_end: quiet.map(|q| q.1.to_string()),
+            battery_threshold_pct: Some(20),
         }
+    }
 
-        log::info!("VLM Scheduler loop stopped");
+    #[test]
+    fn quiet_hours_wrap_midnight() {
+        let p = policy(Some(("22:00", "07:00")));
+        for now in [at(23, 30), at(2, 0)] {
+            match evaluate_policy(&p, now, "prospecting", 0, None) {
+                PolicyDecision::Wait { reason, until } => {
+                    assert_eq!(reason, "quiet_hours");
+                    let until = until.unwrap();
+                    assert_eq!(until.time(), NaiveTime::from_hms_opt(7, 0, 0).unwrap());
+                    assert!(until > now);
+                }
+                other => panic!("expected quiet hours at {}, got {:?}", now, other),
+            }
+        }
+        assert!(matches!(
+            evaluate_policy(&p, at(7, 0), "prospecting", 0, None),
+            PolicyDecision::Run { .. }
+        ));
+    }
+
+    #[test]
+    fn battery_guard_only_on_battery() {
+        let p = policy(None);
+        let low = |on_battery| BatteryStatus {
+            on_battery,
+            percent: Some(15),
+        };
+        assert_eq!(
+            evaluate_policy(&p, at(10, 0), "prospecting", 0, Some(low(true))),
+            PolicyDecision::Wait {
+                reason: "battery_low",
+                until: None
+            }
+        );
+        assert!(matches!(
+            evaluate_policy(&p, at(10, 0), "prospecting", 0, Some(low(false))),
+            PolicyDecision::Run { .. }
+        ));
+    }
+
+    #[test]
+    fn hourly_budget_limits_batch_and_resets() {
+        let p = policy(None);
+        assert_eq!(
+            evaluate_policy(&p, at(10, 15), "prospecting", 25, None),
+            PolicyDecision::Run {
+                frame_limit: Some(5)
+            }
+        );
+        assert_eq!(
+            evaluate_policy(&p, at(10, 15), "prospecting", 30, None),
+            PolicyDecision::Wait {
+                reason: "hourly_budget",
+                until: Some(at(11, 0))
+            }
+        );
+        // Themes without a budget are unlimited
+        assert_eq!(
+            evaluate_policy(&p, at(10, 15), "research", 500, None),
+            PolicyDecision::Run { frame_limit: None }
+        );
+
+        let mut usage = HourlyUsage::default();
+        usage.add(at(10, 5), "prospecting", 8);
+        usage.add(at(10, 50), "research", 2);
+        assert_eq!(usage.theme_count(at(10, 59), "prospecting"), 8);
+        assert_eq!(usage.total(at(10, 59)), 10);
+        assert_eq!(usage.total(at(11, 0)), 0);
+    }
+
+    #[test]
+    fn policy_validation() {
+        assert!(policy(Some(("22:00", "07:00"))).validate().is_ok());
+        assert!(policy(Some(("25:00", "07:00"))).validate().is_err());
+        let mut p = policy(None);
+        p.quiet_hours_start = Some("22:00".to_string());
+        assert!(p.validate().is_err());
+        p.quiet_hours_start = None;
+        p.battery_threshold_pct = Some(120);
+        assert!(p.validate().is_err());
     }
 }
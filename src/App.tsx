@@ -3,7 +3,7 @@ import { useState, useEffect, useRef } from "react";
 import { listen } from "@tauri-apps/api/event";
 import { invoke } from "@tauri-apps/api/core";
 import { CommandPalette, useCommandPalette } from "./components/CommandPalette";
-import { debugLog } from "./lib/tauri";
+import { debugLog, type RecordingStateChanged } from "./lib/tauri";
 import "./App.css";
 import { MeetingDetectionBanner } from "./components/MeetingDetectionBanner";
 import { SetupWizard, useSetupRequired } from "./features/onboarding/SetupWizard";
@@ -34,18 +34,20 @@ function App() {
       listeners.push(await listen("menu:search", () => setActiveMode("deck")));
       listeners.push(await listen("menu:insights", () => setActiveMode("deck")));
       listeners.push(await listen("menu:settings", () => setActiveMode("deck")));
-      // Tray menu events
-      listeners.push(await listen("tray:start_recording", async () => {
-        if (!recording.isRecording) {
+      // Tray menu and global shortcuts drive recording in the backend
+      listeners.push(await listen<RecordingStateChanged>("recording-state-changed", (e) => {
+        if (e.payload.is_recording && !recording.isRecording) {
           transcripts.clearLiveTranscripts();
-          await recording.startRecording();
         }
-      }));
-      listeners.push(await listen("tray:stop_recording", async () => {
-        if (recording.isRecording) {
-          await recording.stopRecording();
+        if (!e.payload.is_recording && recording.isRecording) {
           setMeetingListRefreshKey((k) => k + 1);
         }
+        recording.syncFromBackend(e.payload);
+      }));
+      listeners.push(await listen<string>("tray:open_meeting", (e) => {
+        setSelectedMeetingId(e.payload);
+        transcripts.loadTranscripts(e.payload);
+        setActiveMode("deck");
       }));
       listeners.push(await listen("enter-genie-mode", async () => {
         if (!isGenieModeRef.current) {
@@ -53,6 +55,12 @@ function App() {
           setIsGenieMode(true);
         }
       }));
+      // Global shortcut (⌥⌘G by default)
+      listeners.push(await listen("shortcut:toggle_genie", async () => {
+        const next = !isGenieModeRef.current;
+        await invoke("set_genie_mode", { isGenie: next });
+        setIsGenieMode(next);
+      }));
     };
 
     setupListeners();
@@ -19,6 +19,8 @@ interface TimelineEvent {
     state_id: string | null;
     topic: string | null;
     importance: number;
+    importance_reason: string | null;
+    category: string | null;
 }
 
 interface TopicCluster {
@@ -30,6 +32,7 @@ interface TopicCluster {
     end_ts: string | null;
     event_count: number;
     total_duration_ms: number;
+    quality_score: number | null;
 }
 
 interface ActivityTimelineProps {
@@ -57,7 +60,14 @@ const EVENT_ICONS: Record<string, string> = {
     "meeting_start": "🎬",
     "meeting_end": "🏁",
     "topic_change": "🏷️",
-    "activity_gap": "☕"
+    "activity_gap": "☕",
+    "idle_gap": "💤",
+    "mic_silent": "🔇",
+    "quick_note": "📝",
+    "monologue": "🗣️",
+    "question_burst": "❓",
+    "decision": "✅",
+    "action_item": "📌"
 };
 
 export function ActivityTimeline({ meetingId, onEventClick }: ActivityTimelineProps) {
@@ -312,6 +322,12 @@ export function ActivityTimeline({ meetingId, onEventClick }: ActivityTimelinePr
                                                     <span className="value">{formatDuration(event.duration_ms)}</span>
                                                 </div>
                                             )}
+                                            {event.importance_reason && (
+                                                <div className="detail-row">
+                                                    <span className="label">Why:</span>
+                                                    <span className="value">{event.importance_reason}</span>
+                                                </div>
+                                            )}
                                             {event.description && (
                                                 <div className="detail-row description">
                                                     <span className="value">{event.description}</span>
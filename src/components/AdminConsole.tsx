@@ -20,6 +20,24 @@ interface ServiceHealth {
     last_check: string;
 }
 
+type ProbeStatus = 'ok' | 'degraded' | 'down';
+
+interface DependencyHealth {
+    name: string;
+    status: ProbeStatus;
+    latency_ms: number | null;
+    message: string | null;
+    last_error: string | null;
+    checked_at: string;
+}
+
+interface SystemHealthReport {
+    overall: ProbeStatus;
+    services: ServiceHealth[];
+    dependencies: DependencyHealth[];
+    cached: boolean;
+}
+
 interface QueueStats {
     pending: number;
     processing: number;
@@ -35,6 +53,10 @@ interface FeatureFlags {
     vlm_auto_process: boolean;
     enable_ingest: boolean;
     queue_frames_for_vlm: boolean;
+    accessibility_capture_enabled: boolean;
+    ambient_capture_enabled: boolean;
+    obsidian_auto_export: boolean;
+    require_archive_before_delete: boolean;
 }
 
 type AdminTab = 'recordings' | 'data' | 'audit' | 'health' | 'tools' | 'diagnostics' | 'flags' | 'about';
@@ -110,6 +132,8 @@ function AboutPanel() {
 
 function SystemHealth() {
     const [services, setServices] = useState<ServiceHealth[]>([]);
+    const [dependencies, setDependencies] = useState<DependencyHealth[]>([]);
+    const [overall, setOverall] = useState<ProbeStatus | null>(null);
     const [queueStats, setQueueStats] = useState<QueueStats | null>(null);
     const [isLoading, setIsLoading] = useState(true);
     const [error, setError] = useState<string | null>(null);
@@ -118,10 +142,12 @@ function SystemHealth() {
         setIsLoading(true);
         try {
             const [health, queue] = await Promise.all([
-                invoke<ServiceHealth[]>('get_system_health'),
+                invoke<SystemHealthReport>('get_system_health'),
                 invoke<QueueStats>('get_admin_queue_stats')
             ]);
-            setServices(health);
+            setServices(health.services);
+            setDependencies(health.dependencies);
+            setOverall(health.overall);
             setQueueStats(queue);
         } catch (err) {
             setError(err instanceof Error ? err.message : String(err));
@@ -138,18 +164,22 @@ function SystemHealth() {
 
     const getStatusIcon = (status: string) => {
         switch (status) {
-            case 'healthy': return '✅';
+            case 'healthy':
+            case 'ok': return '✅';
             case 'degraded': return '⚠️';
-            case 'error': return '❌';
+            case 'error':
+            case 'down': return '❌';
             default: return '❓';
         }
     };
 
     const getStatusClass = (status: string) => {
         switch (status) {
-            case 'healthy': return 'status-healthy';
+            case 'healthy':
+            case 'ok': return 'status-healthy';
             case 'degraded': return 'status-degraded';
-            case 'error': return 'status-error';
+            case 'error':
+            case 'down': return 'status-error';
             default: return 'status-unknown';
         }
     };
@@ -166,7 +196,10 @@ function SystemHealth() {
     return (
         <div className="system-health">
             <div className="health-header">
-                <h3>🏥 System Health</h3>
+                <h3>
+                    🏥 System Health
+                    {overall && <span className={`overall-status ${getStatusClass(overall)}`}> {getStatusIcon(overall)} {overall}</span>}
+                </h3>
                 <button className="btn btn-icon" onClick={loadHealth} title="Refresh">
                     🔄
                 </button>
@@ -194,6 +227,34 @@ function SystemHealth() {
                 ))}
             </div>
 
+            {dependencies.length > 0 && (
+                <>
+                    <h4>🔌 Dependencies</h4>
+                    <div className="services-grid">
+                        {dependencies.map((dep) => (
+                            <div key={dep.name} className={`service-card ${getStatusClass(dep.status)}`}>
+                                <div className="service-header">
+                                    <span className="service-icon">{getStatusIcon(dep.status)}</span>
+                                    <span className="service-name">{dep.name}</span>
+                                </div>
+                                <div className="service-status">
+                                    {dep.status}
+                                    {dep.latency_ms !== null && ` · ${dep.latency_ms} ms`}
+                                </div>
+                                {dep.message && (
+                                    <div className="service-message">{dep.message}</div>
+                                )}
+                                {dep.last_error && (
+                                    <div className="service-message" title={dep.checked_at}>
+                                        Last error: {dep.last_error}
+                                    </div>
+                                )}
+                            </div>
+                        ))}
+                    </div>
+                </>
+            )}
+
             {queueStats && (
                 <div className="queue-stats">
                     <h4>📊 Ingest Queue</h4>
@@ -278,6 +339,10 @@ function FeatureFlagsPanel() {
         { key: 'vlm_auto_process', label: 'VLM Auto Process', description: 'Automatically process frames with Vision LLM', icon: '🧠' },
         { key: 'enable_ingest', label: 'Intelligence Ingest', description: 'Send data to intelligence pipeline', icon: '📡' },
         { key: 'queue_frames_for_vlm', label: 'Queue VLM Frames', description: 'Queue captured frames for VLM analysis', icon: '📸' },
+        { key: 'accessibility_capture_enabled', label: 'Accessibility Capture', description: 'Read on-screen text from the focused window', icon: '📝' },
+        { key: 'ambient_capture_enabled', label: 'Ambient Capture', description: 'Allow screen capture between meetings', icon: '🌙' },
+        { key: 'obsidian_auto_export', label: 'Obsidian Auto-Export', description: 'Export meetings to the vault when recording stops', icon: '📓' },
+        { key: 'require_archive_before_delete', label: 'Archive Before Delete', description: 'Require recordings to be archived before deletion', icon: '🗄️' },
     ];
 
     return (
@@ -15,6 +15,9 @@ interface RagChatResponse {
     response: string;
     context_used: ContextItem[];
     model: string;
+    conversation_id: string | null;
+    history_turns_used: number;
+    context_chunks_used: number;
 }
 
 interface Message {
@@ -54,6 +57,8 @@ export function CopilotPanel({ meetingId: _meetingId, onClose }: CopilotPanelPro
     const [ragEnabled, setRagEnabled] = useState(true);
     const [expandedContext, setExpandedContext] = useState<string | null>(null);
     const messagesEndRef = useRef<HTMLDivElement>(null);
+    // Thread id so follow-up questions see the earlier turns
+    const conversationIdRef = useRef<string | null>(null);
 
     const scrollToBottom = () => {
         messagesEndRef.current?.scrollIntoView({ behavior: 'smooth' });
@@ -99,7 +104,9 @@ export function CopilotPanel({ meetingId: _meetingId, onClose }: CopilotPanelPro
                     message: text,
                     model: selectedModel,
                     topK: 5,
+                    conversationId: conversationIdRef.current,
                 });
+                conversationIdRef.current = ragResponse.conversation_id;
                 responseContent = ragResponse.response;
                 contextItems = ragResponse.context_used;
             } else {
@@ -4,13 +4,17 @@ import { invoke } from '@tauri-apps/api/core';
 
 interface Entity {
     id: number;
-    activity_id: number;
     entity_type: string;
     name: string;
+    domain: string | null;
+    aliases: string[];
+    mention_count: number;
     metadata: any;
     confidence: number;
     theme: string | null;
-    created_at: string;
+    first_seen: string | null;
+    last_seen: string | null;
+    activity_ids: number[];
 }
 
 interface EntitiesViewProps {
@@ -172,8 +176,17 @@ export const EntitiesView: React.FC<EntitiesViewProps> = ({ className, activeThe
                                             </div>
                                         )}
 
+                                        {entity.aliases.length > 1 && (
+                                            <div className="text-[10px] text-gray-400 truncate mb-1" title={entity.aliases.join(', ')}>
+                                                aka {entity.aliases.filter(a => a !== entity.name).join(', ')}
+                                            </div>
+                                        )}
+
                                         <div className="mt-3 pt-3 border-t border-gray-50 flex justify-between items-center text-[10px] text-gray-400">
-                                            <span>{new Date(entity.created_at).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' })}</span>
+                                            <span>
+                                                {entity.mention_count} mention{entity.mention_count === 1 ? '' : 's'}
+                                                {entity.last_seen && ` · ${new Date(entity.last_seen).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' })}`}
+                                            </span>
                                             {entity.theme && (
                                                 <span className="capitalize bg-gray-50 px-1.5 py-0.5 rounded text-gray-500">
                                                     {entity.theme}
@@ -12,6 +12,7 @@ interface KBSearchResult {
     category: string | null;
     summary: string;
     score: number | null;
+    match_sources: string[];
 }
 
 interface SearchOptions {
@@ -43,8 +44,8 @@ export function KBSearch() {
                 sources: searchSources,
             };
 
-            const searchResults = await invoke<KBSearchResult[]>("search_knowledge_base", { options });
-            setResults(searchResults);
+            const response = await invoke<{ results: KBSearchResult[] }>("search_knowledge_base", { options });
+            setResults(response.results);
         } catch (err) {
             console.error("Search failed:", err);
             setError(String(err));
@@ -0,0 +1,48 @@
+// Mini input shown by the quick-note shortcut and tray item. Saves the note
+// against the recording meeting (or parks it) and closes itself.
+import React, { useState } from 'react';
+import { getCurrentWindow } from '@tauri-apps/api/window';
+import { quickNote } from '../lib/tauri';
+
+export const QuickNoteInput: React.FC = () => {
+    const [text, setText] = useState('');
+    const [error, setError] = useState<string | null>(null);
+
+    const close = () => getCurrentWindow().close();
+
+    const onKeyDown = async (e: React.KeyboardEvent<HTMLInputElement>) => {
+        if (e.key === 'Escape') {
+            await close();
+        } else if (e.key === 'Enter' && text.trim()) {
+            try {
+                await quickNote(text);
+                await close();
+            } catch (err) {
+                setError(String(err));
+            }
+        }
+    };
+
+    return (
+        <div style={{ padding: 12, background: 'rgba(15, 23, 42, 0.95)', height: '100vh', boxSizing: 'border-box' }}>
+            <input
+                autoFocus
+                value={text}
+                onChange={(e) => { setText(e.target.value); setError(null); }}
+                onKeyDown={onKeyDown}
+                onBlur={close}
+                placeholder={error ?? '📝 Quick note — Enter to save, Esc to cancel'}
+                style={{
+                    width: '100%',
+                    padding: '8px 12px',
+                    fontSize: 15,
+                    color: '#f1f5f9',
+                    background: 'transparent',
+                    border: `1px solid ${error ? '#ef4444' : '#334155'}`,
+                    borderRadius: 8,
+                    outline: 'none',
+                }}
+            />
+        </div>
+    );
+};
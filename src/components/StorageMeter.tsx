@@ -8,6 +8,7 @@ interface StorageStats {
     total_bytes: number;
     video_bytes: number;
     frames_bytes: number;
+    audio_bytes: number;
     meetings_count: number;
     chunks_count: number;
     usage_percent: number;
@@ -20,6 +20,7 @@ interface StudyMaterials {
     summary: string;
     concepts: KeyConcept[];
     quiz: QuizQuestion[];
+    flashcards?: { front: string; back: string }[];
     created_at: string;
 }
 
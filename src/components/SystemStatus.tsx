@@ -2,9 +2,10 @@
 import { useEffect, useState } from "react";
 import { listen } from "@tauri-apps/api/event";
 import { invoke } from "@tauri-apps/api/core";
+import type { SubsystemStatus } from "../lib/tauri";
 
 export function SystemStatus({ onComplete, onRetry }: { onComplete?: () => void, onRetry: () => void }) {
-    const [steps, setSteps] = useState<{ msg: string, time: string }[]>([]);
+    const [steps, setSteps] = useState<{ msg: string, time: string, warn?: boolean }[]>([]);
     const [status, setStatus] = useState<"initializing" | "ready" | "failed">("initializing");
     const [error, setError] = useState<string | null>(null);
 
@@ -13,6 +14,12 @@ export function SystemStatus({ onComplete, onRetry }: { onComplete?: () => void,
             listen<string>("init-step", (e) => {
                 setSteps(prev => [...prev, { msg: e.payload, time: new Date().toLocaleTimeString() }]);
             }),
+            // Optional subsystems don't block startup; say which ones are unavailable
+            listen<SubsystemStatus>("subsystem-status", (e) => {
+                if (e.payload.state !== "failed") return;
+                const msg = `${e.payload.label} unavailable: ${e.payload.message ?? "unknown error"} (retried on first use)`;
+                setSteps(prev => [...prev, { msg, time: new Date().toLocaleTimeString(), warn: true }]);
+            }),
             listen<string>("init-error", (e) => {
                 setError(e.payload);
                 setStatus("failed");
@@ -72,7 +79,9 @@ export function SystemStatus({ onComplete, onRetry }: { onComplete?: () => void,
                     {steps.map((s, i) => (
                         <div key={i} style={{ display: 'flex', gap: '12px' }}>
                             <span style={{ color: '#6b7280' }}>[{s.time}]</span>
-                            <span style={{ color: '#10b981' }}>✓</span>
+                            {s.warn
+                                ? <span style={{ color: '#f59e0b' }}>⚠</span>
+                                : <span style={{ color: '#10b981' }}>✓</span>}
                             <span>{s.msg}</span>
                         </div>
                     ))}
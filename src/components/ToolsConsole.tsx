@@ -1,8 +1,9 @@
 // Management Suite - Tools Console
-// Job history, database stats, and queue control
+// Job history, database stats, queue control, and settings transfer
 
 import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { open, save } from '@tauri-apps/plugin-dialog';
 
 // =============================================================================
 // Types
@@ -25,6 +26,40 @@ interface DatabaseStats {
     entities: number;
     frame_queue: number;
     audit_log: number;
+    vlm_cache: number;
+    write_queue: {
+        depth: number;
+        max_depth: number;
+        longest_wait_ms: number;
+        writes: number;
+    };
+}
+
+interface MaintenanceReport {
+    started_at: string;
+    duration_ms: number;
+    integrity_ok: boolean | null;
+    integrity_errors: string[];
+    fts_rebuilt: string[];
+    reindexed: boolean;
+    vacuumed: boolean;
+    dangling_keyframes: number;
+    keyframe_paths_cleared: number;
+    orphans: { table: string; parent: string; count: number }[];
+    orphans_deleted: number;
+    skipped: string[];
+    errors: string[];
+}
+
+interface SettingIssue {
+    key: string;
+    reason: string;
+}
+
+interface SettingsImportReport {
+    imported: string[];
+    skipped: SettingIssue[];
+    failed: SettingIssue[];
 }
 
 // =============================================================================
@@ -38,6 +73,10 @@ export function ToolsConsole() {
     const [error, setError] = useState<string | null>(null);
     const [queuePaused, setQueuePaused] = useState(false);
     const [isPausing, setIsPausing] = useState(false);
+    const [maintenance, setMaintenance] = useState<MaintenanceReport | null>(null);
+    const [isMaintaining, setIsMaintaining] = useState(false);
+    const [transferStatus, setTransferStatus] = useState<string | null>(null);
+    const [importReport, setImportReport] = useState<SettingsImportReport | null>(null);
 
     const loadData = useCallback(async () => {
         setIsLoading(true);
@@ -76,6 +115,64 @@ export function ToolsConsole() {
         }
     };
 
+    const handleMaintenance = async (repair: boolean) => {
+        setIsMaintaining(true);
+        try {
+            const report = await invoke<MaintenanceReport>('run_database_maintenance', {
+                options: repair
+                    ? { clear_dangling_keyframes: true, delete_orphans: true }
+                    : null
+            });
+            setMaintenance(report);
+            await loadData();
+        } catch (err) {
+            setError(err instanceof Error ? err.message : String(err));
+        } finally {
+            setIsMaintaining(false);
+        }
+    };
+
+    const handleExportSettings = async (includeSecrets: boolean) => {
+        const path = await save({
+            defaultPath: 'nofriction-settings.json',
+            filters: [{ name: 'Settings', extensions: ['json'] }]
+        });
+        if (!path) return;
+        const passphrase = includeSecrets
+            ? window.prompt('Passphrase to encrypt API keys and tokens')
+            : null;
+        if (includeSecrets && !passphrase) return;
+        try {
+            const summary = await invoke<{ settings: number; secrets: number }>('export_settings', {
+                path,
+                includeSecrets,
+                passphrase
+            });
+            setImportReport(null);
+            setTransferStatus(`Exported ${summary.settings} settings and ${summary.secrets} secrets`);
+        } catch (err) {
+            setError(err instanceof Error ? err.message : String(err));
+        }
+    };
+
+    const handleImportSettings = async () => {
+        const path = await open({
+            multiple: false,
+            filters: [{ name: 'Settings', extensions: ['json'] }]
+        });
+        if (!path || Array.isArray(path)) return;
+        const passphrase = window.prompt('Passphrase for secrets (leave empty to skip them)') || null;
+        try {
+            const report = await invoke<SettingsImportReport>('import_settings', { path, passphrase });
+            setImportReport(report);
+            setTransferStatus(
+                `Imported ${report.imported.length} · skipped ${report.skipped.length} · failed ${report.failed.length}`
+            );
+        } catch (err) {
+            setError(err instanceof Error ? err.message : String(err));
+        }
+    };
+
     const formatDate = (dateStr: string) => {
         const date = new Date(dateStr);
         return date.toLocaleString('en-US', {
@@ -174,10 +271,69 @@ export function ToolsConsole() {
                             <div className="stat-value">{dbStats.audit_log.toLocaleString()}</div>
                             <div className="stat-label">Audit Entries</div>
                         </div>
+                        <div className="db-stat">
+                            <div className="stat-value">
+                                {dbStats.write_queue.depth} / {dbStats.write_queue.max_depth}
+                            </div>
+                            <div className="stat-label">Write Queue (now / peak)</div>
+                        </div>
+                        <div className="db-stat">
+                            <div className="stat-value">{dbStats.write_queue.longest_wait_ms.toFixed(1)}ms</div>
+                            <div className="stat-label">Longest Write Wait</div>
+                        </div>
                     </div>
                 </div>
             )}
 
+            {/* Database Maintenance */}
+            <div className="queue-control-section">
+                <div className="queue-control-header">
+                    <h4>🩺 Database Maintenance</h4>
+                    <div>
+                        <button className="btn" onClick={() => handleMaintenance(false)} disabled={isMaintaining}>
+                            {isMaintaining ? '...' : 'Check & Rebuild Index'}
+                        </button>
+                        <button className="btn btn-danger" onClick={() => handleMaintenance(true)} disabled={isMaintaining}>
+                            Repair
+                        </button>
+                    </div>
+                </div>
+                {maintenance && (
+                    <p className="queue-status-text">
+                        Integrity {maintenance.integrity_ok === false ? `failed (${maintenance.integrity_errors.length} problems)` : 'ok'}
+                        {' · '}{maintenance.fts_rebuilt.length} search indexes rebuilt
+                        {' · '}{maintenance.dangling_keyframes} missing keyframes ({maintenance.keyframe_paths_cleared} cleared)
+                        {' · '}{maintenance.orphans.reduce((n, o) => n + o.count, 0)} orphaned rows ({maintenance.orphans_deleted} deleted)
+                        {' · '}{formatDuration(maintenance.duration_ms)}
+                        {maintenance.errors.length > 0 && ` · errors: ${maintenance.errors.join('; ')}`}
+                    </p>
+                )}
+            </div>
+
+            {/* Settings Transfer */}
+            <div className="queue-control-section">
+                <div className="queue-control-header">
+                    <h4>📦 Settings Transfer</h4>
+                    <div>
+                        <button className="btn" onClick={() => handleExportSettings(false)}>
+                            Export
+                        </button>
+                        <button className="btn" onClick={() => handleExportSettings(true)}>
+                            Export with Secrets
+                        </button>
+                        <button className="btn" onClick={handleImportSettings}>
+                            Import
+                        </button>
+                    </div>
+                </div>
+                {transferStatus && <p className="queue-status-text">{transferStatus}</p>}
+                {importReport && importReport.failed.length > 0 && (
+                    <p className="queue-status-text">
+                        Failed: {importReport.failed.map(i => `${i.key} (${i.reason})`).join('; ')}
+                    </p>
+                )}
+            </div>
+
             {/* Job History */}
             <div className="job-history-section">
                 <h4>📋 Recent Jobs</h4>
@@ -17,6 +17,19 @@ interface CaptureDiagnostics {
     is_recording: boolean;
     screen_permission: boolean;
     mic_permission: boolean;
+    capture_all_monitors: boolean;
+    capture_window_id: number | null;
+    monitor_health: MonitorHealth[];
+}
+
+interface MonitorHealth {
+    monitor_id: number;
+    name: string;
+    frames_captured: number;
+    failures: number;
+    consecutive_failures: number;
+    last_frame_at: string | null;
+    last_error: string | null;
 }
 
 interface TestCaptureResult {
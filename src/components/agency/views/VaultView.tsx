@@ -569,7 +569,11 @@ export const VaultView: React.FC<VaultViewProps> = ({ onSelectMeeting: _onSelect
                                     {' · '}
                                     <Building2 size={12} /> {intelResult.companies_count} companies
                                 </span>
-                                <span className="intel-result-detail">Notes saved to Obsidian vault</span>
+                                <span className="intel-result-detail">
+                                    {intelResult.failed_count > 0
+                                        ? `Notes saved to Obsidian vault · ${intelResult.failed_count} failed`
+                                        : 'Notes saved to Obsidian vault'}
+                                </span>
                             </div>
                             <button className="dismiss-btn" onClick={() => setIntelResult(null)}><X size={14} /></button>
                         </div>
@@ -23,6 +23,9 @@ interface RagChatResponse {
     response: string;
     context_used: ContextItem[];
     model: string;
+    conversation_id: string | null;
+    history_turns_used: number;
+    context_chunks_used: number;
 }
 
 interface ChatMessage {
@@ -59,6 +62,8 @@ export function AIChat({ meetingId }: AIChatProps) {
     const [isCapturing, setIsCapturing] = useState(false);
 
     const chatRef = useRef<HTMLDivElement>(null);
+    // Thread id so follow-up questions see the earlier turns
+    const conversationIdRef = useRef<string | null>(null);
     const inputRef = useRef<HTMLTextAreaElement>(null);
 
     // Check TheBrain status on mount
@@ -107,6 +112,7 @@ export function AIChat({ meetingId }: AIChatProps) {
                 text_preview: string;
                 word_count: number;
                 source: string;
+                fallback_reason: string | null;
             }>("capture_accessibility_snapshot");
 
             setCaptureCount(prev => prev + 1);
@@ -115,7 +121,7 @@ export function AIChat({ meetingId }: AIChatProps) {
             const captureMsg: ChatMessage = {
                 id: `capture-${Date.now()}`,
                 role: "system",
-                content: `📸 Captured ${result.word_count} words from screen. Preview: "${result.text_preview.slice(0, 100)}..."`,
+                content: `📸 Captured ${result.word_count} words from screen${result.source === "ocr" ? " (OCR)" : ""}. Preview: "${result.text_preview.slice(0, 100)}..."`,
                 timestamp: new Date(),
             };
             setMessages(prev => [...prev, captureMsg]);
@@ -156,7 +162,9 @@ export function AIChat({ meetingId }: AIChatProps) {
                     message: userMessage.content,
                     model: selectedModel,
                     topK: 5,
+                    conversationId: conversationIdRef.current,
                 });
+                conversationIdRef.current = ragResponse.conversation_id;
                 responseContent = ragResponse.response;
                 contextItems = ragResponse.context_used;
             } else {
@@ -238,7 +246,9 @@ export function AIChat({ meetingId }: AIChatProps) {
                 message: prompt,
                 model: selectedModel,
                 topK: 10,
+                conversationId: conversationIdRef.current,
             });
+            conversationIdRef.current = ragResponse.conversation_id;
 
             setMessages((prev) => [...prev, {
                 id: `assistant-${Date.now()}`,
@@ -45,6 +45,7 @@ export function SetupWizard({ onComplete }: SetupWizardProps) {
             if (state.deepgramApiKey.trim()) {
                 await invoke('set_deepgram_api_key', {
                     apiKey: state.deepgramApiKey.trim(),
+                    validate: true,
                 });
             }
 
@@ -10,6 +10,14 @@ interface OllamaModel {
     modified_at: string;
 }
 
+interface AIProviderStatus {
+    backend: "local" | "remote";
+    url: string;
+    reachable: boolean;
+    models: string[];
+    error: string | null;
+}
+
 
 
 export function AISettings() {
@@ -23,39 +31,45 @@ export function AISettings() {
     // Remote settings
     const [remoteUrl, setRemoteUrl] = useState("https://api.openai.com/v1");
     const [remoteKey, setRemoteKey] = useState("");
+    const [remoteModel, setRemoteModel] = useState("");
+    const [remoteStatus, setRemoteStatus] = useState<AIProviderStatus | null>(null);
 
     // Load settings on mount
     useEffect(() => {
         loadSettings();
     }, []);
 
-    // Check Ollama only if selected
+    // Check whichever backend is selected
     useEffect(() => {
         if (aiProvider === "local") {
             checkOllamaStatus();
+        } else {
+            checkRemoteStatus();
         }
     }, [aiProvider]);
 
     const loadSettings = async () => {
         try {
-            const [provider, url, key] = await invoke<[string, string | null, string | null]>("get_ai_provider_settings");
+            const [provider, url, key, model] = await invoke<[string, string | null, string | null, string | null]>("get_ai_provider_settings");
             // Basic validation to prevent invalid state
             if (provider === "local" || provider === "remote") {
                 setAiProvider(provider);
             }
             if (url) setRemoteUrl(url);
             if (key) setRemoteKey(key);
+            if (model) setRemoteModel(model);
         } catch (err) {
             console.error("Failed to load AI settings:", err);
         }
     };
 
-    const saveSettings = async (newProvider?: string, newUrl?: string, newKey?: string) => {
+    const saveSettings = async (newProvider?: string, newUrl?: string, newKey?: string, newModel?: string) => {
         try {
             await invoke("set_ai_provider_settings", {
                 provider: newProvider || aiProvider,
                 url: newUrl !== undefined ? newUrl : null,
-                key: newKey !== undefined ? newKey : null
+                key: newKey !== undefined ? newKey : null,
+                model: newModel !== undefined ? newModel : null
             });
         } catch (err) {
             console.error("Failed to save AI settings:", err);
@@ -71,7 +85,8 @@ export function AISettings() {
     const checkOllamaStatus = async () => {
         setIsLoading(true);
         try {
-            const available = await invoke<boolean>("check_ollama");
+            const status = await invoke<AIProviderStatus>("check_ai_provider");
+            const available = status.backend === "local" && status.reachable;
             setOllamaAvailable(available);
 
             if (available) {
@@ -90,6 +105,24 @@ export function AISettings() {
         }
     };
 
+    const checkRemoteStatus = async () => {
+        setIsLoading(true);
+        try {
+            setRemoteStatus(await invoke<AIProviderStatus>("check_ai_provider"));
+        } catch (err) {
+            console.error("Failed to check remote provider:", err);
+            setRemoteStatus(null);
+        } finally {
+            setIsLoading(false);
+        }
+    };
+
+    // Persist a remote field, then re-check the endpoint with it
+    const saveRemote = async (url?: string, key?: string, model?: string) => {
+        await saveSettings("remote", url, key, model);
+        await checkRemoteStatus();
+    };
+
     return (
         <div className="ai-settings">
             <section className="settings-section">
@@ -221,6 +254,7 @@ export function AISettings() {
                                 className="settings-input"
                                 value={remoteUrl}
                                 onChange={(e) => setRemoteUrl(e.target.value)}
+                                onBlur={() => saveRemote(remoteUrl)}
                                 placeholder="https://api.openai.com/v1"
                             />
                         </div>
@@ -236,10 +270,46 @@ export function AISettings() {
                                 className="settings-input"
                                 value={remoteKey}
                                 onChange={(e) => setRemoteKey(e.target.value)}
+                                onBlur={() => saveRemote(undefined, remoteKey)}
                                 placeholder="sk-..."
                             />
                         </div>
                     </div>
+                    <div className="settings-row">
+                        <div className="settings-label">
+                            <span className="label-main">Model</span>
+                            <span className="label-sub">Leave empty to use the preset models</span>
+                        </div>
+                        <div className="settings-control">
+                            <input
+                                type="text"
+                                className="settings-input"
+                                list="remote-models"
+                                value={remoteModel}
+                                onChange={(e) => setRemoteModel(e.target.value)}
+                                onBlur={() => saveRemote(undefined, undefined, remoteModel)}
+                                placeholder="gpt-4o-mini"
+                            />
+                            <datalist id="remote-models">
+                                {remoteStatus?.models.map((m) => <option key={m} value={m} />)}
+                            </datalist>
+                        </div>
+                    </div>
+                    <div className="settings-row">
+                        <div className="settings-label">
+                            <span className="label-main">Status</span>
+                            {remoteStatus?.error && <span className="label-sub">{remoteStatus.error}</span>}
+                        </div>
+                        <div className="settings-control">
+                            {isLoading ? (
+                                <span className="status-badge loading">Checking...</span>
+                            ) : remoteStatus?.reachable ? (
+                                <span className="status-badge success">✓ Connected ({remoteStatus.models.length} models)</span>
+                            ) : (
+                                <span className="status-badge error">✗ Not Available</span>
+                            )}
+                        </div>
+                    </div>
                 </section>
             )}
         </div>
@@ -1,13 +1,14 @@
 // Intelligence Pipeline Settings Component
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { getIngestQueueStats, type IngestQueueStats } from "../../lib/tauri";
 
 
 export function IngestSettings() {
     const [enabled, setEnabled] = useState(false);
     const [baseUrl, setBaseUrl] = useState("https://localhost:8080");
     const [bearerToken, setBearerToken] = useState("");
-    const [queueStats, setQueueStats] = useState<[number, number]>([0, 0]);
+    const [queueStats, setQueueStats] = useState<IngestQueueStats | null>(null);
     const [connectionStatus, setConnectionStatus] = useState<"unknown" | "connected" | "error">("unknown");
     const [saving, setSaving] = useState(false);
 
@@ -53,8 +54,7 @@ export function IngestSettings() {
 
     const refreshQueueStats = async () => {
         try {
-            const stats: [number, number] = await invoke("get_ingest_queue_stats");
-            setQueueStats(stats);
+            setQueueStats(await getIngestQueueStats());
         } catch (err) {
             console.error("Failed to get queue stats:", err);
         }
@@ -120,8 +120,10 @@ export function IngestSettings() {
                     <div className="settings-row">
                         <label>Upload Queue</label>
                         <div className="queue-stats">
-                            <span>Pending: {queueStats[0]}</span>
-                            <span>Failed: {queueStats[1]}</span>
+                            <span>Pending: {queueStats?.pending ?? 0}</span>
+                            <span>Uploading: {queueStats?.uploading ?? 0}</span>
+                            <span>Done: {queueStats?.done ?? 0}</span>
+                            <span>Failed: {queueStats?.failed ?? 0}</span>
                         </div>
                     </div>
                 </>
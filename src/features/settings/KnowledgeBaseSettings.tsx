@@ -109,7 +109,15 @@ export function KnowledgeBaseSettings() {
         if (!supabaseConnString.trim()) return;
         setIsSaving(true);
         try {
-            await invoke("configure_supabase", { connectionString: supabaseConnString });
+            try {
+                await invoke("configure_supabase", { connectionString: supabaseConnString });
+            } catch (err) {
+                // Fresh project: offer to create the tables
+                if (!String(err).includes("tables are missing") || !confirm(`${err}\n\nCreate the missing tables now?`)) {
+                    throw err;
+                }
+                await invoke("configure_supabase", { connectionString: supabaseConnString, createSchema: true });
+            }
             await checkHealth();
         } catch (err) {
             console.error("Failed to configure Supabase:", err);
@@ -1,5 +1,13 @@
 import { useEffect, useState } from 'react';
-import { getActiveTheme, setActiveTheme } from '../../lib/tauri';
+import { listen } from '@tauri-apps/api/event';
+import {
+    getActiveTheme,
+    setActiveTheme,
+    getThemeSuggestion,
+    acceptThemeSuggestion,
+    dismissThemeSuggestion,
+    type ThemeSuggestion,
+} from '../../lib/tauri';
 
 interface ThemeSelectorProps {
     compact?: boolean;
@@ -55,9 +63,17 @@ const THEMES: ThemeConfig[] = [
 export default function ThemeSelector({ compact = false }: ThemeSelectorProps) {
     const [activeTheme, setActiveThemeState] = useState<string>('prospecting');
     const [isOpen, setIsOpen] = useState(false);
+    const [suggestion, setSuggestion] = useState<ThemeSuggestion | null>(null);
 
     useEffect(() => {
         loadActiveTheme();
+        getThemeSuggestion().then(setSuggestion).catch(() => setSuggestion(null));
+        const unlisten = listen<ThemeSuggestion>('theme-suggestion', (event) => {
+            setSuggestion(event.payload);
+        });
+        return () => {
+            unlisten.then((fn) => fn());
+        };
     }, []);
 
     const loadActiveTheme = async () => {
@@ -79,7 +95,29 @@ export default function ThemeSelector({ compact = false }: ThemeSelectorProps) {
         }
     };
 
+    const handleAcceptSuggestion = async () => {
+        if (!suggestion) return;
+        try {
+            await acceptThemeSuggestion(suggestion.id);
+            setActiveThemeState(suggestion.proposed_theme);
+        } catch (error) {
+            console.error('Failed to accept theme suggestion:', error);
+        }
+        setSuggestion(null);
+    };
+
+    const handleDismissSuggestion = async () => {
+        if (!suggestion) return;
+        try {
+            await dismissThemeSuggestion(suggestion.id);
+        } catch (error) {
+            console.error('Failed to dismiss theme suggestion:', error);
+        }
+        setSuggestion(null);
+    };
+
     const currentTheme = THEMES.find(t => t.id === activeTheme) || THEMES[0];
+    const suggestedTheme = suggestion ? THEMES.find(t => t.id === suggestion.proposed_theme) : undefined;
 
     if (compact) {
         return (
@@ -95,6 +133,17 @@ export default function ThemeSelector({ compact = false }: ThemeSelectorProps) {
                     <span className="theme-label">{currentTheme.label}</span>
                 </button>
 
+                {suggestion && suggestedTheme && (
+                    <div className="theme-suggestion" style={{ borderLeft: `3px solid ${suggestedTheme.color}` }}>
+                        <span>
+                            {suggestion.observed_mins} min of {suggestedTheme.icon} {suggestedTheme.label} work.
+                            Switch? ({Math.round(suggestion.confidence * 100)}%)
+                        </span>
+                        <button onClick={handleAcceptSuggestion}>Switch</button>
+                        <button onClick={handleDismissSuggestion}>Dismiss</button>
+                    </div>
+                )}
+
                 {isOpen && (
                     <>
                         <div
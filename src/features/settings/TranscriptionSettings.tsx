@@ -56,7 +56,7 @@ export function TranscriptionSettings({ onSave }: TranscriptionSettingsProps) {
             // Save keys if changed (length > 0 && not masked)
             if (deepgramKey && !deepgramKey.includes("****")) {
                 try {
-                    await invoke("set_deepgram_api_key", { apiKey: deepgramKey });
+                    await invoke("set_deepgram_api_key", { apiKey: deepgramKey, validate: true });
                 } catch (err) {
                     const errorMsg = err instanceof Error ? err.message : String(err);
                     console.error("❌ Failed to save Deepgram key:", errorMsg);
@@ -67,7 +67,7 @@ export function TranscriptionSettings({ onSave }: TranscriptionSettingsProps) {
             }
             if (geminiKey && !geminiKey.includes("****")) {
                 try {
-                    await invoke("set_gemini_api_key", { apiKey: geminiKey });
+                    await invoke("set_gemini_api_key", { apiKey: geminiKey, validate: true });
                 } catch (err) {
                     const errorMsg = err instanceof Error ? err.message : String(err);
                     console.error("❌ Failed to save Gemini key:", errorMsg);
@@ -86,7 +86,7 @@ export function TranscriptionSettings({ onSave }: TranscriptionSettingsProps) {
             }
             if (gladiaKey && !gladiaKey.includes("****")) {
                 try {
-                    await invoke("set_gladia_api_key", { apiKey: gladiaKey });
+                    await invoke("set_gladia_api_key", { apiKey: gladiaKey, validate: true });
                 } catch (err) {
                     const errorMsg = err instanceof Error ? err.message : String(err);
                     console.error("❌ Failed to save Gladia key:", errorMsg);
@@ -97,7 +97,7 @@ export function TranscriptionSettings({ onSave }: TranscriptionSettingsProps) {
             }
             if (googleKey && !googleKey.includes("****")) {
                 try {
-                    await invoke("set_google_stt_key", { keyJson: googleKey });
+                    await invoke("set_google_stt_key", { keyJson: googleKey, validate: true });
                 } catch (err) {
                     const errorMsg = err instanceof Error ? err.message : String(err);
                     console.error("❌ Failed to save Google STT key:", errorMsg);
@@ -136,6 +136,19 @@ export function useRecording() {
         // await tauri.pauseRecording();
     }, []);
 
+    // Recording started, stopped or paused outside the webview (tray, global shortcuts)
+    const syncFromBackend = useCallback((change: tauri.RecordingStateChanged) => {
+        setState((prev) => ({
+            ...prev,
+            isRecording: change.is_recording,
+            isPaused: change.is_paused,
+            meetingId: change.meeting_id ?? prev.meetingId,
+            ...(change.is_recording && !prev.isRecording
+                ? { duration: 0, videoFrames: 0, audioSamples: 0 }
+                : {}),
+        }));
+    }, []);
+
     const toggleRecording = useCallback(async () => {
         if (state.isRecording) {
             await stopRecording();
@@ -151,5 +164,6 @@ export function useRecording() {
         stopRecording,
         pauseRecording,
         toggleRecording,
+        syncFromBackend,
     };
 }
@@ -6,9 +6,73 @@ import { invoke } from "@tauri-apps/api/core";
 // Types
 export interface RecordingStatus {
     is_recording: boolean;
+    is_paused: boolean;
     duration_seconds: number;
     video_frames: number;
     audio_samples: number;
+    transcripts_pending_retry: number;
+    transcripts_failed: number;
+    transcription_health: ConnectionHealth;
+    /** Provider reconnecting or given up while recording */
+    transcription_degraded: boolean;
+}
+
+export type ConnectionHealth =
+    | { state: "idle" | "connecting" | "connected" }
+    | { state: "reconnecting"; attempt: number; max_attempts: number; buffered_secs: number }
+    | { state: "failed"; reason: string };
+
+/** Level of one source over the last ~200ms window */
+export interface AudioLevel {
+    rms: number;
+    peak: number;
+    rms_db: number;
+    peak_db: number;
+}
+
+/** Payload of the "audio-level" event (~5 Hz) */
+export interface AudioLevels {
+    microphone: AudioLevel | null;
+    system: AudioLevel | null;
+    mic_silent_secs: number;
+    at: string;
+}
+
+/** Warn when the microphone goes dead mid-recording */
+export interface SilenceWarningPolicy {
+    enabled: boolean;
+    threshold_db: number;
+    after_secs: number;
+}
+
+/** Payload of the "silence-warning" event */
+export interface SilenceWarning {
+    meeting_id: string | null;
+    since: string;
+    silent_secs: number;
+    threshold_db: number;
+    level_db: number | null;
+}
+
+export type CheckInDecision = "continue" | "pause";
+
+/** "Still capturing?" check-ins during ambient capture */
+export interface CheckInPolicy {
+    enabled: boolean;
+    interval_mins: number;
+    timeout_secs: number;
+    default_action: CheckInDecision; // applied when the check-in goes unanswered
+}
+
+/** Payload of the "interaction-checkin" event */
+export interface CheckInEvent {
+    id: string;
+    title: string;
+    message: string;
+    created_at: string;
+    expires_at: string | null;
+    capturing_mins: number;
+    default_action: CheckInDecision;
 }
 
 export interface AudioDevice {
@@ -34,14 +98,55 @@ export interface Meeting {
     duration_seconds: number | null;
 }
 
+export interface MeetingFilter {
+    date_from?: string | null;
+    date_to?: string | null;
+    title_query?: string | null;
+    min_duration_secs?: number | null;
+    has_notes?: boolean | null;
+    include_archived?: boolean | null;
+}
+
+export interface MeetingSummary extends Meeting {
+    transcript_count: number;
+    frame_count: number;
+    has_notes: boolean;
+}
+
+export interface MeetingPage {
+    meetings: MeetingSummary[];
+    total: number;
+    offset: number;
+    limit: number;
+}
+
 export interface Transcript {
     id: number;
     meeting_id: string;
     text: string;
     speaker: string | null;
+    speaker_label: string | null;
     timestamp: string;
     is_final: boolean;
     confidence: number;
+    /** Transcribed from audio buffered while the provider was reconnecting */
+    after_reconnect: boolean;
+}
+
+export interface TranscriptRevision {
+    id: number;
+    transcript_id: number;
+    meeting_id: string;
+    previous_text: string;
+    new_text: string;
+    edited_at: string;
+}
+
+export interface MeetingSpeaker {
+    meeting_id: string;
+    speaker_label: string;
+    display_name: string | null;
+    segment_count: number;
 }
 
 export interface Frame {
@@ -60,6 +165,20 @@ export interface SearchResult {
     relevance: number;
 }
 
+export type SearchSource = "transcript" | "snapshot" | "ocr";
+
+export interface UnifiedSearchResult {
+    source: SearchSource;
+    /** Transcript id, snapshot id or frame id, depending on source */
+    id: string;
+    meeting_id: string | null;
+    meeting_title: string | null;
+    timestamp: string;
+    /** Matching excerpt with hits wrapped in <mark> tags */
+    snippet: string;
+    score: number;
+}
+
 export interface TranscriptEvent {
     text: string;
     is_final: boolean;
@@ -67,9 +186,11 @@ export interface TranscriptEvent {
     start: number;
     duration: number;
     speaker: string | null;
+    after_reconnect?: boolean;
 }
 
 export interface AppSettings {
+    /** Always null: secrets are never returned, see getSecretStatus */
     deepgram_api_key: string | null;
     selected_microphone: string | null;
     selected_monitor: number | null;
@@ -86,13 +207,21 @@ export async function stopRecording(): Promise<void> {
     return invoke("stop_recording");
 }
 
+export async function pauseRecording(): Promise<void> {
+    return invoke("pause_recording");
+}
+
+export async function resumeRecording(): Promise<void> {
+    return invoke("resume_recording");
+}
+
 export async function getRecordingStatus(): Promise<RecordingStatus> {
     return invoke<RecordingStatus>("get_recording_status");
 }
 
 // Screenshot command (for preview)
-export async function captureScreenshot(monitorId?: number): Promise<string> {
-    return invoke<string>("capture_screenshot", { monitorId });
+export async function captureScreenshot(monitorId?: number, windowId?: number): Promise<string> {
+    return invoke<string>("capture_screenshot", { monitorId, windowId });
 }
 
 // Transcript commands
@@ -100,10 +229,116 @@ export async function getTranscripts(meetingId: string): Promise<Transcript[]> {
     return invoke<Transcript[]>("get_transcripts", { meetingId });
 }
 
+export async function updateTranscriptText(transcriptId: number, newText: string): Promise<TranscriptRevision | null> {
+    return invoke<TranscriptRevision | null>("update_transcript_text", { transcriptId, newText });
+}
+
+export async function bulkReplaceInMeeting(
+    meetingId: string,
+    find: string,
+    replace: string,
+    caseSensitive: boolean
+): Promise<TranscriptRevision[]> {
+    return invoke<TranscriptRevision[]>("bulk_replace_in_meeting", { meetingId, find, replace, caseSensitive });
+}
+
+export async function getTranscriptRevisions(transcriptId: number): Promise<TranscriptRevision[]> {
+    return invoke<TranscriptRevision[]>("get_transcript_revisions", { transcriptId });
+}
+
+export async function getTranscriptsNeedingReview(meetingId: string): Promise<Transcript[]> {
+    return invoke<Transcript[]>("get_transcripts_needing_review", { meetingId });
+}
+
+export async function markTranscriptReviewed(transcriptId: number): Promise<boolean> {
+    return invoke<boolean>("mark_transcript_reviewed", { transcriptId });
+}
+
+export type TranscriptionProvider = "deepgram" | "gemini" | "gladia" | "googlestt" | "whisperlocal";
+
+/** Switch provider when the active one dies mid-meeting */
+export interface TranscriptionFailoverPolicy {
+    enabled: boolean;
+    after_secs: number;
+    buffer_secs: number;
+}
+
+/** Payload of the "transcription-failover" event */
+export interface TranscriptionFailoverEvent {
+    meeting_id: string;
+    from: TranscriptionProvider;
+    to: TranscriptionProvider;
+    reason: string;
+    replayed_secs: number;
+}
+
+export interface ProviderHealth {
+    provider: TranscriptionProvider;
+    active: boolean;
+    connected: boolean;
+    last_transcript_at: string | null;
+    unhealthy_secs: number | null;
+    failovers: number;
+}
+
+export async function getTranscriptionFailover(): Promise<TranscriptionFailoverPolicy> {
+    return invoke<TranscriptionFailoverPolicy>("get_transcription_failover");
+}
+
+export async function setTranscriptionFailover(policy: TranscriptionFailoverPolicy): Promise<void> {
+    return invoke("set_transcription_failover", { policy });
+}
+
+export async function getTranscriptionHealth(): Promise<ProviderHealth[]> {
+    return invoke<ProviderHealth[]>("get_transcription_health");
+}
+
+export async function getAudioLevels(): Promise<AudioLevels> {
+    return invoke<AudioLevels>("get_audio_levels");
+}
+
+export async function getSilenceWarningPolicy(): Promise<SilenceWarningPolicy> {
+    return invoke<SilenceWarningPolicy>("get_silence_warning_policy");
+}
+
+export async function setSilenceWarningPolicy(policy: SilenceWarningPolicy): Promise<void> {
+    return invoke("set_silence_warning_policy", { policy });
+}
+
+export async function getCheckInPolicy(): Promise<CheckInPolicy> {
+    return invoke<CheckInPolicy>("get_checkin_policy");
+}
+
+export async function setCheckInPolicy(policy: CheckInPolicy): Promise<void> {
+    return invoke("set_checkin_policy", { policy });
+}
+
+/** Answer the open "interaction-checkin" prompt */
+export async function respondToCheckIn(decision: CheckInDecision): Promise<void> {
+    return invoke("respond_to_checkin", { decision });
+}
+
+export async function renameSpeaker(meetingId: string, speakerLabel: string, displayName: string): Promise<void> {
+    return invoke("rename_speaker", { meetingId, speakerLabel, displayName });
+}
+
+export async function getMeetingSpeakers(meetingId: string): Promise<MeetingSpeaker[]> {
+    return invoke<MeetingSpeaker[]>("get_meeting_speakers", { meetingId });
+}
+
 export async function searchTranscripts(query: string): Promise<SearchResult[]> {
     return invoke<SearchResult[]>("search_transcripts", { query });
 }
 
+/** Search transcripts, screen text snapshots and frame OCR together */
+export async function searchEverything(
+    query: string,
+    sources?: SearchSource[],
+    limit?: number
+): Promise<UnifiedSearchResult[]> {
+    return invoke<UnifiedSearchResult[]>("search_everything", { query, sources, limit });
+}
+
 // Frame commands (for rewind timeline)
 export async function getFrames(meetingId: string, limit?: number): Promise<Frame[]> {
     return invoke<Frame[]>("get_frames", { meetingId, limit });
@@ -130,9 +365,64 @@ export async function setMonitor(monitorId: number): Promise<void> {
     return invoke("set_monitor", { monitorId });
 }
 
+export interface CapturableWindow {
+    id: number;
+    app_name: string;
+    title: string;
+    width: number;
+    height: number;
+    monitor_id: number | null;
+    is_focused: boolean;
+}
+
+/** Windows that can be recorded on their own (e.g. just the Zoom window) */
+export async function getCapturableWindows(): Promise<CapturableWindow[]> {
+    return invoke<CapturableWindow[]>("get_capturable_windows");
+}
+
+/**
+ * Record only this window (null = whole monitor). If it closes mid-recording,
+ * capture falls back to the monitor and `capture-window-lost` is emitted.
+ */
+export async function setCaptureWindow(windowId: number | null): Promise<void> {
+    return invoke("set_capture_window", { windowId });
+}
+
+/** Capture every display during meetings instead of only the selected one */
+export async function setCaptureAllMonitors(enabled: boolean): Promise<void> {
+    return invoke("set_capture_all_monitors", { enabled });
+}
+
+export interface SubsystemStatus {
+    name: string;
+    label: string;
+    state: "pending" | "ready" | "disabled" | "failed";
+    message: string | null;
+    attempts: number;
+    duration_ms: number | null;
+    updated_at: string;
+}
+
+/** Optional subsystems (Pinecone, Supabase, ingest, calendar, vault, accessibility capture) */
+export async function getSubsystemStatus(): Promise<SubsystemStatus[]> {
+    return invoke<SubsystemStatus[]>("get_subsystem_status");
+}
+
 // Settings commands
-export async function setDeepgramApiKey(apiKey: string): Promise<void> {
-    return invoke("set_deepgram_api_key", { apiKey });
+export async function setDeepgramApiKey(apiKey: string, validate = false): Promise<void> {
+    return invoke("set_deepgram_api_key", { apiKey, validate });
+}
+
+export interface KeyValidation {
+    provider: string;
+    status: "ok" | "invalid" | "network_error";
+    message: string | null;
+    checked_at: string;
+}
+
+/** Check a provider key with the provider; omit `key` to check the stored one */
+export async function validateProviderKey(provider: string, key?: string): Promise<KeyValidation> {
+    return invoke<KeyValidation>("validate_provider_key", { provider, key: key ?? null });
 }
 
 export async function getDeepgramApiKey(): Promise<string | null> {
@@ -143,16 +433,55 @@ export async function getSettings(): Promise<AppSettings> {
     return invoke<AppSettings>("get_settings");
 }
 
+export interface SecretStatus {
+    key: string;
+    is_set: boolean;
+    masked: string | null;
+    in_keychain: boolean;
+    updated_at: string | null;
+}
+
+/** Which API keys/tokens are configured, without their values */
+export async function getSecretStatus(): Promise<SecretStatus[]> {
+    return invoke<SecretStatus[]>("get_secret_status");
+}
+
 // Set frame capture interval (milliseconds)
 export async function setFrameCaptureInterval(intervalMs: number): Promise<void> {
     return invoke("set_frame_capture_interval", { intervalMs });
 }
 
+/** Emitted as `capture-interval-adjusted` when the adaptive interval changes */
+export interface IntervalAdjustment {
+    factor: number;
+    interval_ms: number;
+    base_interval_ms: number;
+    reason: "battery_low" | "cpu_over_budget" | "ac_power" | "cpu_recovered" | "config_changed" | "disabled";
+}
+
+/** Stretch the frame interval on low battery or high CPU, up to maxFactor × the configured interval */
+export async function setAdaptiveFrameInterval(
+    enabled: boolean,
+    batteryThreshold: number,
+    cpuBudgetMs: number,
+    maxFactor: number
+): Promise<void> {
+    return invoke("set_adaptive_frame_interval", { enabled, batteryThreshold, cpuBudgetMs, maxFactor });
+}
+
 // Meeting commands
 export async function getMeetings(limit?: number): Promise<Meeting[]> {
     return invoke<Meeting[]>("get_meetings", { limit });
 }
 
+export async function queryMeetings(
+    offset: number,
+    limit: number,
+    filter?: MeetingFilter
+): Promise<MeetingPage> {
+    return invoke<MeetingPage>("query_meetings", { offset, limit, filter });
+}
+
 export async function getMeeting(meetingId: string): Promise<Meeting | null> {
     return invoke<Meeting | null>("get_meeting", { meetingId });
 }
@@ -161,12 +490,25 @@ export async function deleteMeeting(meetingId: string): Promise<void> {
     return invoke("delete_meeting", { meetingId });
 }
 
+export async function archiveMeeting(meetingId: string): Promise<void> {
+    return invoke("archive_meeting", { meetingId });
+}
+
+export async function unarchiveMeeting(meetingId: string): Promise<void> {
+    return invoke("unarchive_meeting", { meetingId });
+}
+
+export async function listArchivedMeetings(limit?: number): Promise<Meeting[]> {
+    return invoke<Meeting[]>("list_archived_meetings", { limit });
+}
+
 // Synced Timeline types
 export interface TimelineFrame {
     id: string;
     frame_number: number;
     timestamp_ms: number;
     thumbnail_path: string | null;
+    monitor_id: number | null;
 }
 
 export interface TimelineTranscript {
@@ -184,11 +526,36 @@ export interface SyncedTimeline {
     duration_seconds: number;
     frames: TimelineFrame[];
     transcripts: TimelineTranscript[];
+    monitor_ids: number[];
 }
 
 // Synced timeline command
-export async function getSyncedTimeline(meetingId: string): Promise<SyncedTimeline> {
-    return invoke<SyncedTimeline>("get_synced_timeline", { meetingId });
+export async function getSyncedTimeline(meetingId: string, monitorId?: number): Promise<SyncedTimeline> {
+    return invoke<SyncedTimeline>("get_synced_timeline", { meetingId, monitorId: monitorId ?? null });
+}
+
+// Timeline event highlighted for a meeting, with why it scored high
+export interface MeetingHighlight {
+    event_id: string;
+    meeting_id: string;
+    ts: string;
+    event_type: string;
+    title: string;
+    description: string | null;
+    app_name: string | null;
+    window_title: string | null;
+    duration_ms: number | null;
+    episode_id: string | null;
+    state_id: string | null;
+    topic: string | null;
+    importance: number;
+    importance_reason: string | null;
+    category: string | null;
+}
+
+// Top timeline events by importance, in meeting order
+export async function getMeetingHighlights(meetingId: string, maxEvents: number = 5): Promise<MeetingHighlight[]> {
+    return invoke<MeetingHighlight[]>("get_meeting_highlights", { meetingId, maxEvents });
 }
 
 // Get frame thumbnail (full or thumbnail size)
@@ -219,14 +586,50 @@ export async function getSavedSettings(): Promise<{ microphone: string | null; m
 // ============================================
 
 // Supabase commands
-export async function configureSupabase(connectionString: string): Promise<void> {
-    return invoke("configure_supabase", { connectionString });
+// Pass createSchema once the user confirms creating missing tables
+export async function configureSupabase(connectionString: string, createSchema = false): Promise<void> {
+    return invoke("configure_supabase", { connectionString, createSchema });
 }
 
 export async function checkSupabase(): Promise<boolean> {
     return invoke<boolean>("check_supabase");
 }
 
+export interface SupabaseHealth {
+    connected: boolean;
+    latency_ms: number | null;
+    tables: { name: string; exists: boolean; row_count: number | null }[];
+    error: string | null;
+}
+
+export async function checkSupabaseHealth(): Promise<SupabaseHealth> {
+    return invoke<SupabaseHealth>("check_supabase_health");
+}
+
+export interface ConversationRecord {
+    id: string;
+    timestamp: string;
+    user_query: string;
+    assistant_response: string;
+    model_used: string;
+    context_refs: string[];
+    source: "local" | "supabase" | "both";
+}
+
+// Local and Supabase chat history, merged by id
+export async function getConversationHistory(limit?: number): Promise<ConversationRecord[]> {
+    return invoke<ConversationRecord[]>("get_conversation_history", { limit });
+}
+
+export async function deleteConversation(id: string): Promise<void> {
+    return invoke<void>("delete_conversation", { id });
+}
+
+// Returns how many conversations were removed
+export async function clearConversationHistory(): Promise<number> {
+    return invoke<number>("clear_conversation_history");
+}
+
 // Pinecone commands
 export async function configurePinecone(apiKey: string, indexHost: string, namespace?: string): Promise<void> {
     return invoke("configure_pinecone", { apiKey, indexHost, namespace });
@@ -236,6 +639,20 @@ export async function checkPinecone(): Promise<boolean> {
     return invoke<boolean>("check_pinecone");
 }
 
+export interface PineconeNamespaces {
+    transcripts: string;
+    activities: string;
+    conversations: string;
+    snapshots: string;
+}
+
+/** Override per-kind namespaces; empty values fall back to "<namespace>-<kind>" */
+export async function configurePineconeNamespaces(
+    namespaces: Partial<PineconeNamespaces>
+): Promise<PineconeNamespaces> {
+    return invoke<PineconeNamespaces>("configure_pinecone_namespaces", { ...namespaces });
+}
+
 // VLM commands
 export async function checkVlm(): Promise<boolean> {
     return invoke<boolean>("check_vlm");
@@ -246,14 +663,70 @@ export async function checkVlmVision(): Promise<boolean> {
 }
 
 // Knowledge Base Processing
-export async function analyzePendingFrames(limit?: number): Promise<{ frames_processed: number; activities_created: number }> {
+export interface VlmProgress {
+    processed: number;
+    total: number;
+    failed: number;
+    manual: boolean;
+}
+
+export async function analyzePendingFrames(
+    limit?: number
+): Promise<{ frames_processed: number; activities_created: number; errors: string[]; cancelled: boolean }> {
     return invoke("analyze_pending_frames", { limit });
 }
 
+export async function cancelVlmAnalysis(): Promise<boolean> {
+    return invoke<boolean>("cancel_vlm_analysis");
+}
+
 export async function syncToCloud(limit?: number): Promise<{ activities_synced: number; pinecone_upserts: number; supabase_inserts: number }> {
     return invoke("sync_to_cloud", { limit });
 }
 
+// Background sync worker
+export interface SyncWorkerStatus {
+    running: boolean;
+    online: boolean;
+    queue_depth: number;
+    parked: number;
+    synced_total: number;
+    failed_total: number;
+    last_error: string | null;
+    last_error_at: string | null;
+    last_success_at: string | null;
+}
+
+/** Payload of "sync-progress" events */
+export interface SyncProgress {
+    processed: number;
+    total: number;
+    failed: number;
+    online: boolean;
+}
+
+export async function getSyncWorkerStatus(): Promise<SyncWorkerStatus> {
+    return invoke<SyncWorkerStatus>("get_sync_worker_status");
+}
+
+export async function startSyncWorker(): Promise<void> {
+    return invoke("start_sync_worker");
+}
+
+export async function stopSyncWorker(): Promise<void> {
+    return invoke("stop_sync_worker");
+}
+
+/** Re-queue jobs parked after repeated failures; returns how many */
+export async function retryParkedSyncJobs(): Promise<number> {
+    return invoke<number>("retry_parked_sync_jobs");
+}
+
+/** Index meetings' transcripts in the background via the sync worker */
+export async function queueTranscriptIndex(meetingIds: string[]): Promise<void> {
+    return invoke("queue_transcript_index", { meetingIds });
+}
+
 export async function getPendingFrameCount(): Promise<number> {
     return invoke<number>("get_pending_frame_count");
 }
@@ -267,8 +740,12 @@ export interface KBSearchResult {
     category: string | null;
     summary: string;
     score: number | null;
+    /** Every backend that returned this record */
+    match_sources: string[];
 }
 
+export type SearchMode = "local" | "semantic" | "hybrid";
+
 export interface SearchOptions {
     query?: string;
     start_date?: string;
@@ -276,16 +753,43 @@ export interface SearchOptions {
     category?: string;
     limit?: number;
     sources?: string[];
+    /** Defaults to hybrid when Pinecone is configured */
+    search_mode?: SearchMode;
+}
+
+export interface KBSearchResponse {
+    results: KBSearchResult[];
+    search_mode: SearchMode;
+    latency: {
+        local_ms: number | null;
+        semantic_ms: number | null;
+        supabase_ms: number | null;
+    };
 }
 
-export async function searchKnowledgeBase(options: SearchOptions): Promise<KBSearchResult[]> {
-    return invoke<KBSearchResult[]>("search_knowledge_base", { options });
+export async function searchKnowledgeBase(options: SearchOptions): Promise<KBSearchResponse> {
+    return invoke<KBSearchResponse>("search_knowledge_base", { options });
 }
 
 export async function quickSemanticSearch(query: string, limit?: number): Promise<KBSearchResult[]> {
     return invoke<KBSearchResult[]>("quick_semantic_search", { query, limit });
 }
 
+/** Where semantic search vectors live: Pinecone or the local SQLite store */
+export async function setVectorBackend(backend: "pinecone" | "local"): Promise<void> {
+    return invoke("set_vector_backend", { backend });
+}
+
+export interface LocalIndexProgress {
+    indexed: number;
+    total: number;
+}
+
+/** Embed not-yet-indexed transcripts locally; listen to "local-index-progress" for progress */
+export async function indexTranscriptsLocally(): Promise<number> {
+    return invoke<number>("index_transcripts_locally");
+}
+
 // ============================================================================
 // Video Recording Commands
 // ============================================================================
@@ -304,6 +808,8 @@ export interface PinMoment {
     offset_secs: number;
     label: string | null;
     chunk_number: number;
+    /** Seconds into chunk_number, for extractFrameAt */
+    chunk_offset_secs: number;
 }
 
 export interface RecordingSession {
@@ -327,6 +833,8 @@ export interface StorageStats {
     total_bytes: number;
     video_bytes: number;
     frames_bytes: number;
+    /** Archived meeting audio */
+    audio_bytes: number;
     meetings_count: number;
     chunks_count: number;
     oldest_meeting: string | null;
@@ -349,6 +857,92 @@ export async function getVideoRecordingStatus(): Promise<RecordingSession | null
     return invoke<RecordingSession | null>("get_video_recording_status");
 }
 
+export interface VideoCaptureConfig {
+    /** Scale down to this width; 0 = native resolution */
+    max_width: number;
+    fps: number;
+    bitrate_kbps: number;
+    chunk_minutes: number;
+}
+
+export interface ChunkSpan {
+    chunk_number: number;
+    path: string;
+    start_ms: number;
+    end_ms: number;
+}
+
+export type PlaybackEntryKind = "transcript" | "screen_state" | "episode" | "timeline_event" | "pin";
+
+export interface PlaybackEntry {
+    kind: PlaybackEntryKind;
+    /** Transcript id, state_id, episode_id, event_id or pin id */
+    ref_id: string;
+    start_ms: number;
+    end_ms: number | null;
+    text: string;
+    /** Speaker, app name or event/insight type */
+    detail: string | null;
+}
+
+export interface PlaybackManifest {
+    meeting_id: string;
+    started_at: string;
+    duration_ms: number;
+    /** Per-source clock correction (ms) applied to anchor everything to started_at */
+    offsets: { video_ms: number; transcripts_ms: number; screen_ms: number; pins_ms: number };
+    chunks: ChunkSpan[];
+    /** start_ms of each chunk, ascending (binary-search this) */
+    chunk_boundaries_ms: number[];
+    /** Sorted by start_ms */
+    entries: PlaybackEntry[];
+}
+
+// Video chunks, transcripts, screen states, episodes, timeline events and pins keyed by offset
+export async function getPlaybackManifest(meetingId: string): Promise<PlaybackManifest> {
+    return invoke<PlaybackManifest>("get_playback_manifest", { meetingId });
+}
+
+export interface HighlightExport {
+    path: string;
+    duration_secs: number;
+}
+
+/** Payload of the "highlight-export-progress" event */
+export interface HighlightExportProgress {
+    meeting_id: string;
+    /** 0.0 - 1.0 */
+    progress: number;
+}
+
+// Export a clip around a pin (pinId) or an offset from the meeting start; needs ffmpeg
+export async function exportHighlight(
+    meetingId: string,
+    moment: { pinId: string } | { offsetSecs: number },
+    outputPath: string,
+    options: { preSecs?: number; postSecs?: number; burnSubtitles?: boolean } = {}
+): Promise<HighlightExport> {
+    return invoke<HighlightExport>("export_highlight", {
+        meetingId,
+        pinId: "pinId" in moment ? moment.pinId : null,
+        offsetSecs: "offsetSecs" in moment ? moment.offsetSecs : null,
+        preSecs: options.preSecs ?? null,
+        postSecs: options.postSecs ?? null,
+        outputPath,
+        burnSubtitles: options.burnSubtitles ?? null,
+    });
+}
+
+// Get screen video capture settings
+export async function getVideoCaptureConfig(): Promise<VideoCaptureConfig> {
+    return invoke<VideoCaptureConfig>("get_video_capture_config");
+}
+
+// Save screen video capture settings (applies to the next recording)
+export async function setVideoCaptureConfig(config: VideoCaptureConfig): Promise<void> {
+    return invoke("set_video_capture_config", { config });
+}
+
 // Pin the current moment in recording
 export async function videoPinMoment(label?: string): Promise<PinMoment> {
     return invoke<PinMoment>("video_pin_moment", { label });
@@ -387,6 +981,111 @@ export async function getStorageStats(): Promise<StorageStats> {
     return invoke<StorageStats>("get_storage_stats");
 }
 
+/** Chunk of archived meeting audio on disk */
+export interface AudioChunk {
+    id: string;
+    meeting_id: string;
+    chunk_index: number;
+    path: string;
+    format: "wav" | "aac";
+    sample_rate: number;
+    channels: number;
+    start_ts: string;
+    /** null while the chunk is still being written */
+    end_ts: string | null;
+    size_bytes: number;
+}
+
+export interface AudioChunkPosition {
+    chunk: AudioChunk;
+    offset_secs: number;
+}
+
+export async function setAudioRecordingEnabled(enabled: boolean): Promise<void> {
+    return invoke("set_audio_recording_enabled", { enabled });
+}
+
+export async function setAudioRecordingFormat(format: "wav" | "aac"): Promise<void> {
+    return invoke("set_audio_recording_format", { format });
+}
+
+export async function getAudioChunks(meetingId: string): Promise<AudioChunk[]> {
+    return invoke<AudioChunk[]>("get_audio_chunks", { meetingId });
+}
+
+// Locate the audio file and offset for a moment of a meeting
+export async function getAudioChunkForTimestamp(
+    meetingId: string,
+    timestampMs: number
+): Promise<AudioChunkPosition | null> {
+    return invoke<AudioChunkPosition | null>("get_audio_chunk_for_timestamp", {
+        meetingId,
+        timestampMs,
+    });
+}
+
+export interface RetranscribeJob {
+    id: string;
+    meeting_id: string;
+    provider: string;
+    replace_existing: boolean;
+    status: "running" | "completed" | "failed";
+    next_chunk: number;
+    total_chunks: number;
+    error: string | null;
+    created_at: string;
+    updated_at: string;
+}
+
+export interface RetranscribedSegment {
+    id: number;
+    job_id: string;
+    chunk_position: number;
+    text: string;
+    speaker: string | null;
+    timestamp: string;
+    confidence: number;
+}
+
+export interface RevisionSetApplied {
+    updated: number;
+    inserted: number;
+    removed_ids: number[];
+}
+
+// Payload of the "retranscribe-progress" event
+export interface RetranscribeProgress {
+    job_id: string;
+    meeting_id: string;
+    completed_chunks: number;
+    total_chunks: number;
+    segments: number;
+    status: "running" | "completed" | "failed";
+    error: string | null;
+    applied: RevisionSetApplied | null;
+}
+
+// Re-transcribe a meeting's archived audio with another provider
+export async function retranscribeMeeting(
+    meetingId: string,
+    provider: string,
+    replaceExisting: boolean
+): Promise<RetranscribeJob> {
+    return invoke<RetranscribeJob>("retranscribe_meeting", {
+        meetingId,
+        provider,
+        replaceExisting,
+    });
+}
+
+export async function getRetranscribeJobs(meetingId: string): Promise<RetranscribeJob[]> {
+    return invoke<RetranscribeJob[]>("get_retranscribe_jobs", { meetingId });
+}
+
+export async function getRevisionSet(jobId: string): Promise<RetranscribedSegment[]> {
+    return invoke<RetranscribedSegment[]>("get_revision_set", { jobId });
+}
+
 // Apply retention policies
 export async function applyRetention(): Promise<[number, number]> {
     return invoke<[number, number]>("apply_retention");
@@ -420,6 +1119,29 @@ export async function getActiveTheme(): Promise<string> {
     return invoke<string>("get_active_theme");
 }
 
+// Theme switch proposed from observed activity ("theme-suggestion" event)
+export interface ThemeSuggestion {
+    id: string;
+    active_theme: string;
+    proposed_theme: string;
+    confidence: number;
+    observed_mins: number;
+    created_at: string;
+}
+
+export async function getThemeSuggestion(): Promise<ThemeSuggestion | null> {
+    return invoke<ThemeSuggestion | null>("get_theme_suggestion");
+}
+
+// Switch to the suggested theme (ends and starts theme sessions like a manual switch)
+export async function acceptThemeSuggestion(id: string): Promise<void> {
+    return invoke<void>("accept_theme_suggestion", { id });
+}
+
+export async function dismissThemeSuggestion(id: string): Promise<void> {
+    return invoke<void>("dismiss_theme_suggestion", { id });
+}
+
 // Get all theme settings
 export async function getThemeSettings(): Promise<ThemeSettings> {
     return invoke<ThemeSettings>("get_theme_settings");
@@ -436,17 +1158,113 @@ export async function getThemeTimeToday(theme: string): Promise<number> {
     return invoke<number>("get_theme_time_today", { theme });
 }
 
+export type ThemeReportGranularity = 'day' | 'week';
+
+export interface ThemeTimeRow {
+    theme: string;
+    hours: number[];
+    total_hours: number;
+}
+
+export interface ThemeTimeReport {
+    date_from: string;
+    date_to: string;
+    granularity: ThemeReportGranularity;
+    periods: string[];
+    themes: ThemeTimeRow[];
+    period_totals: number[];
+    total_hours: number;
+}
+
+// Hours per theme per day/week; dates are YYYY-MM-DD (inclusive, local time)
+export async function getThemeTimeReport(
+    dateFrom: string,
+    dateTo: string,
+    granularity: ThemeReportGranularity = 'day'
+): Promise<ThemeTimeReport> {
+    return invoke<ThemeTimeReport>("get_theme_time_report", { dateFrom, dateTo, granularity });
+}
+
+export async function exportThemeTimeReportCsv(
+    dateFrom: string,
+    dateTo: string,
+    granularity: ThemeReportGranularity,
+    path: string
+): Promise<number> {
+    return invoke<number>("export_theme_time_report_csv", { dateFrom, dateTo, granularity, path });
+}
+
 // Trigger manual ingest for a meeting
 export async function triggerMeetingIngest(meetingId: string): Promise<string> {
     return invoke<string>("trigger_meeting_ingest", { meetingId });
 }
 
+export interface IngestSession {
+    meeting_id: string;
+    session_id: string;
+    started_at: string;
+    metadata: Record<string, unknown>;
+    remote_started: boolean;
+}
+
+export interface IngestQueueStats {
+    pending: number;
+    uploading: number;
+    done: number;
+    failed: number;
+    running: boolean;
+    current_session: IngestSession | null;
+    bandwidth_kbps: number;
+    bytes_uploaded: number;
+    last_error: string | null;
+}
+
+// Ingest queue counts per state and the session being uploaded
+export async function getIngestQueueStats(): Promise<IngestQueueStats> {
+    return invoke<IngestQueueStats>("get_ingest_queue_stats");
+}
+
+// Cap ingest upload bandwidth in kilobits/sec (0 = unlimited)
+export async function setIngestBandwidthCap(kbps: number): Promise<void> {
+    return invoke<void>("set_ingest_bandwidth_cap", { kbps });
+}
+
+export interface SessionVerification {
+    meeting_id: string;
+    session_id: string;
+    local_frames: number;
+    remote_frames: number;
+    local_transcripts: number;
+    remote_transcripts: number;
+    missing_frames: { frame_id: number; file_path: string; sha256: string }[];
+    unreadable_frames: number;
+    requeued: number;
+}
+
+// Compare a meeting's uploads with what the pipeline received; optionally re-push the gaps
+export async function verifyRemoteSession(meetingId: string, repush = false): Promise<SessionVerification> {
+    return invoke<SessionVerification>("verify_remote_session", { meetingId, repush });
+}
+
 // Debug logging to terminal
 export async function debugLog(message: string): Promise<void> {
     return invoke("debug_log", { message });
 }
 
 // AI / LLM Commands
+export interface AIProviderStatus {
+    backend: "local" | "remote";
+    url: string;
+    reachable: boolean;
+    models: string[];
+    error: string | null;
+}
+
+// Active AI backend (ai_provider setting), whether it answers, and its models
+export async function checkAiProvider(): Promise<AIProviderStatus> {
+    return invoke<AIProviderStatus>("check_ai_provider");
+}
+
 export async function aiChat(presetId: string, message: string, meetingId?: string): Promise<string> {
     return invoke<string>("ai_chat", {
         presetId,
@@ -455,6 +1273,32 @@ export async function aiChat(presetId: string, message: string, meetingId?: stri
     });
 }
 
+/** Payload of the "ai-stream" event; `error` is set on a failed or cancelled final chunk */
+export interface AiStreamChunk {
+    request_id: string;
+    delta: string;
+    done: boolean;
+    error: string | null;
+}
+
+// Streaming variants return a request id at once; the answer arrives as "ai-stream" events
+export async function aiChatStream(presetId: string, message: string, meetingId?: string): Promise<string> {
+    return invoke<string>("ai_chat_stream", { presetId, message, meetingId });
+}
+
+export async function summarizeMeetingStream(meetingId: string): Promise<string> {
+    return invoke<string>("summarize_meeting_stream", { meetingId });
+}
+
+export async function thebrainChatStream(message: string, model: string): Promise<string> {
+    return invoke<string>("thebrain_chat_stream", { message, model });
+}
+
+// Returns false if the stream had already finished
+export async function cancelAiStream(requestId: string): Promise<boolean> {
+    return invoke<boolean>("cancel_ai_stream", { requestId });
+}
+
 // ============================================
 // Intelligence / Meeting State Commands
 // ============================================
@@ -499,6 +1343,7 @@ export interface CatchUpCapsule {
     sixty_second_version: string;
     confidence: number;
     generated_at_minute: number;
+    cache_age_secs: number;
 }
 
 export interface LiveInsightEvent {
@@ -519,31 +1364,81 @@ export async function getMeetingState(): Promise<MeetingState> {
     return invoke<MeetingState>("get_meeting_state");
 }
 
-export async function generateCatchUp(meetingId: string): Promise<CatchUpCapsule> {
-    return invoke<CatchUpCapsule>("generate_catch_up", { meetingId });
+export async function generateCatchUp(meetingId: string, forceRefresh = false): Promise<CatchUpCapsule> {
+    return invoke<CatchUpCapsule>("generate_catch_up", { meetingId, forceRefresh });
+}
+
+/** Persisted live insights; event ids are row ids, so pass the last one as sinceId to poll for new ones */
+export async function getLiveInsights(meetingId: string, sinceId?: number): Promise<LiveInsightEvent[]> {
+    return invoke<LiveInsightEvent[]>("get_live_insights", { meetingId, sinceId: sinceId ?? null });
+}
+
+export interface PinnedInsight {
+    id: string;
+    meeting_id: string;
+    insight_type: string;
+    text: string;
+    timestamp_ms: number;
+    transcript_id: number | null;
+    created_by: 'user' | 'agent';
+    created_at: string;
 }
 
-export async function getLiveInsights(meetingId: string): Promise<LiveInsightEvent[]> {
-    return invoke<LiveInsightEvent[]>("get_live_insights", { meetingId });
+export interface DecisionRecord {
+    id: string;
+    meeting_id: string;
+    text: string;
+    context: string | null;
+    timestamp_ms: number;
+    transcript_id: number | null;
+    created_by: 'user' | 'agent';
+    created_at: string;
 }
 
-export async function pinInsight(meetingId: string, insightType: string, insightText: string, timestampMs: number): Promise<void> {
-    return invoke("pin_insight", {
+export async function pinInsight(meetingId: string, insightType: string, insightText: string, timestampMs: number, transcriptId?: number): Promise<string> {
+    return invoke<string>("pin_insight", {
         meetingId,
         insightType,
         insightText,
-        timestampMs
+        timestampMs,
+        transcriptId: transcriptId ?? null
     });
 }
 
-export async function markDecision(meetingId: string, decisionText: string, context: string | null): Promise<void> {
-    return invoke("mark_decision", {
+export async function getPinnedInsights(meetingId: string): Promise<PinnedInsight[]> {
+    return invoke<PinnedInsight[]>("get_pinned_insights", { meetingId });
+}
+
+export async function updatePinnedInsight(id: string, insightText: string, insightType?: string): Promise<void> {
+    return invoke("update_pinned_insight", { id, insightText, insightType: insightType ?? null });
+}
+
+export async function deletePinnedInsight(id: string): Promise<void> {
+    return invoke("delete_pinned_insight", { id });
+}
+
+export async function markDecision(meetingId: string, decisionText: string, context: string | null, timestampMs?: number, transcriptId?: number): Promise<string> {
+    return invoke<string>("mark_decision", {
         meetingId,
         decisionText,
-        context
+        context,
+        timestampMs: timestampMs ?? null,
+        transcriptId: transcriptId ?? null
     });
 }
 
+export async function getDecisions(meetingId: string): Promise<DecisionRecord[]> {
+    return invoke<DecisionRecord[]>("get_decisions", { meetingId });
+}
+
+export async function updateDecision(id: string, decisionText: string, context: string | null): Promise<void> {
+    return invoke("update_decision", { id, decisionText, context });
+}
+
+export async function deleteDecision(id: string): Promise<void> {
+    return invoke("delete_decision", { id });
+}
+
 // ============================================
 // Always-On Recording Commands
 // ============================================
@@ -560,8 +1455,21 @@ export interface AlwaysOnSettings {
     app_detection: boolean;
 }
 
+export interface CaptureModeStatus {
+    mode: CaptureMode;
+    /** Ambient capture paused for inactivity (meetings are never paused) */
+    is_idle: boolean;
+    idle_since: string | null;
+    last_activity: string | null;
+    idle_timeout_mins: number;
+}
+
+export async function getCaptureModeStatus(): Promise<CaptureModeStatus> {
+    return invoke<CaptureModeStatus>("get_capture_mode");
+}
+
 export async function getCaptureMode(): Promise<CaptureMode> {
-    return invoke<string>("get_capture_mode").then(mode => mode as CaptureMode);
+    return getCaptureModeStatus().then(status => status.mode);
 }
 
 export async function startAmbientCapture(): Promise<void> {
@@ -576,11 +1484,30 @@ export async function pauseCapture(): Promise<void> {
     return invoke("pause_capture");
 }
 
+/** Save the rolling ambient buffer (last N minutes) as a new meeting; returns its id */
+export async function promoteAmbientBufferToMeeting(title: string): Promise<string> {
+    return invoke<string>("promote_ambient_buffer_to_meeting", { title });
+}
+
+export async function setAmbientBufferMinutes(minutes: number): Promise<void> {
+    return invoke("set_ambient_buffer_minutes", { minutes });
+}
+
+/** Minutes without input before ambient capture pauses (0 = never); emits `ambient-idle-changed` */
+export async function setAmbientIdleTimeout(minutes: number): Promise<void> {
+    return invoke("set_ambient_idle_timeout", { minutes });
+}
+
 // Link accessibility captures to the current meeting
 export async function setAccessibilityMeetingId(meetingId: string | null): Promise<void> {
     return invoke("set_accessibility_meeting_id", { meetingId });
 }
 
+/** OCR the latest keyframe when accessibility extraction fails or returns low-quality text */
+export async function setOcrFallbackEnabled(enabled: boolean): Promise<void> {
+    return invoke("set_ocr_fallback_enabled", { enabled });
+}
+
 export async function getAlwaysOnSettings(): Promise<AlwaysOnSettings> {
     return invoke<AlwaysOnSettings>("get_always_on_settings");
 }
@@ -598,10 +1525,674 @@ export async function dismissMeetingDetection(detectionId: string): Promise<void
     return invoke("dismiss_meeting_detection", { detectionId });
 }
 
+/** Calendar event a meeting was recorded during */
+export interface MeetingCalendarLink {
+    calendar_event_id: string;
+    attendees: string[];
+    meeting_url: string | null;
+}
+
+export async function linkMeetingToEvent(meetingId: string, eventId: string): Promise<MeetingCalendarLink> {
+    return invoke<MeetingCalendarLink>("link_meeting_to_event", { meetingId, eventId });
+}
+
+export async function getMeetingCalendarLink(meetingId: string): Promise<MeetingCalendarLink | null> {
+    return invoke<MeetingCalendarLink | null>("get_meeting_calendar_link", { meetingId });
+}
+
+/** Payload of the `meeting-auto-started` event */
+export interface AutoStartedMeeting {
+    meeting_id: string;
+    title: string;
+    detection_id: string;
+    undo_until: string;
+}
+
+export async function undoAutoStartedMeeting(meetingId: string): Promise<void> {
+    return invoke("undo_auto_started_meeting", { meetingId });
+}
+
+export async function setAutoStartOnMeetingDetected(enabled: boolean): Promise<void> {
+    return invoke("set_auto_start_on_meeting_detected", { enabled });
+}
+
+/** Payload of the `suggest-stop-recording` event */
+export interface StopSuggestion {
+    reason: "meeting_apps_closed" | "calendar_event_ended";
+    detail: string;
+    suggested_at: string;
+    auto_stop: boolean;
+}
+
+export async function setAutoStopRecording(
+    enabled: boolean,
+    idleGraceMinutes: number,
+    eventGraceMinutes: number
+): Promise<void> {
+    return invoke("set_auto_stop_recording", { enabled, idleGraceMinutes, eventGraceMinutes });
+}
+
+/** Apps (bundle ids) and window-title regexes never captured */
+export interface PrivacyRules {
+    bundle_ids: string[];
+    title_patterns: string[];
+}
+
+export async function getPrivacyRules(): Promise<PrivacyRules> {
+    return invoke("get_privacy_rules");
+}
+
+export async function setPrivacyRules(rules: PrivacyRules): Promise<PrivacyRules> {
+    return invoke("set_privacy_rules", { rules });
+}
+
+/** How PII is handled in text sent to Pinecone/Supabase */
+export type RedactionMode = "off" | "mask" | "drop_segment";
+
+export async function setRedactionMode(mode: RedactionMode): Promise<void> {
+    return invoke("set_redaction_mode", { mode });
+}
+
+export interface UsageTotals {
+    calls: number;
+    failures: number;
+    input_tokens: number;
+    output_tokens: number;
+    audio_seconds: number;
+    duration_ms: number;
+    estimated_cost_usd: number | null;
+}
+
+export interface UsageSummary {
+    period: string;
+    since: string | null;
+    by_day: (UsageTotals & { day: string; provider: string })[];
+    by_provider: (UsageTotals & { provider: string })[];
+    total: UsageTotals;
+    unpriced_providers: string[];
+}
+
+export type UsagePeriod = "today" | "week" | "month" | "30d" | "all";
+
+// Outbound AI/VLM/embedding/STT calls grouped by provider and day (default: month to date)
+export async function getUsageSummary(period?: UsagePeriod): Promise<UsageSummary> {
+    return invoke<UsageSummary>("get_usage_summary", { period });
+}
+
+/** Unit prices per provider ("ollama", "thebrain", "pinecone", "deepgram", ...) */
+export interface UsagePrice {
+    provider: string;
+    input_per_1k_tokens: number;
+    output_per_1k_tokens: number;
+    per_audio_minute: number;
+}
+
+export async function getUsagePrices(): Promise<UsagePrice[]> {
+    return invoke<UsagePrice[]>("get_usage_prices");
+}
+
+export async function setUsagePrices(prices: UsagePrice[]): Promise<UsagePrice[]> {
+    return invoke<UsagePrice[]>("set_usage_prices", { prices });
+}
+
+// Prompt A/B evaluation
+export interface PromptRef {
+    id: string;
+    name: string;
+    version: number;
+    theme: string | null;
+}
+
+export interface PromptEvaluation {
+    id: string;
+    run_id: string;
+    prompt_a_id: string;
+    prompt_b_id: string;
+    sample_source: "frames" | "transcripts";
+    sample_ref: string;
+    sample_preview: string | null;
+    model_a: string;
+    model_b: string;
+    output_a: string | null;
+    output_b: string | null;
+    error_a: string | null;
+    error_b: string | null;
+    duration_a_ms: number;
+    duration_b_ms: number;
+    winner: "a" | "b" | "tie" | null;
+    created_at: string;
+    judged_at: string | null;
+}
+
+export interface EvaluationRun {
+    run_id: string;
+    prompt_a: PromptRef;
+    prompt_b: PromptRef;
+    sample_source: "frames" | "transcripts";
+    items: PromptEvaluation[];
+}
+
+export interface PromptWinRate {
+    prompt_id: string;
+    name: string;
+    version: number;
+    theme: string | null;
+    judged: number;
+    wins: number;
+    losses: number;
+    ties: number;
+    win_rate: number;
+}
+
+// Runs both prompts on real samples (pending frames or recent transcripts); can take a while
+export async function evaluatePrompt(
+    promptIdA: string,
+    promptIdB: string,
+    sampleSource: "frames" | "transcripts",
+    sampleCount?: number
+): Promise<EvaluationRun> {
+    return invoke<EvaluationRun>("evaluate_prompt", { promptIdA, promptIdB, sampleSource, sampleCount });
+}
+
+export async function recordEvaluationVerdict(evaluationId: string, winner: "a" | "b" | "tie"): Promise<void> {
+    return invoke("record_evaluation_verdict", { evaluationId, winner });
+}
+
+export async function getPromptEvaluationSummary(promptName?: string): Promise<PromptWinRate[]> {
+    return invoke<PromptWinRate[]>("get_prompt_evaluation_summary", { promptName });
+}
+
+// VLM scheduler policy
+export interface VLMSchedulerPolicy {
+    /** Frames per clock hour by theme (missing or 0 = unlimited) */
+    max_frames_per_hour: Record<string, number>;
+    /** Local "HH:MM"; the window may wrap past midnight */
+    quiet_hours_start: string | null;
+    quiet_hours_end: string | null;
+    /** Pause on battery at or below this charge (null = no guard) */
+    battery_threshold_pct: number | null;
+}
+
+export interface VLMSchedulerStatus {
+    running: boolean;
+    enabled: boolean;
+    interval_secs: number;
+    pending_frames: number;
+    last_run: string | null;
+    next_run: string | null;
+    frames_processed: number;
+    batch_running: boolean;
+    concurrency: number;
+    batch_size: number;
+    requests_saved: number;
+    tokens_saved_estimate: number;
+    policy: VLMSchedulerPolicy;
+    frames_this_hour: number;
+    theme_frames_this_hour: number;
+    paused_reason: "quiet_hours" | "battery_low" | "hourly_budget" | null;
+    next_allowed_run: string | null;
+}
+
+export async function getVlmSchedulerStatus(): Promise<VLMSchedulerStatus> {
+    return invoke<VLMSchedulerStatus>("get_vlm_scheduler_status");
+}
+
+export async function getVlmSchedulerPolicy(): Promise<VLMSchedulerPolicy> {
+    return invoke<VLMSchedulerPolicy>("get_vlm_scheduler_policy");
+}
+
+export async function setVlmSchedulerPolicy(policy: VLMSchedulerPolicy): Promise<void> {
+    return invoke("set_vlm_scheduler_policy", { policy });
+}
+
+// Canonical entities
+export interface CanonicalEntity {
+    id: number;
+    entity_type: string;
+    name: string;
+    domain: string | null;
+    aliases: string[];
+    locked: boolean;
+    mention_count: number;
+    confidence: number;
+    first_seen: string | null;
+    last_seen: string | null;
+    activity_ids: number[];
+    metadata: Record<string, unknown> | null;
+    theme: string | null;
+}
+
+export async function getRecentEntities(limit?: number): Promise<CanonicalEntity[]> {
+    return invoke<CanonicalEntity[]>("get_recent_entities", { limit });
+}
+
+export async function mergeEntities(ids: number[], canonicalName: string): Promise<CanonicalEntity> {
+    return invoke<CanonicalEntity>("merge_entities", { ids, canonicalName });
+}
+
+export async function splitEntity(id: number): Promise<CanonicalEntity[]> {
+    return invoke<CanonicalEntity[]>("split_entity", { id });
+}
+
+// Entity dossier
+export type DossierSource = "meeting" | "transcript" | "activity" | "calendar_event" | "vault_note";
+
+export interface DossierItem {
+    source: DossierSource;
+    timestamp: string | null;
+    title: string;
+    snippet: string | null;
+    meeting_id: string | null;
+    activity_id: number | null;
+    event_id: string | null;
+    path: string | null;
+}
+
+export interface DossierRollup {
+    text: string;
+    model: string;
+    generated_at: string;
+    stale: boolean;
+}
+
+export interface EntityDossier {
+    entity: CanonicalEntity;
+    items: DossierItem[]; // Oldest first
+    rollup: DossierRollup | null;
+}
+
+export async function getEntityDossier(
+    entity: { entityId?: number; name?: string },
+    includeRollup?: boolean,
+    refreshRollup?: boolean
+): Promise<EntityDossier> {
+    return invoke<EntityDossier>("get_entity_dossier", {
+        entityId: entity.entityId,
+        name: entity.name,
+        includeRollup,
+        refreshRollup,
+    });
+}
+
+// VLM backfill
+export interface RequeueSummary {
+    frames_requeued: number;
+    newly_queued: number;
+    activities_deleted: number;
+    pinecone_ids: string[];
+}
+
+/** Payload of `vlm-backfill-progress` events */
+export interface VLMBackfillProgress {
+    total: number;
+    processed: number;
+    remaining: number;
+}
+
+/** Requeue a meeting's keyframes, or those in a date range (YYYY-MM-DD or RFC 3339) */
+export async function requeueFramesForAnalysis(options: {
+    meetingId?: string;
+    dateFrom?: string;
+    dateTo?: string;
+    overwrite: boolean;
+}): Promise<RequeueSummary> {
+    return invoke<RequeueSummary>("requeue_frames_for_analysis", options);
+}
+
+/** Embedding-based topic segmentation when a recording stops (uses Pinecone embeddings) */
+export async function setSemanticTopicsEnabled(enabled: boolean): Promise<void> {
+    return invoke("set_semantic_topics_enabled", { enabled });
+}
+
+/** Episodes shorter than this are not summarized when a recording stops */
+export async function setEpisodeSummaryMinSecs(secs: number): Promise<void> {
+    return invoke("set_episode_summary_min_secs", { secs });
+}
+
+export interface EpisodeSummary {
+    summary: string;
+    keywords: string[];
+}
+
+export async function regenerateEpisodeSummary(episodeId: string): Promise<EpisodeSummary> {
+    return invoke("regenerate_episode_summary", { episodeId });
+}
+
+/** Length/tone preset for generated notes */
+export type NotesStyle = "standard" | "brief" | "detailed" | "executive";
+
+export interface NotesOptions {
+    instructions?: string;
+    style?: NotesStyle;
+    /** Seconds from meeting start */
+    startSecs?: number;
+    endSecs?: number;
+}
+
+/** One notes generation and how it was produced */
+export interface MeetingNotesVersion {
+    id: string;
+    meeting_id: string;
+    version: number | null;
+    style: string | null;
+    instructions: string | null;
+    prompt: string | null;
+    model_used: string | null;
+    range_start_secs: number | null;
+    range_end_secs: number | null;
+    created_at: string;
+    summary: string | null;
+    is_active: boolean;
+}
+
+export async function regenerateMeetingNotes(meetingId: string, options: NotesOptions = {}): Promise<unknown> {
+    return invoke("generate_meeting_notes", { meetingId, ...options });
+}
+
+export async function listMeetingNotesVersions(meetingId: string): Promise<MeetingNotesVersion[]> {
+    return invoke("list_meeting_notes_versions", { meetingId });
+}
+
+export async function setActiveNotesVersion(meetingId: string, versionId: string): Promise<void> {
+    return invoke("set_active_notes_version", { meetingId, versionId });
+}
+
+/** Decision, action item or topic in a meeting comparison */
+export interface DeltaItem {
+    text: string;
+    owner: string | null;
+    status: string | null;
+}
+
+export interface ItemDelta {
+    added: DeltaItem[];
+    removed: DeltaItem[];
+    carried_over: { before: DeltaItem; after: DeltaItem; similarity: number }[];
+}
+
+export interface ComparedMeeting {
+    id: string;
+    title: string;
+    started_at: string;
+    has_notes: boolean;
+    notes_generated: boolean;
+}
+
+/** Earlier vs later meeting; `partial` when one had no notes */
+export interface MeetingComparison {
+    earlier: ComparedMeeting;
+    later: ComparedMeeting;
+    partial: boolean;
+    decisions: ItemDelta;
+    action_items: ItemDelta;
+    topics: ItemDelta;
+    delta_summary: string | null;
+    generated_at: string;
+}
+
+export async function compareMeetings(
+    meetingIdA: string,
+    meetingIdB: string,
+    generateMissingNotes?: boolean
+): Promise<MeetingComparison> {
+    return invoke("compare_meetings", { meetingIdA, meetingIdB, generateMissingNotes });
+}
+
+/** Write a comparison to the vault as a "Sync Delta" note; returns its path */
+export async function exportMeetingComparison(comparison: MeetingComparison): Promise<string> {
+    return invoke("export_meeting_comparison", { comparison });
+}
+
+/** Recurring meeting series, grouped by calendar recurrence or by title and time slot */
+export interface MeetingSeriesSummary {
+    id: string;
+    title: string;
+    source: "calendar" | "title";
+    meeting_count: number;
+    first_meeting_at: string | null;
+    last_meeting_at: string | null;
+    average_duration_secs: number | null;
+    action_items_total: number;
+    action_items_open: number;
+}
+
+export interface SeriesMeeting {
+    id: string;
+    title: string;
+    started_at: string;
+    duration_seconds: number | null;
+    attendees: string[];
+    action_items: number;
+}
+
+export interface SeriesAttendee {
+    email: string;
+    meetings_attended: number;
+    attendance_rate: number;
+}
+
+export interface MeetingSeries extends MeetingSeriesSummary {
+    meetings: SeriesMeeting[];
+    attendance: SeriesAttendee[];
+}
+
+export async function listMeetingSeries(): Promise<MeetingSeriesSummary[]> {
+    return invoke("list_meeting_series");
+}
+
+export async function getSeries(seriesId: string): Promise<MeetingSeries> {
+    return invoke("get_series", { seriesId });
+}
+
+/** Tracked action item (live, extracted or manual) */
+export type ActionItemStatus = "open" | "in_progress" | "done" | "cancelled";
+
+export interface ActionItemRecord {
+    id: string;
+    meeting_id: string;
+    text: string;
+    assignee: string | null;
+    due_hint: string | null;
+    status: ActionItemStatus;
+    source: "live" | "extracted" | "manual";
+    source_transcript_id: number | null;
+    /** Follow-up event created from this item */
+    calendar_event_id: string | null;
+    created_at: string;
+    updated_at: string;
+}
+
+export async function extractActionItems(meetingId: string): Promise<ActionItemRecord[]> {
+    return invoke("extract_action_items", { meetingId });
+}
+
+/** Items for one meeting, or all open items when meetingId is omitted */
+export async function listActionItems(meetingId?: string): Promise<ActionItemRecord[]> {
+    return invoke("list_action_items", { meetingId: meetingId ?? null });
+}
+
+export async function updateActionItemStatus(id: string, status: ActionItemStatus): Promise<void> {
+    return invoke("update_action_item_status", { id, status });
+}
+
+export async function createActionItemManual(
+    meetingId: string,
+    text: string,
+    assignee?: string,
+    dueHint?: string
+): Promise<ActionItemRecord> {
+    return invoke("create_action_item_manual", {
+        meetingId,
+        text,
+        assignee: assignee ?? null,
+        dueHint: dueHint ?? null,
+    });
+}
+
+/** Calendar follow-up proposed from an action item's time hint */
+export interface FollowupProposal {
+    action_item_id: string;
+    title: string;
+    start: string;
+    end: string;
+    notes: string | null;
+    /** null writes to the default calendar */
+    calendar_name: string | null;
+    due_hint: string;
+}
+
+export interface ScheduledFollowup {
+    action_item_id: string;
+    event_id: string;
+    /** false when the item was already on the calendar */
+    created: boolean;
+}
+
+export async function proposeFollowups(meetingId: string): Promise<FollowupProposal[]> {
+    return invoke<FollowupProposal[]>("propose_followups", { meetingId });
+}
+
+// Creates only the proposals the user confirmed (edited times/titles are kept)
+export async function scheduleFollowups(
+    meetingId: string,
+    confirmed: FollowupProposal[]
+): Promise<ScheduledFollowup[]> {
+    return invoke<ScheduledFollowup[]>("schedule_followups", { meetingId, confirmed });
+}
+
+// ============================================
+// CRM Webhook Commands
+// ============================================
+
+export type WebhookEventType = "meeting-notes-generated" | "action-item-created" | "meeting-ended";
+
+export interface WebhookDelivery {
+    id: number;
+    event_type: WebhookEventType;
+    status: "pending" | "delivered" | "failed";
+    attempts: number;
+    last_status_code: number | null;
+    last_error: string | null;
+    created_at: string;
+    next_attempt_at: string | null;
+    delivered_at: string | null;
+}
+
+export interface WebhookTestResult {
+    status_code: number;
+    success: boolean;
+    response: string;
+}
+
+/** Empty values clear the stored URL, token or signing secret */
+export async function setWebhookConfig(
+    url: string | null,
+    bearerToken: string | null,
+    secret: string | null,
+    eventTypes: WebhookEventType[]
+): Promise<void> {
+    return invoke("set_webhook_config", { url, bearerToken, secret, eventTypes });
+}
+
+export async function getWebhookDeliveryLog(limit?: number): Promise<WebhookDelivery[]> {
+    return invoke("get_webhook_delivery_log", { limit: limit ?? null });
+}
+
+/** Sends a sample payload directly, bypassing the retry queue */
+export async function testWebhook(): Promise<WebhookTestResult> {
+    return invoke("test_webhook");
+}
+
+// ============================================
+// Slack Commands
+// ============================================
+
+export interface SlackPostResult {
+    channel: string;
+    /** Slack message timestamp of the parent post */
+    ts: string;
+    thread_replies: number;
+}
+
+/** Pass null (or an empty string) to remove the stored token */
+export async function setSlackBotToken(token: string | null): Promise<void> {
+    return invoke("set_slack_bot_token", { token });
+}
+
+/** Notes and action items as the parent post; highlights follow in its thread */
+export async function sendMeetingSummaryToSlack(meetingId: string, channel: string): Promise<SlackPostResult> {
+    return invoke("send_meeting_summary_to_slack", { meetingId, channel });
+}
+
+/** Post the live catch-up capsule for late joiners */
+export async function sendCatchUpToSlack(meetingId: string, channel: string): Promise<SlackPostResult> {
+    return invoke("send_catch_up_to_slack", { meetingId, channel });
+}
+
 export async function setGenieMode(isGenie: boolean): Promise<void> {
     return invoke("set_genie_mode", { isGenie });
 }
 
+/** Payload of `recording-state-changed` (recording driven from the tray or a global shortcut) */
+export interface RecordingStateChanged {
+    is_recording: boolean;
+    is_paused: boolean;
+    meeting_id: string | null;
+}
+
+export type ShortcutAction = "toggle_recording" | "pin_moment" | "toggle_genie" | "quick_note";
+
+export interface ShortcutBinding {
+    action: ShortcutAction;
+    label: string;
+    accelerator: string | null;
+    display: string | null; // e.g. "⌥⌘R"
+    default_accelerator: string;
+    registered: boolean;
+    error: string | null;
+}
+
+export async function getShortcuts(): Promise<ShortcutBinding[]> {
+    return invoke("get_shortcuts");
+}
+
+/** Rebind a global shortcut (e.g. "Alt+CmdOrCtrl+R"); null unbinds it. Rejects invalid or conflicting accelerators. */
+export async function setShortcut(action: ShortcutAction, accelerator: string | null): Promise<ShortcutBinding[]> {
+    return invoke("set_shortcut", { action, accelerator });
+}
+
+// ============================================
+// Quick Notes
+// ============================================
+
+/** Where a quick note was stored: on the recording meeting, or parked (noteId) */
+export interface QuickNoteSaved {
+    ts: string;
+    meeting_id: string | null;
+    comment_id: string | null;
+    note_id: number | null;
+}
+
+/** Quick note taken while no meeting was recording */
+export interface UnattachedNote {
+    id: number;
+    text: string;
+    ts: string;
+}
+
+export async function quickNote(text: string): Promise<QuickNoteSaved> {
+    return invoke("quick_note", { text });
+}
+
+export async function getUnattachedNotes(): Promise<UnattachedNote[]> {
+    return invoke("get_unattached_notes");
+}
+
+/** Attach a parked note to a meeting; returns the new comment id */
+export async function attachQuickNote(noteId: number, meetingId: string): Promise<string> {
+    return invoke("attach_quick_note", { noteId, meetingId });
+}
+
 // ============================================
 // v3.0.0: Obsidian Vault Commands
 // ============================================
@@ -675,6 +2266,48 @@ export async function exportMeetingToVault(topicName: string, meetingId: string)
     return invoke<string>("export_meeting_to_vault", { topicName, meetingId });
 }
 
+/** Render a meeting through an export template without writing it (defaults to the active template) */
+export async function previewExportTemplate(meetingId: string, templateId?: string): Promise<string> {
+    return invoke<string>("preview_export_template", { meetingId, templateId: templateId ?? null });
+}
+
+/** Choose the export template used for vault exports (null restores the builtin layout) */
+export async function setExportTemplate(templateId: string | null): Promise<void> {
+    return invoke("set_export_template", { templateId });
+}
+
+export interface VaultExportDrift {
+    path: string;
+    meeting_id: string;
+    exported_at: string;
+    status: "modified" | "missing";
+}
+
+/** What re-exports do with notes edited in the vault: append new sections ("merge") or fail ("refuse") */
+export async function setVaultConflictPolicy(policy: "merge" | "refuse"): Promise<void> {
+    return invoke("set_vault_conflict_policy", { policy });
+}
+
+/** File auto-exported meetings of a recurring series under the series' topic instead of Inbox */
+export async function setVaultSeriesFolders(enabled: boolean): Promise<void> {
+    return invoke("set_vault_series_folders", { enabled });
+}
+
+/** Exported notes whose content on disk changed since the app wrote them */
+export async function getVaultExportConflicts(meetingId?: string): Promise<VaultExportDrift[]> {
+    return invoke<VaultExportDrift[]>("get_vault_export_conflicts", { meetingId: meetingId ?? null });
+}
+
+/** Write a daily or weekly digest note to the vault's Digests/ folder; returns its path */
+export async function generateDigest(period: "daily" | "weekly", date?: string): Promise<string> {
+    return invoke<string>("generate_digest", { period, date: date ?? null });
+}
+
+/** Turn the evening digest on/off; `time` is local "HH:MM" */
+export async function setDigestSchedule(enabled: boolean, time: string): Promise<void> {
+    return invoke("set_digest_schedule", { enabled, time });
+}
+
 export async function readVaultFile(filePath: string): Promise<VaultFileContent> {
     return invoke<VaultFileContent>("read_vault_file", { filePath });
 }
@@ -786,14 +2419,213 @@ export interface MeetingIntelResult {
     event_title: string;
     attendees_count: number;
     companies_count: number;
-    attendees: { name: string; email: string; company: string }[];
-    companies: { name: string; domain: string }[];
+    /** Attendees plus companies whose briefing or note failed; the rest still went through */
+    failed_count: number;
+    meeting_prep_written: boolean;
+    meeting_prep_error: string | null;
+    attendees: ({ name: string; email: string; company: string } & IntelNoteFlags)[];
+    companies: ({ name: string; domain: string } & IntelNoteFlags)[];
+}
+
+/** How each vault note was handled: reused as-is, regenerated (stale) or newly created */
+export interface IntelNoteFlags {
+    status: 'generated' | 'cached' | 'failed';
+    error: string | null;
+    cached: boolean;
+    regenerated: boolean;
+    created: boolean;
 }
 
 export async function getEnrichedCalendarEvents(): Promise<CalendarEventEnriched[]> {
     return invoke<CalendarEventEnriched[]>("get_enriched_calendar_events");
 }
 
+/**
+ * Import an .ics file (recurring events expanded two weeks ahead, all-day events skipped).
+ * With prepDays, meeting-prep notes are written for events with attendees in that window.
+ */
+export async function importIcs(path: string, prepDays?: number, topicName?: string): Promise<CalendarEventEnriched[]> {
+    return invoke<CalendarEventEnriched[]>("import_ics", {
+        path,
+        prepDays: prepDays ?? null,
+        topicName: topicName ?? null,
+    });
+}
+
 export async function generateMeetingIntel(eventId: string, topicName: string): Promise<MeetingIntelResult> {
     return invoke<MeetingIntelResult>("generate_meeting_intel", { eventId, topicName });
 }
+
+/** Vault person/company notes younger than this many days are reused instead of regenerated */
+export async function setAttendeeIntelStalenessDays(days: number): Promise<void> {
+    return invoke("set_attendee_intel_staleness_days", { days });
+}
+
+/** Cap on AI requests per minute across all features (0 = unlimited) */
+export async function setAiRequestsPerMinute(requestsPerMinute: number): Promise<void> {
+    return invoke("set_ai_requests_per_minute", { requestsPerMinute });
+}
+
+export interface CalendarInfo {
+    id: string;
+    name: string;
+    /** "#rrggbb" */
+    color: string | null;
+    /** Account the calendar belongs to (iCloud, Google, ...) */
+    source: string | null;
+    selected: boolean;
+}
+
+export async function listCalendars(): Promise<CalendarInfo[]> {
+    return invoke<CalendarInfo[]>("list_calendars");
+}
+
+/** Calendars used for meeting detection and attendee intel; an empty list means all of them */
+export async function setSelectedCalendars(calendarIds: string[]): Promise<void> {
+    return invoke("set_selected_calendars", { calendarIds });
+}
+
+// ============================================
+// Dork Mode (Study Mode)
+// ============================================
+
+export interface KeyConcept {
+    term: string;
+    definition: string;
+}
+
+export interface QuizQuestion {
+    question: string;
+    options: string[];
+    correct_index: number;
+    explanation: string;
+}
+
+export interface Flashcard {
+    front: string;
+    back: string;
+}
+
+export interface StudyMaterials {
+    session_id: string;
+    summary: string;
+    concepts: KeyConcept[];
+    quiz: QuizQuestion[];
+    flashcards: Flashcard[];
+    created_at: string;
+}
+
+export interface DorkSession {
+    id: string;
+    started_at: string;
+    ended_at: string | null;
+    status: "active" | "ended";
+    chunk_count: number;
+    content_chars: number;
+    has_materials: boolean;
+}
+
+export interface DorkSessionStatus {
+    session_mode: string;
+    active: boolean;
+    session_id: string | null;
+    started_at: string | null;
+    chunk_count: number;
+    content_chars: number;
+    auto_capture: boolean; // accessibility capture is feeding the session
+}
+
+export async function getDorkSessionStatus(): Promise<DorkSessionStatus> {
+    return invoke<DorkSessionStatus>("get_dork_session_status");
+}
+
+/** Apps whose captured text feeds a dork session; an empty list admits every app */
+export async function setDorkAppAllowlist(apps: string[]): Promise<void> {
+    return invoke("set_dork_app_allowlist", { apps });
+}
+
+export async function getStudyMaterials(sessionId: string): Promise<StudyMaterials | null> {
+    return invoke<StudyMaterials | null>("get_study_materials", { sessionId });
+}
+
+export async function listDorkSessions(limit?: number): Promise<DorkSession[]> {
+    return invoke<DorkSession[]>("list_dork_sessions", { limit: limit ?? null });
+}
+
+/** Reopen a past session; new content adds to what it already captured */
+export async function resumeDorkSession(sessionId: string): Promise<DorkSession> {
+    return invoke<DorkSession>("resume_dork_session", { sessionId });
+}
+
+/** Write study notes and flashcards to the vault's Study/ folder; returns the paths */
+export async function exportStudyMaterialsToVault(sessionId: string): Promise<string[]> {
+    return invoke<string[]>("export_study_materials_to_vault", { sessionId });
+}
+
+/** A flashcard with its SM-2 review schedule */
+export interface FlashcardCard {
+    id: string;
+    session_id: string | null;
+    front: string;
+    back: string;
+    ease: number;
+    interval_days: number;
+    repetitions: number;
+    lapses: number;
+    due_at: string;
+    last_reviewed_at: string | null;
+    created_at: string;
+}
+
+export interface FlashcardStats {
+    total: number;
+    due: number;
+    new: number; // never reviewed
+    mature: number;
+    reviewed_today: number;
+}
+
+export async function getDueFlashcards(limit?: number): Promise<FlashcardCard[]> {
+    return invoke<FlashcardCard[]>("get_due_flashcards", { limit: limit ?? null });
+}
+
+/** Grade a review 0-5 (below 3 means forgotten); returns the rescheduled card */
+export async function gradeFlashcard(cardId: string, grade: number): Promise<FlashcardCard> {
+    return invoke<FlashcardCard>("grade_flashcard", { cardId, grade });
+}
+
+export async function getFlashcardStats(): Promise<FlashcardStats> {
+    return invoke<FlashcardStats>("get_flashcard_stats");
+}
+
+// ============================================
+// Activity Clusters (automatic project grouping)
+// ============================================
+
+export interface ActivityCluster {
+    id: string;
+    label: string;
+    activity_count: number;
+    total_seconds: number;
+    first_seen: string;
+    last_seen: string;
+    created_at: string;
+}
+
+export interface ActivityClusterReport {
+    period: string;
+    since: string | null;
+    clusters: ActivityCluster[];
+    unclustered_seconds: number;
+    total_seconds: number;
+}
+
+export type ActivityClusterPeriod = 'today' | 'week' | 'month' | '30d' | 'all';
+
+export async function getActivityClusters(period: ActivityClusterPeriod = 'week'): Promise<ActivityClusterReport> {
+    return invoke<ActivityClusterReport>("get_activity_clusters", { period });
+}
+
+export async function renameActivityCluster(id: string, label: string): Promise<void> {
+    return invoke("rename_activity_cluster", { id, label });
+}
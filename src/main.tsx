@@ -2,11 +2,15 @@ import React from "react";
 import ReactDOM from "react-dom/client";
 import App from "./App";
 import { GlobalErrorBoundary } from "./components/GlobalErrorBoundary";
+import { QuickNoteInput } from "./components/QuickNoteInput";
+
+// The quick-note mini window loads the same bundle with ?window=quick-note
+const isQuickNote = new URLSearchParams(window.location.search).get("window") === "quick-note";
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
     <GlobalErrorBoundary>
-      <App />
+      {isQuickNote ? <QuickNoteInput /> : <App />}
     </GlobalErrorBoundary>
   </React.StrictMode>,
 );